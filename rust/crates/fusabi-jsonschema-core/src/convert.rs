@@ -0,0 +1,628 @@
+//! JSON Schema converter
+
+use fusabi_provider_common::{inline_name, item_name, InlineRecords, TypeShape};
+use fusabi_type_providers::{
+    DuDef, GeneratedModule, GeneratedTypes, NamingStrategy, RecordDef, TypeDefinition, TypeExpr,
+    TypeGenerator, VariantDef,
+};
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+
+/// Property names treated as discriminators in `oneOf` variants
+const DISCRIMINATORS: [&str; 2] = ["type", "kind"];
+
+/// Converts JSON Schema values into Fusabi type definitions
+pub struct Converter {
+    generator: TypeGenerator,
+}
+
+impl Converter {
+    /// Create a converter using PascalCase type names
+    pub fn new() -> Self {
+        Self::with_generator(TypeGenerator::new(NamingStrategy::PascalCase))
+    }
+
+    /// Create a converter with a custom type generator
+    pub fn with_generator(generator: TypeGenerator) -> Self {
+        Self { generator }
+    }
+
+    /// Fusabi type name for a `$ref` target
+    pub fn ref_name(&self, reference: &str) -> String {
+        let name = reference.rsplit('/').next().unwrap_or(reference);
+        self.generator.naming.apply(name)
+    }
+
+    /// Convert a schema used in type position (property, item, payload)
+    pub fn type_expr(&self, schema: &Value) -> TypeExpr {
+        self.shape(schema).into()
+    }
+
+    /// Convert an object schema's properties to record fields
+    ///
+    /// Properties missing from `required` become `T option`.
+    pub fn record_fields(&self, schema: &Value) -> Vec<(String, TypeExpr)> {
+        self.fields_in(schema, "", None)
+    }
+
+    /// [`Self::record_fields`] of record `record`, hoisting nested objects
+    ///
+    /// Object properties with `properties` of their own become records in
+    /// `inline`, named after `record` and the property (see
+    /// [`fusabi_provider_common::inline`]), instead of `Map<string, any>`.
+    pub fn hoisted_record_fields(
+        &self,
+        schema: &Value,
+        record: &str,
+        inline: &mut InlineRecords,
+    ) -> Vec<(String, TypeExpr)> {
+        self.fields_in(schema, record, Some(inline))
+    }
+
+    fn fields_in(
+        &self,
+        schema: &Value,
+        record: &str,
+        mut inline: Option<&mut InlineRecords>,
+    ) -> Vec<(String, TypeExpr)> {
+        let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+            return Vec::new();
+        };
+
+        let required: BTreeSet<&str> = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|r| r.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+
+        properties
+            .iter()
+            .map(|(name, prop)| {
+                let shape = self.shape_in(prop, &inline_name(record, name), inline.as_deref_mut());
+                let shape = if required.contains(&name.as_str()) || shape.is_option() {
+                    shape
+                } else {
+                    TypeShape::Option(Box::new(shape))
+                };
+                (name.clone(), shape.into())
+            })
+            .collect()
+    }
+
+    /// Convert a named schema into a type definition
+    ///
+    /// Returns `None` for schemas that map to a primitive or an alias, which
+    /// Fusabi has no definition for.
+    pub fn definition(&self, name: &str, schema: &Value) -> Option<TypeDefinition> {
+        let type_name = self.generator.naming.apply(name);
+
+        if schema.get("$ref").is_some() {
+            return None;
+        }
+
+        if let Some(values) = string_enum(schema) {
+            return Some(TypeDefinition::Du(DuDef {
+                name: type_name,
+                variants: values
+                    .iter()
+                    .map(|v| VariantDef::new_simple(self.generator.naming.apply(v)))
+                    .collect(),
+            }));
+        }
+
+        let alternatives = schema
+            .get("oneOf")
+            .or_else(|| schema.get("anyOf"))
+            .and_then(Value::as_array)
+            .filter(|alts| !alts.is_empty() && nullable_alternative(alts).is_none());
+        if let Some(alternatives) = alternatives {
+            return Some(TypeDefinition::Du(DuDef {
+                name: type_name,
+                variants: alternatives
+                    .iter()
+                    .enumerate()
+                    .map(|(i, alt)| self.variant(i, alt))
+                    .collect(),
+            }));
+        }
+
+        let types = schema_types(schema);
+        if types.contains(&"object") || (types.is_empty() && schema.get("properties").is_some()) {
+            return Some(TypeDefinition::Record(RecordDef {
+                name: type_name,
+                fields: self.record_fields(schema),
+            }));
+        }
+
+        None
+    }
+
+    /// Convert every entry of a definitions map
+    pub fn definitions(&self, definitions: &Map<String, Value>) -> Vec<TypeDefinition> {
+        definitions
+            .iter()
+            .filter_map(|(name, schema)| self.definition(name, schema))
+            .collect()
+    }
+
+    /// Convert a complete schema document
+    ///
+    /// Definitions (`definitions`, `$defs` or `components.schemas`) go into a
+    /// module named after `namespace`; the document itself becomes the
+    /// `Root` type when it describes an object, enum or union.
+    pub fn convert_document(&self, document: &Value, namespace: &str) -> GeneratedTypes {
+        let mut result = GeneratedTypes::new();
+
+        if let Some(definitions) = definitions_of(document) {
+            let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+            module.types = self.definitions(definitions);
+            if !module.types.is_empty() {
+                result.modules.push(module);
+            }
+        }
+
+        if let Some(root) = self.definition("Root", document) {
+            result.root_types.push(root);
+        }
+
+        result
+    }
+
+    fn variant(&self, index: usize, schema: &Value) -> VariantDef {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let name = self.ref_name(reference);
+            return VariantDef::new(name.clone(), vec![TypeShape::named(name).into()]);
+        }
+
+        let properties = schema.get("properties").and_then(Value::as_object);
+        let name = properties
+            .and_then(|props| DISCRIMINATORS.iter().find_map(|d| props.get(*d)))
+            .and_then(|d| d.get("const"))
+            .and_then(Value::as_str)
+            .map(|s| self.generator.naming.apply(s))
+            .or_else(|| schema.get("title").and_then(Value::as_str).map(|t| self.generator.naming.apply(t)))
+            .unwrap_or_else(|| format!("Variant{}", index));
+
+        let fields = match properties {
+            Some(props) => props
+                .iter()
+                .filter(|(k, _)| !DISCRIMINATORS.contains(&k.as_str()))
+                .map(|(_, v)| self.type_expr(v))
+                .collect(),
+            None if schema_types(schema).iter().any(|t| *t != "object") => {
+                vec![self.type_expr(schema)]
+            }
+            None => Vec::new(),
+        };
+
+        VariantDef::new(name, fields)
+    }
+
+    /// Structured form of [`Self::type_expr`], for callers that wrap it further
+    pub fn shape(&self, schema: &Value) -> TypeShape {
+        self.shape_in(schema, "", None)
+    }
+
+    /// [`Self::shape`] of a schema at a position named `name`, hoisting
+    /// nested objects into `inline`
+    pub fn hoisted_shape(&self, schema: &Value, name: &str, inline: &mut InlineRecords) -> TypeShape {
+        self.shape_in(schema, name, Some(inline))
+    }
+
+    /// `name` names the record an object at this position is hoisted to;
+    /// without `inline`, objects become maps
+    fn shape_in(&self, schema: &Value, name: &str, inline: Option<&mut InlineRecords>) -> TypeShape {
+        let shape = self.base_shape(schema, name, inline);
+        let nullable = schema.get("nullable").and_then(Value::as_bool) == Some(true);
+        if nullable && !shape.is_option() {
+            TypeShape::Option(Box::new(shape))
+        } else {
+            shape
+        }
+    }
+
+    fn base_shape(&self, schema: &Value, name: &str, inline: Option<&mut InlineRecords>) -> TypeShape {
+        if !schema.is_object() {
+            return named("any");
+        }
+
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            return TypeShape::Named(self.ref_name(reference));
+        }
+
+        if let Some(values) = string_enum(schema) {
+            let mut literals: Vec<TypeShape> =
+                values.into_iter().map(|v| TypeShape::Literal(v.to_string())).collect();
+            return if literals.len() == 1 {
+                literals.remove(0)
+            } else {
+                TypeShape::Union(literals)
+            };
+        }
+
+        if let Some(value) = schema.get("const").and_then(Value::as_str) {
+            return TypeShape::Literal(value.to_string());
+        }
+
+        if schema.get("x-kubernetes-int-or-string").and_then(Value::as_bool) == Some(true) {
+            return named("string");
+        }
+
+        for keyword in ["oneOf", "anyOf"] {
+            if let Some(alternatives) = schema.get(keyword).and_then(Value::as_array) {
+                return match nullable_alternative(alternatives) {
+                    Some(inner) => TypeShape::Option(Box::new(self.shape_in(inner, name, inline))),
+                    None if alternatives.len() == 1 => self.shape_in(&alternatives[0], name, inline),
+                    None => named("any"),
+                };
+            }
+        }
+
+        if let Some([single]) = schema.get("allOf").and_then(Value::as_array).map(Vec::as_slice) {
+            return self.shape_in(single, name, inline);
+        }
+
+        let types = schema_types(schema);
+        let non_null: Vec<&str> = types.iter().copied().filter(|t| *t != "null").collect();
+        let shape = match non_null.as_slice() {
+            [] if types.is_empty() => self.untyped_shape(schema, name, inline),
+            [] => named("unit"),
+            [single] => self.typed_shape(single, schema, name, inline),
+            _ => named("any"),
+        };
+
+        if types.len() > non_null.len() && !non_null.is_empty() {
+            TypeShape::Option(Box::new(shape))
+        } else {
+            shape
+        }
+    }
+
+    fn typed_shape(
+        &self,
+        schema_type: &str,
+        schema: &Value,
+        name: &str,
+        inline: Option<&mut InlineRecords>,
+    ) -> TypeShape {
+        match schema_type {
+            "string" => match schema.get("format").and_then(Value::as_str) {
+                Some("byte") | Some("binary") => named("bytes"),
+                _ => named("string"),
+            },
+            "integer" => named("int"),
+            "number" => named("float"),
+            "boolean" => named("bool"),
+            "array" => TypeShape::List(Box::new(
+                schema
+                    .get("items")
+                    .map_or_else(|| named("any"), |items| self.shape_in(items, &item_name(name), inline)),
+            )),
+            "object" => self.object_shape(schema, name, inline),
+            _ => named("any"),
+        }
+    }
+
+    fn untyped_shape(&self, schema: &Value, name: &str, inline: Option<&mut InlineRecords>) -> TypeShape {
+        if schema.get("properties").is_some() || schema.get("additionalProperties").is_some() {
+            self.object_shape(schema, name, inline)
+        } else if let Some(items) = schema.get("items") {
+            TypeShape::List(Box::new(self.shape_in(items, &item_name(name), inline)))
+        } else {
+            named("any")
+        }
+    }
+
+    /// Objects in type position are hoisted to a record when they declare
+    /// properties and `inline` is given, and become maps otherwise or when
+    /// nested deeper than `inline` allows
+    fn object_shape(&self, schema: &Value, name: &str, inline: Option<&mut InlineRecords>) -> TypeShape {
+        let has_properties = schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .is_some_and(|properties| !properties.is_empty());
+
+        match inline {
+            Some(inline) if has_properties => {
+                if !inline.enter() {
+                    return self.map_shape(schema, name, None);
+                }
+                let fields = self.fields_in(schema, name, Some(&mut *inline));
+                inline.leave();
+                TypeShape::Named(inline.hoist(name, fields))
+            }
+            inline => self.map_shape(schema, name, inline),
+        }
+    }
+
+    /// `Map<string, T>` of an object's `additionalProperties`, or of `any`
+    fn map_shape(&self, schema: &Value, name: &str, inline: Option<&mut InlineRecords>) -> TypeShape {
+        let value = match schema.get("additionalProperties") {
+            Some(additional) if additional.is_object() => {
+                self.shape_in(additional, &format!("{}Value", name), inline)
+            }
+            _ => named("any"),
+        };
+        TypeShape::Map(Box::new(named("string")), Box::new(value))
+    }
+}
+
+impl Default for Converter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Definitions map of a schema document
+///
+/// Looks in `definitions`, `$defs` and OpenAPI `components.schemas`.
+pub fn definitions_of(document: &Value) -> Option<&Map<String, Value>> {
+    document
+        .get("definitions")
+        .or_else(|| document.get("$defs"))
+        .or_else(|| document.pointer("/components/schemas"))
+        .and_then(Value::as_object)
+}
+
+fn named(name: &str) -> TypeShape {
+    TypeShape::Named(name.to_string())
+}
+
+/// Declared `type` keyword, as a list
+fn schema_types(schema: &Value) -> Vec<&str> {
+    match schema.get("type") {
+        Some(Value::String(t)) => vec![t.as_str()],
+        Some(Value::Array(ts)) => ts.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// `enum` values when every value is a string
+fn string_enum(schema: &Value) -> Option<Vec<&str>> {
+    let values = schema.get("enum")?.as_array()?;
+    if values.is_empty() {
+        return None;
+    }
+    values.iter().map(Value::as_str).collect()
+}
+
+/// The non-null alternative of a two-way `[T, null]` union
+fn nullable_alternative(alternatives: &[Value]) -> Option<&Value> {
+    let is_null = |v: &Value| schema_types(v) == ["null"];
+    match alternatives {
+        [a, b] if is_null(b) && !is_null(a) => Some(a),
+        [a, b] if is_null(a) && !is_null(b) => Some(b),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn expr(schema: Value) -> String {
+        Converter::new().type_expr(&schema).to_string()
+    }
+
+    #[test]
+    fn test_primitives() {
+        assert_eq!(expr(json!({ "type": "string" })), "string");
+        assert_eq!(expr(json!({ "type": "integer" })), "int");
+        assert_eq!(expr(json!({ "type": "number" })), "float");
+        assert_eq!(expr(json!({ "type": "boolean" })), "bool");
+        assert_eq!(expr(json!({ "type": "null" })), "unit");
+        assert_eq!(expr(json!({ "type": "string", "format": "byte" })), "bytes");
+        assert_eq!(expr(json!({})), "any");
+        assert_eq!(expr(json!(true)), "any");
+    }
+
+    #[test]
+    fn test_containers() {
+        assert_eq!(expr(json!({ "type": "array", "items": { "type": "string" } })), "string list");
+        assert_eq!(expr(json!({ "type": "array" })), "any list");
+        assert_eq!(expr(json!({ "type": "object" })), "Map<string, any>");
+        assert_eq!(
+            expr(json!({ "type": "object", "additionalProperties": { "type": "integer" } })),
+            "Map<string, int>"
+        );
+        assert_eq!(
+            expr(json!({ "type": "array", "items": { "$ref": "#/definitions/Pet" } })),
+            "Pet list"
+        );
+    }
+
+    #[test]
+    fn test_nullability() {
+        assert_eq!(expr(json!({ "type": ["string", "null"] })), "string option");
+        assert_eq!(expr(json!({ "type": "integer", "nullable": true })), "int option");
+        assert_eq!(
+            expr(json!({ "anyOf": [{ "$ref": "#/$defs/Pet" }, { "type": "null" }] })),
+            "Pet option"
+        );
+        assert_eq!(expr(json!({ "anyOf": [{ "type": "string" }, { "type": "integer" }] })), "any");
+    }
+
+    #[test]
+    fn test_inline_enums() {
+        assert_eq!(expr(json!({ "type": "string", "enum": ["a", "b"] })), "\"a\" | \"b\"");
+        assert_eq!(expr(json!({ "const": "fixed" })), "\"fixed\"");
+        assert_eq!(expr(json!({ "type": "integer", "enum": [1, 2] })), "int");
+    }
+
+    #[test]
+    fn test_record_definition() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "age": { "type": "integer" },
+                "nickname": { "type": ["string", "null"] }
+            },
+            "required": ["name"]
+        });
+
+        let Some(TypeDefinition::Record(record)) = Converter::new().definition("person", &schema) else {
+            panic!("Expected record");
+        };
+        assert_eq!(record.name, "Person");
+
+        let fields: Vec<(&str, String)> =
+            record.fields.iter().map(|(n, t)| (n.as_str(), t.to_string())).collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("age", "int option".to_string()),
+                ("name", "string".to_string()),
+                ("nickname", "string option".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hoisted_record_fields() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "location": {
+                    "type": "object",
+                    "properties": {
+                        "city": { "type": "string" },
+                        "coords": {
+                            "type": "object",
+                            "properties": { "lat": { "type": "number" }, "lon": { "type": "number" } },
+                            "required": ["lat", "lon"]
+                        }
+                    },
+                    "required": ["city"]
+                },
+                "stops": {
+                    "type": "array",
+                    "items": { "type": "object", "properties": { "city": { "type": "string" } } }
+                },
+                "tags": { "type": "object", "additionalProperties": { "type": "string" } }
+            },
+            "required": ["location"]
+        });
+
+        let mut inline = InlineRecords::new();
+        let fields: Vec<(String, String)> = Converter::new()
+            .hoisted_record_fields(&schema, "WeatherInput", &mut inline)
+            .into_iter()
+            .map(|(n, t)| (n, t.to_string()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("location".to_string(), "WeatherInputLocation".to_string()),
+                ("stops".to_string(), "WeatherInputStopsItem list option".to_string()),
+                ("tags".to_string(), "Map<string, string> option".to_string()),
+            ]
+        );
+
+        let records: Vec<(&str, Vec<String>)> = inline
+            .records()
+            .iter()
+            .map(|r| (r.name.as_str(), r.fields.iter().map(|(n, t)| format!("{}: {}", n, t)).collect()))
+            .collect();
+        assert_eq!(
+            records,
+            vec![
+                ("WeatherInputLocationCoords", vec!["lat: float".to_string(), "lon: float".to_string()]),
+                (
+                    "WeatherInputLocation",
+                    vec!["city: string".to_string(), "coords: WeatherInputLocationCoords option".to_string()]
+                ),
+                ("WeatherInputStopsItem", vec!["city: string option".to_string()]),
+            ]
+        );
+
+        // Without hoisting, nested objects stay maps
+        assert_eq!(Converter::new().record_fields(&schema)[0].1.to_string(), "Map<string, any>");
+
+        // Below the maximum depth, too
+        let mut shallow = InlineRecords::new().with_max_depth(1);
+        let fields = Converter::new().hoisted_record_fields(&schema, "WeatherInput", &mut shallow);
+        assert_eq!(fields[0].1.to_string(), "WeatherInputLocation");
+        assert_eq!(shallow.records()[0].fields[1].1.to_string(), "Map<string, any> option");
+    }
+
+    #[test]
+    fn test_union_definitions() {
+        let converter = Converter::new();
+
+        let Some(TypeDefinition::Du(status)) =
+            converter.definition("Status", &json!({ "type": "string", "enum": ["active", "done"] }))
+        else {
+            panic!("Expected DU");
+        };
+        let names: Vec<&str> = status.variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["Active", "Done"]);
+        assert!(status.variants.iter().all(|v| v.fields.is_empty()));
+
+        let shape = json!({
+            "oneOf": [
+                { "$ref": "#/definitions/Circle" },
+                {
+                    "type": "object",
+                    "properties": {
+                        "kind": { "const": "square" },
+                        "side": { "type": "number" }
+                    }
+                },
+                { "type": "string" }
+            ]
+        });
+        let Some(TypeDefinition::Du(du)) = converter.definition("Shape", &shape) else {
+            panic!("Expected DU");
+        };
+        let variants: Vec<(&str, Vec<String>)> = du
+            .variants
+            .iter()
+            .map(|v| (v.name.as_str(), v.fields.iter().map(|f| f.to_string()).collect()))
+            .collect();
+        assert_eq!(
+            variants,
+            vec![
+                ("Circle", vec!["Circle".to_string()]),
+                ("Square", vec!["float".to_string()]),
+                ("Variant2", vec!["string".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_primitive_definition_is_skipped() {
+        let converter = Converter::new();
+        assert!(converter.definition("Id", &json!({ "type": "string" })).is_none());
+        assert!(converter.definition("Alias", &json!({ "$ref": "#/definitions/Id" })).is_none());
+    }
+
+    #[test]
+    fn test_convert_document() {
+        let document = json!({
+            "type": "object",
+            "properties": { "pet": { "$ref": "#/$defs/Pet" } },
+            "required": ["pet"],
+            "$defs": {
+                "Pet": {
+                    "type": "object",
+                    "properties": { "name": { "type": "string" } }
+                }
+            }
+        });
+
+        let types = Converter::new().convert_document(&document, "Zoo");
+        assert_eq!(types.root_types.len(), 1);
+        assert_eq!(types.modules.len(), 1);
+        assert_eq!(types.modules[0].path, vec!["Zoo".to_string()]);
+
+        let openapi = json!({
+            "openapi": "3.0.0",
+            "components": { "schemas": { "Pet": { "type": "object" } } }
+        });
+        let types = Converter::new().convert_document(&openapi, "Api");
+        assert!(types.root_types.is_empty());
+        assert_eq!(types.modules[0].types.len(), 1);
+    }
+}
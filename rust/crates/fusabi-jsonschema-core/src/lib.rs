@@ -0,0 +1,42 @@
+//! Shared JSON Schema → Fusabi conversion
+//!
+//! JSON Schema shows up in many sources: standalone schema files, MCP tool
+//! input schemas, OpenAPI `components.schemas`, and Kubernetes
+//! `openAPIV3Schema` blocks. This crate holds the one converter they all use
+//! so that objects, enums, `oneOf`, `$ref` and arrays map to the same Fusabi
+//! types everywhere.
+//!
+//! # Mapping
+//!
+//! | JSON Schema                          | Fusabi                 |
+//! |--------------------------------------|------------------------|
+//! | `string` / `integer` / `number`      | `string` / `int` / `float` |
+//! | `boolean` / `null`                   | `bool` / `unit`        |
+//! | `string` with `format: byte`         | `bytes`                |
+//! | `array` with `items: T`              | `T list`               |
+//! | `object` with `additionalProperties: T` | `Map<string, T>`    |
+//! | `enum` of strings (inline)           | `"a" \| "b"`           |
+//! | `["T", "null"]`, `nullable: true`, `anyOf [T, null]` | `T option` |
+//! | `$ref: #/.../Name`                   | `Name`                 |
+//! | named `object`                       | record                 |
+//! | named `enum` / `oneOf`               | discriminated union    |
+//!
+//! Objects with `properties` in type position have no Fusabi counterpart
+//! and become `Map<string, any>`, unless the caller passes an
+//! [`InlineRecords`](fusabi_provider_common::InlineRecords) to
+//! [`Converter::hoisted_record_fields`] or [`Converter::hoisted_shape`],
+//! which hoists them to named records. Objects nested deeper than the
+//! records' maximum depth stay maps.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_jsonschema_core::Converter;
+//!
+//! let converter = Converter::new();
+//! let types = converter.convert_document(&schema_json, "Api");
+//! ```
+
+mod convert;
+
+pub use convert::{definitions_of, Converter};
@@ -0,0 +1,475 @@
+//! Ansible Type Provider
+//!
+//! Generates Fusabi option records from role argument specs and module
+//! documentation, so automation that drives Ansible from Fusabi can check
+//! playbook variables and module arguments before anything runs.
+//!
+//! # Sources
+//!
+//! - A role directory, read from its `meta/argument_specs.yml`
+//! - An `argument_specs.yml` file, inline or from a path
+//! - A module's Python source, or its `DOCUMENTATION` YAML
+//!
+//! # Generated Types
+//!
+//! Each role entry point becomes `<Role>Options` for `main` and
+//! `<Role><EntryPoint>Options` otherwise; a module becomes
+//! `<Module>Options` (`community.general.ufw` → `UfwOptions`). Nested
+//! `dict` options get records named after the path to them
+//! (`NginxSitesOptions`).
+//!
+//! | Ansible                   | Fusabi                               |
+//! |---------------------------|--------------------------------------|
+//! | `str`, `path`, `bytes`, `bits` | `string`, or a union of its `choices` |
+//! | `int` / `float` / `bool`  | `int` / `float` / `bool`             |
+//! | `list` with `elements: T` | `T list`                             |
+//! | `dict` with `options`     | record                               |
+//! | `dict` without `options`  | `Map<string, any>`                   |
+//! | `raw`, `json`, `jsonarg`  | `any`                                |
+//!
+//! Options that are not `required` are `T option`, including those with a
+//! `default`: callers may leave them out.
+//!
+//! # Params
+//!
+//! | Param  | Meaning                                                      |
+//! |--------|--------------------------------------------------------------|
+//! | `role` | Role name; defaults to the role directory's name, else `Role` |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_ansible::AnsibleProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = AnsibleProvider::new();
+//! let schema = provider.resolve_schema("roles/nginx", &ProviderParams::default())?;
+//! let types = provider.generate_types(&schema, "Roles")?;
+//! ```
+
+pub mod spec;
+
+pub use spec::{parse_argument_specs, parse_module_documentation, ArgumentSpec, OptionSpec};
+
+use std::path::Path;
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceKind, SourceValidator, TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Role name used when neither the source nor the `role` param gives one
+pub const DEFAULT_ROLE: &str = "Role";
+
+/// Argument specs file names inside a role, in lookup order
+const ARGUMENT_SPECS_FILES: [&str; 2] = ["meta/argument_specs.yml", "meta/argument_specs.yaml"];
+
+/// Role entry points or a module, stored in [`Schema::Custom`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum Resolved {
+    Role { role: String, entry_points: Vec<ArgumentSpec> },
+    Module { module: ArgumentSpec },
+}
+
+/// Ansible type provider
+pub struct AnsibleProvider {
+    generator: TypeGenerator,
+}
+
+impl AnsibleProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<Resolved> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected Ansible argument specs: {}", e)))
+    }
+
+    /// Read a role directory, spec file, module or inline content, with the
+    /// role name the location implies
+    fn read(&self, source: &str, params: &ProviderParams) -> ProviderResult<(String, Option<String>)> {
+        if source.contains('\n') {
+            return Ok((source.to_string(), None));
+        }
+
+        let path = Path::new(source);
+        if path.is_dir() {
+            let file = ARGUMENT_SPECS_FILES
+                .iter()
+                .map(|file| path.join(file))
+                .find(|file| file.is_file())
+                .ok_or_else(|| {
+                    ProviderError::IoError(format!("Role `{}` has no meta/argument_specs.yml", source))
+                })?;
+            let content = read_source(&file.to_string_lossy(), params)?;
+            return Ok((content, file_name(path)));
+        }
+
+        // `roles/nginx/meta/argument_specs.yml` belongs to role `nginx`
+        let role = path
+            .parent()
+            .filter(|meta| meta.file_name().is_some_and(|name| name == "meta"))
+            .and_then(Path::parent)
+            .and_then(file_name);
+        Ok((read_source(source, params)?, role))
+    }
+
+    fn generate(&self, resolved: &Resolved) -> (Vec<TypeDefinition>, Vec<Diagnostic>) {
+        let mut generation = Generation {
+            generator: &self.generator,
+            types: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+        match resolved {
+            Resolved::Role { role, entry_points } => {
+                if entry_points.is_empty() {
+                    generation
+                        .diagnostics
+                        .push(Diagnostic::warning("no-entry-points", "The role declares no entry points"));
+                }
+                let role = self.generator.naming.apply(role);
+                for entry_point in entry_points {
+                    let base = match entry_point.name.as_str() {
+                        "main" => role.clone(),
+                        name => format!("{}{}", role, self.generator.naming.apply(name)),
+                    };
+                    generation.record(&base, &entry_point.options, &entry_point.name);
+                }
+            }
+            Resolved::Module { module } => {
+                let short = module.name.rsplit('.').next().unwrap_or(&module.name);
+                generation.record(&self.generator.naming.apply(short), &module.options, &module.name);
+            }
+        }
+        (generation.types, generation.diagnostics)
+    }
+}
+
+fn file_name(path: &Path) -> Option<String> {
+    path.file_name().map(|name| name.to_string_lossy().into_owned())
+}
+
+/// State while generating: records in dependency order, and diagnostics
+struct Generation<'a> {
+    generator: &'a TypeGenerator,
+    types: Vec<TypeDefinition>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Generation<'_> {
+    /// Push `<base>Options` for a set of options, after its nested records
+    fn record(&mut self, base: &str, options: &[OptionSpec], location: &str) -> String {
+        let name = format!("{}Options", base);
+        let fields = options
+            .iter()
+            .map(|option| {
+                let option_location = format!("{}.{}", location, option.name);
+                self.check(option, &option_location);
+                let nested = format!("{}{}", base, self.generator.naming.apply(&option.name));
+                let shape = self.shape(option, &nested, &option_location);
+                let shape = if option.required { shape } else { TypeShape::Option(Box::new(shape)) };
+                (option.name.clone(), shape.into())
+            })
+            .collect();
+        self.types.push(TypeDefinition::Record(RecordDef { name: name.clone(), fields }));
+        name
+    }
+
+    fn shape(&mut self, option: &OptionSpec, nested: &str, location: &str) -> TypeShape {
+        match option.option_type.as_str() {
+            "list" => {
+                let element = match option.elements.as_deref() {
+                    Some("dict") if !option.options.is_empty() => {
+                        TypeShape::Named(self.record(nested, &option.options, location))
+                    }
+                    Some(element) => self.scalar(element, &option.choices, location),
+                    None => TypeShape::Named("any".to_string()),
+                };
+                TypeShape::List(Box::new(element))
+            }
+            "dict" if !option.options.is_empty() => TypeShape::Named(self.record(nested, &option.options, location)),
+            scalar => self.scalar(scalar, &option.choices, location),
+        }
+    }
+
+    fn scalar(&mut self, option_type: &str, choices: &[Value], location: &str) -> TypeShape {
+        let named = |name: &str| TypeShape::Named(name.to_string());
+        match option_type {
+            "str" | "path" | "bytes" | "bits" => {
+                let literals: Option<Vec<TypeShape>> = choices
+                    .iter()
+                    .map(|choice| choice.as_str().map(|c| TypeShape::Literal(c.to_string())))
+                    .collect();
+                match literals {
+                    Some(mut literals) if literals.len() == 1 => literals.remove(0),
+                    Some(literals) if !literals.is_empty() => TypeShape::Union(literals),
+                    _ => named("string"),
+                }
+            }
+            "int" => named("int"),
+            "float" => named("float"),
+            "bool" => named("bool"),
+            "dict" => TypeShape::Map(Box::new(named("string")), Box::new(named("any"))),
+            "raw" | "json" | "jsonarg" => named("any"),
+            other => {
+                self.diagnostics.push(
+                    Diagnostic::warning("unmapped-type", format!("Unknown option type `{}`; generated as `any`", other))
+                        .at(location.to_string()),
+                );
+                named("any")
+            }
+        }
+    }
+
+    /// Report the spec mistakes `ansible-test sanity` would
+    fn check(&mut self, option: &OptionSpec, location: &str) {
+        let default = option.default.as_ref().filter(|default| !default.is_null());
+        if option.required && default.is_some() {
+            self.diagnostics.push(
+                Diagnostic::warning("required-with-default", "`required` and `default` are mutually exclusive")
+                    .at(location.to_string()),
+            );
+        }
+        if let Some(default) = default.filter(|_| !option.choices.is_empty()) {
+            let values = match default {
+                Value::Array(values) if option.option_type == "list" => values.iter().collect(),
+                value => vec![value],
+            };
+            if let Some(value) = values.into_iter().find(|value| !option.choices.contains(value)) {
+                self.diagnostics.push(
+                    Diagnostic::warning("default-not-in-choices", format!("Default {} is not one of the choices", value))
+                        .at(location.to_string()),
+                );
+            }
+        }
+    }
+}
+
+impl Default for AnsibleProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for AnsibleProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("AnsibleProvider")
+            .param(
+                ParamSpec::new("role", ParamKind::String)
+                    .describe("Role name; defaults to the role directory's name"),
+            )
+    }
+}
+
+impl TypeProvider for AnsibleProvider {
+    fn name(&self) -> &str {
+        "AnsibleProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let (content, role) = self.read(source, params)?;
+        let resolved = if content.contains("argument_specs") && !content.contains("DOCUMENTATION") {
+            Resolved::Role {
+                role: typed
+                    .str("role")
+                    .map(str::to_string)
+                    .or(role)
+                    .unwrap_or_else(|| DEFAULT_ROLE.to_string()),
+                entry_points: parse_argument_specs(&content)?,
+            }
+        } else {
+            Resolved::Module {
+                module: parse_module_documentation(&content)?,
+            }
+        };
+
+        let json = serde_json::to_string(&resolved)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected Ansible argument specs".to_string()));
+        };
+
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+        module.types = self.generate(&self.parse_schema(json)?).0;
+
+        let mut result = GeneratedTypes::new();
+        result.modules.push(module);
+        Ok(result)
+    }
+}
+
+impl SourceValidator for AnsibleProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected Ansible argument specs".to_string()));
+        };
+
+        Ok(self.generate(&self.parse_schema(json)?).1)
+    }
+}
+
+impl DescribedProvider for AnsibleProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .source(SourceKind::Directory)
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    const ARGUMENT_SPECS: &str = r#"---
+argument_specs:
+  main:
+    short_description: Install and configure nginx
+    options:
+      nginx_port:
+        type: int
+        default: 80
+      nginx_state:
+        type: str
+        choices: [present, absent]
+        default: present
+      nginx_sites:
+        type: list
+        elements: dict
+        required: true
+        options:
+          name: {required: true}
+          tls: {type: bool, default: false}
+      nginx_extra: {type: dict}
+  reload:
+    options:
+      nginx_signal: {type: str, required: true, default: HUP}
+"#;
+
+    fn fields(types: &GeneratedTypes, name: &str) -> Vec<(String, String)> {
+        types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+            .fields
+            .iter()
+            .map(|(field, ty)| (field.clone(), ty.to_string()))
+            .collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(f, t)| (f.to_string(), t.to_string())).collect()
+    }
+
+    #[test]
+    fn test_role_directory() {
+        let dir = std::env::temp_dir().join(format!("fusabi-ansible-{}", std::process::id()));
+        let role = dir.join("nginx");
+        std::fs::create_dir_all(role.join("meta")).unwrap();
+        std::fs::write(role.join("meta/argument_specs.yml"), ARGUMENT_SPECS).unwrap();
+
+        let provider = AnsibleProvider::new();
+        let schema = provider.resolve_schema(role.to_str().unwrap(), &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Roles").unwrap();
+        let names: Vec<&str> = types.modules[0]
+            .types
+            .iter()
+            .map(|t| match t {
+                TypeDefinition::Record(r) => r.name.as_str(),
+                TypeDefinition::Du(du) => du.name.as_str(),
+            })
+            .collect();
+        assert_eq!(names, ["NginxNginxSitesOptions", "NginxOptions", "NginxReloadOptions"]);
+
+        assert_eq!(
+            fields(&types, "NginxOptions"),
+            pairs(&[
+                ("nginx_port", "int option"),
+                ("nginx_state", "(\"present\" | \"absent\") option"),
+                ("nginx_sites", "NginxNginxSitesOptions list"),
+                ("nginx_extra", "Map<string, any> option"),
+            ])
+        );
+        assert_eq!(
+            fields(&types, "NginxNginxSitesOptions"),
+            pairs(&[("name", "string"), ("tls", "bool option")])
+        );
+
+        // The spec file alone still names the role
+        let file = role.join("meta/argument_specs.yml");
+        let schema = provider.resolve_schema(file.to_str().unwrap(), &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Roles").unwrap();
+        assert_eq!(fields(&types, "NginxReloadOptions").len(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_module_documentation() {
+        let provider = AnsibleProvider::new();
+        let source = "#!/usr/bin/python\n\nDOCUMENTATION = '''\nmodule: community.general.ufw\noptions:\n  state:\n    choices: [enabled, disabled]\n  rules:\n    type: list\n    elements: str\n  interface:\n    type: dict\n    suboptions:\n      name: {type: str, required: true}\n'''\n";
+        let schema = provider.resolve_schema(source, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Modules").unwrap();
+        assert_eq!(
+            fields(&types, "UfwOptions"),
+            pairs(&[
+                ("state", "(\"enabled\" | \"disabled\") option"),
+                ("rules", "string list option"),
+                ("interface", "UfwInterfaceOptions option"),
+            ])
+        );
+        assert_eq!(fields(&types, "UfwInterfaceOptions"), pairs(&[("name", "string")]));
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/ansible.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let provider = AnsibleProvider::new();
+        let params = ProviderParams::default().with("role", "web");
+        let source = format!("{}      nginx_mode: {{type: str, choices: [a, b], default: c}}\n      nginx_limit: {{type: size}}\n", ARGUMENT_SPECS);
+        let schema = provider.resolve_schema(&source, &params).unwrap();
+        let diagnostics = provider.schema_diagnostics(&schema).unwrap();
+        let found: Vec<(&str, &str)> = diagnostics
+            .iter()
+            .map(|d| (d.code.as_str(), d.location.as_deref().unwrap_or("")))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("required-with-default", "reload.nginx_signal"),
+                ("default-not-in-choices", "reload.nginx_mode"),
+                ("unmapped-type", "reload.nginx_limit"),
+            ]
+        );
+
+        let types = provider.generate_types(&schema, "Roles").unwrap();
+        assert!(types.modules[0].types.iter().any(|t| matches!(t, TypeDefinition::Record(r) if r.name == "WebReloadOptions")));
+    }
+}
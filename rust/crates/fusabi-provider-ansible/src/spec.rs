@@ -0,0 +1,198 @@
+//! Argument specs
+//!
+//! Roles declare their variables in `meta/argument_specs.yml`, one entry
+//! point per task file:
+//!
+//! ```yaml
+//! argument_specs:
+//!   main:
+//!     options:
+//!       nginx_port: {type: int, default: 80}
+//!       nginx_sites: {type: list, elements: dict, options: {name: {required: true}}}
+//! ```
+//!
+//! Modules document theirs in the `DOCUMENTATION` string of their Python
+//! source, with nested options under `suboptions`. Options without a
+//! `type` are strings, as Ansible treats them.
+
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use serde_yaml::{Mapping, Value};
+
+/// A role entry point or module and its options
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArgumentSpec {
+    /// Entry point name for roles, module name for modules
+    pub name: String,
+    pub options: Vec<OptionSpec>,
+}
+
+/// An option, in spec order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OptionSpec {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub option_type: String,
+    /// Element type of `list` options
+    pub elements: Option<String>,
+    pub required: bool,
+    pub default: Option<JsonValue>,
+    #[serde(default)]
+    pub choices: Vec<JsonValue>,
+    /// Options of `dict` options and of `list` options with `dict` elements
+    #[serde(default)]
+    pub options: Vec<OptionSpec>,
+}
+
+/// Parse a role's `meta/argument_specs.yml`
+pub fn parse_argument_specs(content: &str) -> ProviderResult<Vec<ArgumentSpec>> {
+    let value: Value = serde_yaml::from_str(content)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid argument specs: {}", e)))?;
+    let Some(Value::Mapping(entry_points)) = value.get("argument_specs") else {
+        return Err(ProviderError::ParseError(
+            "Expected role argument specs with an `argument_specs` mapping".to_string(),
+        ));
+    };
+
+    entry_points
+        .iter()
+        .map(|(name, entry_point)| {
+            let name = string_key(name)?;
+            Ok(ArgumentSpec {
+                options: options(entry_point.get("options"), "options", &name)?,
+                name,
+            })
+        })
+        .collect()
+}
+
+/// Parse a module's `DOCUMENTATION`, given its Python source or the YAML
+/// itself
+pub fn parse_module_documentation(content: &str) -> ProviderResult<ArgumentSpec> {
+    let yaml = documentation_string(content).unwrap_or(content);
+    let value: Value = serde_yaml::from_str(yaml)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid module DOCUMENTATION: {}", e)))?;
+    let name = value
+        .get("module")
+        .and_then(Value::as_str)
+        .ok_or_else(|| ProviderError::ParseError("Module DOCUMENTATION has no `module` name".to_string()))?;
+    Ok(ArgumentSpec {
+        name: name.to_string(),
+        options: options(value.get("options"), "suboptions", name)?,
+    })
+}
+
+/// The contents of the triple-quoted string assigned to `DOCUMENTATION`
+pub fn documentation_string(source: &str) -> Option<&str> {
+    let start = source.find("\nDOCUMENTATION").map(|i| i + 1).or_else(|| {
+        source.starts_with("DOCUMENTATION").then_some(0)
+    })?;
+    let rest = source[start + "DOCUMENTATION".len()..].trim_start().strip_prefix('=')?.trim_start();
+    let rest = rest.strip_prefix(['r', 'R']).unwrap_or(rest);
+    let quote = ["'''", "\"\"\""].into_iter().find(|quote| rest.starts_with(quote))?;
+    let body = &rest[quote.len()..];
+    body.find(quote).map(|end| &body[..end])
+}
+
+/// Parse an options mapping; `nested_key` names the nested options key
+/// (`options` in argument specs, `suboptions` in module docs)
+fn options(value: Option<&Value>, nested_key: &str, owner: &str) -> ProviderResult<Vec<OptionSpec>> {
+    let entries = match value {
+        Some(Value::Mapping(entries)) => entries,
+        Some(Value::Null) | None => return Ok(Vec::new()),
+        Some(_) => return Err(ProviderError::ParseError(format!("Options of `{}` must be a mapping", owner))),
+    };
+    entries
+        .iter()
+        .map(|(name, option)| {
+            let name = string_key(name)?;
+            let empty = Mapping::new();
+            let option = match option {
+                Value::Mapping(option) => option,
+                Value::Null => &empty,
+                _ => return Err(ProviderError::ParseError(format!("Option `{}.{}` must be a mapping", owner, name))),
+            };
+            let location = format!("{}.{}", owner, name);
+            Ok(OptionSpec {
+                option_type: option.get("type").and_then(Value::as_str).unwrap_or("str").to_string(),
+                elements: option.get("elements").and_then(Value::as_str).map(str::to_string),
+                required: option.get("required").and_then(Value::as_bool).unwrap_or(false),
+                default: option.get("default").map(json).transpose()?,
+                choices: match option.get("choices") {
+                    Some(Value::Sequence(choices)) => choices.iter().map(json).collect::<ProviderResult<_>>()?,
+                    _ => Vec::new(),
+                },
+                options: options(option.get(nested_key), nested_key, &location)?,
+                name,
+            })
+        })
+        .collect()
+}
+
+fn string_key(key: &Value) -> ProviderResult<String> {
+    key.as_str()
+        .map(str::to_string)
+        .ok_or_else(|| ProviderError::ParseError(format!("Names must be strings, got {:?}", key)))
+}
+
+fn json(value: &Value) -> ProviderResult<JsonValue> {
+    serde_json::to_value(value).map_err(|e| ProviderError::ParseError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_argument_specs() {
+        let specs = parse_argument_specs(
+            "argument_specs:\n  main:\n    short_description: Install nginx\n    options:\n      nginx_port:\n        type: int\n        default: 80\n      nginx_sites:\n        type: list\n        elements: dict\n        options:\n          name: {required: true}\n  reload: {}\n",
+        )
+        .unwrap();
+        let names: Vec<&str> = specs.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["main", "reload"]);
+
+        let port = &specs[0].options[0];
+        assert_eq!((port.option_type.as_str(), port.default.clone()), ("int", Some(JsonValue::from(80))));
+        let sites = &specs[0].options[1];
+        assert_eq!(sites.elements.as_deref(), Some("dict"));
+        assert_eq!(sites.options[0].option_type, "str");
+        assert!(sites.options[0].required);
+        assert!(specs[1].options.is_empty());
+
+        assert!(parse_argument_specs("galaxy_info: {}").is_err());
+    }
+
+    #[test]
+    fn test_parse_module_documentation() {
+        let source = r#"#!/usr/bin/python
+from __future__ import annotations
+
+DOCUMENTATION = r'''
+---
+module: community.general.ufw
+short_description: Manage firewall with UFW
+options:
+  state:
+    type: str
+    choices: [enabled, disabled, reloaded, reset]
+  rule:
+    type: dict
+    suboptions:
+      port: {type: int, required: true}
+'''
+
+EXAMPLES = r'''
+- community.general.ufw: {state: enabled}
+'''
+"#;
+        let spec = parse_module_documentation(source).unwrap();
+        assert_eq!(spec.name, "community.general.ufw");
+        assert_eq!(spec.options[0].choices.len(), 4);
+        assert_eq!(spec.options[1].options[0].name, "port");
+
+        assert!(documentation_string("EXAMPLES = '''x'''").is_none());
+        assert!(parse_module_documentation("options: {}").is_err());
+    }
+}
@@ -0,0 +1,437 @@
+//! Schema extraction from Arrow IPC bytes
+//!
+//! Accepts an encapsulated schema message (as sent first on an IPC stream,
+//! with or without the `0xFFFFFFFF` continuation marker), an IPC file
+//! (`ARROW1` magic followed by the stream), or a Flight `SchemaResult`
+//! (the protobuf wrapper returned by `GetSchema`). Only the flatbuffer
+//! metadata is read; record batches are never touched.
+
+use crate::types::*;
+use fusabi_type_providers::{ProviderError, ProviderResult};
+
+/// Magic bytes at the start and end of an IPC file
+pub const FILE_MAGIC: &[u8; 6] = b"ARROW1";
+
+/// Marker preceding the metadata length in current IPC streams
+const CONTINUATION: [u8; 4] = [0xff; 4];
+
+/// `MessageHeader` union tag for `Schema`
+const HEADER_SCHEMA: u8 = 1;
+
+/// Nesting limit, so malicious input cannot exhaust the stack
+const MAX_DEPTH: usize = 64;
+
+/// Decode the schema from IPC stream, file or Flight `SchemaResult` bytes
+pub fn read_ipc_schema(bytes: &[u8]) -> ProviderResult<ArrowSchema> {
+    let message = if let Some(rest) = bytes.strip_prefix(FILE_MAGIC) {
+        // Magic is padded to 8 bytes
+        encapsulated_message(rest.get(2..).ok_or_else(truncated)?)?
+    } else if bytes.first() == Some(&0x0a) {
+        flight_schema_result(bytes)?
+    } else {
+        encapsulated_message(bytes)?
+    };
+
+    let root = Table::root(message)?;
+    if root.scalar::<u8>(1, 0)? != HEADER_SCHEMA {
+        return Err(ProviderError::ParseError(
+            "IPC message is not a schema message".to_string(),
+        ));
+    }
+    let schema = root.table(2)?.ok_or_else(|| parse_error("Schema message has no header"))?;
+
+    Ok(ArrowSchema {
+        fields: read_fields(&schema, 1, 0)?,
+    })
+}
+
+/// Flatbuffer of the first encapsulated message
+fn encapsulated_message(bytes: &[u8]) -> ProviderResult<&[u8]> {
+    let bytes = bytes.strip_prefix(&CONTINUATION).unwrap_or(bytes);
+    let len = read_u32(bytes, 0)? as usize;
+    bytes.get(4..4 + len).ok_or_else(truncated)
+}
+
+/// Unwrap `SchemaResult { bytes schema = 1; }`
+fn flight_schema_result(bytes: &[u8]) -> ProviderResult<&[u8]> {
+    let mut len: u64 = 0;
+    let mut pos = 1;
+    for shift in (0..64).step_by(7) {
+        let byte = *bytes.get(pos).ok_or_else(truncated)?;
+        pos += 1;
+        len |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            let inner = bytes.get(pos..pos + len as usize).ok_or_else(truncated)?;
+            return encapsulated_message(inner);
+        }
+    }
+    Err(parse_error("Invalid varint in Flight SchemaResult"))
+}
+
+fn read_fields(table: &Table, slot: usize, depth: usize) -> ProviderResult<Vec<ArrowField>> {
+    if depth > MAX_DEPTH {
+        return Err(parse_error("Arrow schema is nested too deeply"));
+    }
+    table
+        .tables(slot)?
+        .iter()
+        .map(|field| read_field(field, depth))
+        .collect()
+}
+
+/// Decode a `Field` table
+fn read_field(field: &Table, depth: usize) -> ProviderResult<ArrowField> {
+    let name = field.string(0)?.unwrap_or_default();
+    let nullable = field.scalar::<u8>(1, 0)? != 0;
+    let type_tag = field.scalar::<u8>(2, 0)?;
+    let type_table = field.table(3)?;
+    let mut children = read_fields(field, 5, depth + 1)?;
+
+    // Dictionary encoding does not change the logical type, and run-end
+    // encoded values are typed by their second child
+    if type_tag == 22 {
+        let values = children
+            .pop()
+            .ok_or_else(|| parse_error(&format!("Run-end encoded field `{}` has no values", name)))?;
+        return Ok(ArrowField {
+            name,
+            nullable,
+            ..values
+        });
+    }
+
+    let data_type = read_type(type_tag, type_table.as_ref(), &name)?;
+    Ok(ArrowField {
+        name,
+        nullable,
+        data_type,
+        children,
+    })
+}
+
+/// Decode the `Type` union
+fn read_type(tag: u8, table: Option<&Table>, field: &str) -> ProviderResult<DataType> {
+    let int = |slot, default| table.map_or(Ok(default), |t| t.scalar::<i32>(slot, default));
+    let short = |slot, default| table.map_or(Ok(default), |t| t.scalar::<i16>(slot, default));
+    let flag = |slot| table.map_or(Ok(false), |t| t.scalar::<u8>(slot, 0).map(|b| b != 0));
+
+    Ok(match tag {
+        1 => DataType::Null,
+        2 => DataType::Int {
+            bit_width: int(0, 32)? as u32,
+            is_signed: flag(1)?,
+        },
+        3 => DataType::FloatingPoint {
+            precision: match short(0, 0)? {
+                0 => Precision::Half,
+                1 => Precision::Single,
+                _ => Precision::Double,
+            },
+        },
+        4 => DataType::Binary,
+        5 => DataType::Utf8,
+        6 => DataType::Bool,
+        7 => DataType::Decimal {
+            precision: int(0, 0)? as u32,
+            scale: int(1, 0)?,
+            bit_width: int(2, 128)? as u32,
+        },
+        8 => DataType::Date {
+            unit: if short(0, 1)? == 0 { DateUnit::Day } else { DateUnit::Millisecond },
+        },
+        9 => DataType::Time {
+            unit: time_unit(short(0, 1)?),
+            bit_width: int(1, 32)? as u32,
+        },
+        10 => DataType::Timestamp {
+            unit: time_unit(short(0, 0)?),
+            timezone: table.map_or(Ok(None), |t| t.string(1))?,
+        },
+        11 => DataType::Interval {
+            unit: match short(0, 0)? {
+                0 => IntervalUnit::YearMonth,
+                1 => IntervalUnit::DayTime,
+                _ => IntervalUnit::MonthDayNano,
+            },
+        },
+        12 => DataType::List,
+        13 => DataType::Struct,
+        14 => DataType::Union {
+            mode: if short(0, 0)? == 0 { UnionMode::Sparse } else { UnionMode::Dense },
+        },
+        15 => DataType::FixedSizeBinary {
+            byte_width: int(0, 0)? as u32,
+        },
+        16 => DataType::FixedSizeList {
+            list_size: int(0, 0)? as u32,
+        },
+        17 => DataType::Map {
+            keys_sorted: flag(0)?,
+        },
+        18 => DataType::Duration {
+            unit: time_unit(short(0, 1)?),
+        },
+        19 => DataType::LargeBinary,
+        20 => DataType::LargeUtf8,
+        21 => DataType::LargeList,
+        23 => DataType::BinaryView,
+        24 => DataType::Utf8View,
+        25 => DataType::ListView,
+        26 => DataType::LargeListView,
+        other => {
+            return Err(parse_error(&format!(
+                "Field `{}` has unknown Arrow type tag {}",
+                field, other
+            )))
+        }
+    })
+}
+
+fn time_unit(value: i16) -> TimeUnit {
+    match value {
+        0 => TimeUnit::Second,
+        1 => TimeUnit::Millisecond,
+        2 => TimeUnit::Microsecond,
+        _ => TimeUnit::Nanosecond,
+    }
+}
+
+/// Read-only view of a flatbuffer table
+struct Table<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+/// Little-endian scalars stored inline in tables
+trait Scalar: Sized {
+    const SIZE: usize;
+    fn from_le(bytes: &[u8]) -> Self;
+}
+
+macro_rules! scalar {
+    ($($t:ty),*) => {$(
+        impl Scalar for $t {
+            const SIZE: usize = std::mem::size_of::<$t>();
+            fn from_le(bytes: &[u8]) -> Self {
+                <$t>::from_le_bytes(bytes.try_into().expect("slice has scalar size"))
+            }
+        }
+    )*};
+}
+
+scalar!(u8, i16, u16, i32, u32);
+
+impl<'a> Table<'a> {
+    /// The root table of a buffer
+    fn root(buf: &'a [u8]) -> ProviderResult<Self> {
+        let pos = read_u32(buf, 0)? as usize;
+        Ok(Self { buf, pos })
+    }
+
+    /// Offset of a field within the table, or 0 if absent
+    fn field_offset(&self, slot: usize) -> ProviderResult<usize> {
+        let vtable = self
+            .pos
+            .checked_add_signed(-(read::<i32>(self.buf, self.pos)? as isize))
+            .ok_or_else(truncated)?;
+        let vtable_len = read::<u16>(self.buf, vtable)? as usize;
+        let entry = 4 + 2 * slot;
+        if entry + 2 > vtable_len {
+            return Ok(0);
+        }
+        Ok(read::<u16>(self.buf, vtable + entry)? as usize)
+    }
+
+    fn scalar<T: Scalar>(&self, slot: usize, default: T) -> ProviderResult<T> {
+        match self.field_offset(slot)? {
+            0 => Ok(default),
+            offset => read(self.buf, self.pos + offset),
+        }
+    }
+
+    /// Follow an offset field to the position it points at
+    fn indirect(&self, slot: usize) -> ProviderResult<Option<usize>> {
+        match self.field_offset(slot)? {
+            0 => Ok(None),
+            offset => {
+                let at = self.pos + offset;
+                Ok(Some(at + read_u32(self.buf, at)? as usize))
+            }
+        }
+    }
+
+    fn table(&self, slot: usize) -> ProviderResult<Option<Table<'a>>> {
+        Ok(self.indirect(slot)?.map(|pos| Table { buf: self.buf, pos }))
+    }
+
+    fn string(&self, slot: usize) -> ProviderResult<Option<String>> {
+        let Some(pos) = self.indirect(slot)? else {
+            return Ok(None);
+        };
+        let len = read_u32(self.buf, pos)? as usize;
+        let bytes = self.buf.get(pos + 4..pos + 4 + len).ok_or_else(truncated)?;
+        Ok(Some(String::from_utf8_lossy(bytes).into_owned()))
+    }
+
+    fn tables(&self, slot: usize) -> ProviderResult<Vec<Table<'a>>> {
+        let Some(pos) = self.indirect(slot)? else {
+            return Ok(Vec::new());
+        };
+        let len = read_u32(self.buf, pos)? as usize;
+        (0..len)
+            .map(|i| {
+                let at = pos + 4 + 4 * i;
+                Ok(Table {
+                    buf: self.buf,
+                    pos: at + read_u32(self.buf, at)? as usize,
+                })
+            })
+            .collect()
+    }
+}
+
+fn read<T: Scalar>(buf: &[u8], pos: usize) -> ProviderResult<T> {
+    buf.get(pos..pos + T::SIZE)
+        .map(T::from_le)
+        .ok_or_else(truncated)
+}
+
+fn read_u32(buf: &[u8], pos: usize) -> ProviderResult<u32> {
+    read(buf, pos)
+}
+
+fn parse_error(message: &str) -> ProviderError {
+    ProviderError::ParseError(message.to_string())
+}
+
+fn truncated() -> ProviderError {
+    parse_error("Truncated Arrow IPC schema")
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use flatbuffers::{FlatBufferBuilder, WIPOffset};
+
+    /// A field for [`schema_message`]: name, nullable, type tag, type table
+    /// builder and children
+    pub(crate) struct TestField {
+        pub name: &'static str,
+        pub nullable: bool,
+        pub type_tag: u8,
+        pub type_fields: Vec<(u16, i32)>,
+        pub children: Vec<TestField>,
+    }
+
+    pub(crate) fn field(name: &'static str, type_tag: u8, type_fields: &[(u16, i32)]) -> TestField {
+        TestField {
+            name,
+            nullable: false,
+            type_tag,
+            type_fields: type_fields.to_vec(),
+            children: Vec::new(),
+        }
+    }
+
+    fn build_field<'a>(fbb: &mut FlatBufferBuilder<'a>, f: &TestField) -> WIPOffset<()> {
+        let children: Vec<_> = f.children.iter().map(|c| build_field(fbb, c)).collect();
+        let children = fbb.create_vector(&children);
+        let name = fbb.create_string(f.name);
+
+        // Type tables only use int-sized fields here; the reader tolerates
+        // any width as long as the vtable points at it
+        let start = fbb.start_table();
+        for (slot, value) in &f.type_fields {
+            fbb.push_slot_always::<i32>(4 + 2 * slot, *value);
+        }
+        let type_table = fbb.end_table(start);
+
+        let start = fbb.start_table();
+        fbb.push_slot_always(4, name);
+        fbb.push_slot::<u8>(6, f.nullable as u8, 0);
+        fbb.push_slot::<u8>(8, f.type_tag, 0);
+        fbb.push_slot_always(10, type_table);
+        fbb.push_slot_always(14, children);
+        WIPOffset::new(fbb.end_table(start).value())
+    }
+
+    /// Encode a schema message with the continuation marker
+    pub(crate) fn schema_message(fields: &[TestField]) -> Vec<u8> {
+        let mut fbb = FlatBufferBuilder::new();
+        let fields: Vec<_> = fields.iter().map(|f| build_field(&mut fbb, f)).collect();
+        let fields = fbb.create_vector(&fields);
+
+        let start = fbb.start_table();
+        fbb.push_slot_always(6, fields);
+        let schema = fbb.end_table(start);
+
+        let start = fbb.start_table();
+        fbb.push_slot::<i16>(4, 4, 0);
+        fbb.push_slot::<u8>(6, HEADER_SCHEMA, 0);
+        fbb.push_slot_always(8, schema);
+        let message = fbb.end_table(start);
+        fbb.finish_minimal(message);
+
+        let metadata = fbb.finished_data();
+        let mut out = CONTINUATION.to_vec();
+        out.extend_from_slice(&(metadata.len() as u32).to_le_bytes());
+        out.extend_from_slice(metadata);
+        out
+    }
+
+    fn sample() -> Vec<u8> {
+        let mut id = field("id", 2, &[(0, 64), (1, 1)]);
+        id.nullable = false;
+        let mut tags = field("tags", 12, &[]);
+        tags.nullable = true;
+        tags.children = vec![field("item", 5, &[])];
+        schema_message(&[id, tags, field("at", 10, &[(0, 2)])])
+    }
+
+    #[test]
+    fn test_read_stream_schema() {
+        let schema = read_ipc_schema(&sample()).unwrap();
+        assert_eq!(schema.fields.len(), 3);
+        assert_eq!(
+            schema.fields[0].data_type,
+            DataType::Int { bit_width: 64, is_signed: true }
+        );
+        assert!(!schema.fields[0].nullable);
+        assert_eq!(schema.fields[1].data_type, DataType::List);
+        assert_eq!(schema.fields[1].children[0].data_type, DataType::Utf8);
+        assert_eq!(
+            schema.fields[2].data_type,
+            DataType::Timestamp { unit: TimeUnit::Microsecond, timezone: None }
+        );
+    }
+
+    #[test]
+    fn test_read_file_and_flight() {
+        let stream = sample();
+
+        let mut file = FILE_MAGIC.to_vec();
+        file.extend_from_slice(&[0, 0]);
+        file.extend_from_slice(&stream);
+        assert_eq!(read_ipc_schema(&file).unwrap().fields.len(), 3);
+
+        // Legacy streams have no continuation marker
+        assert_eq!(read_ipc_schema(&stream[4..]).unwrap().fields.len(), 3);
+
+        let mut flight = vec![0x0a];
+        let mut len = stream.len();
+        while len >= 0x80 {
+            flight.push((len as u8 & 0x7f) | 0x80);
+            len >>= 7;
+        }
+        flight.push(len as u8);
+        flight.extend_from_slice(&stream);
+        assert_eq!(read_ipc_schema(&flight).unwrap().fields.len(), 3);
+    }
+
+    #[test]
+    fn test_invalid_bytes() {
+        assert!(read_ipc_schema(b"").is_err());
+        assert!(read_ipc_schema(&sample()[..20]).is_err());
+        assert!(read_ipc_schema(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f]).is_err());
+    }
+}
@@ -0,0 +1,468 @@
+//! Arrow Type Provider
+//!
+//! Generates Fusabi records for Apache Arrow schemas, so analytics plugins
+//! can consume Arrow streams with typed rows. Sources can be:
+//!
+//! - an IPC stream or file (`.arrow`, `.arrows`, `.feather`), whose leading
+//!   schema message is decoded
+//! - a Flight `GetSchema` result (the serialized `SchemaResult` message)
+//! - a schema in the Arrow JSON integration format, inline or as a `.json`
+//!   file
+//!
+//! The top-level columns become a record named by the `row_type` param
+//! (default `Row`); nested structs and unions are named after their path,
+//! e.g. `RowAddress`.
+//!
+//! # Type Mapping
+//!
+//! | Arrow                                   | Fusabi                        |
+//! |-----------------------------------------|-------------------------------|
+//! | `null`                                  | `unit`                        |
+//! | `bool`                                  | `bool`                        |
+//! | `int8` – `int32`                        | `int`                         |
+//! | `int64`                                 | `int64`                       |
+//! | `uint8` – `uint32`                      | `uint`                        |
+//! | `uint64`                                | `uint64`                      |
+//! | `float16`, `float32`, `float64`         | `float`                       |
+//! | `utf8`, `large_utf8`, `utf8_view`       | `string`                      |
+//! | `binary` variants, `fixed_size_binary`  | `bytes`                       |
+//! | `decimal`                               | `float` (lossy)               |
+//! | `date32`, `time32`                      | `int` (days, time units)      |
+//! | `date64`, `time64`, `timestamp`, `duration` | `int64` (time units)      |
+//! | `interval[year_month]`                  | `int` (months)                |
+//! | other intervals                         | `bytes` (lossy)               |
+//! | list variants                           | `T list`                      |
+//! | `struct`                                | record                        |
+//! | `map<K, V>`                             | `Map<K, V>`                   |
+//! | `union`                                 | union with a case per child   |
+//! | nullable fields                         | `T option`                    |
+//!
+//! Dictionary-encoded fields take their value type; run-end encoded fields
+//! take the type of their values.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_arrow::ArrowProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = ArrowProvider::new();
+//! let params = ProviderParams::default().with("row_type", "Trade");
+//! let schema = provider.resolve_schema("trades.arrows", &params)?;
+//! let types = provider.generate_types(&schema, "Market")?;
+//! ```
+
+mod ipc;
+mod types;
+
+pub use ipc::read_ipc_schema;
+pub use types::{
+    ArrowField, ArrowSchema, DataType, DateUnit, IntervalUnit, Precision, TimeUnit, UnionMode,
+};
+
+use fusabi_provider_common::{
+    read_binary_source, read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature,
+    ParamKind, ParamSchema, ParamSpec, ParameterizedProvider, SourceValidator, StructuredError,
+    TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, DuDef, VariantDef, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use serde::{Deserialize, Serialize};
+
+/// Default name of the generated row record
+pub const DEFAULT_ROW_TYPE: &str = "Row";
+
+/// A decoded schema with the name of its row record, stored in
+/// [`Schema::Custom`] as JSON
+#[derive(Debug, Serialize, Deserialize)]
+struct ResolvedSchema {
+    row_type: String,
+    schema: ArrowSchema,
+}
+
+/// Arrow type provider
+pub struct ArrowProvider {
+    generator: TypeGenerator,
+}
+
+impl ArrowProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    /// Parse a schema in the JSON integration format
+    fn parse_json(&self, json: &str) -> ProviderResult<ArrowSchema> {
+        ArrowSchema::from_json(json)
+            .map_err(|e| ProviderError::ParseError(format!("Invalid Arrow JSON schema: {}", e)))
+    }
+
+    /// Read the schema from a local IPC stream, IPC file or Flight result
+    fn read_ipc(&self, source: &str, params: &ProviderParams) -> ProviderResult<ArrowSchema> {
+        read_ipc_schema(&read_binary_source(source, params)?)
+    }
+
+    fn parse_resolved(&self, json: &str) -> ProviderResult<ResolvedSchema> {
+        serde_json::from_str(json)
+            .map_err(|e| StructuredError::parse("Invalid Arrow schema").with_source(e).into())
+    }
+
+    /// Generate the row record and every nested type
+    fn generate_from_schema(&self, resolved: &ResolvedSchema, namespace: &str) -> GeneratedTypes {
+        let mut result = GeneratedTypes::new();
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+
+        let row_type = self.generator.naming.apply(&resolved.row_type);
+        self.push_record(&row_type, &resolved.schema.fields, &mut module.types);
+
+        result.modules.push(module);
+        result
+    }
+
+    /// Push a record for `fields`, after the types its fields need
+    fn push_record(&self, name: &str, fields: &[ArrowField], types: &mut Vec<TypeDefinition>) {
+        let fields = fields
+            .iter()
+            .map(|field| {
+                let context = format!("{}{}", name, self.generator.naming.apply(&field.name));
+                (field.name.clone(), self.field_type(field, &context, types).into())
+            })
+            .collect();
+
+        types.push(TypeDefinition::Record(RecordDef {
+            name: name.to_string(),
+            fields,
+        }));
+    }
+
+    /// Type of a field, wrapped in `option` when nullable
+    ///
+    /// Nested structs and unions are named after `context`.
+    fn field_type(
+        &self,
+        field: &ArrowField,
+        context: &str,
+        types: &mut Vec<TypeDefinition>,
+    ) -> TypeShape {
+        let base = self.data_type(field, context, types);
+        base.option_unless(!field.nullable || field.data_type == DataType::Null)
+    }
+
+    fn data_type(
+        &self,
+        field: &ArrowField,
+        context: &str,
+        types: &mut Vec<TypeDefinition>,
+    ) -> TypeShape {
+        let child = |index: usize, types: &mut Vec<TypeDefinition>| {
+            field
+                .children
+                .get(index)
+                .map(|c| self.field_type(c, context, types))
+                .unwrap_or_else(|| TypeShape::named("unit"))
+        };
+
+        let name = match &field.data_type {
+            DataType::Null => "unit".to_string(),
+            DataType::Bool => "bool".to_string(),
+            DataType::Int { bit_width, is_signed } => match (*is_signed, *bit_width > 32) {
+                (true, false) => "int".to_string(),
+                (true, true) => "int64".to_string(),
+                (false, false) => "uint".to_string(),
+                (false, true) => "uint64".to_string(),
+            },
+            DataType::FloatingPoint { .. } | DataType::Decimal { .. } => "float".to_string(),
+            DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => "string".to_string(),
+            DataType::Binary
+            | DataType::LargeBinary
+            | DataType::BinaryView
+            | DataType::FixedSizeBinary { .. } => "bytes".to_string(),
+            DataType::Date { unit: DateUnit::Day } => "int".to_string(),
+            DataType::Date { unit: DateUnit::Millisecond } => "int64".to_string(),
+            DataType::Time { bit_width, .. } if *bit_width <= 32 => "int".to_string(),
+            DataType::Time { .. } | DataType::Timestamp { .. } | DataType::Duration { .. } => {
+                "int64".to_string()
+            }
+            DataType::Interval { unit: IntervalUnit::YearMonth } => "int".to_string(),
+            DataType::Interval { .. } => "bytes".to_string(),
+            DataType::List
+            | DataType::LargeList
+            | DataType::ListView
+            | DataType::LargeListView
+            | DataType::FixedSizeList { .. } => return child(0, types).list(),
+            DataType::Map { .. } => {
+                // The single child is an `entries` struct of key and value
+                let entries = field.children.first().map(|e| e.children.as_slice()).unwrap_or(&[]);
+                let part = |index: usize, types: &mut Vec<TypeDefinition>| {
+                    entries
+                        .get(index)
+                        .map(|c| self.field_type(c, context, types))
+                        .unwrap_or_else(|| TypeShape::named("unit"))
+                };
+                let key = part(0, types);
+                let value = part(1, types);
+                return TypeShape::map(key, value);
+            }
+            DataType::Struct => {
+                self.push_record(context, &field.children, types);
+                context.to_string()
+            }
+            DataType::Union { .. } => {
+                let variants = field
+                    .children
+                    .iter()
+                    .map(|c| {
+                        let case = self.generator.naming.apply(&c.name);
+                        let nested = format!("{}{}", context, case);
+                        VariantDef::new(case, vec![self.data_type(c, &nested, types).into()])
+                    })
+                    .collect();
+                types.push(TypeDefinition::Du(DuDef {
+                    name: context.to_string(),
+                    variants,
+                }));
+                context.to_string()
+            }
+        };
+
+        TypeShape::named(name)
+    }
+}
+
+impl Default for ArrowProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for ArrowProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("ArrowProvider")
+            .param(
+                ParamSpec::new("row_type", ParamKind::String)
+                    .describe("Name of the generated record"),
+            )
+    }
+}
+
+impl TypeProvider for ArrowProvider {
+    fn name(&self) -> &str {
+        "ArrowProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let schema = if source.trim_start().starts_with('{') {
+            self.parse_json(source)?
+        } else if source.ends_with(".json") {
+            self.parse_json(&read_source(source, params)?)?
+        } else {
+            self.read_ipc(source, params)?
+        };
+
+        let resolved = ResolvedSchema {
+            row_type: typed.str("row_type").unwrap_or(DEFAULT_ROW_TYPE).to_string(),
+            schema,
+        };
+        let json = serde_json::to_string(&resolved)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        match schema {
+            Schema::Custom(json) => {
+                let resolved = self.parse_resolved(json)?;
+                Ok(self.generate_from_schema(&resolved, namespace))
+            }
+            _ => Err(ProviderError::ParseError("Expected Arrow schema".to_string())),
+        }
+    }
+}
+
+impl SourceValidator for ArrowProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected Arrow schema".to_string()));
+        };
+
+        let resolved = self.parse_resolved(json)?;
+        let mut diagnostics = Vec::new();
+        let row_type = self.generator.naming.apply(&resolved.row_type);
+        for field in &resolved.schema.fields {
+            lossy_fields(field, &row_type, &mut diagnostics);
+        }
+
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for ArrowProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+/// Report fields whose values do not round-trip through their Fusabi type
+fn lossy_fields(field: &ArrowField, parent: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let location = format!("{}.{}", parent, field.name);
+
+    let message = match &field.data_type {
+        DataType::Decimal { precision, scale, .. } => {
+            Some(format!("decimal({}, {}) is typed as `float`", precision, scale))
+        }
+        DataType::Interval { unit } if *unit != IntervalUnit::YearMonth => {
+            Some(format!("{:?} interval is typed as `bytes`", unit))
+        }
+        _ => None,
+    };
+    if let Some(message) = message {
+        diagnostics.push(Diagnostic::info("lossy-conversion", message).at(location.clone()));
+    }
+
+    for child in &field.children {
+        lossy_fields(child, &location, diagnostics);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    const SCHEMA: &str = r#"{
+        "schema": {
+            "fields": [
+                {"name": "id", "nullable": false, "type": {"name": "int", "bitWidth": 64, "isSigned": true}, "children": []},
+                {"name": "price", "nullable": true, "type": {"name": "decimal", "precision": 18, "scale": 4}, "children": []},
+                {"name": "ts", "nullable": false, "type": {"name": "timestamp", "unit": "NANOSECOND", "timezone": "UTC"}, "children": []},
+                {"name": "tags", "nullable": false, "type": {"name": "list"}, "children": [
+                    {"name": "item", "nullable": false, "type": {"name": "utf8"}, "children": []}
+                ]},
+                {"name": "venue", "nullable": true, "type": {"name": "struct"}, "children": [
+                    {"name": "code", "nullable": false, "type": {"name": "utf8"}, "children": []},
+                    {"name": "lot", "nullable": false, "type": {"name": "int", "bitWidth": 16, "isSigned": false}, "children": []}
+                ]},
+                {"name": "attrs", "nullable": false, "type": {"name": "map", "keysSorted": false}, "children": [
+                    {"name": "entries", "nullable": false, "type": {"name": "struct"}, "children": [
+                        {"name": "key", "nullable": false, "type": {"name": "utf8"}, "children": []},
+                        {"name": "value", "nullable": true, "type": {"name": "floatingpoint", "precision": "DOUBLE"}, "children": []}
+                    ]}
+                ]},
+                {"name": "side", "nullable": false, "type": {"name": "union", "mode": "DENSE"}, "children": [
+                    {"name": "buy", "nullable": false, "type": {"name": "bool"}, "children": []},
+                    {"name": "note", "nullable": false, "type": {"name": "utf8"}, "children": []}
+                ]}
+            ]
+        }
+    }"#;
+
+    fn record(types: &GeneratedTypes, name: &str) -> Vec<(String, String)> {
+        types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(
+                    r.fields
+                        .iter()
+                        .map(|(n, t)| (n.clone(), t.to_string()))
+                        .collect(),
+                ),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+    }
+
+    #[test]
+    fn test_generate_from_json() {
+        let provider = ArrowProvider::new();
+        let params = ProviderParams::default().with("row_type", "trade");
+        let schema = provider.resolve_schema(SCHEMA, &params).unwrap();
+        let types = provider.generate_types(&schema, "Market").unwrap();
+
+        assert_eq!(types.modules[0].path, vec!["Market"]);
+        let fields = record(&types, "Trade");
+        let field = |name: &str| fields.iter().find(|(n, _)| n == name).unwrap().1.clone();
+        assert_eq!(field("id"), "int64");
+        assert_eq!(field("price"), "float option");
+        assert_eq!(field("ts"), "int64");
+        assert_eq!(field("tags"), "string list");
+        assert_eq!(field("venue"), "TradeVenue option");
+        assert_eq!(field("attrs"), "Map<string, float option>");
+        assert_eq!(field("side"), "TradeSide");
+
+        assert_eq!(
+            record(&types, "TradeVenue"),
+            [
+                ("code".to_string(), "string".to_string()),
+                ("lot".to_string(), "uint".to_string()),
+            ]
+        );
+
+        let side = types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Du(d) if d.name == "TradeSide" => Some(d),
+                _ => None,
+            })
+            .unwrap();
+        let cases: Vec<&str> = side.variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(cases, ["Buy", "Note"]);
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/arrow.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_ipc_stream_file() {
+        let mut ts = ipc::tests::field("ts", 10, &[(0, 1)]);
+        ts.nullable = true;
+        let bytes = ipc::tests::schema_message(&[
+            ipc::tests::field("symbol", 5, &[]),
+            ts,
+        ]);
+        let path = std::env::temp_dir().join("fusabi-arrow-schema-test.arrows");
+        std::fs::write(&path, bytes).unwrap();
+
+        let provider = ArrowProvider::new();
+        let schema = provider
+            .resolve_schema(path.to_str().unwrap(), &ProviderParams::default())
+            .unwrap();
+        let types = provider.generate_types(&schema, "Ticks").unwrap();
+        assert_eq!(
+            record(&types, "Row"),
+            [
+                ("symbol".to_string(), "string".to_string()),
+                ("ts".to_string(), "int64 option".to_string()),
+            ]
+        );
+
+        // Oversized files are rejected rather than read truncated
+        let params = ProviderParams::default().with("max_source_bytes", "16");
+        let err = provider.resolve_schema(path.to_str().unwrap(), &params).unwrap_err();
+        assert!(err.to_string().contains("max_source_bytes"));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let report = ArrowProvider::new().validate_source(SCHEMA, &ProviderParams::default());
+        assert!(report.is_valid());
+
+        let lossy = report.diagnostics.iter().find(|d| d.code == "lossy-conversion").unwrap();
+        assert_eq!(lossy.location.as_deref(), Some("Row.price"));
+    }
+}
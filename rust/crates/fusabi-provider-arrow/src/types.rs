@@ -0,0 +1,141 @@
+//! Arrow schema type definitions
+//!
+//! These mirror the schema part of the Arrow JSON integration format, so a
+//! schema decoded from IPC bytes and one written by hand share a single
+//! representation.
+
+use serde::{Deserialize, Serialize};
+
+/// An Arrow schema
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArrowSchema {
+    /// Top-level columns
+    pub fields: Vec<ArrowField>,
+}
+
+/// An Arrow field (column, struct member or list item)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArrowField {
+    /// Field name
+    pub name: String,
+    /// Whether the field may hold nulls
+    #[serde(default = "default_nullable")]
+    pub nullable: bool,
+    /// Field type
+    #[serde(rename = "type")]
+    pub data_type: DataType,
+    /// Child fields of nested types
+    #[serde(default)]
+    pub children: Vec<ArrowField>,
+}
+
+fn default_nullable() -> bool {
+    true
+}
+
+/// Arrow logical type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "name", rename_all = "lowercase")]
+pub enum DataType {
+    Null,
+    Bool,
+    #[serde(rename_all = "camelCase")]
+    Int { bit_width: u32, is_signed: bool },
+    FloatingPoint { precision: Precision },
+    Utf8,
+    LargeUtf8,
+    Utf8View,
+    Binary,
+    LargeBinary,
+    BinaryView,
+    #[serde(rename_all = "camelCase")]
+    FixedSizeBinary { byte_width: u32 },
+    Decimal {
+        precision: u32,
+        scale: i32,
+        #[serde(default = "default_decimal_width", rename = "bitWidth")]
+        bit_width: u32,
+    },
+    Date { unit: DateUnit },
+    #[serde(rename_all = "camelCase")]
+    Time { unit: TimeUnit, bit_width: u32 },
+    Timestamp {
+        unit: TimeUnit,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timezone: Option<String>,
+    },
+    Interval { unit: IntervalUnit },
+    Duration { unit: TimeUnit },
+    List,
+    LargeList,
+    ListView,
+    LargeListView,
+    #[serde(rename_all = "camelCase")]
+    FixedSizeList { list_size: u32 },
+    Struct,
+    #[serde(rename_all = "camelCase")]
+    Map { keys_sorted: bool },
+    Union { mode: UnionMode },
+}
+
+fn default_decimal_width() -> u32 {
+    128
+}
+
+/// Floating point precision
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Precision {
+    Half,
+    Single,
+    Double,
+}
+
+/// Date unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum DateUnit {
+    Day,
+    Millisecond,
+}
+
+/// Time, timestamp and duration unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TimeUnit {
+    Second,
+    Millisecond,
+    Microsecond,
+    Nanosecond,
+}
+
+/// Interval unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum IntervalUnit {
+    YearMonth,
+    DayTime,
+    MonthDayNano,
+}
+
+/// Union memory layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum UnionMode {
+    Sparse,
+    Dense,
+}
+
+impl ArrowSchema {
+    /// Parse a schema from JSON
+    ///
+    /// Accepts a bare `{"fields": [...]}` object or a full integration file
+    /// with the schema under `"schema"`.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        match value.get("schema") {
+            Some(schema) => serde_json::from_value(schema.clone()),
+            None => serde_json::from_value(value),
+        }
+    }
+}
@@ -0,0 +1,261 @@
+//! Conversion of ASN.1 type assignments to Fusabi types
+
+use crate::types::{Alternative, Asn1File, Asn1Type, Assignment, Component, Module, Presence, Range};
+use fusabi_provider_common::{Diagnostic, TypeShape};
+use fusabi_type_providers::{DuDef, RecordDef, TypeDefinition, TypeExpr, TypeGenerator, VariantDef};
+use std::collections::HashMap;
+
+/// Converts the modules of a file, collecting types and diagnostics
+pub(crate) struct Conversion<'a> {
+    generator: &'a TypeGenerator,
+    /// Type assignments of every module, by name
+    assignments: HashMap<&'a str, &'a Assignment>,
+    /// Dummy parameters of the assignment being converted
+    params: &'a [String],
+    pub diagnostics: Vec<Diagnostic>,
+    /// Names of definitions generated so far
+    defined: Vec<String>,
+    /// References being inlined, to stop alias cycles
+    inlining: Vec<String>,
+}
+
+impl<'a> Conversion<'a> {
+    pub fn new(generator: &'a TypeGenerator, file: &'a Asn1File) -> Self {
+        Self {
+            generator,
+            assignments: file
+                .modules
+                .iter()
+                .flat_map(|m| &m.assignments)
+                .map(|a| (a.name.as_str(), a))
+                .collect(),
+            params: &[],
+            diagnostics: Vec::new(),
+            defined: Vec::new(),
+            inlining: Vec::new(),
+        }
+    }
+
+    /// Generate the records and unions of a module, including those for
+    /// nested anonymous types; references to other modules are resolved
+    /// across the whole file
+    pub fn convert_module(&mut self, module: &'a Module) -> Vec<TypeDefinition> {
+        let mut types = Vec::new();
+        for assignment in &module.assignments {
+            if assignment.ty.is_structured() {
+                self.params = &assignment.params;
+                let name = self.generator.naming.apply(&assignment.name);
+                self.define(&name, &assignment.ty, &assignment.name, &mut types);
+                self.params = &[];
+            }
+        }
+        types
+    }
+
+    /// Define a named record or union for a structured type
+    fn define(&mut self, name: &str, ty: &'a Asn1Type, location: &str, types: &mut Vec<TypeDefinition>) {
+        if self.defined.iter().any(|d| d == name) {
+            return;
+        }
+        self.defined.push(name.to_string());
+
+        let definition = match ty {
+            Asn1Type::Sequence(components) => {
+                let mut fields = Vec::new();
+                self.fields(components, name, location, &mut fields, types);
+                TypeDefinition::Record(RecordDef { name: name.to_string(), fields })
+            }
+            Asn1Type::Choice(alternatives) => TypeDefinition::Du(DuDef {
+                name: name.to_string(),
+                variants: alternatives
+                    .iter()
+                    .map(|Alternative { name: alternative, ty }| {
+                        let case = self.generator.naming.apply(alternative);
+                        let context = format!("{}{}", name, case);
+                        let shape = self.type_shape(ty, &context, &format!("{}.{}", location, alternative), types);
+                        VariantDef::new(case, vec![shape.into()])
+                    })
+                    .collect(),
+            }),
+            Asn1Type::Enumerated(names) => TypeDefinition::Du(DuDef {
+                name: name.to_string(),
+                variants: names
+                    .iter()
+                    .map(|n| VariantDef::new_simple(self.generator.naming.apply(n)))
+                    .collect(),
+            }),
+            // Named bits are flags
+            Asn1Type::BitString(bits) => TypeDefinition::Record(RecordDef {
+                name: name.to_string(),
+                fields: bits
+                    .iter()
+                    .map(|bit| (field_name(bit), TypeShape::named("bool").into()))
+                    .collect(),
+            }),
+            _ => return,
+        };
+
+        types.push(definition);
+    }
+
+    fn fields(
+        &mut self,
+        components: &'a [Component],
+        record: &str,
+        location: &str,
+        fields: &mut Vec<(String, TypeExpr)>,
+        types: &mut Vec<TypeDefinition>,
+    ) {
+        for component in components {
+            let field = match component {
+                Component::Field(field) => field,
+                Component::ComponentsOf(Asn1Type::Sequence(included)) => {
+                    self.fields(included, record, location, fields, types);
+                    continue;
+                }
+                Component::ComponentsOf(Asn1Type::Reference(name)) => {
+                    match self.resolve_sequence(name) {
+                        Some(included) if !self.inlining.contains(name) => {
+                            self.inlining.push(name.clone());
+                            self.fields(included, record, location, fields, types);
+                            self.inlining.pop();
+                        }
+                        _ => self.unresolved(name, location),
+                    }
+                    continue;
+                }
+                Component::ComponentsOf(_) => continue,
+            };
+
+            let field_location = format!("{}.{}", location, field.name);
+            let context = format!("{}{}", record, self.generator.naming.apply(&field.name));
+            let shape = self.type_shape(&field.ty, &context, &field_location, types);
+
+            let shape = match field.presence {
+                Presence::Required if !field.extension => shape,
+                Presence::Required | Presence::Optional => optional(shape),
+                Presence::Default => {
+                    self.diagnostics.push(
+                        Diagnostic::info(
+                            "default-skipped",
+                            format!(
+                                "Default value of `{}` is not applied; the field is generated as an option",
+                                field_location
+                            ),
+                        )
+                        .at(field_location),
+                    );
+                    optional(shape)
+                }
+            };
+            fields.push((field_name(&field.name), shape.into()));
+        }
+    }
+
+    /// Components of the SEQUENCE a `COMPONENTS OF` reference names,
+    /// following aliases
+    fn resolve_sequence(&self, name: &str) -> Option<&'a [Component]> {
+        let mut name = name;
+        for _ in 0..=self.assignments.len() {
+            match &self.assignments.get(name)?.ty {
+                Asn1Type::Sequence(components) => return Some(components),
+                Asn1Type::Reference(target) => name = target,
+                _ => return None,
+            }
+        }
+        None
+    }
+
+    fn type_shape(&mut self, ty: &'a Asn1Type, context: &str, location: &str, types: &mut Vec<TypeDefinition>) -> TypeShape {
+        let named = |name: &str| TypeShape::Named(name.to_string());
+        match ty {
+            Asn1Type::Boolean => named("bool"),
+            Asn1Type::Integer(range) => self.integer(range.as_ref(), location),
+            Asn1Type::Real => named("float"),
+            Asn1Type::Null => named("unit"),
+            Asn1Type::BitString(bits) if bits.is_empty() => named("bytes"),
+            Asn1Type::OctetString => named("bytes"),
+            Asn1Type::ObjectIdentifier | Asn1Type::CharacterString | Asn1Type::Time => named("string"),
+            Asn1Type::SequenceOf(element) => {
+                TypeShape::List(Box::new(self.type_shape(element, &format!("{}Item", context), location, types)))
+            }
+            Asn1Type::BitString(_) | Asn1Type::Enumerated(_) | Asn1Type::Sequence(_) | Asn1Type::Choice(_) => {
+                self.define(context, ty, location, types);
+                named(context)
+            }
+            Asn1Type::Reference(name) => self.reference(name, location, types),
+            Asn1Type::Open(_) => named("any"),
+        }
+    }
+
+    /// `int` unless a range constraint needs 64 bits
+    fn integer(&mut self, range: Option<&Range>, location: &str) -> TypeShape {
+        let bounds: Vec<i128> = range.into_iter().flat_map(|r| [r.lower, r.upper]).flatten().collect();
+
+        let fits = |min: i128, max: i128| bounds.iter().all(|b| (min..=max).contains(b));
+        let name = if fits(i32::MIN.into(), i32::MAX.into()) {
+            "int"
+        } else {
+            if !fits(i64::MIN.into(), i64::MAX.into()) {
+                self.diagnostics.push(
+                    Diagnostic::info(
+                        "lossy-conversion",
+                        "Integer range exceeds 64 bits; values beyond `int64` will not round-trip",
+                    )
+                    .at(location.to_string()),
+                );
+            }
+            "int64"
+        };
+        TypeShape::Named(name.to_string())
+    }
+
+    fn reference(&mut self, name: &str, location: &str, types: &mut Vec<TypeDefinition>) -> TypeShape {
+        // Dummy parameters of parameterized types are not instantiated
+        if self.params.iter().any(|p| p == name) {
+            return TypeShape::Named("any".to_string());
+        }
+
+        // Types from other modules are referenced by their name
+        let Some(assignment) = self.assignments.get(name).copied() else {
+            return TypeShape::Named(self.generator.naming.apply(name));
+        };
+
+        let type_name = self.generator.naming.apply(name);
+        if assignment.ty.is_structured() {
+            return TypeShape::Named(type_name);
+        }
+
+        // Aliases are inlined; nested types are named after the alias
+        if self.inlining.iter().any(|n| n == name) {
+            self.unresolved(name, location);
+            return TypeShape::Named("any".to_string());
+        }
+        self.inlining.push(name.to_string());
+        let shape = self.type_shape(&assignment.ty, &type_name, location, types);
+        self.inlining.pop();
+        shape
+    }
+
+    fn unresolved(&mut self, name: &str, location: &str) {
+        self.diagnostics.push(
+            Diagnostic::warning(
+                "name-unresolved",
+                format!("`{}` does not resolve to a type in the loaded modules", name),
+            )
+            .at(location.to_string()),
+        );
+    }
+}
+
+/// Field name for an ASN.1 identifier: hyphens become `_`
+fn field_name(name: &str) -> String {
+    name.replace('-', "_")
+}
+
+fn optional(shape: TypeShape) -> TypeShape {
+    match shape {
+        TypeShape::Option(_) => shape,
+        _ => TypeShape::Option(Box::new(shape)),
+    }
+}
@@ -0,0 +1,450 @@
+//! ASN.1 Type Provider
+//!
+//! Generates Fusabi types from ASN.1 (X.680) module definitions, such as
+//! the PKIX modules behind X.509 certificates or the SMI and PDU modules
+//! of SNMP.
+//!
+//! | ASN.1                                 | Fusabi                        |
+//! |---------------------------------------|-------------------------------|
+//! | `SEQUENCE { ... }`, `SET { ... }`     | record                        |
+//! | `CHOICE { ... }`                      | DU with one case per alternative |
+//! | `ENUMERATED { ... }`                  | DU with simple cases          |
+//! | `OPTIONAL`, `DEFAULT`, extensions     | `type option`                 |
+//! | `SEQUENCE OF T`, `SET OF T`           | `T list`                      |
+//! | `BOOLEAN`                             | `bool`                        |
+//! | `INTEGER`                             | `int`, or `int64` for wide ranges |
+//! | `REAL`                                | `float`                       |
+//! | `NULL`                                | `unit`                        |
+//! | `OCTET STRING`, `BIT STRING`          | `bytes`                       |
+//! | `BIT STRING { named bits }`           | record of `bool`              |
+//! | `OBJECT IDENTIFIER`, string and time types | `string`                 |
+//! | `ANY`, open types                     | `any`                         |
+//!
+//! Each ASN.1 module becomes a Fusabi module named after it; assignments
+//! outside a module header go in the namespace module. Type aliases are
+//! inlined, and nested anonymous types are named after their enclosing
+//! type and component. Tags, constraints and value assignments do not
+//! affect the generated types.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_asn1::Asn1Provider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = Asn1Provider::new();
+//! let schema = provider.resolve_schema("rfc5280.asn1", &ProviderParams::default())?;
+//! let types = provider.generate_types(&schema, "Pkix")?;
+//! ```
+
+mod convert;
+mod parser;
+mod types;
+
+pub use types::{
+    Alternative, Asn1File, Asn1Type, Assignment, Component, Field, Import, Module, Presence,
+    Range, Skipped, SkippedKind,
+};
+
+use convert::Conversion;
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, SourceValidator,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    ProviderError, ProviderResult,
+};
+
+/// ASN.1 type provider
+pub struct Asn1Provider {
+    generator: TypeGenerator,
+}
+
+impl Asn1Provider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    /// Parse ASN.1 modules from string content
+    fn parse_asn1(&self, content: &str) -> ProviderResult<Asn1File> {
+        parser::parse_asn1(content)
+    }
+
+    /// Generate types from parsed modules
+    fn generate_from_asn1(&self, asn1: &Asn1File, namespace: &str) -> GeneratedTypes {
+        let mut result = GeneratedTypes::new();
+        let mut conversion = Conversion::new(&self.generator, asn1);
+
+        for module in &asn1.modules {
+            let path = match &module.name {
+                Some(name) => vec![self.generator.naming.apply(name)],
+                None => vec![namespace.to_string()],
+            };
+            let mut types_module = GeneratedModule::new(path);
+            types_module.types = conversion.convert_module(module);
+            if !types_module.types.is_empty() {
+                result.modules.push(types_module);
+            }
+        }
+
+        result
+    }
+
+    fn expect_asn1<'a>(&self, schema: &'a Schema) -> ProviderResult<&'a str> {
+        match schema {
+            Schema::Custom(content) => Ok(content),
+            _ => Err(ProviderError::ParseError("Expected ASN.1 schema".to_string())),
+        }
+    }
+}
+
+impl Default for Asn1Provider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypeProvider for Asn1Provider {
+    fn name(&self) -> &str {
+        "Asn1Provider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        // Every assignment uses `::=`
+        let content = if source.contains("::=") {
+            source.to_string()
+        } else {
+            read_source(source, params)?
+        };
+
+        // Parse the modules to validate them
+        let _asn1 = self.parse_asn1(&content)?;
+
+        Ok(Schema::Custom(content))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let asn1 = self.parse_asn1(self.expect_asn1(schema)?)?;
+        Ok(self.generate_from_asn1(&asn1, namespace))
+    }
+}
+
+impl SourceValidator for Asn1Provider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let asn1 = self.parse_asn1(self.expect_asn1(schema)?)?;
+        let mut diagnostics = Vec::new();
+
+        if asn1.modules.iter().all(|m| m.assignments.is_empty()) {
+            diagnostics.push(Diagnostic::warning(
+                "no-types",
+                "No type assignments found; no types will be generated",
+            ));
+        }
+
+        let defined: Vec<&str> = asn1.modules.iter().filter_map(|m| m.name.as_deref()).collect();
+        for module in &asn1.modules {
+            for import in module.imports.iter().filter(|i| !defined.contains(&i.module.as_str())) {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        "import-unresolved",
+                        format!(
+                            "Module `{}` is not loaded; imported types will be unresolved",
+                            import.module
+                        ),
+                    )
+                    .at(import.module.clone()),
+                );
+            }
+
+            for skipped in &module.skipped {
+                let (code, what) = match skipped.kind {
+                    SkippedKind::Value => ("value-skipped", "Value assignment"),
+                    SkippedKind::Class => ("class-skipped", "Information object class"),
+                    SkippedKind::ObjectSet => ("class-skipped", "Information object set"),
+                    SkippedKind::Macro => ("macro-skipped", "Macro"),
+                };
+                diagnostics.push(
+                    Diagnostic::info(code, format!("{} `{}` does not generate types", what, skipped.name))
+                        .at(skipped.name.clone()),
+                );
+            }
+        }
+
+        let mut conversion = Conversion::new(&self.generator, &asn1);
+        for module in &asn1.modules {
+            conversion.convert_module(module);
+        }
+        diagnostics.extend(conversion.diagnostics);
+
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for Asn1Provider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+    use fusabi_type_providers::{RecordDef, TypeDefinition};
+
+    fn generate(asn1: &str) -> GeneratedTypes {
+        let provider = Asn1Provider::new();
+        let schema = provider.resolve_schema(asn1, &ProviderParams::default()).unwrap();
+        provider.generate_types(&schema, "Test").unwrap()
+    }
+
+    fn record<'a>(module: &'a GeneratedModule, name: &str) -> &'a RecordDef {
+        module
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+    }
+
+    fn fields(record: &RecordDef) -> Vec<(&str, String)> {
+        record
+            .fields
+            .iter()
+            .map(|(name, ty)| (name.as_str(), ty.to_string()))
+            .collect()
+    }
+
+    fn variants(module: &GeneratedModule, name: &str) -> Vec<(String, String)> {
+        module
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Du(du) if du.name == name => Some(
+                    du.variants
+                        .iter()
+                        .map(|v| {
+                            let fields: Vec<String> = v.fields.iter().map(|f| f.to_string()).collect();
+                            (v.name.clone(), fields.join(" * "))
+                        })
+                        .collect(),
+                ),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected DU {}", name))
+    }
+
+    #[test]
+    fn test_generate_x509() {
+        let types = generate(
+            r#"
+            PKIX1Explicit88 DEFINITIONS EXPLICIT TAGS ::=
+            BEGIN
+            TBSCertificate  ::=  SEQUENCE  {
+                 version         [0]  Version DEFAULT v1,
+                 serialNumber         CertificateSerialNumber,
+                 signature            AlgorithmIdentifier,
+                 validity             Validity,
+                 subjectPublicKeyInfo SubjectPublicKeyInfo,
+                 issuerUniqueID  [1]  IMPLICIT UniqueIdentifier OPTIONAL,
+                 extensions      [3]  Extensions OPTIONAL }
+
+            Version  ::=  INTEGER  {  v1(0), v2(1), v3(2)  }
+            CertificateSerialNumber  ::=  INTEGER
+            UniqueIdentifier  ::=  BIT STRING
+
+            AlgorithmIdentifier  ::=  SEQUENCE  {
+                 algorithm               OBJECT IDENTIFIER,
+                 parameters              ANY DEFINED BY algorithm OPTIONAL  }
+
+            Validity ::= SEQUENCE { notBefore Time, notAfter Time }
+            Time ::= CHOICE { utcTime UTCTime, generalTime GeneralizedTime }
+
+            SubjectPublicKeyInfo  ::=  SEQUENCE  {
+                 algorithm            AlgorithmIdentifier,
+                 subjectPublicKey     BIT STRING  }
+
+            Extensions  ::=  SEQUENCE SIZE (1..MAX) OF Extension
+            Extension  ::=  SEQUENCE  {
+                 extnID      OBJECT IDENTIFIER,
+                 critical    BOOLEAN DEFAULT FALSE,
+                 extnValue   OCTET STRING  }
+
+            KeyUsage ::= BIT STRING {
+                 digitalSignature        (0),
+                 keyEncipherment         (2) }
+            END
+            "#,
+        );
+
+        assert_eq!(types.modules.len(), 1);
+        let module = &types.modules[0];
+        assert_eq!(module.path.len(), 1);
+        assert_eq!(
+            fields(record(module, "Tbscertificate")),
+            [
+                ("version", "int option".to_string()),
+                ("serialNumber", "int".to_string()),
+                ("signature", "AlgorithmIdentifier".to_string()),
+                ("validity", "Validity".to_string()),
+                ("subjectPublicKeyInfo", "SubjectPublicKeyInfo".to_string()),
+                ("issuerUniqueID", "bytes option".to_string()),
+                ("extensions", "Extension list option".to_string()),
+            ]
+        );
+        assert_eq!(
+            fields(record(module, "AlgorithmIdentifier")),
+            [("algorithm", "string".to_string()), ("parameters", "any option".to_string())]
+        );
+        assert_eq!(
+            variants(module, "Time"),
+            [("UtcTime".to_string(), "string".to_string()), ("GeneralTime".to_string(), "string".to_string())]
+        );
+        assert_eq!(
+            fields(record(module, "KeyUsage")),
+            [("digitalSignature", "bool".to_string()), ("keyEncipherment", "bool".to_string())]
+        );
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/asn1.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_generate_snmp_pdus() {
+        let types = generate(
+            r#"
+            Counter32 ::= [APPLICATION 1] IMPLICIT INTEGER (0..4294967295)
+            Counter64 ::= [APPLICATION 6] IMPLICIT INTEGER (0..18446744073709551615)
+            ObjectName ::= OBJECT IDENTIFIER
+
+            PDUs ::= CHOICE {
+                get-request      GetRequest-PDU,
+                response         Response-PDU
+            }
+            GetRequest-PDU ::= [0] IMPLICIT PDU
+            Response-PDU ::= [2] IMPLICIT PDU
+
+            max-bindings INTEGER ::= 2147483647
+
+            PDU ::= SEQUENCE {
+                request-id INTEGER (-214783648..214783647),
+                error-status INTEGER { noError(0), tooBig(1) },
+                error-index INTEGER (0..max-bindings),
+                variable-bindings VarBindList
+            }
+
+            VarBind ::= SEQUENCE {
+                name ObjectName,
+                CHOICE {
+                    value          Counter64,
+                    unSpecified    NULL,
+                    noSuchObject   [0] IMPLICIT NULL
+                }
+            }
+            VarBindList ::= SEQUENCE (SIZE (0..max-bindings)) OF VarBind
+            "#,
+        );
+
+        let module = &types.modules[0];
+        assert_eq!(module.path, vec!["Test"]);
+        assert_eq!(
+            variants(module, "Pdus"),
+            [("GetRequest".to_string(), "Pdu".to_string()), ("Response".to_string(), "Pdu".to_string())]
+        );
+        assert_eq!(
+            fields(record(module, "Pdu")),
+            [
+                ("request_id", "int".to_string()),
+                ("error_status", "int".to_string()),
+                ("error_index", "int".to_string()),
+                ("variable_bindings", "VarBind list".to_string()),
+            ]
+        );
+        assert_eq!(
+            fields(record(module, "VarBind")),
+            [("name", "string".to_string()), ("item_2", "VarBindItem2".to_string())]
+        );
+        assert_eq!(
+            variants(module, "VarBindItem2"),
+            [
+                ("Value".to_string(), "int64".to_string()),
+                ("UnSpecified".to_string(), "unit".to_string()),
+                ("NoSuchObject".to_string(), "unit".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generate_extensions_and_components_of() {
+        let types = generate(
+            r#"
+            Header ::= SEQUENCE { id INTEGER, kind Kind }
+            Kind ::= ENUMERATED { request, response, ... }
+
+            Message ::= SEQUENCE {
+                COMPONENTS OF Header,
+                body CHOICE { text UTF8String, binary OCTET STRING },
+                ...,
+                trace SEQUENCE { span INTEGER } OPTIONAL,
+                sent GeneralizedTime
+            }
+            "#,
+        );
+
+        let module = &types.modules[0];
+        assert_eq!(
+            fields(record(module, "Message")),
+            [
+                ("id", "int".to_string()),
+                ("kind", "Kind".to_string()),
+                ("body", "MessageBody".to_string()),
+                ("trace", "MessageTrace option".to_string()),
+                ("sent", "string option".to_string()),
+            ]
+        );
+        assert_eq!(
+            variants(module, "Kind"),
+            [("Request".to_string(), String::new()), ("Response".to_string(), String::new())]
+        );
+        assert_eq!(record(module, "MessageTrace").fields.len(), 1);
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = Asn1Provider::new();
+        let asn1 = r#"
+            Example DEFINITIONS ::= BEGIN
+            IMPORTS Name FROM InformationFramework;
+
+            id-example OBJECT IDENTIFIER ::= { 1 3 6 1 4 1 99999 }
+
+            Record ::= SEQUENCE {
+                subject  Name,
+                owner    Person,
+                payload  ANY,
+                size     INTEGER (0..18446744073709551615),
+                flags    BOOLEAN DEFAULT TRUE
+            }
+            END
+        "#;
+
+        let report = provider.validate_source(asn1, &ProviderParams::default());
+        assert!(report.is_valid());
+        let warnings: Vec<&str> = report.warnings().map(|d| d.code.as_str()).collect();
+        assert_eq!(warnings, ["import-unresolved", "unresolved-reference", "unresolved-reference", "coerced-any"]);
+        let codes: Vec<&str> = report.diagnostics.iter().map(|d| d.code.as_str()).collect();
+        assert!(codes.contains(&"value-skipped"));
+        assert!(codes.contains(&"lossy-conversion"));
+        assert!(codes.contains(&"default-skipped"));
+    }
+}
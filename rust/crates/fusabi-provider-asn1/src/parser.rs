@@ -0,0 +1,1035 @@
+//! ASN.1 module parser
+//!
+//! This is a simplified X.680 parser that handles type assignments with the
+//! common built-in types, tags, constraints, extension markers and
+//! parameterized references. Value assignments, information object classes,
+//! object sets and `MACRO` definitions (as used by SMI MIB modules) are
+//! skipped.
+
+use crate::types::{
+    Alternative, Asn1File, Asn1Type, Assignment, Component, Field, Import, Module, Presence,
+    Range, Skipped, SkippedKind,
+};
+use fusabi_type_providers::{ProviderError, ProviderResult};
+
+/// Parse ASN.1 modules from string content
+pub fn parse_asn1(content: &str) -> ProviderResult<Asn1File> {
+    let mut parser = Parser::new(content);
+    parser.parse_file()
+}
+
+/// Restricted and unrestricted character string types
+const STRING_TYPES: [&str; 14] = [
+    "UTF8String",
+    "PrintableString",
+    "IA5String",
+    "VisibleString",
+    "ISO646String",
+    "NumericString",
+    "TeletexString",
+    "T61String",
+    "VideotexString",
+    "GraphicString",
+    "GeneralString",
+    "UniversalString",
+    "BMPString",
+    "ObjectDescriptor",
+];
+
+/// Time types
+const TIME_TYPES: [&str; 7] = [
+    "UTCTime",
+    "GeneralizedTime",
+    "DATE",
+    "TIME-OF-DAY",
+    "DATE-TIME",
+    "TIME",
+    "DURATION",
+];
+
+/// Simple ASN.1 parser
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    // Symbols
+    LeftBrace,
+    RightBrace,
+    LeftParen,
+    RightParen,
+    LeftBracket,
+    RightBracket,
+    Comma,
+    Semicolon,
+    Assign,
+    Range,
+    Ellipsis,
+    Dot,
+    Symbol(char),
+
+    // Literals
+    Identifier(String),
+    Number(String),
+    StringLiteral(String),
+    /// `'0101'B` or `'0A'H`
+    BitsLiteral(String),
+
+    // End of file
+    Eof,
+}
+
+impl Parser {
+    fn new(content: &str) -> Self {
+        let tokens = tokenize(content);
+        Self { tokens, pos: 0 }
+    }
+
+    fn current(&self) -> &Token {
+        self.tokens.get(self.pos).unwrap_or(&Token::Eof)
+    }
+
+    fn peek(&self, offset: usize) -> &Token {
+        self.tokens.get(self.pos + offset).unwrap_or(&Token::Eof)
+    }
+
+    fn advance(&mut self) {
+        if self.pos < self.tokens.len() {
+            self.pos += 1;
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.current(), Token::Identifier(s) if s == keyword)
+    }
+
+    /// Advance past `keyword` if it is the current token
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.is_keyword(keyword);
+        if found {
+            self.advance();
+        }
+        found
+    }
+
+    fn expect(&mut self, expected: Token) -> ProviderResult<()> {
+        if self.current() == &expected {
+            self.advance();
+            Ok(())
+        } else {
+            Err(ProviderError::ParseError(format!(
+                "Expected {:?}, got {:?}",
+                expected,
+                self.current()
+            )))
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> ProviderResult<()> {
+        if self.eat_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(ProviderError::ParseError(format!(
+                "Expected {}, got {:?}",
+                keyword,
+                self.current()
+            )))
+        }
+    }
+
+    fn expect_identifier(&mut self) -> ProviderResult<String> {
+        match self.current() {
+            Token::Identifier(s) => {
+                let result = s.clone();
+                self.advance();
+                Ok(result)
+            }
+            _ => Err(ProviderError::ParseError(format!(
+                "Expected identifier, got {:?}",
+                self.current()
+            ))),
+        }
+    }
+
+    /// Skip a balanced group starting at the current open token
+    fn skip_group(&mut self) {
+        let mut depth = 0;
+        loop {
+            match self.current() {
+                Token::LeftParen | Token::LeftBrace | Token::LeftBracket => depth += 1,
+                Token::RightParen | Token::RightBrace | Token::RightBracket => depth -= 1,
+                Token::Eof => return,
+                _ => {}
+            }
+            self.advance();
+            if depth <= 0 {
+                return;
+            }
+        }
+    }
+
+    /// Skip a value up to the `,` or closing brace that ends it
+    fn skip_value(&mut self) {
+        loop {
+            match self.current() {
+                Token::LeftParen | Token::LeftBrace | Token::LeftBracket => self.skip_group(),
+                Token::Comma | Token::RightBrace | Token::RightParen | Token::RightBracket | Token::Eof => return,
+                _ => self.advance(),
+            }
+        }
+    }
+
+    /// Whether the tokens at the current position start a module header:
+    /// `Name [{ oid }] DEFINITIONS`
+    fn at_module_header(&self) -> bool {
+        let Token::Identifier(_) = self.current() else {
+            return false;
+        };
+        let mut offset = 1;
+        if self.peek(offset) == &Token::LeftBrace {
+            let mut depth = 0;
+            loop {
+                match self.peek(offset) {
+                    Token::LeftBrace => depth += 1,
+                    Token::RightBrace => depth -= 1,
+                    Token::Eof => return false,
+                    _ => {}
+                }
+                offset += 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+        }
+        matches!(self.peek(offset), Token::Identifier(s) if s == "DEFINITIONS")
+    }
+
+    fn parse_file(&mut self) -> ProviderResult<Asn1File> {
+        let mut file = Asn1File::default();
+
+        while self.current() != &Token::Eof {
+            if self.at_module_header() {
+                let name = self.expect_identifier()?;
+                if self.current() == &Token::LeftBrace {
+                    self.skip_group();
+                }
+                // Tag defaults and extensibility do not affect the types
+                while !matches!(self.current(), Token::Assign | Token::Eof) {
+                    self.advance();
+                }
+                self.expect(Token::Assign)?;
+                self.expect_keyword("BEGIN")?;
+
+                let mut module = Module { name: Some(name), ..Default::default() };
+                self.parse_module_body(&mut module)?;
+                self.expect_keyword("END")?;
+                file.modules.push(module);
+            } else {
+                // Bare assignments without a module header
+                let mut module = Module::default();
+                self.parse_module_body(&mut module)?;
+                if self.current() != &Token::Eof {
+                    return Err(ProviderError::ParseError(format!(
+                        "Unexpected {:?} outside of a module",
+                        self.current()
+                    )));
+                }
+                file.modules.push(module);
+            }
+        }
+
+        Ok(file)
+    }
+
+    /// Parse exports, imports and assignments up to `END`
+    fn parse_module_body(&mut self, module: &mut Module) -> ProviderResult<()> {
+        loop {
+            match self.current() {
+                Token::Eof => return Ok(()),
+                Token::Identifier(s) if s == "END" => return Ok(()),
+                Token::Identifier(s) if s == "EXPORTS" => {
+                    while !matches!(self.current(), Token::Semicolon | Token::Eof) {
+                        self.advance();
+                    }
+                    self.advance();
+                }
+                Token::Identifier(s) if s == "IMPORTS" => {
+                    self.advance();
+                    self.parse_imports(module);
+                }
+                Token::Identifier(_) => self.parse_assignment(module)?,
+                other => {
+                    return Err(ProviderError::ParseError(format!(
+                        "Expected assignment, got {:?}",
+                        other
+                    )))
+                }
+            }
+        }
+    }
+
+    /// Parse `a, b FROM Module { oid } c FROM Other ;`
+    fn parse_imports(&mut self, module: &mut Module) {
+        let mut symbols = Vec::new();
+        loop {
+            match self.current().clone() {
+                Token::Semicolon | Token::Eof => {
+                    self.advance();
+                    return;
+                }
+                Token::Identifier(s) if s == "FROM" => {
+                    self.advance();
+                    let Token::Identifier(from) = self.current().clone() else {
+                        continue;
+                    };
+                    self.advance();
+                    if self.current() == &Token::LeftBrace {
+                        self.skip_group();
+                    }
+                    if self.eat_keyword("WITH") {
+                        self.advance();
+                    }
+                    module.imports.push(Import {
+                        symbols: std::mem::take(&mut symbols),
+                        module: from,
+                    });
+                }
+                Token::Identifier(symbol) => {
+                    symbols.push(symbol);
+                    self.advance();
+                }
+                // Parameterized symbols are imported as `Name{}`
+                Token::LeftBrace => self.skip_group(),
+                _ => self.advance(),
+            }
+        }
+    }
+
+    fn parse_assignment(&mut self, module: &mut Module) -> ProviderResult<()> {
+        let name = self.expect_identifier()?;
+        let is_type_name = name.starts_with(|c: char| c.is_ascii_uppercase());
+
+        if is_type_name && self.current() == &Token::LeftBrace {
+            let params = self.parse_params();
+            self.expect(Token::Assign)?;
+            let ty = self.parse_type()?;
+            module.assignments.push(Assignment { name, params, ty });
+            return Ok(());
+        }
+
+        if self.current() == &Token::Assign {
+            self.advance();
+            if self.eat_keyword("CLASS") {
+                self.skip_group();
+                if self.eat_keyword("WITH") {
+                    self.expect_keyword("SYNTAX")?;
+                    self.skip_group();
+                }
+                module.skipped.push(Skipped { name, kind: SkippedKind::Class });
+            } else if self.eat_keyword("TEXTUAL-CONVENTION") {
+                // SMI textual conventions end with the underlying `SYNTAX`
+                while !matches!(self.current(), Token::Eof) && !self.is_keyword("SYNTAX") {
+                    self.advance();
+                }
+                self.expect_keyword("SYNTAX")?;
+                let ty = self.parse_type()?;
+                module.assignments.push(Assignment { name, params: Vec::new(), ty });
+            } else {
+                let ty = self.parse_type()?;
+                module.assignments.push(Assignment { name, params: Vec::new(), ty });
+            }
+            return Ok(());
+        }
+
+        if self.eat_keyword("MACRO") {
+            self.expect(Token::Assign)?;
+            while !matches!(self.current(), Token::Eof) && !self.is_keyword("END") {
+                self.advance();
+            }
+            self.expect_keyword("END")?;
+            module.skipped.push(Skipped { name, kind: SkippedKind::Macro });
+            return Ok(());
+        }
+
+        // `name Type ::= value`, `Name CLASS ::= { ... }` or an SMI macro
+        // instance such as `sysDescr OBJECT-TYPE ... ::= { system 1 }`
+        loop {
+            match self.current() {
+                Token::Assign => break,
+                Token::LeftParen | Token::LeftBrace | Token::LeftBracket => self.skip_group(),
+                Token::Eof => {
+                    return Err(ProviderError::ParseError(format!(
+                        "Expected ::= in assignment of {}",
+                        name
+                    )))
+                }
+                _ => self.advance(),
+            }
+        }
+        self.advance();
+        if self.current() == &Token::LeftBrace {
+            self.skip_group();
+        } else {
+            self.advance();
+        }
+
+        let kind = if is_type_name { SkippedKind::ObjectSet } else { SkippedKind::Value };
+        module.skipped.push(Skipped { name, kind });
+        Ok(())
+    }
+
+    /// Parse `{ Param, Class: param }`, keeping the name of each parameter
+    fn parse_params(&mut self) -> Vec<String> {
+        let mut params = Vec::new();
+        let mut last = None;
+        self.advance();
+        loop {
+            match self.current().clone() {
+                Token::RightBrace | Token::Eof => break,
+                Token::Comma => params.extend(last.take()),
+                Token::Identifier(name) => last = Some(name),
+                _ => {}
+            }
+            self.advance();
+        }
+        self.advance();
+        params.extend(last);
+        params
+    }
+
+    fn parse_type(&mut self) -> ProviderResult<Asn1Type> {
+        // Tags only affect the encoding
+        while self.current() == &Token::LeftBracket {
+            self.skip_group();
+            if !self.eat_keyword("IMPLICIT") {
+                self.eat_keyword("EXPLICIT");
+            }
+        }
+
+        let Token::Identifier(keyword) = self.current().clone() else {
+            return Err(ProviderError::ParseError(format!(
+                "Expected type, got {:?}",
+                self.current()
+            )));
+        };
+        self.advance();
+
+        let ty = match keyword.as_str() {
+            "BOOLEAN" => Asn1Type::Boolean,
+            "INTEGER" => {
+                if self.current() == &Token::LeftBrace {
+                    self.parse_named_list()?;
+                }
+                let range = self.parse_constraints();
+                return Ok(Asn1Type::Integer(range));
+            }
+            "REAL" => Asn1Type::Real,
+            "NULL" => Asn1Type::Null,
+            "BIT" => {
+                self.expect_keyword("STRING")?;
+                let bits = if self.current() == &Token::LeftBrace {
+                    self.parse_named_list()?
+                } else {
+                    Vec::new()
+                };
+                Asn1Type::BitString(bits)
+            }
+            "OCTET" => {
+                self.expect_keyword("STRING")?;
+                Asn1Type::OctetString
+            }
+            "OBJECT" => {
+                self.expect_keyword("IDENTIFIER")?;
+                Asn1Type::ObjectIdentifier
+            }
+            "RELATIVE-OID" | "OID-IRI" | "RELATIVE-OID-IRI" => Asn1Type::ObjectIdentifier,
+            "CHARACTER" => {
+                self.expect_keyword("STRING")?;
+                Asn1Type::CharacterString
+            }
+            s if STRING_TYPES.contains(&s) => Asn1Type::CharacterString,
+            s if TIME_TYPES.contains(&s) => Asn1Type::Time,
+            "ENUMERATED" => Asn1Type::Enumerated(self.parse_named_list()?),
+            "SEQUENCE" | "SET" => {
+                if self.current() == &Token::LeftBrace {
+                    Asn1Type::Sequence(self.parse_components()?)
+                } else {
+                    // `SEQUENCE SIZE (1..MAX) OF` and `SEQUENCE (SIZE (1)) OF`
+                    self.eat_keyword("SIZE");
+                    while self.current() == &Token::LeftParen {
+                        self.skip_group();
+                    }
+                    self.expect_keyword("OF")?;
+                    // Named elements: `SEQUENCE OF item Type`
+                    if matches!(self.current(), Token::Identifier(s) if s.starts_with(|c: char| c.is_ascii_lowercase()))
+                        && matches!(self.peek(1), Token::Identifier(_) | Token::LeftBracket)
+                    {
+                        self.advance();
+                    }
+                    Asn1Type::SequenceOf(Box::new(self.parse_type()?))
+                }
+            }
+            "CHOICE" => Asn1Type::Choice(self.parse_alternatives()?),
+            "ANY" => {
+                if self.eat_keyword("DEFINED") {
+                    self.expect_keyword("BY")?;
+                    let field = self.expect_identifier()?;
+                    Asn1Type::Open(format!("ANY DEFINED BY {}", field))
+                } else {
+                    Asn1Type::Open("ANY".to_string())
+                }
+            }
+            "EXTERNAL" => Asn1Type::Open(keyword),
+            "EMBEDDED" => {
+                self.expect_keyword("PDV")?;
+                Asn1Type::Open("EMBEDDED PDV".to_string())
+            }
+            "INSTANCE" => {
+                self.expect_keyword("OF")?;
+                let class = self.expect_identifier()?;
+                Asn1Type::Open(format!("INSTANCE OF {}", class))
+            }
+            name if name.starts_with(|c: char| c.is_ascii_uppercase()) => {
+                let mut name = name.to_string();
+                // `Module.Type` or `CLASS.&Field`
+                let mut open = false;
+                while self.current() == &Token::Dot {
+                    self.advance();
+                    let part = self.expect_identifier()?;
+                    open |= part.starts_with('&');
+                    name = if open { format!("{}.{}", name, part) } else { part };
+                }
+                // Actual parameters of a parameterized type
+                if self.current() == &Token::LeftBrace {
+                    self.skip_group();
+                }
+                if open {
+                    Asn1Type::Open(name)
+                } else {
+                    Asn1Type::Reference(name)
+                }
+            }
+            // Selection types: `alternative < Type`
+            name if self.current() == &Token::Symbol('<') => {
+                self.advance();
+                self.parse_type()?;
+                Asn1Type::Open(format!("{} < ...", name))
+            }
+            other => {
+                return Err(ProviderError::ParseError(format!(
+                    "Unknown type {}",
+                    other
+                )))
+            }
+        };
+
+        self.parse_constraints();
+        Ok(ty)
+    }
+
+    /// Skip constraints, returning the bounds of a value range constraint
+    fn parse_constraints(&mut self) -> Option<Range> {
+        let mut range = None;
+        while self.current() == &Token::LeftParen {
+            let bound = |token: &Token| match token {
+                Token::Number(n) => Some(n.parse::<i128>().ok()),
+                Token::Identifier(s) if s == "MIN" || s == "MAX" => Some(None),
+                // Value references such as `(0..max-bindings)`
+                Token::Identifier(_) => Some(None),
+                _ => None,
+            };
+            if let (Some(lower), Token::Range, Some(upper)) =
+                (bound(self.peek(1)), self.peek(2), bound(self.peek(3)))
+            {
+                if matches!(self.peek(4), Token::RightParen | Token::Comma) {
+                    range = Some(Range { lower, upper });
+                }
+            }
+            self.skip_group();
+        }
+        range
+    }
+
+    /// Parse `{ a(0), b, ..., c(3) }`, keeping the names
+    fn parse_named_list(&mut self) -> ProviderResult<Vec<String>> {
+        self.expect(Token::LeftBrace)?;
+        let mut names = Vec::new();
+        loop {
+            match self.current().clone() {
+                Token::RightBrace => break,
+                Token::Identifier(name) => {
+                    names.push(name);
+                    self.advance();
+                    if self.current() == &Token::LeftParen {
+                        self.skip_group();
+                    }
+                }
+                Token::Comma | Token::Ellipsis => self.advance(),
+                // Exception specifications after the extension marker
+                Token::Symbol('!') => {
+                    self.advance();
+                    self.skip_value();
+                }
+                other => {
+                    return Err(ProviderError::ParseError(format!(
+                        "Expected named value, got {:?}",
+                        other
+                    )))
+                }
+            }
+        }
+        self.advance();
+        Ok(names)
+    }
+
+    /// Skip `[[` and `]]` version brackets of extension additions
+    fn skip_version_brackets(&mut self) -> bool {
+        let opening = self.current() == &Token::LeftBracket && self.peek(1) == &Token::LeftBracket;
+        let closing = self.current() == &Token::RightBracket && self.peek(1) == &Token::RightBracket;
+        if opening {
+            self.advance();
+            self.advance();
+            // Version numbers: `[[2: ...`
+            if matches!(self.current(), Token::Number(_)) && self.peek(1) == &Token::Symbol(':') {
+                self.advance();
+                self.advance();
+            }
+        } else if closing {
+            self.advance();
+            self.advance();
+        }
+        opening || closing
+    }
+
+    /// Skip an extension marker with its exception specification; returns
+    /// whether one was found
+    fn skip_extension_marker(&mut self) -> bool {
+        if self.current() != &Token::Ellipsis {
+            return false;
+        }
+        self.advance();
+        if self.current() == &Token::Symbol('!') {
+            self.advance();
+            self.skip_value();
+        }
+        true
+    }
+
+    /// Name of a component or alternative; components written without one
+    /// (allowed before X.680 1994) are named after their type
+    fn parse_element_name(&mut self, index: usize) -> String {
+        match self.current().clone() {
+            Token::Identifier(name) if name.starts_with(|c: char| c.is_ascii_lowercase()) => {
+                self.advance();
+                name
+            }
+            Token::Identifier(name) if !is_builtin(&name) && self.peek(1) != &Token::Dot => {
+                let mut chars = name.chars();
+                let first = chars.next().map(|c| c.to_ascii_lowercase());
+                first.into_iter().chain(chars).collect()
+            }
+            _ => format!("item_{}", index + 1),
+        }
+    }
+
+    fn parse_components(&mut self) -> ProviderResult<Vec<Component>> {
+        self.expect(Token::LeftBrace)?;
+        let mut components = Vec::new();
+        let mut extension = false;
+
+        loop {
+            if self.skip_version_brackets() || self.current() == &Token::Comma {
+                if self.current() == &Token::Comma {
+                    self.advance();
+                }
+                continue;
+            }
+            if self.skip_extension_marker() {
+                extension = !extension;
+                continue;
+            }
+            if self.current() == &Token::RightBrace {
+                self.advance();
+                break;
+            }
+            if self.current() == &Token::Eof {
+                return Err(ProviderError::ParseError("Unterminated SEQUENCE".to_string()));
+            }
+
+            if self.is_keyword("COMPONENTS") && matches!(self.peek(1), Token::Identifier(s) if s == "OF") {
+                self.advance();
+                self.advance();
+                components.push(Component::ComponentsOf(self.parse_type()?));
+                continue;
+            }
+
+            let name = self.parse_element_name(components.len());
+            let ty = self.parse_type()?;
+            let presence = if self.eat_keyword("OPTIONAL") {
+                Presence::Optional
+            } else if self.eat_keyword("DEFAULT") {
+                self.skip_value();
+                Presence::Default
+            } else {
+                Presence::Required
+            };
+            components.push(Component::Field(Field { name, ty, presence, extension }));
+        }
+
+        Ok(components)
+    }
+
+    fn parse_alternatives(&mut self) -> ProviderResult<Vec<Alternative>> {
+        self.expect(Token::LeftBrace)?;
+        let mut alternatives = Vec::new();
+
+        loop {
+            if self.skip_version_brackets() || self.skip_extension_marker() {
+                continue;
+            }
+            match self.current() {
+                Token::Comma => self.advance(),
+                Token::RightBrace => {
+                    self.advance();
+                    break;
+                }
+                Token::Eof => {
+                    return Err(ProviderError::ParseError("Unterminated CHOICE".to_string()))
+                }
+                _ => {
+                    let name = self.parse_element_name(alternatives.len());
+                    let ty = self.parse_type()?;
+                    alternatives.push(Alternative { name, ty });
+                }
+            }
+        }
+
+        Ok(alternatives)
+    }
+}
+
+/// Built-in type keywords, which never name a component
+fn is_builtin(name: &str) -> bool {
+    matches!(
+        name,
+        "BOOLEAN" | "INTEGER" | "REAL" | "NULL" | "BIT" | "OCTET" | "OBJECT" | "RELATIVE-OID"
+            | "CHARACTER" | "ENUMERATED" | "SEQUENCE" | "SET" | "CHOICE" | "ANY" | "EXTERNAL"
+            | "EMBEDDED" | "INSTANCE"
+    ) || STRING_TYPES.contains(&name)
+        || TIME_TYPES.contains(&name)
+}
+
+/// Tokenize ASN.1 source
+fn tokenize(content: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = content.chars().peekable();
+
+    while let Some(&ch) = chars.peek() {
+        match ch {
+            ' ' | '\t' | '\n' | '\r' => {
+                chars.next();
+            }
+            '-' if content_starts_comment(&chars) => {
+                // `--` comments end at the line end or at the next `--`
+                chars.next();
+                chars.next();
+                while let Some(c) = chars.next() {
+                    if c == '\n' || (c == '-' && chars.peek() == Some(&'-')) {
+                        if c == '-' {
+                            chars.next();
+                        }
+                        break;
+                    }
+                }
+            }
+            '/' if chars.clone().nth(1) == Some('*') => {
+                chars.next();
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            '{' => {
+                tokens.push(Token::LeftBrace);
+                chars.next();
+            }
+            '}' => {
+                tokens.push(Token::RightBrace);
+                chars.next();
+            }
+            '(' => {
+                tokens.push(Token::LeftParen);
+                chars.next();
+            }
+            ')' => {
+                tokens.push(Token::RightParen);
+                chars.next();
+            }
+            '[' => {
+                tokens.push(Token::LeftBracket);
+                chars.next();
+            }
+            ']' => {
+                tokens.push(Token::RightBracket);
+                chars.next();
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                chars.next();
+            }
+            ';' => {
+                tokens.push(Token::Semicolon);
+                chars.next();
+            }
+            ':' => {
+                chars.next();
+                let mut lookahead = chars.clone();
+                if lookahead.next() == Some(':') && lookahead.next() == Some('=') {
+                    chars.next();
+                    chars.next();
+                    tokens.push(Token::Assign);
+                } else {
+                    tokens.push(Token::Symbol(':'));
+                }
+            }
+            '.' => {
+                chars.next();
+                if chars.peek() == Some(&'.') {
+                    chars.next();
+                    if chars.peek() == Some(&'.') {
+                        chars.next();
+                        tokens.push(Token::Ellipsis);
+                    } else {
+                        tokens.push(Token::Range);
+                    }
+                } else {
+                    tokens.push(Token::Dot);
+                }
+            }
+            '"' => {
+                chars.next();
+                let mut string = String::new();
+                while let Some(c) = chars.next() {
+                    if c == '"' {
+                        // `""` escapes a quote
+                        if chars.peek() == Some(&'"') {
+                            chars.next();
+                            string.push('"');
+                            continue;
+                        }
+                        break;
+                    }
+                    string.push(c);
+                }
+                tokens.push(Token::StringLiteral(string));
+            }
+            '\'' => {
+                chars.next();
+                let mut bits = String::new();
+                for c in chars.by_ref() {
+                    if c == '\'' {
+                        break;
+                    }
+                    bits.push(c);
+                }
+                // Radix suffix: `B` or `H`
+                if matches!(chars.peek(), Some('B') | Some('H')) {
+                    chars.next();
+                }
+                tokens.push(Token::BitsLiteral(bits));
+            }
+            '0'..='9' | '-' => {
+                let mut number = String::new();
+                number.push(ch);
+                chars.next();
+                while let Some(&c) = chars.peek() {
+                    if c.is_ascii_digit() {
+                        number.push(c);
+                        chars.next();
+                    } else if c == '.' && chars.clone().nth(1).is_some_and(|n| n.is_ascii_digit()) {
+                        // Real values; `..` is a range
+                        number.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                if number == "-" {
+                    tokens.push(Token::Symbol('-'));
+                } else {
+                    tokens.push(Token::Number(number));
+                }
+            }
+            'a'..='z' | 'A'..='Z' | '&' => {
+                let mut ident = String::new();
+                ident.push(ch);
+                chars.next();
+                while let Some(&c) = chars.peek() {
+                    let hyphen = c == '-'
+                        && chars.clone().nth(1).is_some_and(|n| n.is_ascii_alphanumeric());
+                    if c.is_ascii_alphanumeric() || c == '_' || hyphen {
+                        ident.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Identifier(ident));
+            }
+            other => {
+                tokens.push(Token::Symbol(other));
+                chars.next();
+            }
+        }
+    }
+
+    tokens.push(Token::Eof);
+    tokens
+}
+
+fn content_starts_comment(chars: &std::iter::Peekable<std::str::Chars>) -> bool {
+    let mut lookahead = chars.clone();
+    lookahead.next() == Some('-') && lookahead.next() == Some('-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_module() {
+        let asn1 = r#"
+            PKIX1Explicit88 { iso(1) identified-organization(3) dod(6) internet(1)
+              security(5) mechanisms(5) pkix(7) id-mod(0) id-pkix1-explicit(18) }
+            DEFINITIONS EXPLICIT TAGS ::=
+            BEGIN
+            IMPORTS Name FROM InformationFramework { joint-iso-itu-t ds(5) 1 };
+
+            id-pkix OBJECT IDENTIFIER ::= { iso(1) identified-organization(3) 1 }
+
+            Version ::= INTEGER { v1(0), v2(1), v3(2) }
+
+            Validity ::= SEQUENCE {
+                notBefore      Time,
+                notAfter       Time  }
+
+            Extension ::= SEQUENCE {
+                extnID      OBJECT IDENTIFIER,
+                critical    BOOLEAN DEFAULT FALSE,
+                extnValue   OCTET STRING -- contains the DER encoding -- }
+
+            Time ::= CHOICE { utcTime UTCTime, generalTime GeneralizedTime }
+            END
+        "#;
+
+        let file = parse_asn1(asn1).unwrap();
+        let module = &file.modules[0];
+        assert_eq!(module.name.as_deref(), Some("PKIX1Explicit88"));
+        assert_eq!(module.imports[0], Import { symbols: vec!["Name".to_string()], module: "InformationFramework".to_string() });
+        assert_eq!(module.skipped, [Skipped { name: "id-pkix".to_string(), kind: SkippedKind::Value }]);
+        assert_eq!(module.assignment("Version").unwrap().ty, Asn1Type::Integer(None));
+
+        let Asn1Type::Sequence(components) = &module.assignment("Extension").unwrap().ty else {
+            panic!("Expected SEQUENCE");
+        };
+        let Component::Field(critical) = &components[1] else {
+            panic!("Expected field");
+        };
+        assert_eq!(critical.name, "critical");
+        assert_eq!(critical.presence, Presence::Default);
+        assert_eq!(components.len(), 3);
+    }
+
+    #[test]
+    fn test_parse_tags_constraints_and_extensions() {
+        let asn1 = r#"
+            Counter64 ::= [APPLICATION 6] IMPLICIT INTEGER (0..18446744073709551615)
+            Names ::= SEQUENCE SIZE (1..MAX) OF name UTF8String (SIZE (1..64))
+            Message ::= SEQUENCE {
+                id      [0] INTEGER (-1..10),
+                kind    ENUMERATED { request(0), response(1), ... },
+                ...,
+                [[ 2: trace [1] IMPLICIT OCTET STRING OPTIONAL ]],
+                ...
+            }
+            Algorithm ::= SEQUENCE {
+                algorithm  ALGORITHM.&id,
+                parameters ANY DEFINED BY algorithm OPTIONAL
+            }
+        "#;
+
+        let file = parse_asn1(asn1).unwrap();
+        let module = &file.modules[0];
+        assert!(module.name.is_none());
+        assert_eq!(
+            module.assignment("Counter64").unwrap().ty,
+            Asn1Type::Integer(Some(Range { lower: Some(0), upper: Some(18446744073709551615) }))
+        );
+        assert_eq!(
+            module.assignment("Names").unwrap().ty,
+            Asn1Type::SequenceOf(Box::new(Asn1Type::CharacterString))
+        );
+
+        let Asn1Type::Sequence(components) = &module.assignment("Message").unwrap().ty else {
+            panic!("Expected SEQUENCE");
+        };
+        let fields: Vec<(&str, bool)> = components
+            .iter()
+            .filter_map(|c| match c {
+                Component::Field(f) => Some((f.name.as_str(), f.extension)),
+                Component::ComponentsOf(_) => None,
+            })
+            .collect();
+        assert_eq!(fields, [("id", false), ("kind", false), ("trace", true)]);
+
+        let Asn1Type::Sequence(components) = &module.assignment("Algorithm").unwrap().ty else {
+            panic!("Expected SEQUENCE");
+        };
+        assert!(matches!(&components[0], Component::Field(f) if f.ty == Asn1Type::Open("ALGORITHM.&id".to_string())));
+    }
+
+    #[test]
+    fn test_parse_smi_macros() {
+        let asn1 = r#"
+            SNMPv2-SMI DEFINITIONS ::= BEGIN
+            OBJECT-TYPE MACRO ::=
+            BEGIN
+                TYPE NOTATION ::= "SYNTAX" Syntax
+                VALUE NOTATION ::= value(VALUE ObjectName)
+            END
+
+            sysDescr OBJECT-TYPE
+                SYNTAX      DisplayString (SIZE (0..255))
+                MAX-ACCESS  read-only
+                DESCRIPTION "A textual description of the entity."
+                ::= { system 1 }
+
+            IpAddress ::= [APPLICATION 0] IMPLICIT OCTET STRING (SIZE (4))
+
+            TruthValue ::= TEXTUAL-CONVENTION
+                STATUS       current
+                DESCRIPTION  "Represents a boolean value."
+                SYNTAX       INTEGER { true(1), false(2) }
+            END
+        "#;
+
+        let file = parse_asn1(asn1).unwrap();
+        let module = &file.modules[0];
+        let skipped: Vec<SkippedKind> = module.skipped.iter().map(|s| s.kind).collect();
+        assert_eq!(skipped, [SkippedKind::Macro, SkippedKind::Value]);
+        assert_eq!(module.assignment("IpAddress").unwrap().ty, Asn1Type::OctetString);
+        assert_eq!(module.assignment("TruthValue").unwrap().ty, Asn1Type::Integer(None));
+    }
+
+    #[test]
+    fn test_invalid_asn1() {
+        assert!(parse_asn1("Foo ::= SEQUENCE { a INTEGER").is_err());
+        assert!(parse_asn1("M DEFINITIONS ::= BEGIN Foo ::= BOOLEAN").is_err());
+    }
+}
@@ -0,0 +1,147 @@
+//! ASN.1 AST type definitions
+
+/// Represents a parsed ASN.1 source with one or more modules
+#[derive(Debug, Clone, Default)]
+pub struct Asn1File {
+    pub modules: Vec<Module>,
+}
+
+/// An ASN.1 module: `Name DEFINITIONS ::= BEGIN ... END`
+#[derive(Debug, Clone, Default)]
+pub struct Module {
+    /// Module name; `None` for bare assignments without a module header
+    pub name: Option<String>,
+    /// Modules named in `IMPORTS ... FROM`
+    pub imports: Vec<Import>,
+    /// Type assignments in order of definition
+    pub assignments: Vec<Assignment>,
+    /// Assignments that do not define types
+    pub skipped: Vec<Skipped>,
+}
+
+/// Symbols imported from another module
+#[derive(Debug, Clone, PartialEq)]
+pub struct Import {
+    pub symbols: Vec<String>,
+    pub module: String,
+}
+
+/// A type assignment: `Name ::= Type` or `Name { Param } ::= Type`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assignment {
+    pub name: String,
+    /// Dummy parameters of a parameterized type
+    pub params: Vec<String>,
+    pub ty: Asn1Type,
+}
+
+/// An assignment that generates no types
+#[derive(Debug, Clone, PartialEq)]
+pub struct Skipped {
+    pub name: String,
+    pub kind: SkippedKind,
+}
+
+/// Kind of skipped assignment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkippedKind {
+    /// `name Type ::= value`, including SMI `OBJECT-TYPE` definitions
+    Value,
+    /// `NAME ::= CLASS { ... }`
+    Class,
+    /// `Name CLASS ::= { ... }`
+    ObjectSet,
+    /// `NAME MACRO ::= BEGIN ... END`
+    Macro,
+}
+
+/// An ASN.1 type with tags and constraints removed
+#[derive(Debug, Clone, PartialEq)]
+pub enum Asn1Type {
+    Boolean,
+    /// `INTEGER`, with the bounds of a value range constraint if present
+    Integer(Option<Range>),
+    Real,
+    Null,
+    /// `BIT STRING`, with named bits if present
+    BitString(Vec<String>),
+    OctetString,
+    /// `OBJECT IDENTIFIER` or `RELATIVE-OID`
+    ObjectIdentifier,
+    /// Restricted character string types such as `UTF8String`
+    CharacterString,
+    /// `UTCTime`, `GeneralizedTime` and the X.680 time types
+    Time,
+    /// `ENUMERATED { a(0), b(1), ... }`
+    Enumerated(Vec<String>),
+    /// `SEQUENCE { ... }` or `SET { ... }`
+    Sequence(Vec<Component>),
+    /// `SEQUENCE OF Type` or `SET OF Type`
+    SequenceOf(Box<Asn1Type>),
+    /// `CHOICE { ... }`
+    Choice(Vec<Alternative>),
+    /// Reference to another type, possibly in another module
+    Reference(String),
+    /// `ANY`, open types and other types without structure; holds the
+    /// original notation
+    Open(String),
+}
+
+/// Bounds of `(lower..upper)`; `None` is `MIN` or `MAX`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Range {
+    pub lower: Option<i128>,
+    pub upper: Option<i128>,
+}
+
+/// A component of a `SEQUENCE` or `SET`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Component {
+    Field(Field),
+    /// `COMPONENTS OF Type`
+    ComponentsOf(Asn1Type),
+}
+
+/// A named component
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub name: String,
+    pub ty: Asn1Type,
+    pub presence: Presence,
+    /// Declared after the `...` extension marker
+    pub extension: bool,
+}
+
+/// Presence of a component
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Presence {
+    Required,
+    Optional,
+    /// `DEFAULT value`
+    Default,
+}
+
+/// An alternative of a `CHOICE`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alternative {
+    pub name: String,
+    pub ty: Asn1Type,
+}
+
+impl Module {
+    /// The type assignment with the given name
+    pub fn assignment(&self, name: &str) -> Option<&Assignment> {
+        self.assignments.iter().find(|a| a.name == name)
+    }
+}
+
+impl Asn1Type {
+    /// Types that are generated as records or unions rather than inlined
+    pub fn is_structured(&self) -> bool {
+        match self {
+            Asn1Type::Sequence(_) | Asn1Type::Choice(_) | Asn1Type::Enumerated(_) => true,
+            Asn1Type::BitString(bits) => !bits.is_empty(),
+            _ => false,
+        }
+    }
+}
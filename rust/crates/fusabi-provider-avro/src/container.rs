@@ -0,0 +1,139 @@
+//! Schema extraction from Avro object container files
+//!
+//! A container file starts with the magic bytes `Obj\x01` followed by a
+//! metadata map whose `avro.schema` entry holds the writer schema as JSON.
+//! Only the header is read; data blocks are never touched.
+
+use fusabi_provider_common::StructuredError;
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use std::io::Read;
+
+/// Magic bytes at the start of every container file
+pub const MAGIC: &[u8; 4] = b"Obj\x01";
+
+/// Metadata key holding the writer schema
+const SCHEMA_KEY: &str = "avro.schema";
+
+/// Read the writer schema from a container file header
+pub fn read_container_schema(mut reader: impl Read) -> ProviderResult<String> {
+    let mut magic = [0u8; 4];
+    reader
+        .read_exact(&mut magic)
+        .map_err(|e| StructuredError::io("Failed to read Avro container header").with_source(e))?;
+    if &magic != MAGIC {
+        return Err(ProviderError::ParseError(
+            "Not an Avro object container file".to_string(),
+        ));
+    }
+
+    loop {
+        let mut count = read_long(&mut reader)?;
+        if count == 0 {
+            break;
+        }
+        if count < 0 {
+            // Negative counts are followed by the block size in bytes
+            count = -count;
+            read_long(&mut reader)?;
+        }
+
+        for _ in 0..count {
+            let key = read_bytes(&mut reader)?;
+            let value = read_bytes(&mut reader)?;
+            if key == SCHEMA_KEY.as_bytes() {
+                return String::from_utf8(value).map_err(|_| {
+                    ProviderError::ParseError("Container schema is not valid UTF-8".to_string())
+                });
+            }
+        }
+    }
+
+    Err(ProviderError::ParseError(
+        "Container header has no avro.schema entry".to_string(),
+    ))
+}
+
+/// Read a zig-zag encoded variable-length long
+fn read_long(reader: &mut impl Read) -> ProviderResult<i64> {
+    let mut value: u64 = 0;
+    for shift in (0..64).step_by(7) {
+        let mut byte = [0u8; 1];
+        reader
+            .read_exact(&mut byte)
+            .map_err(|_| truncated())?;
+        value |= u64::from(byte[0] & 0x7f) << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok((value >> 1) as i64 ^ -((value & 1) as i64));
+        }
+    }
+    Err(ProviderError::ParseError("Invalid varint in container header".to_string()))
+}
+
+fn read_bytes(reader: &mut impl Read) -> ProviderResult<Vec<u8>> {
+    let len = read_long(reader)?;
+    let len = u64::try_from(len).map_err(|_| truncated())?;
+
+    let mut buf = Vec::new();
+    reader
+        .take(len)
+        .read_to_end(&mut buf)
+        .map_err(|e| StructuredError::io("Failed to read Avro container header").with_source(e))?;
+    if buf.len() as u64 != len {
+        return Err(truncated());
+    }
+    Ok(buf)
+}
+
+fn truncated() -> ProviderError {
+    ProviderError::ParseError("Truncated Avro container header".to_string())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    fn write_long(out: &mut Vec<u8>, value: i64) {
+        let mut n = ((value << 1) ^ (value >> 63)) as u64;
+        while n >= 0x80 {
+            out.push((n as u8 & 0x7f) | 0x80);
+            n >>= 7;
+        }
+        out.push(n as u8);
+    }
+
+    fn write_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+        write_long(out, bytes.len() as i64);
+        out.extend_from_slice(bytes);
+    }
+
+    /// Build a container header with the given metadata entries
+    pub(crate) fn container(entries: &[(&str, &str)]) -> Vec<u8> {
+        let mut out = MAGIC.to_vec();
+        write_long(&mut out, entries.len() as i64);
+        for (key, value) in entries {
+            write_bytes(&mut out, key.as_bytes());
+            write_bytes(&mut out, value.as_bytes());
+        }
+        write_long(&mut out, 0);
+        out.extend_from_slice(&[0xab; 16]);
+        out
+    }
+
+    #[test]
+    fn test_read_schema() {
+        let long_schema = format!("\"{}\"", "x".repeat(300));
+        let bytes = container(&[("avro.codec", "null"), ("avro.schema", &long_schema)]);
+        assert_eq!(read_container_schema(bytes.as_slice()).unwrap(), long_schema);
+    }
+
+    #[test]
+    fn test_invalid_headers() {
+        assert!(read_container_schema(&b"{\"type\": \"int\"}"[..]).is_err());
+
+        let bytes = container(&[("avro.codec", "null")]);
+        assert!(read_container_schema(bytes.as_slice()).is_err());
+
+        let bytes = container(&[("avro.schema", "\"int\"")]);
+        assert!(read_container_schema(&bytes[..10]).is_err());
+    }
+}
@@ -0,0 +1,426 @@
+//! Avro Type Provider
+//!
+//! Generates Fusabi types from Apache Avro schemas: `.avsc` JSON files,
+//! inline schema JSON, or the writer schema embedded in the header of an
+//! `.avro` object container file.
+//!
+//! # Type Mapping
+//!
+//! | Avro                                   | Fusabi                      |
+//! |----------------------------------------|-----------------------------|
+//! | `null`                                 | `unit`                      |
+//! | `boolean`                              | `bool`                      |
+//! | `int`                                  | `int`                       |
+//! | `long`                                 | `int64`                     |
+//! | `float`, `double`                      | `float`                     |
+//! | `bytes`, `fixed`                       | `bytes`                     |
+//! | `string`                               | `string`                    |
+//! | `record`                               | record                      |
+//! | `enum`                                 | union of simple cases       |
+//! | `array<T>`                             | `T list`                    |
+//! | `map<T>`                               | `Map<string, T>`            |
+//! | `["null", T]`                          | `T option`                  |
+//! | other unions                           | union named `<Record><Field>` |
+//! | `decimal`                              | `float` (lossy)             |
+//! | `uuid`                                 | `string`                    |
+//! | `date`, `time-millis`                  | `int` (days, milliseconds)  |
+//! | `time-micros`, `timestamp-*`           | `int64` (epoch units)       |
+//! | `duration`                             | `bytes`                     |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_avro::AvroProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = AvroProvider::new();
+//! let schema = provider.resolve_schema("user.avsc", &ProviderParams::default())?;
+//! let types = provider.generate_types(&schema, "Events")?;
+//! ```
+
+mod container;
+mod parser;
+mod types;
+
+pub use container::read_container_schema;
+pub use types::{AvroType, Enum, Fixed, LogicalType, Record, RecordField};
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, SourceOptions,
+    SourceValidator, StructuredError, TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, DuDef, VariantDef, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use std::collections::HashMap;
+use std::io::Read;
+
+/// Avro type provider
+pub struct AvroProvider {
+    generator: TypeGenerator,
+}
+
+impl AvroProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    /// Parse an Avro schema from JSON
+    fn parse_schema(&self, json: &str) -> ProviderResult<AvroType> {
+        parser::parse_avro_schema(json)
+    }
+
+    /// Read the schema embedded in a local container file
+    fn read_container(&self, source: &str, params: &ProviderParams) -> ProviderResult<String> {
+        let path = source.strip_prefix("file://").unwrap_or(source);
+        let options = SourceOptions::from_params(params)?;
+        let file = std::fs::File::open(path)
+            .map_err(|e| StructuredError::io(format!("Failed to open {}", path)).with_source(e))?;
+        read_container_schema(file.take(options.max_source_bytes))
+    }
+
+    /// Generate types for every named type in the schema
+    fn generate_from_schema(&self, schema: &AvroType, namespace: &str) -> GeneratedTypes {
+        let mut result = GeneratedTypes::new();
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+        let named = named_types(schema);
+
+        // Children are visited before parents, so dependencies come first
+        let mut seen = Vec::new();
+        schema.walk(&mut |avro_type| {
+            let Some(name) = avro_type.type_name() else {
+                return;
+            };
+            if seen.contains(&name) {
+                return;
+            }
+            seen.push(name);
+
+            match avro_type {
+                AvroType::Record(record) => {
+                    let record_name = self.generator.naming.apply(&record.name);
+                    let mut fields = Vec::new();
+                    for field in &record.fields {
+                        let context = format!("{}{}", record_name, self.generator.naming.apply(&field.name));
+                        let shape = self.type_shape(&field.field_type, &context, &named, &mut module.types);
+                        fields.push((field.name.clone(), shape.into()));
+                    }
+                    module.types.push(TypeDefinition::Record(RecordDef {
+                        name: record_name,
+                        fields,
+                    }));
+                }
+                AvroType::Enum(enum_def) => {
+                    module.types.push(TypeDefinition::Du(DuDef {
+                        name: self.generator.naming.apply(&enum_def.name),
+                        variants: enum_def
+                            .symbols
+                            .iter()
+                            .map(|s| VariantDef::new_simple(self.generator.naming.apply(s)))
+                            .collect(),
+                    }));
+                }
+                // Fixed types are plain bytes and need no definition
+                _ => {}
+            }
+        });
+
+        if !module.types.is_empty() {
+            result.modules.push(module);
+        }
+        result
+    }
+
+    /// Convert an Avro type in field position
+    ///
+    /// Unions that are not simply nullable become a union type named after
+    /// `context`, which is pushed onto `types`.
+    fn type_shape(
+        &self,
+        avro_type: &AvroType,
+        context: &str,
+        named: &HashMap<&str, &AvroType>,
+        types: &mut Vec<TypeDefinition>,
+    ) -> TypeShape {
+        let name = match avro_type {
+            AvroType::Null => "unit".to_string(),
+            AvroType::Boolean => "bool".to_string(),
+            AvroType::Int => "int".to_string(),
+            AvroType::Long => "int64".to_string(),
+            AvroType::Float | AvroType::Double => "float".to_string(),
+            AvroType::Bytes | AvroType::Fixed(_) => "bytes".to_string(),
+            AvroType::String => "string".to_string(),
+            AvroType::Record(record) => self.generator.naming.apply(&record.name),
+            AvroType::Enum(enum_def) => self.generator.naming.apply(&enum_def.name),
+            AvroType::Named(name) => match named.get(name.as_str()) {
+                Some(AvroType::Fixed(_)) => "bytes".to_string(),
+                _ => self.generator.naming.apply(name),
+            },
+            AvroType::Array(items) => {
+                return self.type_shape(items, context, named, types).list();
+            }
+            AvroType::Map(values) => {
+                return TypeShape::map(TypeShape::named("string"), self.type_shape(values, context, named, types));
+            }
+            AvroType::Logical(logical, base) => match logical {
+                LogicalType::Decimal { .. } => "float".to_string(),
+                LogicalType::Uuid => "string".to_string(),
+                LogicalType::Date | LogicalType::TimeMillis => "int".to_string(),
+                LogicalType::TimeMicros
+                | LogicalType::TimestampMillis
+                | LogicalType::TimestampMicros
+                | LogicalType::LocalTimestampMillis
+                | LogicalType::LocalTimestampMicros => "int64".to_string(),
+                LogicalType::Duration => return self.type_shape(base, context, named, types),
+            },
+            AvroType::Union(branches) => {
+                let non_null: Vec<&AvroType> =
+                    branches.iter().filter(|b| **b != AvroType::Null).collect();
+                let nullable = non_null.len() < branches.len();
+
+                let inner = match non_null.as_slice() {
+                    [] => TypeShape::named("unit"),
+                    [single] => self.type_shape(single, context, named, types),
+                    _ => {
+                        let variants = non_null
+                            .iter()
+                            .map(|branch| {
+                                let payload = self.type_shape(branch, context, named, types);
+                                VariantDef::new(self.branch_label(branch), vec![payload.into()])
+                            })
+                            .collect();
+                        types.push(TypeDefinition::Du(DuDef {
+                            name: context.to_string(),
+                            variants,
+                        }));
+                        TypeShape::named(context)
+                    }
+                };
+
+                return inner.option_unless(!nullable || non_null.is_empty());
+            }
+        };
+
+        TypeShape::named(name)
+    }
+
+    /// Case name for a union branch
+    fn branch_label(&self, avro_type: &AvroType) -> String {
+        let label = match avro_type {
+            AvroType::Null => "null",
+            AvroType::Boolean => "boolean",
+            AvroType::Int => "int",
+            AvroType::Long => "long",
+            AvroType::Float => "float",
+            AvroType::Double => "double",
+            AvroType::Bytes => "bytes",
+            AvroType::String => "string",
+            AvroType::Array(_) => "array",
+            AvroType::Map(_) => "map",
+            AvroType::Union(_) => "union",
+            AvroType::Named(name) => name,
+            AvroType::Logical(_, base) => return self.branch_label(base),
+            named => named.type_name().unwrap_or("value"),
+        };
+        self.generator.naming.apply(label)
+    }
+}
+
+impl Default for AvroProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypeProvider for AvroProvider {
+    fn name(&self) -> &str {
+        "AvroProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let trimmed = source.trim_start();
+        let json = if trimmed.starts_with('{') || trimmed.starts_with('[') || trimmed.starts_with('"') {
+            source.to_string()
+        } else if source.ends_with(".avro") {
+            self.read_container(source, params)?
+        } else {
+            read_source(source, params)?
+        };
+
+        // Parse to validate before handing the schema on
+        self.parse_schema(&json)?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        match schema {
+            Schema::Custom(json) => {
+                let avro_type = self.parse_schema(json)?;
+                Ok(self.generate_from_schema(&avro_type, namespace))
+            }
+            _ => Err(ProviderError::ParseError("Expected Avro schema".to_string())),
+        }
+    }
+}
+
+impl SourceValidator for AvroProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected Avro schema".to_string()));
+        };
+
+        let avro_type = self.parse_schema(json)?;
+        let mut diagnostics = Vec::new();
+
+        avro_type.walk(&mut |t| {
+            let AvroType::Record(record) = t else {
+                return;
+            };
+            for field in &record.fields {
+                field.field_type.walk(&mut |inner| {
+                    if let AvroType::Logical(LogicalType::Decimal { precision, scale }, _) = inner {
+                        diagnostics.push(
+                            Diagnostic::info(
+                                "lossy-conversion",
+                                format!("decimal({}, {}) is typed as `float`", precision, scale),
+                            )
+                            .at(format!("{}.{}", record.name, field.name)),
+                        );
+                    }
+                });
+            }
+        });
+
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for AvroProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+/// Named types defined anywhere in the schema, by short name
+fn named_types(schema: &AvroType) -> HashMap<&str, &AvroType> {
+    let mut named = HashMap::new();
+    schema.walk(&mut |t| {
+        if let Some(name) = t.type_name() {
+            named.entry(name).or_insert(t);
+        }
+    });
+    named
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    const SCHEMA: &str = r#"{
+        "type": "record",
+        "name": "com.example.Order",
+        "fields": [
+            {"name": "id", "type": {"type": "fixed", "name": "Id", "size": 16}},
+            {"name": "customer", "type": {
+                "type": "record",
+                "name": "Customer",
+                "fields": [{"name": "name", "type": "string"}]
+            }},
+            {"name": "status", "type": {"type": "enum", "name": "Status", "symbols": ["NEW", "SHIPPED"]}},
+            {"name": "note", "type": ["null", "string"], "default": null},
+            {"name": "payment", "type": ["Customer", "string", "long"]},
+            {"name": "tags", "type": {"type": "map", "values": {"type": "array", "items": "string"}}},
+            {"name": "placed_at", "type": {"type": "long", "logicalType": "timestamp-millis"}},
+            {"name": "total", "type": {"type": "bytes", "logicalType": "decimal", "precision": 9, "scale": 2}},
+            {"name": "ref", "type": ["null", "Id"]}
+        ]
+    }"#;
+
+    fn field_types(types: &GeneratedTypes, name: &str) -> Vec<(String, String)> {
+        types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(
+                    r.fields
+                        .iter()
+                        .map(|(n, t)| (n.clone(), t.to_string()))
+                        .collect(),
+                ),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_generate_types() {
+        let provider = AvroProvider::new();
+        let schema = provider.resolve_schema(SCHEMA, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Shop").unwrap();
+
+        let names: Vec<&str> = types.modules[0]
+            .types
+            .iter()
+            .map(|t| match t {
+                TypeDefinition::Record(r) => r.name.as_str(),
+                TypeDefinition::Du(d) => d.name.as_str(),
+            })
+            .collect();
+        assert_eq!(names, ["Customer", "Status", "OrderPayment", "Order"]);
+
+        let fields = field_types(&types, "Order");
+        let field = |name: &str| fields.iter().find(|(n, _)| n == name).unwrap().1.as_str();
+        assert_eq!(field("id"), "bytes");
+        assert_eq!(field("customer"), "Customer");
+        assert_eq!(field("note"), "string option");
+        assert_eq!(field("payment"), "OrderPayment");
+        assert_eq!(field("tags"), "Map<string, string list>");
+        assert_eq!(field("placed_at"), "int64");
+        assert_eq!(field("total"), "float");
+        assert_eq!(field("ref"), "bytes option");
+
+        let Some(TypeDefinition::Du(payment)) = types.modules[0].types.get(2) else {
+            panic!("Expected union");
+        };
+        let cases: Vec<&str> = payment.variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(cases, ["Customer", "String", "Long"]);
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/avro.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_container_file() {
+        let path = std::env::temp_dir().join("fusabi-avro-container-test.avro");
+        std::fs::write(&path, container::tests::container(&[("avro.schema", SCHEMA)])).unwrap();
+
+        let provider = AvroProvider::new();
+        let schema = provider
+            .resolve_schema(path.to_str().unwrap(), &ProviderParams::default())
+            .unwrap();
+        let types = provider.generate_types(&schema, "Shop").unwrap();
+        assert_eq!(types.modules[0].types.len(), 4);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let report = AvroProvider::new().validate_source(SCHEMA, &ProviderParams::default());
+        assert!(report.is_valid());
+
+        let lossy = report.diagnostics.iter().find(|d| d.code == "lossy-conversion").unwrap();
+        assert_eq!(lossy.location.as_deref(), Some("Order.total"));
+    }
+}
@@ -0,0 +1,241 @@
+//! Avro schema parser (`.avsc` JSON)
+
+use crate::types::*;
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde_json::Value;
+
+/// Parse an Avro schema from JSON text
+pub fn parse_avro_schema(json: &str) -> ProviderResult<AvroType> {
+    let value: Value = serde_json::from_str(json)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid Avro schema JSON: {}", e)))?;
+    parse_type(&value, None)
+}
+
+/// Parse a schema value, inheriting the enclosing namespace
+fn parse_type(value: &Value, namespace: Option<&str>) -> ProviderResult<AvroType> {
+    match value {
+        Value::String(name) => Ok(primitive(name).unwrap_or_else(|| AvroType::Named(short_name(name)))),
+        Value::Array(branches) => {
+            let branches = branches
+                .iter()
+                .map(|b| parse_type(b, namespace))
+                .collect::<ProviderResult<Vec<_>>>()?;
+            Ok(AvroType::Union(branches))
+        }
+        Value::Object(object) => {
+            let type_value = object
+                .get("type")
+                .ok_or_else(|| parse_error("Avro schema object is missing `type`"))?;
+
+            let base = match type_value.as_str() {
+                Some("record") | Some("error") => parse_record(value, namespace)?,
+                Some("enum") => parse_enum(value, namespace)?,
+                Some("fixed") => parse_fixed(value, namespace)?,
+                Some("array") => {
+                    let items = object
+                        .get("items")
+                        .ok_or_else(|| parse_error("Avro array is missing `items`"))?;
+                    AvroType::Array(Box::new(parse_type(items, namespace)?))
+                }
+                Some("map") => {
+                    let values = object
+                        .get("values")
+                        .ok_or_else(|| parse_error("Avro map is missing `values`"))?;
+                    AvroType::Map(Box::new(parse_type(values, namespace)?))
+                }
+                // `{"type": "string"}`, `{"type": ["null", "int"]}` or a named reference
+                _ => parse_type(type_value, namespace)?,
+            };
+
+            // Unknown logical types fall back to the underlying type, per the spec
+            let logical = object
+                .get("logicalType")
+                .and_then(Value::as_str)
+                .and_then(|name| {
+                    let precision = object.get("precision").and_then(Value::as_u64).unwrap_or(0);
+                    let scale = object.get("scale").and_then(Value::as_u64).unwrap_or(0);
+                    LogicalType::parse(name, precision as u32, scale as u32)
+                });
+
+            Ok(match logical {
+                Some(logical) => AvroType::Logical(logical, Box::new(base)),
+                None => base,
+            })
+        }
+        _ => Err(parse_error("Avro schema must be a string, object or array")),
+    }
+}
+
+fn parse_record(value: &Value, namespace: Option<&str>) -> ProviderResult<AvroType> {
+    let (name, namespace) = parse_name(value, namespace)?;
+    let fields = value
+        .get("fields")
+        .and_then(Value::as_array)
+        .ok_or_else(|| parse_error(&format!("Avro record `{}` is missing `fields`", name)))?;
+
+    let fields = fields
+        .iter()
+        .map(|field| {
+            let field_name = field
+                .get("name")
+                .and_then(Value::as_str)
+                .ok_or_else(|| parse_error(&format!("Field in record `{}` is missing `name`", name)))?;
+            let field_type = field.get("type").ok_or_else(|| {
+                parse_error(&format!("Field `{}.{}` is missing `type`", name, field_name))
+            })?;
+
+            Ok(RecordField {
+                name: field_name.to_string(),
+                field_type: parse_type(field_type, namespace.as_deref())?,
+                has_default: field.get("default").is_some(),
+            })
+        })
+        .collect::<ProviderResult<Vec<_>>>()?;
+
+    Ok(AvroType::Record(Record {
+        name,
+        namespace,
+        fields,
+    }))
+}
+
+fn parse_enum(value: &Value, namespace: Option<&str>) -> ProviderResult<AvroType> {
+    let (name, namespace) = parse_name(value, namespace)?;
+    let symbols = value
+        .get("symbols")
+        .and_then(Value::as_array)
+        .ok_or_else(|| parse_error(&format!("Avro enum `{}` is missing `symbols`", name)))?
+        .iter()
+        .filter_map(Value::as_str)
+        .map(String::from)
+        .collect();
+
+    Ok(AvroType::Enum(Enum {
+        name,
+        namespace,
+        symbols,
+    }))
+}
+
+fn parse_fixed(value: &Value, namespace: Option<&str>) -> ProviderResult<AvroType> {
+    let (name, namespace) = parse_name(value, namespace)?;
+    let size = value
+        .get("size")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| parse_error(&format!("Avro fixed `{}` is missing `size`", name)))?;
+
+    Ok(AvroType::Fixed(Fixed {
+        name,
+        namespace,
+        size: size as usize,
+    }))
+}
+
+/// Short name and effective namespace of a named type
+fn parse_name(value: &Value, namespace: Option<&str>) -> ProviderResult<(String, Option<String>)> {
+    let full_name = value
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or_else(|| parse_error("Avro named type is missing `name`"))?;
+
+    // A dotted name carries its own namespace
+    if let Some((ns, name)) = full_name.rsplit_once('.') {
+        return Ok((name.to_string(), Some(ns.to_string())));
+    }
+
+    let namespace = value
+        .get("namespace")
+        .and_then(Value::as_str)
+        .or(namespace)
+        .filter(|ns| !ns.is_empty())
+        .map(String::from);
+
+    Ok((full_name.to_string(), namespace))
+}
+
+fn primitive(name: &str) -> Option<AvroType> {
+    match name {
+        "null" => Some(AvroType::Null),
+        "boolean" => Some(AvroType::Boolean),
+        "int" => Some(AvroType::Int),
+        "long" => Some(AvroType::Long),
+        "float" => Some(AvroType::Float),
+        "double" => Some(AvroType::Double),
+        "bytes" => Some(AvroType::Bytes),
+        "string" => Some(AvroType::String),
+        _ => None,
+    }
+}
+
+fn short_name(name: &str) -> String {
+    name.rsplit('.').next().unwrap_or(name).to_string()
+}
+
+fn parse_error(message: &str) -> ProviderError {
+    ProviderError::ParseError(message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_record() {
+        let schema = r#"{
+            "type": "record",
+            "name": "User",
+            "namespace": "com.example",
+            "fields": [
+                {"name": "id", "type": "long"},
+                {"name": "email", "type": ["null", "string"], "default": null},
+                {"name": "status", "type": {"type": "enum", "name": "Status", "symbols": ["ACTIVE", "BANNED"]}},
+                {"name": "friend", "type": ["null", "com.example.User"]}
+            ]
+        }"#;
+
+        let AvroType::Record(record) = parse_avro_schema(schema).unwrap() else {
+            panic!("Expected record");
+        };
+        assert_eq!(record.name, "User");
+        assert_eq!(record.namespace.as_deref(), Some("com.example"));
+        assert_eq!(record.fields[0].field_type, AvroType::Long);
+        assert!(record.fields[1].has_default);
+        assert_eq!(
+            record.fields[1].field_type,
+            AvroType::Union(vec![AvroType::Null, AvroType::String])
+        );
+
+        let AvroType::Enum(status) = &record.fields[2].field_type else {
+            panic!("Expected enum");
+        };
+        assert_eq!(status.namespace.as_deref(), Some("com.example"));
+        assert_eq!(status.symbols, vec!["ACTIVE", "BANNED"]);
+
+        assert_eq!(
+            record.fields[3].field_type,
+            AvroType::Union(vec![AvroType::Null, AvroType::Named("User".to_string())])
+        );
+    }
+
+    #[test]
+    fn test_parse_logical_types() {
+        let decimal = r#"{"type": "bytes", "logicalType": "decimal", "precision": 10, "scale": 2}"#;
+        assert_eq!(
+            parse_avro_schema(decimal).unwrap(),
+            AvroType::Logical(
+                LogicalType::Decimal { precision: 10, scale: 2 },
+                Box::new(AvroType::Bytes)
+            )
+        );
+
+        let unknown = r#"{"type": "string", "logicalType": "iso-country"}"#;
+        assert_eq!(parse_avro_schema(unknown).unwrap(), AvroType::String);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_avro_schema(r#"{"type": "record", "name": "R"}"#).is_err());
+        assert!(parse_avro_schema(r#"{"type": "array"}"#).is_err());
+        assert!(parse_avro_schema("42").is_err());
+    }
+}
@@ -0,0 +1,142 @@
+//! Avro schema type definitions
+
+/// An Avro schema
+#[derive(Debug, Clone, PartialEq)]
+pub enum AvroType {
+    // Primitive types
+    Null,
+    Boolean,
+    Int,
+    Long,
+    Float,
+    Double,
+    Bytes,
+    String,
+
+    // Named types, defined inline
+    Record(Record),
+    Enum(Enum),
+    Fixed(Fixed),
+
+    // Complex types
+    Array(Box<AvroType>),
+    Map(Box<AvroType>),
+    Union(Vec<AvroType>),
+
+    /// Reference to a named type defined elsewhere in the schema
+    Named(String),
+
+    /// A logical type annotating its underlying type
+    Logical(LogicalType, Box<AvroType>),
+}
+
+/// Avro record definition
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    /// Short name
+    pub name: String,
+    /// Namespace, if any
+    pub namespace: Option<String>,
+    /// Fields, in declaration order
+    pub fields: Vec<RecordField>,
+}
+
+/// Avro record field
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordField {
+    /// Field name
+    pub name: String,
+    /// Field type
+    pub field_type: AvroType,
+    /// Whether the field declares a default value
+    pub has_default: bool,
+}
+
+/// Avro enum definition
+#[derive(Debug, Clone, PartialEq)]
+pub struct Enum {
+    /// Short name
+    pub name: String,
+    /// Namespace, if any
+    pub namespace: Option<String>,
+    /// Enum symbols
+    pub symbols: Vec<String>,
+}
+
+/// Avro fixed definition
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fixed {
+    /// Short name
+    pub name: String,
+    /// Namespace, if any
+    pub namespace: Option<String>,
+    /// Size in bytes
+    pub size: usize,
+}
+
+/// Logical types from the Avro specification
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogicalType {
+    Decimal { precision: u32, scale: u32 },
+    Uuid,
+    Date,
+    TimeMillis,
+    TimeMicros,
+    TimestampMillis,
+    TimestampMicros,
+    LocalTimestampMillis,
+    LocalTimestampMicros,
+    Duration,
+}
+
+impl LogicalType {
+    /// Parse a `logicalType` name, with decimal precision and scale
+    pub fn parse(name: &str, precision: u32, scale: u32) -> Option<Self> {
+        match name {
+            "decimal" => Some(Self::Decimal { precision, scale }),
+            "uuid" => Some(Self::Uuid),
+            "date" => Some(Self::Date),
+            "time-millis" => Some(Self::TimeMillis),
+            "time-micros" => Some(Self::TimeMicros),
+            "timestamp-millis" => Some(Self::TimestampMillis),
+            "timestamp-micros" => Some(Self::TimestampMicros),
+            "local-timestamp-millis" => Some(Self::LocalTimestampMillis),
+            "local-timestamp-micros" => Some(Self::LocalTimestampMicros),
+            "duration" => Some(Self::Duration),
+            _ => None,
+        }
+    }
+}
+
+impl AvroType {
+    /// Name of a named type (record, enum or fixed)
+    pub fn type_name(&self) -> Option<&str> {
+        match self {
+            AvroType::Record(r) => Some(&r.name),
+            AvroType::Enum(e) => Some(&e.name),
+            AvroType::Fixed(f) => Some(&f.name),
+            _ => None,
+        }
+    }
+
+    /// Visit this type and every type nested in it, depth first
+    pub fn walk<'a>(&'a self, visit: &mut impl FnMut(&'a AvroType)) {
+        match self {
+            AvroType::Record(record) => {
+                for field in &record.fields {
+                    field.field_type.walk(visit);
+                }
+            }
+            AvroType::Array(inner) | AvroType::Map(inner) | AvroType::Logical(_, inner) => {
+                inner.walk(visit)
+            }
+            AvroType::Union(branches) => {
+                for branch in branches {
+                    branch.walk(visit);
+                }
+            }
+            _ => {}
+        }
+        visit(self);
+    }
+}
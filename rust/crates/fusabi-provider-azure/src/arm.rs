@@ -0,0 +1,157 @@
+//! ARM deployment template schemas
+//!
+//! `schema.management.azure.com` publishes one JSON Schema per resource
+//! provider and API version (`2023-01-01/Microsoft.Storage.json`), with
+//! the resources under `resourceDefinitions` and the shapes they use under
+//! `definitions`. Every property also accepts a template expression
+//! (`"[parameters('name')]"`) through a `oneOf`; those alternatives are
+//! dropped before conversion so properties keep their own type.
+
+use std::collections::BTreeSet;
+
+use fusabi_jsonschema_core::Converter;
+use fusabi_type_providers::{GeneratedModule, TypeDefinition};
+use serde_json::{Map, Value};
+
+/// Whether a document is an ARM template schema for a resource provider
+pub fn is_arm_schema(document: &Value) -> bool {
+    document.get("resourceDefinitions").is_some_and(Value::is_object)
+}
+
+/// The `type` and `apiVersion` a resource definition declares
+pub fn resource_identity(definition: &Value) -> (Option<&str>, Option<&str>) {
+    let constant = |property: &str| {
+        let schema = definition.pointer(&format!("/properties/{}", property))?;
+        schema
+            .get("enum")
+            .and_then(|values| values.get(0))
+            .or_else(|| schema.get("const"))
+            .and_then(Value::as_str)
+    };
+    (constant("type"), constant("apiVersion"))
+}
+
+/// Replace `oneOf: [T, expression]` with `T`, recursively
+pub fn strip_expressions(schema: &mut Value) {
+    match schema {
+        Value::Object(object) => {
+            for value in object.values_mut() {
+                strip_expressions(value);
+            }
+            for keyword in ["oneOf", "anyOf"] {
+                let Some(Value::Array(alternatives)) = object.get_mut(keyword) else {
+                    continue;
+                };
+                let before = alternatives.len();
+                alternatives.retain(|alternative| !is_expression(alternative));
+                if alternatives.len() == 1 && before > 1 {
+                    let Some(Value::Array(mut alternatives)) = object.remove(keyword) else {
+                        unreachable!("checked above");
+                    };
+                    if let Value::Object(single) = alternatives.remove(0) {
+                        for (key, value) in single {
+                            object.entry(key).or_insert(value);
+                        }
+                    }
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_expressions),
+        _ => {}
+    }
+}
+
+fn is_expression(schema: &Value) -> bool {
+    schema
+        .get("$ref")
+        .and_then(Value::as_str)
+        .is_some_and(|reference| reference.ends_with("/definitions/expression"))
+}
+
+/// Names of the `definitions` reachable from the kept resource definitions
+pub fn reachable_definitions(document: &Value) -> BTreeSet<String> {
+    let definitions = document.get("definitions").and_then(Value::as_object);
+    let mut reached = BTreeSet::new();
+    let mut pending = Vec::new();
+    if let Some(resources) = document.get("resourceDefinitions") {
+        collect_refs(resources, &mut pending);
+    }
+    while let Some(name) = pending.pop() {
+        if !reached.insert(name.clone()) {
+            continue;
+        }
+        if let Some(definition) = definitions.and_then(|d| d.get(&name)) {
+            collect_refs(definition, &mut pending);
+        }
+    }
+    reached
+}
+
+fn collect_refs(schema: &Value, refs: &mut Vec<String>) {
+    match schema {
+        Value::Object(object) => {
+            if let Some(name) = object
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|reference| reference.strip_prefix("#/definitions/"))
+            {
+                refs.push(name.to_string());
+            }
+            object.values().for_each(|value| collect_refs(value, refs));
+        }
+        Value::Array(items) => items.iter().for_each(|item| collect_refs(item, refs)),
+        _ => {}
+    }
+}
+
+/// Convert a stripped and filtered schema into the records of one module
+pub fn convert(document: &Value, path: Vec<String>, record_name: impl Fn(&str) -> String) -> GeneratedModule {
+    let converter = Converter::new();
+    let empty = Map::new();
+    let mut module = GeneratedModule::new(path);
+
+    let reachable = reachable_definitions(document);
+    let definitions = document.get("definitions").and_then(Value::as_object).unwrap_or(&empty);
+    module.types = definitions
+        .iter()
+        .filter(|(name, _)| reachable.contains(*name))
+        .filter_map(|(name, schema)| converter.definition(name, schema))
+        .collect();
+
+    let resources = document.get("resourceDefinitions").and_then(Value::as_object).unwrap_or(&empty);
+    module.types.extend(resources.iter().filter_map(|(key, definition)| {
+        let name = resource_identity(definition).0.map_or_else(|| key.clone(), &record_name);
+        converter.definition(&name, definition).map(|definition| match definition {
+            TypeDefinition::Record(mut record) => {
+                record.name = name;
+                TypeDefinition::Record(record)
+            }
+            other => other,
+        })
+    }));
+    module
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_expressions() {
+        let mut schema: Value = serde_json::from_str(
+            r##"{"properties": {"sku": {
+                "oneOf": [
+                  {"$ref": "#/definitions/Sku"},
+                  {"$ref": "https://schema.management.azure.com/schemas/common/definitions.json#/definitions/expression"}
+                ],
+                "description": "The SKU"
+            }}}"##,
+        )
+        .unwrap();
+        strip_expressions(&mut schema);
+        assert_eq!(
+            schema["properties"]["sku"],
+            serde_json::json!({"$ref": "#/definitions/Sku", "description": "The SKU"})
+        );
+    }
+}
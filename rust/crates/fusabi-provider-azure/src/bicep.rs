@@ -0,0 +1,216 @@
+//! Bicep type files
+//!
+//! The Bicep compiler ships Azure resource types as `types.json` files,
+//! one per resource provider and API version, listing a graph of types
+//! that refer to each other by position (`{"$ref": "#/12"}`). An
+//! `index.json` maps every `<type>@<apiVersion>` to its entry in one of
+//! those files.
+
+use std::collections::BTreeMap;
+
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Property flag: required on create
+pub const FLAG_REQUIRED: u32 = 1;
+/// Property flag: only returned by the service
+pub const FLAG_READ_ONLY: u32 = 2;
+
+/// A reference to another type in the same file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RawRef", into = "RawRef")]
+pub struct TypeRef(pub usize);
+
+#[derive(Serialize, Deserialize)]
+struct RawRef {
+    #[serde(rename = "$ref")]
+    reference: String,
+}
+
+impl TryFrom<RawRef> for TypeRef {
+    type Error = String;
+
+    fn try_from(raw: RawRef) -> Result<Self, String> {
+        raw.reference
+            .strip_prefix("#/")
+            .and_then(|index| index.parse().ok())
+            .map(TypeRef)
+            .ok_or_else(|| format!("Unsupported type reference `{}`", raw.reference))
+    }
+}
+
+impl From<TypeRef> for RawRef {
+    fn from(reference: TypeRef) -> Self {
+        RawRef {
+            reference: format!("#/{}", reference.0),
+        }
+    }
+}
+
+/// A Bicep type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "$type")]
+pub enum BicepType {
+    StringType,
+    IntegerType,
+    BooleanType,
+    AnyType,
+    NullType,
+    StringLiteralType {
+        value: String,
+    },
+    ArrayType {
+        #[serde(rename = "itemType")]
+        item_type: TypeRef,
+    },
+    ObjectType {
+        name: String,
+        #[serde(default)]
+        properties: BTreeMap<String, ObjectProperty>,
+        #[serde(default, rename = "additionalProperties")]
+        additional_properties: Option<TypeRef>,
+    },
+    DiscriminatedObjectType {
+        name: String,
+        discriminator: String,
+        #[serde(default, rename = "baseProperties")]
+        base_properties: BTreeMap<String, ObjectProperty>,
+        elements: BTreeMap<String, TypeRef>,
+    },
+    UnionType {
+        elements: Vec<TypeRef>,
+    },
+    ResourceType {
+        name: String,
+        body: TypeRef,
+    },
+    /// Resource functions and other types that describe no data
+    #[serde(other)]
+    Unsupported,
+}
+
+/// A property of an object type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObjectProperty {
+    #[serde(rename = "type")]
+    pub type_ref: TypeRef,
+    #[serde(default)]
+    pub flags: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl ObjectProperty {
+    pub fn is_required(&self) -> bool {
+        self.flags & FLAG_REQUIRED != 0 && self.flags & FLAG_READ_ONLY == 0
+    }
+}
+
+/// Parse a `types.json` file
+pub fn parse_types(content: &str) -> ProviderResult<Vec<BicepType>> {
+    serde_json::from_str(content)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid Bicep types file: {}", e)))
+}
+
+/// Whether a document is a `types.json` file
+pub fn is_types_file(document: &Value) -> bool {
+    document
+        .as_array()
+        .is_some_and(|types| types.iter().all(|t| t.get("$type").is_some()))
+}
+
+/// Whether a document is an `index.json` file
+pub fn is_index(document: &Value) -> bool {
+    document.get("resources").is_some_and(Value::is_object)
+}
+
+/// An `index.json` entry: the file holding a resource type and its position
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexEntry {
+    pub resource_type: String,
+    pub file: String,
+    pub index: usize,
+}
+
+/// Parse the resource entries of an `index.json` file
+pub fn parse_index(document: &Value) -> ProviderResult<Vec<IndexEntry>> {
+    let resources = document
+        .get("resources")
+        .and_then(Value::as_object)
+        .ok_or_else(|| ProviderError::ParseError("Bicep index has no `resources`".to_string()))?;
+    resources
+        .iter()
+        .map(|(resource_type, entry)| {
+            let reference = entry.get("$ref").and_then(Value::as_str).unwrap_or_default();
+            let (file, index) = reference
+                .split_once("#/")
+                .and_then(|(file, index)| Some((file, index.parse().ok()?)))
+                .ok_or_else(|| {
+                    ProviderError::ParseError(format!(
+                        "Index entry `{}` has an invalid reference `{}`",
+                        resource_type, reference
+                    ))
+                })?;
+            Ok(IndexEntry {
+                resource_type: resource_type.clone(),
+                file: file.to_string(),
+                index,
+            })
+        })
+        .collect()
+}
+
+/// `Microsoft.Storage/storageAccounts@2023-01-01` →
+/// (`Microsoft.Storage/storageAccounts`, `2023-01-01`)
+pub fn split_api_version(name: &str) -> (&str, &str) {
+    name.split_once('@').unwrap_or((name, ""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_types() {
+        let types = parse_types(
+            r##"[
+              {"$type": "StringType", "minLength": 3},
+              {"$type": "ObjectType", "name": "Sku", "properties": {"name": {"type": {"$ref": "#/0"}, "flags": 1}}},
+              {"$type": "ResourceFunctionType", "name": "listKeys"},
+              {"$type": "ResourceType", "name": "Microsoft.Storage/storageAccounts@2023-01-01", "scopeType": 8, "body": {"$ref": "#/1"}}
+            ]"##,
+        )
+        .unwrap();
+        assert_eq!(types[0], BicepType::StringType);
+        assert_eq!(types[2], BicepType::Unsupported);
+        let BicepType::ObjectType { properties, .. } = &types[1] else {
+            panic!("Expected an object type");
+        };
+        assert_eq!(properties["name"].type_ref, TypeRef(0));
+        assert!(properties["name"].is_required());
+
+        assert!(parse_types(r#"[{"$type": "ArrayType", "itemType": {"$ref": "other.json#/1"}}]"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_index() {
+        let index: Value = serde_json::from_str(
+            r#"{"resources": {"Microsoft.Storage/storageAccounts@2023-01-01": {"$ref": "storage/microsoft.storage/2023-01-01/types.json#/42"}}, "resourceFunctions": {}}"#,
+        )
+        .unwrap();
+        assert!(is_index(&index));
+        assert_eq!(
+            parse_index(&index).unwrap(),
+            [IndexEntry {
+                resource_type: "Microsoft.Storage/storageAccounts@2023-01-01".to_string(),
+                file: "storage/microsoft.storage/2023-01-01/types.json".to_string(),
+                index: 42,
+            }]
+        );
+        assert_eq!(
+            split_api_version("Microsoft.Storage/storageAccounts@2023-01-01"),
+            ("Microsoft.Storage/storageAccounts", "2023-01-01")
+        );
+    }
+}
@@ -0,0 +1,831 @@
+//! Azure Resource Type Provider
+//!
+//! Generates Fusabi records for Azure resource properties, so ARM
+//! templates and Bicep parameter files can be generated from Fusabi with
+//! every property checked.
+//!
+//! # Sources
+//!
+//! - A Bicep `types.json` file, or the `index.json` of a Bicep types
+//!   directory, which loads the `types.json` files of the selected
+//!   resources from beside it; see [`bicep`]
+//! - An ARM deployment template schema such as
+//!   `schemas/2023-01-01/Microsoft.Storage.json`; see [`arm`]
+//!
+//! # Generated Types
+//!
+//! Each resource provider gets a module, `<Namespace>.<Provider>`
+//! (`Microsoft.Storage` goes in `<Namespace>.Storage`). Resources become
+//! records named after their type below the provider
+//! (`Microsoft.Storage/storageAccounts/blobServices` →
+//! `StorageAccountsBlobServices`), alongside records for the object types
+//! they use. Discriminated object types become unions with a case per
+//! discriminator value.
+//!
+//! Properties that are not required on create, including read-only ones,
+//! are `T option`. Unions of string literals stay literal unions
+//! (`"Hot" | "Cool"`).
+//!
+//! # Params
+//!
+//! | Param         | Meaning                                                        |
+//! |---------------|----------------------------------------------------------------|
+//! | `resources`   | Comma-separated resource types to keep; `Microsoft.Storage/*` matches by prefix |
+//! | `api_version` | API version to use; defaults to the latest each resource has   |
+//!
+//! Preview versions sort after the stable version of the same date.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_azure::AzureProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = AzureProvider::new();
+//! let params = ProviderParams::default().with("resources", "Microsoft.Storage/storageAccounts");
+//! let schema = provider.resolve_schema("bicep-types-az/generated/index.json", &params)?;
+//! let types = provider.generate_types(&schema, "Azure")?;
+//! ```
+
+pub mod arm;
+pub mod bicep;
+
+pub use bicep::{BicepType, ObjectProperty, TypeRef};
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceValidator, TypeShape, TypedParams,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, DuDef, VariantDef, TypeExpr, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The selected resources, stored in [`Schema::Custom`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum Resolved {
+    Bicep { files: Vec<TypeFile> },
+    Arm { schema: Value },
+}
+
+/// A `types.json` file and the positions of its selected resources
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TypeFile {
+    types: Vec<BicepType>,
+    resources: Vec<usize>,
+}
+
+/// Azure resource type provider
+pub struct AzureProvider {
+    generator: TypeGenerator,
+}
+
+impl AzureProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<Resolved> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected Azure resource types: {}", e)))
+    }
+
+    /// Resolve a Bicep `index.json`, reading the files it points to
+    fn resolve_index(
+        &self,
+        document: &Value,
+        location: &str,
+        params: &ProviderParams,
+        typed: &TypedParams,
+    ) -> ProviderResult<Resolved> {
+        let entries = bicep::parse_index(document)?;
+        let names: Vec<&str> = entries.iter().map(|e| e.resource_type.as_str()).collect();
+        let selected = select(&names, typed)?;
+
+        let mut by_file: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+        for entry in entries.iter().filter(|e| selected.contains(&e.resource_type.as_str())) {
+            by_file.entry(entry.file.as_str()).or_default().push(entry.index);
+        }
+        let files = by_file
+            .into_iter()
+            .map(|(file, resources)| {
+                let types = bicep::parse_types(&read_source(&sibling(location, file), params)?)?;
+                Ok(TypeFile { types, resources })
+            })
+            .collect::<ProviderResult<Vec<_>>>()?;
+        Ok(Resolved::Bicep { files })
+    }
+
+    /// Resolve a single `types.json` file
+    fn resolve_types(&self, content: &str, typed: &TypedParams) -> ProviderResult<Resolved> {
+        let types = bicep::parse_types(content)?;
+        let resources: Vec<(usize, &str)> = types
+            .iter()
+            .enumerate()
+            .filter_map(|(index, t)| match t {
+                BicepType::ResourceType { name, .. } => Some((index, name.as_str())),
+                _ => None,
+            })
+            .collect();
+        let names: Vec<&str> = resources.iter().map(|(_, name)| *name).collect();
+        let selected = select(&names, typed)?;
+        let resources = resources
+            .iter()
+            .filter(|(_, name)| selected.contains(name))
+            .map(|(index, _)| *index)
+            .collect();
+        Ok(Resolved::Bicep {
+            files: vec![TypeFile { types, resources }],
+        })
+    }
+
+    /// Resolve an ARM template schema, keeping the selected resources
+    fn resolve_arm(&self, mut document: Value, typed: &TypedParams) -> ProviderResult<Resolved> {
+        let patterns = typed.list("resources").unwrap_or_default();
+        let api_version = typed.str("api_version");
+        if let Some(Value::Object(resources)) = document.get_mut("resourceDefinitions") {
+            if let Some(api_version) = api_version {
+                if let Some(found) = resources
+                    .values()
+                    .filter_map(|definition| arm::resource_identity(definition).1)
+                    .find(|version| *version != api_version)
+                {
+                    return Err(ProviderError::InvalidSource(format!(
+                        "The schema is for API version {}, not {}",
+                        found, api_version
+                    )));
+                }
+            }
+            if !patterns.is_empty() {
+                resources.retain(|_, definition| {
+                    arm::resource_identity(definition)
+                        .0
+                        .is_some_and(|resource_type| matches_any(&patterns, resource_type))
+                });
+                if resources.is_empty() {
+                    return Err(ProviderError::InvalidSource(format!(
+                        "No resource type matches '{}'",
+                        patterns.join(",")
+                    )));
+                }
+            }
+        }
+        arm::strip_expressions(&mut document);
+        Ok(Resolved::Arm { schema: document })
+    }
+
+    fn generate(&self, resolved: &Resolved, namespace: &str) -> (Vec<GeneratedModule>, Vec<Diagnostic>) {
+        match resolved {
+            Resolved::Bicep { files } => {
+                let mut generation = BicepGeneration {
+                    generator: &self.generator,
+                    modules: BTreeMap::new(),
+                    diagnostics: Vec::new(),
+                };
+                if files.iter().all(|file| file.resources.is_empty()) {
+                    generation
+                        .diagnostics
+                        .push(Diagnostic::warning("no-resources", "No resource types were selected"));
+                }
+                for file in files {
+                    generation.file(file);
+                }
+                let modules = generation
+                    .modules
+                    .into_iter()
+                    .map(|(path, (types, _))| {
+                        let mut module_path = vec![namespace.to_string()];
+                        module_path.extend(path);
+                        let mut module = GeneratedModule::new(module_path);
+                        module.types = types;
+                        module
+                    })
+                    .collect();
+                (modules, generation.diagnostics)
+            }
+            Resolved::Arm { schema } => {
+                let provider = schema
+                    .get("resourceDefinitions")
+                    .and_then(Value::as_object)
+                    .and_then(|resources| resources.values().find_map(|d| arm::resource_identity(d).0))
+                    .map(|resource_type| resource_type.split('/').next().unwrap_or(resource_type))
+                    .or_else(|| schema.get("title").and_then(Value::as_str))
+                    .unwrap_or_default();
+                let mut path = vec![namespace.to_string()];
+                path.extend(provider_path(provider));
+                let module = arm::convert(schema, path, |resource_type| self.resource_name(resource_type));
+                let diagnostics = if module.types.is_empty() {
+                    vec![Diagnostic::warning("no-resources", "The schema defines no resource types")]
+                } else {
+                    Vec::new()
+                };
+                (vec![module], diagnostics)
+            }
+        }
+    }
+
+    fn resource_name(&self, resource_type: &str) -> String {
+        resource_name(&self.generator, resource_type)
+    }
+}
+
+/// Record name of a resource type: its segments below the provider,
+/// PascalCased and joined
+fn resource_name(generator: &TypeGenerator, resource_type: &str) -> String {
+    let (resource_type, _) = bicep::split_api_version(resource_type);
+    resource_type
+        .split('/')
+        .skip(1)
+        .map(|segment| generator.naming.apply(segment))
+        .collect()
+}
+
+/// `Microsoft.Storage` → `["Storage"]`; other publishers keep their name
+fn provider_path(provider: &str) -> Vec<String> {
+    let provider = provider.strip_prefix("Microsoft.").unwrap_or(provider);
+    provider.split('.').filter(|s| !s.is_empty()).map(str::to_string).collect()
+}
+
+/// State while generating from Bicep types: each module's definitions and
+/// the names they use, and diagnostics
+struct BicepGeneration<'a> {
+    generator: &'a TypeGenerator,
+    modules: BTreeMap<Vec<String>, (Vec<TypeDefinition>, BTreeSet<String>)>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// Generation state for one file: the module its types go in, the names
+/// given to its types and which of them have been generated
+struct FileScope<'f> {
+    types: &'f [BicepType],
+    path: Vec<String>,
+    names: HashMap<usize, String>,
+    defined: HashSet<usize>,
+}
+
+impl BicepGeneration<'_> {
+    fn file(&mut self, file: &TypeFile) {
+        let mut scope: Option<FileScope> = None;
+        for &index in &file.resources {
+            let Some(BicepType::ResourceType { name, body }) = file.types.get(index) else {
+                self.diagnostics.push(Diagnostic::warning(
+                    "type-unresolved",
+                    format!("Type #/{} is not a resource type", index),
+                ));
+                continue;
+            };
+            let (resource_type, _) = bicep::split_api_version(name);
+            // A file holds one provider's types, so they share a module
+            let scope = scope.get_or_insert_with(|| FileScope {
+                types: &file.types,
+                path: provider_path(resource_type.split('/').next().unwrap_or_default()),
+                names: HashMap::new(),
+                defined: HashSet::new(),
+            });
+            let record = self.unique_name(&scope.path, &resource_name(self.generator, resource_type), name);
+            scope.names.insert(body.0, record);
+            self.shape(scope, *body, name);
+        }
+    }
+
+    /// Claim `name` in a module, numbering it when another type has it
+    fn unique_name(&mut self, path: &[String], name: &str, location: &str) -> String {
+        let (_, taken) = self.modules.entry(path.to_vec()).or_default();
+        let mut unique = name.to_string();
+        let mut suffix = 2;
+        while taken.contains(&unique) {
+            unique = format!("{}{}", name, suffix);
+            suffix += 1;
+        }
+        if unique != name {
+            self.diagnostics.push(
+                Diagnostic::info("type-renamed", format!("`{}` is already defined; generated as `{}`", name, unique))
+                    .at(location.to_string()),
+            );
+        }
+        taken.insert(unique.clone());
+        unique
+    }
+
+    fn push(&mut self, path: &[String], definition: TypeDefinition) {
+        self.modules.entry(path.to_vec()).or_default().0.push(definition);
+    }
+
+    fn shape(&mut self, scope: &mut FileScope, type_ref: TypeRef, location: &str) -> TypeShape {
+        let types = scope.types;
+        let Some(bicep_type) = types.get(type_ref.0) else {
+            self.diagnostics.push(
+                Diagnostic::warning("type-unresolved", format!("Type #/{} does not exist", type_ref.0))
+                    .at(location.to_string()),
+            );
+            return named("any");
+        };
+
+        match bicep_type {
+            BicepType::StringType => named("string"),
+            BicepType::IntegerType => named("int"),
+            BicepType::BooleanType => named("bool"),
+            BicepType::NullType => named("unit"),
+            BicepType::AnyType | BicepType::ResourceType { .. } | BicepType::Unsupported => named("any"),
+            BicepType::StringLiteralType { value } => TypeShape::Literal(value.clone()),
+            BicepType::ArrayType { item_type } => TypeShape::List(Box::new(self.shape(scope, *item_type, location))),
+            BicepType::UnionType { elements } => {
+                let nullable = elements
+                    .iter()
+                    .any(|element| matches!(types.get(element.0), Some(BicepType::NullType)));
+                let mut shapes: Vec<TypeShape> = elements
+                    .iter()
+                    .filter(|element| !matches!(types.get(element.0), Some(BicepType::NullType)))
+                    .map(|element| self.shape(scope, *element, location))
+                    .collect();
+                let shape = if shapes.len() == 1 { shapes.remove(0) } else { TypeShape::Union(shapes) };
+                if nullable {
+                    TypeShape::Option(Box::new(shape))
+                } else {
+                    shape
+                }
+            }
+            BicepType::ObjectType { properties, additional_properties, .. } if properties.is_empty() => {
+                let value = match additional_properties {
+                    Some(value) => self.shape(scope, *value, location),
+                    None => named("any"),
+                };
+                TypeShape::Map(Box::new(named("string")), Box::new(value))
+            }
+            BicepType::ObjectType { name, properties, .. } => {
+                if scope.defined.contains(&type_ref.0) {
+                    return named(&scope.names[&type_ref.0]);
+                }
+                let record = self.claim(scope, type_ref, name, location);
+                let fields = self.fields(scope, properties, None, &record);
+                self.push(&scope.path, TypeDefinition::Record(RecordDef { name: record.clone(), fields }));
+                named(&record)
+            }
+            BicepType::DiscriminatedObjectType { name, base_properties, elements, .. } => {
+                if scope.defined.contains(&type_ref.0) {
+                    return named(&scope.names[&type_ref.0]);
+                }
+                let union = self.claim(scope, type_ref, name, location);
+                let mut variants = Vec::new();
+                for (value, element) in elements {
+                    let record = match types.get(element.0) {
+                        Some(BicepType::ObjectType { name, properties, .. }) => {
+                            let record = self.claim(scope, *element, name, location);
+                            let fields = self.fields(scope, properties, Some(base_properties), &record);
+                            self.push(&scope.path, TypeDefinition::Record(RecordDef { name: record.clone(), fields }));
+                            TypeShape::named(record).into()
+                        }
+                        _ => self.shape(scope, *element, location).into(),
+                    };
+                    variants.push(VariantDef::new(self.generator.naming.apply(value), vec![record]));
+                }
+                self.push(&scope.path, TypeDefinition::Du(DuDef { name: union.clone(), variants }));
+                named(&union)
+            }
+        }
+    }
+
+    /// Name a type, unless it was named up front, and mark it generated so
+    /// recursive references stop at the name
+    fn claim(&mut self, scope: &mut FileScope, type_ref: TypeRef, name: &str, location: &str) -> String {
+        let record = match scope.names.get(&type_ref.0) {
+            Some(record) => record.clone(),
+            None => {
+                let name = self.generator.naming.apply(name.rsplit('/').next().unwrap_or(name));
+                self.unique_name(&scope.path, &name, location)
+            }
+        };
+        scope.names.insert(type_ref.0, record.clone());
+        scope.defined.insert(type_ref.0);
+        record
+    }
+
+    fn fields(
+        &mut self,
+        scope: &mut FileScope,
+        properties: &BTreeMap<String, ObjectProperty>,
+        base: Option<&BTreeMap<String, ObjectProperty>>,
+        record: &str,
+    ) -> Vec<(String, TypeExpr)> {
+        base.into_iter()
+            .flatten()
+            .filter(|(name, _)| !properties.contains_key(*name))
+            .chain(properties)
+            .map(|(name, property)| {
+                let shape = self.shape(scope, property.type_ref, &format!("{}.{}", record, name));
+                let shape = if property.is_required() || shape.is_option() {
+                    shape
+                } else {
+                    TypeShape::Option(Box::new(shape))
+                };
+                (name.clone(), shape.into())
+            })
+            .collect()
+    }
+}
+
+fn named(name: &str) -> TypeShape {
+    TypeShape::Named(name.to_string())
+}
+
+/// Pick the resources the `resources` and `api_version` params select
+/// from `<type>@<apiVersion>` names
+fn select<'n>(names: &[&'n str], typed: &TypedParams) -> ProviderResult<Vec<&'n str>> {
+    let patterns = typed.list("resources").unwrap_or_default();
+    let api_version = typed.str("api_version");
+
+    let mut latest: BTreeMap<&str, &str> = BTreeMap::new();
+    for name in names {
+        let (resource_type, version) = bicep::split_api_version(name);
+        if !patterns.is_empty() && !matches_any(&patterns, resource_type) {
+            continue;
+        }
+        if api_version.is_some_and(|wanted| wanted != version) {
+            continue;
+        }
+        let current = latest.entry(resource_type).or_insert(name);
+        if version_key(version) > version_key(bicep::split_api_version(current).1) {
+            *current = name;
+        }
+    }
+
+    if latest.is_empty() && (!patterns.is_empty() || api_version.is_some()) {
+        return Err(ProviderError::InvalidSource(format!(
+            "No resource type matches '{}'{}",
+            patterns.join(","),
+            api_version.map(|v| format!(" at API version {}", v)).unwrap_or_default()
+        )));
+    }
+    Ok(latest.into_values().collect())
+}
+
+/// Sort key for API versions: by date, then `2023-01-01` before
+/// `2023-01-01-preview`
+fn version_key(version: &str) -> (&str, &str) {
+    version.split_at(version.len().min(10))
+}
+
+fn matches_any(patterns: &[&str], name: &str) -> bool {
+    patterns.iter().any(|pattern| match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => name.eq_ignore_ascii_case(pattern),
+    })
+}
+
+/// A path relative to the directory of a file or URL
+fn sibling(location: &str, relative: &str) -> String {
+    match location.rfind('/') {
+        Some(slash) => format!("{}/{}", &location[..slash], relative),
+        None => relative.to_string(),
+    }
+}
+
+impl Default for AzureProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for AzureProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("AzureProvider")
+            .param(
+                ParamSpec::new("resources", ParamKind::List)
+                    .describe("Resource types to keep; `Microsoft.Storage/*` matches by prefix"),
+            )
+            .param(
+                ParamSpec::new("api_version", ParamKind::String)
+                    .describe("API version to use; defaults to the latest each resource has"),
+            )
+    }
+}
+
+impl TypeProvider for AzureProvider {
+    fn name(&self) -> &str {
+        "AzureProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let trimmed = source.trim_start();
+        let content = if trimmed.starts_with('{') || trimmed.starts_with('[') {
+            source.to_string()
+        } else {
+            read_source(source, params)?
+        };
+        let document: Value = serde_json::from_str(&content)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+
+        let resolved = if bicep::is_types_file(&document) {
+            self.resolve_types(&content, &typed)?
+        } else if bicep::is_index(&document) {
+            self.resolve_index(&document, source, params, &typed)?
+        } else if arm::is_arm_schema(&document) {
+            self.resolve_arm(document, &typed)?
+        } else {
+            return Err(ProviderError::ParseError(
+                "Expected a Bicep types.json or index.json file, or an ARM template schema".to_string(),
+            ));
+        };
+
+        let json = serde_json::to_string(&resolved)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected Azure resource types".to_string()));
+        };
+
+        let mut result = GeneratedTypes::new();
+        result.modules = self.generate(&self.parse_schema(json)?, namespace).0;
+        Ok(result)
+    }
+}
+
+impl SourceValidator for AzureProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected Azure resource types".to_string()));
+        };
+
+        Ok(self.generate(&self.parse_schema(json)?, "").1)
+    }
+}
+
+impl DescribedProvider for AzureProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    const TYPES: &str = r##"[
+      {"$type": "StringType"},
+      {"$type": "StringLiteralType", "value": "Microsoft.Storage/storageAccounts"},
+      {"$type": "StringLiteralType", "value": "2023-01-01"},
+      {"$type": "StringLiteralType", "value": "Hot"},
+      {"$type": "StringLiteralType", "value": "Cool"},
+      {"$type": "UnionType", "elements": [{"$ref": "#/3"}, {"$ref": "#/4"}]},
+      {"$type": "ObjectType", "name": "Sku", "properties": {
+        "name": {"type": {"$ref": "#/0"}, "flags": 1},
+        "tier": {"type": {"$ref": "#/5"}, "flags": 0}
+      }},
+      {"$type": "ObjectType", "name": "StorageAccountPropertiesCreateParameters", "properties": {
+        "accessTier": {"type": {"$ref": "#/5"}, "flags": 0},
+        "primaryEndpoints": {"type": {"$ref": "#/8"}, "flags": 2}
+      }},
+      {"$type": "ObjectType", "name": "Endpoints", "properties": {"blob": {"type": {"$ref": "#/0"}, "flags": 2}}},
+      {"$type": "ObjectType", "name": "Microsoft.Storage/storageAccounts", "properties": {
+        "id": {"type": {"$ref": "#/0"}, "flags": 10},
+        "name": {"type": {"$ref": "#/0"}, "flags": 9},
+        "type": {"type": {"$ref": "#/1"}, "flags": 10},
+        "apiVersion": {"type": {"$ref": "#/2"}, "flags": 10},
+        "location": {"type": {"$ref": "#/0"}, "flags": 1},
+        "sku": {"type": {"$ref": "#/6"}, "flags": 1},
+        "properties": {"type": {"$ref": "#/7"}, "flags": 0},
+        "tags": {"type": {"$ref": "#/10"}, "flags": 0}
+      }},
+      {"$type": "ObjectType", "name": "TrackedResourceTags", "properties": {}, "additionalProperties": {"$ref": "#/0"}},
+      {"$type": "ResourceType", "name": "Microsoft.Storage/storageAccounts@2023-01-01", "scopeType": 8, "body": {"$ref": "#/9"}, "flags": 0},
+      {"$type": "ObjectType", "name": "Microsoft.Storage/storageAccounts/managementPolicies", "properties": {
+        "name": {"type": {"$ref": "#/0"}, "flags": 9},
+        "rule": {"type": {"$ref": "#/13"}, "flags": 0},
+        "sku": {"type": {"$ref": "#/6"}, "flags": 0},
+        "error": {"type": {"$ref": "#/22"}, "flags": 2}
+      }},
+      {"$type": "DiscriminatedObjectType", "name": "PolicyRule", "discriminator": "kind",
+        "baseProperties": {"enabled": {"type": {"$ref": "#/14"}, "flags": 0}},
+        "elements": {"Lifecycle": {"$ref": "#/15"}, "Retention": {"$ref": "#/16"}}},
+      {"$type": "BooleanType"},
+      {"$type": "ObjectType", "name": "LifecycleRule", "properties": {
+        "kind": {"type": {"$ref": "#/17"}, "flags": 1},
+        "days": {"type": {"$ref": "#/18"}, "flags": 0}
+      }},
+      {"$type": "ObjectType", "name": "RetentionRule", "properties": {"kind": {"type": {"$ref": "#/19"}, "flags": 1}}},
+      {"$type": "StringLiteralType", "value": "Lifecycle"},
+      {"$type": "IntegerType", "minValue": 1},
+      {"$type": "StringLiteralType", "value": "Retention"},
+      {"$type": "ResourceType", "name": "Microsoft.Storage/storageAccounts/managementPolicies@2023-01-01", "scopeType": 8, "body": {"$ref": "#/12"}, "flags": 0},
+      {"$type": "ResourceType", "name": "Microsoft.Storage/storageAccounts@2022-09-01", "scopeType": 8, "body": {"$ref": "#/9"}, "flags": 0},
+      {"$type": "ObjectType", "name": "ErrorDetail", "properties": {"details": {"type": {"$ref": "#/23"}, "flags": 2}}},
+      {"$type": "ArrayType", "itemType": {"$ref": "#/22"}},
+      {"$type": "ResourceFunctionType", "name": "listKeys", "resourceType": "Microsoft.Storage/storageAccounts", "apiVersion": "2023-01-01"}
+    ]"##;
+
+    fn generate(source: &str, params: &ProviderParams) -> GeneratedTypes {
+        let provider = AzureProvider::new();
+        let schema = provider.resolve_schema(source, params).unwrap();
+        provider.generate_types(&schema, "Azure").unwrap()
+    }
+
+    fn names(module: &GeneratedModule) -> Vec<&str> {
+        module
+            .types
+            .iter()
+            .map(|t| match t {
+                TypeDefinition::Record(r) => r.name.as_str(),
+                TypeDefinition::Du(du) => du.name.as_str(),
+            })
+            .collect()
+    }
+
+    fn fields(types: &GeneratedTypes, name: &str) -> Vec<(String, String)> {
+        types
+            .modules
+            .iter()
+            .flat_map(|m| &m.types)
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+            .fields
+            .iter()
+            .map(|(field, ty)| (field.clone(), ty.to_string()))
+            .collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(f, t)| (f.to_string(), t.to_string())).collect()
+    }
+
+    #[test]
+    fn test_bicep_types() {
+        let types = generate(TYPES, &ProviderParams::default());
+        assert_eq!(types.modules.len(), 1);
+        assert_eq!(types.modules[0].path, ["Azure", "Storage"]);
+        assert_eq!(
+            names(&types.modules[0]),
+            [
+                "Endpoints",
+                "StorageAccountPropertiesCreateParameters",
+                "Sku",
+                "StorageAccounts",
+                "ErrorDetail",
+                "LifecycleRule",
+                "RetentionRule",
+                "PolicyRule",
+                "StorageAccountsManagementPolicies",
+            ]
+        );
+
+        assert_eq!(
+            fields(&types, "StorageAccounts"),
+            pairs(&[
+                ("apiVersion", "\"2023-01-01\" option"),
+                ("id", "string option"),
+                ("location", "string"),
+                ("name", "string"),
+                ("properties", "StorageAccountPropertiesCreateParameters option"),
+                ("sku", "Sku"),
+                ("tags", "Map<string, string> option"),
+                ("type", "\"Microsoft.Storage/storageAccounts\" option"),
+            ])
+        );
+        assert_eq!(
+            fields(&types, "Sku"),
+            pairs(&[("name", "string"), ("tier", "(\"Hot\" | \"Cool\") option")])
+        );
+        assert_eq!(fields(&types, "ErrorDetail"), pairs(&[("details", "ErrorDetail list option")]));
+        assert_eq!(
+            fields(&types, "LifecycleRule"),
+            pairs(&[("enabled", "bool option"), ("days", "int option"), ("kind", "\"Lifecycle\"")])
+        );
+
+        let rule = types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Du(du) if du.name == "PolicyRule" => Some(du),
+                _ => None,
+            })
+            .unwrap();
+        let cases: Vec<&str> = rule.variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(cases, ["Lifecycle", "Retention"]);
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/azure.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_selection_params() {
+        let params = ProviderParams::default()
+            .with("resources", "Microsoft.Storage/storageAccounts")
+            .with("api_version", "2022-09-01");
+        let types = generate(TYPES, &params);
+        assert_eq!(names(&types.modules[0]), ["Endpoints", "StorageAccountPropertiesCreateParameters", "Sku", "StorageAccounts"]);
+
+        let provider = AzureProvider::new();
+        let params = ProviderParams::default().with("resources", "Microsoft.Compute/*");
+        let err = provider.resolve_schema(TYPES, &params).unwrap_err();
+        assert!(err.to_string().contains("No resource type matches 'Microsoft.Compute/*'"));
+
+        assert!(version_key("2023-01-01") < version_key("2023-01-01-preview"));
+        assert!(version_key("2022-12-01-preview") < version_key("2023-01-01"));
+    }
+
+    #[test]
+    fn test_bicep_index() {
+        let dir = std::env::temp_dir().join(format!("fusabi-azure-{}", std::process::id()));
+        let types_dir = dir.join("storage/microsoft.storage/2023-01-01");
+        std::fs::create_dir_all(&types_dir).unwrap();
+        std::fs::write(types_dir.join("types.json"), TYPES).unwrap();
+        let index = dir.join("index.json");
+        std::fs::write(
+            &index,
+            r#"{"resources": {
+                "Microsoft.Storage/storageAccounts@2023-01-01": {"$ref": "storage/microsoft.storage/2023-01-01/types.json#/11"},
+                "Microsoft.Storage/storageAccounts/managementPolicies@2023-01-01": {"$ref": "storage/microsoft.storage/2023-01-01/types.json#/20"},
+                "Microsoft.Compute/virtualMachines@2023-03-01": {"$ref": "compute/microsoft.compute/2023-03-01/types.json#/1"}
+            }, "resourceFunctions": {}}"#,
+        )
+        .unwrap();
+
+        let params = ProviderParams::default().with("resources", "Microsoft.Storage/*");
+        let types = generate(index.to_str().unwrap(), &params);
+        assert_eq!(names(&types.modules[0]).len(), 9);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_arm_schema() {
+        let schema = r##"{
+          "id": "https://schema.management.azure.com/schemas/2023-01-01/Microsoft.Storage.json#",
+          "$schema": "http://json-schema.org/draft-04/schema#",
+          "title": "Microsoft.Storage",
+          "resourceDefinitions": {
+            "storageAccounts": {
+              "type": "object",
+              "properties": {
+                "apiVersion": {"type": "string", "enum": ["2023-01-01"]},
+                "name": {"type": "string"},
+                "sku": {"oneOf": [
+                  {"$ref": "#/definitions/Sku"},
+                  {"$ref": "https://schema.management.azure.com/schemas/common/definitions.json#/definitions/expression"}
+                ]},
+                "type": {"type": "string", "enum": ["Microsoft.Storage/storageAccounts"]}
+              },
+              "required": ["apiVersion", "name", "sku", "type"]
+            }
+          },
+          "definitions": {
+            "Sku": {
+              "type": "object",
+              "properties": {"name": {"oneOf": [
+                {"type": "string", "enum": ["Standard_LRS", "Premium_LRS"]},
+                {"$ref": "https://schema.management.azure.com/schemas/common/definitions.json#/definitions/expression"}
+              ]}},
+              "required": ["name"]
+            },
+            "Unused": {"type": "object", "properties": {"x": {"type": "string"}}}
+          }
+        }"##;
+
+        let types = generate(schema, &ProviderParams::default());
+        assert_eq!(types.modules[0].path, ["Azure", "Storage"]);
+        assert_eq!(names(&types.modules[0]), ["Sku", "StorageAccounts"]);
+        assert_eq!(
+            fields(&types, "StorageAccounts"),
+            pairs(&[
+                ("apiVersion", "\"2023-01-01\""),
+                ("name", "string"),
+                ("sku", "Sku"),
+                ("type", "\"Microsoft.Storage/storageAccounts\""),
+            ])
+        );
+        assert_eq!(fields(&types, "Sku"), pairs(&[("name", "\"Standard_LRS\" | \"Premium_LRS\"")]));
+
+        let provider = AzureProvider::new();
+        let params = ProviderParams::default().with("api_version", "2022-09-01");
+        assert!(provider.resolve_schema(schema, &params).is_err());
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = AzureProvider::new();
+        let report = provider.validate_source(TYPES, &ProviderParams::default());
+        assert!(report.is_valid());
+        assert!(!report.diagnostics.iter().any(|d| d.code == "unresolved-reference"));
+    }
+}
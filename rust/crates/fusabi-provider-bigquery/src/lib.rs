@@ -0,0 +1,610 @@
+//! BigQuery Type Provider
+//!
+//! Generates Fusabi types from BigQuery table schemas.
+//!
+//! # Sources
+//!
+//! - A `bigquery://<project>/<dataset>` URI: tables are listed and fetched
+//!   with the BigQuery REST API (or named in the path,
+//!   `bigquery://my-project/sales/orders,customers`). The `auth` param
+//!   references an OAuth access token, such as the output of
+//!   `gcloud auth print-access-token` stored in an environment variable
+//! - A table resource (`bq show --format=json project:dataset.table`), or
+//!   an array of them
+//! - A bare schema, either a field array (`bq show --schema`) or an object
+//!   with `fields`; the record is named by the `record` param
+//!
+//! # Generated Types
+//!
+//! Each table becomes a record in the `<namespace>` module, and each
+//! `RECORD` (`STRUCT`) field a nested record named `<Parent><Field>`.
+//! Modes carry over: `REQUIRED` fields are plain, `NULLABLE` fields are
+//! `T option` and `REPEATED` fields are `T list`.
+//!
+//! | BigQuery                                 | Fusabi                   |
+//! |------------------------------------------|--------------------------|
+//! | `STRING`                                 | `string`                 |
+//! | `BYTES`                                  | `bytes`                  |
+//! | `INTEGER` / `INT64`                      | `int64`                  |
+//! | `FLOAT` / `FLOAT64`                      | `float`                  |
+//! | `NUMERIC` / `BIGNUMERIC`                 | `float`                  |
+//! | `BOOLEAN` / `BOOL`                       | `bool`                   |
+//! | `DATE`, `TIME`, `DATETIME`, `TIMESTAMP`  | `string`                 |
+//! | `GEOGRAPHY` (WKT), `INTERVAL`, `JSON`    | `string`                 |
+//! | `RANGE<T>`                               | `string option * string option` |
+//! | `RECORD` / `STRUCT`                      | record `<Parent><Field>` |
+//!
+//! # Params
+//!
+//! | Param      | Meaning                                                     |
+//! |------------|-------------------------------------------------------------|
+//! | `table`    | Comma-separated tables to keep                              |
+//! | `record`   | Record name for a bare schema; defaults to `Row`            |
+//! | `endpoint` | API base URL; defaults to `https://bigquery.googleapis.com/bigquery/v2` |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_bigquery::BigQueryProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = BigQueryProvider::new();
+//! let params = ProviderParams::default().with("auth", "env:GOOGLE_OAUTH_ACCESS_TOKEN");
+//! let schema = provider.resolve_schema("bigquery://my-project/sales", &params)?;
+//! let types = provider.generate_types(&schema, "Sales")?;
+//! ```
+
+mod types;
+
+pub use types::{Field, Mode, RangeElementType, Table};
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceValidator, TypeShape, TypedParams,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use serde_json::Value;
+
+const DEFAULT_ENDPOINT: &str = "https://bigquery.googleapis.com/bigquery/v2";
+
+/// Record name for a bare schema when the `record` param is not set
+const DEFAULT_RECORD: &str = "Row";
+
+/// BigQuery type provider
+pub struct BigQueryProvider {
+    generator: TypeGenerator,
+}
+
+impl BigQueryProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<Vec<Table>> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected BigQuery schema: {}", e)))
+    }
+
+    /// Read table resources, or a bare schema
+    fn parse_source(&self, content: &str, typed: &TypedParams) -> ProviderResult<Vec<Table>> {
+        let value: Value = serde_json::from_str(content)
+            .map_err(|e| ProviderError::ParseError(format!("Invalid BigQuery schema: {}", e)))?;
+        let bare = |fields: &Value| -> ProviderResult<Vec<Table>> {
+            let name = typed.str("record").unwrap_or(DEFAULT_RECORD);
+            Ok(vec![Table {
+                table_id: name.to_string(),
+                kind: None,
+                fields: fields_of(fields)?,
+            }])
+        };
+
+        match &value {
+            Value::Array(entries) if entries.iter().all(|e| e.get("schema").is_none()) => bare(&value),
+            Value::Array(entries) => entries.iter().map(table_of).collect(),
+            entry if entry.get("schema").is_some() => Ok(vec![table_of(entry)?]),
+            entry => match entry.get("fields") {
+                Some(fields) => bare(fields),
+                None => Err(ProviderError::ParseError(
+                    "Expected a BigQuery table resource, or a schema with `fields`".to_string(),
+                )),
+            },
+        }
+    }
+
+    /// Fetch the table schemas of a live dataset
+    fn fetch_tables(&self, uri: &str, params: &ProviderParams, typed: &TypedParams) -> ProviderResult<Vec<Table>> {
+        let mut parts = uri.trim_start_matches("bigquery://").splitn(3, '/');
+        let (project, dataset) = match (parts.next(), parts.next()) {
+            (Some(project), Some(dataset)) if !project.is_empty() && !dataset.is_empty() => (project, dataset),
+            _ => {
+                return Err(ProviderError::InvalidSource(format!(
+                    "Expected bigquery://<project>/<dataset>, got {}",
+                    uri
+                )))
+            }
+        };
+        let endpoint = typed.str("endpoint").map_or(DEFAULT_ENDPOINT, |e| e.trim_end_matches('/'));
+        let base = format!(
+            "{}/projects/{}/datasets/{}/tables",
+            endpoint,
+            encode_segment(project),
+            encode_segment(dataset)
+        );
+
+        let mut names: Vec<String> = parts
+            .next()
+            .unwrap_or_default()
+            .split(',')
+            .filter(|t| !t.is_empty())
+            .map(String::from)
+            .collect();
+        if names.is_empty() {
+            let mut page_token: Option<String> = None;
+            loop {
+                let url = match &page_token {
+                    Some(token) => format!("{}?pageToken={}", base, encode_segment(token)),
+                    None => base.clone(),
+                };
+                let page = fetch_json(&url, params)?;
+                let tables = page.get("tables").and_then(Value::as_array);
+                names.extend(
+                    tables
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|t| t.pointer("/tableReference/tableId").and_then(Value::as_str))
+                        .map(String::from),
+                );
+                page_token = page.get("nextPageToken").and_then(Value::as_str).map(String::from);
+                if page_token.is_none() {
+                    break;
+                }
+            }
+        }
+        if let Some(filter) = typed.list("table") {
+            names.retain(|name| filter.contains(&name.as_str()));
+        }
+
+        names
+            .iter()
+            .map(|name| table_of(&fetch_json(&format!("{}/{}", base, encode_segment(name)), params)?))
+            .collect()
+    }
+
+    /// Generate the records of a table, nested records first
+    fn table_types(&self, table: &Table) -> (Vec<TypeDefinition>, Vec<Diagnostic>) {
+        let mut generation = Generation {
+            generator: &self.generator,
+            types: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+        generation.record(self.generator.naming.apply(&table.table_id), &table.fields, &table.table_id);
+        (generation.types, generation.diagnostics)
+    }
+}
+
+impl Default for BigQueryProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for BigQueryProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("BigQueryProvider")
+            .param(ParamSpec::new("table", ParamKind::List).describe("Tables to keep"))
+            .param(
+                ParamSpec::new("record", ParamKind::String)
+                    .default("Row")
+                    .describe("Record name for a bare schema"),
+            )
+            .param(ParamSpec::new("endpoint", ParamKind::String).describe("API base URL"))
+    }
+}
+
+impl TypeProvider for BigQueryProvider {
+    fn name(&self) -> &str {
+        "BigQueryProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let trimmed = source.trim();
+        let mut tables = if trimmed.starts_with("bigquery://") {
+            self.fetch_tables(trimmed, params, &typed)?
+        } else if trimmed.starts_with('{') || trimmed.starts_with('[') {
+            self.parse_source(source, &typed)?
+        } else {
+            self.parse_source(&read_source(source, params)?, &typed)?
+        };
+
+        if let Some(filter) = typed.list("table") {
+            tables.retain(|t| filter.contains(&t.table_id.as_str()));
+        }
+
+        let json = serde_json::to_string(&tables)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected BigQuery schema".to_string()));
+        };
+
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+        for table in self.parse_schema(json)? {
+            module.types.extend(self.table_types(&table).0);
+        }
+
+        let mut result = GeneratedTypes::new();
+        if !module.types.is_empty() {
+            result.modules.push(module);
+        }
+        Ok(result)
+    }
+}
+
+impl SourceValidator for BigQueryProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected BigQuery schema".to_string()));
+        };
+
+        let tables = self.parse_schema(json)?;
+        let mut diagnostics = Vec::new();
+        if tables.is_empty() {
+            diagnostics.push(Diagnostic::warning("no-tables", "No tables found"));
+        }
+        for table in &tables {
+            diagnostics.extend(self.table_types(table).1);
+        }
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for BigQueryProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .live("bigquery://")
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+/// Records generated for one table, with what was lost on the way
+struct Generation<'a> {
+    generator: &'a TypeGenerator,
+    types: Vec<TypeDefinition>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Generation<'_> {
+    /// Push a record for `fields`, after the records nested in it
+    fn record(&mut self, name: String, fields: &[Field], location: &str) {
+        let fields = fields
+            .iter()
+            .map(|field| {
+                let location = format!("{}.{}", location, field.name);
+                let shape = self.value_shape(field, &name, &location);
+                let shape = match field.mode() {
+                    Mode::Required => shape,
+                    Mode::Nullable => TypeShape::Option(Box::new(shape)),
+                    Mode::Repeated => TypeShape::List(Box::new(shape)),
+                };
+                (field.name.clone(), shape.into())
+            })
+            .collect();
+        self.types.push(TypeDefinition::Record(RecordDef { name, fields }));
+    }
+
+    /// Type of a single value of a field, ignoring its mode
+    fn value_shape(&mut self, field: &Field, parent: &str, location: &str) -> TypeShape {
+        let named = |name: &str| TypeShape::Named(name.to_string());
+        match field.normalized_type().as_str() {
+            "STRING" | "DATE" | "TIME" | "DATETIME" | "TIMESTAMP" | "GEOGRAPHY" | "INTERVAL" => named("string"),
+            "BYTES" => named("bytes"),
+            "INTEGER" => named("int64"),
+            "FLOAT" => named("float"),
+            "BOOLEAN" => named("bool"),
+            "NUMERIC" | "BIGNUMERIC" => {
+                self.diagnostics.push(
+                    Diagnostic::info(
+                        "lossy-conversion",
+                        format!("Fixed-point `{}` is typed as `float`", field.field_type.to_ascii_uppercase()),
+                    )
+                    .at(location.to_string()),
+                );
+                named("float")
+            }
+            "JSON" => {
+                self.diagnostics.push(
+                    Diagnostic::warning("lossy-conversion", "JSON column is typed as `string`")
+                        .at(location.to_string()),
+                );
+                named("string")
+            }
+            // Bounds are `DATE`, `DATETIME` or `TIMESTAMP`; either may be unbounded
+            "RANGE" => {
+                let bound = || TypeShape::Option(Box::new(named("string")));
+                TypeShape::Tuple(vec![bound(), bound()])
+            }
+            "RECORD" => {
+                let name = format!("{}{}", parent, self.generator.naming.apply(&field.name));
+                self.record(name.clone(), &field.fields, location);
+                TypeShape::Named(name)
+            }
+            _ => {
+                self.diagnostics.push(
+                    Diagnostic::warning(
+                        "unmapped-type",
+                        format!("BigQuery type `{}` has no Fusabi mapping", field.field_type),
+                    )
+                    .at(location.to_string()),
+                );
+                named("any")
+            }
+        }
+    }
+}
+
+/// The table of a `tables.get` resource
+fn table_of(resource: &Value) -> ProviderResult<Table> {
+    let table_id = resource
+        .pointer("/tableReference/tableId")
+        .and_then(Value::as_str)
+        // `id` is `project:dataset.table`
+        .or_else(|| resource.get("id").and_then(Value::as_str).and_then(|id| id.rsplit('.').next()))
+        .ok_or_else(|| ProviderError::ParseError("BigQuery table resource has no tableReference".to_string()))?;
+    let fields = match resource.pointer("/schema/fields") {
+        Some(fields) => fields_of(fields)?,
+        None => Vec::new(),
+    };
+
+    Ok(Table {
+        table_id: table_id.to_string(),
+        kind: resource.get("type").and_then(Value::as_str).map(String::from),
+        fields,
+    })
+}
+
+fn fields_of(fields: &Value) -> ProviderResult<Vec<Field>> {
+    serde_json::from_value(fields.clone())
+        .map_err(|e| ProviderError::ParseError(format!("Invalid BigQuery schema fields: {}", e)))
+}
+
+fn fetch_json(url: &str, params: &ProviderParams) -> ProviderResult<Value> {
+    serde_json::from_str(&read_source(url, params)?)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid BigQuery API response from {}: {}", url, e)))
+}
+
+/// Percent-encode a URL path segment or query value
+fn encode_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Tables named by the `table` param
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    const ORDERS: &str = r#"{
+        "kind": "bigquery#table",
+        "id": "shop-prod:sales.orders",
+        "tableReference": {"projectId": "shop-prod", "datasetId": "sales", "tableId": "orders"},
+        "type": "TABLE",
+        "schema": {"fields": [
+            {"name": "order_id", "type": "STRING", "mode": "REQUIRED"},
+            {"name": "placed_at", "type": "TIMESTAMP", "mode": "REQUIRED"},
+            {"name": "total", "type": "NUMERIC"},
+            {"name": "customer", "type": "RECORD", "mode": "NULLABLE", "fields": [
+                {"name": "id", "type": "INTEGER", "mode": "REQUIRED"},
+                {"name": "email", "type": "STRING"}
+            ]},
+            {"name": "line_items", "type": "RECORD", "mode": "REPEATED", "fields": [
+                {"name": "sku", "type": "STRING", "mode": "REQUIRED"},
+                {"name": "quantity", "type": "INTEGER"},
+                {"name": "options", "type": "RECORD", "mode": "REPEATED", "fields": [
+                    {"name": "key", "type": "STRING"},
+                    {"name": "value", "type": "STRING"}
+                ]}
+            ]},
+            {"name": "tags", "type": "STRING", "mode": "REPEATED"}
+        ]}
+    }"#;
+
+    fn record<'a>(types: &'a GeneratedTypes, name: &str) -> &'a RecordDef {
+        types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+    }
+
+    fn fields(record: &RecordDef) -> Vec<(String, String)> {
+        record.fields.iter().map(|(name, ty)| (name.clone(), ty.to_string())).collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect()
+    }
+
+    #[test]
+    fn test_nested_records_and_modes() {
+        let provider = BigQueryProvider::new();
+        let schema = provider.resolve_schema(ORDERS, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Sales").unwrap();
+
+        assert_eq!(types.modules[0].path, vec!["Sales".to_string()]);
+        assert_eq!(
+            fields(record(&types, "Orders")),
+            pairs(&[
+                ("order_id", "string"),
+                ("placed_at", "string"),
+                ("total", "float option"),
+                ("customer", "OrdersCustomer option"),
+                ("line_items", "OrdersLineItems list"),
+                ("tags", "string list"),
+            ])
+        );
+        assert_eq!(
+            fields(record(&types, "OrdersLineItems")),
+            pairs(&[
+                ("sku", "string"),
+                ("quantity", "int64 option"),
+                ("options", "OrdersLineItemsOptions list"),
+            ])
+        );
+        assert_eq!(
+            fields(record(&types, "OrdersCustomer")),
+            pairs(&[("id", "int64"), ("email", "string option")])
+        );
+
+        // Nested records come before the records that use them
+        let names: Vec<&str> = types.modules[0]
+            .types
+            .iter()
+            .map(|t| match t {
+                TypeDefinition::Record(r) => r.name.as_str(),
+                TypeDefinition::Du(d) => d.name.as_str(),
+            })
+            .collect();
+        assert_eq!(names, ["OrdersCustomer", "OrdersLineItemsOptions", "OrdersLineItems", "Orders"]);
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/bigquery.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_bare_schema() {
+        let provider = BigQueryProvider::new();
+        let fields_json = r#"[
+            {"name": "id", "type": "INT64", "mode": "required"},
+            {"name": "ratio", "type": "FLOAT64"},
+            {"name": "active", "type": "BOOL"},
+            {"name": "validity", "type": "RANGE", "rangeElementType": {"type": "DATE"}},
+            {"name": "meta", "type": "STRUCT", "fields": [{"name": "raw", "type": "BYTES", "mode": "REQUIRED"}]}
+        ]"#;
+
+        let params = ProviderParams::default().with("record", "event");
+        let schema = provider.resolve_schema(fields_json, &params).unwrap();
+        let types = provider.generate_types(&schema, "Events").unwrap();
+        assert_eq!(
+            fields(record(&types, "Event")),
+            pairs(&[
+                ("id", "int64"),
+                ("ratio", "float option"),
+                ("active", "bool option"),
+                ("validity", "(string option * string option) option"),
+                ("meta", "EventMeta option"),
+            ])
+        );
+
+        let wrapped = format!(r#"{{"fields": {}}}"#, fields_json);
+        let schema = provider.resolve_schema(&wrapped, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Events").unwrap();
+        assert_eq!(fields(record(&types, "RowMeta")), pairs(&[("raw", "bytes")]));
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = BigQueryProvider::new();
+        let source = r#"[{"name": "payload", "type": "JSON"}, {"name": "spend", "type": "BIGNUMERIC"}, {"name": "blob", "type": "VECTOR"}]"#;
+        let report = provider.validate_source(source, &ProviderParams::default());
+        assert!(report.is_valid());
+
+        let codes: Vec<(&str, Option<&str>)> =
+            report.diagnostics.iter().map(|d| (d.code.as_str(), d.location.as_deref())).collect();
+        assert!(codes.contains(&("lossy-conversion", Some("Row.payload"))));
+        assert!(codes.contains(&("lossy-conversion", Some("Row.spend"))));
+        assert!(codes.contains(&("unmapped-type", Some("Row.blob"))));
+
+        let report = provider.validate_source(&format!("[{}]", ORDERS), &ProviderParams::default().with("table", "missing"));
+        assert_eq!(report.warnings().next().unwrap().code, "no-tables");
+
+        assert!(!provider.validate_source(r#"{"rows": []}"#, &ProviderParams::default()).is_valid());
+    }
+
+    /// Serve canned JSON bodies by request path until the listener is dropped
+    fn serve(routes: Vec<(&'static str, String)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                let mut buf = [0u8; 4096];
+                let n = stream.read(&mut buf).unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let path = request.split_whitespace().nth(1).unwrap_or("");
+                let response = match routes.iter().find(|(route, _)| *route == path) {
+                    Some((_, body)) => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    ),
+                    None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+                };
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    #[test]
+    fn test_fetch_dataset() {
+        let events = r#"{
+            "tableReference": {"projectId": "shop-prod", "datasetId": "sales", "tableId": "events"},
+            "schema": {"fields": [{"name": "at", "type": "TIMESTAMP", "mode": "REQUIRED"}]}
+        }"#;
+        let endpoint = serve(vec![
+            (
+                "/projects/shop-prod/datasets/sales/tables",
+                r#"{"tables": [{"tableReference": {"tableId": "orders"}}], "nextPageToken": "p/2"}"#.to_string(),
+            ),
+            (
+                "/projects/shop-prod/datasets/sales/tables?pageToken=p%2F2",
+                r#"{"tables": [{"tableReference": {"tableId": "events"}}, {"tableReference": {"tableId": "audit"}}]}"#
+                    .to_string(),
+            ),
+            ("/projects/shop-prod/datasets/sales/tables/orders", ORDERS.to_string()),
+            ("/projects/shop-prod/datasets/sales/tables/events", events.to_string()),
+        ]);
+
+        let provider = BigQueryProvider::new();
+        let params = ProviderParams::default()
+            .with("endpoint", &endpoint)
+            .with("table", "orders,events");
+        let schema = provider.resolve_schema("bigquery://shop-prod/sales", &params).unwrap();
+        let types = provider.generate_types(&schema, "Sales").unwrap();
+        assert_eq!(fields(record(&types, "Events")), pairs(&[("at", "string")]));
+        assert_eq!(types.modules[0].types.len(), 5);
+
+        let schema = provider.resolve_schema("bigquery://shop-prod/sales/events", &params).unwrap();
+        let types = provider.generate_types(&schema, "Sales").unwrap();
+        assert_eq!(types.modules[0].types.len(), 1);
+
+        assert!(provider.resolve_schema("bigquery://shop-prod", &params).is_err());
+    }
+}
@@ -0,0 +1,81 @@
+//! BigQuery table schema types
+//!
+//! The subset of the `tables.get` resource the provider reads, with the
+//! API's camelCase field names. Other fields are ignored.
+
+use serde::{Deserialize, Serialize};
+
+/// A table and its schema
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Table {
+    pub table_id: String,
+    /// `TABLE`, `VIEW`, `MATERIALIZED_VIEW`, `EXTERNAL` or `SNAPSHOT`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(default)]
+    pub fields: Vec<Field>,
+}
+
+/// A column, or a field of a `RECORD` column
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Field {
+    pub name: String,
+    /// Legacy (`INTEGER`, `RECORD`) or GoogleSQL (`INT64`, `STRUCT`) name
+    #[serde(rename = "type")]
+    pub field_type: String,
+    /// `REQUIRED`, `NULLABLE` or `REPEATED`; `NULLABLE` when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    /// Subfields of a `RECORD`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<Field>,
+    /// Element type of a `RANGE`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range_element_type: Option<RangeElementType>,
+}
+
+/// The `rangeElementType` of a `RANGE` field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RangeElementType {
+    #[serde(rename = "type")]
+    pub field_type: String,
+}
+
+/// How many values a field holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Required,
+    Nullable,
+    Repeated,
+}
+
+impl Field {
+    pub fn mode(&self) -> Mode {
+        match self.mode.as_deref().map(str::to_ascii_uppercase).as_deref() {
+            Some("REQUIRED") => Mode::Required,
+            Some("REPEATED") => Mode::Repeated,
+            _ => Mode::Nullable,
+        }
+    }
+
+    /// The type name in upper case, with GoogleSQL aliases normalized to
+    /// their legacy names
+    pub fn normalized_type(&self) -> String {
+        normalize_type(&self.field_type)
+    }
+}
+
+pub(crate) fn normalize_type(field_type: &str) -> String {
+    let upper = field_type.trim().to_ascii_uppercase();
+    match upper.as_str() {
+        "INT64" => "INTEGER".to_string(),
+        "FLOAT64" => "FLOAT".to_string(),
+        "BOOL" => "BOOLEAN".to_string(),
+        "STRUCT" => "RECORD".to_string(),
+        "DECIMAL" => "NUMERIC".to_string(),
+        "BIGDECIMAL" => "BIGNUMERIC".to_string(),
+        _ => upper,
+    }
+}
@@ -0,0 +1,618 @@
+//! Conversion of CDDL rules to Fusabi types
+
+use crate::types::{
+    ChoiceSource, CddlFile, EntryKind, Group, GroupEntry, MemberKey, Operator, Rule, RuleBody,
+    Type, Type1, Type2, Value,
+};
+use fusabi_provider_common::{Diagnostic, TypeShape};
+use fusabi_type_providers::{
+    DuDef, RecordDef, TypeDefinition, TypeExpr, TypeGenerator, VariantDef,
+};
+use std::collections::HashMap;
+
+/// Generic arguments in scope, by parameter name
+type Env = HashMap<String, TypeShape>;
+
+/// Standard prelude types and their Fusabi equivalents
+const PRELUDE: &[(&str, &str)] = &[
+    ("any", "any"),
+    ("uint", "int"),
+    ("nint", "int"),
+    ("int", "int"),
+    ("integer", "int"),
+    ("unsigned", "int"),
+    ("biguint", "int"),
+    ("bignint", "int"),
+    ("bigint", "int"),
+    ("float", "float"),
+    ("float16", "float"),
+    ("float32", "float"),
+    ("float64", "float"),
+    ("float16-32", "float"),
+    ("float32-64", "float"),
+    ("number", "float"),
+    ("decfrac", "float"),
+    ("bigfloat", "float"),
+    ("time", "float"),
+    ("tstr", "string"),
+    ("text", "string"),
+    ("tdate", "string"),
+    ("uri", "string"),
+    ("b64url", "string"),
+    ("b64legacy", "string"),
+    ("regexp", "string"),
+    ("mime-message", "string"),
+    ("bstr", "bytes"),
+    ("bytes", "bytes"),
+    ("encoded-cbor", "bytes"),
+    ("eb64url", "bytes"),
+    ("eb64legacy", "bytes"),
+    ("eb16", "bytes"),
+    ("cbor-any", "any"),
+    ("bool", "bool"),
+    ("true", "bool"),
+    ("false", "bool"),
+];
+
+/// Names of the null value
+const NULLS: [&str; 3] = ["null", "nil", "undefined"];
+
+/// Control operators that only constrain values of the base type
+const CONSTRAINTS: [&str; 12] = [
+    "size", "bits", "regexp", "pcre", "lt", "le", "gt", "ge", "eq", "ne", "within", "and",
+];
+
+/// Converts the rules of a specification, collecting types and diagnostics
+pub(crate) struct Conversion<'a> {
+    generator: &'a TypeGenerator,
+    rules: HashMap<&'a str, &'a Rule>,
+    pub types: Vec<TypeDefinition>,
+    pub diagnostics: Vec<Diagnostic>,
+    /// Names of definitions generated or being generated
+    defined: Vec<String>,
+    /// Rules being inlined, to stop alias cycles
+    inlining: Vec<String>,
+}
+
+impl<'a> Conversion<'a> {
+    pub fn new(generator: &'a TypeGenerator, file: &'a CddlFile) -> Self {
+        Self {
+            generator,
+            rules: file.rules.iter().map(|r| (r.name.as_str(), r)).collect(),
+            types: Vec::new(),
+            diagnostics: Vec::new(),
+            defined: Vec::new(),
+            inlining: Vec::new(),
+        }
+    }
+
+    /// Generate a definition for every non-generic type rule that needs one
+    pub fn convert(mut self, file: &'a CddlFile) -> Self {
+        for rule in &file.rules {
+            if !rule.params.is_empty() {
+                continue;
+            }
+            let RuleBody::Type(ty) = &rule.body else {
+                continue;
+            };
+            if self.needs_definition(ty) {
+                let name = self.generator.naming.apply(&rule.name);
+                self.define(&name, ty, &Env::new(), &rule.name);
+            } else if let Some(Type1 { base: Type2::Name(_, args), .. }) = ty.single() {
+                // Aliases of generic instantiations still define them
+                if !args.is_empty() {
+                    let context = self.generator.naming.apply(&rule.name);
+                    self.type_shape(ty, &context, &Env::new(), &rule.name);
+                }
+            }
+        }
+        self
+    }
+
+    /// Whether a type becomes a named record or union rather than being
+    /// inlined where it is used
+    fn needs_definition(&self, ty: &Type) -> bool {
+        let choices = non_null(ty);
+        match choices.as_slice() {
+            [] => false,
+            [only] => match (&only.base, &only.operator) {
+                (_, Some(_)) => false,
+                (Type2::Map(group), None) => !is_dictionary(group),
+                (Type2::Array(group), None) => self.is_record_array(group),
+                (Type2::ChoiceFrom(_), None) => true,
+                (Type2::Parens(inner), None) => self.needs_definition(inner),
+                _ => false,
+            },
+            // Integer choices such as COSE algorithm ids stay integers
+            many => !many.iter().all(|c| matches!(c.base, Type2::Value(Value::Int(_)))),
+        }
+    }
+
+    /// Arrays whose entries are all single, named members encode records
+    fn is_record_array(&self, group: &Group) -> bool {
+        let Some(entries) = group.entries() else {
+            return false;
+        };
+        let entries = self.flatten(entries);
+        !entries.is_empty()
+            && entries.iter().all(|(entry, _)| {
+                !entry.occurrence.is_repeated()
+                    && matches!(&entry.kind, EntryKind::Member { key: Some(MemberKey::Bareword(_)), .. })
+            })
+    }
+
+    /// Define a named type for `ty` unless it already exists
+    fn define(&mut self, name: &str, ty: &Type, env: &Env, location: &str) {
+        if self.defined.iter().any(|d| d == name) {
+            return;
+        }
+        self.defined.push(name.to_string());
+
+        let choices = non_null(ty);
+        let nullable = choices.len() < ty.choices.len();
+
+        let definition = match choices.as_slice() {
+            [only] if !nullable || matches!(only.base, Type2::Map(_) | Type2::Array(_)) => {
+                match &only.base {
+                    Type2::Map(group) | Type2::Array(group) => self.group_definition(name, group, env, location),
+                    Type2::ChoiceFrom(source) => self.choice_from(name, source, location),
+                    Type2::Parens(inner) => {
+                        self.defined.retain(|d| d != name);
+                        return self.define(name, inner, env, location);
+                    }
+                    _ => return,
+                }
+            }
+            _ if choices.iter().all(|c| matches!(c.base, Type2::Value(Value::Text(_)))) => {
+                let mut variants: Vec<VariantDef> = choices
+                    .iter()
+                    .filter_map(|c| match &c.base {
+                        Type2::Value(Value::Text(text)) => Some(VariantDef::new_simple(self.case_name(text))),
+                        _ => None,
+                    })
+                    .collect();
+                if nullable {
+                    variants.push(VariantDef::new_simple("Null".to_string()));
+                }
+                TypeDefinition::Du(DuDef { name: name.to_string(), variants })
+            }
+            _ => {
+                let mut variants = Vec::new();
+                for (index, choice) in choices.iter().enumerate() {
+                    let variant = match &choice.base {
+                        Type2::Value(Value::Text(text)) => VariantDef::new_simple(self.case_name(text)),
+                        Type2::Name(case, args) if args.is_empty() && !env.contains_key(case) => {
+                            let case_name = self.generator.naming.apply(case);
+                            let shape = self.type1_shape(choice, &format!("{}{}", name, case_name), env, location);
+                            VariantDef::new(case_name, vec![shape.into()])
+                        }
+                        _ => {
+                            let case_name = format!("Case{}", index + 1);
+                            let shape = self.type1_shape(choice, &format!("{}{}", name, case_name), env, location);
+                            VariantDef::new(case_name, vec![shape.into()])
+                        }
+                    };
+                    variants.push(variant);
+                }
+                if nullable {
+                    variants.push(VariantDef::new_simple("Null".to_string()));
+                }
+                TypeDefinition::Du(DuDef { name: name.to_string(), variants })
+            }
+        };
+
+        self.types.push(definition);
+    }
+
+    /// Record for a map or array group; group choices become a union of
+    /// records
+    fn group_definition(&mut self, name: &str, group: &Group, env: &Env, location: &str) -> TypeDefinition {
+        if let Some(entries) = group.entries() {
+            return TypeDefinition::Record(RecordDef {
+                name: name.to_string(),
+                fields: self.record_fields(entries, name, env, location),
+            });
+        }
+
+        let mut variants = Vec::new();
+        for (index, entries) in group.choices.iter().enumerate() {
+            let case_name = format!("Alt{}", index + 1);
+            let record_name = format!("{}{}", name, case_name);
+            let fields = self.record_fields(entries, &record_name, env, location);
+            self.types.push(TypeDefinition::Record(RecordDef { name: record_name.clone(), fields }));
+            variants.push(VariantDef::new(case_name, vec![TypeShape::named(record_name).into()]));
+        }
+        TypeDefinition::Du(DuDef { name: name.to_string(), variants })
+    }
+
+    /// Union whose cases are the member names of a group (`&(a: 1, b: 2)`)
+    fn choice_from(&mut self, name: &str, source: &ChoiceSource, location: &str) -> TypeDefinition {
+        let group = match source {
+            ChoiceSource::Group(group) => group.clone(),
+            ChoiceSource::Name(rule) => match self.rules.get(rule.as_str()).map(|r| &r.body) {
+                Some(RuleBody::Group(group)) => group.clone(),
+                _ => {
+                    self.unresolved(rule, location);
+                    return TypeDefinition::Du(DuDef { name: name.to_string(), variants: Vec::new() });
+                }
+            },
+        };
+
+        let entries: Vec<&GroupEntry> = group.choices.iter().flatten().collect();
+        let variants = entries
+            .iter()
+            .filter_map(|entry| match &entry.kind {
+                EntryKind::Member { key: Some(MemberKey::Bareword(key)), .. } => {
+                    Some(VariantDef::new_simple(self.case_name(key)))
+                }
+                _ => None,
+            })
+            .collect();
+        TypeDefinition::Du(DuDef { name: name.to_string(), variants })
+    }
+
+    /// Entries of a group with included groups expanded; the flag marks
+    /// entries made optional by an optional inclusion
+    fn flatten(&self, entries: &[GroupEntry]) -> Vec<(GroupEntry, bool)> {
+        let mut flat = Vec::new();
+        self.flatten_into(entries, false, &mut Vec::new(), &mut flat);
+        flat
+    }
+
+    fn flatten_into(
+        &self,
+        entries: &[GroupEntry],
+        optional: bool,
+        visiting: &mut Vec<String>,
+        flat: &mut Vec<(GroupEntry, bool)>,
+    ) {
+        for entry in entries {
+            let optional = optional || entry.occurrence.min == 0;
+            let included = match &entry.kind {
+                EntryKind::Group(group) => group.entries().map(|e| e.to_vec()),
+                EntryKind::Member { key: None, value } => match value.single() {
+                    Some(Type1 { base: Type2::Name(name, _), operator: None }) => {
+                        self.included_entries(name, false, visiting)
+                    }
+                    Some(Type1 { base: Type2::Unwrap(name), operator: None }) => {
+                        self.included_entries(name, true, visiting)
+                    }
+                    _ => None,
+                },
+                _ => None,
+            };
+
+            match included {
+                Some(included) => {
+                    let name = included_name(entry);
+                    visiting.push(name);
+                    self.flatten_into(&included, optional, visiting, flat);
+                    visiting.pop();
+                }
+                None => flat.push((entry.clone(), optional && entry.occurrence.min > 0)),
+            }
+        }
+    }
+
+    /// Entries of a group rule included by name, or of the group inside a
+    /// map or array rule when unwrapped with `~`
+    fn included_entries(&self, name: &str, unwrap: bool, visiting: &[String]) -> Option<Vec<GroupEntry>> {
+        if visiting.iter().any(|v| v == name) {
+            return Some(Vec::new());
+        }
+        let Some(rule) = self.rules.get(name) else {
+            // Sockets (`$$name`) may be left empty
+            return name.starts_with('$').then(Vec::new);
+        };
+        let group = match &rule.body {
+            RuleBody::Group(group) => group,
+            RuleBody::Type(_) if !unwrap => return None,
+            RuleBody::Type(ty) => match ty.single().map(|t| &t.base) {
+                Some(Type2::Map(group)) | Some(Type2::Array(group)) => group,
+                _ => return None,
+            },
+        };
+        group.entries().map(|e| e.to_vec())
+    }
+
+    fn record_fields(&mut self, entries: &[GroupEntry], record: &str, env: &Env, location: &str) -> Vec<(String, TypeExpr)> {
+        let mut fields = Vec::new();
+
+        for (index, (entry, included_optional)) in self.flatten(entries).into_iter().enumerate() {
+            let EntryKind::Member { key, value } = &entry.kind else {
+                continue;
+            };
+
+            let field = match key {
+                Some(MemberKey::Bareword(name)) => field_name(name),
+                Some(MemberKey::Value(Value::Text(text))) => field_name(text),
+                Some(MemberKey::Value(Value::Int(label))) if *label < 0 => format!("key_neg_{}", -label),
+                Some(MemberKey::Value(Value::Int(label))) => format!("key_{}", label),
+                Some(MemberKey::Type(ty)) => match ty.as_name().and_then(|name| self.label_name(name)) {
+                    // `alg => int` where `alg = 1`: the constant names the key
+                    Some(name) => field_name(&name),
+                    None => {
+                        self.diagnostics.push(
+                            Diagnostic::info(
+                                "extension-skipped",
+                                format!("`{}` accepts computed keys; only fixed keys become fields", record),
+                            )
+                            .at(location.to_string()),
+                        );
+                        continue;
+                    }
+                },
+                Some(MemberKey::Value(_)) => continue,
+                // Positional array elements
+                None => format!("item_{}", index + 1),
+            };
+
+            let field_location = format!("{}.{}", location, field);
+            let context = format!("{}{}", record, self.generator.naming.apply(&field));
+            let shape = self.type_shape(value, &context, env, &field_location);
+            let shape = if entry.occurrence.is_repeated() {
+                TypeShape::List(Box::new(shape))
+            } else if entry.occurrence.is_optional() || included_optional {
+                optional(shape)
+            } else {
+                shape
+            };
+            fields.push((field, shape.into()));
+        }
+
+        fields
+    }
+
+    /// Name of a rule that is a single literal, usable as a key
+    fn label_name(&self, name: &str) -> Option<String> {
+        let rule = self.rules.get(name)?;
+        match &rule.body {
+            RuleBody::Type(ty) => match ty.single()?.base {
+                Type2::Value(_) => Some(rule.name.clone()),
+                _ => None,
+            },
+            RuleBody::Group(_) => None,
+        }
+    }
+
+    /// Convert a type in field position; anonymous maps, arrays and
+    /// choices are defined under `context`
+    fn type_shape(&mut self, ty: &Type, context: &str, env: &Env, location: &str) -> TypeShape {
+        let choices = non_null(ty);
+        let nullable = choices.len() < ty.choices.len();
+
+        let shape = match choices.as_slice() {
+            [] => TypeShape::Named("unit".to_string()),
+            [only] => self.type1_shape(only, context, env, location),
+            many if many.iter().all(|c| matches!(c.base, Type2::Value(Value::Int(_)))) => {
+                TypeShape::Named("int".to_string())
+            }
+            _ => {
+                let without_null = Type { choices: choices.into_iter().cloned().collect() };
+                self.define(context, &without_null, env, location);
+                TypeShape::Named(context.to_string())
+            }
+        };
+
+        if nullable {
+            optional(shape)
+        } else {
+            shape
+        }
+    }
+
+    fn type1_shape(&mut self, ty: &Type1, context: &str, env: &Env, location: &str) -> TypeShape {
+        match &ty.operator {
+            Some(Operator::Range(upper)) => {
+                let float = matches!(ty.base, Type2::Value(Value::Float(_)))
+                    || matches!(upper, Type2::Value(Value::Float(_)));
+                return TypeShape::Named(if float { "float" } else { "int" }.to_string());
+            }
+            Some(Operator::Control(control, _)) if control == "cbor" || control == "cborseq" => {
+                self.diagnostics.push(
+                    Diagnostic::info(
+                        "lossy-conversion",
+                        format!("Embedded CBOR (`.{}`) is generated as `bytes`", control),
+                    )
+                    .at(location.to_string()),
+                );
+                return TypeShape::Named("bytes".to_string());
+            }
+            Some(Operator::Control(control, _)) if CONSTRAINTS.contains(&control.as_str()) => {
+                self.diagnostics.push(
+                    Diagnostic::info(
+                        "constraint-skipped",
+                        format!("Control `.{}` constrains values only; it is not enforced by the type", control),
+                    )
+                    .at(location.to_string()),
+                );
+            }
+            _ => {}
+        }
+        self.type2_shape(&ty.base, context, env, location)
+    }
+
+    fn type2_shape(&mut self, ty: &Type2, context: &str, env: &Env, location: &str) -> TypeShape {
+        let named = |name: &str| TypeShape::Named(name.to_string());
+        match ty {
+            Type2::Value(Value::Text(text)) => TypeShape::Literal(text.clone()),
+            Type2::Value(Value::Int(_)) => named("int"),
+            Type2::Value(Value::Float(_)) => named("float"),
+            Type2::Value(Value::Bytes) => named("bytes"),
+            Type2::Name(name, args) => self.name_shape(name, args, context, env, location),
+            Type2::Parens(inner) => self.type_shape(inner, context, env, location),
+            Type2::Tagged(inner) => self.type_shape(inner, context, env, location),
+            Type2::Map(group) if is_dictionary(group) => {
+                let Some([GroupEntry { kind: EntryKind::Member { key: Some(MemberKey::Type(key)), value }, .. }]) =
+                    group.entries()
+                else {
+                    return named("any");
+                };
+                let key = self.type_shape(key, &format!("{}Key", context), env, location);
+                let value = self.type_shape(value, &format!("{}Value", context), env, location);
+                TypeShape::Map(Box::new(key), Box::new(value))
+            }
+            Type2::Array(group) if !self.is_record_array(group) => self.array_shape(group, context, env, location),
+            Type2::Map(_) | Type2::Array(_) | Type2::ChoiceFrom(_) => {
+                let ty = Type { choices: vec![Type1 { base: ty.clone(), operator: None }] };
+                self.define(context, &ty, env, location);
+                named(context)
+            }
+            Type2::Unwrap(name) => self.name_shape(name, &[], context, env, location),
+            Type2::Major(major) => match major {
+                0 | 1 => named("int"),
+                2 => named("bytes"),
+                3 => named("string"),
+                4 => TypeShape::List(Box::new(named("any"))),
+                5 => TypeShape::Map(Box::new(named("any")), Box::new(named("any"))),
+                _ => named("any"),
+            },
+            Type2::Any => named("any"),
+        }
+    }
+
+    /// Lists for arrays of one repeated type, tuples for fixed positions
+    fn array_shape(&mut self, group: &Group, context: &str, env: &Env, location: &str) -> TypeShape {
+        let entries: Vec<GroupEntry> = match group.entries() {
+            Some(entries) => self.flatten(entries).into_iter().map(|(entry, _)| entry).collect(),
+            None => Vec::new(),
+        };
+        let values: Vec<&Type> = entries
+            .iter()
+            .filter_map(|entry| match &entry.kind {
+                EntryKind::Member { value, .. } => Some(value),
+                EntryKind::Group(_) => None,
+            })
+            .collect();
+
+        match values.as_slice() {
+            [value] => TypeShape::List(Box::new(self.type_shape(value, &format!("{}Item", context), env, location))),
+            many if !many.is_empty() && entries.iter().all(|e| !e.occurrence.is_repeated() && !e.occurrence.is_optional()) => {
+                TypeShape::Tuple(
+                    many.iter()
+                        .enumerate()
+                        .map(|(i, value)| self.type_shape(value, &format!("{}Item{}", context, i + 1), env, location))
+                        .collect(),
+                )
+            }
+            _ => {
+                self.diagnostics.push(
+                    Diagnostic::info(
+                        "lossy-conversion",
+                        "Array mixes repeated or optional element types; generated as `any list`",
+                    )
+                    .at(location.to_string()),
+                );
+                TypeShape::List(Box::new(TypeShape::Named("any".to_string())))
+            }
+        }
+    }
+
+    fn name_shape(&mut self, name: &str, args: &[Type], context: &str, env: &Env, location: &str) -> TypeShape {
+        if let Some(shape) = env.get(name) {
+            return shape.clone();
+        }
+        if NULLS.contains(&name) {
+            return TypeShape::Named("unit".to_string());
+        }
+        let Some(rule) = self.rules.get(name).copied() else {
+            if let Some((_, fusabi)) = PRELUDE.iter().find(|(cddl, _)| *cddl == name) {
+                return TypeShape::Named(fusabi.to_string());
+            }
+            if !name.starts_with('$') {
+                self.unresolved(name, location);
+            }
+            return TypeShape::Named("any".to_string());
+        };
+
+        let RuleBody::Type(body) = &rule.body else {
+            // A group used where a type is expected means a map of it
+            let record = self.generator.naming.apply(name);
+            if let RuleBody::Group(group) = &rule.body {
+                let map = Type { choices: vec![Type1 { base: Type2::Map(group.clone()), operator: None }] };
+                self.define(&record, &map, &Env::new(), name);
+            }
+            return TypeShape::Named(record);
+        };
+
+        let mut rule_env = Env::new();
+        let mut type_name = self.generator.naming.apply(name);
+        for (param, arg) in rule.params.iter().zip(args) {
+            let shape = self.type_shape(arg, &format!("{}{}", context, self.generator.naming.apply(param)), env, location);
+            type_name.push_str(&self.generator.naming.apply(&shape.to_string()));
+            rule_env.insert(param.clone(), shape);
+        }
+
+        if self.needs_definition(body) {
+            // Generic rules get one definition per instantiation
+            if !rule.params.is_empty() {
+                self.define(&type_name, body, &rule_env, name);
+            }
+            return TypeShape::Named(type_name);
+        }
+
+        if self.inlining.iter().any(|n| n == name) {
+            self.unresolved(name, location);
+            return TypeShape::Named("any".to_string());
+        }
+        self.inlining.push(name.to_string());
+        let shape = self.type_shape(body, context, &rule_env, location);
+        self.inlining.pop();
+        shape
+    }
+
+    fn unresolved(&mut self, name: &str, location: &str) {
+        self.diagnostics.push(
+            Diagnostic::warning(
+                "name-unresolved",
+                format!("`{}` is not defined by this specification or the prelude; generated as `any`", name),
+            )
+            .at(location.to_string()),
+        );
+    }
+
+    /// Union case name for a text value or key
+    fn case_name(&self, text: &str) -> String {
+        self.generator.naming.apply(&field_name(text))
+    }
+}
+
+/// Choices of a type other than `null`
+fn non_null(ty: &Type) -> Vec<&Type1> {
+    ty.choices
+        .iter()
+        .filter(|c| !matches!(&c.base, Type2::Name(name, _) if NULLS.contains(&name.as_str())))
+        .collect()
+}
+
+/// Maps with a single computed key (`{ * tstr => int }`) are dictionaries
+fn is_dictionary(group: &Group) -> bool {
+    matches!(
+        group.entries(),
+        Some([GroupEntry { kind: EntryKind::Member { key: Some(MemberKey::Type(_)), .. }, .. }])
+    )
+}
+
+fn included_name(entry: &GroupEntry) -> String {
+    match &entry.kind {
+        EntryKind::Member { value, .. } => match value.single().map(|t| &t.base) {
+            Some(Type2::Name(name, _)) | Some(Type2::Unwrap(name)) => name.clone(),
+            _ => String::new(),
+        },
+        EntryKind::Group(_) => String::new(),
+    }
+}
+
+/// Field name for a CDDL key: `kebab-case` and other punctuation become `_`
+fn field_name(key: &str) -> String {
+    key.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect()
+}
+
+fn optional(shape: TypeShape) -> TypeShape {
+    match shape {
+        TypeShape::Option(_) => shape,
+        _ => TypeShape::Option(Box::new(shape)),
+    }
+}
@@ -0,0 +1,350 @@
+//! CDDL Type Provider
+//!
+//! Generates Fusabi types from CDDL (RFC 8610) specifications, as used by
+//! COSE, CWT and many IoT data formats.
+//!
+//! | CDDL                              | Fusabi                          |
+//! |-----------------------------------|---------------------------------|
+//! | `name = { ... }`                  | record                          |
+//! | `key: type`, `"key": type`        | field                           |
+//! | `1: type`, `-1: type`             | field `key_1`, `key_neg_1`      |
+//! | `? key: type`                     | `type option`                   |
+//! | `* key: type`, `+ key: type`      | `type list`                     |
+//! | `{ * tstr => type }`              | `Map<string, type>`             |
+//! | `[ * type ]`                      | `type list`                     |
+//! | `[ a, b ]`                        | `A * B`                         |
+//! | `[ name: a, ... ]`                | record                          |
+//! | `"a" / "b"`                       | DU with simple cases            |
+//! | `a / b`                           | DU with `A of a` / `B of b`     |
+//! | `type / null`                     | `type option`                   |
+//! | `{ a // b }`                      | DU of `<Name>Alt<n>` records    |
+//! | `&( a: 1, b: 2 )`                 | DU with simple cases            |
+//!
+//! Group rules are flattened into the maps and arrays that include them,
+//! and other rules that do not define a record or union are inlined.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_cddl::CddlProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = CddlProvider::new();
+//! let schema = provider.resolve_schema("cose.cddl", &ProviderParams::default())?;
+//! let types = provider.generate_types(&schema, "Cose")?;
+//! ```
+
+mod convert;
+mod parser;
+mod types;
+
+pub use types::{
+    CddlFile, ChoiceSource, EntryKind, Group, GroupEntry, MemberKey, Occurrence, Operator, Rule,
+    RuleBody, Type, Type1, Type2, Value,
+};
+
+use convert::Conversion;
+use fusabi_provider_common::source::is_location;
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, SourceValidator,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    ProviderError, ProviderResult,
+};
+
+/// CDDL type provider
+pub struct CddlProvider {
+    generator: TypeGenerator,
+}
+
+impl CddlProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    /// Parse a CDDL specification from string content
+    fn parse_cddl(&self, content: &str) -> ProviderResult<CddlFile> {
+        parser::parse_cddl(content)
+    }
+
+    /// Generate types from a parsed specification
+    fn generate_from_cddl(&self, cddl: &CddlFile, namespace: &str) -> GeneratedTypes {
+        let mut result = GeneratedTypes::new();
+        let conversion = Conversion::new(&self.generator, cddl).convert(cddl);
+
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+        module.types = conversion.types;
+        if !module.types.is_empty() {
+            result.modules.push(module);
+        }
+
+        result
+    }
+
+    fn expect_cddl<'a>(&self, schema: &'a Schema) -> ProviderResult<&'a str> {
+        match schema {
+            Schema::Custom(content) => Ok(content),
+            _ => Err(ProviderError::ParseError("Expected CDDL schema".to_string())),
+        }
+    }
+}
+
+impl Default for CddlProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypeProvider for CddlProvider {
+    fn name(&self) -> &str {
+        "CddlProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        // Every CDDL rule has an `=`
+        let looks_like_cddl =
+            !is_location(source) && (source.contains('\n') || source.contains('='));
+
+        let content = if looks_like_cddl {
+            source.to_string()
+        } else {
+            read_source(source, params)?
+        };
+
+        // Parse the specification to validate it
+        let _cddl = self.parse_cddl(&content)?;
+
+        Ok(Schema::Custom(content))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let cddl = self.parse_cddl(self.expect_cddl(schema)?)?;
+        Ok(self.generate_from_cddl(&cddl, namespace))
+    }
+}
+
+impl SourceValidator for CddlProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let cddl = self.parse_cddl(self.expect_cddl(schema)?)?;
+
+        if cddl.rules.is_empty() {
+            return Ok(vec![Diagnostic::warning(
+                "no-types",
+                "Specification defines no rules; no types will be generated",
+            )]);
+        }
+
+        let conversion = Conversion::new(&self.generator, &cddl).convert(&cddl);
+        Ok(conversion.diagnostics)
+    }
+}
+
+impl DescribedProvider for CddlProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+    use fusabi_type_providers::{RecordDef, TypeDefinition};
+
+    fn generate(cddl: &str) -> GeneratedModule {
+        let provider = CddlProvider::new();
+        let schema = provider.resolve_schema(cddl, &ProviderParams::default()).unwrap();
+        let mut types = provider.generate_types(&schema, "Test").unwrap();
+        assert_eq!(types.modules.len(), 1);
+        types.modules.remove(0)
+    }
+
+    fn record<'a>(module: &'a GeneratedModule, name: &str) -> &'a RecordDef {
+        module
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {} in {:?}", name, names(module)))
+    }
+
+    fn fields(record: &RecordDef) -> Vec<(&str, String)> {
+        record
+            .fields
+            .iter()
+            .map(|(name, ty)| (name.as_str(), ty.to_string()))
+            .collect()
+    }
+
+    fn variants(module: &GeneratedModule, name: &str) -> Vec<String> {
+        module
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Du(du) if du.name == name => {
+                    Some(du.variants.iter().map(|v| v.name.clone()).collect())
+                }
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected DU {}", name))
+    }
+
+    fn names(module: &GeneratedModule) -> Vec<&str> {
+        module
+            .types
+            .iter()
+            .map(|t| match t {
+                TypeDefinition::Record(r) => r.name.as_str(),
+                TypeDefinition::Du(du) => du.name.as_str(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_generate_cose_key() {
+        let module = generate(
+            r#"
+            ; COSE_Key, RFC 9052 section 7
+            COSE_Key = {
+                1 => tstr / int,          ; kty
+                ? 2 => bstr,              ; kid
+                ? 3 => tstr / int,        ; alg
+                ? 4 => [+ (tstr / int)],  ; key_ops
+                crv => int,
+                * label => values
+            }
+
+            crv = -1
+            label = int / tstr
+            values = any
+            "#,
+        );
+
+        assert_eq!(module.path, vec!["Test"]);
+        assert_eq!(
+            fields(record(&module, "CoseKey")),
+            [
+                ("key_1", "CoseKeyKey1".to_string()),
+                ("key_2", "bytes option".to_string()),
+                ("key_3", "CoseKeyKey3 option".to_string()),
+                ("key_4", "CoseKeyKey4Item list option".to_string()),
+                ("crv", "int".to_string()),
+            ]
+        );
+        assert_eq!(variants(&module, "CoseKeyKey1"), ["Tstr", "Int"]);
+        assert_eq!(variants(&module, "Label"), ["Int", "Tstr"]);
+
+        let mut types = GeneratedTypes::new();
+        types.modules.push(module);
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/cddl.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_inline_rule_with_url() {
+        let module = generate(r#"link = { href: tstr } ; see https://www.rfc-editor.org/rfc/rfc8610"#);
+        assert_eq!(names(&module), ["Link"]);
+    }
+
+    #[test]
+    fn test_generate_choices() {
+        let module = generate(
+            r#"
+            reading = {
+                sensor-id: tstr,
+                unit: "celsius" / "fahrenheit",
+                value: float / null,
+                status: status,
+                ? note: tstr,
+                ? tags: { * tstr => tstr },
+                location: { lat: float, lon: float },
+            }
+
+            status = &( ok: 0, degraded: 1, failed: 2 )
+            severity = 1 / 2 / 3
+            "#,
+        );
+
+        assert_eq!(
+            fields(record(&module, "Reading")),
+            [
+                ("sensor_id", "string".to_string()),
+                ("unit", "ReadingUnit".to_string()),
+                ("value", "float option".to_string()),
+                ("status", "Status".to_string()),
+                ("note", "string option".to_string()),
+                ("tags", "Map<string, string> option".to_string()),
+                ("location", "ReadingLocation".to_string()),
+            ]
+        );
+        assert_eq!(variants(&module, "ReadingUnit"), ["Celsius", "Fahrenheit"]);
+        assert_eq!(variants(&module, "Status"), ["Ok", "Degraded", "Failed"]);
+        assert_eq!(record(&module, "ReadingLocation").fields.len(), 2);
+        // Integer choices stay integers and define no type
+        assert!(!names(&module).contains(&"Severity"));
+    }
+
+    #[test]
+    fn test_generate_groups_and_arrays() {
+        let module = generate(
+            r#"
+            envelope<t> = { header, body: t }
+            header = ( id: uint, ? trace: tstr )
+
+            event = envelope<point>
+            point = [x: float, y: float]
+            track = [* point]
+            pair = [tstr, uint]
+            shape = { circle: float // width: float, height: float }
+            "#,
+        );
+
+        assert_eq!(
+            fields(record(&module, "EnvelopePoint")),
+            [
+                ("id", "int".to_string()),
+                ("trace", "string option".to_string()),
+                ("body", "Point".to_string()),
+            ]
+        );
+        assert_eq!(fields(record(&module, "Point")), [("x", "float".to_string()), ("y", "float".to_string())]);
+        assert_eq!(variants(&module, "Shape"), ["Alt1", "Alt2"]);
+        assert_eq!(record(&module, "ShapeAlt2").fields.len(), 2);
+        // Aliases of lists and tuples are inlined
+        let names = names(&module);
+        assert!(!names.contains(&"Track") && !names.contains(&"Pair"));
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = CddlProvider::new();
+        let cddl = r#"
+            message = {
+                id: tstr .size 16,
+                payload: bstr .cbor inner,
+                sender: unknown-type,
+                * tstr => any,
+            }
+            inner = [* uint, ? tstr]
+        "#;
+
+        let report = provider.validate_source(cddl, &ProviderParams::default());
+        assert!(report.is_valid());
+        assert_eq!(report.warnings().next().unwrap().code, "name-unresolved");
+        let codes: Vec<&str> = report.diagnostics.iter().map(|d| d.code.as_str()).collect();
+        assert!(codes.contains(&"constraint-skipped"));
+        assert!(codes.contains(&"lossy-conversion"));
+        assert!(codes.contains(&"extension-skipped"));
+    }
+}
@@ -0,0 +1,745 @@
+//! CDDL parser
+//!
+//! Handles the RFC 8610 grammar: type and group rules, generic parameters,
+//! type and group choices (including the `/=` and `//=` extensions), maps,
+//! arrays, occurrence indicators, member keys, ranges, control operators,
+//! `~` unwrapping, `&` choices and CBOR tags. Byte string literals are
+//! recognized but their content is not kept.
+
+use crate::types::{
+    ChoiceSource, CddlFile, EntryKind, Group, GroupEntry, MemberKey, Occurrence, Operator, Rule,
+    RuleBody, Type, Type1, Type2, Value,
+};
+use fusabi_type_providers::{ProviderError, ProviderResult};
+
+/// Parse a CDDL specification from string content
+pub fn parse_cddl(content: &str) -> ProviderResult<CddlFile> {
+    let mut parser = Parser::new(content);
+    parser.parse_file()
+}
+
+/// Simple CDDL parser
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    // Assignments
+    Assign,
+    TypeChoiceAssign,
+    GroupChoiceAssign,
+
+    // Symbols
+    Slash,
+    DoubleSlash,
+    Arrow,
+    Colon,
+    Comma,
+    Question,
+    Star,
+    Plus,
+    LeftParen,
+    RightParen,
+    LeftBrace,
+    RightBrace,
+    LeftBracket,
+    RightBracket,
+    LeftAngle,
+    RightAngle,
+    Tilde,
+    Ampersand,
+    Caret,
+    /// `..` or `...`
+    Range,
+    /// `.size`, `.regexp`, ...
+    Control(String),
+    /// `#`, `#6.32`, `#1`: the text after the hash
+    Hash(String),
+
+    // Literals
+    Identifier(String),
+    Number(String),
+    Text(String),
+    Bytes,
+
+    // End of file
+    Eof,
+}
+
+impl Parser {
+    fn new(content: &str) -> Self {
+        let tokens = tokenize(content);
+        Self { tokens, pos: 0 }
+    }
+
+    fn current(&self) -> &Token {
+        self.tokens.get(self.pos).unwrap_or(&Token::Eof)
+    }
+
+    fn peek(&self, offset: usize) -> &Token {
+        self.tokens.get(self.pos + offset).unwrap_or(&Token::Eof)
+    }
+
+    fn advance(&mut self) {
+        if self.pos < self.tokens.len() {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: Token) -> ProviderResult<()> {
+        if self.current() == &expected {
+            self.advance();
+            Ok(())
+        } else {
+            Err(ProviderError::ParseError(format!(
+                "Expected {:?}, got {:?}",
+                expected,
+                self.current()
+            )))
+        }
+    }
+
+    fn expect_identifier(&mut self) -> ProviderResult<String> {
+        match self.current() {
+            Token::Identifier(s) => {
+                let result = s.clone();
+                self.advance();
+                Ok(result)
+            }
+            _ => Err(ProviderError::ParseError(format!(
+                "Expected identifier, got {:?}",
+                self.current()
+            ))),
+        }
+    }
+
+    fn skip_comma(&mut self) {
+        if self.current() == &Token::Comma {
+            self.advance();
+        }
+    }
+
+    fn parse_file(&mut self) -> ProviderResult<CddlFile> {
+        let mut file = CddlFile::default();
+
+        while self.current() != &Token::Eof {
+            let name = self.expect_identifier()?;
+            let params = if self.current() == &Token::LeftAngle {
+                self.parse_generic_params()?
+            } else {
+                Vec::new()
+            };
+
+            let assign = self.current().clone();
+            self.advance();
+            match assign {
+                Token::Assign => {
+                    let body = self.parse_rule_body()?;
+                    file.rules.push(Rule { name, params, body });
+                }
+                Token::TypeChoiceAssign => {
+                    let extension = self.parse_type()?;
+                    match file.rules.iter_mut().find(|r| r.name == name) {
+                        Some(Rule { body: RuleBody::Type(existing), .. }) => {
+                            existing.choices.extend(extension.choices);
+                        }
+                        Some(_) => {
+                            return Err(ProviderError::ParseError(format!(
+                                "`{} /=` extends a group rule",
+                                name
+                            )));
+                        }
+                        None => file.rules.push(Rule { name, params, body: RuleBody::Type(extension) }),
+                    }
+                }
+                Token::GroupChoiceAssign => {
+                    let extension = self.parse_group_entry_rule()?;
+                    match file.rules.iter_mut().find(|r| r.name == name) {
+                        Some(Rule { body: RuleBody::Group(existing), .. }) => {
+                            existing.choices.extend(extension.choices);
+                        }
+                        Some(_) => {
+                            return Err(ProviderError::ParseError(format!(
+                                "`{} //=` extends a type rule",
+                                name
+                            )));
+                        }
+                        None => file.rules.push(Rule { name, params, body: RuleBody::Group(extension) }),
+                    }
+                }
+                other => {
+                    return Err(ProviderError::ParseError(format!(
+                        "Expected `=` after rule `{}`, got {:?}",
+                        name, other
+                    )));
+                }
+            }
+        }
+
+        Ok(file)
+    }
+
+    fn parse_generic_params(&mut self) -> ProviderResult<Vec<String>> {
+        self.expect(Token::LeftAngle)?;
+        let mut params = Vec::new();
+        while self.current() != &Token::RightAngle && self.current() != &Token::Eof {
+            params.push(self.expect_identifier()?);
+            self.skip_comma();
+        }
+        self.expect(Token::RightAngle)?;
+        Ok(params)
+    }
+
+    /// A rule body is a type unless it is a parenthesized group or a bare
+    /// group entry (`name = ? key: type`)
+    fn parse_rule_body(&mut self) -> ProviderResult<RuleBody> {
+        if self.current() == &Token::LeftParen {
+            self.advance();
+            let group = self.parse_group(Token::RightParen)?;
+            self.expect(Token::RightParen)?;
+
+            // `(type)` or `(a / b) / c` is a parenthesized type
+            let as_type = match group.entries() {
+                Some([GroupEntry { occurrence, kind: EntryKind::Member { key: None, value } }])
+                    if *occurrence == Occurrence::ONCE =>
+                {
+                    Some(value.clone())
+                }
+                _ => None,
+            };
+            return Ok(match as_type {
+                Some(inner) => {
+                    let mut ty = Type { choices: vec![self.parse_type1_after(Type2::Parens(inner))?] };
+                    while self.current() == &Token::Slash {
+                        self.advance();
+                        ty.choices.push(self.parse_type1()?);
+                    }
+                    RuleBody::Type(ty)
+                }
+                None => RuleBody::Group(group),
+            });
+        }
+
+        if self.starts_group_entry() {
+            return Ok(RuleBody::Group(self.parse_group_entry_rule()?));
+        }
+
+        Ok(RuleBody::Type(self.parse_type()?))
+    }
+
+    /// Whether the current tokens start an occurrence indicator or a keyed
+    /// member rather than a type
+    fn starts_group_entry(&self) -> bool {
+        match self.current() {
+            Token::Question | Token::Star | Token::Plus => true,
+            Token::Identifier(_) | Token::Text(_) | Token::Number(_) => {
+                matches!(self.peek(1), Token::Colon | Token::Arrow)
+                    || (matches!(self.current(), Token::Number(_)) && self.peek(1) == &Token::Star)
+            }
+            _ => false,
+        }
+    }
+
+    /// Group rule body: `( group )` or a single entry
+    fn parse_group_entry_rule(&mut self) -> ProviderResult<Group> {
+        if self.current() == &Token::LeftParen {
+            self.advance();
+            let group = self.parse_group(Token::RightParen)?;
+            self.expect(Token::RightParen)?;
+            return Ok(group);
+        }
+        Ok(Group { choices: vec![vec![self.parse_group_entry()?]] })
+    }
+
+    /// Parse group entries up to (not including) `close`
+    fn parse_group(&mut self, close: Token) -> ProviderResult<Group> {
+        let mut group = Group { choices: vec![Vec::new()] };
+        while self.current() != &close && self.current() != &Token::Eof {
+            if self.current() == &Token::DoubleSlash {
+                self.advance();
+                group.choices.push(Vec::new());
+                continue;
+            }
+            let entry = self.parse_group_entry()?;
+            group.choices.last_mut().expect("group has a choice").push(entry);
+            self.skip_comma();
+        }
+        Ok(group)
+    }
+
+    fn parse_occurrence(&mut self) -> Occurrence {
+        match self.current().clone() {
+            Token::Question => {
+                self.advance();
+                Occurrence { min: 0, max: Some(1) }
+            }
+            Token::Plus => {
+                self.advance();
+                Occurrence { min: 1, max: None }
+            }
+            Token::Star => {
+                self.advance();
+                Occurrence { min: 0, max: self.parse_occurrence_bound() }
+            }
+            Token::Number(min) if self.peek(1) == &Token::Star => {
+                self.advance();
+                self.advance();
+                Occurrence {
+                    min: min.parse().unwrap_or(0),
+                    max: self.parse_occurrence_bound(),
+                }
+            }
+            _ => Occurrence::ONCE,
+        }
+    }
+
+    /// Upper bound after `*`, unless the number is a member key (`* 1 => x`)
+    fn parse_occurrence_bound(&mut self) -> Option<u64> {
+        match self.current().clone() {
+            Token::Number(max) if !matches!(self.peek(1), Token::Colon | Token::Arrow) => {
+                self.advance();
+                max.parse().ok()
+            }
+            _ => None,
+        }
+    }
+
+    fn parse_group_entry(&mut self) -> ProviderResult<GroupEntry> {
+        let occurrence = self.parse_occurrence();
+
+        // `name: type` and `"name": type` / `1: type`
+        if self.peek(1) == &Token::Colon {
+            let key = match self.current().clone() {
+                Token::Identifier(name) => Some(MemberKey::Bareword(name)),
+                Token::Text(text) => Some(MemberKey::Value(Value::Text(text))),
+                Token::Number(number) => Some(MemberKey::Value(parse_number(&number)?)),
+                _ => None,
+            };
+            if let Some(key) = key {
+                self.advance();
+                self.advance();
+                let value = self.parse_type()?;
+                return Ok(GroupEntry { occurrence, kind: EntryKind::Member { key: Some(key), value } });
+            }
+        }
+
+        if self.current() == &Token::LeftParen {
+            self.advance();
+            let group = self.parse_group(Token::RightParen)?;
+            self.expect(Token::RightParen)?;
+            return Ok(GroupEntry { occurrence, kind: EntryKind::Group(group) });
+        }
+
+        let ty = self.parse_type()?;
+        if self.current() == &Token::Caret {
+            self.advance();
+        }
+        if self.current() == &Token::Arrow {
+            self.advance();
+            let key = match ty.single() {
+                Some(Type1 { base: Type2::Value(value), operator: None }) => MemberKey::Value(value.clone()),
+                _ => MemberKey::Type(ty),
+            };
+            let value = self.parse_type()?;
+            return Ok(GroupEntry { occurrence, kind: EntryKind::Member { key: Some(key), value } });
+        }
+
+        Ok(GroupEntry { occurrence, kind: EntryKind::Member { key: None, value: ty } })
+    }
+
+    fn parse_type(&mut self) -> ProviderResult<Type> {
+        let mut choices = vec![self.parse_type1()?];
+        while self.current() == &Token::Slash {
+            self.advance();
+            choices.push(self.parse_type1()?);
+        }
+        Ok(Type { choices })
+    }
+
+    fn parse_type1(&mut self) -> ProviderResult<Type1> {
+        let base = self.parse_type2()?;
+        self.parse_type1_after(base)
+    }
+
+    /// Parse an optional range or control operator after a type
+    fn parse_type1_after(&mut self, base: Type2) -> ProviderResult<Type1> {
+        let operator = match self.current().clone() {
+            Token::Range => {
+                self.advance();
+                Some(Operator::Range(self.parse_type2()?))
+            }
+            Token::Control(name) => {
+                self.advance();
+                Some(Operator::Control(name, self.parse_type2()?))
+            }
+            _ => None,
+        };
+        Ok(Type1 { base, operator })
+    }
+
+    fn parse_type2(&mut self) -> ProviderResult<Type2> {
+        let token = self.current().clone();
+        self.advance();
+
+        Ok(match token {
+            Token::Text(text) => Type2::Value(Value::Text(text)),
+            Token::Number(number) => Type2::Value(parse_number(&number)?),
+            Token::Bytes => Type2::Value(Value::Bytes),
+            Token::Identifier(name) => Type2::Name(name, self.parse_generic_args()?),
+            Token::LeftParen => {
+                let inner = self.parse_type()?;
+                self.expect(Token::RightParen)?;
+                Type2::Parens(inner)
+            }
+            Token::LeftBrace => {
+                let group = self.parse_group(Token::RightBrace)?;
+                self.expect(Token::RightBrace)?;
+                Type2::Map(group)
+            }
+            Token::LeftBracket => {
+                let group = self.parse_group(Token::RightBracket)?;
+                self.expect(Token::RightBracket)?;
+                Type2::Array(group)
+            }
+            Token::Tilde => {
+                let name = self.expect_identifier()?;
+                self.parse_generic_args()?;
+                Type2::Unwrap(name)
+            }
+            Token::Ampersand => {
+                if self.current() == &Token::LeftParen {
+                    self.advance();
+                    let group = self.parse_group(Token::RightParen)?;
+                    self.expect(Token::RightParen)?;
+                    Type2::ChoiceFrom(ChoiceSource::Group(group))
+                } else {
+                    let name = self.expect_identifier()?;
+                    self.parse_generic_args()?;
+                    Type2::ChoiceFrom(ChoiceSource::Name(name))
+                }
+            }
+            Token::Hash(spec) => {
+                let mut parts = spec.splitn(2, '.');
+                match parts.next().filter(|m| !m.is_empty()) {
+                    None => Type2::Any,
+                    // `#6.32(uri)` is a tagged data item
+                    Some("6") if self.current() == &Token::LeftParen => {
+                        self.advance();
+                        let inner = self.parse_type()?;
+                        self.expect(Token::RightParen)?;
+                        Type2::Tagged(Box::new(inner))
+                    }
+                    Some(major) => Type2::Major(major.parse().map_err(|_| {
+                        ProviderError::ParseError(format!("Invalid major type `#{}`", spec))
+                    })?),
+                }
+            }
+            other => {
+                return Err(ProviderError::ParseError(format!("Expected type, got {:?}", other)));
+            }
+        })
+    }
+
+    fn parse_generic_args(&mut self) -> ProviderResult<Vec<Type>> {
+        if self.current() != &Token::LeftAngle {
+            return Ok(Vec::new());
+        }
+        self.advance();
+        let mut args = Vec::new();
+        while self.current() != &Token::RightAngle && self.current() != &Token::Eof {
+            args.push(Type { choices: vec![self.parse_type1()?] });
+            self.skip_comma();
+        }
+        self.expect(Token::RightAngle)?;
+        Ok(args)
+    }
+}
+
+fn parse_number(number: &str) -> ProviderResult<Value> {
+    let (negative, digits) = match number.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, number),
+    };
+    let parsed = if let Some(hex) = digits.strip_prefix("0x") {
+        i64::from_str_radix(hex, 16).ok().map(Value::Int)
+    } else if let Some(binary) = digits.strip_prefix("0b") {
+        i64::from_str_radix(binary, 2).ok().map(Value::Int)
+    } else if digits.contains(['.', 'e', 'E']) {
+        digits.parse::<f64>().ok().map(Value::Float)
+    } else {
+        digits.parse::<i64>().ok().map(Value::Int)
+    };
+    match parsed {
+        Some(Value::Int(n)) if negative => Ok(Value::Int(-n)),
+        Some(Value::Float(n)) if negative => Ok(Value::Float(-n)),
+        Some(value) => Ok(value),
+        None => Err(ProviderError::ParseError(format!("Invalid number: {}", number))),
+    }
+}
+
+/// Tokenize a CDDL specification
+fn tokenize(content: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = content.chars().collect();
+    let mut i = 0;
+
+    let is_ident_start = |c: char| c.is_ascii_alphabetic() || matches!(c, '@' | '_' | '$');
+    let is_ident_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '@' | '_' | '$' | '-' | '.');
+
+    while i < chars.len() {
+        let ch = chars[i];
+        let next = chars.get(i + 1).copied();
+        match ch {
+            ' ' | '\t' | '\n' | '\r' => i += 1,
+            ';' => {
+                // Line comment
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '=' if next == Some('>') => {
+                tokens.push(Token::Arrow);
+                i += 2;
+            }
+            '=' => {
+                tokens.push(Token::Assign);
+                i += 1;
+            }
+            '/' => {
+                if next == Some('/') && chars.get(i + 2) == Some(&'=') {
+                    tokens.push(Token::GroupChoiceAssign);
+                    i += 3;
+                } else if next == Some('/') {
+                    tokens.push(Token::DoubleSlash);
+                    i += 2;
+                } else if next == Some('=') {
+                    tokens.push(Token::TypeChoiceAssign);
+                    i += 2;
+                } else {
+                    tokens.push(Token::Slash);
+                    i += 1;
+                }
+            }
+            '.' if next == Some('.') => {
+                tokens.push(Token::Range);
+                i += 2;
+                if chars.get(i) == Some(&'.') {
+                    i += 1;
+                }
+            }
+            '.' if next.is_some_and(is_ident_start) => {
+                let start = i + 1;
+                i = start;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '-' || chars[i] == '_') {
+                    i += 1;
+                }
+                tokens.push(Token::Control(chars[start..i].iter().collect()));
+            }
+            '#' => {
+                let start = i + 1;
+                i = start;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                tokens.push(Token::Hash(chars[start..i].iter().collect()));
+            }
+            '"' => {
+                i += 1;
+                let mut text = String::new();
+                while i < chars.len() && chars[i] != '"' {
+                    if chars[i] == '\\' && i + 1 < chars.len() {
+                        i += 1;
+                    }
+                    text.push(chars[i]);
+                    i += 1;
+                }
+                i += 1;
+                tokens.push(Token::Text(text));
+            }
+            '\'' => {
+                i += 1;
+                while i < chars.len() && chars[i] != '\'' {
+                    i += 1;
+                }
+                i += 1;
+                tokens.push(Token::Bytes);
+            }
+            '0'..='9' | '-' => {
+                let start = i;
+                i += 1;
+                let hex = ch == '0' && matches!(next, Some('x' | 'b'));
+                if hex {
+                    i += 1;
+                }
+                while i < chars.len() {
+                    let c = chars[i];
+                    let fraction = c == '.' && chars.get(i + 1).is_some_and(|d| d.is_ascii_digit());
+                    let exponent = matches!(c, 'e' | 'E') && !hex;
+                    let sign = matches!(c, '+' | '-') && matches!(chars[i - 1], 'e' | 'E') && !hex;
+                    if c.is_ascii_digit() || (hex && c.is_ascii_hexdigit()) || fraction || exponent || sign {
+                        i += 1;
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Number(chars[start..i].iter().collect()));
+            }
+            c if is_ident_start(c) => {
+                let start = i;
+                while i < chars.len() && is_ident_char(chars[i]) {
+                    i += 1;
+                }
+                // Identifiers cannot end in `.` or `-`
+                while matches!(chars[i - 1], '.' | '-') {
+                    i -= 1;
+                }
+                let ident: String = chars[start..i].iter().collect();
+                // `h'00ff'` and `b64'...'` byte strings
+                if (ident == "h" || ident == "b64") && chars.get(i) == Some(&'\'') {
+                    continue;
+                }
+                tokens.push(Token::Identifier(ident));
+            }
+            _ => {
+                tokens.push(match ch {
+                    ':' => Token::Colon,
+                    ',' => Token::Comma,
+                    '?' => Token::Question,
+                    '*' => Token::Star,
+                    '+' => Token::Plus,
+                    '(' => Token::LeftParen,
+                    ')' => Token::RightParen,
+                    '{' => Token::LeftBrace,
+                    '}' => Token::RightBrace,
+                    '[' => Token::LeftBracket,
+                    ']' => Token::RightBracket,
+                    '<' => Token::LeftAngle,
+                    '>' => Token::RightAngle,
+                    '~' => Token::Tilde,
+                    '&' => Token::Ampersand,
+                    '^' => Token::Caret,
+                    _ => {
+                        i += 1;
+                        continue;
+                    }
+                });
+                i += 1;
+            }
+        }
+    }
+
+    tokens.push(Token::Eof);
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_map_rule() {
+        let cddl = r#"
+            ; A person
+            person = {
+              name: tstr,
+              ? age: uint .le 150,
+              "e-mail": tstr,
+              1 => int,
+              * tstr => any
+            }
+        "#;
+
+        let file = parse_cddl(cddl).unwrap();
+        let RuleBody::Type(ty) = &file.rules[0].body else {
+            panic!("Expected type rule");
+        };
+        let Some(Type1 { base: Type2::Map(group), .. }) = ty.single() else {
+            panic!("Expected map");
+        };
+        let entries = group.entries().unwrap();
+        assert_eq!(entries.len(), 5);
+        assert!(entries[1].occurrence.is_optional());
+
+        let keys: Vec<&MemberKey> = entries
+            .iter()
+            .filter_map(|e| match &e.kind {
+                EntryKind::Member { key: Some(key), .. } => Some(key),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(keys[0], &MemberKey::Bareword("name".to_string()));
+        assert_eq!(keys[2], &MemberKey::Value(Value::Text("e-mail".to_string())));
+        assert_eq!(keys[3], &MemberKey::Value(Value::Int(1)));
+        assert!(matches!(keys[4], MemberKey::Type(_)));
+        assert_eq!(entries[4].occurrence, Occurrence { min: 0, max: None });
+
+        let EntryKind::Member { value, .. } = &entries[1].kind else {
+            panic!("Expected member");
+        };
+        assert!(matches!(value.choices[0].operator, Some(Operator::Control(ref name, _)) if name == "le"));
+    }
+
+    #[test]
+    fn test_parse_groups_and_choices() {
+        let cddl = r#"
+            header-fields = (id: uint, ? created: tdate)
+            color = "red" / "green"
+            color /= "blue"
+            point = [x: int, y: int]
+            tags = [1*10 tstr]
+            message<t> = { header-fields, body: t // error: tstr }
+            port = 0..65535
+            uri-ref = #6.32(tstr)
+            $$extension //= (trace: bstr)
+        "#;
+
+        let file = parse_cddl(cddl).unwrap();
+        let names: Vec<&str> = file.rules.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["header-fields", "color", "point", "tags", "message", "port", "uri-ref", "$$extension"]
+        );
+
+        assert!(matches!(file.rules[0].body, RuleBody::Group(_)));
+        let RuleBody::Type(color) = &file.rules[1].body else {
+            panic!("Expected type rule");
+        };
+        assert_eq!(color.choices.len(), 3);
+
+        let RuleBody::Type(tags) = &file.rules[3].body else {
+            panic!("Expected type rule");
+        };
+        let Some(Type1 { base: Type2::Array(group), .. }) = tags.single() else {
+            panic!("Expected array");
+        };
+        assert_eq!(group.entries().unwrap()[0].occurrence, Occurrence { min: 1, max: Some(10) });
+
+        assert_eq!(file.rules[4].params, ["t"]);
+        let RuleBody::Type(message) = &file.rules[4].body else {
+            panic!("Expected type rule");
+        };
+        let Some(Type1 { base: Type2::Map(group), .. }) = message.single() else {
+            panic!("Expected map");
+        };
+        assert_eq!(group.choices.len(), 2);
+
+        let RuleBody::Type(port) = &file.rules[5].body else {
+            panic!("Expected type rule");
+        };
+        assert!(matches!(port.choices[0].operator, Some(Operator::Range(_))));
+
+        let RuleBody::Type(uri) = &file.rules[6].body else {
+            panic!("Expected type rule");
+        };
+        assert!(matches!(uri.choices[0].base, Type2::Tagged(_)));
+    }
+
+    #[test]
+    fn test_invalid_cddl() {
+        assert!(parse_cddl("person { name: tstr }").is_err());
+        assert!(parse_cddl("person = { name: }").is_err());
+        assert!(parse_cddl("a = (x: int)\na /= int").is_err());
+    }
+}
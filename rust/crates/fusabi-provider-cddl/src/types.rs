@@ -0,0 +1,176 @@
+//! CDDL AST type definitions
+
+/// Represents a complete CDDL specification
+#[derive(Debug, Clone, Default)]
+pub struct CddlFile {
+    /// Rules in order of definition; `/=` and `//=` extensions are merged
+    /// into the rule they extend
+    pub rules: Vec<Rule>,
+}
+
+/// A CDDL rule: `name = type` or `name = (group)`
+#[derive(Debug, Clone)]
+pub struct Rule {
+    /// Rule name
+    pub name: String,
+    /// Generic parameters (`message<t, v> = ...`)
+    pub params: Vec<String>,
+    /// Rule body
+    pub body: RuleBody,
+}
+
+/// Body of a rule
+#[derive(Debug, Clone)]
+pub enum RuleBody {
+    /// Type rule
+    Type(Type),
+    /// Group rule
+    Group(Group),
+}
+
+/// A type: one or more choices separated by `/`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Type {
+    pub choices: Vec<Type1>,
+}
+
+/// A type choice with an optional range or control operator
+#[derive(Debug, Clone, PartialEq)]
+pub struct Type1 {
+    pub base: Type2,
+    pub operator: Option<Operator>,
+}
+
+/// Range or control operator applied to a type
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operator {
+    /// `min..max` or `min...max`; the upper bound
+    Range(Type2),
+    /// `.size`, `.regexp`, `.default`, ... and its argument
+    Control(String, Type2),
+}
+
+/// A single type
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type2 {
+    /// Literal value
+    Value(Value),
+    /// Type or rule name with generic arguments
+    Name(String, Vec<Type>),
+    /// `( type )`
+    Parens(Type),
+    /// `{ group }`
+    Map(Group),
+    /// `[ group ]`
+    Array(Group),
+    /// `~name`: the group inside a map or array rule
+    Unwrap(String),
+    /// `&( group )` or `&name`: a choice from the group's values
+    ChoiceFrom(ChoiceSource),
+    /// `#6.n(type)`
+    Tagged(Box<Type>),
+    /// `#n` or `#n.ai`: any data item of a major type
+    Major(u8),
+    /// `#`: any data item
+    Any,
+}
+
+/// Group that `&` takes its choices from
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChoiceSource {
+    Group(Group),
+    Name(String),
+}
+
+/// Literal value
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Text(String),
+    Int(i64),
+    Float(f64),
+    Bytes,
+}
+
+/// A group: one or more choices separated by `//`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Group {
+    pub choices: Vec<Vec<GroupEntry>>,
+}
+
+/// A group entry with its occurrence indicator
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupEntry {
+    pub occurrence: Occurrence,
+    pub kind: EntryKind,
+}
+
+/// Occurrence indicator: `?`, `*`, `+` or `n*m`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Occurrence {
+    pub min: u64,
+    /// Upper bound; `None` is unbounded
+    pub max: Option<u64>,
+}
+
+/// Kind of group entry
+#[derive(Debug, Clone, PartialEq)]
+pub enum EntryKind {
+    /// `key: type`, `key => type` or a bare type
+    Member { key: Option<MemberKey>, value: Type },
+    /// `( group )`
+    Group(Group),
+}
+
+/// Key of a member
+#[derive(Debug, Clone, PartialEq)]
+pub enum MemberKey {
+    /// `name:`
+    Bareword(String),
+    /// `"name":`, `1:` or `"name" =>`
+    Value(Value),
+    /// `type =>`, e.g. `tstr => any`
+    Type(Type),
+}
+
+impl Occurrence {
+    /// Exactly once: no indicator
+    pub const ONCE: Occurrence = Occurrence { min: 1, max: Some(1) };
+
+    /// `?`: zero or one
+    pub fn is_optional(&self) -> bool {
+        self.min == 0 && self.max == Some(1)
+    }
+
+    /// More than one occurrence allowed
+    pub fn is_repeated(&self) -> bool {
+        self.max != Some(1)
+    }
+}
+
+impl Type {
+    /// The single choice of a type without alternatives
+    pub fn single(&self) -> Option<&Type1> {
+        match self.choices.as_slice() {
+            [only] => Some(only),
+            _ => None,
+        }
+    }
+
+    /// The name of a type that is just a name reference
+    pub fn as_name(&self) -> Option<&str> {
+        match self.single() {
+            Some(Type1 { base: Type2::Name(name, args), operator: None }) if args.is_empty() => Some(name),
+            _ => None,
+        }
+    }
+}
+
+impl Group {
+    /// Group with a single choice
+    pub fn entries(&self) -> Option<&[GroupEntry]> {
+        match self.choices.as_slice() {
+            [only] => Some(only),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,263 @@
+//! Extension key dictionaries
+//!
+//! A dictionary names the extension keys a pipeline expects, with their
+//! types, on top of the keys the CEF and LEEF specifications predefine:
+//!
+//! ```yaml
+//! format: cef          # cef, leef or both (the default)
+//! standard: true       # include the predefined keys (the default)
+//! keys:
+//!   src: ip
+//!   tenantId: {type: long, required: true}
+//! ```
+//!
+//! Key types are `string`, `int`, `long`, `float`, `bool`, `ip`, `ipv6`,
+//! `mac`, `hostname` and `timestamp`.
+
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+/// ArcSight CEF extension keys from the Common Event Format specification
+pub const CEF_STANDARD_KEYS: &[(&str, &str)] = &[
+    ("act", "string"),
+    ("app", "string"),
+    ("cat", "string"),
+    ("cnt", "int"),
+    ("cn1", "long"),
+    ("cn1Label", "string"),
+    ("cn2", "long"),
+    ("cn2Label", "string"),
+    ("cn3", "long"),
+    ("cn3Label", "string"),
+    ("cs1", "string"),
+    ("cs1Label", "string"),
+    ("cs2", "string"),
+    ("cs2Label", "string"),
+    ("cs3", "string"),
+    ("cs3Label", "string"),
+    ("cs4", "string"),
+    ("cs4Label", "string"),
+    ("cs5", "string"),
+    ("cs5Label", "string"),
+    ("cs6", "string"),
+    ("cs6Label", "string"),
+    ("deviceDirection", "int"),
+    ("deviceExternalId", "string"),
+    ("deviceFacility", "string"),
+    ("dhost", "hostname"),
+    ("dmac", "mac"),
+    ("dntdom", "string"),
+    ("dpid", "int"),
+    ("dpriv", "string"),
+    ("dproc", "string"),
+    ("dpt", "int"),
+    ("dst", "ip"),
+    ("duid", "string"),
+    ("duser", "string"),
+    ("dvc", "ip"),
+    ("dvchost", "hostname"),
+    ("dvcpid", "int"),
+    ("end", "timestamp"),
+    ("externalId", "string"),
+    ("fname", "string"),
+    ("fsize", "int"),
+    ("in", "int"),
+    ("msg", "string"),
+    ("out", "int"),
+    ("outcome", "string"),
+    ("proto", "string"),
+    ("reason", "string"),
+    ("request", "string"),
+    ("requestMethod", "string"),
+    ("rt", "timestamp"),
+    ("shost", "hostname"),
+    ("smac", "mac"),
+    ("sntdom", "string"),
+    ("spid", "int"),
+    ("spriv", "string"),
+    ("sproc", "string"),
+    ("spt", "int"),
+    ("src", "ip"),
+    ("start", "timestamp"),
+    ("suid", "string"),
+    ("suser", "string"),
+];
+
+/// QRadar LEEF predefined event attributes
+pub const LEEF_STANDARD_KEYS: &[(&str, &str)] = &[
+    ("cat", "string"),
+    ("devTime", "timestamp"),
+    ("devTimeFormat", "string"),
+    ("proto", "string"),
+    ("sev", "int"),
+    ("src", "ip"),
+    ("dst", "ip"),
+    ("srcPort", "int"),
+    ("dstPort", "int"),
+    ("srcPreNAT", "ip"),
+    ("dstPreNAT", "ip"),
+    ("srcPostNAT", "ip"),
+    ("dstPostNAT", "ip"),
+    ("srcPreNATPort", "int"),
+    ("dstPreNATPort", "int"),
+    ("srcPostNATPort", "int"),
+    ("dstPostNATPort", "int"),
+    ("srcMAC", "mac"),
+    ("dstMAC", "mac"),
+    ("usrName", "string"),
+    ("accountName", "string"),
+    ("groupID", "string"),
+    ("identSrc", "ip"),
+    ("identHostName", "hostname"),
+    ("identNetBios", "string"),
+    ("identGrpName", "string"),
+    ("identMAC", "mac"),
+    ("vSrc", "ip"),
+    ("vSrcName", "string"),
+    ("role", "string"),
+    ("realm", "string"),
+    ("policy", "string"),
+    ("resource", "string"),
+    ("url", "string"),
+    ("srcBytes", "long"),
+    ("dstBytes", "long"),
+    ("srcPackets", "long"),
+    ("dstPackets", "long"),
+    ("totalPackets", "long"),
+    ("isLoginEvent", "bool"),
+    ("isLogoutEvent", "bool"),
+];
+
+/// A log format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    Cef,
+    Leef,
+}
+
+impl LogFormat {
+    /// The formats a `format` value selects
+    pub fn parse_list(value: &str) -> ProviderResult<Vec<LogFormat>> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "cef" => Ok(vec![LogFormat::Cef]),
+            "leef" => Ok(vec![LogFormat::Leef]),
+            "both" | "" => Ok(vec![LogFormat::Cef, LogFormat::Leef]),
+            other => Err(ProviderError::InvalidSource(format!(
+                "Unknown format `{}`; expected `cef`, `leef` or `both`",
+                other
+            ))),
+        }
+    }
+
+    pub fn standard_keys(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            LogFormat::Cef => CEF_STANDARD_KEYS,
+            LogFormat::Leef => LEEF_STANDARD_KEYS,
+        }
+    }
+}
+
+/// A parsed key dictionary
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyDictionary {
+    pub formats: Vec<LogFormat>,
+    /// Include the predefined keys of each format
+    pub standard: bool,
+    /// User keys, in dictionary order
+    pub keys: Vec<KeyDef>,
+}
+
+impl Default for KeyDictionary {
+    fn default() -> Self {
+        Self {
+            formats: vec![LogFormat::Cef, LogFormat::Leef],
+            standard: true,
+            keys: Vec::new(),
+        }
+    }
+}
+
+/// An extension key
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyDef {
+    pub key: String,
+    #[serde(rename = "type")]
+    pub key_type: String,
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// Parse a dictionary from YAML or JSON
+pub fn parse_dictionary(content: &str) -> ProviderResult<KeyDictionary> {
+    let value: Value = serde_yaml::from_str(content)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid key dictionary: {}", e)))?;
+    let Value::Mapping(map) = value else {
+        return Err(ProviderError::ParseError("Expected a key dictionary mapping".to_string()));
+    };
+
+    let mut dictionary = KeyDictionary::default();
+    if let Some(format) = map.get("format") {
+        let format = format
+            .as_str()
+            .ok_or_else(|| ProviderError::ParseError("`format` must be a string".to_string()))?;
+        dictionary.formats = LogFormat::parse_list(format)?;
+    }
+    if let Some(standard) = map.get("standard") {
+        dictionary.standard = standard
+            .as_bool()
+            .ok_or_else(|| ProviderError::ParseError("`standard` must be true or false".to_string()))?;
+    }
+
+    let keys = match map.get("keys") {
+        Some(Value::Mapping(keys)) => keys,
+        Some(Value::Null) | None => return Ok(dictionary),
+        Some(_) => return Err(ProviderError::ParseError("`keys` must map keys to types".to_string())),
+    };
+    for (key, definition) in keys {
+        let key = key
+            .as_str()
+            .ok_or_else(|| ProviderError::ParseError(format!("Key names must be strings, got {:?}", key)))?;
+        let (key_type, required) = match definition {
+            Value::String(key_type) => (key_type.clone(), false),
+            Value::Mapping(definition) => (
+                definition.get("type").and_then(Value::as_str).unwrap_or("string").to_string(),
+                definition.get("required").and_then(Value::as_bool).unwrap_or(false),
+            ),
+            _ => {
+                return Err(ProviderError::ParseError(format!(
+                    "Key `{}` must have a type name or a mapping with `type`",
+                    key
+                )))
+            }
+        };
+        dictionary.keys.push(KeyDef {
+            key: key.to_string(),
+            key_type,
+            required,
+        });
+    }
+    Ok(dictionary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dictionary() {
+        let dictionary = parse_dictionary(
+            "format: leef\nstandard: false\nkeys:\n  tenantId: {type: long, required: true}\n  src: ip\n  note: {}\n",
+        )
+        .unwrap();
+        assert_eq!(dictionary.formats, [LogFormat::Leef]);
+        assert!(!dictionary.standard);
+        let keys: Vec<(&str, &str, bool)> =
+            dictionary.keys.iter().map(|k| (k.key.as_str(), k.key_type.as_str(), k.required)).collect();
+        assert_eq!(keys, [("tenantId", "long", true), ("src", "ip", false), ("note", "string", false)]);
+
+        assert!(parse_dictionary("format: syslog").is_err());
+        assert_eq!(parse_dictionary("{}").unwrap(), KeyDictionary::default());
+    }
+}
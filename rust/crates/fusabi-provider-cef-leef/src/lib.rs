@@ -0,0 +1,378 @@
+//! CEF / LEEF Type Provider
+//!
+//! Generates Fusabi types for ArcSight Common Event Format and QRadar Log
+//! Event Extended Format messages, the two line formats SIEMs ingest. The
+//! regex provider can type a header; this provider also types the
+//! extension keys, from the specifications and a user-supplied dictionary.
+//!
+//! # Sources
+//!
+//! - `cef`, `leef` or `both`: the envelopes with the predefined keys only
+//! - A key dictionary, inline or from a file; see [`dictionary`]
+//!
+//! # Generated Types
+//!
+//! For CEF (`CEF:0|Vendor|Product|1.0|100|Name|5|src=10.0.0.1 spt=443`):
+//!
+//! | Record         | Fields                                                   |
+//! |----------------|----------------------------------------------------------|
+//! | `CefEvent`     | `version: int`, `deviceVendor`, `deviceProduct`, `deviceVersion`, `signatureId`, `name`, `severity: string`, `extension: CefExtension` |
+//! | `CefExtension` | one field per key                                        |
+//!
+//! For LEEF (`LEEF:2.0|Vendor|Product|1.0|Login|^|src=10.0.0.1^usrName=bob`):
+//!
+//! | Record           | Fields                                                 |
+//! |------------------|--------------------------------------------------------|
+//! | `LeefEvent`      | `version`, `vendor`, `product`, `productVersion`, `eventId`, `delimiter: string option`, `attributes: LeefAttributes` |
+//! | `LeefAttributes` | one field per key                                      |
+//!
+//! Keys are `T option` unless the dictionary marks them `required`. IP and
+//! MAC addresses, host names and timestamps are `string`, `int` is `int`,
+//! `long` is `int64`, `float` is `float` and `bool` is `bool`. Keys outside
+//! the dictionary are collected in `extra: Map<string, string>`.
+//!
+//! # Params
+//!
+//! | Param    | Meaning                                              |
+//! |----------|------------------------------------------------------|
+//! | `format` | `cef`, `leef` or `both`, overriding the dictionary   |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_cef_leef::CefLeefProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = CefLeefProvider::new();
+//! let params = ProviderParams::default().with("format", "cef");
+//! let schema = provider.resolve_schema("siem-keys.yaml", &params)?;
+//! let types = provider.generate_types(&schema, "Siem")?;
+//! ```
+
+pub mod dictionary;
+
+pub use dictionary::{parse_dictionary, KeyDef, KeyDictionary, LogFormat, CEF_STANDARD_KEYS, LEEF_STANDARD_KEYS};
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceValidator, TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule,
+    RecordDef, TypeExpr, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+
+/// Field collecting the keys outside the dictionary
+pub const EXTRA_FIELD: &str = "extra";
+
+/// CEF / LEEF type provider
+pub struct CefLeefProvider;
+
+impl CefLeefProvider {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<KeyDictionary> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected CEF/LEEF key dictionary: {}", e)))
+    }
+
+    /// Generate the envelope and key records of each format
+    fn generate(&self, dictionary: &KeyDictionary) -> (Vec<TypeDefinition>, Vec<Diagnostic>) {
+        let mut types = Vec::new();
+        let mut diagnostics = Vec::new();
+
+        for (index, key) in dictionary.keys.iter().enumerate() {
+            let location = format!("keys.{}", key.key);
+            if key.key.is_empty() || !key.key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.') {
+                diagnostics.push(
+                    Diagnostic::warning("key-invalid", "Keys may hold only letters, digits, `_` and `.`")
+                        .at(location.clone()),
+                );
+            }
+            if key_shape(&key.key_type).is_none() {
+                diagnostics.push(
+                    Diagnostic::warning("unmapped-type", format!("Unknown key type `{}`", key.key_type))
+                        .at(location.clone()),
+                );
+            }
+            if dictionary.keys[..index].iter().any(|k| k.key == key.key) {
+                diagnostics.push(
+                    Diagnostic::warning("key-duplicate", "Key is defined more than once; the last definition wins")
+                        .at(location),
+                );
+            }
+        }
+        if dictionary.keys.iter().any(|k| k.key == EXTRA_FIELD) {
+            diagnostics.push(
+                Diagnostic::info("extra-shadowed", "Key `extra` replaces the field collecting undeclared keys")
+                    .at(format!("keys.{}", EXTRA_FIELD)),
+            );
+        }
+
+        for format in &dictionary.formats {
+            let (envelope, keys_record) = match format {
+                LogFormat::Cef => ("CefEvent", "CefExtension"),
+                LogFormat::Leef => ("LeefEvent", "LeefAttributes"),
+            };
+            types.push(TypeDefinition::Record(RecordDef {
+                name: keys_record.to_string(),
+                fields: key_fields(*format, dictionary),
+            }));
+            types.push(TypeDefinition::Record(RecordDef {
+                name: envelope.to_string(),
+                fields: envelope_fields(*format, keys_record),
+            }));
+        }
+        (types, diagnostics)
+    }
+}
+
+impl Default for CefLeefProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for CefLeefProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("CefLeefProvider")
+            .param(
+                ParamSpec::new("format", ParamKind::Choice(&["cef", "leef", "both"]))
+                    .describe("Format overriding the dictionary"),
+            )
+    }
+}
+
+impl TypeProvider for CefLeefProvider {
+    fn name(&self) -> &str {
+        "CefLeefProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let trimmed = source.trim();
+        let mut dictionary = match trimmed.to_ascii_lowercase().as_str() {
+            "cef" | "leef" | "both" => KeyDictionary {
+                formats: LogFormat::parse_list(trimmed)?,
+                ..Default::default()
+            },
+            _ if trimmed.starts_with('{') || trimmed.contains('\n') => parse_dictionary(source)?,
+            _ => parse_dictionary(&read_source(source, params)?)?,
+        };
+        if let Some(format) = typed.str("format") {
+            dictionary.formats = LogFormat::parse_list(format)?;
+        }
+
+        let json = serde_json::to_string(&dictionary)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected CEF/LEEF key dictionary".to_string()));
+        };
+
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+        module.types = self.generate(&self.parse_schema(json)?).0;
+
+        let mut result = GeneratedTypes::new();
+        result.modules.push(module);
+        Ok(result)
+    }
+}
+
+impl SourceValidator for CefLeefProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected CEF/LEEF key dictionary".to_string()));
+        };
+
+        Ok(self.generate(&self.parse_schema(json)?).1)
+    }
+}
+
+impl DescribedProvider for CefLeefProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .embedded("cef")
+            .embedded("leef")
+            .embedded("both")
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+/// Header fields of a format, then its key record
+fn envelope_fields(format: LogFormat, keys_record: &str) -> Vec<(String, TypeExpr)> {
+    let field = |name: &str, shape: TypeShape| (name.to_string(), shape.into());
+    let string = || TypeShape::Named("string".to_string());
+    match format {
+        LogFormat::Cef => vec![
+            field("version", TypeShape::Named("int".to_string())),
+            field("deviceVendor", string()),
+            field("deviceProduct", string()),
+            field("deviceVersion", string()),
+            field("signatureId", string()),
+            field("name", string()),
+            // `0`-`10`, or `Low` / `Medium` / `High` / `Very-High`
+            field("severity", string()),
+            field("extension", TypeShape::Named(keys_record.to_string())),
+        ],
+        LogFormat::Leef => vec![
+            field("version", string()),
+            field("vendor", string()),
+            field("product", string()),
+            field("productVersion", string()),
+            field("eventId", string()),
+            // LEEF 2.0 only; 1.0 always separates attributes with tabs
+            field("delimiter", TypeShape::Option(Box::new(string()))),
+            field("attributes", TypeShape::Named(keys_record.to_string())),
+        ],
+    }
+}
+
+/// Predefined keys then dictionary keys, which override predefined ones
+/// in place
+fn key_fields(format: LogFormat, dictionary: &KeyDictionary) -> Vec<(String, TypeExpr)> {
+    let mut keys: Vec<(&str, &str, bool)> = Vec::new();
+    if dictionary.standard {
+        keys.extend(format.standard_keys().iter().map(|(key, key_type)| (*key, *key_type, false)));
+    }
+    for key in &dictionary.keys {
+        match keys.iter_mut().find(|(existing, _, _)| *existing == key.key) {
+            Some(existing) => *existing = (&key.key, &key.key_type, key.required),
+            None => keys.push((&key.key, &key.key_type, key.required)),
+        }
+    }
+
+    let mut fields: Vec<(String, TypeExpr)> = keys
+        .into_iter()
+        .map(|(key, key_type, required)| {
+            let shape = key_shape(key_type).unwrap_or_else(|| TypeShape::Named("any".to_string()));
+            let shape = if required { shape } else { TypeShape::Option(Box::new(shape)) };
+            (key.to_string(), shape.into())
+        })
+        .collect();
+    if !fields.iter().any(|(name, _)| name == EXTRA_FIELD) {
+        let extra = TypeShape::Map(
+            Box::new(TypeShape::Named("string".to_string())),
+            Box::new(TypeShape::Named("string".to_string())),
+        );
+        fields.push((EXTRA_FIELD.to_string(), extra.into()));
+    }
+    fields
+}
+
+/// The Fusabi type of a dictionary key type
+fn key_shape(key_type: &str) -> Option<TypeShape> {
+    let name = match key_type.to_ascii_lowercase().as_str() {
+        "string" | "ip" | "ipv4" | "ipv6" | "mac" | "hostname" | "timestamp" => "string",
+        "int" | "integer" => "int",
+        "long" => "int64",
+        "float" | "double" => "float",
+        "bool" | "boolean" => "bool",
+        _ => return None,
+    };
+    Some(TypeShape::Named(name.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    fn record<'a>(types: &'a GeneratedTypes, name: &str) -> &'a RecordDef {
+        types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+    }
+
+    fn field(record: &RecordDef, name: &str) -> String {
+        record
+            .fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, ty)| ty.to_string())
+            .unwrap_or_else(|| panic!("Expected field {}.{}", record.name, name))
+    }
+
+    #[test]
+    fn test_envelopes_and_standard_keys() {
+        let provider = CefLeefProvider::new();
+        let schema = provider.resolve_schema("both", &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Siem").unwrap();
+
+        let names: Vec<&str> = types.modules[0]
+            .types
+            .iter()
+            .map(|t| match t {
+                TypeDefinition::Record(r) => r.name.as_str(),
+                TypeDefinition::Du(du) => du.name.as_str(),
+            })
+            .collect();
+        assert_eq!(names, ["CefExtension", "CefEvent", "LeefAttributes", "LeefEvent"]);
+
+        let cef = record(&types, "CefEvent");
+        assert_eq!(field(cef, "version"), "int");
+        assert_eq!(field(cef, "extension"), "CefExtension");
+        let extension = record(&types, "CefExtension");
+        assert_eq!(field(extension, "spt"), "int option");
+        assert_eq!(field(extension, "cn1"), "int64 option");
+        assert_eq!(field(extension, "extra"), "Map<string, string>");
+
+        assert_eq!(field(record(&types, "LeefEvent"), "delimiter"), "string option");
+        assert_eq!(field(record(&types, "LeefAttributes"), "isLoginEvent"), "bool option");
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/cef-leef.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_dictionary_keys() {
+        let provider = CefLeefProvider::new();
+        let dictionary = "keys:\n  tenantId: {type: long, required: true}\n  spt: string\n  riskScore: float\n";
+        let params = ProviderParams::default().with("format", "cef");
+        let schema = provider.resolve_schema(dictionary, &params).unwrap();
+        let types = provider.generate_types(&schema, "Siem").unwrap();
+
+        assert_eq!(types.modules[0].types.len(), 2);
+        let extension = record(&types, "CefExtension");
+        assert_eq!(field(extension, "tenantId"), "int64");
+        assert_eq!(field(extension, "spt"), "string option");
+        assert_eq!(field(extension, "riskScore"), "float option");
+
+        let names: Vec<&str> = extension.fields.iter().map(|(name, _)| name.as_str()).collect();
+        let spt = names.iter().position(|n| *n == "spt").unwrap();
+        assert_eq!(names[spt - 1], "sproc");
+        assert_eq!(&names[names.len() - 3..], ["tenantId", "riskScore", "extra"]);
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = CefLeefProvider::new();
+        let dictionary = "format: leef\nstandard: false\nkeys:\n  bad key: string\n  score: decimal\n  extra: string\n";
+        let report = provider.validate_source(dictionary, &ProviderParams::default());
+        assert!(report.is_valid());
+
+        let codes: Vec<(&str, Option<&str>)> =
+            report.diagnostics.iter().map(|d| (d.code.as_str(), d.location.as_deref())).collect();
+        assert!(codes.contains(&("key-invalid", Some("keys.bad key"))));
+        assert!(codes.contains(&("unmapped-type", Some("keys.score"))));
+        assert!(codes.contains(&("extra-shadowed", Some("keys.extra"))));
+        assert!(codes.contains(&("coerced-any", Some("LeefAttributes.score"))));
+    }
+}
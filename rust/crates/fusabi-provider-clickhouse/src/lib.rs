@@ -0,0 +1,686 @@
+//! ClickHouse Type Provider
+//!
+//! Generates Fusabi types from ClickHouse table schemas, keeping the
+//! structure of ClickHouse's own types (`Nullable`, `LowCardinality`,
+//! `Array`, `Tuple`, `Map`, `Enum8`/`Enum16`) that a generic SQL DDL
+//! parser cannot follow.
+//!
+//! # Sources
+//!
+//! - A `clickhouse://[user@]host[:port]/<database>` URI: columns are read
+//!   from `system.columns` over the HTTP interface (port 8123, or 8443
+//!   with `tls`). Tables may be named in the path,
+//!   `clickhouse://localhost/analytics/events,sessions`. The `auth` param
+//!   references the password, or `USER:PASSWORD`
+//! - `CREATE TABLE` and `CREATE DICTIONARY` statements, inline or from a
+//!   file, such as the output of `SHOW CREATE TABLE`. Output still escaped
+//!   by `clickhouse-client` (`\n` instead of line breaks) is accepted
+//!
+//! # Generated Types
+//!
+//! Each table becomes a record in the `<namespace>` module. `EPHEMERAL`
+//! columns, which are never stored, are left out.
+//!
+//! | ClickHouse                               | Fusabi                     |
+//! |------------------------------------------|----------------------------|
+//! | `Nullable(T)`                            | `T option`                 |
+//! | `LowCardinality(T)`                      | `T`                        |
+//! | `Array(T)`                               | `T list`                   |
+//! | `Map(K, V)`                              | `Map<K, V>`                |
+//! | `Tuple(A, B)`                            | `A * B`                    |
+//! | `Tuple(a A, b B)`, `Nested(a A, b B)`    | record `<Table><Column>` (a list for `Nested`) |
+//! | `Enum8(...)`, `Enum16(...)`              | union `<Table><Column>`    |
+//! | `Variant(A, B)`                          | `A \| B`                   |
+//! | `Int8`-`Int32`, `UInt8`-`UInt32`         | `int`                      |
+//! | `Int64`, `UInt64`, `Interval*`           | `int64`                    |
+//! | `Int128`/`256`, `UInt128`/`256`          | `string`                   |
+//! | `Float32`, `Float64`, `Decimal*`         | `float`                    |
+//! | `Bool`                                   | `bool`                     |
+//! | `String`, `FixedString(N)`, `UUID`, `IPv4`, `IPv6` | `string`         |
+//! | `Date`, `Date32`, `DateTime`, `DateTime64` | `string`                 |
+//! | `SimpleAggregateFunction(f, T)`          | `T`                        |
+//! | `AggregateFunction(...)`                 | `bytes`                    |
+//! | `JSON`, `Object('json')`, `Dynamic`      | `any`                      |
+//!
+//! # Params
+//!
+//! | Param   | Meaning                                        |
+//! |---------|------------------------------------------------|
+//! | `table` | Comma-separated tables to keep                 |
+//! | `tls`   | `true` to connect over HTTPS                   |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_clickhouse::ClickHouseProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = ClickHouseProvider::new();
+//! let params = ProviderParams::default().with("auth", "env:CLICKHOUSE_PASSWORD");
+//! let schema = provider.resolve_schema("clickhouse://reader@localhost/analytics", &params)?;
+//! let types = provider.generate_types(&schema, "Analytics")?;
+//! ```
+
+mod parser;
+mod types;
+
+pub use parser::{parse_ddl, parse_type};
+pub use types::{ChArg, ChType, ClickHouseSchema, Column, Table};
+
+use fusabi_provider_common::{
+    read_source, Capabilities, CredentialResolver, DescribedProvider, Diagnostic, OutputFeature,
+    ParamKind, ParamSchema, ParamSpec, ParameterizedProvider, SourceOptions, SourceValidator,
+    StructuredError, TypeShape, TypedParams,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, DuDef, VariantDef, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use serde_json::Value;
+use std::io::Read;
+
+/// Columns of every table in a database, in table order
+const COLUMNS_QUERY: &str = "SELECT table, name, type, default_kind FROM system.columns \
+     WHERE database = {database:String} ORDER BY table, position FORMAT JSON";
+
+/// ClickHouse type provider
+pub struct ClickHouseProvider {
+    generator: TypeGenerator,
+}
+
+impl ClickHouseProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<ClickHouseSchema> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected ClickHouse schema: {}", e)))
+    }
+
+    /// Read the columns of a live database from `system.columns`
+    fn fetch_tables(&self, uri: &str, params: &ProviderParams, typed: &TypedParams) -> ProviderResult<ClickHouseSchema> {
+        let rest = uri.trim_start_matches("clickhouse://");
+        let (user, rest) = match rest.split_once('@') {
+            Some((user, rest)) => (Some(user), rest),
+            None => (None, rest),
+        };
+        let mut parts = rest.splitn(3, '/');
+        let host = parts.next().unwrap_or_default();
+        let database = parts.next().unwrap_or_default();
+        if host.is_empty() || database.is_empty() {
+            return Err(ProviderError::InvalidSource(format!(
+                "Expected clickhouse://[user@]host[:port]/<database>, got {}",
+                uri
+            )));
+        }
+
+        let tls = typed.bool("tls").unwrap_or(false);
+        let host = if host.contains(':') {
+            host.to_string()
+        } else {
+            format!("{}:{}", host, if tls { 8443 } else { 8123 })
+        };
+        let (user, password) = match CredentialResolver::new().from_params(params)? {
+            Some(credential) => match credential.expose().split_once(':') {
+                Some((user, password)) => (user.to_string(), password.to_string()),
+                None => (user.unwrap_or("default").to_string(), credential.expose().to_string()),
+            },
+            None => (user.unwrap_or("default").to_string(), String::new()),
+        };
+
+        let url = format!(
+            "{}://{}/?query={}&param_database={}",
+            if tls { "https" } else { "http" },
+            host,
+            encode_component(COLUMNS_QUERY),
+            encode_component(database)
+        );
+        let response = query(&url, &user, &password, &SourceOptions::from_params(params)?)?;
+
+        let tables_in_path: Vec<&str> = parts.next().unwrap_or_default().split(',').filter(|t| !t.is_empty()).collect();
+        let mut schema = ClickHouseSchema::default();
+        for row in response.get("data").and_then(Value::as_array).into_iter().flatten() {
+            let text = |key: &str| row.get(key).and_then(Value::as_str).unwrap_or_default().to_string();
+            let table = text("table");
+            if !tables_in_path.is_empty() && !tables_in_path.contains(&table.as_str()) {
+                continue;
+            }
+            let column = Column {
+                name: text("name"),
+                column_type: text("type"),
+                default_kind: Some(text("default_kind")).filter(|k| !k.is_empty()),
+            };
+            match schema.tables.last_mut() {
+                Some(last) if last.name == table => last.columns.push(column),
+                _ => schema.tables.push(Table {
+                    database: Some(database.to_string()),
+                    name: table,
+                    columns: vec![column],
+                }),
+            }
+        }
+        Ok(schema)
+    }
+
+    /// Generate the record of a table and the types nested in it
+    fn table_types(&self, table: &Table) -> (Vec<TypeDefinition>, Vec<Diagnostic>) {
+        let mut generation = Generation {
+            generator: &self.generator,
+            types: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+        let record = self.generator.naming.apply(&table.name);
+
+        let mut fields = Vec::new();
+        for column in &table.columns {
+            if column.default_kind.as_deref() == Some("EPHEMERAL") {
+                continue;
+            }
+            let location = format!("{}.{}", table.name, column.name);
+            let shape = if column.column_type.is_empty() {
+                generation.diagnostics.push(
+                    Diagnostic::warning("type-undeclared", "Column type is inferred from its default expression")
+                        .at(location.clone()),
+                );
+                TypeShape::Named("any".to_string())
+            } else {
+                match parse_type(&column.column_type) {
+                    Ok(ty) => {
+                        let name = format!("{}{}", record, self.generator.naming.apply(&column.name));
+                        generation.shape(&ty, &name, &location)
+                    }
+                    Err(e) => {
+                        generation.diagnostics.push(
+                            Diagnostic::warning("unmapped-type", format!("Unreadable type `{}`: {}", column.column_type, e))
+                                .at(location.clone()),
+                        );
+                        TypeShape::Named("any".to_string())
+                    }
+                }
+            };
+            fields.push((column.name.clone(), shape.into()));
+        }
+        generation.types.push(TypeDefinition::Record(RecordDef { name: record, fields }));
+
+        (generation.types, generation.diagnostics)
+    }
+}
+
+impl Default for ClickHouseProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for ClickHouseProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("ClickHouseProvider")
+            .param(ParamSpec::new("table", ParamKind::List).describe("Tables to keep"))
+            .param(ParamSpec::new("tls", ParamKind::Bool).describe("Connect over HTTPS"))
+    }
+}
+
+impl TypeProvider for ClickHouseProvider {
+    fn name(&self) -> &str {
+        "ClickHouseProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let trimmed = source.trim();
+        let upper = trimmed.to_uppercase();
+        let mut schema = if trimmed.starts_with("clickhouse://") {
+            self.fetch_tables(trimmed, params, &typed)?
+        } else if upper.contains("CREATE ") || upper.contains("ATTACH ") {
+            parse_ddl(&unescape(source))?
+        } else {
+            parse_ddl(&unescape(&read_source(source, params)?))?
+        };
+
+        if let Some(filter) = typed.list("table") {
+            schema.tables.retain(|t| filter.contains(&t.name.as_str()));
+        }
+
+        let json = serde_json::to_string(&schema)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected ClickHouse schema".to_string()));
+        };
+
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+        for table in &self.parse_schema(json)?.tables {
+            module.types.extend(self.table_types(table).0);
+        }
+
+        let mut result = GeneratedTypes::new();
+        if !module.types.is_empty() {
+            result.modules.push(module);
+        }
+        Ok(result)
+    }
+}
+
+impl SourceValidator for ClickHouseProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected ClickHouse schema".to_string()));
+        };
+
+        let parsed = self.parse_schema(json)?;
+        let mut diagnostics = Vec::new();
+        if parsed.tables.is_empty() {
+            diagnostics.push(Diagnostic::warning("no-tables", "No tables found"));
+        }
+        for table in &parsed.tables {
+            diagnostics.extend(self.table_types(table).1);
+        }
+        for statement in &parsed.skipped {
+            diagnostics.push(Diagnostic::info(
+                "statement-skipped",
+                format!("{} statement does not define a table", statement),
+            ));
+        }
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for ClickHouseProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .live("clickhouse://")
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+/// Types generated for one table, with what was lost on the way
+struct Generation<'a> {
+    generator: &'a TypeGenerator,
+    types: Vec<TypeDefinition>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Generation<'_> {
+    /// Map a type; `name` names the record or union it needs, if any
+    fn shape(&mut self, ty: &ChType, name: &str, location: &str) -> TypeShape {
+        let named = |name: &str| TypeShape::Named(name.to_string());
+        let arg = |index: usize| ty.type_args().nth(index);
+        let lower = ty.name.to_ascii_lowercase();
+
+        match lower.as_str() {
+            "nullable" | "lowcardinality" | "array" if arg(0).is_none() => self.unmapped(ty, location),
+            "nullable" => TypeShape::Option(Box::new(self.shape(arg(0).unwrap(), name, location))),
+            "lowcardinality" => self.shape(arg(0).unwrap(), name, location),
+            "array" => TypeShape::List(Box::new(self.shape(arg(0).unwrap(), name, location))),
+            "map" => match (arg(0), arg(1)) {
+                (Some(key), Some(value)) => TypeShape::Map(
+                    Box::new(self.shape(key, &format!("{}Key", name), location)),
+                    Box::new(self.shape(value, &format!("{}Value", name), location)),
+                ),
+                _ => self.unmapped(ty, location),
+            },
+            "tuple" if !ty.args.is_empty() && ty.args.iter().all(|a| matches!(a, ChArg::Named(..))) => {
+                self.record(ty, name, location)
+            }
+            "tuple" => TypeShape::Tuple(ty.type_args().map(|t| self.shape(t, name, location)).collect()),
+            "nested" => TypeShape::List(Box::new(self.record(ty, name, location))),
+            "enum8" | "enum16" | "enum" => {
+                let variants = ty
+                    .args
+                    .iter()
+                    .filter_map(|a| match a {
+                        ChArg::EnumValue(label, _) => Some(label),
+                        _ => None,
+                    })
+                    .map(|label| {
+                        let case = self.generator.naming.apply(label);
+                        if case.is_empty() || case.starts_with(|c: char| c.is_ascii_digit()) {
+                            VariantDef::new_simple(format!("Value{}", case))
+                        } else {
+                            VariantDef::new_simple(case)
+                        }
+                    })
+                    .collect();
+                self.types.push(TypeDefinition::Du(DuDef {
+                    name: name.to_string(),
+                    variants,
+                }));
+                named(name)
+            }
+            "variant" => TypeShape::Union(ty.type_args().map(|t| self.shape(t, name, location)).collect()),
+            "simpleaggregatefunction" => match ty.type_args().last() {
+                Some(inner) if ty.args.len() > 1 => self.shape(inner, name, location),
+                _ => self.unmapped(ty, location),
+            },
+            "aggregatefunction" => {
+                self.diagnostics.push(
+                    Diagnostic::warning(
+                        "unmapped-type",
+                        format!("Aggregate function state `{}` is typed as `bytes`", ty),
+                    )
+                    .at(location.to_string()),
+                );
+                named("bytes")
+            }
+            "int8" | "int16" | "int32" | "uint8" | "uint16" | "uint32" | "int" | "integer" | "smallint"
+            | "tinyint" => named("int"),
+            "int64" | "uint64" | "bigint" => named("int64"),
+            name if name.starts_with("interval") => named("int64"),
+            "int128" | "int256" | "uint128" | "uint256" => {
+                self.lossy(location, format!("`{}` is typed as `string`", ty.name));
+                named("string")
+            }
+            "float32" | "float64" | "bfloat16" | "float" | "double" | "real" => named("float"),
+            "decimal" | "decimal32" | "decimal64" | "decimal128" | "decimal256" | "numeric" => {
+                self.lossy(location, format!("Fixed-point `{}` is typed as `float`", ty));
+                named("float")
+            }
+            "bool" | "boolean" => named("bool"),
+            "string" | "fixedstring" | "text" | "varchar" | "char" | "uuid" | "ipv4" | "ipv6" | "date"
+            | "date32" | "datetime" | "datetime64" | "timestamp" => named("string"),
+            "point" => TypeShape::Tuple(vec![named("float"), named("float")]),
+            "json" | "object" | "dynamic" => {
+                self.diagnostics.push(
+                    Diagnostic::info("coerced-any", format!("Semi-structured `{}` is typed as `any`", ty))
+                        .at(location.to_string()),
+                );
+                named("any")
+            }
+            _ => self.unmapped(ty, location),
+        }
+    }
+
+    /// A record for a named `Tuple` or `Nested`
+    fn record(&mut self, ty: &ChType, name: &str, location: &str) -> TypeShape {
+        let fields = ty
+            .args
+            .iter()
+            .filter_map(|a| match a {
+                ChArg::Named(field, field_type) => Some((field, field_type)),
+                _ => None,
+            })
+            .map(|(field, field_type)| {
+                let nested = format!("{}{}", name, self.generator.naming.apply(field));
+                let location = format!("{}.{}", location, field);
+                (field.clone(), self.shape(field_type, &nested, &location).into())
+            })
+            .collect();
+        self.types.push(TypeDefinition::Record(RecordDef {
+            name: name.to_string(),
+            fields,
+        }));
+        TypeShape::Named(name.to_string())
+    }
+
+    fn lossy(&mut self, location: &str, message: String) {
+        self.diagnostics
+            .push(Diagnostic::info("lossy-conversion", message).at(location.to_string()));
+    }
+
+    fn unmapped(&mut self, ty: &ChType, location: &str) -> TypeShape {
+        self.diagnostics.push(
+            Diagnostic::warning("unmapped-type", format!("ClickHouse type `{}` has no Fusabi mapping", ty))
+                .at(location.to_string()),
+        );
+        TypeShape::Named("any".to_string())
+    }
+}
+
+/// Run a query over the HTTP interface and read its JSON result
+fn query(url: &str, user: &str, password: &str, options: &SourceOptions) -> ProviderResult<Value> {
+    let agent = ureq::AgentBuilder::new().timeout(options.fetch_timeout).redirects(0).build();
+    let response = agent
+        .get(url)
+        .set("X-ClickHouse-User", user)
+        .set("X-ClickHouse-Key", password)
+        .call()
+        .map_err(|e| match e {
+            ureq::Error::Status(code, response) => {
+                // The body is the server's exception text
+                let body = response.into_string().unwrap_or_default();
+                ProviderError::from(StructuredError::network(format!(
+                    "ClickHouse query failed with HTTP {}: {}",
+                    code,
+                    body.lines().next().unwrap_or_default()
+                )))
+            }
+            ureq::Error::Transport(t) => StructuredError::network("ClickHouse query failed").with_source(t).into(),
+        })?;
+
+    let mut buf = Vec::new();
+    response
+        .into_reader()
+        .take(options.max_source_bytes + 1)
+        .read_to_end(&mut buf)
+        .map_err(|e| StructuredError::network("Failed to read ClickHouse response").with_source(e))?;
+    if buf.len() as u64 > options.max_source_bytes {
+        return Err(StructuredError::validation(format!(
+            "ClickHouse response exceeds max_source_bytes ({})",
+            options.max_source_bytes
+        ))
+        .into());
+    }
+    serde_json::from_slice(&buf)
+        .map_err(|e| StructuredError::parse("Invalid ClickHouse response").with_source(e).into())
+}
+
+/// Undo the escaping `clickhouse-client` applies to `SHOW CREATE TABLE`
+/// output in its default TSV format
+fn unescape(ddl: &str) -> String {
+    if ddl.trim().contains('\n') || !ddl.contains("\\n") {
+        return ddl.to_string();
+    }
+    let mut result = String::with_capacity(ddl.len());
+    let mut chars = ddl.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                chars.next();
+                result.push('\n');
+            }
+            ('\\', Some('t')) => {
+                chars.next();
+                result.push('\t');
+            }
+            ('\\', Some('\\')) => {
+                chars.next();
+                result.push('\\');
+            }
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+/// Percent-encode a URL query component
+fn encode_component(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+    use std::io::Write;
+    use std::net::TcpListener;
+
+    const EVENTS: &str = r#"
+        CREATE TABLE analytics.events
+        (
+            `event_id` UUID,
+            `ts` DateTime64(3, 'UTC'),
+            `kind` LowCardinality(String),
+            `status` Enum8('new' = 1, 'in-progress' = 2, 'done' = 3),
+            `user_id` Nullable(UInt64),
+            `tags` Array(LowCardinality(Nullable(String))),
+            `counts` Map(String, UInt32),
+            `geo` Tuple(Float64, Float64),
+            `device` Tuple(os String, version Nullable(String)),
+            `items` Nested(sku String, qty UInt16),
+            `total` Decimal(18, 4),
+            `uniq_users` AggregateFunction(uniq, UInt64),
+            `hits` SimpleAggregateFunction(sum, UInt64),
+            `raw` String EPHEMERAL
+        )
+        ENGINE = MergeTree
+        ORDER BY (kind, ts)
+    "#;
+
+    fn record<'a>(types: &'a GeneratedTypes, name: &str) -> &'a RecordDef {
+        types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+    }
+
+    fn fields(record: &RecordDef) -> Vec<(String, String)> {
+        record.fields.iter().map(|(name, ty)| (name.clone(), ty.to_string())).collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect()
+    }
+
+    #[test]
+    fn test_clickhouse_types() {
+        let provider = ClickHouseProvider::new();
+        let schema = provider.resolve_schema(EVENTS, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Analytics").unwrap();
+
+        assert_eq!(
+            fields(record(&types, "Events")),
+            pairs(&[
+                ("event_id", "string"),
+                ("ts", "string"),
+                ("kind", "string"),
+                ("status", "EventsStatus"),
+                ("user_id", "int64 option"),
+                ("tags", "string option list"),
+                ("counts", "Map<string, int>"),
+                ("geo", "float * float"),
+                ("device", "EventsDevice"),
+                ("items", "EventsItems list"),
+                ("total", "float"),
+                ("uniq_users", "bytes"),
+                ("hits", "int64"),
+            ])
+        );
+        assert_eq!(
+            fields(record(&types, "EventsDevice")),
+            pairs(&[("os", "string"), ("version", "string option")])
+        );
+
+        let status = types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Du(du) if du.name == "EventsStatus" => Some(du),
+                _ => None,
+            })
+            .unwrap();
+        let cases: Vec<&str> = status.variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(cases, ["New", "InProgress", "Done"]);
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/clickhouse.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_escaped_client_output() {
+        let provider = ClickHouseProvider::new();
+        let escaped = "CREATE TABLE default.t\\n(\\n    `id` UInt64,\\n    `name` String\\n)\\nENGINE = Memory";
+        let schema = provider.resolve_schema(escaped, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Db").unwrap();
+        assert_eq!(fields(record(&types, "T")), pairs(&[("id", "int64"), ("name", "string")]));
+    }
+
+    #[test]
+    fn test_params_are_checked() {
+        let provider = ClickHouseProvider::new();
+        let params = ProviderParams::default().with("tables", "events");
+        let err = provider.resolve_schema(EVENTS, &params).unwrap_err().to_string();
+        assert!(err.contains("did you mean 'table'?"));
+
+        let params = ProviderParams::default().with("tls", "yes");
+        let err = provider.resolve_schema("clickhouse://db.example/default", &params).unwrap_err().to_string();
+        assert!(err.contains("Invalid tls 'yes': expected true or false"));
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = ClickHouseProvider::new();
+        let ddl = format!("{};\nCREATE MATERIALIZED VIEW mv TO totals AS SELECT 1;\nCREATE TABLE odd (`x` Int256, `y` Ring)", EVENTS);
+        let report = provider.validate_source(&ddl, &ProviderParams::default());
+        assert!(report.is_valid());
+
+        let codes: Vec<(&str, Option<&str>)> =
+            report.diagnostics.iter().map(|d| (d.code.as_str(), d.location.as_deref())).collect();
+        assert!(codes.contains(&("lossy-conversion", Some("events.total"))));
+        assert!(codes.contains(&("unmapped-type", Some("events.uniq_users"))));
+        assert!(codes.contains(&("lossy-conversion", Some("odd.x"))));
+        assert!(codes.contains(&("unmapped-type", Some("odd.y"))));
+        assert!(codes.contains(&("statement-skipped", None)));
+        assert!(!codes.iter().any(|(code, _)| *code == "unresolved-reference"));
+    }
+
+    #[test]
+    fn test_fetch_system_columns() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 8192];
+            let n = stream.read(&mut buf).unwrap();
+            let body = r#"{"meta": [], "data": [
+                {"table": "events", "name": "id", "type": "UInt64", "default_kind": ""},
+                {"table": "events", "name": "kind", "type": "LowCardinality(String)", "default_kind": ""},
+                {"table": "sessions", "name": "started", "type": "DateTime", "default_kind": "DEFAULT"}
+            ], "rows": 3}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+
+        std::env::set_var("FUSABI_CLICKHOUSE_TEST_PASSWORD", "s3cret");
+        let provider = ClickHouseProvider::new();
+        let params = ProviderParams::default().with("auth", "env:FUSABI_CLICKHOUSE_TEST_PASSWORD");
+        let uri = format!("clickhouse://reader@{}/analytics", addr);
+        let schema = provider.resolve_schema(&uri, &params).unwrap();
+        let types = provider.generate_types(&schema, "Analytics").unwrap();
+
+        let request = server.join().unwrap().to_ascii_lowercase();
+        assert!(request.contains("param_database=analytics"));
+        assert!(request.contains("x-clickhouse-user: reader"));
+        assert!(request.contains("x-clickhouse-key: s3cret"));
+
+        assert_eq!(fields(record(&types, "Events")), pairs(&[("id", "int64"), ("kind", "string")]));
+        assert_eq!(fields(record(&types, "Sessions")), pairs(&[("started", "string")]));
+    }
+}
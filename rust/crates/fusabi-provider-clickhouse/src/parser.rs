@@ -0,0 +1,514 @@
+//! ClickHouse DDL and type parser
+//!
+//! Parses `CREATE TABLE` and `CREATE DICTIONARY` statements, as printed by
+//! `SHOW CREATE TABLE`, and the type strings found in them and in
+//! `system.columns`. Engines, keys, indexes, projections, constraints and
+//! settings are ignored. Other statements are recorded as skipped.
+
+use crate::types::{ChArg, ChType, ClickHouseSchema, Column, Table};
+use fusabi_type_providers::{ProviderError, ProviderResult};
+
+/// Parse CREATE statements from string content
+pub fn parse_ddl(content: &str) -> ProviderResult<ClickHouseSchema> {
+    let mut parser = Parser::new(content);
+    parser.parse_schema()
+}
+
+/// Parse a type such as `Array(LowCardinality(Nullable(String)))`
+pub fn parse_type(text: &str) -> ProviderResult<ChType> {
+    let mut parser = Parser::new(text);
+    let ty = parser.parse_type_expr()?;
+    match parser.current() {
+        Token::Eof => Ok(ty),
+        other => Err(ProviderError::ParseError(format!(
+            "Unexpected {:?} after type in `{}`",
+            other, text
+        ))),
+    }
+}
+
+/// Keywords that end a column's type
+const COLUMN_OPTIONS: &[&str] = &[
+    "NULL", "NOT", "DEFAULT", "MATERIALIZED", "ALIAS", "EPHEMERAL", "CODEC", "TTL", "COMMENT", "SETTINGS",
+    "STATISTICS", "PRIMARY",
+];
+
+/// Table elements that are not columns
+const NON_COLUMNS: &[&str] = &["INDEX", "PROJECTION", "CONSTRAINT", "PRIMARY", "STATISTICS"];
+
+/// Simple ClickHouse parser
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Bare identifier or keyword, case preserved
+    Identifier(String),
+    /// `` `quoted` `` or `"quoted"` identifier
+    QuotedIdentifier(String),
+    /// `'text'`, unescaped
+    StringLiteral(String),
+    Number(String),
+    Symbol(char),
+    Eof,
+}
+
+impl Parser {
+    fn new(content: &str) -> Self {
+        Self {
+            tokens: tokenize(content),
+            pos: 0,
+        }
+    }
+
+    fn current(&self) -> &Token {
+        self.tokens.get(self.pos).unwrap_or(&Token::Eof)
+    }
+
+    fn peek(&self, offset: usize) -> &Token {
+        self.tokens.get(self.pos + offset).unwrap_or(&Token::Eof)
+    }
+
+    fn advance(&mut self) {
+        if self.pos < self.tokens.len() {
+            self.pos += 1;
+        }
+    }
+
+    /// Keywords are case-insensitive
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.current(), Token::Identifier(s) if s.eq_ignore_ascii_case(keyword))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.is_keyword(keyword);
+        if found {
+            self.advance();
+        }
+        found
+    }
+
+    fn is_symbol(&self, symbol: char) -> bool {
+        self.current() == &Token::Symbol(symbol)
+    }
+
+    fn expect_symbol(&mut self, symbol: char) -> ProviderResult<()> {
+        if self.is_symbol(symbol) {
+            self.advance();
+            Ok(())
+        } else {
+            Err(ProviderError::ParseError(format!(
+                "Expected '{}', got {:?}",
+                symbol,
+                self.current()
+            )))
+        }
+    }
+
+    fn expect_name(&mut self) -> ProviderResult<String> {
+        match self.current() {
+            Token::Identifier(s) | Token::QuotedIdentifier(s) => {
+                let result = s.clone();
+                self.advance();
+                Ok(result)
+            }
+            _ => Err(ProviderError::ParseError(format!(
+                "Expected identifier, got {:?}",
+                self.current()
+            ))),
+        }
+    }
+
+    /// Skip to the end of the current statement
+    fn skip_statement(&mut self) {
+        while !matches!(self.current(), Token::Symbol(';') | Token::Eof) {
+            self.advance();
+        }
+    }
+
+    /// Skip to the `,` or `)` that ends the current table element
+    fn skip_element(&mut self) {
+        let mut depth = 0usize;
+        loop {
+            match self.current() {
+                Token::Eof => return,
+                Token::Symbol('(') => depth += 1,
+                Token::Symbol(')') if depth == 0 => return,
+                Token::Symbol(')') => depth -= 1,
+                Token::Symbol(',') if depth == 0 => return,
+                _ => {}
+            }
+            self.advance();
+        }
+    }
+
+    fn parse_schema(&mut self) -> ProviderResult<ClickHouseSchema> {
+        let mut schema = ClickHouseSchema::default();
+
+        while self.current() != &Token::Eof {
+            if self.is_symbol(';') {
+                self.advance();
+            } else if self.is_keyword("create") || self.is_keyword("attach") {
+                self.parse_create(&mut schema)?;
+            } else {
+                schema.skipped.push(self.leading_keywords(2));
+                self.skip_statement();
+            }
+        }
+
+        Ok(schema)
+    }
+
+    fn parse_create(&mut self, schema: &mut ClickHouseSchema) -> ProviderResult<()> {
+        let start = self.pos;
+        self.advance();
+        if self.eat_keyword("or") && !self.eat_keyword("replace") {
+            return Err(ProviderError::ParseError("Expected REPLACE after CREATE OR".to_string()));
+        }
+        self.eat_keyword("temporary");
+
+        if self.eat_keyword("table") || self.eat_keyword("dictionary") {
+            match self.parse_table()? {
+                Some(table) => schema.tables.push(table),
+                None => schema.skipped.push("CREATE TABLE AS".to_string()),
+            }
+        } else if !self.is_keyword("database") {
+            self.pos = start;
+            let words = if matches!(self.peek(1), Token::Identifier(s) if s.eq_ignore_ascii_case("materialized")) {
+                3
+            } else {
+                2
+            };
+            schema.skipped.push(self.leading_keywords(words));
+        }
+
+        self.skip_statement();
+        Ok(())
+    }
+
+    /// Up to `words` leading keywords, upper-cased
+    fn leading_keywords(&mut self, words: usize) -> String {
+        let mut keywords = Vec::new();
+        while keywords.len() < words {
+            match self.current() {
+                Token::Identifier(word) => keywords.push(word.to_uppercase()),
+                _ => break,
+            }
+            self.advance();
+        }
+        keywords.join(" ")
+    }
+
+    /// The table after `CREATE TABLE`; `None` for `CREATE TABLE ... AS`,
+    /// which copies its columns from elsewhere
+    fn parse_table(&mut self) -> ProviderResult<Option<Table>> {
+        if self.eat_keyword("if") {
+            self.eat_keyword("not");
+            self.eat_keyword("exists");
+        }
+        let first = self.expect_name()?;
+        let (database, name) = if self.is_symbol('.') {
+            self.advance();
+            (Some(first), self.expect_name()?)
+        } else {
+            (None, first)
+        };
+        if self.eat_keyword("uuid") {
+            self.advance();
+        }
+        if self.eat_keyword("on") {
+            self.eat_keyword("cluster");
+            self.advance();
+        }
+        if !self.is_symbol('(') {
+            return Ok(None);
+        }
+        self.advance();
+
+        let mut columns = Vec::new();
+        while !self.is_symbol(')') && self.current() != &Token::Eof {
+            // `SHOW CREATE TABLE` quotes column names, so a bare keyword
+            // starts an index, projection, constraint or key
+            if !NON_COLUMNS.iter().any(|k| self.is_keyword(k)) {
+                columns.push(self.parse_column()?);
+            }
+            self.skip_element();
+            if self.is_symbol(',') {
+                self.advance();
+            }
+        }
+        self.expect_symbol(')')?;
+
+        Ok(Some(Table { database, name, columns }))
+    }
+
+    /// `name [Type] [NULL | NOT NULL] [DEFAULT | MATERIALIZED | ALIAS | EPHEMERAL expr] ...`
+    fn parse_column(&mut self) -> ProviderResult<Column> {
+        let name = self.expect_name()?;
+
+        let is_option = |parser: &Self| COLUMN_OPTIONS.iter().any(|k| parser.is_keyword(k));
+        let mut column_type = if is_option(self) || self.is_symbol(',') || self.is_symbol(')') {
+            // The type is inferred from the default expression
+            None
+        } else {
+            Some(self.parse_type_expr()?)
+        };
+
+        if self.eat_keyword("null") {
+            column_type = column_type.map(|ty| ChType {
+                name: "Nullable".to_string(),
+                args: vec![ChArg::Type(ty)],
+            });
+        } else if self.eat_keyword("not") {
+            self.eat_keyword("null");
+        }
+
+        let default_kind = ["DEFAULT", "MATERIALIZED", "ALIAS", "EPHEMERAL"]
+            .into_iter()
+            .find(|k| self.is_keyword(k))
+            .map(String::from);
+
+        Ok(Column {
+            name,
+            column_type: column_type.map(|ty| ty.to_string()).unwrap_or_default(),
+            default_kind,
+        })
+    }
+
+    fn parse_type_expr(&mut self) -> ProviderResult<ChType> {
+        let name = self.expect_name()?;
+        let mut args = Vec::new();
+
+        if self.is_symbol('(') {
+            self.advance();
+            while !self.is_symbol(')') {
+                args.push(self.parse_type_arg()?);
+                if self.is_symbol(',') {
+                    self.advance();
+                } else {
+                    break;
+                }
+            }
+            self.expect_symbol(')')?;
+        }
+
+        Ok(ChType { name, args })
+    }
+
+    fn parse_type_arg(&mut self) -> ProviderResult<ChArg> {
+        match self.current().clone() {
+            Token::StringLiteral(s) => {
+                self.advance();
+                if !self.is_symbol('=') {
+                    return Ok(ChArg::Literal(format!("'{}'", s.replace('\'', "\\'"))));
+                }
+                self.advance();
+                let negative = self.is_symbol('-');
+                if negative {
+                    self.advance();
+                }
+                match self.current() {
+                    Token::Number(n) => {
+                        let value: i64 = n
+                            .parse()
+                            .map_err(|_| ProviderError::ParseError(format!("Invalid enum value {}", n)))?;
+                        self.advance();
+                        Ok(ChArg::EnumValue(s, if negative { -value } else { value }))
+                    }
+                    other => Err(ProviderError::ParseError(format!(
+                        "Expected enum value for '{}', got {:?}",
+                        s, other
+                    ))),
+                }
+            }
+            Token::Number(n) => {
+                self.advance();
+                Ok(ChArg::Literal(n))
+            }
+            Token::Symbol('-') => {
+                self.advance();
+                match self.current().clone() {
+                    Token::Number(n) => {
+                        self.advance();
+                        Ok(ChArg::Literal(format!("-{}", n)))
+                    }
+                    other => Err(ProviderError::ParseError(format!("Expected number, got {:?}", other))),
+                }
+            }
+            // A named `Tuple` or `Nested` element
+            Token::Identifier(name) | Token::QuotedIdentifier(name)
+                if matches!(self.peek(1), Token::Identifier(_) | Token::QuotedIdentifier(_)) =>
+            {
+                self.advance();
+                Ok(ChArg::Named(name, self.parse_type_expr()?))
+            }
+            _ => Ok(ChArg::Type(self.parse_type_expr()?)),
+        }
+    }
+}
+
+fn tokenize(content: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = content.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '-' => {
+                chars.next();
+                if chars.peek() == Some(&'-') {
+                    // Line comment
+                    for ch in chars.by_ref() {
+                        if ch == '\n' {
+                            break;
+                        }
+                    }
+                } else {
+                    tokens.push(Token::Symbol('-'));
+                }
+            }
+            '/' => {
+                chars.next();
+                if chars.peek() == Some(&'*') {
+                    chars.next();
+                    let mut prev = ' ';
+                    for ch in chars.by_ref() {
+                        if prev == '*' && ch == '/' {
+                            break;
+                        }
+                        prev = ch;
+                    }
+                } else {
+                    tokens.push(Token::Symbol('/'));
+                }
+            }
+            '\'' | '`' | '"' => {
+                chars.next();
+                let mut s = String::new();
+                while let Some(ch) = chars.next() {
+                    match ch {
+                        '\\' => {
+                            if let Some(escaped) = chars.next() {
+                                s.push(escaped);
+                            }
+                        }
+                        ch if ch == c => {
+                            // A doubled quote escapes itself
+                            if chars.peek() == Some(&c) {
+                                chars.next();
+                                s.push(c);
+                            } else {
+                                break;
+                            }
+                        }
+                        ch => s.push(ch),
+                    }
+                }
+                tokens.push(if c == '\'' { Token::StringLiteral(s) } else { Token::QuotedIdentifier(s) });
+            }
+            c if c.is_ascii_digit() => {
+                let mut num = String::new();
+                while let Some(&d) = chars.peek() {
+                    if d.is_ascii_alphanumeric() || d == '.' {
+                        num.push(d);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Number(num));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut ident = String::new();
+                while let Some(&d) = chars.peek() {
+                    if d.is_alphanumeric() || d == '_' {
+                        ident.push(d);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Identifier(ident));
+            }
+            _ => {
+                tokens.push(Token::Symbol(c));
+                chars.next();
+            }
+        }
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_types() {
+        let ty = parse_type("Array(LowCardinality(Nullable(String)))").unwrap();
+        assert_eq!(ty.to_string(), "Array(LowCardinality(Nullable(String)))");
+
+        let ty = parse_type("Enum8('new' = 1, 'in-progress' = 2, 'it\\'s done' = -3)").unwrap();
+        assert_eq!(ty.args[2], ChArg::EnumValue("it's done".to_string(), -3));
+
+        let ty = parse_type("Tuple(id UInt64, `tags` Array(String))").unwrap();
+        assert!(matches!(&ty.args[1], ChArg::Named(name, t) if name == "tags" && t.name == "Array"));
+
+        let ty = parse_type("DateTime64(3, 'Europe/Oslo')").unwrap();
+        assert_eq!(ty.to_string(), "DateTime64(3, 'Europe/Oslo')");
+
+        assert!(parse_type("Map(String, UInt64) extra").is_err());
+    }
+
+    #[test]
+    fn test_parse_show_create_table() {
+        let ddl = r#"
+            CREATE TABLE analytics.events
+            (
+                `event_id` UUID,
+                `ts` DateTime64(3, 'UTC') CODEC(Delta, ZSTD(1)),
+                `kind` LowCardinality(String),
+                `user_id` Nullable(UInt64),
+                `note` String NULL COMMENT 'free text, optional',
+                `day` Date MATERIALIZED toDate(ts),
+                `props` Map(String, String),
+                INDEX kind_idx kind TYPE set(100) GRANULARITY 4,
+                PROJECTION by_kind (SELECT kind, count() GROUP BY kind),
+                CONSTRAINT ts_set CHECK ts > 0
+            )
+            ENGINE = ReplicatedMergeTree('/clickhouse/{shard}/events', '{replica}')
+            PARTITION BY toYYYYMM(ts)
+            ORDER BY (kind, ts)
+            SETTINGS index_granularity = 8192;
+
+            CREATE VIEW analytics.recent AS SELECT * FROM analytics.events;
+            CREATE DATABASE IF NOT EXISTS analytics;
+            INSERT INTO analytics.events VALUES (1);
+        "#;
+
+        let schema = parse_ddl(ddl).unwrap();
+        let table = &schema.tables[0];
+        assert_eq!(table.database.as_deref(), Some("analytics"));
+        let columns: Vec<(&str, &str)> =
+            table.columns.iter().map(|c| (c.name.as_str(), c.column_type.as_str())).collect();
+        assert_eq!(
+            columns,
+            [
+                ("event_id", "UUID"),
+                ("ts", "DateTime64(3, 'UTC')"),
+                ("kind", "LowCardinality(String)"),
+                ("user_id", "Nullable(UInt64)"),
+                ("note", "Nullable(String)"),
+                ("day", "Date"),
+                ("props", "Map(String, String)"),
+            ]
+        );
+        assert_eq!(table.columns[5].default_kind.as_deref(), Some("MATERIALIZED"));
+        assert_eq!(schema.skipped, ["CREATE VIEW", "INSERT INTO"]);
+    }
+}
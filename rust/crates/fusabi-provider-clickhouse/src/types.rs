@@ -0,0 +1,87 @@
+//! ClickHouse schema types
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Tables read from DDL or `system.columns`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClickHouseSchema {
+    pub tables: Vec<Table>,
+    /// Leading keywords of statements that define no tables, in order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<String>,
+}
+
+/// A table or dictionary
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Table {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+    pub name: String,
+    pub columns: Vec<Column>,
+}
+
+/// A table column
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Column {
+    pub name: String,
+    /// The column type as ClickHouse prints it, e.g. `LowCardinality(String)`
+    #[serde(rename = "type")]
+    pub column_type: String,
+    /// `DEFAULT`, `MATERIALIZED`, `ALIAS` or `EPHEMERAL`, if declared
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_kind: Option<String>,
+}
+
+/// A parsed type: a name with optional arguments, such as `Nullable(String)`
+/// or `DateTime64(3, 'UTC')`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChType {
+    pub name: String,
+    pub args: Vec<ChArg>,
+}
+
+/// An argument of a parameterized type
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChArg {
+    Type(ChType),
+    /// A named element of `Tuple` or `Nested`
+    Named(String, ChType),
+    /// An `Enum8` / `Enum16` value: `'label' = 1`
+    EnumValue(String, i64),
+    /// A number, string or bare function name, such as the precision of
+    /// `Decimal(10, 2)` or the function of `AggregateFunction(uniq, UInt64)`
+    Literal(String),
+}
+
+impl ChType {
+    /// The arguments that are types, named or not
+    pub fn type_args(&self) -> impl Iterator<Item = &ChType> {
+        self.args.iter().filter_map(|arg| match arg {
+            ChArg::Type(ty) | ChArg::Named(_, ty) => Some(ty),
+            _ => None,
+        })
+    }
+}
+
+impl fmt::Display for ChType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if self.args.is_empty() {
+            return Ok(());
+        }
+        write!(f, "(")?;
+        for (i, arg) in self.args.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            match arg {
+                ChArg::Type(ty) => write!(f, "{}", ty)?,
+                ChArg::Named(name, ty) => write!(f, "{} {}", name, ty)?,
+                ChArg::EnumValue(label, value) => write!(f, "'{}' = {}", label.replace('\'', "\\'"), value)?,
+                ChArg::Literal(literal) => write!(f, "{}", literal)?,
+            }
+        }
+        write!(f, ")")
+    }
+}
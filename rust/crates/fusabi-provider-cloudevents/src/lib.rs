@@ -0,0 +1,495 @@
+//! CloudEvents Type Provider
+//!
+//! Generates the CloudEvents v1.0 envelope plus typed `data` payloads, so
+//! event-driven Fusabi functions get both the envelope and the payload
+//! checked. The source is a mapping from event `type` to a payload schema
+//! (JSON Schema or Avro); see [`mapping`] for its format.
+//!
+//! # Generated Types
+//!
+//! - one payload type per mapped event, generated by the JSON Schema or
+//!   Avro converter and named after the event type
+//!   (`com.example.order.created` → `OrderCreated`) unless `name` is set
+//! - `EventData`, a union with one case per event type carrying its payload
+//! - `CloudEvent`, the envelope record:
+//!
+//! | Attribute         | Fusabi               |
+//! |-------------------|----------------------|
+//! | `id`              | `string`             |
+//! | `source`          | `string`             |
+//! | `specversion`     | `string`             |
+//! | `type`            | `string`             |
+//! | `datacontenttype` | `string option`      |
+//! | `dataschema`      | `string option`      |
+//! | `subject`         | `string option`      |
+//! | `time`            | `string option`      |
+//! | `data`            | `EventData option`   |
+//! | `extensions`      | `Map<string, string>` |
+//!
+//! With an empty `events` map, `data` is `bytes option`. Relative schema
+//! locations are resolved against the mapping file's
+//! directory.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_cloudevents::CloudEventsProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = CloudEventsProvider::new();
+//! let schema = provider.resolve_schema("events.yaml", &ProviderParams::default())?;
+//! let types = provider.generate_types(&schema, "Events")?;
+//! ```
+
+pub mod mapping;
+
+pub use mapping::{parse_mapping, EventMapping, PayloadFormat, PayloadSource};
+
+use fusabi_jsonschema_core::{definitions_of, Converter};
+use fusabi_provider_avro::AvroProvider;
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, SourceValidator,
+    TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, DuDef, VariantDef, TypeExpr, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+
+/// Name of the envelope record
+pub const ENVELOPE_TYPE: &str = "CloudEvent";
+
+/// Name of the payload union
+pub const DATA_TYPE: &str = "EventData";
+
+/// An event with its payload schema loaded, stored in [`Schema::Custom`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ResolvedEvent {
+    event_type: String,
+    name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    payload: Option<Payload>,
+}
+
+/// A loaded payload schema
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "format", content = "schema", rename_all = "lowercase")]
+enum Payload {
+    JsonSchema(Value),
+    /// Avro schema JSON, as resolved by the Avro provider
+    Avro(String),
+}
+
+/// CloudEvents type provider
+pub struct CloudEventsProvider {
+    generator: TypeGenerator,
+    converter: Converter,
+    avro: AvroProvider,
+}
+
+impl CloudEventsProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+            converter: Converter::new(),
+            avro: AvroProvider::new(),
+        }
+    }
+
+    /// Type name derived from an event type's last two segments
+    fn payload_name(&self, event_type: &str) -> String {
+        let segments: Vec<&str> = event_type.split(['.', '/', ':']).filter(|s| !s.is_empty()).collect();
+        let tail = &segments[segments.len().saturating_sub(2)..];
+        tail.iter().map(|s| self.generator.naming.apply(s)).collect()
+    }
+
+    /// Load the payload schema of one mapping entry
+    fn load_payload(
+        &self,
+        mapping: &EventMapping,
+        base_dir: Option<&Path>,
+        params: &ProviderParams,
+    ) -> ProviderResult<Option<Payload>> {
+        let Some((format, source)) = &mapping.payload else {
+            return Ok(None);
+        };
+
+        let location = match source {
+            PayloadSource::Location(location) => Some(resolve_location(location, base_dir)),
+            PayloadSource::Inline(_) => None,
+        };
+
+        let payload = match (format, source) {
+            (PayloadFormat::JsonSchema, PayloadSource::Inline(value)) => {
+                Payload::JsonSchema(value.clone())
+            }
+            (PayloadFormat::JsonSchema, PayloadSource::Location(_)) => {
+                let text = read_source(location.as_deref().unwrap_or_default(), params)?;
+                let value = serde_json::from_str(&text).map_err(|e| {
+                    ProviderError::ParseError(format!(
+                        "Invalid JSON Schema for `{}`: {}",
+                        mapping.event_type, e
+                    ))
+                })?;
+                Payload::JsonSchema(value)
+            }
+            (PayloadFormat::Avro, source) => {
+                let source = match source {
+                    PayloadSource::Inline(value) => value.to_string(),
+                    PayloadSource::Location(_) => location.unwrap_or_default(),
+                };
+                match self.avro.resolve_schema(&source, params)? {
+                    Schema::Custom(json) => Payload::Avro(json),
+                    _ => return Err(ProviderError::ParseError("Expected Avro schema".to_string())),
+                }
+            }
+        };
+
+        Ok(Some(payload))
+    }
+
+    fn parse_resolved(&self, json: &str) -> ProviderResult<Vec<ResolvedEvent>> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Invalid CloudEvents schema: {}", e)))
+    }
+
+    /// Payload definitions and the payload type of one event
+    fn payload_types(
+        &self,
+        event: &ResolvedEvent,
+        payload: &Payload,
+    ) -> ProviderResult<(Vec<TypeDefinition>, TypeExpr)> {
+        match payload {
+            Payload::JsonSchema(schema) => {
+                let mut types = definitions_of(schema)
+                    .map(|defs| self.converter.definitions(defs))
+                    .unwrap_or_default();
+                match self.converter.definition(&event.name, schema) {
+                    Some(root) => {
+                        types.push(root);
+                        Ok((types, TypeShape::named(self.generator.naming.apply(&event.name)).into()))
+                    }
+                    // Primitive or `$ref` payloads need no definition of their own
+                    None => Ok((types, self.converter.type_expr(schema))),
+                }
+            }
+            Payload::Avro(json) => {
+                let generated = self
+                    .avro
+                    .generate_types(&Schema::Custom(json.clone()), &event.name)?;
+                let types: Vec<TypeDefinition> =
+                    generated.modules.into_iter().flat_map(|m| m.types).collect();
+
+                // The top-level named type is generated last
+                let root = types.last().map(type_name).ok_or_else(|| {
+                    ProviderError::ParseError(format!(
+                        "Avro payload for `{}` must be a named type",
+                        event.event_type
+                    ))
+                })?;
+                let root = TypeShape::named(root).into();
+                Ok((types, root))
+            }
+        }
+    }
+
+    /// Generate payloads, the data union and the envelope
+    fn generate_from_events(
+        &self,
+        events: &[ResolvedEvent],
+        namespace: &str,
+    ) -> ProviderResult<GeneratedTypes> {
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+        let mut variants = Vec::new();
+
+        for event in events {
+            let case = self.generator.naming.apply(&event.name);
+            match &event.payload {
+                Some(payload) => {
+                    let (types, payload_type) = self.payload_types(event, payload)?;
+                    for definition in types {
+                        // Payloads often share definitions; keep the first
+                        if !module.types.iter().any(|t| type_name(t) == type_name(&definition)) {
+                            module.types.push(definition);
+                        }
+                    }
+                    variants.push(VariantDef::new(case, vec![payload_type]));
+                }
+                None => variants.push(VariantDef::new_simple(case)),
+            }
+        }
+
+        if !variants.is_empty() {
+            module.types.push(TypeDefinition::Du(DuDef {
+                name: DATA_TYPE.to_string(),
+                variants,
+            }));
+        }
+
+        // Without any mapped events the payload stays raw
+        let data = if events.is_empty() { "bytes" } else { DATA_TYPE };
+        module.types.push(TypeDefinition::Record(envelope(data)));
+
+        let mut result = GeneratedTypes::new();
+        result.modules.push(module);
+        Ok(result)
+    }
+}
+
+impl Default for CloudEventsProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypeProvider for CloudEventsProvider {
+    fn name(&self) -> &str {
+        "CloudEventsProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        // Mapping documents always contain an `events` key; paths do not
+        let (text, base_dir) = if source.contains("events") && source.contains(['\n', '{']) {
+            (source.to_string(), None)
+        } else {
+            let path = source.strip_prefix("file://").unwrap_or(source);
+            let base_dir = (!source.contains("://") || source.starts_with("file://"))
+                .then(|| Path::new(path).parent().map(Path::to_path_buf))
+                .flatten();
+            (read_source(source, params)?, base_dir)
+        };
+
+        let events = parse_mapping(&text)?
+            .iter()
+            .map(|mapping| {
+                Ok(ResolvedEvent {
+                    event_type: mapping.event_type.clone(),
+                    name: mapping
+                        .name
+                        .clone()
+                        .unwrap_or_else(|| self.payload_name(&mapping.event_type)),
+                    payload: self.load_payload(mapping, base_dir.as_deref(), params)?,
+                })
+            })
+            .collect::<ProviderResult<Vec<_>>>()?;
+
+        let json = serde_json::to_string(&events)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        match schema {
+            Schema::Custom(json) => {
+                let events = self.parse_resolved(json)?;
+                self.generate_from_events(&events, namespace)
+            }
+            _ => Err(ProviderError::ParseError("Expected CloudEvents schema".to_string())),
+        }
+    }
+}
+
+impl SourceValidator for CloudEventsProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected CloudEvents schema".to_string()));
+        };
+
+        let events = self.parse_resolved(json)?;
+        let mut diagnostics = Vec::new();
+        let mut seen: Vec<(String, &str)> = Vec::new();
+
+        for event in &events {
+            let Some(payload) = &event.payload else {
+                diagnostics.push(
+                    Diagnostic::info(
+                        "payload-untyped",
+                        format!("Event `{}` has no payload schema", event.event_type),
+                    )
+                    .at(event.event_type.clone()),
+                );
+                continue;
+            };
+
+            let (types, _) = self.payload_types(event, payload)?;
+            for definition in &types {
+                let name = type_name(definition).to_string();
+                match seen.iter().find(|(n, _)| *n == name) {
+                    Some((_, first)) if *first != event.event_type => diagnostics.push(
+                        Diagnostic::warning(
+                            "duplicate-type",
+                            format!(
+                                "Type `{}` is defined by both `{}` and `{}`; the first is kept",
+                                name, first, event.event_type
+                            ),
+                        )
+                        .at(event.event_type.clone()),
+                    ),
+                    Some(_) => {}
+                    None => seen.push((name, &event.event_type)),
+                }
+            }
+        }
+
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for CloudEventsProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+/// The CloudEvents v1.0 envelope with `data` typed as `data_type`
+fn envelope(data_type: &str) -> RecordDef {
+    let named = |t: &str| -> TypeExpr { TypeShape::named(t).into() };
+    RecordDef {
+        name: ENVELOPE_TYPE.to_string(),
+        fields: vec![
+            ("id".to_string(), named("string")),
+            ("source".to_string(), named("string")),
+            ("specversion".to_string(), named("string")),
+            ("type".to_string(), named("string")),
+            ("datacontenttype".to_string(), TypeShape::named("string").option().into()),
+            ("dataschema".to_string(), TypeShape::named("string").option().into()),
+            ("subject".to_string(), TypeShape::named("string").option().into()),
+            ("time".to_string(), TypeShape::named("string").option().into()),
+            ("data".to_string(), TypeShape::parse(data_type).option().into()),
+            ("extensions".to_string(), TypeShape::map(TypeShape::named("string"), TypeShape::named("string")).into()),
+        ],
+    }
+}
+
+fn type_name(definition: &TypeDefinition) -> &str {
+    match definition {
+        TypeDefinition::Record(r) => &r.name,
+        TypeDefinition::Du(d) => &d.name,
+    }
+}
+
+/// Resolve a schema location against the mapping's directory
+fn resolve_location(location: &str, base_dir: Option<&Path>) -> String {
+    match base_dir {
+        Some(dir) if !location.contains("://") && Path::new(location).is_relative() => {
+            dir.join(location).to_string_lossy().into_owned()
+        }
+        _ => location.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    const MAPPING: &str = r#"
+events:
+  com.example.order.created:
+    jsonschema:
+      type: object
+      required: [order_id]
+      properties:
+        order_id: { type: string }
+        total: { type: number }
+  com.example.user.signup:
+    name: Signup
+    avro:
+      type: record
+      name: User
+      fields:
+        - { name: email, type: string }
+  com.example.ping: {}
+"#;
+
+    fn type_names(types: &GeneratedTypes) -> Vec<&str> {
+        types.modules[0].types.iter().map(type_name).collect()
+    }
+
+    #[test]
+    fn test_generate_envelope_and_payloads() {
+        let provider = CloudEventsProvider::new();
+        let schema = provider.resolve_schema(MAPPING, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Events").unwrap();
+
+        assert_eq!(types.modules[0].path, vec!["Events"]);
+        assert_eq!(type_names(&types), ["OrderCreated", "User", "EventData", "CloudEvent"]);
+
+        let Some(TypeDefinition::Du(data)) = types.modules[0].types.get(2) else {
+            panic!("Expected data union");
+        };
+        let cases: Vec<(&str, String)> = data
+            .variants
+            .iter()
+            .map(|v| (v.name.as_str(), v.fields.iter().map(|f| f.to_string()).collect()))
+            .collect();
+        assert_eq!(
+            cases,
+            [
+                ("OrderCreated", "OrderCreated".to_string()),
+                ("ExamplePing", String::new()),
+                ("Signup", "User".to_string()),
+            ]
+        );
+
+        let Some(TypeDefinition::Record(envelope)) = types.modules[0].types.last() else {
+            panic!("Expected envelope record");
+        };
+        let data_field = envelope.fields.iter().find(|(n, _)| n == "data").unwrap();
+        assert_eq!(data_field.1.to_string(), "EventData option");
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/cloudevents.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_relative_schema_locations() {
+        let dir = std::env::temp_dir().join("fusabi-cloudevents-test");
+        std::fs::create_dir_all(dir.join("schemas")).unwrap();
+        std::fs::write(
+            dir.join("schemas/shipped.json"),
+            r#"{"type": "object", "properties": {"carrier": {"type": "string"}}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("events.yaml"),
+            "events:\n  com.example.order.shipped: schemas/shipped.json\n",
+        )
+        .unwrap();
+
+        let provider = CloudEventsProvider::new();
+        let schema = provider
+            .resolve_schema(dir.join("events.yaml").to_str().unwrap(), &ProviderParams::default())
+            .unwrap();
+        let types = provider.generate_types(&schema, "Events").unwrap();
+        assert_eq!(type_names(&types), ["OrderShipped", "EventData", "CloudEvent"]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let mapping = r#"
+events:
+  com.example.a.created:
+    jsonschema: { definitions: { Money: { type: object } }, type: object }
+  com.example.b.created:
+    jsonschema: { definitions: { Money: { type: object } }, type: object }
+  com.example.ping: {}
+"#;
+        let report = CloudEventsProvider::new().validate_source(mapping, &ProviderParams::default());
+        assert!(report.is_valid());
+        assert!(report.diagnostics.iter().any(|d| d.code == "payload-untyped"));
+        assert_eq!(report.warnings().next().unwrap().code, "duplicate-type");
+    }
+}
@@ -0,0 +1,165 @@
+//! Event-type → payload schema mapping
+//!
+//! The mapping document is YAML or JSON with an `events` map keyed by
+//! CloudEvents `type`:
+//!
+//! ```yaml
+//! events:
+//!   com.example.order.created:
+//!     jsonschema: schemas/order.json      # path or URL
+//!   com.example.user.signup:
+//!     avro: schemas/user.avsc
+//!     name: Signup                        # overrides the derived type name
+//!   com.example.cart.abandoned:
+//!     jsonschema:                         # inline schema
+//!       type: object
+//!       properties:
+//!         cart_id: { type: string }
+//!   com.example.ping: {}                  # no payload
+//!   com.example.audit: schemas/audit.json # shorthand, format by extension
+//! ```
+
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde_json::Value;
+
+/// Payload schema language
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadFormat {
+    JsonSchema,
+    Avro,
+}
+
+/// Where a payload schema comes from
+#[derive(Debug, Clone, PartialEq)]
+pub enum PayloadSource {
+    /// A path or URL to load
+    Location(String),
+    /// A schema written inline in the mapping
+    Inline(Value),
+}
+
+/// One entry of the mapping
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventMapping {
+    /// CloudEvents `type` attribute
+    pub event_type: String,
+    /// Explicit type name for the payload
+    pub name: Option<String>,
+    /// Payload schema, if the event carries typed data
+    pub payload: Option<(PayloadFormat, PayloadSource)>,
+}
+
+/// Parse a mapping document
+pub fn parse_mapping(text: &str) -> ProviderResult<Vec<EventMapping>> {
+    let document: Value = serde_yaml::from_str(text)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid CloudEvents mapping: {}", e)))?;
+
+    let events = document
+        .get("events")
+        .and_then(Value::as_object)
+        .ok_or_else(|| parse_error("CloudEvents mapping has no `events` map"))?;
+
+    events
+        .iter()
+        .map(|(event_type, entry)| parse_entry(event_type, entry))
+        .collect()
+}
+
+fn parse_entry(event_type: &str, entry: &Value) -> ProviderResult<EventMapping> {
+    let mut mapping = EventMapping {
+        event_type: event_type.to_string(),
+        name: None,
+        payload: None,
+    };
+
+    match entry {
+        Value::Null => {}
+        Value::String(location) => {
+            let format = if location.ends_with(".avsc") || location.ends_with(".avro") {
+                PayloadFormat::Avro
+            } else {
+                PayloadFormat::JsonSchema
+            };
+            mapping.payload = Some((format, PayloadSource::Location(location.clone())));
+        }
+        Value::Object(object) => {
+            mapping.name = object.get("name").and_then(Value::as_str).map(String::from);
+
+            let formats = [("jsonschema", PayloadFormat::JsonSchema), ("avro", PayloadFormat::Avro)];
+            let mut declared = formats
+                .iter()
+                .filter_map(|(key, format)| object.get(*key).map(|schema| (*format, schema)));
+
+            if let Some((format, schema)) = declared.next() {
+                if declared.next().is_some() {
+                    return Err(parse_error(&format!(
+                        "Event `{}` declares both a JSON Schema and an Avro payload",
+                        event_type
+                    )));
+                }
+                let source = match schema {
+                    Value::String(location) => PayloadSource::Location(location.clone()),
+                    inline => PayloadSource::Inline(inline.clone()),
+                };
+                mapping.payload = Some((format, source));
+            }
+        }
+        _ => {
+            return Err(parse_error(&format!(
+                "Event `{}` must map to a schema location or an object",
+                event_type
+            )))
+        }
+    }
+
+    Ok(mapping)
+}
+
+fn parse_error(message: &str) -> ProviderError {
+    ProviderError::ParseError(message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mapping() {
+        let mapping = parse_mapping(
+            r#"
+            events:
+              com.example.order.created:
+                jsonschema: schemas/order.json
+              com.example.user.signup:
+                avro: user.avsc
+                name: Signup
+              com.example.cart.abandoned:
+                jsonschema: { type: object }
+              com.example.ping: {}
+              com.example.audit: audit.avsc
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(mapping.len(), 5);
+        let find = |t: &str| mapping.iter().find(|m| m.event_type == t).unwrap();
+        assert_eq!(
+            find("com.example.order.created").payload,
+            Some((PayloadFormat::JsonSchema, PayloadSource::Location("schemas/order.json".to_string())))
+        );
+        assert_eq!(find("com.example.user.signup").name.as_deref(), Some("Signup"));
+        assert!(matches!(
+            find("com.example.cart.abandoned").payload,
+            Some((PayloadFormat::JsonSchema, PayloadSource::Inline(_)))
+        ));
+        assert_eq!(find("com.example.ping").payload, None);
+        assert_eq!(find("com.example.audit").payload.as_ref().unwrap().0, PayloadFormat::Avro);
+    }
+
+    #[test]
+    fn test_invalid_mapping() {
+        assert!(parse_mapping("types: {}").is_err());
+        assert!(parse_mapping("events: { a: 42 }").is_err());
+        assert!(parse_mapping("events: { a: { jsonschema: a.json, avro: a.avsc } }").is_err());
+    }
+}
@@ -0,0 +1,520 @@
+//! AWS CloudFormation Type Provider
+//!
+//! Generates Fusabi types from the CloudFormation resource specification,
+//! so templates can be generated from Fusabi with every resource property
+//! checked.
+//!
+//! # Sources
+//!
+//! The resource specification JSON, inline or from a file or URL: either
+//! the combined `CloudFormationResourceSpecification.json` or a single
+//! resource's file. AWS publishes the combined file gzipped; decompress
+//! it first.
+//!
+//! # Generated Types
+//!
+//! Each service gets a module, `<Namespace>.<Service>` (`AWS::S3::Bucket`
+//! goes in `<Namespace>.S3`), holding:
+//!
+//! - `<Resource>`: the resource's `Properties`, e.g. `Bucket`
+//! - `<Resource><PropertyType>`: its property types, e.g. `BucketCorsRule`
+//! - `<Resource>Attributes`: the attributes `Fn::GetAtt` returns, with
+//!   dots in their names replaced by `_`
+//!
+//! Property types shared by every resource, such as `Tag`, go in
+//! `<Namespace>` itself. Properties that are not `Required` are `T option`.
+//!
+//! # Type Mapping
+//!
+//! | CloudFormation          | Fusabi              |
+//! |-------------------------|---------------------|
+//! | `String`, `Timestamp`   | `string`            |
+//! | `Integer`, `Long`       | `int`               |
+//! | `Double`                | `float`             |
+//! | `Boolean`               | `bool`              |
+//! | `Json`                  | `Map<string, any>`  |
+//! | `List` of `T`           | `T list`            |
+//! | `Map` of `T`            | `Map<string, T>`    |
+//!
+//! # Params
+//!
+//! | Param       | Meaning                                                         |
+//! |-------------|-----------------------------------------------------------------|
+//! | `resources` | Comma-separated resource types to keep; `AWS::S3::*` matches by prefix |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_cloudformation::CloudFormationProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = CloudFormationProvider::new();
+//! let params = ProviderParams::default().with("resources", "AWS::S3::Bucket,AWS::Lambda::*");
+//! let schema = provider.resolve_schema("CloudFormationResourceSpecification.json", &params)?;
+//! let types = provider.generate_types(&schema, "Cfn")?;
+//! ```
+
+pub mod spec;
+
+pub use spec::{parse_specification, PropertySpec, Specification, TypeSpec};
+
+use std::collections::BTreeMap;
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceValidator, TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule,
+    RecordDef, TypeExpr, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use spec::{split_property_type, split_resource_type};
+
+/// CloudFormation type provider
+pub struct CloudFormationProvider;
+
+impl CloudFormationProvider {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<Specification> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected CloudFormation resource specification: {}", e)))
+    }
+
+    /// Keep the resource types the `resources` param matches, with their
+    /// property types
+    fn filter(&self, specification: &mut Specification, patterns: &[&str]) -> ProviderResult<()> {
+        if patterns.is_empty() {
+            return Ok(());
+        }
+
+        specification.resource_types.retain(|name, _| matches_any(patterns, name));
+        if specification.resource_types.is_empty() {
+            return Err(ProviderError::InvalidSource(format!(
+                "No resource type matches '{}'",
+                patterns.join(",")
+            )));
+        }
+        let resource_types = &specification.resource_types;
+        specification.property_types.retain(|name, _| match split_property_type(name).0 {
+            Some(resource) => resource_types.contains_key(resource),
+            None => true,
+        });
+        Ok(())
+    }
+
+    fn generate(&self, specification: &Specification, namespace: &str) -> (Vec<GeneratedModule>, Vec<Diagnostic>) {
+        let mut generation = Generation {
+            specification,
+            modules: BTreeMap::new(),
+            diagnostics: Vec::new(),
+        };
+        if specification.resource_types.is_empty() {
+            generation
+                .diagnostics
+                .push(Diagnostic::warning("no-resources", "The specification defines no resource types"));
+        }
+
+        for (name, property_type) in &specification.property_types {
+            let Some(properties) = &property_type.properties else {
+                continue;
+            };
+            let (resource, _) = split_property_type(name);
+            let fields = generation.fields(resource, properties, name);
+            generation.push(resource, record_name(name), fields);
+        }
+
+        for (name, resource_type) in &specification.resource_types {
+            let (_, resource) = split_resource_type(name);
+            let fields = generation.fields(Some(name), resource_type.properties.iter().flatten(), name);
+            generation.push(Some(name), resource.to_string(), fields);
+
+            if !resource_type.attributes.is_empty() {
+                let fields = resource_type
+                    .attributes
+                    .iter()
+                    .map(|(attribute, spec)| {
+                        let location = format!("{}.{}", name, attribute);
+                        let shape = generation.type_shape(Some(name), spec, &location);
+                        (attribute.replace('.', "_"), shape.into())
+                    })
+                    .collect();
+                generation.push(Some(name), format!("{}Attributes", resource), fields);
+            }
+        }
+
+        let modules = generation
+            .modules
+            .into_iter()
+            .map(|(path, types)| {
+                let mut module_path = vec![namespace.to_string()];
+                module_path.extend(path);
+                let mut module = GeneratedModule::new(module_path);
+                module.types = types;
+                module
+            })
+            .collect();
+        (modules, generation.diagnostics)
+    }
+}
+
+/// State while generating: records by service path, and diagnostics
+struct Generation<'a> {
+    specification: &'a Specification,
+    modules: BTreeMap<Vec<String>, Vec<TypeDefinition>>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Generation<'_> {
+    /// Add a record to the module of `resource`, or the root module
+    fn push(&mut self, resource: Option<&str>, name: String, fields: Vec<(String, TypeExpr)>) {
+        let path = resource
+            .map(|resource| split_resource_type(resource).0.into_iter().map(str::to_string).collect())
+            .unwrap_or_default();
+        self.modules
+            .entry(path)
+            .or_default()
+            .push(TypeDefinition::Record(RecordDef { name, fields }));
+    }
+
+    fn fields<'s>(
+        &mut self,
+        resource: Option<&str>,
+        properties: impl IntoIterator<Item = (&'s String, &'s PropertySpec)>,
+        owner: &str,
+    ) -> Vec<(String, TypeExpr)> {
+        properties
+            .into_iter()
+            .map(|(property, spec)| {
+                let location = format!("{}.{}", owner, property);
+                let shape = self.type_shape(resource, spec, &location);
+                (property.clone(), shape.option_unless(spec.required).into())
+            })
+            .collect()
+    }
+
+    fn type_shape(&mut self, resource: Option<&str>, spec: &PropertySpec, location: &str) -> TypeShape {
+        if let Some(primitive) = &spec.primitive_type {
+            return self.primitive(primitive, location);
+        }
+        let item = |generation: &mut Self| match (&spec.primitive_item_type, &spec.item_type) {
+            (Some(primitive), _) => generation.primitive(primitive, location),
+            (None, Some(item_type)) => generation.reference(resource, item_type, location),
+            (None, None) => generation.unresolved("List or Map without an item type", location),
+        };
+        match spec.type_name.as_deref() {
+            Some("List") => item(self).list(),
+            Some("Map") => TypeShape::map(TypeShape::named("string"), item(self)),
+            Some(property_type) => self.reference(resource, property_type, location),
+            None => self.unresolved("Property has neither PrimitiveType nor Type", location),
+        }
+    }
+
+    /// A property type, looked up on the resource first and then among the
+    /// shared property types. Aliases resolve to the type they name.
+    fn reference(&mut self, resource: Option<&str>, property_type: &str, location: &str) -> TypeShape {
+        let specification = self.specification;
+        let key = resource
+            .map(|resource| format!("{}.{}", resource, property_type))
+            .filter(|key| specification.property_types.contains_key(key))
+            .unwrap_or_else(|| property_type.to_string());
+        let Some(spec) = specification.property_types.get(&key) else {
+            return self.unresolved(&format!("Unknown property type `{}`", property_type), location);
+        };
+
+        match (&spec.properties, spec.alias.is_empty()) {
+            (None, false) if spec.alias.type_name.as_deref() != Some(property_type) => {
+                self.type_shape(split_property_type(&key).0, &spec.alias, location)
+            }
+            _ => TypeShape::named(record_name(&key)),
+        }
+    }
+
+    fn primitive(&mut self, primitive: &str, location: &str) -> TypeShape {
+        let name = match primitive {
+            "String" | "Timestamp" => "string",
+            "Integer" | "Long" => "int",
+            "Double" => "float",
+            "Boolean" => "bool",
+            "Json" => return TypeShape::map(TypeShape::named("string"), TypeShape::named("any")),
+            other => {
+                self.diagnostics.push(
+                    Diagnostic::warning("unmapped-type", format!("Unknown primitive type `{}`; generated as `any`", other))
+                        .at(location.to_string()),
+                );
+                "any"
+            }
+        };
+        TypeShape::named(name)
+    }
+
+    fn unresolved(&mut self, message: &str, location: &str) -> TypeShape {
+        self.diagnostics.push(
+            Diagnostic::warning("unresolved-property-type", format!("{}; generated as `any`", message))
+                .at(location.to_string()),
+        );
+        TypeShape::named("any")
+    }
+}
+
+/// `AWS::S3::Bucket.CorsRule` → `BucketCorsRule`; `Tag` → `Tag`
+fn record_name(property_type: &str) -> String {
+    match split_property_type(property_type) {
+        (Some(resource), name) => format!("{}{}", split_resource_type(resource).1, name),
+        (None, name) => name.to_string(),
+    }
+}
+
+fn matches_any(patterns: &[&str], name: &str) -> bool {
+    patterns.iter().any(|pattern| match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => name == *pattern,
+    })
+}
+
+impl Default for CloudFormationProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for CloudFormationProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("CloudFormationProvider")
+            .param(
+                ParamSpec::new("resources", ParamKind::List)
+                    .describe("Resource types to keep; `AWS::S3::*` matches by prefix"),
+            )
+    }
+}
+
+impl TypeProvider for CloudFormationProvider {
+    fn name(&self) -> &str {
+        "CloudFormationProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let mut specification = if source.trim_start().starts_with('{') {
+            parse_specification(source)?
+        } else {
+            parse_specification(&read_source(source, params)?)?
+        };
+        if let Some(patterns) = typed.list("resources") {
+            self.filter(&mut specification, &patterns)?;
+        }
+
+        let json = serde_json::to_string(&specification)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected CloudFormation resource specification".to_string()));
+        };
+
+        let mut result = GeneratedTypes::new();
+        result.modules = self.generate(&self.parse_schema(json)?, namespace).0;
+        Ok(result)
+    }
+}
+
+impl SourceValidator for CloudFormationProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected CloudFormation resource specification".to_string()));
+        };
+
+        Ok(self.generate(&self.parse_schema(json)?, "").1)
+    }
+}
+
+impl DescribedProvider for CloudFormationProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    const SPECIFICATION: &str = r#"{
+      "ResourceSpecificationVersion": "187.0.0",
+      "PropertyTypes": {
+        "Tag": {
+          "Properties": {
+            "Key": {"PrimitiveType": "String", "Required": true, "UpdateType": "Mutable"},
+            "Value": {"PrimitiveType": "String", "Required": true, "UpdateType": "Mutable"}
+          }
+        },
+        "AWS::S3::Bucket.CorsConfiguration": {
+          "Properties": {
+            "CorsRules": {"Type": "List", "ItemType": "CorsRule", "Required": true, "DuplicatesAllowed": false}
+          }
+        },
+        "AWS::S3::Bucket.CorsRule": {
+          "Properties": {
+            "AllowedMethods": {"Type": "List", "PrimitiveItemType": "String", "Required": true},
+            "MaxAge": {"PrimitiveType": "Integer", "Required": false}
+          }
+        },
+        "AWS::Lambda::Function.Environment": {
+          "Properties": {
+            "Variables": {"Type": "Map", "PrimitiveItemType": "String", "Required": false}
+          }
+        },
+        "AWS::Lambda::Function.Layers": {"Type": "List", "PrimitiveItemType": "String"}
+      },
+      "ResourceTypes": {
+        "AWS::S3::Bucket": {
+          "Attributes": {
+            "Arn": {"PrimitiveType": "String"},
+            "DualStackDomainName": {"PrimitiveType": "String"}
+          },
+          "Properties": {
+            "BucketName": {"PrimitiveType": "String", "Required": false},
+            "CorsConfiguration": {"Type": "CorsConfiguration", "Required": false},
+            "Tags": {"Type": "List", "ItemType": "Tag", "Required": false}
+          }
+        },
+        "AWS::Lambda::Function": {
+          "Properties": {
+            "Environment": {"Type": "Environment", "Required": false},
+            "Layers": {"Type": "Layers", "Required": false},
+            "MemorySize": {"PrimitiveType": "Integer", "Required": false},
+            "Policy": {"PrimitiveType": "Json", "Required": false},
+            "Role": {"PrimitiveType": "String", "Required": true},
+            "VpcConfig": {"Type": "VpcConfig", "Required": false}
+          }
+        }
+      }
+    }"#;
+
+    fn generate(params: &ProviderParams) -> GeneratedTypes {
+        let provider = CloudFormationProvider::new();
+        let schema = provider.resolve_schema(SPECIFICATION, params).unwrap();
+        provider.generate_types(&schema, "Cfn").unwrap()
+    }
+
+    fn fields(types: &GeneratedTypes, name: &str) -> Vec<(String, String)> {
+        types
+            .modules
+            .iter()
+            .flat_map(|m| &m.types)
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+            .fields
+            .iter()
+            .map(|(field, ty)| (field.clone(), ty.to_string()))
+            .collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(f, t)| (f.to_string(), t.to_string())).collect()
+    }
+
+    #[test]
+    fn test_generate_resources() {
+        let types = generate(&ProviderParams::default());
+        let modules: Vec<(String, Vec<String>)> = types
+            .modules
+            .iter()
+            .map(|m| {
+                let names = m
+                    .types
+                    .iter()
+                    .map(|t| match t {
+                        TypeDefinition::Record(r) => r.name.clone(),
+                        TypeDefinition::Du(du) => du.name.clone(),
+                    })
+                    .collect();
+                (m.path.join("."), names)
+            })
+            .collect();
+        assert_eq!(
+            modules,
+            [
+                ("Cfn".to_string(), vec!["Tag".to_string()]),
+                ("Cfn.Lambda".to_string(), vec!["FunctionEnvironment".to_string(), "Function".to_string()]),
+                (
+                    "Cfn.S3".to_string(),
+                    vec![
+                        "BucketCorsConfiguration".to_string(),
+                        "BucketCorsRule".to_string(),
+                        "Bucket".to_string(),
+                        "BucketAttributes".to_string(),
+                    ]
+                ),
+            ]
+        );
+
+        assert_eq!(
+            fields(&types, "Bucket"),
+            pairs(&[
+                ("BucketName", "string option"),
+                ("CorsConfiguration", "BucketCorsConfiguration option"),
+                ("Tags", "Tag list option"),
+            ])
+        );
+        assert_eq!(fields(&types, "BucketCorsConfiguration"), pairs(&[("CorsRules", "BucketCorsRule list")]));
+        assert_eq!(
+            fields(&types, "BucketAttributes"),
+            pairs(&[("Arn", "string"), ("DualStackDomainName", "string")])
+        );
+        assert_eq!(
+            fields(&types, "Function"),
+            pairs(&[
+                ("Environment", "FunctionEnvironment option"),
+                ("Layers", "string list option"),
+                ("MemorySize", "int option"),
+                ("Policy", "Map<string, any> option"),
+                ("Role", "string"),
+                ("VpcConfig", "any option"),
+            ])
+        );
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/cloudformation.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_resources_filter() {
+        let types = generate(&ProviderParams::default().with("resources", "AWS::S3::*"));
+        let paths: Vec<String> = types.modules.iter().map(|m| m.path.join(".")).collect();
+        assert_eq!(paths, ["Cfn", "Cfn.S3"]);
+
+        let provider = CloudFormationProvider::new();
+        let params = ProviderParams::default().with("resources", "AWS::EC2::*");
+        let err = provider.resolve_schema(SPECIFICATION, &params).unwrap_err();
+        assert!(err.to_string().contains("No resource type matches"));
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let provider = CloudFormationProvider::new();
+        let schema = provider.resolve_schema(SPECIFICATION, &ProviderParams::default()).unwrap();
+        let diagnostics = provider.schema_diagnostics(&schema).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "unresolved-property-type");
+        assert_eq!(diagnostics[0].location.as_deref(), Some("AWS::Lambda::Function.VpcConfig"));
+
+        let report = provider.validate_source(SPECIFICATION, &ProviderParams::default());
+        assert!(!report.diagnostics.iter().any(|d| d.code == "unresolved-reference"));
+    }
+}
@@ -0,0 +1,134 @@
+//! The CloudFormation resource specification
+//!
+//! Both the combined `CloudFormationResourceSpecification.json` and the
+//! per-resource files (which use a singular `ResourceType` key) are read.
+//! Property types are keyed `AWS::S3::Bucket.CorsRule`, or by bare name
+//! (`Tag`) when shared by every resource.
+
+use std::collections::BTreeMap;
+
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde::{Deserialize, Serialize};
+
+/// A parsed resource specification
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Specification {
+    #[serde(default)]
+    pub resource_specification_version: Option<String>,
+    #[serde(default)]
+    pub property_types: BTreeMap<String, TypeSpec>,
+    #[serde(default, alias = "ResourceType")]
+    pub resource_types: BTreeMap<String, TypeSpec>,
+}
+
+/// A resource or property type. Property types without `Properties` alias
+/// another type through the [`PropertySpec`] keys.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct TypeSpec {
+    #[serde(default)]
+    pub documentation: Option<String>,
+    #[serde(default)]
+    pub properties: Option<BTreeMap<String, PropertySpec>>,
+    #[serde(default)]
+    pub attributes: BTreeMap<String, PropertySpec>,
+    #[serde(flatten)]
+    pub alias: PropertySpec,
+}
+
+/// A property or attribute
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PropertySpec {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primitive_type: Option<String>,
+    /// `List`, `Map`, or a property type name
+    #[serde(default, rename = "Type", skip_serializing_if = "Option::is_none")]
+    pub type_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primitive_item_type: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub required: bool,
+}
+
+impl PropertySpec {
+    pub fn is_empty(&self) -> bool {
+        self.primitive_type.is_none() && self.type_name.is_none()
+    }
+}
+
+/// Parse a resource specification document
+pub fn parse_specification(content: &str) -> ProviderResult<Specification> {
+    let specification: Specification = serde_json::from_str(content)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid CloudFormation resource specification: {}", e)))?;
+    if specification.resource_types.is_empty() && specification.property_types.is_empty() {
+        return Err(ProviderError::ParseError(
+            "Expected a CloudFormation resource specification with `ResourceTypes` or `PropertyTypes`".to_string(),
+        ));
+    }
+    Ok(specification)
+}
+
+/// `AWS::S3::Bucket.CorsRule` → (`AWS::S3::Bucket`, `CorsRule`); shared
+/// property types have no resource
+pub fn split_property_type(name: &str) -> (Option<&str>, &str) {
+    match name.rsplit_once('.') {
+        Some((resource, property_type)) => (Some(resource), property_type),
+        None => (None, name),
+    }
+}
+
+/// `AWS::S3::Bucket` → (`["S3"]`, `Bucket`); vendors other than AWS keep
+/// their name (`Alexa::ASK::Skill` → (`["Alexa", "ASK"]`, `Skill`))
+pub fn split_resource_type(name: &str) -> (Vec<&str>, &str) {
+    let mut segments: Vec<&str> = name.split("::").collect();
+    let resource = segments.pop().unwrap_or(name);
+    if segments.first() == Some(&"AWS") {
+        segments.remove(0);
+    }
+    (segments, resource)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_single_resource_file() {
+        let specification = parse_specification(
+            r#"{
+              "ResourceSpecificationVersion": "187.0.0",
+              "PropertyTypes": {
+                "AWS::SQS::Queue.RedrivePolicy": {"PrimitiveType": "Json"}
+              },
+              "ResourceType": {
+                "AWS::SQS::Queue": {
+                  "Attributes": {"Arn": {"PrimitiveType": "String"}},
+                  "Properties": {"QueueName": {"PrimitiveType": "String", "Required": false, "UpdateType": "Immutable"}}
+                }
+              }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(specification.resource_specification_version.as_deref(), Some("187.0.0"));
+        let queue = &specification.resource_types["AWS::SQS::Queue"];
+        assert_eq!(queue.properties.as_ref().unwrap()["QueueName"].primitive_type.as_deref(), Some("String"));
+        assert!(queue.alias.is_empty());
+        let redrive = &specification.property_types["AWS::SQS::Queue.RedrivePolicy"];
+        assert!(redrive.properties.is_none());
+        assert_eq!(redrive.alias.primitive_type.as_deref(), Some("Json"));
+
+        assert!(parse_specification(r#"{"Resources": {}}"#).is_err());
+    }
+
+    #[test]
+    fn test_split_names() {
+        assert_eq!(split_property_type("AWS::S3::Bucket.CorsRule"), (Some("AWS::S3::Bucket"), "CorsRule"));
+        assert_eq!(split_property_type("Tag"), (None, "Tag"));
+        assert_eq!(split_resource_type("AWS::S3::Bucket"), (vec!["S3"], "Bucket"));
+        assert_eq!(split_resource_type("Alexa::ASK::Skill"), (vec!["Alexa", "ASK"], "Skill"));
+    }
+}
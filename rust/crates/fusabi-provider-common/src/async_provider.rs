@@ -0,0 +1,213 @@
+//! Async type providers for network-backed sources
+//!
+//! Providers that introspect live endpoints (GraphQL, Kubernetes, MCP
+//! servers, databases) spend most of their time waiting on IO. They can
+//! implement [`AsyncTypeProvider`] instead of `TypeProvider` so that callers
+//! running on an async runtime are not blocked.
+//!
+//! [`BlockingProvider`] adapts an async provider back to `TypeProvider`, so it
+//! can be registered, watched or locked like any other provider. It drives the
+//! futures on the calling thread with [`block_on`], which needs no runtime;
+//! providers built on runtime-specific IO (e.g. tokio sockets) should enter
+//! their runtime inside the returned futures.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_common::async_provider::{AsyncTypeProvider, BlockingProvider};
+//!
+//! // From async code
+//! let schema = provider.resolve_schema("https://example.com/graphql", &params).await?;
+//! let types = provider.generate_types(&schema, "Api").await?;
+//!
+//! // From sync code
+//! let blocking = BlockingProvider::new(provider);
+//! let types = blocking.generate_types(&blocking.resolve_schema(source, &params)?, "Api")?;
+//! ```
+
+use fusabi_type_providers::{
+    GeneratedTypes, ProviderParams, ProviderResult, Schema, TypeProvider,
+};
+use std::future::Future;
+use std::pin::{pin, Pin};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::Thread;
+
+/// Boxed future returned by [`AsyncTypeProvider`] methods
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Type providers whose schema resolution and generation are asynchronous
+///
+/// Methods return boxed futures so the trait can be used as
+/// `dyn AsyncTypeProvider`, matching how `TypeProvider` is used by the registry.
+pub trait AsyncTypeProvider: Send + Sync {
+    /// Provider name
+    fn name(&self) -> &str;
+
+    /// Resolve a schema from a source
+    fn resolve_schema<'a>(
+        &'a self,
+        source: &'a str,
+        params: &'a ProviderParams,
+    ) -> BoxFuture<'a, ProviderResult<Schema>>;
+
+    /// Generate types from a resolved schema
+    fn generate_types<'a>(
+        &'a self,
+        schema: &'a Schema,
+        namespace: &'a str,
+    ) -> BoxFuture<'a, ProviderResult<GeneratedTypes>>;
+}
+
+/// Runs an [`AsyncTypeProvider`] as a synchronous `TypeProvider`
+#[derive(Debug, Clone, Default)]
+pub struct BlockingProvider<P> {
+    inner: P,
+}
+
+impl<P: AsyncTypeProvider> BlockingProvider<P> {
+    /// Wrap an async provider
+    pub fn new(inner: P) -> Self {
+        Self { inner }
+    }
+
+    /// The wrapped provider
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    /// Unwrap the async provider
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+}
+
+impl<P: AsyncTypeProvider> TypeProvider for BlockingProvider<P> {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        block_on(self.inner.resolve_schema(source, params))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        block_on(self.inner.generate_types(schema, namespace))
+    }
+}
+
+/// Wakes the thread blocked in [`block_on`]
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Drive a future to completion on the current thread
+///
+/// The thread parks while the future is pending and is unparked by its waker.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_type_providers::{GeneratedModule, ProviderError, RecordDef, TypeDefinition};
+    use std::time::Duration;
+
+    /// Completes after another thread wakes it
+    struct Delayed<T>(Option<T>, bool);
+
+    impl<T: Unpin> Future for Delayed<T> {
+        type Output = T;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+            if self.1 {
+                return Poll::Ready(self.0.take().unwrap());
+            }
+            self.1 = true;
+            let waker = cx.waker().clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(10));
+                waker.wake();
+            });
+            Poll::Pending
+        }
+    }
+
+    /// Resolves the source text after a simulated round trip
+    struct RemoteProvider;
+
+    impl AsyncTypeProvider for RemoteProvider {
+        fn name(&self) -> &str {
+            "RemoteProvider"
+        }
+
+        fn resolve_schema<'a>(
+            &'a self,
+            source: &'a str,
+            _params: &'a ProviderParams,
+        ) -> BoxFuture<'a, ProviderResult<Schema>> {
+            Box::pin(async move {
+                if source.is_empty() {
+                    return Err(ProviderError::InvalidSource("empty source".to_string()));
+                }
+                Ok(Schema::Custom(Delayed(Some(source.to_string()), false).await))
+            })
+        }
+
+        fn generate_types<'a>(
+            &'a self,
+            schema: &'a Schema,
+            namespace: &'a str,
+        ) -> BoxFuture<'a, ProviderResult<GeneratedTypes>> {
+            Box::pin(async move {
+                let Schema::Custom(name) = schema else { unreachable!() };
+                let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+                module.types.push(TypeDefinition::Record(RecordDef {
+                    name: name.clone(),
+                    fields: Vec::new(),
+                }));
+                let mut types = GeneratedTypes::new();
+                types.modules.push(module);
+                Ok(types)
+            })
+        }
+    }
+
+    #[test]
+    fn test_block_on_waits_for_wake() {
+        assert_eq!(block_on(Delayed(Some(42), false)), 42);
+    }
+
+    #[test]
+    fn test_blocking_provider() {
+        let provider = BlockingProvider::new(RemoteProvider);
+        let dynamic: &dyn TypeProvider = &provider;
+        assert_eq!(dynamic.name(), "RemoteProvider");
+
+        let schema = dynamic.resolve_schema("Users", &ProviderParams::default()).unwrap();
+        let types = dynamic.generate_types(&schema, "Api").unwrap();
+        assert_eq!(types.modules[0].path, vec!["Api"]);
+        assert_eq!(types.modules[0].types.len(), 1);
+
+        assert!(dynamic.resolve_schema("", &ProviderParams::default()).is_err());
+    }
+}
@@ -0,0 +1,174 @@
+//! Machine-readable metadata for generated types
+//!
+//! Serializers that map generated types back onto the source format need
+//! details the type definitions do not carry: protobuf field numbers, SQL
+//! column types, MCP tool names. Providers record them as string key/value
+//! attributes in a [`TypeAttributes`] table, keyed by module path, type name
+//! and, for fields and variants, member name, the same way
+//! [`TypeDocs`](crate::docs::TypeDocs) carries descriptions.
+//!
+//! Keys are prefixed with the source format, as in `protobuf.number` or
+//! `sql.type`; each provider documents the keys it sets.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_common::AttributedProvider;
+//!
+//! let schema = provider.resolve_schema("schema.proto", &params)?;
+//! let (types, attributes) = provider.generate_attributed(&schema, "Api")?;
+//! let number = attributes.member_attribute(&path, "User", "email", "protobuf.number");
+//! ```
+
+use fusabi_type_providers::{GeneratedTypes, ProviderResult, Schema, TypeProvider};
+use std::collections::BTreeMap;
+
+/// Key of an annotated type or member
+type AttributeKey = (Vec<String>, String, Option<String>);
+
+/// Annotated type or member as `(path, type name, member, attributes)`
+pub type AttributeEntry<'a> = (&'a [String], &'a str, Option<&'a str>, &'a [(String, String)]);
+
+/// Key/value attributes for generated types, fields and variants
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TypeAttributes {
+    entries: BTreeMap<AttributeKey, Vec<(String, String)>>,
+}
+
+impl TypeAttributes {
+    /// Create an empty table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set an attribute of a type declared in the module at `path`
+    ///
+    /// Setting a key again replaces its value.
+    pub fn set_type(&mut self, path: &[String], type_name: &str, key: &str, value: impl ToString) {
+        self.insert((path.to_vec(), type_name.to_string(), None), key, value.to_string());
+    }
+
+    /// Set an attribute of a record field or union variant of a type
+    pub fn set_member(
+        &mut self,
+        path: &[String],
+        type_name: &str,
+        member: &str,
+        key: &str,
+        value: impl ToString,
+    ) {
+        self.insert(
+            (path.to_vec(), type_name.to_string(), Some(member.to_string())),
+            key,
+            value.to_string(),
+        );
+    }
+
+    /// Attributes of a type, in the order they were set
+    pub fn type_attributes(&self, path: &[String], type_name: &str) -> &[(String, String)] {
+        self.get(&(path.to_vec(), type_name.to_string(), None))
+    }
+
+    /// Attributes of a record field or union variant, in the order they were set
+    pub fn member_attributes(&self, path: &[String], type_name: &str, member: &str) -> &[(String, String)] {
+        self.get(&(path.to_vec(), type_name.to_string(), Some(member.to_string())))
+    }
+
+    /// Value of one attribute of a type
+    pub fn type_attribute(&self, path: &[String], type_name: &str, key: &str) -> Option<&str> {
+        find(self.type_attributes(path, type_name), key)
+    }
+
+    /// Value of one attribute of a record field or union variant
+    pub fn member_attribute(
+        &self,
+        path: &[String],
+        type_name: &str,
+        member: &str,
+        key: &str,
+    ) -> Option<&str> {
+        find(self.member_attributes(path, type_name, member), key)
+    }
+
+    /// Every annotated type or member
+    pub fn iter(&self) -> impl Iterator<Item = AttributeEntry<'_>> {
+        self.entries.iter().map(|((path, type_name, member), attributes)| {
+            (path.as_slice(), type_name.as_str(), member.as_deref(), attributes.as_slice())
+        })
+    }
+
+    /// Number of annotated types and members
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether nothing is annotated
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Add every attribute of `other`, replacing existing values
+    pub fn extend(&mut self, other: TypeAttributes) {
+        for (key, attributes) in other.entries {
+            for (name, value) in attributes {
+                self.insert(key.clone(), &name, value);
+            }
+        }
+    }
+
+    fn insert(&mut self, key: AttributeKey, name: &str, value: String) {
+        let attributes = self.entries.entry(key).or_default();
+        match attributes.iter_mut().find(|(existing, _)| existing == name) {
+            Some((_, existing)) => *existing = value,
+            None => attributes.push((name.to_string(), value)),
+        }
+    }
+
+    fn get(&self, key: &AttributeKey) -> &[(String, String)] {
+        self.entries.get(key).map(Vec::as_slice).unwrap_or_default()
+    }
+}
+
+fn find<'a>(attributes: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value.as_str())
+}
+
+/// Type providers that record source metadata on generated types
+pub trait AttributedProvider: TypeProvider {
+    /// Generate types together with their attributes
+    ///
+    /// The types must be the same as [`TypeProvider::generate_types`]
+    /// returns for the same schema and namespace.
+    fn generate_attributed(
+        &self,
+        schema: &Schema,
+        namespace: &str,
+    ) -> ProviderResult<(GeneratedTypes, TypeAttributes)>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_type_attributes() {
+        let path = vec!["Api".to_string()];
+        let mut attributes = TypeAttributes::new();
+        attributes.set_type(&path, "User", "protobuf.name", "api.User");
+        attributes.set_member(&path, "User", "id", "protobuf.number", 1);
+        attributes.set_member(&path, "User", "id", "protobuf.type", "int64");
+        attributes.set_member(&path, "User", "id", "protobuf.number", 2);
+
+        assert_eq!(attributes.len(), 2);
+        assert_eq!(attributes.type_attribute(&path, "User", "protobuf.name"), Some("api.User"));
+        assert_eq!(attributes.member_attribute(&path, "User", "id", "protobuf.number"), Some("2"));
+        assert_eq!(attributes.member_attributes(&path, "User", "id").len(), 2);
+        assert!(attributes.member_attributes(&path, "User", "email").is_empty());
+
+        let members: Vec<_> = attributes.iter().filter_map(|(_, _, member, _)| member).collect();
+        assert_eq!(members, ["id"]);
+    }
+}
@@ -0,0 +1,344 @@
+//! Parallel generation of many sources
+//!
+//! A build that generates types from dozens of protos, DDL files and
+//! configs spends most of its time waiting on one provider at a time.
+//! [`Batch`] runs a list of (provider, source, namespace) jobs on a bounded
+//! pool of scoped threads and reports every job's outcome, in job order,
+//! instead of stopping at the first failure:
+//!
+//! ```rust,ignore
+//! use fusabi_provider_common::batch::{Batch, BatchJob};
+//!
+//! let sql = SqlProvider::new();
+//! let proto = ProtobufProvider::new();
+//! let report = Batch::new()
+//!     .with_threads(8)
+//!     .with_job(BatchJob::new(&sql, "db/schema.sql", "Db"))
+//!     .with_job(BatchJob::new(&proto, "api/user.proto", "Api").with_param("naming", "pascal"))
+//!     .run();
+//!
+//! for failure in report.failures() {
+//!     eprintln!("{}", failure);
+//! }
+//! let types = report.into_result()?;
+//! ```
+//!
+//! Providers must be `Sync`, since several jobs may share one. A job that
+//! panics fails on its own; the other jobs still run. Observers added with
+//! [`Batch::with_observer`] see every job's calls, as described in
+//! [`telemetry`](crate::telemetry).
+
+use crate::error::{ErrorKind, StructuredError};
+use crate::telemetry::{generate_observed, resolve_observed, Observers, ProviderObserver};
+use fusabi_type_providers::{GeneratedTypes, ProviderParams, ProviderResult, TypeProvider};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// One provider invocation in a batch
+pub struct BatchJob<'a> {
+    provider: &'a (dyn TypeProvider + Sync),
+    source: String,
+    namespace: String,
+    params: BTreeMap<String, String>,
+}
+
+impl<'a> BatchJob<'a> {
+    /// Resolve `source` with `provider` and generate into `namespace`
+    pub fn new(provider: &'a (dyn TypeProvider + Sync), source: &str, namespace: &str) -> Self {
+        Self {
+            provider,
+            source: source.to_string(),
+            namespace: namespace.to_string(),
+            params: BTreeMap::new(),
+        }
+    }
+
+    /// Add a provider param
+    pub fn with_param(mut self, key: &str, value: &str) -> Self {
+        self.params.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    fn run(&self, observers: &[Arc<dyn ProviderObserver>]) -> JobOutcome {
+        let started = Instant::now();
+        let params = self
+            .params
+            .iter()
+            .fold(ProviderParams::default(), |params, (key, value)| params.with(key, value));
+        let generate = || -> ProviderResult<GeneratedTypes> {
+            let schema = resolve_observed(self.provider, &self.source, &params, observers)?;
+            generate_observed(self.provider, &schema, &self.namespace, observers)
+        };
+        let result = catch_unwind(AssertUnwindSafe(generate)).unwrap_or_else(|panic| {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            Err(StructuredError::validation(format!("{} panicked: {}", self.provider.name(), message)).into())
+        });
+
+        JobOutcome {
+            provider: self.provider.name().to_string(),
+            source: self.source.clone(),
+            namespace: self.namespace.clone(),
+            result,
+            elapsed: started.elapsed(),
+        }
+    }
+}
+
+/// Jobs run together on a bounded thread pool
+#[derive(Default)]
+pub struct Batch<'a> {
+    jobs: Vec<BatchJob<'a>>,
+    threads: Option<usize>,
+    observers: Observers,
+}
+
+impl<'a> Batch<'a> {
+    /// Create an empty batch
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a job
+    pub fn with_job(mut self, job: BatchJob<'a>) -> Self {
+        self.push(job);
+        self
+    }
+
+    /// Add a job
+    pub fn push(&mut self, job: BatchJob<'a>) {
+        self.jobs.push(job);
+    }
+
+    /// Run at most `threads` jobs at a time (default: the available
+    /// parallelism)
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads.max(1));
+        self
+    }
+
+    /// Report every job's provider calls to `observer`
+    pub fn with_observer(mut self, observer: Arc<dyn ProviderObserver>) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    /// Number of jobs in the batch
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    /// Whether the batch has no jobs
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    /// Run every job and collect the outcomes in job order
+    pub fn run(&self) -> BatchReport {
+        let started = Instant::now();
+        let threads = self
+            .threads
+            .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()))
+            .min(self.jobs.len());
+
+        // Workers take the next unclaimed job until none are left
+        let next = AtomicUsize::new(0);
+        let mut outcomes: Vec<(usize, JobOutcome)> = std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut done = Vec::new();
+                        loop {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(job) = self.jobs.get(index) else {
+                                break;
+                            };
+                            done.push((index, job.run(&self.observers)));
+                        }
+                        done
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().unwrap_or_default())
+                .collect()
+        });
+        outcomes.sort_by_key(|(index, _)| *index);
+
+        BatchReport {
+            outcomes: outcomes.into_iter().map(|(_, outcome)| outcome).collect(),
+            elapsed: started.elapsed(),
+        }
+    }
+}
+
+/// Result of one job
+#[derive(Debug)]
+pub struct JobOutcome {
+    /// Name of the provider
+    pub provider: String,
+    /// Source the job resolved
+    pub source: String,
+    /// Namespace the job generated into
+    pub namespace: String,
+    /// Generated types, or why the job failed
+    pub result: ProviderResult<GeneratedTypes>,
+    /// Time the job took
+    pub elapsed: Duration,
+}
+
+impl JobOutcome {
+    /// Whether the job generated types
+    pub fn is_ok(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+impl fmt::Display for JobOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} -> {}", self.provider, self.source, self.namespace)?;
+        match &self.result {
+            Ok(_) => write!(f, ": ok in {:?}", self.elapsed),
+            Err(e) => write!(f, ": {}", e),
+        }
+    }
+}
+
+/// Outcomes of a batch, in job order
+#[derive(Debug)]
+pub struct BatchReport {
+    /// One outcome per job
+    pub outcomes: Vec<JobOutcome>,
+    /// Wall-clock time of the whole batch
+    pub elapsed: Duration,
+}
+
+impl BatchReport {
+    /// Whether every job generated types
+    pub fn is_success(&self) -> bool {
+        self.outcomes.iter().all(JobOutcome::is_ok)
+    }
+
+    /// Jobs that failed
+    pub fn failures(&self) -> impl Iterator<Item = &JobOutcome> {
+        self.outcomes.iter().filter(|outcome| !outcome.is_ok())
+    }
+
+    /// Generated types of every job, in job order, or one error listing
+    /// every failed job
+    ///
+    /// The error has the kind of the first failure.
+    pub fn into_result(self) -> ProviderResult<Vec<GeneratedTypes>> {
+        let failures: Vec<String> = self.failures().map(|outcome| format!("  {}", outcome)).collect();
+        let Some(kind) = self.failures().find_map(|outcome| outcome.result.as_ref().err()).map(ErrorKind::of) else {
+            return Ok(self.outcomes.into_iter().filter_map(|outcome| outcome.result.ok()).collect());
+        };
+
+        let message = format!(
+            "{} of {} batch jobs failed:\n{}",
+            failures.len(),
+            self.outcomes.len(),
+            failures.join("\n")
+        );
+        Err(StructuredError::new(kind, message).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::PipelineMetrics;
+    use fusabi_type_providers::{GeneratedModule, ProviderError, RecordDef, Schema, TypeDefinition, TypeExpr};
+
+    /// Generates one record per source; fails on `bad` and panics on
+    /// `panic`, and records how many jobs run at once
+    #[derive(Default)]
+    struct CountingProvider {
+        running: AtomicUsize,
+        peak: AtomicUsize,
+    }
+
+    impl TypeProvider for CountingProvider {
+        fn name(&self) -> &str {
+            "CountingProvider"
+        }
+
+        fn resolve_schema(&self, source: &str, _params: &ProviderParams) -> ProviderResult<Schema> {
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(running, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(5));
+            self.running.fetch_sub(1, Ordering::SeqCst);
+
+            match source {
+                "bad" => Err(ProviderError::ParseError("bad source".to_string())),
+                "panic" => panic!("provider bug"),
+                _ => Ok(Schema::Custom(source.to_string())),
+            }
+        }
+
+        fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+            let Schema::Custom(name) = schema else {
+                return Err(ProviderError::ParseError("Expected text".to_string()));
+            };
+            let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+            module.types.push(TypeDefinition::Record(RecordDef {
+                name: name.clone(),
+                fields: vec![("id".to_string(), TypeExpr::Named("int".to_string()))],
+            }));
+            let mut types = GeneratedTypes::new();
+            types.modules.push(module);
+            Ok(types)
+        }
+    }
+
+    #[test]
+    fn test_batch() {
+        let provider = CountingProvider::default();
+        let mut batch = Batch::new().with_threads(3);
+        for i in 0..12 {
+            batch.push(BatchJob::new(&provider, &format!("T{}", i), &format!("Ns{}", i)));
+        }
+        let report = batch.run();
+        assert!(report.is_success());
+        assert!(provider.peak.load(Ordering::SeqCst) <= 3);
+        let namespaces: Vec<_> = report.outcomes.iter().map(|o| o.namespace.as_str()).collect();
+        assert_eq!(namespaces[..3], ["Ns0", "Ns1", "Ns2"]);
+
+        let types = report.into_result().unwrap();
+        assert_eq!(types.len(), 12);
+        assert_eq!(types[11].modules[0].path, ["Ns11"]);
+    }
+
+    #[test]
+    fn test_batch_failures() {
+        let provider = CountingProvider::default();
+        let metrics = Arc::new(PipelineMetrics::new());
+        let report = Batch::new()
+            .with_threads(2)
+            .with_observer(metrics.clone())
+            .with_job(BatchJob::new(&provider, "bad", "A"))
+            .with_job(BatchJob::new(&provider, "Ok", "B"))
+            .with_job(BatchJob::new(&provider, "panic", "C"))
+            .run();
+        assert!(!report.is_success());
+        assert!(report.outcomes[1].is_ok());
+        let failed: Vec<_> = report.failures().map(|o| o.namespace.as_str()).collect();
+        assert_eq!(failed, ["A", "C"]);
+        assert!(report.outcomes[2].to_string().contains("panicked: provider bug"));
+        let counts = &metrics.snapshot()["CountingProvider"];
+        assert_eq!((counts.resolves, counts.resolve_failures, counts.generations), (2, 1, 1));
+
+        let error = report.into_result().unwrap_err();
+        assert_eq!(ErrorKind::of(&error), ErrorKind::Parse);
+        assert!(error.to_string().contains("2 of 3 batch jobs failed"));
+        assert!(Batch::new().run().into_result().unwrap().is_empty());
+    }
+}
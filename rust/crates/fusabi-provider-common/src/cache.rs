@@ -0,0 +1,539 @@
+//! On-disk schema cache
+//!
+//! Remote sources fetched through [`crate::source::SourceResolver`] are
+//! cached on disk so repeated generations against the same endpoint do not
+//! hit the network every time. Entries are keyed by a SHA-256 of the source
+//! location and the params that shape the request (credential reference and
+//! auth scheme), and store a digest of their content so a truncated or
+//! edited entry is treated as a miss.
+//!
+//! | Param         | Default                 | Meaning                       |
+//! |---------------|-------------------------|-------------------------------|
+//! | `cache_dir`   | `$XDG_CACHE_HOME/fusabi/schemas` | Cache directory      |
+//! | `cache_ttl`   | `1h`                    | Age after which entries expire |
+//! | `refresh`     | `ttl`                   | `always`, `never` or `ttl`    |
+//! | `offline`     | `false`                 | Never touch the network       |
+//!
+//! `refresh=always` refetches every time (still updating the cache);
+//! `refresh=never` reuses any cached entry regardless of age.
+//!
+//! Offline mode serves any cached entry regardless of age and fails on a
+//! miss instead of fetching. Setting `FUSABI_OFFLINE=1` enables it for
+//! every source, which is how command-line tools implement `--offline`.
+//!
+//! Entries are held to the same `max_source_bytes` as fetched sources, so
+//! an oversized entry left in the directory is a miss rather than read
+//! whole into memory.
+
+use crate::credentials::{AUTH_PARAM, AUTH_SCHEME_PARAM};
+use crate::source::DEFAULT_MAX_SOURCE_BYTES;
+use fusabi_type_providers::{ProviderError, ProviderParams, ProviderResult};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Default time-to-live for cached sources
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Environment variable that enables offline mode for every source
+pub const OFFLINE_ENV: &str = "FUSABI_OFFLINE";
+
+/// Room in an entry file for its fields other than the content
+const ENTRY_OVERHEAD_BYTES: u64 = 64 * 1024;
+
+/// Distinguishes the temporary files of concurrent writes in one process
+static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// When cached entries are refetched
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefreshPolicy {
+    /// Always refetch
+    Always,
+    /// Never refetch a cached entry
+    Never,
+    /// Refetch entries older than the TTL
+    Ttl,
+}
+
+impl RefreshPolicy {
+    /// Parse a `refresh` param value
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "always" => Some(Self::Always),
+            "never" => Some(Self::Never),
+            "ttl" => Some(Self::Ttl),
+            _ => None,
+        }
+    }
+}
+
+/// Cache settings for a single source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheOptions {
+    /// Cache directory, or `None` when no cache directory is available
+    pub dir: Option<PathBuf>,
+    /// Age after which entries expire
+    pub ttl: Duration,
+    /// Refresh policy
+    pub refresh: RefreshPolicy,
+    /// Serve only from the cache, failing on a miss
+    pub offline: bool,
+}
+
+impl Default for CacheOptions {
+    fn default() -> Self {
+        Self {
+            dir: default_cache_dir(),
+            ttl: DEFAULT_CACHE_TTL,
+            refresh: RefreshPolicy::Ttl,
+            offline: std::env::var_os(OFFLINE_ENV).is_some_and(|value| value != "0"),
+        }
+    }
+}
+
+impl CacheOptions {
+    /// Read options from params, falling back to defaults
+    pub fn from_params(params: &ProviderParams) -> ProviderResult<Self> {
+        let mut options = Self::default();
+
+        if let Some(dir) = params.custom.get("cache_dir") {
+            options.dir = Some(PathBuf::from(dir));
+        }
+
+        if let Some(value) = params.custom.get("cache_ttl") {
+            options.ttl = crate::source::parse_duration(value).ok_or_else(|| {
+                ProviderError::InvalidSource(format!(
+                    "Invalid cache_ttl '{}': expected a duration such as 30s, 10m or 1h",
+                    value
+                ))
+            })?;
+        }
+
+        if let Some(value) = params.custom.get("refresh") {
+            options.refresh = RefreshPolicy::parse(value).ok_or_else(|| {
+                ProviderError::InvalidSource(format!(
+                    "Invalid refresh '{}': expected always, never or ttl",
+                    value
+                ))
+            })?;
+        }
+
+        if let Some(value) = params.custom.get("offline") {
+            options.offline = value.parse().map_err(|_| {
+                ProviderError::InvalidSource(format!(
+                    "Invalid offline '{}': expected true or false",
+                    value
+                ))
+            })?;
+        }
+
+        Ok(options)
+    }
+}
+
+/// A cached source
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CacheEntry {
+    /// Source location the content was fetched from
+    pub source: String,
+    /// Seconds since the Unix epoch when the content was fetched
+    pub fetched_at: u64,
+    /// Hex-encoded SHA-256 of the content
+    pub sha256: String,
+    /// Source content
+    pub content: String,
+}
+
+impl CacheEntry {
+    /// Age of the entry
+    pub fn age(&self) -> Duration {
+        Duration::from_secs(now_secs().saturating_sub(self.fetched_at))
+    }
+}
+
+/// Directory of cached sources
+#[derive(Debug, Clone)]
+pub struct SchemaCache {
+    dir: PathBuf,
+    max_source_bytes: u64,
+}
+
+impl SchemaCache {
+    /// Use a cache directory, creating it on first write
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            max_source_bytes: DEFAULT_MAX_SOURCE_BYTES,
+        }
+    }
+
+    /// Treat entries with content larger than `max_source_bytes` as missing
+    pub fn with_max_source_bytes(mut self, max_source_bytes: u64) -> Self {
+        self.max_source_bytes = max_source_bytes;
+        self
+    }
+
+    /// Cache directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Look up an entry; corrupt and oversized entries are treated as
+    /// missing
+    pub fn get(&self, key: &str) -> Option<CacheEntry> {
+        // JSON escaping grows content at most sixfold (`\u0000`)
+        let max_entry_bytes = self.max_source_bytes.saturating_mul(6).saturating_add(ENTRY_OVERHEAD_BYTES);
+        let file = std::fs::File::open(self.entry_path(key)).ok()?;
+        let mut data = String::new();
+        file.take(max_entry_bytes.saturating_add(1)).read_to_string(&mut data).ok()?;
+        if data.len() as u64 > max_entry_bytes {
+            return None;
+        }
+
+        let entry: CacheEntry = serde_json::from_str(&data).ok()?;
+        let valid = entry.content.len() as u64 <= self.max_source_bytes && sha256_hex(&entry.content) == entry.sha256;
+        valid.then_some(entry)
+    }
+
+    /// Store content under a key
+    pub fn put(&self, key: &str, source: &str, content: &str) -> ProviderResult<CacheEntry> {
+        let entry = CacheEntry {
+            source: source.to_string(),
+            fetched_at: now_secs(),
+            sha256: sha256_hex(content),
+            content: content.to_string(),
+        };
+
+        std::fs::create_dir_all(&self.dir).map_err(|e| ProviderError::IoError(e.to_string()))?;
+        let data = serde_json::to_string(&entry)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+
+        // Write then rename so concurrent readers never see a partial entry;
+        // each write has its own temporary file so concurrent writers of
+        // one key do not rename each other's
+        let path = self.entry_path(key);
+        let tmp = self.dir.join(format!(
+            "{}.{}.{}.tmp",
+            key,
+            std::process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let written = std::fs::write(&tmp, data).and_then(|()| std::fs::rename(&tmp, &path));
+        if let Err(e) = written {
+            let _ = std::fs::remove_file(&tmp);
+            return Err(ProviderError::IoError(e.to_string()));
+        }
+
+        Ok(entry)
+    }
+
+    /// Remove an entry
+    pub fn remove(&self, key: &str) {
+        let _ = std::fs::remove_file(self.entry_path(key));
+    }
+
+    /// Remove the entry for a source fetched with `params`
+    ///
+    /// Returns whether an entry existed.
+    pub fn invalidate(&self, source: &str, params: &ProviderParams) -> bool {
+        std::fs::remove_file(self.entry_path(&request_key(source, params))).is_ok()
+    }
+
+    /// Valid entries with their keys, sorted by key
+    pub fn entries(&self) -> Vec<(String, CacheEntry)> {
+        let mut entries: Vec<_> = self
+            .keys()
+            .into_iter()
+            .filter_map(|key| self.get(&key).map(|entry| (key, entry)))
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        entries
+    }
+
+    /// Remove entries older than `ttl`, and corrupt entries
+    ///
+    /// Returns the number of entries removed.
+    pub fn prune(&self, ttl: Duration) -> usize {
+        let stale: Vec<_> = self
+            .keys()
+            .into_iter()
+            .filter(|key| self.get(key).is_none_or(|entry| entry.age() >= ttl))
+            .collect();
+        stale.iter().for_each(|key| self.remove(key));
+        stale.len()
+    }
+
+    /// Remove every entry
+    ///
+    /// Returns the number of entries removed.
+    pub fn clear(&self) -> usize {
+        let keys = self.keys();
+        keys.iter().for_each(|key| self.remove(key));
+        keys.len()
+    }
+
+    /// Fetch through the cache according to `options`
+    pub fn fetch_with<F>(
+        &self,
+        key: &str,
+        source: &str,
+        options: &CacheOptions,
+        fetch: F,
+    ) -> ProviderResult<String>
+    where
+        F: FnOnce() -> ProviderResult<String>,
+    {
+        if options.offline {
+            return self.get(key).map(|entry| entry.content).ok_or_else(|| offline_miss(source));
+        }
+
+        let cached = match options.refresh {
+            RefreshPolicy::Always => None,
+            RefreshPolicy::Never => self.get(key),
+            RefreshPolicy::Ttl => self.get(key).filter(|e| e.age() < options.ttl),
+        };
+
+        if let Some(entry) = cached {
+            return Ok(entry.content);
+        }
+
+        let content = fetch()?;
+        // A read-only or full cache directory must not fail the generation
+        let _ = self.put(key, source, &content);
+        Ok(content)
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+
+    /// Keys of the entry files in the cache directory
+    fn keys(&self) -> Vec<String> {
+        let Ok(dir) = std::fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        dir.filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            name.strip_suffix(".json").map(str::to_string)
+        })
+        .collect()
+    }
+}
+
+/// Error for a source that offline mode cannot serve
+pub(crate) fn offline_miss(source: &str) -> ProviderError {
+    ProviderError::IoError(format!(
+        "{} is not cached and offline mode is enabled",
+        source
+    ))
+}
+
+/// Cache key for a source location and optional credential reference
+///
+/// Only the reference (e.g. `env:GITHUB_TOKEN`) is hashed, never the secret.
+pub fn cache_key(source: &str, auth_reference: Option<&str>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(source.as_bytes());
+    if let Some(auth) = auth_reference {
+        hasher.update(b"\0");
+        hasher.update(auth.as_bytes());
+    }
+    hex(&hasher.finalize())
+}
+
+/// Cache key for a source fetched with `params`
+///
+/// Covers the params that change the request; limits and cache settings do
+/// not change the content and are left out.
+pub fn request_key(source: &str, params: &ProviderParams) -> String {
+    let auth = params.custom.get(AUTH_PARAM).map(String::as_str);
+    match params.custom.get(AUTH_SCHEME_PARAM) {
+        Some(scheme) => cache_key(source, Some(&format!("{}\0{}", auth.unwrap_or(""), scheme))),
+        None => cache_key(source, auth),
+    }
+}
+
+/// Default cache directory (`$XDG_CACHE_HOME/fusabi/schemas` on Linux)
+pub fn default_cache_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("fusabi").join("schemas"))
+}
+
+fn sha256_hex(content: &str) -> String {
+    hex(&Sha256::digest(content.as_bytes()))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn options(refresh: RefreshPolicy, ttl: Duration) -> CacheOptions {
+        CacheOptions {
+            dir: None,
+            ttl,
+            refresh,
+            offline: false,
+        }
+    }
+
+    #[test]
+    fn test_options_from_params() {
+        let params = ProviderParams::default()
+            .with("cache_dir", "/tmp/fusabi-cache")
+            .with("cache_ttl", "10m")
+            .with("refresh", "never")
+            .with("offline", "true");
+
+        let options = CacheOptions::from_params(&params).unwrap();
+        assert_eq!(options.dir, Some(PathBuf::from("/tmp/fusabi-cache")));
+        assert_eq!(options.ttl, Duration::from_secs(600));
+        assert_eq!(options.refresh, RefreshPolicy::Never);
+        assert!(options.offline);
+
+        let params = ProviderParams::default().with("refresh", "sometimes");
+        assert!(CacheOptions::from_params(&params).is_err());
+    }
+
+    #[test]
+    fn test_refresh_policies() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = SchemaCache::new(dir.path());
+        let key = cache_key("https://example.com/schema.json", None);
+        let fetches = Cell::new(0);
+        let fetch = || {
+            fetches.set(fetches.get() + 1);
+            Ok(format!("v{}", fetches.get()))
+        };
+
+        let ttl = options(RefreshPolicy::Ttl, DEFAULT_CACHE_TTL);
+        assert_eq!(cache.fetch_with(&key, "src", &ttl, fetch).unwrap(), "v1");
+        assert_eq!(cache.fetch_with(&key, "src", &ttl, fetch).unwrap(), "v1");
+        assert_eq!(fetches.get(), 1);
+
+        let expired = options(RefreshPolicy::Ttl, Duration::ZERO);
+        assert_eq!(cache.fetch_with(&key, "src", &expired, fetch).unwrap(), "v2");
+
+        let always = options(RefreshPolicy::Always, DEFAULT_CACHE_TTL);
+        assert_eq!(cache.fetch_with(&key, "src", &always, fetch).unwrap(), "v3");
+
+        let never = options(RefreshPolicy::Never, Duration::ZERO);
+        assert_eq!(cache.fetch_with(&key, "src", &never, fetch).unwrap(), "v3");
+        assert_eq!(fetches.get(), 3);
+    }
+
+    #[test]
+    fn test_offline() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = SchemaCache::new(dir.path());
+        let offline = CacheOptions {
+            offline: true,
+            ..options(RefreshPolicy::Always, Duration::ZERO)
+        };
+        let fetch = || -> ProviderResult<String> { panic!("offline mode must not fetch") };
+
+        let err = cache.fetch_with("missing", "src", &offline, fetch).unwrap_err();
+        assert!(err.to_string().contains("offline"));
+
+        cache.put("stale", "src", "{}").unwrap();
+        assert_eq!(cache.fetch_with("stale", "src", &offline, fetch).unwrap(), "{}");
+    }
+
+    #[test]
+    fn test_request_key() {
+        let source = "https://example.com/a";
+        let params = ProviderParams::default().with("auth", "env:TOKEN");
+        assert_eq!(request_key(source, &params), cache_key(source, Some("env:TOKEN")));
+        assert_eq!(
+            request_key(source, &params.clone().with("cache_ttl", "5m")),
+            request_key(source, &params)
+        );
+        assert_ne!(
+            request_key(source, &params.clone().with("auth_scheme", "basic")),
+            request_key(source, &params)
+        );
+    }
+
+    #[test]
+    fn test_invalidation() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = SchemaCache::new(dir.path());
+        let params = ProviderParams::default();
+        let a = request_key("https://example.com/a", &params);
+        let b = request_key("https://example.com/b", &params);
+        cache.put(&a, "https://example.com/a", "{}").unwrap();
+        cache.put(&b, "https://example.com/b", "[]").unwrap();
+        std::fs::write(dir.path().join("corrupt.json"), "not json").unwrap();
+
+        assert_eq!(cache.entries().len(), 2);
+        assert_eq!(cache.prune(DEFAULT_CACHE_TTL), 1);
+
+        assert!(cache.invalidate("https://example.com/a", &params));
+        assert!(!cache.invalidate("https://example.com/a", &params));
+        assert_eq!(cache.entries()[0].1.source, "https://example.com/b");
+
+        assert_eq!(cache.clear(), 1);
+        assert!(cache.entries().is_empty());
+        assert_eq!(SchemaCache::new(dir.path().join("missing")).clear(), 0);
+    }
+
+    #[test]
+    fn test_corrupt_entry_is_a_miss() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = SchemaCache::new(dir.path());
+        let key = cache_key("https://example.com/a", Some("env:TOKEN"));
+        assert_ne!(key, cache_key("https://example.com/a", None));
+
+        cache.put(&key, "https://example.com/a", "{}").unwrap();
+        assert!(cache.get(&key).is_some());
+
+        let path = dir.path().join(format!("{}.json", key));
+        let tampered = std::fs::read_to_string(&path).unwrap().replace("{}", "[]");
+        std::fs::write(&path, tampered).unwrap();
+        assert!(cache.get(&key).is_none());
+    }
+
+    #[test]
+    fn test_oversized_entry_is_a_miss() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = SchemaCache::new(dir.path()).with_max_source_bytes(8);
+        cache.put("small", "src", "{}").unwrap();
+        cache.put("large", "src", "{\"a\": \"b\"}").unwrap();
+
+        assert!(cache.get("small").is_some());
+        assert!(cache.get("large").is_none());
+        assert!(SchemaCache::new(dir.path()).get("large").is_some());
+    }
+
+    #[test]
+    fn test_concurrent_puts() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = SchemaCache::new(dir.path());
+        std::thread::scope(|scope| {
+            for i in 0..8 {
+                let cache = &cache;
+                scope.spawn(move || {
+                    for _ in 0..20 {
+                        cache.put("shared", "src", &format!("v{}", i)).unwrap();
+                    }
+                });
+            }
+        });
+
+        assert!(cache.get("shared").is_some());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
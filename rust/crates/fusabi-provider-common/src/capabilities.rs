@@ -0,0 +1,288 @@
+//! What a provider accepts and produces
+//!
+//! Tooling cannot tell from a `TypeProvider` whether it has built-in
+//! schemas, fetches sources over HTTP or introspects a running service
+//! without trying it. Providers describe themselves through
+//! [`DescribedProvider::capabilities`] instead: the [`SourceKind`]s their
+//! `resolve_schema` accepts, the names of their built-in schemas, the URI
+//! schemes they introspect, the params they read and the
+//! [`OutputFeature`]s of their output.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_common::capabilities::{Capabilities, DescribedProvider, OutputFeature, SourceKind};
+//! use fusabi_provider_common::params::{ParamKind, ParamSpec};
+//!
+//! impl DescribedProvider for MongoProvider {
+//!     fn capabilities(&self) -> Capabilities {
+//!         Capabilities::new(self.name())
+//!             .documents()
+//!             .live("mongodb://")
+//!             .live("mongodb+srv://")
+//!             .param(ParamSpec::new("sample", ParamKind::UInt).default("100"))
+//!             .feature(OutputFeature::Unions)
+//!     }
+//! }
+//!
+//! let capabilities = MongoProvider::new().capabilities();
+//! assert!(capabilities.supports(SourceKind::Live));
+//! ```
+
+use crate::params::{ParamSchema, ParamSpec};
+use fusabi_type_providers::TypeProvider;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// A kind of source `resolve_schema` accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SourceKind {
+    /// The source text itself
+    Inline,
+    /// A path to a local file
+    File,
+    /// A path to a local directory, such as a repository checkout
+    Directory,
+    /// An `http://` or `https://` URL, fetched through the shared source layer
+    Remote,
+    /// The name of a schema built into the provider
+    Embedded,
+    /// A connection URI of a running service, introspected live
+    Live,
+}
+
+impl SourceKind {
+    /// Name used in JSON and messages
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SourceKind::Inline => "inline",
+            SourceKind::File => "file",
+            SourceKind::Directory => "directory",
+            SourceKind::Remote => "remote",
+            SourceKind::Embedded => "embedded",
+            SourceKind::Live => "live",
+        }
+    }
+}
+
+impl fmt::Display for SourceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Something a provider's output carries beyond plain records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutputFeature {
+    /// Discriminated unions
+    Unions,
+    /// Source descriptions, through [`DocumentedProvider`](crate::docs::DocumentedProvider)
+    Docs,
+    /// Source metadata, through [`AttributedProvider`](crate::attributes::AttributedProvider)
+    Attributes,
+    /// Field defaults, through [`DefaultedProvider`](crate::defaults::DefaultedProvider)
+    Defaults,
+    /// Provider-specific diagnostics from
+    /// [`SourceValidator::schema_diagnostics`](crate::validate::SourceValidator::schema_diagnostics)
+    Diagnostics,
+    /// Decoders for data in the source format, through
+    /// [`DecoderCodegen`](crate::decode::DecoderCodegen)
+    Decoders,
+}
+
+impl OutputFeature {
+    /// Name used in JSON and messages
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutputFeature::Unions => "unions",
+            OutputFeature::Docs => "docs",
+            OutputFeature::Attributes => "attributes",
+            OutputFeature::Defaults => "defaults",
+            OutputFeature::Diagnostics => "diagnostics",
+            OutputFeature::Decoders => "decoders",
+        }
+    }
+}
+
+impl fmt::Display for OutputFeature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What a provider accepts and produces
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    provider: String,
+    sources: BTreeSet<SourceKind>,
+    embedded: Vec<&'static str>,
+    live: Vec<&'static str>,
+    params: Vec<ParamSpec>,
+    features: BTreeSet<OutputFeature>,
+}
+
+impl Capabilities {
+    /// Describe the provider named `provider`, which accepts nothing yet
+    pub fn new(provider: &str) -> Self {
+        Self {
+            provider: provider.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Accept the source text itself, a file path and an HTTP(S) URL, as
+    /// providers reading through [`read_source`](crate::source::read_source) do
+    pub fn documents(self) -> Self {
+        self.source(SourceKind::Inline)
+            .source(SourceKind::File)
+            .source(SourceKind::Remote)
+    }
+
+    /// Accept a kind of source
+    pub fn source(mut self, kind: SourceKind) -> Self {
+        self.sources.insert(kind);
+        self
+    }
+
+    /// Accept `name` as the source of a built-in schema
+    pub fn embedded(mut self, name: &'static str) -> Self {
+        self.embedded.push(name);
+        self.source(SourceKind::Embedded)
+    }
+
+    /// Introspect services at URIs starting with `scheme`, e.g. `mongodb://`
+    pub fn live(mut self, scheme: &'static str) -> Self {
+        self.live.push(scheme);
+        self.source(SourceKind::Live)
+    }
+
+    /// Read a param
+    pub fn param(mut self, spec: ParamSpec) -> Self {
+        self.params.push(spec);
+        self
+    }
+
+    /// Read every param of a provider's [`ParamSchema`]
+    pub fn params(mut self, schema: &ParamSchema) -> Self {
+        self.params.extend(schema.specs().iter().cloned());
+        self
+    }
+
+    /// Produce an output feature
+    pub fn feature(mut self, feature: OutputFeature) -> Self {
+        self.features.insert(feature);
+        self
+    }
+
+    /// Name of the provider
+    pub fn provider(&self) -> &str {
+        &self.provider
+    }
+
+    /// Kinds of source accepted
+    pub fn sources(&self) -> impl Iterator<Item = SourceKind> + '_ {
+        self.sources.iter().copied()
+    }
+
+    /// Whether a kind of source is accepted
+    pub fn supports(&self, kind: SourceKind) -> bool {
+        self.sources.contains(&kind)
+    }
+
+    /// Sources naming built-in schemas
+    pub fn embedded_sources(&self) -> &[&'static str] {
+        &self.embedded
+    }
+
+    /// URI schemes introspected live
+    pub fn live_schemes(&self) -> &[&'static str] {
+        &self.live
+    }
+
+    /// Params read by the provider, besides the shared ones
+    pub fn param_specs(&self) -> &[ParamSpec] {
+        &self.params
+    }
+
+    /// Declaration of the param named `key`
+    pub fn spec(&self, key: &str) -> Option<&ParamSpec> {
+        self.params.iter().find(|spec| spec.key == key)
+    }
+
+    /// Output features produced
+    pub fn features(&self) -> impl Iterator<Item = OutputFeature> + '_ {
+        self.features.iter().copied()
+    }
+
+    /// Whether an output feature is produced
+    pub fn has_feature(&self, feature: OutputFeature) -> bool {
+        self.features.contains(&feature)
+    }
+
+    /// Capabilities as JSON, for tools that list providers
+    pub fn to_json(&self) -> Value {
+        let params: Vec<Value> = self
+            .params
+            .iter()
+            .map(|spec| {
+                json!({
+                    "key": spec.key,
+                    "expected": spec.kind.expected(),
+                    "default": spec.default,
+                    "description": spec.description,
+                })
+            })
+            .collect();
+
+        json!({
+            "provider": self.provider,
+            "sources": self.sources,
+            "embedded": self.embedded,
+            "live": self.live,
+            "params": params,
+            "features": self.features,
+        })
+    }
+}
+
+/// Type providers that describe what they accept and produce
+pub trait DescribedProvider: TypeProvider {
+    /// Sources, params and output features of the provider
+    fn capabilities(&self) -> Capabilities;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::params::ParamKind;
+
+    #[test]
+    fn test_capabilities() {
+        let schema = ParamSchema::new("MongoProvider")
+            .param(ParamSpec::new("sample", ParamKind::UInt).default("100").describe("Documents sampled"));
+        let capabilities = Capabilities::new("MongoProvider")
+            .documents()
+            .live("mongodb://")
+            .live("mongodb+srv://")
+            .params(&schema)
+            .feature(OutputFeature::Unions);
+
+        assert!(capabilities.supports(SourceKind::Inline));
+        assert!(capabilities.supports(SourceKind::Live));
+        assert!(!capabilities.supports(SourceKind::Embedded));
+        assert_eq!(capabilities.live_schemes(), ["mongodb://", "mongodb+srv://"]);
+        assert_eq!(capabilities.spec("sample").and_then(|spec| spec.default), Some("100"));
+        assert!(capabilities.has_feature(OutputFeature::Unions));
+        assert!(!capabilities.has_feature(OutputFeature::Docs));
+
+        let json = capabilities.to_json();
+        assert_eq!(json["provider"], "MongoProvider");
+        assert_eq!(json["sources"], json!(["inline", "file", "remote", "live"]));
+        assert_eq!(json["params"][0]["expected"], "a non-negative integer");
+        assert_eq!(json["features"], json!(["unions"]));
+    }
+}
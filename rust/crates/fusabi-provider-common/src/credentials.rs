@@ -0,0 +1,326 @@
+//! Credential resolution for remote sources
+//!
+//! Providers that talk to remote endpoints need secrets, but secrets must
+//! never appear inline in a schema source string or a checked-in params
+//! block. Instead, params carry a *reference* to where the secret lives:
+//!
+//! - `auth=env:GITHUB_TOKEN` - read an environment variable
+//! - `auth=file:/run/secrets/token` - read a file (trailing newline trimmed)
+//! - `auth=host:github` - ask the embedding host through a callback
+//!
+//! [`CredentialResolver`] turns a reference into a [`Credential`], whose
+//! `Debug` output is redacted so it cannot leak through logs or errors.
+//!
+//! HTTP sources send the credential as a bearer token unless
+//! `auth_scheme=basic` is set, in which case the secret must have the form
+//! `user:password`.
+
+use base64::Engine;
+use fusabi_type_providers::{ProviderError, ProviderParams, ProviderResult};
+use std::fmt;
+use std::path::PathBuf;
+
+/// Default params key holding a credential reference
+pub const AUTH_PARAM: &str = "auth";
+
+/// Params key selecting how a credential is sent over HTTP
+pub const AUTH_SCHEME_PARAM: &str = "auth_scheme";
+
+/// Callback through which a host supplies named credentials
+pub type HostCredentialFn = dyn Fn(&str) -> Option<String> + Send + Sync;
+
+/// Where a credential comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CredentialSource {
+    /// Environment variable
+    Env(String),
+    /// File containing the secret
+    File(PathBuf),
+    /// Named credential supplied by the host
+    Host(String),
+}
+
+impl CredentialSource {
+    /// Parse a `scheme:name` credential reference
+    ///
+    /// Anything without a known scheme is rejected, so a raw token pasted
+    /// into params fails loudly instead of being used.
+    pub fn parse(reference: &str) -> ProviderResult<Self> {
+        let (scheme, name) = reference.split_once(':').ok_or_else(invalid_reference)?;
+        if name.is_empty() {
+            return Err(invalid_reference());
+        }
+
+        match scheme {
+            "env" => Ok(Self::Env(name.to_string())),
+            "file" => Ok(Self::File(PathBuf::from(name))),
+            "host" => Ok(Self::Host(name.to_string())),
+            _ => Err(invalid_reference()),
+        }
+    }
+}
+
+impl fmt::Display for CredentialSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Env(name) => write!(f, "env:{}", name),
+            Self::File(path) => write!(f, "file:{}", path.display()),
+            Self::Host(name) => write!(f, "host:{}", name),
+        }
+    }
+}
+
+/// HTTP authentication scheme for a credential
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AuthScheme {
+    /// `Authorization: Bearer <secret>`
+    #[default]
+    Bearer,
+    /// `Authorization: Basic <base64(user:password)>`
+    Basic,
+}
+
+impl AuthScheme {
+    /// Read the scheme from the `auth_scheme` param, defaulting to bearer
+    pub fn from_params(params: &ProviderParams) -> ProviderResult<Self> {
+        match params.custom.get(AUTH_SCHEME_PARAM).map(String::as_str) {
+            None | Some("bearer") => Ok(Self::Bearer),
+            Some("basic") => Ok(Self::Basic),
+            Some(other) => Err(ProviderError::InvalidSource(format!(
+                "Invalid auth_scheme '{}': expected bearer or basic",
+                other
+            ))),
+        }
+    }
+}
+
+/// A resolved secret
+#[derive(Clone, PartialEq, Eq)]
+pub struct Credential {
+    secret: String,
+}
+
+impl Credential {
+    /// Wrap a secret value
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self {
+            secret: secret.into(),
+        }
+    }
+
+    /// The secret value
+    pub fn expose(&self) -> &str {
+        &self.secret
+    }
+
+    /// `Authorization` header value using the bearer scheme
+    pub fn bearer(&self) -> String {
+        format!("Bearer {}", self.secret)
+    }
+
+    /// `Authorization` header value using the basic scheme
+    ///
+    /// The secret must be `user:password`; the error does not echo it.
+    pub fn basic(&self) -> ProviderResult<String> {
+        if !self.secret.contains(':') {
+            return Err(ProviderError::InvalidSource(
+                "Basic auth credential must have the form user:password".to_string(),
+            ));
+        }
+        let encoded = base64::engine::general_purpose::STANDARD.encode(&self.secret);
+        Ok(format!("Basic {}", encoded))
+    }
+
+    /// `Authorization` header value for a scheme
+    pub fn authorization(&self, scheme: AuthScheme) -> ProviderResult<String> {
+        match scheme {
+            AuthScheme::Bearer => Ok(self.bearer()),
+            AuthScheme::Basic => self.basic(),
+        }
+    }
+}
+
+impl fmt::Debug for Credential {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Credential(<redacted>)")
+    }
+}
+
+/// Resolves credential references to secrets
+#[derive(Default)]
+pub struct CredentialResolver {
+    host: Option<Box<HostCredentialFn>>,
+}
+
+impl CredentialResolver {
+    /// Create a resolver supporting `env:` and `file:` references
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable `host:` references through a callback
+    pub fn with_host_callback<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&str) -> Option<String> + Send + Sync + 'static,
+    {
+        self.host = Some(Box::new(callback));
+        self
+    }
+
+    /// Resolve a credential source
+    pub fn resolve(&self, source: &CredentialSource) -> ProviderResult<Credential> {
+        let secret = match source {
+            CredentialSource::Env(name) => std::env::var(name).map_err(|_| {
+                ProviderError::InvalidSource(format!(
+                    "Credential {} is not set in the environment",
+                    source
+                ))
+            })?,
+            CredentialSource::File(path) => std::fs::read_to_string(path)
+                .map_err(|e| {
+                    ProviderError::IoError(format!("Failed to read credential {}: {}", source, e))
+                })?
+                .trim_end_matches(['\r', '\n'])
+                .to_string(),
+            CredentialSource::Host(name) => {
+                let callback = self.host.as_ref().ok_or_else(|| {
+                    ProviderError::InvalidSource(format!(
+                        "Credential {} requires a host credential callback",
+                        source
+                    ))
+                })?;
+                callback(name).ok_or_else(|| {
+                    ProviderError::InvalidSource(format!(
+                        "Host did not supply credential {}",
+                        source
+                    ))
+                })?
+            }
+        };
+
+        if secret.is_empty() {
+            return Err(ProviderError::InvalidSource(format!(
+                "Credential {} is empty",
+                source
+            )));
+        }
+
+        Ok(Credential::new(secret))
+    }
+
+    /// Resolve a `scheme:name` reference
+    pub fn resolve_reference(&self, reference: &str) -> ProviderResult<Credential> {
+        self.resolve(&CredentialSource::parse(reference)?)
+    }
+
+    /// Resolve the credential referenced by the `auth` param, if any
+    pub fn from_params(&self, params: &ProviderParams) -> ProviderResult<Option<Credential>> {
+        self.from_param(params, AUTH_PARAM)
+    }
+
+    /// Resolve the credential referenced by a specific param, if any
+    pub fn from_param(
+        &self,
+        params: &ProviderParams,
+        key: &str,
+    ) -> ProviderResult<Option<Credential>> {
+        params
+            .custom
+            .get(key)
+            .map(|reference| self.resolve_reference(reference))
+            .transpose()
+    }
+}
+
+impl fmt::Debug for CredentialResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CredentialResolver")
+            .field("host", &self.host.is_some())
+            .finish()
+    }
+}
+
+fn invalid_reference() -> ProviderError {
+    // The reference itself may be a pasted secret, so it is not echoed back
+    ProviderError::InvalidSource(
+        "Credential reference must be env:NAME, file:PATH or host:NAME".to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reference() {
+        assert_eq!(
+            CredentialSource::parse("env:GITHUB_TOKEN").unwrap(),
+            CredentialSource::Env("GITHUB_TOKEN".to_string())
+        );
+        assert_eq!(
+            CredentialSource::parse("file:/run/secrets/token").unwrap(),
+            CredentialSource::File(PathBuf::from("/run/secrets/token"))
+        );
+        assert!(CredentialSource::parse("ghp_abcdef123456").is_err());
+        assert!(CredentialSource::parse("env:").is_err());
+        assert!(CredentialSource::parse("vault:secret").is_err());
+    }
+
+    #[test]
+    fn test_env_and_file() {
+        std::env::set_var("FUSABI_CREDENTIALS_TEST_TOKEN", "s3cret");
+        let resolver = CredentialResolver::new();
+        let params = ProviderParams::default().with("auth", "env:FUSABI_CREDENTIALS_TEST_TOKEN");
+
+        let credential = resolver.from_params(&params).unwrap().unwrap();
+        assert_eq!(credential.expose(), "s3cret");
+        assert_eq!(credential.bearer(), "Bearer s3cret");
+        assert_eq!(format!("{:?}", credential), "Credential(<redacted>)");
+
+        let path = std::env::temp_dir().join("fusabi-credentials-test-token");
+        std::fs::write(&path, "from-file\n").unwrap();
+        let credential = resolver
+            .resolve(&CredentialSource::File(path.clone()))
+            .unwrap();
+        assert_eq!(credential.expose(), "from-file");
+        std::fs::remove_file(path).unwrap();
+
+        assert!(resolver
+            .resolve_reference("env:FUSABI_CREDENTIALS_TEST_MISSING")
+            .is_err());
+        assert!(resolver.from_params(&ProviderParams::default()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_host_callback() {
+        let resolver = CredentialResolver::new();
+        assert!(resolver.resolve_reference("host:github").is_err());
+
+        let resolver = CredentialResolver::new()
+            .with_host_callback(|name| (name == "github").then(|| "from-host".to_string()));
+        assert_eq!(
+            resolver.resolve_reference("host:github").unwrap().expose(),
+            "from-host"
+        );
+        assert!(resolver.resolve_reference("host:gitlab").is_err());
+    }
+
+    #[test]
+    fn test_auth_scheme() {
+        assert_eq!(
+            AuthScheme::from_params(&ProviderParams::default()).unwrap(),
+            AuthScheme::Bearer
+        );
+        let params = ProviderParams::default().with("auth_scheme", "basic");
+        assert_eq!(AuthScheme::from_params(&params).unwrap(), AuthScheme::Basic);
+        let params = ProviderParams::default().with("auth_scheme", "digest");
+        assert!(AuthScheme::from_params(&params).is_err());
+
+        let credential = Credential::new("aladdin:opensesame");
+        assert_eq!(
+            credential.authorization(AuthScheme::Basic).unwrap(),
+            "Basic YWxhZGRpbjpvcGVuc2VzYW1l"
+        );
+        assert!(Credential::new("token-only").basic().is_err());
+    }
+}
@@ -0,0 +1,278 @@
+//! Decoders for data described by generated types
+//!
+//! Generated types describe the shape of data; reading actual payloads
+//! into them needs details the types do not carry: the JSON key of each
+//! field, protobuf field numbers and wire encodings, the column each field
+//! is read from. Providers that know how their data is encoded implement
+//! [`DecoderCodegen`] and return a [`DecoderPlan`] next to the generated
+//! types, keyed by module path and type name the same way
+//! [`TypeAttributes`](crate::attributes::TypeAttributes) is.
+//!
+//! A plan is independent of the target language. The Rust emitter renders
+//! it as decode functions next to each type through
+//! [`emit_rust_decoders`](crate::emit::rust::emit_rust_decoders):
+//!
+//! | Format                  | Rust decoder                                |
+//! |-------------------------|---------------------------------------------|
+//! | [`WireFormat::Json`]     | `T::from_json(&str)`, through `serde`       |
+//! | [`WireFormat::Protobuf`] | `T::decode_protobuf(&[u8])`                 |
+//! | [`WireFormat::Row`]      | `T::from_row(&[&str])`, e.g. a CSV record   |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_common::{emit, DecoderCodegen};
+//!
+//! let schema = provider.resolve_schema("schema.proto", &params)?;
+//! let (types, plan) = provider.generate_decoders(&schema, "Api")?;
+//! let source = emit::emit_rust_decoders(&types, &plan, &Default::default());
+//! ```
+
+use fusabi_type_providers::{GeneratedTypes, ProviderResult, Schema, TypeDefinition, TypeProvider};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Encoding of the data a source describes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WireFormat {
+    /// JSON documents
+    Json,
+    /// Protocol Buffers binary encoding
+    Protobuf,
+    /// Rows of text columns, such as CSV records or query results
+    Row,
+}
+
+impl WireFormat {
+    /// Name used in messages
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WireFormat::Json => "json",
+            WireFormat::Protobuf => "protobuf",
+            WireFormat::Row => "row",
+        }
+    }
+}
+
+impl fmt::Display for WireFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Wire encoding of a protobuf field value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProtoEncoding {
+    /// `int32`, `int64`, `uint32` and `uint64`
+    Varint,
+    /// ZigZag-encoded `sint32` and `sint64`
+    ZigZag,
+    /// `bool`
+    Bool,
+    /// `fixed32`
+    Fixed32,
+    /// `fixed64`
+    Fixed64,
+    /// `sfixed32`
+    SFixed32,
+    /// `sfixed64`
+    SFixed64,
+    /// `float`
+    Float,
+    /// `double`
+    Double,
+    /// `string`
+    String,
+    /// `bytes`
+    Bytes,
+    /// Embedded message
+    Message,
+    /// Enum value number
+    Enum,
+    /// `map<K, V>`, as repeated entries with the key in field 1 and the
+    /// value in field 2
+    Map(Box<ProtoEncoding>, Box<ProtoEncoding>),
+}
+
+impl ProtoEncoding {
+    /// Whether repeated values may be packed into one length-delimited field
+    pub fn is_packable(&self) -> bool {
+        !matches!(
+            self,
+            ProtoEncoding::String | ProtoEncoding::Bytes | ProtoEncoding::Message | ProtoEncoding::Map(..)
+        )
+    }
+}
+
+/// Where and how a record field is read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldCodec {
+    /// Member `key` of a JSON object
+    Json { key: String },
+    /// Protobuf field `number`
+    Protobuf { number: u32, encoding: ProtoEncoding },
+    /// Text column at `index` of a row
+    Column { index: usize },
+}
+
+/// Decoder of one record field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDecoder {
+    /// Field name, as in the record definition
+    pub field: String,
+    /// Where and how the field is read
+    pub codec: FieldCodec,
+}
+
+impl FieldDecoder {
+    /// Decode field `field` with `codec`
+    pub fn new(field: impl Into<String>, codec: FieldCodec) -> Self {
+        Self {
+            field: field.into(),
+            codec,
+        }
+    }
+}
+
+/// Decoder of a generated type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeDecoder {
+    /// Record read field by field; fields without a decoder are not read
+    Record(Vec<FieldDecoder>),
+    /// Union of unit variants read from a number, as protobuf enums are
+    Numbered(Vec<(i64, String)>),
+    /// Union read from its tagged JSON representation
+    Tagged,
+}
+
+/// Key of a decoded type
+type DecoderKey = (Vec<String>, String);
+
+/// How data in one wire format is decoded into generated types
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecoderPlan {
+    format: WireFormat,
+    decoders: BTreeMap<DecoderKey, TypeDecoder>,
+}
+
+impl DecoderPlan {
+    /// Create an empty plan for data in `format`
+    pub fn new(format: WireFormat) -> Self {
+        Self {
+            format,
+            decoders: BTreeMap::new(),
+        }
+    }
+
+    /// Plan for JSON documents matching `types`
+    ///
+    /// Record fields are read from the member of the same name, and unions
+    /// from their tagged representation.
+    pub fn json(types: &GeneratedTypes) -> Self {
+        let mut plan = Self::new(WireFormat::Json);
+        let modules = std::iter::once((&[] as &[String], &types.root_types))
+            .chain(types.modules.iter().map(|m| (m.path.as_slice(), &m.types)));
+
+        for (path, definitions) in modules {
+            for definition in definitions {
+                match definition {
+                    TypeDefinition::Record(record) => {
+                        let fields = record
+                            .fields
+                            .iter()
+                            .map(|(name, _)| FieldDecoder::new(name, FieldCodec::Json { key: name.clone() }))
+                            .collect();
+                        plan.set(path, &record.name, TypeDecoder::Record(fields));
+                    }
+                    TypeDefinition::Du(du) => plan.set(path, &du.name, TypeDecoder::Tagged),
+                }
+            }
+        }
+        plan
+    }
+
+    /// Wire format of the data
+    pub fn format(&self) -> WireFormat {
+        self.format
+    }
+
+    /// Set the decoder of a type declared in the module at `path`
+    pub fn set(&mut self, path: &[String], type_name: &str, decoder: TypeDecoder) {
+        self.decoders.insert((path.to_vec(), type_name.to_string()), decoder);
+    }
+
+    /// Decoder of a type, if it has one
+    pub fn decoder(&self, path: &[String], type_name: &str) -> Option<&TypeDecoder> {
+        self.decoders.get(&(path.to_vec(), type_name.to_string()))
+    }
+
+    /// Every decoded type as `(path, type name, decoder)`
+    pub fn iter(&self) -> impl Iterator<Item = (&[String], &str, &TypeDecoder)> {
+        self.decoders
+            .iter()
+            .map(|((path, type_name), decoder)| (path.as_slice(), type_name.as_str(), decoder))
+    }
+
+    /// Number of decoded types
+    pub fn len(&self) -> usize {
+        self.decoders.len()
+    }
+
+    /// Whether no type is decoded
+    pub fn is_empty(&self) -> bool {
+        self.decoders.is_empty()
+    }
+}
+
+/// Type providers that describe how data is decoded into their types
+pub trait DecoderCodegen: TypeProvider {
+    /// Encoding of the data the provider's sources describe
+    fn wire_format(&self) -> WireFormat;
+
+    /// Generate types together with a plan for decoding data into them
+    ///
+    /// The types must be the same as [`TypeProvider::generate_types`]
+    /// returns for the same schema and namespace.
+    fn generate_decoders(
+        &self,
+        schema: &Schema,
+        namespace: &str,
+    ) -> ProviderResult<(GeneratedTypes, DecoderPlan)>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_type_providers::{DuDef, GeneratedModule, RecordDef, TypeExpr, VariantDef};
+
+    #[test]
+    fn test_json_plan() {
+        let mut types = GeneratedTypes::new();
+        let mut module = GeneratedModule::new(vec!["Api".to_string()]);
+        module.types.push(TypeDefinition::Record(RecordDef {
+            name: "User".to_string(),
+            fields: vec![("userId".to_string(), TypeExpr::Named("int".to_string()))],
+        }));
+        module.types.push(TypeDefinition::Du(DuDef {
+            name: "Role".to_string(),
+            variants: vec![VariantDef::new_simple("Admin".to_string())],
+        }));
+        types.modules.push(module);
+
+        let plan = DecoderPlan::json(&types);
+        let path = vec!["Api".to_string()];
+        assert_eq!(plan.format(), WireFormat::Json);
+        assert_eq!(plan.len(), 2);
+        assert_eq!(plan.decoder(&path, "Role"), Some(&TypeDecoder::Tagged));
+        assert_eq!(
+            plan.decoder(&path, "User"),
+            Some(&TypeDecoder::Record(vec![FieldDecoder::new(
+                "userId",
+                FieldCodec::Json { key: "userId".to_string() }
+            )]))
+        );
+        assert!(plan.decoder(&[], "User").is_none());
+        assert!(ProtoEncoding::ZigZag.is_packable());
+        assert!(!ProtoEncoding::Message.is_packable());
+    }
+}
@@ -0,0 +1,185 @@
+//! Default values for generated record fields
+//!
+//! Record definitions only carry names and type expressions, so default
+//! values found in a source (SQL `DEFAULT` clauses, JSON Schema `default`,
+//! the values of a TOML or `.env` file) travel next to the generated types
+//! in a [`FieldDefaults`] table, keyed by module path, type name and field
+//! name, the same way [`TypeDocs`](crate::docs::TypeDocs) carries
+//! descriptions.
+//!
+//! Providers that keep defaults implement [`DefaultedProvider`]. The Fusabi
+//! emitter renders them as a constructor next to each record through
+//! [`emit_fusabi_with_defaults`](crate::emit::fusabi::emit_fusabi_with_defaults),
+//! so that consumers only pass the fields without a default.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_common::{emit, DefaultedProvider, TypeDocs};
+//!
+//! let schema = provider.resolve_schema("schema.sql", &params)?;
+//! let (types, defaults) = provider.generate_with_defaults(&schema, "Database")?;
+//! let source = emit::emit_fusabi_with_defaults(&types, &TypeDocs::new(), &defaults, &Default::default());
+//! ```
+
+use fusabi_type_providers::{GeneratedTypes, ProviderResult, Schema, TypeProvider};
+use std::collections::BTreeMap;
+
+/// Key of a defaulted field
+type FieldKey = (Vec<String>, String, String);
+
+/// Default value of a record field
+#[derive(Debug, Clone, PartialEq)]
+pub enum DefaultValue {
+    /// `true` or `false`
+    Bool(bool),
+    /// Integer literal
+    Int(i64),
+    /// Floating-point literal
+    Float(f64),
+    /// String literal
+    String(String),
+    /// List of values
+    List(Vec<DefaultValue>),
+    /// No value, for optional fields (`NULL`, JSON `null`)
+    None,
+    /// Expression evaluated by the source system, such as `CURRENT_TIMESTAMP`
+    Expr(String),
+}
+
+impl DefaultValue {
+    /// Convert a JSON value; objects have no default representation
+    pub fn from_json(value: &serde_json::Value) -> Option<Self> {
+        Some(match value {
+            serde_json::Value::Null => DefaultValue::None,
+            serde_json::Value::Bool(b) => DefaultValue::Bool(*b),
+            serde_json::Value::Number(n) => match n.as_i64() {
+                Some(i) => DefaultValue::Int(i),
+                None => DefaultValue::Float(n.as_f64()?),
+            },
+            serde_json::Value::String(s) => DefaultValue::String(s.clone()),
+            serde_json::Value::Array(items) => {
+                DefaultValue::List(items.iter().map(Self::from_json).collect::<Option<_>>()?)
+            }
+            serde_json::Value::Object(_) => return None,
+        })
+    }
+
+    /// Fusabi expression for the value
+    ///
+    /// Returns `None` for [`DefaultValue::Expr`], which only the source
+    /// system can evaluate, and for lists containing one.
+    pub fn to_fusabi(&self) -> Option<String> {
+        Some(match self {
+            DefaultValue::Bool(b) => b.to_string(),
+            DefaultValue::Int(i) => i.to_string(),
+            // `{:?}` keeps the decimal point on whole numbers
+            DefaultValue::Float(f) if f.is_finite() => format!("{:?}", f),
+            DefaultValue::Float(_) => return None,
+            DefaultValue::String(s) => format!("{:?}", s),
+            DefaultValue::List(items) => {
+                let items = items.iter().map(Self::to_fusabi).collect::<Option<Vec<_>>>()?;
+                format!("[{}]", items.join("; "))
+            }
+            DefaultValue::None => "None".to_string(),
+            DefaultValue::Expr(_) => return None,
+        })
+    }
+}
+
+/// Default values for generated record fields
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FieldDefaults {
+    entries: BTreeMap<FieldKey, DefaultValue>,
+}
+
+impl FieldDefaults {
+    /// Create an empty table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the default of a field of a record declared in the module at `path`
+    pub fn set(&mut self, path: &[String], type_name: &str, field: &str, value: DefaultValue) {
+        self.entries
+            .insert((path.to_vec(), type_name.to_string(), field.to_string()), value);
+    }
+
+    /// Default of a record field
+    pub fn get(&self, path: &[String], type_name: &str, field: &str) -> Option<&DefaultValue> {
+        self.entries
+            .get(&(path.to_vec(), type_name.to_string(), field.to_string()))
+    }
+
+    /// Number of defaulted fields
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no field has a default
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Add every entry of `other`, replacing existing defaults
+    pub fn extend(&mut self, other: FieldDefaults) {
+        self.entries.extend(other.entries);
+    }
+}
+
+/// Type providers that carry source default values into generated types
+pub trait DefaultedProvider: TypeProvider {
+    /// Generate types together with their field defaults
+    ///
+    /// The types must be the same as [`TypeProvider::generate_types`]
+    /// returns for the same schema and namespace.
+    fn generate_with_defaults(
+        &self,
+        schema: &Schema,
+        namespace: &str,
+    ) -> ProviderResult<(GeneratedTypes, FieldDefaults)>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_fusabi() {
+        assert_eq!(DefaultValue::Bool(true).to_fusabi().as_deref(), Some("true"));
+        assert_eq!(DefaultValue::Int(-3).to_fusabi().as_deref(), Some("-3"));
+        assert_eq!(DefaultValue::Float(2.0).to_fusabi().as_deref(), Some("2.0"));
+        assert_eq!(
+            DefaultValue::String("say \"hi\"".to_string()).to_fusabi().as_deref(),
+            Some(r#""say \"hi\"""#)
+        );
+        assert_eq!(
+            DefaultValue::List(vec![DefaultValue::Int(1), DefaultValue::Int(2)])
+                .to_fusabi()
+                .as_deref(),
+            Some("[1; 2]")
+        );
+        assert_eq!(DefaultValue::Expr("now()".to_string()).to_fusabi(), None);
+    }
+
+    #[test]
+    fn test_from_json_and_table() {
+        let value = serde_json::json!(["a", 1.5, null]);
+        assert_eq!(
+            DefaultValue::from_json(&value),
+            Some(DefaultValue::List(vec![
+                DefaultValue::String("a".to_string()),
+                DefaultValue::Float(1.5),
+                DefaultValue::None,
+            ]))
+        );
+        assert_eq!(DefaultValue::from_json(&serde_json::json!({ "a": 1 })), None);
+
+        let path = vec!["Db".to_string()];
+        let mut defaults = FieldDefaults::new();
+        defaults.set(&path, "Users", "active", DefaultValue::Bool(true));
+        assert_eq!(defaults.len(), 1);
+        assert_eq!(defaults.get(&path, "Users", "active"), Some(&DefaultValue::Bool(true)));
+        assert_eq!(defaults.get(&[], "Users", "active"), None);
+    }
+}
@@ -0,0 +1,345 @@
+//! Structured differences between generated types
+//!
+//! [`diff_generated`] compares two [`GeneratedTypes`] trees by module path
+//! and type name. Every type that was added, removed or changed is listed
+//! with the fields or cases that differ and their old and new types, so
+//! that a review of a regeneration reads as a list of changes rather than
+//! a diff of emitted source.
+//!
+//! Drift checks and the watcher summarize the result as a [`DriftReport`];
+//! [`TypesDiff::changed_modules`] tells incremental generation which
+//! modules need to be written again.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_common::diff::{diff_generated, ChangeKind};
+//!
+//! let diff = diff_generated(&previous, &types);
+//! for ty in &diff.types {
+//!     for member in ty.members.iter().filter(|m| m.change == ChangeKind::Changed) {
+//!         eprintln!("{}.{}: {:?} -> {:?}", ty.qualified_name(), member.name, member.old_type, member.new_type);
+//!     }
+//! }
+//! ```
+
+use crate::index::qualified_name;
+use crate::lock::{DriftReport, TypeChange};
+use crate::shape::TypeShape;
+use fusabi_type_providers::{GeneratedTypes, TypeDefinition, TypeExpr};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// How a type or member differs between two trees
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    /// Only in the new tree
+    Added,
+    /// Only in the old tree
+    Removed,
+    /// In both, with a different definition
+    Changed,
+}
+
+/// Difference in one record field or union case
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemberDiff {
+    /// Field or case name
+    pub name: String,
+    /// How the member differs
+    pub change: ChangeKind,
+    /// Type in the old tree; case fields are joined with ` * `
+    pub old_type: Option<String>,
+    /// Type in the new tree
+    pub new_type: Option<String>,
+}
+
+/// Difference in one type
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypeDiff {
+    /// Path of the module declaring the type; empty for root types
+    pub module: Vec<String>,
+    /// Type name
+    pub name: String,
+    /// How the type differs
+    pub change: ChangeKind,
+    /// Whether the type switched between record and union
+    pub kind_changed: bool,
+    /// Members that differ; every member of an added or removed type
+    pub members: Vec<MemberDiff>,
+}
+
+impl TypeDiff {
+    /// Type name qualified by module path
+    pub fn qualified_name(&self) -> String {
+        qualified_name(&self.module, &self.name)
+    }
+
+    fn member_names(&self, change: ChangeKind) -> Vec<String> {
+        self.members
+            .iter()
+            .filter(|member| member.change == change)
+            .map(|member| member.name.clone())
+            .collect()
+    }
+}
+
+/// Differences between two generated type trees
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypesDiff {
+    /// Added, removed and changed types, ordered by qualified name within
+    /// each kind of change
+    pub types: Vec<TypeDiff>,
+}
+
+impl TypesDiff {
+    /// Whether the trees declare the same types
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+
+    /// Paths of the modules that declare an added, removed or changed type
+    pub fn changed_modules(&self) -> BTreeSet<Vec<String>> {
+        self.types.iter().map(|ty| ty.module.clone()).collect()
+    }
+
+    /// Type-level summary as reported by drift checks
+    pub fn to_drift_report(&self) -> DriftReport {
+        let mut report = DriftReport::default();
+        for ty in &self.types {
+            let name = ty.qualified_name();
+            match ty.change {
+                ChangeKind::Added => report.added.push(name),
+                ChangeKind::Removed => report.removed.push(name),
+                ChangeKind::Changed => report.changed.push(TypeChange {
+                    name,
+                    kind_changed: ty.kind_changed,
+                    members_added: ty.member_names(ChangeKind::Added),
+                    members_removed: ty.member_names(ChangeKind::Removed),
+                    members_changed: ty.member_names(ChangeKind::Changed),
+                }),
+            }
+        }
+        report
+    }
+}
+
+/// Compare two sets of generated types by module path and type name
+pub fn diff_generated(old: &GeneratedTypes, new: &GeneratedTypes) -> TypesDiff {
+    let old = signatures(old);
+    let new = signatures(new);
+    let mut diff = TypesDiff::default();
+
+    for (key, signature) in &new {
+        match old.get(key) {
+            None => diff.types.push(whole(signature, ChangeKind::Added)),
+            Some(previous) if previous != signature => diff.types.push(compare(previous, signature)),
+            Some(_) => {}
+        }
+    }
+    for (key, signature) in &old {
+        if !new.contains_key(key) {
+            diff.types.push(whole(signature, ChangeKind::Removed));
+        }
+    }
+
+    diff
+}
+
+/// Kind and members of a type, with member types rendered canonically
+#[derive(Debug, PartialEq)]
+struct Signature<'a> {
+    module: &'a [String],
+    name: &'a str,
+    is_record: bool,
+    members: BTreeMap<&'a str, String>,
+}
+
+fn signatures(types: &GeneratedTypes) -> BTreeMap<String, Signature<'_>> {
+    let root = types.root_types.iter().map(|t| (&[] as &[String], t));
+    let modules = types
+        .modules
+        .iter()
+        .flat_map(|m| m.types.iter().map(move |t| (m.path.as_slice(), t)));
+
+    root.chain(modules)
+        .map(|(module, type_def)| {
+            let (name, is_record, members) = match type_def {
+                TypeDefinition::Record(record) => (
+                    record.name.as_str(),
+                    true,
+                    record
+                        .fields
+                        .iter()
+                        .map(|(name, ty)| (name.as_str(), render(ty)))
+                        .collect(),
+                ),
+                TypeDefinition::Du(du) => (
+                    du.name.as_str(),
+                    false,
+                    du.variants
+                        .iter()
+                        .map(|variant| {
+                            let fields: Vec<_> = variant.fields.iter().map(render).collect();
+                            (variant.name.as_str(), fields.join(" * "))
+                        })
+                        .collect(),
+                ),
+            };
+            let signature = Signature {
+                module,
+                name,
+                is_record,
+                members,
+            };
+            (qualified_name(module, name), signature)
+        })
+        .collect()
+}
+
+fn render(ty: &TypeExpr) -> String {
+    TypeShape::from_type_expr(ty).to_string()
+}
+
+/// A type that is only in one tree, with all of its members
+fn whole(signature: &Signature, change: ChangeKind) -> TypeDiff {
+    let members = signature
+        .members
+        .iter()
+        .map(|(name, ty)| {
+            let ty = Some(ty.clone());
+            let (old_type, new_type) = match change {
+                ChangeKind::Removed => (ty, None),
+                _ => (None, ty),
+            };
+            MemberDiff {
+                name: name.to_string(),
+                change,
+                old_type,
+                new_type,
+            }
+        })
+        .collect();
+
+    TypeDiff {
+        module: signature.module.to_vec(),
+        name: signature.name.to_string(),
+        change,
+        kind_changed: false,
+        members,
+    }
+}
+
+fn compare(old: &Signature, new: &Signature) -> TypeDiff {
+    let names: BTreeSet<&str> = old.members.keys().chain(new.members.keys()).copied().collect();
+    let members = names
+        .into_iter()
+        .filter_map(|name| {
+            let old_type = old.members.get(name).cloned();
+            let new_type = new.members.get(name).cloned();
+            let change = match (&old_type, &new_type) {
+                (None, _) => ChangeKind::Added,
+                (_, None) => ChangeKind::Removed,
+                (old_type, new_type) if old_type != new_type => ChangeKind::Changed,
+                _ => return None,
+            };
+            Some(MemberDiff {
+                name: name.to_string(),
+                change,
+                old_type,
+                new_type,
+            })
+        })
+        .collect();
+
+    TypeDiff {
+        module: new.module.to_vec(),
+        name: new.name.to_string(),
+        change: ChangeKind::Changed,
+        kind_changed: old.is_record != new.is_record,
+        members,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_type_providers::{DuDef, GeneratedModule, RecordDef, VariantDef};
+
+    fn types(user_fields: &[(&str, &str)], with_status: bool) -> GeneratedTypes {
+        let mut types = GeneratedTypes::new();
+        let mut db = GeneratedModule::new(vec!["Db".to_string()]);
+        db.types.push(TypeDefinition::Record(RecordDef {
+            name: "User".to_string(),
+            fields: user_fields
+                .iter()
+                .map(|(name, ty)| (name.to_string(), TypeShape::parse(ty).into()))
+                .collect(),
+        }));
+        types.modules.push(db);
+
+        let mut api = GeneratedModule::new(vec!["Api".to_string()]);
+        if with_status {
+            api.types.push(TypeDefinition::Du(DuDef {
+                name: "Status".to_string(),
+                variants: vec![
+                    VariantDef::new_simple("Active".to_string()),
+                    VariantDef::new("Banned".to_string(), vec![TypeShape::named("string").into()]),
+                ],
+            }));
+        }
+        types.modules.push(api);
+        types
+    }
+
+    #[test]
+    fn test_diff_generated() {
+        let old = types(&[("id", "int"), ("name", "string")], false);
+        let new = types(&[("id", "int64"), ("email", "string option")], true);
+        assert!(diff_generated(&old, &old).is_empty());
+
+        let diff = diff_generated(&old, &new);
+        let names: Vec<_> = diff.types.iter().map(|t| (t.qualified_name(), t.change)).collect();
+        assert_eq!(
+            names,
+            [
+                ("Api.Status".to_string(), ChangeKind::Added),
+                ("Db.User".to_string(), ChangeKind::Changed),
+            ]
+        );
+        assert_eq!(diff.types[0].members[1].new_type.as_deref(), Some("string"));
+
+        let user = &diff.types[1];
+        assert_eq!(
+            user.members,
+            [
+                MemberDiff {
+                    name: "email".to_string(),
+                    change: ChangeKind::Added,
+                    old_type: None,
+                    new_type: Some("string option".to_string()),
+                },
+                MemberDiff {
+                    name: "id".to_string(),
+                    change: ChangeKind::Changed,
+                    old_type: Some("int".to_string()),
+                    new_type: Some("int64".to_string()),
+                },
+                MemberDiff {
+                    name: "name".to_string(),
+                    change: ChangeKind::Removed,
+                    old_type: Some("string".to_string()),
+                    new_type: None,
+                },
+            ]
+        );
+
+        let modules: Vec<_> = diff.changed_modules().into_iter().collect();
+        assert_eq!(modules, [vec!["Api".to_string()], vec!["Db".to_string()]]);
+
+        let report = diff_generated(&new, &old).to_drift_report();
+        assert_eq!(report.removed, ["Api.Status"]);
+        assert_eq!(report.changed[0].members_changed, ["id"]);
+    }
+}
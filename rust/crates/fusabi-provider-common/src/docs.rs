@@ -0,0 +1,123 @@
+//! Documentation for generated types
+//!
+//! Type definitions only carry names and type expressions, so descriptions
+//! found in a source (protobuf comments, SQL `COMMENT`s, tool and field
+//! descriptions) travel next to the generated types in a [`TypeDocs`] table,
+//! keyed by module path, type name and, for fields and variants, member name.
+//!
+//! Providers that keep descriptions implement [`DocumentedProvider`]. The
+//! Fusabi emitter renders the table as `///` comments through
+//! [`emit_fusabi_documented`](crate::emit::fusabi::emit_fusabi_documented).
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_common::{emit, DocumentedProvider};
+//!
+//! let schema = provider.resolve_schema("schema.sql", &params)?;
+//! let (types, docs) = provider.generate_documented(&schema, "Database")?;
+//! let source = emit::emit_fusabi_documented(&types, &docs, &Default::default());
+//! ```
+
+use fusabi_type_providers::{GeneratedTypes, ProviderResult, Schema, TypeProvider};
+use std::collections::BTreeMap;
+
+/// Key of a documented type or member
+type DocKey = (Vec<String>, String, Option<String>);
+
+/// Descriptions for generated types, fields and variants
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TypeDocs {
+    entries: BTreeMap<DocKey, String>,
+}
+
+impl TypeDocs {
+    /// Create an empty table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Document a type declared in the module at `path`
+    ///
+    /// Blank descriptions are ignored, and surrounding whitespace is trimmed.
+    pub fn set_type(&mut self, path: &[String], type_name: &str, doc: &str) {
+        self.insert((path.to_vec(), type_name.to_string(), None), doc);
+    }
+
+    /// Document a record field or union variant of a type
+    pub fn set_member(&mut self, path: &[String], type_name: &str, member: &str, doc: &str) {
+        self.insert(
+            (path.to_vec(), type_name.to_string(), Some(member.to_string())),
+            doc,
+        );
+    }
+
+    /// Description of a type
+    pub fn type_doc(&self, path: &[String], type_name: &str) -> Option<&str> {
+        self.get((path.to_vec(), type_name.to_string(), None))
+    }
+
+    /// Description of a record field or union variant
+    pub fn member_doc(&self, path: &[String], type_name: &str, member: &str) -> Option<&str> {
+        self.get((path.to_vec(), type_name.to_string(), Some(member.to_string())))
+    }
+
+    /// Number of documented types and members
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether nothing is documented
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Add every entry of `other`, replacing existing descriptions
+    pub fn extend(&mut self, other: TypeDocs) {
+        self.entries.extend(other.entries);
+    }
+
+    fn insert(&mut self, key: DocKey, doc: &str) {
+        let doc = doc.trim();
+        if !doc.is_empty() {
+            self.entries.insert(key, doc.to_string());
+        }
+    }
+
+    fn get(&self, key: DocKey) -> Option<&str> {
+        self.entries.get(&key).map(String::as_str)
+    }
+}
+
+/// Type providers that carry source descriptions into generated types
+pub trait DocumentedProvider: TypeProvider {
+    /// Generate types together with their documentation
+    ///
+    /// The types must be the same as [`TypeProvider::generate_types`]
+    /// returns for the same schema and namespace.
+    fn generate_documented(
+        &self,
+        schema: &Schema,
+        namespace: &str,
+    ) -> ProviderResult<(GeneratedTypes, TypeDocs)>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_type_docs() {
+        let path = vec!["Db".to_string()];
+        let mut docs = TypeDocs::new();
+        docs.set_type(&path, "Users", "  Registered users\n");
+        docs.set_member(&path, "Users", "email", "Primary contact address");
+        docs.set_member(&path, "Users", "name", "   ");
+
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs.type_doc(&path, "Users"), Some("Registered users"));
+        assert_eq!(docs.member_doc(&path, "Users", "email"), Some("Primary contact address"));
+        assert_eq!(docs.member_doc(&path, "Users", "name"), None);
+        assert_eq!(docs.type_doc(&[], "Users"), None);
+    }
+}
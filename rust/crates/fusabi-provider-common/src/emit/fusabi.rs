@@ -0,0 +1,509 @@
+//! Fusabi source emitter
+//!
+//! Renders generated types back to Fusabi syntax, so that a provider's
+//! output can be checked in or shipped as part of a pack. Records become
+//! `type X = { ... }` blocks, discriminated unions become `| Case of T`
+//! lists, and generated modules become `module X =` blocks, either nested
+//! one level per path segment or flattened to dotted paths. Descriptions
+//! from a [`TypeDocs`] table become `///` comments, and records with
+//! defaults from a [`FieldDefaults`] table get a `make` constructor that
+//! takes only the fields without one.
+
+use super::{CodeWriter, ModuleTree};
+use crate::defaults::{DefaultValue, FieldDefaults};
+use crate::docs::TypeDocs;
+use crate::generics::base_name;
+use crate::identifiers::{escape_keyword, is_identifier, KeywordEscape};
+use crate::shape::TypeShape;
+use fusabi_type_providers::{DuDef, GeneratedTypes, RecordDef, TypeDefinition, TypeExpr};
+
+/// Options for the Fusabi emitter
+#[derive(Debug, Clone)]
+pub struct FusabiOptions {
+    /// Spaces per indentation level
+    pub indent: usize,
+    /// How generated module paths are laid out
+    pub modules: ModuleLayout,
+}
+
+impl Default for FusabiOptions {
+    fn default() -> Self {
+        Self {
+            indent: 4,
+            modules: ModuleLayout::Nested,
+        }
+    }
+}
+
+/// Layout of generated modules in Fusabi source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleLayout {
+    /// One `module X =` block per path segment, indented inside its parent
+    Nested,
+    /// One top-level `module A.B =` block per module that declares types
+    Qualified,
+}
+
+/// Render generated types as Fusabi source
+pub fn emit_fusabi(types: &GeneratedTypes, options: &FusabiOptions) -> String {
+    emit_fusabi_documented(types, &TypeDocs::new(), options)
+}
+
+/// Render generated types as Fusabi source, with `///` doc comments
+pub fn emit_fusabi_documented(
+    types: &GeneratedTypes,
+    docs: &TypeDocs,
+    options: &FusabiOptions,
+) -> String {
+    emit_fusabi_with_defaults(types, docs, &FieldDefaults::new(), options)
+}
+
+/// Render generated types as Fusabi source, with `///` doc comments and a
+/// constructor for every record that has field defaults
+pub fn emit_fusabi_with_defaults(
+    types: &GeneratedTypes,
+    docs: &TypeDocs,
+    defaults: &FieldDefaults,
+    options: &FusabiOptions,
+) -> String {
+    let emitter = FusabiEmitter {
+        docs,
+        defaults,
+        layout: options.modules,
+    };
+    let mut writer = CodeWriter::new(options.indent);
+
+    writer.line("// Generated from Fusabi type provider output. Do not edit by hand.");
+    writer.blank();
+    emitter.emit_module(&ModuleTree::build(types), &mut writer);
+
+    writer.finish()
+}
+
+struct FusabiEmitter<'a> {
+    docs: &'a TypeDocs,
+    defaults: &'a FieldDefaults,
+    layout: ModuleLayout,
+}
+
+impl FusabiEmitter<'_> {
+    fn emit_module(&self, module: &ModuleTree, writer: &mut CodeWriter) {
+        self.emit_types(module, writer);
+
+        for child in &module.children {
+            match self.layout {
+                ModuleLayout::Nested => {
+                    writer.line(&format!("module {} =", child.name()));
+                    writer.blank();
+                    writer.indent();
+                    self.emit_module(child, writer);
+                    writer.dedent();
+                    writer.blank();
+                }
+                ModuleLayout::Qualified => self.emit_qualified_module(child, writer),
+            }
+        }
+    }
+
+    /// Emit a module and its descendants as top-level `module A.B =` blocks,
+    /// skipping intermediate modules that declare no types of their own
+    fn emit_qualified_module(&self, module: &ModuleTree, writer: &mut CodeWriter) {
+        if !module.types.is_empty() {
+            writer.line(&format!("module {} =", module.path.join(".")));
+            writer.blank();
+            writer.indent();
+            self.emit_types(module, writer);
+            writer.dedent();
+            writer.blank();
+        }
+
+        for child in &module.children {
+            self.emit_qualified_module(child, writer);
+        }
+    }
+
+    fn emit_types(&self, module: &ModuleTree, writer: &mut CodeWriter) {
+        for type_def in &module.types {
+            match type_def {
+                TypeDefinition::Record(record) => self.emit_record(record, &module.path, writer),
+                TypeDefinition::Du(du) => self.emit_du(du, &module.path, writer),
+            }
+            writer.blank();
+        }
+    }
+
+    fn emit_record(&self, record: &RecordDef, path: &[String], writer: &mut CodeWriter) {
+        emit_doc(self.docs.type_doc(path, &record.name), writer);
+        if record.fields.is_empty() {
+            writer.line(&format!("type {} = {{}}", record.name));
+            return;
+        }
+
+        writer.line(&format!("type {} = {{", record.name));
+        writer.indent();
+        for (field_name, type_expr) in &record.fields {
+            emit_doc(self.docs.member_doc(path, &record.name, field_name), writer);
+            writer.line(&format!("{}: {}", field_identifier(field_name), render_type(type_expr)));
+        }
+        writer.dedent();
+        writer.line("}");
+        self.emit_constructor(record, path, writer);
+    }
+
+    /// Emit `makeX`, taking the fields without a default, or `defaultX` when
+    /// every field has one; nothing when no default can be rendered
+    fn emit_constructor(&self, record: &RecordDef, path: &[String], writer: &mut CodeWriter) {
+        let fields: Vec<(String, &TypeExpr, Option<String>)> = record
+            .fields
+            .iter()
+            .map(|(name, type_expr)| {
+                let default = self
+                    .defaults
+                    .get(path, &record.name, name)
+                    .and_then(|value| render_default(value, type_expr));
+                (field_identifier(name), type_expr, default)
+            })
+            .collect();
+        if fields.iter().all(|(_, _, default)| default.is_none()) {
+            return;
+        }
+
+        let params: Vec<String> = fields
+            .iter()
+            .filter(|(_, _, default)| default.is_none())
+            .map(|(name, type_expr, _)| format!("({}: {})", name, render_type(type_expr)))
+            .collect();
+        let base = base_name(&record.name);
+        writer.blank();
+        if params.is_empty() {
+            writer.line(&format!("let default{} : {} =", base, record.name));
+        } else {
+            writer.line(&format!("let make{} {} : {} =", base, params.join(" "), record.name));
+        }
+        writer.indent();
+        writer.line("{");
+        writer.indent();
+        for (name, _, default) in &fields {
+            writer.line(&format!("{} = {}", name, default.as_deref().unwrap_or(name)));
+        }
+        writer.dedent();
+        writer.line("}");
+        writer.dedent();
+    }
+
+    fn emit_du(&self, du: &DuDef, path: &[String], writer: &mut CodeWriter) {
+        emit_doc(self.docs.type_doc(path, &du.name), writer);
+        writer.line(&format!("type {} =", du.name));
+        writer.indent();
+        for variant in &du.variants {
+            emit_doc(self.docs.member_doc(path, &du.name, &variant.name), writer);
+            if variant.fields.is_empty() {
+                writer.line(&format!("| {}", variant.name));
+            } else {
+                let payload: Vec<String> = variant
+                    .fields
+                    .iter()
+                    .map(|field| match TypeShape::from_type_expr(field) {
+                        // Tuples and unions need parentheses inside a `*` payload
+                        shape @ (TypeShape::Tuple(_) | TypeShape::Union(_))
+                            if variant.fields.len() > 1 =>
+                        {
+                            format!("({})", shape)
+                        }
+                        shape => shape.to_string(),
+                    })
+                    .collect();
+                writer.line(&format!("| {} of {}", variant.name, payload.join(" * ")));
+            }
+        }
+        writer.dedent();
+    }
+}
+
+/// Write a description as `///` lines
+fn emit_doc(doc: Option<&str>, writer: &mut CodeWriter) {
+    for line in doc.into_iter().flat_map(str::lines) {
+        let line = line.trim_end();
+        if line.is_empty() {
+            writer.line("///");
+        } else {
+            writer.line(&format!("/// {}", line));
+        }
+    }
+}
+
+/// Default value as an expression of the field's type
+fn render_default(value: &DefaultValue, type_expr: &TypeExpr) -> Option<String> {
+    let optional = TypeShape::from_type_expr(type_expr).is_option();
+    match value {
+        DefaultValue::None if optional => Some("None".to_string()),
+        DefaultValue::None => None,
+        value if optional => {
+            let rendered = value.to_fusabi()?;
+            if rendered.starts_with('-') {
+                Some(format!("Some ({})", rendered))
+            } else {
+                Some(format!("Some {}", rendered))
+            }
+        }
+        value => value.to_fusabi(),
+    }
+}
+
+/// Canonical rendering of a type expression
+fn render_type(type_expr: &TypeExpr) -> String {
+    TypeShape::from_type_expr(type_expr).to_string()
+}
+
+/// Field name, quoted with double backticks when it is a keyword or not an
+/// identifier
+fn field_identifier(name: &str) -> String {
+    if is_identifier(name) {
+        escape_keyword(name, KeywordEscape::Backtick)
+    } else {
+        format!("``{}``", name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_type_providers::{GeneratedModule, VariantDef};
+
+    fn named(s: &str) -> TypeExpr {
+        TypeExpr::Named(s.to_string())
+    }
+
+    #[test]
+    fn test_emit_records_and_unions() {
+        let mut types = GeneratedTypes::new();
+        types.root_types.push(TypeDefinition::Record(RecordDef {
+            name: "Config".to_string(),
+            fields: vec![
+                ("name".to_string(), named("string")),
+                ("content-type".to_string(), named("string option")),
+                ("labels".to_string(), named("Map<string,string>")),
+            ],
+        }));
+
+        let mut module = GeneratedModule::new(vec!["Api".to_string()]);
+        module.types.push(TypeDefinition::Du(DuDef {
+            name: "Event".to_string(),
+            variants: vec![
+                VariantDef::new_simple("Started".to_string()),
+                VariantDef::new("Failed".to_string(), vec![named("string"), named("int")]),
+            ],
+        }));
+        module.types.push(TypeDefinition::Record(RecordDef {
+            name: "Empty".to_string(),
+            fields: vec![],
+        }));
+        types.modules.push(module);
+
+        let expected = "\
+// Generated from Fusabi type provider output. Do not edit by hand.
+
+type Config = {
+    name: string
+    ``content-type``: string option
+    labels: Map<string, string>
+}
+
+module Api =
+
+    type Event =
+        | Started
+        | Failed of string * int
+
+    type Empty = {}
+";
+        assert_eq!(emit_fusabi(&types, &FusabiOptions::default()), expected);
+    }
+
+    #[test]
+    fn test_nested_modules_and_indent() {
+        let mut types = GeneratedTypes::new();
+        let mut module = GeneratedModule::new(vec!["A".to_string(), "B".to_string()]);
+        module.types.push(TypeDefinition::Record(RecordDef {
+            name: "Leaf".to_string(),
+            fields: vec![("id".to_string(), named("int"))],
+        }));
+        types.modules.push(module);
+
+        let options = FusabiOptions {
+            indent: 2,
+            ..FusabiOptions::default()
+        };
+        let output = emit_fusabi(&types, &options);
+        assert!(output.contains("module A =\n\n  module B =\n\n    type Leaf = {\n      id: int\n    }\n"));
+    }
+
+    #[test]
+    fn test_qualified_modules() {
+        let mut types = GeneratedTypes::new();
+        let mut module = GeneratedModule::new(vec!["A".to_string(), "B".to_string()]);
+        module.types.push(TypeDefinition::Record(RecordDef {
+            name: "Leaf".to_string(),
+            fields: vec![("id".to_string(), named("int"))],
+        }));
+        types.modules.push(module);
+        let mut module = GeneratedModule::new(vec!["A".to_string()]);
+        module.types.push(TypeDefinition::Du(DuDef {
+            name: "Kind".to_string(),
+            variants: vec![VariantDef::new_simple("Leaf".to_string())],
+        }));
+        types.modules.push(module);
+
+        let options = FusabiOptions {
+            modules: ModuleLayout::Qualified,
+            ..FusabiOptions::default()
+        };
+        let expected = "\
+// Generated from Fusabi type provider output. Do not edit by hand.
+
+module A =
+
+    type Kind =
+        | Leaf
+
+module A.B =
+
+    type Leaf = {
+        id: int
+    }
+";
+        assert_eq!(emit_fusabi(&types, &options), expected);
+    }
+
+    #[test]
+    fn test_doc_comments() {
+        let mut types = GeneratedTypes::new();
+        let mut module = GeneratedModule::new(vec!["Db".to_string()]);
+        module.types.push(TypeDefinition::Record(RecordDef {
+            name: "Users".to_string(),
+            fields: vec![
+                ("id".to_string(), named("int")),
+                ("email".to_string(), named("string option")),
+            ],
+        }));
+        module.types.push(TypeDefinition::Du(DuDef {
+            name: "Role".to_string(),
+            variants: vec![
+                VariantDef::new_simple("Admin".to_string()),
+                VariantDef::new_simple("Member".to_string()),
+            ],
+        }));
+        types.modules.push(module);
+
+        let path = vec!["Db".to_string()];
+        let mut docs = TypeDocs::new();
+        docs.set_type(&path, "Users", "Registered users\n\nOne row per account.");
+        docs.set_member(&path, "Users", "email", "Primary contact address");
+        docs.set_member(&path, "Role", "Admin", "Full access");
+
+        let expected = "\
+// Generated from Fusabi type provider output. Do not edit by hand.
+
+module Db =
+
+    /// Registered users
+    ///
+    /// One row per account.
+    type Users = {
+        id: int
+        /// Primary contact address
+        email: string option
+    }
+
+    type Role =
+        /// Full access
+        | Admin
+        | Member
+";
+        assert_eq!(emit_fusabi_documented(&types, &docs, &FusabiOptions::default()), expected);
+    }
+
+    #[test]
+    fn test_generic_definitions() {
+        let mut types = GeneratedTypes::new();
+        types.root_types.push(TypeDefinition::Record(RecordDef {
+            name: "Connection<'node>".to_string(),
+            fields: vec![
+                ("edges".to_string(), named("'node list")),
+                ("hasNextPage".to_string(), named("bool")),
+            ],
+        }));
+        types.root_types.push(TypeDefinition::Record(RecordDef {
+            name: "Query".to_string(),
+            fields: vec![("users".to_string(), named("Connection<User>"))],
+        }));
+        let mut defaults = FieldDefaults::new();
+        defaults.set(&[], "Connection<'node>", "hasNextPage", DefaultValue::Bool(false));
+
+        let output = emit_fusabi_with_defaults(&types, &TypeDocs::new(), &defaults, &FusabiOptions::default());
+
+        assert!(output.contains("type Connection<'node> = {\n    edges: 'node list\n"));
+        assert!(output.contains("let makeConnection (edges: 'node list) : Connection<'node> ="));
+        assert!(output.contains("    users: Connection<User>\n"));
+    }
+
+    #[test]
+    fn test_default_constructors() {
+        let mut types = GeneratedTypes::new();
+        let mut module = GeneratedModule::new(vec!["Db".to_string()]);
+        module.types.push(TypeDefinition::Record(RecordDef {
+            name: "Users".to_string(),
+            fields: vec![
+                ("id".to_string(), named("int")),
+                ("active".to_string(), named("bool")),
+                ("score".to_string(), named("int option")),
+                ("created_at".to_string(), named("string")),
+            ],
+        }));
+        module.types.push(TypeDefinition::Record(RecordDef {
+            name: "Settings".to_string(),
+            fields: vec![("theme".to_string(), named("string"))],
+        }));
+        types.modules.push(module);
+
+        let path = vec!["Db".to_string()];
+        let mut defaults = FieldDefaults::new();
+        defaults.set(&path, "Users", "active", DefaultValue::Bool(true));
+        defaults.set(&path, "Users", "score", DefaultValue::Int(-1));
+        defaults.set(&path, "Users", "created_at", DefaultValue::Expr("now()".to_string()));
+        defaults.set(&path, "Settings", "theme", DefaultValue::String("light".to_string()));
+
+        let expected = "\
+// Generated from Fusabi type provider output. Do not edit by hand.
+
+module Db =
+
+    type Users = {
+        id: int
+        active: bool
+        score: int option
+        created_at: string
+    }
+
+    let makeUsers (id: int) (created_at: string) : Users =
+        {
+            id = id
+            active = true
+            score = Some (-1)
+            created_at = created_at
+        }
+
+    type Settings = {
+        theme: string
+    }
+
+    let defaultSettings : Settings =
+        {
+            theme = \"light\"
+        }
+";
+        let output =
+            emit_fusabi_with_defaults(&types, &TypeDocs::new(), &defaults, &FusabiOptions::default());
+        assert_eq!(output, expected);
+    }
+}
@@ -0,0 +1,104 @@
+//! Golden-file checks for emitted sources
+//!
+//! Providers pin their generated types by comparing the emitted Fusabi
+//! source against a checked-in file, so that any change to the generated
+//! shapes shows up as a reviewable diff. Set `FUSABI_BLESS=1` to write the
+//! current output instead of comparing, then review and commit the result.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_common::emit::golden;
+//!
+//! let types = provider.generate_types(&schema, "Events")?;
+//! golden::assert_fusabi(
+//!     &types,
+//!     concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/events.fsx"),
+//! );
+//! ```
+
+use super::{emit_fusabi, emit_fusabi_documented, FusabiOptions};
+use crate::docs::TypeDocs;
+use fusabi_type_providers::GeneratedTypes;
+use std::path::Path;
+
+/// Environment variable that switches golden checks to rewriting the files
+pub const BLESS_ENV: &str = "FUSABI_BLESS";
+
+/// Compare `actual` against the golden file at `path`
+///
+/// Panics with both texts when they differ, or when the file is missing.
+/// Line endings are normalized so that checkouts with CRLF still compare.
+pub fn assert_golden(path: impl AsRef<Path>, actual: &str) {
+    let bless = std::env::var_os(BLESS_ENV).is_some_and(|value| value != "0");
+    check_golden(path.as_ref(), actual, bless);
+}
+
+/// Emit `types` as Fusabi with default options and compare against `path`
+pub fn assert_fusabi(types: &GeneratedTypes, path: impl AsRef<Path>) {
+    assert_golden(path, &emit_fusabi(types, &FusabiOptions::default()));
+}
+
+/// Like [`assert_fusabi`], with descriptions rendered as doc comments
+pub fn assert_fusabi_documented(types: &GeneratedTypes, docs: &TypeDocs, path: impl AsRef<Path>) {
+    assert_golden(
+        path,
+        &emit_fusabi_documented(types, docs, &FusabiOptions::default()),
+    );
+}
+
+fn check_golden(path: &Path, actual: &str, bless: bool) {
+    if bless {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .unwrap_or_else(|e| panic!("cannot create {}: {}", parent.display(), e));
+        }
+        std::fs::write(path, actual)
+            .unwrap_or_else(|e| panic!("cannot write {}: {}", path.display(), e));
+        return;
+    }
+
+    let expected = std::fs::read_to_string(path).unwrap_or_else(|e| {
+        panic!(
+            "cannot read golden file {}: {}; rerun with {}=1 to create it",
+            path.display(),
+            e,
+            BLESS_ENV
+        )
+    });
+
+    if expected.replace("\r\n", "\n") != actual {
+        panic!(
+            "emitted source does not match {}; rerun with {}=1 to update it\n\
+             --- expected ---\n{}\n--- actual ---\n{}",
+            path.display(),
+            BLESS_ENV,
+            expected,
+            actual
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_golden() {
+        let dir = std::env::temp_dir().join(format!("fusabi-golden-{}", std::process::id()));
+        let path = dir.join("types.fsx");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(&path, "type A = {}\r\n").unwrap();
+
+        check_golden(&path, "type A = {}\n", false);
+        let mismatch = std::panic::catch_unwind(|| check_golden(&path, "type B = {}\n", false));
+        assert!(mismatch.is_err());
+        let missing = std::panic::catch_unwind(|| check_golden(&dir.join("missing.fsx"), "", false));
+        assert!(missing.is_err());
+
+        check_golden(&dir.join("new/types.fsx"), "type B = {}\n", true);
+        check_golden(&dir.join("new/types.fsx"), "type B = {}\n", false);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
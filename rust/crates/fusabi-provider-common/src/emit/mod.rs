@@ -0,0 +1,291 @@
+//! Source emitters for generated types
+//!
+//! Emitters render [`GeneratedTypes`] as source code: Fusabi itself, for
+//! checked-in or packaged types, and host languages, so that plugins and UIs
+//! written outside Fusabi share exactly the same shapes as the Fusabi
+//! scripts that use them.
+//!
+//! Each target language is a [`Backend`]. Built-in backends are available
+//! by name through [`backend`]; further backends only need to implement the
+//! trait.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_common::emit;
+//!
+//! let backend = emit::backend("typescript").unwrap();
+//! let source = backend.emit(&types);
+//! std::fs::write(format!("types.{}", backend.file_extension()), source)?;
+//! ```
+
+pub mod fusabi;
+pub mod golden;
+pub mod rust;
+pub mod typescript;
+
+pub use fusabi::{
+    emit_fusabi, emit_fusabi_documented, emit_fusabi_with_defaults, FusabiOptions, ModuleLayout,
+};
+pub use rust::{emit_rust, emit_rust_decoders, RustOptions};
+pub use typescript::{emit_typescript, TypeScriptOptions};
+
+use fusabi_type_providers::{GeneratedTypes, TypeDefinition};
+
+/// Target language backend
+pub trait Backend {
+    /// Backend name used for lookup
+    fn name(&self) -> &str;
+
+    /// File extension for emitted sources, without the leading dot
+    fn file_extension(&self) -> &str;
+
+    /// Render generated types as source text
+    fn emit(&self, types: &GeneratedTypes) -> String;
+}
+
+/// Fusabi source backend
+#[derive(Debug, Clone, Default)]
+pub struct FusabiBackend {
+    pub options: FusabiOptions,
+}
+
+impl Backend for FusabiBackend {
+    fn name(&self) -> &str {
+        "fusabi"
+    }
+
+    fn file_extension(&self) -> &str {
+        "fsx"
+    }
+
+    fn emit(&self, types: &GeneratedTypes) -> String {
+        emit_fusabi(types, &self.options)
+    }
+}
+
+/// Rust backend
+#[derive(Debug, Clone, Default)]
+pub struct RustBackend {
+    pub options: RustOptions,
+}
+
+impl Backend for RustBackend {
+    fn name(&self) -> &str {
+        "rust"
+    }
+
+    fn file_extension(&self) -> &str {
+        "rs"
+    }
+
+    fn emit(&self, types: &GeneratedTypes) -> String {
+        emit_rust(types, &self.options)
+    }
+}
+
+/// TypeScript declaration backend
+#[derive(Debug, Clone, Default)]
+pub struct TypeScriptBackend {
+    pub options: TypeScriptOptions,
+}
+
+impl Backend for TypeScriptBackend {
+    fn name(&self) -> &str {
+        "typescript"
+    }
+
+    fn file_extension(&self) -> &str {
+        "d.ts"
+    }
+
+    fn emit(&self, types: &GeneratedTypes) -> String {
+        emit_typescript(types, &self.options)
+    }
+}
+
+/// Names of the built-in backends
+pub fn backend_names() -> &'static [&'static str] {
+    &["fusabi", "rust", "typescript"]
+}
+
+/// Look up a built-in backend by name, with default options
+pub fn backend(name: &str) -> Option<Box<dyn Backend>> {
+    match name {
+        "fusabi" | "fsx" => Some(Box::new(FusabiBackend::default())),
+        "rust" | "rs" => Some(Box::new(RustBackend::default())),
+        "typescript" | "ts" => Some(Box::new(TypeScriptBackend::default())),
+        _ => None,
+    }
+}
+
+/// Generated types arranged as a tree of nested modules
+pub(crate) struct ModuleTree<'a> {
+    /// Module path from the root
+    pub path: Vec<String>,
+    /// Types declared directly in this module
+    pub types: Vec<&'a TypeDefinition>,
+    /// Child modules in first-seen order
+    pub children: Vec<ModuleTree<'a>>,
+}
+
+impl<'a> ModuleTree<'a> {
+    /// Build the module tree for a set of generated types
+    pub fn build(types: &'a GeneratedTypes) -> Self {
+        let mut root = ModuleTree::new(Vec::new());
+        root.types.extend(types.root_types.iter());
+
+        for module in &types.modules {
+            let mut node = &mut root;
+            for segment in &module.path {
+                let index = match node.children.iter().position(|c| c.name() == segment) {
+                    Some(index) => index,
+                    None => {
+                        let mut path = node.path.clone();
+                        path.push(segment.clone());
+                        node.children.push(ModuleTree::new(path));
+                        node.children.len() - 1
+                    }
+                };
+                node = &mut node.children[index];
+            }
+            node.types.extend(module.types.iter());
+        }
+
+        root
+    }
+
+    fn new(path: Vec<String>) -> Self {
+        Self {
+            path,
+            types: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Last path segment (empty for the root)
+    pub fn name(&self) -> &str {
+        self.path.last().map(String::as_str).unwrap_or("")
+    }
+}
+
+/// Convert an identifier to snake_case
+pub(crate) fn to_snake_case(name: &str) -> String {
+    let mut result = String::new();
+    let mut prev_lower_or_digit = false;
+
+    for ch in name.chars() {
+        if ch == '-' || ch == '.' || ch == ' ' || ch == '_' {
+            if !result.is_empty() && !result.ends_with('_') {
+                result.push('_');
+            }
+            prev_lower_or_digit = false;
+        } else if ch.is_uppercase() {
+            if prev_lower_or_digit && !result.ends_with('_') {
+                result.push('_');
+            }
+            result.extend(ch.to_lowercase());
+            prev_lower_or_digit = false;
+        } else {
+            result.push(ch);
+            prev_lower_or_digit = ch.is_lowercase() || ch.is_ascii_digit();
+        }
+    }
+
+    result.trim_end_matches('_').to_string()
+}
+
+/// Replace characters that are not valid in identifiers
+pub(crate) fn sanitize_identifier(name: &str) -> String {
+    let mut result: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '_' { c } else { '_' })
+        .collect();
+
+    if result.is_empty() || result.starts_with(|c: char| c.is_ascii_digit()) {
+        result.insert(0, '_');
+    }
+
+    result
+}
+
+/// Line-oriented writer with indentation tracking
+pub(crate) struct CodeWriter {
+    output: String,
+    indent: usize,
+    level: usize,
+}
+
+impl CodeWriter {
+    pub fn new(indent: usize) -> Self {
+        Self {
+            output: String::new(),
+            indent,
+            level: 0,
+        }
+    }
+
+    /// Write a line at the current indentation
+    pub fn line(&mut self, text: &str) {
+        if !text.is_empty() {
+            self.output.push_str(&" ".repeat(self.indent * self.level));
+            self.output.push_str(text);
+        }
+        self.output.push('\n');
+    }
+
+    /// Write an empty line, collapsing consecutive blank lines
+    pub fn blank(&mut self) {
+        if !self.output.is_empty() && !self.output.ends_with("\n\n") && !self.output.ends_with("{\n") {
+            self.output.push('\n');
+        }
+    }
+
+    pub fn indent(&mut self) {
+        self.level += 1;
+    }
+
+    /// Spaces per indentation level
+    pub fn indent_width(&self) -> usize {
+        self.indent
+    }
+
+    pub fn dedent(&mut self) {
+        self.level = self.level.saturating_sub(1);
+    }
+
+    pub fn finish(mut self) -> String {
+        while self.output.ends_with("\n\n") {
+            self.output.pop();
+        }
+        self.output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_snake_case() {
+        assert_eq!(to_snake_case("requestMethod"), "request_method");
+        assert_eq!(to_snake_case("Http"), "http");
+        assert_eq!(to_snake_case("user_id"), "user_id");
+        assert_eq!(to_snake_case("max-length"), "max_length");
+    }
+
+    #[test]
+    fn test_backend_lookup() {
+        for name in backend_names() {
+            assert_eq!(backend(name).unwrap().name(), *name);
+        }
+        assert_eq!(backend("ts").unwrap().file_extension(), "d.ts");
+        assert!(backend("cobol").is_none());
+    }
+
+    #[test]
+    fn test_sanitize_identifier() {
+        assert_eq!(sanitize_identifier("get-weather"), "get_weather");
+        assert_eq!(sanitize_identifier("2fa"), "_2fa");
+    }
+}
@@ -0,0 +1,388 @@
+//! Rust emitter
+//!
+//! Renders generated types as `serde`-deriving Rust structs and enums.
+//! Records become structs, discriminated unions become externally tagged
+//! enums, and generated modules become nested `pub mod` blocks. Field and
+//! variant names that are not valid Rust identifiers are sanitized and keep
+//! their original name through `#[serde(rename)]`. Generic definitions
+//! become generic types, with type parameters written in PascalCase
+//! (`'node` as `Node`).
+//!
+//! [`emit_rust_decoders`] also renders a [`DecoderPlan`] as decode
+//! functions on each type, with their runtime support in a
+//! `decode_runtime` module at the root of the file.
+
+mod decode;
+
+use super::{sanitize_identifier, to_snake_case, CodeWriter, ModuleTree};
+use crate::decode::{DecoderPlan, FieldCodec, TypeDecoder};
+use crate::generics::{host_name, host_param, GenericName};
+use crate::index::TypeIndex;
+use crate::shape::TypeShape;
+use fusabi_type_providers::{DuDef, GeneratedTypes, RecordDef, TypeDefinition};
+
+/// Rust keywords that must be written as raw identifiers
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+    "pub", "ref", "return", "static", "struct", "trait", "true", "type", "unsafe", "use",
+    "where", "while", "abstract", "become", "box", "do", "final", "macro", "override", "priv",
+    "try", "typeof", "unsized", "virtual", "yield",
+];
+
+/// Options for the Rust emitter
+#[derive(Debug, Clone)]
+pub struct RustOptions {
+    /// Traits derived on every generated type
+    pub derives: Vec<String>,
+    /// Spaces per indentation level
+    pub indent: usize,
+}
+
+impl Default for RustOptions {
+    fn default() -> Self {
+        Self {
+            derives: ["Debug", "Clone", "PartialEq", "Serialize", "Deserialize"]
+                .iter()
+                .map(|d| d.to_string())
+                .collect(),
+            indent: 4,
+        }
+    }
+}
+
+/// Render generated types as Rust source
+pub fn emit_rust(types: &GeneratedTypes, options: &RustOptions) -> String {
+    emit(types, None, options)
+}
+
+/// Render generated types as Rust source, with decoders from `plan`
+///
+/// Generic definitions get no decoder. JSON decoders go through `serde`,
+/// so `options.derives` must keep `Deserialize`.
+pub fn emit_rust_decoders(types: &GeneratedTypes, plan: &DecoderPlan, options: &RustOptions) -> String {
+    emit(types, Some(plan), options)
+}
+
+fn emit(types: &GeneratedTypes, decoders: Option<&DecoderPlan>, options: &RustOptions) -> String {
+    let emitter = RustEmitter {
+        index: TypeIndex::new(types),
+        options,
+        decoders,
+    };
+    let mut writer = CodeWriter::new(options.indent);
+
+    writer.line("// Generated from Fusabi type provider output. Do not edit by hand.");
+    writer.blank();
+    emitter.emit_module(&ModuleTree::build(types), &mut writer);
+    if let Some(plan) = decoders {
+        decode::emit_runtime(plan.format(), &mut writer);
+    }
+
+    writer.finish()
+}
+
+struct RustEmitter<'a> {
+    index: TypeIndex<'a>,
+    options: &'a RustOptions,
+    decoders: Option<&'a DecoderPlan>,
+}
+
+impl RustEmitter<'_> {
+    fn emit_module(&self, module: &ModuleTree, writer: &mut CodeWriter) {
+        if !module.types.is_empty() {
+            writer.line("use serde::{Deserialize, Serialize};");
+            writer.blank();
+        }
+
+        for type_def in &module.types {
+            match type_def {
+                TypeDefinition::Record(record) => self.emit_record(record, &module.path, writer),
+                TypeDefinition::Du(du) => self.emit_du(du, &module.path, writer),
+            }
+            self.emit_decoder(type_def, &module.path, writer);
+            writer.blank();
+        }
+
+        for child in &module.children {
+            writer.line(&format!("pub mod {} {{", module_ident(child.name())));
+            writer.indent();
+            self.emit_module(child, writer);
+            writer.dedent();
+            writer.line("}");
+            writer.blank();
+        }
+    }
+
+    fn emit_derives(&self, writer: &mut CodeWriter) {
+        if !self.options.derives.is_empty() {
+            writer.line(&format!("#[derive({})]", self.options.derives.join(", ")));
+        }
+    }
+
+    fn emit_record(&self, record: &RecordDef, module: &[String], writer: &mut CodeWriter) {
+        self.emit_derives(writer);
+        writer.line(&format!("pub struct {} {{", definition_ident(&record.name)));
+        writer.indent();
+
+        for (field_name, type_expr) in &record.fields {
+            let shape = TypeShape::from_type_expr(type_expr);
+            let ident = field_ident(field_name);
+
+            let key = self.json_key(record, module, field_name);
+            let mut serde_args = Vec::new();
+            if ident.trim_start_matches("r#") != key {
+                serde_args.push(format!("rename = \"{}\"", key));
+            }
+            if shape.is_option() {
+                serde_args.push("default".to_string());
+                serde_args.push("skip_serializing_if = \"Option::is_none\"".to_string());
+            }
+            if !serde_args.is_empty() {
+                writer.line(&format!("#[serde({})]", serde_args.join(", ")));
+            }
+
+            writer.line(&format!("pub {}: {},", ident, self.rust_type(&shape, module)));
+        }
+
+        writer.dedent();
+        writer.line("}");
+    }
+
+    /// JSON member a field is read from, when it differs from the field name
+    fn json_key<'f>(&'f self, record: &RecordDef, module: &[String], field_name: &'f str) -> &'f str {
+        let fields = match self.decoders.and_then(|plan| plan.decoder(module, &record.name)) {
+            Some(TypeDecoder::Record(fields)) => fields,
+            _ => return field_name,
+        };
+        fields
+            .iter()
+            .find_map(|decoder| match &decoder.codec {
+                FieldCodec::Json { key } if decoder.field == field_name => Some(key.as_str()),
+                _ => None,
+            })
+            .unwrap_or(field_name)
+    }
+
+    fn emit_du(&self, du: &DuDef, module: &[String], writer: &mut CodeWriter) {
+        self.emit_derives(writer);
+        writer.line(&format!("pub enum {} {{", definition_ident(&du.name)));
+        writer.indent();
+
+        for variant in &du.variants {
+            let ident = type_ident(&variant.name);
+            if ident != variant.name {
+                writer.line(&format!("#[serde(rename = \"{}\")]", variant.name));
+            }
+
+            if variant.fields.is_empty() {
+                writer.line(&format!("{},", ident));
+            } else {
+                let fields: Vec<String> = variant
+                    .fields
+                    .iter()
+                    .map(|f| self.rust_type(&TypeShape::from_type_expr(f), module))
+                    .collect();
+                writer.line(&format!("{}({}),", ident, fields.join(", ")));
+            }
+        }
+
+        writer.dedent();
+        writer.line("}");
+    }
+
+    fn rust_type(&self, shape: &TypeShape, module: &[String]) -> String {
+        match shape {
+            TypeShape::Named(name) => self.named_type(name, module),
+            TypeShape::Option(inner) => format!("Option<{}>", self.rust_type(inner, module)),
+            TypeShape::List(inner) => format!("Vec<{}>", self.rust_type(inner, module)),
+            TypeShape::Map(key, value) => format!(
+                "std::collections::HashMap<{}, {}>",
+                self.rust_type(key, module),
+                self.rust_type(value, module)
+            ),
+            TypeShape::Tuple(items) => {
+                let items: Vec<String> = items.iter().map(|i| self.rust_type(i, module)).collect();
+                format!("({})", items.join(", "))
+            }
+            TypeShape::Literal(_) => "String".to_string(),
+            TypeShape::Union(items) => {
+                if items.iter().all(|i| matches!(i, TypeShape::Literal(_))) {
+                    "String".to_string()
+                } else {
+                    "serde_json::Value".to_string()
+                }
+            }
+            TypeShape::Param(name) => host_param(name),
+            TypeShape::Generic(name, args) => {
+                let args: Vec<String> = args.iter().map(|a| self.rust_type(a, module)).collect();
+                format!("{}<{}>", self.named_type(name, module), args.join(", "))
+            }
+        }
+    }
+
+    fn named_type(&self, name: &str, module: &[String]) -> String {
+        match name {
+            "string" => "String".to_string(),
+            "int" | "int64" => "i64".to_string(),
+            "uint" | "uint64" => "u64".to_string(),
+            "float" => "f64".to_string(),
+            "bool" => "bool".to_string(),
+            "bytes" => "Vec<u8>".to_string(),
+            "unit" => "()".to_string(),
+            "any" => "serde_json::Value".to_string(),
+            _ => match self.index.resolve(name, module) {
+                Some(entry) => {
+                    let type_name = type_ident(crate::index::definition_name(entry.definition));
+                    relative_path(module, entry.module, &type_name)
+                }
+                // Unknown references fall back to a dynamic value
+                None => "serde_json::Value".to_string(),
+            },
+        }
+    }
+}
+
+/// Path to `type_name` in module `to`, as seen from module `from`
+fn relative_path(from: &[String], to: &[String], type_name: &str) -> String {
+    if from == to {
+        return type_name.to_string();
+    }
+
+    let mut segments: Vec<String> = vec!["super".to_string(); from.len()];
+    segments.extend(to.iter().map(|s| module_ident(s)));
+    segments.push(type_name.to_string());
+    segments.join("::")
+}
+
+fn type_ident(name: &str) -> String {
+    sanitize_identifier(name)
+}
+
+/// Struct or enum name, with type parameters for generic definitions
+fn definition_ident(name: &str) -> String {
+    let name = GenericName::parse(name);
+    host_name(&name, &type_ident(&name.name))
+}
+
+fn module_ident(name: &str) -> String {
+    escape_keyword(sanitize_identifier(&to_snake_case(name)))
+}
+
+fn field_ident(name: &str) -> String {
+    escape_keyword(sanitize_identifier(&to_snake_case(name)))
+}
+
+fn escape_keyword(ident: String) -> String {
+    if RUST_KEYWORDS.contains(&ident.as_str()) {
+        format!("r#{}", ident)
+    } else {
+        ident
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_type_providers::{GeneratedModule, TypeExpr, VariantDef};
+
+    fn named(s: &str) -> TypeExpr {
+        TypeExpr::Named(s.to_string())
+    }
+
+    #[test]
+    fn test_emit_record() {
+        let mut types = GeneratedTypes::new();
+        types.root_types.push(TypeDefinition::Record(RecordDef {
+            name: "Client".to_string(),
+            fields: vec![
+                ("requestMethod".to_string(), named("string")),
+                ("serverPort".to_string(), named("int option")),
+                ("type".to_string(), named("Map<string, string list>")),
+            ],
+        }));
+
+        let output = emit_rust(&types, &RustOptions::default());
+
+        assert!(output.contains("pub struct Client {"));
+        assert!(output.contains("#[serde(rename = \"requestMethod\")]\n    pub request_method: String,"));
+        assert!(output.contains("pub server_port: Option<i64>,"));
+        assert!(output.contains("skip_serializing_if = \"Option::is_none\""));
+        assert!(output.contains("pub r#type: std::collections::HashMap<String, Vec<String>>,"));
+    }
+
+    #[test]
+    fn test_emit_du() {
+        let mut types = GeneratedTypes::new();
+        types.root_types.push(TypeDefinition::Du(DuDef {
+            name: "Shape".to_string(),
+            variants: vec![
+                VariantDef::new_simple("Empty".to_string()),
+                VariantDef::new("Rect".to_string(), vec![named("float"), named("float")]),
+            ],
+        }));
+
+        let output = emit_rust(&types, &RustOptions::default());
+
+        assert!(output.contains("pub enum Shape {\n    Empty,\n    Rect(f64, f64),\n}"));
+    }
+
+    #[test]
+    fn test_emit_nested_modules_and_references() {
+        let mut types = GeneratedTypes::new();
+        let mut core = GeneratedModule::new(vec!["K8s".to_string(), "Core".to_string()]);
+        core.types.push(TypeDefinition::Record(RecordDef {
+            name: "ObjectMeta".to_string(),
+            fields: vec![("name".to_string(), named("string"))],
+        }));
+        let mut apps = GeneratedModule::new(vec!["K8s".to_string(), "Apps".to_string()]);
+        apps.types.push(TypeDefinition::Record(RecordDef {
+            name: "Deployment".to_string(),
+            fields: vec![("metadata".to_string(), named("ObjectMeta"))],
+        }));
+        types.modules.push(core);
+        types.modules.push(apps);
+
+        let output = emit_rust(&types, &RustOptions::default());
+
+        assert!(output.contains("pub mod k8s {\n    pub mod core {"));
+        assert!(output.contains("pub mod apps {"));
+        assert!(output.contains("pub metadata: super::super::k8s::core::ObjectMeta,"));
+    }
+
+    #[test]
+    fn test_emit_generics() {
+        let mut types = GeneratedTypes::new();
+        types.root_types.push(TypeDefinition::Record(RecordDef {
+            name: "Edge<'node>".to_string(),
+            fields: vec![("cursor".to_string(), named("string")), ("node".to_string(), named("'node"))],
+        }));
+        types.root_types.push(TypeDefinition::Record(RecordDef {
+            name: "User".to_string(),
+            fields: vec![("friends".to_string(), named("Edge<User> list"))],
+        }));
+
+        let output = emit_rust(&types, &RustOptions::default());
+
+        assert!(output.contains("pub struct Edge<Node> {"));
+        assert!(output.contains("pub node: Node,"));
+        assert!(output.contains("pub friends: Vec<Edge<User>>,"));
+    }
+
+    #[test]
+    fn test_custom_derives() {
+        let mut types = GeneratedTypes::new();
+        types.root_types.push(TypeDefinition::Record(RecordDef {
+            name: "Empty".to_string(),
+            fields: vec![],
+        }));
+
+        let options = RustOptions {
+            derives: vec!["Debug".to_string()],
+            indent: 2,
+        };
+        let output = emit_rust(&types, &options);
+
+        assert!(output.contains("#[derive(Debug)]\npub struct Empty {\n}"));
+    }
+}
@@ -0,0 +1,714 @@
+//! Rust decoders rendered from a decoder plan
+//!
+//! Each decoded type gets an inherent `impl` block next to its definition:
+//! `from_json`, `decode_protobuf` or `from_row` depending on the plan's
+//! format, and `from_number` for numbered unions. The functions they share
+//! are written once, in a `decode_runtime` module at the root of the file.
+//!
+//! Protobuf decoders follow proto3 semantics: absent scalars take their
+//! default, absent enums their value numbered 0, repeated scalars are read
+//! packed or not, and unknown fields are skipped. An absent embedded
+//! message that is not `optional` is an error.
+
+use super::{definition_ident, field_ident, type_ident, RustEmitter};
+use crate::decode::{FieldCodec, FieldDecoder, ProtoEncoding, TypeDecoder, WireFormat};
+use crate::emit::CodeWriter;
+use crate::generics::GenericName;
+use crate::index::definition_name;
+use crate::shape::TypeShape;
+use fusabi_type_providers::{RecordDef, TypeDefinition};
+
+/// Name of the runtime support module at the root of the file
+const RUNTIME: &str = "decode_runtime";
+
+/// How a record field collects the values read for it
+enum Slot<'s> {
+    /// One value, defaulted when absent
+    Single(&'s TypeShape),
+    /// One value, `None` when absent
+    Optional(&'s TypeShape),
+    /// Every value read
+    Repeated(&'s TypeShape),
+    /// Entries of a protobuf map
+    Map(&'s TypeShape, &'s TypeShape),
+}
+
+impl<'s> Slot<'s> {
+    fn of(shape: &'s TypeShape) -> Self {
+        match shape {
+            TypeShape::Option(inner) => Slot::Optional(inner),
+            TypeShape::List(inner) => Slot::Repeated(inner),
+            TypeShape::Map(key, value) => Slot::Map(key, value),
+            _ => Slot::Single(shape),
+        }
+    }
+}
+
+impl RustEmitter<'_> {
+    /// Render the decoder of a type, if the plan has one
+    pub(super) fn emit_decoder(&self, type_def: &TypeDefinition, module: &[String], writer: &mut CodeWriter) {
+        let Some(plan) = self.decoders else {
+            return;
+        };
+        let name = definition_name(type_def);
+        let Some(decoder) = plan.decoder(module, name) else {
+            return;
+        };
+        let supported = match (plan.format(), decoder, type_def) {
+            (WireFormat::Json, _, _) => true,
+            (_, TypeDecoder::Record(_), TypeDefinition::Record(_)) => true,
+            (_, TypeDecoder::Numbered(_), TypeDefinition::Du(_)) => true,
+            _ => false,
+        };
+        if !supported || GenericName::parse(name).is_generic() {
+            return;
+        }
+
+        let rt = runtime_path(module);
+        writer.blank();
+        writer.line(&format!("impl {} {{", definition_ident(name)));
+        writer.indent();
+        match (plan.format(), decoder, type_def) {
+            (WireFormat::Json, _, _) => {
+                writer.line("/// Decode a JSON document");
+                writer.line(&format!("pub fn from_json(json: &str) -> Result<Self, {}::DecodeError> {{", rt));
+                writer.indent();
+                writer.line(&format!(
+                    "serde_json::from_str(json).map_err(|e| {}::DecodeError::Json(e.to_string()))",
+                    rt
+                ));
+                writer.dedent();
+                writer.line("}");
+            }
+            (WireFormat::Protobuf, TypeDecoder::Record(fields), TypeDefinition::Record(record)) => {
+                self.emit_decode_protobuf(record, fields, module, &rt, writer)
+            }
+            (WireFormat::Row, TypeDecoder::Record(fields), TypeDefinition::Record(record)) => {
+                emit_from_row(record, fields, &rt, writer)
+            }
+            (_, TypeDecoder::Numbered(values), _) => emit_from_number(name, values, &rt, writer),
+            _ => {}
+        }
+        writer.dedent();
+        writer.line("}");
+    }
+
+    fn emit_decode_protobuf(
+        &self,
+        record: &RecordDef,
+        fields: &[FieldDecoder],
+        module: &[String],
+        rt: &str,
+        writer: &mut CodeWriter,
+    ) {
+        let mut locals = Vec::new();
+        let mut arms = Vec::new();
+        let mut values = Vec::new();
+
+        for (field_name, type_expr) in &record.fields {
+            let ident = field_ident(field_name);
+            let codec = fields.iter().find(|decoder| &decoder.field == field_name).map(|d| &d.codec);
+            let shape = TypeShape::from_type_expr(type_expr);
+            let read = match codec {
+                Some(FieldCodec::Protobuf { number, encoding }) => {
+                    self.protobuf_field(field_name, *number, encoding, &shape, module, rt)
+                }
+                _ => None,
+            };
+
+            match read {
+                Some((local, arm, value)) => {
+                    let var = format!("field_{}", ident.trim_start_matches("r#"));
+                    locals.push(format!("let mut {} = {};", var, local));
+                    arms.push(arm.replace("$var", &var));
+                    values.push(format!("{}: {},", ident, value.replace("$var", &var)));
+                }
+                None => values.push(format!("{}: Default::default(),", ident)),
+            }
+        }
+
+        writer.line("/// Decode the protobuf encoding of a message");
+        writer.line(&format!("pub fn decode_protobuf(bytes: &[u8]) -> Result<Self, {}::DecodeError> {{", rt));
+        writer.indent();
+        for local in &locals {
+            writer.line(local);
+        }
+        writer.line("let mut rest = bytes;");
+        writer.line("let buf = &mut rest;");
+        writer.line("while !buf.is_empty() {");
+        writer.indent();
+        writer.line(&format!("let (number, wire) = {}::key(buf)?;", rt));
+        writer.line("match number {");
+        writer.indent();
+        for arm in &arms {
+            writer.line(arm);
+        }
+        writer.line(&format!("_ => {}::skip(buf, wire)?,", rt));
+        writer.dedent();
+        writer.line("}");
+        writer.dedent();
+        writer.line("}");
+        writer.line("Ok(Self {");
+        writer.indent();
+        for value in &values {
+            writer.line(value);
+        }
+        writer.dedent();
+        writer.line("})");
+        writer.dedent();
+        writer.line("}");
+    }
+
+    /// Initial value, match arm and final value of a protobuf field, with
+    /// `$var` standing for its local variable
+    fn protobuf_field(
+        &self,
+        field_name: &str,
+        number: u32,
+        encoding: &ProtoEncoding,
+        shape: &TypeShape,
+        module: &[String],
+        rt: &str,
+    ) -> Option<(String, String, String)> {
+        let field = match (encoding, Slot::of(shape)) {
+            (ProtoEncoding::Map(key_encoding, value_encoding), Slot::Map(key, value)) => {
+                let read_key = self.read_protobuf(key_encoding, key, module, rt)?;
+                let read_value = self.read_protobuf(value_encoding, value, module, rt)?;
+                let arm = format!(
+                    "{} => {{ let (key, value) = {}::entry(buf, |buf| {}, |buf| {})?; $var.insert(key.unwrap_or_default(), {}); }}",
+                    number,
+                    rt,
+                    read_key,
+                    read_value,
+                    self.finish_protobuf(value_encoding, value, field_name, "value", module, rt)
+                );
+                ("std::collections::HashMap::new()".to_string(), arm, "$var".to_string())
+            }
+            (ProtoEncoding::Map(..), _) | (_, Slot::Map(..)) => return None,
+            (_, Slot::Repeated(item)) => {
+                let arm = format!(
+                    "{} => {}::repeated(buf, wire, {}, |buf| {}, &mut $var)?,",
+                    number,
+                    rt,
+                    encoding.is_packable(),
+                    self.read_protobuf(encoding, item, module, rt)?
+                );
+                ("Vec::new()".to_string(), arm, "$var".to_string())
+            }
+            (_, Slot::Optional(item)) => {
+                let arm = format!("{} => $var = Some({}?),", number, self.read_protobuf(encoding, item, module, rt)?);
+                ("None".to_string(), arm, "$var".to_string())
+            }
+            (_, Slot::Single(item)) => {
+                let arm = format!("{} => $var = Some({}?),", number, self.read_protobuf(encoding, item, module, rt)?);
+                let value = self.finish_protobuf(encoding, item, field_name, "$var", module, rt);
+                ("None".to_string(), arm, value)
+            }
+        };
+        Some(field)
+    }
+
+    /// Expression reading one value from `buf: &mut &[u8]`
+    fn read_protobuf(&self, encoding: &ProtoEncoding, shape: &TypeShape, module: &[String], rt: &str) -> Option<String> {
+        let ty = self.rust_type(shape, module);
+        let convert = |read: String, from: &str| {
+            if ty == from {
+                read
+            } else {
+                format!("{}.map(|v| v as {})", read, ty)
+            }
+        };
+
+        let read = match encoding {
+            ProtoEncoding::Varint => convert(format!("{}::varint(buf)", rt), "u64"),
+            ProtoEncoding::ZigZag => convert(format!("{0}::varint(buf).map({0}::zigzag)", rt), "i64"),
+            ProtoEncoding::Bool => format!("{}::varint(buf).map(|v| v != 0)", rt),
+            ProtoEncoding::Fixed32 => convert(format!("{}::fixed32(buf)", rt), "u32"),
+            ProtoEncoding::Fixed64 => convert(format!("{}::fixed64(buf)", rt), "u64"),
+            ProtoEncoding::SFixed32 => convert(format!("{}::fixed32(buf).map(|v| v as i32)", rt), "i32"),
+            ProtoEncoding::SFixed64 => convert(format!("{}::fixed64(buf).map(|v| v as i64)", rt), "i64"),
+            ProtoEncoding::Float => convert(format!("{}::fixed32(buf).map(f32::from_bits)", rt), "f32"),
+            ProtoEncoding::Double => convert(format!("{}::fixed64(buf).map(f64::from_bits)", rt), "f64"),
+            ProtoEncoding::String => format!("{}::string(buf)", rt),
+            ProtoEncoding::Bytes => format!("{}::bytes(buf).map(<[u8]>::to_vec)", rt),
+            // References to types that were not generated cannot be decoded
+            ProtoEncoding::Message | ProtoEncoding::Enum if ty == "serde_json::Value" => return None,
+            ProtoEncoding::Message => format!("{}::bytes(buf).and_then(<{}>::decode_protobuf)", rt, ty),
+            ProtoEncoding::Enum => format!("{}::varint(buf).and_then(|v| <{}>::from_number(v as i64))", rt, ty),
+            ProtoEncoding::Map(..) => return None,
+        };
+        Some(read)
+    }
+
+    /// Value of a field from the `Option` in `var`, applying proto3 defaults
+    fn finish_protobuf(
+        &self,
+        encoding: &ProtoEncoding,
+        shape: &TypeShape,
+        field_name: &str,
+        var: &str,
+        module: &[String],
+        rt: &str,
+    ) -> String {
+        match encoding {
+            ProtoEncoding::Message => format!("{}::required({}, \"{}\")?", rt, var, field_name),
+            ProtoEncoding::Enum => format!(
+                "{}.map_or_else(|| <{}>::from_number(0), Ok)?",
+                var,
+                self.rust_type(shape, module)
+            ),
+            _ => format!("{}.unwrap_or_default()", var),
+        }
+    }
+}
+
+fn emit_from_row(record: &RecordDef, fields: &[FieldDecoder], rt: &str, writer: &mut CodeWriter) {
+    writer.line("/// Decode a row of text columns, such as a CSV record");
+    writer.line(&format!("pub fn from_row(row: &[&str]) -> Result<Self, {}::DecodeError> {{", rt));
+    writer.indent();
+    writer.line("Ok(Self {");
+    writer.indent();
+
+    for (field_name, type_expr) in &record.fields {
+        let ident = field_ident(field_name);
+        let index = fields.iter().find_map(|decoder| match decoder.codec {
+            FieldCodec::Column { index } if &decoder.field == field_name => Some(index),
+            _ => None,
+        });
+        let Some(index) = index else {
+            writer.line(&format!("{}: Default::default(),", ident));
+            continue;
+        };
+
+        let shape = TypeShape::from_type_expr(type_expr);
+        let read = match &shape {
+            TypeShape::Option(inner) if is_bytes(inner) => "optional_bytes_column",
+            TypeShape::Option(_) => "optional_column",
+            shape if is_bytes(shape) => "bytes_column",
+            _ => "column",
+        };
+        writer.line(&format!("{}: {}::{}(row, {}, \"{}\")?,", ident, rt, read, index, field_name));
+    }
+
+    writer.dedent();
+    writer.line("})");
+    writer.dedent();
+    writer.line("}");
+}
+
+fn emit_from_number(name: &str, values: &[(i64, String)], rt: &str, writer: &mut CodeWriter) {
+    writer.line("/// Variant numbered `number`");
+    writer.line(&format!("pub fn from_number(number: i64) -> Result<Self, {}::DecodeError> {{", rt));
+    writer.indent();
+    writer.line("match number {");
+    writer.indent();
+
+    let mut seen = Vec::new();
+    for (number, variant) in values {
+        // Aliases decode to the first variant with the number
+        if !seen.contains(number) {
+            seen.push(*number);
+            writer.line(&format!("{} => Ok(Self::{}),", number, type_ident(variant)));
+        }
+    }
+    writer.line(&format!(
+        "_ => Err({}::DecodeError::UnknownNumber {{ type_name: \"{}\", number }}),",
+        rt, name
+    ));
+
+    writer.dedent();
+    writer.line("}");
+    writer.dedent();
+    writer.line("}");
+}
+
+fn is_bytes(shape: &TypeShape) -> bool {
+    matches!(shape, TypeShape::Named(name) if name == "bytes")
+}
+
+/// Path to the runtime module as seen from `module`
+fn runtime_path(module: &[String]) -> String {
+    format!("{}{}", "super::".repeat(module.len()), RUNTIME)
+}
+
+/// Write the runtime module the decoders of `format` use
+pub(super) fn emit_runtime(format: WireFormat, writer: &mut CodeWriter) {
+    writer.blank();
+    writer.line("/// Support for the generated decoders");
+    writer.line(&format!("pub mod {} {{", RUNTIME));
+    writer.indent();
+
+    let sections = match format {
+        WireFormat::Json => [RUNTIME_ERRORS, ""],
+        WireFormat::Protobuf => [RUNTIME_ERRORS, RUNTIME_PROTOBUF],
+        WireFormat::Row => [RUNTIME_ERRORS, RUNTIME_ROW],
+    };
+    for section in sections.iter().filter(|s| !s.is_empty()) {
+        writer.blank();
+        for line in section.trim_matches('\n').lines() {
+            // Sections are written with 4-space indents
+            let body = line.trim_start();
+            let level = (line.len() - body.len()) / 4;
+            writer.line(&format!("{}{}", " ".repeat(writer.indent_width() * level), body));
+        }
+    }
+
+    writer.dedent();
+    writer.line("}");
+}
+
+const RUNTIME_ERRORS: &str = r#"
+use std::fmt;
+
+/// Error decoding data into a generated type
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodeError {
+    /// Malformed JSON, or JSON that does not match the type
+    Json(String),
+    /// Truncated or malformed protobuf encoding
+    Protobuf(&'static str),
+    /// Required field or column that is absent
+    Missing(&'static str),
+    /// Column text that is not a valid value
+    Column { column: &'static str, value: String },
+    /// Number of no variant
+    UnknownNumber { type_name: &'static str, number: i64 },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Json(message) => write!(f, "invalid JSON: {}", message),
+            DecodeError::Protobuf(message) => write!(f, "invalid protobuf: {}", message),
+            DecodeError::Missing(name) => write!(f, "missing {}", name),
+            DecodeError::Column { column, value } => write!(f, "invalid {} '{}'", column, value),
+            DecodeError::UnknownNumber { type_name, number } => write!(f, "no {} numbered {}", type_name, number),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+"#;
+
+const RUNTIME_PROTOBUF: &str = r#"
+/// Field number and wire type of the next field
+pub fn key(buf: &mut &[u8]) -> Result<(u64, u8), DecodeError> {
+    let key = varint(buf)?;
+    Ok((key >> 3, (key & 7) as u8))
+}
+
+pub fn varint(buf: &mut &[u8]) -> Result<u64, DecodeError> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = buf.split_first().ok_or(DecodeError::Protobuf("truncated varint"))?;
+        *buf = rest;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(DecodeError::Protobuf("varint longer than 10 bytes"))
+}
+
+pub fn zigzag(value: u64) -> i64 {
+    (value >> 1) as i64 ^ -((value & 1) as i64)
+}
+
+pub fn fixed32(buf: &mut &[u8]) -> Result<u32, DecodeError> {
+    let bytes = take(buf, 4)?;
+    Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+pub fn fixed64(buf: &mut &[u8]) -> Result<u64, DecodeError> {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(take(buf, 8)?);
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Contents of a length-delimited field
+pub fn bytes<'a>(buf: &mut &'a [u8]) -> Result<&'a [u8], DecodeError> {
+    let len = varint(buf)?;
+    take(buf, usize::try_from(len).map_err(|_| DecodeError::Protobuf("length too large"))?)
+}
+
+pub fn string(buf: &mut &[u8]) -> Result<String, DecodeError> {
+    String::from_utf8(bytes(buf)?.to_vec()).map_err(|_| DecodeError::Protobuf("string is not UTF-8"))
+}
+
+/// Read one value of a repeated field, or every value of a packed one
+pub fn repeated<T>(
+    buf: &mut &[u8],
+    wire: u8,
+    packable: bool,
+    mut read: impl FnMut(&mut &[u8]) -> Result<T, DecodeError>,
+    values: &mut Vec<T>,
+) -> Result<(), DecodeError> {
+    if packable && wire == 2 {
+        let mut packed = bytes(buf)?;
+        while !packed.is_empty() {
+            values.push(read(&mut packed)?);
+        }
+    } else {
+        values.push(read(buf)?);
+    }
+    Ok(())
+}
+
+/// Key and value of a map entry
+pub fn entry<K, V>(
+    buf: &mut &[u8],
+    read_key: impl Fn(&mut &[u8]) -> Result<K, DecodeError>,
+    read_value: impl Fn(&mut &[u8]) -> Result<V, DecodeError>,
+) -> Result<(Option<K>, Option<V>), DecodeError> {
+    let mut entry = bytes(buf)?;
+    let (mut key, mut value) = (None, None);
+    while !entry.is_empty() {
+        match self::key(&mut entry)? {
+            (1, _) => key = Some(read_key(&mut entry)?),
+            (2, _) => value = Some(read_value(&mut entry)?),
+            (_, wire) => skip(&mut entry, wire)?,
+        }
+    }
+    Ok((key, value))
+}
+
+pub fn skip(buf: &mut &[u8], wire: u8) -> Result<(), DecodeError> {
+    match wire {
+        0 => varint(buf).map(drop),
+        1 => take(buf, 8).map(drop),
+        2 => bytes(buf).map(drop),
+        5 => take(buf, 4).map(drop),
+        _ => Err(DecodeError::Protobuf("unsupported wire type")),
+    }
+}
+
+pub fn required<T>(value: Option<T>, name: &'static str) -> Result<T, DecodeError> {
+    value.ok_or(DecodeError::Missing(name))
+}
+
+fn take<'a>(buf: &mut &'a [u8], len: usize) -> Result<&'a [u8], DecodeError> {
+    if buf.len() < len {
+        return Err(DecodeError::Protobuf("truncated field"));
+    }
+    let (head, rest) = buf.split_at(len);
+    *buf = rest;
+    Ok(head)
+}
+"#;
+
+const RUNTIME_ROW: &str = r#"
+/// Values read from the text of a column
+pub trait FromColumn: Sized {
+    fn from_column(text: &str) -> Option<Self>;
+}
+
+impl FromColumn for String {
+    fn from_column(text: &str) -> Option<Self> {
+        Some(text.to_string())
+    }
+}
+
+impl FromColumn for i64 {
+    fn from_column(text: &str) -> Option<Self> {
+        text.trim().parse().ok()
+    }
+}
+
+impl FromColumn for u64 {
+    fn from_column(text: &str) -> Option<Self> {
+        text.trim().parse().ok()
+    }
+}
+
+impl FromColumn for f64 {
+    fn from_column(text: &str) -> Option<Self> {
+        text.trim().parse().ok()
+    }
+}
+
+impl FromColumn for bool {
+    fn from_column(text: &str) -> Option<Self> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "true" | "t" | "yes" | "y" | "1" => Some(true),
+            "false" | "f" | "no" | "n" | "0" => Some(false),
+            _ => None,
+        }
+    }
+}
+
+impl FromColumn for serde_json::Value {
+    fn from_column(text: &str) -> Option<Self> {
+        Some(serde_json::from_str(text).unwrap_or_else(|_| serde_json::Value::String(text.to_string())))
+    }
+}
+
+/// Arrays written as `{a,b}` or `[a,b]`
+impl<T: FromColumn> FromColumn for Vec<T> {
+    fn from_column(text: &str) -> Option<Self> {
+        let inner = text.trim().strip_prefix(['{', '[']).and_then(|t| t.strip_suffix(['}', ']']))?;
+        if inner.trim().is_empty() {
+            return Some(Vec::new());
+        }
+        inner.split(',').map(|item| T::from_column(item.trim().trim_matches('"'))).collect()
+    }
+}
+
+pub fn column<T: FromColumn>(row: &[&str], index: usize, name: &'static str) -> Result<T, DecodeError> {
+    let text = row.get(index).ok_or(DecodeError::Missing(name))?;
+    T::from_column(text).ok_or_else(|| invalid(name, text))
+}
+
+/// Empty text, `NULL` and `\N` read as `None`
+pub fn optional_column<T: FromColumn>(row: &[&str], index: usize, name: &'static str) -> Result<Option<T>, DecodeError> {
+    match row.get(index) {
+        Some(text) if !is_null(text) => T::from_column(text).map(Some).ok_or_else(|| invalid(name, text)),
+        _ => Ok(None),
+    }
+}
+
+/// Binary columns, hex-encoded with a `\x` prefix or raw
+pub fn bytes_column(row: &[&str], index: usize, name: &'static str) -> Result<Vec<u8>, DecodeError> {
+    let text = row.get(index).ok_or(DecodeError::Missing(name))?;
+    match text.strip_prefix("\\x") {
+        Some(hex) => (0..hex.len())
+            .step_by(2)
+            .map(|i| hex.get(i..i + 2).and_then(|b| u8::from_str_radix(b, 16).ok()))
+            .collect::<Option<_>>()
+            .ok_or_else(|| invalid(name, text)),
+        None => Ok(text.as_bytes().to_vec()),
+    }
+}
+
+pub fn optional_bytes_column(row: &[&str], index: usize, name: &'static str) -> Result<Option<Vec<u8>>, DecodeError> {
+    match row.get(index) {
+        Some(text) if !is_null(text) => bytes_column(row, index, name).map(Some),
+        _ => Ok(None),
+    }
+}
+
+fn is_null(text: &str) -> bool {
+    text.is_empty() || text == "\\N" || text.eq_ignore_ascii_case("null")
+}
+
+fn invalid(name: &'static str, text: &str) -> DecodeError {
+    DecodeError::Column {
+        column: name,
+        value: text.to_string(),
+    }
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode::DecoderPlan;
+    use crate::emit::rust::{emit_rust_decoders, RustOptions};
+    use fusabi_type_providers::{DuDef, GeneratedModule, GeneratedTypes, TypeExpr, VariantDef};
+
+    fn named(s: &str) -> TypeExpr {
+        TypeExpr::Named(s.to_string())
+    }
+
+    #[test]
+    fn test_emit_protobuf_decoders() {
+        let path = vec!["Api".to_string()];
+        let mut types = GeneratedTypes::new();
+        let mut module = GeneratedModule::new(path.clone());
+        module.types.push(TypeDefinition::Du(DuDef {
+            name: "Status".to_string(),
+            variants: vec![
+                VariantDef::new_simple("Unknown".to_string()),
+                VariantDef::new_simple("Active".to_string()),
+            ],
+        }));
+        module.types.push(TypeDefinition::Record(RecordDef {
+            name: "User".to_string(),
+            fields: vec![
+                ("id".to_string(), named("int64")),
+                ("type".to_string(), named("Status")),
+                ("scores".to_string(), named("int list")),
+                ("labels".to_string(), named("Map<string, string>")),
+            ],
+        }));
+        types.modules.push(module);
+
+        let mut plan = DecoderPlan::new(WireFormat::Protobuf);
+        plan.set(&path, "Status", TypeDecoder::Numbered(vec![(0, "Unknown".to_string()), (1, "Active".to_string())]));
+        let field = |name: &str, number, encoding| {
+            FieldDecoder::new(name, FieldCodec::Protobuf { number, encoding })
+        };
+        let map = ProtoEncoding::Map(Box::new(ProtoEncoding::String), Box::new(ProtoEncoding::String));
+        plan.set(
+            &path,
+            "User",
+            TypeDecoder::Record(vec![
+                field("id", 1, ProtoEncoding::Varint),
+                field("type", 2, ProtoEncoding::Enum),
+                field("scores", 3, ProtoEncoding::ZigZag),
+                field("labels", 4, map),
+            ]),
+        );
+
+        let output = emit_rust_decoders(&types, &plan, &RustOptions::default());
+        let rt = "super::decode_runtime";
+
+        assert!(output.contains(&format!(
+            "pub fn from_number(number: i64) -> Result<Self, {}::DecodeError> {{\n            match number {{\n                0 => Ok(Self::Unknown),",
+            rt
+        )));
+        assert!(output.contains(&format!("1 => field_id = Some({}::varint(buf).map(|v| v as i64)?),", rt)));
+        assert!(output.contains(&format!(
+            "2 => field_type = Some({}::varint(buf).and_then(|v| <Status>::from_number(v as i64))?),",
+            rt
+        )));
+        assert!(output.contains(&format!(
+            "3 => {0}::repeated(buf, wire, true, |buf| {0}::varint(buf).map({0}::zigzag), &mut field_scores)?,",
+            rt
+        )));
+        assert!(output.contains(&format!("4 => {{ let (key, value) = {0}::entry(buf, |buf| {0}::string(buf)", rt)));
+        assert!(output.contains("r#type: field_type.map_or_else(|| <Status>::from_number(0), Ok)?,"));
+        assert!(output.contains("labels: field_labels,"));
+        assert!(output.contains("pub mod decode_runtime {"));
+        assert!(output.contains("pub fn zigzag(value: u64) -> i64 {"));
+        assert!(!output.contains("pub trait FromColumn"));
+    }
+
+    #[test]
+    fn test_emit_row_and_json_decoders() {
+        let mut types = GeneratedTypes::new();
+        types.root_types.push(TypeDefinition::Record(RecordDef {
+            name: "Users".to_string(),
+            fields: vec![
+                ("id".to_string(), named("int")),
+                ("avatar".to_string(), named("bytes option")),
+                ("tags".to_string(), named("string list option")),
+            ],
+        }));
+
+        let mut plan = DecoderPlan::new(WireFormat::Row);
+        let columns = ["id", "avatar", "tags"]
+            .iter()
+            .enumerate()
+            .map(|(index, name)| FieldDecoder::new(*name, FieldCodec::Column { index }))
+            .collect();
+        plan.set(&[], "Users", TypeDecoder::Record(columns));
+
+        let output = emit_rust_decoders(&types, &plan, &RustOptions::default());
+        assert!(output.contains("pub fn from_row(row: &[&str]) -> Result<Self, decode_runtime::DecodeError> {"));
+        assert!(output.contains("id: decode_runtime::column(row, 0, \"id\")?,"));
+        assert!(output.contains("avatar: decode_runtime::optional_bytes_column(row, 1, \"avatar\")?,"));
+        assert!(output.contains("tags: decode_runtime::optional_column(row, 2, \"tags\")?,"));
+        assert!(output.contains("pub trait FromColumn: Sized {\n        fn from_column"));
+
+        let mut plan = DecoderPlan::json(&types);
+        plan.set(
+            &[],
+            "Users",
+            TypeDecoder::Record(vec![FieldDecoder::new("id", FieldCodec::Json { key: "userId".to_string() })]),
+        );
+        let output = emit_rust_decoders(&types, &plan, &RustOptions::default());
+        assert!(output.contains("#[serde(rename = \"userId\")]\n    pub id: i64,"));
+        assert!(output.contains("serde_json::from_str(json).map_err(|e| decode_runtime::DecodeError::Json(e.to_string()))"));
+        assert!(!output.contains("pub fn varint"));
+    }
+}
@@ -0,0 +1,288 @@
+//! TypeScript declaration emitter
+//!
+//! Renders generated types as a `.d.ts` file. Records become interfaces,
+//! discriminated unions become union types using the same externally tagged
+//! encoding as the JSON Schema exporter, and generated modules become
+//! nested namespaces. Generic definitions become generic interfaces and
+//! type aliases, with type parameters written in PascalCase (`'node` as
+//! `Node`).
+
+use super::{CodeWriter, ModuleTree};
+use crate::generics::{host_name, host_param, GenericName};
+use crate::index::{definition_name, TypeIndex};
+use crate::shape::TypeShape;
+use fusabi_type_providers::{DuDef, GeneratedTypes, RecordDef, TypeDefinition};
+
+/// Options for the TypeScript emitter
+#[derive(Debug, Clone)]
+pub struct TypeScriptOptions {
+    /// Spaces per indentation level
+    pub indent: usize,
+    /// Allow `null` for optional fields in addition to omitting them
+    pub nullable_optionals: bool,
+}
+
+impl Default for TypeScriptOptions {
+    fn default() -> Self {
+        Self {
+            indent: 2,
+            nullable_optionals: true,
+        }
+    }
+}
+
+/// Render generated types as a TypeScript declaration file
+pub fn emit_typescript(types: &GeneratedTypes, options: &TypeScriptOptions) -> String {
+    let emitter = TypeScriptEmitter {
+        index: TypeIndex::new(types),
+        options,
+    };
+    let mut writer = CodeWriter::new(options.indent);
+
+    writer.line("// Generated from Fusabi type provider output. Do not edit by hand.");
+    writer.blank();
+    emitter.emit_module(&ModuleTree::build(types), &mut writer);
+
+    writer.finish()
+}
+
+struct TypeScriptEmitter<'a> {
+    index: TypeIndex<'a>,
+    options: &'a TypeScriptOptions,
+}
+
+impl TypeScriptEmitter<'_> {
+    fn emit_module(&self, module: &ModuleTree, writer: &mut CodeWriter) {
+        for type_def in &module.types {
+            match type_def {
+                TypeDefinition::Record(record) => self.emit_record(record, &module.path, writer),
+                TypeDefinition::Du(du) => self.emit_du(du, &module.path, writer),
+            }
+            writer.blank();
+        }
+
+        for child in &module.children {
+            writer.line(&format!("export namespace {} {{", child.name()));
+            writer.indent();
+            self.emit_module(child, writer);
+            writer.dedent();
+            writer.line("}");
+            writer.blank();
+        }
+    }
+
+    fn emit_record(&self, record: &RecordDef, module: &[String], writer: &mut CodeWriter) {
+        let name = GenericName::parse(&record.name);
+        writer.line(&format!("export interface {} {{", host_name(&name, &name.name)));
+        writer.indent();
+
+        for (field_name, type_expr) in &record.fields {
+            let shape = TypeShape::from_type_expr(type_expr);
+            let key = property_key(field_name);
+
+            let line = match &shape {
+                TypeShape::Option(inner) => {
+                    let mut ts_type = self.ts_type(inner, module);
+                    if self.options.nullable_optionals {
+                        ts_type.push_str(" | null");
+                    }
+                    format!("{}?: {};", key, ts_type)
+                }
+                other => format!("{}: {};", key, self.ts_type(other, module)),
+            };
+            writer.line(&line);
+        }
+
+        writer.dedent();
+        writer.line("}");
+    }
+
+    fn emit_du(&self, du: &DuDef, module: &[String], writer: &mut CodeWriter) {
+        let name = GenericName::parse(&du.name);
+        let type_name = host_name(&name, &name.name);
+        let variants: Vec<String> = du
+            .variants
+            .iter()
+            .map(|variant| match variant.fields.as_slice() {
+                [] => format!("\"{}\"", variant.name),
+                [single] => format!(
+                    "{{ {}: {} }}",
+                    property_key(&variant.name),
+                    self.ts_type(&TypeShape::from_type_expr(single), module)
+                ),
+                many => {
+                    let items: Vec<String> = many
+                        .iter()
+                        .map(|f| self.ts_type(&TypeShape::from_type_expr(f), module))
+                        .collect();
+                    format!("{{ {}: [{}] }}", property_key(&variant.name), items.join(", "))
+                }
+            })
+            .collect();
+
+        if variants.is_empty() {
+            writer.line(&format!("export type {} = never;", type_name));
+            return;
+        }
+
+        writer.line(&format!("export type {} =", type_name));
+        writer.indent();
+        let last = variants.len() - 1;
+        for (i, variant) in variants.iter().enumerate() {
+            let terminator = if i == last { ";" } else { "" };
+            writer.line(&format!("| {}{}", variant, terminator));
+        }
+        writer.dedent();
+    }
+
+    fn ts_type(&self, shape: &TypeShape, module: &[String]) -> String {
+        match shape {
+            TypeShape::Named(name) => self.named_type(name, module),
+            TypeShape::Option(inner) => format!("{} | null", self.ts_type(inner, module)),
+            TypeShape::List(inner) => {
+                let item = self.ts_type(inner, module);
+                if item.contains(' ') {
+                    format!("({})[]", item)
+                } else {
+                    format!("{}[]", item)
+                }
+            }
+            TypeShape::Map(_, value) => format!("Record<string, {}>", self.ts_type(value, module)),
+            TypeShape::Tuple(items) => {
+                let items: Vec<String> = items.iter().map(|i| self.ts_type(i, module)).collect();
+                format!("[{}]", items.join(", "))
+            }
+            TypeShape::Literal(text) => format!("\"{}\"", text),
+            TypeShape::Union(items) => {
+                let items: Vec<String> = items.iter().map(|i| self.ts_type(i, module)).collect();
+                items.join(" | ")
+            }
+            TypeShape::Param(name) => host_param(name),
+            TypeShape::Generic(name, args) => {
+                let args: Vec<String> = args.iter().map(|a| self.ts_type(a, module)).collect();
+                format!("{}<{}>", self.named_type(name, module), args.join(", "))
+            }
+        }
+    }
+
+    fn named_type(&self, name: &str, module: &[String]) -> String {
+        match name {
+            "string" | "bytes" => "string".to_string(),
+            "int" | "int64" | "uint" | "uint64" | "float" => "number".to_string(),
+            "bool" => "boolean".to_string(),
+            "unit" => "null".to_string(),
+            "any" => "unknown".to_string(),
+            _ => match self.index.resolve(name, module) {
+                Some(entry) if entry.module == module => {
+                    definition_name(entry.definition).to_string()
+                }
+                Some(entry) => entry.key.clone(),
+                None => "unknown".to_string(),
+            },
+        }
+    }
+}
+
+/// Property key, quoted when it is not a valid identifier
+fn property_key(name: &str) -> String {
+    let valid = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+
+    if valid {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_type_providers::{GeneratedModule, TypeExpr, VariantDef};
+
+    fn named(s: &str) -> TypeExpr {
+        TypeExpr::Named(s.to_string())
+    }
+
+    #[test]
+    fn test_emit_interface() {
+        let mut types = GeneratedTypes::new();
+        types.root_types.push(TypeDefinition::Record(RecordDef {
+            name: "Resource".to_string(),
+            fields: vec![
+                ("uri".to_string(), named("string")),
+                ("mimeType".to_string(), named("string option")),
+                ("content-type".to_string(), named("string list")),
+                ("labels".to_string(), named("Map<string, int>")),
+            ],
+        }));
+
+        let output = emit_typescript(&types, &TypeScriptOptions::default());
+
+        assert!(output.contains("export interface Resource {"));
+        assert!(output.contains("  uri: string;"));
+        assert!(output.contains("  mimeType?: string | null;"));
+        assert!(output.contains("  \"content-type\": string[];"));
+        assert!(output.contains("  labels: Record<string, number>;"));
+    }
+
+    #[test]
+    fn test_emit_union() {
+        let mut types = GeneratedTypes::new();
+        types.root_types.push(TypeDefinition::Du(DuDef {
+            name: "ToolCall".to_string(),
+            variants: vec![
+                VariantDef::new_simple("Ping".to_string()),
+                VariantDef::new("GetWeather".to_string(), vec![named("GetWeatherInput")]),
+            ],
+        }));
+
+        let output = emit_typescript(&types, &TypeScriptOptions::default());
+
+        assert!(output.contains("export type ToolCall =\n  | \"Ping\"\n  | { GetWeather: unknown };"));
+    }
+
+    #[test]
+    fn test_emit_generics() {
+        let mut types = GeneratedTypes::new();
+        types.root_types.push(TypeDefinition::Du(DuDef {
+            name: "Page<'item>".to_string(),
+            variants: vec![
+                VariantDef::new_simple("Empty".to_string()),
+                VariantDef::new("Items".to_string(), vec![named("'item list")]),
+            ],
+        }));
+        types.root_types.push(TypeDefinition::Record(RecordDef {
+            name: "Catalog".to_string(),
+            fields: vec![("products".to_string(), named("Page<string>"))],
+        }));
+
+        let output = emit_typescript(&types, &TypeScriptOptions::default());
+
+        assert!(output.contains("export type Page<Item> =\n  | \"Empty\"\n  | { Items: Item[] };"));
+        assert!(output.contains("  products: Page<string>;"));
+    }
+
+    #[test]
+    fn test_emit_namespaces() {
+        let mut types = GeneratedTypes::new();
+        let mut module = GeneratedModule::new(vec!["Otel".to_string(), "Http".to_string()]);
+        module.types.push(TypeDefinition::Record(RecordDef {
+            name: "Client".to_string(),
+            fields: vec![("route".to_string(), named("string"))],
+        }));
+        types.modules.push(module);
+        types.root_types.push(TypeDefinition::Record(RecordDef {
+            name: "Span".to_string(),
+            fields: vec![("http".to_string(), named("Client"))],
+        }));
+
+        let output = emit_typescript(&types, &TypeScriptOptions::default());
+
+        assert!(output.contains("export namespace Otel {\n  export namespace Http {\n    export interface Client {"));
+        assert!(output.contains("  http: Otel.Http.Client;"));
+    }
+}
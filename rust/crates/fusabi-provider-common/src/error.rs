@@ -0,0 +1,316 @@
+//! Structured provider errors with context and causes
+//!
+//! `ProviderError` from the core crate has three variants that carry a
+//! message only, so a failure deep in a provider loses both its kind and
+//! its cause. Providers build a [`StructuredError`] instead: it has an
+//! [`ErrorKind`], a cause reachable through `Error::source`, and context
+//! added on the way out, such as `while parsing table users`. At the
+//! `TypeProvider` boundary it converts into `ProviderError`, with context
+//! and causes flattened into the message:
+//!
+//! | Kind                 | `ProviderError` variant | Message prefix          |
+//! |----------------------|-------------------------|-------------------------|
+//! | `Io`                 | `IoError`               |                         |
+//! | `Network`            | `IoError`               | `network error: `       |
+//! | `Parse`              | `ParseError`            |                         |
+//! | `Validation`         | `InvalidSource`         |                         |
+//! | `UnsupportedFeature` | `InvalidSource`         | `unsupported feature: ` |
+//!
+//! [`ErrorKind::of`] reads the kind back from a `ProviderError`, and
+//! [`Context`] adds context to results of either error type.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_common::error::{Context, StructuredError};
+//!
+//! let text = std::fs::read_to_string(path)
+//!     .map_err(|e| StructuredError::io(format!("Cannot read {}", path)).with_source(e))?;
+//! let table = parse_table(&text).with_context(|| format!("while parsing table {}", name))?;
+//! ```
+
+use fusabi_type_providers::ProviderError;
+use std::error::Error;
+use std::fmt;
+
+/// Message prefix of network errors converted into `ProviderError`
+const NETWORK_PREFIX: &str = "network error: ";
+
+/// Message prefix of unsupported-feature errors converted into
+/// `ProviderError`
+const UNSUPPORTED_PREFIX: &str = "unsupported feature: ";
+
+/// Cause of a boxed error
+type BoxError = Box<dyn Error + Send + Sync + 'static>;
+
+/// What kind of failure an error reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Reading a local source failed
+    Io,
+    /// Fetching a remote source failed
+    Network,
+    /// The source is malformed
+    Parse,
+    /// The source or params are well-formed but not acceptable
+    Validation,
+    /// The source uses something the provider does not support
+    UnsupportedFeature,
+}
+
+impl ErrorKind {
+    /// Kind of a `ProviderError`, as converted from a [`StructuredError`]
+    ///
+    /// Each variant maps to one kind; the kinds that share a variant are
+    /// told apart by the message prefix the conversion adds. The prefix
+    /// must stay first in the message, so add context through [`Context`]:
+    /// wrapping the message by hand, as in
+    /// `ProviderError::IoError(format!("{}: {}", context, message))`,
+    /// hides it and a network error reads back as `Io`, an unsupported
+    /// feature as `Validation`.
+    pub fn of(error: &ProviderError) -> Self {
+        match error {
+            ProviderError::IoError(message) => {
+                if message.starts_with(NETWORK_PREFIX) {
+                    ErrorKind::Network
+                } else {
+                    ErrorKind::Io
+                }
+            }
+            ProviderError::ParseError(_) => ErrorKind::Parse,
+            ProviderError::InvalidSource(message) => {
+                if message.starts_with(UNSUPPORTED_PREFIX) {
+                    ErrorKind::UnsupportedFeature
+                } else {
+                    ErrorKind::Validation
+                }
+            }
+        }
+    }
+}
+
+/// A provider error with a kind, context and cause
+#[derive(Debug)]
+pub struct StructuredError {
+    kind: ErrorKind,
+    message: String,
+    /// Context, innermost first
+    context: Vec<String>,
+    source: Option<BoxError>,
+}
+
+impl StructuredError {
+    /// Create an error
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            context: Vec::new(),
+            source: None,
+        }
+    }
+
+    /// Reading a local source failed
+    pub fn io(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Io, message)
+    }
+
+    /// Fetching a remote source failed
+    pub fn network(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Network, message)
+    }
+
+    /// The source is malformed
+    pub fn parse(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Parse, message)
+    }
+
+    /// The source or params are not acceptable
+    pub fn validation(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Validation, message)
+    }
+
+    /// The source uses `feature`, which the provider does not support
+    pub fn unsupported(feature: impl Into<String>) -> Self {
+        Self::new(ErrorKind::UnsupportedFeature, feature)
+    }
+
+    /// Attach the underlying error
+    pub fn with_source(mut self, source: impl Into<BoxError>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Add context, such as `while parsing table users`
+    pub fn context(mut self, context: impl fmt::Display) -> Self {
+        self.context.push(context.to_string());
+        self
+    }
+
+    /// What kind of failure this is
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Message without context or causes
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for StructuredError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for context in self.context.iter().rev() {
+            write!(f, "{}: ", context)?;
+        }
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for StructuredError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_deref().map(|e| e as &(dyn Error + 'static))
+    }
+}
+
+impl From<std::io::Error> for StructuredError {
+    fn from(error: std::io::Error) -> Self {
+        Self::io("I/O error").with_source(error)
+    }
+}
+
+impl From<StructuredError> for ProviderError {
+    fn from(error: StructuredError) -> Self {
+        let mut message = error.to_string();
+        let mut cause = error.source();
+        while let Some(e) = cause {
+            message = format!("{}: {}", message, e);
+            cause = e.source();
+        }
+
+        match error.kind {
+            ErrorKind::Io => ProviderError::IoError(message),
+            ErrorKind::Network => ProviderError::IoError(format!("{}{}", NETWORK_PREFIX, message)),
+            ErrorKind::Parse => ProviderError::ParseError(message),
+            ErrorKind::Validation => ProviderError::InvalidSource(message),
+            ErrorKind::UnsupportedFeature => {
+                ProviderError::InvalidSource(format!("{}{}", UNSUPPORTED_PREFIX, message))
+            }
+        }
+    }
+}
+
+/// Add context to the error of a result
+pub trait Context<T, E> {
+    /// Add `context` to the error, if any
+    fn context(self, context: impl fmt::Display) -> Result<T, E>;
+
+    /// Add context computed only when there is an error
+    fn with_context<C: fmt::Display>(self, context: impl FnOnce() -> C) -> Result<T, E>;
+}
+
+impl<T> Context<T, StructuredError> for Result<T, StructuredError> {
+    fn context(self, context: impl fmt::Display) -> Result<T, StructuredError> {
+        self.map_err(|e| e.context(context))
+    }
+
+    fn with_context<C: fmt::Display>(self, context: impl FnOnce() -> C) -> Result<T, StructuredError> {
+        self.map_err(|e| e.context(context()))
+    }
+}
+
+impl<T> Context<T, ProviderError> for Result<T, ProviderError> {
+    fn context(self, context: impl fmt::Display) -> Result<T, ProviderError> {
+        self.map_err(|e| add_context(e, &context))
+    }
+
+    fn with_context<C: fmt::Display>(self, context: impl FnOnce() -> C) -> Result<T, ProviderError> {
+        self.map_err(|e| add_context(e, &context()))
+    }
+}
+
+/// Prefix the message with `context`, after any kind prefix
+fn add_context(error: ProviderError, context: &dyn fmt::Display) -> ProviderError {
+    let prefixed = |message: String, prefix: &str| match message.strip_prefix(prefix) {
+        Some(rest) => format!("{}{}: {}", prefix, context, rest),
+        None => format!("{}: {}", context, message),
+    };
+
+    match error {
+        ProviderError::IoError(message) => ProviderError::IoError(prefixed(message, NETWORK_PREFIX)),
+        ProviderError::ParseError(message) => ProviderError::ParseError(prefixed(message, "")),
+        ProviderError::InvalidSource(message) => {
+            ProviderError::InvalidSource(prefixed(message, UNSUPPORTED_PREFIX))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(path: &str) -> Result<String, StructuredError> {
+        std::fs::read_to_string(path)
+            .map_err(|e| StructuredError::io(format!("Cannot read {}", path)).with_source(e))
+    }
+
+    #[test]
+    fn test_structured_error() {
+        let error = read("/nonexistent/schema.sql")
+            .context("while loading the schema")
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::Io);
+        assert_eq!(error.to_string(), "while loading the schema: Cannot read /nonexistent/schema.sql");
+        assert!(error.source().unwrap().downcast_ref::<std::io::Error>().is_some());
+
+        let error = ProviderError::from(error);
+        assert_eq!(ErrorKind::of(&error), ErrorKind::Io);
+        let ProviderError::IoError(message) = &error else {
+            panic!("expected an I/O error, got {}", error);
+        };
+        assert!(message.starts_with("while loading the schema: Cannot read /nonexistent/schema.sql: "));
+    }
+
+    #[test]
+    fn test_from_io_error() {
+        let error = StructuredError::from(std::io::Error::new(std::io::ErrorKind::NotFound, "schema.sql"));
+        assert_eq!(error.kind(), ErrorKind::Io);
+        let source = error.source().unwrap().downcast_ref::<std::io::Error>().unwrap();
+        assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+        let ProviderError::IoError(message) = ProviderError::from(error) else {
+            unreachable!();
+        };
+        assert_eq!(message, "I/O error: schema.sql");
+    }
+
+    #[test]
+    fn test_context_keeps_kind() {
+        let network: Result<(), ProviderError> =
+            Err(StructuredError::network("Fetching https://example.com failed").into());
+        let error = network.with_context(|| "while resolving the spec").unwrap_err();
+        assert_eq!(ErrorKind::of(&error), ErrorKind::Network);
+        assert!(error
+            .to_string()
+            .contains("network error: while resolving the spec: Fetching https://example.com failed"));
+
+        let unsupported: Result<(), ProviderError> = Err(StructuredError::unsupported("proto2 groups").into());
+        let error = unsupported.context("while parsing message Order").unwrap_err();
+        assert_eq!(ErrorKind::of(&error), ErrorKind::UnsupportedFeature);
+
+        let parse: Result<(), ProviderError> = Err(ProviderError::ParseError("Missing type".to_string()));
+        let error = parse.context("while parsing table users").unwrap_err();
+        assert_eq!(ErrorKind::of(&error), ErrorKind::Parse);
+        assert!(error.to_string().contains("while parsing table users: Missing type"));
+        assert_eq!(
+            ErrorKind::of(&ProviderError::InvalidSource("bad param".to_string())),
+            ErrorKind::Validation
+        );
+
+        // Context written into the message by hand hides the kind prefix
+        let ProviderError::IoError(message) = ProviderError::from(StructuredError::network("timed out")) else {
+            unreachable!();
+        };
+        let error = ProviderError::IoError(format!("while resolving the spec: {}", message));
+        assert_eq!(ErrorKind::of(&error), ErrorKind::Io);
+    }
+}
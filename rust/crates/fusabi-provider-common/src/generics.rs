@@ -0,0 +1,147 @@
+//! Generic type definitions
+//!
+//! `RecordDef` and `DuDef`, defined upstream, name a type with a plain
+//! string. A generic definition carries its type parameters in that name,
+//! `Connection<'node>`, the same way `TypeExpr` carries structure as a
+//! rendered string. Field types refer to a parameter as `'node`
+//! ([`TypeShape::Param`]) and other types use an instantiation such as
+//! `Connection<User>` ([`TypeShape::Generic`]), so that a provider declares
+//! a wrapper once instead of a concrete copy per wrapped type:
+//!
+//! ```rust,ignore
+//! let node = TypeShape::param("node");
+//! module.types.push(TypeDefinition::Record(RecordDef {
+//!     name: GenericName::new("Edge", ["node"]).to_string(),
+//!     fields: vec![("cursor".to_string(), TypeShape::named("string").into()), ("node".to_string(), node.into())],
+//! }));
+//! let users: TypeExpr = TypeShape::generic("Edge", vec![TypeShape::named("User")]).list().into();
+//! ```
+//!
+//! References resolve by the base name (`Edge`), and the `naming` param
+//! re-cases the base name only. Side tables such as
+//! [`TypeDocs`](crate::docs::TypeDocs) are keyed by the full definition name.
+
+use std::fmt;
+
+/// Name of a type definition split into base name and type parameters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenericName {
+    /// Base name (`Connection`)
+    pub name: String,
+    /// Parameter names without the leading quote (`node`)
+    pub params: Vec<String>,
+}
+
+impl GenericName {
+    /// A name with the given type parameters
+    pub fn new<I, S>(name: impl Into<String>, params: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            params: params.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Split a definition name such as `Connection<'node>`
+    ///
+    /// Names without parameters, and names whose parameter list does not
+    /// parse, are returned whole with no parameters.
+    pub fn parse(name: &str) -> Self {
+        match split(name) {
+            Some((base, params)) => Self::new(base, params),
+            None => Self::new(name, Vec::<String>::new()),
+        }
+    }
+
+    /// Whether the definition declares type parameters
+    pub fn is_generic(&self) -> bool {
+        !self.params.is_empty()
+    }
+
+    /// The same parameters under another base name
+    pub fn with_name(&self, name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            params: self.params.clone(),
+        }
+    }
+}
+
+impl fmt::Display for GenericName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if self.is_generic() {
+            let params: Vec<String> = self.params.iter().map(|p| format!("'{}", p)).collect();
+            write!(f, "<{}>", params.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Base name of a definition name, without its type parameters
+pub fn base_name(name: &str) -> &str {
+    split(name).map_or(name, |(base, _)| base)
+}
+
+/// Base name and parameters of `Name<'a, 'b>`
+fn split(name: &str) -> Option<(&str, Vec<&str>)> {
+    let (base, list) = name.strip_suffix('>')?.split_once('<')?;
+    let params = list
+        .split(',')
+        .map(|p| p.trim().strip_prefix('\''))
+        .collect::<Option<Vec<_>>>()?;
+    let valid = !base.trim().is_empty()
+        && params
+            .iter()
+            .all(|p| !p.is_empty() && p.chars().all(|c| c.is_alphanumeric() || c == '_'));
+    valid.then_some((base.trim(), params))
+}
+
+/// Type parameter as written in host languages, where `'a` would be a
+/// lifetime or invalid: `node` becomes `Node`
+pub(crate) fn host_param(param: &str) -> String {
+    let mut chars = param.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Definition name as written in host languages, such as `Connection<Node>`
+pub(crate) fn host_name(name: &GenericName, base: &str) -> String {
+    if name.is_generic() {
+        let params: Vec<String> = name.params.iter().map(|p| host_param(p)).collect();
+        format!("{}<{}>", base, params.join(", "))
+    } else {
+        base.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display() {
+        let name = GenericName::parse("Connection<'node, 'cursor>");
+        assert_eq!(name, GenericName::new("Connection", ["node", "cursor"]));
+        assert_eq!(name.to_string(), "Connection<'node, 'cursor>");
+
+        assert!(!GenericName::parse("User").is_generic());
+        // Concrete arguments are not parameters
+        assert_eq!(GenericName::parse("Edge<User>").name, "Edge<User>");
+        assert_eq!(base_name("Edge<User>"), "Edge<User>");
+        assert_eq!(base_name("Connection<'node>"), "Connection");
+        assert_eq!(base_name("User"), "User");
+    }
+
+    #[test]
+    fn test_host_name() {
+        let name = GenericName::new("Edge", ["node", "a"]);
+        assert_eq!(host_name(&name, "Edge"), "Edge<Node, A>");
+        assert_eq!(host_name(&GenericName::parse("User"), "User"), "User");
+    }
+}
@@ -0,0 +1,460 @@
+//! Identifier checks for generated output
+//!
+//! Providers name types, fields and union cases after the source, and
+//! sources allow names Fusabi does not: a SQL table named `type`, an MCP
+//! tool named `get-weather`, two Protobuf packages declaring the same
+//! message in one namespace. [`check_identifiers`] flags duplicate names,
+//! reserved keywords and invalid identifiers; it runs as part of
+//! [`check_generated_types`](crate::validate::check_generated_types).
+//!
+//! [`fix_identifiers`] renames the offending names: invalid characters
+//! become `_`, keywords get a trailing `_` (`type_`) and duplicates a
+//! numeric suffix (`Users2`). It is opt-in through the `fix_identifiers`
+//! param, and every rename is recorded in the [`NameMap`]. Without it the
+//! Fusabi emitter still quotes field names that are keywords or not
+//! identifiers with double backticks, which is valid for fields only.
+//!
+//! Keywords alone are escaped the same way for every provider through the
+//! `keyword_escape` param, applied by [`escape_keywords`]:
+//!
+//! | `keyword_escape` | Field `match`     | Case or type `type` |
+//! |------------------|-------------------|---------------------|
+//! | `backtick`       | ``` ``match`` ``` | `type_`             |
+//! | `suffix`         | `match_`          | `type_`             |
+//!
+//! Without the param, keyword fields are quoted as with `backtick` and
+//! keyword type and case names are only reported.
+
+use crate::generics::{base_name, GenericName};
+use crate::index::qualified_name;
+use crate::naming::{rename_expr, NameKind, NameMap, NameMapping};
+use crate::validate::Diagnostic;
+use fusabi_type_providers::{
+    GeneratedTypes, ProviderError, ProviderParams, ProviderResult, TypeDefinition,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Params key enabling [`fix_identifiers`]
+pub const FIX_IDENTIFIERS_PARAM: &str = "fix_identifiers";
+
+/// Params key selecting how keywords are escaped
+pub const KEYWORD_ESCAPE_PARAM: &str = "keyword_escape";
+
+/// How names that are reserved keywords are escaped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeywordEscape {
+    /// Quote with double backticks (``` ``match`` ```)
+    Backtick,
+    /// Append an underscore (`match_`)
+    Suffix,
+}
+
+impl KeywordEscape {
+    /// Parse a `keyword_escape` param value
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "backtick" => Some(KeywordEscape::Backtick),
+            "suffix" => Some(KeywordEscape::Suffix),
+            _ => None,
+        }
+    }
+
+    /// Read the `keyword_escape` param, if given
+    pub fn from_params(params: &ProviderParams) -> ProviderResult<Option<Self>> {
+        params
+            .custom
+            .get(KEYWORD_ESCAPE_PARAM)
+            .map(|value| {
+                Self::parse(value).ok_or_else(|| {
+                    ProviderError::InvalidSource(format!(
+                        "Invalid {} '{}': expected backtick or suffix",
+                        KEYWORD_ESCAPE_PARAM, value
+                    ))
+                })
+            })
+            .transpose()
+    }
+}
+
+/// Reserved words of the Fusabi language
+pub const FUSABI_KEYWORDS: &[&str] = &[
+    "and", "as", "assert", "async", "begin", "do", "done", "downto", "elif", "else", "end",
+    "exception", "false", "finally", "for", "fun", "function", "if", "import", "in", "lazy",
+    "let", "match", "module", "mutable", "namespace", "not", "null", "of", "open", "or", "rec",
+    "return", "then", "to", "true", "try", "type", "use", "val", "when", "while", "with",
+    "yield",
+];
+
+/// Whether `name` is a valid Fusabi identifier
+pub fn is_identifier(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '\'')
+}
+
+/// Whether `name` is a reserved Fusabi keyword
+pub fn is_keyword(name: &str) -> bool {
+    FUSABI_KEYWORDS.contains(&name)
+}
+
+/// Read the `fix_identifiers` param; `false` when it is not given
+pub fn fix_from_params(params: &ProviderParams) -> ProviderResult<bool> {
+    match params.custom.get(FIX_IDENTIFIERS_PARAM) {
+        None => Ok(false),
+        Some(value) => value.parse().map_err(|_| {
+            ProviderError::InvalidSource(format!(
+                "Invalid {} '{}': expected true or false",
+                FIX_IDENTIFIERS_PARAM, value
+            ))
+        }),
+    }
+}
+
+/// Flag duplicate names, reserved keywords and invalid identifiers
+///
+/// Duplicates and invalid type or case names are errors. Field names are
+/// warnings, since the Fusabi emitter quotes them.
+pub fn check_identifiers(types: &GeneratedTypes) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for (path, type_defs) in modules(types) {
+        let mut seen = BTreeSet::new();
+        for type_def in type_defs {
+            let name = base_name(type_name(type_def));
+            let location = qualified_name(path, name);
+            if !seen.insert(name) {
+                diagnostics.push(
+                    Diagnostic::error("duplicate-type", format!("Type `{}` is generated twice", location))
+                        .at(location.clone()),
+                );
+            }
+            diagnostics.extend(check_name(name, "Type", &location, false));
+
+            let (members, kind, quoted) = match type_def {
+                TypeDefinition::Record(record) => {
+                    (record.fields.iter().map(|(f, _)| f.as_str()).collect::<Vec<_>>(), "Field", true)
+                }
+                TypeDefinition::Du(du) => (du.variants.iter().map(|v| v.name.as_str()).collect(), "Case", false),
+            };
+            let mut seen_members = BTreeSet::new();
+            for member in members {
+                let member_location = format!("{}.{}", location, member);
+                if !seen_members.insert(member) {
+                    diagnostics.push(
+                        Diagnostic::error(
+                            "duplicate-member",
+                            format!("{} `{}` is declared twice", kind, member_location),
+                        )
+                        .at(member_location.clone()),
+                    );
+                }
+                diagnostics.extend(check_name(member, kind, &member_location, quoted));
+            }
+        }
+    }
+
+    diagnostics
+}
+
+fn check_name(name: &str, kind: &str, location: &str, quoted: bool) -> Option<Diagnostic> {
+    let (code, problem) = if is_keyword(name) {
+        ("reserved-keyword", "is a reserved keyword")
+    } else if !is_identifier(name) {
+        ("invalid-identifier", "is not a valid identifier")
+    } else {
+        return None;
+    };
+
+    let message = format!("{} `{}` {}", kind, location, problem);
+    let diagnostic = if quoted {
+        Diagnostic::warning(code, message)
+    } else {
+        Diagnostic::error(code, message)
+    };
+    Some(diagnostic.at(location))
+}
+
+/// Rename duplicate, reserved and invalid names so that every name is a
+/// unique Fusabi identifier
+///
+/// References to renamed types are rewritten; a duplicate type keeps the
+/// references, which resolve to the first type of that name.
+pub fn fix_identifiers(types: GeneratedTypes) -> (GeneratedTypes, NameMap) {
+    rewrite_names(types, &|name, _| sanitize(name), true)
+}
+
+/// Escape type, field and case names that are reserved keywords
+///
+/// With [`KeywordEscape::Suffix`] every keyword gets a trailing `_`. With
+/// [`KeywordEscape::Backtick`] field names are kept, for the Fusabi emitter
+/// to quote, and type and case names, which cannot be quoted, get the
+/// suffix.
+pub fn escape_keywords(types: GeneratedTypes, escape: KeywordEscape) -> (GeneratedTypes, NameMap) {
+    rewrite_names(
+        types,
+        &|name, is_field| match escape {
+            KeywordEscape::Backtick if is_field => name.to_string(),
+            _ => escape_keyword(name, KeywordEscape::Suffix),
+        },
+        false,
+    )
+}
+
+/// Apply `fix` to every type, field (`true`) and case (`false`) name
+///
+/// A fixed name that is already taken gets a numeric suffix, and so do
+/// existing duplicates when `dedupe` is set.
+fn rewrite_names(
+    mut types: GeneratedTypes,
+    fix: &dyn Fn(&str, bool) -> String,
+    dedupe: bool,
+) -> (GeneratedTypes, NameMap) {
+    let mut map = NameMap::default();
+    let mut renamed: BTreeMap<String, String> = BTreeMap::new();
+    let unique = |name: &str, taken: &mut BTreeSet<String>, is_field: bool| {
+        let fixed = fix(name, is_field);
+        let result = if fixed != name || dedupe {
+            unique_name(&fixed, taken)
+        } else {
+            fixed
+        };
+        taken.insert(result.clone());
+        result
+    };
+
+    let modules = std::iter::once((Vec::new(), &mut types.root_types))
+        .chain(types.modules.iter_mut().map(|m| (m.path.clone(), &mut m.types)));
+    for (path, type_defs) in modules {
+        let mut taken = BTreeSet::new();
+        for type_def in type_defs.iter_mut() {
+            let original = type_name(type_def).to_string();
+            let generic = GenericName::parse(&original);
+            let new_name = unique(&generic.name, &mut taken, false);
+            if new_name == generic.name {
+                continue;
+            }
+
+            // References keep pointing at the first of several duplicates
+            if fix(&generic.name, false) == new_name {
+                renamed.insert(generic.name.clone(), new_name.clone());
+            }
+            let name = generic.with_name(new_name).to_string();
+            map.push(NameMapping {
+                kind: NameKind::Type,
+                module: path.clone(),
+                owner: None,
+                name: name.clone(),
+                original,
+            });
+            set_type_name(type_def, name);
+        }
+
+        for type_def in type_defs.iter_mut() {
+            let owner = type_name(type_def).to_string();
+            let mut taken = BTreeSet::new();
+            match type_def {
+                TypeDefinition::Record(record) => {
+                    for (field, _) in &mut record.fields {
+                        let new_name = unique(field, &mut taken, true);
+                        if new_name != *field {
+                            let original = std::mem::replace(field, new_name.clone());
+                            map.record_field(&path, &owner, &original, &new_name);
+                        }
+                    }
+                }
+                TypeDefinition::Du(du) => {
+                    for variant in &mut du.variants {
+                        let new_name = unique(&variant.name, &mut taken, false);
+                        if new_name != variant.name {
+                            map.push(NameMapping {
+                                kind: NameKind::Case,
+                                module: path.clone(),
+                                owner: Some(owner.clone()),
+                                name: new_name.clone(),
+                                original: std::mem::replace(&mut variant.name, new_name),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if !renamed.is_empty() {
+        let all = types
+            .root_types
+            .iter_mut()
+            .chain(types.modules.iter_mut().flat_map(|m| m.types.iter_mut()));
+        for type_def in all {
+            match type_def {
+                TypeDefinition::Record(record) => {
+                    for (_, ty) in &mut record.fields {
+                        *ty = rename_expr(ty, &renamed);
+                    }
+                }
+                TypeDefinition::Du(du) => {
+                    for ty in du.variants.iter_mut().flat_map(|v| v.fields.iter_mut()) {
+                        *ty = rename_expr(ty, &renamed);
+                    }
+                }
+            }
+        }
+    }
+
+    (types, map)
+}
+
+/// `name` escaped if it is a reserved keyword
+pub fn escape_keyword(name: &str, escape: KeywordEscape) -> String {
+    match escape {
+        _ if !is_keyword(name) => name.to_string(),
+        KeywordEscape::Suffix => format!("{}_", name),
+        KeywordEscape::Backtick => format!("``{}``", name),
+    }
+}
+
+/// `name` with invalid characters replaced and keywords suffixed
+fn sanitize(name: &str) -> String {
+    let mut result: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '_' || c == '\'' { c } else { '_' })
+        .collect();
+    if !result.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+        result.insert(0, '_');
+    }
+    escape_keyword(&result, KeywordEscape::Suffix)
+}
+
+/// `name`, or `name` with the lowest numeric suffix from 2 that is not taken
+fn unique_name(name: &str, taken: &BTreeSet<String>) -> String {
+    if !taken.contains(name) {
+        return name.to_string();
+    }
+    (2..)
+        .map(|n| format!("{}{}", name, n))
+        .find(|candidate| !taken.contains(candidate))
+        .unwrap_or_default()
+}
+
+fn modules(types: &GeneratedTypes) -> impl Iterator<Item = (&[String], &Vec<TypeDefinition>)> {
+    std::iter::once((&[] as &[String], &types.root_types))
+        .chain(types.modules.iter().map(|m| (m.path.as_slice(), &m.types)))
+}
+
+fn type_name(type_def: &TypeDefinition) -> &str {
+    match type_def {
+        TypeDefinition::Record(record) => &record.name,
+        TypeDefinition::Du(du) => &du.name,
+    }
+}
+
+fn set_type_name(type_def: &mut TypeDefinition, name: String) {
+    match type_def {
+        TypeDefinition::Record(record) => record.name = name,
+        TypeDefinition::Du(du) => du.name = name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shape::TypeShape;
+    use fusabi_type_providers::{DuDef, GeneratedModule, RecordDef, VariantDef};
+
+    fn sample() -> GeneratedTypes {
+        let field = |name: &str, ty: &str| (name.to_string(), TypeShape::parse(ty).into());
+        let mut types = GeneratedTypes::new();
+        let mut module = GeneratedModule::new(vec!["Db".to_string()]);
+        module.types.push(TypeDefinition::Record(RecordDef {
+            name: "user-account".to_string(),
+            fields: vec![field("type", "string"), field("2fa", "bool"), field("type", "int")],
+        }));
+        module.types.push(TypeDefinition::Du(DuDef {
+            name: "ToolCall".to_string(),
+            variants: vec![
+                VariantDef::new("get-weather".to_string(), vec![TypeShape::named("user-account").into()]),
+                VariantDef::new_simple("match".to_string()),
+            ],
+        }));
+        module.types.push(TypeDefinition::Record(RecordDef {
+            name: "ToolCall".to_string(),
+            fields: vec![],
+        }));
+        types.modules.push(module);
+        types
+    }
+
+    #[test]
+    fn test_check_identifiers() {
+        let diagnostics = check_identifiers(&sample());
+        let found: Vec<(&str, &str)> = diagnostics
+            .iter()
+            .map(|d| (d.code.as_str(), d.location.as_deref().unwrap_or_default()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("invalid-identifier", "Db.user-account"),
+                ("reserved-keyword", "Db.user-account.type"),
+                ("invalid-identifier", "Db.user-account.2fa"),
+                ("duplicate-member", "Db.user-account.type"),
+                ("reserved-keyword", "Db.user-account.type"),
+                ("invalid-identifier", "Db.ToolCall.get-weather"),
+                ("reserved-keyword", "Db.ToolCall.match"),
+                ("duplicate-type", "Db.ToolCall"),
+            ]
+        );
+        assert_eq!(diagnostics[1].severity, crate::validate::Severity::Warning);
+        assert_eq!(diagnostics[5].severity, crate::validate::Severity::Error);
+    }
+
+    #[test]
+    fn test_fix_identifiers() {
+        let (types, names) = fix_identifiers(sample());
+        assert!(check_identifiers(&types).is_empty());
+
+        let path = ["Db".to_string()];
+        let TypeDefinition::Record(record) = &types.modules[0].types[0] else { panic!() };
+        assert_eq!(record.name, "user_account");
+        let fields: Vec<_> = record.fields.iter().map(|(f, _)| f.as_str()).collect();
+        assert_eq!(fields, ["type_", "_2fa", "type_2"]);
+        assert_eq!(names.original_field(&path, "user_account", "_2fa"), Some("2fa"));
+
+        let TypeDefinition::Du(du) = &types.modules[0].types[1] else { panic!() };
+        assert_eq!(du.variants[0].name, "get_weather");
+        assert_eq!(du.variants[0].fields[0].to_string(), "user_account");
+        assert_eq!(du.variants[1].name, "match_");
+        assert_eq!(names.original_type(&path, "ToolCall2"), Some("ToolCall"));
+    }
+
+    #[test]
+    fn test_escape_keywords() {
+        let (types, names) = escape_keywords(sample(), KeywordEscape::Suffix);
+        let TypeDefinition::Record(record) = &types.modules[0].types[0] else { panic!() };
+        assert_eq!(record.name, "user-account");
+        let fields: Vec<_> = record.fields.iter().map(|(f, _)| f.as_str()).collect();
+        assert_eq!(fields, ["type_", "2fa", "type_2"]);
+        let TypeDefinition::Du(du) = &types.modules[0].types[1] else { panic!() };
+        assert_eq!(du.variants[1].name, "match_");
+        assert_eq!(names.len(), 3);
+
+        let (types, names) = escape_keywords(sample(), KeywordEscape::Backtick);
+        let TypeDefinition::Record(record) = &types.modules[0].types[0] else { panic!() };
+        assert_eq!(record.fields[0].0, "type");
+        assert_eq!(names.entries()[0].kind, NameKind::Case);
+
+        assert_eq!(escape_keyword("match", KeywordEscape::Backtick), "``match``");
+        assert_eq!(escape_keyword("matches", KeywordEscape::Suffix), "matches");
+    }
+
+    #[test]
+    fn test_fix_from_params() {
+        assert!(!fix_from_params(&ProviderParams::default()).unwrap());
+        assert!(fix_from_params(&ProviderParams::default().with("fix_identifiers", "true")).unwrap());
+        assert!(fix_from_params(&ProviderParams::default().with("fix_identifiers", "yes")).is_err());
+
+        let params = ProviderParams::default().with("keyword_escape", "suffix");
+        assert_eq!(KeywordEscape::from_params(&params).unwrap(), Some(KeywordEscape::Suffix));
+        let params = ProviderParams::default().with("keyword_escape", "quote");
+        assert!(KeywordEscape::from_params(&params).is_err());
+    }
+}
@@ -0,0 +1,105 @@
+//! Lookup of generated types by name
+//!
+//! Generated field types refer to other types by bare name. Exporters and
+//! emitters use [`TypeIndex`] to find the definition a reference points at,
+//! preferring the referencing module, then the root, then any module.
+
+use crate::generics::base_name;
+use fusabi_type_providers::{GeneratedTypes, TypeDefinition};
+use std::collections::HashMap;
+
+/// A generated type together with its location
+pub(crate) struct IndexedType<'a> {
+    /// Module-qualified name (`Otel.Http.Client`)
+    pub key: String,
+    /// Path of the module that contains the type (empty for root types)
+    pub module: &'a [String],
+    /// The type definition
+    pub definition: &'a TypeDefinition,
+}
+
+/// Lookup table from names to generated types
+pub(crate) struct TypeIndex<'a> {
+    entries: Vec<IndexedType<'a>>,
+    by_key: HashMap<String, usize>,
+    by_name: HashMap<&'a str, Vec<usize>>,
+}
+
+impl<'a> TypeIndex<'a> {
+    pub fn new(types: &'a GeneratedTypes) -> Self {
+        let mut index = Self {
+            entries: Vec::new(),
+            by_key: HashMap::new(),
+            by_name: HashMap::new(),
+        };
+
+        for definition in &types.root_types {
+            index.insert(&[], definition);
+        }
+        for module in &types.modules {
+            for definition in &module.types {
+                index.insert(&module.path, definition);
+            }
+        }
+
+        index
+    }
+
+    fn insert(&mut self, module: &'a [String], definition: &'a TypeDefinition) {
+        let name = definition_name(definition);
+        let key = qualified_name(module, name);
+
+        // First definition wins when the same qualified name is generated twice
+        if self.by_key.contains_key(&key) {
+            return;
+        }
+
+        self.by_key.insert(key.clone(), self.entries.len());
+        self.by_name.entry(name).or_default().push(self.entries.len());
+        self.entries.push(IndexedType {
+            key,
+            module,
+            definition,
+        });
+    }
+
+    /// All indexed types in generation order
+    pub fn entries(&self) -> &[IndexedType<'a>] {
+        &self.entries
+    }
+
+    /// Resolve a type reference as seen from `module`
+    pub fn resolve(&self, name: &str, module: &[String]) -> Option<&IndexedType<'a>> {
+        // Qualified references
+        if let Some(&i) = self.by_key.get(name) {
+            return Some(&self.entries[i]);
+        }
+
+        let bare = name.rsplit('.').next().unwrap_or(name);
+        let candidates = self.by_name.get(bare)?;
+        let preferred = candidates
+            .iter()
+            .find(|&&i| self.entries[i].module == module)
+            .or_else(|| candidates.iter().find(|&&i| self.entries[i].module.is_empty()))
+            .or_else(|| candidates.first())?;
+
+        Some(&self.entries[*preferred])
+    }
+}
+
+/// Name of a type definition, without the parameters of a generic one
+pub(crate) fn definition_name(definition: &TypeDefinition) -> &str {
+    match definition {
+        TypeDefinition::Record(record) => base_name(&record.name),
+        TypeDefinition::Du(du) => base_name(&du.name),
+    }
+}
+
+/// Module-qualified name of a type
+pub(crate) fn qualified_name(module: &[String], name: &str) -> String {
+    if module.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", module.join("."), name)
+    }
+}
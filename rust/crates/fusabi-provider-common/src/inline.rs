@@ -0,0 +1,188 @@
+//! Named records for anonymous nested objects
+//!
+//! Fusabi records are always named, but sources are full of objects that
+//! are not: nested JSON Schema `object` properties, TOML tables inside
+//! arrays, GraphQL field arguments. Rather than degrading them to
+//! `Map<string, any>`, providers hoist each one to a named record with
+//! [`InlineRecords`] and reference it by name from the parent.
+//!
+//! Names are derived from where the object appears, so the same source
+//! always gives the same names:
+//!
+//! | Position                          | Name                  |
+//! |-----------------------------------|-----------------------|
+//! | field `location` of `WeatherInput` | `WeatherInputLocation` |
+//! | elements of list field `servers`  | `ConfigServersItem`   |
+//!
+//! Two different objects deriving the same name are told apart by a
+//! numeric suffix (`ConfigAB`, `ConfigAB2`) in the order they are hoisted;
+//! hoisting an object identical to one already hoisted under that name
+//! reuses the existing record.
+//!
+//! [`InlineRecords::with_max_depth`] bounds how deeply objects nest: a
+//! converter calls [`InlineRecords::enter`] before hoisting an object and
+//! keeps objects below the limit as maps instead.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! let mut inline = InlineRecords::new();
+//! inline.reserve("Config");
+//! let name = inline.hoist(&inline_name("Config", "database"), fields);
+//! module.types.extend(inline.into_definitions());
+//! ```
+
+use crate::naming::Naming;
+use fusabi_type_providers::{RecordDef, TypeDefinition, TypeExpr};
+use std::collections::HashSet;
+
+/// Name of the record hoisted from field `field` of type `parent`
+pub fn inline_name(parent: &str, field: &str) -> String {
+    format!("{}{}", parent, Naming::PascalCase.apply(field))
+}
+
+/// Name of the record hoisted from the elements of a list named `list`
+pub fn item_name(list: &str) -> String {
+    format!("{}Item", list)
+}
+
+/// Records hoisted out of type position, in the order they were hoisted
+#[derive(Debug, Default)]
+pub struct InlineRecords {
+    records: Vec<RecordDef>,
+    taken: HashSet<String>,
+    max_depth: Option<usize>,
+    depth: usize,
+}
+
+impl InlineRecords {
+    /// Create an empty set of hoisted records
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hoist objects nested at most `max_depth` levels below the type that
+    /// contains them
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Start converting the fields of an object about to be hoisted
+    ///
+    /// Returns `false`, and enters nothing, when the object is nested
+    /// deeper than the maximum depth; otherwise [`Self::leave`] must follow
+    /// once its fields are converted.
+    pub fn enter(&mut self) -> bool {
+        if self.max_depth.is_some_and(|max| self.depth >= max) {
+            return false;
+        }
+        self.depth += 1;
+        true
+    }
+
+    /// Finish converting the fields of an entered object
+    pub fn leave(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+
+    /// Keep `name` free, e.g. because the source defines a type with it
+    pub fn reserve(&mut self, name: impl Into<String>) {
+        self.taken.insert(name.into());
+    }
+
+    /// Hoist an object with `fields` to a record, returning the record's name
+    ///
+    /// The name is `name` unless another type already has it.
+    pub fn hoist(&mut self, name: &str, fields: Vec<(String, TypeExpr)>) -> String {
+        for n in 1.. {
+            let candidate = if n == 1 { name.to_string() } else { format!("{}{}", name, n) };
+            if let Some(existing) = self.records.iter().find(|r| r.name == candidate) {
+                if same_fields(&existing.fields, &fields) {
+                    return candidate;
+                }
+            } else if self.taken.insert(candidate.clone()) {
+                self.records.push(RecordDef {
+                    name: candidate.clone(),
+                    fields,
+                });
+                return candidate;
+            }
+        }
+        unreachable!("suffixes are unbounded")
+    }
+
+    /// Records hoisted so far
+    pub fn records(&self) -> &[RecordDef] {
+        &self.records
+    }
+
+    /// Whether nothing was hoisted
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Hoisted records as type definitions, in hoisting order
+    ///
+    /// A record is always hoisted after the records its fields reference,
+    /// so definitions come before their uses.
+    pub fn into_definitions(self) -> Vec<TypeDefinition> {
+        self.records.into_iter().map(TypeDefinition::Record).collect()
+    }
+}
+
+/// `TypeExpr` only carries its rendered form, so compare that
+fn same_fields(a: &[(String, TypeExpr)], b: &[(String, TypeExpr)]) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|((a_name, a_ty), (b_name, b_ty))| a_name == b_name && a_ty.to_string() == b_ty.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(names: &[&str]) -> Vec<(String, TypeExpr)> {
+        names
+            .iter()
+            .map(|name| (name.to_string(), TypeExpr::Named("string".to_string())))
+            .collect()
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(inline_name("WeatherInput", "location"), "WeatherInputLocation");
+        assert_eq!(inline_name("Config", "tls-options"), "ConfigTlsOptions");
+        assert_eq!(item_name(&inline_name("Config", "servers")), "ConfigServersItem");
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let mut inline = InlineRecords::new().with_max_depth(2);
+        assert!(inline.enter());
+        assert!(inline.enter());
+        assert!(!inline.enter());
+        inline.leave();
+        assert!(inline.enter());
+
+        let mut unlimited = InlineRecords::new();
+        assert!((0..100).all(|_| unlimited.enter()));
+    }
+
+    #[test]
+    fn test_hoist() {
+        let mut inline = InlineRecords::new();
+        inline.reserve("ConfigDatabase");
+
+        assert_eq!(inline.hoist("ConfigServer", fields(&["host"])), "ConfigServer");
+        assert_eq!(inline.hoist("ConfigServer", fields(&["host"])), "ConfigServer");
+        assert_eq!(inline.hoist("ConfigServer", fields(&["port"])), "ConfigServer2");
+        assert_eq!(inline.hoist("ConfigServer", fields(&["port"])), "ConfigServer2");
+        assert_eq!(inline.hoist("ConfigDatabase", fields(&["url"])), "ConfigDatabase2");
+
+        let names: Vec<&str> = inline.records().iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["ConfigServer", "ConfigServer2", "ConfigDatabase2"]);
+        assert_eq!(inline.into_definitions().len(), 3);
+    }
+}
@@ -0,0 +1,343 @@
+//! JSON interchange form of generated types
+//!
+//! `GeneratedTypes` and its definitions live upstream in
+//! `fusabi-type-providers` and carry no serde support, so build tooling
+//! outside Rust reads and writes them through [`TypesDocument`], a mirror
+//! with a stable, versioned JSON layout:
+//!
+//! ```json
+//! {
+//!   "interchange_version": 1,
+//!   "root_types": [],
+//!   "modules": [
+//!     {
+//!       "path": ["Db"],
+//!       "types": [
+//!         {
+//!           "kind": "record",
+//!           "name": "Users",
+//!           "fields": [
+//!             {
+//!               "name": "email",
+//!               "type": "string option",
+//!               "shape": { "kind": "option", "of": { "kind": "named", "of": "string" } }
+//!             }
+//!           ]
+//!         },
+//!         {
+//!           "kind": "du",
+//!           "name": "Status",
+//!           "variants": [
+//!             { "name": "Active", "fields": [] },
+//!             { "name": "Banned", "fields": [{ "type": "string", "shape": { "kind": "named", "of": "string" } }] }
+//!           ]
+//!         }
+//!       ]
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Every type expression appears twice: `type` is the rendered Fusabi
+//! syntax and `shape` the same expression as a [`TypeShape`] tree, tagged
+//! by `kind` (`named`, `option`, `list`, `map`, `tuple`, `literal`,
+//! `union`, `param`, `generic`) with its operands under `of`. Reading only
+//! uses `type`, so tools that rewrite types may drop `shape`.
+//!
+//! The layout only changes together with [`INTERCHANGE_VERSION`]; reading a
+//! document of another version fails.
+
+use crate::shape::TypeShape;
+use fusabi_type_providers::{
+    DuDef, GeneratedModule, GeneratedTypes, ProviderError, ProviderResult, RecordDef,
+    TypeDefinition, TypeExpr, VariantDef,
+};
+use serde::{Deserialize, Serialize};
+
+/// Version of the interchange JSON layout
+pub const INTERCHANGE_VERSION: u32 = 1;
+
+/// Generated types in interchange form
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypesDocument {
+    /// Interchange layout version
+    pub interchange_version: u32,
+    /// Types generated at the root level
+    #[serde(default)]
+    pub root_types: Vec<TypeDocument>,
+    /// Generated modules and their types
+    #[serde(default)]
+    pub modules: Vec<ModuleDocument>,
+}
+
+/// Generated module
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModuleDocument {
+    /// Module path segments
+    pub path: Vec<String>,
+    /// Types in the module
+    #[serde(default)]
+    pub types: Vec<TypeDocument>,
+}
+
+/// Generated type definition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum TypeDocument {
+    /// Record type
+    Record {
+        name: String,
+        #[serde(default)]
+        fields: Vec<FieldDocument>,
+    },
+    /// Discriminated union
+    Du {
+        name: String,
+        #[serde(default)]
+        variants: Vec<VariantDocument>,
+    },
+}
+
+/// Record field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldDocument {
+    /// Field name
+    pub name: String,
+    /// Field type
+    #[serde(flatten)]
+    pub expr: ExprDocument,
+}
+
+/// Union variant
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VariantDocument {
+    /// Variant name
+    pub name: String,
+    /// Types of the variant's fields; empty for simple variants
+    #[serde(default)]
+    pub fields: Vec<ExprDocument>,
+}
+
+/// Type expression
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExprDocument {
+    /// Rendered Fusabi type, e.g. `Map<string, int> option`
+    #[serde(rename = "type")]
+    pub rendered: String,
+    /// Structured form of the rendered type; ignored when reading
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shape: Option<TypeShape>,
+}
+
+impl TypesDocument {
+    /// Mirror generated types
+    pub fn new(types: &GeneratedTypes) -> Self {
+        Self {
+            interchange_version: INTERCHANGE_VERSION,
+            root_types: types.root_types.iter().map(TypeDocument::new).collect(),
+            modules: types
+                .modules
+                .iter()
+                .map(|module| ModuleDocument {
+                    path: module.path.clone(),
+                    types: module.types.iter().map(TypeDocument::new).collect(),
+                })
+                .collect(),
+        }
+    }
+
+    /// The generated types this document describes
+    pub fn into_types(self) -> GeneratedTypes {
+        let mut types = GeneratedTypes::new();
+        types.root_types = self.root_types.into_iter().map(TypeDocument::into_definition).collect();
+        for module in self.modules {
+            let mut generated = GeneratedModule::new(module.path);
+            generated.types = module.types.into_iter().map(TypeDocument::into_definition).collect();
+            types.modules.push(generated);
+        }
+        types
+    }
+
+    /// Serialize the document to pretty-printed JSON
+    pub fn to_json(&self) -> ProviderResult<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| ProviderError::ParseError(format!("Failed to serialize types: {}", e)))
+    }
+
+    /// Parse a document from JSON
+    pub fn from_json(json: &str) -> ProviderResult<Self> {
+        let document: Self = serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Invalid types JSON: {}", e)))?;
+
+        if document.interchange_version != INTERCHANGE_VERSION {
+            return Err(ProviderError::ParseError(format!(
+                "Unsupported interchange_version {}: expected {}",
+                document.interchange_version, INTERCHANGE_VERSION
+            )));
+        }
+        Ok(document)
+    }
+}
+
+impl TypeDocument {
+    /// Mirror a type definition
+    pub fn new(definition: &TypeDefinition) -> Self {
+        match definition {
+            TypeDefinition::Record(record) => TypeDocument::Record {
+                name: record.name.clone(),
+                fields: record
+                    .fields
+                    .iter()
+                    .map(|(name, expr)| FieldDocument {
+                        name: name.clone(),
+                        expr: ExprDocument::new(expr),
+                    })
+                    .collect(),
+            },
+            TypeDefinition::Du(du) => TypeDocument::Du {
+                name: du.name.clone(),
+                variants: du
+                    .variants
+                    .iter()
+                    .map(|variant| VariantDocument {
+                        name: variant.name.clone(),
+                        fields: variant.fields.iter().map(ExprDocument::new).collect(),
+                    })
+                    .collect(),
+            },
+        }
+    }
+
+    /// The type definition this document describes
+    pub fn into_definition(self) -> TypeDefinition {
+        match self {
+            TypeDocument::Record { name, fields } => TypeDefinition::Record(RecordDef {
+                name,
+                fields: fields
+                    .into_iter()
+                    .map(|field| (field.name, field.expr.into_type_expr()))
+                    .collect(),
+            }),
+            TypeDocument::Du { name, variants } => TypeDefinition::Du(DuDef {
+                name,
+                variants: variants
+                    .into_iter()
+                    .map(|variant| {
+                        let fields: Vec<TypeExpr> =
+                            variant.fields.into_iter().map(ExprDocument::into_type_expr).collect();
+                        if fields.is_empty() {
+                            VariantDef::new_simple(variant.name)
+                        } else {
+                            VariantDef::new(variant.name, fields)
+                        }
+                    })
+                    .collect(),
+            }),
+        }
+    }
+}
+
+impl ExprDocument {
+    /// Mirror a type expression with its structured form
+    pub fn new(expr: &TypeExpr) -> Self {
+        Self {
+            rendered: expr.to_string(),
+            shape: Some(TypeShape::from_type_expr(expr)),
+        }
+    }
+
+    fn into_type_expr(self) -> TypeExpr {
+        TypeExpr::Named(self.rendered)
+    }
+}
+
+/// Serialize generated types to interchange JSON
+pub fn types_to_json(types: &GeneratedTypes) -> ProviderResult<String> {
+    TypesDocument::new(types).to_json()
+}
+
+/// Parse generated types from interchange JSON
+pub fn types_from_json(json: &str) -> ProviderResult<GeneratedTypes> {
+    Ok(TypesDocument::from_json(json)?.into_types())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample() -> GeneratedTypes {
+        let mut module = GeneratedModule::new(vec!["Db".to_string()]);
+        module.types.push(TypeDefinition::Record(RecordDef {
+            name: "Users".to_string(),
+            fields: vec![
+                ("id".to_string(), TypeExpr::Named("int".to_string())),
+                (
+                    "tags".to_string(),
+                    TypeShape::map(TypeShape::named("string"), TypeShape::named("string")).option().into(),
+                ),
+            ],
+        }));
+        module.types.push(TypeDefinition::Du(DuDef {
+            name: "Status".to_string(),
+            variants: vec![
+                VariantDef::new_simple("Active".to_string()),
+                VariantDef::new("Banned".to_string(), vec![TypeExpr::Named("string".to_string())]),
+            ],
+        }));
+
+        let mut types = GeneratedTypes::new();
+        types.modules.push(module);
+        types
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let types = sample();
+        let parsed = types_from_json(&types_to_json(&types).unwrap()).unwrap();
+        assert_eq!(TypesDocument::new(&parsed), TypesDocument::new(&types));
+    }
+
+    #[test]
+    fn test_json_layout() {
+        let value = serde_json::to_value(TypesDocument::new(&sample())).unwrap();
+        assert_eq!(value["interchange_version"], 1);
+
+        let users = &value["modules"][0]["types"][0];
+        assert_eq!(users["kind"], "record");
+        assert_eq!(users["fields"][1]["type"], "Map<string, string> option");
+        assert_eq!(
+            users["fields"][1]["shape"],
+            json!({
+                "kind": "option",
+                "of": {
+                    "kind": "map",
+                    "of": [{ "kind": "named", "of": "string" }, { "kind": "named", "of": "string" }]
+                }
+            })
+        );
+
+        let status = &value["modules"][0]["types"][1];
+        assert_eq!(status["kind"], "du");
+        assert_eq!(status["variants"][0]["fields"], json!([]));
+        assert_eq!(status["variants"][1]["fields"][0]["type"], "string");
+    }
+
+    #[test]
+    fn test_reads_type_without_shape() {
+        let json = r#"{
+            "interchange_version": 1,
+            "modules": [{ "path": ["Api"], "types": [
+                { "kind": "record", "name": "User", "fields": [{ "name": "name", "type": "string" }] }
+            ] }]
+        }"#;
+        let types = types_from_json(json).unwrap();
+        let TypeDefinition::Record(user) = &types.modules[0].types[0] else {
+            panic!("Expected record");
+        };
+        assert_eq!(user.fields[0].1.to_string(), "string");
+
+        let newer = json.replace("\"interchange_version\": 1", "\"interchange_version\": 2");
+        assert!(types_from_json(&newer).unwrap_err().to_string().contains("interchange_version 2"));
+    }
+}
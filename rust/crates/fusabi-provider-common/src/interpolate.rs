@@ -0,0 +1,144 @@
+//! Environment variable interpolation in sources and params
+//!
+//! Schema locations and param values may reference environment variables,
+//! so deployment-specific URLs and settings stay out of checked-in config:
+//!
+//! - `${SCHEMA_REGISTRY_URL}/subjects/orders` - replaced by the variable
+//! - `$${LITERAL}` - an escaped `$`, giving `${LITERAL}`
+//! - `$HOME` - a `$` not followed by `{` is kept as is
+//!
+//! A referenced variable that is not set is an error rather than an empty
+//! string, so a missing deployment setting fails before any request is made.
+//! Errors name the variable and the param, never the interpolated value.
+//!
+//! [`SourceResolver`](crate::SourceResolver) interpolates the location and
+//! every param before reading, so all providers using [`read_source`](crate::read_source)
+//! behave the same way.
+
+use crate::error::{Context, StructuredError};
+use fusabi_type_providers::{ProviderParams, ProviderResult};
+
+/// Replace `${VAR}` references with values from the process environment
+pub fn interpolate_env(value: &str) -> ProviderResult<String> {
+    interpolate(value, |name| std::env::var(name).ok())
+}
+
+/// Replace `${VAR}` references with values from `lookup`
+pub fn interpolate(value: &str, lookup: impl Fn(&str) -> Option<String>) -> ProviderResult<String> {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(at) = rest.find('$') {
+        out.push_str(&rest[..at]);
+        let tail = &rest[at + 1..];
+
+        if let Some(tail) = tail.strip_prefix('$') {
+            out.push('$');
+            rest = tail;
+        } else if let Some(tail) = tail.strip_prefix('{') {
+            let end = tail.find('}').ok_or_else(|| {
+                StructuredError::validation(format!("Unterminated '${{' in '{}'", value))
+            })?;
+            let name = &tail[..end];
+            if !is_var_name(name) {
+                return Err(StructuredError::validation(format!(
+                    "Invalid environment variable name '{}' in '{}'",
+                    name, value
+                ))
+                .into());
+            }
+            let resolved = lookup(name).ok_or_else(|| {
+                StructuredError::validation(format!("Environment variable '{}' is not set", name))
+            })?;
+            out.push_str(&resolved);
+            rest = &tail[end + 1..];
+        } else {
+            out.push('$');
+            rest = tail;
+        }
+    }
+
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Interpolate every param value from the process environment
+pub fn interpolate_params(params: &ProviderParams) -> ProviderResult<ProviderParams> {
+    interpolate_params_with(params, |name| std::env::var(name).ok())
+}
+
+/// Interpolate every param value from `lookup`
+pub fn interpolate_params_with(
+    params: &ProviderParams,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> ProviderResult<ProviderParams> {
+    params
+        .custom
+        .iter()
+        .try_fold(ProviderParams::default(), |interpolated, (key, value)| {
+            let value = interpolate(value, &lookup).with_context(|| format!("param '{}'", key))?;
+            Ok(interpolated.with(key, &value))
+        })
+}
+
+/// `[A-Za-z_][A-Za-z0-9_]*`
+fn is_var_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "SCHEMA_REGISTRY_URL" => Some("https://registry.example.com".to_string()),
+            "TOKEN_NAME" => Some("ci".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_interpolate() {
+        assert_eq!(
+            interpolate("${SCHEMA_REGISTRY_URL}/subjects/orders", lookup).unwrap(),
+            "https://registry.example.com/subjects/orders"
+        );
+        assert_eq!(interpolate("env:${TOKEN_NAME}_TOKEN", lookup).unwrap(), "env:ci_TOKEN");
+        assert_eq!(interpolate("plain", lookup).unwrap(), "plain");
+    }
+
+    #[test]
+    fn test_escaping() {
+        assert_eq!(interpolate("$${SCHEMA_REGISTRY_URL}", lookup).unwrap(), "${SCHEMA_REGISTRY_URL}");
+        assert_eq!(interpolate("cost: $5 or $$", lookup).unwrap(), "cost: $5 or $");
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = interpolate("${MISSING}/x", lookup).unwrap_err().to_string();
+        assert!(err.contains("Environment variable 'MISSING' is not set"));
+
+        assert!(interpolate("${SCHEMA_REGISTRY_URL", lookup).is_err());
+        assert!(interpolate("${1BAD}", lookup).is_err());
+        assert!(interpolate("${}", lookup).is_err());
+    }
+
+    #[test]
+    fn test_interpolate_params() {
+        let params = ProviderParams::default()
+            .with("auth", "env:${TOKEN_NAME}_TOKEN")
+            .with("fetch_timeout", "5s");
+        let interpolated = interpolate_params_with(&params, lookup).unwrap();
+        assert_eq!(interpolated.custom.get("auth").unwrap(), "env:ci_TOKEN");
+        assert_eq!(interpolated.custom.get("fetch_timeout").unwrap(), "5s");
+
+        let params = ProviderParams::default().with("cache_dir", "${MISSING}");
+        let err = interpolate_params_with(&params, lookup).unwrap_err().to_string();
+        assert!(err.contains("param 'cache_dir': Environment variable 'MISSING' is not set"));
+    }
+}
@@ -0,0 +1,299 @@
+//! Export generated types as JSON Schema
+//!
+//! Converts any provider's [`GeneratedTypes`] into a JSON Schema (draft
+//! 2020-12) document so that types generated from SQL, Protobuf, TOML and
+//! friends can validate runtime payloads with standard tooling.
+//!
+//! Every generated type becomes an entry under `$defs`, keyed by its
+//! module-qualified name (`Otel.Http.Client`). Discriminated unions follow the
+//! externally tagged convention: simple variants are bare strings, variants
+//! with a single field are `{ "Variant": value }` and variants with several
+//! fields are `{ "Variant": [values...] }`. JSON Schema has no type
+//! parameters, so generic definitions accept any value where a field uses a
+//! parameter, and instantiations refer to the generic definition.
+
+use crate::index::TypeIndex;
+use crate::shape::TypeShape;
+use fusabi_type_providers::{DuDef, GeneratedTypes, RecordDef, TypeDefinition};
+use serde_json::{json, Map, Value};
+
+/// JSON Schema dialect emitted by the exporter
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Export all generated types as a single JSON Schema document
+///
+/// The returned document only contains `$defs`; use [`type_to_json_schema`]
+/// to get a document whose root validates a particular type.
+pub fn to_json_schema(types: &GeneratedTypes) -> Value {
+    let exporter = Exporter {
+        index: TypeIndex::new(types),
+    };
+    json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "$defs": exporter.definitions(),
+    })
+}
+
+/// Export a JSON Schema document whose root validates the named type
+///
+/// `type_name` may be a bare type name or a module-qualified name. Returns
+/// `None` if no such type was generated.
+pub fn type_to_json_schema(types: &GeneratedTypes, type_name: &str) -> Option<Value> {
+    let exporter = Exporter {
+        index: TypeIndex::new(types),
+    };
+    let key = &exporter.index.resolve(type_name, &[])?.key;
+    Some(json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "$ref": format!("#/$defs/{}", key),
+        "$defs": exporter.definitions(),
+    }))
+}
+
+/// Converts indexed types to JSON Schema
+struct Exporter<'a> {
+    index: TypeIndex<'a>,
+}
+
+impl Exporter<'_> {
+    fn definitions(&self) -> Map<String, Value> {
+        self.index
+            .entries()
+            .iter()
+            .map(|entry| {
+                let schema = match entry.definition {
+                    TypeDefinition::Record(record) => self.record_schema(record, entry.module),
+                    TypeDefinition::Du(du) => self.du_schema(du, entry.module),
+                };
+                (entry.key.clone(), schema)
+            })
+            .collect()
+    }
+
+    fn record_schema(&self, record: &RecordDef, module: &[String]) -> Value {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+
+        for (field_name, type_expr) in &record.fields {
+            let shape = TypeShape::from_type_expr(type_expr);
+            if !shape.is_option() {
+                required.push(Value::String(field_name.clone()));
+            }
+            properties.insert(field_name.clone(), self.shape_schema(&shape, module));
+        }
+
+        json!({
+            "title": record.name,
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false,
+        })
+    }
+
+    fn du_schema(&self, du: &DuDef, module: &[String]) -> Value {
+        if du.variants.iter().all(|v| v.fields.is_empty()) {
+            let names: Vec<&str> = du.variants.iter().map(|v| v.name.as_str()).collect();
+            return json!({
+                "title": du.name,
+                "type": "string",
+                "enum": names,
+            });
+        }
+
+        let variants: Vec<Value> = du
+            .variants
+            .iter()
+            .map(|variant| {
+                let payload = match variant.fields.as_slice() {
+                    [] => return json!({ "const": variant.name }),
+                    [single] => self.shape_schema(&TypeShape::from_type_expr(single), module),
+                    many => {
+                        let items: Vec<Value> = many
+                            .iter()
+                            .map(|f| self.shape_schema(&TypeShape::from_type_expr(f), module))
+                            .collect();
+                        json!({
+                            "type": "array",
+                            "prefixItems": items,
+                            "minItems": many.len(),
+                            "maxItems": many.len(),
+                        })
+                    }
+                };
+
+                json!({
+                    "type": "object",
+                    "properties": { variant.name.clone(): payload },
+                    "required": [variant.name],
+                    "additionalProperties": false,
+                })
+            })
+            .collect();
+
+        json!({
+            "title": du.name,
+            "oneOf": variants,
+        })
+    }
+
+    fn shape_schema(&self, shape: &TypeShape, module: &[String]) -> Value {
+        match shape {
+            TypeShape::Named(name) => self.named_schema(name, module),
+            TypeShape::Option(inner) => json!({
+                "anyOf": [self.shape_schema(inner, module), { "type": "null" }],
+            }),
+            TypeShape::List(inner) => json!({
+                "type": "array",
+                "items": self.shape_schema(inner, module),
+            }),
+            TypeShape::Map(_, value) => json!({
+                "type": "object",
+                "additionalProperties": self.shape_schema(value, module),
+            }),
+            TypeShape::Tuple(items) => {
+                let items: Vec<Value> = items.iter().map(|i| self.shape_schema(i, module)).collect();
+                let len = items.len();
+                json!({
+                    "type": "array",
+                    "prefixItems": items,
+                    "minItems": len,
+                    "maxItems": len,
+                })
+            }
+            TypeShape::Literal(text) => json!({ "const": text }),
+            TypeShape::Union(items) => {
+                let literals: Vec<&String> = items
+                    .iter()
+                    .filter_map(|i| match i {
+                        TypeShape::Literal(text) => Some(text),
+                        _ => None,
+                    })
+                    .collect();
+
+                if literals.len() == items.len() {
+                    json!({ "enum": literals })
+                } else {
+                    let items: Vec<Value> = items.iter().map(|i| self.shape_schema(i, module)).collect();
+                    json!({ "anyOf": items })
+                }
+            }
+            TypeShape::Param(_) => json!({}),
+            TypeShape::Generic(name, _) => self.named_schema(name, module),
+        }
+    }
+
+    fn named_schema(&self, name: &str, module: &[String]) -> Value {
+        match name {
+            "string" => json!({ "type": "string" }),
+            "int" | "int64" => json!({ "type": "integer" }),
+            "uint" | "uint64" => json!({ "type": "integer", "minimum": 0 }),
+            "float" => json!({ "type": "number" }),
+            "bool" => json!({ "type": "boolean" }),
+            "bytes" => json!({ "type": "string", "contentEncoding": "base64" }),
+            "unit" => json!({ "type": "null" }),
+            "any" => json!({}),
+            _ => match self.index.resolve(name, module) {
+                Some(entry) => json!({ "$ref": format!("#/$defs/{}", entry.key) }),
+                None => json!({ "$comment": format!("unresolved type {}", name) }),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_type_providers::{GeneratedModule, TypeExpr, VariantDef};
+
+    fn named(s: &str) -> TypeExpr {
+        TypeExpr::Named(s.to_string())
+    }
+
+    fn sample_types() -> GeneratedTypes {
+        let mut types = GeneratedTypes::new();
+        let mut module = GeneratedModule::new(vec!["Db".to_string()]);
+        module.types.push(TypeDefinition::Record(RecordDef {
+            name: "User".to_string(),
+            fields: vec![
+                ("id".to_string(), named("int")),
+                ("email".to_string(), named("string option")),
+                ("tags".to_string(), named("string list")),
+                ("status".to_string(), named("Status")),
+            ],
+        }));
+        module.types.push(TypeDefinition::Du(DuDef {
+            name: "Status".to_string(),
+            variants: vec![
+                VariantDef::new_simple("Active".to_string()),
+                VariantDef::new_simple("Inactive".to_string()),
+            ],
+        }));
+        types.modules.push(module);
+        types
+    }
+
+    #[test]
+    fn test_record_export() {
+        let schema = to_json_schema(&sample_types());
+        let user = &schema["$defs"]["Db.User"];
+
+        assert_eq!(user["type"], "object");
+        assert_eq!(user["required"], json!(["id", "tags", "status"]));
+        assert_eq!(user["properties"]["id"]["type"], "integer");
+        assert_eq!(user["properties"]["tags"]["items"]["type"], "string");
+        assert_eq!(user["properties"]["status"]["$ref"], "#/$defs/Db.Status");
+        assert_eq!(user["properties"]["email"]["anyOf"][1]["type"], "null");
+    }
+
+    #[test]
+    fn test_simple_du_exports_enum() {
+        let schema = to_json_schema(&sample_types());
+        assert_eq!(schema["$defs"]["Db.Status"]["enum"], json!(["Active", "Inactive"]));
+    }
+
+    #[test]
+    fn test_du_with_payload() {
+        let mut types = GeneratedTypes::new();
+        types.root_types.push(TypeDefinition::Du(DuDef {
+            name: "Shape".to_string(),
+            variants: vec![
+                VariantDef::new_simple("Empty".to_string()),
+                VariantDef::new("Circle".to_string(), vec![named("float")]),
+                VariantDef::new("Rect".to_string(), vec![named("float"), named("float")]),
+            ],
+        }));
+
+        let schema = to_json_schema(&types);
+        let variants = schema["$defs"]["Shape"]["oneOf"].as_array().unwrap();
+
+        assert_eq!(variants[0]["const"], "Empty");
+        assert_eq!(variants[1]["properties"]["Circle"]["type"], "number");
+        assert_eq!(variants[2]["properties"]["Rect"]["maxItems"], 2);
+    }
+
+    #[test]
+    fn test_type_to_json_schema() {
+        let schema = type_to_json_schema(&sample_types(), "User").unwrap();
+        assert_eq!(schema["$ref"], "#/$defs/Db.User");
+        assert!(type_to_json_schema(&sample_types(), "Missing").is_none());
+    }
+
+    #[test]
+    fn test_map_and_literal_union() {
+        let mut types = GeneratedTypes::new();
+        types.root_types.push(TypeDefinition::Record(RecordDef {
+            name: "Config".to_string(),
+            fields: vec![
+                ("labels".to_string(), named("Map<string, string>")),
+                ("units".to_string(), named("\"celsius\" | \"fahrenheit\"")),
+            ],
+        }));
+
+        let schema = to_json_schema(&types);
+        let config = &schema["$defs"]["Config"]["properties"];
+
+        assert_eq!(config["labels"]["additionalProperties"]["type"], "string");
+        assert_eq!(config["units"]["enum"], json!(["celsius", "fahrenheit"]));
+    }
+}
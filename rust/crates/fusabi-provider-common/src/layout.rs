@@ -0,0 +1,237 @@
+//! Flat or per-category module layout of generated types
+//!
+//! Providers group their output into modules by category: MCP tools,
+//! resources and prompts, Hibana metrics and logs sinks, SQL tables. The
+//! `module_layout` param picks how much of that grouping is kept:
+//!
+//! | `module_layout` | Types land in                                   |
+//! |-----------------|-------------------------------------------------|
+//! | `flat`          | one module named after the namespace             |
+//! | `nested`        | one module per category below the namespace     |
+//!
+//! Providers place types with a [`ModuleBuilder`], which maps categories to
+//! module paths for the chosen [`ModuleGrouping`], and implement
+//! [`GroupedProvider`]; [`generate_with_layout`] reads the param and
+//! generates accordingly. Without the param, each provider keeps its own
+//! default, the layout `generate_types` returns.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_common::layout::generate_with_layout;
+//! use fusabi_provider_sql::SqlProvider;
+//!
+//! let params = ProviderParams::default().with("module_layout", "nested");
+//! // One module per table: Db.Users, Db.Orders
+//! let types = generate_with_layout(&SqlProvider::new(), "schema.sql", &params, "Db")?;
+//! ```
+
+use fusabi_type_providers::{
+    GeneratedModule, GeneratedTypes, ProviderError, ProviderParams, ProviderResult, Schema,
+    TypeDefinition, TypeProvider,
+};
+
+/// Params key selecting the module layout
+pub const MODULE_LAYOUT_PARAM: &str = "module_layout";
+
+/// How generated types are grouped into modules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleGrouping {
+    /// Every type in the namespace module
+    Flat,
+    /// One module per category below the namespace module
+    Nested,
+}
+
+impl ModuleGrouping {
+    /// Parse a `module_layout` param value
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "flat" => Some(ModuleGrouping::Flat),
+            "nested" => Some(ModuleGrouping::Nested),
+            _ => None,
+        }
+    }
+
+    /// Read the `module_layout` param, if given
+    pub fn from_params(params: &ProviderParams) -> ProviderResult<Option<Self>> {
+        params
+            .custom
+            .get(MODULE_LAYOUT_PARAM)
+            .map(|value| {
+                Self::parse(value).ok_or_else(|| {
+                    ProviderError::InvalidSource(format!(
+                        "Invalid module_layout '{}': expected flat or nested",
+                        value
+                    ))
+                })
+            })
+            .transpose()
+    }
+}
+
+/// Collects generated types into modules by category
+///
+/// A category is a module path relative to the namespace, such as
+/// `["tools"]`; the empty category is the namespace module itself.
+#[derive(Debug)]
+pub struct ModuleBuilder {
+    namespace: String,
+    grouping: ModuleGrouping,
+    modules: Vec<GeneratedModule>,
+}
+
+impl ModuleBuilder {
+    /// Create a builder for modules below `namespace`
+    pub fn new(namespace: &str, grouping: ModuleGrouping) -> Self {
+        Self {
+            namespace: namespace.to_string(),
+            grouping,
+            modules: Vec::new(),
+        }
+    }
+
+    /// Grouping the builder lays modules out for
+    pub fn grouping(&self) -> ModuleGrouping {
+        self.grouping
+    }
+
+    /// Path of the module types of `category` land in, for keying docs,
+    /// defaults and attributes
+    pub fn path(&self, category: &[&str]) -> Vec<String> {
+        let mut path = vec![self.namespace.clone()];
+        if self.grouping == ModuleGrouping::Nested {
+            path.extend(category.iter().map(|segment| segment.to_string()));
+        }
+        path
+    }
+
+    /// Add a type of `category`
+    pub fn push(&mut self, category: &[&str], type_def: TypeDefinition) {
+        self.extend(category, [type_def]);
+    }
+
+    /// Add types of `category`
+    pub fn extend(&mut self, category: &[&str], types: impl IntoIterator<Item = TypeDefinition>) {
+        let path = self.path(category);
+        let index = match self.modules.iter().position(|m| m.path == path) {
+            Some(index) => index,
+            None => {
+                self.modules.push(GeneratedModule::new(path));
+                self.modules.len() - 1
+            }
+        };
+        self.modules[index].types.extend(types);
+    }
+
+    /// Modules in order of first use, skipping empty ones
+    ///
+    /// Fails when two categories put types with the same name in one
+    /// module, which only a flat layout can cause.
+    pub fn finish(self) -> ProviderResult<Vec<GeneratedModule>> {
+        for module in &self.modules {
+            let mut names: Vec<&str> = module.types.iter().map(type_name).collect();
+            names.sort_unstable();
+            if let Some(pair) = names.windows(2).find(|pair| pair[0] == pair[1]) {
+                return Err(ProviderError::InvalidSource(format!(
+                    "Type '{}' is generated more than once in module {}; use module_layout=nested",
+                    pair[0],
+                    module.path.join(".")
+                )));
+            }
+        }
+        Ok(self.modules.into_iter().filter(|m| !m.types.is_empty()).collect())
+    }
+}
+
+/// Type providers whose output can be laid out flat or per category
+pub trait GroupedProvider: TypeProvider {
+    /// Grouping of [`TypeProvider::generate_types`]
+    fn default_grouping(&self) -> ModuleGrouping;
+
+    /// Generate types grouped into modules by `grouping`
+    ///
+    /// With the default grouping, the types must be the same as
+    /// [`TypeProvider::generate_types`] returns for the same schema and
+    /// namespace.
+    fn generate_grouped(
+        &self,
+        schema: &Schema,
+        namespace: &str,
+        grouping: ModuleGrouping,
+    ) -> ProviderResult<GeneratedTypes>;
+}
+
+/// Resolve a source and generate types in the layout of the `module_layout`
+/// param
+pub fn generate_with_layout(
+    provider: &dyn GroupedProvider,
+    source: &str,
+    params: &ProviderParams,
+    namespace: &str,
+) -> ProviderResult<GeneratedTypes> {
+    let grouping = ModuleGrouping::from_params(params)?.unwrap_or_else(|| provider.default_grouping());
+    let schema = provider.resolve_schema(source, params)?;
+    provider.generate_grouped(&schema, namespace, grouping)
+}
+
+fn type_name(type_def: &TypeDefinition) -> &str {
+    match type_def {
+        TypeDefinition::Record(record) => &record.name,
+        TypeDefinition::Du(du) => &du.name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_type_providers::RecordDef;
+
+    fn record(name: &str) -> TypeDefinition {
+        TypeDefinition::Record(RecordDef {
+            name: name.to_string(),
+            fields: vec![],
+        })
+    }
+
+    #[test]
+    fn test_from_params() {
+        let params = ProviderParams::default().with(MODULE_LAYOUT_PARAM, "flat");
+        assert_eq!(ModuleGrouping::from_params(&params).unwrap(), Some(ModuleGrouping::Flat));
+        assert_eq!(ModuleGrouping::from_params(&ProviderParams::default()).unwrap(), None);
+
+        let params = ProviderParams::default().with(MODULE_LAYOUT_PARAM, "deep");
+        assert!(ModuleGrouping::from_params(&params).is_err());
+    }
+
+    #[test]
+    fn test_builder_layouts() {
+        let build = |grouping| {
+            let mut builder = ModuleBuilder::new("Mcp", grouping);
+            builder.push(&["tools"], record("SearchInput"));
+            builder.push(&["prompts"], record("SummarizeArgs"));
+            builder.push(&["tools"], record("FetchInput"));
+            builder.finish().unwrap()
+        };
+
+        let nested = build(ModuleGrouping::Nested);
+        assert_eq!(nested.len(), 2);
+        assert_eq!(nested[0].path, vec!["Mcp".to_string(), "tools".to_string()]);
+        assert_eq!(nested[0].types.len(), 2);
+        assert_eq!(nested[1].path, vec!["Mcp".to_string(), "prompts".to_string()]);
+
+        let flat = build(ModuleGrouping::Flat);
+        assert_eq!(flat.len(), 1);
+        assert_eq!(flat[0].path, vec!["Mcp".to_string()]);
+        assert_eq!(flat[0].types.len(), 3);
+    }
+
+    #[test]
+    fn test_flat_collision() {
+        let mut builder = ModuleBuilder::new("Hibana", ModuleGrouping::Flat);
+        builder.push(&["Metrics"], record("Http"));
+        builder.push(&["Logs"], record("Http"));
+        let err = builder.finish().unwrap_err().to_string();
+        assert!(err.contains("Type 'Http' is generated more than once in module Hibana"));
+    }
+}
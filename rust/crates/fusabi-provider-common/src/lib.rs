@@ -0,0 +1,153 @@
+//! Shared utilities for Fusabi community type providers
+//!
+//! Functionality that every community provider needs, but that does not
+//! belong in the core `fusabi-type-providers` crate, lives here.
+//!
+//! # Modules
+//!
+//! - [`manifest`] - machine-readable generation manifests
+//! - [`credentials`] - secret resolution for remote sources
+//! - [`source`] - source retrieval with timeouts and size limits
+//! - [`interpolate`] - `${VAR}` environment references in sources and params
+//! - [`limits`] - size, nesting and type count limits for untrusted sources
+//! - [`params`] - declared provider params with validation
+//! - [`capabilities`] - accepted sources, params and output features of a provider
+//! - [`cache`] - on-disk cache for remote sources
+//! - [`lock`] - schema lock files and drift detection
+//! - [`diff`] - structured differences between generated types
+//! - [`error`] - structured errors with kinds, context and causes
+//! - [`shape`] - structured view of type expressions
+//! - [`generics`] - type parameters of generic definitions
+//! - [`inline`] - named records for anonymous nested objects
+//! - [`naming`] - re-case generated names through the `naming` param
+//! - [`overrides`] - rename and exclude individual generated fields
+//! - [`identifiers`] - flag and fix duplicate, reserved and invalid names
+//! - [`layout`] - flat or per-category module layout through the `module_layout` param
+//! - [`transform`] - post-processing transforms: type prefixes, stripped and injected fields
+//! - [`merge`] - combine several providers' output with collision handling
+//! - [`json_schema`] - export generated types as JSON Schema
+//! - [`interchange`] - versioned JSON form of generated types for external tooling
+//! - [`reverse`] - export generated types as a provider's source format
+//! - [`docs`] - descriptions carried next to generated types
+//! - [`attributes`] - source metadata carried next to generated types
+//! - [`defaults`] - field default values carried next to generated types
+//! - [`emit`] - Fusabi, Rust and TypeScript source emitters, golden-file checks
+//! - [`decode`] - plans for decoding JSON, protobuf and row data into generated types
+//! - [`validate`] - dry-run source validation with structured diagnostics
+//! - [`span`] - line and column positions in parse errors
+//! - [`report`] - statistics and findings for a generation run
+//! - [`pack`] - assemble several providers' output into a versioned pack
+//! - [`batch`] - generate many sources in parallel with per-job results
+//! - [`telemetry`] - progress events, metrics and `tracing` spans for provider calls
+//! - [`watch`] - regenerate types when a source file changes
+//! - [`async_provider`] - async providers for network-backed sources
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_common::generate_with_manifest;
+//! use fusabi_provider_sql::SqlProvider;
+//! use fusabi_type_providers::ProviderParams;
+//!
+//! let provider = SqlProvider::new();
+//! let (types, manifest) = generate_with_manifest(
+//!     &provider,
+//!     "0.1.0",
+//!     "schema.sql",
+//!     &ProviderParams::default(),
+//!     "Database",
+//! )?;
+//! manifest.write_to("types.manifest.json")?;
+//! ```
+
+pub mod async_provider;
+pub mod attributes;
+pub mod batch;
+pub mod cache;
+pub mod capabilities;
+pub mod credentials;
+pub mod decode;
+pub mod defaults;
+pub mod diff;
+pub mod docs;
+pub mod emit;
+pub mod error;
+pub mod generics;
+pub mod identifiers;
+mod index;
+pub mod inline;
+pub mod interchange;
+pub mod interpolate;
+pub mod json_schema;
+pub mod layout;
+pub mod limits;
+pub mod lock;
+pub mod manifest;
+pub mod merge;
+pub mod naming;
+pub mod overrides;
+pub mod pack;
+pub mod params;
+pub mod report;
+pub mod reverse;
+pub mod shape;
+pub mod source;
+pub mod span;
+pub mod telemetry;
+pub mod transform;
+pub mod validate;
+pub mod watch;
+
+pub use async_provider::{block_on, AsyncTypeProvider, BlockingProvider, BoxFuture};
+pub use attributes::{AttributeEntry, AttributedProvider, TypeAttributes};
+pub use batch::{Batch, BatchJob, BatchReport, JobOutcome};
+pub use cache::{request_key, CacheOptions, RefreshPolicy, SchemaCache};
+pub use capabilities::{Capabilities, DescribedProvider, OutputFeature, SourceKind};
+pub use credentials::{AuthScheme, Credential, CredentialResolver, CredentialSource};
+pub use decode::{
+    DecoderCodegen, DecoderPlan, FieldCodec, FieldDecoder, ProtoEncoding, TypeDecoder, WireFormat,
+};
+pub use defaults::{DefaultValue, DefaultedProvider, FieldDefaults};
+pub use diff::{diff_generated, ChangeKind, MemberDiff, TypeDiff, TypesDiff};
+pub use docs::{DocumentedProvider, TypeDocs};
+pub use emit::{
+    emit_fusabi, emit_fusabi_documented, emit_fusabi_with_defaults, emit_rust, emit_rust_decoders,
+    emit_typescript, Backend, FusabiOptions, ModuleLayout, RustOptions, TypeScriptOptions,
+};
+pub use error::{Context, ErrorKind, StructuredError};
+pub use generics::{base_name, GenericName};
+pub use identifiers::{
+    check_identifiers, escape_keyword, escape_keywords, fix_identifiers, KeywordEscape, FUSABI_KEYWORDS,
+};
+pub use inline::{inline_name, item_name, InlineRecords};
+pub use interchange::{
+    types_from_json, types_to_json, ExprDocument, FieldDocument, ModuleDocument, TypeDocument,
+    TypesDocument, VariantDocument, INTERCHANGE_VERSION,
+};
+pub use interpolate::{interpolate, interpolate_env, interpolate_params, interpolate_params_with};
+pub use json_schema::{to_json_schema, type_to_json_schema};
+pub use layout::{generate_with_layout, GroupedProvider, ModuleBuilder, ModuleGrouping};
+pub use limits::InputLimits;
+pub use lock::{check_drift, diff_types, DriftReport, LockedSchema, SchemaLock, TypeChange, LOCK_VERSION};
+pub use manifest::{
+    generate_with_manifest, schema_digest, GenerationManifest, ManifestModule, ManifestType,
+    ManifestTypeKind, ProviderInfo, SourceDigest, MANIFEST_VERSION,
+};
+pub use merge::{CollisionPolicy, MergeReport, MergeTypes};
+pub use naming::{
+    apply_naming, generate_with_naming, rename_with_params, NameKind, NameMap, NameMapping, Naming,
+};
+pub use overrides::FieldOverrides;
+pub use pack::{PackArtifact, PackBuilder, PackInfo, PackSource, PackSpec};
+pub use params::{ParamKind, ParamSchema, ParamSpec, ParameterizedProvider, TypedParams};
+pub use report::{FindingKind, GenerationReport, ModuleStats};
+pub use reverse::SchemaExporter;
+pub use shape::{ListSyntax, TypeShape};
+pub use source::{read_binary_source, read_source, SourceOptions, SourceResolver};
+pub use span::{parse_error_at, slice_offset, span_of, SourceSpan};
+pub use telemetry::{Instrumented, PipelineMetrics, ProviderEvent, ProviderMetrics, ProviderObserver};
+pub use transform::{InjectFields, PrefixTypes, StripFields, Transform, TransformChain};
+pub use validate::{
+    check_generated_types, Diagnostic, Severity, SourceValidator, ValidationReport,
+};
+pub use watch::{SourceWatcher, WatchEvent, WatchOptions};
@@ -0,0 +1,229 @@
+//! Limits for untrusted sources
+//!
+//! Hosts that generate types from user-submitted schemas bound the work a
+//! single source can cause. [`read_source`] already rejects large files and
+//! responses; parsers check inline sources, nesting and the number of
+//! declared types against the same [`InputLimits`], read from these params:
+//!
+//! | Param              | Default | Meaning                                      |
+//! |--------------------|---------|----------------------------------------------|
+//! | `max_source_bytes` | 16 MiB  | Larger sources are rejected                  |
+//! | `max_depth`        | `64`    | Deepest nesting of objects, messages or `(`  |
+//! | `max_types`        | `10000` | Most types a source may declare              |
+//!
+//! Exceeding a limit is an [`ErrorKind::Validation`] error naming the param,
+//! so CI can tell a hostile or oversized schema from a malformed one.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_common::limits::InputLimits;
+//!
+//! let limits = InputLimits::from_params(params)?;
+//! limits.check_size(&content)?;
+//! let value: serde_json::Value = serde_json::from_str(&content)?;
+//! limits.check_json_depth(&value)?;
+//! ```
+//!
+//! [`read_source`]: crate::source::read_source
+//! [`ErrorKind::Validation`]: crate::error::ErrorKind::Validation
+
+use crate::error::StructuredError;
+use crate::source::{invalid_param, max_source_bytes_param, DEFAULT_MAX_SOURCE_BYTES};
+use crate::span::SourceSpan;
+use fusabi_type_providers::{GeneratedTypes, ProviderParams, ProviderResult};
+use serde_json::Value;
+
+/// Params key for the deepest nesting a source may use
+pub const MAX_DEPTH_PARAM: &str = "max_depth";
+
+/// Params key for the most types a source may declare
+pub const MAX_TYPES_PARAM: &str = "max_types";
+
+/// Default deepest nesting
+pub const DEFAULT_MAX_DEPTH: usize = 64;
+
+/// Default most declared types
+pub const DEFAULT_MAX_TYPES: usize = 10_000;
+
+/// Bounds on the size and shape of a source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputLimits {
+    /// Maximum size of a source in bytes
+    pub max_source_bytes: u64,
+    /// Deepest nesting of objects, messages or parentheses
+    pub max_depth: usize,
+    /// Most types a source may declare
+    pub max_types: usize,
+}
+
+impl Default for InputLimits {
+    fn default() -> Self {
+        Self {
+            max_source_bytes: DEFAULT_MAX_SOURCE_BYTES,
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_types: DEFAULT_MAX_TYPES,
+        }
+    }
+}
+
+impl InputLimits {
+    /// No limits, for re-parsing a source already checked at resolve time
+    pub fn unlimited() -> Self {
+        Self {
+            max_source_bytes: u64::MAX,
+            max_depth: usize::MAX,
+            max_types: usize::MAX,
+        }
+    }
+
+    /// Read limits from params, falling back to defaults
+    pub fn from_params(params: &ProviderParams) -> ProviderResult<Self> {
+        let mut limits = Self::default();
+        let count = |key: &str, value: &str| {
+            value
+                .trim()
+                .parse()
+                .map_err(|_| invalid_param(key, value, "a non-negative integer"))
+        };
+
+        if let Some(max_source_bytes) = max_source_bytes_param(params)? {
+            limits.max_source_bytes = max_source_bytes;
+        }
+        if let Some(value) = params.custom.get(MAX_DEPTH_PARAM) {
+            limits.max_depth = count(MAX_DEPTH_PARAM, value)?;
+        }
+        if let Some(value) = params.custom.get(MAX_TYPES_PARAM) {
+            limits.max_types = count(MAX_TYPES_PARAM, value)?;
+        }
+        Ok(limits)
+    }
+
+    /// Reject a source larger than `max_source_bytes`
+    ///
+    /// Files and URLs are checked by `read_source` already; this covers
+    /// inline sources.
+    pub fn check_size(&self, source: &str) -> ProviderResult<()> {
+        if source.len() as u64 > self.max_source_bytes {
+            return Err(StructuredError::validation(format!(
+                "Source is {} bytes, more than max_source_bytes ({} bytes)",
+                source.len(),
+                self.max_source_bytes
+            ))
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Reject nesting deeper than `max_depth`
+    ///
+    /// Recursive parsers call this with their depth on the way down.
+    pub fn check_depth(&self, depth: usize) -> ProviderResult<()> {
+        if depth > self.max_depth {
+            return Err(too_deep(self.max_depth).into());
+        }
+        Ok(())
+    }
+
+    /// Reject nesting of `open` and `close` deeper than `max_depth`
+    ///
+    /// Brackets in `'...'` and `"..."` literals are skipped. The error
+    /// gives the position of the bracket past the limit.
+    pub fn check_nesting(&self, source: &str, open: char, close: char) -> ProviderResult<()> {
+        let mut depth = 0usize;
+        let mut quote = None;
+        let mut escaped = false;
+        for (offset, c) in source.char_indices() {
+            if let Some(q) = quote {
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == q {
+                    quote = None;
+                }
+            } else if c == '\'' || c == '"' {
+                quote = Some(c);
+            } else if c == open {
+                depth += 1;
+                if depth > self.max_depth {
+                    let span = SourceSpan::locate(source, offset);
+                    return Err(too_deep(self.max_depth).context(format_args!("at line {}", span)).into());
+                }
+            } else if c == close {
+                depth = depth.saturating_sub(1);
+            }
+        }
+        Ok(())
+    }
+
+    /// Reject a JSON value nested deeper than `max_depth`
+    ///
+    /// The walk is iterative, so it is safe on values of any depth.
+    pub fn check_json_depth(&self, value: &Value) -> ProviderResult<()> {
+        let mut stack = vec![(value, 0usize)];
+        while let Some((value, depth)) = stack.pop() {
+            let children: Box<dyn Iterator<Item = &Value>> = match value {
+                Value::Array(items) => Box::new(items.iter()),
+                Value::Object(fields) => Box::new(fields.values()),
+                _ => continue,
+            };
+            self.check_depth(depth + 1)?;
+            stack.extend(children.map(|child| (child, depth + 1)));
+        }
+        Ok(())
+    }
+
+    /// Reject a source declaring more than `max_types` types
+    pub fn check_type_count(&self, count: usize) -> ProviderResult<()> {
+        if count > self.max_types {
+            return Err(StructuredError::validation(format!(
+                "Source declares {} types, more than max_types ({})",
+                count, self.max_types
+            ))
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Reject generated output with more than `max_types` types
+    pub fn check_types(&self, types: &GeneratedTypes) -> ProviderResult<()> {
+        self.check_type_count(types.root_types.len() + types.modules.iter().map(|m| m.types.len()).sum::<usize>())
+    }
+}
+
+fn too_deep(max_depth: usize) -> StructuredError {
+    StructuredError::validation(format!("Nesting is deeper than max_depth ({})", max_depth))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+
+    #[test]
+    fn test_input_limits() {
+        let params = ProviderParams::default().with("max_depth", "3").with("max_types", "2");
+        let limits = InputLimits::from_params(&params).unwrap();
+        assert_eq!(limits.max_source_bytes, DEFAULT_MAX_SOURCE_BYTES);
+
+        assert!(limits.check_nesting("a (b (c (d)))", '(', ')').is_ok());
+        assert!(limits.check_nesting("a (b (c ')))(((' (d)))", '(', ')').is_ok());
+        let error = limits.check_nesting("x\n((((y))))", '(', ')').unwrap_err();
+        assert_eq!(ErrorKind::of(&error), ErrorKind::Validation);
+        assert!(error.to_string().contains("at line 2, column 4: Nesting is deeper than max_depth (3)"));
+
+        let value: Value = serde_json::from_str(r#"{"a": [{"b": 1}], "c": 2}"#).unwrap();
+        assert!(limits.check_json_depth(&value).is_ok());
+        let value: Value = serde_json::from_str(r#"{"a": [{"b": [1]}]}"#).unwrap();
+        assert!(limits.check_json_depth(&value).is_err());
+
+        assert!(limits.check_type_count(2).is_ok());
+        assert!(limits.check_type_count(3).unwrap_err().to_string().contains("max_types (2)"));
+
+        let small = InputLimits { max_source_bytes: 4, ..limits };
+        assert!(small.check_size("abcd").is_ok());
+        assert!(small.check_size("abcde").is_err());
+        assert!(InputLimits::from_params(&ProviderParams::default().with("max_depth", "deep")).is_err());
+    }
+}
@@ -0,0 +1,329 @@
+//! Schema lock files and drift detection
+//!
+//! A lock pins the schema a set of types was generated from: the provider,
+//! the source, a digest and the normalized schema itself. Checking it in
+//! next to the generated types lets CI notice when an upstream schema
+//! changes without anyone regenerating.
+//!
+//! [`check_drift`] resolves the live source again and, when the digest no
+//! longer matches, generates types from both the locked and the live schema
+//! and reports which types were added, removed or changed.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_common::lock::{check_drift, SchemaLock};
+//!
+//! let lock = SchemaLock::resolve(&provider, "https://example.com/api.json", &params, "Api")?;
+//! lock.write_to("api.lock.json")?;
+//!
+//! let drift = check_drift(&provider, &SchemaLock::read_from("api.lock.json")?, &params)?;
+//! for change in &drift.changed {
+//!     eprintln!("{} changed: {:?}", change.name, change.members_changed);
+//! }
+//! ```
+
+use crate::diff::diff_generated;
+use crate::manifest::schema_digest;
+use fusabi_type_providers::{
+    GeneratedTypes, ProviderError, ProviderParams, ProviderResult, Schema, TypeProvider,
+};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Version of the lock JSON layout
+pub const LOCK_VERSION: u32 = 1;
+
+/// Snapshot of a resolved schema
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaLock {
+    /// Lock layout version
+    pub lock_version: u32,
+    /// Provider name as reported by `TypeProvider::name`
+    pub provider: String,
+    /// Source specifier the schema was resolved from
+    pub source: String,
+    /// Namespace the types are generated into
+    pub namespace: String,
+    /// Hex-encoded SHA-256 of the normalized schema
+    pub sha256: String,
+    /// Normalized schema
+    pub schema: LockedSchema,
+}
+
+/// Normalized schema content
+///
+/// Text schemas have their line endings normalized; JSON schemas are
+/// stored with sorted keys.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "content", rename_all = "snake_case")]
+pub enum LockedSchema {
+    /// JSON Schema document
+    JsonSchema(serde_json::Value),
+    /// OpenAPI document
+    OpenApi(serde_json::Value),
+    /// Provider-specific text
+    Custom(String),
+}
+
+impl LockedSchema {
+    /// Normalize a resolved schema
+    pub fn normalize(schema: &Schema) -> Self {
+        match schema {
+            Schema::JsonSchema(value) => Self::JsonSchema(value.clone()),
+            Schema::OpenApi(value) => Self::OpenApi(value.clone()),
+            Schema::Custom(content) => Self::Custom(content.replace("\r\n", "\n")),
+        }
+    }
+
+    /// The schema in the form providers consume
+    pub fn to_schema(&self) -> Schema {
+        match self {
+            Self::JsonSchema(value) => Schema::JsonSchema(value.clone()),
+            Self::OpenApi(value) => Schema::OpenApi(value.clone()),
+            Self::Custom(content) => Schema::Custom(content.clone()),
+        }
+    }
+
+    /// Hex-encoded SHA-256 of the normalized content
+    pub fn digest(&self) -> String {
+        schema_digest(&self.to_schema())
+    }
+}
+
+impl SchemaLock {
+    /// Lock an already resolved schema
+    pub fn from_schema(provider: &str, source: &str, namespace: &str, schema: &Schema) -> Self {
+        let schema = LockedSchema::normalize(schema);
+        Self {
+            lock_version: LOCK_VERSION,
+            provider: provider.to_string(),
+            source: source.to_string(),
+            namespace: namespace.to_string(),
+            sha256: schema.digest(),
+            schema,
+        }
+    }
+
+    /// Resolve a source and lock the result
+    pub fn resolve(
+        provider: &dyn TypeProvider,
+        source: &str,
+        params: &ProviderParams,
+        namespace: &str,
+    ) -> ProviderResult<Self> {
+        let schema = provider.resolve_schema(source, params)?;
+        Ok(Self::from_schema(provider.name(), source, namespace, &schema))
+    }
+
+    /// Serialize the lock to pretty-printed JSON
+    pub fn to_json(&self) -> ProviderResult<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| ProviderError::ParseError(format!("Failed to serialize lock: {}", e)))
+    }
+
+    /// Parse a lock from JSON
+    pub fn from_json(json: &str) -> ProviderResult<Self> {
+        let lock: Self = serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Invalid lock JSON: {}", e)))?;
+
+        if lock.schema.digest() != lock.sha256 {
+            return Err(ProviderError::ParseError(
+                "Lock schema does not match its sha256".to_string(),
+            ));
+        }
+        Ok(lock)
+    }
+
+    /// Write the lock as JSON to a file
+    pub fn write_to(&self, path: impl AsRef<Path>) -> ProviderResult<()> {
+        std::fs::write(path, self.to_json()?).map_err(|e| ProviderError::IoError(e.to_string()))
+    }
+
+    /// Read a lock file
+    pub fn read_from(path: impl AsRef<Path>) -> ProviderResult<Self> {
+        let json = std::fs::read_to_string(path).map_err(|e| ProviderError::IoError(e.to_string()))?;
+        Self::from_json(&json)
+    }
+}
+
+/// Difference between a lock and the live source
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DriftReport {
+    /// Digest recorded in the lock
+    pub locked_sha256: String,
+    /// Digest of the live schema
+    pub live_sha256: String,
+    /// Types only in the live schema, qualified by module path
+    pub added: Vec<String>,
+    /// Types only in the locked schema
+    pub removed: Vec<String>,
+    /// Types present in both with different members
+    pub changed: Vec<TypeChange>,
+}
+
+impl DriftReport {
+    /// Whether the live schema differs from the lock at all
+    pub fn schema_changed(&self) -> bool {
+        self.locked_sha256 != self.live_sha256
+    }
+
+    /// Whether the generated types differ
+    ///
+    /// A schema can change without its types changing, e.g. when only
+    /// comments were edited.
+    pub fn types_changed(&self) -> bool {
+        !self.added.is_empty() || !self.removed.is_empty() || !self.changed.is_empty()
+    }
+}
+
+/// Member-level changes to a type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypeChange {
+    /// Type name qualified by module path
+    pub name: String,
+    /// Whether the type switched between record and union
+    pub kind_changed: bool,
+    /// Fields or variants only in the live type
+    pub members_added: Vec<String>,
+    /// Fields or variants only in the locked type
+    pub members_removed: Vec<String>,
+    /// Fields or variants whose type changed
+    pub members_changed: Vec<String>,
+}
+
+/// Compare the live source of a lock against the lock
+///
+/// Types are only generated when the digests differ.
+pub fn check_drift(
+    provider: &dyn TypeProvider,
+    lock: &SchemaLock,
+    params: &ProviderParams,
+) -> ProviderResult<DriftReport> {
+    let live = LockedSchema::normalize(&provider.resolve_schema(&lock.source, params)?);
+    let mut report = DriftReport {
+        locked_sha256: lock.sha256.clone(),
+        live_sha256: live.digest(),
+        ..Default::default()
+    };
+
+    if report.schema_changed() {
+        let locked_types = provider.generate_types(&lock.schema.to_schema(), &lock.namespace)?;
+        let live_types = provider.generate_types(&live.to_schema(), &lock.namespace)?;
+        let diff = diff_types(&locked_types, &live_types);
+        report.added = diff.added;
+        report.removed = diff.removed;
+        report.changed = diff.changed;
+    }
+
+    Ok(report)
+}
+
+/// Compare two sets of generated types by qualified name
+///
+/// The digests of the returned report are left empty; use
+/// [`diff_generated`] for the member types behind each change.
+pub fn diff_types(old: &GeneratedTypes, new: &GeneratedTypes) -> DriftReport {
+    diff_generated(old, new).to_drift_report()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_type_providers::{GeneratedModule, RecordDef, TypeDefinition, TypeExpr};
+    use std::sync::Mutex;
+
+    /// Serves the text it holds; each line is `Type field:type ...`
+    struct LineProvider {
+        live: Mutex<String>,
+    }
+
+    impl LineProvider {
+        fn new(live: &str) -> Self {
+            Self {
+                live: Mutex::new(live.to_string()),
+            }
+        }
+    }
+
+    impl TypeProvider for LineProvider {
+        fn name(&self) -> &str {
+            "LineProvider"
+        }
+
+        fn resolve_schema(&self, _source: &str, _params: &ProviderParams) -> ProviderResult<Schema> {
+            Ok(Schema::Custom(self.live.lock().unwrap().clone()))
+        }
+
+        fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+            let Schema::Custom(text) = schema else {
+                return Err(ProviderError::ParseError("Expected text".to_string()));
+            };
+
+            let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+            for line in text.lines().filter(|l| !l.starts_with('#')) {
+                let mut words = line.split_whitespace();
+                let Some(name) = words.next() else { continue };
+                module.types.push(TypeDefinition::Record(RecordDef {
+                    name: name.to_string(),
+                    fields: words
+                        .filter_map(|w| w.split_once(':'))
+                        .map(|(f, t)| (f.to_string(), TypeExpr::Named(t.to_string())))
+                        .collect(),
+                }));
+            }
+
+            let mut types = GeneratedTypes::new();
+            types.modules.push(module);
+            Ok(types)
+        }
+    }
+
+    #[test]
+    fn test_lock_roundtrip() {
+        let provider = LineProvider::new("User id:int\r\nPost id:int\r\n");
+        let lock = SchemaLock::resolve(&provider, "schema.txt", &ProviderParams::default(), "Db")
+            .unwrap();
+
+        assert_eq!(lock.schema, LockedSchema::Custom("User id:int\nPost id:int\n".to_string()));
+        assert_eq!(lock.sha256.len(), 64);
+
+        let json = lock.to_json().unwrap();
+        assert!(json.contains("\"kind\": \"custom\""));
+        assert_eq!(SchemaLock::from_json(&json).unwrap(), lock);
+
+        let tampered = json.replace("Post id:int", "Post id:string");
+        assert!(SchemaLock::from_json(&tampered).is_err());
+    }
+
+    #[test]
+    fn test_check_drift() {
+        let provider = LineProvider::new("User id:int name:string\nPost id:int\nTag id:int\n");
+        let params = ProviderParams::default();
+        let lock = SchemaLock::resolve(&provider, "schema.txt", &params, "Db").unwrap();
+
+        let report = check_drift(&provider, &lock, &params).unwrap();
+        assert!(!report.schema_changed());
+        assert!(!report.types_changed());
+
+        *provider.live.lock().unwrap() = "# users\nUser id:int name:string\nPost id:int\nTag id:int\n".to_string();
+        let report = check_drift(&provider, &lock, &params).unwrap();
+        assert!(report.schema_changed());
+        assert!(!report.types_changed());
+
+        *provider.live.lock().unwrap() = "User id:int64 email:string\nPost id:int\nComment id:int\n".to_string();
+        let report = check_drift(&provider, &lock, &params).unwrap();
+        assert_eq!(report.added, vec!["Db.Comment"]);
+        assert_eq!(report.removed, vec!["Db.Tag"]);
+        assert_eq!(
+            report.changed,
+            vec![TypeChange {
+                name: "Db.User".to_string(),
+                kind_changed: false,
+                members_added: vec!["email".to_string()],
+                members_removed: vec!["name".to_string()],
+                members_changed: vec!["id".to_string()],
+            }]
+        );
+    }
+}
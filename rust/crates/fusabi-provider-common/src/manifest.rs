@@ -0,0 +1,343 @@
+//! Generation manifests
+//!
+//! A manifest records what a set of generated types was built from: the
+//! provider that produced it, a digest of every resolved source, the modules
+//! and types that came out, and anything the provider could not map.
+//! Registries and CI archive it next to a pack to audit its provenance.
+
+use crate::naming::{rename_with_params, NameMapping};
+use fusabi_type_providers::{
+    GeneratedTypes, ProviderError, ProviderParams, ProviderResult, Schema, TypeDefinition,
+    TypeProvider,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+/// Version of the manifest JSON layout
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// Sources longer than this are recorded as inline content
+const MAX_RECORDED_SOURCE_LEN: usize = 256;
+
+/// Machine-readable record of a single generation run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenerationManifest {
+    /// Manifest layout version
+    pub manifest_version: u32,
+    /// Provider that produced the types
+    pub provider: ProviderInfo,
+    /// Namespace the types were generated into
+    pub namespace: String,
+    /// Digests of every resolved source
+    pub sources: Vec<SourceDigest>,
+    /// Types generated at the root level
+    pub root_types: Vec<ManifestType>,
+    /// Generated modules and their types
+    pub modules: Vec<ManifestModule>,
+    /// Warnings raised during generation
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    /// Source constructs the provider could not map to Fusabi types
+    #[serde(default)]
+    pub unmapped: Vec<String>,
+    /// Names changed by the `naming` param, with the names they replaced
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub names: Vec<NameMapping>,
+}
+
+/// Provider identity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProviderInfo {
+    /// Provider name as reported by `TypeProvider::name`
+    pub name: String,
+    /// Provider crate version
+    pub version: String,
+}
+
+/// Digest of a resolved source
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceDigest {
+    /// Source specifier (path or URL), or `<inline>` for inline content
+    pub source: String,
+    /// Hex-encoded SHA-256 of the resolved schema content
+    pub sha256: String,
+}
+
+/// Generated module entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestModule {
+    /// Module path segments
+    pub path: Vec<String>,
+    /// Types in the module
+    pub types: Vec<ManifestType>,
+}
+
+/// Generated type entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestType {
+    /// Type name
+    pub name: String,
+    /// Type kind
+    pub kind: ManifestTypeKind,
+    /// Number of fields (records) or variants (unions)
+    pub members: usize,
+}
+
+/// Kind of a generated type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ManifestTypeKind {
+    /// Record type
+    Record,
+    /// Discriminated union
+    Du,
+}
+
+impl GenerationManifest {
+    /// Create an empty manifest for a provider run
+    pub fn new(provider_name: &str, provider_version: &str, namespace: &str) -> Self {
+        Self {
+            manifest_version: MANIFEST_VERSION,
+            provider: ProviderInfo {
+                name: provider_name.to_string(),
+                version: provider_version.to_string(),
+            },
+            namespace: namespace.to_string(),
+            sources: Vec::new(),
+            root_types: Vec::new(),
+            modules: Vec::new(),
+            warnings: Vec::new(),
+            unmapped: Vec::new(),
+            names: Vec::new(),
+        }
+    }
+
+    /// Record the digest of a resolved source
+    pub fn add_source(&mut self, source: &str, schema: &Schema) {
+        let source = if source.len() > MAX_RECORDED_SOURCE_LEN || source.contains('\n') {
+            "<inline>".to_string()
+        } else {
+            source.to_string()
+        };
+
+        self.sources.push(SourceDigest {
+            source,
+            sha256: schema_digest(schema),
+        });
+    }
+
+    /// Record every module and type in the generated output
+    pub fn record_types(&mut self, types: &GeneratedTypes) {
+        self.root_types
+            .extend(types.root_types.iter().map(ManifestType::from_definition));
+
+        for module in &types.modules {
+            self.modules.push(ManifestModule {
+                path: module.path.clone(),
+                types: module.types.iter().map(ManifestType::from_definition).collect(),
+            });
+        }
+    }
+
+    /// Add a warning
+    pub fn warn(&mut self, message: impl Into<String>) {
+        self.warnings.push(message.into());
+    }
+
+    /// Add a source construct that could not be mapped
+    pub fn add_unmapped(&mut self, construct: impl Into<String>) {
+        self.unmapped.push(construct.into());
+    }
+
+    /// Total number of types recorded in the manifest
+    pub fn type_count(&self) -> usize {
+        self.root_types.len() + self.modules.iter().map(|m| m.types.len()).sum::<usize>()
+    }
+
+    /// Serialize the manifest to pretty-printed JSON
+    pub fn to_json(&self) -> ProviderResult<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| ProviderError::ParseError(format!("Failed to serialize manifest: {}", e)))
+    }
+
+    /// Parse a manifest from JSON
+    pub fn from_json(json: &str) -> ProviderResult<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Invalid manifest JSON: {}", e)))
+    }
+
+    /// Write the manifest as JSON to a file
+    pub fn write_to(&self, path: impl AsRef<Path>) -> ProviderResult<()> {
+        std::fs::write(path, self.to_json()?).map_err(|e| ProviderError::IoError(e.to_string()))
+    }
+}
+
+impl ManifestType {
+    fn from_definition(type_def: &TypeDefinition) -> Self {
+        match type_def {
+            TypeDefinition::Record(record) => Self {
+                name: record.name.clone(),
+                kind: ManifestTypeKind::Record,
+                members: record.fields.len(),
+            },
+            TypeDefinition::Du(du) => Self {
+                name: du.name.clone(),
+                kind: ManifestTypeKind::Du,
+                members: du.variants.len(),
+            },
+        }
+    }
+}
+
+/// Compute the hex-encoded SHA-256 of a resolved schema's content
+pub fn schema_digest(schema: &Schema) -> String {
+    let content = match schema {
+        Schema::Custom(content) => content.clone(),
+        Schema::JsonSchema(value) | Schema::OpenApi(value) => value.to_string(),
+    };
+
+    Sha256::digest(content.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Resolve a source, generate types and build the matching manifest
+///
+/// The `naming` param and field overrides are honored, and their renames
+/// recorded in the manifest.
+pub fn generate_with_manifest(
+    provider: &dyn TypeProvider,
+    provider_version: &str,
+    source: &str,
+    params: &ProviderParams,
+    namespace: &str,
+) -> ProviderResult<(GeneratedTypes, GenerationManifest)> {
+    let schema = provider.resolve_schema(source, params)?;
+    let types = provider.generate_types(&schema, namespace)?;
+    let (types, names) = rename_with_params(types, params)?;
+
+    let mut manifest = GenerationManifest::new(provider.name(), provider_version, namespace);
+    manifest.names = names.into_entries();
+    manifest.add_source(source, &schema);
+    manifest.record_types(&types);
+
+    Ok((types, manifest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_type_providers::{DuDef, GeneratedModule, RecordDef, TypeExpr, VariantDef};
+
+    struct FixedProvider;
+
+    impl TypeProvider for FixedProvider {
+        fn name(&self) -> &str {
+            "FixedProvider"
+        }
+
+        fn resolve_schema(&self, source: &str, _params: &ProviderParams) -> ProviderResult<Schema> {
+            Ok(Schema::Custom(source.to_string()))
+        }
+
+        fn generate_types(&self, _schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+            let mut result = GeneratedTypes::new();
+            let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+            module.types.push(TypeDefinition::Record(RecordDef {
+                name: "User".to_string(),
+                fields: vec![
+                    ("id".to_string(), TypeExpr::Named("int".to_string())),
+                    ("name".to_string(), TypeExpr::Named("string".to_string())),
+                ],
+            }));
+            module.types.push(TypeDefinition::Du(DuDef {
+                name: "Status".to_string(),
+                variants: vec![
+                    VariantDef::new_simple("Active".to_string()),
+                    VariantDef::new_simple("Inactive".to_string()),
+                ],
+            }));
+            result.modules.push(module);
+            Ok(result)
+        }
+    }
+
+    #[test]
+    fn test_generate_with_manifest() {
+        let (types, manifest) = generate_with_manifest(
+            &FixedProvider,
+            "1.2.3",
+            "schema.sql",
+            &ProviderParams::default(),
+            "Db",
+        )
+        .unwrap();
+
+        assert_eq!(types.modules.len(), 1);
+        assert_eq!(manifest.provider.name, "FixedProvider");
+        assert_eq!(manifest.provider.version, "1.2.3");
+        assert_eq!(manifest.sources[0].source, "schema.sql");
+        assert_eq!(manifest.sources[0].sha256.len(), 64);
+        assert_eq!(manifest.type_count(), 2);
+
+        let module = &manifest.modules[0];
+        assert_eq!(module.path, vec!["Db"]);
+        assert_eq!(module.types[0].kind, ManifestTypeKind::Record);
+        assert_eq!(module.types[0].members, 2);
+        assert_eq!(module.types[1].kind, ManifestTypeKind::Du);
+    }
+
+    #[test]
+    fn test_naming_is_recorded() {
+        let params = ProviderParams::default().with("naming", "snake_case");
+        let (_, manifest) =
+            generate_with_manifest(&FixedProvider, "1.2.3", "schema.sql", &params, "Db").unwrap();
+
+        assert_eq!(manifest.modules[0].types[0].name, "user");
+        assert_eq!(manifest.modules[0].types[1].name, "status");
+        assert_eq!(manifest.names.len(), 2);
+        assert_eq!(manifest.names[1].original, "Status");
+        assert!(manifest.to_json().unwrap().contains("\"names\""));
+
+        let (_, manifest) =
+            generate_with_manifest(&FixedProvider, "1.2.3", "schema.sql", &ProviderParams::default(), "Db")
+                .unwrap();
+        assert!(manifest.names.is_empty());
+        assert!(!manifest.to_json().unwrap().contains("\"names\""));
+    }
+
+    #[test]
+    fn test_inline_source_is_not_recorded() {
+        let mut manifest = GenerationManifest::new("FixedProvider", "0.1.0", "Db");
+        let source = "CREATE TABLE users (\n  id INT\n);";
+        manifest.add_source(source, &Schema::Custom(source.to_string()));
+
+        assert_eq!(manifest.sources[0].source, "<inline>");
+    }
+
+    #[test]
+    fn test_digest_is_stable() {
+        let a = schema_digest(&Schema::Custom("abc".to_string()));
+        let b = schema_digest(&Schema::Custom("abc".to_string()));
+        let c = schema_digest(&Schema::Custom("abd".to_string()));
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(a, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let mut manifest = GenerationManifest::new("FixedProvider", "0.1.0", "Db");
+        manifest.warn("column `geom` mapped to any");
+        manifest.add_unmapped("FOREIGN KEY posts.user_id");
+
+        let json = manifest.to_json().unwrap();
+        let parsed = GenerationManifest::from_json(&json).unwrap();
+
+        assert_eq!(parsed, manifest);
+        assert!(json.contains("\"manifest_version\": 1"));
+    }
+}
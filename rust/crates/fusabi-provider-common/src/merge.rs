@@ -0,0 +1,362 @@
+//! Combine the output of several providers
+//!
+//! Projects often generate one module tree from several sources, such as a
+//! SQL schema, an `.env` file and an MCP server. [`MergeTypes::merge`] joins
+//! modules that share a path and decides what happens when both sides
+//! declare a type with the same name in the same module:
+//!
+//! - [`CollisionPolicy::Error`] fails on the first collision
+//! - [`CollisionPolicy::DedupeIdentical`] keeps one copy of types whose
+//!   fields or variants are identical and fails on any other collision
+//! - [`CollisionPolicy::Prefix`] renames the incoming type (`Config` becomes
+//!   `McpConfig`) and rewrites the incoming references to it
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_common::{CollisionPolicy, MergeTypes};
+//!
+//! let mut types = sql.generate_types(&sql_schema, "App")?;
+//! types.merge(env.generate_types(&env_schema, "App")?, &CollisionPolicy::DedupeIdentical)?;
+//! let mcp_types = mcp.generate_types(&mcp_schema, "App")?;
+//! let report = types.merge(mcp_types, &CollisionPolicy::Prefix("Mcp".to_string()))?;
+//! for (from, to) in &report.renamed {
+//!     println!("renamed {} to {}", from, to);
+//! }
+//! ```
+
+use crate::generics::{base_name, GenericName};
+use crate::index::qualified_name;
+use crate::naming::rename_expr;
+use crate::shape::TypeShape;
+use fusabi_type_providers::{
+    GeneratedModule, GeneratedTypes, ProviderError, ProviderResult, TypeDefinition,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// What to do when both sides of a merge declare the same type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CollisionPolicy {
+    /// Fail on the first collision
+    Error,
+    /// Keep the existing type if the incoming one is identical, fail otherwise
+    DedupeIdentical,
+    /// Prefix the incoming type's name with the given string
+    Prefix(String),
+}
+
+/// What a merge did about collisions
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MergeReport {
+    /// Module-qualified names of incoming types dropped as duplicates
+    pub deduplicated: Vec<String>,
+    /// Module-qualified names of renamed incoming types, with their new name
+    pub renamed: Vec<(String, String)>,
+}
+
+impl MergeReport {
+    /// Whether every incoming type was added unchanged
+    pub fn is_clean(&self) -> bool {
+        self.deduplicated.is_empty() && self.renamed.is_empty()
+    }
+}
+
+/// Merging of generated type trees
+pub trait MergeTypes {
+    /// Add every module and type of `other`, joining modules that share a path
+    fn merge(&mut self, other: GeneratedTypes, policy: &CollisionPolicy) -> ProviderResult<MergeReport>;
+}
+
+impl MergeTypes for GeneratedTypes {
+    fn merge(&mut self, mut other: GeneratedTypes, policy: &CollisionPolicy) -> ProviderResult<MergeReport> {
+        let mut report = MergeReport::default();
+        if let CollisionPolicy::Prefix(prefix) = policy {
+            prefix_collisions(self, &mut other, prefix, &mut report)?;
+        }
+        check_collisions(self, &other, policy, &mut report)?;
+
+        extend_new(&mut self.root_types, other.root_types);
+
+        for module in other.modules {
+            let index = match self.modules.iter().position(|m| m.path == module.path) {
+                Some(index) => index,
+                None => {
+                    self.modules.push(GeneratedModule::new(module.path.clone()));
+                    self.modules.len() - 1
+                }
+            };
+
+            extend_new(&mut self.modules[index].types, module.types);
+        }
+
+        Ok(report)
+    }
+}
+
+/// Fail on collisions the policy does not resolve, before anything is added,
+/// and record the duplicates it drops
+fn check_collisions(
+    target: &GeneratedTypes,
+    source: &GeneratedTypes,
+    policy: &CollisionPolicy,
+    report: &mut MergeReport,
+) -> ProviderResult<()> {
+    let mut seen: BTreeMap<&[String], BTreeMap<&str, &TypeDefinition>> = BTreeMap::new();
+    for (path, type_defs) in modules(target) {
+        let known = seen.entry(path).or_default();
+        for type_def in type_defs {
+            known.entry(base_name(type_name(type_def))).or_insert(type_def);
+        }
+    }
+
+    for (path, type_defs) in modules(source) {
+        let known = seen.entry(path).or_default();
+        for type_def in type_defs {
+            let name = base_name(type_name(type_def));
+            match known.get(name).copied() {
+                None => {
+                    known.insert(name, type_def);
+                }
+                Some(existing)
+                    if *policy == CollisionPolicy::DedupeIdentical && same_definition(existing, type_def) =>
+                {
+                    report.deduplicated.push(qualified_name(path, name));
+                }
+                Some(_) => return Err(collision_error(name, path)),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Add the types whose name is not already there
+fn extend_new(types: &mut Vec<TypeDefinition>, incoming: Vec<TypeDefinition>) {
+    let mut names: BTreeSet<String> = types.iter().map(|t| base_name(type_name(t)).to_string()).collect();
+    for type_def in incoming {
+        if names.insert(base_name(type_name(&type_def)).to_string()) {
+            types.push(type_def);
+        }
+    }
+}
+
+/// Types of the root and of each module, by module path
+fn modules(types: &GeneratedTypes) -> impl Iterator<Item = (&[String], &Vec<TypeDefinition>)> {
+    std::iter::once((&[] as &[String], &types.root_types))
+        .chain(types.modules.iter().map(|m| (m.path.as_slice(), &m.types)))
+}
+
+/// Rename incoming types that collide, and the incoming references to them
+///
+/// A reference is rewritten in the module that declares the renamed type
+/// and, for renamed root types, in every module that does not declare a
+/// type of the same name.
+fn prefix_collisions(
+    target: &GeneratedTypes,
+    source: &mut GeneratedTypes,
+    prefix: &str,
+    report: &mut MergeReport,
+) -> ProviderResult<()> {
+    let mut renames: BTreeMap<Vec<String>, BTreeMap<String, String>> = BTreeMap::new();
+
+    let modules = std::iter::once((Vec::new(), &mut source.root_types))
+        .chain(source.modules.iter_mut().map(|m| (m.path.clone(), &mut m.types)));
+    for (path, type_defs) in modules {
+        let existing = declared(target, &path);
+        let incoming: BTreeSet<String> = type_defs.iter().map(|t| base_name(type_name(t)).to_string()).collect();
+
+        for type_def in type_defs.iter_mut() {
+            let generic = GenericName::parse(type_name(type_def));
+            if !existing.contains(&generic.name.as_str()) {
+                continue;
+            }
+
+            let new_name = format!("{}{}", prefix, generic.name);
+            if existing.contains(&new_name.as_str()) || incoming.contains(&new_name) {
+                return Err(collision_error(&new_name, &path));
+            }
+            report.renamed.push((qualified_name(&path, &generic.name), qualified_name(&path, &new_name)));
+            renames.entry(path.clone()).or_default().insert(generic.name.clone(), new_name.clone());
+            set_type_name(type_def, generic.with_name(new_name).to_string());
+        }
+    }
+    if renames.is_empty() {
+        return Ok(());
+    }
+
+    let root = renames.get(&Vec::new()).cloned().unwrap_or_default();
+    let modules = std::iter::once((Vec::new(), &mut source.root_types))
+        .chain(source.modules.iter_mut().map(|m| (m.path.clone(), &mut m.types)));
+    for (path, type_defs) in modules {
+        let own: BTreeSet<String> = type_defs.iter().map(|t| base_name(type_name(t)).to_string()).collect();
+        let mut visible: BTreeMap<String, String> = root
+            .iter()
+            .filter(|(from, to)| !own.contains(*from) || own.contains(*to))
+            .map(|(from, to)| (from.clone(), to.clone()))
+            .collect();
+        visible.extend(renames.get(&path).cloned().unwrap_or_default());
+        if visible.is_empty() {
+            continue;
+        }
+
+        for type_def in type_defs.iter_mut() {
+            let exprs: Vec<_> = match type_def {
+                TypeDefinition::Record(record) => record.fields.iter_mut().map(|(_, ty)| ty).collect(),
+                TypeDefinition::Du(du) => du.variants.iter_mut().flat_map(|v| v.fields.iter_mut()).collect(),
+            };
+            for ty in exprs {
+                *ty = rename_expr(ty, &visible);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Base names of the types declared in the module at `path`
+fn declared<'a>(types: &'a GeneratedTypes, path: &[String]) -> BTreeSet<&'a str> {
+    let type_defs = if path.is_empty() {
+        Some(&types.root_types)
+    } else {
+        types.modules.iter().find(|m| m.path == path).map(|m| &m.types)
+    };
+    type_defs
+        .into_iter()
+        .flatten()
+        .map(|t| base_name(type_name(t)))
+        .collect()
+}
+
+fn collision_error(name: &str, path: &[String]) -> ProviderError {
+    let location = if path.is_empty() {
+        "the root".to_string()
+    } else {
+        format!("module {}", path.join("."))
+    };
+    ProviderError::InvalidSource(format!("Type '{}' is generated twice in {}", name, location))
+}
+
+/// Whether two definitions have the same name, kind and members, in order
+fn same_definition(a: &TypeDefinition, b: &TypeDefinition) -> bool {
+    let render = |ty| TypeShape::from_type_expr(ty).to_string();
+    match (a, b) {
+        (TypeDefinition::Record(a), TypeDefinition::Record(b)) => {
+            a.name == b.name
+                && a.fields.len() == b.fields.len()
+                && a.fields
+                    .iter()
+                    .zip(&b.fields)
+                    .all(|((a_name, a_ty), (b_name, b_ty))| a_name == b_name && render(a_ty) == render(b_ty))
+        }
+        (TypeDefinition::Du(a), TypeDefinition::Du(b)) => {
+            a.name == b.name
+                && a.variants.len() == b.variants.len()
+                && a.variants.iter().zip(&b.variants).all(|(a, b)| {
+                    a.name == b.name
+                        && a.fields.len() == b.fields.len()
+                        && a.fields.iter().zip(&b.fields).all(|(a, b)| render(a) == render(b))
+                })
+        }
+        _ => false,
+    }
+}
+
+fn type_name(type_def: &TypeDefinition) -> &str {
+    match type_def {
+        TypeDefinition::Record(record) => &record.name,
+        TypeDefinition::Du(du) => &du.name,
+    }
+}
+
+fn set_type_name(type_def: &mut TypeDefinition, name: String) {
+    match type_def {
+        TypeDefinition::Record(record) => record.name = name,
+        TypeDefinition::Du(du) => du.name = name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_type_providers::{DuDef, RecordDef, VariantDef};
+
+    fn record(name: &str, fields: &[(&str, &str)]) -> TypeDefinition {
+        TypeDefinition::Record(RecordDef {
+            name: name.to_string(),
+            fields: fields
+                .iter()
+                .map(|(field, ty)| (field.to_string(), TypeShape::parse(ty).into()))
+                .collect(),
+        })
+    }
+
+    fn app(types: Vec<TypeDefinition>) -> GeneratedTypes {
+        let mut result = GeneratedTypes::new();
+        let mut module = GeneratedModule::new(vec!["App".to_string()]);
+        module.types = types;
+        result.modules.push(module);
+        result
+    }
+
+    fn names(types: &GeneratedTypes) -> Vec<&str> {
+        types.modules[0].types.iter().map(type_name).collect()
+    }
+
+    #[test]
+    fn test_merge_joins_modules() {
+        let mut types = app(vec![record("Users", &[("id", "int")])]);
+        let mut other = app(vec![record("Settings", &[("port", "int")])]);
+        other.root_types.push(record("Meta", &[]));
+        other.modules.push(GeneratedModule::new(vec!["Tools".to_string()]));
+
+        let report = types.merge(other, &CollisionPolicy::Error).unwrap();
+        assert!(report.is_clean());
+        assert_eq!(names(&types), ["Users", "Settings"]);
+        assert_eq!(types.root_types.len(), 1);
+        assert_eq!(types.modules.len(), 2);
+    }
+
+    #[test]
+    fn test_error_and_dedupe() {
+        let users = || record("Users", &[("id", "int"), ("email", "string option")]);
+        let mut types = app(vec![users()]);
+
+        let err = app(vec![users()]).merge(app(vec![users()]), &CollisionPolicy::Error).unwrap_err();
+        assert!(err.to_string().contains("'Users' is generated twice in module App"));
+
+        let report = types.merge(app(vec![users()]), &CollisionPolicy::DedupeIdentical).unwrap();
+        assert_eq!(report.deduplicated, ["App.Users"]);
+        assert_eq!(names(&types), ["Users"]);
+
+        // Nothing is added when a collision fails the merge
+        let changed = app(vec![
+            record("Settings", &[]),
+            record("Users", &[("email", "string option"), ("id", "int")]),
+        ]);
+        assert!(types.merge(changed, &CollisionPolicy::DedupeIdentical).is_err());
+        assert_eq!(names(&types), ["Users"]);
+    }
+
+    #[test]
+    fn test_prefix_renames_references() {
+        let mut types = app(vec![record("Config", &[("dsn", "string")])]);
+        let other = app(vec![
+            record("Config", &[("server", "string")]),
+            TypeDefinition::Du(DuDef {
+                name: "ToolCall".to_string(),
+                variants: vec![VariantDef::new("Configure".to_string(), vec![TypeShape::named("Config").into()])],
+            }),
+            record("Session", &[("config", "App.Config option"), ("calls", "ToolCall list")]),
+        ]);
+
+        let report = types.merge(other, &CollisionPolicy::Prefix("Mcp".to_string())).unwrap();
+        assert_eq!(report.renamed, [("App.Config".to_string(), "App.McpConfig".to_string())]);
+        assert_eq!(names(&types), ["Config", "McpConfig", "ToolCall", "Session"]);
+
+        let TypeDefinition::Du(du) = &types.modules[0].types[2] else { panic!() };
+        assert_eq!(du.variants[0].fields[0].to_string(), "McpConfig");
+        let TypeDefinition::Record(session) = &types.modules[0].types[3] else { panic!() };
+        assert_eq!(session.fields[0].1.to_string(), "App.McpConfig option");
+        assert_eq!(session.fields[1].1.to_string(), "ToolCall list");
+    }
+}
@@ -0,0 +1,525 @@
+//! Naming conventions for generated type and field names
+//!
+//! Providers name types in PascalCase and keep field names as the source
+//! spells them. The `naming` param re-cases both after generation, the same
+//! way for every provider:
+//!
+//! | `naming`     | Type names    | Field names   |
+//! |--------------|---------------|---------------|
+//! | `PascalCase` | `UserAccount` | `CreatedAt`   |
+//! | `camelCase`  | `userAccount` | `createdAt`   |
+//! | `snake_case` | `user_account`| `created_at`  |
+//! | `preserve`   | unchanged     | unchanged     |
+//!
+//! Type expressions referring to renamed types are rewritten to match.
+//! Union cases and module paths keep their names. Every rename is recorded
+//! in a [`NameMap`], which [`generate_with_manifest`] stores in the
+//! manifest so that generated names can be traced back to the source.
+//! Explicit field renames and exclusions are handled by
+//! [`overrides`](crate::overrides), and fixes for invalid names by
+//! [`identifiers`](crate::identifiers); both are recorded in the same map.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_common::naming::{apply_naming, Naming};
+//!
+//! let types = provider.generate_types(&schema, "Db")?;
+//! let (types, names) = apply_naming(types, Naming::SnakeCase);
+//! for entry in names.entries() {
+//!     eprintln!("{} was {}", entry.name, entry.original);
+//! }
+//! ```
+//!
+//! [`generate_with_manifest`]: crate::manifest::generate_with_manifest
+
+use crate::generics::{base_name, GenericName};
+use crate::identifiers::{escape_keywords, fix_from_params, fix_identifiers, KeywordEscape};
+use crate::overrides::FieldOverrides;
+use crate::shape::TypeShape;
+use crate::transform::TransformChain;
+use fusabi_type_providers::{
+    GeneratedTypes, ProviderError, ProviderParams, ProviderResult, TypeDefinition, TypeExpr,
+    TypeProvider,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Params key selecting the naming convention
+pub const NAMING_PARAM: &str = "naming";
+
+/// Casing applied to generated type and field names
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Naming {
+    /// `UserAccount`
+    PascalCase,
+    /// `userAccount`
+    CamelCase,
+    /// `user_account`
+    SnakeCase,
+    /// Names as the provider generated them
+    Preserve,
+}
+
+impl Naming {
+    /// Parse a `naming` param value
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "PascalCase" | "pascal_case" => Some(Naming::PascalCase),
+            "camelCase" | "camel_case" => Some(Naming::CamelCase),
+            "snake_case" => Some(Naming::SnakeCase),
+            "preserve" => Some(Naming::Preserve),
+            _ => None,
+        }
+    }
+
+    /// Read the `naming` param, if given
+    pub fn from_params(params: &ProviderParams) -> ProviderResult<Option<Self>> {
+        params
+            .custom
+            .get(NAMING_PARAM)
+            .map(|value| {
+                Self::parse(value).ok_or_else(|| {
+                    ProviderError::InvalidSource(format!(
+                        "Invalid naming '{}': expected PascalCase, camelCase, snake_case or preserve",
+                        value
+                    ))
+                })
+            })
+            .transpose()
+    }
+
+    /// Re-case a name
+    pub fn apply(&self, name: &str) -> String {
+        let words = split_words(name);
+        if words.is_empty() {
+            return name.to_string();
+        }
+
+        match self {
+            Naming::Preserve => name.to_string(),
+            Naming::SnakeCase => words.join("_"),
+            Naming::PascalCase => words.iter().map(String::as_str).map(capitalize).collect(),
+            Naming::CamelCase => {
+                let mut result = words[0].clone();
+                result.extend(words[1..].iter().map(String::as_str).map(capitalize));
+                result
+            }
+        }
+    }
+}
+
+/// Whether a name was given to a type or a field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NameKind {
+    /// Record or union name
+    Type,
+    /// Record field name
+    Field,
+    /// Union case name
+    Case,
+}
+
+/// A generated name and the name it replaced
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NameMapping {
+    /// What was renamed
+    pub kind: NameKind,
+    /// Path of the module declaring the type
+    pub module: Vec<String>,
+    /// Renamed type owning the field or case; `None` for types
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// Name in the generated output
+    pub name: String,
+    /// Name as the provider generated it
+    pub original: String,
+}
+
+/// Renames made by [`apply_naming`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NameMap {
+    entries: Vec<NameMapping>,
+}
+
+impl NameMap {
+    /// Every rename, in output order
+    pub fn entries(&self) -> &[NameMapping] {
+        &self.entries
+    }
+
+    /// Original name of a type in the module at `module`
+    pub fn original_type(&self, module: &[String], name: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| e.kind == NameKind::Type && e.module == module && e.name == name)
+            .map(|e| e.original.as_str())
+    }
+
+    /// Whether nothing was renamed
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of renames
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Original name of a field of the type `owner` in the module at `module`
+    pub fn original_field(&self, module: &[String], owner: &str, name: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| {
+                e.kind == NameKind::Field
+                    && e.module == module
+                    && e.owner.as_deref() == Some(owner)
+                    && e.name == name
+            })
+            .map(|e| e.original.as_str())
+    }
+
+    /// Record a rename
+    pub(crate) fn push(&mut self, mapping: NameMapping) {
+        self.entries.push(mapping);
+    }
+
+    /// Record that a field was renamed, keeping the earliest original name
+    pub(crate) fn record_field(&mut self, module: &[String], owner: &str, from: &str, to: &str) {
+        let existing = self.entries.iter_mut().find(|e| {
+            e.kind == NameKind::Field
+                && e.module == module
+                && e.owner.as_deref() == Some(owner)
+                && e.name == from
+        });
+        match existing {
+            Some(entry) => entry.name = to.to_string(),
+            None => self.entries.push(NameMapping {
+                kind: NameKind::Field,
+                module: module.to_vec(),
+                owner: Some(owner.to_string()),
+                name: to.to_string(),
+                original: from.to_string(),
+            }),
+        }
+    }
+
+    /// Consume the map, returning its entries
+    pub fn into_entries(self) -> Vec<NameMapping> {
+        self.entries
+    }
+}
+
+/// Re-case every type and field name and rewrite references to renamed types
+pub fn apply_naming(mut types: GeneratedTypes, naming: Naming) -> (GeneratedTypes, NameMap) {
+    let mut map = NameMap::default();
+    if naming == Naming::Preserve {
+        return (types, map);
+    }
+
+    let renamed: BTreeMap<String, String> = types
+        .root_types
+        .iter()
+        .chain(types.modules.iter().flat_map(|m| m.types.iter()))
+        .map(|t| base_name(type_name(t)).to_string())
+        .map(|name| (naming.apply(&name), name))
+        .filter(|(new, old)| new != old)
+        .map(|(new, old)| (old, new))
+        .collect();
+
+    rename_all(&mut types.root_types, &[], naming, &renamed, &mut map);
+    for module in &mut types.modules {
+        rename_all(&mut module.types, &module.path, naming, &renamed, &mut map);
+    }
+    (types, map)
+}
+
+/// Apply the `naming` param, then the field overrides of the `overrides`,
+/// `rename` and `exclude` params, then the `keyword_escape` and
+/// `fix_identifiers` params, then the [`transform`](crate::transform)
+/// params
+///
+/// Transforms run last and are not recorded in the returned map.
+pub fn rename_with_params(
+    types: GeneratedTypes,
+    params: &ProviderParams,
+) -> ProviderResult<(GeneratedTypes, NameMap)> {
+    let overrides = FieldOverrides::from_params(params)?;
+    let escape = KeywordEscape::from_params(params)?;
+    let fix = fix_from_params(params)?;
+    let transforms = TransformChain::from_params(params)?;
+    let (mut types, mut names) = match Naming::from_params(params)? {
+        Some(naming) => apply_naming(types, naming),
+        None => (types, NameMap::default()),
+    };
+    if let Some(overrides) = overrides {
+        overrides.apply(&mut types, &mut names);
+    }
+    if let Some(escape) = escape {
+        let (escaped, escapes) = escape_keywords(types, escape);
+        types = escaped;
+        names.entries.extend(escapes.entries);
+    }
+    if fix {
+        let (fixed, fixes) = fix_identifiers(types);
+        types = fixed;
+        names.entries.extend(fixes.entries);
+    }
+    Ok((transforms.apply(types), names))
+}
+
+/// Resolve a source and generate types, honoring the `naming` param and
+/// field overrides
+pub fn generate_with_naming(
+    provider: &dyn TypeProvider,
+    source: &str,
+    params: &ProviderParams,
+    namespace: &str,
+) -> ProviderResult<(GeneratedTypes, NameMap)> {
+    let schema = provider.resolve_schema(source, params)?;
+    let types = provider.generate_types(&schema, namespace)?;
+    rename_with_params(types, params)
+}
+
+fn rename_all(
+    type_defs: &mut [TypeDefinition],
+    module: &[String],
+    naming: Naming,
+    renamed: &BTreeMap<String, String>,
+    map: &mut NameMap,
+) {
+    for type_def in type_defs {
+        let original = type_name(type_def).to_string();
+        // Generic definitions keep their parameters under the new base name
+        let generic = GenericName::parse(&original);
+        let name = match renamed.get(&generic.name) {
+            Some(new) => generic.with_name(new.clone()).to_string(),
+            None => original.clone(),
+        };
+        if name != original {
+            map.entries.push(NameMapping {
+                kind: NameKind::Type,
+                module: module.to_vec(),
+                owner: None,
+                name: name.clone(),
+                original,
+            });
+        }
+
+        match type_def {
+            TypeDefinition::Record(record) => {
+                record.name = name.clone();
+                for (field, ty) in &mut record.fields {
+                    let new_field = naming.apply(field);
+                    if new_field != *field {
+                        map.entries.push(NameMapping {
+                            kind: NameKind::Field,
+                            module: module.to_vec(),
+                            owner: Some(name.clone()),
+                            name: new_field.clone(),
+                            original: std::mem::replace(field, new_field),
+                        });
+                    }
+                    if !renamed.is_empty() {
+                        *ty = rename_expr(ty, renamed);
+                    }
+                }
+            }
+            TypeDefinition::Du(du) => {
+                du.name = name;
+                for ty in du.variants.iter_mut().flat_map(|v| v.fields.iter_mut()) {
+                    if !renamed.is_empty() {
+                        *ty = rename_expr(ty, renamed);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn type_name(type_def: &TypeDefinition) -> &str {
+    match type_def {
+        TypeDefinition::Record(record) => &record.name,
+        TypeDefinition::Du(du) => &du.name,
+    }
+}
+
+pub(crate) fn rename_expr(expr: &TypeExpr, renamed: &BTreeMap<String, String>) -> TypeExpr {
+    rename_shape(TypeShape::from_type_expr(expr), renamed).into()
+}
+
+/// Rename references, matching qualified names (`Db.Users`) by their last segment
+fn rename_shape(shape: TypeShape, renamed: &BTreeMap<String, String>) -> TypeShape {
+    let rename = |shape: TypeShape| Box::new(rename_shape(shape, renamed));
+    match shape {
+        TypeShape::Named(name) => TypeShape::Named(rename_reference(name, renamed)),
+        TypeShape::Generic(name, args) => TypeShape::Generic(
+            rename_reference(name, renamed),
+            args.into_iter().map(|a| rename_shape(a, renamed)).collect(),
+        ),
+        TypeShape::Option(inner) => TypeShape::Option(rename(*inner)),
+        TypeShape::List(inner) => TypeShape::List(rename(*inner)),
+        TypeShape::Map(key, value) => TypeShape::Map(rename(*key), rename(*value)),
+        TypeShape::Tuple(items) => {
+            TypeShape::Tuple(items.into_iter().map(|i| rename_shape(i, renamed)).collect())
+        }
+        TypeShape::Union(items) => {
+            TypeShape::Union(items.into_iter().map(|i| rename_shape(i, renamed)).collect())
+        }
+        TypeShape::Literal(text) => TypeShape::Literal(text),
+        TypeShape::Param(name) => TypeShape::Param(name),
+    }
+}
+
+fn rename_reference(name: String, renamed: &BTreeMap<String, String>) -> String {
+    let (prefix, last) = match name.rsplit_once('.') {
+        Some((prefix, last)) => (Some(prefix), last),
+        None => (None, name.as_str()),
+    };
+    match (renamed.get(last), prefix) {
+        (Some(new), Some(prefix)) => format!("{}.{}", prefix, new),
+        (Some(new), None) => new.clone(),
+        (None, _) => name,
+    }
+}
+
+/// Lowercase words of an identifier, split at `_`, `-`, spaces and case changes
+///
+/// Runs of capitals stay together as one word (`HTTPServer` → `http`, `server`).
+fn split_words(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words = Vec::new();
+    let mut current = String::new();
+
+    for (i, &ch) in chars.iter().enumerate() {
+        if !ch.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+
+        let prev = i.checked_sub(1).map(|p| chars[p]);
+        let next = chars.get(i + 1).copied();
+        let boundary = ch.is_uppercase()
+            && match prev {
+                Some(p) if p.is_lowercase() || p.is_ascii_digit() => true,
+                Some(p) if p.is_uppercase() => next.is_some_and(char::is_lowercase),
+                _ => false,
+            };
+        if boundary && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        current.extend(ch.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_type_providers::{DuDef, GeneratedModule, RecordDef, VariantDef};
+
+    #[test]
+    fn test_apply() {
+        assert_eq!(Naming::SnakeCase.apply("UserAccount"), "user_account");
+        assert_eq!(Naming::SnakeCase.apply("HTTPServer2Config"), "http_server2_config");
+        assert_eq!(Naming::CamelCase.apply("created_at"), "createdAt");
+        assert_eq!(Naming::PascalCase.apply("created-at"), "CreatedAt");
+        assert_eq!(Naming::PascalCase.apply("userID"), "UserId");
+        assert_eq!(Naming::Preserve.apply("created_at"), "created_at");
+        assert_eq!(Naming::SnakeCase.apply("_"), "_");
+    }
+
+    #[test]
+    fn test_from_params() {
+        let params = ProviderParams::default().with("naming", "camelCase");
+        assert_eq!(Naming::from_params(&params).unwrap(), Some(Naming::CamelCase));
+        assert_eq!(Naming::from_params(&ProviderParams::default()).unwrap(), None);
+        let params = ProviderParams::default().with("naming", "kebab");
+        assert!(Naming::from_params(&params).is_err());
+    }
+
+    #[test]
+    fn test_apply_naming_to_generics() {
+        let mut types = GeneratedTypes::new();
+        types.root_types.push(TypeDefinition::Record(RecordDef {
+            name: "page_info<'node>".to_string(),
+            fields: vec![("items".to_string(), TypeShape::param("node").list().into())],
+        }));
+        types.root_types.push(TypeDefinition::Record(RecordDef {
+            name: "user".to_string(),
+            fields: vec![(
+                "friends".to_string(),
+                TypeShape::generic("page_info", vec![TypeShape::named("user")]).into(),
+            )],
+        }));
+
+        let (types, names) = apply_naming(types, Naming::PascalCase);
+        let TypeDefinition::Record(page) = &types.root_types[0] else { panic!() };
+        assert_eq!(page.name, "PageInfo<'node>");
+        assert_eq!(page.fields[0].1.to_string(), "'node list");
+        let TypeDefinition::Record(user) = &types.root_types[1] else { panic!() };
+        assert_eq!(user.fields[0].1.to_string(), "PageInfo<User>");
+        assert_eq!(names.original_type(&[], "PageInfo<'node>"), Some("page_info<'node>"));
+    }
+
+    #[test]
+    fn test_apply_naming() {
+        let mut types = GeneratedTypes::new();
+        let mut module = GeneratedModule::new(vec!["Db".to_string()]);
+        module.types.push(TypeDefinition::Record(RecordDef {
+            name: "UserAccount".to_string(),
+            fields: vec![
+                ("id".to_string(), TypeShape::named("int").into()),
+                ("createdAt".to_string(), TypeShape::named("string").into()),
+                ("status".to_string(), TypeShape::named("AccountStatus").option().into()),
+                ("peers".to_string(), TypeShape::named("Db.UserAccount").list().into()),
+            ],
+        }));
+        module.types.push(TypeDefinition::Du(DuDef {
+            name: "AccountStatus".to_string(),
+            variants: vec![
+                VariantDef::new_simple("Active".to_string()),
+                VariantDef::new("Moved".to_string(), vec![TypeShape::named("UserAccount").into()]),
+            ],
+        }));
+        types.modules.push(module);
+
+        let (types, names) = apply_naming(types, Naming::SnakeCase);
+        let TypeDefinition::Record(record) = &types.modules[0].types[0] else { panic!() };
+        assert_eq!(record.name, "user_account");
+        let fields: Vec<_> = record.fields.iter().map(|(n, t)| (n.as_str(), t.to_string())).collect();
+        assert_eq!(
+            fields,
+            [
+                ("id", "int".to_string()),
+                ("created_at", "string".to_string()),
+                ("status", "account_status option".to_string()),
+                ("peers", "Db.user_account list".to_string()),
+            ]
+        );
+        let TypeDefinition::Du(du) = &types.modules[0].types[1] else { panic!() };
+        assert_eq!(du.variants[1].name, "Moved");
+        assert_eq!(du.variants[1].fields[0].to_string(), "user_account");
+
+        assert_eq!(names.len(), 3);
+        assert_eq!(names.original_type(&["Db".to_string()], "user_account"), Some("UserAccount"));
+        let field = &names.entries()[1];
+        assert_eq!(field.kind, NameKind::Field);
+        assert_eq!(field.owner.as_deref(), Some("user_account"));
+        assert_eq!(field.original, "createdAt");
+    }
+}
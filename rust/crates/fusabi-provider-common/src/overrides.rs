@@ -0,0 +1,253 @@
+//! Field renames and exclusions
+//!
+//! Generated field names follow the source, which is not always what the
+//! consuming code wants: `user_id` should read `userId`, and columns such as
+//! `password_hash` should not appear at all. [`FieldOverrides`] renames and
+//! drops fields after generation, the same way for every provider.
+//!
+//! Overrides come from a TOML file named by the `overrides` param, from the
+//! inline `rename` and `exclude` params, or both; inline entries win.
+//!
+//! ```toml
+//! # overrides.toml
+//! exclude = ["password_hash", "Users.ssn"]
+//!
+//! [rename]
+//! user_id = "userId"
+//! "Users.email" = "contactEmail"
+//! ```
+//!
+//! ```text
+//! rename=user_id:userId,Users.email:contactEmail
+//! exclude=password_hash,Users.ssn
+//! ```
+//!
+//! A bare key matches the field in every type; `Type.field` matches it in
+//! one type and takes precedence. Keys may use either the name the provider
+//! generated or, when the `naming` param re-cased it, the name after
+//! re-casing. Generic types are named without their parameters
+//! (`Connection.edges`).
+
+use crate::generics::base_name;
+use crate::naming::NameMap;
+use fusabi_type_providers::{
+    GeneratedTypes, ProviderError, ProviderParams, ProviderResult, TypeDefinition,
+};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Params key naming a TOML overrides file
+pub const OVERRIDES_PARAM: &str = "overrides";
+
+/// Params key holding inline `from:to` renames
+pub const RENAME_PARAM: &str = "rename";
+
+/// Params key holding inline field exclusions
+pub const EXCLUDE_PARAM: &str = "exclude";
+
+/// Renames and exclusions applied to generated fields
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FieldOverrides {
+    /// New field names keyed by `field` or `Type.field`
+    #[serde(default)]
+    pub rename: BTreeMap<String, String>,
+    /// Fields to drop, as `field` or `Type.field`
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+impl FieldOverrides {
+    /// Parse overrides from TOML
+    pub fn from_toml(content: &str) -> ProviderResult<Self> {
+        toml::from_str(content)
+            .map_err(|e| ProviderError::ParseError(format!("Invalid overrides file: {}", e)))
+    }
+
+    /// Load overrides from a TOML file
+    pub fn load(path: impl AsRef<Path>) -> ProviderResult<Self> {
+        let content = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| ProviderError::IoError(e.to_string()))?;
+        Self::from_toml(&content)
+    }
+
+    /// Read the `overrides`, `rename` and `exclude` params, if any is given
+    pub fn from_params(params: &ProviderParams) -> ProviderResult<Option<Self>> {
+        let file = params.custom.get(OVERRIDES_PARAM);
+        let rename = params.custom.get(RENAME_PARAM);
+        let exclude = params.custom.get(EXCLUDE_PARAM);
+        if file.is_none() && rename.is_none() && exclude.is_none() {
+            return Ok(None);
+        }
+
+        let mut overrides = match file {
+            Some(path) => Self::load(path)?,
+            None => Self::default(),
+        };
+        for entry in rename.into_iter().map(String::as_str).flat_map(list) {
+            let (from, to) = entry
+                .split_once(':')
+                .map(|(from, to)| (from.trim(), to.trim()))
+                .filter(|(from, to)| !from.is_empty() && !to.is_empty())
+                .ok_or_else(|| {
+                    ProviderError::InvalidSource(format!(
+                        "Invalid rename '{}': expected from:to pairs such as user_id:userId",
+                        entry
+                    ))
+                })?;
+            overrides.rename.insert(from.to_string(), to.to_string());
+        }
+        overrides
+            .exclude
+            .extend(exclude.into_iter().map(String::as_str).flat_map(list).map(String::from));
+
+        Ok(Some(overrides))
+    }
+
+    /// Whether nothing is renamed or excluded
+    pub fn is_empty(&self) -> bool {
+        self.rename.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Rename and drop fields in place
+    ///
+    /// `names` holds the renames made by the `naming` param, so that keys
+    /// can refer to fields by the name the provider generated; explicit
+    /// renames are added to it.
+    pub fn apply(&self, types: &mut GeneratedTypes, names: &mut NameMap) {
+        let modules = std::iter::once((Vec::new(), &mut types.root_types))
+            .chain(types.modules.iter_mut().map(|m| (m.path.clone(), &mut m.types)));
+
+        for (module, type_defs) in modules {
+            for type_def in type_defs.iter_mut() {
+                let TypeDefinition::Record(record) = type_def else {
+                    continue;
+                };
+                let type_name = record.name.clone();
+                let original_type = names
+                    .original_type(&module, &type_name)
+                    .unwrap_or(&type_name)
+                    .to_string();
+
+                let keys = |field: &str| {
+                    let original_field = names.original_field(&module, &type_name, field).unwrap_or(field);
+                    [
+                        format!("{}.{}", base_name(&type_name), field),
+                        format!("{}.{}", base_name(&original_type), original_field),
+                        field.to_string(),
+                        original_field.to_string(),
+                    ]
+                };
+
+                let mut renames = Vec::new();
+                record.fields.retain(|(field, _)| {
+                    !keys(field.as_str()).iter().any(|key| self.exclude.contains(key))
+                });
+                for (field, _) in &mut record.fields {
+                    let keys = keys(field.as_str());
+                    if let Some(new) = keys.iter().find_map(|key| self.rename.get(key)) {
+                        if new != field {
+                            renames.push((std::mem::replace(field, new.clone()), new.clone()));
+                        }
+                    }
+                }
+                for (from, to) in renames {
+                    names.record_field(&module, &type_name, &from, &to);
+                }
+            }
+        }
+    }
+}
+
+/// Trimmed, non-empty items of a comma-separated param
+fn list(value: &str) -> impl Iterator<Item = &str> {
+    value.split(',').map(str::trim).filter(|item| !item.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::naming::{apply_naming, Naming};
+    use crate::shape::TypeShape;
+    use fusabi_type_providers::{GeneratedModule, RecordDef};
+
+    fn users() -> GeneratedTypes {
+        let field = |name: &str| (name.to_string(), TypeShape::named("string").into());
+        let mut types = GeneratedTypes::new();
+        let mut module = GeneratedModule::new(vec!["Db".to_string()]);
+        for name in ["Users", "Posts"] {
+            module.types.push(TypeDefinition::Record(RecordDef {
+                name: name.to_string(),
+                fields: vec![field("user_id"), field("email"), field("password_hash")],
+            }));
+        }
+        types.modules.push(module);
+        types
+    }
+
+    fn fields(types: &GeneratedTypes, index: usize) -> Vec<String> {
+        let TypeDefinition::Record(record) = &types.modules[0].types[index] else { panic!() };
+        record.fields.iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    #[test]
+    fn test_from_params() {
+        assert_eq!(FieldOverrides::from_params(&ProviderParams::default()).unwrap(), None);
+
+        let params = ProviderParams::default()
+            .with("rename", "user_id:userId, Users.email : contactEmail")
+            .with("exclude", "password_hash,");
+        let overrides = FieldOverrides::from_params(&params).unwrap().unwrap();
+        assert_eq!(overrides.rename["Users.email"], "contactEmail");
+        assert_eq!(overrides.exclude, vec!["password_hash"]);
+
+        let params = ProviderParams::default().with("rename", "user_id");
+        assert!(FieldOverrides::from_params(&params).is_err());
+    }
+
+    #[test]
+    fn test_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("overrides.toml");
+        std::fs::write(&path, "exclude = [\"password_hash\"]\n\n[rename]\nuser_id = \"userId\"\n").unwrap();
+
+        let params = ProviderParams::default()
+            .with("overrides", path.to_str().unwrap())
+            .with("rename", "user_id:uid");
+        let overrides = FieldOverrides::from_params(&params).unwrap().unwrap();
+        assert_eq!(overrides.rename["user_id"], "uid");
+        assert_eq!(overrides.exclude, vec!["password_hash"]);
+
+        assert!(FieldOverrides::from_toml("renames = {}").is_err());
+    }
+
+    #[test]
+    fn test_apply() {
+        let overrides = FieldOverrides::from_toml(
+            "exclude = [\"password_hash\", \"Posts.email\"]\n[rename]\nuser_id = \"userId\"\n\"Users.user_id\" = \"id\"\n",
+        )
+        .unwrap();
+
+        let mut types = users();
+        let mut names = NameMap::default();
+        overrides.apply(&mut types, &mut names);
+        assert_eq!(fields(&types, 0), ["id", "email"]);
+        assert_eq!(fields(&types, 1), ["userId"]);
+        assert_eq!(names.original_field(&["Db".to_string()], "Users", "id"), Some("user_id"));
+    }
+
+    #[test]
+    fn test_apply_after_naming() {
+        let overrides = FieldOverrides::from_toml("[rename]\nuser_id = \"uid\"\n\"Users.email\" = \"mail\"\n").unwrap();
+
+        let (mut types, mut names) = apply_naming(users(), Naming::CamelCase);
+        overrides.apply(&mut types, &mut names);
+        assert_eq!(fields(&types, 0), ["uid", "mail", "passwordHash"]);
+
+        // Still traced back to the provider's name, not the re-cased one
+        let module = ["Db".to_string()];
+        assert_eq!(names.original_field(&module, "users", "uid"), Some("user_id"));
+        assert_eq!(names.original_field(&module, "users", "mail"), Some("email"));
+    }
+}
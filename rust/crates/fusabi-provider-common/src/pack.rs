@@ -0,0 +1,391 @@
+//! Community pack builder
+//!
+//! A pack bundles the output of several providers (for example an
+//! "observability pack" combining OpenTelemetry, Prometheus and OBI types)
+//! into one versioned Fusabi package. Packs are described declaratively:
+//!
+//! ```toml
+//! [pack]
+//! name = "k8s"
+//! version = "0.1.0"
+//! description = "Kubernetes core and CRD types"
+//!
+//! [[sources]]
+//! provider = "kubernetes"
+//! source = "embedded"
+//! namespace = "K8s"
+//!
+//! [[sources]]
+//! provider = "kubernetes"
+//! source = "crds/backup.yaml"
+//! namespace = "Backup"
+//! params = { fetch_timeout = "5s" }
+//! ```
+//!
+//! [`PackBuilder`] runs every source through its provider, merges the
+//! resulting modules, renders them as Fusabi source and returns a
+//! [`PackArtifact`] that can be written out in the same layout as the
+//! packages under `packages/`.
+
+use crate::emit::{emit_fusabi, FusabiOptions};
+use crate::manifest::{generate_with_manifest, GenerationManifest};
+use crate::merge::{CollisionPolicy, MergeTypes};
+use fusabi_type_providers::{GeneratedTypes, ProviderError, ProviderParams, ProviderResult, TypeProvider};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// File name of the rendered pack source
+pub const PACK_SOURCE_FILE: &str = "src/lib.fsx";
+
+/// File name of the generation manifests written with a pack
+pub const PACK_MANIFEST_FILE: &str = "pack.manifest.json";
+
+/// Declarative pack description
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackSpec {
+    /// Pack identity
+    pub pack: PackInfo,
+    /// Sources to generate, in order
+    #[serde(default)]
+    pub sources: Vec<PackSource>,
+}
+
+/// Pack identity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackInfo {
+    /// Package name
+    pub name: String,
+    /// Semantic version
+    pub version: String,
+    /// Short description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// One provider invocation in a pack
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackSource {
+    /// Provider name as registered with the builder
+    pub provider: String,
+    /// Source passed to `resolve_schema`
+    pub source: String,
+    /// Namespace passed to `generate_types`
+    pub namespace: String,
+    /// Provider params
+    #[serde(default)]
+    pub params: BTreeMap<String, String>,
+}
+
+impl PackSpec {
+    /// Parse a pack spec from TOML
+    pub fn from_toml(content: &str) -> ProviderResult<Self> {
+        toml::from_str(content)
+            .map_err(|e| ProviderError::ParseError(format!("Invalid pack spec: {}", e)))
+    }
+
+    /// Load a pack spec from a TOML file
+    pub fn load(path: impl AsRef<Path>) -> ProviderResult<Self> {
+        let content = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| ProviderError::IoError(e.to_string()))?;
+        Self::from_toml(&content)
+    }
+}
+
+struct RegisteredProvider {
+    name: String,
+    version: String,
+    provider: Box<dyn TypeProvider>,
+}
+
+/// Builds packs from a spec and a set of providers
+#[derive(Default)]
+pub struct PackBuilder {
+    providers: Vec<RegisteredProvider>,
+    options: FusabiOptions,
+}
+
+impl PackBuilder {
+    /// Create a builder with no providers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a provider under the name used in pack specs
+    pub fn with_provider(
+        mut self,
+        name: &str,
+        version: &str,
+        provider: impl TypeProvider + 'static,
+    ) -> Self {
+        self.register(name, version, Box::new(provider));
+        self
+    }
+
+    /// Register a boxed provider under the name used in pack specs
+    pub fn register(&mut self, name: &str, version: &str, provider: Box<dyn TypeProvider>) {
+        self.providers.retain(|p| p.name != name);
+        self.providers.push(RegisteredProvider {
+            name: name.to_string(),
+            version: version.to_string(),
+            provider,
+        });
+    }
+
+    /// Use custom options for the rendered Fusabi source
+    pub fn with_options(mut self, options: FusabiOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Run every source in the spec and assemble the pack
+    pub fn build(&self, spec: &PackSpec) -> ProviderResult<PackArtifact> {
+        semver::Version::parse(&spec.pack.version).map_err(|e| {
+            ProviderError::InvalidSource(format!(
+                "Pack version '{}' is not a semantic version: {}",
+                spec.pack.version, e
+            ))
+        })?;
+
+        if spec.sources.is_empty() {
+            return Err(ProviderError::InvalidSource(format!(
+                "Pack '{}' has no sources",
+                spec.pack.name
+            )));
+        }
+
+        let mut types = GeneratedTypes::new();
+        let mut manifests = Vec::new();
+
+        for entry in &spec.sources {
+            let registered = self
+                .providers
+                .iter()
+                .find(|p| p.name == entry.provider)
+                .ok_or_else(|| {
+                    ProviderError::InvalidSource(format!(
+                        "Pack source uses unknown provider '{}'",
+                        entry.provider
+                    ))
+                })?;
+
+            let mut params = ProviderParams::default();
+            for (key, value) in &entry.params {
+                params = params.with(key, value);
+            }
+
+            let (generated, manifest) = generate_with_manifest(
+                registered.provider.as_ref(),
+                &registered.version,
+                &entry.source,
+                &params,
+                &entry.namespace,
+            )?;
+
+            types.merge(generated, &CollisionPolicy::Error)?;
+            manifests.push(manifest);
+        }
+
+        let source = emit_fusabi(&types, &self.options);
+
+        Ok(PackArtifact {
+            info: spec.pack.clone(),
+            types,
+            source,
+            manifests,
+        })
+    }
+}
+
+/// A built pack
+#[derive(Debug, Clone)]
+pub struct PackArtifact {
+    /// Pack identity
+    pub info: PackInfo,
+    /// Merged generated types
+    pub types: GeneratedTypes,
+    /// Rendered Fusabi source
+    pub source: String,
+    /// One generation manifest per pack source
+    pub manifests: Vec<GenerationManifest>,
+}
+
+#[derive(Serialize)]
+struct PackageToml<'a> {
+    package: &'a PackInfo,
+    pack: PackProvenance,
+}
+
+#[derive(Serialize)]
+struct PackProvenance {
+    generated: bool,
+    providers: Vec<String>,
+}
+
+impl PackArtifact {
+    /// Total number of types in the pack
+    pub fn type_count(&self) -> usize {
+        self.manifests.iter().map(GenerationManifest::type_count).sum()
+    }
+
+    /// Contents of the pack's `fusabi.toml`
+    pub fn package_toml(&self) -> ProviderResult<String> {
+        let mut providers: Vec<String> = self
+            .manifests
+            .iter()
+            .map(|m| format!("{}@{}", m.provider.name, m.provider.version))
+            .collect();
+        providers.sort();
+        providers.dedup();
+
+        toml::to_string(&PackageToml {
+            package: &self.info,
+            pack: PackProvenance {
+                generated: true,
+                providers,
+            },
+        })
+        .map_err(|e| ProviderError::ParseError(format!("Failed to render fusabi.toml: {}", e)))
+    }
+
+    /// Write the pack as `<dir>/<name>/` in the package layout
+    ///
+    /// Produces `fusabi.toml`, `src/lib.fsx` and `pack.manifest.json`, and
+    /// returns the package directory.
+    pub fn write_to(&self, dir: impl AsRef<Path>) -> ProviderResult<PathBuf> {
+        let package_dir = dir.as_ref().join(&self.info.name);
+        let io = |e: std::io::Error| ProviderError::IoError(e.to_string());
+
+        std::fs::create_dir_all(package_dir.join("src")).map_err(io)?;
+        std::fs::write(package_dir.join("fusabi.toml"), self.package_toml()?).map_err(io)?;
+        std::fs::write(package_dir.join(PACK_SOURCE_FILE), &self.source).map_err(io)?;
+
+        let manifests = serde_json::to_string_pretty(&self.manifests)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        std::fs::write(package_dir.join(PACK_MANIFEST_FILE), manifests).map_err(io)?;
+
+        Ok(package_dir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_type_providers::{GeneratedModule, RecordDef, Schema, TypeDefinition, TypeExpr};
+
+    /// Generates one record named after the source
+    struct EchoProvider;
+
+    impl TypeProvider for EchoProvider {
+        fn name(&self) -> &str {
+            "EchoProvider"
+        }
+
+        fn resolve_schema(&self, source: &str, _params: &ProviderParams) -> ProviderResult<Schema> {
+            Ok(Schema::Custom(source.to_string()))
+        }
+
+        fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+            let Schema::Custom(name) = schema else {
+                return Err(ProviderError::ParseError("Expected custom schema".to_string()));
+            };
+            let mut result = GeneratedTypes::new();
+            let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+            module.types.push(TypeDefinition::Record(RecordDef {
+                name: name.clone(),
+                fields: vec![("id".to_string(), TypeExpr::Named("int".to_string()))],
+            }));
+            result.modules.push(module);
+            Ok(result)
+        }
+    }
+
+    const SPEC: &str = r#"
+        [pack]
+        name = "demo"
+        version = "0.1.0"
+        description = "Demo pack"
+
+        [[sources]]
+        provider = "echo"
+        source = "User"
+        namespace = "Demo"
+
+        [[sources]]
+        provider = "echo"
+        source = "Order"
+        namespace = "Demo"
+        params = { mode = "test" }
+    "#;
+
+    #[test]
+    fn test_parse_spec() {
+        let spec = PackSpec::from_toml(SPEC).unwrap();
+        assert_eq!(spec.pack.name, "demo");
+        assert_eq!(spec.sources.len(), 2);
+        assert_eq!(spec.sources[1].params.get("mode").map(String::as_str), Some("test"));
+    }
+
+    #[test]
+    fn test_build_merges_modules() {
+        let spec = PackSpec::from_toml(SPEC).unwrap();
+        let artifact = PackBuilder::new()
+            .with_provider("echo", "1.2.3", EchoProvider)
+            .build(&spec)
+            .unwrap();
+
+        assert_eq!(artifact.types.modules.len(), 1);
+        assert_eq!(artifact.types.modules[0].types.len(), 2);
+        assert_eq!(artifact.type_count(), 2);
+        assert_eq!(artifact.manifests.len(), 2);
+        assert!(artifact.source.contains("module Demo =\n\n    type User = {"));
+        assert!(artifact.source.contains("    type Order = {"));
+
+        let package_toml = artifact.package_toml().unwrap();
+        assert!(package_toml.contains("name = \"demo\""));
+        assert!(package_toml.contains("\"EchoProvider@1.2.3\""));
+    }
+
+    #[test]
+    fn test_build_errors() {
+        let builder = PackBuilder::new().with_provider("echo", "1.2.3", EchoProvider);
+
+        let mut spec = PackSpec::from_toml(SPEC).unwrap();
+        spec.sources[1].source = "User".to_string();
+        let err = builder.build(&spec).unwrap_err();
+        assert!(err.to_string().contains("'User' is generated twice in module Demo"));
+
+        let mut spec = PackSpec::from_toml(SPEC).unwrap();
+        spec.sources[0].provider = "missing".to_string();
+        assert!(builder.build(&spec).is_err());
+
+        let mut spec = PackSpec::from_toml(SPEC).unwrap();
+        spec.pack.version = "latest".to_string();
+        assert!(builder.build(&spec).is_err());
+    }
+
+    #[test]
+    fn test_write_package() {
+        let spec = PackSpec::from_toml(SPEC).unwrap();
+        let artifact = PackBuilder::new()
+            .with_provider("echo", "1.2.3", EchoProvider)
+            .build(&spec)
+            .unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let package_dir = artifact.write_to(dir.path()).unwrap();
+
+        assert_eq!(package_dir, dir.path().join("demo"));
+        assert!(package_dir.join("fusabi.toml").exists());
+        assert_eq!(
+            std::fs::read_to_string(package_dir.join(PACK_SOURCE_FILE)).unwrap(),
+            artifact.source
+        );
+
+        let manifests: Vec<GenerationManifest> = serde_json::from_str(
+            &std::fs::read_to_string(package_dir.join(PACK_MANIFEST_FILE)).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(manifests, artifact.manifests);
+    }
+}
@@ -0,0 +1,400 @@
+//! Declared provider params with validation
+//!
+//! `ProviderParams` is an untyped string map, so a misspelled key or a
+//! malformed value is silently ignored. Providers that declare the params
+//! they accept in a [`ParamSchema`] get them checked up front: unknown keys
+//! are rejected with the closest accepted key as a suggestion, values are
+//! checked against their [`ParamKind`], and defaults are filled in.
+//!
+//! The source, cache and credential params read by [`read_source`]
+//! (`fetch_timeout`, `cache_ttl`, `auth`, ...), the `naming` param, the
+//! field override params (`overrides`, `rename`, `exclude`),
+//! `keyword_escape`, `fix_identifiers`, `module_layout`, the transform params
+//! (`strip_fields`, `base_fields`, `type_prefix`) and the input limits
+//! (`max_depth`, `max_types`) are accepted by every schema and validated by their own
+//! option parsers.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_common::params::{ParamKind, ParamSchema, ParamSpec};
+//!
+//! let schema = ParamSchema::new("CsvProvider")
+//!     .param(ParamSpec::new("has_headers", ParamKind::Bool).default("true"))
+//!     .param(ParamSpec::new("sample_rows", ParamKind::UInt).default("100"));
+//!
+//! let params = schema.validate(&ProviderParams::default().with("sample_rows", "20"))?;
+//! assert_eq!(params.uint("sample_rows"), Some(20));
+//! assert_eq!(params.bool("has_headers"), Some(true));
+//! ```
+//!
+//! [`read_source`]: crate::source::read_source
+
+use crate::cache::CacheOptions;
+use crate::credentials::{AUTH_PARAM, AUTH_SCHEME_PARAM};
+use crate::identifiers::{fix_from_params, KeywordEscape, FIX_IDENTIFIERS_PARAM, KEYWORD_ESCAPE_PARAM};
+use crate::layout::{ModuleGrouping, MODULE_LAYOUT_PARAM};
+use crate::limits::{InputLimits, MAX_DEPTH_PARAM, MAX_TYPES_PARAM};
+use crate::naming::{Naming, NAMING_PARAM};
+use crate::overrides::{FieldOverrides, EXCLUDE_PARAM, OVERRIDES_PARAM, RENAME_PARAM};
+use crate::source::{parse_duration, SourceOptions};
+use crate::transform::{TransformChain, BASE_FIELDS_PARAM, STRIP_FIELDS_PARAM, TYPE_PREFIX_PARAM};
+use fusabi_type_providers::{ProviderError, ProviderParams, ProviderResult, TypeProvider};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Params read by the shared source, cache, credential, naming and override
+/// layers
+pub const SHARED_PARAMS: &[&str] = &[
+    "fetch_timeout",
+    "max_source_bytes",
+    "follow_redirects",
+    "cache_dir",
+    "cache_ttl",
+    "refresh",
+    "offline",
+    AUTH_PARAM,
+    AUTH_SCHEME_PARAM,
+    NAMING_PARAM,
+    OVERRIDES_PARAM,
+    RENAME_PARAM,
+    EXCLUDE_PARAM,
+    FIX_IDENTIFIERS_PARAM,
+    KEYWORD_ESCAPE_PARAM,
+    MODULE_LAYOUT_PARAM,
+    STRIP_FIELDS_PARAM,
+    BASE_FIELDS_PARAM,
+    TYPE_PREFIX_PARAM,
+    MAX_DEPTH_PARAM,
+    MAX_TYPES_PARAM,
+];
+
+/// Accepted format of a param value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamKind {
+    /// Any text
+    String,
+    /// `true` or `false`
+    Bool,
+    /// Non-negative integer
+    UInt,
+    /// Duration such as `500ms`, `5s`, `10m` or `1h`
+    Duration,
+    /// Comma-separated list
+    List,
+    /// One of a fixed set of values
+    Choice(&'static [&'static str]),
+}
+
+impl ParamKind {
+    fn accepts(&self, value: &str) -> bool {
+        match self {
+            ParamKind::String | ParamKind::List => true,
+            ParamKind::Bool => value.parse::<bool>().is_ok(),
+            ParamKind::UInt => value.trim().parse::<u64>().is_ok(),
+            ParamKind::Duration => parse_duration(value).is_some(),
+            ParamKind::Choice(choices) => choices.contains(&value),
+        }
+    }
+
+    /// What a valid value looks like, for error messages
+    pub fn expected(&self) -> String {
+        match self {
+            ParamKind::String => "text".to_string(),
+            ParamKind::Bool => "true or false".to_string(),
+            ParamKind::UInt => "a non-negative integer".to_string(),
+            ParamKind::Duration => "a duration such as 500ms, 5s or 10m".to_string(),
+            ParamKind::List => "a comma-separated list".to_string(),
+            ParamKind::Choice(choices) => format!("one of {}", choices.join(", ")),
+        }
+    }
+}
+
+/// A param a provider accepts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParamSpec {
+    /// Params key
+    pub key: &'static str,
+    /// Accepted format
+    pub kind: ParamKind,
+    /// Value used when the param is not given
+    pub default: Option<&'static str>,
+    /// One-line description
+    pub description: &'static str,
+}
+
+impl ParamSpec {
+    /// Declare a param without default or description
+    pub fn new(key: &'static str, kind: ParamKind) -> Self {
+        Self {
+            key,
+            kind,
+            default: None,
+            description: "",
+        }
+    }
+
+    /// Use `value` when the param is not given
+    pub fn default(mut self, value: &'static str) -> Self {
+        self.default = Some(value);
+        self
+    }
+
+    /// Describe the param
+    pub fn describe(mut self, description: &'static str) -> Self {
+        self.description = description;
+        self
+    }
+}
+
+/// The params a provider accepts
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParamSchema {
+    provider: String,
+    specs: Vec<ParamSpec>,
+}
+
+impl ParamSchema {
+    /// Create an empty schema for the provider named `provider`
+    pub fn new(provider: &str) -> Self {
+        Self {
+            provider: provider.to_string(),
+            specs: Vec::new(),
+        }
+    }
+
+    /// Accept a param
+    pub fn param(mut self, spec: ParamSpec) -> Self {
+        self.specs.push(spec);
+        self
+    }
+
+    /// Declared params, in declaration order
+    pub fn specs(&self) -> &[ParamSpec] {
+        &self.specs
+    }
+
+    /// Declaration of `key`
+    pub fn spec(&self, key: &str) -> Option<&ParamSpec> {
+        self.specs.iter().find(|spec| spec.key == key)
+    }
+
+    /// Check params against the schema and fill in defaults
+    ///
+    /// Every problem is reported in a single error.
+    pub fn validate(&self, params: &ProviderParams) -> ProviderResult<TypedParams> {
+        let mut problems = Vec::new();
+        let mut values = BTreeMap::new();
+
+        let mut given: Vec<_> = params.custom.iter().collect();
+        given.sort();
+        for (key, value) in given {
+            if SHARED_PARAMS.contains(&key.as_str()) {
+                continue;
+            }
+            match self.spec(key) {
+                Some(spec) if spec.kind.accepts(value) => {
+                    values.insert(spec.key, value.clone());
+                }
+                Some(spec) => problems.push(format!(
+                    "Invalid {} '{}': expected {}",
+                    key,
+                    value,
+                    spec.kind.expected()
+                )),
+                None => problems.push(self.unknown(key)),
+            }
+        }
+
+        for spec in &self.specs {
+            if let Some(default) = spec.default {
+                values.entry(spec.key).or_insert_with(|| default.to_string());
+            }
+        }
+
+        for shared in [
+            SourceOptions::from_params(params).err(),
+            CacheOptions::from_params(params).err(),
+            Naming::from_params(params).err(),
+            FieldOverrides::from_params(params).err(),
+            fix_from_params(params).err(),
+            KeywordEscape::from_params(params).err(),
+            ModuleGrouping::from_params(params).err(),
+            TransformChain::from_params(params).err(),
+            InputLimits::from_params(params).err(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            // `max_source_bytes` is read by both the source options and the limits
+            let shared = message(shared);
+            if !problems.contains(&shared) {
+                problems.push(shared);
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(TypedParams { values })
+        } else {
+            Err(ProviderError::InvalidSource(problems.join("; ")))
+        }
+    }
+
+    fn unknown(&self, key: &str) -> String {
+        let mut text = format!("Unknown param '{}' for {}", key, self.provider);
+        let closest = self
+            .specs
+            .iter()
+            .map(|spec| spec.key)
+            .chain(SHARED_PARAMS.iter().copied())
+            .map(|candidate| (edit_distance(key, candidate), candidate))
+            .min();
+        match closest {
+            Some((distance, candidate)) if distance <= 2 => {
+                text.push_str(&format!("; did you mean '{}'?", candidate));
+            }
+            _ if self.specs.is_empty() => text.push_str("; it accepts no params of its own"),
+            _ => {
+                let keys: Vec<_> = self.specs.iter().map(|spec| spec.key).collect();
+                text.push_str(&format!("; accepted params: {}", keys.join(", ")));
+            }
+        }
+        text
+    }
+}
+
+/// Validated params with defaults applied
+///
+/// Accessors return `None` for params that were neither given nor have a
+/// default, and for keys that are not declared with a matching kind.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TypedParams {
+    values: BTreeMap<&'static str, String>,
+}
+
+impl TypedParams {
+    /// Raw value of a param
+    pub fn str(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(String::as_str)
+    }
+
+    /// Value of a [`ParamKind::Bool`] param
+    pub fn bool(&self, key: &str) -> Option<bool> {
+        self.str(key).and_then(|value| value.parse().ok())
+    }
+
+    /// Value of a [`ParamKind::UInt`] param
+    pub fn uint(&self, key: &str) -> Option<u64> {
+        self.str(key).and_then(|value| value.trim().parse().ok())
+    }
+
+    /// Value of a [`ParamKind::Duration`] param
+    pub fn duration(&self, key: &str) -> Option<Duration> {
+        self.str(key).and_then(parse_duration)
+    }
+
+    /// Trimmed, non-empty items of a [`ParamKind::List`] param
+    pub fn list(&self, key: &str) -> Option<Vec<&str>> {
+        self.str(key)
+            .map(|value| value.split(',').map(str::trim).filter(|item| !item.is_empty()).collect())
+    }
+}
+
+/// Type providers that declare the params they accept
+pub trait ParameterizedProvider: TypeProvider {
+    /// Params accepted by `resolve_schema`
+    fn param_schema(&self) -> ParamSchema;
+}
+
+fn message(error: ProviderError) -> String {
+    match error {
+        ProviderError::InvalidSource(message) => message,
+        other => other.to_string(),
+    }
+}
+
+/// Levenshtein distance between two keys
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> ParamSchema {
+        ParamSchema::new("CsvProvider")
+            .param(ParamSpec::new("delimiter", ParamKind::String).default(","))
+            .param(ParamSpec::new("has_headers", ParamKind::Bool).default("true"))
+            .param(ParamSpec::new("sample_rows", ParamKind::UInt))
+            .param(ParamSpec::new("headers", ParamKind::List))
+            .param(ParamSpec::new("mode", ParamKind::Choice(&["file", "embedded"])))
+    }
+
+    #[test]
+    fn test_defaults_and_accessors() {
+        let params = ProviderParams::default()
+            .with("sample_rows", "20")
+            .with("headers", "id, name,,")
+            .with("fetch_timeout", "5s");
+        let typed = schema().validate(&params).unwrap();
+
+        assert_eq!(typed.str("delimiter"), Some(","));
+        assert_eq!(typed.bool("has_headers"), Some(true));
+        assert_eq!(typed.uint("sample_rows"), Some(20));
+        assert_eq!(typed.list("headers"), Some(vec!["id", "name"]));
+        assert_eq!(typed.str("mode"), None);
+        assert_eq!(typed.str("fetch_timeout"), None);
+    }
+
+    #[test]
+    fn test_rejects_unknown_and_malformed() {
+        let params = ProviderParams::default().with("delimter", ";");
+        let err = schema().validate(&params).unwrap_err().to_string();
+        assert!(err.contains("Unknown param 'delimter' for CsvProvider; did you mean 'delimiter'?"));
+
+        let params = ProviderParams::default().with("colour", "red");
+        let err = schema().validate(&params).unwrap_err().to_string();
+        assert!(err.contains("accepted params: delimiter, has_headers"));
+
+        let params = ProviderParams::default()
+            .with("has_headers", "yes")
+            .with("mode", "remote")
+            .with("cache_ttl", "soon")
+            .with("naming", "kebab")
+            .with("rename", "user_id");
+        let err = schema().validate(&params).unwrap_err().to_string();
+        assert!(err.contains("Invalid has_headers 'yes': expected true or false"));
+        assert!(err.contains("Invalid mode 'remote': expected one of file, embedded"));
+        assert!(err.contains("Invalid cache_ttl 'soon'"));
+        assert!(err.contains("Invalid naming 'kebab'"));
+        assert!(err.contains("Invalid rename 'user_id'"));
+
+        let err = ParamSchema::new("RegexProvider")
+            .validate(&ProviderParams::default().with("flags", "i"))
+            .unwrap_err();
+        assert!(err.to_string().contains("accepts no params of its own"));
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("delimter", "delimiter"), 1);
+        assert_eq!(edit_distance("offline", "offline"), 0);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+}
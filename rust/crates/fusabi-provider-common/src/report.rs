@@ -0,0 +1,257 @@
+//! Generation reports
+//!
+//! A [`GenerationReport`] is returned next to [`GeneratedTypes`] by
+//! [`SourceValidator::generate_with_report`] and summarizes what a provider
+//! produced and, more importantly, what it silently degraded or dropped:
+//!
+//! - types and fields generated per module
+//! - fields coerced to `any` or left without a mapping
+//! - lossy mappings such as `u64` to `int` or `DECIMAL` to `float`
+//! - skipped constructs such as Protobuf services or SQL foreign keys
+//!
+//! [`SourceValidator::generate_with_report`]: crate::validate::SourceValidator::generate_with_report
+
+use crate::validate::{Diagnostic, Severity};
+use fusabi_type_providers::{GeneratedTypes, TypeDefinition};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// What a diagnostic says about the generated output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FindingKind {
+    /// A value lost its type and was generated as `any` or left unmapped
+    Coerced,
+    /// A value was mapped to a type that cannot represent all of it
+    Lossy,
+    /// A source construct produced no types at all
+    Skipped,
+    /// Anything else
+    Other,
+}
+
+impl FindingKind {
+    /// Classify a diagnostic code
+    pub fn of(code: &str) -> Self {
+        match code {
+            "coerced-any" | "unmapped-type" => Self::Coerced,
+            "lossy-conversion" => Self::Lossy,
+            "unsupported-keyword" => Self::Skipped,
+            _ if code.ends_with("-skipped") || code.ends_with("-ignored") => Self::Skipped,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Counts for one generated module
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModuleStats {
+    /// Module path (empty for root types)
+    pub path: Vec<String>,
+    /// Number of types
+    pub types: usize,
+    /// Number of record fields and union variants
+    pub members: usize,
+}
+
+/// Statistics and findings for a single generation run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenerationReport {
+    /// Provider that generated the types
+    pub provider: String,
+    /// Namespace the types were generated into
+    pub namespace: String,
+    /// Per-module counts, root types first
+    pub modules: Vec<ModuleStats>,
+    /// Provider and generic diagnostics, in the order they were produced
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl GenerationReport {
+    /// Build a report from generated output and its diagnostics
+    pub fn new(
+        provider: &str,
+        namespace: &str,
+        types: &GeneratedTypes,
+        diagnostics: Vec<Diagnostic>,
+    ) -> Self {
+        let mut modules = Vec::new();
+        if !types.root_types.is_empty() {
+            modules.push(ModuleStats::new(Vec::new(), &types.root_types));
+        }
+        modules.extend(
+            types
+                .modules
+                .iter()
+                .map(|m| ModuleStats::new(m.path.clone(), &m.types)),
+        );
+
+        Self {
+            provider: provider.to_string(),
+            namespace: namespace.to_string(),
+            modules,
+            diagnostics,
+        }
+    }
+
+    /// Total number of generated types
+    pub fn type_count(&self) -> usize {
+        self.modules.iter().map(|m| m.types).sum()
+    }
+
+    /// Fields generated as `any` or left without a mapping
+    pub fn coerced(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.of_kind(FindingKind::Coerced)
+    }
+
+    /// Lossy mappings
+    pub fn lossy(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.of_kind(FindingKind::Lossy)
+    }
+
+    /// Source constructs that produced no types
+    pub fn skipped(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.of_kind(FindingKind::Skipped)
+    }
+
+    /// Whether nothing was coerced, lost or skipped
+    pub fn is_lossless(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .all(|d| FindingKind::of(&d.code) == FindingKind::Other)
+    }
+
+    /// Serialize the report to pretty-printed JSON
+    pub fn to_json(&self) -> String {
+        // Only plain strings and numbers, so serialization cannot fail
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    fn of_kind(&self, kind: FindingKind) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(move |d| FindingKind::of(&d.code) == kind)
+    }
+}
+
+impl ModuleStats {
+    fn new(path: Vec<String>, types: &[TypeDefinition]) -> Self {
+        let members = types
+            .iter()
+            .map(|t| match t {
+                TypeDefinition::Record(record) => record.fields.len(),
+                TypeDefinition::Du(du) => du.variants.len(),
+            })
+            .sum();
+
+        Self {
+            path,
+            types: types.len(),
+            members,
+        }
+    }
+}
+
+impl fmt::Display for GenerationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{}: {} types in {} modules",
+            self.provider,
+            self.type_count(),
+            self.modules.len()
+        )?;
+
+        for module in &self.modules {
+            let path = if module.path.is_empty() {
+                "<root>".to_string()
+            } else {
+                module.path.join(".")
+            };
+            writeln!(f, "  {}: {} types, {} members", path, module.types, module.members)?;
+        }
+
+        writeln!(
+            f,
+            "  coerced: {}, lossy: {}, skipped: {}",
+            self.coerced().count(),
+            self.lossy().count(),
+            self.skipped().count()
+        )?;
+
+        for diagnostic in &self.diagnostics {
+            let severity = match diagnostic.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Info => "info",
+            };
+            match &diagnostic.location {
+                Some(location) => writeln!(
+                    f,
+                    "  {} [{}] {}: {}",
+                    severity, diagnostic.code, location, diagnostic.message
+                )?,
+                None => writeln!(f, "  {} [{}] {}", severity, diagnostic.code, diagnostic.message)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_type_providers::{DuDef, GeneratedModule, RecordDef, TypeExpr, VariantDef};
+
+    #[test]
+    fn test_classify_codes() {
+        assert_eq!(FindingKind::of("coerced-any"), FindingKind::Coerced);
+        assert_eq!(FindingKind::of("unmapped-type"), FindingKind::Coerced);
+        assert_eq!(FindingKind::of("lossy-conversion"), FindingKind::Lossy);
+        assert_eq!(FindingKind::of("service-skipped"), FindingKind::Skipped);
+        assert_eq!(FindingKind::of("foreign-key-ignored"), FindingKind::Skipped);
+        assert_eq!(FindingKind::of("no-tables"), FindingKind::Other);
+    }
+
+    #[test]
+    fn test_module_stats_and_summary() {
+        let mut types = GeneratedTypes::new();
+        types.root_types.push(TypeDefinition::Record(RecordDef {
+            name: "Root".to_string(),
+            fields: vec![("id".to_string(), TypeExpr::Named("int".to_string()))],
+        }));
+        let mut module = GeneratedModule::new(vec!["Db".to_string()]);
+        module.types.push(TypeDefinition::Du(DuDef {
+            name: "Status".to_string(),
+            variants: vec![
+                VariantDef::new_simple("Active".to_string()),
+                VariantDef::new_simple("Deleted".to_string()),
+            ],
+        }));
+        types.modules.push(module);
+
+        let report = GenerationReport::new(
+            "TestProvider",
+            "Db",
+            &types,
+            vec![
+                Diagnostic::warning("lossy-conversion", "u64 is typed as `int`").at("Root.id"),
+                Diagnostic::info("service-skipped", "Service `Api` does not generate types"),
+            ],
+        );
+
+        assert_eq!(report.type_count(), 2);
+        assert_eq!(report.modules[0].path, Vec::<String>::new());
+        assert_eq!(report.modules[1].members, 2);
+        assert_eq!(report.lossy().count(), 1);
+        assert_eq!(report.skipped().count(), 1);
+        assert_eq!(report.coerced().count(), 0);
+        assert!(!report.is_lossless());
+
+        let summary = report.to_string();
+        assert!(summary.starts_with("TestProvider: 2 types in 2 modules\n"));
+        assert!(summary.contains("  Db: 1 types, 2 members\n"));
+        assert!(summary.contains("warning [lossy-conversion] Root.id: u64 is typed as `int`"));
+    }
+}
@@ -0,0 +1,36 @@
+//! Provider schemas from Fusabi types
+//!
+//! Reverse mode: types maintained by hand in Fusabi, or generated by one
+//! provider, are exported as a source another team's tooling reads. A
+//! provider that can write its own source format implements
+//! [`SchemaExporter`]; feeding the export back through `resolve_schema` and
+//! `generate_types` gives back the same records and fields, up to naming,
+//! though types the format has no counterpart for are widened on the way.
+//!
+//! JSON Schema export does not depend on any provider and is also
+//! available directly through [`to_json_schema`](crate::json_schema::to_json_schema).
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_common::SchemaExporter;
+//! use fusabi_provider_sql::SqlProvider;
+//!
+//! let provider = SqlProvider::new();
+//! let params = ProviderParams::default().with("dialect", "postgres");
+//! let ddl = provider.export_schema(&types, &params)?;
+//! std::fs::write(format!("schema.{}", provider.schema_extension()), ddl)?;
+//! ```
+
+use fusabi_type_providers::{GeneratedTypes, ProviderParams, ProviderResult, TypeProvider};
+
+/// Type providers that can write their source format from generated types
+pub trait SchemaExporter: TypeProvider {
+    /// File extension of exported schemas, without the leading dot
+    fn schema_extension(&self) -> &str;
+
+    /// Render `types` as a source `resolve_schema` accepts
+    ///
+    /// `params` selects format options, such as the SQL dialect.
+    fn export_schema(&self, types: &GeneratedTypes, params: &ProviderParams) -> ProviderResult<String>;
+}
@@ -0,0 +1,581 @@
+//! Structured view of type expressions
+//!
+//! `TypeExpr`, defined upstream in `fusabi-type-providers`, only carries a
+//! rendered type string such as `"Map<string, string> option"`. Providers build their types as
+//! [`TypeShape`] trees and convert them with `.into()`, so that no provider
+//! writes type syntax by hand and operands are parenthesized where needed:
+//!
+//! ```rust,ignore
+//! let labels: TypeExpr = TypeShape::map(TypeShape::named("string"), TypeShape::named("string"))
+//!     .option()
+//!     .into();
+//! ```
+//!
+//! [`TypeShape::Named`] is both a primitive and a reference to a generated
+//! type. Static tables of spec-defined fields keep the rendered form and go
+//! through [`TypeShape::parse`], which also turns rendered strings back into
+//! a tree so that exporters and emitters can work on the structure instead
+//! of re-parsing text.
+//!
+//! Lists render postfix (`string list`). Providers whose published output
+//! writes them as `list<string>` or `List<string>` keep that form with
+//! [`TypeShape::into_type_expr`] and a [`ListSyntax`].
+//!
+//! [`TypeShape::Param`] and [`TypeShape::Generic`] refer to the type
+//! parameters and instantiations of generic definitions, see
+//! [`generics`](crate::generics).
+
+use fusabi_type_providers::TypeExpr;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Built-in Fusabi primitive type names
+pub const PRIMITIVES: &[&str] = &[
+    "string", "int", "int64", "uint", "uint64", "float", "bool", "bytes", "unit", "any",
+];
+
+/// How [`TypeShape::List`] is written when rendered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ListSyntax {
+    /// `T list`
+    #[default]
+    Postfix,
+    /// `name<T>`, e.g. `list<T>` or `List<T>`
+    Generic(&'static str),
+}
+
+/// Structured type expression
+///
+/// Serializes tagged by `kind` with the operands under `of`, e.g.
+/// `{"kind": "list", "of": {"kind": "named", "of": "int"}}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "of", rename_all = "snake_case")]
+pub enum TypeShape {
+    /// Primitive or named type reference
+    Named(String),
+    /// Optional value (`T option`)
+    Option(Box<TypeShape>),
+    /// List of values (`T list` or `list<T>`)
+    List(Box<TypeShape>),
+    /// Map from keys to values (`Map<K, V>`)
+    Map(Box<TypeShape>, Box<TypeShape>),
+    /// Tuple of values (`A * B`)
+    Tuple(Vec<TypeShape>),
+    /// String literal type (`"text"`)
+    Literal(String),
+    /// Union of alternatives (`A | B`)
+    Union(Vec<TypeShape>),
+    /// Type parameter of a generic definition (`'node`), without the quote
+    Param(String),
+    /// Instantiation of a generic definition (`Connection<User>`)
+    Generic(String, Vec<TypeShape>),
+}
+
+impl TypeShape {
+    /// A primitive or a reference to a named type
+    pub fn named(name: impl Into<String>) -> Self {
+        TypeShape::Named(name.into())
+    }
+
+    /// `K` to `V` map
+    pub fn map(key: TypeShape, value: TypeShape) -> Self {
+        TypeShape::Map(Box::new(key), Box::new(value))
+    }
+
+    /// Type parameter `'name`
+    pub fn param(name: impl Into<String>) -> Self {
+        TypeShape::Param(name.into())
+    }
+
+    /// Generic definition `name` applied to `args`
+    pub fn generic(name: impl Into<String>, args: Vec<TypeShape>) -> Self {
+        TypeShape::Generic(name.into(), args)
+    }
+
+    /// `A * B ...` tuple
+    pub fn tuple(items: Vec<TypeShape>) -> Self {
+        TypeShape::Tuple(items)
+    }
+
+    /// This shape wrapped in `option`
+    pub fn option(self) -> Self {
+        TypeShape::Option(Box::new(self))
+    }
+
+    /// This shape wrapped in `list`
+    pub fn list(self) -> Self {
+        TypeShape::List(Box::new(self))
+    }
+
+    /// This shape wrapped in `option` unless `required`
+    pub fn option_unless(self, required: bool) -> Self {
+        if required { self } else { self.option() }
+    }
+
+    /// Render with lists written in `lists`; `Display` uses
+    /// [`ListSyntax::Postfix`]
+    pub fn render(&self, lists: ListSyntax) -> String {
+        Rendered { shape: self, lists }.to_string()
+    }
+
+    /// Convert to a `TypeExpr` with lists written in `lists`
+    pub fn into_type_expr(self, lists: ListSyntax) -> TypeExpr {
+        TypeExpr::Named(self.render(lists))
+    }
+
+    /// Parse a type expression string
+    ///
+    /// Unparseable input is kept as a single [`TypeShape::Named`] so that no
+    /// information is lost.
+    pub fn parse(input: &str) -> Self {
+        let Some(tokens) = tokenize(input) else {
+            return TypeShape::Named(input.trim().to_string());
+        };
+        let mut parser = ShapeParser { tokens, pos: 0 };
+        match parser.parse_union() {
+            Some(shape) if parser.pos == parser.tokens.len() => shape,
+            _ => TypeShape::Named(input.trim().to_string()),
+        }
+    }
+
+    /// Parse the rendered form of a `TypeExpr`
+    pub fn from_type_expr(expr: &TypeExpr) -> Self {
+        Self::parse(&expr.to_string())
+    }
+
+    /// Whether this shape is a built-in primitive
+    pub fn is_primitive(&self) -> bool {
+        matches!(self, TypeShape::Named(name) if PRIMITIVES.contains(&name.as_str()))
+    }
+
+    /// Whether this shape is optional
+    pub fn is_option(&self) -> bool {
+        matches!(self, TypeShape::Option(_))
+    }
+
+    /// Strip one level of `option`, if present
+    pub fn unwrap_option(&self) -> &TypeShape {
+        match self {
+            TypeShape::Option(inner) => inner,
+            other => other,
+        }
+    }
+
+    /// Names of all non-primitive types referenced by this shape
+    pub fn references(&self) -> Vec<&str> {
+        let mut refs = Vec::new();
+        self.collect_references(&mut refs);
+        refs
+    }
+
+    /// Names of all type parameters used by this shape, without the quote
+    pub fn params(&self) -> Vec<&str> {
+        let mut params = Vec::new();
+        self.collect_params(&mut params);
+        params
+    }
+
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a str>) {
+        match self {
+            TypeShape::Param(name) => params.push(name),
+            TypeShape::Option(inner) | TypeShape::List(inner) => inner.collect_params(params),
+            TypeShape::Map(key, value) => {
+                key.collect_params(params);
+                value.collect_params(params);
+            }
+            TypeShape::Tuple(items) | TypeShape::Union(items) | TypeShape::Generic(_, items) => {
+                for item in items {
+                    item.collect_params(params);
+                }
+            }
+            TypeShape::Named(_) | TypeShape::Literal(_) => {}
+        }
+    }
+
+    fn collect_references<'a>(&'a self, refs: &mut Vec<&'a str>) {
+        match self {
+            TypeShape::Named(name) => {
+                if !PRIMITIVES.contains(&name.as_str()) {
+                    refs.push(name);
+                }
+            }
+            TypeShape::Option(inner) | TypeShape::List(inner) => inner.collect_references(refs),
+            TypeShape::Map(key, value) => {
+                key.collect_references(refs);
+                value.collect_references(refs);
+            }
+            TypeShape::Tuple(items) | TypeShape::Union(items) => {
+                for item in items {
+                    item.collect_references(refs);
+                }
+            }
+            TypeShape::Generic(name, args) => {
+                refs.push(name);
+                for arg in args {
+                    arg.collect_references(refs);
+                }
+            }
+            TypeShape::Literal(_) | TypeShape::Param(_) => {}
+        }
+    }
+}
+
+impl fmt::Display for TypeShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Rendered {
+            shape: self,
+            lists: ListSyntax::Postfix,
+        }
+        .fmt(f)
+    }
+}
+
+impl From<TypeShape> for TypeExpr {
+    fn from(shape: TypeShape) -> Self {
+        TypeExpr::Named(shape.to_string())
+    }
+}
+
+/// Renders a shape with lists in a given syntax
+struct Rendered<'a> {
+    shape: &'a TypeShape,
+    lists: ListSyntax,
+}
+
+impl<'a> Rendered<'a> {
+    fn of(&self, shape: &'a TypeShape) -> Self {
+        Rendered { shape, lists: self.lists }
+    }
+
+    /// Renders a shape as the operand of a postfix or tuple, parenthesizing if needed
+    fn operand(&self, shape: &'a TypeShape) -> String {
+        match shape {
+            TypeShape::Tuple(_) | TypeShape::Union(_) => format!("({})", self.of(shape)),
+            other => self.of(other).to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Rendered<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.shape {
+            TypeShape::Named(name) => write!(f, "{}", name),
+            TypeShape::Option(inner) => write!(f, "{} option", self.operand(inner)),
+            TypeShape::List(inner) => match self.lists {
+                ListSyntax::Postfix => write!(f, "{} list", self.operand(inner)),
+                ListSyntax::Generic(name) => write!(f, "{}<{}>", name, self.of(inner)),
+            },
+            TypeShape::Map(key, value) => write!(f, "Map<{}, {}>", self.of(key), self.of(value)),
+            TypeShape::Tuple(items) => {
+                let parts: Vec<String> = items.iter().map(|i| self.operand(i)).collect();
+                write!(f, "{}", parts.join(" * "))
+            }
+            TypeShape::Literal(text) => write!(f, "\"{}\"", text),
+            TypeShape::Union(items) => {
+                let parts: Vec<String> = items.iter().map(|i| self.of(i).to_string()).collect();
+                write!(f, "{}", parts.join(" | "))
+            }
+            TypeShape::Param(name) => write!(f, "'{}", name),
+            TypeShape::Generic(name, args) => {
+                let args: Vec<String> = args.iter().map(|a| self.of(a).to_string()).collect();
+                write!(f, "{}<{}>", name, args.join(", "))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Literal(String),
+    LeftAngle,
+    RightAngle,
+    LeftParen,
+    RightParen,
+    Comma,
+    Pipe,
+    Star,
+}
+
+fn tokenize(input: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&ch) = chars.peek() {
+        match ch {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '<' => {
+                chars.next();
+                tokens.push(Token::LeftAngle);
+            }
+            '>' => {
+                chars.next();
+                tokens.push(Token::RightAngle);
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::LeftParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::RightParen);
+            }
+            ',' => {
+                chars.next();
+                tokens.push(Token::Comma);
+            }
+            '|' => {
+                chars.next();
+                tokens.push(Token::Pipe);
+            }
+            '*' => {
+                chars.next();
+                tokens.push(Token::Star);
+            }
+            '"' => {
+                chars.next();
+                let mut text = String::new();
+                for c in chars.by_ref() {
+                    if c == '"' {
+                        break;
+                    }
+                    text.push(c);
+                }
+                tokens.push(Token::Literal(text));
+            }
+            _ => {
+                let mut ident = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_alphanumeric() || c == '_' || c == '.' || c == '\'' {
+                        ident.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                if ident.is_empty() {
+                    return None;
+                }
+                tokens.push(Token::Ident(ident));
+            }
+        }
+    }
+
+    Some(tokens)
+}
+
+struct ShapeParser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl ShapeParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_union(&mut self) -> Option<TypeShape> {
+        let mut items = vec![self.parse_tuple()?];
+        while self.eat(&Token::Pipe) {
+            items.push(self.parse_tuple()?);
+        }
+        Some(if items.len() == 1 { items.remove(0) } else { TypeShape::Union(items) })
+    }
+
+    fn parse_tuple(&mut self) -> Option<TypeShape> {
+        let mut items = vec![self.parse_postfix()?];
+        while self.eat(&Token::Star) {
+            items.push(self.parse_postfix()?);
+        }
+        Some(if items.len() == 1 { items.remove(0) } else { TypeShape::Tuple(items) })
+    }
+
+    fn parse_postfix(&mut self) -> Option<TypeShape> {
+        let mut shape = self.parse_atom()?;
+        loop {
+            match self.peek() {
+                Some(Token::Ident(word)) if word == "option" => {
+                    self.pos += 1;
+                    shape = TypeShape::Option(Box::new(shape));
+                }
+                Some(Token::Ident(word)) if word == "list" => {
+                    self.pos += 1;
+                    shape = TypeShape::List(Box::new(shape));
+                }
+                _ => return Some(shape),
+            }
+        }
+    }
+
+    fn parse_atom(&mut self) -> Option<TypeShape> {
+        match self.peek()?.clone() {
+            Token::Literal(text) => {
+                self.pos += 1;
+                Some(TypeShape::Literal(text))
+            }
+            Token::LeftParen => {
+                self.pos += 1;
+                let inner = self.parse_union()?;
+                self.eat(&Token::RightParen).then_some(inner)
+            }
+            Token::Ident(name) => {
+                self.pos += 1;
+                if let Some(param) = name.strip_prefix('\'') {
+                    return (!param.is_empty()).then(|| TypeShape::Param(param.to_string()));
+                }
+                if !self.eat(&Token::LeftAngle) {
+                    return Some(TypeShape::Named(name));
+                }
+
+                let mut args = vec![self.parse_union()?];
+                while self.eat(&Token::Comma) {
+                    args.push(self.parse_union()?);
+                }
+                if !self.eat(&Token::RightAngle) {
+                    return None;
+                }
+
+                match (name.to_lowercase().as_str(), args.len()) {
+                    ("list", 1) => Some(TypeShape::List(Box::new(args.remove(0)))),
+                    ("option", 1) => Some(TypeShape::Option(Box::new(args.remove(0)))),
+                    ("map", 2) => {
+                        let value = args.pop()?;
+                        let key = args.pop()?;
+                        Some(TypeShape::Map(Box::new(key), Box::new(value)))
+                    }
+                    ("list" | "option" | "map", _) => None,
+                    _ => Some(TypeShape::Generic(name, args)),
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn named(name: &str) -> TypeShape {
+        TypeShape::Named(name.to_string())
+    }
+
+    #[test]
+    fn test_parse_postfix() {
+        assert_eq!(
+            TypeShape::parse("string option"),
+            TypeShape::Option(Box::new(named("string")))
+        );
+        assert_eq!(
+            TypeShape::parse("int list option"),
+            TypeShape::Option(Box::new(TypeShape::List(Box::new(named("int")))))
+        );
+    }
+
+    #[test]
+    fn test_parse_generic_forms() {
+        assert_eq!(
+            TypeShape::parse("Map<string, list<string>> option"),
+            TypeShape::Option(Box::new(TypeShape::Map(
+                Box::new(named("string")),
+                Box::new(TypeShape::List(Box::new(named("string")))),
+            )))
+        );
+        assert_eq!(
+            TypeShape::parse("List<string>"),
+            TypeShape::List(Box::new(named("string")))
+        );
+    }
+
+    #[test]
+    fn test_parse_literal_union() {
+        assert_eq!(
+            TypeShape::parse("\"celsius\" | \"fahrenheit\""),
+            TypeShape::Union(vec![
+                TypeShape::Literal("celsius".to_string()),
+                TypeShape::Literal("fahrenheit".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_unparseable_is_preserved() {
+        assert_eq!(TypeShape::parse("Map<string"), named("Map<string"));
+    }
+
+    #[test]
+    fn test_display_roundtrip() {
+        for input in [
+            "string option",
+            "Map<string, any>",
+            "TlsConfig option",
+            "int list",
+            "string * int",
+            "(string * int) list",
+        ] {
+            assert_eq!(TypeShape::parse(input).to_string(), input);
+        }
+    }
+
+    #[test]
+    fn test_builders() {
+        let labels = TypeShape::map(TypeShape::named("string"), TypeShape::named("string")).option();
+        assert_eq!(labels, TypeShape::parse("Map<string, string> option"));
+        assert_eq!(TypeExpr::from(labels).to_string(), "Map<string, string> option");
+
+        let union = TypeShape::Union(vec![TypeShape::Literal("a".to_string()), TypeShape::Literal("b".to_string())]);
+        assert_eq!(union.option().to_string(), "(\"a\" | \"b\") option");
+        let pair = TypeShape::tuple(vec![named("string"), named("int")]).list();
+        assert_eq!(pair.to_string(), "(string * int) list");
+        assert_eq!(named("Tag").option_unless(true), named("Tag"));
+        assert_eq!(named("Tag").option_unless(false).to_string(), "Tag option");
+    }
+
+    #[test]
+    fn test_list_syntax() {
+        let filters = TypeShape::parse("Map<string, list<string>> option");
+        assert_eq!(filters.to_string(), "Map<string, string list> option");
+        assert_eq!(filters.render(ListSyntax::Generic("list")), "Map<string, list<string>> option");
+
+        let hosts = named("string").list().into_type_expr(ListSyntax::Generic("List"));
+        assert_eq!(hosts.to_string(), "List<string>");
+        let pairs = TypeShape::tuple(vec![named("string"), named("int")]).list();
+        assert_eq!(pairs.render(ListSyntax::Generic("list")), "list<string * int>");
+    }
+
+    #[test]
+    fn test_parse_generics() {
+        assert_eq!(
+            TypeShape::parse("Connection<User> option"),
+            TypeShape::generic("Connection", vec![named("User")]).option()
+        );
+        assert_eq!(
+            TypeShape::parse("Pair<'a, 'b list>"),
+            TypeShape::generic("Pair", vec![TypeShape::param("a"), TypeShape::param("b").list()])
+        );
+        assert_eq!(TypeShape::parse("list<string, int>"), named("list<string, int>"));
+
+        let shape = TypeShape::parse("Map<string, Edge<'node>> list");
+        assert_eq!(shape.to_string(), "Map<string, Edge<'node>> list");
+        assert_eq!(shape.references(), vec!["Edge"]);
+        assert_eq!(shape.params(), vec!["node"]);
+    }
+
+    #[test]
+    fn test_references() {
+        let shape = TypeShape::parse("Map<string, Address list> option");
+        assert_eq!(shape.references(), vec!["Address"]);
+        assert!(named("int64").is_primitive());
+    }
+}
@@ -0,0 +1,455 @@
+//! Shared source retrieval
+//!
+//! Providers read non-inline sources through [`read_source`], which accepts
+//! local paths, `file://` URLs and `http(s)://` URLs, and local binary
+//! files through [`read_binary_source`]. Hosts embedding providers in
+//! latency-sensitive paths bound worst-case behavior with these params:
+//!
+//! | Param              | Default | Meaning                                  |
+//! |--------------------|---------|------------------------------------------|
+//! | `fetch_timeout`    | `30s`   | Overall timeout (`500ms`, `5s` or ms)    |
+//! | `max_source_bytes` | 16 MiB  | Larger sources are rejected              |
+//! | `follow_redirects` | `true`  | Whether HTTP redirects are followed      |
+//!
+//! HTTP requests also send the credential referenced by the `auth` param
+//! (see [`crate::credentials`]) as a bearer token, or with basic auth when
+//! `auth_scheme=basic`, and are cached on disk (see [`crate::cache`]).
+//!
+//! `${VAR}` references in the location and in param values are replaced
+//! from the environment first (see [`crate::interpolate`]).
+
+use crate::cache::{offline_miss, request_key, CacheOptions, SchemaCache};
+use crate::credentials::{AuthScheme, CredentialResolver};
+use crate::error::StructuredError;
+use crate::interpolate::{interpolate_env, interpolate_params};
+use fusabi_type_providers::{ProviderError, ProviderParams, ProviderResult};
+use std::io::Read;
+use std::time::Duration;
+
+/// Default overall timeout for remote sources
+pub const DEFAULT_FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default maximum source size
+pub const DEFAULT_MAX_SOURCE_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Redirect hops followed when redirects are enabled
+const MAX_REDIRECTS: u32 = 5;
+
+/// Limits applied when retrieving a source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceOptions {
+    /// Overall timeout for remote sources
+    pub fetch_timeout: Duration,
+    /// Maximum size of a source in bytes
+    pub max_source_bytes: u64,
+    /// Whether HTTP redirects are followed
+    pub follow_redirects: bool,
+}
+
+impl Default for SourceOptions {
+    fn default() -> Self {
+        Self {
+            fetch_timeout: DEFAULT_FETCH_TIMEOUT,
+            max_source_bytes: DEFAULT_MAX_SOURCE_BYTES,
+            follow_redirects: true,
+        }
+    }
+}
+
+impl SourceOptions {
+    /// Read options from params, falling back to defaults
+    pub fn from_params(params: &ProviderParams) -> ProviderResult<Self> {
+        let mut options = Self::default();
+
+        if let Some(value) = params.custom.get("fetch_timeout") {
+            options.fetch_timeout = parse_duration(value).ok_or_else(|| {
+                invalid_param("fetch_timeout", value, "a duration such as 500ms or 5s")
+            })?;
+        }
+
+        if let Some(max_source_bytes) = max_source_bytes_param(params)? {
+            options.max_source_bytes = max_source_bytes;
+        }
+
+        if let Some(value) = params.custom.get("follow_redirects") {
+            options.follow_redirects = value
+                .parse()
+                .map_err(|_| invalid_param("follow_redirects", value, "true or false"))?;
+        }
+
+        Ok(options)
+    }
+}
+
+/// Retrieves sources from disk or over HTTP
+#[derive(Debug, Default)]
+pub struct SourceResolver {
+    credentials: CredentialResolver,
+}
+
+impl SourceResolver {
+    /// Create a resolver with the default credential resolver
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use a custom credential resolver, e.g. one with a host callback
+    pub fn with_credentials(mut self, credentials: CredentialResolver) -> Self {
+        self.credentials = credentials;
+        self
+    }
+
+    /// Read a source, taking limits and credentials from params
+    pub fn read(&self, location: &str, params: &ProviderParams) -> ProviderResult<String> {
+        // Nested in the provider's `resolve_schema` span when instrumented
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("read_source", location).entered();
+        let location = interpolate_env(location)?;
+        let location = location.as_str();
+        let params = &interpolate_params(params)?;
+        let options = SourceOptions::from_params(params)?;
+
+        if is_remote(location) {
+            let scheme = AuthScheme::from_params(params)?;
+            // Credentials are resolved only when fetching, so cache hits and
+            // offline runs work without the secret being available
+            let fetch = || {
+                let auth = self
+                    .credentials
+                    .from_params(params)?
+                    .map(|c| c.authorization(scheme))
+                    .transpose()?;
+                fetch_url(location, &options, auth.as_deref())
+            };
+
+            let cache_options = CacheOptions::from_params(params)?;
+            match &cache_options.dir {
+                Some(dir) => {
+                    let key = request_key(location, params);
+                    SchemaCache::new(dir)
+                        .with_max_source_bytes(options.max_source_bytes)
+                        .fetch_with(&key, location, &cache_options, fetch)
+                }
+                None if cache_options.offline => Err(offline_miss(location)),
+                None => fetch(),
+            }
+        } else {
+            read_file(location.strip_prefix("file://").unwrap_or(location), &options)
+        }
+    }
+}
+
+/// Read a source with the default resolver
+pub fn read_source(location: &str, params: &ProviderParams) -> ProviderResult<String> {
+    SourceResolver::new().read(location, params)
+}
+
+/// Read a local binary source, such as an Arrow IPC or Avro container
+/// file, rejecting it when larger than `max_source_bytes`
+pub fn read_binary_source(location: &str, params: &ProviderParams) -> ProviderResult<Vec<u8>> {
+    let location = interpolate_env(location)?;
+    let options = SourceOptions::from_params(&interpolate_params(params)?)?;
+    read_file_bytes(location.strip_prefix("file://").unwrap_or(&location), &options)
+}
+
+/// Whether a location is fetched over HTTP
+pub fn is_remote(location: &str) -> bool {
+    location.starts_with("http://") || location.starts_with("https://")
+}
+
+/// Whether a source names a file or URL to read rather than carrying the
+/// schema inline: a `file://` or `http(s)://` URL, or an existing path
+pub fn is_location(source: &str) -> bool {
+    source.starts_with("file://") || is_remote(source) || std::path::Path::new(source).exists()
+}
+
+fn read_file(path: &str, options: &SourceOptions) -> ProviderResult<String> {
+    utf8(read_file_bytes(path, options)?, path)
+}
+
+fn read_file_bytes(path: &str, options: &SourceOptions) -> ProviderResult<Vec<u8>> {
+    let cannot_read = |e: std::io::Error| StructuredError::io(format!("Cannot read {}", path)).with_source(e);
+    let file = std::fs::File::open(path).map_err(cannot_read)?;
+
+    let len = file.metadata().map_err(cannot_read)?.len();
+    if len > options.max_source_bytes {
+        return Err(too_large(path, options));
+    }
+
+    read_limited_bytes(file, path, options)
+}
+
+fn fetch_url(url: &str, options: &SourceOptions, auth: Option<&str>) -> ProviderResult<String> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout(options.fetch_timeout)
+        .redirects(if options.follow_redirects { MAX_REDIRECTS } else { 0 })
+        .build();
+
+    let mut request = agent.get(url);
+    if let Some(auth) = auth {
+        request = request.set("Authorization", auth);
+    }
+
+    let response = request.call().map_err(|e| match e {
+        ureq::Error::Status(code, _) => {
+            StructuredError::network(format!("Fetching {} failed with HTTP {}", url, code))
+        }
+        ureq::Error::Transport(t) => {
+            StructuredError::network(format!("Fetching {} failed", url)).with_source(t)
+        }
+    })?;
+
+    if (300..400).contains(&response.status()) {
+        return Err(StructuredError::network(format!(
+            "Fetching {} returned a redirect and follow_redirects is false",
+            url
+        ))
+        .into());
+    }
+
+    let declared_len = response
+        .header("Content-Length")
+        .and_then(|len| len.parse::<u64>().ok());
+    if declared_len.is_some_and(|len| len > options.max_source_bytes) {
+        return Err(too_large(url, options));
+    }
+
+    read_limited(response.into_reader(), url, options)
+}
+
+/// Read at most `max_source_bytes`, failing if the source is larger
+fn read_limited(reader: impl Read, location: &str, options: &SourceOptions) -> ProviderResult<String> {
+    utf8(read_limited_bytes(reader, location, options)?, location)
+}
+
+fn read_limited_bytes(reader: impl Read, location: &str, options: &SourceOptions) -> ProviderResult<Vec<u8>> {
+    let mut buf = Vec::new();
+    reader
+        .take(options.max_source_bytes + 1)
+        .read_to_end(&mut buf)
+        .map_err(|e| StructuredError::io(format!("Failed to read {}", location)).with_source(e))?;
+
+    if buf.len() as u64 > options.max_source_bytes {
+        return Err(too_large(location, options));
+    }
+    Ok(buf)
+}
+
+fn utf8(buf: Vec<u8>, location: &str) -> ProviderResult<String> {
+    String::from_utf8(buf).map_err(|e| {
+        StructuredError::parse(format!("{} is not valid UTF-8", location))
+            .with_source(e)
+            .into()
+    })
+}
+
+/// Parse `500ms`, `5s`, `10m`, `1h`, or a bare millisecond count
+pub(crate) fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let secs = |n: &str, scale: u64| n.trim().parse::<u64>().ok().map(|n| Duration::from_secs(n * scale));
+    if let Some(ms) = value.strip_suffix("ms") {
+        ms.trim().parse().ok().map(Duration::from_millis)
+    } else if let Some(n) = value.strip_suffix('s') {
+        secs(n, 1)
+    } else if let Some(n) = value.strip_suffix('m') {
+        secs(n, 60)
+    } else if let Some(n) = value.strip_suffix('h') {
+        secs(n, 60 * 60)
+    } else {
+        value.parse().ok().map(Duration::from_millis)
+    }
+}
+
+/// The `max_source_bytes` param, shared by [`SourceOptions`] and
+/// [`InputLimits`](crate::limits::InputLimits)
+pub(crate) fn max_source_bytes_param(params: &ProviderParams) -> ProviderResult<Option<u64>> {
+    params
+        .custom
+        .get("max_source_bytes")
+        .map(|value| {
+            value
+                .trim()
+                .parse()
+                .map_err(|_| invalid_param("max_source_bytes", value, "a byte count"))
+        })
+        .transpose()
+}
+
+fn too_large(location: &str, options: &SourceOptions) -> ProviderError {
+    StructuredError::validation(format!(
+        "{} exceeds max_source_bytes ({} bytes)",
+        location, options.max_source_bytes
+    ))
+    .into()
+}
+
+pub(crate) fn invalid_param(name: &str, value: &str, expected: &str) -> ProviderError {
+    StructuredError::validation(format!("Invalid {} '{}': expected {}", name, value, expected)).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+    use std::io::Write;
+    use std::net::TcpListener;
+
+    /// Serve one canned HTTP response on a local port
+    fn serve_once(response: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            if let Ok((mut stream, _)) = listener.accept() {
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf);
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        format!("http://{}/schema.json", addr)
+    }
+
+    #[test]
+    fn test_options_from_params() {
+        let params = ProviderParams::default()
+            .with("fetch_timeout", "250ms")
+            .with("max_source_bytes", "1024")
+            .with("follow_redirects", "false");
+
+        let options = SourceOptions::from_params(&params).unwrap();
+        assert_eq!(options.fetch_timeout, Duration::from_millis(250));
+        assert_eq!(options.max_source_bytes, 1024);
+        assert!(!options.follow_redirects);
+
+        assert_eq!(parse_duration("5s"), Some(Duration::from_secs(5)));
+        assert_eq!(parse_duration("2h"), Some(Duration::from_secs(7200)));
+        assert_eq!(parse_duration("1500"), Some(Duration::from_millis(1500)));
+
+        let params = ProviderParams::default().with("fetch_timeout", "soon");
+        assert!(SourceOptions::from_params(&params).is_err());
+    }
+
+    #[test]
+    fn test_file_size_limit() {
+        let path = std::env::temp_dir().join("fusabi-source-limit-test.json");
+        std::fs::write(&path, "0123456789").unwrap();
+        let location = format!("file://{}", path.display());
+
+        let params = ProviderParams::default().with("max_source_bytes", "10");
+        assert_eq!(read_source(&location, &params).unwrap(), "0123456789");
+
+        let params = ProviderParams::default().with("max_source_bytes", "9");
+        assert!(read_source(&location, &params).is_err());
+        // Binary sources are rejected too, not truncated
+        assert!(read_binary_source(&location, &params).is_err());
+        let params = ProviderParams::default().with("max_source_bytes", "10");
+        assert_eq!(read_binary_source(&location, &params).unwrap(), b"0123456789");
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_interpolated_location_and_params() {
+        let dir = std::env::temp_dir();
+        std::fs::write(dir.join("fusabi-source-env-test.json"), "{}").unwrap();
+        std::env::set_var("FUSABI_SOURCE_TEST_DIR", &dir);
+        std::env::set_var("FUSABI_SOURCE_TEST_LIMIT", "1");
+
+        let location = "${FUSABI_SOURCE_TEST_DIR}/fusabi-source-env-test.json";
+        assert_eq!(read_source(location, &ProviderParams::default()).unwrap(), "{}");
+
+        let params = ProviderParams::default().with("max_source_bytes", "${FUSABI_SOURCE_TEST_LIMIT}");
+        assert!(read_source(location, &params).is_err());
+
+        let err = read_source("${FUSABI_SOURCE_TEST_UNSET}/schema.json", &ProviderParams::default());
+        assert_eq!(ErrorKind::of(&err.unwrap_err()), ErrorKind::Validation);
+
+        std::fs::remove_file(dir.join("fusabi-source-env-test.json")).unwrap();
+    }
+
+    /// Params that keep tests out of the user's cache directory
+    fn uncached() -> ProviderParams {
+        ProviderParams::default().with("refresh", "always").with(
+            "cache_dir",
+            std::env::temp_dir().join("fusabi-source-tests").to_str().unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_http_fetch() {
+        let url = serve_once("HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}");
+        assert_eq!(read_source(&url, &uncached()).unwrap(), "{}");
+
+        let url = serve_once("HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}");
+        let params = uncached().with("max_source_bytes", "1");
+        assert!(read_source(&url, &params).is_err());
+    }
+
+    #[test]
+    fn test_http_fetch_is_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let params = ProviderParams::default().with("cache_dir", dir.path().to_str().unwrap());
+
+        // The server answers once; the second read must come from the cache
+        let url = serve_once("HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\n[]");
+        assert_eq!(read_source(&url, &params).unwrap(), "[]");
+        assert_eq!(read_source(&url, &params).unwrap(), "[]");
+
+        let offline = params.clone().with("offline", "true");
+        assert_eq!(read_source(&url, &offline).unwrap(), "[]");
+        let other = format!("{}?v=2", url);
+        assert!(read_source(&other, &offline).unwrap_err().to_string().contains("offline"));
+
+        let params = params.with("refresh", "always");
+        assert!(read_source(&url, &params).is_err());
+    }
+
+    #[test]
+    fn test_http_basic_auth() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/schema.json", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 4096];
+            let n = stream.read(&mut buf).unwrap();
+            let _ = stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}");
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+
+        std::env::set_var("FUSABI_SOURCE_TEST_BASIC", "user:pass");
+        let params = uncached()
+            .with("auth", "env:FUSABI_SOURCE_TEST_BASIC")
+            .with("auth_scheme", "basic");
+        assert_eq!(read_source(&url, &params).unwrap(), "{}");
+
+        let request = handle.join().unwrap().to_ascii_lowercase();
+        assert!(request.contains("authorization: basic dxnlcjpwyxnz"));
+    }
+
+    #[test]
+    fn test_redirects_disabled() {
+        let url = serve_once(
+            "HTTP/1.1 302 Found\r\nLocation: http://127.0.0.1:1/\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        );
+        let params = uncached().with("follow_redirects", "false");
+        let err = read_source(&url, &params).unwrap_err();
+        assert!(err.to_string().contains("redirect"));
+        assert_eq!(ErrorKind::of(&err), ErrorKind::Network);
+    }
+
+    #[test]
+    fn test_fetch_timeout() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/slow", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            // Accept but never respond
+            let conn = listener.accept();
+            std::thread::sleep(Duration::from_millis(500));
+            drop(conn);
+        });
+
+        let params = uncached().with("fetch_timeout", "100ms");
+        let err = read_source(&url, &params).unwrap_err();
+        assert_eq!(ErrorKind::of(&err), ErrorKind::Network);
+        handle.join().unwrap();
+    }
+}
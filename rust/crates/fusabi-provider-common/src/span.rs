@@ -0,0 +1,185 @@
+//! Source positions for parse errors
+//!
+//! `ProviderError::ParseError` carries a message only, so a parser that
+//! knows where it failed folds the position into that message:
+//! [`parse_error_at`] turns a byte offset into a line, a column and the
+//! text around it, and formats
+//!
+//! ```text
+//! Expected identifier, got Equals at line 3, column 16
+//!   message User { = 1; }
+//!                  ^
+//! ```
+//!
+//! [`span_of`] reads the position back out of such an error, which is how
+//! validation diagnostics get their location.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_common::span::{parse_error_at, span_of};
+//!
+//! let error = parse_error_at(source, offset, "Unmatched parentheses");
+//! assert_eq!(span_of(&error).map(|s| s.line), Some(4));
+//! ```
+
+use fusabi_type_providers::ProviderError;
+use std::fmt;
+
+/// Characters of context kept on each side of the position in a snippet
+const SNIPPET_CONTEXT: usize = 40;
+
+/// Text between the message and the position in a formatted error
+const POSITION_MARKER: &str = " at line ";
+
+/// A position in a source text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceSpan {
+    /// Line number, starting at 1
+    pub line: usize,
+    /// Column in characters, starting at 1
+    pub column: usize,
+    /// Text of the line around the position, without the line break
+    pub snippet: String,
+}
+
+impl SourceSpan {
+    /// Position of the byte `offset` in `source`
+    ///
+    /// Offsets past the end point just after the last character, and
+    /// offsets inside a character point at that character.
+    pub fn locate(source: &str, offset: usize) -> Self {
+        let mut offset = offset.min(source.len());
+        while !source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[offset..].find('\n').map_or(source.len(), |i| offset + i);
+        let line = source[..line_start].matches('\n').count() + 1;
+
+        let before: Vec<char> = source[line_start..offset].chars().collect();
+        let column = before.len() + 1;
+        let kept = before.len().saturating_sub(SNIPPET_CONTEXT);
+        let snippet: String = before[kept..]
+            .iter()
+            .copied()
+            .chain(source[offset..line_end].chars().take(SNIPPET_CONTEXT))
+            .collect();
+
+        Self {
+            line,
+            column,
+            snippet: snippet.trim_end_matches('\r').to_string(),
+        }
+    }
+
+    /// Position given as a 1-based line and byte column, as reported by
+    /// `serde_json`
+    pub fn at_line_column(source: &str, line: usize, column: usize) -> Self {
+        let line_start = source
+            .split_inclusive('\n')
+            .take(line.saturating_sub(1))
+            .map(str::len)
+            .sum::<usize>();
+        Self::locate(source, line_start + column.saturating_sub(1))
+    }
+
+    /// `ProviderError::ParseError` with `message` and this position
+    pub fn error(&self, message: impl fmt::Display) -> ProviderError {
+        let context = self.column - 1 - self.column.saturating_sub(SNIPPET_CONTEXT + 1);
+        let indent: String = self
+            .snippet
+            .chars()
+            .take(context)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        ProviderError::ParseError(format!(
+            "{}{}{}\n  {}\n  {}^",
+            message, POSITION_MARKER, self, self.snippet, indent
+        ))
+    }
+}
+
+impl fmt::Display for SourceSpan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, column {}", self.line, self.column)
+    }
+}
+
+/// `ProviderError::ParseError` with `message` at the byte `offset` of
+/// `source`
+pub fn parse_error_at(source: &str, offset: usize, message: impl fmt::Display) -> ProviderError {
+    SourceSpan::locate(source, offset).error(message)
+}
+
+/// Byte offset of `slice` in `source`, if it is a subslice of it
+///
+/// Parsers that work on `&str` slices of their input use this to recover
+/// where a slice came from.
+pub fn slice_offset(source: &str, slice: &str) -> Option<usize> {
+    let start = source.as_ptr() as usize;
+    let at = slice.as_ptr() as usize;
+    if at >= start && at + slice.len() <= start + source.len() {
+        Some(at - start)
+    } else {
+        None
+    }
+}
+
+/// Position recorded in an error built by [`SourceSpan::error`]
+pub fn span_of(error: &ProviderError) -> Option<SourceSpan> {
+    let ProviderError::ParseError(message) = error else {
+        return None;
+    };
+    let mut lines = message.lines();
+    let (_, position) = lines.next()?.rsplit_once(POSITION_MARKER)?;
+    let (line, column) = position.split_once(", column ")?;
+    let snippet = lines.next()?.strip_prefix("  ")?;
+
+    Some(SourceSpan {
+        line: line.parse().ok()?,
+        column: column.parse().ok()?,
+        snippet: snippet.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_at() {
+        let source = "message User {\n\tstring = 1;\n}\n";
+        let offset = source.find('=').unwrap();
+        let error = parse_error_at(source, offset, "Expected identifier");
+        assert_eq!(
+            error.to_string().lines().skip(1).collect::<Vec<_>>(),
+            ["  \tstring = 1;", "  \t       ^"]
+        );
+
+        let span = span_of(&error).unwrap();
+        assert_eq!(span, SourceSpan::locate(source, offset));
+        assert_eq!((span.line, span.column, span.snippet.as_str()), (2, 9, "\tstring = 1;"));
+        assert_eq!(SourceSpan::at_line_column(source, 2, 9), span);
+
+        let end = SourceSpan::locate(source, source.len() + 10);
+        assert_eq!((end.line, end.column), (4, 1));
+        assert!(span_of(&ProviderError::ParseError("Expected identifier".to_string())).is_none());
+    }
+
+    #[test]
+    fn test_long_lines_are_clipped() {
+        let source = format!("{}é{}", "a".repeat(100), "b".repeat(100));
+        let span = SourceSpan::locate(&source, 101);
+        assert_eq!(span.column, 101);
+        assert_eq!(span.snippet.chars().count(), 2 * SNIPPET_CONTEXT);
+        assert!(span.snippet.starts_with('a') && span.snippet.ends_with('b'));
+
+        let error = span.error("Unexpected character").to_string();
+        let caret = error.lines().last().unwrap();
+        assert_eq!(caret.len(), 2 + SNIPPET_CONTEXT + 1);
+        assert_eq!(slice_offset(&source, &source[102..]), Some(102));
+        assert_eq!(slice_offset(&source, "b"), None);
+    }
+}
@@ -0,0 +1,429 @@
+//! Progress and metrics hooks around schema resolution and generation
+//!
+//! [`Instrumented`] wraps any provider and reports every `resolve_schema`
+//! and `generate_types` call to its [`ProviderObserver`]s: a
+//! [`ProviderEvent`] when the call starts, and one with the elapsed time,
+//! the number of generated types and the error, if any, when it returns.
+//! [`PipelineMetrics`] is an observer that sums these up per provider, for
+//! build services that export latency and generation counts. [`Batch`]
+//! reports its jobs to the same observers.
+//!
+//! With the `tracing` feature, each call also runs in a `resolve_schema` or
+//! `generate_types` span carrying the provider name and the source or
+//! namespace, and failures are logged as `tracing` warnings. Sources read
+//! through [`read_source`](crate::source::read_source) get a nested
+//! `read_source` span, so fetch time shows up separately from parsing.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_common::telemetry::{Instrumented, PipelineMetrics, ProviderEvent};
+//! use std::sync::Arc;
+//!
+//! let metrics = Arc::new(PipelineMetrics::new());
+//! let provider = Instrumented::new(SqlProvider::new())
+//!     .with_observer(metrics.clone())
+//!     .with_observer(Arc::new(|event: &ProviderEvent<'_>| eprintln!("{}", event)));
+//!
+//! let schema = provider.resolve_schema("db/schema.sql", &params)?;
+//! let types = provider.generate_types(&schema, "Db")?;
+//! let sql = &metrics.snapshot()["SqlProvider"];
+//! println!("resolved in {:?}, {} types", sql.resolve_time, sql.types);
+//! ```
+//!
+//! [`Batch`]: crate::batch::Batch
+
+use crate::capabilities::{Capabilities, DescribedProvider};
+use crate::params::{ParamSchema, ParameterizedProvider};
+use crate::validate::{Diagnostic, SourceValidator};
+use fusabi_type_providers::{GeneratedTypes, ProviderError, ProviderParams, ProviderResult, Schema, TypeProvider};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A step of a provider call
+#[derive(Debug, Clone, Copy)]
+pub enum ProviderEvent<'a> {
+    /// `resolve_schema` is about to run
+    ResolveStarted {
+        /// Name of the provider
+        provider: &'a str,
+        /// Source being resolved
+        source: &'a str,
+    },
+    /// `resolve_schema` returned
+    Resolved {
+        /// Name of the provider
+        provider: &'a str,
+        /// Source that was resolved
+        source: &'a str,
+        /// Time the call took
+        elapsed: Duration,
+        /// Why the call failed, if it did
+        error: Option<&'a ProviderError>,
+    },
+    /// `generate_types` is about to run
+    GenerateStarted {
+        /// Name of the provider
+        provider: &'a str,
+        /// Namespace being generated into
+        namespace: &'a str,
+    },
+    /// `generate_types` returned
+    Generated {
+        /// Name of the provider
+        provider: &'a str,
+        /// Namespace that was generated into
+        namespace: &'a str,
+        /// Time the call took
+        elapsed: Duration,
+        /// Number of generated types, root and module types together
+        types: usize,
+        /// Why the call failed, if it did
+        error: Option<&'a ProviderError>,
+    },
+}
+
+impl ProviderEvent<'_> {
+    /// Name of the provider
+    pub fn provider(&self) -> &str {
+        match self {
+            ProviderEvent::ResolveStarted { provider, .. }
+            | ProviderEvent::Resolved { provider, .. }
+            | ProviderEvent::GenerateStarted { provider, .. }
+            | ProviderEvent::Generated { provider, .. } => provider,
+        }
+    }
+}
+
+impl fmt::Display for ProviderEvent<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProviderEvent::ResolveStarted { provider, source } => {
+                write!(f, "{}: resolving {}", provider, source)
+            }
+            ProviderEvent::Resolved { provider, source, elapsed, error: None } => {
+                write!(f, "{}: resolved {} in {:?}", provider, source, elapsed)
+            }
+            ProviderEvent::Resolved { provider, source, error: Some(e), .. } => {
+                write!(f, "{}: resolving {} failed: {}", provider, source, e)
+            }
+            ProviderEvent::GenerateStarted { provider, namespace } => {
+                write!(f, "{}: generating {}", provider, namespace)
+            }
+            ProviderEvent::Generated { provider, namespace, elapsed, types, error: None } => {
+                write!(f, "{}: generated {} types into {} in {:?}", provider, types, namespace, elapsed)
+            }
+            ProviderEvent::Generated { provider, namespace, error: Some(e), .. } => {
+                write!(f, "{}: generating {} failed: {}", provider, namespace, e)
+            }
+        }
+    }
+}
+
+/// Receives the [`ProviderEvent`]s of instrumented providers
+///
+/// Closures taking `&ProviderEvent` are observers.
+pub trait ProviderObserver: Send + Sync {
+    /// Called for every event, on the thread making the provider call
+    fn on_event(&self, event: &ProviderEvent<'_>);
+}
+
+impl<F: Fn(&ProviderEvent<'_>) + Send + Sync> ProviderObserver for F {
+    fn on_event(&self, event: &ProviderEvent<'_>) {
+        self(event)
+    }
+}
+
+/// Observers shared between instrumented providers and batches
+pub type Observers = Vec<Arc<dyn ProviderObserver>>;
+
+fn notify(observers: &[Arc<dyn ProviderObserver>], event: ProviderEvent<'_>) {
+    for observer in observers {
+        observer.on_event(&event);
+    }
+}
+
+/// Number of types in generated output
+fn type_count(types: &GeneratedTypes) -> usize {
+    types.root_types.len() + types.modules.iter().map(|m| m.types.len()).sum::<usize>()
+}
+
+/// Run `provider.resolve_schema`, reporting it to `observers`
+pub(crate) fn resolve_observed(
+    provider: &dyn TypeProvider,
+    source: &str,
+    params: &ProviderParams,
+    observers: &[Arc<dyn ProviderObserver>],
+) -> ProviderResult<Schema> {
+    let name = provider.name();
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("resolve_schema", provider = name, source).entered();
+
+    notify(observers, ProviderEvent::ResolveStarted { provider: name, source });
+    let started = Instant::now();
+    let result = provider.resolve_schema(source, params);
+    let elapsed = started.elapsed();
+
+    #[cfg(feature = "tracing")]
+    match &result {
+        Ok(_) => tracing::debug!(elapsed_ms = elapsed.as_millis() as u64, "resolved schema"),
+        Err(e) => tracing::warn!(elapsed_ms = elapsed.as_millis() as u64, error = %e, "resolving schema failed"),
+    }
+    notify(
+        observers,
+        ProviderEvent::Resolved { provider: name, source, elapsed, error: result.as_ref().err() },
+    );
+    result
+}
+
+/// Run `provider.generate_types`, reporting it to `observers`
+pub(crate) fn generate_observed(
+    provider: &dyn TypeProvider,
+    schema: &Schema,
+    namespace: &str,
+    observers: &[Arc<dyn ProviderObserver>],
+) -> ProviderResult<GeneratedTypes> {
+    let name = provider.name();
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("generate_types", provider = name, namespace).entered();
+
+    notify(observers, ProviderEvent::GenerateStarted { provider: name, namespace });
+    let started = Instant::now();
+    let result = provider.generate_types(schema, namespace);
+    let elapsed = started.elapsed();
+    let types = result.as_ref().map_or(0, type_count);
+
+    #[cfg(feature = "tracing")]
+    match &result {
+        Ok(_) => tracing::debug!(elapsed_ms = elapsed.as_millis() as u64, types, "generated types"),
+        Err(e) => tracing::warn!(elapsed_ms = elapsed.as_millis() as u64, error = %e, "generating types failed"),
+    }
+    notify(
+        observers,
+        ProviderEvent::Generated { provider: name, namespace, elapsed, types, error: result.as_ref().err() },
+    );
+    result
+}
+
+/// A provider whose calls are reported to observers
+///
+/// Extension traits of the wrapped provider (`SourceValidator`,
+/// `ParameterizedProvider`, `DescribedProvider`) are implemented too, so
+/// validation runs are observed as well.
+pub struct Instrumented<P> {
+    inner: P,
+    observers: Observers,
+}
+
+impl<P: TypeProvider> Instrumented<P> {
+    /// Wrap a provider, with no observers yet
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            observers: Vec::new(),
+        }
+    }
+
+    /// Report calls to `observer`
+    pub fn with_observer(mut self, observer: Arc<dyn ProviderObserver>) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    /// The wrapped provider
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    /// Unwrap the provider
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+}
+
+impl<P: TypeProvider> TypeProvider for Instrumented<P> {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        resolve_observed(&self.inner, source, params, &self.observers)
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        generate_observed(&self.inner, schema, namespace, &self.observers)
+    }
+}
+
+impl<P: SourceValidator> SourceValidator for Instrumented<P> {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        self.inner.schema_diagnostics(schema)
+    }
+}
+
+impl<P: ParameterizedProvider> ParameterizedProvider for Instrumented<P> {
+    fn param_schema(&self) -> ParamSchema {
+        self.inner.param_schema()
+    }
+}
+
+impl<P: DescribedProvider> DescribedProvider for Instrumented<P> {
+    fn capabilities(&self) -> Capabilities {
+        self.inner.capabilities()
+    }
+}
+
+/// Call counts and times of one provider
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ProviderMetrics {
+    /// `resolve_schema` calls
+    pub resolves: u64,
+    /// `resolve_schema` calls that failed
+    pub resolve_failures: u64,
+    /// Total time spent in `resolve_schema`
+    pub resolve_time: Duration,
+    /// Longest `resolve_schema` call
+    pub slowest_resolve: Duration,
+    /// `generate_types` calls
+    pub generations: u64,
+    /// `generate_types` calls that failed
+    pub generate_failures: u64,
+    /// Total time spent in `generate_types`
+    pub generate_time: Duration,
+    /// Types generated by successful calls
+    pub types: u64,
+}
+
+/// Observer summing up calls per provider
+#[derive(Debug, Default)]
+pub struct PipelineMetrics {
+    providers: Mutex<BTreeMap<String, ProviderMetrics>>,
+}
+
+impl PipelineMetrics {
+    /// Create empty metrics
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Metrics so far, by provider name
+    pub fn snapshot(&self) -> BTreeMap<String, ProviderMetrics> {
+        self.lock().clone()
+    }
+
+    /// Metrics so far, summed over every provider
+    pub fn totals(&self) -> ProviderMetrics {
+        self.lock().values().fold(ProviderMetrics::default(), |mut total, m| {
+            total.resolves += m.resolves;
+            total.resolve_failures += m.resolve_failures;
+            total.resolve_time += m.resolve_time;
+            total.slowest_resolve = total.slowest_resolve.max(m.slowest_resolve);
+            total.generations += m.generations;
+            total.generate_failures += m.generate_failures;
+            total.generate_time += m.generate_time;
+            total.types += m.types;
+            total
+        })
+    }
+
+    /// Forget every call so far
+    pub fn reset(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, ProviderMetrics>> {
+        // An observer that panicked mid-update leaves counts that are still usable
+        self.providers.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl ProviderObserver for PipelineMetrics {
+    fn on_event(&self, event: &ProviderEvent<'_>) {
+        let mut providers = self.lock();
+        let metrics = providers.entry(event.provider().to_string()).or_default();
+        match *event {
+            ProviderEvent::Resolved { elapsed, error, .. } => {
+                metrics.resolves += 1;
+                metrics.resolve_failures += u64::from(error.is_some());
+                metrics.resolve_time += elapsed;
+                metrics.slowest_resolve = metrics.slowest_resolve.max(elapsed);
+            }
+            ProviderEvent::Generated { elapsed, types, error, .. } => {
+                metrics.generations += 1;
+                metrics.generate_failures += u64::from(error.is_some());
+                metrics.generate_time += elapsed;
+                metrics.types += types as u64;
+            }
+            ProviderEvent::ResolveStarted { .. } | ProviderEvent::GenerateStarted { .. } => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_type_providers::{RecordDef, TypeDefinition, TypeExpr};
+
+    /// Generates one record per source, and fails on `bad`
+    struct RecordProvider;
+
+    impl TypeProvider for RecordProvider {
+        fn name(&self) -> &str {
+            "RecordProvider"
+        }
+
+        fn resolve_schema(&self, source: &str, _params: &ProviderParams) -> ProviderResult<Schema> {
+            match source {
+                "bad" => Err(ProviderError::ParseError("bad source".to_string())),
+                _ => Ok(Schema::Custom(source.to_string())),
+            }
+        }
+
+        fn generate_types(&self, schema: &Schema, _namespace: &str) -> ProviderResult<GeneratedTypes> {
+            let Schema::Custom(name) = schema else {
+                return Err(ProviderError::ParseError("Expected text".to_string()));
+            };
+            let mut types = GeneratedTypes::new();
+            types.root_types.push(TypeDefinition::Record(RecordDef {
+                name: name.clone(),
+                fields: vec![("id".to_string(), TypeExpr::Named("int".to_string()))],
+            }));
+            Ok(types)
+        }
+    }
+
+    #[test]
+    fn test_instrumented_provider() {
+        let metrics = Arc::new(PipelineMetrics::new());
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let events = log.clone();
+        let provider = Instrumented::new(RecordProvider)
+            .with_observer(metrics.clone())
+            .with_observer(Arc::new(move |event: &ProviderEvent<'_>| {
+                events.lock().unwrap().push(event.to_string());
+            }));
+
+        let schema = provider.resolve_schema("User", &ProviderParams::default()).unwrap();
+        provider.generate_types(&schema, "Users").unwrap();
+        assert!(provider.resolve_schema("bad", &ProviderParams::default()).is_err());
+
+        let log = log.lock().unwrap();
+        assert_eq!(log.len(), 6);
+        assert_eq!(log[0], "RecordProvider: resolving User");
+        assert!(log[3].starts_with("RecordProvider: generated 1 types into Users in "));
+        assert!(log[5].starts_with("RecordProvider: resolving bad failed: ") && log[5].ends_with("bad source"));
+
+        let snapshot = metrics.snapshot();
+        let record = &snapshot["RecordProvider"];
+        assert_eq!((record.resolves, record.resolve_failures), (2, 1));
+        assert_eq!((record.generations, record.types), (1, 1));
+        assert!(record.resolve_time >= record.slowest_resolve);
+        assert_eq!(metrics.totals(), *record);
+
+        metrics.reset();
+        assert!(metrics.snapshot().is_empty());
+    }
+}
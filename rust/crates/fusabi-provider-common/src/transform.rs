@@ -0,0 +1,426 @@
+//! Post-processing transforms for generated types
+//!
+//! A [`Transform`] rewrites a provider's [`GeneratedTypes`] after
+//! generation, the same way for every provider. Any
+//! `Fn(GeneratedTypes) -> GeneratedTypes` is a transform; a
+//! [`TransformChain`] runs several in order. Three are built in, each
+//! enabled by a param:
+//!
+//! | Param         | Transform         | Example                       |
+//! |---------------|-------------------|-------------------------------|
+//! | `strip_fields`| [`StripFields`]   | `strip_fields=legacy*,Users.ssn` |
+//! | `base_fields` | [`InjectFields`]  | `base_fields=id:string,createdAt:int64` |
+//! | `type_prefix` | [`PrefixTypes`]   | `type_prefix=Api`             |
+//!
+//! [`TransformChain::from_params`] runs them in that order, so patterns and
+//! injected fields refer to type names before prefixing.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_common::transform::{PrefixTypes, StripFields, TransformChain};
+//!
+//! let chain = TransformChain::new()
+//!     .with(StripFields::new(["deprecated*"]))
+//!     .with(PrefixTypes::new("Api"))
+//!     .with(|mut types: GeneratedTypes| {
+//!         types.modules.retain(|m| !m.types.is_empty());
+//!         types
+//!     });
+//! let types = chain.apply(provider.generate_types(&schema, "Api")?);
+//! ```
+
+use crate::generics::{base_name, GenericName};
+use crate::naming::rename_expr;
+use crate::shape::TypeShape;
+use fusabi_type_providers::{
+    GeneratedTypes, ProviderError, ProviderParams, ProviderResult, TypeDefinition, TypeExpr,
+};
+use std::collections::BTreeMap;
+
+/// Params key holding the prefix for every generated type name
+pub const TYPE_PREFIX_PARAM: &str = "type_prefix";
+
+/// Params key holding field patterns to drop
+pub const STRIP_FIELDS_PARAM: &str = "strip_fields";
+
+/// Params key holding `name:type` fields to add to every record
+pub const BASE_FIELDS_PARAM: &str = "base_fields";
+
+/// Rewrites generated types after generation
+pub trait Transform: Send + Sync {
+    /// Transform the types
+    fn apply(&self, types: GeneratedTypes) -> GeneratedTypes;
+}
+
+impl<F> Transform for F
+where
+    F: Fn(GeneratedTypes) -> GeneratedTypes + Send + Sync,
+{
+    fn apply(&self, types: GeneratedTypes) -> GeneratedTypes {
+        self(types)
+    }
+}
+
+/// Transforms run one after another
+#[derive(Default)]
+pub struct TransformChain {
+    transforms: Vec<Box<dyn Transform>>,
+}
+
+impl TransformChain {
+    /// Create an empty chain
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Chain with `transform` appended
+    pub fn with(mut self, transform: impl Transform + 'static) -> Self {
+        self.push(transform);
+        self
+    }
+
+    /// Append a transform
+    pub fn push(&mut self, transform: impl Transform + 'static) {
+        self.transforms.push(Box::new(transform));
+    }
+
+    /// Build the chain of built-in transforms the `strip_fields`,
+    /// `base_fields` and `type_prefix` params enable
+    pub fn from_params(params: &ProviderParams) -> ProviderResult<Self> {
+        let mut chain = Self::new();
+        if let Some(patterns) = params.custom.get(STRIP_FIELDS_PARAM) {
+            chain.push(StripFields::new(list(patterns)));
+        }
+        if let Some(fields) = params.custom.get(BASE_FIELDS_PARAM) {
+            chain.push(InjectFields::parse(fields)?);
+        }
+        if let Some(prefix) = params.custom.get(TYPE_PREFIX_PARAM) {
+            chain.push(PrefixTypes::parse(prefix)?);
+        }
+        Ok(chain)
+    }
+
+    /// Number of transforms
+    pub fn len(&self) -> usize {
+        self.transforms.len()
+    }
+
+    /// Whether the chain leaves types unchanged
+    pub fn is_empty(&self) -> bool {
+        self.transforms.is_empty()
+    }
+
+    /// Run every transform in order
+    pub fn apply(&self, types: GeneratedTypes) -> GeneratedTypes {
+        self.transforms
+            .iter()
+            .fold(types, |types, transform| transform.apply(types))
+    }
+}
+
+impl std::fmt::Debug for TransformChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransformChain")
+            .field("transforms", &self.transforms.len())
+            .finish()
+    }
+}
+
+/// Prefixes every generated type name and rewrites references to match
+///
+/// Generic definitions keep their parameters: `Edge<'node>` becomes
+/// `ApiEdge<'node>`. Module paths and union cases keep their names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixTypes {
+    prefix: String,
+}
+
+impl PrefixTypes {
+    /// Prefix type names with `prefix`
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self { prefix: prefix.into() }
+    }
+
+    /// Parse a `type_prefix` param value
+    pub fn parse(value: &str) -> ProviderResult<Self> {
+        let valid = value.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+            && value.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if !valid {
+            return Err(ProviderError::InvalidSource(format!(
+                "Invalid type_prefix '{}': expected an identifier such as Api",
+                value
+            )));
+        }
+        Ok(Self::new(value))
+    }
+}
+
+impl Transform for PrefixTypes {
+    fn apply(&self, mut types: GeneratedTypes) -> GeneratedTypes {
+        let renamed: BTreeMap<String, String> = definitions(&types)
+            .map(|t| base_name(type_name(t)).to_string())
+            .map(|name| (format!("{}{}", self.prefix, name), name))
+            .map(|(new, old)| (old, new))
+            .collect();
+
+        for type_def in definitions_mut(&mut types) {
+            let original = type_name(type_def).to_string();
+            let generic = GenericName::parse(&original);
+            let name = match renamed.get(&generic.name) {
+                Some(new) => generic.with_name(new.clone()).to_string(),
+                None => original,
+            };
+            match type_def {
+                TypeDefinition::Record(record) => {
+                    record.name = name;
+                    for (_, ty) in &mut record.fields {
+                        *ty = rename_expr(ty, &renamed);
+                    }
+                }
+                TypeDefinition::Du(du) => {
+                    du.name = name;
+                    for ty in du.variants.iter_mut().flat_map(|v| v.fields.iter_mut()) {
+                        *ty = rename_expr(ty, &renamed);
+                    }
+                }
+            }
+        }
+        types
+    }
+}
+
+/// Drops record fields that match a pattern
+///
+/// A pattern is `field` to match the field in every record, or
+/// `Type.field` to match it in one; `*` matches any run of characters in
+/// either part, as in `deprecated*` or `*.internalId`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StripFields {
+    patterns: Vec<String>,
+}
+
+impl StripFields {
+    /// Drop fields matching any of `patterns`
+    pub fn new<I, S>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            patterns: patterns.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    fn matches(&self, type_name: &str, field: &str) -> bool {
+        self.patterns.iter().any(|pattern| match pattern.split_once('.') {
+            Some((ty, name)) => glob_match(ty, base_name(type_name)) && glob_match(name, field),
+            None => glob_match(pattern, field),
+        })
+    }
+}
+
+impl Transform for StripFields {
+    fn apply(&self, mut types: GeneratedTypes) -> GeneratedTypes {
+        for type_def in definitions_mut(&mut types) {
+            if let TypeDefinition::Record(record) = type_def {
+                let name = record.name.clone();
+                record.fields.retain(|(field, _)| !self.matches(&name, field));
+            }
+        }
+        types
+    }
+}
+
+/// Adds common fields to the front of every record
+///
+/// Records that already have a field of the same name keep their own.
+#[derive(Debug, Clone)]
+pub struct InjectFields {
+    fields: Vec<(String, TypeExpr)>,
+}
+
+impl InjectFields {
+    /// Add `fields` to every record
+    pub fn new(fields: Vec<(String, TypeExpr)>) -> Self {
+        Self { fields }
+    }
+
+    /// Parse a `base_fields` param value such as `id:string,createdAt:int64 option`
+    pub fn parse(value: &str) -> ProviderResult<Self> {
+        let fields = list(value)
+            .map(|entry| {
+                entry
+                    .split_once(':')
+                    .map(|(name, ty)| (name.trim(), ty.trim()))
+                    .filter(|(name, ty)| !name.is_empty() && !ty.is_empty())
+                    .map(|(name, ty)| (name.to_string(), TypeShape::parse(ty).into()))
+                    .ok_or_else(|| {
+                        ProviderError::InvalidSource(format!(
+                            "Invalid base_fields '{}': expected name:type pairs such as id:string",
+                            entry
+                        ))
+                    })
+            })
+            .collect::<ProviderResult<Vec<_>>>()?;
+        Ok(Self::new(fields))
+    }
+}
+
+impl Transform for InjectFields {
+    fn apply(&self, mut types: GeneratedTypes) -> GeneratedTypes {
+        for type_def in definitions_mut(&mut types) {
+            if let TypeDefinition::Record(record) = type_def {
+                let missing: Vec<_> = self
+                    .fields
+                    .iter()
+                    .filter(|(name, _)| !record.fields.iter().any(|(field, _)| field == name))
+                    .cloned()
+                    .collect();
+                record.fields.splice(0..0, missing);
+            }
+        }
+        types
+    }
+}
+
+fn definitions(types: &GeneratedTypes) -> impl Iterator<Item = &TypeDefinition> {
+    types
+        .root_types
+        .iter()
+        .chain(types.modules.iter().flat_map(|m| m.types.iter()))
+}
+
+fn definitions_mut(types: &mut GeneratedTypes) -> impl Iterator<Item = &mut TypeDefinition> {
+    types
+        .root_types
+        .iter_mut()
+        .chain(types.modules.iter_mut().flat_map(|m| m.types.iter_mut()))
+}
+
+fn type_name(type_def: &TypeDefinition) -> &str {
+    match type_def {
+        TypeDefinition::Record(record) => &record.name,
+        TypeDefinition::Du(du) => &du.name,
+    }
+}
+
+/// Trimmed, non-empty items of a comma-separated param
+fn list(value: &str) -> impl Iterator<Item = &str> {
+    value.split(',').map(str::trim).filter(|item| !item.is_empty())
+}
+
+/// Whether `text` matches `pattern`, where `*` matches any run of characters
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+
+    let middle: Vec<&str> = parts.collect();
+    let Some((last, middle)) = middle.split_last() else {
+        // No `*` at all
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_type_providers::{DuDef, GeneratedModule, RecordDef, VariantDef};
+
+    fn sample() -> GeneratedTypes {
+        let mut module = GeneratedModule::new(vec!["Db".to_string()]);
+        module.types.push(TypeDefinition::Record(RecordDef {
+            name: "Users".to_string(),
+            fields: vec![
+                ("id".to_string(), TypeExpr::Named("int".to_string())),
+                ("deprecatedEmail".to_string(), TypeExpr::Named("string".to_string())),
+                ("ssn".to_string(), TypeExpr::Named("string".to_string())),
+                ("status".to_string(), TypeExpr::Named("Status option".to_string())),
+            ],
+        }));
+        module.types.push(TypeDefinition::Du(DuDef {
+            name: "Status".to_string(),
+            variants: vec![VariantDef::new("Banned".to_string(), vec![TypeExpr::Named("Users".to_string())])],
+        }));
+
+        let mut types = GeneratedTypes::new();
+        types.modules.push(module);
+        types
+    }
+
+    fn fields(types: &GeneratedTypes) -> Vec<String> {
+        let TypeDefinition::Record(record) = &types.modules[0].types[0] else {
+            panic!("Expected record");
+        };
+        record.fields.iter().map(|(name, ty)| format!("{}: {}", name, ty)).collect()
+    }
+
+    #[test]
+    fn test_prefix_types() {
+        let types = PrefixTypes::new("Api").apply(sample());
+        assert_eq!(fields(&types)[3], "status: ApiStatus option");
+
+        let TypeDefinition::Du(status) = &types.modules[0].types[1] else {
+            panic!("Expected union");
+        };
+        assert_eq!(status.name, "ApiStatus");
+        assert_eq!(status.variants[0].name, "Banned");
+        assert_eq!(status.variants[0].fields[0].to_string(), "ApiUsers");
+
+        assert!(PrefixTypes::parse("Api").is_ok());
+        assert!(PrefixTypes::parse("9lives").is_err());
+    }
+
+    #[test]
+    fn test_strip_and_inject_fields() {
+        let types = StripFields::new(["deprecated*", "Users.ssn", "Orders.id"]).apply(sample());
+        assert_eq!(fields(&types), ["id: int", "status: Status option"]);
+
+        let inject = InjectFields::parse("id:string, createdAt:int64 option").unwrap();
+        let types = inject.apply(types);
+        assert_eq!(fields(&types), ["createdAt: int64 option", "id: int", "status: Status option"]);
+
+        assert!(InjectFields::parse("createdAt").is_err());
+    }
+
+    #[test]
+    fn test_chain_from_params() {
+        let params = ProviderParams::default()
+            .with(STRIP_FIELDS_PARAM, "ssn,deprecated*")
+            .with(BASE_FIELDS_PARAM, "tenant:string")
+            .with(TYPE_PREFIX_PARAM, "Api");
+        let chain = TransformChain::from_params(&params).unwrap();
+        assert_eq!(chain.len(), 3);
+
+        let types = chain
+            .with(|mut types: GeneratedTypes| {
+                types.modules[0].path.push("Generated".to_string());
+                types
+            })
+            .apply(sample());
+        assert_eq!(fields(&types), ["tenant: string", "id: int", "status: ApiStatus option"]);
+        assert_eq!(types.modules[0].path, vec!["Db".to_string(), "Generated".to_string()]);
+
+        assert!(TransformChain::from_params(&ProviderParams::default()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("deprecated*", "deprecatedEmail"));
+        assert!(glob_match("*Id", "internalId"));
+        assert!(glob_match("a*b*c", "aXbYc"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("a*a", "a"));
+        assert!(!glob_match("id", "ids"));
+    }
+}
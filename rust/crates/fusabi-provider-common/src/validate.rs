@@ -0,0 +1,423 @@
+//! Dry-run source validation
+//!
+//! [`SourceValidator::validate_source`] fully resolves, parses and generates
+//! types for a source, then throws the output away and returns structured
+//! [`Diagnostic`]s instead. Editors and CI use it to surface unmapped types,
+//! lossy conversions and deprecated constructs without writing anything.
+//!
+//! Providers get a generic validation pass for free and can add their own
+//! diagnostics by overriding [`SourceValidator::schema_diagnostics`]. The
+//! same diagnostics back [`SourceValidator::generate_with_report`], which
+//! keeps the generated types and returns a [`GenerationReport`] next to them.
+
+use crate::generics::GenericName;
+use crate::identifiers::check_identifiers;
+use crate::index::TypeIndex;
+use crate::report::GenerationReport;
+use crate::shape::TypeShape;
+use crate::span::span_of;
+use fusabi_type_providers::{
+    GeneratedTypes, ProviderError, ProviderParams, ProviderResult, Schema, TypeDefinition, TypeProvider,
+};
+use serde::{Deserialize, Serialize};
+
+/// Namespace used for the throwaway generation pass
+const VALIDATION_NAMESPACE: &str = "Validate";
+
+/// Diagnostic severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The source cannot be used
+    Error,
+    /// The source can be used, but something was lost or is suspicious
+    Warning,
+    /// Informational note
+    Info,
+}
+
+/// A single validation finding
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// Severity
+    pub severity: Severity,
+    /// Stable machine-readable code (e.g. `lossy-conversion`)
+    pub code: String,
+    /// Human-readable message
+    pub message: String,
+    /// Location in the source, such as `users.email` or `Person.address`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
+impl Diagnostic {
+    /// Create a diagnostic
+    pub fn new(severity: Severity, code: &str, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            code: code.to_string(),
+            message: message.into(),
+            location: None,
+        }
+    }
+
+    /// Create an error diagnostic
+    pub fn error(code: &str, message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, code, message)
+    }
+
+    /// Create a warning diagnostic
+    pub fn warning(code: &str, message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, code, message)
+    }
+
+    /// Create an informational diagnostic
+    pub fn info(code: &str, message: impl Into<String>) -> Self {
+        Self::new(Severity::Info, code, message)
+    }
+
+    /// Attach a source location
+    pub fn at(mut self, location: impl Into<String>) -> Self {
+        self.location = Some(location.into());
+        self
+    }
+
+    /// Error diagnostic for a provider error, located at the line and
+    /// column the error reports, if any
+    pub fn from_error(code: &str, error: &ProviderError) -> Self {
+        let diagnostic = Self::error(code, error.to_string());
+        match span_of(error) {
+            Some(span) => diagnostic.at(format!("line {}", span)),
+            None => diagnostic,
+        }
+    }
+}
+
+/// Result of validating a source
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationReport {
+    /// Provider that validated the source
+    pub provider: String,
+    /// Number of types the source would generate
+    pub type_count: usize,
+    /// Findings, in the order they were produced
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ValidationReport {
+    /// Create an empty report
+    pub fn new(provider: &str) -> Self {
+        Self {
+            provider: provider.to_string(),
+            type_count: 0,
+            diagnostics: Vec::new(),
+        }
+    }
+
+    /// Add a diagnostic
+    pub fn push(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+
+    /// Whether the source is usable (no errors)
+    pub fn is_valid(&self) -> bool {
+        !self
+            .diagnostics
+            .iter()
+            .any(|d| d.severity == Severity::Error)
+    }
+
+    /// Diagnostics with error severity
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.with_severity(Severity::Error)
+    }
+
+    /// Diagnostics with warning severity
+    pub fn warnings(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.with_severity(Severity::Warning)
+    }
+
+    fn with_severity(&self, severity: Severity) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(move |d| d.severity == severity)
+    }
+}
+
+impl Extend<Diagnostic> for ValidationReport {
+    fn extend<I: IntoIterator<Item = Diagnostic>>(&mut self, iter: I) {
+        self.diagnostics.extend(iter);
+    }
+}
+
+/// Dry-run validation for type providers
+pub trait SourceValidator: TypeProvider {
+    /// Provider-specific diagnostics for a resolved schema
+    ///
+    /// Returning an error marks the source as invalid.
+    fn schema_diagnostics(&self, _schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        Ok(Vec::new())
+    }
+
+    /// Fully parse and check a source without producing output
+    fn validate_source(&self, source: &str, params: &ProviderParams) -> ValidationReport {
+        let mut report = ValidationReport::new(self.name());
+
+        let schema = match self.resolve_schema(source, params) {
+            Ok(schema) => schema,
+            Err(e) => {
+                report.push(Diagnostic::from_error("resolve-failed", &e));
+                return report;
+            }
+        };
+
+        match self.schema_diagnostics(&schema) {
+            Ok(diagnostics) => report.extend(diagnostics),
+            Err(e) => {
+                report.push(Diagnostic::from_error("parse-failed", &e));
+                return report;
+            }
+        }
+
+        match self.generate_types(&schema, VALIDATION_NAMESPACE) {
+            Ok(types) => {
+                report.type_count = types.root_types.len()
+                    + types.modules.iter().map(|m| m.types.len()).sum::<usize>();
+                report.extend(check_generated_types(&types));
+            }
+            Err(e) => report.push(Diagnostic::from_error("generate-failed", &e)),
+        }
+
+        report
+    }
+
+    /// Generate types together with a report of what was coerced or dropped
+    fn generate_with_report(
+        &self,
+        source: &str,
+        params: &ProviderParams,
+        namespace: &str,
+    ) -> ProviderResult<(GeneratedTypes, GenerationReport)> {
+        let schema = self.resolve_schema(source, params)?;
+        let mut diagnostics = self.schema_diagnostics(&schema)?;
+        let types = self.generate_types(&schema, namespace)?;
+        diagnostics.extend(check_generated_types(&types));
+
+        let report = GenerationReport::new(self.name(), namespace, &types, diagnostics);
+        Ok((types, report))
+    }
+}
+
+/// Provider-independent checks over generated output
+///
+/// Flags fields that degraded to `any`, references to types that were
+/// never generated, type parameters the definition does not declare, and
+/// duplicate, reserved or invalid names (see [`check_identifiers`]).
+pub fn check_generated_types(types: &GeneratedTypes) -> Vec<Diagnostic> {
+    let index = TypeIndex::new(types);
+    let mut diagnostics = check_identifiers(types);
+
+    for entry in index.entries() {
+        let declared = match entry.definition {
+            TypeDefinition::Record(record) => GenericName::parse(&record.name),
+            TypeDefinition::Du(du) => GenericName::parse(&du.name),
+        };
+        let members: Vec<(String, TypeShape)> = match entry.definition {
+            TypeDefinition::Record(record) => record
+                .fields
+                .iter()
+                .map(|(name, expr)| {
+                    (
+                        format!("{}.{}", record.name, name),
+                        TypeShape::from_type_expr(expr),
+                    )
+                })
+                .collect(),
+            TypeDefinition::Du(du) => du
+                .variants
+                .iter()
+                .flat_map(|v| {
+                    v.fields.iter().map(move |expr| {
+                        (
+                            format!("{}.{}", du.name, v.name),
+                            TypeShape::from_type_expr(expr),
+                        )
+                    })
+                })
+                .collect(),
+        };
+
+        for (location, shape) in members {
+            if contains_any(&shape) {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        "coerced-any",
+                        format!("`{}` is typed as `{}`", location, shape),
+                    )
+                    .at(location.clone()),
+                );
+            }
+
+            for param in shape.params() {
+                if !declared.params.iter().any(|p| p == param) {
+                    diagnostics.push(
+                        Diagnostic::error(
+                            "unbound-type-param",
+                            format!(
+                                "`{}` uses type parameter `'{}` which `{}` does not declare",
+                                location, param, declared.name
+                            ),
+                        )
+                        .at(location.clone()),
+                    );
+                }
+            }
+
+            for reference in shape.references() {
+                if index.resolve(reference, entry.module).is_none() {
+                    diagnostics.push(
+                        Diagnostic::warning(
+                            "unresolved-reference",
+                            format!(
+                                "`{}` refers to type `{}` which was not generated",
+                                location, reference
+                            ),
+                        )
+                        .at(location.clone()),
+                    );
+                }
+            }
+        }
+    }
+
+    diagnostics
+}
+
+fn contains_any(shape: &TypeShape) -> bool {
+    match shape {
+        TypeShape::Named(name) => name == "any",
+        TypeShape::Option(inner) | TypeShape::List(inner) => contains_any(inner),
+        TypeShape::Map(key, value) => contains_any(key) || contains_any(value),
+        TypeShape::Tuple(items) | TypeShape::Union(items) | TypeShape::Generic(_, items) => {
+            items.iter().any(contains_any)
+        }
+        TypeShape::Literal(_) | TypeShape::Param(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_type_providers::{GeneratedModule, ProviderError, RecordDef, TypeExpr};
+
+    struct EchoProvider;
+
+    impl TypeProvider for EchoProvider {
+        fn name(&self) -> &str {
+            "EchoProvider"
+        }
+
+        fn resolve_schema(&self, source: &str, _params: &ProviderParams) -> ProviderResult<Schema> {
+            if source.is_empty() {
+                return Err(ProviderError::InvalidSource("empty source".to_string()));
+            }
+            if let Some(offset) = source.find('?') {
+                return Err(crate::span::parse_error_at(source, offset, "Unexpected '?'"));
+            }
+            Ok(Schema::Custom(source.to_string()))
+        }
+
+        fn generate_types(
+            &self,
+            schema: &Schema,
+            namespace: &str,
+        ) -> ProviderResult<GeneratedTypes> {
+            let Schema::Custom(field_type) = schema else {
+                return Err(ProviderError::ParseError(
+                    "Expected custom schema".to_string(),
+                ));
+            };
+
+            let mut result = GeneratedTypes::new();
+            let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+            module.types.push(TypeDefinition::Record(RecordDef {
+                name: "Echo".to_string(),
+                fields: vec![("value".to_string(), TypeExpr::Named(field_type.clone()))],
+            }));
+            result.modules.push(module);
+            Ok(result)
+        }
+    }
+
+    impl SourceValidator for EchoProvider {
+        fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+            match schema {
+                Schema::Custom(s) if s == "legacy" => Ok(vec![Diagnostic::warning(
+                    "deprecated",
+                    "legacy is deprecated",
+                )]),
+                _ => Ok(Vec::new()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_clean_source() {
+        let report = EchoProvider.validate_source("string", &ProviderParams::default());
+        assert!(report.is_valid());
+        assert_eq!(report.type_count, 1);
+        assert!(report.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_resolve_failure_is_error() {
+        let report = EchoProvider.validate_source("", &ProviderParams::default());
+        assert!(!report.is_valid());
+        assert_eq!(report.errors().next().unwrap().code, "resolve-failed");
+
+        let report = EchoProvider.validate_source("string\nint?", &ProviderParams::default());
+        let error = report.errors().next().unwrap();
+        assert_eq!(error.location.as_deref(), Some("line 2, column 4"));
+    }
+
+    #[test]
+    fn test_generic_checks() {
+        let report = EchoProvider.validate_source("Map<string, any>", &ProviderParams::default());
+        assert!(report.is_valid());
+        assert_eq!(report.warnings().next().unwrap().code, "coerced-any");
+
+        let report = EchoProvider.validate_source("Missing option", &ProviderParams::default());
+        let warning = report.warnings().next().unwrap();
+        assert_eq!(warning.code, "unresolved-reference");
+        assert_eq!(warning.location.as_deref(), Some("Echo.value"));
+
+        let report = EchoProvider.validate_source("'node list", &ProviderParams::default());
+        assert!(!report.is_valid());
+        assert_eq!(report.errors().next().unwrap().code, "unbound-type-param");
+
+        let report = EchoProvider.validate_source("Echo<string> option", &ProviderParams::default());
+        assert!(report.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_generate_with_report() {
+        let (types, report) = EchoProvider
+            .generate_with_report("any", &ProviderParams::default(), "Demo")
+            .unwrap();
+        assert_eq!(types.modules[0].path, vec!["Demo".to_string()]);
+        assert_eq!(report.namespace, "Demo");
+        assert_eq!(report.type_count(), 1);
+        assert_eq!(report.coerced().next().unwrap().location.as_deref(), Some("Echo.value"));
+
+        assert!(EchoProvider
+            .generate_with_report("", &ProviderParams::default(), "Demo")
+            .is_err());
+    }
+
+    #[test]
+    fn test_provider_diagnostics() {
+        let report = EchoProvider.validate_source("legacy", &ProviderParams::default());
+        assert_eq!(report.warnings().count(), 2);
+        assert_eq!(report.diagnostics[0].code, "deprecated");
+    }
+}
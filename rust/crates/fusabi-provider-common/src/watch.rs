@@ -0,0 +1,302 @@
+//! Regenerate types when a source file changes
+//!
+//! [`SourceWatcher`] polls a file-based source, waits until edits settle,
+//! regenerates, and hands each result to a callback together with a
+//! [`DriftReport`] against the previous output. Polling keeps the watcher
+//! free of platform-specific file notification APIs and works the same on
+//! network filesystems and in containers.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_common::watch::{SourceWatcher, WatchEvent, WatchOptions};
+//! use std::ops::ControlFlow;
+//!
+//! let watcher = SourceWatcher::new(&provider, "schema.sql", params, "Db", WatchOptions::default())?;
+//! watcher.run(|event| {
+//!     if let WatchEvent::Generated { types, changes } = event {
+//!         std::fs::write("types.fsx", emit_fusabi(types, &Default::default())).unwrap();
+//!         eprintln!("{} added, {} changed", changes.added.len(), changes.changed.len());
+//!     }
+//!     ControlFlow::Continue(())
+//! });
+//! ```
+
+use crate::lock::{diff_types, DriftReport};
+use crate::naming::generate_with_naming;
+use crate::source::is_remote;
+use fusabi_type_providers::{
+    GeneratedTypes, ProviderError, ProviderParams, ProviderResult, TypeProvider,
+};
+use sha2::{Digest, Sha256};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Default interval between checks of the source
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Default time a source must stay unchanged before regenerating
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Polling and debounce settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchOptions {
+    /// Interval between checks of the source
+    pub poll_interval: Duration,
+    /// Time the source must stay unchanged before regenerating
+    pub debounce: Duration,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            debounce: DEFAULT_DEBOUNCE,
+        }
+    }
+}
+
+/// Outcome of a generation triggered by the watcher
+#[derive(Debug)]
+pub enum WatchEvent<'w> {
+    /// Types were generated; `changes` compares against the previous output
+    ///
+    /// On the first generation every type is reported as added.
+    Generated {
+        /// Generated types, owned by the watcher until the next generation
+        types: &'w GeneratedTypes,
+        /// Difference from the previous successful generation
+        changes: DriftReport,
+    },
+    /// The source could not be resolved or generated; watching continues
+    Failed(ProviderError),
+}
+
+/// Watches a file-based source and regenerates on change
+pub struct SourceWatcher<'a> {
+    provider: &'a dyn TypeProvider,
+    source: String,
+    path: PathBuf,
+    params: ProviderParams,
+    namespace: String,
+    options: WatchOptions,
+    fingerprint: Option<[u8; 32]>,
+    previous: GeneratedTypes,
+}
+
+impl<'a> SourceWatcher<'a> {
+    /// Watch `source`, which must be a local path or `file://` URL
+    pub fn new(
+        provider: &'a dyn TypeProvider,
+        source: &str,
+        params: ProviderParams,
+        namespace: &str,
+        options: WatchOptions,
+    ) -> ProviderResult<Self> {
+        let path = PathBuf::from(source.strip_prefix("file://").unwrap_or(source));
+        if is_remote(source) || !path.is_file() {
+            return Err(ProviderError::InvalidSource(format!(
+                "Watch mode needs a local source file, got: {}",
+                source
+            )));
+        }
+
+        Ok(Self {
+            provider,
+            source: source.to_string(),
+            fingerprint: fingerprint(&path),
+            path,
+            params,
+            namespace: namespace.to_string(),
+            options,
+            previous: GeneratedTypes::new(),
+        })
+    }
+
+    /// Path of the watched file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Generate from the current source and diff against the last output
+    pub fn regenerate(&mut self) -> WatchEvent<'_> {
+        let generated =
+            generate_with_naming(self.provider, &self.source, &self.params, &self.namespace);
+
+        match generated {
+            Ok((types, _)) => {
+                let changes = diff_types(&self.previous, &types);
+                self.previous = types;
+                WatchEvent::Generated {
+                    types: &self.previous,
+                    changes,
+                }
+            }
+            Err(e) => WatchEvent::Failed(e),
+        }
+    }
+
+    /// Whether the file changed since the last check
+    ///
+    /// A missing file counts as unchanged, so that editors which save by
+    /// deleting and recreating a file do not trigger a failed generation.
+    pub fn poll(&mut self) -> bool {
+        match fingerprint(&self.path) {
+            Some(current) if Some(current) != self.fingerprint => {
+                self.fingerprint = Some(current);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Block until the file changed and then stayed unchanged for the debounce time
+    pub fn wait_for_change(&mut self) {
+        while !self.poll() {
+            std::thread::sleep(self.options.poll_interval);
+        }
+
+        let mut settled_since = Instant::now();
+        while settled_since.elapsed() < self.options.debounce {
+            std::thread::sleep(self.options.poll_interval.min(self.options.debounce));
+            if self.poll() {
+                settled_since = Instant::now();
+            }
+        }
+    }
+
+    /// Generate once, then regenerate after every change until `on_event` breaks
+    pub fn run<F>(mut self, mut on_event: F)
+    where
+        F: FnMut(WatchEvent<'_>) -> ControlFlow<()>,
+    {
+        loop {
+            if on_event(self.regenerate()).is_break() {
+                return;
+            }
+            self.wait_for_change();
+        }
+    }
+}
+
+/// SHA-256 of the file content, or `None` when it cannot be read
+fn fingerprint(path: &Path) -> Option<[u8; 32]> {
+    std::fs::read(path).ok().map(|content| Sha256::digest(content).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_type_providers::{GeneratedModule, RecordDef, Schema, TypeDefinition};
+
+    /// Generates one empty record per line of the file
+    struct LinesProvider;
+
+    impl TypeProvider for LinesProvider {
+        fn name(&self) -> &str {
+            "LinesProvider"
+        }
+
+        fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+            crate::read_source(source, params).map(Schema::Custom)
+        }
+
+        fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+            let Schema::Custom(text) = schema else { unreachable!() };
+            if text.contains("error") {
+                return Err(ProviderError::ParseError("bad line".to_string()));
+            }
+
+            let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+            module.types.extend(text.lines().map(|line| {
+                TypeDefinition::Record(RecordDef {
+                    name: line.trim().to_string(),
+                    fields: Vec::new(),
+                })
+            }));
+            let mut types = GeneratedTypes::new();
+            types.modules.push(module);
+            Ok(types)
+        }
+    }
+
+    fn fast() -> WatchOptions {
+        WatchOptions {
+            poll_interval: Duration::from_millis(10),
+            debounce: Duration::from_millis(30),
+        }
+    }
+
+    #[test]
+    fn test_rejects_non_file_sources() {
+        let params = ProviderParams::default();
+        assert!(SourceWatcher::new(&LinesProvider, "https://example.com/a", params.clone(), "A", fast()).is_err());
+        assert!(SourceWatcher::new(&LinesProvider, "/nonexistent/schema.txt", params, "A", fast()).is_err());
+    }
+
+    #[test]
+    fn test_poll_and_regenerate() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("schema.txt");
+        std::fs::write(&path, "User\n").unwrap();
+        let source = format!("file://{}", path.display());
+        let mut watcher =
+            SourceWatcher::new(&LinesProvider, &source, ProviderParams::default(), "Db", fast()).unwrap();
+
+        let WatchEvent::Generated { changes, .. } = watcher.regenerate() else { panic!() };
+        assert_eq!(changes.added, vec!["Db.User"]);
+        assert!(!watcher.poll());
+
+        std::fs::write(&path, "User\nPost\n").unwrap();
+        assert!(watcher.poll());
+        assert!(!watcher.poll());
+        let WatchEvent::Generated { changes, .. } = watcher.regenerate() else { panic!() };
+        assert_eq!(changes.added, vec!["Db.Post"]);
+        assert!(changes.removed.is_empty());
+
+        std::fs::write(&path, "error\n").unwrap();
+        assert!(matches!(watcher.regenerate(), WatchEvent::Failed(_)));
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(!watcher.poll());
+    }
+
+    #[test]
+    fn test_run_until_break() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("schema.txt");
+        std::fs::write(&path, "User\n").unwrap();
+        let watcher = SourceWatcher::new(
+            &LinesProvider,
+            path.to_str().unwrap(),
+            ProviderParams::default(),
+            "Db",
+            fast(),
+        )
+        .unwrap();
+
+        let writer = {
+            let path = path.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(50));
+                std::fs::write(&path, "Account\n").unwrap();
+            })
+        };
+
+        let mut reports = Vec::new();
+        watcher.run(|event| {
+            let WatchEvent::Generated { changes, .. } = event else { panic!() };
+            reports.push(changes);
+            if reports.len() == 2 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        writer.join().unwrap();
+
+        assert_eq!(reports[1].added, vec!["Db.Account"]);
+        assert_eq!(reports[1].removed, vec!["Db.User"]);
+    }
+}
@@ -0,0 +1,160 @@
+//! Structure inference over JSON values
+//!
+//! Every value is reduced to a [`Shape`], and shapes of values found in the
+//! same position (the same key across services, the elements of a list) are
+//! merged so that one record covers them all.
+
+use serde_json::Value;
+
+/// Inferred structure of one or more values
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    /// Only `null` was seen
+    Null,
+    Bool,
+    Int,
+    Float,
+    String,
+    /// Scalars of different kinds; Compose accepts them as strings
+    Mixed,
+    /// Element shape; `Null` for lists that were always empty
+    List(Box<Shape>),
+    Object(ObjectShape),
+    /// Values with incompatible structure
+    Any,
+}
+
+/// Merged keys of one or more objects
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ObjectShape {
+    /// Number of objects merged
+    pub samples: usize,
+    /// Keys in first-seen order
+    pub fields: Vec<FieldShape>,
+}
+
+/// One key of an [`ObjectShape`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldShape {
+    pub name: String,
+    pub shape: Shape,
+    /// Number of merged objects that had the key
+    pub present: usize,
+    /// Whether the key was `null` in some object
+    pub nullable: bool,
+}
+
+impl FieldShape {
+    /// Whether some object lacked the key or set it to `null`
+    pub fn is_optional(&self, samples: usize) -> bool {
+        self.nullable || self.present < samples
+    }
+}
+
+impl Shape {
+    /// Infer the shape of a single value
+    pub fn of(value: &Value) -> Self {
+        match value {
+            Value::Null => Shape::Null,
+            Value::Bool(_) => Shape::Bool,
+            Value::Number(n) if n.is_f64() => Shape::Float,
+            Value::Number(_) => Shape::Int,
+            Value::String(_) => Shape::String,
+            Value::Array(items) => Shape::List(Box::new(Shape::merge_all(items.iter().map(Shape::of)))),
+            Value::Object(object) => Shape::Object(ObjectShape {
+                samples: 1,
+                fields: object
+                    .iter()
+                    .map(|(name, value)| FieldShape {
+                        name: name.clone(),
+                        shape: Shape::of(value),
+                        present: 1,
+                        nullable: value.is_null(),
+                    })
+                    .collect(),
+            }),
+        }
+    }
+
+    /// Merge the shapes of values found in the same position
+    pub fn merge(self, other: Shape) -> Shape {
+        match (self, other) {
+            (Shape::Null, shape) | (shape, Shape::Null) => shape,
+            (a, b) if a == b && !matches!(a, Shape::Object(_)) => a,
+            (Shape::Int, Shape::Float) | (Shape::Float, Shape::Int) => Shape::Float,
+            (a, b) if a.is_scalar() && b.is_scalar() => Shape::Mixed,
+            (Shape::List(a), Shape::List(b)) => Shape::List(Box::new(a.merge(*b))),
+            (Shape::Object(a), Shape::Object(b)) => Shape::Object(a.merge(b)),
+            _ => Shape::Any,
+        }
+    }
+
+    /// Merge any number of shapes; `Null` when there are none
+    pub fn merge_all(shapes: impl IntoIterator<Item = Shape>) -> Shape {
+        shapes.into_iter().fold(Shape::Null, Shape::merge)
+    }
+
+    fn is_scalar(&self) -> bool {
+        matches!(
+            self,
+            Shape::Bool | Shape::Int | Shape::Float | Shape::String | Shape::Mixed
+        )
+    }
+}
+
+impl ObjectShape {
+    fn merge(mut self, other: ObjectShape) -> ObjectShape {
+        self.samples += other.samples;
+        for field in other.fields {
+            match self.fields.iter_mut().find(|f| f.name == field.name) {
+                Some(existing) => {
+                    existing.present += field.present;
+                    existing.nullable |= field.nullable;
+                    let shape = std::mem::replace(&mut existing.shape, Shape::Null);
+                    existing.shape = shape.merge(field.shape);
+                }
+                None => self.fields.push(field),
+            }
+        }
+        self
+    }
+
+    /// The merged shape of every value, for objects keyed by user-chosen
+    /// names such as `environment` or `labels`
+    pub fn values(&self) -> Shape {
+        Shape::merge_all(self.fields.iter().map(|f| f.shape.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_merge_objects() {
+        let shape = Shape::merge_all([
+            Shape::of(&json!({ "image": "nginx", "ports": ["80:80"], "restart": null })),
+            Shape::of(&json!({ "image": "redis", "ports": [6379], "command": ["redis-server"] })),
+        ]);
+
+        let Shape::Object(object) = shape else {
+            panic!("Expected object shape");
+        };
+        assert_eq!(object.samples, 2);
+        let field = |name: &str| object.fields.iter().find(|f| f.name == name).unwrap();
+        assert!(!field("image").is_optional(2));
+        assert_eq!(field("ports").shape, Shape::List(Box::new(Shape::Mixed)));
+        assert!(field("restart").is_optional(2));
+        assert_eq!(field("restart").shape, Shape::Null);
+        assert!(field("command").is_optional(2));
+    }
+
+    #[test]
+    fn test_merge_incompatible() {
+        assert_eq!(Shape::Int.merge(Shape::Float), Shape::Float);
+        assert_eq!(Shape::String.merge(Shape::Null), Shape::String);
+        assert_eq!(Shape::of(&json!("./app")).merge(Shape::of(&json!({ "context": "." }))), Shape::Any);
+        assert_eq!(Shape::of(&json!([])), Shape::List(Box::new(Shape::Null)));
+    }
+}
@@ -0,0 +1,594 @@
+//! Docker Compose Type Provider
+//!
+//! Generates Fusabi types for Docker Compose files, in one of two ways:
+//!
+//! - **Structure inference** (a compose file as the source): the services,
+//!   networks, volumes, secrets and configs of the file are inferred and
+//!   merged into shared records, so every service is typed by one `Service`
+//!   record covering the keys used anywhere in the file.
+//! - **Compose specification** (the published `compose-spec.json` schema as
+//!   the source): the schema's definitions are converted like any other
+//!   JSON Schema, with a `Compose` record for the file itself.
+//!
+//! The mode is picked from the document: JSON Schemas (with `$schema` or
+//! `definitions`) use the specification, anything with `services` is
+//! inferred.
+//!
+//! # Inferred Types
+//!
+//! | Compose                                   | Fusabi                          |
+//! |-------------------------------------------|---------------------------------|
+//! | `services` / `networks` / `volumes` / ... | `Map<string, Service>`, ...     |
+//! | nested mapping (`build`, `deploy`)        | record `<Parent><Key>`          |
+//! | user-keyed mapping (`environment`, `labels`, `args`, ...) | `Map<string, T>` |
+//! | list                                      | `T list`                        |
+//! | key missing from some services, or `null` | `T option`                      |
+//! | mixed scalars (`8080` and `"80:80"`)      | `string`                        |
+//! | mixed structure (short and long syntax)   | `any`                           |
+//!
+//! `ServiceName` is a union of the file's services and `Profile` a union of
+//! the profiles they use. Extension keys (`x-*`) are skipped; YAML anchors
+//! and `<<` merge keys are applied before inference.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_compose::ComposeProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = ComposeProvider::new();
+//! let schema = provider.resolve_schema("docker-compose.yml", &ProviderParams::default())?;
+//! let types = provider.generate_types(&schema, "DevEnv")?;
+//! ```
+
+mod infer;
+
+pub use infer::{FieldShape, ObjectShape, Shape};
+
+use fusabi_jsonschema_core::{definitions_of, Converter};
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, SourceValidator,
+    TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, DuDef, VariantDef, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use serde_json::{Map, Value};
+
+/// Top-level sections keyed by user-chosen names, and their record names
+pub const SECTIONS: [(&str, &str); 5] = [
+    ("services", "Service"),
+    ("networks", "Network"),
+    ("volumes", "Volume"),
+    ("secrets", "Secret"),
+    ("configs", "Config"),
+];
+
+/// Mappings whose keys are user-chosen names rather than a fixed set of
+/// attributes; they become `Map<string, T>` instead of records
+pub const DICTIONARY_KEYS: [&str; 12] = [
+    "annotations",
+    "args",
+    "depends_on",
+    "driver_opts",
+    "environment",
+    "extra_hosts",
+    "labels",
+    "networks",
+    "options",
+    "storage_opt",
+    "sysctls",
+    "ulimits",
+];
+
+/// Name of the record for the compose file itself
+const ROOT_TYPE: &str = "Compose";
+
+/// Docker Compose type provider
+pub struct ComposeProvider {
+    converter: Converter,
+    generator: TypeGenerator,
+}
+
+impl ComposeProvider {
+    pub fn new() -> Self {
+        Self {
+            converter: Converter::new(),
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    /// Parse YAML or JSON, applying `<<` merge keys
+    fn parse_document(&self, content: &str) -> ProviderResult<Value> {
+        let mut yaml: serde_yaml::Value = serde_yaml::from_str(content)
+            .map_err(|e| ProviderError::ParseError(format!("Invalid compose file: {}", e)))?;
+        yaml.apply_merge()
+            .map_err(|e| ProviderError::ParseError(format!("Invalid compose file: {}", e)))?;
+        serde_json::to_value(yaml).map_err(|e| ProviderError::ParseError(e.to_string()))
+    }
+
+    fn compose_file<'a>(&self, document: &'a Value) -> ProviderResult<&'a Map<String, Value>> {
+        document
+            .as_object()
+            .filter(|root| root.get("services").is_some_and(Value::is_object))
+            .ok_or_else(|| {
+                ProviderError::ParseError("Compose file has no `services` mapping".to_string())
+            })
+    }
+
+    /// Generate types for the compose specification schema
+    fn generate_from_spec(&self, spec: &Value, namespace: &str) -> GeneratedTypes {
+        let mut result = GeneratedTypes::new();
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+
+        if let Some(definitions) = definitions_of(spec) {
+            module.types = self.converter.definitions(definitions);
+        }
+        module.types.extend(self.converter.definition(ROOT_TYPE, spec));
+
+        if !module.types.is_empty() {
+            result.modules.push(module);
+        }
+        result
+    }
+
+    /// Infer types from a compose file, returning them with the
+    /// diagnostics raised along the way
+    fn infer_types(&self, file: &Map<String, Value>) -> (Vec<TypeDefinition>, Vec<Diagnostic>) {
+        let mut inference = Inference {
+            generator: &self.generator,
+            types: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+        let mut fields = Vec::new();
+
+        for (key, value) in file {
+            if key.starts_with("x-") {
+                inference.extension_skipped(key);
+                continue;
+            }
+
+            let shape = match SECTIONS.iter().find(|(section, _)| section == key) {
+                Some((section, record)) => {
+                    let entries = value.as_object().into_iter().flatten();
+                    let shape = match Shape::merge_all(entries.map(|(_, v)| Shape::of(v))) {
+                        // `networks: { default: }` declares entries with no settings
+                        Shape::Null => Shape::Object(ObjectShape::default()),
+                        shape => shape,
+                    };
+                    let element = inference.type_shape(&shape, record, &format!("{}.*", section), false);
+                    TypeShape::map(TypeShape::named("string"), element)
+                }
+                None => {
+                    if key == "version" {
+                        inference.diagnostics.push(
+                            Diagnostic::info(
+                                "version-obsolete",
+                                "The top-level `version` key is obsolete in the Compose specification",
+                            )
+                            .at("version"),
+                        );
+                    }
+                    let context = format!("{}{}", ROOT_TYPE, self.generator.naming.apply(key));
+                    inference.type_shape(&Shape::of(value), &context, key, false)
+                }
+            };
+            fields.push((key.clone(), shape.into()));
+        }
+
+        let services = file.get("services").and_then(Value::as_object);
+        let service_names: Vec<&str> = services.into_iter().flat_map(|s| s.keys()).map(String::as_str).collect();
+        inference.union("ServiceName", &service_names);
+
+        let mut profiles: Vec<&str> = Vec::new();
+        for service in services.into_iter().flat_map(|s| s.values()) {
+            for profile in service.get("profiles").and_then(Value::as_array).into_iter().flatten() {
+                if let Some(profile) = profile.as_str().filter(|p| !profiles.contains(p)) {
+                    profiles.push(profile);
+                }
+            }
+        }
+        inference.union("Profile", &profiles);
+
+        inference.types.push(TypeDefinition::Record(RecordDef {
+            name: ROOT_TYPE.to_string(),
+            fields,
+        }));
+        (inference.types, inference.diagnostics)
+    }
+}
+
+/// Record generation state for structure inference
+struct Inference<'a> {
+    generator: &'a TypeGenerator,
+    types: Vec<TypeDefinition>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Inference<'_> {
+    /// Convert a shape to a TypeShape; objects become records named `name`,
+    /// or maps when `dictionary` is set
+    fn type_shape(&mut self, shape: &Shape, name: &str, location: &str, dictionary: bool) -> TypeShape {
+        match shape {
+            Shape::Bool => TypeShape::named("bool"),
+            Shape::Int => TypeShape::named("int"),
+            Shape::Float => TypeShape::named("float"),
+            Shape::String => TypeShape::named("string"),
+            Shape::Mixed => {
+                self.diagnostics.push(
+                    Diagnostic::info(
+                        "lossy-conversion",
+                        format!("`{}` mixes numbers, booleans and strings; generated as `string`", location),
+                    )
+                    .at(location.to_string()),
+                );
+                TypeShape::named("string")
+            }
+            Shape::List(element) => self.type_shape(element, name, &format!("{}[]", location), false).list(),
+            Shape::Object(object) if dictionary => TypeShape::map(
+                TypeShape::named("string"),
+                self.type_shape(&object.values(), name, &format!("{}.*", location), false),
+            ),
+            Shape::Object(object) => {
+                let mut fields = Vec::new();
+                for field in &object.fields {
+                    let field_location = format!("{}.{}", location, field.name);
+                    if field.name.starts_with("x-") {
+                        self.extension_skipped(&field_location);
+                        continue;
+                    }
+                    let context = format!("{}{}", name, self.generator.naming.apply(&field.name));
+                    let dictionary = DICTIONARY_KEYS.contains(&field.name.as_str());
+                    let base = self.type_shape(&field.shape, &context, &field_location, dictionary);
+                    let shape = base.option_unless(!field.is_optional(object.samples));
+                    fields.push((field.name.clone(), shape.into()));
+                }
+                self.types.push(TypeDefinition::Record(RecordDef {
+                    name: name.to_string(),
+                    fields,
+                }));
+                TypeShape::named(name)
+            }
+            Shape::Null | Shape::Any => {
+                let reason = if *shape == Shape::Null {
+                    "is always null or empty"
+                } else {
+                    "mixes incompatible forms"
+                };
+                self.diagnostics.push(
+                    Diagnostic::info("coerced-any", format!("`{}` {}; generated as `any`", location, reason))
+                        .at(location.to_string()),
+                );
+                TypeShape::named("any")
+            }
+        }
+    }
+
+    fn union(&mut self, name: &str, values: &[&str]) {
+        if values.is_empty() {
+            return;
+        }
+        self.types.push(TypeDefinition::Du(DuDef {
+            name: name.to_string(),
+            variants: values
+                .iter()
+                .map(|v| VariantDef::new_simple(self.generator.naming.apply(v)))
+                .collect(),
+        }));
+    }
+
+    fn extension_skipped(&mut self, location: &str) {
+        self.diagnostics.push(
+            Diagnostic::info(
+                "extension-skipped",
+                format!("Extension `{}` does not generate types", location),
+            )
+            .at(location.to_string()),
+        );
+    }
+}
+
+impl Default for ComposeProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypeProvider for ComposeProvider {
+    fn name(&self) -> &str {
+        "ComposeProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        // Paths and URLs never span lines; inline YAML and JSON do
+        let content = if source.contains('\n') || source.trim_start().starts_with('{') {
+            source.to_string()
+        } else {
+            read_source(source, params)?
+        };
+
+        let document = self.parse_document(&content)?;
+        if is_specification(&document) {
+            return Ok(Schema::JsonSchema(normalize_pattern_properties(document)));
+        }
+
+        self.compose_file(&document)?;
+        Ok(Schema::Custom(document.to_string()))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        match schema {
+            Schema::JsonSchema(spec) => Ok(self.generate_from_spec(spec, namespace)),
+            Schema::Custom(content) => {
+                let document = self.parse_document(content)?;
+                let (types, _) = self.infer_types(self.compose_file(&document)?);
+
+                let mut result = GeneratedTypes::new();
+                let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+                module.types = types;
+                result.modules.push(module);
+                Ok(result)
+            }
+            _ => Err(ProviderError::ParseError("Expected Compose schema".to_string())),
+        }
+    }
+}
+
+impl SourceValidator for ComposeProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        match schema {
+            Schema::JsonSchema(_) => Ok(Vec::new()),
+            Schema::Custom(content) => {
+                let document = self.parse_document(content)?;
+                let (_, diagnostics) = self.infer_types(self.compose_file(&document)?);
+                Ok(diagnostics)
+            }
+            _ => Err(ProviderError::ParseError("Expected Compose schema".to_string())),
+        }
+    }
+}
+
+impl DescribedProvider for ComposeProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+/// Whether the document is the compose specification schema rather than a
+/// compose file
+fn is_specification(document: &Value) -> bool {
+    document.get("$schema").is_some()
+        || (document.get("definitions").is_some() && document.get("services").is_none())
+}
+
+/// The specification keys `services`, `networks` and friends by a single
+/// name pattern; treat that pattern as `additionalProperties` so they
+/// convert to maps of the referenced definition
+fn normalize_pattern_properties(mut schema: Value) -> Value {
+    if let Value::Object(object) = &mut schema {
+        let single_pattern = object
+            .get("patternProperties")
+            .and_then(Value::as_object)
+            .filter(|patterns| patterns.len() == 1)
+            .and_then(|patterns| patterns.values().next().cloned());
+        if let Some(pattern) = single_pattern {
+            if !object.get("additionalProperties").is_some_and(Value::is_object) {
+                object.insert("additionalProperties".to_string(), pattern);
+            }
+        }
+        for value in object.values_mut() {
+            *value = normalize_pattern_properties(std::mem::take(value));
+        }
+    } else if let Value::Array(items) = &mut schema {
+        for item in items.iter_mut() {
+            *item = normalize_pattern_properties(std::mem::take(item));
+        }
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    const COMPOSE: &str = r#"
+version: "3.9"
+name: shop
+
+x-logging: &logging
+  driver: json-file
+  options:
+    max-size: 10m
+
+services:
+  web:
+    build:
+      context: ./web
+      args:
+        NODE_ENV: production
+    ports:
+      - "80:80"
+      - 8443
+    environment:
+      API_URL: http://api:8080
+      WORKERS: 4
+    depends_on:
+      - api
+    profiles: [frontend]
+    logging: *logging
+  api:
+    image: shop/api:latest
+    environment:
+      - DEBUG=1
+    deploy:
+      resources:
+        limits:
+          cpus: "0.5"
+          memory: 512M
+    profiles: [backend, debug]
+    logging:
+      <<: *logging
+      driver: local
+
+volumes:
+  data:
+
+networks:
+  internal:
+    driver: bridge
+"#;
+
+    fn generate(source: &str) -> GeneratedModule {
+        let provider = ComposeProvider::new();
+        let schema = provider.resolve_schema(source, &ProviderParams::default()).unwrap();
+        let mut types = provider.generate_types(&schema, "Shop").unwrap();
+        assert_eq!(types.modules.len(), 1);
+        types.modules.remove(0)
+    }
+
+    fn fields(module: &GeneratedModule, name: &str) -> Vec<(String, String)> {
+        module
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+            .fields
+            .iter()
+            .map(|(name, ty)| (name.clone(), ty.to_string()))
+            .collect()
+    }
+
+    fn field<'a>(fields: &'a [(String, String)], name: &str) -> &'a str {
+        &fields.iter().find(|(n, _)| n == name).unwrap().1
+    }
+
+    #[test]
+    fn test_infer_services() {
+        let module = generate(COMPOSE);
+
+        let compose = fields(&module, "Compose");
+        assert_eq!(
+            compose.iter().map(|(n, _)| n.as_str()).collect::<Vec<_>>(),
+            ["name", "networks", "services", "version", "volumes"]
+        );
+        assert_eq!(field(&compose, "services"), "Map<string, Service>");
+        assert_eq!(field(&compose, "volumes"), "Map<string, Volume>");
+
+        let service = fields(&module, "Service");
+        assert_eq!(field(&service, "build"), "ServiceBuild option");
+        assert_eq!(field(&service, "ports"), "string list option");
+        assert_eq!(field(&service, "environment"), "any");
+        assert_eq!(field(&service, "profiles"), "string list");
+        assert_eq!(field(&service, "logging"), "ServiceLogging");
+        assert_eq!(field(&service, "deploy"), "ServiceDeploy option");
+
+        assert_eq!(
+            fields(&module, "ServiceBuild"),
+            [
+                ("args".to_string(), "Map<string, string>".to_string()),
+                ("context".to_string(), "string".to_string()),
+            ]
+        );
+        assert_eq!(
+            field(&fields(&module, "ServiceLogging"), "options"),
+            "Map<string, string>"
+        );
+        assert_eq!(
+            field(&fields(&module, "ServiceDeployResourcesLimits"), "memory"),
+            "string"
+        );
+        assert!(fields(&module, "Volume").is_empty());
+        assert_eq!(fields(&module, "Network"), [("driver".to_string(), "string".to_string())]);
+
+        let mut types = GeneratedTypes::new();
+        types.modules.push(module);
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/compose.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_service_name_and_profile_unions() {
+        let module = generate(COMPOSE);
+        let union = |name: &str| {
+            module
+                .types
+                .iter()
+                .find_map(|t| match t {
+                    TypeDefinition::Du(du) if du.name == name => {
+                        Some(du.variants.iter().map(|v| v.name.clone()).collect::<Vec<_>>())
+                    }
+                    _ => None,
+                })
+                .unwrap()
+        };
+        assert_eq!(union("ServiceName"), ["Api", "Web"]);
+        assert_eq!(union("Profile"), ["Backend", "Debug", "Frontend"]);
+    }
+
+    #[test]
+    fn test_specification_mode() {
+        let spec = r##"{
+          "$schema": "https://json-schema.org/draft/2019-09/schema#",
+          "type": "object",
+          "properties": {
+            "name": { "type": "string" },
+            "services": {
+              "type": "object",
+              "patternProperties": { "^[a-zA-Z0-9._-]+$": { "$ref": "#/definitions/service" } }
+            }
+          },
+          "definitions": {
+            "service": {
+              "type": "object",
+              "properties": {
+                "image": { "type": "string" },
+                "deploy": { "$ref": "#/definitions/deployment" }
+              }
+            },
+            "deployment": {
+              "type": ["object", "null"],
+              "properties": { "replicas": { "type": "integer" } }
+            }
+          }
+        }"##;
+
+        let module = generate(spec);
+        assert_eq!(
+            field(&fields(&module, "Compose"), "services"),
+            "Map<string, Service> option"
+        );
+        assert_eq!(field(&fields(&module, "Service"), "deploy"), "Deployment option");
+        assert_eq!(fields(&module, "Deployment").len(), 1);
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = ComposeProvider::new();
+        let report = provider.validate_source(COMPOSE, &ProviderParams::default());
+        assert!(report.is_valid());
+
+        let codes: Vec<(&str, Option<&str>)> = report
+            .diagnostics
+            .iter()
+            .map(|d| (d.code.as_str(), d.location.as_deref()))
+            .collect();
+        assert!(codes.contains(&("extension-skipped", Some("x-logging"))));
+        assert!(codes.contains(&("version-obsolete", Some("version"))));
+        assert!(codes.contains(&("lossy-conversion", Some("services.*.ports[]"))));
+        assert!(codes.contains(&("coerced-any", Some("services.*.environment"))));
+
+        assert!(!provider.validate_source("name: empty\n", &ProviderParams::default()).is_valid());
+    }
+}
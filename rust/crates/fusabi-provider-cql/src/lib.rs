@@ -0,0 +1,525 @@
+//! Cassandra CQL Type Provider
+//!
+//! Generates Fusabi types from CQL schema statements, as used by Apache
+//! Cassandra and ScyllaDB. The source is inline CQL, a `.cql` file, or the
+//! output of `cqlsh -e "DESCRIBE KEYSPACE ..."`.
+//!
+//! # Generated Types
+//!
+//! Types are grouped into a module per keyspace, `<namespace>.<Keyspace>`,
+//! or `<namespace>` for statements with no keyspace (no `USE` and no
+//! qualified name).
+//!
+//! - `CREATE TABLE t`: a record `T` with every column, plus
+//!   `TPartitionKey` with the partition key columns and, if the table has
+//!   clustering columns, `TClusteringKey`. Primary key columns are
+//!   required; every other column is `T option`, as Cassandra rows may
+//!   omit any regular or static column
+//! - `CREATE TYPE u`: a record `U` with every field optional
+//! - `CREATE MATERIALIZED VIEW v`: a record `V` with the selected columns,
+//!   typed from the base table, plus key records as for tables
+//!
+//! | CQL                                    | Fusabi                    |
+//! |----------------------------------------|---------------------------|
+//! | `ascii`, `text`, `varchar`, `inet`     | `string`                  |
+//! | `uuid`, `timeuuid`, `date`, `duration` | `string`                  |
+//! | `tinyint`, `smallint`, `int`           | `int`                     |
+//! | `bigint`, `counter`, `varint`          | `int64`                   |
+//! | `timestamp` (ms), `time` (ns)          | `int64`                   |
+//! | `float`, `double`, `decimal`           | `float`                   |
+//! | `boolean`                              | `bool`                    |
+//! | `blob`, custom types                   | `bytes`                   |
+//! | `list<T>`, `set<T>`, `vector<T, n>`    | `T list`                  |
+//! | `map<K, V>`                            | `Map<K, V>`               |
+//! | `tuple<A, B>`                          | `A * B`                   |
+//! | `frozen<T>`                            | `T`                       |
+//! | user-defined type `u`                  | `U`                       |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_cql::CqlProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = CqlProvider::new();
+//! let schema = provider.resolve_schema("schema.cql", &ProviderParams::default())?;
+//! let types = provider.generate_types(&schema, "Store")?;
+//! ```
+
+mod parser;
+mod types;
+
+pub use parser::parse_cql;
+pub use types::{Column, CqlSchema, CqlType, Skipped, Table, UserType, View};
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, SourceValidator,
+    TypeShape,
+};
+use fusabi_type_providers::{
+    GeneratedModule, GeneratedTypes, NamingStrategy, ProviderError, ProviderParams,
+    ProviderResult, RecordDef, Schema, TypeDefinition, TypeGenerator, TypeProvider,
+};
+
+/// Cassandra/ScyllaDB CQL type provider
+pub struct CqlProvider {
+    generator: TypeGenerator,
+}
+
+impl CqlProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    fn parse_schema(&self, schema: &Schema) -> ProviderResult<CqlSchema> {
+        match schema {
+            Schema::Custom(cql) => parse_cql(cql),
+            _ => Err(ProviderError::ParseError("Expected CQL schema".to_string())),
+        }
+    }
+
+    /// Generate types from a parsed CQL schema
+    fn generate_from_schema(&self, schema: &CqlSchema, namespace: &str) -> GeneratedTypes {
+        // Types by keyspace, in statement order
+        let mut generated: Vec<(&Option<String>, Vec<TypeDefinition>)> = Vec::new();
+
+        for user_type in &schema.types {
+            let fields = user_type
+                .fields
+                .iter()
+                .map(|(name, ty)| (name.clone(), TypeShape::Option(Box::new(self.shape_of(ty))).into()))
+                .collect();
+            let record = TypeDefinition::Record(RecordDef {
+                name: self.generator.naming.apply(&user_type.name),
+                fields,
+            });
+            generated.push((&user_type.keyspace, vec![record]));
+        }
+
+        for table in &schema.tables {
+            let types = self.table_types(&table.name, &table.columns, &table.partition_key, &table.clustering);
+            generated.push((&table.keyspace, types));
+        }
+
+        for view in &schema.views {
+            // Views over unknown tables are reported by `schema_diagnostics`
+            let Some(base) = base_table(schema, view) else {
+                continue;
+            };
+            let columns: Vec<Column> = base
+                .columns
+                .iter()
+                .filter(|c| {
+                    view.columns.is_empty()
+                        || view.columns.contains(&c.name)
+                        || view.partition_key.contains(&c.name)
+                        || view.clustering.contains(&c.name)
+                })
+                .cloned()
+                .collect();
+            let types = self.table_types(&view.name, &columns, &view.partition_key, &view.clustering);
+            generated.push((&view.keyspace, types));
+        }
+
+        let mut result = GeneratedTypes::new();
+        let mut keyspaces: Vec<&Option<String>> = Vec::new();
+        for (keyspace, types) in generated {
+            let index = match keyspaces.iter().position(|k| *k == keyspace) {
+                Some(index) => index,
+                None => {
+                    let mut path = vec![namespace.to_string()];
+                    path.extend(keyspace.as_deref().map(|k| self.generator.naming.apply(k)));
+                    result.modules.push(GeneratedModule::new(path));
+                    keyspaces.push(keyspace);
+                    keyspaces.len() - 1
+                }
+            };
+            result.modules[index].types.extend(types);
+        }
+        result
+    }
+
+    /// The row record and key records of a table or view
+    fn table_types(
+        &self,
+        name: &str,
+        columns: &[Column],
+        partition_key: &[String],
+        clustering: &[String],
+    ) -> Vec<TypeDefinition> {
+        let type_name = self.generator.naming.apply(name);
+        let is_key = |column: &str| partition_key.iter().chain(clustering).any(|k| k == column);
+
+        let fields = columns
+            .iter()
+            .map(|column| {
+                let shape = self.shape_of(&column.ty);
+                let shape = if is_key(&column.name) { shape } else { TypeShape::Option(Box::new(shape)) };
+                (column.name.clone(), shape.into())
+            })
+            .collect();
+        let mut types = vec![TypeDefinition::Record(RecordDef { name: type_name.clone(), fields })];
+
+        for (suffix, key) in [("PartitionKey", partition_key), ("ClusteringKey", clustering)] {
+            if key.is_empty() {
+                continue;
+            }
+            let fields = key
+                .iter()
+                .filter_map(|name| columns.iter().find(|c| &c.name == name))
+                .map(|column| (column.name.clone(), self.shape_of(&column.ty).into()))
+                .collect();
+            types.push(TypeDefinition::Record(RecordDef {
+                name: format!("{}{}", type_name, suffix),
+                fields,
+            }));
+        }
+        types
+    }
+
+    /// Map a CQL type to a Fusabi type
+    fn shape_of(&self, ty: &CqlType) -> TypeShape {
+        let named = |name: &str| TypeShape::Named(name.to_string());
+        match ty {
+            CqlType::Ascii
+            | CqlType::Text
+            | CqlType::Inet
+            | CqlType::Uuid
+            | CqlType::Timeuuid
+            | CqlType::Date
+            | CqlType::Duration => named("string"),
+            CqlType::Tinyint | CqlType::Smallint | CqlType::Int => named("int"),
+            CqlType::Bigint | CqlType::Counter | CqlType::Varint | CqlType::Timestamp | CqlType::Time => {
+                named("int64")
+            }
+            CqlType::Float | CqlType::Double | CqlType::Decimal => named("float"),
+            CqlType::Boolean => named("bool"),
+            CqlType::Blob | CqlType::Custom(_) => named("bytes"),
+            CqlType::List(element) | CqlType::Set(element) | CqlType::Vector(element, _) => {
+                TypeShape::List(Box::new(self.shape_of(element)))
+            }
+            CqlType::Map(key, value) => TypeShape::Map(Box::new(self.shape_of(key)), Box::new(self.shape_of(value))),
+            CqlType::Tuple(elements) => TypeShape::Tuple(elements.iter().map(|e| self.shape_of(e)).collect()),
+            CqlType::Frozen(inner) => self.shape_of(inner),
+            CqlType::Udt(name) => TypeShape::Named(self.generator.naming.apply(name)),
+        }
+    }
+}
+
+impl Default for CqlProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypeProvider for CqlProvider {
+    fn name(&self) -> &str {
+        "CqlProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        // Inline CQL, or a file path
+        let cql = if source.to_uppercase().contains("CREATE ") {
+            source.to_string()
+        } else {
+            read_source(source, params)?
+        };
+
+        // Fail early on invalid CQL
+        parse_cql(&cql)?;
+        Ok(Schema::Custom(cql))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let parsed = self.parse_schema(schema)?;
+        Ok(self.generate_from_schema(&parsed, namespace))
+    }
+}
+
+impl SourceValidator for CqlProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let parsed = self.parse_schema(schema)?;
+        let mut diagnostics = Vec::new();
+
+        if parsed.tables.is_empty() && parsed.types.is_empty() && parsed.views.is_empty() {
+            diagnostics.push(Diagnostic::warning(
+                "no-types",
+                "No CREATE TABLE, CREATE TYPE or CREATE MATERIALIZED VIEW statements found",
+            ));
+        }
+
+        let qualified = |keyspace: &Option<String>, name: &str| match keyspace {
+            Some(keyspace) => format!("{}.{}", keyspace, name),
+            None => name.to_string(),
+        };
+        let members = parsed
+            .tables
+            .iter()
+            .flat_map(|t| t.columns.iter().map(move |c| (qualified(&t.keyspace, &t.name), &c.name, &c.ty)))
+            .chain(
+                parsed
+                    .types
+                    .iter()
+                    .flat_map(|u| u.fields.iter().map(move |(name, ty)| (qualified(&u.keyspace, &u.name), name, ty))),
+            );
+        for (parent, name, ty) in members {
+            type_diagnostics(ty, &format!("{}.{}", parent, name), &mut diagnostics);
+        }
+
+        for view in &parsed.views {
+            let location = qualified(&view.keyspace, &view.name);
+            match base_table(&parsed, view) {
+                Some(base) => {
+                    for column in &view.columns {
+                        if base.column(column).is_none() {
+                            diagnostics.push(
+                                Diagnostic::warning(
+                                    "column-unresolved",
+                                    format!("Column `{}` is not defined in table `{}`", column, base.name),
+                                )
+                                .at(location.clone()),
+                            );
+                        }
+                    }
+                }
+                None => diagnostics.push(
+                    Diagnostic::warning(
+                        "table-unresolved",
+                        format!("Base table `{}` is not defined; no types are generated for the view", view.base_table),
+                    )
+                    .at(location),
+                ),
+            }
+        }
+
+        for skipped in &parsed.skipped {
+            let mut diagnostic = Diagnostic::info(
+                "statement-skipped",
+                format!("{} statement does not define types", skipped.statement),
+            );
+            if let Some(name) = &skipped.name {
+                diagnostic = diagnostic.at(name.clone());
+            }
+            diagnostics.push(diagnostic);
+        }
+
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for CqlProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+/// The table a view selects from, preferring one in the view's keyspace
+fn base_table<'a>(schema: &'a CqlSchema, view: &View) -> Option<&'a Table> {
+    let mut candidates = schema.tables.iter().filter(|t| t.name == view.base_table);
+    let first = candidates.clone().next();
+    candidates.find(|t| t.keyspace == view.keyspace).or(first)
+}
+
+/// Lossy and unmapped types within a column or field type
+fn type_diagnostics(ty: &CqlType, location: &str, diagnostics: &mut Vec<Diagnostic>) {
+    match ty {
+        CqlType::Decimal => diagnostics.push(
+            Diagnostic::info("lossy-conversion", "Fixed-point `decimal` is typed as `float`").at(location.to_string()),
+        ),
+        CqlType::Varint => diagnostics.push(
+            Diagnostic::info("lossy-conversion", "Arbitrary-precision `varint` is typed as `int64`")
+                .at(location.to_string()),
+        ),
+        CqlType::Custom(class) => diagnostics.push(
+            Diagnostic::warning("unmapped-type", format!("Custom type `{}` is typed as `bytes`", class))
+                .at(location.to_string()),
+        ),
+        CqlType::List(inner) | CqlType::Set(inner) | CqlType::Frozen(inner) | CqlType::Vector(inner, _) => {
+            type_diagnostics(inner, location, diagnostics)
+        }
+        CqlType::Map(key, value) => {
+            type_diagnostics(key, location, diagnostics);
+            type_diagnostics(value, location, diagnostics);
+        }
+        CqlType::Tuple(elements) => {
+            for element in elements {
+                type_diagnostics(element, location, diagnostics);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    const SCHEMA: &str = r#"
+        CREATE KEYSPACE shop WITH replication = {'class': 'NetworkTopologyStrategy', 'dc1': 3};
+        USE shop;
+
+        CREATE TYPE address (street text, city text, geo frozen<tuple<double, double>>);
+
+        CREATE TABLE orders (
+            customer_id uuid,
+            order_id timeuuid,
+            status text,
+            total decimal,
+            region text STATIC,
+            ship_to frozen<address>,
+            lines map<text, int>,
+            PRIMARY KEY ((customer_id), order_id)
+        ) WITH CLUSTERING ORDER BY (order_id DESC);
+
+        CREATE MATERIALIZED VIEW orders_by_status AS
+            SELECT status, total FROM orders
+            WHERE status IS NOT NULL AND customer_id IS NOT NULL AND order_id IS NOT NULL
+            PRIMARY KEY (status, customer_id, order_id);
+
+        CREATE INDEX orders_region ON orders (region);
+    "#;
+
+    fn record<'a>(module: &'a GeneratedModule, name: &str) -> &'a RecordDef {
+        module
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+    }
+
+    fn fields(record: &RecordDef) -> Vec<(String, String)> {
+        record.fields.iter().map(|(name, ty)| (name.clone(), ty.to_string())).collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect()
+    }
+
+    #[test]
+    fn test_table_and_key_types() {
+        let provider = CqlProvider::new();
+        let schema = provider.resolve_schema(SCHEMA, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Store").unwrap();
+
+        assert_eq!(types.modules.len(), 1);
+        let module = &types.modules[0];
+        assert_eq!(module.path, vec!["Store".to_string(), "Shop".to_string()]);
+
+        assert_eq!(
+            fields(record(module, "Orders")),
+            pairs(&[
+                ("customer_id", "string"),
+                ("order_id", "string"),
+                ("status", "string option"),
+                ("total", "float option"),
+                ("region", "string option"),
+                ("ship_to", "Address option"),
+                ("lines", "Map<string, int> option"),
+            ])
+        );
+        assert_eq!(fields(record(module, "OrdersPartitionKey")), pairs(&[("customer_id", "string")]));
+        assert_eq!(fields(record(module, "OrdersClusteringKey")), pairs(&[("order_id", "string")]));
+        assert_eq!(
+            fields(record(module, "Address")),
+            pairs(&[
+                ("street", "string option"),
+                ("city", "string option"),
+                ("geo", "(float * float) option"),
+            ])
+        );
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/cql.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_materialized_view() {
+        let provider = CqlProvider::new();
+        let schema = provider.resolve_schema(SCHEMA, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Store").unwrap();
+        let module = &types.modules[0];
+
+        assert_eq!(
+            fields(record(module, "OrdersByStatus")),
+            pairs(&[
+                ("customer_id", "string"),
+                ("order_id", "string"),
+                ("status", "string"),
+                ("total", "float option"),
+            ])
+        );
+        assert_eq!(fields(record(module, "OrdersByStatusPartitionKey")), pairs(&[("status", "string")]));
+        assert_eq!(
+            fields(record(module, "OrdersByStatusClusteringKey")),
+            pairs(&[("customer_id", "string"), ("order_id", "string")])
+        );
+    }
+
+    #[test]
+    fn test_collection_and_scalar_types() {
+        let provider = CqlProvider::new();
+        let cql = r#"
+            CREATE TABLE events (
+                id bigint PRIMARY KEY,
+                at timestamp,
+                flags set<boolean>,
+                embedding vector<float, 3>,
+                payload blob,
+                history list<frozen<map<int, text>>>
+            );
+        "#;
+        let schema = provider.resolve_schema(cql, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Db").unwrap();
+        let module = &types.modules[0];
+
+        assert_eq!(module.path, vec!["Db".to_string()]);
+        assert_eq!(
+            fields(record(module, "Events")),
+            pairs(&[
+                ("id", "int64"),
+                ("at", "int64 option"),
+                ("flags", "bool list option"),
+                ("embedding", "float list option"),
+                ("payload", "bytes option"),
+                ("history", "Map<int, string> list option"),
+            ])
+        );
+        assert!(module.types.iter().all(|t| !matches!(t, TypeDefinition::Record(r) if r.name == "EventsClusteringKey")));
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = CqlProvider::new();
+        let cql = format!(
+            "{}\nCREATE TABLE shop.legacy (id varint PRIMARY KEY, blob 'org.example.Codec');\n\
+             CREATE MATERIALIZED VIEW shop.ghost AS SELECT * FROM missing WHERE id IS NOT NULL PRIMARY KEY (id);",
+            SCHEMA
+        );
+        let report = provider.validate_source(&cql, &ProviderParams::default());
+        assert!(report.is_valid());
+
+        let codes: Vec<(&str, Option<&str>)> =
+            report.diagnostics.iter().map(|d| (d.code.as_str(), d.location.as_deref())).collect();
+        assert!(codes.contains(&("lossy-conversion", Some("shop.orders.total"))));
+        assert!(codes.contains(&("lossy-conversion", Some("shop.legacy.id"))));
+        assert!(codes.contains(&("unmapped-type", Some("shop.legacy.blob"))));
+        assert!(codes.contains(&("table-unresolved", Some("shop.ghost"))));
+        assert!(codes.contains(&("statement-skipped", Some("orders_region"))));
+        assert!(!codes.iter().any(|(code, _)| *code == "unresolved-reference"));
+
+        let report = provider.validate_source("CREATE KEYSPACE empty WITH durable_writes = true;", &ProviderParams::default());
+        assert_eq!(report.warnings().next().unwrap().code, "no-types");
+
+        assert!(!provider.validate_source("CREATE TABLE t (id int);", &ProviderParams::default()).is_valid());
+    }
+}
@@ -0,0 +1,656 @@
+//! CQL schema parser
+//!
+//! Parses the DDL statements that define types: `CREATE TABLE`,
+//! `CREATE TYPE` and `CREATE MATERIALIZED VIEW`, as written by hand or by
+//! `cqlsh DESCRIBE`. Table options (`WITH ...`) are ignored. Other
+//! statements are recorded as skipped, except `CREATE KEYSPACE` and `USE`,
+//! which only set the keyspace of what follows.
+
+use crate::types::{Column, CqlSchema, CqlType, Skipped, Table, UserType, View};
+use fusabi_type_providers::{ProviderError, ProviderResult};
+
+/// Parse CQL statements from string content
+pub fn parse_cql(content: &str) -> ProviderResult<CqlSchema> {
+    let mut parser = Parser::new(content);
+    parser.parse_schema()
+}
+
+/// Simple CQL parser
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Keyspace selected by the last `USE`
+    keyspace: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    // Symbols
+    LeftParen,
+    RightParen,
+    LessThan,
+    GreaterThan,
+    Comma,
+    Semicolon,
+    Dot,
+    Symbol(char),
+
+    // Literals
+    /// Unquoted identifier or keyword, lowercased
+    Identifier(String),
+    /// `"Quoted"` identifier, case preserved
+    QuotedIdentifier(String),
+    Number(String),
+    /// `'text'` or `$$text$$`
+    StringLiteral(String),
+
+    // End of file
+    Eof,
+}
+
+impl Parser {
+    fn new(content: &str) -> Self {
+        let tokens = tokenize(content);
+        Self { tokens, pos: 0, keyspace: None }
+    }
+
+    fn current(&self) -> &Token {
+        self.tokens.get(self.pos).unwrap_or(&Token::Eof)
+    }
+
+    fn peek(&self, offset: usize) -> &Token {
+        self.tokens.get(self.pos + offset).unwrap_or(&Token::Eof)
+    }
+
+    fn advance(&mut self) {
+        if self.pos < self.tokens.len() {
+            self.pos += 1;
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.current(), Token::Identifier(s) if s == keyword)
+    }
+
+    /// Advance past `keyword` if it is the current token
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.is_keyword(keyword);
+        if found {
+            self.advance();
+        }
+        found
+    }
+
+    fn expect(&mut self, expected: Token) -> ProviderResult<()> {
+        if self.current() == &expected {
+            self.advance();
+            Ok(())
+        } else {
+            Err(ProviderError::ParseError(format!(
+                "Expected {:?}, got {:?}",
+                expected,
+                self.current()
+            )))
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> ProviderResult<()> {
+        if self.eat_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(ProviderError::ParseError(format!(
+                "Expected {}, got {:?}",
+                keyword.to_uppercase(),
+                self.current()
+            )))
+        }
+    }
+
+    /// An unquoted or quoted identifier
+    fn expect_name(&mut self) -> ProviderResult<String> {
+        match self.current() {
+            Token::Identifier(s) | Token::QuotedIdentifier(s) => {
+                let result = s.clone();
+                self.advance();
+                Ok(result)
+            }
+            _ => Err(ProviderError::ParseError(format!(
+                "Expected identifier, got {:?}",
+                self.current()
+            ))),
+        }
+    }
+
+    /// `name` or `keyspace.name`; the keyspace defaults to the one in use
+    fn qualified_name(&mut self) -> ProviderResult<(Option<String>, String)> {
+        let first = self.expect_name()?;
+        if self.current() == &Token::Dot {
+            self.advance();
+            let name = self.expect_name()?;
+            Ok((Some(first), name))
+        } else {
+            Ok((self.keyspace.clone(), first))
+        }
+    }
+
+    /// Skip to the end of the current statement
+    fn skip_statement(&mut self) {
+        while !matches!(self.current(), Token::Semicolon | Token::Eof) {
+            self.advance();
+        }
+    }
+
+    fn parse_schema(&mut self) -> ProviderResult<CqlSchema> {
+        let mut schema = CqlSchema::default();
+
+        while self.current() != &Token::Eof {
+            if self.current() == &Token::Semicolon {
+                self.advance();
+            } else if self.eat_keyword("use") {
+                self.keyspace = Some(self.expect_name()?);
+                self.skip_statement();
+            } else if self.is_keyword("create") {
+                self.parse_create(&mut schema)?;
+            } else {
+                schema.skipped.push(self.skipped_statement(2));
+                self.skip_statement();
+            }
+        }
+
+        Ok(schema)
+    }
+
+    fn parse_create(&mut self, schema: &mut CqlSchema) -> ProviderResult<()> {
+        let start = self.pos;
+        self.expect_keyword("create")?;
+        if self.eat_keyword("or") {
+            self.expect_keyword("replace")?;
+        }
+
+        if self.eat_keyword("table") || self.eat_keyword("columnfamily") {
+            schema.tables.push(self.parse_table()?);
+        } else if self.eat_keyword("type") {
+            schema.types.push(self.parse_type()?);
+        } else if self.is_keyword("materialized") && matches!(self.peek(1), Token::Identifier(s) if s == "view") {
+            self.advance();
+            self.advance();
+            schema.views.push(self.parse_view()?);
+        } else if !self.is_keyword("keyspace") && !self.is_keyword("schema") {
+            self.pos = start;
+            // `CREATE CUSTOM INDEX` and `CREATE INDEX` alike
+            let words = if matches!(self.peek(1), Token::Identifier(s) if s == "custom") { 3 } else { 2 };
+            schema.skipped.push(self.skipped_statement(words));
+        }
+
+        self.skip_statement();
+        Ok(())
+    }
+
+    /// Record the leading keywords of a statement and the name it applies to
+    fn skipped_statement(&mut self, words: usize) -> Skipped {
+        let mut keywords = Vec::new();
+        while keywords.len() < words {
+            match self.current() {
+                Token::Identifier(word) => keywords.push(word.to_uppercase()),
+                _ => break,
+            }
+            self.advance();
+        }
+        if self.eat_keyword("if") {
+            self.eat_keyword("not");
+            self.eat_keyword("exists");
+        }
+        let name = match self.current() {
+            Token::Identifier(s) | Token::QuotedIdentifier(s) => Some(s.clone()),
+            _ => None,
+        };
+        Skipped { statement: keywords.join(" "), name }
+    }
+
+    fn if_not_exists(&mut self) -> ProviderResult<()> {
+        if self.eat_keyword("if") {
+            self.expect_keyword("not")?;
+            self.expect_keyword("exists")?;
+        }
+        Ok(())
+    }
+
+    /// `CREATE TABLE [IF NOT EXISTS] name ( columns, PRIMARY KEY (...) )`
+    fn parse_table(&mut self) -> ProviderResult<Table> {
+        self.if_not_exists()?;
+        let (keyspace, name) = self.qualified_name()?;
+        let mut table = Table {
+            keyspace,
+            name,
+            columns: Vec::new(),
+            partition_key: Vec::new(),
+            clustering: Vec::new(),
+        };
+
+        self.expect(Token::LeftParen)?;
+        loop {
+            if self.is_keyword("primary") {
+                self.advance();
+                self.expect_keyword("key")?;
+                let (partition_key, clustering) = self.parse_primary_key()?;
+                table.partition_key = partition_key;
+                table.clustering = clustering;
+            } else {
+                let name = self.expect_name()?;
+                let ty = self.parse_type_expr()?;
+                let is_static = self.eat_keyword("static");
+                if self.eat_keyword("primary") {
+                    self.expect_keyword("key")?;
+                    table.partition_key = vec![name.clone()];
+                }
+                table.columns.push(Column { name, ty, is_static });
+            }
+
+            match self.current() {
+                Token::Comma => self.advance(),
+                Token::RightParen => break,
+                other => {
+                    return Err(ProviderError::ParseError(format!(
+                        "Expected , or ) in table {}, got {:?}",
+                        table.name, other
+                    )))
+                }
+            }
+            // A trailing comma before `)` is accepted
+            if self.current() == &Token::RightParen {
+                break;
+            }
+        }
+        self.expect(Token::RightParen)?;
+
+        if table.partition_key.is_empty() {
+            return Err(ProviderError::ParseError(format!("Table {} has no PRIMARY KEY", table.name)));
+        }
+        Ok(table)
+    }
+
+    /// `( (a, b), c, d )` or `( a, c, d )`: partition key, then clustering
+    /// columns
+    fn parse_primary_key(&mut self) -> ProviderResult<(Vec<String>, Vec<String>)> {
+        self.expect(Token::LeftParen)?;
+        let partition_key = if self.current() == &Token::LeftParen {
+            self.advance();
+            let columns = self.name_list()?;
+            self.expect(Token::RightParen)?;
+            columns
+        } else {
+            vec![self.expect_name()?]
+        };
+
+        let mut clustering = Vec::new();
+        while self.current() == &Token::Comma {
+            self.advance();
+            clustering.push(self.expect_name()?);
+        }
+        self.expect(Token::RightParen)?;
+        Ok((partition_key, clustering))
+    }
+
+    fn name_list(&mut self) -> ProviderResult<Vec<String>> {
+        let mut names = vec![self.expect_name()?];
+        while self.current() == &Token::Comma {
+            self.advance();
+            names.push(self.expect_name()?);
+        }
+        Ok(names)
+    }
+
+    /// `CREATE TYPE [IF NOT EXISTS] name ( field type, ... )`
+    fn parse_type(&mut self) -> ProviderResult<UserType> {
+        self.if_not_exists()?;
+        let (keyspace, name) = self.qualified_name()?;
+        let mut fields = Vec::new();
+
+        self.expect(Token::LeftParen)?;
+        while self.current() != &Token::RightParen {
+            let field = self.expect_name()?;
+            let ty = self.parse_type_expr()?;
+            fields.push((field, ty));
+            if self.current() == &Token::Comma {
+                self.advance();
+            } else {
+                break;
+            }
+        }
+        self.expect(Token::RightParen)?;
+
+        Ok(UserType { keyspace, name, fields })
+    }
+
+    /// `CREATE MATERIALIZED VIEW name AS SELECT cols FROM base WHERE ...
+    /// PRIMARY KEY (...)`
+    fn parse_view(&mut self) -> ProviderResult<View> {
+        self.if_not_exists()?;
+        let (keyspace, name) = self.qualified_name()?;
+        self.expect_keyword("as")?;
+        self.expect_keyword("select")?;
+
+        let columns = if self.current() == &Token::Symbol('*') {
+            self.advance();
+            Vec::new()
+        } else {
+            self.name_list()?
+        };
+        self.expect_keyword("from")?;
+        let (_, base_table) = self.qualified_name()?;
+
+        // The WHERE clause restricts rows; only the key matters here
+        while !(self.is_keyword("primary") && matches!(self.peek(1), Token::Identifier(s) if s == "key")) {
+            if matches!(self.current(), Token::Semicolon | Token::Eof) {
+                return Err(ProviderError::ParseError(format!("View {} has no PRIMARY KEY", name)));
+            }
+            self.advance();
+        }
+        self.advance();
+        self.advance();
+        let (partition_key, clustering) = self.parse_primary_key()?;
+
+        Ok(View { keyspace, name, base_table, columns, partition_key, clustering })
+    }
+
+    fn parse_type_expr(&mut self) -> ProviderResult<CqlType> {
+        if let Token::StringLiteral(class) = self.current() {
+            let class = class.clone();
+            self.advance();
+            return Ok(CqlType::Custom(class));
+        }
+
+        let (_, name) = self.qualified_name()?;
+        let ty = match name.as_str() {
+            "frozen" => CqlType::Frozen(Box::new(self.type_argument()?)),
+            "list" => CqlType::List(Box::new(self.type_argument()?)),
+            "set" => CqlType::Set(Box::new(self.type_argument()?)),
+            "map" => {
+                self.expect(Token::LessThan)?;
+                let key = self.parse_type_expr()?;
+                self.expect(Token::Comma)?;
+                let value = self.parse_type_expr()?;
+                self.expect(Token::GreaterThan)?;
+                CqlType::Map(Box::new(key), Box::new(value))
+            }
+            "tuple" => {
+                self.expect(Token::LessThan)?;
+                let mut elements = vec![self.parse_type_expr()?];
+                while self.current() == &Token::Comma {
+                    self.advance();
+                    elements.push(self.parse_type_expr()?);
+                }
+                self.expect(Token::GreaterThan)?;
+                CqlType::Tuple(elements)
+            }
+            "vector" => {
+                self.expect(Token::LessThan)?;
+                let element = self.parse_type_expr()?;
+                self.expect(Token::Comma)?;
+                let dimension = match self.current() {
+                    Token::Number(n) => n.parse().unwrap_or(0),
+                    other => {
+                        return Err(ProviderError::ParseError(format!(
+                            "Expected vector dimension, got {:?}",
+                            other
+                        )))
+                    }
+                };
+                self.advance();
+                self.expect(Token::GreaterThan)?;
+                CqlType::Vector(Box::new(element), dimension)
+            }
+            "ascii" => CqlType::Ascii,
+            "bigint" => CqlType::Bigint,
+            "blob" => CqlType::Blob,
+            "boolean" => CqlType::Boolean,
+            "counter" => CqlType::Counter,
+            "date" => CqlType::Date,
+            "decimal" => CqlType::Decimal,
+            "double" => CqlType::Double,
+            "duration" => CqlType::Duration,
+            "float" => CqlType::Float,
+            "inet" => CqlType::Inet,
+            "int" => CqlType::Int,
+            "smallint" => CqlType::Smallint,
+            "text" | "varchar" => CqlType::Text,
+            "time" => CqlType::Time,
+            "timestamp" => CqlType::Timestamp,
+            "timeuuid" => CqlType::Timeuuid,
+            "tinyint" => CqlType::Tinyint,
+            "uuid" => CqlType::Uuid,
+            "varint" => CqlType::Varint,
+            _ => CqlType::Udt(name),
+        };
+        Ok(ty)
+    }
+
+    /// `<T>`
+    fn type_argument(&mut self) -> ProviderResult<CqlType> {
+        self.expect(Token::LessThan)?;
+        let ty = self.parse_type_expr()?;
+        self.expect(Token::GreaterThan)?;
+        Ok(ty)
+    }
+}
+
+fn tokenize(content: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = content.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            ' ' | '\t' | '\n' | '\r' => {
+                chars.next();
+            }
+            '-' | '/' => {
+                chars.next();
+                match chars.peek() {
+                    Some(&next) if next == c => {
+                        // Line comment: `--` or `//`
+                        for ch in chars.by_ref() {
+                            if ch == '\n' {
+                                break;
+                            }
+                        }
+                    }
+                    Some('*') if c == '/' => {
+                        chars.next();
+                        let mut prev = ' ';
+                        for ch in chars.by_ref() {
+                            if prev == '*' && ch == '/' {
+                                break;
+                            }
+                            prev = ch;
+                        }
+                    }
+                    Some(d) if c == '-' && d.is_ascii_digit() => {
+                        let mut num = String::from("-");
+                        while let Some(&d) = chars.peek() {
+                            if d.is_ascii_alphanumeric() || d == '.' {
+                                num.push(d);
+                                chars.next();
+                            } else {
+                                break;
+                            }
+                        }
+                        tokens.push(Token::Number(num));
+                    }
+                    _ => tokens.push(Token::Symbol(c)),
+                }
+            }
+            '(' => {
+                tokens.push(Token::LeftParen);
+                chars.next();
+            }
+            ')' => {
+                tokens.push(Token::RightParen);
+                chars.next();
+            }
+            '<' => {
+                tokens.push(Token::LessThan);
+                chars.next();
+            }
+            '>' => {
+                tokens.push(Token::GreaterThan);
+                chars.next();
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                chars.next();
+            }
+            ';' => {
+                tokens.push(Token::Semicolon);
+                chars.next();
+            }
+            '.' => {
+                tokens.push(Token::Dot);
+                chars.next();
+            }
+            '\'' | '"' => {
+                chars.next();
+                let mut s = String::new();
+                while let Some(ch) = chars.next() {
+                    if ch == c {
+                        // A doubled quote escapes itself
+                        if chars.peek() == Some(&c) {
+                            chars.next();
+                        } else {
+                            break;
+                        }
+                    }
+                    s.push(ch);
+                }
+                tokens.push(if c == '"' { Token::QuotedIdentifier(s) } else { Token::StringLiteral(s) });
+            }
+            '$' => {
+                chars.next();
+                if chars.peek() == Some(&'$') {
+                    chars.next();
+                    // `$$ ... $$` function bodies may contain anything
+                    let mut s = String::new();
+                    while let Some(ch) = chars.next() {
+                        if ch == '$' && chars.peek() == Some(&'$') {
+                            chars.next();
+                            break;
+                        }
+                        s.push(ch);
+                    }
+                    tokens.push(Token::StringLiteral(s));
+                } else {
+                    tokens.push(Token::Symbol('$'));
+                }
+            }
+            c if c.is_ascii_digit() => {
+                let mut num = String::new();
+                while let Some(&d) = chars.peek() {
+                    if d.is_ascii_alphanumeric() || d == '.' {
+                        num.push(d);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Number(num));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut ident = String::new();
+                while let Some(&d) = chars.peek() {
+                    if d.is_alphanumeric() || d == '_' {
+                        ident.push(d);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                // Unquoted identifiers are case-insensitive
+                tokens.push(Token::Identifier(ident.to_lowercase()));
+            }
+            _ => {
+                tokens.push(Token::Symbol(c));
+                chars.next();
+            }
+        }
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_table() {
+        let cql = r#"
+            CREATE TABLE IF NOT EXISTS shop.Orders (
+                customer_id uuid,
+                "orderDate" date,
+                order_id timeuuid,
+                region text STATIC,
+                items list<frozen<tuple<text, int>>>,
+                PRIMARY KEY ((customer_id, "orderDate"), order_id)
+            ) WITH CLUSTERING ORDER BY (order_id DESC) AND comment = 'orders; by customer';
+        "#;
+
+        let schema = parse_cql(cql).unwrap();
+        let table = &schema.tables[0];
+        assert_eq!(table.keyspace.as_deref(), Some("shop"));
+        assert_eq!(table.name, "orders");
+        assert_eq!(table.partition_key, vec!["customer_id", "orderDate"]);
+        assert_eq!(table.clustering, vec!["order_id"]);
+        assert!(table.column("region").unwrap().is_static);
+        assert_eq!(
+            table.column("items").unwrap().ty,
+            CqlType::List(Box::new(CqlType::Frozen(Box::new(CqlType::Tuple(vec![
+                CqlType::Text,
+                CqlType::Int
+            ])))))
+        );
+    }
+
+    #[test]
+    fn test_parse_types_views_and_skipped() {
+        let cql = r#"
+            CREATE KEYSPACE iot WITH replication = {'class': 'SimpleStrategy', 'replication_factor': 1};
+            USE iot;
+            CREATE TYPE address (street text, city text, zip int);
+            CREATE TABLE sensors (id uuid PRIMARY KEY, home frozen<address>, readings map<timestamp, double>);
+            CREATE MATERIALIZED VIEW sensors_by_home AS
+                SELECT id, home FROM sensors WHERE home IS NOT NULL AND id IS NOT NULL
+                PRIMARY KEY (home, id);
+            CREATE INDEX ON sensors (readings);
+            CREATE FUNCTION iot.avg_state(state tuple<int, bigint>, val int)
+                CALLED ON NULL INPUT RETURNS tuple<int, bigint> LANGUAGE java
+                AS $$ if (val != null) { state.setInt(0, state.getInt(0) + 1); } return state; $$;
+            ALTER TABLE sensors ADD firmware text;
+        "#;
+
+        let schema = parse_cql(cql).unwrap();
+        assert_eq!(schema.types[0].keyspace.as_deref(), Some("iot"));
+        assert_eq!(schema.types[0].fields.len(), 3);
+        assert_eq!(schema.tables[0].partition_key, vec!["id"]);
+        assert_eq!(
+            schema.tables[0].column("home").unwrap().ty,
+            CqlType::Frozen(Box::new(CqlType::Udt("address".to_string())))
+        );
+
+        let view = &schema.views[0];
+        assert_eq!(view.base_table, "sensors");
+        assert_eq!(view.columns, vec!["id", "home"]);
+        assert_eq!(view.partition_key, vec!["home"]);
+        assert_eq!(view.clustering, vec!["id"]);
+
+        let skipped: Vec<(&str, Option<&str>)> =
+            schema.skipped.iter().map(|s| (s.statement.as_str(), s.name.as_deref())).collect();
+        assert_eq!(
+            skipped,
+            [("CREATE INDEX", Some("on")), ("CREATE FUNCTION", Some("iot")), ("ALTER TABLE", Some("sensors"))]
+        );
+    }
+
+    #[test]
+    fn test_missing_primary_key() {
+        assert!(parse_cql("CREATE TABLE t (id int, name text);").is_err());
+    }
+}
@@ -0,0 +1,111 @@
+//! CQL schema type definitions
+
+/// Represents the parsed statements of a CQL schema
+#[derive(Debug, Clone, Default)]
+pub struct CqlSchema {
+    pub tables: Vec<Table>,
+    pub types: Vec<UserType>,
+    pub views: Vec<View>,
+    /// Statements that do not define types, in order
+    pub skipped: Vec<Skipped>,
+}
+
+/// `CREATE TABLE`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    /// Explicit keyspace, or the one selected by `USE`
+    pub keyspace: Option<String>,
+    pub name: String,
+    pub columns: Vec<Column>,
+    /// Partition key columns, in order
+    pub partition_key: Vec<String>,
+    /// Clustering columns, in order
+    pub clustering: Vec<String>,
+}
+
+/// A table column
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    pub name: String,
+    pub ty: CqlType,
+    /// Shared by all rows of a partition
+    pub is_static: bool,
+}
+
+/// `CREATE TYPE`: a user-defined type
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserType {
+    pub keyspace: Option<String>,
+    pub name: String,
+    pub fields: Vec<(String, CqlType)>,
+}
+
+/// `CREATE MATERIALIZED VIEW`
+#[derive(Debug, Clone, PartialEq)]
+pub struct View {
+    pub keyspace: Option<String>,
+    pub name: String,
+    pub base_table: String,
+    /// Selected columns; empty for `SELECT *`
+    pub columns: Vec<String>,
+    pub partition_key: Vec<String>,
+    pub clustering: Vec<String>,
+}
+
+/// A statement that generates no types
+#[derive(Debug, Clone, PartialEq)]
+pub struct Skipped {
+    /// Leading keywords, e.g. `CREATE INDEX` or `ALTER TABLE`
+    pub statement: String,
+    /// Name of the object the statement applies to, if any
+    pub name: Option<String>,
+}
+
+/// A CQL data type
+#[derive(Debug, Clone, PartialEq)]
+pub enum CqlType {
+    Ascii,
+    Bigint,
+    Blob,
+    Boolean,
+    Counter,
+    Date,
+    Decimal,
+    Double,
+    Duration,
+    Float,
+    Inet,
+    Int,
+    Smallint,
+    /// `text` or `varchar`
+    Text,
+    Time,
+    Timestamp,
+    Timeuuid,
+    Tinyint,
+    Uuid,
+    Varint,
+    List(Box<CqlType>),
+    Set(Box<CqlType>),
+    Map(Box<CqlType>, Box<CqlType>),
+    Tuple(Vec<CqlType>),
+    /// `frozen<T>`: stored as a single value, with the same shape as `T`
+    Frozen(Box<CqlType>),
+    /// `vector<T, n>`
+    Vector(Box<CqlType>, usize),
+    /// A user-defined type, by name
+    Udt(String),
+    /// A custom type given by its Java class name
+    Custom(String),
+}
+
+impl Table {
+    /// Whether the column is part of the primary key
+    pub fn is_key(&self, column: &str) -> bool {
+        self.partition_key.iter().chain(&self.clustering).any(|k| k == column)
+    }
+
+    pub fn column(&self, name: &str) -> Option<&Column> {
+        self.columns.iter().find(|c| c.name == name)
+    }
+}
@@ -0,0 +1,208 @@
+//! Column type inference
+//!
+//! Each sampled cell is classified on its own and the column type is the
+//! narrowest type that fits every non-empty cell. Empty cells do not vote;
+//! they only mark the column as nullable.
+
+use serde::{Deserialize, Serialize};
+
+/// Inferred type of a column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnType {
+    /// `true` / `false`, in any case
+    Bool,
+    /// Integers that fit in 64 bits, without leading zeros
+    Int,
+    /// Decimal numbers, including exponent notation
+    Float,
+    /// ISO 8601 dates (`2024-01-31`), optionally with a time part
+    Date,
+    /// Anything else
+    String,
+}
+
+impl ColumnType {
+    /// Classify a single non-empty cell
+    pub fn of(value: &str) -> Self {
+        if value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false") {
+            ColumnType::Bool
+        } else if is_int(value) {
+            ColumnType::Int
+        } else if is_float(value) {
+            ColumnType::Float
+        } else if is_date(value) {
+            ColumnType::Date
+        } else {
+            ColumnType::String
+        }
+    }
+
+    /// Narrowest type that fits both
+    pub fn merge(self, other: ColumnType) -> ColumnType {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (ColumnType::Int, ColumnType::Float) | (ColumnType::Float, ColumnType::Int) => {
+                ColumnType::Float
+            }
+            _ => ColumnType::String,
+        }
+    }
+
+    /// Fusabi type name
+    pub fn type_name(self) -> &'static str {
+        match self {
+            ColumnType::Bool => "bool",
+            ColumnType::Int => "int",
+            ColumnType::Float => "float",
+            ColumnType::Date | ColumnType::String => "string",
+        }
+    }
+}
+
+/// An inferred column
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Column {
+    /// Sanitized field name
+    pub name: String,
+    /// Header as written in the file (or the override)
+    pub header: String,
+    /// Inferred type
+    pub column_type: ColumnType,
+    /// Whether any sampled cell was empty or missing
+    pub nullable: bool,
+    /// Number of non-empty sampled cells
+    pub samples: usize,
+}
+
+/// Infer a column for each name from the sampled rows
+///
+/// `names` pairs each sanitized field name with its original header. Short
+/// rows count as empty cells in the missing columns.
+pub fn infer_columns(names: &[(String, String)], rows: &[Vec<String>]) -> Vec<Column> {
+    names
+        .iter()
+        .enumerate()
+        .map(|(index, (name, header))| {
+            let mut column_type = None;
+            let mut nullable = false;
+            let mut samples = 0;
+
+            for row in rows {
+                match row.get(index).map(|cell| cell.trim()) {
+                    Some(cell) if !cell.is_empty() => {
+                        let cell_type = ColumnType::of(cell);
+                        column_type = Some(match column_type {
+                            Some(current) => ColumnType::merge(current, cell_type),
+                            None => cell_type,
+                        });
+                        samples += 1;
+                    }
+                    _ => nullable = true,
+                }
+            }
+
+            Column {
+                name: name.clone(),
+                header: header.clone(),
+                column_type: column_type.unwrap_or(ColumnType::String),
+                nullable: nullable || samples == 0,
+                samples,
+            }
+        })
+        .collect()
+}
+
+fn is_int(value: &str) -> bool {
+    !has_leading_zero(value) && value.parse::<i64>().is_ok()
+}
+
+fn is_float(value: &str) -> bool {
+    // Rejects `inf`, `NaN` and friends, which `f64::from_str` accepts
+    !has_leading_zero(value)
+        && value.chars().any(|c| c.is_ascii_digit())
+        && value
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E'))
+        && value.parse::<f64>().is_ok()
+}
+
+/// Leading zeros usually mean an identifier such as a zip code
+fn has_leading_zero(value: &str) -> bool {
+    let digits = value.strip_prefix(['-', '+']).unwrap_or(value).as_bytes();
+    matches!(digits, [b'0', next, ..] if next.is_ascii_digit())
+}
+
+fn is_date(value: &str) -> bool {
+    let date = value.get(..10).unwrap_or(value);
+    let rest = value.get(10..).unwrap_or("");
+
+    let parts: Vec<&str> = date.split('-').collect();
+    let [year, month, day] = parts.as_slice() else {
+        return false;
+    };
+    let number = |s: &str, len: usize| -> Option<u32> {
+        (s.len() == len && s.chars().all(|c| c.is_ascii_digit()))
+            .then(|| s.parse().ok())
+            .flatten()
+    };
+
+    let valid_date = number(year, 4).is_some()
+        && number(month, 2).is_some_and(|m| (1..=12).contains(&m))
+        && number(day, 2).is_some_and(|d| (1..=31).contains(&d));
+
+    valid_date
+        && (rest.is_empty()
+            || (rest.starts_with(['T', ' '])
+                && rest[1..].starts_with(|c: char| c.is_ascii_digit())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_cells() {
+        assert_eq!(ColumnType::of("TRUE"), ColumnType::Bool);
+        assert_eq!(ColumnType::of("-42"), ColumnType::Int);
+        assert_eq!(ColumnType::of("00501"), ColumnType::String);
+        assert_eq!(ColumnType::of("3.14"), ColumnType::Float);
+        assert_eq!(ColumnType::of("1e-3"), ColumnType::Float);
+        assert_eq!(ColumnType::of("NaN"), ColumnType::String);
+        assert_eq!(ColumnType::of("2024-01-31"), ColumnType::Date);
+        assert_eq!(ColumnType::of("2024-01-31T10:00:00Z"), ColumnType::Date);
+        assert_eq!(ColumnType::of("2024-13-01"), ColumnType::String);
+        assert_eq!(ColumnType::of("hello"), ColumnType::String);
+    }
+
+    #[test]
+    fn test_infer_columns() {
+        let names: Vec<(String, String)> = ["id", "score", "active", "note", "blank"]
+            .iter()
+            .map(|n| (n.to_string(), n.to_string()))
+            .collect();
+        let rows: Vec<Vec<String>> = [
+            vec!["1", "10", "true", "x", ""],
+            vec!["2", "10.5", "false", ""],
+            vec!["3", "", "yes", "y", ""],
+        ]
+        .iter()
+        .map(|row| row.iter().map(|c| c.to_string()).collect())
+        .collect();
+
+        let columns = infer_columns(&names, &rows);
+        let summary: Vec<(ColumnType, bool)> =
+            columns.iter().map(|c| (c.column_type, c.nullable)).collect();
+        assert_eq!(
+            summary,
+            [
+                (ColumnType::Int, false),
+                (ColumnType::Float, true),
+                (ColumnType::String, false),
+                (ColumnType::String, true),
+                (ColumnType::String, true),
+            ]
+        );
+        assert_eq!(columns[4].samples, 0);
+    }
+}
@@ -0,0 +1,500 @@
+//! CSV Type Provider
+//!
+//! Generates a Fusabi record type for the rows of a CSV file, inferring
+//! each column's type from a sample of rows.
+//!
+//! # Params
+//!
+//! | Param         | Default | Meaning                                           |
+//! |---------------|---------|---------------------------------------------------|
+//! | `delimiter`   | `,`     | Field delimiter (a single character, or `tab`)    |
+//! | `quote`       | `"`     | Quote character                                   |
+//! | `has_headers` | `true`  | Whether the first row holds column names          |
+//! | `headers`     | —       | Comma-separated column names overriding the file  |
+//! | `sample_rows` | `100`   | Number of data rows used for inference            |
+//! | `row_type`    | `Row`   | Name of the generated record                      |
+//!
+//! # Type Inference
+//!
+//! | Cells                                   | Fusabi     |
+//! |-----------------------------------------|------------|
+//! | `true` / `false`                        | `bool`     |
+//! | integers                                | `int`      |
+//! | decimals, or a mix with integers        | `float`    |
+//! | ISO 8601 dates                          | `string`   |
+//! | anything else, or mixed types           | `string`   |
+//!
+//! Columns with an empty or missing cell in the sample become `T option`.
+//! Headers are sanitized into snake_case field names.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_csv::CsvProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = CsvProvider::new();
+//! let params = ProviderParams::default().with("delimiter", ";");
+//! let schema = provider.resolve_schema("orders.csv", &params)?;
+//! let types = provider.generate_types(&schema, "Orders")?;
+//! ```
+
+mod infer;
+mod sanitize;
+
+pub use infer::{infer_columns, Column, ColumnType};
+pub use sanitize::{sanitize_column_name, sanitize_headers};
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceValidator, TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use serde::{Deserialize, Serialize};
+
+/// Default number of sampled data rows
+pub const DEFAULT_SAMPLE_ROWS: usize = 100;
+
+/// Default name of the generated record
+pub const DEFAULT_ROW_TYPE: &str = "Row";
+
+/// Parsing and inference options
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvOptions {
+    /// Field delimiter
+    pub delimiter: u8,
+    /// Quote character
+    pub quote: u8,
+    /// Whether the first row holds column names
+    pub has_headers: bool,
+    /// Column names overriding the file's header row
+    pub headers: Option<Vec<String>>,
+    /// Number of data rows used for inference
+    pub sample_rows: usize,
+    /// Name of the generated record
+    pub row_type: String,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote: b'"',
+            has_headers: true,
+            headers: None,
+            sample_rows: DEFAULT_SAMPLE_ROWS,
+            row_type: DEFAULT_ROW_TYPE.to_string(),
+        }
+    }
+}
+
+impl CsvOptions {
+    /// Params accepted by the CSV provider
+    pub fn param_schema() -> ParamSchema {
+        ParamSchema::new("CsvProvider")
+            .param(ParamSpec::new("delimiter", ParamKind::String).default(",").describe("Field delimiter"))
+            .param(ParamSpec::new("quote", ParamKind::String).default("\"").describe("Quote character"))
+            .param(
+                ParamSpec::new("has_headers", ParamKind::Bool)
+                    .default("true")
+                    .describe("Whether the first row holds column names"),
+            )
+            .param(ParamSpec::new("headers", ParamKind::List).describe("Column names overriding the file"))
+            .param(
+                ParamSpec::new("sample_rows", ParamKind::UInt)
+                    .default("100")
+                    .describe("Number of data rows used for inference"),
+            )
+            .param(
+                ParamSpec::new("row_type", ParamKind::String)
+                    .default(DEFAULT_ROW_TYPE)
+                    .describe("Name of the generated record"),
+            )
+    }
+
+    /// Read options from params, falling back to defaults
+    ///
+    /// Unknown params and malformed values are rejected.
+    pub fn from_params(params: &ProviderParams) -> ProviderResult<Self> {
+        let params = Self::param_schema().validate(params)?;
+        let mut options = Self::default();
+
+        if let Some(value) = params.str("delimiter") {
+            options.delimiter = match value {
+                "tab" | "\\t" => b'\t',
+                other => single_byte(other)
+                    .ok_or_else(|| invalid_param("delimiter", value, "a single character"))?,
+            };
+        }
+
+        if let Some(value) = params.str("quote") {
+            options.quote = single_byte(value)
+                .ok_or_else(|| invalid_param("quote", value, "a single character"))?;
+        }
+
+        if let Some(has_headers) = params.bool("has_headers") {
+            options.has_headers = has_headers;
+        }
+
+        if let Some(headers) = params.list("headers") {
+            options.headers = Some(headers.into_iter().map(String::from).collect());
+        }
+
+        if let Some(value) = params.str("sample_rows") {
+            options.sample_rows = value
+                .parse()
+                .ok()
+                .filter(|n| *n > 0)
+                .ok_or_else(|| invalid_param("sample_rows", value, "a positive row count"))?;
+        }
+
+        if let Some(value) = params.str("row_type") {
+            options.row_type = value.to_string();
+        }
+
+        Ok(options)
+    }
+}
+
+/// Inferred shape of a CSV file, stored in [`Schema::Custom`] as JSON
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CsvSchema {
+    /// Name of the generated record
+    pub row_type: String,
+    /// Number of data rows that were sampled
+    pub sampled_rows: usize,
+    /// Inferred columns, in file order
+    pub columns: Vec<Column>,
+}
+
+/// CSV type provider
+pub struct CsvProvider {
+    generator: TypeGenerator,
+}
+
+impl CsvProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    /// Sample CSV content and infer its columns
+    pub fn infer(&self, content: &str, options: &CsvOptions) -> ProviderResult<CsvSchema> {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(options.delimiter)
+            .quote(options.quote)
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(content.as_bytes());
+
+        let mut records = reader.records();
+        let file_headers = if options.has_headers {
+            match records.next() {
+                Some(record) => record
+                    .map_err(csv_error)?
+                    .iter()
+                    .map(|h| h.trim().to_string())
+                    .collect(),
+                None => return Err(ProviderError::ParseError("CSV source is empty".to_string())),
+            }
+        } else {
+            Vec::new()
+        };
+
+        let mut rows = Vec::new();
+        for record in records.take(options.sample_rows) {
+            rows.push(
+                record
+                    .map_err(csv_error)?
+                    .iter()
+                    .map(String::from)
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let headers = match &options.headers {
+            Some(headers) => headers.clone(),
+            None if options.has_headers => file_headers,
+            None => vec![String::new(); width],
+        };
+        if headers.is_empty() {
+            return Err(ProviderError::ParseError("CSV source has no columns".to_string()));
+        }
+
+        let names: Vec<(String, String)> = sanitize_headers(&headers)
+            .into_iter()
+            .zip(headers)
+            .collect();
+
+        Ok(CsvSchema {
+            row_type: self.generator.naming.apply(&options.row_type),
+            sampled_rows: rows.len(),
+            columns: infer_columns(&names, &rows),
+        })
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<CsvSchema> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Invalid CSV schema: {}", e)))
+    }
+
+    fn generate_from_schema(&self, schema: &CsvSchema, namespace: &str) -> GeneratedTypes {
+        let fields = schema
+            .columns
+            .iter()
+            .map(|column| {
+                let type_name = column.column_type.type_name();
+                let shape = TypeShape::named(type_name).option_unless(!column.nullable);
+                (column.name.clone(), shape.into())
+            })
+            .collect();
+
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+        module.types.push(TypeDefinition::Record(RecordDef {
+            name: schema.row_type.clone(),
+            fields,
+        }));
+
+        let mut result = GeneratedTypes::new();
+        result.modules.push(module);
+        result
+    }
+}
+
+impl Default for CsvProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for CsvProvider {
+    fn param_schema(&self) -> ParamSchema {
+        CsvOptions::param_schema()
+    }
+}
+
+impl TypeProvider for CsvProvider {
+    fn name(&self) -> &str {
+        "CsvProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let options = CsvOptions::from_params(params)?;
+
+        // Paths and URLs never span lines; inline CSV does
+        let content = if source.contains('\n') {
+            source.to_string()
+        } else {
+            read_source(source, params)?
+        };
+
+        let schema = self.infer(&content, &options)?;
+        let json = serde_json::to_string(&schema)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        match schema {
+            Schema::Custom(json) => {
+                let schema = self.parse_schema(json)?;
+                Ok(self.generate_from_schema(&schema, namespace))
+            }
+            _ => Err(ProviderError::ParseError("Expected CSV schema".to_string())),
+        }
+    }
+}
+
+impl SourceValidator for CsvProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected CSV schema".to_string()));
+        };
+
+        let schema = self.parse_schema(json)?;
+        let mut diagnostics = Vec::new();
+
+        if schema.sampled_rows == 0 {
+            diagnostics.push(Diagnostic::warning(
+                "no-rows",
+                "CSV source has no data rows; every column is typed as `string option`",
+            ));
+        }
+
+        for column in &schema.columns {
+            let location = format!("{}.{}", schema.row_type, column.name);
+
+            if column.name != column.header && !column.header.is_empty() {
+                diagnostics.push(
+                    Diagnostic::info(
+                        "column-renamed",
+                        format!("Column `{}` is exposed as `{}`", column.header, column.name),
+                    )
+                    .at(location.clone()),
+                );
+            }
+
+            if column.samples == 0 && schema.sampled_rows > 0 {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        "coerced-any",
+                        format!(
+                            "Column `{}` is empty in every sampled row; typed as `string option`",
+                            column.name
+                        ),
+                    )
+                    .at(location.clone()),
+                );
+            }
+
+            if column.column_type == ColumnType::Date {
+                diagnostics.push(
+                    Diagnostic::info(
+                        "lossy-conversion",
+                        format!("Date column `{}` is typed as `string`", column.name),
+                    )
+                    .at(location),
+                );
+            }
+        }
+
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for CsvProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .params(&self.param_schema())
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+fn single_byte(value: &str) -> Option<u8> {
+    match value.as_bytes() {
+        [byte] => Some(*byte),
+        _ => None,
+    }
+}
+
+fn csv_error(error: csv::Error) -> ProviderError {
+    ProviderError::ParseError(format!("Invalid CSV: {}", error))
+}
+
+fn invalid_param(name: &str, value: &str, expected: &str) -> ProviderError {
+    ProviderError::InvalidSource(format!(
+        "Invalid {} '{}': expected {}",
+        name, value, expected
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    fn fields(types: &GeneratedTypes) -> Vec<(String, String)> {
+        let TypeDefinition::Record(record) = &types.modules[0].types[0] else {
+            panic!("Expected record");
+        };
+        record
+            .fields
+            .iter()
+            .map(|(name, ty)| (name.clone(), ty.to_string()))
+            .collect()
+    }
+
+    fn generate(csv: &str, params: &ProviderParams) -> GeneratedTypes {
+        let provider = CsvProvider::new();
+        let schema = provider.resolve_schema(csv, params).unwrap();
+        provider.generate_types(&schema, "Data").unwrap()
+    }
+
+    #[test]
+    fn test_generate_inferred_record() {
+        let csv = "Order ID,Total ($),Paid,Placed At,Note\n\
+                   1,9.99,true,2024-01-31,\"hello, world\"\n\
+                   2,12,false,2024-02-01,\n";
+
+        let types = generate(csv, &ProviderParams::default());
+        assert_eq!(types.modules[0].path, vec!["Data"]);
+        let TypeDefinition::Record(record) = &types.modules[0].types[0] else {
+            panic!("Expected record");
+        };
+        assert_eq!(record.name, "Row");
+        assert_eq!(
+            fields(&types),
+            [
+                ("order_id".to_string(), "int".to_string()),
+                ("total".to_string(), "float".to_string()),
+                ("paid".to_string(), "bool".to_string()),
+                ("placed_at".to_string(), "string".to_string()),
+                ("note".to_string(), "string option".to_string()),
+            ]
+        );
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/csv.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_params() {
+        let csv = "a;'b;c'\n1;x\n";
+        let params = ProviderParams::default()
+            .with("delimiter", ";")
+            .with("quote", "'")
+            .with("has_headers", "false")
+            .with("headers", "count, label")
+            .with("row_type", "line_item");
+
+        let provider = CsvProvider::new();
+        let schema = provider.resolve_schema(csv, &params).unwrap();
+        let types = provider.generate_types(&schema, "Data").unwrap();
+        let TypeDefinition::Record(record) = &types.modules[0].types[0] else {
+            panic!("Expected record");
+        };
+        assert_eq!(record.name, "LineItem");
+        // Without a header row, the first line is data
+        assert_eq!(
+            fields(&types),
+            [
+                ("count".to_string(), "string".to_string()),
+                ("label".to_string(), "string".to_string()),
+            ]
+        );
+
+        assert!(CsvOptions::from_params(&ProviderParams::default().with("delimiter", "::")).is_err());
+        assert!(CsvOptions::from_params(&ProviderParams::default().with("sample_rows", "0")).is_err());
+        assert!(CsvOptions::from_params(&ProviderParams::default().with("has_header", "false")).is_err());
+    }
+
+    #[test]
+    fn test_sample_rows_limit() {
+        let csv = "n\n1\n2\nnot a number\n";
+        let params = ProviderParams::default().with("sample_rows", "2");
+        assert_eq!(fields(&generate(csv, &params))[0].1, "int");
+        assert_eq!(fields(&generate(csv, &ProviderParams::default()))[0].1, "string");
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = CsvProvider::new();
+        let csv = "Id,Created,Empty\n1,2024-01-01,\n";
+
+        let report = provider.validate_source(csv, &ProviderParams::default());
+        assert!(report.is_valid());
+        assert!(report.diagnostics.iter().any(|d| d.code == "column-renamed"));
+        assert!(report.diagnostics.iter().any(|d| d.code == "lossy-conversion"));
+        assert_eq!(report.warnings().next().unwrap().code, "coerced-any");
+    }
+}
@@ -0,0 +1,87 @@
+//! Column-name sanitizer
+//!
+//! CSV headers are free text (`"Order ID"`, `"price ($)"`, `"2024 total"`),
+//! so they are turned into snake_case identifiers before becoming record
+//! fields. Blank headers are named after their position and duplicates get
+//! a numeric suffix.
+
+/// Sanitize every header, keeping the results unique
+pub fn sanitize_headers(headers: &[String]) -> Vec<String> {
+    let mut result: Vec<String> = Vec::with_capacity(headers.len());
+
+    for (index, header) in headers.iter().enumerate() {
+        let base = match sanitize_column_name(header) {
+            name if name.is_empty() => format!("column_{}", index + 1),
+            name => name,
+        };
+
+        let mut name = base.clone();
+        let mut suffix = 2;
+        while result.contains(&name) {
+            name = format!("{}_{}", base, suffix);
+            suffix += 1;
+        }
+        result.push(name);
+    }
+
+    result
+}
+
+/// Convert a header to a snake_case identifier
+///
+/// Returns an empty string when the header has no usable characters.
+pub fn sanitize_column_name(header: &str) -> String {
+    let mut result = String::new();
+    let mut prev_lower_or_digit = false;
+    let mut pending_separator = false;
+
+    for ch in header.trim().chars() {
+        if ch.is_alphanumeric() {
+            // Word boundary on separators and camelCase transitions
+            if pending_separator || (ch.is_uppercase() && prev_lower_or_digit) {
+                if !result.is_empty() {
+                    result.push('_');
+                }
+                pending_separator = false;
+            }
+            result.extend(ch.to_lowercase());
+            prev_lower_or_digit = ch.is_lowercase() || ch.is_ascii_digit();
+        } else {
+            pending_separator = true;
+            prev_lower_or_digit = false;
+        }
+    }
+
+    if result.starts_with(|c: char| c.is_ascii_digit()) {
+        result.insert(0, '_');
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_column_name() {
+        assert_eq!(sanitize_column_name("Order ID"), "order_id");
+        assert_eq!(sanitize_column_name("price ($)"), "price");
+        assert_eq!(sanitize_column_name("firstName"), "first_name");
+        assert_eq!(sanitize_column_name("  e-mail  address "), "e_mail_address");
+        assert_eq!(sanitize_column_name("2024 total"), "_2024_total");
+        assert_eq!(sanitize_column_name("???"), "");
+    }
+
+    #[test]
+    fn test_sanitize_headers() {
+        let headers: Vec<String> = ["id", "", "ID", "Id", "name"]
+            .iter()
+            .map(|h| h.to_string())
+            .collect();
+        assert_eq!(
+            sanitize_headers(&headers),
+            vec!["id", "column_2", "id_2", "id_3", "name"]
+        );
+    }
+}
@@ -0,0 +1,298 @@
+//! DynamoDB API client
+//!
+//! Calls the DynamoDB JSON API (`X-Amz-Target: DynamoDB_20120810.<Operation>`)
+//! with requests signed by AWS Signature Version 4.
+
+use fusabi_provider_common::{CredentialResolver, SourceOptions, StructuredError};
+use fusabi_type_providers::{ProviderError, ProviderParams, ProviderResult};
+use hmac::{Hmac, Mac};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::io::Read;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SERVICE: &str = "dynamodb";
+
+const TARGET_PREFIX: &str = "DynamoDB_20120810";
+
+/// AWS access keys
+pub struct AwsCredentials {
+    pub access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+}
+
+impl AwsCredentials {
+    /// Credentials referenced by the `auth` param, holding
+    /// `ACCESS_KEY_ID:SECRET_ACCESS_KEY[:SESSION_TOKEN]`, or else the
+    /// standard `AWS_*` environment variables
+    pub fn from_params(params: &ProviderParams) -> ProviderResult<Self> {
+        if let Some(credential) = CredentialResolver::new().from_params(params)? {
+            let mut parts = credential.expose().trim().splitn(3, ':');
+            return match (parts.next(), parts.next()) {
+                (Some(id), Some(secret)) if !id.is_empty() && !secret.is_empty() => Ok(Self {
+                    access_key_id: id.to_string(),
+                    secret_access_key: secret.to_string(),
+                    session_token: parts.next().map(String::from),
+                }),
+                _ => Err(ProviderError::InvalidSource(
+                    "The `auth` credential must hold ACCESS_KEY_ID:SECRET_ACCESS_KEY[:SESSION_TOKEN]".to_string(),
+                )),
+            };
+        }
+
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        match (var("AWS_ACCESS_KEY_ID"), var("AWS_SECRET_ACCESS_KEY")) {
+            (Some(access_key_id), Some(secret_access_key)) => Ok(Self {
+                access_key_id,
+                secret_access_key,
+                session_token: var("AWS_SESSION_TOKEN"),
+            }),
+            _ => Err(ProviderError::InvalidSource(
+                "No AWS credentials; set the `auth` param or AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY".to_string(),
+            )),
+        }
+    }
+
+    #[cfg(test)]
+    fn new(access_key_id: &str, secret_access_key: &str) -> Self {
+        Self {
+            access_key_id: access_key_id.to_string(),
+            secret_access_key: secret_access_key.to_string(),
+            session_token: None,
+        }
+    }
+}
+
+impl fmt::Debug for AwsCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AwsCredentials")
+            .field("access_key_id", &self.access_key_id)
+            .field("secret_access_key", &"<redacted>")
+            .finish()
+    }
+}
+
+/// A client for one region or endpoint
+pub struct DynamoClient {
+    agent: ureq::Agent,
+    endpoint: String,
+    host: String,
+    region: String,
+    credentials: AwsCredentials,
+    max_response_bytes: u64,
+}
+
+impl DynamoClient {
+    /// A client for `region`, at the regional endpoint unless `endpoint`
+    /// overrides it (DynamoDB Local, LocalStack)
+    pub fn new(region: &str, endpoint: Option<&str>, credentials: AwsCredentials, options: &SourceOptions) -> Self {
+        let endpoint = endpoint
+            .map(|e| e.trim_end_matches('/').to_string())
+            .unwrap_or_else(|| format!("https://dynamodb.{}.amazonaws.com", region));
+        let host = endpoint
+            .split_once("://")
+            .map_or(endpoint.as_str(), |(_, rest)| rest)
+            .split('/')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+
+        Self {
+            agent: ureq::AgentBuilder::new().timeout(options.fetch_timeout).redirects(0).build(),
+            endpoint,
+            host,
+            region: region.to_string(),
+            credentials,
+            max_response_bytes: options.max_source_bytes,
+        }
+    }
+
+    /// Call an operation such as `DescribeTable` with a JSON request body
+    pub fn call(&self, operation: &str, request: &Value) -> ProviderResult<Value> {
+        let body = request.to_string();
+        let target = format!("{}.{}", TARGET_PREFIX, operation);
+        let date = amz_date(SystemTime::now());
+
+        let mut headers = vec![
+            ("content-type", "application/x-amz-json-1.0".to_string()),
+            ("host", self.host.clone()),
+            ("x-amz-date", date.clone()),
+            ("x-amz-target", target),
+        ];
+        if let Some(token) = &self.credentials.session_token {
+            headers.push(("x-amz-security-token", token.clone()));
+        }
+        let authorization = sign(&self.credentials, &self.region, SERVICE, "POST", &headers, body.as_bytes(), &date);
+
+        let mut http = self.agent.post(&format!("{}/", self.endpoint));
+        for (name, value) in headers.iter().filter(|(name, _)| *name != "host") {
+            http = http.set(name, value);
+        }
+        let response = http
+            .set("authorization", &authorization)
+            .send_string(&body)
+            .map_err(|e| match e {
+                ureq::Error::Status(code, response) => {
+                    let error: Value = response.into_json().unwrap_or(Value::Null);
+                    let kind = error.get("__type").and_then(Value::as_str).unwrap_or_default();
+                    let message = error
+                        .get("message")
+                        .or_else(|| error.get("Message"))
+                        .and_then(Value::as_str)
+                        .unwrap_or_default();
+                    ProviderError::from(StructuredError::network(format!(
+                        "DynamoDB {} failed with HTTP {}: {} {}",
+                        operation,
+                        code,
+                        kind.rsplit('#').next().unwrap_or(kind),
+                        message
+                    )))
+                }
+                ureq::Error::Transport(t) => {
+                    StructuredError::network(format!("DynamoDB {} failed", operation)).with_source(t).into()
+                }
+            })?;
+
+        let mut buf = Vec::new();
+        response
+            .into_reader()
+            .take(self.max_response_bytes + 1)
+            .read_to_end(&mut buf)
+            .map_err(|e| {
+                StructuredError::network(format!("Failed to read DynamoDB {} response", operation)).with_source(e)
+            })?;
+        if buf.len() as u64 > self.max_response_bytes {
+            return Err(StructuredError::validation(format!(
+                "DynamoDB {} response exceeds max_source_bytes ({})",
+                operation, self.max_response_bytes
+            ))
+            .into());
+        }
+        serde_json::from_slice(&buf).map_err(|e| {
+            StructuredError::parse(format!("Invalid DynamoDB {} response", operation))
+                .with_source(e)
+                .into()
+        })
+    }
+}
+
+/// The `Authorization` header for a request to `/` with no query string;
+/// `headers` are the lowercase headers to sign, including `host` and
+/// `x-amz-date`
+fn sign(
+    credentials: &AwsCredentials,
+    region: &str,
+    service: &str,
+    method: &str,
+    headers: &[(&str, String)],
+    body: &[u8],
+    amz_date: &str,
+) -> String {
+    let mut headers: Vec<&(&str, String)> = headers.iter().collect();
+    headers.sort_by_key(|(name, _)| *name);
+    let canonical_headers: String = headers.iter().map(|(name, value)| format!("{}:{}\n", name, value.trim())).collect();
+    let signed_headers = headers.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(";");
+
+    let canonical_request = format!(
+        "{}\n/\n\n{}\n{}\n{}",
+        method,
+        canonical_headers,
+        signed_headers,
+        hex(&Sha256::digest(body))
+    );
+    let day = &amz_date[..8];
+    let scope = format!("{}/{}/{}/aws4_request", day, region, service);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        hex(&Sha256::digest(canonical_request.as_bytes()))
+    );
+
+    let key = signing_key(&credentials.secret_access_key, day, region, service);
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        credentials.access_key_id,
+        scope,
+        signed_headers,
+        hex(&hmac(&key, string_to_sign.as_bytes()))
+    )
+}
+
+fn signing_key(secret: &str, day: &str, region: &str, service: &str) -> Vec<u8> {
+    [day, region, service, "aws4_request"]
+        .iter()
+        .fold(format!("AWS4{}", secret).into_bytes(), |key, part| hmac(&key, part.as_bytes()))
+}
+
+fn hmac(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// `YYYYMMDD'T'HHMMSS'Z'` in UTC
+fn amz_date(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+
+    // Civil date from days since the epoch (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_signature_v4() {
+        // Signing key example from the AWS Signature Version 4 documentation
+        let key = signing_key("wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY", "20120215", "us-east-1", "iam");
+        assert_eq!(hex(&key), "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d");
+
+        let credentials = AwsCredentials::new("AKIDEXAMPLE", "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY");
+        let headers = [
+            ("x-amz-date", "20150830T123600Z".to_string()),
+            ("host", "example.amazon.com".to_string()),
+        ];
+        let authorization = sign(&credentials, "us-east-1", "service", "GET", &headers, b"", "20150830T123600Z");
+        assert_eq!(
+            authorization,
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+             SignedHeaders=host;x-amz-date, \
+             Signature=7ab4567ae243ee168f6bf18206b2b40b61ce08277323168138fa113ed23c538e"
+        );
+        assert!(!format!("{:?}", credentials).contains("EXAMPLEKEY"));
+    }
+
+    #[test]
+    fn test_amz_date() {
+        assert_eq!(amz_date(UNIX_EPOCH + Duration::from_secs(1_440_938_160)), "20150830T123600Z");
+        assert_eq!(amz_date(UNIX_EPOCH + Duration::from_secs(951_782_400)), "20000229T000000Z");
+    }
+}
@@ -0,0 +1,228 @@
+//! Item shape inference
+//!
+//! Sampled items are in the DynamoDB JSON format, where each value is an
+//! object naming its type: `{"S": "text"}`, `{"N": "42"}`, `{"M": {...}}`.
+//! Items merge attribute by attribute; attributes missing from some items,
+//! or `NULL` in some, become optional.
+
+use serde_json::{Map, Value};
+
+/// Inferred shape of the values of one attribute
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    /// Only `NULL` was seen
+    Null,
+    Bool,
+    String,
+    /// Numbers that are all integers within 64 bits
+    Int,
+    Number,
+    Binary,
+    /// `SS`, `NS` or `BS`, by element shape
+    Set(Box<Shape>),
+    /// Element shape; `Null` for lists that were always empty
+    List(Box<Shape>),
+    Map(ObjectShape),
+    /// Scalars of different types
+    Mixed,
+    /// Values with incompatible structure, or not in DynamoDB JSON
+    Any,
+}
+
+/// Merged attributes of one or more items or maps
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ObjectShape {
+    /// Number of items merged
+    pub samples: usize,
+    /// Attributes in first-seen order
+    pub fields: Vec<FieldShape>,
+}
+
+/// One attribute of an [`ObjectShape`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldShape {
+    pub name: String,
+    pub shape: Shape,
+    /// Number of merged items that had the attribute
+    pub present: usize,
+    /// Whether the attribute was `NULL` in some item
+    pub nullable: bool,
+}
+
+impl FieldShape {
+    /// Whether some item lacked the attribute or set it to `NULL`
+    pub fn is_optional(&self, samples: usize) -> bool {
+        self.nullable || self.present < samples
+    }
+}
+
+impl Shape {
+    /// Shape of an attribute value in DynamoDB JSON
+    pub fn of(value: &Value) -> Self {
+        let Some((kind, inner)) = value.as_object().filter(|o| o.len() == 1).and_then(|o| o.iter().next()) else {
+            return Shape::Any;
+        };
+        match kind.as_str() {
+            "NULL" => Shape::Null,
+            "BOOL" => Shape::Bool,
+            "S" => Shape::String,
+            "N" => Shape::of_number(inner),
+            "B" => Shape::Binary,
+            "SS" => Shape::Set(Box::new(Shape::String)),
+            "BS" => Shape::Set(Box::new(Shape::Binary)),
+            "NS" => Shape::Set(Box::new(Shape::merge_all(
+                inner.as_array().into_iter().flatten().map(Shape::of_number),
+            ))),
+            "L" => Shape::List(Box::new(Shape::merge_all(inner.as_array().into_iter().flatten().map(Shape::of)))),
+            "M" => match inner.as_object() {
+                Some(map) => Shape::Map(ObjectShape::of(map)),
+                None => Shape::Any,
+            },
+            _ => Shape::Any,
+        }
+    }
+
+    /// Shape of the key attribute type `S`, `N` or `B`
+    pub fn of_attribute_type(attribute_type: &str) -> Self {
+        match attribute_type {
+            "S" => Shape::String,
+            "N" => Shape::Number,
+            "B" => Shape::Binary,
+            _ => Shape::Any,
+        }
+    }
+
+    /// Numbers are strings in DynamoDB JSON
+    fn of_number(value: &Value) -> Self {
+        match value.as_str().map(str::parse::<i64>) {
+            Some(Ok(_)) => Shape::Int,
+            _ => Shape::Number,
+        }
+    }
+
+    /// Merge the shapes of values of the same attribute
+    pub fn merge(self, other: Shape) -> Shape {
+        match (self, other) {
+            (Shape::Null, shape) | (shape, Shape::Null) => shape,
+            (a, b) if a == b && !matches!(a, Shape::Map(_)) => a,
+            (Shape::Int, Shape::Number) | (Shape::Number, Shape::Int) => Shape::Number,
+            (Shape::Set(a), Shape::Set(b)) => match a.merge(*b) {
+                Shape::Mixed => Shape::Any,
+                element => Shape::Set(Box::new(element)),
+            },
+            (a, b) if a.is_scalar() && b.is_scalar() => Shape::Mixed,
+            (Shape::List(a), Shape::List(b)) => Shape::List(Box::new(a.merge(*b))),
+            (Shape::Map(a), Shape::Map(b)) => Shape::Map(a.merge(b)),
+            _ => Shape::Any,
+        }
+    }
+
+    /// Merge any number of shapes; `Null` when there are none
+    pub fn merge_all(shapes: impl IntoIterator<Item = Shape>) -> Shape {
+        shapes.into_iter().fold(Shape::Null, Shape::merge)
+    }
+
+    fn is_scalar(&self) -> bool {
+        matches!(self, Shape::Bool | Shape::String | Shape::Int | Shape::Number | Shape::Binary | Shape::Mixed)
+    }
+}
+
+impl ObjectShape {
+    /// Shape of one item or map
+    pub fn of(map: &Map<String, Value>) -> Self {
+        ObjectShape {
+            samples: 1,
+            fields: map
+                .iter()
+                .map(|(name, value)| {
+                    let shape = Shape::of(value);
+                    FieldShape {
+                        name: name.clone(),
+                        nullable: shape == Shape::Null,
+                        shape,
+                        present: 1,
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// Merged shape of sampled items
+    pub fn of_items(items: &[Value]) -> Self {
+        items
+            .iter()
+            .filter_map(Value::as_object)
+            .map(ObjectShape::of)
+            .reduce(ObjectShape::merge)
+            .unwrap_or_default()
+    }
+
+    fn merge(mut self, other: ObjectShape) -> ObjectShape {
+        self.samples += other.samples;
+        for field in other.fields {
+            match self.fields.iter_mut().find(|f| f.name == field.name) {
+                Some(existing) => {
+                    existing.present += field.present;
+                    existing.nullable |= field.nullable;
+                    let shape = std::mem::replace(&mut existing.shape, Shape::Null);
+                    existing.shape = shape.merge(field.shape);
+                }
+                None => self.fields.push(field),
+            }
+        }
+        self
+    }
+
+    /// The merged attribute with the given name
+    pub fn field(&self, name: &str) -> Option<&FieldShape> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_merge_items() {
+        let items = [
+            json!({
+                "pk": { "S": "USER#1" },
+                "age": { "N": "36" },
+                "tags": { "SS": ["admin"] },
+                "address": { "M": { "city": { "S": "Oslo" } } },
+                "scores": { "L": [{ "N": "1" }, { "N": "2.5" }] }
+            }),
+            json!({
+                "pk": { "S": "USER#2" },
+                "age": { "N": "41" },
+                "nickname": { "NULL": true },
+                "address": { "M": { "city": { "S": "Bergen" }, "zip": { "S": "5003" } } },
+                "scores": { "L": [] }
+            }),
+        ];
+
+        let shape = ObjectShape::of_items(&items);
+        assert_eq!(shape.samples, 2);
+        assert_eq!(shape.field("age").unwrap().shape, Shape::Int);
+        assert!(!shape.field("age").unwrap().is_optional(2));
+        assert_eq!(shape.field("tags").unwrap().shape, Shape::Set(Box::new(Shape::String)));
+        assert!(shape.field("tags").unwrap().is_optional(2));
+        assert!(shape.field("nickname").unwrap().is_optional(2));
+        assert_eq!(shape.field("scores").unwrap().shape, Shape::List(Box::new(Shape::Number)));
+
+        let Shape::Map(address) = &shape.field("address").unwrap().shape else {
+            panic!("Expected map shape");
+        };
+        assert!(address.field("zip").unwrap().is_optional(address.samples));
+    }
+
+    #[test]
+    fn test_mixed_values() {
+        assert_eq!(Shape::of(&json!({ "N": "1" })).merge(Shape::of(&json!({ "S": "x" }))), Shape::Mixed);
+        assert_eq!(Shape::of(&json!({ "M": {} })).merge(Shape::of(&json!({ "S": "x" }))), Shape::Any);
+        assert_eq!(Shape::of(&json!("plain")), Shape::Any);
+        assert_eq!(Shape::of(&json!({ "NS": ["1", "1.5"] })), Shape::Set(Box::new(Shape::Number)));
+    }
+}
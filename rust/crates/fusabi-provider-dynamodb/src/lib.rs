@@ -0,0 +1,698 @@
+//! DynamoDB Type Provider
+//!
+//! Generates Fusabi types for DynamoDB tables from their descriptions, with
+//! item attributes inferred from an optional sample of items.
+//!
+//! # Sources
+//!
+//! - A `dynamodb://<region>` URI: tables are listed with `ListTables` (or
+//!   named in the path, `dynamodb://us-east-1/orders,users`), described
+//!   with `DescribeTable` and, when `sample` is set, sampled with `Scan`
+//! - `DescribeTable` output, inline or from a file, or an array of them.
+//!   Items sampled offline (`aws dynamodb scan` output) can sit alongside
+//!   `Table` as `Items`, or come from the `items` param
+//!
+//! Live calls are signed with AWS Signature Version 4. Keys are never read
+//! from the source: the `auth` param references a credential holding
+//! `ACCESS_KEY_ID:SECRET_ACCESS_KEY[:SESSION_TOKEN]`, and without it the
+//! standard `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and
+//! `AWS_SESSION_TOKEN` environment variables are used.
+//!
+//! # Generated Types
+//!
+//! Each table gets a module `<namespace>.<Table>` holding:
+//!
+//! - `Key`: the partition and sort key attributes
+//! - `<Index>Key` for each global and local secondary index
+//! - `Item`: the key attributes, index key attributes (optional, as
+//!   indexes may be sparse) and every attribute seen in sampled items
+//!
+//! | Attribute value        | Fusabi                                        |
+//! |------------------------|-----------------------------------------------|
+//! | `S`                    | `string`                                      |
+//! | `N`                    | `int64` if every sampled value is an integer, otherwise `float` |
+//! | `B`                    | `bytes`                                       |
+//! | `BOOL`                 | `bool`                                        |
+//! | `SS` / `NS` / `BS`     | `string list` / `int64 list` or `float list` / `bytes list` |
+//! | `L`                    | `T list`                                      |
+//! | `M`                    | record `<Parent><Attribute>`                   |
+//! | missing from some items, or `NULL` | `T option`                        |
+//! | mixed types            | `any`                                         |
+//!
+//! # Params
+//!
+//! | Param      | Meaning                                                      |
+//! |------------|--------------------------------------------------------------|
+//! | `table`    | Comma-separated tables to keep                               |
+//! | `sample`   | Items to scan per table from a live source; none by default |
+//! | `items`    | Scan output to sample, for a source describing one table     |
+//! | `endpoint` | API endpoint, e.g. `http://localhost:8000` for DynamoDB Local |
+//!
+//! `fetch_timeout` and `max_source_bytes` apply to each API call.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_dynamodb::DynamoDbProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = DynamoDbProvider::new();
+//! let params = ProviderParams::default()
+//!     .with("auth", "env:AWS_KEYS")
+//!     .with("sample", "50");
+//! let schema = provider.resolve_schema("dynamodb://eu-west-1/orders", &params)?;
+//! let types = provider.generate_types(&schema, "Store")?;
+//! ```
+
+mod aws;
+mod infer;
+mod types;
+
+pub use infer::{FieldShape, ObjectShape, Shape};
+pub use types::{AttributeDefinition, KeySchemaElement, SecondaryIndex, TableDescription};
+
+use aws::{AwsCredentials, DynamoClient};
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceOptions, SourceValidator, TypeShape, TypedParams,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, TypeExpr, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Name of the record for a table's items
+const ITEM_TYPE: &str = "Item";
+
+/// A table description with its sampled items
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Table {
+    #[serde(flatten)]
+    description: TableDescription,
+    /// `None` when the table was not sampled
+    #[serde(rename = "Items", default, skip_serializing_if = "Option::is_none")]
+    items: Option<Vec<Value>>,
+}
+
+/// DynamoDB type provider
+pub struct DynamoDbProvider {
+    generator: TypeGenerator,
+}
+
+impl DynamoDbProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<Vec<Table>> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected DynamoDB schema: {}", e)))
+    }
+
+    /// Read `DescribeTable` output, or an array of it
+    fn parse_source(&self, content: &str) -> ProviderResult<Vec<Table>> {
+        let value: Value = serde_json::from_str(content)
+            .map_err(|e| ProviderError::ParseError(format!("Invalid DynamoDB table description: {}", e)))?;
+        match value {
+            Value::Array(entries) => entries.iter().map(table_of).collect(),
+            entry => Ok(vec![table_of(&entry)?]),
+        }
+    }
+
+    /// Describe, and optionally sample, the tables of a live account
+    fn fetch_tables(&self, uri: &str, params: &ProviderParams, typed: &TypedParams) -> ProviderResult<Vec<Table>> {
+        let rest = uri.trim_start_matches("dynamodb://");
+        let (region, path) = rest.split_once('/').unwrap_or((rest, ""));
+        if region.is_empty() {
+            return Err(ProviderError::InvalidSource(format!("No region in {}; expected dynamodb://<region>", uri)));
+        }
+        let sample = typed.uint("sample").unwrap_or(0);
+
+        let credentials = AwsCredentials::from_params(params)?;
+        let options = SourceOptions::from_params(params)?;
+        let endpoint = typed.str("endpoint");
+        let client = DynamoClient::new(region, endpoint, credentials, &options);
+
+        let mut names: Vec<String> = path.split(',').filter(|t| !t.is_empty()).map(String::from).collect();
+        if names.is_empty() {
+            let mut start: Option<Value> = None;
+            loop {
+                let request = match &start {
+                    Some(name) => json!({ "ExclusiveStartTableName": name }),
+                    None => json!({}),
+                };
+                let response = client.call("ListTables", &request)?;
+                let page = response.get("TableNames").and_then(Value::as_array);
+                names.extend(page.into_iter().flatten().filter_map(Value::as_str).map(String::from));
+                start = response.get("LastEvaluatedTableName").cloned();
+                if start.is_none() {
+                    break;
+                }
+            }
+        }
+        if let Some(filter) = typed.list("table") {
+            names.retain(|name| filter.contains(&name.as_str()));
+        }
+
+        let mut tables = Vec::new();
+        for name in names {
+            let response = client.call("DescribeTable", &json!({ "TableName": name }))?;
+            let mut table = table_of(&response)?;
+            if sample > 0 {
+                let response = client.call("Scan", &json!({ "TableName": name, "Limit": sample }))?;
+                table.items = Some(items_of(&response));
+            }
+            tables.push(table);
+        }
+        Ok(tables)
+    }
+
+    /// Generate the key, index key and item types of a table
+    fn table_types(&self, table: &Table) -> (Vec<TypeDefinition>, Vec<Diagnostic>) {
+        let mut inference = Inference {
+            generator: &self.generator,
+            types: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+        let description = &table.description;
+        let name = &description.table_name;
+        let sampled = ObjectShape::of_items(table.items.as_deref().unwrap_or_default());
+
+        let key_sets: Vec<(String, &[KeySchemaElement])> = std::iter::once(("Key".to_string(), &description.key_schema[..]))
+            .chain(description.indexes().map(|index| {
+                (format!("{}Key", self.generator.naming.apply(&index.index_name)), &index.key_schema[..])
+            }))
+            .collect();
+
+        // Key attributes of the table and its indexes, each typed once
+        let mut keys: Vec<(&str, TypeShape)> = Vec::new();
+        for element in key_sets.iter().flat_map(|(_, key_schema)| key_schema.iter()) {
+            let attribute = element.attribute_name.as_str();
+            if !keys.iter().any(|(name, _)| *name == attribute) {
+                let shape = inference.key_shape(description, &sampled, attribute);
+                keys.push((attribute, shape));
+            }
+        }
+        let key_shape = |attribute: &str| keys.iter().find(|(name, _)| *name == attribute).map(|(_, s)| s.clone());
+
+        for (record, key_schema) in &key_sets {
+            let fields = key_schema
+                .iter()
+                .filter_map(|k| Some((k.attribute_name.clone(), key_shape(&k.attribute_name)?.into())))
+                .collect();
+            inference.types.push(TypeDefinition::Record(RecordDef { name: record.clone(), fields }));
+        }
+
+        // Table keys are always present; index keys and everything else may not be
+        let table_keys: Vec<&str> = description.key_schema.iter().map(|k| k.attribute_name.as_str()).collect();
+        let mut fields = Vec::new();
+        for (attribute, shape) in &keys {
+            let shape = if table_keys.contains(attribute) {
+                shape.clone()
+            } else {
+                TypeShape::Option(Box::new(shape.clone()))
+            };
+            fields.push((attribute.to_string(), shape.into()));
+        }
+        for field in sampled.fields.iter().filter(|f| !keys.iter().any(|(name, _)| *name == f.name)) {
+            let context = format!("{}{}", ITEM_TYPE, self.generator.naming.apply(&field.name));
+            let location = format!("{}.{}", name, field.name);
+            let shape = inference.shape_expr(&field.shape, &context, &location);
+            let shape = if field.is_optional(sampled.samples) {
+                TypeShape::Option(Box::new(shape))
+            } else {
+                shape
+            };
+            fields.push((field.name.clone(), shape.into()));
+        }
+        inference.types.push(TypeDefinition::Record(RecordDef {
+            name: ITEM_TYPE.to_string(),
+            fields,
+        }));
+
+        match &table.items {
+            None => inference.diagnostics.push(
+                Diagnostic::info(
+                    "items-unsampled",
+                    format!("Table `{}` was not sampled; `Item` has only key attributes", name),
+                )
+                .at(name.clone()),
+            ),
+            Some(items) if items.is_empty() => inference.diagnostics.push(
+                Diagnostic::info(
+                    "items-unsampled",
+                    format!("Table `{}` has no items to sample; `Item` has only key attributes", name),
+                )
+                .at(name.clone()),
+            ),
+            Some(_) => {}
+        }
+
+        (inference.types, inference.diagnostics)
+    }
+}
+
+impl Default for DynamoDbProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for DynamoDbProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("DynamoDbProvider")
+            .param(ParamSpec::new("table", ParamKind::List).describe("Tables to keep"))
+            .param(
+                ParamSpec::new("sample", ParamKind::UInt)
+                    .describe("Items to scan per table from a live source"),
+            )
+            .param(
+                ParamSpec::new("items", ParamKind::String)
+                    .describe("Scan output to sample, for a source describing one table"),
+            )
+            .param(
+                ParamSpec::new("endpoint", ParamKind::String)
+                    .describe("API endpoint, e.g. `http://localhost:8000` for DynamoDB Local"),
+            )
+    }
+}
+
+impl TypeProvider for DynamoDbProvider {
+    fn name(&self) -> &str {
+        "DynamoDbProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let trimmed = source.trim();
+        let mut tables = if trimmed.starts_with("dynamodb://") {
+            self.fetch_tables(trimmed, params, &typed)?
+        } else if trimmed.starts_with('{') || trimmed.starts_with('[') {
+            self.parse_source(source)?
+        } else {
+            self.parse_source(&read_source(source, params)?)?
+        };
+
+        if let Some(items) = typed.str("items") {
+            let [table] = tables.as_mut_slice() else {
+                return Err(ProviderError::InvalidSource(
+                    "The `items` param needs a source describing exactly one table".to_string(),
+                ));
+            };
+            let content = if items.trim_start().starts_with('{') {
+                items.to_string()
+            } else {
+                read_source(items, params)?
+            };
+            let response: Value = serde_json::from_str(&content)
+                .map_err(|e| ProviderError::ParseError(format!("Invalid Scan output in `items`: {}", e)))?;
+            table.items = Some(items_of(&response));
+        }
+
+        if let Some(filter) = typed.list("table") {
+            tables.retain(|t| filter.contains(&t.description.table_name.as_str()));
+        }
+
+        let json = serde_json::to_string(&tables)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected DynamoDB schema".to_string()));
+        };
+
+        let mut result = GeneratedTypes::new();
+        for table in self.parse_schema(json)? {
+            let mut module = GeneratedModule::new(vec![
+                namespace.to_string(),
+                self.generator.naming.apply(&table.description.table_name),
+            ]);
+            (module.types, _) = self.table_types(&table);
+            result.modules.push(module);
+        }
+        Ok(result)
+    }
+}
+
+impl SourceValidator for DynamoDbProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected DynamoDB schema".to_string()));
+        };
+
+        let tables = self.parse_schema(json)?;
+        let mut diagnostics = Vec::new();
+        if tables.is_empty() {
+            diagnostics.push(Diagnostic::warning("no-types", "No table was found"));
+        }
+        for table in &tables {
+            diagnostics.extend(self.table_types(table).1);
+        }
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for DynamoDbProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .live("dynamodb://")
+            .params(&self.param_schema())
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+/// Collects the types inferred for a table
+struct Inference<'a> {
+    generator: &'a TypeGenerator,
+    types: Vec<TypeDefinition>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Inference<'_> {
+    /// Type of a key attribute: its declared type, narrowed to `int64` when
+    /// every sampled value is an integer
+    fn key_shape(&mut self, description: &TableDescription, sampled: &ObjectShape, attribute: &str) -> TypeShape {
+        let location = format!("{}.{}", description.table_name, attribute);
+        let sampled_shape = sampled.field(attribute).map(|f| &f.shape);
+        let shape = match (description.attribute_type(attribute), sampled_shape) {
+            (Some("N"), Some(Shape::Int)) => Shape::Int,
+            (Some(declared), _) => Shape::of_attribute_type(declared),
+            (None, sampled_shape) => {
+                self.diagnostics.push(
+                    Diagnostic::warning(
+                        "attribute-undefined",
+                        format!("Key attribute `{}` has no attribute definition", attribute),
+                    )
+                    .at(location.clone()),
+                );
+                sampled_shape.cloned().unwrap_or(Shape::Any)
+            }
+        };
+        let context = format!("{}{}", ITEM_TYPE, self.generator.naming.apply(attribute));
+        self.shape_expr(&shape, &context, &location)
+    }
+
+    fn shape_expr(&mut self, shape: &Shape, name: &str, location: &str) -> TypeShape {
+        let named = |name: &str| TypeShape::Named(name.to_string());
+        match shape {
+            Shape::Bool => named("bool"),
+            Shape::String => named("string"),
+            Shape::Int => named("int64"),
+            Shape::Number => named("float"),
+            Shape::Binary => named("bytes"),
+            Shape::Set(element) | Shape::List(element) => {
+                TypeShape::List(Box::new(self.shape_expr(element, name, &format!("{}[]", location))))
+            }
+            // An empty map carries no attributes to build a record from
+            Shape::Map(object) if object.fields.is_empty() => {
+                TypeShape::Map(Box::new(named("string")), Box::new(self.coerced(location, "is always an empty map")))
+            }
+            Shape::Map(object) => {
+                let mut fields = Vec::new();
+                for field in &object.fields {
+                    let context = format!("{}{}", name, self.generator.naming.apply(&field.name));
+                    let field_location = format!("{}.{}", location, field.name);
+                    let shape = self.shape_expr(&field.shape, &context, &field_location);
+                    let shape = if field.is_optional(object.samples) {
+                        TypeShape::Option(Box::new(shape))
+                    } else {
+                        shape
+                    };
+                    fields.push((field.name.clone(), TypeExpr::from(shape)));
+                }
+                self.types.push(TypeDefinition::Record(RecordDef {
+                    name: name.to_string(),
+                    fields,
+                }));
+                named(name)
+            }
+            Shape::Null => self.coerced(location, "is always NULL or empty"),
+            Shape::Mixed => self.coerced(location, "mixes attribute types"),
+            Shape::Any => self.coerced(location, "mixes incompatible forms"),
+        }
+    }
+
+    fn coerced(&mut self, location: &str, reason: &str) -> TypeShape {
+        self.diagnostics.push(
+            Diagnostic::info("coerced-any", format!("`{}` {}; generated as `any`", location, reason))
+                .at(location.to_string()),
+        );
+        TypeShape::Named("any".to_string())
+    }
+}
+
+/// The table of a `DescribeTable` response or bare table description,
+/// with any `Items` beside it
+fn table_of(entry: &Value) -> ProviderResult<Table> {
+    let (description, items) = match entry.get("Table") {
+        Some(table) => (table, entry.get("Items").or_else(|| table.get("Items"))),
+        None => (entry, entry.get("Items")),
+    };
+    let description: TableDescription = serde_json::from_value(description.clone())
+        .map_err(|e| ProviderError::ParseError(format!("Invalid DynamoDB table description: {}", e)))?;
+    Ok(Table {
+        description,
+        items: items.map(|items| items.as_array().cloned().unwrap_or_default()),
+    })
+}
+
+/// Items of a `Scan` or `Query` response
+fn items_of(response: &Value) -> Vec<Value> {
+    response.get("Items").and_then(Value::as_array).cloned().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    const ORDERS: &str = r#"{
+        "Table": {
+            "TableName": "orders",
+            "TableStatus": "ACTIVE",
+            "KeySchema": [
+                {"AttributeName": "customer_id", "KeyType": "HASH"},
+                {"AttributeName": "order_id", "KeyType": "RANGE"}
+            ],
+            "AttributeDefinitions": [
+                {"AttributeName": "customer_id", "AttributeType": "S"},
+                {"AttributeName": "order_id", "AttributeType": "N"},
+                {"AttributeName": "status", "AttributeType": "S"},
+                {"AttributeName": "placed_at", "AttributeType": "N"}
+            ],
+            "GlobalSecondaryIndexes": [{
+                "IndexName": "by-status",
+                "KeySchema": [
+                    {"AttributeName": "status", "KeyType": "HASH"},
+                    {"AttributeName": "placed_at", "KeyType": "RANGE"}
+                ],
+                "Projection": {"ProjectionType": "ALL"}
+            }]
+        },
+        "Items": [
+            {"customer_id": {"S": "c1"}, "order_id": {"N": "1"}, "status": {"S": "NEW"}, "placed_at": {"N": "1704450000.5"},
+             "total": {"N": "19.99"}, "tags": {"SS": ["gift"]},
+             "shipping": {"M": {"city": {"S": "Oslo"}, "express": {"BOOL": true}}}},
+            {"customer_id": {"S": "c2"}, "order_id": {"N": "2"}, "total": {"N": "5"},
+             "shipping": {"M": {"city": {"S": "Bergen"}}}, "note": {"NULL": true}}
+        ]
+    }"#;
+
+    fn module<'a>(types: &'a GeneratedTypes, table: &str) -> &'a GeneratedModule {
+        types
+            .modules
+            .iter()
+            .find(|m| m.path.last().map(String::as_str) == Some(table))
+            .unwrap_or_else(|| panic!("Expected module {}", table))
+    }
+
+    fn record<'a>(module: &'a GeneratedModule, name: &str) -> &'a RecordDef {
+        module
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+    }
+
+    fn fields(record: &RecordDef) -> Vec<(String, String)> {
+        record.fields.iter().map(|(name, ty)| (name.clone(), ty.to_string())).collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect()
+    }
+
+    #[test]
+    fn test_table_types() {
+        let provider = DynamoDbProvider::new();
+        let schema = provider.resolve_schema(ORDERS, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Store").unwrap();
+
+        let orders = module(&types, "Orders");
+        assert_eq!(orders.path, vec!["Store", "Orders"]);
+        assert_eq!(fields(record(orders, "Key")), pairs(&[("customer_id", "string"), ("order_id", "int64")]));
+        assert_eq!(fields(record(orders, "ByStatusKey")), pairs(&[("status", "string"), ("placed_at", "float")]));
+        assert_eq!(
+            fields(record(orders, "Item")),
+            pairs(&[
+                ("customer_id", "string"),
+                ("order_id", "int64"),
+                ("status", "string option"),
+                ("placed_at", "float option"),
+                ("shipping", "ItemShipping"),
+                ("tags", "string list option"),
+                ("total", "float"),
+                ("note", "any option"),
+            ])
+        );
+        assert_eq!(
+            fields(record(orders, "ItemShipping")),
+            pairs(&[("city", "string"), ("express", "bool option")])
+        );
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/dynamodb.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_unsampled_tables() {
+        let description: Value = serde_json::from_str(ORDERS).unwrap();
+        let bare = json!([description["Table"], {
+            "TableName": "sessions",
+            "KeySchema": [{"AttributeName": "token", "KeyType": "HASH"}],
+            "AttributeDefinitions": [{"AttributeName": "token", "AttributeType": "B"}]
+        }]);
+
+        let provider = DynamoDbProvider::new();
+        let params = ProviderParams::default().with("table", "sessions");
+        let schema = provider.resolve_schema(&bare.to_string(), &params).unwrap();
+        let types = provider.generate_types(&schema, "Store").unwrap();
+        assert_eq!(types.modules.len(), 1);
+        assert_eq!(fields(record(module(&types, "Sessions"), "Item")), pairs(&[("token", "bytes")]));
+
+        // Scan output from the `items` param samples a single table
+        let params = ProviderParams::default()
+            .with("table", "sessions")
+            .with("items", r#"{"Items": [{"token": {"B": "AAE="}, "ttl": {"N": "1704450000"}}], "Count": 1}"#);
+        assert!(provider.resolve_schema(&bare.to_string(), &params).is_err());
+        let schema = provider.resolve_schema(&bare[1].to_string(), &params).unwrap();
+        let types = provider.generate_types(&schema, "Store").unwrap();
+        assert_eq!(
+            fields(record(module(&types, "Sessions"), "Item")),
+            pairs(&[("token", "bytes"), ("ttl", "int64")])
+        );
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = DynamoDbProvider::new();
+        let report = provider.validate_source(ORDERS, &ProviderParams::default());
+        assert!(report.is_valid());
+
+        let codes: Vec<(&str, Option<&str>)> = report
+            .diagnostics
+            .iter()
+            .map(|d| (d.code.as_str(), d.location.as_deref()))
+            .collect();
+        assert!(codes.contains(&("coerced-any", Some("orders.note"))));
+
+        let unsampled = r#"{"TableName": "t", "KeySchema": [{"AttributeName": "id", "KeyType": "HASH"}]}"#;
+        let report = provider.validate_source(unsampled, &ProviderParams::default());
+        let codes: Vec<&str> = report.diagnostics.iter().map(|d| d.code.as_str()).collect();
+        assert!(codes.contains(&"items-unsampled"));
+        assert!(codes.contains(&"attribute-undefined"));
+    }
+
+    /// Answer DynamoDB API calls with canned bodies, keyed by operation
+    fn serve(routes: Vec<(&'static str, Value)>) -> (String, std::thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handle = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for _ in 0..routes.len() {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                // Read headers, then as much body as Content-Length says
+                loop {
+                    let n = stream.read(&mut buf).unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        let len = head
+                            .lines()
+                            .find_map(|l| l.to_ascii_lowercase().strip_prefix("content-length: ").map(String::from))
+                            .and_then(|l| l.trim().parse::<usize>().ok())
+                            .unwrap_or(0);
+                        if body.len() >= len {
+                            break;
+                        }
+                    }
+                }
+                let text = String::from_utf8_lossy(&request).to_string();
+                let target = text
+                    .lines()
+                    .find_map(|l| l.to_ascii_lowercase().strip_prefix("x-amz-target: ").map(String::from))
+                    .unwrap_or_default();
+                let body = routes
+                    .iter()
+                    .find(|(operation, _)| target.ends_with(&operation.to_ascii_lowercase()))
+                    .map_or(Value::Null, |(_, body)| body.clone())
+                    .to_string();
+                requests.push(text);
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/x-amz-json-1.0\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+        (format!("http://{}", addr), handle)
+    }
+
+    #[test]
+    fn test_fetch_live_tables() {
+        let description: Value = serde_json::from_str(ORDERS).unwrap();
+        let (endpoint, server) = serve(vec![
+            ("ListTables", json!({ "TableNames": ["orders"] })),
+            ("DescribeTable", json!({ "Table": description["Table"] })),
+            ("Scan", json!({ "Items": description["Items"], "Count": 2, "ScannedCount": 2 })),
+        ]);
+
+        let credentials = std::env::temp_dir().join("fusabi-dynamodb-test-keys");
+        std::fs::write(&credentials, "AKIDEXAMPLE:wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY\n").unwrap();
+        let params = ProviderParams::default()
+            .with("endpoint", &endpoint)
+            .with("auth", &format!("file:{}", credentials.display()))
+            .with("sample", "10");
+
+        let provider = DynamoDbProvider::new();
+        let schema = provider.resolve_schema("dynamodb://us-east-1", &params).unwrap();
+        let types = provider.generate_types(&schema, "Store").unwrap();
+        assert_eq!(record(module(&types, "Orders"), "Item").fields.len(), 8);
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].contains("Credential=AKIDEXAMPLE/"));
+        assert!(requests[0].contains("/us-east-1/dynamodb/aws4_request"));
+        assert!(requests[2].contains(r#""Limit":10"#));
+        assert!(!requests.iter().any(|r| r.contains("EXAMPLEKEY")));
+    }
+}
@@ -0,0 +1,64 @@
+//! DynamoDB table description types
+//!
+//! The subset of the `DescribeTable` response the provider reads, with the
+//! API's PascalCase field names. Other fields are ignored.
+
+use serde::{Deserialize, Serialize};
+
+/// A table, as in the `Table` member of a `DescribeTable` response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct TableDescription {
+    pub table_name: String,
+    #[serde(default)]
+    pub key_schema: Vec<KeySchemaElement>,
+    /// Types of the key attributes of the table and its indexes
+    #[serde(default)]
+    pub attribute_definitions: Vec<AttributeDefinition>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub global_secondary_indexes: Vec<SecondaryIndex>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub local_secondary_indexes: Vec<SecondaryIndex>,
+}
+
+/// One key attribute and its role
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct KeySchemaElement {
+    pub attribute_name: String,
+    /// `HASH` (partition key) or `RANGE` (sort key)
+    pub key_type: String,
+}
+
+/// The scalar type of a key attribute
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AttributeDefinition {
+    pub attribute_name: String,
+    /// `S`, `N` or `B`
+    pub attribute_type: String,
+}
+
+/// A global or local secondary index
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SecondaryIndex {
+    pub index_name: String,
+    #[serde(default)]
+    pub key_schema: Vec<KeySchemaElement>,
+}
+
+impl TableDescription {
+    /// Declared type of an attribute
+    pub fn attribute_type(&self, name: &str) -> Option<&str> {
+        self.attribute_definitions
+            .iter()
+            .find(|a| a.attribute_name == name)
+            .map(|a| a.attribute_type.as_str())
+    }
+
+    /// Secondary indexes, global ones first
+    pub fn indexes(&self) -> impl Iterator<Item = &SecondaryIndex> {
+        self.global_secondary_indexes.iter().chain(&self.local_secondary_indexes)
+    }
+}
@@ -0,0 +1,193 @@
+//! ECS field reference formats
+//!
+//! Each ECS release publishes its fields in several generated forms; these
+//! are read into the same flat list:
+//!
+//! - `generated/ecs/ecs_flat.yml`: fields by dotted name
+//! - `generated/ecs/ecs_nested.yml`: fields grouped by fieldset. Fieldsets
+//!   that are only reused inside others (`geo`, `os`) are skipped, since
+//!   their reused copies are listed under each fieldset they appear in
+//! - `generated/csv/fields.csv`: one row per field, with the ECS version
+
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Fields that ECS requires in every document; the CSV does not mark them
+const REQUIRED_FIELDS: &[&str] = &["@timestamp", "ecs.version"];
+
+/// The fields of one ECS release
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EcsFields {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    pub fields: Vec<EcsField>,
+}
+
+/// A field by its dotted name, e.g. `host.os.name`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EcsField {
+    pub name: String,
+    /// Elasticsearch field type, e.g. `keyword` or `long`
+    #[serde(rename = "type")]
+    pub field_type: String,
+    /// `core`, `extended` or `custom`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+    /// Normalized to an array of values
+    #[serde(default)]
+    pub array: bool,
+    #[serde(default)]
+    pub required: bool,
+    /// Value type of an `object` field, e.g. `keyword` for `labels`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object_type: Option<String>,
+}
+
+impl EcsField {
+    /// The fieldset a field belongs to; fields without a dot are in `base`
+    pub fn fieldset(&self) -> &str {
+        match self.name.split_once('.') {
+            Some((fieldset, _)) => fieldset,
+            None => "base",
+        }
+    }
+}
+
+/// Whether content is the CSV field reference
+pub fn is_csv(content: &str) -> bool {
+    content.trim_start().starts_with("ECS_Version,")
+}
+
+/// Read `fields.csv`
+pub fn parse_csv(content: &str) -> ProviderResult<EcsFields> {
+    let mut reader = csv::Reader::from_reader(content.trim_start().as_bytes());
+    let headers = reader
+        .headers()
+        .map_err(|e| ProviderError::ParseError(format!("Invalid ECS CSV: {}", e)))?
+        .clone();
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|h| h == name)
+            .ok_or_else(|| ProviderError::ParseError(format!("ECS CSV has no `{}` column", name)))
+    };
+    let (version, field, field_type, level, normalization) = (
+        column("ECS_Version")?,
+        column("Field")?,
+        column("Type")?,
+        column("Level")?,
+        column("Normalization")?,
+    );
+
+    let mut result = EcsFields::default();
+    for record in reader.records() {
+        let record = record.map_err(|e| ProviderError::ParseError(format!("Invalid ECS CSV: {}", e)))?;
+        let cell = |index: usize| record.get(index).unwrap_or_default().trim();
+        if result.version.is_none() && !cell(version).is_empty() {
+            result.version = Some(cell(version).to_string());
+        }
+        let name = cell(field);
+        result.fields.push(EcsField {
+            name: name.to_string(),
+            field_type: cell(field_type).to_string(),
+            level: Some(cell(level).to_string()).filter(|l| !l.is_empty()),
+            array: cell(normalization).split(',').any(|n| n.trim() == "array"),
+            required: REQUIRED_FIELDS.contains(&name),
+            object_type: None,
+        });
+    }
+    Ok(result)
+}
+
+/// Read `ecs_flat.yml` or `ecs_nested.yml`
+pub fn parse_yaml(content: &str) -> ProviderResult<EcsFields> {
+    let value: Value = serde_yaml::from_str(content)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid ECS YAML: {}", e)))?;
+    let entries = value
+        .as_object()
+        .ok_or_else(|| ProviderError::ParseError("Expected an ECS field reference mapping".to_string()))?;
+
+    let nested = entries.values().any(|v| v.get("fields").is_some_and(Value::is_object));
+    let mut result = EcsFields::default();
+    if nested {
+        for fieldset in entries.values() {
+            let top_level = fieldset
+                .pointer("/reusable/top_level")
+                .and_then(Value::as_bool)
+                .unwrap_or(true);
+            if !top_level {
+                continue;
+            }
+            for (name, definition) in fieldset.get("fields").and_then(Value::as_object).into_iter().flatten() {
+                result.fields.push(field(name, definition)?);
+            }
+        }
+    } else {
+        for (name, definition) in entries {
+            result.fields.push(field(name, definition)?);
+        }
+    }
+    result.fields.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(result)
+}
+
+fn field(key: &str, definition: &Value) -> ProviderResult<EcsField> {
+    let text = |key: &str| definition.get(key).and_then(Value::as_str).map(String::from);
+    let name = text("flat_name").unwrap_or_else(|| key.to_string());
+    let field_type = text("type")
+        .ok_or_else(|| ProviderError::ParseError(format!("ECS field `{}` has no type", name)))?;
+
+    Ok(EcsField {
+        array: definition
+            .get("normalize")
+            .and_then(Value::as_array)
+            .is_some_and(|n| n.iter().any(|n| n == "array")),
+        required: definition.get("required").and_then(Value::as_bool).unwrap_or(false),
+        level: text("level"),
+        object_type: text("object_type"),
+        field_type,
+        name,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv() {
+        let csv = "ECS_Version,Indexed,Field_Set,Field,Type,Level,Normalization,Example,Description\n\
+            8.11.0,true,base,@timestamp,date,core,,2016-05-23T08:05:34.853Z,Date/time when the event originated.\n\
+            8.11.0,true,base,tags,keyword,core,array,\"[\"\"production\"\", \"\"env2\"\"]\",List of keywords.\n\
+            8.11.0,true,host,host.os.name,keyword,extended,,Mac OS X,\"Operating system name, without the version.\"\n";
+        let fields = parse_csv(csv).unwrap();
+        assert_eq!(fields.version.as_deref(), Some("8.11.0"));
+        assert_eq!(fields.fields.len(), 3);
+        assert!(fields.fields[0].required);
+        assert!(fields.fields[1].array);
+        assert_eq!(fields.fields[2].fieldset(), "host");
+        assert_eq!(fields.fields[2].level.as_deref(), Some("extended"));
+    }
+
+    #[test]
+    fn test_parse_nested_yaml() {
+        let yaml = r#"
+geo:
+  name: geo
+  reusable:
+    top_level: false
+  fields:
+    geo.city_name: {flat_name: geo.city_name, type: keyword, level: core}
+host:
+  name: host
+  fields:
+    host.geo.city_name: {flat_name: host.geo.city_name, type: keyword, level: core}
+    host.ip: {flat_name: host.ip, type: ip, level: core, normalize: [array]}
+"#;
+        let fields = parse_yaml(yaml).unwrap();
+        let names: Vec<&str> = fields.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["host.geo.city_name", "host.ip"]);
+        assert!(fields.fields[1].array);
+    }
+}
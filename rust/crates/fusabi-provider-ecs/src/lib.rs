@@ -0,0 +1,589 @@
+//! Elastic Common Schema Type Provider
+//!
+//! Generates Fusabi records for documents in the Elastic Common Schema, so
+//! log normalization pipelines can build and read ECS events with checked
+//! field names.
+//!
+//! # Sources
+//!
+//! - An `ecs://<version>` URI, e.g. `ecs://8.11.0`: the release's
+//!   `ecs_flat.yml` is fetched from the ECS repository (`ecs://main` for
+//!   unreleased fields). With a bare `ecs://`, the `version` param names
+//!   the release
+//! - A field reference from a release, inline or from a file:
+//!   `ecs_flat.yml`, `ecs_nested.yml` or `fields.csv`; see [`fields`]
+//!
+//! When the source states its version (the URI, or the CSV's
+//! `ECS_Version` column), the `version` param must match it.
+//!
+//! # Generated Types
+//!
+//! Dotted field names become nested records: `host.os.name` is the `name`
+//! field of `HostOs`, the `os` field of `Host`, which is the `host` field
+//! of the document record (`Document`, unless the `record` param says
+//! otherwise). Fields are `T option` except the required `@timestamp` and
+//! `ecs.version`, and fields normalized to arrays are `T list`.
+//!
+//! | ECS type                                          | Fusabi                 |
+//! |---------------------------------------------------|------------------------|
+//! | `keyword`, `constant_keyword`, `wildcard`         | `string`               |
+//! | `text`, `match_only_text`, `ip`, `version`        | `string`               |
+//! | `date`, `date_nanos`, `binary`                    | `string`               |
+//! | `long`, `unsigned_long`                           | `int64`                |
+//! | `integer`, `short`, `byte`                        | `int`                  |
+//! | `float`, `half_float`, `scaled_float`, `double`   | `float`                |
+//! | `boolean`                                         | `bool`                 |
+//! | `geo_point`                                       | `GeoPoint` (`lat`, `lon`) |
+//! | `object`, `flattened` without sub-fields          | `Map<string, T>`       |
+//! | `object`, `nested`, `group` with sub-fields       | a record               |
+//!
+//! Multi-fields such as `user.name.text` index the same value again and
+//! are not generated.
+//!
+//! # Params
+//!
+//! | Param      | Meaning                                                      |
+//! |------------|--------------------------------------------------------------|
+//! | `version`  | ECS release, e.g. `8.11.0`                                   |
+//! | `fieldset` | Comma-separated fieldsets to keep, e.g. `event,host`; `base` holds `@timestamp`, `message`, `tags`, `labels` |
+//! | `level`    | `core` to keep only core fields                             |
+//! | `record`   | Name of the document record; defaults to `Document`          |
+//! | `endpoint` | Repository base URL; defaults to `https://raw.githubusercontent.com/elastic/ecs` |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_ecs::EcsProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = EcsProvider::new();
+//! let params = ProviderParams::default().with("fieldset", "base,event,host,user");
+//! let schema = provider.resolve_schema("ecs://8.11.0", &params)?;
+//! let types = provider.generate_types(&schema, "Ecs")?;
+//! ```
+
+pub mod fields;
+
+pub use fields::{is_csv, parse_csv, parse_yaml, EcsField, EcsFields};
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceValidator, TypeShape, TypedParams,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, TypeExpr, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use serde::{Deserialize, Serialize};
+
+/// Default ECS repository for `ecs://` sources
+pub const DEFAULT_ENDPOINT: &str = "https://raw.githubusercontent.com/elastic/ecs";
+
+/// Default name of the document record
+pub const DEFAULT_RECORD: &str = "Document";
+
+/// Record generated for `geo_point` fields
+const GEO_POINT: &str = "GeoPoint";
+
+/// Fields and the name of their document record, stored in [`Schema::Custom`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ResolvedFields {
+    record: String,
+    #[serde(flatten)]
+    fields: EcsFields,
+}
+
+/// A field name segment, with the field it completes and the fields below it
+#[derive(Default)]
+struct Node<'a> {
+    field: Option<&'a EcsField>,
+    children: Vec<(&'a str, Node<'a>)>,
+}
+
+impl<'a> Node<'a> {
+    fn insert(&mut self, segments: &[&'a str], field: &'a EcsField) {
+        match segments.split_first() {
+            None => self.field = Some(field),
+            Some((first, rest)) => {
+                let index = match self.children.iter().position(|(name, _)| name == first) {
+                    Some(index) => index,
+                    None => {
+                        self.children.push((first, Node::default()));
+                        self.children.len() - 1
+                    }
+                };
+                self.children[index].1.insert(rest, field);
+            }
+        }
+    }
+
+    /// Names of the fields below this node
+    fn descendants(&self) -> Vec<&'a str> {
+        self.children
+            .iter()
+            .flat_map(|(_, child)| child.field.map(|f| f.name.as_str()).into_iter().chain(child.descendants()))
+            .collect()
+    }
+}
+
+/// ECS type provider
+pub struct EcsProvider {
+    generator: TypeGenerator,
+}
+
+impl EcsProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<ResolvedFields> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected ECS fields: {}", e)))
+    }
+
+    fn parse_content(&self, content: &str) -> ProviderResult<EcsFields> {
+        if is_csv(content) {
+            parse_csv(content)
+        } else {
+            parse_yaml(content)
+        }
+    }
+
+    /// Fetch `ecs_flat.yml` of a release from the ECS repository
+    fn fetch_release(&self, uri: &str, params: &ProviderParams, typed: &TypedParams) -> ProviderResult<EcsFields> {
+        let version = match uri.trim_start_matches("ecs://").trim_matches('/') {
+            "" => typed.str("version").ok_or_else(|| {
+                ProviderError::InvalidSource("Expected ecs://<version>, or a `version` param".to_string())
+            })?,
+            version => version,
+        };
+        let version = version.trim_start_matches('v');
+        let endpoint = typed.str("endpoint").map_or(DEFAULT_ENDPOINT, |e| e.trim_end_matches('/'));
+        // Releases are tagged `v8.11.0`; anything else is a branch
+        let reference = if version.starts_with(|c: char| c.is_ascii_digit()) {
+            format!("v{}", version)
+        } else {
+            version.to_string()
+        };
+
+        let url = format!("{}/{}/generated/ecs/ecs_flat.yml", endpoint, reference);
+        let mut fields = parse_yaml(&read_source(&url, params)?)?;
+        if reference.starts_with('v') {
+            fields.version = Some(version.to_string());
+        }
+        Ok(fields)
+    }
+
+    /// Generate the document record and the records nested in it
+    fn generate(&self, resolved: &ResolvedFields) -> (Vec<TypeDefinition>, Vec<Diagnostic>) {
+        let mut root = Node::default();
+        for field in &resolved.fields.fields {
+            root.insert(&field.name.split('.').collect::<Vec<_>>(), field);
+        }
+
+        let mut generation = Generation {
+            generator: &self.generator,
+            types: Vec::new(),
+            diagnostics: Vec::new(),
+            geo_point: false,
+        };
+        let fields = generation.fields(&root, "");
+        if generation.geo_point {
+            generation.types.push(TypeDefinition::Record(RecordDef {
+                name: GEO_POINT.to_string(),
+                fields: vec![
+                    ("lat".to_string(), TypeShape::Named("float".to_string()).into()),
+                    ("lon".to_string(), TypeShape::Named("float".to_string()).into()),
+                ],
+            }));
+        }
+        generation.types.push(TypeDefinition::Record(RecordDef {
+            name: resolved.record.clone(),
+            fields,
+        }));
+        (generation.types, generation.diagnostics)
+    }
+}
+
+impl Default for EcsProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for EcsProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("EcsProvider")
+            .param(
+                ParamSpec::new("version", ParamKind::String).describe("ECS release, e.g. `8.11.0`"),
+            )
+            .param(
+                ParamSpec::new("fieldset", ParamKind::List)
+                    .describe("Fieldsets to keep, e.g. `event,host`"),
+            )
+            .param(
+                ParamSpec::new("level", ParamKind::Choice(&["core"]))
+                    .describe("`core` to keep only core fields"),
+            )
+            .param(
+                ParamSpec::new("record", ParamKind::String)
+                    .default(DEFAULT_RECORD)
+                    .describe("Name of the document record"),
+            )
+            .param(
+                ParamSpec::new("endpoint", ParamKind::String)
+                    .default(DEFAULT_ENDPOINT)
+                    .describe("Repository base URL for `ecs://` sources"),
+            )
+    }
+}
+
+impl TypeProvider for EcsProvider {
+    fn name(&self) -> &str {
+        "EcsProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let trimmed = source.trim();
+        let mut fields = if trimmed.starts_with("ecs://") {
+            self.fetch_release(trimmed, params, &typed)?
+        } else if is_csv(source) || trimmed.contains('\n') {
+            self.parse_content(source)?
+        } else {
+            self.parse_content(&read_source(source, params)?)?
+        };
+
+        if let (Some(pinned), Some(version)) = (typed.str("version"), &fields.version) {
+            if pinned.trim_start_matches('v') != version {
+                return Err(ProviderError::InvalidSource(format!(
+                    "Expected ECS {}, but the source is ECS {}",
+                    pinned, version
+                )));
+            }
+        }
+
+        if let Some(fieldsets) = typed.list("fieldset") {
+            fields.fields.retain(|f| fieldsets.contains(&f.fieldset()));
+        }
+        if typed.str("level") == Some("core") {
+            fields.fields.retain(|f| f.level.as_deref() == Some("core"));
+        }
+
+        let resolved = ResolvedFields {
+            record: typed.str("record").unwrap_or(DEFAULT_RECORD).to_string(),
+            fields,
+        };
+        let json = serde_json::to_string(&resolved)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected ECS fields".to_string()));
+        };
+
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+        module.types = self.generate(&self.parse_schema(json)?).0;
+
+        let mut result = GeneratedTypes::new();
+        result.modules.push(module);
+        Ok(result)
+    }
+}
+
+impl SourceValidator for EcsProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected ECS fields".to_string()));
+        };
+
+        let resolved = self.parse_schema(json)?;
+        let mut diagnostics = Vec::new();
+        if resolved.fields.fields.is_empty() {
+            diagnostics.push(Diagnostic::warning("no-fields", "No ECS fields found"));
+        }
+        diagnostics.extend(self.generate(&resolved).1);
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for EcsProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .params(&self.param_schema())
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+/// Records generated for a document, with what was lost on the way
+struct Generation<'a> {
+    generator: &'a TypeGenerator,
+    types: Vec<TypeDefinition>,
+    diagnostics: Vec<Diagnostic>,
+    geo_point: bool,
+}
+
+impl Generation<'_> {
+    /// The fields of the record for `node`; `record` names it, empty for
+    /// the document itself
+    fn fields(&mut self, node: &Node, record: &str) -> Vec<(String, TypeExpr)> {
+        node.children
+            .iter()
+            .map(|(segment, child)| {
+                let name = format!("{}{}", record, self.generator.naming.apply(segment));
+                let mut shape = self.shape(child, &name);
+                match child.field {
+                    Some(field) if field.required => {}
+                    _ => shape = TypeShape::Option(Box::new(shape)),
+                }
+                (segment.to_string(), shape.into())
+            })
+            .collect()
+    }
+
+    fn shape(&mut self, node: &Node, name: &str) -> TypeShape {
+        let Some(field) = node.field else {
+            return self.record(node, name);
+        };
+
+        let container = matches!(field.field_type.as_str(), "object" | "nested" | "group" | "flattened");
+        let shape = if container && !node.children.is_empty() {
+            self.record(node, name)
+        } else {
+            if !node.children.is_empty() {
+                self.diagnostics.push(
+                    Diagnostic::info(
+                        "multi-field-skipped",
+                        format!("Multi-fields {} are skipped", node.descendants().join(", ")),
+                    )
+                    .at(field.name.clone()),
+                );
+            }
+            self.leaf(field)
+        };
+
+        if field.array || field.field_type == "nested" {
+            TypeShape::List(Box::new(shape))
+        } else {
+            shape
+        }
+    }
+
+    fn record(&mut self, node: &Node, name: &str) -> TypeShape {
+        let fields = self.fields(node, name);
+        self.types.push(TypeDefinition::Record(RecordDef {
+            name: name.to_string(),
+            fields,
+        }));
+        TypeShape::Named(name.to_string())
+    }
+
+    fn leaf(&mut self, field: &EcsField) -> TypeShape {
+        let named = |name: &str| TypeShape::Named(name.to_string());
+        match field.field_type.as_str() {
+            "object" | "flattened" | "nested" => {
+                let value = match field.object_type.as_deref() {
+                    Some(object_type) => self.scalar(object_type, &field.name),
+                    None => named("any"),
+                };
+                TypeShape::Map(Box::new(named("string")), Box::new(value))
+            }
+            field_type => self.scalar(field_type, &field.name),
+        }
+    }
+
+    fn scalar(&mut self, field_type: &str, location: &str) -> TypeShape {
+        let named = |name: &str| TypeShape::Named(name.to_string());
+        match field_type {
+            "keyword" | "constant_keyword" | "wildcard" | "text" | "match_only_text" | "ip" | "version"
+            | "date" | "date_nanos" | "binary" => named("string"),
+            "long" => named("int64"),
+            "unsigned_long" => {
+                self.diagnostics.push(
+                    Diagnostic::info("lossy-conversion", "`unsigned_long` above 2^63 does not fit `int64`")
+                        .at(location.to_string()),
+                );
+                named("int64")
+            }
+            "integer" | "short" | "byte" => named("int"),
+            "float" | "half_float" | "scaled_float" | "double" => named("float"),
+            "boolean" => named("bool"),
+            "geo_point" => {
+                self.geo_point = true;
+                named(GEO_POINT)
+            }
+            _ => {
+                self.diagnostics.push(
+                    Diagnostic::warning("unmapped-type", format!("ECS type `{}` has no Fusabi mapping", field_type))
+                        .at(location.to_string()),
+                );
+                named("any")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    const FLAT: &str = r#"
+'@timestamp': {dashed_name: timestamp, flat_name: '@timestamp', level: core, name: '@timestamp', required: true, type: date}
+ecs.version: {flat_name: ecs.version, level: core, required: true, type: keyword}
+event.category: {flat_name: event.category, level: core, normalize: [array], type: keyword}
+event.duration: {flat_name: event.duration, level: core, type: long}
+host.os.name: {flat_name: host.os.name, level: extended, type: keyword, multi_fields: [{flat_name: host.os.name.text, name: text, type: match_only_text}]}
+host.geo.location: {flat_name: host.geo.location, level: core, type: geo_point}
+labels: {flat_name: labels, level: core, object_type: keyword, type: object}
+dns.answers: {flat_name: dns.answers, level: extended, normalize: [array], type: object}
+dns.answers.ttl: {flat_name: dns.answers.ttl, level: extended, type: long}
+user.name: {flat_name: user.name, level: core, type: keyword}
+"#;
+
+    fn record<'a>(types: &'a GeneratedTypes, name: &str) -> &'a RecordDef {
+        types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+    }
+
+    fn fields(record: &RecordDef) -> Vec<(String, String)> {
+        record.fields.iter().map(|(name, ty)| (name.clone(), ty.to_string())).collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect()
+    }
+
+    #[test]
+    fn test_ecs_records() {
+        let provider = EcsProvider::new();
+        let schema = provider.resolve_schema(FLAT, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Ecs").unwrap();
+
+        assert_eq!(
+            fields(record(&types, "Document")),
+            pairs(&[
+                ("@timestamp", "string"),
+                ("dns", "Dns option"),
+                ("ecs", "Ecs option"),
+                ("event", "Event option"),
+                ("host", "Host option"),
+                ("labels", "Map<string, string> option"),
+                ("user", "User option"),
+            ])
+        );
+        assert_eq!(fields(record(&types, "Ecs")), pairs(&[("version", "string")]));
+        assert_eq!(
+            fields(record(&types, "Event")),
+            pairs(&[("category", "string list option"), ("duration", "int64 option")])
+        );
+        assert_eq!(
+            fields(record(&types, "Host")),
+            pairs(&[("geo", "HostGeo option"), ("os", "HostOs option")])
+        );
+        assert_eq!(fields(record(&types, "HostGeo")), pairs(&[("location", "GeoPoint option")]));
+        assert_eq!(fields(record(&types, "Dns")), pairs(&[("answers", "DnsAnswers list option")]));
+        assert_eq!(fields(record(&types, "DnsAnswers")), pairs(&[("ttl", "int64 option")]));
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/ecs.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_fieldset_and_level_filters() {
+        let provider = EcsProvider::new();
+        let params = ProviderParams::default()
+            .with("fieldset", "base,host")
+            .with("level", "core")
+            .with("record", "LogEvent");
+        let schema = provider.resolve_schema(FLAT, &params).unwrap();
+        let types = provider.generate_types(&schema, "Ecs").unwrap();
+
+        assert_eq!(
+            fields(record(&types, "LogEvent")),
+            pairs(&[
+                ("@timestamp", "string"),
+                ("host", "Host option"),
+                ("labels", "Map<string, string> option"),
+            ])
+        );
+        assert_eq!(fields(record(&types, "Host")), pairs(&[("geo", "HostGeo option")]));
+    }
+
+    #[test]
+    fn test_csv_version_pinning() {
+        let csv = "ECS_Version,Indexed,Field_Set,Field,Type,Level,Normalization,Example,Description\n\
+            8.11.0,true,user,user.name,keyword,core,,a.einstein,Short name or login of the user.\n\
+            8.11.0,true,user,user.name.text,match_only_text,core,,a.einstein,Short name or login of the user.\n";
+        let provider = EcsProvider::new();
+
+        let pinned = ProviderParams::default().with("version", "8.11.0");
+        let schema = provider.resolve_schema(csv, &pinned).unwrap();
+        let types = provider.generate_types(&schema, "Ecs").unwrap();
+        assert_eq!(fields(record(&types, "User")), pairs(&[("name", "string option")]));
+
+        let other = ProviderParams::default().with("version", "8.12.0");
+        let err = provider.resolve_schema(csv, &other).unwrap_err();
+        assert!(err.to_string().contains("Expected ECS 8.12.0, but the source is ECS 8.11.0"));
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = EcsProvider::new();
+        let yaml = format!("{}process.pe.exports: {{flat_name: process.pe.exports, level: extended, type: flattened}}\nx.y: {{type: histogram}}\n", FLAT);
+        let report = provider.validate_source(&yaml, &ProviderParams::default());
+        assert!(report.is_valid());
+
+        let codes: Vec<(&str, Option<&str>)> =
+            report.diagnostics.iter().map(|d| (d.code.as_str(), d.location.as_deref())).collect();
+        assert!(codes.contains(&("unmapped-type", Some("x.y"))));
+        assert!(codes.contains(&("coerced-any", Some("ProcessPe.exports"))));
+        assert!(!codes.iter().any(|(code, _)| *code == "unresolved-reference"));
+    }
+
+    #[test]
+    fn test_fetch_release() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 4096];
+            let n = stream.read(&mut buf).unwrap();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                FLAT.len(),
+                FLAT
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+
+        let provider = EcsProvider::new();
+        let params = ProviderParams::default()
+            .with("endpoint", &format!("http://{}", addr))
+            .with("version", "8.11.0");
+        let schema = provider.resolve_schema("ecs://", &params).unwrap();
+        let types = provider.generate_types(&schema, "Ecs").unwrap();
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /v8.11.0/generated/ecs/ecs_flat.yml "));
+        assert_eq!(fields(record(&types, "User")), pairs(&[("name", "string option")]));
+    }
+}
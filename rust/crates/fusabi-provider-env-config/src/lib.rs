@@ -1,7 +1,13 @@
 //! Environment Configuration Type Provider
 //!
-//! Generates Fusabi types from .env file definitions.
+//! Generates Fusabi types from .env file definitions. The values in the
+//! file become field defaults through [`DefaultedProvider`].
 
+use fusabi_provider_common::source::is_location;
+use fusabi_provider_common::{
+    read_source, Capabilities, DefaultValue, DefaultedProvider, DescribedProvider, FieldDefaults,
+    OutputFeature, SourceValidator, TypeShape,
+};
 use fusabi_type_providers::{
     TypeProvider, ProviderParams, Schema,
     GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
@@ -39,13 +45,29 @@ impl EnvConfigProvider {
     fn infer_type(&self, value: &str) -> TypeExpr {
         // Try to infer type from value
         if value.parse::<i64>().is_ok() {
-            TypeExpr::Named("int".to_string())
+            TypeShape::named("int").into()
         } else if value.parse::<f64>().is_ok() {
-            TypeExpr::Named("float".to_string())
+            TypeShape::named("float").into()
+        } else if value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false") {
+            TypeShape::named("bool").into()
+        } else {
+            TypeShape::named("string").into()
+        }
+    }
+
+    /// Default value for a variable, matching the type [`Self::infer_type`] infers
+    fn infer_default(&self, value: &str) -> DefaultValue {
+        if let Ok(i) = value.parse::<i64>() {
+            DefaultValue::Int(i)
+        } else if let Ok(f) = value.parse::<f64>() {
+            DefaultValue::Float(f)
         } else if value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false") {
-            TypeExpr::Named("bool".to_string())
+            DefaultValue::Bool(value.eq_ignore_ascii_case("true"))
         } else {
-            TypeExpr::Named("string".to_string())
+            let unquoted = ['"', '\'']
+                .iter()
+                .find_map(|q| value.strip_prefix(*q).and_then(|v| v.strip_suffix(*q)));
+            DefaultValue::String(unquoted.unwrap_or(value).to_string())
         }
     }
 }
@@ -61,47 +83,109 @@ impl TypeProvider for EnvConfigProvider {
         "EnvConfigProvider"
     }
 
-    fn resolve_schema(&self, source: &str, _params: &ProviderParams) -> ProviderResult<Schema> {
-        let content = if source.starts_with("file://") {
-            let path = source.strip_prefix("file://").unwrap();
-            std::fs::read_to_string(path)
-                .map_err(|e| ProviderError::IoError(e.to_string()))?
-        } else if source.contains('=') {
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let content = if is_location(source) || !source.contains('=') {
+            read_source(source, params)?
+        } else {
             // Inline env content
             source.to_string()
-        } else {
-            // Treat as file path
-            std::fs::read_to_string(source)
-                .map_err(|e| ProviderError::IoError(e.to_string()))?
         };
 
         Ok(Schema::Custom(content))
     }
 
     fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        self.generate_with_defaults(schema, namespace).map(|(types, _)| types)
+    }
+}
+
+impl DefaultedProvider for EnvConfigProvider {
+    fn generate_with_defaults(
+        &self,
+        schema: &Schema,
+        namespace: &str,
+    ) -> ProviderResult<(GeneratedTypes, FieldDefaults)> {
         let content = match schema {
             Schema::Custom(s) => s,
             _ => return Err(ProviderError::ParseError("Expected env config".to_string())),
         };
 
+        let mut result = GeneratedTypes::new();
+        let mut defaults = FieldDefaults::new();
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+
         let vars = self.parse_env_file(content);
         let fields: Vec<(String, TypeExpr)> = vars
             .into_iter()
             .map(|(name, value)| {
                 let type_expr = self.infer_type(&value);
-                (self.generator.naming.apply(&name.to_lowercase()), type_expr)
+                let field_name = self.generator.naming.apply(&name.to_lowercase());
+                defaults.set(&module.path, "Config", &field_name, self.infer_default(&value));
+                (field_name, type_expr)
             })
             .collect();
 
-        let mut result = GeneratedTypes::new();
-        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
-
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "Config".to_string(),
             fields,
         }));
 
         result.modules.push(module);
-        Ok(result)
+        Ok((result, defaults))
+    }
+}
+
+impl SourceValidator for EnvConfigProvider {}
+
+impl DescribedProvider for EnvConfigProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .feature(OutputFeature::Defaults)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    #[test]
+    fn test_generate_config() {
+        let provider = EnvConfigProvider::new();
+        let env = "\
+# Service settings
+DATABASE_URL=postgres://db/app
+PORT=8080
+RATIO=0.5
+DEBUG=true
+";
+
+        let schema = provider.resolve_schema(env, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Env").unwrap();
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/env-config.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_inline_url_value() {
+        let provider = EnvConfigProvider::new();
+        let schema = provider
+            .resolve_schema("DATABASE_URL=postgres://db/app", &ProviderParams::default())
+            .unwrap();
+        let (types, defaults) = provider.generate_with_defaults(&schema, "Env").unwrap();
+
+        let TypeDefinition::Record(config) = &types.modules[0].types[0] else {
+            panic!("Expected record");
+        };
+        assert_eq!(config.fields[0].0, "DatabaseUrl");
+        assert_eq!(config.fields[0].1.to_string(), "string");
+        assert_eq!(
+            defaults.get(&types.modules[0].path, "Config", "DatabaseUrl"),
+            Some(&DefaultValue::String("postgres://db/app".to_string()))
+        );
     }
 }
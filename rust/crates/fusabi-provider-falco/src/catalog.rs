@@ -0,0 +1,238 @@
+//! Falco field catalogs
+//!
+//! Output fields are typed from a catalog of supported fields. The
+//! built-in catalog covers the syscall fields Falco's default rules print;
+//! plugin sources (`k8s_audit`, `aws_cloudtrail`, ...) bring their own,
+//! in the format plugins report from `get_fields`:
+//!
+//! ```json
+//! [{"name": "ct.user", "type": "string", "desc": "The user", "isList": false,
+//!   "arg": {"isRequired": false, "isKey": true}}]
+//! ```
+//!
+//! A plain `{field: type}` mapping, in YAML or JSON, is accepted too.
+//! Types are plugin types (`string`, `uint64`, `reltime`, `ipaddr`) or
+//! libsinsp ones (`CHARBUF`, `INT64`, `PID`), in any case.
+
+use std::collections::BTreeMap;
+
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+/// A catalog field: its type and whether it accepts an argument
+/// (`proc.aname[2]`, `evt.arg.flags`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldDef {
+    #[serde(rename = "type")]
+    pub field_type: String,
+    #[serde(default)]
+    pub is_list: bool,
+    #[serde(default)]
+    pub takes_arg: bool,
+}
+
+/// Fields by name
+pub type Catalog = BTreeMap<String, FieldDef>;
+
+/// Built-in syscall fields: name, libsinsp type, takes an argument
+const SYSCALL_FIELDS: &[(&str, &str, bool)] = &[
+    ("evt.num", "UINT64", false),
+    ("evt.time", "ABSTIME", false),
+    ("evt.time.s", "CHARBUF", false),
+    ("evt.time.iso8601", "CHARBUF", false),
+    ("evt.datetime", "CHARBUF", false),
+    ("evt.rawtime", "ABSTIME", false),
+    ("evt.type", "CHARBUF", false),
+    ("evt.dir", "CHARBUF", false),
+    ("evt.category", "CHARBUF", false),
+    ("evt.hostname", "CHARBUF", false),
+    ("evt.source", "CHARBUF", false),
+    ("evt.res", "CHARBUF", false),
+    ("evt.rawres", "INT64", false),
+    ("evt.failed", "BOOL", false),
+    ("evt.args", "CHARBUF", false),
+    ("evt.arg", "CHARBUF", true),
+    ("evt.rawarg", "CHARBUF", true),
+    ("evt.buffer", "BYTEBUF", false),
+    ("evt.is_open_read", "BOOL", false),
+    ("evt.is_open_write", "BOOL", false),
+    ("proc.pid", "INT64", false),
+    ("proc.exe", "CHARBUF", false),
+    ("proc.name", "CHARBUF", false),
+    ("proc.args", "CHARBUF", false),
+    ("proc.cmdline", "CHARBUF", false),
+    ("proc.exeline", "CHARBUF", false),
+    ("proc.exepath", "CHARBUF", false),
+    ("proc.cwd", "CHARBUF", false),
+    ("proc.tty", "UINT32", false),
+    ("proc.ppid", "INT64", false),
+    ("proc.pname", "CHARBUF", false),
+    ("proc.pcmdline", "CHARBUF", false),
+    ("proc.pexe", "CHARBUF", false),
+    ("proc.pexepath", "CHARBUF", false),
+    ("proc.apid", "INT64", true),
+    ("proc.aname", "CHARBUF", true),
+    ("proc.aexe", "CHARBUF", true),
+    ("proc.acmdline", "CHARBUF", true),
+    ("proc.sid", "INT64", false),
+    ("proc.vpgid", "INT64", false),
+    ("proc.loginshellid", "INT64", false),
+    ("proc.env", "CHARBUF", true),
+    ("proc.duration", "RELTIME", false),
+    ("proc.is_exe_writable", "BOOL", false),
+    ("proc.is_exe_upper_layer", "BOOL", false),
+    ("thread.tid", "INT64", false),
+    ("thread.vtid", "INT64", false),
+    ("user.uid", "UINT32", false),
+    ("user.name", "CHARBUF", false),
+    ("user.loginuid", "INT64", false),
+    ("user.loginname", "CHARBUF", false),
+    ("group.gid", "UINT32", false),
+    ("group.name", "CHARBUF", false),
+    ("container.id", "CHARBUF", false),
+    ("container.name", "CHARBUF", false),
+    ("container.type", "CHARBUF", false),
+    ("container.image", "CHARBUF", false),
+    ("container.image.id", "CHARBUF", false),
+    ("container.image.repository", "CHARBUF", false),
+    ("container.image.tag", "CHARBUF", false),
+    ("container.image.digest", "CHARBUF", false),
+    ("container.privileged", "BOOL", false),
+    ("k8s.ns.name", "CHARBUF", false),
+    ("k8s.pod.name", "CHARBUF", false),
+    ("k8s.pod.uid", "CHARBUF", false),
+    ("k8s.pod.label", "CHARBUF", true),
+    ("k8s.pod.labels", "CHARBUF", false),
+    ("fd.num", "INT64", false),
+    ("fd.type", "CHARBUF", false),
+    ("fd.typechar", "CHARBUF", false),
+    ("fd.name", "CHARBUF", false),
+    ("fd.nameraw", "CHARBUF", false),
+    ("fd.directory", "CHARBUF", false),
+    ("fd.filename", "CHARBUF", false),
+    ("fd.cip", "IPV4ADDR", false),
+    ("fd.sip", "IPV4ADDR", false),
+    ("fd.lip", "IPV4ADDR", false),
+    ("fd.rip", "IPV4ADDR", false),
+    ("fd.cport", "PORT", false),
+    ("fd.sport", "PORT", false),
+    ("fd.lport", "PORT", false),
+    ("fd.rport", "PORT", false),
+    ("fd.l4proto", "CHARBUF", false),
+    ("fd.sockfamily", "CHARBUF", false),
+    ("fd.connected", "BOOL", false),
+    ("fs.path.name", "CHARBUF", false),
+    ("fs.path.source", "CHARBUF", false),
+    ("fs.path.target", "CHARBUF", false),
+];
+
+/// The built-in syscall catalog
+pub fn syscall_catalog() -> Catalog {
+    SYSCALL_FIELDS
+        .iter()
+        .map(|(name, field_type, takes_arg)| {
+            let field = FieldDef {
+                field_type: field_type.to_string(),
+                is_list: false,
+                takes_arg: *takes_arg,
+            };
+            (name.to_string(), field)
+        })
+        .collect()
+}
+
+/// Parse a plugin field list or a `{field: type}` mapping
+pub fn parse_catalog(content: &str) -> ProviderResult<Catalog> {
+    let value: Value = serde_yaml::from_str(content)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid Falco field catalog: {}", e)))?;
+    let invalid = || ProviderError::ParseError("Expected a Falco field list or a mapping of field to type".to_string());
+    match value {
+        Value::Sequence(fields) => fields
+            .iter()
+            .map(|field| {
+                let name = field.get("name").and_then(Value::as_str).ok_or_else(invalid)?;
+                let field_type = field.get("type").and_then(Value::as_str).ok_or_else(invalid)?;
+                let def = FieldDef {
+                    field_type: field_type.to_string(),
+                    is_list: field.get("isList").and_then(Value::as_bool).unwrap_or(false),
+                    takes_arg: field.get("arg").is_some_and(Value::is_mapping),
+                };
+                Ok((name.to_string(), def))
+            })
+            .collect(),
+        Value::Mapping(fields) => fields
+            .iter()
+            .map(|(name, field_type)| {
+                let def = FieldDef {
+                    field_type: field_type.as_str().ok_or_else(invalid)?.to_string(),
+                    is_list: false,
+                    takes_arg: false,
+                };
+                Ok((name.as_str().ok_or_else(invalid)?.to_string(), def))
+            })
+            .collect(),
+        _ => Err(invalid()),
+    }
+}
+
+/// Find the catalog field an output field reads: `proc.aname[2]` reads
+/// `proc.aname`, and `evt.arg.flags` reads `evt.arg`
+pub fn lookup<'a>(catalog: &'a Catalog, field: &str) -> Option<&'a FieldDef> {
+    let base = field.split('[').next().unwrap_or(field);
+    catalog.get(base).or_else(|| {
+        catalog
+            .iter()
+            .filter(|(name, def)| def.takes_arg && base.strip_prefix(name.as_str()).is_some_and(|rest| rest.starts_with('.')))
+            .max_by_key(|(name, _)| name.len())
+            .map(|(_, def)| def)
+    })
+}
+
+/// The Fusabi type of a field type, or `None` if it is not known
+pub fn fusabi_type(field_type: &str) -> Option<&'static str> {
+    match field_type.to_ascii_lowercase().as_str() {
+        "string" | "charbuf" | "bytebuf" | "fspath" | "fsrelpath" | "ipaddr" | "ipv4addr" | "ipv6addr"
+        | "ipnet" | "ipv4net" | "ipv6net" => Some("string"),
+        "int8" | "int16" | "int32" => Some("int"),
+        "int64" | "pid" | "errno" | "fd" => Some("int64"),
+        "uint8" | "uint16" | "uint32" | "port" | "uid" | "gid" | "mode" => Some("uint"),
+        "uint64" | "abstime" | "reltime" => Some("uint64"),
+        "bool" => Some("bool"),
+        "double" => Some("float"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup() {
+        let catalog = syscall_catalog();
+        assert_eq!(lookup(&catalog, "proc.aname[2]").map(|f| f.field_type.as_str()), Some("CHARBUF"));
+        assert_eq!(lookup(&catalog, "evt.arg.flags").map(|f| f.field_type.as_str()), Some("CHARBUF"));
+        assert_eq!(lookup(&catalog, "fd.cport").map(|f| f.field_type.as_str()), Some("PORT"));
+        assert!(lookup(&catalog, "proc.name.extra").is_none());
+        assert!(lookup(&catalog, "ka.user.name").is_none());
+    }
+
+    #[test]
+    fn test_parse_catalog() {
+        let catalog = parse_catalog(
+            r#"[{"name": "ct.user", "type": "string", "desc": "The user"},
+                {"name": "ct.srcip", "type": "ipaddr"},
+                {"name": "ct.resources", "type": "string", "isList": true},
+                {"name": "ct.request", "type": "string", "arg": {"isRequired": true}}]"#,
+        )
+        .unwrap();
+        assert!(catalog["ct.resources"].is_list);
+        assert!(catalog["ct.request"].takes_arg);
+        assert_eq!(fusabi_type(&catalog["ct.srcip"].field_type), Some("string"));
+
+        let catalog = parse_catalog("ka.user.name: string\nka.response.code: uint64\n").unwrap();
+        assert_eq!(fusabi_type(&catalog["ka.response.code"].field_type), Some("uint64"));
+        assert_eq!(fusabi_type("FLAGS32"), None);
+    }
+}
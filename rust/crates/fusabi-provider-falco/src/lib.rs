@@ -0,0 +1,466 @@
+//! Falco Type Provider
+//!
+//! Generates typed alert records from Falco rules files, so consumers of
+//! Falco's JSON output read `output_fields` by type instead of parsing
+//! output strings by hand.
+//!
+//! # Sources
+//!
+//! A rules file (`falco_rules.yaml`), inline or from a file. Rules, macros
+//! and lists are merged the way Falco loads them; see [`rules`].
+//!
+//! # Generated Types
+//!
+//! For each enabled rule with an output, named after the rule
+//! (`Terminal shell in container` → `TerminalShellInContainer`):
+//!
+//! - `<Rule>Fields`: a field per `%field` in its output template, typed
+//!   from the field catalog (`%proc.aname[2]` → `proc_aname_2`). Every
+//!   field is `T option`: Falco writes `null` for fields without a value
+//!   on the event.
+//! - `<Rule>Alert`: the JSON alert, with `output_fields: <Rule>Fields`
+//!
+//! `FalcoAlert` is a union of every rule's alert, and `Priority` the rule
+//! priorities.
+//!
+//! # Params
+//!
+//! | Param    | Meaning                                                       |
+//! |----------|---------------------------------------------------------------|
+//! | `fields` | Field catalog for plugin sources, inline or a path; see [`catalog`] |
+//! | `rules`  | Comma-separated rule names to generate; a trailing `*` matches a prefix |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_falco::FalcoProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = FalcoProvider::new();
+//! let schema = provider.resolve_schema("/etc/falco/falco_rules.yaml", &ProviderParams::default())?;
+//! let types = provider.generate_types(&schema, "Falco")?;
+//! ```
+
+pub mod catalog;
+pub mod rules;
+
+pub use catalog::{parse_catalog, syscall_catalog, Catalog, FieldDef};
+pub use rules::{output_fields, parse_rules, RuleDef, RulesFile};
+
+use std::collections::HashSet;
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceValidator, TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, DuDef, VariantDef, TypeExpr, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use serde::{Deserialize, Serialize};
+
+/// Rule priorities, most severe first
+pub const PRIORITIES: &[&str] = &[
+    "Emergency", "Alert", "Critical", "Error", "Warning", "Notice", "Informational", "Debug",
+];
+
+/// Rules and the catalog to type them with, stored in [`Schema::Custom`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Resolved {
+    rules: RulesFile,
+    catalog: Catalog,
+}
+
+/// Falco type provider
+pub struct FalcoProvider {
+    generator: TypeGenerator,
+}
+
+impl FalcoProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<Resolved> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected Falco rules: {}", e)))
+    }
+
+    /// `Terminal shell in container` → `TerminalShellInContainer`
+    fn type_name(&self, rule: &str) -> String {
+        self.generator
+            .naming
+            .apply(rule)
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .collect()
+    }
+
+    fn generate(&self, resolved: &Resolved) -> (Vec<TypeDefinition>, Vec<Diagnostic>) {
+        let named = |name: &str| -> TypeExpr { TypeShape::named(name).into() };
+        let mut types = vec![TypeDefinition::Du(DuDef {
+            name: "Priority".to_string(),
+            variants: PRIORITIES.iter().map(|p| VariantDef::new_simple(p.to_string())).collect(),
+        })];
+        let mut diagnostics: Vec<Diagnostic> = resolved
+            .rules
+            .redefined
+            .iter()
+            .map(|item| {
+                Diagnostic::info("redefined", format!("The {} is defined again and replaces the earlier one", item))
+            })
+            .collect();
+
+        let mut variants = Vec::new();
+        let mut names = HashSet::new();
+        for rule in &resolved.rules.rules {
+            let location = rule.name.clone();
+            if !rule.enabled {
+                diagnostics.push(
+                    Diagnostic::info("rule-disabled", "The rule is disabled; no alert type is generated").at(location),
+                );
+                continue;
+            }
+            let Some(output) = &rule.output else {
+                diagnostics.push(
+                    Diagnostic::info("no-output", "The rule has no output here; no alert type is generated")
+                        .at(location),
+                );
+                continue;
+            };
+            if let Some(priority) = &rule.priority {
+                if !is_priority(priority) {
+                    diagnostics.push(
+                        Diagnostic::warning("unknown-priority", format!("Unknown priority `{}`", priority))
+                            .at(location.clone()),
+                    );
+                }
+            }
+            let base = self.type_name(&rule.name);
+            if !names.insert(base.clone()) {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        "duplicate-name",
+                        format!("Another rule is also named `{}` once converted; skipped", base),
+                    )
+                    .at(location),
+                );
+                continue;
+            }
+
+            let fields = output_fields(output)
+                .into_iter()
+                .map(|field| {
+                    let ty = self.field_type(&resolved.catalog, &field, &location, &mut diagnostics);
+                    (field_name(&field), ty.option().into())
+                })
+                .collect();
+            let fields_record = format!("{}Fields", base);
+            let alert_record = format!("{}Alert", base);
+            types.push(TypeDefinition::Record(RecordDef { name: fields_record.clone(), fields }));
+            types.push(TypeDefinition::Record(RecordDef {
+                name: alert_record.clone(),
+                fields: vec![
+                    ("hostname".to_string(), named("string")),
+                    ("output".to_string(), named("string")),
+                    ("output_fields".to_string(), named(&fields_record)),
+                    ("priority".to_string(), named("Priority")),
+                    ("rule".to_string(), named("string")),
+                    ("source".to_string(), named("string")),
+                    ("tags".to_string(), TypeShape::named("string").list().into()),
+                    ("time".to_string(), named("string")),
+                ],
+            }));
+            variants.push(VariantDef::new(base, vec![named(&alert_record)]));
+        }
+
+        if !variants.is_empty() {
+            types.push(TypeDefinition::Du(DuDef { name: "FalcoAlert".to_string(), variants }));
+        }
+        (types, diagnostics)
+    }
+
+    fn field_type(&self, catalog: &Catalog, field: &str, location: &str, diagnostics: &mut Vec<Diagnostic>) -> TypeShape {
+        let Some(def) = catalog::lookup(catalog, field) else {
+            diagnostics.push(
+                Diagnostic::warning(
+                    "unknown-field",
+                    format!("Output field `{}` is not in the field catalog; generated as `string`", field),
+                )
+                .at(location.to_string()),
+            );
+            return TypeShape::named("string");
+        };
+        let ty = catalog::fusabi_type(&def.field_type).unwrap_or_else(|| {
+            diagnostics.push(
+                Diagnostic::warning(
+                    "unmapped-type",
+                    format!("Field `{}` has unknown type `{}`; generated as `string`", field, def.field_type),
+                )
+                .at(location.to_string()),
+            );
+            "string"
+        });
+        let ty = TypeShape::named(ty);
+        if def.is_list { ty.list() } else { ty }
+    }
+}
+
+/// Falco accepts priorities in any case, and `info` for `Informational`
+fn is_priority(priority: &str) -> bool {
+    priority.eq_ignore_ascii_case("info") || PRIORITIES.iter().any(|p| p.eq_ignore_ascii_case(priority))
+}
+
+/// `proc.aname[2]` → `proc_aname_2`
+fn field_name(field: &str) -> String {
+    let name: String = field
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect();
+    name.split('_').filter(|part| !part.is_empty()).collect::<Vec<_>>().join("_")
+}
+
+fn matches_any(patterns: &[&str], name: &str) -> bool {
+    patterns.iter().any(|pattern| match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => name == *pattern,
+    })
+}
+
+impl Default for FalcoProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for FalcoProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("FalcoProvider")
+            .param(
+                ParamSpec::new("fields", ParamKind::String)
+                    .describe("Field catalog for plugin sources, inline or a path"),
+            )
+            .param(
+                ParamSpec::new("rules", ParamKind::List)
+                    .describe("Rule names to generate; a trailing `*` matches a prefix"),
+            )
+    }
+}
+
+impl TypeProvider for FalcoProvider {
+    fn name(&self) -> &str {
+        "FalcoProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let inline = |content: &str| content.trim_start().starts_with(['[', '{', '-']) || content.contains('\n');
+        let mut rules = if inline(source) {
+            parse_rules(source)?
+        } else {
+            parse_rules(&read_source(source, params)?)?
+        };
+
+        let mut catalog = syscall_catalog();
+        if let Some(fields) = typed.str("fields") {
+            catalog.extend(if inline(fields) {
+                parse_catalog(fields)?
+            } else {
+                parse_catalog(&read_source(fields, params)?)?
+            });
+        }
+
+        if let Some(patterns) = typed.list("rules") {
+            rules.rules.retain(|rule| matches_any(&patterns, &rule.name));
+        }
+
+        let json = serde_json::to_string(&Resolved { rules, catalog })
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected Falco rules".to_string()));
+        };
+
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+        module.types = self.generate(&self.parse_schema(json)?).0;
+
+        let mut result = GeneratedTypes::new();
+        result.modules.push(module);
+        Ok(result)
+    }
+}
+
+impl SourceValidator for FalcoProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected Falco rules".to_string()));
+        };
+
+        Ok(self.generate(&self.parse_schema(json)?).1)
+    }
+}
+
+impl DescribedProvider for FalcoProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    const RULES: &str = r#"
+- macro: spawned_process
+  condition: evt.type in (execve, execveat)
+- rule: Terminal shell in container
+  desc: A shell was spawned in a container
+  condition: spawned_process and container.id != host and proc.name = bash
+  output: >
+    Shell spawned (user=%user.name uid=%user.uid container=%container.id
+    ancestor=%proc.aname[2] port=%fd.sport flags=%evt.arg.flags)
+  priority: NOTICE
+  tags: [container, shell]
+- rule: CloudTrail console login without MFA
+  condition: ct.name = ConsoleLogin and not ct.additionaleventdata.mfaused = "Yes"
+  output: Login without MFA (user=%ct.user ip=%ct.srcip regions=%ct.regions extra=%ct.unknown)
+  priority: CRITICAL
+  source: aws_cloudtrail
+- rule: Read sensitive file
+  condition: open_read
+  output: File opened (file=%fd.name)
+  priority: SEVERE
+- rule: Disabled rule
+  condition: evt.type = open
+  output: Opened (file=%fd.name)
+  priority: DEBUG
+  enabled: false
+"#;
+
+    const CLOUDTRAIL_FIELDS: &str = r#"[
+        {"name": "ct.user", "type": "string"},
+        {"name": "ct.srcip", "type": "ipaddr"},
+        {"name": "ct.regions", "type": "string", "isList": true}
+    ]"#;
+
+    fn fields(types: &GeneratedTypes, name: &str) -> Vec<(String, String)> {
+        types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+            .fields
+            .iter()
+            .map(|(field, ty)| (field.clone(), ty.to_string()))
+            .collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(f, t)| (f.to_string(), t.to_string())).collect()
+    }
+
+    #[test]
+    fn test_alert_types() {
+        let provider = FalcoProvider::new();
+        let params = ProviderParams::default().with("fields", CLOUDTRAIL_FIELDS);
+        let schema = provider.resolve_schema(RULES, &params).unwrap();
+        let types = provider.generate_types(&schema, "Falco").unwrap();
+
+        assert_eq!(
+            fields(&types, "TerminalShellInContainerFields"),
+            pairs(&[
+                ("user_name", "string option"),
+                ("user_uid", "uint option"),
+                ("container_id", "string option"),
+                ("proc_aname_2", "string option"),
+                ("fd_sport", "uint option"),
+                ("evt_arg_flags", "string option"),
+            ])
+        );
+        assert_eq!(
+            fields(&types, "CloudTrailConsoleLoginWithoutMfaFields"),
+            pairs(&[
+                ("ct_user", "string option"),
+                ("ct_srcip", "string option"),
+                ("ct_regions", "string list option"),
+                ("ct_unknown", "string option"),
+            ])
+        );
+        assert_eq!(
+            fields(&types, "ReadSensitiveFileAlert")[2],
+            ("output_fields".to_string(), "ReadSensitiveFileFields".to_string())
+        );
+
+        let alert = types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Du(du) if du.name == "FalcoAlert" => Some(du),
+                _ => None,
+            })
+            .unwrap();
+        let variants: Vec<&str> = alert.variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(variants, ["TerminalShellInContainer", "CloudTrailConsoleLoginWithoutMfa", "ReadSensitiveFile"]);
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/falco.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let provider = FalcoProvider::new();
+        let params = ProviderParams::default().with("fields", CLOUDTRAIL_FIELDS);
+        let schema = provider.resolve_schema(RULES, &params).unwrap();
+        let diagnostics = provider.schema_diagnostics(&schema).unwrap();
+        let found: Vec<(&str, &str)> = diagnostics
+            .iter()
+            .map(|d| (d.code.as_str(), d.location.as_deref().unwrap_or("")))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("unknown-field", "CloudTrail console login without MFA"),
+                ("unknown-priority", "Read sensitive file"),
+                ("rule-disabled", "Disabled rule"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rules_filter_and_file_source() {
+        let path = std::env::temp_dir().join(format!("fusabi-falco-{}.yaml", std::process::id()));
+        std::fs::write(&path, RULES).unwrap();
+
+        let provider = FalcoProvider::new();
+        let params = ProviderParams::default().with("rules", "Terminal*, Read sensitive file");
+        let schema = provider.resolve_schema(path.to_str().unwrap(), &params).unwrap();
+        let types = provider.generate_types(&schema, "Falco").unwrap();
+        let alerts: Vec<&str> = types.modules[0]
+            .types
+            .iter()
+            .filter_map(|t| match t {
+                TypeDefinition::Record(r) if r.name.ends_with("Alert") => Some(r.name.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(alerts, ["TerminalShellInContainerAlert", "ReadSensitiveFileAlert"]);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
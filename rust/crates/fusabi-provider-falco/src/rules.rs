@@ -0,0 +1,249 @@
+//! Falco rules files
+//!
+//! A rules file is a YAML list of `rule`, `macro` and `list` items, plus
+//! version requirements. Later items with the same name change earlier
+//! ones the way Falco applies them: `append: true` or an `override`
+//! mapping merges into the existing item, a bare `enabled` toggles it, and
+//! anything else replaces it.
+
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+
+/// Rule source when a rule names none
+pub const DEFAULT_SOURCE: &str = "syscall";
+
+/// Rules, macros and lists, after merging
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RulesFile {
+    pub rules: Vec<RuleDef>,
+    pub macros: Vec<String>,
+    pub lists: Vec<ListDef>,
+    /// Items defined again without `append` or `override`: `macro `x``
+    pub redefined: Vec<String>,
+}
+
+/// A rule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleDef {
+    pub name: String,
+    /// The output template, if any item gave one
+    pub output: Option<String>,
+    pub priority: Option<String>,
+    pub source: String,
+    pub tags: Vec<String>,
+    pub enabled: bool,
+}
+
+/// A list and its items
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ListDef {
+    pub name: String,
+    pub items: Vec<String>,
+}
+
+/// Parse a rules file
+pub fn parse_rules(content: &str) -> ProviderResult<RulesFile> {
+    let value: Value = serde_yaml::from_str(content)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid Falco rules file: {}", e)))?;
+    let items = match value {
+        Value::Sequence(items) => items,
+        Value::Null => Vec::new(),
+        _ => return Err(ProviderError::ParseError("Expected a Falco rules file: a list of items".to_string())),
+    };
+
+    let mut file = RulesFile::default();
+    for item in &items {
+        let Value::Mapping(item) = item else {
+            return Err(ProviderError::ParseError("Falco rules file items must be mappings".to_string()));
+        };
+        if let Some(name) = item.get("rule") {
+            rule(&mut file, string(name)?, item);
+        } else if let Some(name) = item.get("macro") {
+            let name = string(name)?;
+            if file.macros.contains(&name) {
+                if !merges(item) {
+                    file.redefined.push(format!("macro `{}`", name));
+                }
+            } else {
+                file.macros.push(name);
+            }
+        } else if let Some(name) = item.get("list") {
+            let name = string(name)?;
+            let items = strings(item.get("items"));
+            match file.lists.iter_mut().find(|list| list.name == name) {
+                Some(list) if merges(item) => list.items.extend(items),
+                Some(list) => {
+                    file.redefined.push(format!("list `{}`", name));
+                    list.items = items;
+                }
+                None => file.lists.push(ListDef { name, items }),
+            }
+        }
+    }
+    Ok(file)
+}
+
+fn rule(file: &mut RulesFile, name: String, item: &Mapping) {
+    let output = item.get("output").and_then(Value::as_str).map(|output| output.trim().to_string());
+    let priority = item.get("priority").and_then(Value::as_str).map(str::to_string);
+    let tags = strings(item.get("tags"));
+    let enabled = item.get("enabled").and_then(Value::as_bool);
+
+    let Some(existing) = file.rules.iter_mut().find(|rule| rule.name == name) else {
+        file.rules.push(RuleDef {
+            name,
+            output,
+            priority,
+            source: item.get("source").and_then(Value::as_str).unwrap_or(DEFAULT_SOURCE).to_string(),
+            tags,
+            enabled: enabled.unwrap_or(true),
+        });
+        return;
+    };
+
+    let overrides = item.get("override").and_then(Value::as_mapping);
+    let appends = |key: &str| match overrides {
+        Some(overrides) => overrides.get(key).and_then(Value::as_str) == Some("append"),
+        None => item.get("append").and_then(Value::as_bool).unwrap_or(false),
+    };
+    let is_toggle = item.len() == 2 && enabled.is_some();
+    if !merges(item) && !is_toggle {
+        file.redefined.push(format!("rule `{}`", name));
+    }
+
+    if let Some(output) = output {
+        existing.output = match (&existing.output, appends("output")) {
+            (Some(existing), true) => Some(format!("{} {}", existing, output)),
+            _ => Some(output),
+        };
+    }
+    if priority.is_some() {
+        existing.priority = priority;
+    }
+    if appends("tags") {
+        existing.tags.extend(tags);
+    } else if item.contains_key("tags") {
+        existing.tags = tags;
+    }
+    if let Some(enabled) = enabled {
+        existing.enabled = enabled;
+    }
+}
+
+/// Whether an item merges into an earlier one of the same name
+fn merges(item: &Mapping) -> bool {
+    item.get("append").and_then(Value::as_bool).unwrap_or(false) || item.contains_key("override")
+}
+
+fn string(value: &Value) -> ProviderResult<String> {
+    value
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| ProviderError::ParseError(format!("Item names must be strings, got {:?}", value)))
+}
+
+fn strings(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::Sequence(values)) => values
+            .iter()
+            .filter_map(|value| match value {
+                Value::String(s) => Some(s.clone()),
+                Value::Number(n) => Some(n.to_string()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The fields an output template prints, in order and without repeats:
+/// `%proc.name`, `%proc.aname[2]`, `%jevt.value[/user/username]`
+pub fn output_fields(output: &str) -> Vec<String> {
+    let mut fields: Vec<String> = Vec::new();
+    let mut rest = output;
+    while let Some(start) = rest.find('%') {
+        rest = &rest[start + 1..];
+        let end = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '.' || c == '_'))
+            .unwrap_or(rest.len());
+        let mut field = rest[..end].to_string();
+        rest = &rest[end..];
+        if rest.starts_with('[') {
+            if let Some(close) = rest.find(']') {
+                field.push_str(&rest[..=close]);
+                rest = &rest[close + 1..];
+            }
+        }
+        if field.contains('.') && !fields.contains(&field) {
+            fields.push(field);
+        }
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rules() {
+        let file = parse_rules(
+            r#"
+- required_engine_version: 0.31.0
+- list: shell_binaries
+  items: [bash, sh]
+- list: shell_binaries
+  items: [zsh]
+  append: true
+- macro: spawned_process
+  condition: evt.type in (execve, execveat)
+- rule: Terminal shell in container
+  desc: A shell was spawned in a container
+  condition: spawned_process and proc.name in (shell_binaries)
+  output: Shell spawned (user=%user.name container=%container.id)
+  priority: NOTICE
+  tags: [container, shell]
+- rule: Terminal shell in container
+  output: parent=%proc.pname
+  override:
+    output: append
+- rule: Read sensitive file
+  condition: open_read
+  output: File opened (file=%fd.name)
+  priority: WARNING
+  source: syscall
+- rule: Read sensitive file
+  enabled: false
+- macro: spawned_process
+  condition: evt.type = execve
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(file.lists[0].items, ["bash", "sh", "zsh"]);
+        assert_eq!(file.macros, ["spawned_process"]);
+        assert_eq!(file.redefined, ["macro `spawned_process`"]);
+        assert_eq!(
+            file.rules[0].output.as_deref(),
+            Some("Shell spawned (user=%user.name container=%container.id) parent=%proc.pname")
+        );
+        assert_eq!(file.rules[0].tags, ["container", "shell"]);
+        assert!(!file.rules[1].enabled);
+        assert_eq!(file.rules[1].source, "syscall");
+
+        assert!(parse_rules("rule: x").is_err());
+    }
+
+    #[test]
+    fn test_output_fields() {
+        assert_eq!(
+            output_fields("Shell (user=%user.name pid=%proc.pid anc=%proc.aname[2] again=%user.name 100%)"),
+            ["user.name", "proc.pid", "proc.aname[2]"]
+        );
+        assert_eq!(
+            output_fields("%evt.time: user=%jevt.value[/user/username] flags=%evt.arg.flags"),
+            ["evt.time", "jevt.value[/user/username]", "evt.arg.flags"]
+        );
+    }
+}
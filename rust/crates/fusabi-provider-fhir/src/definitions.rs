@@ -0,0 +1,291 @@
+//! FHIR conformance resources
+//!
+//! Reads the resources that describe FHIR types from JSON: a single
+//! resource, a `Bundle` of them (as in the specification's
+//! `profiles-resources.json` and `valuesets.json`), or a list.
+//!
+//! - `StructureDefinition`: a resource, datatype, profile or logical model,
+//!   and its elements. The snapshot is used when present; sliced elements
+//!   (`Patient.identifier:mrn`) and elements a profile removes (`max: 0`)
+//!   are dropped.
+//! - `ValueSet` and `CodeSystem`: the codes a `code` element can take when
+//!   it has a required binding.
+//!
+//! Other resources are ignored.
+
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Structure definitions and terminology, in document order
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Definitions {
+    pub structures: Vec<StructureDef>,
+    pub value_sets: Vec<ValueSetDef>,
+    pub code_systems: Vec<CodeSystemDef>,
+}
+
+/// A `StructureDefinition`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructureDef {
+    pub url: String,
+    pub name: String,
+    /// The type it defines or constrains: `Patient` for a patient profile
+    pub type_name: String,
+    /// `resource`, `complex-type`, `primitive-type` or `logical`
+    pub kind: String,
+    pub is_abstract: bool,
+    /// Whether `elements` come from the snapshot, or only the differential
+    pub snapshot: bool,
+    pub elements: Vec<ElementDef>,
+}
+
+/// An element of a structure definition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ElementDef {
+    /// `Patient.contact.name`, `Observation.value[x]`
+    pub path: String,
+    pub min: u32,
+    /// A number or `*`
+    pub max: String,
+    /// Type codes: `string`, `HumanName`, `http://hl7.org/fhirpath/System.String`
+    pub types: Vec<String>,
+    /// `#Questionnaire.item` for elements that repeat another element's
+    /// definition
+    pub content_reference: Option<String>,
+    /// The value set canonical of a required binding, without a version
+    pub required_binding: Option<String>,
+}
+
+impl ElementDef {
+    /// Whether the element repeats
+    pub fn is_list(&self) -> bool {
+        self.max == "*" || self.max.parse::<u32>().is_ok_and(|max| max > 1)
+    }
+}
+
+/// A `ValueSet`: the codes it lists and the code systems it includes
+/// whole
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValueSetDef {
+    pub url: String,
+    pub name: String,
+    pub codes: Vec<String>,
+    pub systems: Vec<String>,
+    /// False when the set is defined by filters or other value sets, whose
+    /// codes are not read
+    pub complete: bool,
+}
+
+/// A `CodeSystem` and its codes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CodeSystemDef {
+    pub url: String,
+    pub codes: Vec<String>,
+}
+
+/// Parse a resource, a `Bundle` or a list of resources
+pub fn parse_definitions(content: &str) -> ProviderResult<Definitions> {
+    let document: Value = serde_json::from_str(content)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid FHIR JSON: {}", e)))?;
+    let mut definitions = Definitions::default();
+    definitions.add(&document)?;
+    Ok(definitions)
+}
+
+impl Definitions {
+    /// Add the definitions of another document
+    pub fn extend(&mut self, other: Definitions) {
+        self.structures.extend(other.structures);
+        self.value_sets.extend(other.value_sets);
+        self.code_systems.extend(other.code_systems);
+    }
+
+    /// The codes of a value set, or `None` if it is not loaded or its
+    /// codes can't be listed
+    pub fn codes(&self, url: &str) -> Option<Vec<String>> {
+        let value_set = self.value_sets.iter().find(|vs| vs.url == url)?;
+        if !value_set.complete {
+            return None;
+        }
+        let mut codes = value_set.codes.clone();
+        for system in &value_set.systems {
+            let code_system = self.code_systems.iter().find(|cs| cs.url == *system)?;
+            codes.extend(code_system.codes.iter().cloned());
+        }
+        Some(codes)
+    }
+
+    fn add(&mut self, resource: &Value) -> ProviderResult<()> {
+        if let Value::Array(resources) = resource {
+            return resources.iter().try_for_each(|resource| self.add(resource));
+        }
+        match resource.get("resourceType").and_then(Value::as_str) {
+            Some("Bundle") => {
+                let entries = resource.get("entry").and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default();
+                for entry in entries {
+                    if let Some(resource) = entry.get("resource") {
+                        self.add(resource)?;
+                    }
+                }
+            }
+            Some("StructureDefinition") => self.structures.push(structure(resource)?),
+            Some("ValueSet") => self.value_sets.push(value_set(resource)),
+            Some("CodeSystem") => self.code_systems.push(CodeSystemDef {
+                url: string(resource, "url"),
+                codes: concept_codes(resource.get("concept")),
+            }),
+            Some(_) => {}
+            None => {
+                return Err(ProviderError::ParseError(
+                    "Expected a FHIR resource, Bundle or list of resources: no `resourceType`".to_string(),
+                ))
+            }
+        }
+        Ok(())
+    }
+}
+
+fn string(value: &Value, key: &str) -> String {
+    value.get(key).and_then(Value::as_str).unwrap_or_default().to_string()
+}
+
+fn structure(resource: &Value) -> ProviderResult<StructureDef> {
+    let name = string(resource, "name");
+    let (snapshot, elements) = match resource.pointer("/snapshot/element").and_then(Value::as_array) {
+        Some(elements) => (true, elements),
+        None => match resource.pointer("/differential/element").and_then(Value::as_array) {
+            Some(elements) => (false, elements),
+            None => {
+                return Err(ProviderError::ParseError(format!(
+                    "StructureDefinition `{}` has neither a snapshot nor a differential",
+                    name
+                )))
+            }
+        },
+    };
+
+    let elements = elements
+        .iter()
+        .filter(|element| {
+            let sliced = element.get("sliceName").is_some()
+                || element.get("id").and_then(Value::as_str).is_some_and(|id| id.contains(':'));
+            !sliced && element.get("max").and_then(Value::as_str) != Some("0")
+        })
+        .map(|element| ElementDef {
+            path: string(element, "path"),
+            min: element.get("min").and_then(Value::as_u64).unwrap_or(0) as u32,
+            max: element.get("max").and_then(Value::as_str).unwrap_or("1").to_string(),
+            types: element
+                .get("type")
+                .and_then(Value::as_array)
+                .map(|types| types.iter().filter_map(|t| t.get("code").and_then(Value::as_str)).map(str::to_string).collect())
+                .unwrap_or_default(),
+            content_reference: element.get("contentReference").and_then(Value::as_str).map(str::to_string),
+            required_binding: element
+                .get("binding")
+                .filter(|binding| binding.get("strength").and_then(Value::as_str) == Some("required"))
+                .and_then(|binding| binding.get("valueSet").and_then(Value::as_str))
+                .map(|url| url.split('|').next().unwrap_or(url).to_string()),
+        })
+        .collect();
+
+    Ok(StructureDef {
+        url: string(resource, "url"),
+        type_name: string(resource, "type"),
+        kind: string(resource, "kind"),
+        is_abstract: resource.get("abstract").and_then(Value::as_bool).unwrap_or(false),
+        name,
+        snapshot,
+        elements,
+    })
+}
+
+fn value_set(resource: &Value) -> ValueSetDef {
+    let mut value_set = ValueSetDef {
+        url: string(resource, "url"),
+        name: string(resource, "name"),
+        codes: Vec::new(),
+        systems: Vec::new(),
+        complete: true,
+    };
+    // An expansion lists every code
+    if let Some(contains) = resource.pointer("/expansion/contains") {
+        value_set.codes = concept_codes(Some(contains));
+        return value_set;
+    }
+    let includes = resource.pointer("/compose/include").and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default();
+    for include in includes {
+        if include.get("filter").is_some() || include.get("valueSet").is_some() {
+            value_set.complete = false;
+        } else if let Some(concepts) = include.get("concept") {
+            value_set.codes.extend(concept_codes(Some(concepts)));
+        } else if let Some(system) = include.get("system").and_then(Value::as_str) {
+            value_set.systems.push(system.to_string());
+        }
+    }
+    if resource.pointer("/compose/exclude").is_some() {
+        value_set.complete = false;
+    }
+    value_set
+}
+
+/// Codes of a concept list, including nested concepts
+fn concept_codes(concepts: Option<&Value>) -> Vec<String> {
+    let mut codes = Vec::new();
+    for concept in concepts.and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default() {
+        if let Some(code) = concept.get("code").and_then(Value::as_str) {
+            codes.push(code.to_string());
+        }
+        codes.extend(concept_codes(concept.get("concept").or_else(|| concept.get("contains"))));
+    }
+    codes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bundle() {
+        let definitions = parse_definitions(
+            r#"{"resourceType": "Bundle", "entry": [
+                {"resource": {"resourceType": "StructureDefinition", "url": "http://example.org/Thing",
+                  "name": "Thing", "type": "Thing", "kind": "logical", "abstract": false,
+                  "differential": {"element": [
+                    {"id": "Thing", "path": "Thing"},
+                    {"id": "Thing.status", "path": "Thing.status", "min": 1, "max": "1", "type": [{"code": "code"}],
+                     "binding": {"strength": "required", "valueSet": "http://example.org/vs/status|1.0"}},
+                    {"id": "Thing.tag", "path": "Thing.tag", "min": 0, "max": "*", "type": [{"code": "string"}]},
+                    {"id": "Thing.tag:first", "path": "Thing.tag", "sliceName": "first", "max": "1"},
+                    {"id": "Thing.legacy", "path": "Thing.legacy", "max": "0"}
+                  ]}}},
+                {"resource": {"resourceType": "ValueSet", "url": "http://example.org/vs/status", "name": "Status",
+                  "compose": {"include": [{"system": "http://example.org/cs/status"},
+                                          {"system": "http://example.org/cs/other", "concept": [{"code": "retired"}]}]}}},
+                {"resource": {"resourceType": "CodeSystem", "url": "http://example.org/cs/status",
+                  "concept": [{"code": "active", "concept": [{"code": "suspended"}]}, {"code": "inactive"}]}},
+                {"resource": {"resourceType": "ValueSet", "url": "http://example.org/vs/filtered", "name": "Filtered",
+                  "compose": {"include": [{"system": "http://loinc.org", "filter": [{"property": "class"}]}]}}},
+                {"resource": {"resourceType": "Patient", "id": "example"}}
+            ]}"#,
+        )
+        .unwrap();
+
+        let thing = &definitions.structures[0];
+        assert!(!thing.snapshot);
+        let paths: Vec<&str> = thing.elements.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, ["Thing", "Thing.status", "Thing.tag"]);
+        assert_eq!(thing.elements[1].required_binding.as_deref(), Some("http://example.org/vs/status"));
+        assert!(thing.elements[2].is_list());
+
+        assert_eq!(
+            definitions.codes("http://example.org/vs/status").unwrap(),
+            ["retired", "active", "suspended", "inactive"]
+        );
+        assert_eq!(definitions.codes("http://example.org/vs/filtered"), None);
+        assert_eq!(definitions.codes("http://example.org/vs/missing"), None);
+
+        assert!(parse_definitions(r#"{"name": "x"}"#).is_err());
+    }
+}
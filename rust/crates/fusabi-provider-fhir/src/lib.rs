@@ -0,0 +1,554 @@
+//! FHIR Type Provider
+//!
+//! Generates Fusabi types for FHIR resources and datatypes from their
+//! `StructureDefinition`s, so healthcare integrations work with typed
+//! records instead of raw JSON. Base resources and profiles are handled
+//! alike: a profile is generated from its own snapshot, under its own name.
+//!
+//! # Sources
+//!
+//! FHIR JSON, inline, from a file or from a directory of `.json` files:
+//! a `StructureDefinition`, a `Bundle` of them such as the specification's
+//! `profiles-resources.json`, or a list. See [`definitions`].
+//!
+//! # Generated Types
+//!
+//! For each structure definition (primitive types excepted):
+//!
+//! - A record named after it (`Patient`, `USCorePatientProfile`) with a
+//!   field per element, under its JSON name. Resources start with a
+//!   `resourceType: string` field.
+//! - A record per backbone element, named after its path
+//!   (`Patient.contact` → `PatientContact`)
+//! - A union per choice element with more than one type
+//!   (`Observation.value[x]` → `ObservationValue`, a variant per type); the
+//!   field is named without the `[x]`
+//!
+//! Cardinality decides the wrapper: `max` above 1 gives `T list`, and
+//! `min: 0` gives `T option`. FHIR primitives map to Fusabi primitives
+//! (`boolean` → `bool`, `decimal` → `float`, `dateTime` → `string`).
+//!
+//! `code` elements with a required binding to a value set whose codes are
+//! known (listed, expanded, or from an included `CodeSystem`) become an
+//! enum named after the value set (`AdministrativeGender`); other codes
+//! stay `string`.
+//!
+//! # Params
+//!
+//! | Param       | Meaning                                                        |
+//! |-------------|----------------------------------------------------------------|
+//! | `resources` | Comma-separated definition names or types to generate; a trailing `*` matches a prefix |
+//! | `valuesets` | Further FHIR JSON to read value sets and code systems from, inline or a path (`valuesets.json`) |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_fhir::FhirProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = FhirProvider::new();
+//! let params = ProviderParams::default()
+//!     .with("resources", "Patient, Observation")
+//!     .with("valuesets", "fhir/valuesets.json");
+//! let schema = provider.resolve_schema("fhir/profiles-resources.json", &params)?;
+//! let types = provider.generate_types(&schema, "Fhir")?;
+//! ```
+
+pub mod definitions;
+
+pub use definitions::{parse_definitions, CodeSystemDef, Definitions, ElementDef, StructureDef, ValueSetDef};
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceKind, SourceValidator, StructuredError, TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, DuDef, VariantDef, TypeExpr, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+
+const FHIRPATH_SYSTEM: &str = "http://hl7.org/fhirpath/System.";
+
+/// FHIR type provider
+pub struct FhirProvider {
+    generator: TypeGenerator,
+}
+
+/// Types and diagnostics built up across structure definitions
+#[derive(Default)]
+struct Output {
+    enums: Vec<TypeDefinition>,
+    types: Vec<TypeDefinition>,
+    diagnostics: Vec<Diagnostic>,
+    /// Enum name per value set, or `None` if it stays `string`
+    value_sets: HashMap<String, Option<String>>,
+}
+
+impl FhirProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<Definitions> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected FHIR definitions: {}", e)))
+    }
+
+    fn read_dir(&self, dir: &Path, params: &ProviderParams) -> ProviderResult<Definitions> {
+        let mut paths: Vec<_> = std::fs::read_dir(dir)
+            .map_err(|e| StructuredError::io(format!("Failed to read {}", dir.display())).with_source(e))?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        paths.sort();
+
+        let mut definitions = Definitions::default();
+        for path in paths {
+            definitions.extend(parse_definitions(&read_source(&path.to_string_lossy(), params)?)?);
+        }
+        Ok(definitions)
+    }
+
+    fn generate(&self, definitions: &Definitions) -> (Vec<TypeDefinition>, Vec<Diagnostic>) {
+        let mut output = Output::default();
+        let mut names = HashSet::new();
+        for structure in definitions.structures.iter().filter(|s| s.kind != "primitive-type") {
+            let root = type_name(if structure.name.is_empty() { &structure.type_name } else { &structure.name });
+            if !names.insert(root.clone()) {
+                output.diagnostics.push(
+                    Diagnostic::warning("duplicate-definition", format!("`{}` is defined more than once; skipped", root))
+                        .at(structure.url.clone()),
+                );
+                continue;
+            }
+            if !structure.snapshot {
+                output.diagnostics.push(
+                    Diagnostic::warning(
+                        "no-snapshot",
+                        "Only a differential is given; the types cover only the elements it lists",
+                    )
+                    .at(structure.name.clone()),
+                );
+            }
+            self.structure(definitions, structure, &root, &mut output);
+        }
+
+        let mut types = output.enums;
+        types.extend(output.types);
+        (types, output.diagnostics)
+    }
+
+    fn structure(&self, definitions: &Definitions, structure: &StructureDef, root: &str, output: &mut Output) {
+        let Some(root_path) = structure.elements.first().map(|e| e.path.as_str()).filter(|p| !p.contains('.')) else {
+            return;
+        };
+        let record_name = |path: &str| {
+            let mut segments = path.split('.');
+            let first = segments.next().unwrap_or_default();
+            let mut name = if first == root_path { root.to_string() } else { type_name(first) };
+            name.extend(segments.map(|segment| type_name(segment.trim_end_matches("[x]"))));
+            name
+        };
+        let has_children = |path: &str| {
+            let prefix = format!("{}.", path);
+            structure.elements.iter().any(|e| e.path.starts_with(&prefix))
+        };
+
+        let records = std::iter::once(root_path)
+            .chain(structure.elements.iter().map(|e| e.path.as_str()).filter(|p| p.contains('.') && has_children(p)));
+        for record in records {
+            let mut fields = Vec::new();
+            if record == root_path && structure.kind == "resource" && !structure.is_abstract {
+                fields.push(("resourceType".to_string(), TypeShape::named("string").into()));
+            }
+            for element in &structure.elements {
+                let Some((parent, segment)) = element.path.rsplit_once('.') else {
+                    continue;
+                };
+                if parent != record {
+                    continue;
+                }
+                let ty = if let Some(reference) = &element.content_reference {
+                    record_name(reference.rsplit('#').next().unwrap_or(reference))
+                } else if has_children(&element.path) {
+                    record_name(&element.path)
+                } else if element.types.len() > 1 {
+                    let name = record_name(&element.path);
+                    let variants = element
+                        .types
+                        .iter()
+                        .map(|code| {
+                            let ty = self.fhir_type(code, &element.path, output);
+                            VariantDef::new(type_name(code), vec![TypeShape::named(ty).into()])
+                        })
+                        .collect();
+                    output.types.push(TypeDefinition::Du(DuDef { name: name.clone(), variants }));
+                    name
+                } else if let Some(url) = element.required_binding.as_ref().filter(|_| element.types == ["code"]) {
+                    self.value_set(definitions, url, &element.path, output)
+                } else if let Some(code) = element.types.first() {
+                    self.fhir_type(code, &element.path, output)
+                } else {
+                    output.diagnostics.push(
+                        Diagnostic::warning("untyped-element", "The element has no type; generated as `string`")
+                            .at(element.path.clone()),
+                    );
+                    "string".to_string()
+                };
+
+                let ty = TypeShape::named(ty);
+                let ty = if element.is_list() { ty.list() } else { ty };
+                let ty = ty.option_unless(element.min != 0);
+                fields.push((segment.trim_end_matches("[x]").to_string(), ty.into()));
+            }
+            output.types.push(TypeDefinition::Record(RecordDef { name: record_name(record), fields }));
+        }
+    }
+
+    /// The enum for a required value set, or `string` if its codes aren't
+    /// known or can't be variant names
+    fn value_set(&self, definitions: &Definitions, url: &str, location: &str, output: &mut Output) -> String {
+        if let Some(name) = output.value_sets.get(url) {
+            return name.clone().unwrap_or_else(|| "string".to_string());
+        }
+
+        let name = match definitions.codes(url) {
+            None => {
+                output.diagnostics.push(
+                    Diagnostic::info(
+                        "unresolved-valueset",
+                        format!("The codes of required value set `{}` are not known; generated as `string`", url),
+                    )
+                    .at(location.to_string()),
+                );
+                None
+            }
+            Some(codes) => {
+                let variants: Vec<String> = codes.iter().map(|code| type_name(&self.generator.naming.apply(code))).collect();
+                let unique: HashSet<&String> = variants.iter().collect();
+                let valid = variants.iter().all(|v| v.starts_with(|c: char| c.is_ascii_alphabetic()));
+                if valid && unique.len() == variants.len() && !variants.is_empty() {
+                    let value_set = definitions.value_sets.iter().find(|vs| vs.url == url);
+                    let name = type_name(value_set.map(|vs| vs.name.as_str()).filter(|n| !n.is_empty()).unwrap_or("Code"));
+                    output.enums.push(TypeDefinition::Du(DuDef {
+                        name: name.clone(),
+                        variants: variants.into_iter().map(VariantDef::new_simple).collect(),
+                    }));
+                    Some(name)
+                } else {
+                    output.diagnostics.push(
+                        Diagnostic::warning(
+                            "invalid-code",
+                            format!("Value set `{}` has codes that aren't distinct variant names; generated as `string`", url),
+                        )
+                        .at(location.to_string()),
+                    );
+                    None
+                }
+            }
+        };
+        output.value_sets.insert(url.to_string(), name.clone());
+        name.unwrap_or_else(|| "string".to_string())
+    }
+
+    fn fhir_type(&self, code: &str, location: &str, output: &mut Output) -> String {
+        if let Some(system) = code.strip_prefix(FHIRPATH_SYSTEM) {
+            let ty = match system {
+                "String" | "Date" | "DateTime" | "Time" => "string",
+                "Boolean" => "bool",
+                "Integer" => "int",
+                "Long" => "int64",
+                "Decimal" => "float",
+                _ => {
+                    output.diagnostics.push(
+                        Diagnostic::warning("unmapped-type", format!("Unknown FHIRPath type `{}`; generated as `string`", code))
+                            .at(location.to_string()),
+                    );
+                    "string"
+                }
+            };
+            return ty.to_string();
+        }
+        primitive_type(code).map(str::to_string).unwrap_or_else(|| type_name(code))
+    }
+}
+
+/// The Fusabi type of a FHIR primitive, or `None` for other types
+fn primitive_type(code: &str) -> Option<&'static str> {
+    match code {
+        "boolean" => Some("bool"),
+        "integer" | "positiveInt" | "unsignedInt" => Some("int"),
+        "integer64" => Some("int64"),
+        "decimal" => Some("float"),
+        "string" | "code" | "id" | "markdown" | "uri" | "url" | "canonical" | "oid" | "uuid" | "base64Binary"
+        | "date" | "dateTime" | "instant" | "time" | "xhtml" => Some("string"),
+        _ => None,
+    }
+}
+
+/// FHIR names are identifiers already; keep their casing (`USCorePatient`,
+/// `supportingInfo` → `SupportingInfo`) and drop anything else
+fn type_name(name: &str) -> String {
+    let mut chars = name.chars().filter(char::is_ascii_alphanumeric);
+    match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + &chars.collect::<String>(),
+        None => String::new(),
+    }
+}
+
+fn matches_any(patterns: &[&str], name: &str) -> bool {
+    patterns.iter().any(|pattern| match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => name == *pattern,
+    })
+}
+
+impl Default for FhirProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for FhirProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("FhirProvider")
+            .param(
+                ParamSpec::new("resources", ParamKind::List)
+                    .describe("Definition names or types to generate; a trailing `*` matches a prefix"),
+            )
+            .param(
+                ParamSpec::new("valuesets", ParamKind::String)
+                    .describe("Further FHIR JSON to read value sets and code systems from, inline or a path"),
+            )
+    }
+}
+
+impl TypeProvider for FhirProvider {
+    fn name(&self) -> &str {
+        "FhirProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let inline = |content: &str| content.trim_start().starts_with(['{', '[']);
+        let mut definitions = if inline(source) {
+            parse_definitions(source)?
+        } else if Path::new(source).is_dir() {
+            self.read_dir(Path::new(source), params)?
+        } else {
+            parse_definitions(&read_source(source, params)?)?
+        };
+
+        if let Some(value_sets) = typed.str("valuesets") {
+            let terminology = if inline(value_sets) {
+                parse_definitions(value_sets)?
+            } else {
+                parse_definitions(&read_source(value_sets, params)?)?
+            };
+            definitions.value_sets.extend(terminology.value_sets);
+            definitions.code_systems.extend(terminology.code_systems);
+        }
+
+        if let Some(patterns) = typed.list("resources") {
+            definitions
+                .structures
+                .retain(|s| matches_any(&patterns, &s.name) || matches_any(&patterns, &s.type_name));
+        }
+
+        let json = serde_json::to_string(&definitions)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected FHIR definitions".to_string()));
+        };
+
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+        module.types = self.generate(&self.parse_schema(json)?).0;
+
+        let mut result = GeneratedTypes::new();
+        result.modules.push(module);
+        Ok(result)
+    }
+}
+
+impl SourceValidator for FhirProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected FHIR definitions".to_string()));
+        };
+
+        Ok(self.generate(&self.parse_schema(json)?).1)
+    }
+}
+
+impl DescribedProvider for FhirProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .source(SourceKind::Directory)
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    const PATIENT: &str = r##"{"resourceType": "StructureDefinition", "url": "http://hl7.org/fhir/StructureDefinition/Patient",
+      "name": "Patient", "type": "Patient", "kind": "resource", "abstract": false,
+      "snapshot": {"element": [
+        {"id": "Patient", "path": "Patient", "min": 0, "max": "*"},
+        {"id": "Patient.id", "path": "Patient.id", "min": 0, "max": "1", "type": [{"code": "http://hl7.org/fhirpath/System.String"}]},
+        {"id": "Patient.identifier", "path": "Patient.identifier", "min": 0, "max": "*", "type": [{"code": "Identifier"}]},
+        {"id": "Patient.identifier:mrn", "path": "Patient.identifier", "sliceName": "mrn", "min": 0, "max": "1", "type": [{"code": "Identifier"}]},
+        {"id": "Patient.active", "path": "Patient.active", "min": 0, "max": "1", "type": [{"code": "boolean"}]},
+        {"id": "Patient.gender", "path": "Patient.gender", "min": 1, "max": "1", "type": [{"code": "code"}],
+         "binding": {"strength": "required", "valueSet": "http://hl7.org/fhir/ValueSet/administrative-gender|4.0.1"}},
+        {"id": "Patient.deceased[x]", "path": "Patient.deceased[x]", "min": 0, "max": "1", "type": [{"code": "boolean"}, {"code": "dateTime"}]},
+        {"id": "Patient.contact", "path": "Patient.contact", "min": 0, "max": "*", "type": [{"code": "BackboneElement"}]},
+        {"id": "Patient.contact.name", "path": "Patient.contact.name", "min": 0, "max": "1", "type": [{"code": "HumanName"}]},
+        {"id": "Patient.contact.contact", "path": "Patient.contact.contact", "min": 0, "max": "*", "contentReference": "#Patient.contact"},
+        {"id": "Patient.link", "path": "Patient.link", "min": 0, "max": "*", "type": [{"code": "BackboneElement"}]},
+        {"id": "Patient.link.other", "path": "Patient.link.other", "min": 1, "max": "1", "type": [{"code": "Reference"}]},
+        {"id": "Patient.link.type", "path": "Patient.link.type", "min": 1, "max": "1", "type": [{"code": "code"}],
+         "binding": {"strength": "required", "valueSet": "http://hl7.org/fhir/ValueSet/link-type|4.0.1"}}
+      ]}}"##;
+
+    const TERMINOLOGY: &str = r#"[
+      {"resourceType": "ValueSet", "url": "http://hl7.org/fhir/ValueSet/administrative-gender", "name": "AdministrativeGender",
+       "compose": {"include": [{"system": "http://hl7.org/fhir/administrative-gender"}]}},
+      {"resourceType": "CodeSystem", "url": "http://hl7.org/fhir/administrative-gender",
+       "concept": [{"code": "male"}, {"code": "female"}, {"code": "other"}, {"code": "unknown"}]}
+    ]"#;
+
+    const PROFILE: &str = r#"{"resourceType": "StructureDefinition", "url": "http://example.org/StructureDefinition/vitals",
+      "name": "VitalSigns", "type": "Observation", "kind": "resource", "derivation": "constraint",
+      "differential": {"element": [
+        {"id": "Observation", "path": "Observation"},
+        {"id": "Observation.code", "path": "Observation.code", "min": 1, "max": "1", "type": [{"code": "CodeableConcept"}]},
+        {"id": "Observation.value[x]", "path": "Observation.value[x]", "min": 0, "max": "1", "type": [{"code": "Quantity"}]},
+        {"id": "Observation.note", "path": "Observation.note", "max": "0"}
+      ]}}"#;
+
+    fn fields(types: &GeneratedTypes, name: &str) -> Vec<(String, String)> {
+        types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+            .fields
+            .iter()
+            .map(|(field, ty)| (field.clone(), ty.to_string()))
+            .collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(f, t)| (f.to_string(), t.to_string())).collect()
+    }
+
+    fn variants(types: &GeneratedTypes, name: &str) -> Vec<String> {
+        types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Du(du) if du.name == name => Some(du),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected union {}", name))
+            .variants
+            .iter()
+            .map(|v| v.name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_resource_types() {
+        let provider = FhirProvider::new();
+        let params = ProviderParams::default().with("valuesets", TERMINOLOGY);
+        let schema = provider.resolve_schema(PATIENT, &params).unwrap();
+        let types = provider.generate_types(&schema, "Fhir").unwrap();
+
+        assert_eq!(
+            fields(&types, "Patient"),
+            pairs(&[
+                ("resourceType", "string"),
+                ("id", "string option"),
+                ("identifier", "Identifier list option"),
+                ("active", "bool option"),
+                ("gender", "AdministrativeGender"),
+                ("deceased", "PatientDeceased option"),
+                ("contact", "PatientContact list option"),
+                ("link", "PatientLink list option"),
+            ])
+        );
+        assert_eq!(
+            fields(&types, "PatientContact"),
+            pairs(&[("name", "HumanName option"), ("contact", "PatientContact list option")])
+        );
+        assert_eq!(fields(&types, "PatientLink"), pairs(&[("other", "Reference"), ("type", "string")]));
+        assert_eq!(variants(&types, "PatientDeceased"), ["Boolean", "DateTime"]);
+        assert_eq!(variants(&types, "AdministrativeGender"), ["Male", "Female", "Other", "Unknown"]);
+
+        let diagnostics = provider.schema_diagnostics(&schema).unwrap();
+        let found: Vec<(&str, &str)> = diagnostics
+            .iter()
+            .map(|d| (d.code.as_str(), d.location.as_deref().unwrap_or("")))
+            .collect();
+        assert_eq!(found, [("unresolved-valueset", "Patient.link.type")]);
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/fhir.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_directory_source_and_filter() {
+        let dir = std::env::temp_dir().join(format!("fusabi-fhir-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("patient.json"), PATIENT).unwrap();
+        std::fs::write(dir.join("vitals.json"), PROFILE).unwrap();
+        std::fs::write(dir.join("notes.txt"), "not FHIR").unwrap();
+
+        let provider = FhirProvider::new();
+        let schema = provider.resolve_schema(dir.to_str().unwrap(), &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Fhir").unwrap();
+        assert_eq!(
+            fields(&types, "VitalSigns"),
+            pairs(&[("resourceType", "string"), ("code", "CodeableConcept"), ("value", "Quantity option")])
+        );
+
+        let params = ProviderParams::default().with("resources", "Observation");
+        let schema = provider.resolve_schema(dir.to_str().unwrap(), &params).unwrap();
+        let types = provider.generate_types(&schema, "Fhir").unwrap();
+        let names: Vec<&str> = types.modules[0]
+            .types
+            .iter()
+            .filter_map(|t| match t {
+                TypeDefinition::Record(r) => Some(r.name.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(names, ["VitalSigns"]);
+        let codes: Vec<String> = provider.schema_diagnostics(&schema).unwrap().into_iter().map(|d| d.code).collect();
+        assert_eq!(codes, ["no-snapshot"]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,524 @@
+//! GitHub Actions Type Provider
+//!
+//! Generates Fusabi types from GitHub Actions workflow files and action
+//! metadata (`action.yml`), for tooling that generates or lints CI
+//! configuration.
+//!
+//! # Sources
+//!
+//! - a workflow file or an `action.yml`, or either inline
+//! - a repository directory: every workflow under `.github/workflows` and
+//!   every `action.yml` / `action.yaml` in the tree (composite actions under
+//!   `.github/actions/*` included)
+//!
+//! # Generated Types
+//!
+//! For a workflow:
+//!
+//! - `Trigger` - a union of the events in `on`
+//! - `WorkflowDispatchInputs` / `WorkflowCallInputs` - typed inputs
+//!   (`boolean` → `bool`, `number` → `float`, `choice` → a union of string
+//!   literals); inputs that are not `required` become `T option`
+//! - `WorkflowCallOutputs` / `WorkflowCallSecrets` - string records for
+//!   reusable workflows
+//! - `JobId` - a union of the job ids
+//! - `<Job>Matrix` - one field per `strategy.matrix` dimension, typed from
+//!   its values; keys only added by `include` become `T option`
+//! - `<Job>Outputs` - the job's outputs
+//! - `<Job>StepId` - a union of the ids of the job's steps
+//!
+//! For an action: `Inputs` and `Outputs` records of strings, with inputs
+//! that are optional or have a default as `string option`.
+//!
+//! A single file generates into `<Namespace>`; a repository generates
+//! `<Namespace>.Workflows.<Workflow>` and `<Namespace>.Actions.<Action>`.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_github_actions::GitHubActionsProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = GitHubActionsProvider::new();
+//! let schema = provider.resolve_schema(".", &ProviderParams::default())?;
+//! let types = provider.generate_types(&schema, "Ci")?;
+//! ```
+
+mod workflow;
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, SourceKind,
+    SourceValidator,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    ProviderError, ProviderResult,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use workflow::FileTypes;
+
+/// Directories never searched for actions
+const SKIPPED_DIRS: [&str; 3] = ["node_modules", "target", "vendor"];
+
+/// Kind of a GitHub Actions file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum FileKind {
+    Workflow,
+    Action,
+}
+
+impl FileKind {
+    /// Workflows have `jobs`, actions have `runs`
+    fn detect(document: &Value) -> Option<Self> {
+        if document.get("jobs").is_some() {
+            Some(FileKind::Workflow)
+        } else if document.get("runs").is_some() {
+            Some(FileKind::Action)
+        } else {
+            None
+        }
+    }
+}
+
+/// One workflow or action file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SourceFile {
+    kind: FileKind,
+    /// Module path below the namespace
+    module: Vec<String>,
+    /// Path relative to the repository, for diagnostics
+    path: Option<String>,
+    document: Value,
+}
+
+/// GitHub Actions type provider
+pub struct GitHubActionsProvider {
+    generator: TypeGenerator,
+}
+
+impl GitHubActionsProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    fn parse_file(&self, content: &str, origin: &str) -> ProviderResult<(FileKind, Value)> {
+        let document: Value = serde_yaml::from_str(content)
+            .map_err(|e| ProviderError::ParseError(format!("Invalid YAML in {}: {}", origin, e)))?;
+        let kind = FileKind::detect(&document).ok_or_else(|| {
+            ProviderError::ParseError(format!(
+                "{} is neither a workflow (no `jobs`) nor an action (no `runs`)",
+                origin
+            ))
+        })?;
+        Ok((kind, document))
+    }
+
+    /// Collect the workflows and actions of a repository
+    fn read_repository(&self, root: &Path, params: &ProviderParams) -> ProviderResult<Vec<SourceFile>> {
+        let mut files = Vec::new();
+
+        let workflows_dir = root.join(".github").join("workflows");
+        if workflows_dir.is_dir() {
+            for path in sorted_entries(&workflows_dir)? {
+                if !path.is_file() || !has_yaml_extension(&path) {
+                    continue;
+                }
+                let stem = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+                let (kind, document) = self.read_file(root, &path, params)?;
+                files.push(SourceFile {
+                    kind,
+                    module: vec!["Workflows".to_string(), self.generator.naming.apply(&stem)],
+                    path: Some(relative(root, &path)),
+                    document,
+                });
+            }
+        }
+
+        let mut action_files = Vec::new();
+        find_actions(root, &mut action_files)?;
+        for path in action_files {
+            let dir = path.parent().unwrap_or(root);
+            let name = dir
+                .canonicalize()
+                .ok()
+                .and_then(|d| d.file_name().map(|n| n.to_string_lossy().into_owned()))
+                .unwrap_or_else(|| "Action".to_string());
+            let (kind, document) = self.read_file(root, &path, params)?;
+            files.push(SourceFile {
+                kind,
+                module: vec!["Actions".to_string(), self.generator.naming.apply(&name)],
+                path: Some(relative(root, &path)),
+                document,
+            });
+        }
+
+        if files.is_empty() {
+            return Err(ProviderError::InvalidSource(format!(
+                "No workflows or actions found in {}",
+                root.display()
+            )));
+        }
+        Ok(files)
+    }
+
+    fn read_file(&self, root: &Path, path: &Path, params: &ProviderParams) -> ProviderResult<(FileKind, Value)> {
+        let content = read_source(&path.to_string_lossy(), params)?;
+        self.parse_file(&content, &relative(root, path))
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<Vec<SourceFile>> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected GitHub Actions schema: {}", e)))
+    }
+
+    fn file_types(&self, file: &SourceFile) -> FileTypes {
+        let prefix = file.path.as_ref().map(|p| format!("{}:", p)).unwrap_or_default();
+        match file.kind {
+            FileKind::Workflow => FileTypes::workflow(&self.generator, &file.document, &prefix),
+            FileKind::Action => FileTypes::action(&file.document, &prefix),
+        }
+    }
+}
+
+impl Default for GitHubActionsProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypeProvider for GitHubActionsProvider {
+    fn name(&self) -> &str {
+        "GitHubActionsProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let files = if source.contains('\n') {
+            let (kind, document) = self.parse_file(source, "inline source")?;
+            vec![SourceFile { kind, module: Vec::new(), path: None, document }]
+        } else if Path::new(source).is_dir() {
+            self.read_repository(Path::new(source), params)?
+        } else {
+            let (kind, document) = self.parse_file(&read_source(source, params)?, source)?;
+            vec![SourceFile { kind, module: Vec::new(), path: None, document }]
+        };
+
+        let json = serde_json::to_string(&files)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected GitHub Actions schema".to_string()));
+        };
+
+        let mut result = GeneratedTypes::new();
+        for file in self.parse_schema(json)? {
+            let mut path = vec![namespace.to_string()];
+            path.extend(file.module.iter().cloned());
+
+            let mut module = GeneratedModule::new(path);
+            module.types = self.file_types(&file).types;
+            if !module.types.is_empty() {
+                result.modules.push(module);
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl SourceValidator for GitHubActionsProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected GitHub Actions schema".to_string()));
+        };
+
+        Ok(self
+            .parse_schema(json)?
+            .iter()
+            .flat_map(|file| self.file_types(file).diagnostics)
+            .collect())
+    }
+}
+
+impl DescribedProvider for GitHubActionsProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .source(SourceKind::Directory)
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+/// Find `action.yml` / `action.yaml` files, skipping hidden directories
+/// other than `.github` and dependency folders
+fn find_actions(dir: &Path, found: &mut Vec<PathBuf>) -> ProviderResult<()> {
+    for path in sorted_entries(dir)? {
+        let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        if path.is_dir() {
+            let hidden = name.starts_with('.') && name != ".github";
+            if !hidden && !SKIPPED_DIRS.contains(&name.as_str()) {
+                find_actions(&path, found)?;
+            }
+        } else if name == "action.yml" || name == "action.yaml" {
+            found.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn sorted_entries(dir: &Path) -> ProviderResult<Vec<PathBuf>> {
+    let io_error = |e: std::io::Error| {
+        ProviderError::IoError(format!("Failed to read {}: {}", dir.display(), e))
+    };
+    let mut paths = std::fs::read_dir(dir)
+        .map_err(io_error)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(io_error)?;
+    paths.sort();
+    Ok(paths)
+}
+
+fn has_yaml_extension(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "yml" || ext == "yaml")
+}
+
+fn relative(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root).unwrap_or(path).to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+    use fusabi_type_providers::{RecordDef, TypeDefinition};
+
+    const WORKFLOW: &str = r#"
+name: CI
+on:
+  push:
+    branches: [main]
+  workflow_dispatch:
+    inputs:
+      environment:
+        type: choice
+        options: [staging, production]
+        required: true
+      dry_run:
+        type: boolean
+      retries:
+        type: number
+        deprecationMessage: Use max_attempts
+  workflow_call:
+    inputs:
+      version:
+        type: string
+        required: true
+    outputs:
+      artifact:
+        value: ${{ jobs.build.outputs.artifact }}
+    secrets:
+      token:
+        required: true
+      webhook: {}
+
+jobs:
+  build-and-test:
+    runs-on: ${{ matrix.os }}
+    strategy:
+      matrix:
+        os: [ubuntu-latest, windows-latest]
+        rust: [1.75, stable]
+        node: [18, 20]
+        include:
+          - os: ubuntu-latest
+            coverage: true
+    outputs:
+      artifact: ${{ steps.upload.outputs.name }}
+    steps:
+      - uses: actions/checkout@v4
+      - id: build
+        run: cargo build
+      - id: upload
+        uses: actions/upload-artifact@v4
+  deploy:
+    needs: build-and-test
+    strategy:
+      matrix: ${{ fromJSON(needs.setup.outputs.targets) }}
+    runs-on: ubuntu-latest
+    steps:
+      - run: ./deploy.sh
+"#;
+
+    const ACTION: &str = r#"
+name: Setup toolchain
+inputs:
+  version:
+    description: Toolchain version
+    required: true
+  cache:
+    required: true
+    default: "true"
+  components:
+    required: false
+outputs:
+  path:
+    value: ${{ steps.install.outputs.path }}
+runs:
+  using: composite
+  steps:
+    - id: install
+      run: ./install.sh
+      shell: bash
+"#;
+
+    fn record<'a>(module: &'a GeneratedModule, name: &str) -> Vec<(&'a str, String)> {
+        module
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .map(|r: &RecordDef| r.fields.iter().map(|(n, ty)| (n.as_str(), ty.to_string())).collect())
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+    }
+
+    fn variants(module: &GeneratedModule, name: &str) -> Vec<String> {
+        module
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Du(du) if du.name == name => {
+                    Some(du.variants.iter().map(|v| v.name.clone()).collect())
+                }
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected union {}", name))
+    }
+
+    fn generate(source: &str) -> GeneratedModule {
+        let provider = GitHubActionsProvider::new();
+        let schema = provider.resolve_schema(source, &ProviderParams::default()).unwrap();
+        let mut types = provider.generate_types(&schema, "Ci").unwrap();
+        assert_eq!(types.modules.len(), 1);
+        types.modules.remove(0)
+    }
+
+    #[test]
+    fn test_generate_workflow() {
+        let module = generate(WORKFLOW);
+        assert_eq!(module.path, vec!["Ci"]);
+
+        assert_eq!(variants(&module, "Trigger"), ["Push", "WorkflowCall", "WorkflowDispatch"]);
+        assert_eq!(variants(&module, "JobId"), ["BuildAndTest", "Deploy"]);
+        assert_eq!(variants(&module, "BuildAndTestStepId"), ["Build", "Upload"]);
+
+        assert_eq!(
+            record(&module, "WorkflowDispatchInputs"),
+            [
+                ("dry_run", "bool option".to_string()),
+                ("environment", "\"staging\" | \"production\"".to_string()),
+                ("retries", "float option".to_string()),
+            ]
+        );
+        assert_eq!(record(&module, "WorkflowCallInputs"), [("version", "string".to_string())]);
+        assert_eq!(record(&module, "WorkflowCallOutputs"), [("artifact", "string".to_string())]);
+        assert_eq!(
+            record(&module, "WorkflowCallSecrets"),
+            [("token", "string".to_string()), ("webhook", "string option".to_string())]
+        );
+        assert_eq!(
+            record(&module, "BuildAndTestMatrix"),
+            [
+                ("node", "int".to_string()),
+                ("os", "string".to_string()),
+                ("rust", "string".to_string()),
+                ("coverage", "bool option".to_string()),
+            ]
+        );
+        assert_eq!(record(&module, "BuildAndTestOutputs"), [("artifact", "string".to_string())]);
+
+        let mut types = GeneratedTypes::new();
+        types.modules.push(module);
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/github-actions.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_generate_action() {
+        let module = generate(ACTION);
+        assert_eq!(
+            record(&module, "Inputs"),
+            [
+                ("cache", "string option".to_string()),
+                ("components", "string option".to_string()),
+                ("version", "string".to_string()),
+            ]
+        );
+        assert_eq!(record(&module, "Outputs"), [("path", "string".to_string())]);
+    }
+
+    #[test]
+    fn test_repository() {
+        let root = std::env::temp_dir().join(format!("fusabi-gha-{}", std::process::id()));
+        let workflows = root.join(".github").join("workflows");
+        let action = root.join(".github").join("actions").join("setup-toolchain");
+        std::fs::create_dir_all(&workflows).unwrap();
+        std::fs::create_dir_all(&action).unwrap();
+        std::fs::create_dir_all(root.join("node_modules").join("dep")).unwrap();
+        std::fs::write(workflows.join("release-pipeline.yml"), WORKFLOW).unwrap();
+        std::fs::write(workflows.join("notes.md"), "not a workflow").unwrap();
+        std::fs::write(action.join("action.yml"), ACTION).unwrap();
+        std::fs::write(root.join("node_modules").join("dep").join("action.yml"), "bad: [").unwrap();
+
+        let provider = GitHubActionsProvider::new();
+        let schema = provider
+            .resolve_schema(&root.to_string_lossy(), &ProviderParams::default())
+            .unwrap();
+        let types = provider.generate_types(&schema, "Ci").unwrap();
+        let report = provider.validate_source(&root.to_string_lossy(), &ProviderParams::default());
+        std::fs::remove_dir_all(&root).unwrap();
+
+        let paths: Vec<Vec<String>> = types.modules.iter().map(|m| m.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                vec!["Ci", "Workflows", "ReleasePipeline"],
+                vec!["Ci", "Actions", "SetupToolchain"],
+            ]
+        );
+        assert!(report
+            .diagnostics
+            .iter()
+            .any(|d| d.location.as_deref() == Some(".github/workflows/release-pipeline.yml:jobs.deploy.strategy.matrix")));
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = GitHubActionsProvider::new();
+        let report = provider.validate_source(WORKFLOW, &ProviderParams::default());
+        assert!(report.is_valid());
+
+        let codes: Vec<(&str, Option<&str>)> = report
+            .diagnostics
+            .iter()
+            .map(|d| (d.code.as_str(), d.location.as_deref()))
+            .collect();
+        assert!(codes.contains(&("deprecated", Some("on.workflow_dispatch.inputs.retries"))));
+        assert!(codes.contains(&("lossy-conversion", Some("jobs.build-and-test.strategy.matrix.rust"))));
+        assert!(codes.contains(&("coerced-any", Some("jobs.deploy.strategy.matrix"))));
+
+        assert!(!provider.validate_source("name: nothing\nsteps: []\n", &ProviderParams::default()).is_valid());
+    }
+}
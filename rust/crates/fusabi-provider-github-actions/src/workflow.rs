@@ -0,0 +1,295 @@
+//! Workflow (`.github/workflows/*.yml`) and action (`action.yml`) types
+
+use fusabi_provider_common::{Diagnostic, TypeShape};
+use fusabi_type_providers::{
+    DuDef, RecordDef, TypeDefinition, TypeExpr, TypeGenerator, VariantDef,
+};
+use serde_json::{Map, Value};
+
+/// Types and diagnostics for one workflow or action file
+#[derive(Default)]
+pub(crate) struct FileTypes {
+    pub types: Vec<TypeDefinition>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Value type of a matrix dimension
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ValueType {
+    Bool,
+    Int,
+    Float,
+    String,
+    /// Scalars of different kinds; Actions compares them as strings
+    Mixed,
+    Any,
+}
+
+impl ValueType {
+    fn of(value: &Value) -> Self {
+        match value {
+            Value::Bool(_) => ValueType::Bool,
+            Value::Number(n) if n.is_f64() => ValueType::Float,
+            Value::Number(_) => ValueType::Int,
+            Value::String(_) => ValueType::String,
+            _ => ValueType::Any,
+        }
+    }
+
+    fn merge(self, other: ValueType) -> ValueType {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (ValueType::Int, ValueType::Float) | (ValueType::Float, ValueType::Int) => ValueType::Float,
+            (ValueType::Any, _) | (_, ValueType::Any) => ValueType::Any,
+            _ => ValueType::Mixed,
+        }
+    }
+
+    fn type_name(self) -> &'static str {
+        match self {
+            ValueType::Bool => "bool",
+            ValueType::Int => "int",
+            ValueType::Float => "float",
+            ValueType::String | ValueType::Mixed => "string",
+            ValueType::Any => "any",
+        }
+    }
+}
+
+impl FileTypes {
+    /// Generate the types of a workflow file
+    pub fn workflow(generator: &TypeGenerator, workflow: &Value, prefix: &str) -> Self {
+        let mut file = FileTypes::default();
+        let on = workflow.get("on").unwrap_or(&Value::Null);
+
+        let triggers: Vec<&str> = match on {
+            Value::String(event) => vec![event.as_str()],
+            Value::Array(events) => events.iter().filter_map(Value::as_str).collect(),
+            Value::Object(events) => events.keys().map(String::as_str).collect(),
+            _ => Vec::new(),
+        };
+        file.union(generator, "Trigger", &triggers);
+
+        if let Some(inputs) = on.pointer("/workflow_dispatch/inputs").and_then(Value::as_object) {
+            file.inputs_record("WorkflowDispatchInputs", inputs, &format!("{}on.workflow_dispatch.inputs", prefix));
+        }
+        if let Some(inputs) = on.pointer("/workflow_call/inputs").and_then(Value::as_object) {
+            file.inputs_record("WorkflowCallInputs", inputs, &format!("{}on.workflow_call.inputs", prefix));
+        }
+        if let Some(outputs) = on.pointer("/workflow_call/outputs").and_then(Value::as_object) {
+            file.string_record("WorkflowCallOutputs", outputs.keys(), |_| false);
+        }
+        if let Some(secrets) = on.pointer("/workflow_call/secrets").and_then(Value::as_object) {
+            file.string_record("WorkflowCallSecrets", secrets.keys(), |name| {
+                secrets[name].get("required").and_then(Value::as_bool) != Some(true)
+            });
+        }
+
+        let jobs = workflow.get("jobs").and_then(Value::as_object);
+        let job_ids: Vec<&str> = jobs.into_iter().flat_map(Map::keys).map(String::as_str).collect();
+        file.union(generator, "JobId", &job_ids);
+
+        for (job_id, job) in jobs.into_iter().flatten() {
+            let job_name = generator.naming.apply(job_id);
+            let location = format!("{}jobs.{}", prefix, job_id);
+
+            match job.pointer("/strategy/matrix") {
+                Some(Value::Object(matrix)) => {
+                    file.matrix_record(&format!("{}Matrix", job_name), matrix, &format!("{}.strategy.matrix", location));
+                }
+                Some(Value::String(expression)) => file.diagnostics.push(
+                    Diagnostic::info(
+                        "coerced-any",
+                        format!("Matrix of job `{}` is computed by `{}`; it is not typed", job_id, expression),
+                    )
+                    .at(format!("{}.strategy.matrix", location)),
+                ),
+                _ => {}
+            }
+
+            if let Some(outputs) = job.get("outputs").and_then(Value::as_object) {
+                file.string_record(&format!("{}Outputs", job_name), outputs.keys(), |_| false);
+            }
+
+            let step_ids: Vec<&str> = job
+                .get("steps")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|step| step.get("id").and_then(Value::as_str))
+                .collect();
+            file.union(generator, &format!("{}StepId", job_name), &step_ids);
+        }
+
+        file
+    }
+
+    /// Generate the input and output records of an action
+    pub fn action(action: &Value, prefix: &str) -> Self {
+        let mut file = FileTypes::default();
+
+        if let Some(inputs) = action.get("inputs").and_then(Value::as_object) {
+            let location = format!("{}inputs", prefix);
+            file.deprecations(inputs, &location);
+            // Inputs with a default are always set, but callers may omit them
+            file.string_record("Inputs", inputs.keys(), |name| {
+                let input = &inputs[name];
+                input.get("required").and_then(Value::as_bool) != Some(true) || input.get("default").is_some()
+            });
+        }
+
+        if let Some(outputs) = action.get("outputs").and_then(Value::as_object) {
+            file.string_record("Outputs", outputs.keys(), |_| false);
+        }
+
+        file
+    }
+
+    /// Typed workflow inputs: `boolean`, `number`, `string`, `choice`
+    /// and `environment`
+    fn inputs_record(&mut self, name: &str, inputs: &Map<String, Value>, location: &str) {
+        self.deprecations(inputs, location);
+
+        let fields = inputs
+            .iter()
+            .map(|(input_name, input)| {
+                let base = match input.get("type").and_then(Value::as_str) {
+                    Some("boolean") => named("bool"),
+                    Some("number") => named("float"),
+                    Some("choice") => {
+                        let mut options: Vec<TypeShape> = input
+                            .get("options")
+                            .and_then(Value::as_array)
+                            .into_iter()
+                            .flatten()
+                            .filter_map(Value::as_str)
+                            .map(|option| TypeShape::Literal(option.to_string()))
+                            .collect();
+                        match options.len() {
+                            0 => named("string"),
+                            1 => options.remove(0),
+                            _ => TypeShape::Union(options),
+                        }
+                    }
+                    _ => named("string"),
+                };
+                let required = input.get("required").and_then(Value::as_bool) == Some(true);
+                (input_name.clone(), optional_unless(required, base))
+            })
+            .collect();
+
+        self.types.push(TypeDefinition::Record(RecordDef {
+            name: name.to_string(),
+            fields,
+        }));
+    }
+
+    /// One field per matrix dimension; keys only added by `include` are
+    /// optional
+    fn matrix_record(&mut self, name: &str, matrix: &Map<String, Value>, location: &str) {
+        let mut dimensions: Vec<(String, Option<ValueType>, bool)> = Vec::new();
+
+        for (key, values) in matrix {
+            if key == "include" || key == "exclude" {
+                continue;
+            }
+            let value_type = match values {
+                Value::Array(values) => values.iter().map(ValueType::of).reduce(ValueType::merge),
+                // An expression such as `${{ fromJSON(needs.setup.outputs.os) }}`
+                _ => Some(ValueType::Any),
+            };
+            dimensions.push((key.clone(), value_type, false));
+        }
+
+        for entry in matrix.get("include").and_then(Value::as_array).into_iter().flatten() {
+            for (key, value) in entry.as_object().into_iter().flatten() {
+                let value_type = ValueType::of(value);
+                match dimensions.iter_mut().find(|(k, _, _)| k == key) {
+                    Some((_, existing, _)) => {
+                        *existing = Some(existing.map_or(value_type, |t| t.merge(value_type)));
+                    }
+                    None => dimensions.push((key.clone(), Some(value_type), true)),
+                }
+            }
+        }
+
+        let mut fields = Vec::new();
+        for (key, value_type, include_only) in dimensions {
+            let value_type = value_type.unwrap_or(ValueType::Any);
+            let dimension_location = format!("{}.{}", location, key);
+            match value_type {
+                ValueType::Any => self.diagnostics.push(
+                    Diagnostic::info(
+                        "coerced-any",
+                        format!("Matrix dimension `{}` has no literal scalar values; generated as `any`", key),
+                    )
+                    .at(dimension_location),
+                ),
+                ValueType::Mixed => self.diagnostics.push(
+                    Diagnostic::info(
+                        "lossy-conversion",
+                        format!("Matrix dimension `{}` mixes value types; generated as `string`", key),
+                    )
+                    .at(dimension_location),
+                ),
+                _ => {}
+            }
+            fields.push((key, optional_unless(!include_only, named(value_type.type_name()))));
+        }
+
+        self.types.push(TypeDefinition::Record(RecordDef {
+            name: name.to_string(),
+            fields,
+        }));
+    }
+
+    fn string_record<'a>(
+        &mut self,
+        name: &str,
+        keys: impl Iterator<Item = &'a String>,
+        optional: impl Fn(&str) -> bool,
+    ) {
+        self.types.push(TypeDefinition::Record(RecordDef {
+            name: name.to_string(),
+            fields: keys
+                .map(|key| (key.clone(), optional_unless(!optional(key), named("string"))))
+                .collect(),
+        }));
+    }
+
+    fn union(&mut self, generator: &TypeGenerator, name: &str, values: &[&str]) {
+        if values.is_empty() {
+            return;
+        }
+        self.types.push(TypeDefinition::Du(DuDef {
+            name: name.to_string(),
+            variants: values
+                .iter()
+                .map(|v| VariantDef::new_simple(generator.naming.apply(v)))
+                .collect(),
+        }));
+    }
+
+    fn deprecations(&mut self, inputs: &Map<String, Value>, location: &str) {
+        for (name, input) in inputs {
+            if let Some(message) = input.get("deprecationMessage").and_then(Value::as_str) {
+                self.diagnostics.push(
+                    Diagnostic::info("deprecated", format!("Input `{}` is deprecated: {}", name, message))
+                        .at(format!("{}.{}", location, name)),
+                );
+            }
+        }
+    }
+}
+
+fn named(name: &str) -> TypeShape {
+    TypeShape::Named(name.to_string())
+}
+
+fn optional_unless(required: bool, shape: TypeShape) -> TypeExpr {
+    if required {
+        shape.into()
+    } else {
+        TypeShape::Option(Box::new(shape)).into()
+    }
+}
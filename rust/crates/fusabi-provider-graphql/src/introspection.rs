@@ -0,0 +1,100 @@
+//! GraphQL introspection results
+//!
+//! The subset of the `__schema` introspection result that types are
+//! generated from, as returned by the standard introspection query.
+
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde::Deserialize;
+
+/// `__schema`
+#[derive(Debug, Deserialize)]
+pub struct IntrospectionSchema {
+    pub types: Vec<FullType>,
+}
+
+/// `__Type` kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TypeKind {
+    Scalar,
+    Object,
+    Interface,
+    Union,
+    Enum,
+    InputObject,
+    List,
+    NonNull,
+}
+
+/// A named type with its members
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FullType {
+    pub kind: TypeKind,
+    pub name: Option<String>,
+    /// Object and interface fields
+    #[serde(default)]
+    pub fields: Option<Vec<Field>>,
+    /// Input object fields
+    #[serde(default)]
+    pub input_fields: Option<Vec<InputValue>>,
+    #[serde(default)]
+    pub enum_values: Option<Vec<EnumValue>>,
+    /// Union members
+    #[serde(default)]
+    pub possible_types: Option<Vec<TypeRef>>,
+}
+
+/// Object or interface field
+#[derive(Debug, Deserialize)]
+pub struct Field {
+    pub name: String,
+    #[serde(default)]
+    pub args: Vec<InputValue>,
+    #[serde(rename = "type")]
+    pub type_ref: TypeRef,
+}
+
+/// Field argument or input object field
+#[derive(Debug, Deserialize)]
+pub struct InputValue {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_ref: TypeRef,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EnumValue {
+    pub name: String,
+}
+
+/// Reference to a type, wrapped in `LIST` and `NON_NULL` modifiers
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeRef {
+    pub kind: TypeKind,
+    pub name: Option<String>,
+    pub of_type: Option<Box<TypeRef>>,
+}
+
+/// Parse an introspection result
+///
+/// Accepts the full response (`{"data": {"__schema": ...}}`), its `data`
+/// (`{"__schema": ...}`) or the schema object itself.
+pub fn parse_introspection(json: &str) -> ProviderResult<IntrospectionSchema> {
+    let value: serde_json::Value =
+        serde_json::from_str(json).map_err(|e| ProviderError::ParseError(e.to_string()))?;
+
+    let schema = value
+        .pointer("/data/__schema")
+        .or_else(|| value.get("__schema"))
+        .unwrap_or(&value);
+    if schema.get("types").is_none() {
+        return Err(ProviderError::InvalidSource(
+            "Expected a GraphQL introspection result with `__schema.types`".to_string(),
+        ));
+    }
+
+    IntrospectionSchema::deserialize(schema)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid introspection result: {}", e)))
+}
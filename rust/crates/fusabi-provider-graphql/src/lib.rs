@@ -1,12 +1,32 @@
 //! GraphQL Type Provider
 //!
 //! Generates Fusabi types from GraphQL introspection schemas.
+//!
+//! Objects, interfaces and input objects become records, enums and unions
+//! become discriminated unions, all in a module named after the namespace.
+//! Fields are `T option` unless their type is non-null. Built-in scalars
+//! map to `int`, `float`, `string` and `bool`; custom scalars are `any`.
+//!
+//! Field arguments have no type of their own in GraphQL; the arguments of
+//! each field that takes any are hoisted to a `{Type}{Field}Args` record
+//! (see [`fusabi_provider_common::inline`]), e.g. `QueryUserArgs` for
+//! `Query.user(id: ID!)`.
+
+mod introspection;
 
+pub use introspection::{parse_introspection, IntrospectionSchema, TypeKind};
+
+use fusabi_provider_common::{
+    inline_name, read_source, Capabilities, DescribedProvider, InlineRecords, SourceValidator,
+    TypeShape,
+};
 use fusabi_type_providers::{
     TypeProvider, ProviderParams, Schema,
-    GeneratedTypes, TypeGenerator, NamingStrategy,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    DuDef, RecordDef, TypeDefinition, TypeExpr, VariantDef,
     ProviderError, ProviderResult,
 };
+use introspection::{FullType, InputValue, TypeRef};
 
 /// GraphQL type provider
 pub struct GraphQLProvider {
@@ -19,6 +39,87 @@ impl GraphQLProvider {
             generator: TypeGenerator::new(NamingStrategy::PascalCase),
         }
     }
+
+    /// Generate types from a parsed introspection schema
+    fn generate_from_schema(&self, schema: &IntrospectionSchema, namespace: &str) -> GeneratedTypes {
+        let named: Vec<(&str, &FullType)> = schema
+            .types
+            .iter()
+            .filter_map(|t| t.name.as_deref().map(|name| (name, t)))
+            .filter(|(name, _)| !name.starts_with("__"))
+            .collect();
+
+        let mut inline = InlineRecords::new();
+        for (name, _) in &named {
+            inline.reserve(*name);
+        }
+
+        let mut types = Vec::new();
+        for (name, full_type) in named {
+            if let Some(definition) = self.generate_type(name, full_type, &mut inline) {
+                types.push(definition);
+            }
+        }
+
+        let mut result = GeneratedTypes::new();
+        if !types.is_empty() || !inline.is_empty() {
+            let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+            module.types = types;
+            module.types.extend(inline.into_definitions());
+            result.modules.push(module);
+        }
+        result
+    }
+
+    /// Definition of a named type; scalars have none
+    fn generate_type(
+        &self,
+        name: &str,
+        full_type: &FullType,
+        inline: &mut InlineRecords,
+    ) -> Option<TypeDefinition> {
+        match full_type.kind {
+            TypeKind::Object | TypeKind::Interface => {
+                let fields = full_type.fields.as_deref().unwrap_or_default();
+                for field in fields.iter().filter(|f| !f.args.is_empty()) {
+                    let args = format!("{}Args", inline_name(name, &field.name));
+                    inline.hoist(&args, input_fields(&field.args));
+                }
+
+                Some(TypeDefinition::Record(RecordDef {
+                    name: name.to_string(),
+                    fields: fields
+                        .iter()
+                        .map(|f| (f.name.clone(), type_shape(&f.type_ref).into()))
+                        .collect(),
+                }))
+            }
+            TypeKind::InputObject => Some(TypeDefinition::Record(RecordDef {
+                name: name.to_string(),
+                fields: input_fields(full_type.input_fields.as_deref().unwrap_or_default()),
+            })),
+            TypeKind::Enum => Some(TypeDefinition::Du(DuDef {
+                name: name.to_string(),
+                variants: full_type
+                    .enum_values
+                    .iter()
+                    .flatten()
+                    .map(|v| VariantDef::new_simple(self.generator.naming.apply(&v.name)))
+                    .collect(),
+            })),
+            TypeKind::Union => Some(TypeDefinition::Du(DuDef {
+                name: name.to_string(),
+                variants: full_type
+                    .possible_types
+                    .iter()
+                    .flatten()
+                    .filter_map(|t| t.name.clone())
+                    .map(|member| VariantDef::new(member.clone(), vec![TypeShape::named(member).into()]))
+                    .collect(),
+            })),
+            TypeKind::Scalar | TypeKind::List | TypeKind::NonNull => None,
+        }
+    }
 }
 
 impl Default for GraphQLProvider {
@@ -32,17 +133,12 @@ impl TypeProvider for GraphQLProvider {
         "GraphQLProvider"
     }
 
-    fn resolve_schema(&self, source: &str, _params: &ProviderParams) -> ProviderResult<Schema> {
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
         // Parse GraphQL introspection response
         let json_str = if source.starts_with('{') {
             source.to_string()
-        } else if source.starts_with("file://") {
-            let path = source.strip_prefix("file://").unwrap();
-            std::fs::read_to_string(path)
-                .map_err(|e| ProviderError::IoError(e.to_string()))?
         } else {
-            std::fs::read_to_string(source)
-                .map_err(|e| ProviderError::IoError(e.to_string()))?
+            read_source(source, params)?
         };
 
         let value: serde_json::Value = serde_json::from_str(&json_str)
@@ -51,8 +147,140 @@ impl TypeProvider for GraphQLProvider {
         Ok(Schema::Custom(serde_json::to_string(&value).unwrap()))
     }
 
-    fn generate_types(&self, _schema: &Schema, _namespace: &str) -> ProviderResult<GeneratedTypes> {
-        // TODO: Implement full GraphQL introspection parsing
-        Ok(GeneratedTypes::new())
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let json = match schema {
+            Schema::Custom(s) => s,
+            _ => return Err(ProviderError::ParseError("Expected GraphQL schema".to_string())),
+        };
+
+        let schema = parse_introspection(json)?;
+        Ok(self.generate_from_schema(&schema, namespace))
+    }
+}
+
+impl SourceValidator for GraphQLProvider {}
+
+impl DescribedProvider for GraphQLProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+    }
+}
+
+/// Record fields of arguments or input object fields
+fn input_fields(values: &[InputValue]) -> Vec<(String, TypeExpr)> {
+    values
+        .iter()
+        .map(|v| (v.name.clone(), type_shape(&v.type_ref).into()))
+        .collect()
+}
+
+/// Shape of a type reference; nullable unless wrapped in `NON_NULL`
+fn type_shape(type_ref: &TypeRef) -> TypeShape {
+    match (type_ref.kind, &type_ref.of_type) {
+        (TypeKind::NonNull, Some(inner)) => non_null_shape(inner),
+        _ => non_null_shape(type_ref).option(),
+    }
+}
+
+fn non_null_shape(type_ref: &TypeRef) -> TypeShape {
+    match (type_ref.kind, &type_ref.of_type) {
+        (TypeKind::List, Some(element)) => type_shape(element).list(),
+        (TypeKind::NonNull, Some(inner)) => non_null_shape(inner),
+        _ => match type_ref.name.as_deref() {
+            Some("Int") => TypeShape::named("int"),
+            Some("Float") => TypeShape::named("float"),
+            Some("String") | Some("ID") => TypeShape::named("string"),
+            Some("Boolean") => TypeShape::named("bool"),
+            Some(name) if type_ref.kind != TypeKind::Scalar => TypeShape::named(name),
+            _ => TypeShape::named("any"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    const INTROSPECTION: &str = r#"{
+        "data": {
+            "__schema": {
+                "types": [
+                    {
+                        "kind": "OBJECT",
+                        "name": "Query",
+                        "fields": [
+                            {
+                                "name": "user",
+                                "args": [
+                                    { "name": "id", "type": { "kind": "NON_NULL", "name": null, "ofType": { "kind": "SCALAR", "name": "ID", "ofType": null } } }
+                                ],
+                                "type": { "kind": "OBJECT", "name": "User", "ofType": null }
+                            }
+                        ]
+                    },
+                    {
+                        "kind": "OBJECT",
+                        "name": "User",
+                        "fields": [
+                            { "name": "id", "args": [], "type": { "kind": "NON_NULL", "name": null, "ofType": { "kind": "SCALAR", "name": "ID", "ofType": null } } },
+                            { "name": "role", "args": [], "type": { "kind": "ENUM", "name": "Role", "ofType": null } },
+                            { "name": "tags", "args": [], "type": { "kind": "NON_NULL", "name": null, "ofType": { "kind": "LIST", "name": null, "ofType": { "kind": "SCALAR", "name": "String", "ofType": null } } } },
+                            { "name": "joined", "args": [], "type": { "kind": "SCALAR", "name": "DateTime", "ofType": null } }
+                        ]
+                    },
+                    { "kind": "ENUM", "name": "Role", "enumValues": [{ "name": "ADMIN" }, { "name": "MEMBER" }] },
+                    { "kind": "SCALAR", "name": "DateTime" },
+                    { "kind": "OBJECT", "name": "__Type", "fields": [] }
+                ]
+            }
+        }
+    }"#;
+
+    #[test]
+    fn test_generate_types() {
+        let provider = GraphQLProvider::new();
+        let schema = provider.resolve_schema(INTROSPECTION, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Api").unwrap();
+
+        let module = &types.modules[0];
+        assert_eq!(module.path, vec!["Api".to_string()]);
+
+        let names: Vec<&str> = module
+            .types
+            .iter()
+            .map(|t| match t {
+                TypeDefinition::Record(r) => r.name.as_str(),
+                TypeDefinition::Du(d) => d.name.as_str(),
+            })
+            .collect();
+        assert_eq!(names, ["Query", "User", "Role", "QueryUserArgs"]);
+
+        let TypeDefinition::Record(user) = &module.types[1] else {
+            panic!("Expected record");
+        };
+        let fields: Vec<String> = user.fields.iter().map(|(n, t)| format!("{}: {}", n, t)).collect();
+        assert_eq!(
+            fields,
+            ["id: string", "role: Role option", "tags: string option list", "joined: any option"]
+        );
+
+        let TypeDefinition::Record(args) = &module.types[3] else {
+            panic!("Expected record");
+        };
+        assert_eq!(args.fields[0].1.to_string(), "string");
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/graphql.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_rejects_non_introspection() {
+        let provider = GraphQLProvider::new();
+        let schema = provider.resolve_schema(r#"{"query": "{ user }"}"#, &ProviderParams::default()).unwrap();
+        assert!(provider.generate_types(&schema, "Api").is_err());
     }
 }
@@ -0,0 +1,353 @@
+//! HAR reading and endpoint grouping
+//!
+//! Entries are grouped into endpoints by host, method and path template.
+//! A path segment becomes a parameter when it looks like an identifier
+//! rather than a name: a number, a UUID, or a long hex string such as a
+//! hash or an ObjectId. `/users/42/orders/7` is `/users/{user_id}/orders/{order_id}`.
+//!
+//! Only API traffic is kept: entries with a JSON or form request body, or
+//! a response labelled JSON. Page loads, scripts and images are counted and
+//! skipped.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The endpoints of a capture, in the order first seen
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Traffic {
+    pub endpoints: Vec<Endpoint>,
+    /// Entries that were not API traffic
+    #[serde(default)]
+    pub skipped: usize,
+}
+
+/// Requests to the same host, method and path template
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Endpoint {
+    pub host: String,
+    /// Upper case
+    pub method: String,
+    /// `/users/{user_id}`
+    pub template: String,
+    /// Number of entries
+    pub entries: usize,
+    /// Path parameters in order, then query parameters
+    pub params: Vec<Param>,
+    /// One per entry with a request body
+    pub bodies: Vec<Body>,
+    /// One per entry, in capture order
+    pub responses: Vec<Response>,
+}
+
+/// A path or query parameter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Param {
+    pub name: String,
+    pub in_path: bool,
+    /// Values seen, one per entry that had it
+    pub values: Vec<String>,
+    /// Whether an entry repeated it, as in `?tag=a&tag=b`
+    #[serde(default)]
+    pub repeated: bool,
+}
+
+/// A captured response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Response {
+    pub status: u16,
+    pub body: Body,
+}
+
+/// A captured body
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Body {
+    Json { value: Value },
+    /// Form field names
+    Form { fields: Vec<String> },
+    /// Labelled JSON, but not
+    Invalid { error: String },
+    /// No content, or content the capture left out
+    Empty,
+    /// Some other media type
+    Other { mime_type: String },
+}
+
+/// Read a HAR capture and group its entries into endpoints
+pub fn parse_har(content: &str) -> ProviderResult<Traffic> {
+    let document: Value = serde_json::from_str(content)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid HAR: {}", e)))?;
+    let entries = document
+        .pointer("/log/entries")
+        .and_then(Value::as_array)
+        .ok_or_else(|| ProviderError::InvalidSource("Expected a HAR capture with `log.entries`".to_string()))?;
+
+    let mut traffic = Traffic::default();
+    for entry in entries {
+        let (Some(request), Some(response)) = (entry.get("request"), entry.get("response")) else {
+            traffic.skipped += 1;
+            continue;
+        };
+        let method = request.get("method").and_then(Value::as_str).unwrap_or("GET").to_uppercase();
+        let url = request.get("url").and_then(Value::as_str).unwrap_or_default();
+        let (host, path, query) = split_url(url);
+
+        let body = request.get("postData").map_or(Body::Empty, request_body);
+        let response_body = response.get("content").map_or(Body::Empty, response_body);
+        // An empty reply labelled JSON, as from a 404, is still API traffic
+        let json_response = response
+            .pointer("/content/mimeType")
+            .and_then(Value::as_str)
+            .is_some_and(|m| m.contains("json"));
+        if !matches!(body, Body::Json { .. } | Body::Form { .. } | Body::Invalid { .. }) && !json_response {
+            traffic.skipped += 1;
+            continue;
+        }
+
+        let (template, path_values) = template(path);
+        let index = match traffic
+            .endpoints
+            .iter()
+            .position(|e| e.host == host && e.method == method && e.template == template)
+        {
+            Some(index) => index,
+            None => {
+                let params = path_values.iter().map(|(name, _)| Param {
+                    name: name.clone(),
+                    in_path: true,
+                    values: Vec::new(),
+                    repeated: false,
+                });
+                traffic.endpoints.push(Endpoint {
+                    host: host.to_string(),
+                    method,
+                    template,
+                    entries: 0,
+                    params: params.collect(),
+                    bodies: Vec::new(),
+                    responses: Vec::new(),
+                });
+                traffic.endpoints.len() - 1
+            }
+        };
+        let endpoint = &mut traffic.endpoints[index];
+        endpoint.entries += 1;
+
+        for (name, value) in path_values {
+            if let Some(param) = endpoint.params.iter_mut().find(|p| p.in_path && p.name == name) {
+                param.values.push(value);
+            }
+        }
+        let query: Vec<(String, String)> = match request.get("queryString").and_then(Value::as_array) {
+            Some(pairs) if !pairs.is_empty() => pairs
+                .iter()
+                .filter_map(|p| Some((p.get("name")?.as_str()?.to_string(), p.get("value")?.as_str()?.to_string())))
+                .collect(),
+            _ => query,
+        };
+        let mut seen: Vec<String> = Vec::new();
+        for (name, value) in query {
+            let repeated = seen.contains(&name);
+            seen.push(name.clone());
+            match endpoint.params.iter_mut().find(|p| !p.in_path && p.name == name) {
+                // Count a repeated parameter's entry once, so it isn't
+                // taken for present more often than the endpoint was called
+                Some(param) if repeated => param.repeated = true,
+                Some(param) => param.values.push(value),
+                None => endpoint.params.push(Param { name, in_path: false, values: vec![value], repeated: false }),
+            }
+        }
+
+        if body != Body::Empty {
+            endpoint.bodies.push(body);
+        }
+        endpoint.responses.push(Response {
+            status: response.get("status").and_then(Value::as_u64).and_then(|s| u16::try_from(s).ok()).unwrap_or(0),
+            body: response_body,
+        });
+    }
+    Ok(traffic)
+}
+
+fn request_body(post_data: &Value) -> Body {
+    let mime_type = post_data.get("mimeType").and_then(Value::as_str).unwrap_or_default();
+    let text = post_data.get("text").and_then(Value::as_str).unwrap_or_default();
+    if mime_type.contains("x-www-form-urlencoded") || mime_type.contains("multipart/form-data") {
+        let mut fields: Vec<String> = match post_data.get("params").and_then(Value::as_array) {
+            Some(params) => params.iter().filter_map(|p| Some(p.get("name")?.as_str()?.to_string())).collect(),
+            None => text.split('&').filter_map(|pair| pair.split('=').next()).map(str::to_string).collect(),
+        };
+        fields.retain(|f| !f.is_empty());
+        fields.dedup();
+        return Body::Form { fields };
+    }
+    body(mime_type, text.to_string())
+}
+
+fn response_body(content: &Value) -> Body {
+    let mime_type = content.get("mimeType").and_then(Value::as_str).unwrap_or_default();
+    let Some(text) = content.get("text").and_then(Value::as_str) else {
+        return Body::Empty;
+    };
+    let text = if content.get("encoding").and_then(Value::as_str) == Some("base64") {
+        match STANDARD.decode(text.trim()).map(String::from_utf8) {
+            Ok(Ok(decoded)) => decoded,
+            _ => return Body::Other { mime_type: mime_type.to_string() },
+        }
+    } else {
+        text.to_string()
+    };
+    body(mime_type, text)
+}
+
+fn body(mime_type: &str, text: String) -> Body {
+    if text.trim().is_empty() {
+        return Body::Empty;
+    }
+    let mime_type = mime_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+    if !mime_type.contains("json") {
+        return Body::Other { mime_type };
+    }
+    match serde_json::from_str(&text) {
+        Ok(value) => Body::Json { value },
+        Err(e) => Body::Invalid { error: e.to_string() },
+    }
+}
+
+/// Host, path and query pairs of a URL
+fn split_url(url: &str) -> (&str, &str, Vec<(String, String)>) {
+    let url = url.split('#').next().unwrap_or_default();
+    let (url, query) = url.split_once('?').unwrap_or((url, ""));
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let (host, path) = match rest.find('/') {
+        Some(start) => (&rest[..start], &rest[start..]),
+        None => (rest, "/"),
+    };
+    let pairs = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode(name), decode(value))
+        })
+        .collect();
+    (host, path, pairs)
+}
+
+/// The template of a path, and the names and values of its parameters
+fn template(path: &str) -> (String, Vec<(String, String)>) {
+    let mut template = String::new();
+    let mut params: Vec<(String, String)> = Vec::new();
+    let mut previous = "";
+    for segment in path.split('/').filter(|s| !s.is_empty()) {
+        template.push('/');
+        if is_identifier(segment) {
+            let base = previous.strip_suffix("ies").map(|s| format!("{}y", s)).unwrap_or_else(|| {
+                previous.strip_suffix('s').unwrap_or(previous).to_string()
+            });
+            let base: String = base.chars().map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' }).collect();
+            let mut name = if base.is_empty() { "id".to_string() } else { format!("{}_id", base) };
+            if params.iter().any(|(existing, _)| existing == &name) {
+                name = format!("{}{}", name, params.len() + 1);
+            }
+            template.push_str(&format!("{{{}}}", name));
+            params.push((name, decode(segment)));
+        } else {
+            template.push_str(segment);
+            previous = segment;
+        }
+    }
+    if template.is_empty() {
+        template.push('/');
+    }
+    (template, params)
+}
+
+/// Whether a path segment holds a value rather than a name
+fn is_identifier(segment: &str) -> bool {
+    let hex = segment.chars().all(|c| c.is_ascii_hexdigit());
+    let uuid = segment.len() == 36
+        && segment.chars().enumerate().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        });
+    segment.chars().all(|c| c.is_ascii_digit())
+        || uuid
+        || (hex && segment.len() >= 16 && segment.chars().any(|c| c.is_ascii_digit()))
+}
+
+/// Percent-decode a URL component
+fn decode(component: &str) -> String {
+    let bytes = component.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match (escaped, bytes[i]) {
+            (Some(byte), _) => {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+            (None, b'+') => decoded.push(b' '),
+            (None, byte) => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(method: &str, url: &str, status: u16, mime_type: &str, text: &str) -> Value {
+        json!({
+            "request": {"method": method, "url": url, "headers": [], "queryString": []},
+            "response": {"status": status, "content": {"mimeType": mime_type, "text": text}}
+        })
+    }
+
+    #[test]
+    fn test_group_entries() {
+        let mut post = entry("POST", "https://api.shop.test/carts/9f8e7d6c5b4a39281706f5e4d3c2b1a0/items", 201, "application/json", "{}");
+        post["request"]["postData"] = json!({"mimeType": "application/json; charset=utf-8", "text": "{\"sku\": \"A1\"}"});
+        let mut encoded = entry("GET", "https://api.shop.test/users/7", 200, "application/json", "eyJpZCI6IDd9");
+        encoded["response"]["content"]["encoding"] = json!("base64");
+        let har = json!({"log": {"version": "1.2", "entries": [
+            entry("GET", "https://api.shop.test/users/42?expand=orders&tag=a&tag=b", 200, "application/json", "{\"id\": 42}"),
+            encoded,
+            entry("GET", "https://api.shop.test/users/123e4567-e89b-12d3-a456-426614174000", 404, "application/json; charset=utf-8", ""),
+            entry("GET", "https://shop.test/index.html", 200, "text/html", "<html></html>"),
+            entry("GET", "https://shop.test/logo.png", 200, "image/png", ""),
+            post,
+        ]}});
+
+        let traffic = parse_har(&har.to_string()).unwrap();
+        assert_eq!(traffic.skipped, 2);
+        let endpoints: Vec<(&str, &str, usize)> =
+            traffic.endpoints.iter().map(|e| (e.method.as_str(), e.template.as_str(), e.entries)).collect();
+        assert_eq!(
+            endpoints,
+            [("GET", "/users/{user_id}", 3), ("POST", "/carts/{cart_id}/items", 1)]
+        );
+
+        let users = &traffic.endpoints[0];
+        let params: Vec<(&str, bool, usize, bool)> =
+            users.params.iter().map(|p| (p.name.as_str(), p.in_path, p.values.len(), p.repeated)).collect();
+        assert_eq!(params, [("user_id", true, 3, false), ("expand", false, 1, false), ("tag", false, 1, true)]);
+        assert_eq!(users.responses[1].body, Body::Json { value: json!({"id": 7}) });
+        assert_eq!(users.responses[2], Response { status: 404, body: Body::Empty });
+        assert_eq!(traffic.endpoints[1].bodies, [Body::Json { value: json!({"sku": "A1"}) }]);
+
+        assert_eq!(decode("a%20b+c%2"), "a b c%2");
+        assert!(parse_har("{}").is_err());
+    }
+}
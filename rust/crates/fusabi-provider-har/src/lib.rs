@@ -0,0 +1,555 @@
+//! HAR Type Provider
+//!
+//! Generates Fusabi types from recorded HTTP traffic: a HAR (HTTP Archive)
+//! capture, as saved by browser developer tools, proxies such as mitmproxy
+//! or Charles, and API clients. Nothing has to be documented; the types
+//! describe what the API was seen to send and accept.
+//!
+//! # Generated types
+//!
+//! Entries are grouped into endpoints by method and path template, with
+//! identifier-like segments as parameters: `GET /users/42` and
+//! `GET /users/7` are both `GET /users/{user_id}`. Endpoints go to the
+//! `<Namespace>` module, or to `<Namespace>.<Host>` when the capture spans
+//! several hosts. For each endpoint:
+//!
+//! - `<Ep>Request` - a record with one field per path and query parameter,
+//!   plus `body` when requests carried one. Types come from the values
+//!   seen; a query parameter some requests lacked is optional
+//! - `<Ep>Response` - a union with one case per status code seen, such as
+//!   `Ok of GetUsersByUserIdOkBody` or `NotFound`
+//!
+//! `<Ep>` is named from the method and template (`GetUsersByUserId`). JSON
+//! bodies get records of their own (`<Ep>Body`, `<Ep><Status>Body`),
+//! unified across every entry: keys missing from some bodies, or `null` in
+//! some, are optional. The more traffic, the better the types; an
+//! endpoint seen once has every key it sent taken as required.
+//!
+//! # Params
+//!
+//! | Param  | Meaning                                                        |
+//! |--------|----------------------------------------------------------------|
+//! | `host` | Comma-separated hosts to keep; `shop.test` also keeps `api.shop.test` |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_har::HarProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = HarProvider::new();
+//! let params = ProviderParams::default().with("host", "api.shop.test");
+//! let schema = provider.resolve_schema("session.har", &params)?;
+//! let types = provider.generate_types(&schema, "Shop")?;
+//! ```
+
+mod archive;
+mod infer;
+
+pub use archive::{parse_har, Body, Endpoint, Param, Response, Traffic};
+pub use infer::{FieldShape, ObjectShape, Shape};
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceValidator, TypeShape,
+};
+use fusabi_type_providers::{
+    DuDef, GeneratedModule, GeneratedTypes, NamingStrategy, ProviderError, ProviderParams,
+    ProviderResult, RecordDef, Schema, TypeDefinition, TypeExpr, TypeGenerator, TypeProvider,
+    VariantDef,
+};
+
+/// HAR type provider
+pub struct HarProvider {
+    generator: TypeGenerator,
+}
+
+impl HarProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<Traffic> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected HAR traffic: {}", e)))
+    }
+
+    /// The modules of a capture, with the types of each and what was lost
+    /// on the way
+    fn generate(&self, traffic: &Traffic, namespace: &str) -> (Vec<GeneratedModule>, Vec<Diagnostic>) {
+        let mut generation = Generation {
+            generator: &self.generator,
+            types: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+        let several_hosts = traffic.endpoints.iter().any(|e| e.host != traffic.endpoints[0].host);
+
+        let mut modules: Vec<GeneratedModule> = Vec::new();
+        for endpoint in &traffic.endpoints {
+            let mut path = vec![namespace.to_string()];
+            if several_hosts {
+                path.push(generation.type_name(&endpoint.host, "Host"));
+            }
+
+            generation.endpoint(endpoint);
+            let types = std::mem::take(&mut generation.types);
+            match modules.iter_mut().find(|m| m.path == path) {
+                Some(module) => module.types.extend(types),
+                None => {
+                    let mut module = GeneratedModule::new(path);
+                    module.types = types;
+                    modules.push(module);
+                }
+            }
+        }
+        (modules, generation.diagnostics)
+    }
+}
+
+impl Default for HarProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for HarProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("HarProvider")
+            .param(
+                ParamSpec::new("host", ParamKind::List)
+                    .describe("Hosts to keep; `shop.test` also keeps `api.shop.test`"),
+            )
+    }
+}
+
+impl TypeProvider for HarProvider {
+    fn name(&self) -> &str {
+        "HarProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let mut traffic = if source.trim_start().starts_with('{') {
+            parse_har(source)?
+        } else {
+            parse_har(&read_source(source, params)?)?
+        };
+
+        if let Some(filter) = typed.list("host") {
+            let hosts: Vec<String> = filter.iter().map(|h| h.to_ascii_lowercase()).collect();
+            traffic.endpoints.retain(|e| {
+                let host = e.host.to_ascii_lowercase();
+                let host = host.split(':').next().unwrap_or_default();
+                hosts.iter().any(|h| host == h || host.ends_with(&format!(".{}", h)))
+            });
+        }
+
+        let json = serde_json::to_string(&traffic)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected HAR traffic".to_string()));
+        };
+
+        let mut result = GeneratedTypes::new();
+        result.modules = self.generate(&self.parse_schema(json)?, namespace).0;
+        Ok(result)
+    }
+}
+
+impl SourceValidator for HarProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected HAR traffic".to_string()));
+        };
+
+        let traffic = self.parse_schema(json)?;
+        let mut diagnostics = Vec::new();
+        if traffic.endpoints.is_empty() {
+            diagnostics.push(Diagnostic::warning("no-endpoints", "No JSON or form API traffic found"));
+        }
+        if traffic.skipped > 0 {
+            diagnostics.push(Diagnostic::info(
+                "entries-skipped",
+                format!("{} entries are not API traffic and were skipped", traffic.skipped),
+            ));
+        }
+        diagnostics.extend(self.generate(&traffic, "Har").1);
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for HarProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+/// Types generated for one endpoint, with what was lost on the way
+struct Generation<'a> {
+    generator: &'a TypeGenerator,
+    types: Vec<TypeDefinition>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Generation<'_> {
+    fn endpoint(&mut self, endpoint: &Endpoint) {
+        let location = format!("{} {}", endpoint.method, endpoint.template);
+        let name = self.type_name(&raw_name(endpoint), "Request");
+        if endpoint.entries == 1 {
+            self.diagnostics.push(
+                Diagnostic::info("single-entry", "Endpoint was seen once, so every key it sent is taken as required")
+                    .at(location.clone()),
+            );
+        }
+
+        let mut fields = Vec::new();
+        for param in &endpoint.params {
+            let ty = match Shape::merge_all(param.values.iter().map(|v| Shape::of_text(v))) {
+                Shape::Bool => "bool",
+                Shape::Int => "int",
+                Shape::Int64 => "int64",
+                Shape::Float => "float",
+                _ => "string",
+            };
+            let ty = if param.repeated { TypeShape::named(ty).list() } else { TypeShape::named(ty) };
+            let required = param.in_path || param.values.len() == endpoint.entries;
+            fields.push((param.name.clone(), optional(ty, required)));
+        }
+        if !endpoint.bodies.is_empty() {
+            let body = self.body(&endpoint.bodies, &format!("{}Body", name), &format!("{}.body", location));
+            fields.push(("body".to_string(), optional(body, endpoint.bodies.len() == endpoint.entries)));
+        }
+        self.types.push(TypeDefinition::Record(RecordDef {
+            name: format!("{}Request", name),
+            fields,
+        }));
+
+        let mut statuses: Vec<u16> = endpoint.responses.iter().map(|r| r.status).filter(|s| *s != 0).collect();
+        statuses.sort();
+        statuses.dedup();
+        let mut variants = Vec::new();
+        for status in statuses {
+            let variant = status_variant(status);
+            let bodies: Vec<Body> = endpoint
+                .responses
+                .iter()
+                .filter(|r| r.status == status && r.body != Body::Empty)
+                .map(|r| r.body.clone())
+                .collect();
+            if bodies.is_empty() {
+                variants.push(VariantDef::new_simple(variant));
+                continue;
+            }
+            let location = format!("{}.{}", location, variant);
+            let ty = self.body(&bodies, &format!("{}{}Body", name, variant), &location);
+            variants.push(VariantDef::new(variant, vec![ty.into()]));
+        }
+        if !variants.is_empty() {
+            self.types.push(TypeDefinition::Du(DuDef {
+                name: format!("{}Response", name),
+                variants,
+            }));
+        }
+    }
+
+    /// Type of the bodies of one request or response; JSON wins over
+    /// anything else seen, and `name` names its record
+    fn body(&mut self, bodies: &[Body], name: &str, location: &str) -> TypeShape {
+        let named = |name: &str| TypeShape::Named(name.to_string());
+        let values: Vec<&serde_json::Value> = bodies
+            .iter()
+            .filter_map(|b| match b {
+                Body::Json { value } => Some(value),
+                _ => None,
+            })
+            .collect();
+        if !values.is_empty() {
+            let shape = Shape::merge_all(values.into_iter().map(Shape::of));
+            return self.shape(&shape, name, location);
+        }
+
+        let mut fields: Vec<&String> = Vec::new();
+        for body in bodies {
+            match body {
+                Body::Form { fields: form } => {
+                    for field in form {
+                        if !fields.contains(&field) {
+                            fields.push(field);
+                        }
+                    }
+                }
+                Body::Invalid { error } => {
+                    self.diagnostics.push(
+                        Diagnostic::warning("invalid-json", format!("Body is labelled JSON but isn't: {}", error))
+                            .at(location.to_string()),
+                    );
+                    return named("any");
+                }
+                Body::Other { mime_type } => {
+                    self.diagnostics.push(
+                        Diagnostic::info("non-json-body", format!("`{}` body is typed as `string`", mime_type))
+                            .at(location.to_string()),
+                    );
+                    return named("string");
+                }
+                Body::Json { .. } | Body::Empty => {}
+            }
+        }
+        let fields = fields.into_iter().map(|f| (f.clone(), TypeShape::named("string").into())).collect();
+        self.types.push(TypeDefinition::Record(RecordDef { name: name.to_string(), fields }));
+        named(name)
+    }
+
+    fn shape(&mut self, shape: &Shape, name: &str, location: &str) -> TypeShape {
+        let named = |name: &str| TypeShape::Named(name.to_string());
+        match shape {
+            Shape::Bool => named("bool"),
+            Shape::Int => named("int"),
+            Shape::Int64 => named("int64"),
+            Shape::Float => named("float"),
+            Shape::String => named("string"),
+            Shape::List(element) => TypeShape::List(Box::new(self.shape(element, name, location))),
+            Shape::Object(object) => {
+                let mut fields = Vec::new();
+                for field in &object.fields {
+                    let nested = format!("{}{}", name, self.type_name(&field.name, "Field"));
+                    let shape = self.shape(&field.shape, &nested, &format!("{}.{}", location, field.name));
+                    fields.push((field.name.clone(), optional(shape, !field.is_optional(object.samples))));
+                }
+                self.types.push(TypeDefinition::Record(RecordDef {
+                    name: name.to_string(),
+                    fields,
+                }));
+                named(name)
+            }
+            Shape::Null => self.coerced(location, "Only `null` or empty lists were seen"),
+            Shape::Any => self.coerced(location, "Entries disagree on the type"),
+        }
+    }
+
+    fn coerced(&mut self, location: &str, reason: &str) -> TypeShape {
+        self.diagnostics.push(
+            Diagnostic::info("coerced-any", format!("{}; typed as `any`", reason)).at(location.to_string()),
+        );
+        TypeShape::Named("any".to_string())
+    }
+
+    /// PascalCase of a name that may hold any characters; `prefix` keeps
+    /// one that starts with a digit a valid identifier
+    fn type_name(&self, raw: &str, prefix: &str) -> String {
+        let words: String = raw.chars().map(|c| if c.is_alphanumeric() { c } else { ' ' }).collect();
+        let name = self.generator.naming.apply(&words);
+        if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+            format!("{}{}", prefix, name)
+        } else {
+            name
+        }
+    }
+}
+
+/// A name from the method and template: `get_users_by_user_id`
+fn raw_name(endpoint: &Endpoint) -> String {
+    let mut name = endpoint.method.to_ascii_lowercase();
+    let segments: Vec<&str> = endpoint.template.split('/').filter(|s| !s.is_empty()).collect();
+    if segments.is_empty() {
+        name.push_str("_root");
+    }
+    for segment in segments {
+        match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            Some(param) => {
+                name.push_str("_by_");
+                name.push_str(param);
+            }
+            None => {
+                name.push('_');
+                name.push_str(segment);
+            }
+        }
+    }
+    name
+}
+
+/// Case name for a response status code
+fn status_variant(status: u16) -> String {
+    let name = match status {
+        200 => "Ok",
+        201 => "Created",
+        202 => "Accepted",
+        204 => "NoContent",
+        301 => "MovedPermanently",
+        302 => "Found",
+        304 => "NotModified",
+        400 => "BadRequest",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "NotFound",
+        405 => "MethodNotAllowed",
+        409 => "Conflict",
+        410 => "Gone",
+        422 => "UnprocessableEntity",
+        429 => "TooManyRequests",
+        500 => "InternalServerError",
+        502 => "BadGateway",
+        503 => "ServiceUnavailable",
+        504 => "GatewayTimeout",
+        _ => return format!("Status{}", status),
+    };
+    name.to_string()
+}
+
+/// Mark a type optional unless the value is required
+fn optional(shape: TypeShape, required: bool) -> TypeExpr {
+    let required = required || shape.is_option();
+    shape.option_unless(required).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+    use serde_json::{json, Value};
+
+    fn entry(method: &str, url: &str, request_body: Option<Value>, status: u16, response_body: Value) -> Value {
+        let mut entry = json!({
+            "request": {"method": method, "url": url, "headers": []},
+            "response": {"status": status, "content": {"mimeType": "application/json", "text": response_body.to_string()}}
+        });
+        if let Some(body) = request_body {
+            entry["request"]["postData"] = json!({"mimeType": "application/json", "text": body.to_string()});
+        }
+        if response_body.is_null() {
+            entry["response"]["content"]["text"] = json!("");
+        }
+        entry
+    }
+
+    fn capture() -> String {
+        json!({"log": {"version": "1.2", "creator": {"name": "test"}, "entries": [
+            entry("GET", "https://api.shop.test/users/42?expand=orders", None, 200,
+                json!({"id": 42, "name": "Ada", "orders": [{"id": 1, "total": 9.5}]})),
+            entry("GET", "https://api.shop.test/users/7", None, 200,
+                json!({"id": 7, "name": "Bob", "nickname": null, "orders": []})),
+            entry("GET", "https://api.shop.test/users/8", None, 404, Value::Null),
+            entry("POST", "https://api.shop.test/orders", Some(json!({"sku": "A1", "qty": 2})), 201, json!({"id": 3})),
+            entry("POST", "https://api.shop.test/orders", Some(json!({"sku": "B2", "qty": 1, "note": "gift"})), 422,
+                json!({"error": "out of stock"})),
+            {"request": {"method": "GET", "url": "https://shop.test/"},
+             "response": {"status": 200, "content": {"mimeType": "text/html", "text": "<html></html>"}}},
+        ]}})
+        .to_string()
+    }
+
+    fn find<'a>(types: &'a GeneratedTypes, name: &str) -> &'a TypeDefinition {
+        types.modules[0]
+            .types
+            .iter()
+            .find(|t| match t {
+                TypeDefinition::Record(r) => r.name == name,
+                TypeDefinition::Du(d) => d.name == name,
+            })
+            .unwrap_or_else(|| panic!("{} not found", name))
+    }
+
+    fn fields(definition: &TypeDefinition) -> Vec<(String, String)> {
+        let TypeDefinition::Record(record) = definition else {
+            panic!("Expected record");
+        };
+        record.fields.iter().map(|(name, ty)| (name.clone(), ty.to_string())).collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect()
+    }
+
+    #[test]
+    fn test_har_types() {
+        let provider = HarProvider::new();
+        let schema = provider.resolve_schema(&capture(), &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Shop").unwrap();
+
+        assert_eq!(types.modules.len(), 1);
+        assert_eq!(
+            fields(find(&types, "GetUsersByUserIdRequest")),
+            pairs(&[("user_id", "int"), ("expand", "string option")])
+        );
+        assert_eq!(
+            fields(find(&types, "GetUsersByUserIdOkBody")),
+            pairs(&[
+                ("id", "int"),
+                ("name", "string"),
+                ("orders", "GetUsersByUserIdOkBodyOrders list"),
+                ("nickname", "any option"),
+            ])
+        );
+        let TypeDefinition::Du(response) = find(&types, "GetUsersByUserIdResponse") else {
+            panic!("Expected union");
+        };
+        let cases: Vec<&str> = response.variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(cases, ["Ok", "NotFound"]);
+        assert!(response.variants[1].fields.is_empty());
+
+        assert_eq!(fields(find(&types, "PostOrdersRequest")), pairs(&[("body", "PostOrdersBody")]));
+        assert_eq!(
+            fields(find(&types, "PostOrdersBody")),
+            pairs(&[("qty", "int"), ("sku", "string"), ("note", "string option")])
+        );
+        let TypeDefinition::Du(response) = find(&types, "PostOrdersResponse") else {
+            panic!("Expected union");
+        };
+        assert_eq!(response.variants[1].name, "UnprocessableEntity");
+        assert_eq!(response.variants[1].fields[0].to_string(), "PostOrdersUnprocessableEntityBody");
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/har.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_hosts() {
+        let har = json!({"log": {"entries": [
+            entry("GET", "https://api.shop.test/items", None, 200, json!([{"id": 1}])),
+            entry("GET", "http://localhost:8080/health", None, 200, json!({"ok": true})),
+        ]}})
+        .to_string();
+        let provider = HarProvider::new();
+        let schema = provider.resolve_schema(&har, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Api").unwrap();
+        let paths: Vec<&[String]> = types.modules.iter().map(|m| m.path.as_slice()).collect();
+        assert_eq!(paths, [&["Api", "ApiShopTest"][..], &["Api", "Localhost8080"]]);
+
+        let params = ProviderParams::default().with("host", "shop.test");
+        let schema = provider.resolve_schema(&har, &params).unwrap();
+        let types = provider.generate_types(&schema, "Api").unwrap();
+        assert_eq!(types.modules.len(), 1);
+        assert_eq!(types.modules[0].path, ["Api"]);
+        assert_eq!(fields(find(&types, "GetItemsOkBody")), pairs(&[("id", "int")]));
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = HarProvider::new();
+        let report = provider.validate_source(&capture(), &ProviderParams::default());
+        assert!(report.is_valid());
+
+        let codes: Vec<(&str, Option<&str>)> =
+            report.diagnostics.iter().map(|d| (d.code.as_str(), d.location.as_deref())).collect();
+        assert!(codes.contains(&("entries-skipped", None)));
+        assert!(codes.contains(&("coerced-any", Some("GET /users/{user_id}.Ok.nickname"))));
+        assert!(!codes.iter().any(|(code, _)| *code == "single-entry"));
+
+        let empty = r#"{"log": {"entries": []}}"#;
+        let report = provider.validate_source(empty, &ProviderParams::default());
+        assert!(report.diagnostics.iter().any(|d| d.code == "no-endpoints"));
+    }
+}
@@ -0,0 +1,315 @@
+//! The component catalog
+//!
+//! Sources and sinks are those of the Hibana sources and sinks providers,
+//! nested under `Sources` and `Sinks`; transforms are defined here. A
+//! component is a record the other records of its module don't refer to,
+//! so `MultilineConfig` and `LokiAuth` are settings, not components.
+//!
+//! The module says what a component carries: `Sources.Logs.FileLog` emits
+//! logs and `Sinks.Metrics.Datadog` accepts metrics. Logs sinks also take
+//! events, which are structured log records, and `Generic` sinks take
+//! anything.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use fusabi_provider_common::TypeShape;
+use fusabi_provider_hibana_sinks::HibanaSinksProvider;
+use fusabi_provider_hibana_sources::HibanaSourcesProvider;
+use fusabi_type_providers::{
+    GeneratedModule, ProviderResult, RecordDef, Schema, TypeDefinition, TypeExpr, TypeProvider,
+};
+
+/// A kind of telemetry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Telemetry {
+    Metrics,
+    Logs,
+    Traces,
+    Events,
+}
+
+impl Telemetry {
+    pub const ALL: [Telemetry; 4] = [Telemetry::Metrics, Telemetry::Logs, Telemetry::Traces, Telemetry::Events];
+
+    /// The variant name: `Metrics`
+    pub fn name(self) -> &'static str {
+        match self {
+            Telemetry::Metrics => "Metrics",
+            Telemetry::Logs => "Logs",
+            Telemetry::Traces => "Traces",
+            Telemetry::Events => "Events",
+        }
+    }
+
+    fn from_module(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+}
+
+impl fmt::Display for Telemetry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name().to_ascii_lowercase())
+    }
+}
+
+/// A source, transform or sink
+#[derive(Debug, Clone)]
+pub struct Component {
+    pub name: String,
+    /// `Hibana.Sources.Logs.FileLog`
+    pub qualified: String,
+    /// Settings, with references to settings types qualified
+    pub fields: Vec<(String, TypeExpr)>,
+    /// What it takes in; nothing for sources
+    pub accepts: Vec<Telemetry>,
+    /// What it sends on; `None` for what it takes in
+    pub emits: Option<Telemetry>,
+}
+
+impl Component {
+    /// Whether a configured `type` names this component. `FileLog` is
+    /// `file_log`, `file-log` or `filelog`, and a `Source` suffix may be
+    /// left off: `StatsDSource` is `statsd`.
+    pub fn matches(&self, component_type: &str) -> bool {
+        let wanted = normalize(component_type);
+        let name = normalize(&self.name);
+        wanted == name || name.strip_suffix("source") == Some(wanted.as_str())
+    }
+}
+
+/// Every component, and the modules that define them
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    pub modules: Vec<GeneratedModule>,
+    pub sources: Vec<Component>,
+    pub transforms: Vec<Component>,
+    pub sinks: Vec<Component>,
+}
+
+/// What a transform takes in and sends on
+struct TransformSpec {
+    name: &'static str,
+    accepts: &'static [Telemetry],
+    emits: Option<Telemetry>,
+    fields: &'static [(&'static str, &'static str)],
+}
+
+use Telemetry::{Events, Logs, Metrics, Traces};
+
+const TRANSFORMS: &[TransformSpec] = &[
+    TransformSpec {
+        name: "Filter",
+        accepts: &Telemetry::ALL,
+        emits: None,
+        fields: &[("condition", "string")],
+    },
+    TransformSpec {
+        name: "Remap",
+        accepts: &Telemetry::ALL,
+        emits: None,
+        fields: &[("source", "string"), ("dropOnError", "bool option")],
+    },
+    TransformSpec {
+        name: "Sample",
+        accepts: &[Logs, Traces, Events],
+        emits: None,
+        fields: &[("rate", "float"), ("key", "string option")],
+    },
+    TransformSpec {
+        name: "Dedupe",
+        accepts: &[Logs, Events],
+        emits: None,
+        fields: &[("fields", "List<string>"), ("cacheSize", "int option")],
+    },
+    TransformSpec {
+        name: "Redact",
+        accepts: &[Logs, Traces, Events],
+        emits: None,
+        fields: &[
+            ("patterns", "List<string>"),
+            ("fields", "List<string> option"),
+            ("replacement", "string option"),
+        ],
+    },
+    TransformSpec {
+        name: "Aggregate",
+        accepts: &[Metrics],
+        emits: None,
+        fields: &[("interval", "int"), ("groupBy", "List<string> option"), ("function", "string option")],
+    },
+    TransformSpec {
+        name: "LogToMetric",
+        accepts: &[Logs, Events],
+        emits: Some(Metrics),
+        fields: &[("metrics", "List<LogMetric>")],
+    },
+    TransformSpec {
+        name: "TailSampling",
+        accepts: &[Traces],
+        emits: None,
+        fields: &[("decisionWait", "int"), ("policies", "List<SamplingPolicy>")],
+    },
+];
+
+/// Settings types of transforms
+const TRANSFORM_SETTINGS: &[(&str, &[(&str, &str)])] = &[
+    (
+        "LogMetric",
+        &[
+            ("name", "string"),
+            ("metricType", "string"),
+            ("field", "string option"),
+            ("tags", "Map<string, string> option"),
+        ],
+    ),
+    (
+        "SamplingPolicy",
+        &[
+            ("name", "string"),
+            ("policyType", "string"),
+            ("rate", "float option"),
+            ("latencyThreshold", "int option"),
+            ("statusCodes", "List<string> option"),
+        ],
+    ),
+];
+
+/// Build the catalog, with its modules under `namespace`
+pub fn catalog(namespace: &str) -> ProviderResult<Catalog> {
+    let embedded = Schema::Custom("embedded".to_string());
+    let sources = HibanaSourcesProvider::new().generate_types(&embedded, "Sources")?;
+    let sinks = HibanaSinksProvider::new().generate_types(&embedded, "Sinks")?;
+
+    let mut catalog = Catalog::default();
+    // `Common` settings types of a side, by name, to qualify references to
+    let mut shared: HashMap<String, String> = HashMap::new();
+    for mut module in sources.modules.into_iter().chain(sinks.modules).chain([transforms_module()]) {
+        module.path.insert(0, namespace.to_string());
+        let prefix = module.path.join(".");
+        let records: Vec<&RecordDef> = module
+            .types
+            .iter()
+            .filter_map(|t| match t {
+                TypeDefinition::Record(record) => Some(record),
+                _ => None,
+            })
+            .collect();
+        let last = module.path.last().map(String::as_str).unwrap_or_default();
+        if last == "Common" {
+            shared.extend(records.iter().map(|r| (r.name.clone(), format!("{}.{}", prefix, r.name))));
+            catalog.modules.push(module);
+            continue;
+        }
+
+        let local: HashMap<&str, String> =
+            records.iter().map(|r| (r.name.as_str(), format!("{}.{}", prefix, r.name))).collect();
+        let referenced: HashSet<String> = records
+            .iter()
+            .flat_map(|r| &r.fields)
+            .flat_map(|(_, ty)| {
+                TypeShape::from_type_expr(ty).references().into_iter().map(str::to_string).collect::<Vec<_>>()
+            })
+            .collect();
+        let side = module.path[1].as_str();
+        for record in records.iter().filter(|r| !referenced.contains(&r.name)) {
+            let (accepts, emits) = match (side, Telemetry::from_module(last)) {
+                ("Sources", Some(kind)) => (Vec::new(), Some(kind)),
+                ("Sinks", Some(Logs)) => (vec![Logs, Events], None),
+                ("Sinks", Some(kind)) => (vec![kind], None),
+                ("Sinks", None) => (Telemetry::ALL.to_vec(), None),
+                _ => match TRANSFORMS.iter().find(|spec| spec.name == record.name) {
+                    Some(spec) => (spec.accepts.to_vec(), spec.emits),
+                    None => continue,
+                },
+            };
+            let lookup = |name: &str| local.get(name).or_else(|| shared.get(name)).cloned();
+            let component = Component {
+                name: record.name.clone(),
+                qualified: format!("{}.{}", prefix, record.name),
+                fields: record.fields.iter().map(|(field, ty)| (field.clone(), qualify(ty, lookup))).collect(),
+                accepts,
+                emits,
+            };
+            match side {
+                "Sources" => catalog.sources.push(component),
+                "Sinks" => catalog.sinks.push(component),
+                _ => catalog.transforms.push(component),
+            }
+        }
+        catalog.modules.push(module);
+    }
+    Ok(catalog)
+}
+
+fn transforms_module() -> GeneratedModule {
+    let record = |name: &str, fields: &[(&str, &str)]| {
+        TypeDefinition::Record(RecordDef {
+            name: name.to_string(),
+            fields: fields.iter().map(|(f, ty)| (f.to_string(), TypeShape::parse(ty).into())).collect(),
+        })
+    };
+    let mut module = GeneratedModule::new(vec!["Transforms".to_string()]);
+    module.types.extend(TRANSFORMS.iter().map(|spec| record(spec.name, spec.fields)));
+    module.types.extend(TRANSFORM_SETTINGS.iter().map(|(name, fields)| record(name, fields)));
+    module
+}
+
+/// Replace the type names `lookup` knows in a type expression:
+/// `LokiAuth option` → `Hibana.Sinks.Logs.LokiAuth option`
+fn qualify(ty: &TypeExpr, lookup: impl Fn(&str) -> Option<String>) -> TypeExpr {
+    let text = ty.to_string();
+    let qualified = text
+        .split_inclusive(|c: char| !c.is_ascii_alphanumeric())
+        .map(|piece| {
+            let end = piece.find(|c: char| !c.is_ascii_alphanumeric()).unwrap_or(piece.len());
+            match lookup(&piece[..end]) {
+                Some(name) => name + &piece[end..],
+                None => piece.to_string(),
+            }
+        })
+        .collect();
+    TypeExpr::Named(qualified)
+}
+
+/// `file_log` → `filelog`
+pub(crate) fn normalize(name: &str) -> String {
+    name.chars().filter(char::is_ascii_alphanumeric).map(|c| c.to_ascii_lowercase()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalog() {
+        let catalog = catalog("Hibana").unwrap();
+        let names = |components: &[Component]| components.iter().map(|c| c.name.clone()).collect::<Vec<_>>();
+
+        let sources = names(&catalog.sources);
+        assert!(sources.contains(&"FileLog".to_string()));
+        assert!(!sources.contains(&"MultilineConfig".to_string()));
+        assert!(!sources.contains(&"TlsConfig".to_string()));
+        assert!(!names(&catalog.sinks).contains(&"BasicAuth".to_string()));
+        assert!(!names(&catalog.transforms).contains(&"LogMetric".to_string()));
+
+        let statsd = catalog.sources.iter().find(|c| c.matches("statsd")).unwrap();
+        assert_eq!(statsd.qualified, "Hibana.Sources.Metrics.StatsDSource");
+        assert_eq!(statsd.emits, Some(Metrics));
+        let prometheus = catalog.sources.iter().find(|c| c.name == "PrometheusScrape").unwrap();
+        assert!(prometheus
+            .fields
+            .iter()
+            .any(|(_, ty)| ty.to_string() == "Hibana.Sources.Common.TlsConfig option"));
+
+        let loki = catalog.sinks.iter().find(|c| c.matches("loki")).unwrap();
+        assert_eq!(loki.accepts, [Logs, Events]);
+        assert!(loki.fields.iter().any(|(_, ty)| ty.to_string() == "Hibana.Sinks.Logs.LokiAuth option"));
+        let console = catalog.sinks.iter().find(|c| c.matches("console")).unwrap();
+        assert_eq!(console.accepts, Telemetry::ALL);
+
+        let log_to_metric = catalog.transforms.iter().find(|c| c.matches("log_to_metric")).unwrap();
+        assert_eq!(log_to_metric.emits, Some(Metrics));
+        assert_eq!(log_to_metric.fields[0].1.to_string(), "List<Hibana.Transforms.LogMetric>");
+    }
+}
@@ -0,0 +1,627 @@
+//! Hibana Pipeline Type Provider
+//!
+//! Generates the typed configuration model of a Hibana agent: the sources
+//! it collects from, the transforms in between, the sinks it ships to, and
+//! the pipeline wiring them together.
+//!
+//! # Sources
+//!
+//! - `embedded`: the component catalog and a pipeline over any components
+//! - A pipeline configuration, inline or from a file; see [`pipeline`].
+//!   Components are looked up in the catalog and checked for their
+//!   required settings, and every edge is checked by telemetry kind, so
+//!   host metrics can't feed a Loki sink and a cycle of transforms is an
+//!   error.
+//!
+//! # Generated Types
+//!
+//! The catalog (see [`catalog`]) under `<ns>.Sources`, `<ns>.Transforms`
+//! and `<ns>.Sinks`, and in `<ns>`, `Telemetry`: `Metrics`, `Logs`,
+//! `Traces` or `Events`.
+//!
+//! For `embedded`:
+//!
+//! - `SourceComponent`, `TransformComponent` and `SinkComponent`: unions
+//!   of the catalog's components
+//! - `TransformNode` and `SinkNode`: a component and its `inputs`
+//! - `Pipeline`: each section as a map from component id
+//!
+//! For a pipeline configuration, with names from component ids
+//! (`drop_debug` → `DropDebug`):
+//!
+//! - `SourceId`, `TransformId` and `SinkId`: a variant per component
+//! - `Input`: a source or transform id, what `inputs` lists
+//! - `<Id>Transform` and `<Id>Sink`: the component's settings and `inputs`
+//! - `Sources`, `Transforms` and `Sinks`: a field per component, typed as
+//!   its catalog record or its node
+//! - `Pipeline`: the sections
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_hibana_pipeline::HibanaPipelineProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = HibanaPipelineProvider::new();
+//! let schema = provider.resolve_schema("hibana/pipeline.yaml", &ProviderParams::default())?;
+//! let types = provider.generate_types(&schema, "Hibana")?;
+//! ```
+
+pub mod catalog;
+pub mod pipeline;
+
+pub use catalog::{catalog, Catalog, Component, Telemetry};
+pub use pipeline::{parse_pipeline, Node, PipelineConfig};
+
+use std::collections::{BTreeSet, HashMap};
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, SourceValidator,
+    TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, DuDef, VariantDef, TypeExpr, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use serde::{Deserialize, Serialize};
+
+/// A pipeline configuration, stored in [`Schema::Custom`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Resolved {
+    /// `None` for `embedded`
+    pipeline: Option<PipelineConfig>,
+}
+
+/// A configured component and its catalog entry
+struct Placed<'a> {
+    node: &'a Node,
+    section: &'static str,
+    /// `None` when its type isn't in the catalog
+    component: Option<&'a Component>,
+    /// Type name from the id
+    name: String,
+}
+
+impl Placed<'_> {
+    fn location(&self) -> String {
+        format!("{}.{}", self.section, self.node.id)
+    }
+}
+
+/// Hibana pipeline type provider
+pub struct HibanaPipelineProvider {
+    generator: TypeGenerator,
+}
+
+impl HibanaPipelineProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<Resolved> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected a Hibana pipeline: {}", e)))
+    }
+
+    fn generate(&self, resolved: &Resolved, namespace: &str) -> ProviderResult<(GeneratedTypes, Vec<Diagnostic>)> {
+        let catalog = catalog(namespace)?;
+        let mut diagnostics = Vec::new();
+
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+        module.types.push(TypeDefinition::Du(DuDef {
+            name: "Telemetry".to_string(),
+            variants: Telemetry::ALL.iter().map(|kind| VariantDef::new_simple(kind.name().to_string())).collect(),
+        }));
+        match &resolved.pipeline {
+            None => module.types.extend(component_types(&catalog)),
+            Some(pipeline) => {
+                let placed = self.place(&catalog, pipeline, &mut diagnostics);
+                check_edges(&placed, &mut diagnostics);
+                module.types.extend(pipeline_types(&placed));
+            }
+        }
+
+        let mut result = GeneratedTypes::new();
+        result.modules = catalog.modules;
+        result.modules.push(module);
+        Ok((result, diagnostics))
+    }
+
+    /// Look up each configured component in the catalog
+    fn place<'a>(
+        &self,
+        catalog: &'a Catalog,
+        pipeline: &'a PipelineConfig,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Vec<Placed<'a>> {
+        let sections = [
+            ("sources", &pipeline.sources, &catalog.sources),
+            ("transforms", &pipeline.transforms, &catalog.transforms),
+            ("sinks", &pipeline.sinks, &catalog.sinks),
+        ];
+        let mut placed: Vec<Placed> = Vec::new();
+        for (section, nodes, components) in sections {
+            for node in nodes {
+                let location = format!("{}.{}", section, node.id);
+                let name = self.generator.naming.apply(&node.id);
+                if let Some(other) = placed.iter().find(|p| p.node.id == node.id || p.name == name) {
+                    let message = if other.node.id == node.id {
+                        format!("`{}` is already the id of a component in `{}`", node.id, other.section)
+                    } else {
+                        format!("`{}` and `{}` would both be named `{}`", other.node.id, node.id, name)
+                    };
+                    diagnostics.push(Diagnostic::error("duplicate-id", message).at(location));
+                    continue;
+                }
+
+                let component = components.iter().find(|c| c.matches(&node.component_type));
+                match component {
+                    Some(component) => check_settings(node, component, &location, diagnostics),
+                    None => diagnostics.push(
+                        Diagnostic::error(
+                            "unknown-component",
+                            format!(
+                                "`{}` is not a {} in the catalog",
+                                node.component_type,
+                                section.trim_end_matches('s')
+                            ),
+                        )
+                        .at(location),
+                    ),
+                }
+                placed.push(Placed { node, section, component, name });
+            }
+        }
+        placed
+    }
+}
+
+fn check_settings(node: &Node, component: &Component, location: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let same = |a: &str, b: &str| catalog::normalize(a) == catalog::normalize(b);
+    for setting in &node.settings {
+        if !component.fields.iter().any(|(field, _)| same(field, setting)) {
+            diagnostics.push(
+                Diagnostic::warning("unknown-setting", format!("`{}` is not a setting of `{}`", setting, component.name))
+                    .at(location.to_string()),
+            );
+        }
+    }
+    for (field, ty) in &component.fields {
+        if !TypeShape::from_type_expr(ty).is_option() && !node.settings.iter().any(|s| same(field, s)) {
+            diagnostics.push(
+                Diagnostic::error("missing-setting", format!("`{}` requires `{}`", component.name, field))
+                    .at(location.to_string()),
+            );
+        }
+    }
+}
+
+/// Check that every input exists and carries what its reader accepts
+fn check_edges(placed: &[Placed], diagnostics: &mut Vec<Diagnostic>) {
+    let by_id: HashMap<&str, &Placed> = placed.iter().map(|p| (p.node.id.as_str(), p)).collect();
+    let readers = || placed.iter().filter(|p| p.section != "sources");
+
+    for reader in readers() {
+        if reader.node.inputs.is_empty() {
+            diagnostics.push(Diagnostic::error("no-inputs", "Reads from nothing; list components in `inputs`").at(reader.location()));
+        }
+        for input in &reader.node.inputs {
+            if by_id.get(input.as_str()).is_none_or(|upstream| upstream.section == "sinks") {
+                diagnostics.push(
+                    Diagnostic::error("undefined-input", format!("`{}` is not a source or transform", input))
+                        .at(reader.location()),
+                );
+            }
+        }
+    }
+
+    let mut emitted = HashMap::new();
+    for reader in readers() {
+        kinds_sent(reader, &by_id, &mut emitted, &mut Vec::new(), diagnostics);
+    }
+
+    for writer in placed.iter().filter(|p| p.section != "sinks") {
+        if !readers().any(|reader| reader.node.inputs.contains(&writer.node.id)) {
+            diagnostics.push(
+                Diagnostic::warning("unused-output", format!("No transform or sink reads from `{}`", writer.node.id))
+                    .at(writer.location()),
+            );
+        }
+    }
+}
+
+/// The kinds of telemetry a component sends on, checking those it
+/// receives along the way; `None` if unknown
+fn kinds_sent<'a>(
+    placed: &'a Placed<'a>,
+    by_id: &HashMap<&str, &'a Placed<'a>>,
+    emitted: &mut HashMap<&'a str, BTreeSet<Telemetry>>,
+    path: &mut Vec<&'a str>,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Option<BTreeSet<Telemetry>> {
+    let id = placed.node.id.as_str();
+    if let Some(kinds) = emitted.get(id) {
+        return Some(kinds.clone());
+    }
+    if let Some(start) = path.iter().position(|step| *step == id) {
+        let cycle: Vec<String> = path[start..].iter().chain([&id]).map(|step| format!("`{}`", step)).collect();
+        diagnostics.push(
+            Diagnostic::error("cycle", format!("{} form a cycle", cycle.join(" → "))).at(placed.location()),
+        );
+        return None;
+    }
+    let component = placed.component?;
+    if placed.section == "sources" {
+        return component.emits.map(|kind| BTreeSet::from([kind]));
+    }
+
+    path.push(id);
+    let mut received = BTreeSet::new();
+    for input in &placed.node.inputs {
+        let Some(upstream) = by_id.get(input.as_str()).filter(|upstream| upstream.section != "sinks") else {
+            continue;
+        };
+        let Some(kinds) = kinds_sent(upstream, by_id, emitted, path, diagnostics) else {
+            continue;
+        };
+        let rejected: Vec<String> =
+            kinds.iter().filter(|kind| !component.accepts.contains(kind)).map(Telemetry::to_string).collect();
+        if !rejected.is_empty() {
+            diagnostics.push(
+                Diagnostic::error(
+                    "kind-mismatch",
+                    format!("`{}` sends {}, which `{}` doesn't accept", input, rejected.join(" and "), component.name),
+                )
+                .at(placed.location()),
+            );
+        }
+        received.extend(kinds.into_iter().filter(|kind| component.accepts.contains(kind)));
+    }
+    path.pop();
+
+    let kinds = match component.emits {
+        Some(kind) => BTreeSet::from([kind]),
+        None => received,
+    };
+    emitted.insert(id, kinds.clone());
+    Some(kinds)
+}
+
+/// A pipeline over any of the catalog's components
+fn component_types(catalog: &Catalog) -> Vec<TypeDefinition> {
+    let union = |name: &str, components: &[Component]| {
+        TypeDefinition::Du(DuDef {
+            name: name.to_string(),
+            variants: components
+                .iter()
+                .map(|c| VariantDef::new(c.name.clone(), vec![TypeShape::named(c.qualified.clone()).into()]))
+                .collect(),
+        })
+    };
+    vec![
+        union("SourceComponent", &catalog.sources),
+        union("TransformComponent", &catalog.transforms),
+        union("SinkComponent", &catalog.sinks),
+        record("TransformNode", &[("inputs", "string list"), ("component", "TransformComponent")]),
+        record("SinkNode", &[("inputs", "string list"), ("component", "SinkComponent")]),
+        record(
+            "Pipeline",
+            &[
+                ("sources", "Map<string, SourceComponent>"),
+                ("transforms", "Map<string, TransformNode> option"),
+                ("sinks", "Map<string, SinkNode>"),
+            ],
+        ),
+    ]
+}
+
+/// A pipeline of the configured components
+fn pipeline_types(placed: &[Placed]) -> Vec<TypeDefinition> {
+    let sections = [
+        ("sources", "Source", ""),
+        ("transforms", "Transform", "Transform"),
+        ("sinks", "Sink", "Sink"),
+    ];
+    let in_section = |section: &str| -> Vec<(&Placed, &Component)> {
+        placed.iter().filter(|p| p.section == section).filter_map(|p| Some((p, p.component?))).collect()
+    };
+
+    let mut types = Vec::new();
+    let mut inputs = Vec::new();
+    for (section, label, _) in sections {
+        let nodes = in_section(section);
+        if nodes.is_empty() {
+            continue;
+        }
+        let id = format!("{}Id", label);
+        types.push(TypeDefinition::Du(DuDef {
+            name: id.clone(),
+            variants: nodes.iter().map(|(p, _)| VariantDef::new_simple(p.name.clone())).collect(),
+        }));
+        if section != "sinks" {
+            inputs.push(VariantDef::new(label.to_string(), vec![TypeShape::named(id).into()]));
+        }
+    }
+    if !inputs.is_empty() {
+        types.push(TypeDefinition::Du(DuDef { name: "Input".to_string(), variants: inputs }));
+    }
+
+    let mut pipeline = Vec::new();
+    for (section, label, suffix) in sections {
+        let nodes = in_section(section);
+        if nodes.is_empty() {
+            continue;
+        }
+        let mut fields = Vec::new();
+        for (p, component) in nodes {
+            let ty = if suffix.is_empty() {
+                component.qualified.clone()
+            } else {
+                let name = format!("{}{}", p.name, suffix);
+                let mut node_fields = vec![("inputs".to_string(), TypeShape::named("Input").list().into())];
+                node_fields.extend(component.fields.iter().cloned());
+                types.push(TypeDefinition::Record(RecordDef { name: name.clone(), fields: node_fields }));
+                name
+            };
+            fields.push((field_name(&p.node.id), TypeShape::named(ty).into()));
+        }
+        let name = format!("{}s", label);
+        types.push(TypeDefinition::Record(RecordDef { name: name.clone(), fields }));
+        pipeline.push((section.to_string(), TypeShape::named(name).into()));
+    }
+    types.push(TypeDefinition::Record(RecordDef { name: "Pipeline".to_string(), fields: pipeline }));
+    types
+}
+
+fn record(name: &str, fields: &[(&str, &str)]) -> TypeDefinition {
+    TypeDefinition::Record(RecordDef {
+        name: name.to_string(),
+        fields: fields.iter().map(|(field, ty)| (field.to_string(), TypeShape::parse(ty).into())).collect(),
+    })
+}
+
+/// `app-logs` → `app_logs`
+fn field_name(id: &str) -> String {
+    id.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect()
+}
+
+impl Default for HibanaPipelineProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypeProvider for HibanaPipelineProvider {
+    fn name(&self) -> &str {
+        "HibanaPipelineProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let pipeline = if source == "embedded" {
+            None
+        } else if source.trim_start().starts_with('{') || source.contains('\n') {
+            Some(parse_pipeline(source)?)
+        } else {
+            Some(parse_pipeline(&read_source(source, params)?)?)
+        };
+
+        let json = serde_json::to_string(&Resolved { pipeline })
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected a Hibana pipeline".to_string()));
+        };
+
+        Ok(self.generate(&self.parse_schema(json)?, namespace)?.0)
+    }
+}
+
+impl SourceValidator for HibanaPipelineProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected a Hibana pipeline".to_string()));
+        };
+
+        Ok(self.generate(&self.parse_schema(json)?, "Hibana")?.1)
+    }
+}
+
+impl DescribedProvider for HibanaPipelineProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .embedded("embedded")
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    const PIPELINE: &str = r#"
+sources:
+  app_logs: {type: file_log, path: /var/log/app.log}
+  host: {type: host_metrics, interval: 10, collectors: [cpu, memory]}
+  audit: {type: audit}
+transforms:
+  drop_debug: {type: filter, inputs: [app_logs], condition: 'level != "debug"'}
+  errors: {type: log_to_metric, inputs: [drop_debug], metrics: []}
+sinks:
+  loki: {type: loki, inputs: [drop_debug, audit], endpoint: "http://loki:3100"}
+  prometheus:
+    type: prometheus_remote_write
+    inputs: [host, errors]
+    endpoint: "http://prometheus:9090/api/v1/write"
+"#;
+
+    fn root(types: &GeneratedTypes) -> &GeneratedModule {
+        types.modules.last().unwrap()
+    }
+
+    fn fields(types: &GeneratedTypes, name: &str) -> Vec<(String, String)> {
+        root(types)
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+            .fields
+            .iter()
+            .map(|(field, ty)| (field.clone(), ty.to_string()))
+            .collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(f, t)| (f.to_string(), t.to_string())).collect()
+    }
+
+    fn variants(types: &GeneratedTypes, name: &str) -> Vec<String> {
+        root(types)
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Du(du) if du.name == name => Some(du),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected union {}", name))
+            .variants
+            .iter()
+            .map(|v| v.name.clone())
+            .collect()
+    }
+
+    fn diagnostics(provider: &HibanaPipelineProvider, source: &str) -> Vec<(String, String)> {
+        let schema = provider.resolve_schema(source, &ProviderParams::default()).unwrap();
+        provider
+            .schema_diagnostics(&schema)
+            .unwrap()
+            .into_iter()
+            .map(|d| (d.code, d.location.unwrap_or_default()))
+            .collect()
+    }
+
+    #[test]
+    fn test_embedded_types() {
+        let provider = HibanaPipelineProvider::new();
+        let schema = provider.resolve_schema("embedded", &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Hibana").unwrap();
+
+        let paths: Vec<String> = types.modules.iter().map(|m| m.path.join(".")).collect();
+        assert!(paths.contains(&"Hibana.Sources.Traces".to_string()));
+        assert!(paths.contains(&"Hibana.Sinks.Traces".to_string()));
+        assert!(paths.contains(&"Hibana.Transforms".to_string()));
+
+        assert_eq!(variants(&types, "Telemetry"), ["Metrics", "Logs", "Traces", "Events"]);
+        assert!(variants(&types, "SourceComponent").contains(&"Jaeger".to_string()));
+        assert!(variants(&types, "SinkComponent").contains(&"Jaeger".to_string()));
+        assert_eq!(fields(&types, "SinkNode"), pairs(&[("inputs", "string list"), ("component", "SinkComponent")]));
+        assert_eq!(fields(&types, "Pipeline")[0], ("sources".to_string(), "Map<string, SourceComponent>".to_string()));
+        assert!(provider.schema_diagnostics(&schema).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_pipeline_types() {
+        let provider = HibanaPipelineProvider::new();
+        let schema = provider.resolve_schema(PIPELINE, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Hibana").unwrap();
+
+        assert_eq!(variants(&types, "SourceId"), ["AppLogs", "Host", "Audit"]);
+        assert_eq!(variants(&types, "TransformId"), ["DropDebug", "Errors"]);
+        assert_eq!(variants(&types, "SinkId"), ["Loki", "Prometheus"]);
+        assert_eq!(variants(&types, "Input"), ["Source", "Transform"]);
+        assert_eq!(
+            fields(&types, "Sources"),
+            pairs(&[
+                ("app_logs", "Hibana.Sources.Logs.FileLog"),
+                ("host", "Hibana.Sources.Metrics.HostMetrics"),
+                ("audit", "Hibana.Sources.Events.Audit"),
+            ])
+        );
+        assert_eq!(
+            fields(&types, "DropDebugTransform"),
+            pairs(&[("inputs", "Input list"), ("condition", "string")])
+        );
+        let loki = fields(&types, "LokiSink");
+        assert_eq!(loki[0], ("inputs".to_string(), "Input list".to_string()));
+        assert!(loki.contains(&("auth".to_string(), "Hibana.Sinks.Logs.LokiAuth option".to_string())));
+        assert_eq!(fields(&types, "Sinks")[1], ("prometheus".to_string(), "PrometheusSink".to_string()));
+        assert_eq!(
+            fields(&types, "Pipeline"),
+            pairs(&[("sources", "Sources"), ("transforms", "Transforms"), ("sinks", "Sinks")])
+        );
+
+        assert!(diagnostics(&provider, PIPELINE).is_empty());
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/hibana-pipeline.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_pipeline_diagnostics() {
+        let provider = HibanaPipelineProvider::new();
+        let pipeline = r#"
+sources:
+  host: {type: host_metrics, interval: 10, collectors: [cpu], scrape: true}
+  spans: {type: otlp_trace}
+  journal: {type: journald}
+transforms:
+  a: {type: sample, inputs: [b, host], rate: 0.1}
+  b: {type: remap, inputs: [a], source: "."}
+  agg: {type: rollup, inputs: [host]}
+sinks:
+  logs: {type: loki, inputs: [host, nowhere], endpoint: "http://loki:3100"}
+  all: {type: console, inputs: []}
+  traces: {type: tempo, inputs: [spans, logs], endpoint: "http://tempo:4317"}
+  journal: {type: console, inputs: [journal]}
+"#;
+
+        assert_eq!(
+            diagnostics(&provider, pipeline),
+            pairs(&[
+                ("unknown-setting", "sources.host"),
+                ("missing-setting", "sources.spans"),
+                ("missing-setting", "sources.spans"),
+                ("unknown-component", "transforms.agg"),
+                ("duplicate-id", "sinks.journal"),
+                ("undefined-input", "sinks.logs"),
+                ("no-inputs", "sinks.all"),
+                ("undefined-input", "sinks.traces"),
+                ("cycle", "transforms.a"),
+                ("kind-mismatch", "transforms.a"),
+                ("kind-mismatch", "sinks.logs"),
+                ("unused-output", "sources.journal"),
+                ("unused-output", "transforms.agg"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_file_source() {
+        let path = std::env::temp_dir().join(format!("fusabi-hibana-pipeline-{}.yaml", std::process::id()));
+        std::fs::write(&path, PIPELINE).unwrap();
+
+        let provider = HibanaPipelineProvider::new();
+        let schema = provider.resolve_schema(path.to_str().unwrap(), &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Agent").unwrap();
+        assert_eq!(fields(&types, "Sources")[0].1, "Agent.Sources.Logs.FileLog");
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(provider.resolve_schema("nowhere.yaml", &ProviderParams::default()).is_err());
+    }
+}
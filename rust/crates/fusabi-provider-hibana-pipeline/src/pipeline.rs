@@ -0,0 +1,133 @@
+//! Pipeline configuration
+//!
+//! An agent's pipeline, in YAML or JSON: a mapping per section from
+//! component id to its `type` and settings. Transforms and sinks name the
+//! components they read from in `inputs`.
+//!
+//! ```yaml
+//! sources:
+//!   app_logs: {type: file_log, path: /var/log/app.log}
+//!   host: {type: host_metrics, interval: 10, collectors: [cpu, memory]}
+//! transforms:
+//!   drop_debug: {type: filter, inputs: [app_logs], condition: 'level != "debug"'}
+//! sinks:
+//!   loki: {type: loki, inputs: [drop_debug], endpoint: "http://loki:3100"}
+//! ```
+
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+/// The components of a pipeline, in the order they are configured
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PipelineConfig {
+    pub sources: Vec<Node>,
+    pub transforms: Vec<Node>,
+    pub sinks: Vec<Node>,
+}
+
+/// A configured component
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Node {
+    pub id: String,
+    pub component_type: String,
+    pub inputs: Vec<String>,
+    /// Names of the other settings
+    pub settings: Vec<String>,
+}
+
+/// Parse a pipeline configuration
+pub fn parse_pipeline(content: &str) -> ProviderResult<PipelineConfig> {
+    let document: Value = serde_yaml::from_str(content)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid pipeline configuration: {}", e)))?;
+    if document.get("sources").is_none() && document.get("sinks").is_none() {
+        return Err(ProviderError::ParseError("Expected a pipeline with `sources` and `sinks`".to_string()));
+    }
+
+    Ok(PipelineConfig {
+        sources: nodes(&document, "sources", "Source")?,
+        transforms: nodes(&document, "transforms", "Transform")?,
+        sinks: nodes(&document, "sinks", "Sink")?,
+    })
+}
+
+fn nodes(document: &Value, section: &str, label: &str) -> ProviderResult<Vec<Node>> {
+    let components = match document.get(section) {
+        None | Some(Value::Null) => return Ok(Vec::new()),
+        Some(Value::Mapping(components)) => components,
+        Some(_) => {
+            return Err(ProviderError::ParseError(format!(
+                "`{}` must be a mapping of component id to settings",
+                section
+            )))
+        }
+    };
+
+    components
+        .iter()
+        .map(|(id, settings)| {
+            let id = id
+                .as_str()
+                .ok_or_else(|| ProviderError::ParseError(format!("Component ids must be strings, got {:?}", id)))?;
+            let error = |message: &str| ProviderError::ParseError(format!("{} `{}` {}", label, id, message));
+            let Value::Mapping(settings) = settings else {
+                return Err(error("must be a mapping of settings"));
+            };
+
+            let mut node = Node {
+                id: id.to_string(),
+                component_type: String::new(),
+                inputs: Vec::new(),
+                settings: Vec::new(),
+            };
+            for (key, value) in settings {
+                match key.as_str() {
+                    Some("type") => {
+                        node.component_type = value.as_str().ok_or_else(|| error("has a `type` that isn't a string"))?.to_string()
+                    }
+                    Some("inputs") if section != "sources" => {
+                        node.inputs = value
+                            .as_sequence()
+                            .and_then(|inputs| inputs.iter().map(|i| i.as_str().map(str::to_string)).collect())
+                            .ok_or_else(|| error("must list component ids in `inputs`"))?
+                    }
+                    Some(key) => node.settings.push(key.to_string()),
+                    None => return Err(error("has a setting name that isn't a string")),
+                }
+            }
+            if node.component_type.is_empty() {
+                return Err(error("has no `type`"));
+            }
+            Ok(node)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pipeline() {
+        let pipeline = parse_pipeline(
+            r#"
+sources:
+  app_logs: {type: file_log, paths: [/var/log/app.log], inputs: [x]}
+transforms:
+sinks:
+  out: {type: console, inputs: [app_logs]}
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(pipeline.sources[0].component_type, "file_log");
+        assert_eq!(pipeline.sources[0].settings, ["paths", "inputs"]);
+        assert!(pipeline.transforms.is_empty());
+        assert_eq!(pipeline.sinks[0].inputs, ["app_logs"]);
+
+        let error = parse_pipeline("sinks:\n  out: {inputs: [a]}\n").unwrap_err();
+        assert!(error.to_string().contains("Sink `out` has no `type`"));
+        assert!(parse_pipeline("sinks:\n  out: {type: console, inputs: a}\n").is_err());
+        assert!(parse_pipeline("keys: {}").is_err());
+    }
+}
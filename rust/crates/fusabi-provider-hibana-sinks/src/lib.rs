@@ -3,14 +3,43 @@
 //! Generates Fusabi types for Hibana observability agent data sinks.
 //! Hibana is a Fusabi-powered observability agent that supports various
 //! destinations for metrics, logs, and traces.
+//!
+//! Each sink category (`metrics`, `logs`, `traces`, `generic`) is behind
+//! the cargo feature of the same name, all enabled by default, and becomes
+//! one module of the `embedded` source. [`embedded_packs`] reports which
+//! are compiled in.
+//!
+//! With `module_layout=flat` (see [`GroupedProvider`]), all categories share
+//! the namespace module instead.
 
+use fusabi_provider_common::{
+    Capabilities, DescribedProvider, GroupedProvider, ListSyntax, ModuleBuilder, ModuleGrouping,
+    SourceValidator, TypeShape,
+};
 use fusabi_type_providers::{
     TypeProvider, ProviderParams, Schema,
     GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
-    RecordDef, TypeExpr, TypeDefinition,
+    RecordDef, TypeDefinition,
     ProviderError, ProviderResult,
 };
 
+/// Lists are written `List<T>`, as in the published sink types
+const LISTS: ListSyntax = ListSyntax::Generic("List");
+
+/// Embedded sink categories compiled into this build
+pub fn embedded_packs() -> &'static [&'static str] {
+    &[
+        #[cfg(feature = "metrics")]
+        "metrics",
+        #[cfg(feature = "logs")]
+        "logs",
+        #[cfg(feature = "traces")]
+        "traces",
+        #[cfg(feature = "generic")]
+        "generic",
+    ]
+}
+
 /// Hibana Sinks type provider
 pub struct HibanaSinksProvider {
     #[allow(dead_code)]
@@ -24,6 +53,7 @@ impl HibanaSinksProvider {
         }
     }
 
+    #[cfg(feature = "metrics")]
     /// Generate metrics sink types
     fn generate_metrics_sinks(&self, namespace: &str) -> GeneratedModule {
         let mut module = GeneratedModule::new(vec![namespace.to_string(), "Metrics".to_string()]);
@@ -32,11 +62,11 @@ impl HibanaSinksProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "PrometheusRemoteWrite".to_string(),
             fields: vec![
-                ("endpoint".to_string(), TypeExpr::Named("string".to_string())),
-                ("headers".to_string(), TypeExpr::Named("Map<string, string> option".to_string())),
-                ("batchSize".to_string(), TypeExpr::Named("int option".to_string())),
-                ("timeout".to_string(), TypeExpr::Named("int option".to_string())),
-                ("compressionEnabled".to_string(), TypeExpr::Named("bool option".to_string())),
+                ("endpoint".to_string(), TypeShape::named("string").into()),
+                ("headers".to_string(), TypeShape::map(TypeShape::named("string"), TypeShape::named("string")).option().into()),
+                ("batchSize".to_string(), TypeShape::named("int").option().into()),
+                ("timeout".to_string(), TypeShape::named("int").option().into()),
+                ("compressionEnabled".to_string(), TypeShape::named("bool").option().into()),
             ],
         }));
 
@@ -44,15 +74,15 @@ impl HibanaSinksProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "InfluxDb".to_string(),
             fields: vec![
-                ("url".to_string(), TypeExpr::Named("string".to_string())),
-                ("database".to_string(), TypeExpr::Named("string".to_string())),
-                ("org".to_string(), TypeExpr::Named("string option".to_string())),
-                ("bucket".to_string(), TypeExpr::Named("string option".to_string())),
-                ("token".to_string(), TypeExpr::Named("string option".to_string())),
-                ("username".to_string(), TypeExpr::Named("string option".to_string())),
-                ("password".to_string(), TypeExpr::Named("string option".to_string())),
-                ("precision".to_string(), TypeExpr::Named("string option".to_string())),
-                ("batchSize".to_string(), TypeExpr::Named("int option".to_string())),
+                ("url".to_string(), TypeShape::named("string").into()),
+                ("database".to_string(), TypeShape::named("string").into()),
+                ("org".to_string(), TypeShape::named("string").option().into()),
+                ("bucket".to_string(), TypeShape::named("string").option().into()),
+                ("token".to_string(), TypeShape::named("string").option().into()),
+                ("username".to_string(), TypeShape::named("string").option().into()),
+                ("password".to_string(), TypeShape::named("string").option().into()),
+                ("precision".to_string(), TypeShape::named("string").option().into()),
+                ("batchSize".to_string(), TypeShape::named("int").option().into()),
             ],
         }));
 
@@ -60,18 +90,19 @@ impl HibanaSinksProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "Datadog".to_string(),
             fields: vec![
-                ("apiKey".to_string(), TypeExpr::Named("string".to_string())),
-                ("site".to_string(), TypeExpr::Named("string option".to_string())),
-                ("endpoint".to_string(), TypeExpr::Named("string option".to_string())),
-                ("namespace".to_string(), TypeExpr::Named("string option".to_string())),
-                ("tags".to_string(), TypeExpr::Named("List<string> option".to_string())),
-                ("batchSize".to_string(), TypeExpr::Named("int option".to_string())),
+                ("apiKey".to_string(), TypeShape::named("string").into()),
+                ("site".to_string(), TypeShape::named("string").option().into()),
+                ("endpoint".to_string(), TypeShape::named("string").option().into()),
+                ("namespace".to_string(), TypeShape::named("string").option().into()),
+                ("tags".to_string(), TypeShape::named("string").list().option().into_type_expr(LISTS)),
+                ("batchSize".to_string(), TypeShape::named("int").option().into()),
             ],
         }));
 
         module
     }
 
+    #[cfg(feature = "logs")]
     /// Generate logs sink types
     fn generate_logs_sinks(&self, namespace: &str) -> GeneratedModule {
         let mut module = GeneratedModule::new(vec![namespace.to_string(), "Logs".to_string()]);
@@ -80,12 +111,12 @@ impl HibanaSinksProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "Elasticsearch".to_string(),
             fields: vec![
-                ("hosts".to_string(), TypeExpr::Named("List<string>".to_string())),
-                ("index".to_string(), TypeExpr::Named("string".to_string())),
-                ("auth".to_string(), TypeExpr::Named("ElasticsearchAuth option".to_string())),
-                ("bulkSize".to_string(), TypeExpr::Named("int option".to_string())),
-                ("timeout".to_string(), TypeExpr::Named("int option".to_string())),
-                ("tlsVerify".to_string(), TypeExpr::Named("bool option".to_string())),
+                ("hosts".to_string(), TypeShape::named("string").list().into_type_expr(LISTS)),
+                ("index".to_string(), TypeShape::named("string").into()),
+                ("auth".to_string(), TypeShape::named("ElasticsearchAuth").option().into()),
+                ("bulkSize".to_string(), TypeShape::named("int").option().into()),
+                ("timeout".to_string(), TypeShape::named("int").option().into()),
+                ("tlsVerify".to_string(), TypeShape::named("bool").option().into()),
             ],
         }));
 
@@ -93,9 +124,9 @@ impl HibanaSinksProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "ElasticsearchAuth".to_string(),
             fields: vec![
-                ("username".to_string(), TypeExpr::Named("string option".to_string())),
-                ("password".to_string(), TypeExpr::Named("string option".to_string())),
-                ("apiKey".to_string(), TypeExpr::Named("string option".to_string())),
+                ("username".to_string(), TypeShape::named("string").option().into()),
+                ("password".to_string(), TypeShape::named("string").option().into()),
+                ("apiKey".to_string(), TypeShape::named("string").option().into()),
             ],
         }));
 
@@ -103,12 +134,12 @@ impl HibanaSinksProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "Loki".to_string(),
             fields: vec![
-                ("endpoint".to_string(), TypeExpr::Named("string".to_string())),
-                ("labels".to_string(), TypeExpr::Named("Map<string, string> option".to_string())),
-                ("tenantId".to_string(), TypeExpr::Named("string option".to_string())),
-                ("batchSize".to_string(), TypeExpr::Named("int option".to_string())),
-                ("timeout".to_string(), TypeExpr::Named("int option".to_string())),
-                ("auth".to_string(), TypeExpr::Named("LokiAuth option".to_string())),
+                ("endpoint".to_string(), TypeShape::named("string").into()),
+                ("labels".to_string(), TypeShape::map(TypeShape::named("string"), TypeShape::named("string")).option().into()),
+                ("tenantId".to_string(), TypeShape::named("string").option().into()),
+                ("batchSize".to_string(), TypeShape::named("int").option().into()),
+                ("timeout".to_string(), TypeShape::named("int").option().into()),
+                ("auth".to_string(), TypeShape::named("LokiAuth").option().into()),
             ],
         }));
 
@@ -116,9 +147,9 @@ impl HibanaSinksProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "LokiAuth".to_string(),
             fields: vec![
-                ("username".to_string(), TypeExpr::Named("string option".to_string())),
-                ("password".to_string(), TypeExpr::Named("string option".to_string())),
-                ("bearerToken".to_string(), TypeExpr::Named("string option".to_string())),
+                ("username".to_string(), TypeShape::named("string").option().into()),
+                ("password".to_string(), TypeShape::named("string").option().into()),
+                ("bearerToken".to_string(), TypeShape::named("string").option().into()),
             ],
         }));
 
@@ -126,14 +157,14 @@ impl HibanaSinksProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "S3".to_string(),
             fields: vec![
-                ("bucket".to_string(), TypeExpr::Named("string".to_string())),
-                ("region".to_string(), TypeExpr::Named("string".to_string())),
-                ("prefix".to_string(), TypeExpr::Named("string option".to_string())),
-                ("compression".to_string(), TypeExpr::Named("string option".to_string())),
-                ("encoding".to_string(), TypeExpr::Named("string option".to_string())),
-                ("batchSize".to_string(), TypeExpr::Named("int option".to_string())),
-                ("accessKeyId".to_string(), TypeExpr::Named("string option".to_string())),
-                ("secretAccessKey".to_string(), TypeExpr::Named("string option".to_string())),
+                ("bucket".to_string(), TypeShape::named("string").into()),
+                ("region".to_string(), TypeShape::named("string").into()),
+                ("prefix".to_string(), TypeShape::named("string").option().into()),
+                ("compression".to_string(), TypeShape::named("string").option().into()),
+                ("encoding".to_string(), TypeShape::named("string").option().into()),
+                ("batchSize".to_string(), TypeShape::named("int").option().into()),
+                ("accessKeyId".to_string(), TypeShape::named("string").option().into()),
+                ("secretAccessKey".to_string(), TypeShape::named("string").option().into()),
             ],
         }));
 
@@ -141,20 +172,21 @@ impl HibanaSinksProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "Splunk".to_string(),
             fields: vec![
-                ("endpoint".to_string(), TypeExpr::Named("string".to_string())),
-                ("token".to_string(), TypeExpr::Named("string".to_string())),
-                ("index".to_string(), TypeExpr::Named("string option".to_string())),
-                ("source".to_string(), TypeExpr::Named("string option".to_string())),
-                ("sourceType".to_string(), TypeExpr::Named("string option".to_string())),
-                ("host".to_string(), TypeExpr::Named("string option".to_string())),
-                ("tlsVerify".to_string(), TypeExpr::Named("bool option".to_string())),
-                ("batchSize".to_string(), TypeExpr::Named("int option".to_string())),
+                ("endpoint".to_string(), TypeShape::named("string").into()),
+                ("token".to_string(), TypeShape::named("string").into()),
+                ("index".to_string(), TypeShape::named("string").option().into()),
+                ("source".to_string(), TypeShape::named("string").option().into()),
+                ("sourceType".to_string(), TypeShape::named("string").option().into()),
+                ("host".to_string(), TypeShape::named("string").option().into()),
+                ("tlsVerify".to_string(), TypeShape::named("bool").option().into()),
+                ("batchSize".to_string(), TypeShape::named("int").option().into()),
             ],
         }));
 
         module
     }
 
+    #[cfg(feature = "traces")]
     /// Generate traces sink types
     fn generate_traces_sinks(&self, namespace: &str) -> GeneratedModule {
         let mut module = GeneratedModule::new(vec![namespace.to_string(), "Traces".to_string()]);
@@ -163,12 +195,12 @@ impl HibanaSinksProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "Otlp".to_string(),
             fields: vec![
-                ("endpoint".to_string(), TypeExpr::Named("string".to_string())),
-                ("protocol".to_string(), TypeExpr::Named("string option".to_string())),
-                ("headers".to_string(), TypeExpr::Named("Map<string, string> option".to_string())),
-                ("compression".to_string(), TypeExpr::Named("string option".to_string())),
-                ("timeout".to_string(), TypeExpr::Named("int option".to_string())),
-                ("tlsVerify".to_string(), TypeExpr::Named("bool option".to_string())),
+                ("endpoint".to_string(), TypeShape::named("string").into()),
+                ("protocol".to_string(), TypeShape::named("string").option().into()),
+                ("headers".to_string(), TypeShape::map(TypeShape::named("string"), TypeShape::named("string")).option().into()),
+                ("compression".to_string(), TypeShape::named("string").option().into()),
+                ("timeout".to_string(), TypeShape::named("int").option().into()),
+                ("tlsVerify".to_string(), TypeShape::named("bool").option().into()),
             ],
         }));
 
@@ -176,12 +208,12 @@ impl HibanaSinksProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "Jaeger".to_string(),
             fields: vec![
-                ("endpoint".to_string(), TypeExpr::Named("string".to_string())),
-                ("agentHost".to_string(), TypeExpr::Named("string option".to_string())),
-                ("agentPort".to_string(), TypeExpr::Named("int option".to_string())),
-                ("serviceName".to_string(), TypeExpr::Named("string".to_string())),
-                ("batchSize".to_string(), TypeExpr::Named("int option".to_string())),
-                ("tags".to_string(), TypeExpr::Named("Map<string, string> option".to_string())),
+                ("endpoint".to_string(), TypeShape::named("string").into()),
+                ("agentHost".to_string(), TypeShape::named("string").option().into()),
+                ("agentPort".to_string(), TypeShape::named("int").option().into()),
+                ("serviceName".to_string(), TypeShape::named("string").into()),
+                ("batchSize".to_string(), TypeShape::named("int").option().into()),
+                ("tags".to_string(), TypeShape::map(TypeShape::named("string"), TypeShape::named("string")).option().into()),
             ],
         }));
 
@@ -189,12 +221,12 @@ impl HibanaSinksProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "Tempo".to_string(),
             fields: vec![
-                ("endpoint".to_string(), TypeExpr::Named("string".to_string())),
-                ("protocol".to_string(), TypeExpr::Named("string option".to_string())),
-                ("auth".to_string(), TypeExpr::Named("TempoAuth option".to_string())),
-                ("headers".to_string(), TypeExpr::Named("Map<string, string> option".to_string())),
-                ("timeout".to_string(), TypeExpr::Named("int option".to_string())),
-                ("batchSize".to_string(), TypeExpr::Named("int option".to_string())),
+                ("endpoint".to_string(), TypeShape::named("string").into()),
+                ("protocol".to_string(), TypeShape::named("string").option().into()),
+                ("auth".to_string(), TypeShape::named("TempoAuth").option().into()),
+                ("headers".to_string(), TypeShape::map(TypeShape::named("string"), TypeShape::named("string")).option().into()),
+                ("timeout".to_string(), TypeShape::named("int").option().into()),
+                ("batchSize".to_string(), TypeShape::named("int").option().into()),
             ],
         }));
 
@@ -202,15 +234,16 @@ impl HibanaSinksProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "TempoAuth".to_string(),
             fields: vec![
-                ("username".to_string(), TypeExpr::Named("string option".to_string())),
-                ("password".to_string(), TypeExpr::Named("string option".to_string())),
-                ("bearerToken".to_string(), TypeExpr::Named("string option".to_string())),
+                ("username".to_string(), TypeShape::named("string").option().into()),
+                ("password".to_string(), TypeShape::named("string").option().into()),
+                ("bearerToken".to_string(), TypeShape::named("string").option().into()),
             ],
         }));
 
         module
     }
 
+    #[cfg(feature = "generic")]
     /// Generate generic sink types
     fn generate_generic_sinks(&self, namespace: &str) -> GeneratedModule {
         let mut module = GeneratedModule::new(vec![namespace.to_string(), "Generic".to_string()]);
@@ -219,15 +252,15 @@ impl HibanaSinksProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "Http".to_string(),
             fields: vec![
-                ("endpoint".to_string(), TypeExpr::Named("string".to_string())),
-                ("method".to_string(), TypeExpr::Named("string option".to_string())),
-                ("headers".to_string(), TypeExpr::Named("Map<string, string> option".to_string())),
-                ("encoding".to_string(), TypeExpr::Named("string option".to_string())),
-                ("compression".to_string(), TypeExpr::Named("string option".to_string())),
-                ("batchSize".to_string(), TypeExpr::Named("int option".to_string())),
-                ("timeout".to_string(), TypeExpr::Named("int option".to_string())),
-                ("tlsVerify".to_string(), TypeExpr::Named("bool option".to_string())),
-                ("auth".to_string(), TypeExpr::Named("HttpAuth option".to_string())),
+                ("endpoint".to_string(), TypeShape::named("string").into()),
+                ("method".to_string(), TypeShape::named("string").option().into()),
+                ("headers".to_string(), TypeShape::map(TypeShape::named("string"), TypeShape::named("string")).option().into()),
+                ("encoding".to_string(), TypeShape::named("string").option().into()),
+                ("compression".to_string(), TypeShape::named("string").option().into()),
+                ("batchSize".to_string(), TypeShape::named("int").option().into()),
+                ("timeout".to_string(), TypeShape::named("int").option().into()),
+                ("tlsVerify".to_string(), TypeShape::named("bool").option().into()),
+                ("auth".to_string(), TypeShape::named("HttpAuth").option().into()),
             ],
         }));
 
@@ -235,16 +268,16 @@ impl HibanaSinksProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "HttpAuth".to_string(),
             fields: vec![
-                ("basic".to_string(), TypeExpr::Named("BasicAuth option".to_string())),
-                ("bearer".to_string(), TypeExpr::Named("string option".to_string())),
+                ("basic".to_string(), TypeShape::named("BasicAuth").option().into()),
+                ("bearer".to_string(), TypeShape::named("string").option().into()),
             ],
         }));
 
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "BasicAuth".to_string(),
             fields: vec![
-                ("username".to_string(), TypeExpr::Named("string".to_string())),
-                ("password".to_string(), TypeExpr::Named("string".to_string())),
+                ("username".to_string(), TypeShape::named("string").into()),
+                ("password".to_string(), TypeShape::named("string").into()),
             ],
         }));
 
@@ -252,15 +285,15 @@ impl HibanaSinksProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "Kafka".to_string(),
             fields: vec![
-                ("brokers".to_string(), TypeExpr::Named("List<string>".to_string())),
-                ("topic".to_string(), TypeExpr::Named("string".to_string())),
-                ("compression".to_string(), TypeExpr::Named("string option".to_string())),
-                ("encoding".to_string(), TypeExpr::Named("string option".to_string())),
-                ("batchSize".to_string(), TypeExpr::Named("int option".to_string())),
-                ("acks".to_string(), TypeExpr::Named("string option".to_string())),
-                ("timeout".to_string(), TypeExpr::Named("int option".to_string())),
-                ("keyField".to_string(), TypeExpr::Named("string option".to_string())),
-                ("auth".to_string(), TypeExpr::Named("KafkaAuth option".to_string())),
+                ("brokers".to_string(), TypeShape::named("string").list().into_type_expr(LISTS)),
+                ("topic".to_string(), TypeShape::named("string").into()),
+                ("compression".to_string(), TypeShape::named("string").option().into()),
+                ("encoding".to_string(), TypeShape::named("string").option().into()),
+                ("batchSize".to_string(), TypeShape::named("int").option().into()),
+                ("acks".to_string(), TypeShape::named("string").option().into()),
+                ("timeout".to_string(), TypeShape::named("int").option().into()),
+                ("keyField".to_string(), TypeShape::named("string").option().into()),
+                ("auth".to_string(), TypeShape::named("KafkaAuth").option().into()),
             ],
         }));
 
@@ -268,10 +301,10 @@ impl HibanaSinksProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "KafkaAuth".to_string(),
             fields: vec![
-                ("saslMechanism".to_string(), TypeExpr::Named("string option".to_string())),
-                ("saslUsername".to_string(), TypeExpr::Named("string option".to_string())),
-                ("saslPassword".to_string(), TypeExpr::Named("string option".to_string())),
-                ("tlsEnabled".to_string(), TypeExpr::Named("bool option".to_string())),
+                ("saslMechanism".to_string(), TypeShape::named("string").option().into()),
+                ("saslUsername".to_string(), TypeShape::named("string").option().into()),
+                ("saslPassword".to_string(), TypeShape::named("string").option().into()),
+                ("tlsEnabled".to_string(), TypeShape::named("bool").option().into()),
             ],
         }));
 
@@ -279,12 +312,12 @@ impl HibanaSinksProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "File".to_string(),
             fields: vec![
-                ("path".to_string(), TypeExpr::Named("string".to_string())),
-                ("encoding".to_string(), TypeExpr::Named("string option".to_string())),
-                ("compression".to_string(), TypeExpr::Named("string option".to_string())),
-                ("maxSize".to_string(), TypeExpr::Named("int option".to_string())),
-                ("maxFiles".to_string(), TypeExpr::Named("int option".to_string())),
-                ("rotateOnDate".to_string(), TypeExpr::Named("bool option".to_string())),
+                ("path".to_string(), TypeShape::named("string").into()),
+                ("encoding".to_string(), TypeShape::named("string").option().into()),
+                ("compression".to_string(), TypeShape::named("string").option().into()),
+                ("maxSize".to_string(), TypeShape::named("int").option().into()),
+                ("maxFiles".to_string(), TypeShape::named("int").option().into()),
+                ("rotateOnDate".to_string(), TypeShape::named("bool").option().into()),
             ],
         }));
 
@@ -292,9 +325,9 @@ impl HibanaSinksProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "Console".to_string(),
             fields: vec![
-                ("encoding".to_string(), TypeExpr::Named("string option".to_string())),
-                ("format".to_string(), TypeExpr::Named("string option".to_string())),
-                ("target".to_string(), TypeExpr::Named("string option".to_string())),
+                ("encoding".to_string(), TypeShape::named("string").option().into()),
+                ("format".to_string(), TypeShape::named("string").option().into()),
+                ("target".to_string(), TypeShape::named("string").option().into()),
             ],
         }));
 
@@ -302,13 +335,27 @@ impl HibanaSinksProvider {
     }
 
     /// Generate all embedded sink types
-    fn generate_embedded_types(&self, namespace: &str) -> GeneratedTypes {
+    fn generate_embedded_types(
+        &self,
+        namespace: &str,
+        grouping: ModuleGrouping,
+    ) -> ProviderResult<GeneratedTypes> {
         let mut result = GeneratedTypes::new();
+        #[cfg(feature = "metrics")]
         result.modules.push(self.generate_metrics_sinks(namespace));
+        #[cfg(feature = "logs")]
         result.modules.push(self.generate_logs_sinks(namespace));
+        #[cfg(feature = "traces")]
         result.modules.push(self.generate_traces_sinks(namespace));
+        #[cfg(feature = "generic")]
         result.modules.push(self.generate_generic_sinks(namespace));
-        result
+        // Categories are the module names below the namespace
+        let mut modules = ModuleBuilder::new(namespace, grouping);
+        for module in result.modules {
+            modules.extend(&[module.path[1].as_str()], module.types);
+        }
+        result.modules = modules.finish()?;
+        Ok(result)
     }
 }
 
@@ -337,16 +384,45 @@ impl TypeProvider for HibanaSinksProvider {
     fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
         match schema {
             Schema::Custom(s) if s == "embedded" => {
-                Ok(self.generate_embedded_types(namespace))
+                self.generate_embedded_types(namespace, ModuleGrouping::Nested)
             }
             _ => Err(ProviderError::ParseError("Expected Hibana Sinks schema".to_string())),
         }
     }
 }
 
+impl GroupedProvider for HibanaSinksProvider {
+    fn default_grouping(&self) -> ModuleGrouping {
+        ModuleGrouping::Nested
+    }
+
+    fn generate_grouped(
+        &self,
+        schema: &Schema,
+        namespace: &str,
+        grouping: ModuleGrouping,
+    ) -> ProviderResult<GeneratedTypes> {
+        match schema {
+            Schema::Custom(s) if s == "embedded" => self.generate_embedded_types(namespace, grouping),
+            _ => Err(ProviderError::ParseError("Expected Hibana Sinks schema".to_string())),
+        }
+    }
+}
+
+impl SourceValidator for HibanaSinksProvider {}
+
+impl DescribedProvider for HibanaSinksProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .embedded("embedded")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fusabi_provider_common::emit::golden;
+    use fusabi_provider_common::generate_with_layout;
 
     #[test]
     fn test_provider_name() {
@@ -371,6 +447,19 @@ mod tests {
     }
 
     #[test]
+    #[cfg(all(feature = "metrics", feature = "logs"))]
+    fn test_flat_layout() {
+        let provider = HibanaSinksProvider::new();
+        let params = ProviderParams::default().with("module_layout", "flat");
+        let types = generate_with_layout(&provider, "embedded", &params, "Hibana").unwrap();
+
+        assert_eq!(types.modules.len(), 1);
+        assert_eq!(types.modules[0].path, vec!["Hibana".to_string()]);
+        assert!(types.modules[0].types.iter().any(|t| matches!(t, TypeDefinition::Record(r) if r.name == "InfluxDb")));
+    }
+
+    #[test]
+    #[cfg(all(feature = "metrics", feature = "logs", feature = "traces", feature = "generic"))]
     fn test_generate_types() {
         let provider = HibanaSinksProvider::new();
         let schema = Schema::Custom("embedded".to_string());
@@ -379,9 +468,15 @@ mod tests {
 
         let types = result.unwrap();
         assert_eq!(types.modules.len(), 4); // Metrics, Logs, Traces, Generic
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/hibana-sinks.fsx"),
+        );
     }
 
     #[test]
+    #[cfg(feature = "metrics")]
     fn test_metrics_sinks_module() {
         let provider = HibanaSinksProvider::new();
         let module = provider.generate_metrics_sinks("Hibana");
@@ -391,6 +486,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "logs")]
     fn test_logs_sinks_module() {
         let provider = HibanaSinksProvider::new();
         let module = provider.generate_logs_sinks("Hibana");
@@ -400,6 +496,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "traces")]
     fn test_traces_sinks_module() {
         let provider = HibanaSinksProvider::new();
         let module = provider.generate_traces_sinks("Hibana");
@@ -409,6 +506,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "generic")]
     fn test_generic_sinks_module() {
         let provider = HibanaSinksProvider::new();
         let module = provider.generate_generic_sinks("Hibana");
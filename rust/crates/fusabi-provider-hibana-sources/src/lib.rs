@@ -2,16 +2,46 @@
 //!
 //! Generates Fusabi types for Hibana observability agent data sources.
 //! Hibana is a Fusabi-powered observability agent that collects metrics, logs, traces, and events.
+//!
+//! Each source category (`metrics`, `logs`, `traces`, `events`) is behind
+//! the cargo feature of the same name, all enabled by default, and becomes
+//! one module of the `embedded` source next to the shared `Common` module.
+//! [`embedded_packs`] reports which are compiled in.
+//!
+//! With `module_layout=flat` (see [`GroupedProvider`]), all categories share
+//! the namespace module instead.
 
+use fusabi_provider_common::{
+    Capabilities, DescribedProvider, GroupedProvider, ListSyntax, ModuleBuilder, ModuleGrouping,
+    SourceValidator, TypeShape,
+};
 use fusabi_type_providers::{
     TypeProvider, ProviderParams, Schema,
     GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
-    RecordDef, TypeExpr, TypeDefinition,
+    RecordDef, TypeDefinition,
     ProviderError, ProviderResult,
 };
 
+/// Lists are written `list<T>`, as in the published source types
+const LISTS: ListSyntax = ListSyntax::Generic("list");
+
+/// Embedded source categories compiled into this build
+pub fn embedded_packs() -> &'static [&'static str] {
+    &[
+        #[cfg(feature = "metrics")]
+        "metrics",
+        #[cfg(feature = "logs")]
+        "logs",
+        #[cfg(feature = "traces")]
+        "traces",
+        #[cfg(feature = "events")]
+        "events",
+    ]
+}
+
 /// Hibana Sources type provider
 pub struct HibanaSourcesProvider {
+    #[allow(dead_code)]
     generator: TypeGenerator,
 }
 
@@ -22,6 +52,7 @@ impl HibanaSourcesProvider {
         }
     }
 
+    #[cfg(feature = "metrics")]
     fn generate_metrics_sources(&self, namespace: &str) -> GeneratedModule {
         let mut module = GeneratedModule::new(vec![namespace.to_string(), "Metrics".to_string()]);
 
@@ -29,13 +60,13 @@ impl HibanaSourcesProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "PrometheusScrape".to_string(),
             fields: vec![
-                ("endpoint".to_string(), TypeExpr::Named("string".to_string())),
-                ("interval".to_string(), TypeExpr::Named("int".to_string())),
-                ("labels".to_string(), TypeExpr::Named("Map<string, string> option".to_string())),
-                ("timeout".to_string(), TypeExpr::Named("int option".to_string())),
-                ("scrapeProtocol".to_string(), TypeExpr::Named("string option".to_string())),
-                ("honorLabels".to_string(), TypeExpr::Named("bool option".to_string())),
-                ("tlsConfig".to_string(), TypeExpr::Named("TlsConfig option".to_string())),
+                ("endpoint".to_string(), TypeShape::named("string").into()),
+                ("interval".to_string(), TypeShape::named("int").into()),
+                ("labels".to_string(), TypeShape::map(TypeShape::named("string"), TypeShape::named("string")).option().into()),
+                ("timeout".to_string(), TypeShape::named("int").option().into()),
+                ("scrapeProtocol".to_string(), TypeShape::named("string").option().into()),
+                ("honorLabels".to_string(), TypeShape::named("bool").option().into()),
+                ("tlsConfig".to_string(), TypeShape::named("TlsConfig").option().into()),
             ],
         }));
 
@@ -43,12 +74,12 @@ impl HibanaSourcesProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "StatsDSource".to_string(),
             fields: vec![
-                ("address".to_string(), TypeExpr::Named("string".to_string())),
-                ("port".to_string(), TypeExpr::Named("int".to_string())),
-                ("protocol".to_string(), TypeExpr::Named("string option".to_string())),
-                ("metricsPrefix".to_string(), TypeExpr::Named("string option".to_string())),
-                ("parseMetricTags".to_string(), TypeExpr::Named("bool option".to_string())),
-                ("aggregationInterval".to_string(), TypeExpr::Named("int option".to_string())),
+                ("address".to_string(), TypeShape::named("string").into()),
+                ("port".to_string(), TypeShape::named("int").into()),
+                ("protocol".to_string(), TypeShape::named("string").option().into()),
+                ("metricsPrefix".to_string(), TypeShape::named("string").option().into()),
+                ("parseMetricTags".to_string(), TypeShape::named("bool").option().into()),
+                ("aggregationInterval".to_string(), TypeShape::named("int").option().into()),
             ],
         }));
 
@@ -56,13 +87,13 @@ impl HibanaSourcesProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "SystemMetrics".to_string(),
             fields: vec![
-                ("interval".to_string(), TypeExpr::Named("int".to_string())),
-                ("collectCpu".to_string(), TypeExpr::Named("bool option".to_string())),
-                ("collectMemory".to_string(), TypeExpr::Named("bool option".to_string())),
-                ("collectDisk".to_string(), TypeExpr::Named("bool option".to_string())),
-                ("collectNetwork".to_string(), TypeExpr::Named("bool option".to_string())),
-                ("collectProcesses".to_string(), TypeExpr::Named("bool option".to_string())),
-                ("namespacePrefix".to_string(), TypeExpr::Named("string option".to_string())),
+                ("interval".to_string(), TypeShape::named("int").into()),
+                ("collectCpu".to_string(), TypeShape::named("bool").option().into()),
+                ("collectMemory".to_string(), TypeShape::named("bool").option().into()),
+                ("collectDisk".to_string(), TypeShape::named("bool").option().into()),
+                ("collectNetwork".to_string(), TypeShape::named("bool").option().into()),
+                ("collectProcesses".to_string(), TypeShape::named("bool").option().into()),
+                ("namespacePrefix".to_string(), TypeShape::named("string").option().into()),
             ],
         }));
 
@@ -70,16 +101,17 @@ impl HibanaSourcesProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "HostMetrics".to_string(),
             fields: vec![
-                ("interval".to_string(), TypeExpr::Named("int".to_string())),
-                ("rootPath".to_string(), TypeExpr::Named("string option".to_string())),
-                ("collectors".to_string(), TypeExpr::Named("list<string>".to_string())),
-                ("filters".to_string(), TypeExpr::Named("Map<string, list<string>> option".to_string())),
+                ("interval".to_string(), TypeShape::named("int").into()),
+                ("rootPath".to_string(), TypeShape::named("string").option().into()),
+                ("collectors".to_string(), TypeShape::named("string").list().into_type_expr(LISTS)),
+                ("filters".to_string(), TypeShape::map(TypeShape::named("string"), TypeShape::named("string").list()).option().into_type_expr(LISTS)),
             ],
         }));
 
         module
     }
 
+    #[cfg(feature = "logs")]
     fn generate_logs_sources(&self, namespace: &str) -> GeneratedModule {
         let mut module = GeneratedModule::new(vec![namespace.to_string(), "Logs".to_string()]);
 
@@ -87,14 +119,14 @@ impl HibanaSourcesProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "FileLog".to_string(),
             fields: vec![
-                ("path".to_string(), TypeExpr::Named("string".to_string())),
-                ("encoding".to_string(), TypeExpr::Named("string option".to_string())),
-                ("multiline".to_string(), TypeExpr::Named("MultilineConfig option".to_string())),
-                ("includeMetadata".to_string(), TypeExpr::Named("bool option".to_string())),
-                ("startPosition".to_string(), TypeExpr::Named("string option".to_string())),
-                ("glob".to_string(), TypeExpr::Named("bool option".to_string())),
-                ("exclude".to_string(), TypeExpr::Named("list<string> option".to_string())),
-                ("maxLineBytes".to_string(), TypeExpr::Named("int option".to_string())),
+                ("path".to_string(), TypeShape::named("string").into()),
+                ("encoding".to_string(), TypeShape::named("string").option().into()),
+                ("multiline".to_string(), TypeShape::named("MultilineConfig").option().into()),
+                ("includeMetadata".to_string(), TypeShape::named("bool").option().into()),
+                ("startPosition".to_string(), TypeShape::named("string").option().into()),
+                ("glob".to_string(), TypeShape::named("bool").option().into()),
+                ("exclude".to_string(), TypeShape::named("string").list().option().into_type_expr(LISTS)),
+                ("maxLineBytes".to_string(), TypeShape::named("int").option().into()),
             ],
         }));
 
@@ -102,11 +134,11 @@ impl HibanaSourcesProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "MultilineConfig".to_string(),
             fields: vec![
-                ("pattern".to_string(), TypeExpr::Named("string".to_string())),
-                ("negate".to_string(), TypeExpr::Named("bool option".to_string())),
-                ("match".to_string(), TypeExpr::Named("string option".to_string())),
-                ("maxLines".to_string(), TypeExpr::Named("int option".to_string())),
-                ("timeout".to_string(), TypeExpr::Named("int option".to_string())),
+                ("pattern".to_string(), TypeShape::named("string").into()),
+                ("negate".to_string(), TypeShape::named("bool").option().into()),
+                ("match".to_string(), TypeShape::named("string").option().into()),
+                ("maxLines".to_string(), TypeShape::named("int").option().into()),
+                ("timeout".to_string(), TypeShape::named("int").option().into()),
             ],
         }));
 
@@ -114,12 +146,12 @@ impl HibanaSourcesProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "Syslog".to_string(),
             fields: vec![
-                ("address".to_string(), TypeExpr::Named("string".to_string())),
-                ("port".to_string(), TypeExpr::Named("int".to_string())),
-                ("protocol".to_string(), TypeExpr::Named("string option".to_string())),
-                ("mode".to_string(), TypeExpr::Named("string option".to_string())),
-                ("maxMessageSize".to_string(), TypeExpr::Named("int option".to_string())),
-                ("frameDelimiter".to_string(), TypeExpr::Named("string option".to_string())),
+                ("address".to_string(), TypeShape::named("string").into()),
+                ("port".to_string(), TypeShape::named("int").into()),
+                ("protocol".to_string(), TypeShape::named("string").option().into()),
+                ("mode".to_string(), TypeShape::named("string").option().into()),
+                ("maxMessageSize".to_string(), TypeShape::named("int").option().into()),
+                ("frameDelimiter".to_string(), TypeShape::named("string").option().into()),
             ],
         }));
 
@@ -127,12 +159,12 @@ impl HibanaSourcesProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "Journald".to_string(),
             fields: vec![
-                ("currentBootOnly".to_string(), TypeExpr::Named("bool option".to_string())),
-                ("units".to_string(), TypeExpr::Named("list<string> option".to_string())),
-                ("includeKernel".to_string(), TypeExpr::Named("bool option".to_string())),
-                ("batchSize".to_string(), TypeExpr::Named("int option".to_string())),
-                ("sinceNow".to_string(), TypeExpr::Named("bool option".to_string())),
-                ("journalDirectory".to_string(), TypeExpr::Named("string option".to_string())),
+                ("currentBootOnly".to_string(), TypeShape::named("bool").option().into()),
+                ("units".to_string(), TypeShape::named("string").list().option().into_type_expr(LISTS)),
+                ("includeKernel".to_string(), TypeShape::named("bool").option().into()),
+                ("batchSize".to_string(), TypeShape::named("int").option().into()),
+                ("sinceNow".to_string(), TypeShape::named("bool").option().into()),
+                ("journalDirectory".to_string(), TypeShape::named("string").option().into()),
             ],
         }));
 
@@ -140,13 +172,13 @@ impl HibanaSourcesProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "Docker".to_string(),
             fields: vec![
-                ("dockerHost".to_string(), TypeExpr::Named("string option".to_string())),
-                ("includeContainers".to_string(), TypeExpr::Named("list<string> option".to_string())),
-                ("excludeContainers".to_string(), TypeExpr::Named("list<string> option".to_string())),
-                ("includeLabels".to_string(), TypeExpr::Named("Map<string, string> option".to_string())),
-                ("excludeLabels".to_string(), TypeExpr::Named("Map<string, string> option".to_string())),
-                ("partialEventMarkerField".to_string(), TypeExpr::Named("string option".to_string())),
-                ("autoPartialMerge".to_string(), TypeExpr::Named("bool option".to_string())),
+                ("dockerHost".to_string(), TypeShape::named("string").option().into()),
+                ("includeContainers".to_string(), TypeShape::named("string").list().option().into_type_expr(LISTS)),
+                ("excludeContainers".to_string(), TypeShape::named("string").list().option().into_type_expr(LISTS)),
+                ("includeLabels".to_string(), TypeShape::map(TypeShape::named("string"), TypeShape::named("string")).option().into()),
+                ("excludeLabels".to_string(), TypeShape::map(TypeShape::named("string"), TypeShape::named("string")).option().into()),
+                ("partialEventMarkerField".to_string(), TypeShape::named("string").option().into()),
+                ("autoPartialMerge".to_string(), TypeShape::named("bool").option().into()),
             ],
         }));
 
@@ -154,18 +186,19 @@ impl HibanaSourcesProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "KubernetesLogs".to_string(),
             fields: vec![
-                ("namespaces".to_string(), TypeExpr::Named("list<string> option".to_string())),
-                ("excludeNamespaces".to_string(), TypeExpr::Named("list<string> option".to_string())),
-                ("labelSelector".to_string(), TypeExpr::Named("string option".to_string())),
-                ("fieldSelector".to_string(), TypeExpr::Named("string option".to_string())),
-                ("annotationFields".to_string(), TypeExpr::Named("Map<string, string> option".to_string())),
-                ("selfNodeName".to_string(), TypeExpr::Named("string option".to_string())),
+                ("namespaces".to_string(), TypeShape::named("string").list().option().into_type_expr(LISTS)),
+                ("excludeNamespaces".to_string(), TypeShape::named("string").list().option().into_type_expr(LISTS)),
+                ("labelSelector".to_string(), TypeShape::named("string").option().into()),
+                ("fieldSelector".to_string(), TypeShape::named("string").option().into()),
+                ("annotationFields".to_string(), TypeShape::map(TypeShape::named("string"), TypeShape::named("string")).option().into()),
+                ("selfNodeName".to_string(), TypeShape::named("string").option().into()),
             ],
         }));
 
         module
     }
 
+    #[cfg(feature = "traces")]
     fn generate_traces_sources(&self, namespace: &str) -> GeneratedModule {
         let mut module = GeneratedModule::new(vec![namespace.to_string(), "Traces".to_string()]);
 
@@ -173,13 +206,13 @@ impl HibanaSourcesProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "OtlpTrace".to_string(),
             fields: vec![
-                ("endpoint".to_string(), TypeExpr::Named("string".to_string())),
-                ("protocol".to_string(), TypeExpr::Named("string".to_string())),
-                ("headers".to_string(), TypeExpr::Named("Map<string, string> option".to_string())),
-                ("timeout".to_string(), TypeExpr::Named("int option".to_string())),
-                ("compression".to_string(), TypeExpr::Named("string option".to_string())),
-                ("tlsConfig".to_string(), TypeExpr::Named("TlsConfig option".to_string())),
-                ("retryConfig".to_string(), TypeExpr::Named("RetryConfig option".to_string())),
+                ("endpoint".to_string(), TypeShape::named("string").into()),
+                ("protocol".to_string(), TypeShape::named("string").into()),
+                ("headers".to_string(), TypeShape::map(TypeShape::named("string"), TypeShape::named("string")).option().into()),
+                ("timeout".to_string(), TypeShape::named("int").option().into()),
+                ("compression".to_string(), TypeShape::named("string").option().into()),
+                ("tlsConfig".to_string(), TypeShape::named("TlsConfig").option().into()),
+                ("retryConfig".to_string(), TypeShape::named("RetryConfig").option().into()),
             ],
         }));
 
@@ -187,12 +220,12 @@ impl HibanaSourcesProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "Jaeger".to_string(),
             fields: vec![
-                ("endpoint".to_string(), TypeExpr::Named("string".to_string())),
-                ("protocol".to_string(), TypeExpr::Named("string option".to_string())),
-                ("agentHost".to_string(), TypeExpr::Named("string option".to_string())),
-                ("agentPort".to_string(), TypeExpr::Named("int option".to_string())),
-                ("sampler".to_string(), TypeExpr::Named("SamplerConfig option".to_string())),
-                ("tags".to_string(), TypeExpr::Named("Map<string, string> option".to_string())),
+                ("endpoint".to_string(), TypeShape::named("string").into()),
+                ("protocol".to_string(), TypeShape::named("string").option().into()),
+                ("agentHost".to_string(), TypeShape::named("string").option().into()),
+                ("agentPort".to_string(), TypeShape::named("int").option().into()),
+                ("sampler".to_string(), TypeShape::named("SamplerConfig").option().into()),
+                ("tags".to_string(), TypeShape::map(TypeShape::named("string"), TypeShape::named("string")).option().into()),
             ],
         }));
 
@@ -200,11 +233,11 @@ impl HibanaSourcesProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "Zipkin".to_string(),
             fields: vec![
-                ("endpoint".to_string(), TypeExpr::Named("string".to_string())),
-                ("port".to_string(), TypeExpr::Named("int".to_string())),
-                ("collectorEndpoint".to_string(), TypeExpr::Named("string option".to_string())),
-                ("maxPayloadSize".to_string(), TypeExpr::Named("int option".to_string())),
-                ("v2Format".to_string(), TypeExpr::Named("bool option".to_string())),
+                ("endpoint".to_string(), TypeShape::named("string").into()),
+                ("port".to_string(), TypeShape::named("int").into()),
+                ("collectorEndpoint".to_string(), TypeShape::named("string").option().into()),
+                ("maxPayloadSize".to_string(), TypeShape::named("int").option().into()),
+                ("v2Format".to_string(), TypeShape::named("bool").option().into()),
             ],
         }));
 
@@ -212,16 +245,17 @@ impl HibanaSourcesProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "SamplerConfig".to_string(),
             fields: vec![
-                ("samplerType".to_string(), TypeExpr::Named("string".to_string())),
-                ("param".to_string(), TypeExpr::Named("float option".to_string())),
-                ("samplingServerUrl".to_string(), TypeExpr::Named("string option".to_string())),
-                ("maxOperations".to_string(), TypeExpr::Named("int option".to_string())),
+                ("samplerType".to_string(), TypeShape::named("string").into()),
+                ("param".to_string(), TypeShape::named("float").option().into()),
+                ("samplingServerUrl".to_string(), TypeShape::named("string").option().into()),
+                ("maxOperations".to_string(), TypeShape::named("int").option().into()),
             ],
         }));
 
         module
     }
 
+    #[cfg(feature = "events")]
     fn generate_events_sources(&self, namespace: &str) -> GeneratedModule {
         let mut module = GeneratedModule::new(vec![namespace.to_string(), "Events".to_string()]);
 
@@ -229,12 +263,12 @@ impl HibanaSourcesProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "EbpfSource".to_string(),
             fields: vec![
-                ("programPath".to_string(), TypeExpr::Named("string".to_string())),
-                ("programType".to_string(), TypeExpr::Named("string".to_string())),
-                ("attachPoint".to_string(), TypeExpr::Named("string option".to_string())),
-                ("mapNames".to_string(), TypeExpr::Named("list<string> option".to_string())),
-                ("pollInterval".to_string(), TypeExpr::Named("int option".to_string())),
-                ("kernelVersion".to_string(), TypeExpr::Named("string option".to_string())),
+                ("programPath".to_string(), TypeShape::named("string").into()),
+                ("programType".to_string(), TypeShape::named("string").into()),
+                ("attachPoint".to_string(), TypeShape::named("string").option().into()),
+                ("mapNames".to_string(), TypeShape::named("string").list().option().into_type_expr(LISTS)),
+                ("pollInterval".to_string(), TypeShape::named("int").option().into()),
+                ("kernelVersion".to_string(), TypeShape::named("string").option().into()),
             ],
         }));
 
@@ -242,11 +276,11 @@ impl HibanaSourcesProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "Audit".to_string(),
             fields: vec![
-                ("socketPath".to_string(), TypeExpr::Named("string option".to_string())),
-                ("auditdPath".to_string(), TypeExpr::Named("string option".to_string())),
-                ("rules".to_string(), TypeExpr::Named("list<string> option".to_string())),
-                ("resolveIds".to_string(), TypeExpr::Named("bool option".to_string())),
-                ("bufferSize".to_string(), TypeExpr::Named("int option".to_string())),
+                ("socketPath".to_string(), TypeShape::named("string").option().into()),
+                ("auditdPath".to_string(), TypeShape::named("string").option().into()),
+                ("rules".to_string(), TypeShape::named("string").list().option().into_type_expr(LISTS)),
+                ("resolveIds".to_string(), TypeShape::named("bool").option().into()),
+                ("bufferSize".to_string(), TypeShape::named("int").option().into()),
             ],
         }));
 
@@ -254,13 +288,13 @@ impl HibanaSourcesProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "CloudWatch".to_string(),
             fields: vec![
-                ("region".to_string(), TypeExpr::Named("string".to_string())),
-                ("logGroupName".to_string(), TypeExpr::Named("string option".to_string())),
-                ("logStreamName".to_string(), TypeExpr::Named("string option".to_string())),
-                ("filterPattern".to_string(), TypeExpr::Named("string option".to_string())),
-                ("startTime".to_string(), TypeExpr::Named("int option".to_string())),
-                ("pollInterval".to_string(), TypeExpr::Named("int option".to_string())),
-                ("awsProfile".to_string(), TypeExpr::Named("string option".to_string())),
+                ("region".to_string(), TypeShape::named("string").into()),
+                ("logGroupName".to_string(), TypeShape::named("string").option().into()),
+                ("logStreamName".to_string(), TypeShape::named("string").option().into()),
+                ("filterPattern".to_string(), TypeShape::named("string").option().into()),
+                ("startTime".to_string(), TypeShape::named("int").option().into()),
+                ("pollInterval".to_string(), TypeShape::named("int").option().into()),
+                ("awsProfile".to_string(), TypeShape::named("string").option().into()),
             ],
         }));
 
@@ -268,11 +302,11 @@ impl HibanaSourcesProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "EventBridge".to_string(),
             fields: vec![
-                ("region".to_string(), TypeExpr::Named("string".to_string())),
-                ("eventBusName".to_string(), TypeExpr::Named("string option".to_string())),
-                ("ruleNames".to_string(), TypeExpr::Named("list<string> option".to_string())),
-                ("eventPattern".to_string(), TypeExpr::Named("string option".to_string())),
-                ("awsProfile".to_string(), TypeExpr::Named("string option".to_string())),
+                ("region".to_string(), TypeShape::named("string").into()),
+                ("eventBusName".to_string(), TypeShape::named("string").option().into()),
+                ("ruleNames".to_string(), TypeShape::named("string").list().option().into_type_expr(LISTS)),
+                ("eventPattern".to_string(), TypeShape::named("string").option().into()),
+                ("awsProfile".to_string(), TypeShape::named("string").option().into()),
             ],
         }));
 
@@ -286,11 +320,11 @@ impl HibanaSourcesProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "TlsConfig".to_string(),
             fields: vec![
-                ("caFile".to_string(), TypeExpr::Named("string option".to_string())),
-                ("certFile".to_string(), TypeExpr::Named("string option".to_string())),
-                ("keyFile".to_string(), TypeExpr::Named("string option".to_string())),
-                ("insecureSkipVerify".to_string(), TypeExpr::Named("bool option".to_string())),
-                ("serverName".to_string(), TypeExpr::Named("string option".to_string())),
+                ("caFile".to_string(), TypeShape::named("string").option().into()),
+                ("certFile".to_string(), TypeShape::named("string").option().into()),
+                ("keyFile".to_string(), TypeShape::named("string").option().into()),
+                ("insecureSkipVerify".to_string(), TypeShape::named("bool").option().into()),
+                ("serverName".to_string(), TypeShape::named("string").option().into()),
             ],
         }));
 
@@ -298,11 +332,11 @@ impl HibanaSourcesProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "RetryConfig".to_string(),
             fields: vec![
-                ("enabled".to_string(), TypeExpr::Named("bool".to_string())),
-                ("initialInterval".to_string(), TypeExpr::Named("int option".to_string())),
-                ("maxInterval".to_string(), TypeExpr::Named("int option".to_string())),
-                ("maxElapsedTime".to_string(), TypeExpr::Named("int option".to_string())),
-                ("multiplier".to_string(), TypeExpr::Named("float option".to_string())),
+                ("enabled".to_string(), TypeShape::named("bool").into()),
+                ("initialInterval".to_string(), TypeShape::named("int").option().into()),
+                ("maxInterval".to_string(), TypeShape::named("int").option().into()),
+                ("maxElapsedTime".to_string(), TypeShape::named("int").option().into()),
+                ("multiplier".to_string(), TypeShape::named("float").option().into()),
             ],
         }));
 
@@ -310,10 +344,10 @@ impl HibanaSourcesProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "BufferConfig".to_string(),
             fields: vec![
-                ("maxSize".to_string(), TypeExpr::Named("int".to_string())),
-                ("flushInterval".to_string(), TypeExpr::Named("int option".to_string())),
-                ("persistent".to_string(), TypeExpr::Named("bool option".to_string())),
-                ("persistPath".to_string(), TypeExpr::Named("string option".to_string())),
+                ("maxSize".to_string(), TypeShape::named("int").into()),
+                ("flushInterval".to_string(), TypeShape::named("int").option().into()),
+                ("persistent".to_string(), TypeShape::named("bool").option().into()),
+                ("persistPath".to_string(), TypeShape::named("string").option().into()),
             ],
         }));
 
@@ -321,31 +355,45 @@ impl HibanaSourcesProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "AuthConfig".to_string(),
             fields: vec![
-                ("authType".to_string(), TypeExpr::Named("string".to_string())),
-                ("username".to_string(), TypeExpr::Named("string option".to_string())),
-                ("password".to_string(), TypeExpr::Named("string option".to_string())),
-                ("bearerToken".to_string(), TypeExpr::Named("string option".to_string())),
-                ("apiKey".to_string(), TypeExpr::Named("string option".to_string())),
-                ("apiKeyHeader".to_string(), TypeExpr::Named("string option".to_string())),
+                ("authType".to_string(), TypeShape::named("string").into()),
+                ("username".to_string(), TypeShape::named("string").option().into()),
+                ("password".to_string(), TypeShape::named("string").option().into()),
+                ("bearerToken".to_string(), TypeShape::named("string").option().into()),
+                ("apiKey".to_string(), TypeShape::named("string").option().into()),
+                ("apiKeyHeader".to_string(), TypeShape::named("string").option().into()),
             ],
         }));
 
         module
     }
 
-    fn generate_embedded_types(&self, namespace: &str) -> GeneratedTypes {
+    fn generate_embedded_types(
+        &self,
+        namespace: &str,
+        grouping: ModuleGrouping,
+    ) -> ProviderResult<GeneratedTypes> {
         let mut result = GeneratedTypes::new();
 
         // Add common types first (used by other modules)
         result.modules.push(self.generate_common_types(namespace));
 
         // Add source-specific types
+        #[cfg(feature = "metrics")]
         result.modules.push(self.generate_metrics_sources(namespace));
+        #[cfg(feature = "logs")]
         result.modules.push(self.generate_logs_sources(namespace));
+        #[cfg(feature = "traces")]
         result.modules.push(self.generate_traces_sources(namespace));
+        #[cfg(feature = "events")]
         result.modules.push(self.generate_events_sources(namespace));
 
-        result
+        // Categories are the module names below the namespace
+        let mut modules = ModuleBuilder::new(namespace, grouping);
+        for module in result.modules {
+            modules.extend(&[module.path[1].as_str()], module.types);
+        }
+        result.modules = modules.finish()?;
+        Ok(result)
     }
 }
 
@@ -374,16 +422,44 @@ impl TypeProvider for HibanaSourcesProvider {
     fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
         match schema {
             Schema::Custom(s) if s == "embedded" => {
-                Ok(self.generate_embedded_types(namespace))
+                self.generate_embedded_types(namespace, ModuleGrouping::Nested)
             }
             _ => Err(ProviderError::ParseError("Expected Hibana Sources schema".to_string())),
         }
     }
 }
 
+impl GroupedProvider for HibanaSourcesProvider {
+    fn default_grouping(&self) -> ModuleGrouping {
+        ModuleGrouping::Nested
+    }
+
+    fn generate_grouped(
+        &self,
+        schema: &Schema,
+        namespace: &str,
+        grouping: ModuleGrouping,
+    ) -> ProviderResult<GeneratedTypes> {
+        match schema {
+            Schema::Custom(s) if s == "embedded" => self.generate_embedded_types(namespace, grouping),
+            _ => Err(ProviderError::ParseError("Expected Hibana Sources schema".to_string())),
+        }
+    }
+}
+
+impl SourceValidator for HibanaSourcesProvider {}
+
+impl DescribedProvider for HibanaSourcesProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .embedded("embedded")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fusabi_provider_common::emit::golden;
 
     #[test]
     fn test_provider_name() {
@@ -408,6 +484,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(all(feature = "metrics", feature = "logs", feature = "traces", feature = "events"))]
     fn test_generate_embedded_types() {
         let provider = HibanaSourcesProvider::new();
         let schema = Schema::Custom("embedded".to_string());
@@ -417,9 +494,15 @@ mod tests {
         let types = result.unwrap();
         // Should have 5 modules: Common, Metrics, Logs, Traces, Events
         assert_eq!(types.modules.len(), 5);
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/hibana-sources.fsx"),
+        );
     }
 
     #[test]
+    #[cfg(feature = "metrics")]
     fn test_metrics_sources_module() {
         let provider = HibanaSourcesProvider::new();
         let module = provider.generate_metrics_sources("HibanaSources");
@@ -439,6 +522,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "logs")]
     fn test_logs_sources_module() {
         let provider = HibanaSourcesProvider::new();
         let module = provider.generate_logs_sources("HibanaSources");
@@ -458,6 +542,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "traces")]
     fn test_traces_sources_module() {
         let provider = HibanaSourcesProvider::new();
         let module = provider.generate_traces_sources("HibanaSources");
@@ -477,6 +562,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "events")]
     fn test_events_sources_module() {
         let provider = HibanaSourcesProvider::new();
         let module = provider.generate_events_sources("HibanaSources");
@@ -0,0 +1,678 @@
+//! JMX Type Provider
+//!
+//! Generates Fusabi types from the MBean metadata of a running JVM, so
+//! pipelines that scrape JMX metrics into Hibana read attributes by name
+//! and type rather than as untyped maps. The source is a Jolokia `list`
+//! response, fetched from `http://<host>:8778/jolokia/list` or saved to a
+//! file, or an `MBeanInfo` dump; see [`mbean`] for both formats.
+//!
+//! # Generated Types
+//!
+//! MBeans go to one module per domain, `<Namespace>.<Domain>`
+//! (`java.lang` → `JavaLang`). Each MBean gets:
+//!
+//! - a record of its readable attributes, named after its `type` key
+//!   (`java.lang:type=Memory` → `Memory`). MBeans sharing a `type` and the
+//!   same attributes and operations share the record, as the JVM's
+//!   garbage collectors do; others are told apart by their `name` key
+//! - `<MBean>Operation`, a union with one case per operation carrying a
+//!   `<MBean><Op>Args` record of its parameters; overloads are named
+//!   after their parameter types (`DumpHeapStringBoolean`)
+//! - `<MBean>Result`, a union with the same cases carrying what each
+//!   operation returns
+//!
+//! | Java                                   | Fusabi                        |
+//! |----------------------------------------|-------------------------------|
+//! | `boolean`                              | `bool`                        |
+//! | `byte` / `short` / `int`               | `int`                         |
+//! | `long`                                 | `int64`                       |
+//! | `float` / `double`                     | `float`                       |
+//! | `char`, `String`, `ObjectName`, `Date` | `string`                      |
+//! | arrays (`[J`, `String[]`)              | `T list`                      |
+//! | `CompositeData`, `TabularData`, `Map`  | `Map<string, any>`            |
+//! | `MemoryUsage` attributes of `java.lang` | `MemoryUsage`                |
+//!
+//! Attributes and results of Java primitive types are plain; any other
+//! type can be `null` and is `T option`.
+//!
+//! # Params
+//!
+//! | Param        | Meaning                                                      |
+//! |--------------|--------------------------------------------------------------|
+//! | `domain`     | Comma-separated domains to keep; `kafka.*` keeps every `kafka.` domain |
+//! | `operations` | `false` to generate attribute records only                   |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_jmx::JmxProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = JmxProvider::new();
+//! let params = ProviderParams::default().with("domain", "java.lang,kafka.server");
+//! let schema = provider.resolve_schema("http://broker:8778/jolokia/list", &params)?;
+//! let types = provider.generate_types(&schema, "Jvm")?;
+//! ```
+
+pub mod mbean;
+
+pub use mbean::{parse_metadata, Arg, Attribute, MBean, Operation};
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceValidator, TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, DuDef, VariantDef, TypeExpr, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use serde::{Deserialize, Serialize};
+
+/// Java types Fusabi has a primitive for, and whether the Java type is
+/// primitive and so never `null`
+const JAVA_TYPES: &[(&str, &str, bool)] = &[
+    ("boolean", "bool", true),
+    ("byte", "int", true),
+    ("short", "int", true),
+    ("int", "int", true),
+    ("long", "int64", true),
+    ("float", "float", true),
+    ("double", "float", true),
+    ("char", "string", true),
+    ("java.lang.Boolean", "bool", false),
+    ("java.lang.Byte", "int", false),
+    ("java.lang.Short", "int", false),
+    ("java.lang.Integer", "int", false),
+    ("java.lang.Long", "int64", false),
+    ("java.lang.Float", "float", false),
+    ("java.lang.Double", "float", false),
+    ("java.lang.Character", "string", false),
+    ("java.lang.String", "string", false),
+    ("javax.management.ObjectName", "string", false),
+    // Jolokia renders dates as ISO 8601 text
+    ("java.util.Date", "string", false),
+    ("java.lang.Object", "any", false),
+];
+
+/// Array type codes of `Class.getName()`, as in `[J`
+const ARRAY_CODES: &[(char, &str)] = &[
+    ('Z', "boolean"),
+    ('B', "byte"),
+    ('S', "short"),
+    ('I', "int"),
+    ('J', "long"),
+    ('F', "float"),
+    ('D', "double"),
+    ('C', "char"),
+];
+
+/// Types whose contents the metadata doesn't describe
+const OPAQUE_TYPES: &[&str] = &[
+    "javax.management.openmbean.CompositeData",
+    "javax.management.openmbean.TabularData",
+    "java.util.Map",
+    "java.util.HashMap",
+    "java.util.Properties",
+];
+
+/// `java.lang` attributes holding a `java.lang.management.MemoryUsage`
+const MEMORY_USAGE_ATTRIBUTES: &[&str] =
+    &["HeapMemoryUsage", "NonHeapMemoryUsage", "Usage", "PeakUsage", "CollectionUsage"];
+
+/// Record for `java.lang.management.MemoryUsage`
+const MEMORY_USAGE: &str = "MemoryUsage";
+
+/// The selected MBeans, stored in [`Schema::Custom`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Metadata {
+    mbeans: Vec<MBean>,
+    operations: bool,
+}
+
+/// JMX MBean type provider
+pub struct JmxProvider {
+    generator: TypeGenerator,
+}
+
+impl JmxProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<Metadata> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected MBean metadata: {}", e)))
+    }
+
+    /// One module per domain, and what was lost on the way
+    fn generate(&self, metadata: &Metadata, namespace: &str) -> (Vec<GeneratedModule>, Vec<Diagnostic>) {
+        let mut generation = Generation {
+            generator: &self.generator,
+            types: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+
+        let mut domains: Vec<&str> = metadata.mbeans.iter().map(|m| m.domain.as_str()).collect();
+        domains.dedup();
+        let mut modules: Vec<GeneratedModule> = Vec::new();
+        for domain in domains {
+            let path = vec![namespace.to_string(), generation.type_name(domain)];
+            // Record names taken in this domain, with the MBean that took them
+            let mut named: Vec<(String, &MBean)> = Vec::new();
+            for mbean in metadata.mbeans.iter().filter(|m| m.domain == domain) {
+                let base = generation.type_name(
+                    mbean.key("type").or_else(|| mbean.key("name")).unwrap_or(&mbean.object_name()),
+                );
+                let shared = named.iter().any(|(name, other)| {
+                    *name == base && other.attributes == mbean.attributes && other.operations == mbean.operations
+                });
+                if shared {
+                    continue;
+                }
+                let mut name = base.clone();
+                if named.iter().any(|(n, _)| *n == name) {
+                    name = format!("{}{}", base, generation.type_name(mbean.key("name").unwrap_or_default()));
+                }
+                let mut n = 2;
+                while named.iter().any(|(existing, _)| *existing == name) {
+                    name = format!("{}{}", base, n);
+                    n += 1;
+                }
+                generation.mbean(mbean, &name, metadata.operations);
+                named.push((name, mbean));
+            }
+
+            let types = std::mem::take(&mut generation.types);
+            match modules.iter_mut().find(|m| m.path == path) {
+                Some(module) => module.types.extend(types),
+                None => {
+                    let mut module = GeneratedModule::new(path);
+                    module.types = types;
+                    modules.push(module);
+                }
+            }
+        }
+        (modules, generation.diagnostics)
+    }
+}
+
+impl Default for JmxProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for JmxProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("JmxProvider")
+            .param(ParamSpec::new("domain", ParamKind::List).describe("Domains to keep"))
+            .param(
+                ParamSpec::new("operations", ParamKind::Bool)
+                    .default("true")
+                    .describe("Whether to generate operation unions"),
+            )
+    }
+}
+
+impl TypeProvider for JmxProvider {
+    fn name(&self) -> &str {
+        "JmxProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let trimmed = source.trim_start();
+        let mut mbeans = if trimmed.starts_with('{') || trimmed.starts_with('[') {
+            parse_metadata(source)?
+        } else {
+            parse_metadata(&read_source(source, params)?)?
+        };
+
+        if let Some(patterns) = typed.list("domain") {
+            mbeans.retain(|m| {
+                patterns.iter().any(|p| match p.strip_suffix('*') {
+                    Some(prefix) => m.domain.starts_with(prefix),
+                    None => m.domain == *p,
+                })
+            });
+        }
+        // Keep each domain's MBeans together, in their listed order
+        let mut domains: Vec<String> = Vec::new();
+        for mbean in &mbeans {
+            if !domains.contains(&mbean.domain) {
+                domains.push(mbean.domain.clone());
+            }
+        }
+        mbeans.sort_by_key(|m| domains.iter().position(|d| *d == m.domain));
+
+        let operations = typed.bool("operations").unwrap_or(true);
+
+        let json = serde_json::to_string(&Metadata { mbeans, operations })
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected MBean metadata".to_string()));
+        };
+
+        let mut result = GeneratedTypes::new();
+        result.modules = self.generate(&self.parse_schema(json)?, namespace).0;
+        Ok(result)
+    }
+}
+
+impl SourceValidator for JmxProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected MBean metadata".to_string()));
+        };
+
+        let metadata = self.parse_schema(json)?;
+        let mut diagnostics = Vec::new();
+        if metadata.mbeans.is_empty() {
+            diagnostics.push(Diagnostic::warning("no-mbeans", "No MBeans to generate types for"));
+        }
+        diagnostics.extend(self.generate(&metadata, "Jmx").1);
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for JmxProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+/// Types generated for one domain, with what was lost on the way
+struct Generation<'a> {
+    generator: &'a TypeGenerator,
+    types: Vec<TypeDefinition>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Generation<'_> {
+    fn mbean(&mut self, mbean: &MBean, name: &str, operations: bool) {
+        let object_name = mbean.object_name();
+        let mut fields = Vec::new();
+        for attribute in &mbean.attributes {
+            let location = format!("{}.{}", object_name, attribute.name);
+            if !attribute.readable {
+                self.diagnostics.push(
+                    Diagnostic::info("write-only", "The attribute can't be read, so it has no field").at(location),
+                );
+                continue;
+            }
+            let memory_usage = mbean.domain == "java.lang" && MEMORY_USAGE_ATTRIBUTES.contains(&attribute.name.as_str());
+            let ty = if memory_usage {
+                self.memory_usage();
+                TypeShape::named(MEMORY_USAGE).option()
+            } else {
+                self.java_type(&attribute.type_name, &location)
+            };
+            fields.push((attribute.name.clone(), ty.into()));
+        }
+        self.types.push(TypeDefinition::Record(RecordDef {
+            name: name.to_string(),
+            fields,
+        }));
+
+        if !operations || mbean.operations.is_empty() {
+            return;
+        }
+        let mut cases = Vec::new();
+        let mut results = Vec::new();
+        for operation in &mbean.operations {
+            let overloaded = mbean.operations.iter().filter(|o| o.name == operation.name).count() > 1;
+            let mut case = self.type_name(&operation.name);
+            if overloaded {
+                let suffix: String = if operation.args.is_empty() {
+                    "NoArgs".to_string()
+                } else {
+                    operation.args.iter().map(|a| simple_name(&a.type_name)).collect()
+                };
+                case.push_str(&suffix);
+            }
+            let location = format!("{}.{}", object_name, operation.name);
+
+            if operation.args.is_empty() {
+                cases.push(VariantDef::new_simple(case.clone()));
+            } else {
+                let args = format!("{}{}Args", name, case);
+                let fields = operation
+                    .args
+                    .iter()
+                    .map(|arg| (arg.name.clone(), self.java_type(&arg.type_name, &location).into()))
+                    .collect();
+                self.types.push(TypeDefinition::Record(RecordDef { name: args.clone(), fields }));
+                cases.push(VariantDef::new(case.clone(), vec![TypeShape::named(args).into()]));
+            }
+
+            if operation.return_type == "void" {
+                results.push(VariantDef::new_simple(case));
+            } else {
+                let ty = self.java_type(&operation.return_type, &location);
+                results.push(VariantDef::new(case, vec![ty.into()]));
+            }
+        }
+        self.types.push(TypeDefinition::Du(DuDef {
+            name: format!("{}Operation", name),
+            variants: cases,
+        }));
+        self.types.push(TypeDefinition::Du(DuDef {
+            name: format!("{}Result", name),
+            variants: results,
+        }));
+    }
+
+    /// Fusabi type of a Java type name, `T option` unless it is primitive
+    fn java_type(&mut self, java: &str, location: &str) -> TypeShape {
+        let (ty, primitive) = self.java_shape(java, location);
+        ty.option_unless(primitive)
+    }
+
+    fn java_shape(&mut self, java: &str, location: &str) -> (TypeShape, bool) {
+        if let Some((_, ty, primitive)) = JAVA_TYPES.iter().find(|(name, _, _)| *name == java) {
+            return (TypeShape::named(*ty), *primitive);
+        }
+
+        let element = java
+            .strip_suffix("[]")
+            .map(str::to_string)
+            .or_else(|| {
+                let code = java.strip_prefix('[')?;
+                match code.strip_prefix('L').and_then(|c| c.strip_suffix(';')) {
+                    Some(class) => Some(class.to_string()),
+                    None => ARRAY_CODES
+                        .iter()
+                        .find(|(c, _)| code.len() == 1 && code.starts_with(*c))
+                        .map(|(_, name)| name.to_string()),
+                }
+            });
+        if let Some(element) = element {
+            let (element, _) = self.java_shape(&element, location);
+            return (element.list(), false);
+        }
+
+        if OPAQUE_TYPES.contains(&java) {
+            self.diagnostics.push(
+                Diagnostic::info(
+                    "composite-untyped",
+                    format!("`{}` doesn't describe its items; typed as `Map<string, any>`", short_name(java)),
+                )
+                .at(location.to_string()),
+            );
+            return (TypeShape::map(TypeShape::named("string"), TypeShape::named("any")), false);
+        }
+
+        self.diagnostics.push(
+            Diagnostic::warning("unmapped-type", format!("No Fusabi type for `{}`; typed as `any`", java))
+                .at(location.to_string()),
+        );
+        (TypeShape::named("any"), false)
+    }
+
+    /// Generate `MemoryUsage` into the module once
+    fn memory_usage(&mut self) {
+        let exists = self.types.iter().any(|t| matches!(t, TypeDefinition::Record(r) if r.name == MEMORY_USAGE));
+        if exists {
+            return;
+        }
+        let int64 = |name: &str| -> (String, TypeExpr) { (name.to_string(), TypeShape::named("int64").into()) };
+        self.types.insert(
+            0,
+            TypeDefinition::Record(RecordDef {
+                name: MEMORY_USAGE.to_string(),
+                fields: vec![int64("init"), int64("used"), int64("committed"), int64("max")],
+            }),
+        );
+    }
+
+    /// PascalCase of a name that may hold any characters
+    fn type_name(&self, raw: &str) -> String {
+        let words: String = raw.chars().map(|c| if c.is_alphanumeric() { c } else { ' ' }).collect();
+        let name = self.generator.naming.apply(&words);
+        if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+            format!("MBean{}", name)
+        } else {
+            name
+        }
+    }
+}
+
+/// Class name without its package
+fn short_name(java: &str) -> &str {
+    java.rsplit('.').next().unwrap_or(java)
+}
+
+/// A parameter type as a name part: `boolean` is `Boolean`, `[J` is
+/// `LongArray`
+fn simple_name(java: &str) -> String {
+    let (element, array) = match java.strip_suffix("[]") {
+        Some(element) => (element, true),
+        None => match java.strip_prefix('[') {
+            Some(code) => {
+                let element = code.strip_prefix('L').and_then(|c| c.strip_suffix(';'));
+                let element = element.or_else(|| ARRAY_CODES.iter().find(|(c, _)| code.starts_with(*c)).map(|(_, n)| *n));
+                (element.unwrap_or("Object"), true)
+            }
+            None => (java, false),
+        },
+    };
+    let short = short_name(element);
+    let mut name: String = short.chars().take(1).flat_map(char::to_uppercase).chain(short.chars().skip(1)).collect();
+    if array {
+        name.push_str("Array");
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+    use serde_json::json;
+
+    fn list() -> String {
+        json!({"status": 200, "value": {
+            "java.lang": {
+                "type=Memory": {
+                    "attr": {
+                        "HeapMemoryUsage": {"type": "javax.management.openmbean.CompositeData", "rw": false},
+                        "ObjectPendingFinalizationCount": {"type": "int", "rw": false},
+                        "Verbose": {"type": "boolean", "rw": true}
+                    },
+                    "op": {"gc": {"args": [], "ret": "void"}}
+                },
+                "name=G1 Young Generation,type=GarbageCollector": {
+                    "attr": {"CollectionCount": {"type": "long"}, "MemoryPoolNames": {"type": "[Ljava.lang.String;"}}
+                },
+                "name=G1 Old Generation,type=GarbageCollector": {
+                    "attr": {"CollectionCount": {"type": "long"}, "MemoryPoolNames": {"type": "[Ljava.lang.String;"}}
+                },
+                "type=Threading": {
+                    "attr": {"AllThreadIds": {"type": "[J"}, "ThreadCount": {"type": "int"}},
+                    "op": {
+                        "getThreadInfo": [
+                            {"args": [{"name": "p1", "type": "long"}], "ret": "javax.management.openmbean.CompositeData"},
+                            {"args": [{"name": "p1", "type": "[J"}, {"name": "p2", "type": "int"}], "ret": "[Ljavax.management.openmbean.CompositeData;"}
+                        ]
+                    }
+                }
+            },
+            "kafka.server": {
+                "type=BrokerTopicMetrics,name=MessagesInPerSec": {
+                    "attr": {"Count": {"type": "long"}, "OneMinuteRate": {"type": "double"}, "RateUnit": {"type": "java.util.concurrent.TimeUnit"}}
+                }
+            }
+        }})
+        .to_string()
+    }
+
+    fn find<'a>(module: &'a GeneratedModule, name: &str) -> &'a TypeDefinition {
+        module
+            .types
+            .iter()
+            .find(|t| match t {
+                TypeDefinition::Record(r) => r.name == name,
+                TypeDefinition::Du(d) => d.name == name,
+            })
+            .unwrap_or_else(|| panic!("{} not found", name))
+    }
+
+    fn fields(definition: &TypeDefinition) -> Vec<(String, String)> {
+        let TypeDefinition::Record(record) = definition else {
+            panic!("Expected record");
+        };
+        record.fields.iter().map(|(name, ty)| (name.clone(), ty.to_string())).collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect()
+    }
+
+    fn cases(definition: &TypeDefinition) -> Vec<(String, String)> {
+        let TypeDefinition::Du(union) = definition else {
+            panic!("Expected union");
+        };
+        union
+            .variants
+            .iter()
+            .map(|v| (v.name.clone(), v.fields.iter().map(|f| f.to_string()).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn test_jolokia_types() {
+        let provider = JmxProvider::new();
+        let schema = provider.resolve_schema(&list(), &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Jvm").unwrap();
+
+        let paths: Vec<&[String]> = types.modules.iter().map(|m| m.path.as_slice()).collect();
+        assert_eq!(paths, [&["Jvm", "JavaLang"][..], &["Jvm", "KafkaServer"]]);
+
+        let java_lang = &types.modules[0];
+        let names: Vec<&str> = java_lang
+            .types
+            .iter()
+            .map(|t| match t {
+                TypeDefinition::Record(r) => r.name.as_str(),
+                TypeDefinition::Du(d) => d.name.as_str(),
+            })
+            .collect();
+        // The two collectors share `GarbageCollector`
+        assert_eq!(
+            names,
+            [
+                "MemoryUsage",
+                "GarbageCollector",
+                "Memory",
+                "MemoryOperation",
+                "MemoryResult",
+                "Threading",
+                "ThreadingGetThreadInfoLongArgs",
+                "ThreadingGetThreadInfoLongArrayIntArgs",
+                "ThreadingOperation",
+                "ThreadingResult",
+            ]
+        );
+        assert_eq!(
+            fields(find(java_lang, "Memory")),
+            pairs(&[
+                ("HeapMemoryUsage", "MemoryUsage option"),
+                ("ObjectPendingFinalizationCount", "int"),
+                ("Verbose", "bool"),
+            ])
+        );
+        assert_eq!(
+            fields(find(java_lang, "Threading")),
+            pairs(&[("AllThreadIds", "int64 list option"), ("ThreadCount", "int")])
+        );
+        assert_eq!(
+            cases(find(java_lang, "ThreadingResult")),
+            [
+                ("GetThreadInfoLong".to_string(), "Map<string, any> option".to_string()),
+                ("GetThreadInfoLongArrayInt".to_string(), "Map<string, any> list option".to_string()),
+            ]
+        );
+        assert_eq!(cases(find(java_lang, "MemoryOperation")), [("Gc".to_string(), String::new())]);
+
+        let kafka = &types.modules[1];
+        assert_eq!(
+            fields(find(kafka, "BrokerTopicMetrics")),
+            pairs(&[("Count", "int64"), ("OneMinuteRate", "float"), ("RateUnit", "any option")])
+        );
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/jmx.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_params() {
+        let provider = JmxProvider::new();
+        let params = ProviderParams::default().with("domain", "kafka.*").with("operations", "false");
+        let schema = provider.resolve_schema(&list(), &params).unwrap();
+        let types = provider.generate_types(&schema, "Jvm").unwrap();
+        assert_eq!(types.modules.len(), 1);
+        assert_eq!(types.modules[0].path, ["Jvm", "KafkaServer"]);
+
+        let params = ProviderParams::default().with("domain", "java.lang").with("operations", "false");
+        let schema = provider.resolve_schema(&list(), &params).unwrap();
+        let types = provider.generate_types(&schema, "Jvm").unwrap();
+        assert_eq!(types.modules[0].types.len(), 4);
+
+        let params = ProviderParams::default().with("operations", "no");
+        assert!(provider.resolve_schema(&list(), &params).is_err());
+        let params = ProviderParams::default().with("domains", "java.lang");
+        let err = provider.resolve_schema(&list(), &params).unwrap_err();
+        assert!(err.to_string().contains("did you mean 'domain'?"));
+    }
+
+    #[test]
+    fn test_distinct_mbeans_of_a_type() {
+        let dump = json!([
+            {"objectName": "app:type=Cache,name=users", "attributes": [{"name": "Size", "type": "int"}]},
+            {"objectName": "app:type=Cache,name=orders", "attributes": [{"name": "Hits", "type": "long"}]},
+            {"objectName": "app:type=Cache,name=users", "attributes": [{"name": "Misses", "type": "long"}]},
+        ]);
+        let provider = JmxProvider::new();
+        let schema = provider.resolve_schema(&dump.to_string(), &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "App").unwrap();
+        let names: Vec<&str> = types.modules[0]
+            .types
+            .iter()
+            .map(|t| match t {
+                TypeDefinition::Record(r) => r.name.as_str(),
+                TypeDefinition::Du(d) => d.name.as_str(),
+            })
+            .collect();
+        assert_eq!(names, ["Cache", "CacheOrders", "CacheUsers"]);
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = JmxProvider::new();
+        let report = provider.validate_source(&list(), &ProviderParams::default());
+        assert!(report.is_valid());
+
+        let codes: Vec<(&str, Option<&str>)> =
+            report.diagnostics.iter().map(|d| (d.code.as_str(), d.location.as_deref())).collect();
+        assert!(codes.contains(&("unmapped-type", Some("kafka.server:type=BrokerTopicMetrics,name=MessagesInPerSec.RateUnit"))));
+        assert!(codes.contains(&("composite-untyped", Some("java.lang:type=Threading.getThreadInfo"))));
+        assert!(!codes.iter().any(|(_, location)| *location == Some("java.lang:type=Memory.HeapMemoryUsage")));
+
+        let report = provider.validate_source(r#"{"value": {}}"#, &ProviderParams::default());
+        assert_eq!(report.warnings().next().unwrap().code, "no-mbeans");
+    }
+}
@@ -0,0 +1,315 @@
+//! MBean metadata reading
+//!
+//! Two exports are read:
+//!
+//! - a Jolokia `list` response, `{"value": {<domain>: {<keys>: {"attr", "op", ...}}}}`,
+//!   or its `value` alone; operations with overloads list each signature
+//! - an `MBeanInfo` dump, an array of MBeans (or `{"mbeans": [...]}`) with
+//!   the properties of `javax.management.MBeanInfo`: `objectName`,
+//!   `className`, `attributes` (`name`, `type`, `readable`, `writable`) and
+//!   `operations` (`name`, `returnType`, `signature`)
+//!
+//! Types stay Java type names, as in the metadata: `long`, `java.lang.String`,
+//! `[J` or `javax.management.openmbean.CompositeData`.
+
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A registered MBean
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MBean {
+    pub domain: String,
+    /// Key properties in object name order, such as `type=Memory`
+    pub keys: Vec<(String, String)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class_name: Option<String>,
+    pub attributes: Vec<Attribute>,
+    pub operations: Vec<Operation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attribute {
+    pub name: String,
+    pub type_name: String,
+    pub readable: bool,
+    pub writable: bool,
+}
+
+/// One operation signature; overloads are separate operations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Operation {
+    pub name: String,
+    pub args: Vec<Arg>,
+    pub return_type: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Arg {
+    pub name: String,
+    pub type_name: String,
+}
+
+impl MBean {
+    /// `domain:key=value,...`
+    pub fn object_name(&self) -> String {
+        let keys: Vec<String> = self.keys.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        format!("{}:{}", self.domain, keys.join(","))
+    }
+
+    pub fn key(&self, name: &str) -> Option<&str> {
+        self.keys.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+    }
+}
+
+/// Read a Jolokia `list` response or an `MBeanInfo` dump
+pub fn parse_metadata(content: &str) -> ProviderResult<Vec<MBean>> {
+    let document: Value = serde_json::from_str(content)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid MBean metadata: {}", e)))?;
+
+    let dump = document.as_array().or_else(|| document.get("mbeans").and_then(Value::as_array));
+    if let Some(mbeans) = dump {
+        return mbeans.iter().map(mbean_info).collect();
+    }
+
+    if let Some(status) = document.get("status").and_then(Value::as_u64).filter(|s| *s != 200) {
+        let error = document.get("error").and_then(Value::as_str).unwrap_or("unknown error");
+        return Err(ProviderError::InvalidSource(format!("Jolokia answered {}: {}", status, error)));
+    }
+    let domains = document
+        .get("value")
+        .unwrap_or(&document)
+        .as_object()
+        .ok_or_else(|| parse_error("Expected a Jolokia `list` response or an MBeanInfo array"))?;
+
+    let mut mbeans = Vec::new();
+    for (domain, entries) in domains {
+        let entries = entries
+            .as_object()
+            .ok_or_else(|| parse_error(&format!("Domain `{}` is not an object of MBeans", domain)))?;
+        for (keys, info) in entries {
+            mbeans.push(jolokia_mbean(domain, keys, info)?);
+        }
+    }
+    Ok(mbeans)
+}
+
+fn jolokia_mbean(domain: &str, keys: &str, info: &Value) -> ProviderResult<MBean> {
+    let mut attributes = Vec::new();
+    if let Some(attrs) = info.get("attr").and_then(Value::as_object) {
+        for (name, attr) in attrs {
+            attributes.push(Attribute {
+                name: name.clone(),
+                type_name: text(attr, "type").unwrap_or("java.lang.Object").to_string(),
+                // Jolokia lists readable attributes; `rw` says whether they
+                // can be written too
+                readable: true,
+                writable: attr.get("rw").and_then(Value::as_bool).unwrap_or(false),
+            });
+        }
+    }
+
+    let mut operations = Vec::new();
+    if let Some(ops) = info.get("op").and_then(Value::as_object) {
+        for (name, op) in ops {
+            let overloads = match op {
+                Value::Array(overloads) => overloads.iter().collect(),
+                op => vec![op],
+            };
+            for op in overloads {
+                let args = op
+                    .get("args")
+                    .and_then(Value::as_array)
+                    .map(|args| args.iter().enumerate().map(|(i, arg)| argument(i, arg)).collect())
+                    .unwrap_or_default();
+                operations.push(Operation {
+                    name: name.clone(),
+                    args,
+                    return_type: text(op, "ret").unwrap_or("void").to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(MBean {
+        domain: domain.to_string(),
+        keys: parse_keys(keys),
+        class_name: text(info, "class").map(str::to_string),
+        attributes,
+        operations,
+    })
+}
+
+fn mbean_info(info: &Value) -> ProviderResult<MBean> {
+    let object_name = text(info, "objectName").ok_or_else(|| parse_error("MBeanInfo entry has no `objectName`"))?;
+    let (domain, keys) = object_name
+        .split_once(':')
+        .ok_or_else(|| parse_error(&format!("`{}` is not an object name", object_name)))?;
+
+    let attributes = info
+        .get("attributes")
+        .and_then(Value::as_array)
+        .map(|attrs| {
+            attrs
+                .iter()
+                .filter_map(|attr| {
+                    Some(Attribute {
+                        name: text(attr, "name")?.to_string(),
+                        type_name: text(attr, "type").unwrap_or("java.lang.Object").to_string(),
+                        readable: attr.get("readable").and_then(Value::as_bool).unwrap_or(true),
+                        writable: attr.get("writable").and_then(Value::as_bool).unwrap_or(false),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let operations = info
+        .get("operations")
+        .and_then(Value::as_array)
+        .map(|ops| {
+            ops.iter()
+                .filter_map(|op| {
+                    let args = op.get("signature").and_then(Value::as_array).map(|args| {
+                        args.iter().enumerate().map(|(i, arg)| argument(i, arg)).collect()
+                    });
+                    Some(Operation {
+                        name: text(op, "name")?.to_string(),
+                        args: args.unwrap_or_default(),
+                        return_type: text(op, "returnType").unwrap_or("void").to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(MBean {
+        domain: domain.to_string(),
+        keys: parse_keys(keys),
+        class_name: text(info, "className").map(str::to_string),
+        attributes,
+        operations,
+    })
+}
+
+/// An operation parameter; unnamed ones, which compiled MBeans often
+/// have, are `p1`, `p2`, ...
+fn argument(index: usize, arg: &Value) -> Arg {
+    Arg {
+        name: text(arg, "name").filter(|n| !n.is_empty()).map_or_else(|| format!("p{}", index + 1), str::to_string),
+        type_name: text(arg, "type").unwrap_or("java.lang.Object").to_string(),
+    }
+}
+
+/// Key properties of an object name, unquoting quoted values
+pub fn parse_keys(keys: &str) -> Vec<(String, String)> {
+    let mut parsed = Vec::new();
+    let mut rest = keys;
+    while !rest.is_empty() {
+        let Some((key, after)) = rest.split_once('=') else {
+            break;
+        };
+        let (value, next) = match after.strip_prefix('"') {
+            Some(quoted) => {
+                let mut end = quoted.len();
+                let mut escaped = false;
+                for (i, c) in quoted.char_indices() {
+                    match c {
+                        '\\' if !escaped => escaped = true,
+                        '"' if !escaped => {
+                            end = i;
+                            break;
+                        }
+                        _ => escaped = false,
+                    }
+                }
+                let value = quoted[..end].replace("\\\"", "\"").replace("\\\\", "\\");
+                let next = quoted.get(end + 1..).unwrap_or_default();
+                (value, next.strip_prefix(',').unwrap_or(next))
+            }
+            None => match after.split_once(',') {
+                Some((value, next)) => (value.to_string(), next),
+                None => (after.to_string(), ""),
+            },
+        };
+        parsed.push((key.trim().to_string(), value));
+        rest = next;
+    }
+    parsed
+}
+
+fn text<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(Value::as_str)
+}
+
+fn parse_error(message: &str) -> ProviderError {
+    ProviderError::ParseError(message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_jolokia_list() {
+        let list = json!({
+            "request": {"type": "list"},
+            "status": 200,
+            "value": {"java.lang": {
+                "name=G1 Young Generation,type=GarbageCollector": {
+                    "class": "com.sun.management.internal.GarbageCollectorExtImpl",
+                    "attr": {"CollectionCount": {"type": "long", "rw": false, "desc": "CollectionCount"}},
+                    "op": {}
+                },
+                "type=Memory": {
+                    "attr": {"Verbose": {"type": "boolean", "rw": true}},
+                    "op": {
+                        "gc": {"args": [], "ret": "void", "desc": "gc"},
+                        "dump": [
+                            {"args": [], "ret": "java.lang.String"},
+                            {"args": [{"name": "", "type": "boolean"}], "ret": "java.lang.String"}
+                        ]
+                    }
+                }
+            }}
+        });
+        let mbeans = parse_metadata(&list.to_string()).unwrap();
+        assert_eq!(mbeans.len(), 2);
+        assert_eq!(mbeans[0].key("name"), Some("G1 Young Generation"));
+        assert_eq!(mbeans[0].object_name(), "java.lang:name=G1 Young Generation,type=GarbageCollector");
+        assert!(mbeans[1].attributes[0].writable);
+
+        let ops: Vec<(&str, usize, &str)> =
+            mbeans[1].operations.iter().map(|o| (o.name.as_str(), o.args.len(), o.return_type.as_str())).collect();
+        assert_eq!(ops, [("dump", 0, "java.lang.String"), ("dump", 1, "java.lang.String"), ("gc", 0, "void")]);
+        assert_eq!(mbeans[1].operations[1].args[0].name, "p1");
+
+        let error = json!({"status": 403, "error": "Access denied"});
+        assert!(parse_metadata(&error.to_string()).unwrap_err().to_string().contains("403"));
+    }
+
+    #[test]
+    fn test_mbean_info_dump() {
+        let dump = json!([{
+            "objectName": "kafka.server:type=BrokerTopicMetrics,name=MessagesInPerSec",
+            "className": "com.yammer.metrics.reporting.JmxReporter$Meter",
+            "attributes": [
+                {"name": "Count", "type": "long", "readable": true, "writable": false},
+                {"name": "Secret", "type": "java.lang.String", "readable": false, "writable": true}
+            ],
+            "operations": [{"name": "reset", "returnType": "void", "signature": [{"name": "force", "type": "boolean"}]}]
+        }]);
+        let mbeans = parse_metadata(&dump.to_string()).unwrap();
+        assert_eq!(mbeans[0].domain, "kafka.server");
+        assert_eq!(mbeans[0].key("type"), Some("BrokerTopicMetrics"));
+        assert!(!mbeans[0].attributes[1].readable);
+        assert_eq!(mbeans[0].operations[0].args[0].name, "force");
+
+        assert_eq!(
+            parse_keys(r#"type=Pool,name="a, \"b\"",x=1"#),
+            [("type".to_string(), "Pool".to_string()), ("name".to_string(), "a, \"b\"".to_string()), ("x".to_string(), "1".to_string())]
+        );
+        assert!(parse_metadata(r#"[{"className": "X"}]"#).is_err());
+    }
+}
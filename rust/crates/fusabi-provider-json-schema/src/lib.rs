@@ -2,6 +2,10 @@
 //!
 //! Generates Fusabi types from JSON Schema definitions.
 //!
+//! As a [`SchemaExporter`], it also writes generated types back out as a
+//! JSON Schema document, with every type under `$defs`, or with the type
+//! named by the `root` param as the document root.
+//!
 //! # Example
 //!
 //! ```rust,ignore
@@ -18,22 +22,25 @@ mod types;
 
 pub use types::JsonSchemaType;
 
+use fusabi_jsonschema_core::Converter;
+use fusabi_provider_common::{
+    read_source, to_json_schema, type_to_json_schema, Capabilities, DescribedProvider, Diagnostic,
+    OutputFeature, SchemaExporter, SourceValidator,
+};
 use fusabi_type_providers::{
-    TypeProvider, ProviderParams, Schema,
-    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
-    RecordDef, DuDef, VariantDef, TypeExpr, TypeDefinition,
+    TypeProvider, ProviderParams, Schema, GeneratedTypes,
     ProviderError, ProviderResult,
 };
 
 /// JSON Schema type provider
 pub struct JsonSchemaProvider {
-    generator: TypeGenerator,
+    converter: Converter,
 }
 
 impl JsonSchemaProvider {
     pub fn new() -> Self {
         Self {
-            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+            converter: Converter::new(),
         }
     }
 
@@ -41,153 +48,6 @@ impl JsonSchemaProvider {
     fn parse_schema(&self, json: &str) -> ProviderResult<types::JsonSchema> {
         parser::parse_json_schema(json)
     }
-
-    /// Generate types from parsed schema
-    fn generate_from_schema(
-        &self,
-        schema: &types::JsonSchema,
-        namespace: &str
-    ) -> ProviderResult<GeneratedTypes> {
-        let mut result = GeneratedTypes::new();
-        let mut definitions_module = GeneratedModule::new(vec![namespace.to_string()]);
-
-        // Process definitions first
-        for (name, def_schema) in &schema.definitions {
-            if let Some(type_def) = self.schema_to_typedef(name, def_schema)? {
-                definitions_module.types.push(type_def);
-            }
-        }
-
-        // Process root schema
-        if let Some(root_type) = self.schema_to_typedef("Root", schema)? {
-            result.root_types.push(root_type);
-        }
-
-        if !definitions_module.types.is_empty() {
-            result.modules.push(definitions_module);
-        }
-
-        Ok(result)
-    }
-
-    /// Convert a JSON Schema to a Fusabi TypeDefinition
-    fn schema_to_typedef(
-        &self,
-        name: &str,
-        schema: &types::JsonSchema,
-    ) -> ProviderResult<Option<TypeDefinition>> {
-        match &schema.schema_type {
-            Some(JsonSchemaType::Object) => {
-                let fields = self.object_to_fields(schema)?;
-                Ok(Some(TypeDefinition::Record(RecordDef {
-                    name: self.generator.naming.apply(name),
-                    fields,
-                })))
-            }
-            Some(JsonSchemaType::String) if !schema.enum_values.is_empty() => {
-                // String enum -> DU
-                let variants = schema.enum_values.iter()
-                    .filter_map(|v| v.as_str())
-                    .map(|s| VariantDef::new_simple(self.generator.naming.apply(s)))
-                    .collect();
-                Ok(Some(TypeDefinition::Du(DuDef {
-                    name: self.generator.naming.apply(name),
-                    variants,
-                })))
-            }
-            _ if !schema.one_of.is_empty() => {
-                // oneOf -> DU
-                let variants = self.one_of_to_variants(&schema.one_of)?;
-                Ok(Some(TypeDefinition::Du(DuDef {
-                    name: self.generator.naming.apply(name),
-                    variants,
-                })))
-            }
-            _ => Ok(None), // Primitive types don't need typedef
-        }
-    }
-
-    /// Convert object properties to record fields
-    fn object_to_fields(
-        &self,
-        schema: &types::JsonSchema,
-    ) -> ProviderResult<Vec<(String, TypeExpr)>> {
-        let mut fields = Vec::new();
-
-        for (prop_name, prop_schema) in &schema.properties {
-            let type_expr = self.schema_to_type_expr(prop_schema)?;
-            let is_required = schema.required.contains(prop_name);
-
-            let final_type = if is_required {
-                type_expr
-            } else {
-                // Wrap in Option for optional fields
-                TypeExpr::Named(format!("{} option", type_expr))
-            };
-
-            fields.push((prop_name.clone(), final_type));
-        }
-
-        Ok(fields)
-    }
-
-    /// Convert JSON Schema to TypeExpr
-    fn schema_to_type_expr(&self, schema: &types::JsonSchema) -> ProviderResult<TypeExpr> {
-        // Handle $ref
-        if let Some(ref_path) = &schema.reference {
-            let type_name = ref_path.split('/').last().unwrap_or("Unknown");
-            return Ok(TypeExpr::Named(self.generator.naming.apply(type_name)));
-        }
-
-        match &schema.schema_type {
-            Some(JsonSchemaType::String) => Ok(TypeExpr::Named("string".to_string())),
-            Some(JsonSchemaType::Integer) => Ok(TypeExpr::Named("int".to_string())),
-            Some(JsonSchemaType::Number) => Ok(TypeExpr::Named("float".to_string())),
-            Some(JsonSchemaType::Boolean) => Ok(TypeExpr::Named("bool".to_string())),
-            Some(JsonSchemaType::Null) => Ok(TypeExpr::Named("unit".to_string())),
-            Some(JsonSchemaType::Array) => {
-                if let Some(items) = &schema.items {
-                    let item_type = self.schema_to_type_expr(items)?;
-                    Ok(TypeExpr::Named(format!("{} list", item_type)))
-                } else {
-                    Ok(TypeExpr::Named("any list".to_string()))
-                }
-            }
-            Some(JsonSchemaType::Object) => {
-                // Inline object - use dynamic map type
-                Ok(TypeExpr::Named("Map<string, any>".to_string()))
-            }
-            None => Ok(TypeExpr::Named("any".to_string())),
-        }
-    }
-
-    /// Convert oneOf to DU variants
-    fn one_of_to_variants(
-        &self,
-        schemas: &[types::JsonSchema],
-    ) -> ProviderResult<Vec<VariantDef>> {
-        let mut variants = Vec::new();
-
-        for (i, schema) in schemas.iter().enumerate() {
-            // Try to find a discriminator field (like "type" or "kind")
-            let variant_name = schema.properties.get("type")
-                .or_else(|| schema.properties.get("kind"))
-                .and_then(|s| s.const_value.as_ref())
-                .and_then(|v| v.as_str())
-                .map(|s| self.generator.naming.apply(s))
-                .unwrap_or_else(|| format!("Variant{}", i));
-
-            // Get fields excluding discriminator
-            let fields: Vec<TypeExpr> = schema.properties.iter()
-                .filter(|(k, _)| *k != "type" && *k != "kind")
-                .map(|(_, v)| self.schema_to_type_expr(v))
-                .collect::<ProviderResult<_>>()?;
-
-            variants.push(VariantDef::new(variant_name, fields));
-        }
-
-        Ok(variants)
-    }
 }
 
 impl Default for JsonSchemaProvider {
@@ -201,18 +61,12 @@ impl TypeProvider for JsonSchemaProvider {
         "JsonSchemaProvider"
     }
 
-    fn resolve_schema(&self, source: &str, _params: &ProviderParams) -> ProviderResult<Schema> {
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
         // For now, treat source as inline JSON or file path
         let json_str = if source.starts_with('{') {
             source.to_string()
-        } else if source.starts_with("file://") {
-            let path = source.strip_prefix("file://").unwrap();
-            std::fs::read_to_string(path)
-                .map_err(|e| ProviderError::IoError(e.to_string()))?
         } else {
-            // Treat as file path without prefix
-            std::fs::read_to_string(source)
-                .map_err(|e| ProviderError::IoError(e.to_string()))?
+            read_source(source, params)?
         };
 
         let value: serde_json::Value = serde_json::from_str(&json_str)
@@ -224,19 +78,90 @@ impl TypeProvider for JsonSchemaProvider {
     fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
         match schema {
             Schema::JsonSchema(value) => {
-                let json_str = serde_json::to_string(value)
-                    .map_err(|e| ProviderError::ParseError(e.to_string()))?;
-                let parsed = self.parse_schema(&json_str)?;
-                self.generate_from_schema(&parsed, namespace)
+                // Reject malformed schemas before converting
+                parser::parse_schema_value(value)?;
+                Ok(self.converter.convert_document(value, namespace))
             }
             _ => Err(ProviderError::ParseError("Expected JSON Schema".to_string())),
         }
     }
 }
 
+impl SourceValidator for JsonSchemaProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::JsonSchema(value) = schema else {
+            return Err(ProviderError::ParseError("Expected JSON Schema".to_string()));
+        };
+
+        let json_str =
+            serde_json::to_string(value).map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        let parsed = self.parse_schema(&json_str)?;
+
+        let mut diagnostics = Vec::new();
+        check_schema(&parsed, "#", &mut diagnostics);
+        Ok(diagnostics)
+    }
+}
+
+impl SchemaExporter for JsonSchemaProvider {
+    fn schema_extension(&self) -> &str {
+        "json"
+    }
+
+    fn export_schema(&self, types: &GeneratedTypes, params: &ProviderParams) -> ProviderResult<String> {
+        let document = match params.custom.get("root") {
+            Some(root) => type_to_json_schema(types, root).ok_or_else(|| {
+                ProviderError::InvalidSource(format!("Invalid root '{}': no such type", root))
+            })?,
+            None => to_json_schema(types),
+        };
+        serde_json::to_string_pretty(&document).map_err(|e| ProviderError::ParseError(e.to_string()))
+    }
+}
+
+impl DescribedProvider for JsonSchemaProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+/// Flag JSON Schema keywords the provider does not translate
+///
+/// Nullable `anyOf: [T, null]` and single-entry `allOf` are understood.
+fn check_schema(schema: &types::JsonSchema, pointer: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let is_null = |s: &types::JsonSchema| s.schema_type == Some(JsonSchemaType::Null);
+    let nullable_any_of = schema.any_of.len() == 2 && schema.any_of.iter().filter(|s| is_null(s)).count() == 1;
+    if !schema.any_of.is_empty() && !nullable_any_of {
+        diagnostics.push(
+            Diagnostic::warning("unsupported-keyword", "`anyOf` is ignored").at(pointer),
+        );
+    }
+    if schema.all_of.len() > 1 {
+        diagnostics.push(
+            Diagnostic::warning("unsupported-keyword", "`allOf` is ignored").at(pointer),
+        );
+    }
+
+    for (name, def) in &schema.definitions {
+        check_schema(def, &format!("{}/definitions/{}", pointer, name), diagnostics);
+    }
+    for (name, prop) in &schema.properties {
+        check_schema(prop, &format!("{}/properties/{}", pointer, name), diagnostics);
+    }
+    if let Some(items) = &schema.items {
+        check_schema(items, &format!("{}/items", pointer), diagnostics);
+    }
+    for (i, variant) in schema.one_of.iter().enumerate() {
+        check_schema(variant, &format!("{}/oneOf/{}", pointer, i), diagnostics);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fusabi_provider_common::emit::golden;
 
     #[test]
     fn test_generate_simple_object() {
@@ -254,5 +179,54 @@ mod tests {
         let types = provider.generate_types(&schema, "Test").unwrap();
 
         assert!(!types.root_types.is_empty());
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/json-schema.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = JsonSchemaProvider::new();
+        let json = r#"{
+            "type": "object",
+            "properties": {
+                "id": { "anyOf": [{ "type": "string" }, { "type": "integer" }] }
+            },
+            "required": ["id"]
+        }"#;
+
+        let report = provider.validate_source(json, &ProviderParams::default());
+        assert!(report.is_valid());
+
+        let codes: Vec<&str> = report.warnings().map(|d| d.code.as_str()).collect();
+        assert_eq!(codes, vec!["unsupported-keyword", "coerced-any"]);
+        assert_eq!(
+            report.diagnostics[0].location.as_deref(),
+            Some("#/properties/id")
+        );
+
+        let report = provider.validate_source("{ not json", &ProviderParams::default());
+        assert!(!report.is_valid());
+    }
+
+    #[test]
+    fn test_export_schema() {
+        let provider = JsonSchemaProvider::new();
+        let json = r#"{
+            "type": "object",
+            "properties": { "name": { "type": "string" } },
+            "required": ["name"]
+        }"#;
+        let schema = provider.resolve_schema(json, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Test").unwrap();
+
+        let exported = provider.export_schema(&types, &ProviderParams::default()).unwrap();
+        let document: serde_json::Value = serde_json::from_str(&exported).unwrap();
+        assert!(document["$defs"].as_object().is_some_and(|defs| !defs.is_empty()));
+
+        let params = ProviderParams::default().with("root", "Missing");
+        assert!(provider.export_schema(&types, &params).is_err());
     }
 }
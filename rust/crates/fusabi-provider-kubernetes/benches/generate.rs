@@ -0,0 +1,37 @@
+//! Resolve and generate benchmarks for large OpenAPI documents
+//!
+//! Run with `cargo bench -p fusabi-provider-kubernetes`. Set
+//! `FUSABI_BENCH_CORPUS` to a directory containing `kubernetes.json`, such
+//! as the output of `kubectl get --raw /openapi/v2`, to measure the real
+//! spec instead of the synthetic one of similar size.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use fusabi_provider_common::check_generated_types;
+use fusabi_provider_kubernetes::KubernetesProvider;
+use fusabi_provider_testkit::corpus;
+use fusabi_type_providers::{ProviderParams, TypeProvider};
+use std::hint::black_box;
+
+fn bench_generate(c: &mut Criterion) {
+    let provider = KubernetesProvider::new();
+    let spec = corpus::load_or("kubernetes.json", || corpus::openapi_document(3000, 20));
+    let path = corpus::write_temp("kubernetes.json", &spec);
+    let source = path.to_str().unwrap();
+    let params = ProviderParams::default();
+    let schema = provider.resolve_schema(source, &params).unwrap();
+    let types = provider.generate_types(&schema, "K8s").unwrap();
+
+    let mut group = c.benchmark_group("kubernetes");
+    group.sample_size(20);
+    group.bench_function("resolve", |b| {
+        b.iter(|| provider.resolve_schema(black_box(source), &params).unwrap())
+    });
+    group.bench_function("generate", |b| {
+        b.iter(|| provider.generate_types(black_box(&schema), "K8s").unwrap())
+    });
+    group.bench_function("validate", |b| b.iter(|| check_generated_types(black_box(&types))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_generate);
+criterion_main!(benches);
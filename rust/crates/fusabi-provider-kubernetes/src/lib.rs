@@ -1,7 +1,19 @@
 //! Kubernetes Type Provider
 //!
 //! Generates Fusabi types from Kubernetes OpenAPI schemas.
+//!
+//! # Sources
+//!
+//! - `embedded` - built-in core metadata types
+//! - path, `file://` or `https://` URL to an OpenAPI document (JSON or YAML), such as
+//!   the output of `kubectl get --raw /openapi/v2`
+//! - path or `file://` URL to a CustomResourceDefinition manifest; each
+//!   served version's `openAPIV3Schema` becomes a record
 
+use fusabi_jsonschema_core::{definitions_of, Converter};
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, SourceKind, SourceValidator, TypeShape,
+};
 use fusabi_type_providers::{
     TypeProvider, ProviderParams, Schema,
     GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
@@ -11,14 +23,54 @@ use fusabi_type_providers::{
 
 /// Kubernetes type provider
 pub struct KubernetesProvider {
-    generator: TypeGenerator,
+    converter: Converter,
 }
 
 impl KubernetesProvider {
     pub fn new() -> Self {
         Self {
-            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+            converter: Converter::with_generator(TypeGenerator::new(NamingStrategy::PascalCase)),
+        }
+    }
+
+    /// Generate types from an OpenAPI document or CRD manifest
+    fn generate_openapi_types(&self, document: &serde_json::Value, namespace: &str) -> GeneratedTypes {
+        let mut result = self.generate_core_types(namespace);
+
+        if document.get("kind").and_then(|k| k.as_str()) == Some("CustomResourceDefinition") {
+            let kind = document
+                .pointer("/spec/names/kind")
+                .and_then(|k| k.as_str())
+                .unwrap_or("Custom");
+            let versions = document
+                .pointer("/spec/versions")
+                .and_then(|v| v.as_array())
+                .cloned()
+                .unwrap_or_default();
+
+            for version in &versions {
+                let (Some(name), Some(schema)) = (
+                    version.get("name").and_then(|n| n.as_str()),
+                    version.pointer("/schema/openAPIV3Schema"),
+                ) else {
+                    continue;
+                };
+
+                if let Some(type_def) = self.converter.definition(kind, schema) {
+                    let mut module = GeneratedModule::new(vec![namespace.to_string(), name.to_string()]);
+                    module.types.push(type_def);
+                    result.modules.push(module);
+                }
+            }
+        } else if let Some(definitions) = definitions_of(document) {
+            let mut module = GeneratedModule::new(vec![namespace.to_string(), "Api".to_string()]);
+            module.types = self.converter.definitions(definitions);
+            if !module.types.is_empty() {
+                result.modules.push(module);
+            }
         }
+
+        result
     }
 
     fn generate_core_types(&self, namespace: &str) -> GeneratedTypes {
@@ -29,10 +81,10 @@ impl KubernetesProvider {
         core_module.types.push(TypeDefinition::Record(RecordDef {
             name: "ObjectMeta".to_string(),
             fields: vec![
-                ("name".to_string(), TypeExpr::Named("string".to_string())),
-                ("namespace".to_string(), TypeExpr::Named("string option".to_string())),
-                ("labels".to_string(), TypeExpr::Named("Map<string, string>".to_string())),
-                ("annotations".to_string(), TypeExpr::Named("Map<string, string>".to_string())),
+                ("name".to_string(), TypeShape::named("string").into()),
+                ("namespace".to_string(), TypeShape::named("string").option().into()),
+                ("labels".to_string(), TypeShape::map(TypeShape::named("string"), TypeShape::named("string")).into()),
+                ("annotations".to_string(), TypeShape::map(TypeShape::named("string"), TypeShape::named("string")).into()),
             ],
         }));
 
@@ -40,8 +92,8 @@ impl KubernetesProvider {
         core_module.types.push(TypeDefinition::Record(RecordDef {
             name: "TypeMeta".to_string(),
             fields: vec![
-                ("apiVersion".to_string(), TypeExpr::Named("string".to_string())),
-                ("kind".to_string(), TypeExpr::Named("string".to_string())),
+                ("apiVersion".to_string(), TypeShape::named("string").into()),
+                ("kind".to_string(), TypeShape::named("string").into()),
             ],
         }));
 
@@ -61,17 +113,19 @@ impl TypeProvider for KubernetesProvider {
         "KubernetesProvider"
     }
 
-    fn resolve_schema(&self, source: &str, _params: &ProviderParams) -> ProviderResult<Schema> {
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
         // For now, support "embedded" mode with built-in types
         if source == "embedded" {
             return Ok(Schema::Custom("embedded".to_string()));
         }
 
-        // Support file:// or http:// URLs for OpenAPI specs
-        Err(ProviderError::InvalidSource(format!(
-            "Kubernetes provider currently only supports 'embedded' source, got: {}",
-            source
-        )))
+        let content = read_source(source, params)?;
+
+        // YAML is a superset of JSON, so one parser covers both
+        let document: serde_json::Value = serde_yaml::from_str(&content)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+
+        Ok(Schema::OpenApi(document))
     }
 
     fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
@@ -79,11 +133,97 @@ impl TypeProvider for KubernetesProvider {
             Schema::Custom(s) if s == "embedded" => {
                 Ok(self.generate_core_types(namespace))
             }
-            Schema::OpenApi(_) => {
-                // TODO: Parse OpenAPI spec for full K8s types
-                Ok(self.generate_core_types(namespace))
+            Schema::OpenApi(document) => {
+                Ok(self.generate_openapi_types(document, namespace))
             }
             _ => Err(ProviderError::ParseError("Expected Kubernetes schema".to_string())),
         }
     }
 }
+
+impl SourceValidator for KubernetesProvider {}
+
+impl DescribedProvider for KubernetesProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .source(SourceKind::File)
+            .source(SourceKind::Remote)
+            .embedded("embedded")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+    use serde_json::json;
+
+    #[test]
+    fn test_embedded_core_types() {
+        let provider = KubernetesProvider::new();
+        let schema = provider.resolve_schema("embedded", &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "K8s").unwrap();
+
+        assert_eq!(types.modules.len(), 1);
+        assert_eq!(types.modules[0].types.len(), 2);
+    }
+
+    #[test]
+    fn test_crd_schema() {
+        let provider = KubernetesProvider::new();
+        let crd = json!({
+            "apiVersion": "apiextensions.k8s.io/v1",
+            "kind": "CustomResourceDefinition",
+            "spec": {
+                "names": { "kind": "Backup" },
+                "versions": [{
+                    "name": "v1",
+                    "schema": {
+                        "openAPIV3Schema": {
+                            "type": "object",
+                            "properties": {
+                                "spec": {
+                                    "type": "object",
+                                    "x-kubernetes-preserve-unknown-fields": true
+                                },
+                                "retries": { "x-kubernetes-int-or-string": true }
+                            }
+                        }
+                    }
+                }]
+            }
+        });
+
+        let types = provider.generate_types(&Schema::OpenApi(crd), "K8s").unwrap();
+        let module = types.modules.iter().find(|m| m.path == ["K8s", "v1"]).unwrap();
+
+        let TypeDefinition::Record(record) = &module.types[0] else {
+            panic!("Expected record");
+        };
+        assert_eq!(record.name, "Backup");
+        assert_eq!(record.fields[0].1.to_string(), "string option");
+        assert_eq!(record.fields[1].1.to_string(), "Map<string, any> option");
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/kubernetes.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_openapi_definitions() {
+        let provider = KubernetesProvider::new();
+        let document = json!({
+            "swagger": "2.0",
+            "definitions": {
+                "Probe": {
+                    "type": "object",
+                    "properties": { "periodSeconds": { "type": "integer" } }
+                }
+            }
+        });
+
+        let types = provider.generate_types(&Schema::OpenApi(document), "K8s").unwrap();
+        assert!(types.modules.iter().any(|m| m.path == ["K8s", "Api"]));
+    }
+}
@@ -0,0 +1,145 @@
+//! Delta Lake table metadata
+//!
+//! The schema of a Delta table is the `schemaString` of the latest
+//! `metaData` action in its `_delta_log`, a JSON-encoded Spark struct type.
+//! Commits are newline-delimited JSON, one action per line.
+
+use crate::types::{DataType, Field, PartitionField, PartitionSource, TableFormat, TableMetadata};
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde_json::Value;
+
+/// The last `metaData` action among the lines of one or more commits
+pub fn latest_metadata<'a>(commits: impl IntoIterator<Item = &'a str>) -> ProviderResult<Option<Value>> {
+    let mut latest = None;
+    for line in commits.into_iter().flat_map(str::lines).map(str::trim).filter(|l| !l.is_empty()) {
+        let action: Value = serde_json::from_str(line)
+            .map_err(|e| ProviderError::ParseError(format!("Invalid Delta log action: {}", e)))?;
+        if let Some(metadata) = action.get("metaData") {
+            latest = Some(metadata.clone());
+        }
+    }
+    Ok(latest)
+}
+
+/// Read a `metaData` action
+pub fn parse_metadata(metadata: &Value) -> ProviderResult<TableMetadata> {
+    let schema_string = metadata
+        .get("schemaString")
+        .and_then(Value::as_str)
+        .ok_or_else(|| ProviderError::ParseError("Delta metaData has no schemaString".to_string()))?;
+    let schema: Value = serde_json::from_str(schema_string)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid Delta schemaString: {}", e)))?;
+
+    let mut table = parse_schema(&schema)?;
+    table.name = metadata.get("name").and_then(Value::as_str).map(String::from);
+    table.partition = metadata
+        .get("partitionColumns")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(|column| PartitionField {
+            name: column.to_string(),
+            source: PartitionSource::Column(column.to_string()),
+            transform: "identity".to_string(),
+        })
+        .collect();
+    Ok(table)
+}
+
+/// Read a bare schema string: a Spark struct type with no partitioning
+pub fn parse_schema(schema: &Value) -> ProviderResult<TableMetadata> {
+    Ok(TableMetadata {
+        format: TableFormat::Delta,
+        name: None,
+        fields: struct_fields(schema)?,
+        partition: Vec::new(),
+    })
+}
+
+fn struct_fields(schema: &Value) -> ProviderResult<Vec<Field>> {
+    let fields = schema
+        .get("fields")
+        .and_then(Value::as_array)
+        .ok_or_else(|| ProviderError::ParseError("Delta struct has no fields".to_string()))?;
+    fields.iter().map(field).collect()
+}
+
+fn field(value: &Value) -> ProviderResult<Field> {
+    let name = value
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or_else(|| ProviderError::ParseError(format!("Delta field has no name: {}", value)))?;
+    let data_type = value
+        .get("type")
+        .ok_or_else(|| ProviderError::ParseError(format!("Delta field `{}` has no type", name)))?;
+
+    Ok(Field {
+        id: None,
+        name: name.to_string(),
+        data_type: data_type_of(data_type)?,
+        required: !value.get("nullable").and_then(Value::as_bool).unwrap_or(true),
+    })
+}
+
+fn data_type_of(value: &Value) -> ProviderResult<DataType> {
+    let nested = |key: &str| -> ProviderResult<Box<DataType>> {
+        let inner = value
+            .get(key)
+            .ok_or_else(|| ProviderError::ParseError(format!("Delta type has no `{}`: {}", key, value)))?;
+        Ok(Box::new(data_type_of(inner)?))
+    };
+    let nullable = |key: &str| value.get(key).and_then(Value::as_bool).unwrap_or(true);
+
+    match value {
+        Value::String(name) => Ok(DataType::Primitive(name.clone())),
+        _ => match value.get("type").and_then(Value::as_str) {
+            Some("struct") => Ok(DataType::Struct(struct_fields(value)?)),
+            Some("array") => Ok(DataType::List {
+                element: nested("elementType")?,
+                element_required: !nullable("containsNull"),
+            }),
+            Some("map") => Ok(DataType::Map {
+                key: nested("keyType")?,
+                value: nested("valueType")?,
+                value_required: !nullable("valueContainsNull"),
+            }),
+            _ => Err(ProviderError::ParseError(format!("Unknown Delta type: {}", value))),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latest_metadata_from_log() {
+        let schema = |fields: &str| {
+            serde_json::to_string(&format!(r#"{{"type":"struct","fields":[{}]}}"#, fields)).unwrap()
+        };
+        let commit0 = format!(
+            "{}\n{{\"metaData\":{{\"id\":\"a\",\"name\":\"events\",\"schemaString\":{},\"partitionColumns\":[\"date\"]}}}}\n",
+            r#"{"protocol":{"minReaderVersion":1,"minWriterVersion":2}}"#,
+            schema(r#"{"name":"id","type":"long","nullable":false,"metadata":{}},{"name":"date","type":"date","nullable":true,"metadata":{}}"#)
+        );
+        let commit1 = format!(
+            r#"{{"metaData":{{"id":"a","name":"events","schemaString":{},"partitionColumns":["date"]}}}}"#,
+            schema(concat!(
+                r#"{"name":"id","type":"long","nullable":false,"metadata":{}},"#,
+                r#"{"name":"date","type":"date","nullable":true,"metadata":{}},"#,
+                r#"{"name":"props","type":{"type":"map","keyType":"string","valueType":"string","valueContainsNull":true},"nullable":true,"metadata":{}}"#
+            ))
+        );
+
+        let metadata = latest_metadata([commit0.as_str(), r#"{"add":{"path":"part-0.parquet"}}"#, &commit1])
+            .unwrap()
+            .unwrap();
+        let table = parse_metadata(&metadata).unwrap();
+        assert_eq!(table.name.as_deref(), Some("events"));
+        assert_eq!(table.fields.len(), 3);
+        assert!(table.fields[0].required);
+        assert!(matches!(&table.fields[2].data_type, DataType::Map { value_required: false, .. }));
+        assert_eq!(table.source_field(&table.partition[0]).unwrap().name, "date");
+    }
+}
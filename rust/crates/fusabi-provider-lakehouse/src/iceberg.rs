@@ -0,0 +1,178 @@
+//! Iceberg table metadata
+//!
+//! Reads the current schema and default partition spec from a
+//! `*.metadata.json` file. Format version 1 metadata holds a single
+//! `schema` and `partition-spec`; version 2 and later hold `schemas` and
+//! `partition-specs`, selected by `current-schema-id` and
+//! `default-spec-id`.
+
+use crate::types::{DataType, Field, PartitionField, PartitionSource, TableFormat, TableMetadata};
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde_json::Value;
+
+/// Whether a JSON document is Iceberg table metadata
+pub fn is_metadata(value: &Value) -> bool {
+    value.get("format-version").is_some()
+}
+
+/// Read the current schema and partition spec
+pub fn parse_metadata(metadata: &Value) -> ProviderResult<TableMetadata> {
+    let schema = match metadata.get("schemas").and_then(Value::as_array) {
+        Some(schemas) => {
+            let current = metadata.get("current-schema-id").and_then(Value::as_i64);
+            schemas
+                .iter()
+                .find(|s| s.get("schema-id").and_then(Value::as_i64) == current)
+                .or_else(|| schemas.last())
+        }
+        None => metadata.get("schema"),
+    }
+    .ok_or_else(|| ProviderError::ParseError("Iceberg metadata has no schema".to_string()))?;
+
+    let spec_fields = match metadata.get("partition-specs").and_then(Value::as_array) {
+        Some(specs) => {
+            let default = metadata.get("default-spec-id").and_then(Value::as_i64);
+            specs
+                .iter()
+                .find(|s| s.get("spec-id").and_then(Value::as_i64) == default)
+                .or_else(|| specs.last())
+                .and_then(|s| s.get("fields"))
+        }
+        None => metadata.get("partition-spec"),
+    };
+    let partition = spec_fields
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(partition_field)
+        .collect::<ProviderResult<Vec<_>>>()?;
+
+    Ok(TableMetadata {
+        format: TableFormat::Iceberg,
+        name: None,
+        fields: struct_fields(schema)?,
+        partition,
+    })
+}
+
+fn struct_fields(schema: &Value) -> ProviderResult<Vec<Field>> {
+    let fields = schema
+        .get("fields")
+        .and_then(Value::as_array)
+        .ok_or_else(|| ProviderError::ParseError("Iceberg struct has no fields".to_string()))?;
+    fields.iter().map(field).collect()
+}
+
+fn field(value: &Value) -> ProviderResult<Field> {
+    let name = value
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or_else(|| ProviderError::ParseError(format!("Iceberg field has no name: {}", value)))?;
+    let data_type = value
+        .get("type")
+        .ok_or_else(|| ProviderError::ParseError(format!("Iceberg field `{}` has no type", name)))?;
+
+    Ok(Field {
+        id: value.get("id").and_then(Value::as_i64),
+        name: name.to_string(),
+        data_type: data_type_of(data_type)?,
+        required: value.get("required").and_then(Value::as_bool).unwrap_or(false),
+    })
+}
+
+fn data_type_of(value: &Value) -> ProviderResult<DataType> {
+    let nested = |key: &str| -> ProviderResult<Box<DataType>> {
+        let inner = value
+            .get(key)
+            .ok_or_else(|| ProviderError::ParseError(format!("Iceberg type has no `{}`: {}", key, value)))?;
+        Ok(Box::new(data_type_of(inner)?))
+    };
+    let flag = |key: &str| value.get(key).and_then(Value::as_bool).unwrap_or(false);
+
+    match value {
+        Value::String(name) => Ok(DataType::Primitive(name.clone())),
+        _ => match value.get("type").and_then(Value::as_str) {
+            Some("struct") => Ok(DataType::Struct(struct_fields(value)?)),
+            Some("list") => Ok(DataType::List {
+                element: nested("element")?,
+                element_required: flag("element-required"),
+            }),
+            Some("map") => Ok(DataType::Map {
+                key: nested("key")?,
+                value: nested("value")?,
+                value_required: flag("value-required"),
+            }),
+            _ => Err(ProviderError::ParseError(format!("Unknown Iceberg type: {}", value))),
+        },
+    }
+}
+
+fn partition_field(value: &Value) -> ProviderResult<PartitionField> {
+    let get = |key: &str| value.get(key);
+    match (
+        get("name").and_then(Value::as_str),
+        get("source-id").and_then(Value::as_i64),
+        get("transform").and_then(Value::as_str),
+    ) {
+        (Some(name), Some(source), Some(transform)) => Ok(PartitionField {
+            name: name.to_string(),
+            source: PartitionSource::Id(source),
+            transform: transform.to_string(),
+        }),
+        _ => Err(ProviderError::ParseError(format!("Invalid Iceberg partition field: {}", value))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_current_schema_and_spec() {
+        let metadata = json!({
+            "format-version": 2,
+            "current-schema-id": 1,
+            "schemas": [
+                {"type": "struct", "schema-id": 0, "fields": [{"id": 1, "name": "id", "required": true, "type": "int"}]},
+                {"type": "struct", "schema-id": 1, "fields": [
+                    {"id": 1, "name": "id", "required": true, "type": "long"},
+                    {"id": 2, "name": "ts", "required": false, "type": "timestamptz"},
+                    {"id": 3, "name": "tags", "required": false,
+                     "type": {"type": "list", "element-id": 4, "element": "string", "element-required": true}}
+                ]}
+            ],
+            "default-spec-id": 1,
+            "partition-specs": [
+                {"spec-id": 0, "fields": []},
+                {"spec-id": 1, "fields": [{"name": "ts_day", "transform": "day", "source-id": 2, "field-id": 1000}]}
+            ]
+        });
+
+        let table = parse_metadata(&metadata).unwrap();
+        assert_eq!(table.fields.len(), 3);
+        assert_eq!(table.fields[0].data_type, DataType::Primitive("long".to_string()));
+        assert_eq!(
+            table.fields[2].data_type,
+            DataType::List {
+                element: Box::new(DataType::Primitive("string".to_string())),
+                element_required: true,
+            }
+        );
+        assert_eq!(table.partition[0].transform, "day");
+        assert_eq!(table.source_field(&table.partition[0]).unwrap().name, "ts");
+    }
+
+    #[test]
+    fn test_format_version_1() {
+        let metadata = json!({
+            "format-version": 1,
+            "schema": {"type": "struct", "fields": [{"id": 1, "name": "region", "required": true, "type": "string"}]},
+            "partition-spec": [{"name": "region", "transform": "identity", "source-id": 1, "field-id": 1000}]
+        });
+
+        let table = parse_metadata(&metadata).unwrap();
+        assert_eq!(table.partition.len(), 1);
+        assert!(parse_metadata(&json!({"format-version": 2})).is_err());
+    }
+}
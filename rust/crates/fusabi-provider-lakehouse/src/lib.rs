@@ -0,0 +1,551 @@
+//! Lakehouse Table Type Provider
+//!
+//! Generates Fusabi types from Apache Iceberg and Delta Lake table
+//! metadata, so jobs reading or writing lakehouse tables are checked
+//! against the table's current schema.
+//!
+//! # Sources
+//!
+//! - A table directory: Iceberg tables are read from
+//!   `metadata/version-hint.text` or else the latest `*.metadata.json`;
+//!   Delta tables from the commits in `_delta_log`. A `metadata` or
+//!   `_delta_log` directory may be given directly
+//! - An Iceberg `*.metadata.json` file, inline or by path
+//! - A Delta commit (`_delta_log/00000000000000000000.json`), a single
+//!   `metaData` action, or a bare `schemaString` value
+//!
+//! Delta logs whose latest schema is only in a Parquet checkpoint are not
+//! supported; point the provider at a commit that changed the schema.
+//!
+//! # Generated Types
+//!
+//! - `Row`: the table's columns. Required (Iceberg) or non-nullable
+//!   (Delta) columns are plain; others are `T option`
+//! - `Partition`: the partition tuple, if the table is partitioned. Iceberg
+//!   `bucket` and time transforms (`year`, `month`, `day`, `hour`) produce
+//!   `int`; `identity` and `truncate` keep the source column's type
+//! - `<Parent><Field>` for each struct column, and `<Parent><Field>Key` /
+//!   `<Parent><Field>Value` for structs inside maps
+//!
+//! | Iceberg / Delta                              | Fusabi                |
+//! |----------------------------------------------|-----------------------|
+//! | `boolean`                                    | `bool`                |
+//! | `int` / `integer`, `short`, `byte`           | `int`                 |
+//! | `long`                                       | `int64`               |
+//! | `float`, `double`, `decimal(P,S)`            | `float`               |
+//! | `string`, `uuid`, `date`, `time`, `timestamp*` | `string`            |
+//! | `binary`, `fixed[L]`                         | `bytes`               |
+//! | `list` / `array`                             | `T list`              |
+//! | `map`                                        | `Map<K, V>`           |
+//! | `struct`                                     | record                |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_lakehouse::LakehouseProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = LakehouseProvider::new();
+//! let schema = provider.resolve_schema("warehouse/sales/orders", &ProviderParams::default())?;
+//! let types = provider.generate_types(&schema, "Orders")?;
+//! ```
+
+mod delta;
+mod iceberg;
+mod types;
+
+pub use types::{DataType, Field, PartitionField, PartitionSource, TableFormat, TableMetadata};
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, SourceKind,
+    SourceValidator, TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, TypeDefinition, TypeExpr,
+    ProviderError, ProviderResult,
+};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// Name of the record for a table's rows
+const ROW_TYPE: &str = "Row";
+
+/// Name of the record for a table's partition tuple
+const PARTITION_TYPE: &str = "Partition";
+
+/// Iceberg and Delta Lake type provider
+pub struct LakehouseProvider {
+    generator: TypeGenerator,
+}
+
+impl LakehouseProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<TableMetadata> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected lakehouse table schema: {}", e)))
+    }
+
+    /// Read Iceberg metadata, or any of the Delta forms
+    fn parse_content(&self, content: &str) -> ProviderResult<TableMetadata> {
+        let Ok(value) = serde_json::from_str::<Value>(content) else {
+            // Several actions, one per line: a Delta commit
+            return match delta::latest_metadata([content])? {
+                Some(metadata) => delta::parse_metadata(&metadata),
+                None => Err(ProviderError::ParseError("Delta commit has no metaData action".to_string())),
+            };
+        };
+
+        if iceberg::is_metadata(&value) {
+            iceberg::parse_metadata(&value)
+        } else if let Some(metadata) = value.get("metaData") {
+            delta::parse_metadata(metadata)
+        } else if value.get("schemaString").is_some() {
+            delta::parse_metadata(&value)
+        } else if value.get("type").and_then(Value::as_str) == Some("struct") {
+            delta::parse_schema(&value)
+        } else {
+            Err(ProviderError::ParseError(
+                "Expected Iceberg table metadata or a Delta metaData action or schema".to_string(),
+            ))
+        }
+    }
+
+    /// Read the current metadata of a table directory
+    fn read_table_dir(&self, dir: &Path, params: &ProviderParams) -> ProviderResult<TableMetadata> {
+        let named = |name: &str| dir.file_name().is_some_and(|n| n == name);
+        let log = if named("_delta_log") { dir.to_path_buf() } else { dir.join("_delta_log") };
+        let iceberg_metadata = if named("metadata") { dir.to_path_buf() } else { dir.join("metadata") };
+
+        if log.is_dir() {
+            // Commits are zero-padded versions, so name order is commit order
+            let commits = list_files(&log, |name| name.ends_with(".json"))?;
+            let contents = commits
+                .iter()
+                .map(|path| read_source(&path.to_string_lossy(), params))
+                .collect::<ProviderResult<Vec<_>>>()?;
+            match delta::latest_metadata(contents.iter().map(String::as_str))? {
+                Some(metadata) => delta::parse_metadata(&metadata),
+                None => Err(ProviderError::InvalidSource(format!(
+                    "No metaData action in the commits of {}; checkpoint-only logs are not supported",
+                    log.display()
+                ))),
+            }
+        } else if iceberg_metadata.is_dir() {
+            let path = current_iceberg_metadata(&iceberg_metadata, params)?;
+            let content = read_source(&path.to_string_lossy(), params)?;
+            let value: Value = serde_json::from_str(&content)
+                .map_err(|e| ProviderError::ParseError(format!("Invalid Iceberg metadata {}: {}", path.display(), e)))?;
+            iceberg::parse_metadata(&value)
+        } else {
+            Err(ProviderError::InvalidSource(format!(
+                "{} has neither a _delta_log nor a metadata directory",
+                dir.display()
+            )))
+        }
+    }
+
+    /// Generate the row and partition records, nested records first
+    fn table_types(&self, table: &TableMetadata) -> (Vec<TypeDefinition>, Vec<Diagnostic>) {
+        let mut generation = Generation {
+            generator: &self.generator,
+            types: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+
+        let fields = generation.fields(ROW_TYPE, &table.fields);
+        generation.types.push(TypeDefinition::Record(RecordDef {
+            name: ROW_TYPE.to_string(),
+            fields,
+        }));
+
+        if !table.partition.is_empty() {
+            let fields = table
+                .partition
+                .iter()
+                .map(|partition| (partition.name.clone(), generation.partition_shape(table, partition).into()))
+                .collect();
+            generation.types.push(TypeDefinition::Record(RecordDef {
+                name: PARTITION_TYPE.to_string(),
+                fields,
+            }));
+        }
+
+        (generation.types, generation.diagnostics)
+    }
+}
+
+impl Default for LakehouseProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypeProvider for LakehouseProvider {
+    fn name(&self) -> &str {
+        "LakehouseProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let trimmed = source.trim();
+        let table = if trimmed.starts_with('{') {
+            self.parse_content(source)?
+        } else if Path::new(trimmed).is_dir() {
+            self.read_table_dir(Path::new(trimmed), params)?
+        } else {
+            self.parse_content(&read_source(source, params)?)?
+        };
+
+        let json = serde_json::to_string(&table)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected lakehouse table schema".to_string()));
+        };
+
+        let table = self.parse_schema(json)?;
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+        module.types = self.table_types(&table).0;
+
+        let mut result = GeneratedTypes::new();
+        result.modules.push(module);
+        Ok(result)
+    }
+}
+
+impl SourceValidator for LakehouseProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected lakehouse table schema".to_string()));
+        };
+
+        let table = self.parse_schema(json)?;
+        let mut diagnostics = Vec::new();
+        if table.fields.is_empty() {
+            diagnostics.push(Diagnostic::warning("no-fields", "Table schema has no columns"));
+        }
+        diagnostics.extend(self.table_types(&table).1);
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for LakehouseProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .source(SourceKind::Directory)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+/// Records generated for one table, with what was lost on the way
+struct Generation<'a> {
+    generator: &'a TypeGenerator,
+    types: Vec<TypeDefinition>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Generation<'_> {
+    /// Record fields for `fields`, generating nested records on the way
+    fn fields(&mut self, record: &str, fields: &[Field]) -> Vec<(String, TypeExpr)> {
+        fields
+            .iter()
+            .map(|field| {
+                let name = format!("{}{}", record, self.generator.naming.apply(&field.name));
+                let location = format!("{}.{}", record, field.name);
+                let shape = self.shape(&field.data_type, &name, &location);
+                let shape = if field.required { shape } else { TypeShape::Option(Box::new(shape)) };
+                (field.name.clone(), shape.into())
+            })
+            .collect()
+    }
+
+    /// Type of a value; `name` names the record if the value is a struct
+    fn shape(&mut self, data_type: &DataType, name: &str, location: &str) -> TypeShape {
+        match data_type {
+            DataType::Primitive(primitive) => self.primitive_shape(primitive, location),
+            DataType::Struct(fields) => {
+                let fields = self.fields(name, fields);
+                self.types.push(TypeDefinition::Record(RecordDef {
+                    name: name.to_string(),
+                    fields,
+                }));
+                TypeShape::Named(name.to_string())
+            }
+            DataType::List { element, element_required } => {
+                let element = self.shape(element, name, &format!("{}[]", location));
+                TypeShape::List(Box::new(optional(element, *element_required)))
+            }
+            DataType::Map { key, value, value_required } => {
+                let key = self.shape(key, &format!("{}Key", name), location);
+                let value = self.shape(value, &format!("{}Value", name), location);
+                TypeShape::Map(Box::new(key), Box::new(optional(value, *value_required)))
+            }
+        }
+    }
+
+    fn primitive_shape(&mut self, primitive: &str, location: &str) -> TypeShape {
+        let named = |name: &str| TypeShape::Named(name.to_string());
+        let lower = primitive.trim().to_ascii_lowercase();
+        // `decimal(10,2)`, `fixed[16]`
+        let base = lower.split(['(', '[']).next().unwrap_or_default();
+
+        match base {
+            "boolean" => named("bool"),
+            "int" | "integer" | "short" | "byte" => named("int"),
+            "long" => named("int64"),
+            "float" | "double" => named("float"),
+            "string" | "uuid" | "date" | "time" | "timestamp" | "timestamptz" | "timestamp_ns"
+            | "timestamptz_ns" | "timestamp_ntz" => named("string"),
+            "binary" | "fixed" => named("bytes"),
+            "decimal" => {
+                self.diagnostics.push(
+                    Diagnostic::info("lossy-conversion", format!("Fixed-point `{}` is typed as `float`", lower))
+                        .at(location.to_string()),
+                );
+                named("float")
+            }
+            _ => {
+                self.diagnostics.push(
+                    Diagnostic::warning("unmapped-type", format!("Type `{}` has no Fusabi mapping", primitive))
+                        .at(location.to_string()),
+                );
+                named("any")
+            }
+        }
+    }
+
+    /// Type of a partition field, from its source column and transform
+    fn partition_shape(&mut self, table: &TableMetadata, partition: &PartitionField) -> TypeShape {
+        let location = format!("{}.{}", PARTITION_TYPE, partition.name);
+        let Some(source) = table.source_field(partition) else {
+            let source = match &partition.source {
+                PartitionSource::Id(id) => format!("field ID {}", id),
+                PartitionSource::Column(name) => format!("column `{}`", name),
+            };
+            self.diagnostics.push(
+                Diagnostic::warning(
+                    "partition-unresolved",
+                    format!("Partition source {} is not in the schema", source),
+                )
+                .at(location.clone()),
+            );
+            return TypeShape::Named("any".to_string());
+        };
+
+        let transform = partition.transform.to_ascii_lowercase();
+        let shape = match transform.split('[').next().unwrap_or_default() {
+            "identity" | "truncate" | "void" => {
+                let name = format!("{}{}", PARTITION_TYPE, self.generator.naming.apply(&partition.name));
+                self.shape(&source.data_type, &name, &location)
+            }
+            "bucket" | "year" | "month" | "day" | "hour" => TypeShape::Named("int".to_string()),
+            _ => {
+                self.diagnostics.push(
+                    Diagnostic::warning(
+                        "unmapped-type",
+                        format!("Partition transform `{}` has no Fusabi mapping", partition.transform),
+                    )
+                    .at(location),
+                );
+                TypeShape::Named("any".to_string())
+            }
+        };
+        // A `void` transform always produces null
+        optional(shape, source.required && transform != "void")
+    }
+}
+
+fn optional(shape: TypeShape, required: bool) -> TypeShape {
+    if required {
+        shape
+    } else {
+        TypeShape::Option(Box::new(shape))
+    }
+}
+
+/// Files in `dir` whose names match, sorted by name
+fn list_files(dir: &Path, matches: impl Fn(&str) -> bool) -> ProviderResult<Vec<PathBuf>> {
+    let entries = std::fs::read_dir(dir).map_err(|e| ProviderError::IoError(format!("{}: {}", dir.display(), e)))?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.file_name().and_then(|n| n.to_str()).is_some_and(&matches))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// The metadata file named by `version-hint.text`, or else the one with
+/// the highest version (`v3.metadata.json`, `00003-<uuid>.metadata.json`)
+fn current_iceberg_metadata(dir: &Path, params: &ProviderParams) -> ProviderResult<PathBuf> {
+    let hint_path = dir.join("version-hint.text");
+    if hint_path.is_file() {
+        let hint = read_source(&hint_path.to_string_lossy(), params)?;
+        let path = dir.join(format!("v{}.metadata.json", hint.trim()));
+        if path.is_file() {
+            return Ok(path);
+        }
+    }
+
+    let version = |path: &PathBuf| -> u64 {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        let digits: String = name.trim_start_matches('v').chars().take_while(char::is_ascii_digit).collect();
+        digits.parse().unwrap_or(0)
+    };
+    list_files(dir, |name| name.ends_with(".metadata.json"))?
+        .into_iter()
+        .max_by_key(version)
+        .ok_or_else(|| ProviderError::InvalidSource(format!("No *.metadata.json files in {}", dir.display())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    const ICEBERG: &str = r#"{
+        "format-version": 2,
+        "table-uuid": "9c12d441-03fe-4693-9a96-a0705ddf69c1",
+        "location": "s3://warehouse/sales/orders",
+        "current-schema-id": 0,
+        "schemas": [{"type": "struct", "schema-id": 0, "fields": [
+            {"id": 1, "name": "order_id", "required": true, "type": "long"},
+            {"id": 2, "name": "placed_at", "required": true, "type": "timestamptz"},
+            {"id": 3, "name": "total", "required": false, "type": "decimal(12, 2)"},
+            {"id": 4, "name": "customer", "required": false, "type": {"type": "struct", "fields": [
+                {"id": 7, "name": "id", "required": true, "type": "uuid"},
+                {"id": 8, "name": "country", "required": false, "type": "string"}
+            ]}},
+            {"id": 5, "name": "lines", "required": true, "type": {
+                "type": "list", "element-id": 9, "element-required": true,
+                "element": {"type": "struct", "fields": [
+                    {"id": 10, "name": "sku", "required": true, "type": "string"},
+                    {"id": 11, "name": "qty", "required": true, "type": "int"}
+                ]}
+            }},
+            {"id": 6, "name": "attrs", "required": false, "type": {
+                "type": "map", "key-id": 12, "key": "string", "value-id": 13, "value": "binary", "value-required": false
+            }}
+        ]}],
+        "default-spec-id": 0,
+        "partition-specs": [{"spec-id": 0, "fields": [
+            {"name": "placed_day", "transform": "day", "source-id": 2, "field-id": 1000},
+            {"name": "country", "transform": "identity", "source-id": 8, "field-id": 1001},
+            {"name": "order_bucket", "transform": "bucket[16]", "source-id": 1, "field-id": 1002}
+        ]}]
+    }"#;
+
+    fn record<'a>(types: &'a GeneratedTypes, name: &str) -> &'a RecordDef {
+        types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+    }
+
+    fn fields(record: &RecordDef) -> Vec<(String, String)> {
+        record.fields.iter().map(|(name, ty)| (name.clone(), ty.to_string())).collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect()
+    }
+
+    #[test]
+    fn test_iceberg_row_and_partition() {
+        let provider = LakehouseProvider::new();
+        let schema = provider.resolve_schema(ICEBERG, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Orders").unwrap();
+
+        assert_eq!(
+            fields(record(&types, "Row")),
+            pairs(&[
+                ("order_id", "int64"),
+                ("placed_at", "string"),
+                ("total", "float option"),
+                ("customer", "RowCustomer option"),
+                ("lines", "RowLines list"),
+                ("attrs", "Map<string, bytes option> option"),
+            ])
+        );
+        assert_eq!(fields(record(&types, "RowLines")), pairs(&[("sku", "string"), ("qty", "int")]));
+        assert_eq!(
+            fields(record(&types, "Partition")),
+            pairs(&[
+                ("placed_day", "int"),
+                ("country", "string option"),
+                ("order_bucket", "int"),
+            ])
+        );
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/lakehouse.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_delta_table_directory() {
+        let dir = std::env::temp_dir().join(format!("fusabi-lakehouse-{}", std::process::id()));
+        let log = dir.join("_delta_log");
+        std::fs::create_dir_all(&log).unwrap();
+
+        let commit = |fields: &str| {
+            let schema = format!(r#"{{"type":"struct","fields":[{}]}}"#, fields);
+            format!(
+                "{}\n{}\n",
+                r#"{"commitInfo":{"operation":"WRITE"}}"#,
+                serde_json::json!({"metaData": {"id": "t", "schemaString": schema, "partitionColumns": ["day"]}})
+            )
+        };
+        let id = r#"{"name":"id","type":"long","nullable":false,"metadata":{}}"#;
+        let day = r#"{"name":"day","type":"date","nullable":false,"metadata":{}}"#;
+        let tags = r#"{"name":"tags","type":{"type":"array","elementType":"string","containsNull":false},"nullable":true,"metadata":{}}"#;
+        std::fs::write(log.join("00000000000000000000.json"), commit(&[id, day].join(","))).unwrap();
+        std::fs::write(log.join("00000000000000000001.json"), r#"{"add":{"path":"day=2024-01-01/part-0.parquet"}}"#).unwrap();
+        std::fs::write(log.join("00000000000000000002.json"), commit(&[id, day, tags].join(","))).unwrap();
+
+        let provider = LakehouseProvider::new();
+        let schema = provider.resolve_schema(dir.to_str().unwrap(), &ProviderParams::default());
+        std::fs::remove_dir_all(&dir).unwrap();
+        let types = provider.generate_types(&schema.unwrap(), "Events").unwrap();
+
+        assert_eq!(
+            fields(record(&types, "Row")),
+            pairs(&[("id", "int64"), ("day", "string"), ("tags", "string list option")])
+        );
+        assert_eq!(fields(record(&types, "Partition")), pairs(&[("day", "string")]));
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = LakehouseProvider::new();
+        let report = provider.validate_source(ICEBERG, &ProviderParams::default());
+        assert!(report.is_valid());
+        let codes: Vec<(&str, Option<&str>)> =
+            report.diagnostics.iter().map(|d| (d.code.as_str(), d.location.as_deref())).collect();
+        assert!(codes.contains(&("lossy-conversion", Some("Row.total"))));
+
+        let schema = r#"{"type":"struct","fields":[{"name":"v","type":"variant","nullable":true,"metadata":{}}]}"#;
+        let report = provider.validate_source(&format!(r#"{{"schemaString": {:?}, "partitionColumns": ["gone"]}}"#, schema), &ProviderParams::default());
+        let codes: Vec<(&str, Option<&str>)> =
+            report.diagnostics.iter().map(|d| (d.code.as_str(), d.location.as_deref())).collect();
+        assert!(codes.contains(&("unmapped-type", Some("Row.v"))));
+        assert!(codes.contains(&("partition-unresolved", Some("Partition.gone"))));
+
+        assert!(!provider.validate_source(r#"{"fields": []}"#, &ProviderParams::default()).is_valid());
+    }
+}
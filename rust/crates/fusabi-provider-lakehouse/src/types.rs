@@ -0,0 +1,99 @@
+//! Table metadata types
+//!
+//! Iceberg and Delta Lake schemas are both trees of named, possibly
+//! nullable fields over struct, list and map types; they are read into
+//! this common form. Primitive type names are kept as written by the
+//! format (`long` in Iceberg, `long` or `integer` in Delta).
+
+use serde::{Deserialize, Serialize};
+
+/// The table format the metadata came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TableFormat {
+    Iceberg,
+    Delta,
+}
+
+/// The current schema and partitioning of a table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableMetadata {
+    pub format: TableFormat,
+    /// Table name, when the metadata records one (Delta only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub fields: Vec<Field>,
+    #[serde(default)]
+    pub partition: Vec<PartitionField>,
+}
+
+/// A column, or a field of a struct
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Field {
+    /// Iceberg field ID; `None` for Delta
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    pub name: String,
+    pub data_type: DataType,
+    pub required: bool,
+}
+
+/// A field type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DataType {
+    /// A primitive type name such as `long`, `decimal(10,2)` or `fixed[16]`
+    Primitive(String),
+    Struct(Vec<Field>),
+    List {
+        element: Box<DataType>,
+        element_required: bool,
+    },
+    Map {
+        key: Box<DataType>,
+        value: Box<DataType>,
+        value_required: bool,
+    },
+}
+
+/// A field of the partition tuple
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartitionField {
+    pub name: String,
+    /// Source column: an Iceberg field ID, or a Delta column name
+    pub source: PartitionSource,
+    /// Iceberg transform such as `identity`, `bucket[16]` or `day`;
+    /// `identity` for Delta
+    pub transform: String,
+}
+
+/// The column a partition field derives from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PartitionSource {
+    Id(i64),
+    Column(String),
+}
+
+impl TableMetadata {
+    /// The field a partition field derives from, searching nested structs
+    /// for Iceberg source IDs
+    pub fn source_field(&self, partition: &PartitionField) -> Option<&Field> {
+        match &partition.source {
+            PartitionSource::Id(id) => find_by_id(&self.fields, *id),
+            PartitionSource::Column(name) => self.fields.iter().find(|f| &f.name == name),
+        }
+    }
+}
+
+fn find_by_id(fields: &[Field], id: i64) -> Option<&Field> {
+    fields.iter().find_map(|field| {
+        if field.id == Some(id) {
+            return Some(field);
+        }
+        match &field.data_type {
+            DataType::Struct(nested) => find_by_id(nested, id),
+            _ => None,
+        }
+    })
+}
@@ -0,0 +1,431 @@
+//! LLM Tool Type Provider
+//!
+//! Generates Fusabi types from the tool definitions sent to OpenAI
+//! (function calling) and Anthropic (tool use), so the arguments a model
+//! returns are decoded into typed records instead of untyped JSON. Input
+//! schemas go through the same converter as the MCP provider; this
+//! provider reads the vendor-native wrappers around them.
+//!
+//! # Sources
+//!
+//! Tool definitions as JSON, inline or from a file, in either vendor's
+//! format; see [`tools`].
+//!
+//! # Generated Types
+//!
+//! - `<Tool>Input`: a record per tool from its input schema
+//!   (`get_weather` → `GetWeatherInput`)
+//! - A record per nested object, named after its path
+//!   (`GetWeatherInputLocation`; list items get `Item`), and per
+//!   `$defs` entry
+//! - `ToolCall`: a union with a variant per tool carrying its input
+//!
+//! # Validation
+//!
+//! Tool names must match `^[a-zA-Z0-9_-]{1,64}$` and be unique, and
+//! inputs must be object schemas. Tools with `strict: true` are checked
+//! against OpenAI's strict mode rules: every property required and
+//! `additionalProperties: false` on every object.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_llm_tools::LlmToolsProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = LlmToolsProvider::new();
+//! let schema = provider.resolve_schema("agent/tools.json", &ProviderParams::default())?;
+//! let types = provider.generate_types(&schema, "Tools")?;
+//! ```
+
+pub mod tools;
+
+pub use tools::{parse_tools, ToolDef, ToolFormat, ToolSet};
+
+use std::collections::HashSet;
+
+use fusabi_jsonschema_core::{definitions_of, Converter};
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, SourceValidator,
+    TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, DuDef, VariantDef, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use serde_json::{json, Value};
+
+/// LLM tool type provider
+pub struct LlmToolsProvider {
+    generator: TypeGenerator,
+    converter: Converter,
+}
+
+impl LlmToolsProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+            converter: Converter::new(),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<ToolSet> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected LLM tool definitions: {}", e)))
+    }
+
+    fn generate(&self, set: &ToolSet) -> (Vec<TypeDefinition>, Vec<Diagnostic>) {
+        let mut diagnostics: Vec<Diagnostic> = set
+            .builtin
+            .iter()
+            .map(|tool_type| {
+                Diagnostic::info("builtin-tool", format!("Built-in tool `{}` has no input schema; skipped", tool_type))
+            })
+            .collect();
+
+        let mut definitions: Vec<(String, Value)> = Vec::new();
+        let mut inputs = Vec::new();
+        let mut variants = Vec::new();
+        let mut names = HashSet::new();
+        for tool in &set.tools {
+            if !tools::is_valid_name(&tool.name) {
+                diagnostics.push(
+                    Diagnostic::error("invalid-name", "Tool names must match `^[a-zA-Z0-9_-]{1,64}$`")
+                        .at(tool.name.clone()),
+                );
+            }
+            if !names.insert(tool.name.as_str()) {
+                diagnostics.push(
+                    Diagnostic::error("duplicate-tool", "Another tool has the same name; skipped").at(tool.name.clone()),
+                );
+                continue;
+            }
+            let schema_type = tool.schema.get("type").and_then(Value::as_str);
+            if schema_type.is_some_and(|t| t != "object") {
+                diagnostics.push(
+                    Diagnostic::error("non-object-input", "Tool input schemas must have `type: object`")
+                        .at(tool.name.clone()),
+                );
+            }
+            if tool.strict {
+                check_strict(&tool.schema, &tool.name, &mut diagnostics);
+            }
+
+            let input = format!("{}Input", self.generator.naming.apply(&tool.name));
+            let mut schema = tool.schema.clone();
+            let mut nested = Vec::new();
+            hoist(&self.generator, &mut schema, &input, &mut nested);
+            if let Some(defs) = definitions_of(&tool.schema) {
+                nested.extend(defs.iter().map(|(name, schema)| (self.converter.ref_name(name), schema.clone())));
+            }
+            for (name, definition) in nested {
+                match definitions.iter().find(|(existing, _)| *existing == name) {
+                    Some((_, existing)) if *existing != definition => diagnostics.push(
+                        Diagnostic::warning(
+                            "conflicting-definition",
+                            format!("`{}` is defined differently by another tool; the first is kept", name),
+                        )
+                        .at(tool.name.clone()),
+                    ),
+                    Some(_) => {}
+                    None => definitions.push((name, definition)),
+                }
+            }
+
+            inputs.push(TypeDefinition::Record(RecordDef {
+                name: input.clone(),
+                fields: self.converter.record_fields(&schema),
+            }));
+            variants.push(VariantDef::new(self.generator.naming.apply(&tool.name), vec![TypeShape::named(input).into()]));
+        }
+
+        let mut types: Vec<TypeDefinition> = definitions
+            .iter()
+            .filter_map(|(name, schema)| self.converter.definition(name, schema))
+            .collect();
+        types.extend(inputs);
+        if !variants.is_empty() {
+            types.push(TypeDefinition::Du(DuDef { name: "ToolCall".to_string(), variants }));
+        }
+        (types, diagnostics)
+    }
+}
+
+/// Whether a schema is an object with properties, which becomes a record
+fn is_record(schema: &Value) -> bool {
+    let object_type = match schema.get("type") {
+        Some(Value::String(t)) => t == "object",
+        Some(Value::Array(types)) => types.iter().any(|t| t == "object"),
+        _ => true,
+    };
+    object_type && schema.get("properties").is_some_and(Value::is_object)
+}
+
+/// Move nested object schemas out into named definitions, so they become
+/// records instead of maps, and point their properties at them
+fn hoist(generator: &TypeGenerator, schema: &mut Value, base: &str, definitions: &mut Vec<(String, Value)>) {
+    let Some(properties) = schema.get_mut("properties").and_then(Value::as_object_mut) else {
+        return;
+    };
+    for (property, value) in properties.iter_mut() {
+        let name = format!("{}{}", base, generator.naming.apply(property));
+        if is_record(value) {
+            *value = extract(generator, value, name, definitions);
+        } else if let Some(items) = value.get_mut("items").filter(|items| is_record(items)) {
+            *items = extract(generator, items, format!("{}Item", name), definitions);
+        }
+    }
+}
+
+fn extract(generator: &TypeGenerator, schema: &Value, name: String, definitions: &mut Vec<(String, Value)>) -> Value {
+    let mut definition = schema.clone();
+    hoist(generator, &mut definition, &name, definitions);
+    let nullable = matches!(schema.get("type"), Some(Value::Array(types)) if types.iter().any(|t| t == "null"));
+    if nullable {
+        definition["type"] = json!("object");
+    }
+    let reference = json!({"$ref": format!("#/$defs/{}", name)});
+    definitions.push((name, definition));
+    if nullable {
+        json!({"anyOf": [reference, {"type": "null"}]})
+    } else {
+        reference
+    }
+}
+
+/// OpenAI strict mode: every object lists all its properties as required
+/// and sets `additionalProperties: false`
+fn check_strict(schema: &Value, path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|r| r.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        let missing: Vec<&str> = properties.keys().map(String::as_str).filter(|p| !required.contains(p)).collect();
+        if !missing.is_empty() {
+            diagnostics.push(
+                Diagnostic::warning(
+                    "strict-violation",
+                    format!(
+                        "Strict mode requires every property; not required: {} (use a `null` type for optional ones)",
+                        missing.join(", ")
+                    ),
+                )
+                .at(path.to_string()),
+            );
+        }
+        if schema.get("additionalProperties") != Some(&Value::Bool(false)) {
+            diagnostics.push(
+                Diagnostic::warning("strict-violation", "Strict mode requires `additionalProperties: false`")
+                    .at(path.to_string()),
+            );
+        }
+        for (property, value) in properties {
+            check_strict(value, &format!("{}.{}", path, property), diagnostics);
+        }
+    }
+    if let Some(items) = schema.get("items") {
+        check_strict(items, &format!("{}[]", path), diagnostics);
+    }
+    if let Some(defs) = definitions_of(schema) {
+        for (name, definition) in defs {
+            check_strict(definition, &format!("{}.$defs.{}", path, name), diagnostics);
+        }
+    }
+}
+
+impl Default for LlmToolsProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypeProvider for LlmToolsProvider {
+    fn name(&self) -> &str {
+        "LlmToolsProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let trimmed = source.trim_start();
+        let set = if trimmed.starts_with('{') || trimmed.starts_with('[') {
+            parse_tools(source)?
+        } else {
+            parse_tools(&read_source(source, params)?)?
+        };
+
+        let json = serde_json::to_string(&set)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected LLM tool definitions".to_string()));
+        };
+
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+        module.types = self.generate(&self.parse_schema(json)?).0;
+
+        let mut result = GeneratedTypes::new();
+        result.modules.push(module);
+        Ok(result)
+    }
+}
+
+impl SourceValidator for LlmToolsProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected LLM tool definitions".to_string()));
+        };
+
+        Ok(self.generate(&self.parse_schema(json)?).1)
+    }
+}
+
+impl DescribedProvider for LlmToolsProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    const OPENAI_TOOLS: &str = r##"{"tools": [
+        {"type": "function", "function": {
+            "name": "get_weather",
+            "description": "Current weather for a location",
+            "strict": true,
+            "parameters": {
+                "type": "object",
+                "properties": {
+                    "location": {
+                        "type": "object",
+                        "properties": {"city": {"type": "string"}, "country": {"type": ["string", "null"]}},
+                        "required": ["city", "country"],
+                        "additionalProperties": false
+                    },
+                    "unit": {"type": "string", "enum": ["celsius", "fahrenheit"]},
+                    "days": {"type": "integer"}
+                },
+                "required": ["location", "unit"],
+                "additionalProperties": false
+            }
+        }},
+        {"type": "function", "function": {"name": "list_alerts"}}
+    ]}"##;
+
+    const ANTHROPIC_TOOLS: &str = r##"[
+        {"name": "create_ticket", "input_schema": {
+            "type": "object",
+            "properties": {
+                "title": {"type": "string"},
+                "labels": {"type": "array", "items": {"type": "object", "properties": {"name": {"type": "string"}}, "required": ["name"]}},
+                "assignee": {"$ref": "#/$defs/user"}
+            },
+            "required": ["title"],
+            "$defs": {"user": {"type": "object", "properties": {"login": {"type": "string"}}, "required": ["login"]}}
+        }},
+        {"type": "bash_20250124", "name": "bash"},
+        {"name": "create ticket", "input_schema": {"type": "string"}}
+    ]"##;
+
+    fn fields(types: &GeneratedTypes, name: &str) -> Vec<(String, String)> {
+        types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+            .fields
+            .iter()
+            .map(|(field, ty)| (field.clone(), ty.to_string()))
+            .collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(f, t)| (f.to_string(), t.to_string())).collect()
+    }
+
+    fn codes(provider: &LlmToolsProvider, schema: &Schema) -> Vec<(String, String)> {
+        provider
+            .schema_diagnostics(schema)
+            .unwrap()
+            .into_iter()
+            .map(|d| (d.code, d.location.unwrap_or_default()))
+            .collect()
+    }
+
+    #[test]
+    fn test_openai_tools() {
+        let provider = LlmToolsProvider::new();
+        let schema = provider.resolve_schema(OPENAI_TOOLS, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Tools").unwrap();
+
+        assert_eq!(
+            fields(&types, "GetWeatherInput"),
+            pairs(&[
+                ("days", "int option"),
+                ("location", "GetWeatherInputLocation"),
+                ("unit", "\"celsius\" | \"fahrenheit\""),
+            ])
+        );
+        assert_eq!(
+            fields(&types, "GetWeatherInputLocation"),
+            pairs(&[("city", "string"), ("country", "string option")])
+        );
+        assert!(fields(&types, "ListAlertsInput").is_empty());
+
+        let Some(TypeDefinition::Du(tool_call)) = types.modules[0].types.last() else {
+            panic!("Expected ToolCall last");
+        };
+        let variants: Vec<&str> = tool_call.variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(variants, ["GetWeather", "ListAlerts"]);
+
+        assert_eq!(codes(&provider, &schema), [("strict-violation".to_string(), "get_weather".to_string())]);
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/llm-tools.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_anthropic_tools() {
+        let path = std::env::temp_dir().join(format!("fusabi-llm-tools-{}.json", std::process::id()));
+        std::fs::write(&path, ANTHROPIC_TOOLS).unwrap();
+
+        let provider = LlmToolsProvider::new();
+        let schema = provider.resolve_schema(path.to_str().unwrap(), &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Tools").unwrap();
+        assert_eq!(
+            fields(&types, "CreateTicketInput"),
+            pairs(&[
+                ("assignee", "User option"),
+                ("labels", "CreateTicketInputLabelsItem list option"),
+                ("title", "string"),
+            ])
+        );
+        assert_eq!(fields(&types, "CreateTicketInputLabelsItem"), pairs(&[("name", "string")]));
+        assert_eq!(fields(&types, "User"), pairs(&[("login", "string")]));
+
+        let found = codes(&provider, &schema);
+        let expected = [
+            ("builtin-tool", ""),
+            ("invalid-name", "create ticket"),
+            ("non-object-input", "create ticket"),
+        ];
+        assert_eq!(found, expected.map(|(c, l)| (c.to_string(), l.to_string())));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,149 @@
+//! Tool definitions in vendor formats
+//!
+//! Both vendors describe a tool as a name, a description and a JSON
+//! Schema for its input, wrapped differently:
+//!
+//! | Format                       | Shape                                                   |
+//! |------------------------------|---------------------------------------------------------|
+//! | OpenAI Chat Completions      | `{"type": "function", "function": {"name", "parameters", "strict"}}` |
+//! | OpenAI Responses             | `{"type": "function", "name", "parameters", "strict"}`  |
+//! | OpenAI legacy `functions`    | `{"name", "parameters"}`                                |
+//! | Anthropic                    | `{"name", "input_schema"}`                              |
+//!
+//! A document is a list of tools, a request body with `tools` (or
+//! `functions`), or a single tool. Entries without an input schema that
+//! name a `type` are built-in tools (`web_search`, `code_interpreter`)
+//! and are skipped.
+
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// The vendor format a tool was written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolFormat {
+    OpenAi,
+    Anthropic,
+}
+
+/// A function tool
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolDef {
+    pub name: String,
+    pub description: Option<String>,
+    pub format: ToolFormat,
+    /// OpenAI strict mode: arguments must match the schema exactly
+    pub strict: bool,
+    /// The input schema
+    pub schema: Value,
+}
+
+/// The function tools of a document, and the built-in tools it skipped
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolSet {
+    pub tools: Vec<ToolDef>,
+    /// `type` of each built-in tool
+    pub builtin: Vec<String>,
+}
+
+/// Parse tool definitions in either vendor's format
+pub fn parse_tools(content: &str) -> ProviderResult<ToolSet> {
+    let document: Value = serde_json::from_str(content)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid tool definitions: {}", e)))?;
+    let entries = match &document {
+        Value::Array(entries) => entries.as_slice(),
+        Value::Object(object) => match object.get("tools").or_else(|| object.get("functions")) {
+            Some(Value::Array(entries)) => entries.as_slice(),
+            Some(_) => return Err(ProviderError::ParseError("`tools` must be a list".to_string())),
+            None => std::slice::from_ref(&document),
+        },
+        _ => return Err(ProviderError::ParseError("Expected a list of tools or an object with `tools`".to_string())),
+    };
+
+    let mut set = ToolSet::default();
+    for entry in entries {
+        match parse_tool(entry)? {
+            Some(tool) => set.tools.push(tool),
+            None => set.builtin.push(entry.get("type").and_then(Value::as_str).unwrap_or_default().to_string()),
+        }
+    }
+    Ok(set)
+}
+
+fn parse_tool(entry: &Value) -> ProviderResult<Option<ToolDef>> {
+    let (definition, format, schema) = if let Some(function) = entry.get("function").filter(|f| f.is_object()) {
+        (function, ToolFormat::OpenAi, function.get("parameters"))
+    } else if let Some(schema) = entry.get("input_schema") {
+        (entry, ToolFormat::Anthropic, Some(schema))
+    } else if entry.get("parameters").is_some() || entry.get("type").is_none() {
+        (entry, ToolFormat::OpenAi, entry.get("parameters"))
+    } else if entry.get("type").and_then(Value::as_str) == Some("function") {
+        (entry, ToolFormat::OpenAi, None)
+    } else {
+        return Ok(None);
+    };
+
+    let name = definition
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or_else(|| ProviderError::ParseError(format!("Tool has no `name`: {}", entry)))?;
+    Ok(Some(ToolDef {
+        name: name.to_string(),
+        description: definition.get("description").and_then(Value::as_str).map(str::to_string),
+        format,
+        strict: definition.get("strict").and_then(Value::as_bool).unwrap_or(false),
+        // Functions without parameters take no arguments
+        schema: schema.cloned().unwrap_or_else(|| json!({"type": "object", "properties": {}})),
+    }))
+}
+
+/// Whether a name is accepted by both vendors: `^[a-zA-Z0-9_-]{1,64}$`
+pub fn is_valid_name(name: &str) -> bool {
+    (1..=64).contains(&name.len()) && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_formats() {
+        let set = parse_tools(
+            r#"{"model": "m", "tools": [
+                {"type": "function", "function": {"name": "get_weather", "parameters": {"type": "object"}, "strict": true}},
+                {"type": "function", "name": "lookup", "parameters": {"type": "object"}},
+                {"name": "search", "description": "Search", "input_schema": {"type": "object"}},
+                {"type": "web_search_20250305", "name": "web_search"},
+                {"type": "function", "function": {"name": "ping"}}
+            ]}"#,
+        )
+        .unwrap();
+        let tools: Vec<(&str, ToolFormat, bool)> = set.tools.iter().map(|t| (t.name.as_str(), t.format, t.strict)).collect();
+        assert_eq!(
+            tools,
+            [
+                ("get_weather", ToolFormat::OpenAi, true),
+                ("lookup", ToolFormat::OpenAi, false),
+                ("search", ToolFormat::Anthropic, false),
+                ("ping", ToolFormat::OpenAi, false),
+            ]
+        );
+        assert_eq!(set.builtin, ["web_search_20250305"]);
+        assert_eq!(set.tools[3].schema["type"], "object");
+
+        let legacy = parse_tools(r#"{"functions": [{"name": "f", "parameters": {"type": "object"}}]}"#).unwrap();
+        assert_eq!(legacy.tools[0].name, "f");
+        let single = parse_tools(r#"{"name": "one", "input_schema": {"type": "object"}}"#).unwrap();
+        assert_eq!(single.tools[0].format, ToolFormat::Anthropic);
+        assert!(parse_tools(r#"[{"description": "nameless", "parameters": {}}]"#).is_err());
+    }
+
+    #[test]
+    fn test_is_valid_name() {
+        assert!(is_valid_name("get_weather-v2"));
+        assert!(!is_valid_name("get weather"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name(&"x".repeat(65)));
+    }
+}
@@ -0,0 +1,545 @@
+//! MCP client for live introspection
+//!
+//! A running server is asked for `tools/list`, `resources/list` and
+//! `prompts/list` after the `initialize` handshake, and the results are
+//! collected into a manifest of the same shape as a specification file.
+//! Lists are only requested for the capabilities the server announces,
+//! and are followed through `nextCursor` pages. Requests the server sends
+//! to the client are answered with "method not found" and notifications
+//! are ignored. Every exchange shares the `fetch_timeout` budget.
+//!
+//! Two transports are spoken:
+//!
+//! - stdio: a `stdio:` source names a command that is launched and spoken
+//!   to in JSON-RPC 2.0 over its stdin and stdout, one message per line.
+//!   The command is split on whitespace; single or double quotes keep an
+//!   argument with spaces together. The server's stderr is discarded and
+//!   the server is killed once introspection is done
+//! - streamable HTTP: each message is POSTed to the endpoint URL, and the
+//!   response is read from a JSON body or a `text/event-stream`. The
+//!   `Mcp-Session-Id` the server assigns is sent back, and the session is
+//!   ended with a DELETE. The credential referenced by the `auth` param is
+//!   sent as a bearer token, or with basic auth when `auth_scheme=basic`
+
+use fusabi_provider_common::{AuthScheme, CredentialResolver, SourceOptions, StructuredError};
+use fusabi_type_providers::{ProviderError, ProviderParams, ProviderResult};
+use serde_json::{json, Map, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+/// Source prefix of a server launched over stdio
+pub const STDIO_SCHEME: &str = "stdio:";
+
+/// Protocol revision requested in `initialize`
+const PROTOCOL_VERSION: &str = "2025-06-18";
+
+/// JSON-RPC error code for unknown methods
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Capabilities listed, with their list method and result key
+const LISTS: [(&str, &str); 3] = [
+    ("tools", "tools/list"),
+    ("resources", "resources/list"),
+    ("prompts", "prompts/list"),
+];
+
+/// Launch the server `command` and collect its tools, resources and prompts
+/// as a manifest
+pub fn introspect_stdio(command: &str, options: &SourceOptions) -> ProviderResult<Value> {
+    introspect(&mut StdioSession::start(command, options)?)
+}
+
+/// Collect the tools, resources and prompts of the server at a streamable
+/// HTTP endpoint as a manifest, taking limits and credentials from params
+pub fn introspect_http(url: &str, params: &ProviderParams) -> ProviderResult<Value> {
+    let mut session = HttpSession::new(url, params)?;
+    let manifest = introspect(&mut session);
+    session.close();
+    manifest
+}
+
+/// Sends JSON-RPC messages to a server
+trait Transport {
+    /// Send a request and wait for its result
+    fn request(&mut self, method: &str, params: Value) -> ProviderResult<Value>;
+
+    /// Send a notification
+    fn notify(&mut self, method: &str) -> ProviderResult<()>;
+
+    /// Learn the protocol revision the server agreed to
+    fn negotiated(&mut self, _protocol_version: &str) {}
+}
+
+fn introspect(transport: &mut impl Transport) -> ProviderResult<Value> {
+    let initialized = transport.request(
+        "initialize",
+        json!({
+            "protocolVersion": PROTOCOL_VERSION,
+            "capabilities": {},
+            "clientInfo": { "name": "fusabi-provider-mcp", "version": env!("CARGO_PKG_VERSION") },
+        }),
+    )?;
+    let version = initialized.get("protocolVersion").and_then(Value::as_str).unwrap_or(PROTOCOL_VERSION);
+    transport.negotiated(version);
+    transport.notify("notifications/initialized")?;
+
+    let capabilities = initialized.get("capabilities");
+    let mut manifest = Map::new();
+    for (capability, method) in LISTS {
+        if capabilities.and_then(|c| c.get(capability)).is_some() {
+            manifest.insert(capability.to_string(), Value::Array(list(transport, method, capability)?));
+        }
+    }
+    Ok(Value::Object(manifest))
+}
+
+/// Every page of a list method, concatenated
+fn list(transport: &mut impl Transport, method: &str, key: &str) -> ProviderResult<Vec<Value>> {
+    let mut items = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let params = match &cursor {
+            Some(cursor) => json!({ "cursor": cursor }),
+            None => json!({}),
+        };
+        let mut page = transport.request(method, params)?;
+        match page.get_mut(key).map(Value::take) {
+            Some(Value::Array(page_items)) => items.extend(page_items),
+            Some(Value::Null) | None => {}
+            Some(_) => {
+                return Err(ProviderError::ParseError(format!(
+                    "MCP `{}` result: expected `{}` to be an array",
+                    method, key
+                )))
+            }
+        }
+
+        let next = page.get("nextCursor").and_then(Value::as_str).map(str::to_string);
+        if next.is_none() || next == cursor {
+            return Ok(items);
+        }
+        cursor = next;
+    }
+}
+
+/// A message received while waiting for the response to a request
+enum Incoming {
+    /// The response: the result, or the error the server reported
+    Response(ProviderResult<Value>),
+    /// A request from the server, with the reply to send: an empty result
+    /// for `ping`, which the spec requires clients to answer, and "method
+    /// not found" for anything else
+    Request(Value),
+    /// A notification or a response to another request
+    Ignored,
+}
+
+impl Incoming {
+    fn classify(mut message: Value, method: &str, id: u64) -> Self {
+        if let Some(request_method) = message.get("method") {
+            return match message.get("id") {
+                Some(request_id) if request_method == "ping" => Incoming::Request(json!({
+                    "jsonrpc": "2.0",
+                    "id": request_id,
+                    "result": {},
+                })),
+                Some(request_id) => Incoming::Request(json!({
+                    "jsonrpc": "2.0",
+                    "id": request_id,
+                    "error": { "code": METHOD_NOT_FOUND, "message": "Method not found" },
+                })),
+                None => Incoming::Ignored,
+            };
+        }
+        if message.get("id").and_then(Value::as_u64) != Some(id) {
+            return Incoming::Ignored;
+        }
+
+        if let Some(error) = message.get("error") {
+            let reason = error.get("message").and_then(Value::as_str).unwrap_or("unknown error");
+            let error = StructuredError::network(format!("MCP request `{}` failed: {}", method, reason));
+            return Incoming::Response(Err(error.into()));
+        }
+        Incoming::Response(Ok(message.get_mut("result").map(Value::take).unwrap_or(Value::Null)))
+    }
+}
+
+fn request_message(id: u64, method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+fn parse_message(text: &str) -> ProviderResult<Value> {
+    serde_json::from_str(text)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid JSON-RPC message from MCP server: {}", e)))
+}
+
+fn timed_out() -> ProviderError {
+    StructuredError::network("MCP server exceeded fetch_timeout").into()
+}
+
+fn too_large(options: &SourceOptions) -> String {
+    format!("message exceeds max_source_bytes ({})", options.max_source_bytes)
+}
+
+/// Read a line of at most `max_source_bytes`; `None` at the end of input
+fn read_line(reader: &mut impl BufRead, options: &SourceOptions) -> std::io::Result<Option<String>> {
+    let mut line = String::new();
+    match reader.take(options.max_source_bytes + 1).read_line(&mut line)? {
+        0 => Ok(None),
+        n if n as u64 > options.max_source_bytes => {
+            Err(std::io::Error::new(std::io::ErrorKind::InvalidData, too_large(options)))
+        }
+        _ => Ok(Some(line)),
+    }
+}
+
+/// A server launched over stdio and its message stream
+struct StdioSession {
+    child: Child,
+    stdin: Option<ChildStdin>,
+    lines: Receiver<std::io::Result<String>>,
+    deadline: Instant,
+    next_id: u64,
+}
+
+impl StdioSession {
+    fn start(command: &str, options: &SourceOptions) -> ProviderResult<Self> {
+        let args = split_command(command)?;
+        let (program, args) = args.split_first().ok_or_else(|| {
+            ProviderError::InvalidSource("Expected a command after `stdio:`, e.g. `stdio:npx my-mcp-server`".to_string())
+        })?;
+
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| StructuredError::io(format!("Cannot start MCP server `{}`", program)).with_source(e))?;
+
+        // Lines are read on their own thread so that waits are bounded by
+        // the deadline even when the server stops writing
+        let mut stdout = BufReader::new(child.stdout.take().expect("stdout is piped"));
+        let reader_options = options.clone();
+        let (sender, lines) = mpsc::channel();
+        std::thread::spawn(move || loop {
+            let line = match read_line(&mut stdout, &reader_options) {
+                Ok(Some(line)) => Ok(line),
+                Ok(None) => break,
+                Err(e) => Err(e),
+            };
+            let failed = line.is_err();
+            if sender.send(line).is_err() || failed {
+                break;
+            }
+        });
+
+        Ok(Self {
+            stdin: child.stdin.take(),
+            child,
+            lines,
+            deadline: Instant::now() + options.fetch_timeout,
+            next_id: 1,
+        })
+    }
+
+    fn send(&mut self, message: &Value) -> ProviderResult<()> {
+        let stdin = self.stdin.as_mut().expect("stdin is open until the session ends");
+        writeln!(stdin, "{}", message)
+            .and_then(|_| stdin.flush())
+            .map_err(|e| StructuredError::io("Cannot write to MCP server").with_source(e).into())
+    }
+
+    fn receive(&mut self) -> ProviderResult<Value> {
+        loop {
+            let remaining = self.deadline.saturating_duration_since(Instant::now());
+            let line = match self.lines.recv_timeout(remaining) {
+                Ok(Ok(line)) => line,
+                Ok(Err(e)) => return Err(StructuredError::io("Cannot read from MCP server").with_source(e).into()),
+                Err(RecvTimeoutError::Timeout) => return Err(timed_out()),
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(StructuredError::io("MCP server exited before answering").into())
+                }
+            };
+            if !line.trim().is_empty() {
+                return parse_message(&line);
+            }
+        }
+    }
+}
+
+impl Transport for StdioSession {
+    fn request(&mut self, method: &str, params: Value) -> ProviderResult<Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.send(&request_message(id, method, params))?;
+
+        loop {
+            match Incoming::classify(self.receive()?, method, id) {
+                Incoming::Response(result) => return result,
+                Incoming::Request(reply) => self.send(&reply)?,
+                Incoming::Ignored => {}
+            }
+        }
+    }
+
+    fn notify(&mut self, method: &str) -> ProviderResult<()> {
+        self.send(&json!({ "jsonrpc": "2.0", "method": method }))
+    }
+}
+
+impl Drop for StdioSession {
+    fn drop(&mut self) {
+        drop(self.stdin.take());
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Split a command line into arguments, honoring single and double quotes
+fn split_command(command: &str) -> ProviderResult<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current: Option<String> = None;
+    let mut quote = None;
+
+    for c in command.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => current.get_or_insert_with(String::new).push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                current.get_or_insert_with(String::new);
+            }
+            (None, c) if c.is_whitespace() => args.extend(current.take()),
+            (None, c) => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quote.is_some() {
+        return Err(ProviderError::InvalidSource(format!("Unterminated quote in MCP server command `{}`", command)));
+    }
+    args.extend(current);
+    Ok(args)
+}
+
+/// A session with a server over streamable HTTP
+struct HttpSession {
+    agent: ureq::Agent,
+    url: String,
+    authorization: Option<String>,
+    session_id: Option<String>,
+    protocol_version: Option<String>,
+    options: SourceOptions,
+    deadline: Instant,
+    next_id: u64,
+}
+
+impl HttpSession {
+    fn new(url: &str, params: &ProviderParams) -> ProviderResult<Self> {
+        let options = SourceOptions::from_params(params)?;
+        let scheme = AuthScheme::from_params(params)?;
+        let authorization = CredentialResolver::new()
+            .from_params(params)?
+            .map(|credential| credential.authorization(scheme))
+            .transpose()?;
+
+        Ok(Self {
+            agent: ureq::AgentBuilder::new().redirects(0).build(),
+            url: url.to_string(),
+            authorization,
+            session_id: None,
+            protocol_version: None,
+            deadline: Instant::now() + options.fetch_timeout,
+            options,
+            next_id: 1,
+        })
+    }
+
+    fn remaining(&self) -> ProviderResult<Duration> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(timed_out());
+        }
+        Ok(remaining)
+    }
+
+    fn with_headers(&self, mut request: ureq::Request) -> ureq::Request {
+        if let Some(authorization) = &self.authorization {
+            request = request.set("Authorization", authorization);
+        }
+        if let Some(session_id) = &self.session_id {
+            request = request.set("Mcp-Session-Id", session_id);
+        }
+        if let Some(version) = &self.protocol_version {
+            request = request.set("MCP-Protocol-Version", version);
+        }
+        request
+    }
+
+    fn post(&self, message: &Value) -> ProviderResult<ureq::Response> {
+        let request = self
+            .agent
+            .post(&self.url)
+            .timeout(self.remaining()?)
+            .set("Content-Type", "application/json")
+            .set("Accept", "application/json, text/event-stream");
+        self.with_headers(request).send_string(&message.to_string()).map_err(|e| match e {
+            // A credential problem is fixed in the params, not by retrying
+            ureq::Error::Status(code @ (401 | 403), _) => {
+                let problem = match self.authorization {
+                    Some(_) => "rejected the `auth` credential",
+                    None => "requires a credential; reference a token with the `auth` param",
+                };
+                StructuredError::validation(format!("MCP server at {} {} (HTTP {})", self.url, problem, code)).into()
+            }
+            ureq::Error::Status(code, _) => {
+                StructuredError::network(format!("MCP server at {} answered HTTP {}", self.url, code)).into()
+            }
+            ureq::Error::Transport(_) if self.remaining().is_err() => timed_out(),
+            ureq::Error::Transport(t) => {
+                StructuredError::network(format!("Cannot reach MCP server at {}", self.url)).with_source(t).into()
+            }
+        })
+    }
+
+    /// The response to request `id` in a JSON body or an event stream,
+    /// answering server requests met on the way
+    fn read_response(&self, response: ureq::Response, method: &str, id: u64) -> ProviderResult<Value> {
+        let read_error =
+            |e: std::io::Error| ProviderError::from(StructuredError::network("Cannot read from MCP server").with_source(e));
+
+        if response.content_type() != "text/event-stream" {
+            let mut body = String::new();
+            response
+                .into_reader()
+                .take(self.options.max_source_bytes + 1)
+                .read_to_string(&mut body)
+                .map_err(read_error)?;
+            if body.len() as u64 > self.options.max_source_bytes {
+                return Err(StructuredError::validation(format!("MCP server {}", too_large(&self.options))).into());
+            }
+            let messages = match parse_message(&body)? {
+                Value::Array(batch) => batch,
+                message => vec![message],
+            };
+            for message in messages {
+                match Incoming::classify(message, method, id) {
+                    Incoming::Response(result) => return result,
+                    Incoming::Request(reply) => self.reply(&reply)?,
+                    Incoming::Ignored => {}
+                }
+            }
+            return Err(StructuredError::network(format!("MCP server did not answer `{}`", method)).into());
+        }
+
+        // One JSON-RPC message per event, in its `data` lines
+        let mut events = BufReader::new(response.into_reader());
+        let mut data = String::new();
+        while let Some(line) = read_line(&mut events, &self.options).map_err(read_error)? {
+            let line = line.trim_end_matches(['\r', '\n']);
+            if let Some(value) = line.strip_prefix("data:") {
+                if !data.is_empty() {
+                    data.push('\n');
+                }
+                data.push_str(value.strip_prefix(' ').unwrap_or(value));
+                continue;
+            }
+            if !line.is_empty() || data.is_empty() {
+                continue;
+            }
+            match Incoming::classify(parse_message(&std::mem::take(&mut data))?, method, id) {
+                Incoming::Response(result) => return result,
+                Incoming::Request(reply) => self.reply(&reply)?,
+                Incoming::Ignored => {}
+            }
+        }
+        Err(StructuredError::network(format!(
+            "MCP server closed the event stream before answering `{}`",
+            method
+        ))
+        .into())
+    }
+
+    /// Send a reply or notification; the server acknowledges it without a body
+    fn reply(&self, message: &Value) -> ProviderResult<()> {
+        self.post(message).map(drop)
+    }
+
+    /// End the session; servers that do not support it answer 405
+    fn close(self) {
+        let (Some(_), Ok(remaining)) = (&self.session_id, self.remaining()) else {
+            return;
+        };
+        let request = self.agent.delete(&self.url).timeout(remaining);
+        let _ = self.with_headers(request).call();
+    }
+}
+
+impl Transport for HttpSession {
+    fn request(&mut self, method: &str, params: Value) -> ProviderResult<Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let response = self.post(&request_message(id, method, params))?;
+        if let Some(session_id) = response.header("Mcp-Session-Id") {
+            self.session_id = Some(session_id.to_string());
+        }
+        self.read_response(response, method, id)
+    }
+
+    fn notify(&mut self, method: &str) -> ProviderResult<()> {
+        self.reply(&json!({ "jsonrpc": "2.0", "method": method }))
+    }
+
+    fn negotiated(&mut self, protocol_version: &str) {
+        self.protocol_version = Some(protocol_version.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_command() {
+        assert_eq!(split_command(" npx  my-mcp-server ").unwrap(), ["npx", "my-mcp-server"]);
+        assert_eq!(
+            split_command("node '/opt/my server/index.js' --name \"\"").unwrap(),
+            ["node", "/opt/my server/index.js", "--name", ""]
+        );
+        assert!(split_command("").unwrap().is_empty());
+        assert!(split_command("node 'index.js").is_err());
+    }
+
+    #[test]
+    fn test_classify_messages() {
+        let response = json!({ "jsonrpc": "2.0", "id": 2, "result": { "tools": [] } });
+        assert!(matches!(
+            Incoming::classify(response, "tools/list", 2),
+            Incoming::Response(Ok(result)) if result == json!({ "tools": [] })
+        ));
+
+        let failure = json!({ "jsonrpc": "2.0", "id": 2, "error": { "code": -32602, "message": "bad cursor" } });
+        let Incoming::Response(Err(err)) = Incoming::classify(failure, "tools/list", 2) else {
+            panic!("Expected an error response");
+        };
+        assert!(err.to_string().contains("MCP request `tools/list` failed: bad cursor"));
+
+        let ping = json!({ "jsonrpc": "2.0", "id": "s1", "method": "ping" });
+        let Incoming::Request(reply) = Incoming::classify(ping, "tools/list", 2) else {
+            panic!("Expected a server request");
+        };
+        assert_eq!(reply, json!({ "jsonrpc": "2.0", "id": "s1", "result": {} }));
+
+        let sampling = json!({ "jsonrpc": "2.0", "id": 7, "method": "sampling/createMessage" });
+        let Incoming::Request(reply) = Incoming::classify(sampling, "tools/list", 2) else {
+            panic!("Expected a server request");
+        };
+        assert_eq!(reply["id"], 7);
+        assert_eq!(reply["error"]["code"], METHOD_NOT_FOUND);
+
+        let other = json!({ "jsonrpc": "2.0", "id": 1, "result": {} });
+        assert!(matches!(Incoming::classify(other, "tools/list", 2), Incoming::Ignored));
+    }
+}
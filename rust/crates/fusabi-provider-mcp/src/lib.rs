@@ -5,12 +5,26 @@
 //!
 //! # Features
 //!
-//! - Tool definitions with input schemas
+//! - Tool definitions with input schemas, and `{Tool}Output` records and a
+//!   `ToolResult` union for tools that declare an `outputSchema`
+//! - Records for nested objects, such as `GetWeatherInputLocation`, down to
+//!   [`MAX_INLINE_DEPTH`] levels (see [`McpProvider::with_inline_depth`])
 //! - Resource definitions with URI templates
 //! - Prompt definitions with arguments
 //! - Full MCP protocol message types
 //! - Content types (text, image, resource)
 //! - Embedded mode with built-in MCP types
+//! - Live introspection of running servers over stdio or streamable HTTP
+//! - Tool, resource, prompt and argument descriptions as documentation
+//! - JSON Schema `default` values as field defaults
+//! - Wire names as attributes: `mcp.tool` on tool input and output types and
+//!   `ToolCall` and `ToolResult` cases, `mcp.resource_uri` and `mcp.mime_type` on resource types,
+//!   `mcp.prompt` on prompt argument types
+//! - JSON decoders for tool inputs, resources and prompt arguments through
+//!   [`DecoderCodegen`]
+//! - `<ns>.tools`, `<ns>.resources`, `<ns>.prompts` and `<ns>.definitions`
+//!   modules, or a single `<ns>` module with `module_layout=flat` through
+//!   [`GroupedProvider`]
 //!
 //! # Example
 //!
@@ -30,141 +44,385 @@
 //! let schema = provider.resolve_schema("", &params)?;
 //! let types = provider.generate_types(&schema, "Mcp")?;
 //! ```
-
+//!
+//! Embedded mode generates the JSON-RPC messages, content, tool, resource,
+//! prompt and capability types of the protocol into `<ns>.protocol`.
+//!
+//! The built-in protocol types are behind the default `protocol` feature.
+//! Hosts that only read server specifications build with
+//! `default-features = false`; [`embedded_packs`] reports whether they are
+//! compiled in.
+//!
+//! # Live Servers
+//!
+//! ```rust,ignore
+//! let schema = provider.resolve_schema("stdio:npx my-mcp-server", &ProviderParams::default())?;
+//!
+//! let params = ProviderParams::default()
+//!     .with("mode", "live")
+//!     .with("auth", "env:MCP_TOKEN");
+//! let schema = provider.resolve_schema("https://mcp.example.com/mcp", &params)?;
+//! ```
+//!
+//! A `stdio:` source launches the command after the prefix, and with
+//! `mode=live` an `http(s)://` source is the endpoint of a server speaking
+//! the streamable HTTP transport; other URLs are read as specification
+//! files. The server is initialized and the schema built from its
+//! `tools/list`, `resources/list` and `prompts/list` responses.
+//! `fetch_timeout` bounds the whole exchange and `max_source_bytes` each
+//! message. A `stdio:` command runs with the privileges of the host, so
+//! only use sources you trust.
+
+mod client;
 mod parser;
+#[cfg(feature = "protocol")]
+mod protocol;
 mod types;
 
 pub use types::{
     ContentType, JsonSchemaObject, JsonSchemaProperty, McpSchema, MessageType, PromptArgument,
     PromptDefinition, ResourceDefinition, ToolDefinition, TypeDefinition, TypeKind,
-    EMBEDDED_MCP_TYPES,
 };
 
+#[cfg(feature = "protocol")]
+pub use types::EMBEDDED_MCP_TYPES;
+
+use fusabi_jsonschema_core::Converter;
+use fusabi_provider_common::{
+    interpolate_env, interpolate_params, read_source, AttributedProvider, Capabilities, DecoderCodegen, DecoderPlan, DefaultValue,
+    DefaultedProvider, DescribedProvider, DocumentedProvider, FieldDefaults, GroupedProvider,
+    inline_name, InlineRecords, InputLimits, ModuleBuilder, ModuleGrouping,
+    OutputFeature, ParamKind, ParamSchema, ParamSpec, ParameterizedProvider, SourceOptions, SourceValidator,
+    TypeAttributes, TypeDocs, TypeShape, WireFormat,
+};
+use fusabi_provider_common::source::is_remote;
 use fusabi_type_providers::{
-    DuDef, GeneratedModule, GeneratedTypes, NamingStrategy, ProviderError, ProviderParams,
+    DuDef, GeneratedTypes, NamingStrategy, ProviderError, ProviderParams,
     ProviderResult, RecordDef, Schema, TypeExpr, TypeGenerator, TypeProvider,
     TypeDefinition as FusabiTypeDef, VariantDef,
 };
 
+/// Embedded packs compiled into this build: `protocol` for the built-in
+/// MCP protocol types
+pub fn embedded_packs() -> &'static [&'static str] {
+    &[
+        #[cfg(feature = "protocol")]
+        "protocol",
+    ]
+}
+
+/// Fail unless the built-in protocol types are compiled in
+fn require_protocol() -> ProviderResult<()> {
+    if embedded_packs().contains(&"protocol") {
+        Ok(())
+    } else {
+        Err(ProviderError::InvalidSource(
+            "Embedded MCP protocol types are not enabled; build fusabi-provider-mcp with the `protocol` feature"
+                .to_string(),
+        ))
+    }
+}
+
+/// Default depth to which nested objects in tool schemas and definitions
+/// get records of their own
+pub const MAX_INLINE_DEPTH: usize = 8;
+
 /// MCP type provider
 pub struct McpProvider {
     generator: TypeGenerator,
+    converter: Converter,
+    inline_depth: usize,
 }
 
 impl McpProvider {
     pub fn new() -> Self {
         Self {
             generator: TypeGenerator::new(NamingStrategy::PascalCase),
+            converter: Converter::new(),
+            inline_depth: MAX_INLINE_DEPTH,
         }
     }
 
+    /// Give nested objects records down to `depth` levels below the tool
+    /// input, output or definition containing them; deeper objects become
+    /// `Map<string, any>`
+    pub fn with_inline_depth(mut self, depth: usize) -> Self {
+        self.inline_depth = depth;
+        self
+    }
+
+    /// Hoisted records for nested objects, limited to the inline depth
+    fn inline_records(&self) -> InlineRecords {
+        InlineRecords::new().with_max_depth(self.inline_depth)
+    }
+
     /// Parse MCP schema from string
     fn parse_schema(&self, json: &str) -> ProviderResult<types::McpSchema> {
         parser::parse_mcp_schema(json)
     }
 
     /// Generate types from parsed MCP schema
+    ///
+    /// Tools, resources, prompts and definitions each get a module, or all
+    /// share the namespace module with a flat grouping.
     fn generate_from_schema(
         &self,
         schema: &types::McpSchema,
         namespace: &str,
-    ) -> ProviderResult<GeneratedTypes> {
+        grouping: ModuleGrouping,
+    ) -> ProviderResult<(GeneratedTypes, TypeDocs, FieldDefaults)> {
         let mut result = GeneratedTypes::new();
+        let mut docs = TypeDocs::new();
+        let mut defaults = FieldDefaults::new();
+        let mut modules = ModuleBuilder::new(namespace, grouping);
 
         // Generate tool types
         if !schema.tools.is_empty() {
-            let mut tools_module = GeneratedModule::new(vec![namespace.to_string(), "tools".to_string()]);
+            let tools_path = modules.path(&["tools"]);
 
+            let mut inline = self.inline_records();
             for tool in &schema.tools {
-                if let Some(type_def) = self.generate_tool_type(tool)? {
-                    tools_module.types.push(type_def);
+                let tool_name = self.generator.naming.apply(&tool.name);
+                inline.reserve(format!("{}Input", tool_name));
+                inline.reserve(format!("{}Output", tool_name));
+            }
+            inline.reserve("ToolCall");
+            inline.reserve("ToolResult");
+
+            let mut tool_types = Vec::new();
+            for tool in &schema.tools {
+                if let Some(type_def) = self.generate_tool_type(tool, &mut inline)? {
+                    tool_types.push(type_def);
+                }
+                if let Some(type_def) = self.generate_tool_output_type(tool, &mut inline)? {
+                    tool_types.push(type_def);
+                }
+                self.document_tool(&mut docs, &tools_path, tool);
+                let tool_name = self.generator.naming.apply(&tool.name);
+                if let Some(input_schema) = &tool.input_schema {
+                    let input_type_name = format!("{}Input", tool_name);
+                    property_defaults(
+                        &mut defaults,
+                        &tools_path,
+                        &input_type_name,
+                        &input_schema.properties,
+                    );
+                }
+                if let Some(output_schema) = &tool.output_schema {
+                    let output_type_name = format!("{}Output", tool_name);
+                    property_defaults(
+                        &mut defaults,
+                        &tools_path,
+                        &output_type_name,
+                        &output_schema.properties,
+                    );
                 }
             }
 
+            // Records of nested objects come before the types using them
+            modules.extend(&["tools"], inline.into_definitions());
+            modules.extend(&["tools"], tool_types);
+
             // Add tool union type
             if schema.tools.len() > 1 {
                 let tool_union = self.generate_tool_union(&schema.tools)?;
-                tools_module.types.push(tool_union);
+                modules.push(&["tools"], tool_union);
             }
 
-            result.modules.push(tools_module);
+            // Add tool result union type, for tools with structured output
+            let output_tools: Vec<_> = schema.tools.iter().filter(|t| t.output_schema.is_some()).collect();
+            if !output_tools.is_empty() {
+                modules.push(&["tools"], self.generate_tool_result_union(&output_tools));
+            }
         }
 
         // Generate resource types
         if !schema.resources.is_empty() {
-            let mut resources_module =
-                GeneratedModule::new(vec![namespace.to_string(), "resources".to_string()]);
+            let resources_path = modules.path(&["resources"]);
 
             for resource in &schema.resources {
                 if let Some(type_def) = self.generate_resource_type(resource)? {
-                    resources_module.types.push(type_def);
+                    modules.push(&["resources"], type_def);
+                }
+                if let Some(description) = &resource.description {
+                    let name = format!("{}Resource", self.generator.naming.apply(&resource.name));
+                    docs.set_type(&resources_path, &name, description);
                 }
             }
-
-            result.modules.push(resources_module);
         }
 
         // Generate prompt types
         if !schema.prompts.is_empty() {
-            let mut prompts_module =
-                GeneratedModule::new(vec![namespace.to_string(), "prompts".to_string()]);
+            let prompts_path = modules.path(&["prompts"]);
 
             for prompt in &schema.prompts {
                 if let Some(type_def) = self.generate_prompt_type(prompt)? {
-                    prompts_module.types.push(type_def);
+                    modules.push(&["prompts"], type_def);
                 }
+                self.document_prompt(&mut docs, &prompts_path, prompt);
             }
-
-            result.modules.push(prompts_module);
         }
 
         // Generate custom types
         if !schema.definitions.is_empty() {
-            let mut defs_module =
-                GeneratedModule::new(vec![namespace.to_string(), "definitions".to_string()]);
+            let defs_path = modules.path(&["definitions"]);
 
-            for (name, type_def) in &schema.definitions {
-                if let Some(fusabi_def) = self.generate_custom_type(name, type_def)? {
-                    defs_module.types.push(fusabi_def);
+            // Sorted, so that hoisted records get the same names every run
+            let mut definitions: Vec<_> = schema.definitions.iter().collect();
+            definitions.sort_by_key(|(name, _)| name.as_str());
+
+            let mut inline = self.inline_records();
+            for (name, _) in &definitions {
+                inline.reserve(self.generator.naming.apply(name));
+            }
+
+            let mut custom_types = Vec::new();
+            for (name, type_def) in definitions {
+                if let Some(fusabi_def) = self.generate_custom_type(name, type_def, &mut inline)? {
+                    custom_types.push(fusabi_def);
+                }
+                if let TypeKind::Object { properties, .. } = &type_def.kind {
+                    let type_name = self.generator.naming.apply(name);
+                    document_properties(&mut docs, &defs_path, &type_name, properties);
+                    property_defaults(&mut defaults, &defs_path, &type_name, properties);
                 }
             }
 
-            result.modules.push(defs_module);
+            modules.extend(&["definitions"], inline.into_definitions());
+            modules.extend(&["definitions"], custom_types);
         }
 
-        Ok(result)
+        result.modules = modules.finish()?;
+        Ok((result, docs, defaults))
+    }
+
+    /// Record the wire names of tools, resources and prompts
+    fn annotate_schema(&self, schema: &types::McpSchema, namespace: &str) -> TypeAttributes {
+        let mut attributes = TypeAttributes::new();
+
+        let tools = vec![namespace.to_string(), "tools".to_string()];
+        for tool in &schema.tools {
+            let tool_name = self.generator.naming.apply(&tool.name);
+            attributes.set_type(&tools, &format!("{}Input", tool_name), "mcp.tool", &tool.name);
+            if schema.tools.len() > 1 {
+                attributes.set_member(&tools, "ToolCall", &tool_name, "mcp.tool", &tool.name);
+            }
+            if tool.output_schema.is_some() {
+                attributes.set_type(&tools, &format!("{}Output", tool_name), "mcp.tool", &tool.name);
+                attributes.set_member(&tools, "ToolResult", &tool_name, "mcp.tool", &tool.name);
+            }
+        }
+
+        let resources = vec![namespace.to_string(), "resources".to_string()];
+        for resource in &schema.resources {
+            let type_name = format!("{}Resource", self.generator.naming.apply(&resource.name));
+            attributes.set_type(&resources, &type_name, "mcp.resource_uri", &resource.uri);
+            if let Some(mime_type) = &resource.mime_type {
+                attributes.set_type(&resources, &type_name, "mcp.mime_type", mime_type);
+            }
+        }
+
+        let prompts = vec![namespace.to_string(), "prompts".to_string()];
+        for prompt in &schema.prompts {
+            let type_name = format!("{}Args", self.generator.naming.apply(&prompt.name));
+            attributes.set_type(&prompts, &type_name, "mcp.prompt", &prompt.name);
+        }
+
+        attributes
+    }
+
+    /// Generate types, docs and defaults from an MCP schema or the embedded marker
+    fn generate_all(
+        &self,
+        schema: &Schema,
+        namespace: &str,
+        grouping: ModuleGrouping,
+    ) -> ProviderResult<(GeneratedTypes, TypeDocs, FieldDefaults)> {
+        match schema {
+            Schema::Custom(content) => {
+                if content == "embedded" {
+                    // Generate embedded MCP types
+                    let types = self.generate_embedded_types(namespace, grouping)?;
+                    Ok((types, TypeDocs::new(), FieldDefaults::new()))
+                } else {
+                    // Parse the JSON content
+                    let parsed = self.parse_schema(content)?;
+                    self.generate_from_schema(&parsed, namespace, grouping)
+                }
+            }
+            _ => Err(ProviderError::ParseError(
+                "Expected MCP schema".to_string(),
+            )),
+        }
+    }
+
+    /// Record a tool's description on its input type and `ToolCall` case,
+    /// and its input and output property descriptions on their fields
+    fn document_tool(&self, docs: &mut TypeDocs, path: &[String], tool: &types::ToolDefinition) {
+        let tool_name = self.generator.naming.apply(&tool.name);
+        let input_type_name = format!("{}Input", tool_name);
+        let output_type_name = format!("{}Output", tool_name);
+
+        if let Some(description) = &tool.description {
+            docs.set_type(path, &input_type_name, description);
+            docs.set_member(path, "ToolCall", &tool_name, description);
+            if tool.output_schema.is_some() {
+                docs.set_member(path, "ToolResult", &tool_name, description);
+            }
+        }
+        if let Some(input_schema) = &tool.input_schema {
+            document_properties(docs, path, &input_type_name, &input_schema.properties);
+        }
+        if let Some(output_schema) = &tool.output_schema {
+            document_properties(docs, path, &output_type_name, &output_schema.properties);
+        }
+    }
+
+    /// Record a prompt's description and its argument descriptions
+    fn document_prompt(&self, docs: &mut TypeDocs, path: &[String], prompt: &types::PromptDefinition) {
+        let args_type_name = format!("{}Args", self.generator.naming.apply(&prompt.name));
+
+        if let Some(description) = &prompt.description {
+            docs.set_type(path, &args_type_name, description);
+        }
+        for arg in &prompt.arguments {
+            if let Some(description) = &arg.description {
+                docs.set_member(path, &args_type_name, &arg.name, description);
+            }
+        }
     }
 
     /// Generate embedded MCP protocol types
-    fn generate_embedded_types(&self, namespace: &str) -> ProviderResult<GeneratedTypes> {
+    fn generate_embedded_types(
+        &self,
+        namespace: &str,
+        grouping: ModuleGrouping,
+    ) -> ProviderResult<GeneratedTypes> {
+        require_protocol()?;
         let mut result = GeneratedTypes::new();
+        let mut modules = ModuleBuilder::new(namespace, grouping);
 
-        // The embedded types are already in Fusabi syntax, so we can return them as a raw module
-        // For now, we'll create a note that these should be included
-        let mut protocol_module =
-            GeneratedModule::new(vec![namespace.to_string(), "protocol".to_string()]);
+        #[cfg(feature = "protocol")]
+        modules.extend(&["protocol"], protocol::protocol_types()?);
 
-        // Add a marker type to indicate embedded types should be included
-        protocol_module.types.push(FusabiTypeDef::Record(RecordDef {
-            name: "__EmbeddedMcpTypes".to_string(),
-            fields: vec![("__marker".to_string(), TypeExpr::Named("unit".to_string()))],
-        }));
-
-        result.modules.push(protocol_module);
+        result.modules = modules.finish()?;
         Ok(result)
     }
 
     /// Generate type definition for a tool
+    ///
+    /// Nested object properties are hoisted into `inline`.
     fn generate_tool_type(
         &self,
         tool: &types::ToolDefinition,
+        inline: &mut InlineRecords,
     ) -> ProviderResult<Option<FusabiTypeDef>> {
         let tool_name = self.generator.naming.apply(&tool.name);
 
         if let Some(input_schema) = &tool.input_schema {
             // Generate input type
             let input_type_name = format!("{}Input", tool_name);
-            let fields = self.schema_object_to_fields(input_schema)?;
+            let fields = self.schema_object_to_fields(input_schema, &input_type_name, inline)?;
 
             Ok(Some(FusabiTypeDef::Record(RecordDef {
                 name: input_type_name,
@@ -179,13 +437,48 @@ impl McpProvider {
         }
     }
 
+    /// Generate the structured result type of a tool, if it declares an
+    /// output schema, hoisting nested object properties into `inline`
+    fn generate_tool_output_type(
+        &self,
+        tool: &types::ToolDefinition,
+        inline: &mut InlineRecords,
+    ) -> ProviderResult<Option<FusabiTypeDef>> {
+        let Some(output_schema) = &tool.output_schema else {
+            return Ok(None);
+        };
+
+        let output_type_name = format!("{}Output", self.generator.naming.apply(&tool.name));
+        Ok(Some(FusabiTypeDef::Record(RecordDef {
+            fields: self.schema_object_to_fields(output_schema, &output_type_name, inline)?,
+            name: output_type_name,
+        })))
+    }
+
+    /// Generate union type of the results of tools with an output schema
+    fn generate_tool_result_union(&self, tools: &[&types::ToolDefinition]) -> FusabiTypeDef {
+        let variants = tools
+            .iter()
+            .map(|tool| {
+                let tool_name = self.generator.naming.apply(&tool.name);
+                let output_type = TypeShape::named(format!("{}Output", tool_name)).into();
+                VariantDef::new(tool_name, vec![output_type])
+            })
+            .collect();
+
+        FusabiTypeDef::Du(DuDef {
+            name: "ToolResult".to_string(),
+            variants,
+        })
+    }
+
     /// Generate union type for all tools
     fn generate_tool_union(&self, tools: &[types::ToolDefinition]) -> ProviderResult<FusabiTypeDef> {
         let variants = tools
             .iter()
             .map(|tool| {
                 let tool_name = self.generator.naming.apply(&tool.name);
-                let input_type = TypeExpr::Named(format!("{}Input", tool_name));
+                let input_type = TypeShape::named(format!("{}Input", tool_name)).into();
                 VariantDef::new(tool_name, vec![input_type])
             })
             .collect();
@@ -204,21 +497,21 @@ impl McpProvider {
         let resource_name = self.generator.naming.apply(&resource.name);
 
         let mut fields = vec![
-            ("uri".to_string(), TypeExpr::Named("string".to_string())),
-            ("name".to_string(), TypeExpr::Named("string".to_string())),
+            ("uri".to_string(), TypeShape::named("string").into()),
+            ("name".to_string(), TypeShape::named("string").into()),
         ];
 
         if resource.description.is_some() {
             fields.push((
                 "description".to_string(),
-                TypeExpr::Named("string option".to_string()),
+                TypeShape::named("string").option().into(),
             ));
         }
 
         if resource.mime_type.is_some() {
             fields.push((
                 "mimeType".to_string(),
-                TypeExpr::Named("string option".to_string()),
+                TypeShape::named("string").option().into(),
             ));
         }
 
@@ -247,10 +540,10 @@ impl McpProvider {
                 .arguments
                 .iter()
                 .map(|arg| {
-                    let type_expr = if arg.required {
-                        TypeExpr::Named("string".to_string())
+                    let type_expr: TypeExpr = if arg.required {
+                        TypeShape::named("string").into()
                     } else {
-                        TypeExpr::Named("string option".to_string())
+                        TypeShape::named("string").option().into()
                     };
                     (arg.name.clone(), type_expr)
                 })
@@ -264,10 +557,13 @@ impl McpProvider {
     }
 
     /// Generate type definition for a custom type
+    ///
+    /// Nested object properties are hoisted into `inline`.
     fn generate_custom_type(
         &self,
         name: &str,
         type_def: &types::TypeDefinition,
+        inline: &mut InlineRecords,
     ) -> ProviderResult<Option<FusabiTypeDef>> {
         let type_name = self.generator.naming.apply(name);
 
@@ -276,7 +572,7 @@ impl McpProvider {
                 properties,
                 required,
             } => {
-                let fields = self.properties_to_fields(properties, required)?;
+                let fields = self.properties_to_fields(properties, required, &type_name, inline)?;
                 Ok(Some(FusabiTypeDef::Record(RecordDef {
                     name: type_name,
                     fields,
@@ -314,81 +610,48 @@ impl McpProvider {
         }
     }
 
-    /// Convert JSON Schema object to record fields
+    /// Convert JSON Schema object of record `record` to record fields,
+    /// hoisting nested objects into `inline`
     fn schema_object_to_fields(
         &self,
         schema: &types::JsonSchemaObject,
+        record: &str,
+        inline: &mut InlineRecords,
     ) -> ProviderResult<Vec<(String, TypeExpr)>> {
-        self.properties_to_fields(&schema.properties, &schema.required)
+        Ok(self.converter.hoisted_record_fields(&schema.schema, record, inline))
     }
 
-    /// Convert properties to record fields
+    /// Convert properties of record `record` to record fields
     fn properties_to_fields(
         &self,
         properties: &std::collections::HashMap<String, types::JsonSchemaProperty>,
         required: &[String],
+        record: &str,
+        inline: &mut InlineRecords,
     ) -> ProviderResult<Vec<(String, TypeExpr)>> {
         let mut fields = Vec::new();
 
         for (prop_name, prop) in properties {
-            let type_expr = self.property_to_type_expr(prop)?;
+            let shape = self.property_shape(prop, &inline_name(record, prop_name), inline)?;
             let is_required = required.contains(prop_name);
 
-            let final_type = if is_required {
-                type_expr
-            } else {
-                TypeExpr::Named(format!("{} option", type_expr))
-            };
+            let final_type = shape.option_unless(is_required);
 
-            fields.push((prop_name.clone(), final_type));
+            fields.push((prop_name.clone(), final_type.into()));
         }
 
         Ok(fields)
     }
 
-    /// Convert JSON Schema property to TypeExpr
-    fn property_to_type_expr(
+    /// Convert JSON Schema property to a TypeShape, hoisting a nested
+    /// object to a record named `name`
+    fn property_shape(
         &self,
         prop: &types::JsonSchemaProperty,
-    ) -> ProviderResult<TypeExpr> {
-        // Handle enum
-        if !prop.enum_values.is_empty() {
-            // For string enums, we use a union type
-            let enum_str = prop
-                .enum_values
-                .iter()
-                .filter_map(|v| v.as_str())
-                .map(|s| format!("\"{}\"", s))
-                .collect::<Vec<_>>()
-                .join(" | ");
-            return Ok(TypeExpr::Named(enum_str));
-        }
-
-        match prop.property_type.as_str() {
-            "string" => Ok(TypeExpr::Named("string".to_string())),
-            "integer" => Ok(TypeExpr::Named("int".to_string())),
-            "number" => Ok(TypeExpr::Named("float".to_string())),
-            "boolean" => Ok(TypeExpr::Named("bool".to_string())),
-            "null" => Ok(TypeExpr::Named("unit".to_string())),
-            "array" => {
-                if let Some(items) = &prop.items {
-                    let item_type = self.property_to_type_expr(items)?;
-                    Ok(TypeExpr::Named(format!("{} list", item_type)))
-                } else {
-                    Ok(TypeExpr::Named("any list".to_string()))
-                }
-            }
-            "object" => {
-                if prop.properties.is_empty() {
-                    // Generic object/map
-                    Ok(TypeExpr::Named("Map<string, any>".to_string()))
-                } else {
-                    // Nested object - would need inline record type support
-                    Ok(TypeExpr::Named("Map<string, any>".to_string()))
-                }
-            }
-            "any" | _ => Ok(TypeExpr::Named("any".to_string())),
-        }
+        name: &str,
+        inline: &mut InlineRecords,
+    ) -> ProviderResult<TypeShape> {
+        Ok(self.converter.hoisted_shape(&prop.schema, name, inline))
     }
 }
 
@@ -398,53 +661,186 @@ impl Default for McpProvider {
     }
 }
 
+impl ParameterizedProvider for McpProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("McpProvider").param(
+            ParamSpec::new("mode", ParamKind::Choice(&["file", "embedded", "live"]))
+                .default("file")
+                .describe("`embedded` to use the built-in MCP protocol types, `live` to introspect the server at an `http(s)://` source"),
+        )
+    }
+}
+
 impl TypeProvider for McpProvider {
     fn name(&self) -> &str {
         "McpProvider"
     }
 
     fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+
         // Check for embedded mode
-        if params.custom.get("mode") == Some(&"embedded".to_string()) || source.is_empty() {
+        if typed.str("mode") == Some("embedded") || source.is_empty() {
             // Return embedded schema marker
+            require_protocol()?;
             return Ok(Schema::Custom("embedded".to_string()));
         }
 
-        // Load from file or parse inline JSON
-        let json_str = if source.starts_with('{') || source.starts_with('[') {
+        // Introspect a live server, or load from file or parse inline JSON
+        let manifest = if let Some(command) = source.trim_start().strip_prefix(client::STDIO_SCHEME) {
+            let options = SourceOptions::from_params(params)?;
+            Some(client::introspect_stdio(&interpolate_env(command)?, &options)?)
+        } else if typed.str("mode") == Some("live") {
+            let url = interpolate_env(source.trim())?;
+            if !is_remote(&url) {
+                return Err(ProviderError::InvalidSource(format!(
+                    "Invalid source '{}' for mode=live: expected an http(s):// server URL or a stdio: command",
+                    source
+                )));
+            }
+            Some(client::introspect_http(&url, &interpolate_params(params)?)?)
+        } else {
+            None
+        };
+
+        let json_str = if let Some(manifest) = manifest {
+            serde_json::to_string_pretty(&manifest).map_err(|e| ProviderError::ParseError(e.to_string()))?
+        } else if source.starts_with('{') || source.starts_with('[') {
             source.to_string()
-        } else if source.starts_with("file://") {
-            let path = source.strip_prefix("file://").unwrap();
-            std::fs::read_to_string(path)
-                .map_err(|e| ProviderError::IoError(e.to_string()))?
         } else {
-            // Treat as file path
-            std::fs::read_to_string(source)
-                .map_err(|e| ProviderError::IoError(e.to_string()))?
+            read_source(source, params)?
         };
 
-        let _value: serde_json::Value = serde_json::from_str(&json_str)
+        let limits = InputLimits::from_params(params)?;
+        limits.check_size(&json_str)?;
+        let value: serde_json::Value = serde_json::from_str(&json_str)
             .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        parser::check_limits(&value, &limits)?;
 
         // Store the JSON in the source for later parsing
         Ok(Schema::Custom(json_str))
     }
 
     fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
-        match schema {
-            Schema::Custom(content) => {
-                if content == "embedded" {
-                    // Generate embedded MCP types
-                    self.generate_embedded_types(namespace)
-                } else {
-                    // Parse the JSON content
-                    let parsed = self.parse_schema(content)?;
-                    self.generate_from_schema(&parsed, namespace)
-                }
+        self.generate_documented(schema, namespace).map(|(types, _)| types)
+    }
+}
+
+impl GroupedProvider for McpProvider {
+    fn default_grouping(&self) -> ModuleGrouping {
+        ModuleGrouping::Nested
+    }
+
+    fn generate_grouped(
+        &self,
+        schema: &Schema,
+        namespace: &str,
+        grouping: ModuleGrouping,
+    ) -> ProviderResult<GeneratedTypes> {
+        self.generate_all(schema, namespace, grouping)
+            .map(|(types, _, _)| types)
+    }
+}
+
+impl DocumentedProvider for McpProvider {
+    fn generate_documented(
+        &self,
+        schema: &Schema,
+        namespace: &str,
+    ) -> ProviderResult<(GeneratedTypes, TypeDocs)> {
+        self.generate_all(schema, namespace, ModuleGrouping::Nested)
+            .map(|(types, docs, _)| (types, docs))
+    }
+}
+
+impl DefaultedProvider for McpProvider {
+    fn generate_with_defaults(
+        &self,
+        schema: &Schema,
+        namespace: &str,
+    ) -> ProviderResult<(GeneratedTypes, FieldDefaults)> {
+        self.generate_all(schema, namespace, ModuleGrouping::Nested)
+            .map(|(types, _, defaults)| (types, defaults))
+    }
+}
+
+impl AttributedProvider for McpProvider {
+    fn generate_attributed(
+        &self,
+        schema: &Schema,
+        namespace: &str,
+    ) -> ProviderResult<(GeneratedTypes, TypeAttributes)> {
+        let (types, _, _) = self.generate_all(schema, namespace, ModuleGrouping::Nested)?;
+        let attributes = match schema {
+            Schema::Custom(content) if content != "embedded" => {
+                self.annotate_schema(&self.parse_schema(content)?, namespace)
             }
-            _ => Err(ProviderError::ParseError(
-                "Expected MCP schema".to_string(),
-            )),
+            _ => TypeAttributes::new(),
+        };
+        Ok((types, attributes))
+    }
+}
+
+impl DecoderCodegen for McpProvider {
+    fn wire_format(&self) -> WireFormat {
+        WireFormat::Json
+    }
+
+    fn generate_decoders(
+        &self,
+        schema: &Schema,
+        namespace: &str,
+    ) -> ProviderResult<(GeneratedTypes, DecoderPlan)> {
+        let (types, _, _) = self.generate_all(schema, namespace, ModuleGrouping::Nested)?;
+        let plan = match schema {
+            Schema::Custom(content) if content != "embedded" => DecoderPlan::json(&types),
+            // Protocol unions are told apart by their `type` field, not tagged
+            _ => DecoderPlan::new(WireFormat::Json),
+        };
+        Ok((types, plan))
+    }
+}
+
+impl SourceValidator for McpProvider {}
+
+impl DescribedProvider for McpProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .live(client::STDIO_SCHEME)
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Docs)
+            .feature(OutputFeature::Attributes)
+            .feature(OutputFeature::Defaults)
+            .feature(OutputFeature::Decoders)
+    }
+}
+
+/// Record JSON Schema property descriptions on the fields of a record
+fn document_properties(
+    docs: &mut TypeDocs,
+    path: &[String],
+    type_name: &str,
+    properties: &std::collections::HashMap<String, types::JsonSchemaProperty>,
+) {
+    for (name, property) in properties {
+        if let Some(description) = &property.description {
+            docs.set_member(path, type_name, name, description);
+        }
+    }
+}
+
+/// Record JSON Schema property defaults on the fields of a record
+fn property_defaults(
+    defaults: &mut FieldDefaults,
+    path: &[String],
+    type_name: &str,
+    properties: &std::collections::HashMap<String, types::JsonSchemaProperty>,
+) {
+    for (name, property) in properties {
+        if let Some(value) = property.default.as_ref().and_then(DefaultValue::from_json) {
+            defaults.set(path, type_name, name, value);
         }
     }
 }
@@ -452,6 +848,8 @@ impl TypeProvider for McpProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fusabi_provider_common::emit::golden;
+    use fusabi_provider_common::generate_with_layout;
 
     #[test]
     fn test_generate_tool_types() {
@@ -485,6 +883,403 @@ mod tests {
         let types = provider.generate_types(&schema, "Weather").unwrap();
 
         assert!(!types.modules.is_empty());
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/mcp.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_generate_documented() {
+        let provider = McpProvider::new();
+        let json = r#"{
+            "tools": [
+                {
+                    "name": "get_weather",
+                    "description": "Get current weather",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "location": {
+                                "type": "string",
+                                "description": "City name"
+                            },
+                            "units": {
+                                "type": "string",
+                                "enum": ["celsius", "fahrenheit"]
+                            }
+                        },
+                        "required": ["location"]
+                    }
+                }
+            ]
+        }"#;
+
+        let schema = provider
+            .resolve_schema(json, &ProviderParams::default())
+            .unwrap();
+        let (types, docs) = provider.generate_documented(&schema, "Weather").unwrap();
+
+        let tools = vec!["Weather".to_string(), "tools".to_string()];
+        assert_eq!(docs.type_doc(&tools, "GetWeatherInput"), Some("Get current weather"));
+        assert_eq!(docs.member_doc(&tools, "GetWeatherInput", "location"), Some("City name"));
+        assert_eq!(docs.member_doc(&tools, "GetWeatherInput", "units"), None);
+
+        golden::assert_fusabi_documented(
+            &types,
+            &docs,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/mcp-documented.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_property_defaults() {
+        let provider = McpProvider::new();
+        let json = r#"{
+            "tools": [
+                {
+                    "name": "search",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "query": { "type": "string" },
+                            "limit": { "type": "integer", "default": 10 },
+                            "sources": { "type": "array", "items": { "type": "string" }, "default": ["web"] }
+                        },
+                        "required": ["query"]
+                    }
+                }
+            ],
+            "definitions": {
+                "settings": {
+                    "type": "object",
+                    "properties": {
+                        "verbose": { "type": "boolean", "default": false }
+                    }
+                }
+            }
+        }"#;
+
+        let schema = provider
+            .resolve_schema(json, &ProviderParams::default())
+            .unwrap();
+        let (_, defaults) = provider.generate_with_defaults(&schema, "Search").unwrap();
+
+        let tools = vec!["Search".to_string(), "tools".to_string()];
+        let definitions = vec!["Search".to_string(), "definitions".to_string()];
+        assert_eq!(defaults.len(), 3);
+        assert_eq!(defaults.get(&tools, "SearchInput", "limit"), Some(&DefaultValue::Int(10)));
+        assert_eq!(defaults.get(&tools, "SearchInput", "query"), None);
+        assert_eq!(
+            defaults.get(&tools, "SearchInput", "sources"),
+            Some(&DefaultValue::List(vec![DefaultValue::String("web".to_string())]))
+        );
+        assert_eq!(
+            defaults.get(&definitions, "Settings", "verbose"),
+            Some(&DefaultValue::Bool(false))
+        );
+    }
+
+    #[test]
+    fn test_attributes() {
+        let provider = McpProvider::new();
+        let json = r#"{
+            "tools": [
+                { "name": "get_weather" },
+                { "name": "get_forecast" }
+            ],
+            "resources": [
+                { "uri": "file:///logs/{date}", "name": "logs", "mimeType": "text/plain" }
+            ]
+        }"#;
+
+        let schema = provider
+            .resolve_schema(json, &ProviderParams::default())
+            .unwrap();
+        let (_, attributes) = provider.generate_attributed(&schema, "Weather").unwrap();
+
+        let tools = vec!["Weather".to_string(), "tools".to_string()];
+        let resources = vec!["Weather".to_string(), "resources".to_string()];
+        assert_eq!(
+            attributes.type_attribute(&tools, "GetWeatherInput", "mcp.tool"),
+            Some("get_weather")
+        );
+        assert_eq!(
+            attributes.member_attribute(&tools, "ToolCall", "GetForecast", "mcp.tool"),
+            Some("get_forecast")
+        );
+        assert_eq!(
+            attributes.type_attribute(&resources, "LogsResource", "mcp.resource_uri"),
+            Some("file:///logs/{date}")
+        );
+        assert_eq!(
+            attributes.type_attribute(&resources, "LogsResource", "mcp.mime_type"),
+            Some("text/plain")
+        );
+    }
+
+    #[test]
+    fn test_tool_input_field_types() {
+        let provider = McpProvider::new();
+        let json = r#"{
+            "tools": [
+                {
+                    "name": "search",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "query": { "type": "string" },
+                            "mode": { "type": "string", "enum": ["fast", "exact"] },
+                            "limit": { "type": ["integer", "null"] },
+                            "tags": { "type": "array", "items": { "type": "string" } }
+                        },
+                        "required": ["query", "mode", "limit"]
+                    }
+                }
+            ]
+        }"#;
+
+        let schema = provider
+            .resolve_schema(json, &ProviderParams::default())
+            .unwrap();
+        let types = provider.generate_types(&schema, "Mcp").unwrap();
+
+        let FusabiTypeDef::Record(record) = &types.modules[0].types[0] else {
+            panic!("Expected record");
+        };
+        let fields: Vec<(&str, String)> = record
+            .fields
+            .iter()
+            .map(|(name, ty)| (name.as_str(), ty.to_string()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("limit", "int option".to_string()),
+                ("mode", "\"fast\" | \"exact\"".to_string()),
+                ("query", "string".to_string()),
+                ("tags", "string list option".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_tool_input() {
+        let json = r#"{
+            "tools": [
+                {
+                    "name": "get_weather",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "location": {
+                                "type": "object",
+                                "properties": {
+                                    "city": { "type": "string" },
+                                    "coords": {
+                                        "type": "object",
+                                        "properties": { "lat": { "type": "number" }, "lon": { "type": "number" } }
+                                    }
+                                },
+                                "required": ["city"]
+                            }
+                        },
+                        "required": ["location"]
+                    }
+                }
+            ]
+        }"#;
+
+        let records = |provider: McpProvider| {
+            let schema = provider
+                .resolve_schema(json, &ProviderParams::default())
+                .unwrap();
+            let types = provider.generate_types(&schema, "Weather").unwrap();
+            types.modules[0]
+                .types
+                .iter()
+                .map(|t| match t {
+                    FusabiTypeDef::Record(r) => {
+                        let fields: Vec<String> = r.fields.iter().map(|(n, t)| format!("{}: {}", n, t)).collect();
+                        format!("{} {{ {} }}", r.name, fields.join(", "))
+                    }
+                    FusabiTypeDef::Du(d) => d.name.clone(),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            records(McpProvider::new()),
+            [
+                "GetWeatherInputLocationCoords { lat: float option, lon: float option }",
+                "GetWeatherInputLocation { city: string, coords: GetWeatherInputLocationCoords option }",
+                "GetWeatherInput { location: GetWeatherInputLocation }",
+            ]
+        );
+        assert_eq!(
+            records(McpProvider::new().with_inline_depth(1)),
+            [
+                "GetWeatherInputLocation { city: string, coords: Map<string, any> option }",
+                "GetWeatherInput { location: GetWeatherInputLocation }",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tool_output_schema() {
+        let provider = McpProvider::new();
+        let json = r#"{
+            "tools": [
+                {
+                    "name": "get_weather",
+                    "outputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "temperature": { "type": "number" },
+                            "conditions": { "type": "string" }
+                        },
+                        "required": ["temperature"]
+                    }
+                },
+                {
+                    "name": "get_forecast",
+                    "outputSchema": {
+                        "type": "object",
+                        "properties": { "days": { "type": "array", "items": { "type": "string" } } }
+                    }
+                },
+                { "name": "ping" }
+            ]
+        }"#;
+
+        let schema = provider
+            .resolve_schema(json, &ProviderParams::default())
+            .unwrap();
+        let (types, attributes) = provider.generate_attributed(&schema, "Weather").unwrap();
+
+        let module = &types.modules[0];
+        let names: Vec<&str> = module
+            .types
+            .iter()
+            .map(|t| match t {
+                FusabiTypeDef::Record(r) => r.name.as_str(),
+                FusabiTypeDef::Du(d) => d.name.as_str(),
+            })
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "GetWeatherInput",
+                "GetWeatherOutput",
+                "GetForecastInput",
+                "GetForecastOutput",
+                "PingInput",
+                "ToolCall",
+                "ToolResult",
+            ]
+        );
+
+        let FusabiTypeDef::Record(output) = &module.types[1] else {
+            panic!("Expected record");
+        };
+        let fields: Vec<String> = output.fields.iter().map(|(n, t)| format!("{}: {}", n, t)).collect();
+        assert_eq!(fields, ["conditions: string option", "temperature: float"]);
+
+        let FusabiTypeDef::Du(result) = &module.types[6] else {
+            panic!("Expected union");
+        };
+        let cases: Vec<String> = result
+            .variants
+            .iter()
+            .map(|v| format!("{} of {}", v.name, v.fields[0]))
+            .collect();
+        assert_eq!(cases, ["GetWeather of GetWeatherOutput", "GetForecast of GetForecastOutput"]);
+
+        let tools = vec!["Weather".to_string(), "tools".to_string()];
+        assert_eq!(
+            attributes.member_attribute(&tools, "ToolResult", "GetForecast", "mcp.tool"),
+            Some("get_forecast")
+        );
+    }
+
+    #[test]
+    fn test_single_tool_result() {
+        let provider = McpProvider::new();
+        let json = r#"{
+            "tools": [
+                {
+                    "name": "get_weather",
+                    "outputSchema": {
+                        "type": "object",
+                        "properties": { "temperature": { "type": "number" } }
+                    }
+                }
+            ]
+        }"#;
+
+        let schema = provider
+            .resolve_schema(json, &ProviderParams::default())
+            .unwrap();
+        let (types, attributes) = provider.generate_attributed(&schema, "Weather").unwrap();
+
+        let module = &types.modules[0];
+        let names: Vec<&str> = module
+            .types
+            .iter()
+            .map(|t| match t {
+                FusabiTypeDef::Record(r) => r.name.as_str(),
+                FusabiTypeDef::Du(d) => d.name.as_str(),
+            })
+            .collect();
+        assert_eq!(names, ["GetWeatherInput", "GetWeatherOutput", "ToolResult"]);
+
+        let FusabiTypeDef::Du(result) = &module.types[2] else {
+            panic!("Expected union");
+        };
+        assert_eq!(result.variants.len(), 1);
+        assert_eq!(result.variants[0].fields[0].to_string(), "GetWeatherOutput");
+
+        let tools = vec!["Weather".to_string(), "tools".to_string()];
+        assert_eq!(
+            attributes.member_attribute(&tools, "ToolResult", "GetWeather", "mcp.tool"),
+            Some("get_weather")
+        );
+    }
+
+    #[test]
+    fn test_nested_definition_properties() {
+        let provider = McpProvider::new();
+        let json = r#"{
+            "tools": [],
+            "definitions": {
+                "forecast": {
+                    "type": "object",
+                    "properties": {
+                        "location": {
+                            "type": "object",
+                            "properties": { "city": { "type": "string" } },
+                            "required": ["city"]
+                        }
+                    },
+                    "required": ["location"]
+                }
+            }
+        }"#;
+
+        let schema = provider
+            .resolve_schema(json, &ProviderParams::default())
+            .unwrap();
+        let types = provider.generate_types(&schema, "Mcp").unwrap();
+
+        let definitions = &types.modules[0];
+        assert_eq!(definitions.path, vec!["Mcp".to_string(), "definitions".to_string()]);
+        let [FusabiTypeDef::Record(location), FusabiTypeDef::Record(forecast)] = definitions.types.as_slice() else {
+            panic!("Expected two records");
+        };
+        assert_eq!(location.name, "ForecastLocation");
+        assert_eq!(location.fields[0].1.to_string(), "string");
+        assert_eq!(forecast.fields[0].1.to_string(), "ForecastLocation");
     }
 
     #[test]
@@ -542,6 +1337,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "protocol")]
     fn test_embedded_mode() {
         let provider = McpProvider::new();
         let params = ProviderParams::default();
@@ -549,6 +1345,272 @@ mod tests {
         let schema = provider.resolve_schema("", &params).unwrap();
         let types = provider.generate_types(&schema, "Mcp").unwrap();
 
-        assert!(!types.modules.is_empty());
+        assert_eq!(types.modules.len(), 1);
+        let module = &types.modules[0];
+        assert_eq!(module.path, vec!["Mcp".to_string(), "protocol".to_string()]);
+        let call = module
+            .types
+            .iter()
+            .find_map(|t| match t {
+                FusabiTypeDef::Record(r) if r.name == "CallToolRequest" => Some(r),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(call.fields[2].1.to_string(), "\"tools/call\"");
+        assert_eq!(call.fields[3].1.to_string(), "CallToolParams");
+        assert!(module
+            .types
+            .iter()
+            .all(|t| !matches!(t, FusabiTypeDef::Record(r) if r.name.starts_with("__"))));
+    }
+
+    #[test]
+    #[cfg(feature = "protocol")]
+    fn test_mode_param() {
+        let provider = McpProvider::new();
+        let params = ProviderParams::default().with("mode", "embedded");
+        let schema = provider.resolve_schema("tools.json", &params).unwrap();
+        assert!(matches!(schema, Schema::Custom(marker) if marker == "embedded"));
+
+        let params = ProviderParams::default().with("mode", "embeded");
+        let err = provider.resolve_schema("", &params).unwrap_err();
+        assert!(err.to_string().contains("expected one of file, embedded, live"));
+        let params = ProviderParams::default().with("mdoe", "embedded");
+        assert!(provider.resolve_schema("", &params).is_err());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_stdio_introspection() {
+        let dir = std::env::temp_dir().join(format!("fusabi-mcp-stdio-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let log = dir.join("requests.log");
+        let replies = [
+            r#"{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2025-06-18","capabilities":{"tools":{},"prompts":{}},"serverInfo":{"name":"weather","version":"1.0.0"}}}"#,
+            "",
+            r#"{"jsonrpc":"2.0","id":"s1","method":"roots/list"}"#,
+            r#"{"jsonrpc":"2.0","method":"notifications/message","params":{"level":"info","data":"ready"}}
+{"jsonrpc":"2.0","id":2,"result":{"tools":[{"name":"get_weather","inputSchema":{"type":"object","properties":{"location":{"type":"string"}},"required":["location"]}}],"nextCursor":"page2"}}"#,
+            r#"{"jsonrpc":"2.0","id":3,"result":{"tools":[{"name":"search","inputSchema":{"type":"object","properties":{"query":{"type":"string"}}}}]}}"#,
+            r#"{"jsonrpc":"2.0","id":4,"result":{"prompts":[{"name":"summarize","arguments":[{"name":"text","required":true}]}]}}"#,
+        ];
+        let script: String = replies
+            .iter()
+            .map(|reply| {
+                let mut step = format!("read -r line; printf '%s\\n' \"$line\" >> '{}'\n", log.display());
+                for message in reply.lines() {
+                    step.push_str(&format!("printf '%s\\n' '{}'\n", message));
+                }
+                step
+            })
+            .collect::<String>()
+            + "read -r line\n";
+        let server = dir.join("server.sh");
+        std::fs::write(&server, script).unwrap();
+
+        let provider = McpProvider::new();
+        let source = format!("stdio:sh {}", server.display());
+        let schema = provider.resolve_schema(&source, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Weather").unwrap();
+        let names: Vec<String> = types
+            .modules
+            .iter()
+            .flat_map(|m| {
+                m.types.iter().map(move |t| match t {
+                    FusabiTypeDef::Record(r) => format!("{}.{}", m.path.join("."), r.name),
+                    FusabiTypeDef::Du(d) => format!("{}.{}", m.path.join("."), d.name),
+                })
+            })
+            .collect();
+        assert!(names.contains(&"Weather.tools.GetWeatherInput".to_string()));
+        assert!(names.contains(&"Weather.tools.SearchInput".to_string()));
+        assert!(names.iter().any(|name| name.starts_with("Weather.prompts.")));
+        assert!(names.iter().all(|name| !name.starts_with("Weather.resources.")));
+
+        let requests: Vec<serde_json::Value> = std::fs::read_to_string(&log)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        std::fs::remove_dir_all(&dir).ok();
+        let methods: Vec<&str> = requests.iter().map(|r| r["method"].as_str().unwrap_or("-")).collect();
+        assert_eq!(
+            methods,
+            ["initialize", "notifications/initialized", "tools/list", "-", "tools/list", "prompts/list"]
+        );
+        assert_eq!(requests[3]["id"], "s1");
+        assert_eq!(requests[3]["error"]["code"], -32601);
+        assert_eq!(requests[4]["params"]["cursor"], "page2");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_stdio_failures() {
+        let provider = McpProvider::new();
+        let params = ProviderParams::default();
+        let err = provider.resolve_schema("stdio:", &params).unwrap_err();
+        assert!(matches!(err, ProviderError::InvalidSource(_)));
+        assert!(provider.resolve_schema("stdio:/nonexistent/mcp-server", &params).is_err());
+
+        let params = ProviderParams::default().with("fetch_timeout", "200ms");
+        let err = provider.resolve_schema("stdio:sleep 5", &params).unwrap_err();
+        assert!(err.to_string().contains("fetch_timeout"));
+    }
+
+    #[test]
+    fn test_http_introspection() {
+        use std::io::{BufRead, BufReader, Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let responses = [
+                (
+                    "200 OK\r\nContent-Type: application/json\r\nMcp-Session-Id: session-1",
+                    r#"{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2025-03-26","capabilities":{"tools":{}}}}"#,
+                ),
+                ("202 Accepted", ""),
+                (
+                    "200 OK\r\nContent-Type: text/event-stream",
+                    "data: {\"jsonrpc\":\"2.0\",\"id\":\"s1\",\"method\":\"ping\"}\n\n\
+                     : keep-alive\n\n\
+                     event: message\ndata: {\"jsonrpc\":\"2.0\",\"id\":2,\n\
+                     data: \"result\":{\"tools\":[{\"name\":\"get_weather\"}]}}\n\n",
+                ),
+                ("202 Accepted", ""),
+                ("200 OK", ""),
+            ];
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut head = String::new();
+                while !head.ends_with("\r\n\r\n") {
+                    reader.read_line(&mut head).unwrap();
+                }
+                let length = head
+                    .lines()
+                    .find_map(|line| line.to_ascii_lowercase().strip_prefix("content-length: ")?.parse().ok())
+                    .unwrap_or(0);
+                let mut payload = vec![0u8; length];
+                reader.read_exact(&mut payload).unwrap();
+                requests.push(head.to_ascii_lowercase() + &String::from_utf8(payload).unwrap());
+
+                let response = format!(
+                    "HTTP/1.1 {}\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+
+        std::env::set_var("FUSABI_MCP_TEST_TOKEN", "t0ken");
+        let provider = McpProvider::new();
+        let params = ProviderParams::default()
+            .with("mode", "live")
+            .with("auth", "env:FUSABI_MCP_TEST_TOKEN");
+        let schema = provider.resolve_schema(&format!("http://{}/mcp", addr), &params).unwrap();
+        let types = provider.generate_types(&schema, "Weather").unwrap();
+        assert_eq!(types.modules.len(), 1);
+        assert_eq!(types.modules[0].path, ["Weather", "tools"]);
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("post /mcp "));
+        assert!(requests[0].contains("authorization: bearer t0ken"));
+        assert!(requests[0].contains("accept: application/json, text/event-stream"));
+        assert!(!requests[0].contains("mcp-session-id"));
+        assert!(requests[1].contains("mcp-session-id: session-1"));
+        assert!(requests[1].contains("mcp-protocol-version: 2025-03-26"));
+        assert!(requests[1].contains("notifications/initialized"));
+        assert!(requests[2].contains("tools/list"));
+        assert!(requests[3].contains(r#""id":"s1""#) && requests[3].contains("-32601"));
+        assert!(requests[4].starts_with("delete /mcp "));
+        assert!(requests[4].contains("mcp-session-id: session-1"));
+
+        let err = provider.resolve_schema("tools.json", &params).unwrap_err();
+        assert!(err.to_string().contains("mode=live"));
+    }
+
+    #[test]
+    fn test_http_unauthorized() {
+        use fusabi_provider_common::ErrorKind;
+        use std::io::{BufRead, BufReader, Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut head = String::new();
+            while !head.ends_with("\r\n\r\n") {
+                reader.read_line(&mut head).unwrap();
+            }
+            let length = head
+                .lines()
+                .find_map(|line| line.to_ascii_lowercase().strip_prefix("content-length: ")?.parse().ok())
+                .unwrap_or(0);
+            reader.read_exact(&mut vec![0u8; length]).unwrap();
+            let response = "HTTP/1.1 401 Unauthorized\r\nConnection: close\r\nContent-Length: 0\r\n\r\n";
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+        });
+
+        let provider = McpProvider::new();
+        let params = ProviderParams::default().with("mode", "live");
+        let err = provider.resolve_schema(&format!("http://{}/mcp", addr), &params).unwrap_err();
+        server.join().unwrap();
+        assert_eq!(ErrorKind::of(&err), ErrorKind::Validation);
+        assert!(err.to_string().contains("reference a token with the `auth` param"));
+    }
+
+    #[test]
+    fn test_module_layout() {
+        let provider = McpProvider::new();
+        let json = r#"{
+            "tools": [{ "name": "search" }],
+            "prompts": [{ "name": "summarize" }]
+        }"#;
+
+        let paths = |layout: &str| {
+            let params = ProviderParams::default().with("module_layout", layout);
+            generate_with_layout(&provider, json, &params, "Mcp")
+                .unwrap()
+                .modules
+                .iter()
+                .map(|m| m.path.join("."))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(paths("nested"), ["Mcp.tools", "Mcp.prompts"]);
+        assert_eq!(paths("flat"), ["Mcp"]);
+    }
+
+    #[test]
+    fn test_generate_decoders() {
+        let provider = McpProvider::new();
+        let json = r#"{
+            "tools": [
+                {
+                    "name": "search",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": { "maxResults": { "type": "integer" } }
+                    }
+                }
+            ]
+        }"#;
+
+        let schema = provider.resolve_schema(json, &ProviderParams::default()).unwrap();
+        let (types, plan) = provider.generate_decoders(&schema, "Search").unwrap();
+        let tools = vec!["Search".to_string(), "tools".to_string()];
+        assert_eq!(plan.format(), WireFormat::Json);
+        assert!(plan.decoder(&tools, "SearchInput").is_some());
+
+        let source = fusabi_provider_common::emit_rust_decoders(&types, &plan, &Default::default());
+        assert!(source.contains("impl SearchInput {\n            /// Decode a JSON document"));
+
+        let schema = provider.resolve_schema("", &ProviderParams::default()).unwrap();
+        assert!(provider.generate_decoders(&schema, "Mcp").unwrap().1.is_empty());
     }
 }
@@ -1,58 +1,95 @@
 //! MCP schema parser
 //!
 //! Parses MCP server manifests and configurations into structured types.
+//!
+//! Errors in a JSON text report the line and column of the tool, resource,
+//! prompt or definition they are about, or of the syntax error.
+//!
+//! [`check_limits`] rejects manifests nested deeper than `max_depth` or
+//! declaring more than `max_types` tools, resources, prompts and
+//! definitions.
 
 use crate::types::{
     JsonSchemaObject, JsonSchemaProperty, McpSchema, PromptArgument, PromptDefinition,
     ResourceDefinition, ToolDefinition, TypeDefinition, TypeKind,
 };
+use fusabi_provider_common::error::Context;
+use fusabi_provider_common::limits::InputLimits;
+use fusabi_provider_common::span::{parse_error_at, SourceSpan};
 use fusabi_type_providers::{ProviderError, ProviderResult};
 use std::collections::HashMap;
 
+/// Step from a JSON value to one of its members
+#[derive(Debug, Clone, PartialEq)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// Result whose error carries the path of the item it is about
+type LocatedResult<T> = Result<T, (Vec<PathSegment>, ProviderError)>;
+
 /// Parse an MCP schema from a JSON string
 pub fn parse_mcp_schema(json: &str) -> ProviderResult<McpSchema> {
-    let value: serde_json::Value = serde_json::from_str(json)
-        .map_err(|e| ProviderError::ParseError(format!("Invalid JSON: {}", e)))?;
+    let value: serde_json::Value = serde_json::from_str(json).map_err(|e| {
+        // The position is reported in the span instead
+        let message = e.to_string();
+        let message = message
+            .rsplit_once(" at line ")
+            .map_or(message.as_str(), |(m, _)| m);
+        SourceSpan::at_line_column(json, e.line(), e.column())
+            .error(format_args!("Invalid JSON: {}", message))
+    })?;
 
-    parse_schema_value(&value)
+    parse_schema_items(&value).map_err(|(path, error)| match (value_offset(json, &path), error) {
+        (Some(offset), ProviderError::ParseError(message)) => parse_error_at(json, offset, message),
+        (_, error) => error,
+    })
 }
 
 /// Parse an MCP schema from a serde_json::Value
 pub fn parse_schema_value(value: &serde_json::Value) -> ProviderResult<McpSchema> {
-    let obj = value
-        .as_object()
-        .ok_or_else(|| ProviderError::ParseError("MCP schema must be an object".to_string()))?;
+    parse_schema_items(value).map_err(|(_, error)| error)
+}
 
-    let mut schema = McpSchema::default();
+/// Check a parsed MCP manifest against input limits
+pub fn check_limits(value: &serde_json::Value, limits: &InputLimits) -> ProviderResult<()> {
+    limits.check_json_depth(value)?;
+    let count = |key: &str| match value.get(key) {
+        Some(serde_json::Value::Array(items)) => items.len(),
+        Some(serde_json::Value::Object(items)) => items.len(),
+        _ => 0,
+    };
+    limits.check_type_count(
+        count("tools") + count("resources") + count("prompts") + count("definitions"),
+    )
+}
 
-    // Parse tools
-    if let Some(tools) = obj.get("tools").and_then(|v| v.as_array()) {
-        schema.tools = tools
-            .iter()
-            .map(parse_tool_definition)
-            .collect::<ProviderResult<_>>()?;
-    }
+fn parse_schema_items(value: &serde_json::Value) -> LocatedResult<McpSchema> {
+    let obj = value.as_object().ok_or_else(|| {
+        let error = ProviderError::ParseError("MCP schema must be an object".to_string());
+        (Vec::new(), error)
+    })?;
 
-    // Parse resources
-    if let Some(resources) = obj.get("resources").and_then(|v| v.as_array()) {
-        schema.resources = resources
-            .iter()
-            .map(parse_resource_definition)
-            .collect::<ProviderResult<_>>()?;
-    }
+    let mut schema = McpSchema::default();
 
-    // Parse prompts
-    if let Some(prompts) = obj.get("prompts").and_then(|v| v.as_array()) {
-        schema.prompts = prompts
-            .iter()
-            .map(parse_prompt_definition)
-            .collect::<ProviderResult<_>>()?;
-    }
+    // Parse tools, resources and prompts
+    schema.tools = parse_items(obj, "tools", parse_tool_definition)?;
+    schema.resources = parse_items(obj, "resources", parse_resource_definition)?;
+    schema.prompts = parse_items(obj, "prompts", parse_prompt_definition)?;
 
     // Parse definitions/types
     if let Some(defs) = obj.get("definitions").and_then(|v| v.as_object()) {
         for (name, def_value) in defs {
-            let type_def = parse_type_definition(name, def_value)?;
+            let type_def = parse_type_definition(name, def_value)
+                .with_context(|| format!("while parsing definition {}", name))
+                .map_err(|e| {
+                    let path = vec![
+                        PathSegment::Key("definitions".to_string()),
+                        PathSegment::Key(name.clone()),
+                    ];
+                    (path, e)
+                })?;
             schema.definitions.insert(name.clone(), type_def);
         }
     }
@@ -60,6 +97,132 @@ pub fn parse_schema_value(value: &serde_json::Value) -> ProviderResult<McpSchema
     Ok(schema)
 }
 
+/// Parse each element of the array under `key`, if there is one
+fn parse_items<T>(
+    obj: &serde_json::Map<String, serde_json::Value>,
+    key: &str,
+    parse: fn(&serde_json::Value) -> ProviderResult<T>,
+) -> LocatedResult<Vec<T>> {
+    let Some(items) = obj.get(key).and_then(|v| v.as_array()) else {
+        return Ok(Vec::new());
+    };
+
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            parse(item)
+                .with_context(|| format!("while parsing {}[{}]", key, i))
+                .map_err(|e| {
+                    (
+                        vec![PathSegment::Key(key.to_string()), PathSegment::Index(i)],
+                        e,
+                    )
+                })
+        })
+        .collect()
+}
+
+/// Byte offset of the value at `path` in a JSON text
+fn value_offset(json: &str, path: &[PathSegment]) -> Option<usize> {
+    let bytes = json.as_bytes();
+    let mut pos = skip_whitespace(bytes, 0);
+
+    for segment in path {
+        let close = match (bytes.get(pos)?, segment) {
+            (b'{', PathSegment::Key(_)) => b'}',
+            (b'[', PathSegment::Index(_)) => b']',
+            _ => return None,
+        };
+        pos += 1;
+
+        let mut index = 0;
+        loop {
+            pos = skip_whitespace(bytes, pos);
+            if *bytes.get(pos)? == close {
+                return None;
+            }
+            let found = match segment {
+                PathSegment::Key(key) => {
+                    let end = skip_value(bytes, pos)?;
+                    let name: String = serde_json::from_str(&json[pos..end]).ok()?;
+                    pos = skip_whitespace(bytes, end);
+                    if bytes.get(pos) != Some(&b':') {
+                        return None;
+                    }
+                    pos = skip_whitespace(bytes, pos + 1);
+                    name == *key
+                }
+                PathSegment::Index(i) => index == *i,
+            };
+            if found {
+                break;
+            }
+
+            index += 1;
+            pos = skip_whitespace(bytes, skip_value(bytes, pos)?);
+            if bytes.get(pos) == Some(&b',') {
+                pos += 1;
+            }
+        }
+    }
+
+    Some(pos)
+}
+
+/// Offset just past the JSON value starting at `pos`
+fn skip_value(bytes: &[u8], pos: usize) -> Option<usize> {
+    match bytes.get(pos)? {
+        b'"' => {
+            let mut i = pos + 1;
+            loop {
+                match bytes.get(i)? {
+                    b'\\' => i += 2,
+                    b'"' => return Some(i + 1),
+                    _ => i += 1,
+                }
+            }
+        }
+        b'{' | b'[' => {
+            let mut depth = 0;
+            let mut i = pos;
+            while i < bytes.len() {
+                match bytes[i] {
+                    b'"' => {
+                        i = skip_value(bytes, i)?;
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(i + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+            None
+        }
+        _ => {
+            let len = bytes[pos..]
+                .iter()
+                .position(|b| matches!(b, b',' | b'}' | b']') || b.is_ascii_whitespace())
+                .unwrap_or(bytes.len() - pos);
+            Some(pos + len)
+        }
+    }
+}
+
+fn skip_whitespace(bytes: &[u8], pos: usize) -> usize {
+    pos + bytes
+        .iter()
+        .skip(pos)
+        .take_while(|b| b.is_ascii_whitespace())
+        .count()
+}
+
 /// Parse a tool definition
 fn parse_tool_definition(value: &serde_json::Value) -> ProviderResult<ToolDefinition> {
     let obj = value.as_object().ok_or_else(|| {
@@ -82,10 +245,16 @@ fn parse_tool_definition(value: &serde_json::Value) -> ProviderResult<ToolDefini
         .map(parse_json_schema_object)
         .transpose()?;
 
+    let output_schema = obj
+        .get("outputSchema")
+        .map(parse_json_schema_object)
+        .transpose()?;
+
     Ok(ToolDefinition {
         name,
         description,
         input_schema,
+        output_schema,
     })
 }
 
@@ -188,9 +357,9 @@ fn parse_prompt_argument(value: &serde_json::Value) -> ProviderResult<PromptArgu
 
 /// Parse a JSON Schema object (for tool input schemas)
 fn parse_json_schema_object(value: &serde_json::Value) -> ProviderResult<JsonSchemaObject> {
-    let obj = value.as_object().ok_or_else(|| {
-        ProviderError::ParseError("JSON Schema must be an object".to_string())
-    })?;
+    let obj = value
+        .as_object()
+        .ok_or_else(|| ProviderError::ParseError("JSON Schema must be an object".to_string()))?;
 
     let schema_type = obj.get("type").and_then(|v| v.as_str()).map(String::from);
 
@@ -221,6 +390,7 @@ fn parse_json_schema_object(value: &serde_json::Value) -> ProviderResult<JsonSch
         properties,
         required,
         additional_properties,
+        schema: value.clone(),
     })
 }
 
@@ -270,14 +440,12 @@ fn parse_json_schema_property(value: &serde_json::Value) -> ProviderResult<JsonS
         items,
         properties,
         default,
+        schema: value.clone(),
     })
 }
 
 /// Parse a type definition
-fn parse_type_definition(
-    name: &str,
-    value: &serde_json::Value,
-) -> ProviderResult<TypeDefinition> {
+fn parse_type_definition(name: &str, value: &serde_json::Value) -> ProviderResult<TypeDefinition> {
     let obj = value.as_object().ok_or_else(|| {
         ProviderError::ParseError("Type definition must be an object".to_string())
     })?;
@@ -358,6 +526,7 @@ fn parse_type_definition(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fusabi_provider_common::span::span_of;
 
     #[test]
     fn test_parse_tool_definition() {
@@ -382,6 +551,7 @@ mod tests {
         assert_eq!(tool.name, "get_weather");
         assert!(tool.description.is_some());
         assert!(tool.input_schema.is_some());
+        assert!(tool.output_schema.is_none());
     }
 
     #[test]
@@ -423,4 +593,48 @@ mod tests {
         assert_eq!(prompt.arguments[0].name, "text");
         assert!(prompt.arguments[0].required);
     }
+
+    #[test]
+    fn test_error_positions() {
+        let json =
+            "{\n  \"tools\": [\n    {\"name\": \"a\"},\n    {\"description\": \"no name\"}\n  ]\n}";
+        let error = parse_mcp_schema(json).unwrap_err();
+        let span = span_of(&error).unwrap();
+        assert_eq!((span.line, span.column), (4, 5));
+        assert!(error
+            .to_string()
+            .contains("while parsing tools[1]: Tool must have a name"));
+
+        let json = r#"{"tools": [], "definitions": {"A": {"type": "object"}, "B\u0042": 5}}"#;
+        let span = span_of(&parse_mcp_schema(json).unwrap_err()).unwrap();
+        assert_eq!(span.column, json.find('5').unwrap() + 1);
+
+        let span = span_of(&parse_mcp_schema("{\n  \"tools\": [,]\n}").unwrap_err()).unwrap();
+        assert_eq!(span.line, 2);
+        assert!(parse_schema_value(&serde_json::json!({ "tools": [5] })).is_err());
+    }
+
+    #[test]
+    fn test_check_limits() {
+        let limits = InputLimits {
+            max_depth: 3,
+            max_types: 2,
+            ..InputLimits::default()
+        };
+        let value = serde_json::json!({ "tools": [{ "name": "a" }], "definitions": { "B": {} } });
+        assert!(check_limits(&value, &limits).is_ok());
+
+        let value =
+            serde_json::json!({ "tools": [{ "name": "a", "inputSchema": { "type": "object" } }] });
+        assert!(check_limits(&value, &limits)
+            .unwrap_err()
+            .to_string()
+            .contains("max_depth (3)"));
+
+        let value = serde_json::json!({ "tools": [{ "name": "a" }], "prompts": [{ "name": "b" }, { "name": "c" }] });
+        assert!(check_limits(&value, &limits)
+            .unwrap_err()
+            .to_string()
+            .contains("max_types (2)"));
+    }
 }
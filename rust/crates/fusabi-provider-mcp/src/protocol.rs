@@ -0,0 +1,187 @@
+//! Built-in MCP protocol types
+//!
+//! [`EMBEDDED_MCP_TYPES`] is written in Fusabi type syntax. It is parsed
+//! here into definitions:
+//!
+//! - `type Name = { field: T, ... }` becomes a record
+//! - `type Name = | A | B`, with one member per line, becomes a union with
+//!   one case per member, named after and carrying the member type
+//! - any other `type Name = T` is an alias, such as `RequestId`, and is
+//!   replaced by `T` wherever it is referenced, since generated types have
+//!   no alias form
+
+use crate::types::EMBEDDED_MCP_TYPES;
+use fusabi_provider_common::TypeShape;
+use fusabi_type_providers::{
+    DuDef, ProviderError, ProviderResult, RecordDef, TypeDefinition, VariantDef,
+};
+use std::collections::HashMap;
+
+/// Definitions of the built-in protocol types, in catalog order
+pub(crate) fn protocol_types() -> ProviderResult<Vec<TypeDefinition>> {
+    parse_catalog(EMBEDDED_MCP_TYPES)
+}
+
+/// Definition body before aliases are resolved
+enum Body {
+    Record(Vec<(String, TypeShape)>),
+    Union(Vec<String>),
+    Alias(TypeShape),
+}
+
+fn parse_catalog(catalog: &str) -> ProviderResult<Vec<TypeDefinition>> {
+    let mut lines = catalog
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with("//"))
+        .peekable();
+
+    let mut bodies = Vec::new();
+    while let Some((number, line)) = lines.next() {
+        let (name, rest) = line
+            .strip_prefix("type ")
+            .and_then(|definition| definition.split_once('='))
+            .map(|(name, rest)| (name.trim().to_string(), rest.trim()))
+            .ok_or_else(|| catalog_error(number, "expected `type Name = ...`"))?;
+
+        let body = match rest {
+            "{" => {
+                let mut fields = Vec::new();
+                loop {
+                    let (number, line) = lines
+                        .next()
+                        .ok_or_else(|| catalog_error(number, "unterminated record"))?;
+                    if line == "}" {
+                        break;
+                    }
+                    let (field, ty) = line
+                        .trim_end_matches(',')
+                        .split_once(':')
+                        .ok_or_else(|| catalog_error(number, "expected `field: type`"))?;
+                    fields.push((field.trim().to_string(), TypeShape::parse(ty)));
+                }
+                Body::Record(fields)
+            }
+            "" => {
+                let mut members = Vec::new();
+                while let Some((_, member)) = lines.next_if(|(_, line)| line.starts_with('|')) {
+                    members.push(member.trim_start_matches('|').trim().to_string());
+                }
+                if members.is_empty() {
+                    return Err(catalog_error(number, "expected `| Member` lines"));
+                }
+                Body::Union(members)
+            }
+            alias => Body::Alias(TypeShape::parse(alias)),
+        };
+        bodies.push((name, body));
+    }
+
+    let aliases: HashMap<&str, &TypeShape> = bodies
+        .iter()
+        .filter_map(|(name, body)| match body {
+            Body::Alias(shape) => Some((name.as_str(), shape)),
+            _ => None,
+        })
+        .collect();
+
+    let definitions = bodies
+        .iter()
+        .filter_map(|(name, body)| match body {
+            Body::Record(fields) => Some(TypeDefinition::Record(RecordDef {
+                name: name.clone(),
+                fields: fields
+                    .iter()
+                    .map(|(field, shape)| (field.clone(), resolve_aliases(shape, &aliases).into()))
+                    .collect(),
+            })),
+            Body::Union(members) => Some(TypeDefinition::Du(DuDef {
+                name: name.clone(),
+                variants: members
+                    .iter()
+                    .map(|member| {
+                        let shape = resolve_aliases(&TypeShape::named(member.as_str()), &aliases);
+                        VariantDef::new(member.clone(), vec![shape.into()])
+                    })
+                    .collect(),
+            })),
+            Body::Alias(_) => None,
+        })
+        .collect();
+    Ok(definitions)
+}
+
+/// Replace references to aliases by their definitions
+fn resolve_aliases(shape: &TypeShape, aliases: &HashMap<&str, &TypeShape>) -> TypeShape {
+    let resolve = |shape: &TypeShape| Box::new(resolve_aliases(shape, aliases));
+    let resolve_all = |items: &[TypeShape]| -> Vec<TypeShape> {
+        items.iter().map(|i| resolve_aliases(i, aliases)).collect()
+    };
+    match shape {
+        TypeShape::Named(name) => match aliases.get(name.as_str()) {
+            Some(alias) => resolve_aliases(alias, aliases),
+            None => shape.clone(),
+        },
+        TypeShape::Option(inner) => TypeShape::Option(resolve(inner)),
+        TypeShape::List(inner) => TypeShape::List(resolve(inner)),
+        TypeShape::Map(key, value) => TypeShape::Map(resolve(key), resolve(value)),
+        TypeShape::Tuple(items) => TypeShape::Tuple(resolve_all(items)),
+        TypeShape::Union(items) => TypeShape::Union(resolve_all(items)),
+        TypeShape::Generic(name, args) => TypeShape::Generic(name.clone(), resolve_all(args)),
+        TypeShape::Literal(_) | TypeShape::Param(_) => shape.clone(),
+    }
+}
+
+fn catalog_error(line: usize, message: &str) -> ProviderError {
+    ProviderError::ParseError(format!("Embedded MCP types, line {}: {}", line, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find<'a>(types: &'a [TypeDefinition], name: &str) -> &'a TypeDefinition {
+        types
+            .iter()
+            .find(|t| match t {
+                TypeDefinition::Record(r) => r.name == name,
+                TypeDefinition::Du(d) => d.name == name,
+            })
+            .unwrap_or_else(|| panic!("{} not generated", name))
+    }
+
+    #[test]
+    fn test_protocol_types() {
+        let types = protocol_types().unwrap();
+
+        let TypeDefinition::Record(request) = find(&types, "JsonRpcRequest") else {
+            panic!("Expected record");
+        };
+        let fields: Vec<String> = request.fields.iter().map(|(n, t)| format!("{}: {}", n, t)).collect();
+        assert_eq!(
+            fields,
+            ["jsonrpc: \"2.0\"", "id: string | int", "method: string", "params: any option"]
+        );
+
+        let TypeDefinition::Du(content) = find(&types, "Content") else {
+            panic!("Expected union");
+        };
+        let cases: Vec<&str> = content.variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(cases, ["TextContent", "ImageContent", "ResourceContent"]);
+        assert_eq!(content.variants[0].fields[0].to_string(), "TextContent");
+
+        // Aliases have no definition of their own
+        assert!(types.iter().all(|t| !matches!(t, TypeDefinition::Record(r) if r.name == "RequestId")));
+        assert!(matches!(find(&types, "GetPromptParams"), TypeDefinition::Record(_)));
+    }
+
+    #[test]
+    fn test_catalog_errors() {
+        let err = parse_catalog("type A = {\n  a: int,\n").unwrap_err();
+        assert!(err.to_string().contains("line 1: unterminated record"));
+
+        let err = parse_catalog("// header\nrecord A").unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+}
@@ -27,6 +27,8 @@ pub struct ToolDefinition {
     pub description: Option<String>,
     /// Input schema (JSON Schema)
     pub input_schema: Option<JsonSchemaObject>,
+    /// Output schema (JSON Schema) of structured results
+    pub output_schema: Option<JsonSchemaObject>,
 }
 
 /// MCP resource definition
@@ -75,6 +77,8 @@ pub struct JsonSchemaObject {
     pub required: Vec<String>,
     /// Additional properties allowed
     pub additional_properties: bool,
+    /// The schema as written, for conversion
+    pub schema: serde_json::Value,
 }
 
 /// JSON Schema property
@@ -92,6 +96,8 @@ pub struct JsonSchemaProperty {
     pub properties: HashMap<String, JsonSchemaProperty>,
     /// Default value
     pub default: Option<serde_json::Value>,
+    /// The schema as written, for conversion
+    pub schema: serde_json::Value,
 }
 
 /// Custom type definition in MCP schema
@@ -143,6 +149,7 @@ pub enum MessageType {
 }
 
 /// Built-in MCP protocol types for embedded mode
+#[cfg(feature = "protocol")]
 pub const EMBEDDED_MCP_TYPES: &str = r#"
 // MCP Protocol Core Types
 
@@ -0,0 +1,310 @@
+//! Minimal BSON encoding
+//!
+//! Commands are encoded from JSON values, and replies are decoded to
+//! canonical Extended JSON so that sampled documents and documents loaded
+//! from exports go through the same inference. Extended JSON wrappers for
+//! binary data and 64-bit integers are encoded as their BSON types, which
+//! is all the commands the provider sends need.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde_json::{json, Map, Value};
+
+/// Encode a document whose keys keep the given order; commands must start
+/// with the command name
+pub fn encode_document<'a>(fields: impl IntoIterator<Item = (&'a str, &'a Value)>) -> Vec<u8> {
+    let mut buf = vec![0; 4];
+    for (key, value) in fields {
+        encode_element(&mut buf, key, value);
+    }
+    buf.push(0);
+    let len = buf.len() as i32;
+    buf[..4].copy_from_slice(&len.to_le_bytes());
+    buf
+}
+
+fn encode_element(buf: &mut Vec<u8>, key: &str, value: &Value) {
+    let start = buf.len();
+    buf.push(0);
+    buf.extend_from_slice(key.as_bytes());
+    buf.push(0);
+
+    let kind = match value {
+        Value::Null => 0x0A,
+        Value::Bool(b) => {
+            buf.push(u8::from(*b));
+            0x08
+        }
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => match i32::try_from(i) {
+                Ok(i) => {
+                    buf.extend_from_slice(&i.to_le_bytes());
+                    0x10
+                }
+                Err(_) => {
+                    buf.extend_from_slice(&i.to_le_bytes());
+                    0x12
+                }
+            },
+            None => {
+                buf.extend_from_slice(&n.as_f64().unwrap_or(0.0).to_le_bytes());
+                0x01
+            }
+        },
+        Value::String(s) => {
+            encode_string(buf, s);
+            0x02
+        }
+        Value::Array(items) => {
+            let keys: Vec<String> = (0..items.len()).map(|i| i.to_string()).collect();
+            buf.extend(encode_document(keys.iter().map(String::as_str).zip(items)));
+            0x04
+        }
+        Value::Object(object) => {
+            if let Some(long) = object.get("$numberLong").and_then(Value::as_str) {
+                buf.extend_from_slice(&long.parse::<i64>().unwrap_or(0).to_le_bytes());
+                0x12
+            } else if let Some(binary) = object.get("$binary") {
+                let data = binary
+                    .get("base64")
+                    .and_then(Value::as_str)
+                    .and_then(|b| STANDARD.decode(b).ok())
+                    .unwrap_or_default();
+                buf.extend_from_slice(&(data.len() as i32).to_le_bytes());
+                buf.push(0);
+                buf.extend(data);
+                0x05
+            } else {
+                buf.extend(encode_document(object.iter().map(|(k, v)| (k.as_str(), v))));
+                0x03
+            }
+        }
+    };
+    buf[start] = kind;
+}
+
+fn encode_string(buf: &mut Vec<u8>, s: &str) {
+    buf.extend_from_slice(&(s.len() as i32 + 1).to_le_bytes());
+    buf.extend_from_slice(s.as_bytes());
+    buf.push(0);
+}
+
+/// Extended JSON for binary data
+pub fn binary(data: &[u8]) -> Value {
+    json!({ "$binary": { "base64": STANDARD.encode(data), "subType": "00" } })
+}
+
+/// Bytes of an Extended JSON binary value
+pub fn binary_bytes(value: &Value) -> Option<Vec<u8>> {
+    let encoded = value.get("$binary")?.get("base64")?.as_str()?;
+    STANDARD.decode(encoded).ok()
+}
+
+/// Decode a document to canonical Extended JSON, returning it with the
+/// number of bytes it used
+pub fn decode_document(bytes: &[u8]) -> ProviderResult<(Value, usize)> {
+    let mut reader = Reader { bytes, pos: 0 };
+    let document = reader.document()?;
+    Ok((Value::Object(document), reader.pos))
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> ProviderResult<&'a [u8]> {
+        let end = self.pos.checked_add(n).filter(|end| *end <= self.bytes.len()).ok_or_else(truncated)?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> ProviderResult<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn i32(&mut self) -> ProviderResult<i32> {
+        Ok(i32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> ProviderResult<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn i64(&mut self) -> ProviderResult<i64> {
+        Ok(i64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn cstring(&mut self) -> ProviderResult<String> {
+        let len = self.bytes[self.pos..].iter().position(|b| *b == 0).ok_or_else(truncated)?;
+        let s = String::from_utf8_lossy(self.take(len)?).into_owned();
+        self.pos += 1;
+        Ok(s)
+    }
+
+    fn string(&mut self) -> ProviderResult<String> {
+        let len = usize::try_from(self.i32()?).map_err(|_| malformed("negative string length"))?;
+        let bytes = self.take(len)?;
+        Ok(String::from_utf8_lossy(bytes.strip_suffix(&[0]).unwrap_or(bytes)).into_owned())
+    }
+
+    fn document(&mut self) -> ProviderResult<Map<String, Value>> {
+        Ok(self.elements()?.into_iter().collect())
+    }
+
+    /// Elements of a document in encoded order, which arrays rely on
+    fn elements(&mut self) -> ProviderResult<Vec<(String, Value)>> {
+        let start = self.pos;
+        let len = usize::try_from(self.i32()?)
+            .ok()
+            .filter(|len| *len >= 5)
+            .ok_or_else(|| malformed("invalid document length"))?;
+        let end = start.checked_add(len).filter(|end| *end <= self.bytes.len()).ok_or_else(truncated)?;
+
+        let mut elements = Vec::new();
+        while self.pos < end - 1 {
+            let kind = self.u8()?;
+            let key = self.cstring()?;
+            elements.push((key, self.value(kind)?));
+        }
+        self.pos = end;
+        Ok(elements)
+    }
+
+    fn value(&mut self, kind: u8) -> ProviderResult<Value> {
+        Ok(match kind {
+            0x01 => {
+                let f = f64::from_le_bytes(self.take(8)?.try_into().unwrap());
+                if f.is_finite() {
+                    json!(f)
+                } else {
+                    json!({ "$numberDouble": f.to_string() })
+                }
+            }
+            0x02 => Value::String(self.string()?),
+            0x03 => Value::Object(self.document()?),
+            0x04 => Value::Array(self.elements()?.into_iter().map(|(_, v)| v).collect()),
+            0x05 => {
+                let len = usize::try_from(self.i32()?).map_err(|_| malformed("negative binary length"))?;
+                let subtype = self.u8()?;
+                let data = self.take(len)?;
+                json!({ "$binary": { "base64": STANDARD.encode(data), "subType": format!("{:02x}", subtype) } })
+            }
+            0x06 => json!({ "$undefined": true }),
+            0x07 => json!({ "$oid": hex(self.take(12)?) }),
+            0x08 => Value::Bool(self.u8()? != 0),
+            0x09 => json!({ "$date": { "$numberLong": self.i64()?.to_string() } }),
+            0x0A => Value::Null,
+            0x0B => {
+                let pattern = self.cstring()?;
+                let options = self.cstring()?;
+                json!({ "$regularExpression": { "pattern": pattern, "options": options } })
+            }
+            0x0C => {
+                let namespace = self.string()?;
+                json!({ "$dbPointer": { "$ref": namespace, "$id": { "$oid": hex(self.take(12)?) } } })
+            }
+            0x0D => json!({ "$code": self.string()? }),
+            0x0E => json!({ "$symbol": self.string()? }),
+            0x0F => {
+                self.i32()?;
+                let code = self.string()?;
+                json!({ "$code": code, "$scope": Value::Object(self.document()?) })
+            }
+            0x10 => json!(self.i32()?),
+            0x11 => {
+                let increment = self.u32()?;
+                let time = self.u32()?;
+                json!({ "$timestamp": { "t": time, "i": increment } })
+            }
+            0x12 => json!({ "$numberLong": self.i64()?.to_string() }),
+            0x13 => json!({ "$numberDecimal": decimal128(self.take(16)?) }),
+            0x7F => json!({ "$maxKey": 1 }),
+            0xFF => json!({ "$minKey": 1 }),
+            other => return Err(malformed(&format!("unknown element type 0x{:02x}", other))),
+        })
+    }
+}
+
+/// Decimal string of an IEEE 754-2008 decimal128 in BID encoding
+fn decimal128(bytes: &[u8]) -> String {
+    let low = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+    let high = u64::from_le_bytes(bytes[8..].try_into().unwrap());
+    let sign = if high >> 63 == 1 { "-" } else { "" };
+
+    let combination = (high >> 58) & 0x1f;
+    if combination == 0x1f {
+        return if (high >> 57) & 1 == 1 { "NaN" } else { "Infinity" }.to_string();
+    }
+    if combination == 0x1e {
+        return format!("{}Infinity", sign);
+    }
+    // The large-coefficient form only encodes non-canonical values, read as zero
+    let (exponent, coefficient) = if (high >> 61) & 3 == 3 {
+        ((high >> 47) & 0x3fff, 0)
+    } else {
+        ((high >> 49) & 0x3fff, (u128::from(high & 0x1_ffff_ffff_ffff) << 64) | u128::from(low))
+    };
+    format!("{}{}E{}", sign, coefficient, exponent as i64 - 6176)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn truncated() -> ProviderError {
+    malformed("truncated document")
+}
+
+fn malformed(detail: &str) -> ProviderError {
+    ProviderError::ParseError(format!("Malformed BSON reply: {}", detail))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let cursor = json!({ "$numberLong": "8000000000" });
+        let payload = binary(b"n,,n=user");
+        let filter = json!({ "name": { "$regex": "^sys" }, "ids": [1, 2.5, null, true] });
+        let command = encode_document([("getMore", &cursor), ("payload", &payload), ("filter", &filter)]);
+
+        let (decoded, used) = decode_document(&command).unwrap();
+        assert_eq!(used, command.len());
+        assert_eq!(decoded["getMore"], cursor);
+        assert_eq!(binary_bytes(&decoded["payload"]).unwrap(), b"n,,n=user");
+        assert_eq!(decoded["filter"], filter);
+        // The command name stays first
+        assert_eq!(&command[4..13], b"\x12getMore\0");
+    }
+
+    #[test]
+    fn test_decode_extended_types() {
+        let mut document = vec![0; 4];
+        document.push(0x07);
+        document.extend_from_slice(b"_id\0");
+        document.extend_from_slice(&[0x65, 0xa1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x01]);
+        document.push(0x09);
+        document.extend_from_slice(b"at\0");
+        document.extend_from_slice(&1_700_000_000_000i64.to_le_bytes());
+        document.push(0x13);
+        document.extend_from_slice(b"price\0");
+        document.extend_from_slice(&1999u64.to_le_bytes());
+        document.extend_from_slice(&((6176u64 - 2) << 49).to_le_bytes());
+        document.push(0);
+        let len = document.len() as i32;
+        document[..4].copy_from_slice(&len.to_le_bytes());
+
+        let (decoded, _) = decode_document(&document).unwrap();
+        assert_eq!(decoded["_id"], json!({ "$oid": "65a100000000000000000001" }));
+        assert_eq!(decoded["at"], json!({ "$date": { "$numberLong": "1700000000000" } }));
+        assert_eq!(decoded["price"], json!({ "$numberDecimal": "1999E-2" }));
+
+        assert!(decode_document(&document[..document.len() - 3]).is_err());
+    }
+}
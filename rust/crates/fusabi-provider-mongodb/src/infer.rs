@@ -0,0 +1,488 @@
+//! Document shape inference
+//!
+//! Sampled documents (in MongoDB Extended JSON) and `$jsonSchema`
+//! validators are both reduced to a [`Shape`], so the two sources generate
+//! types the same way. Sampled shapes merge field by field: keys missing
+//! from some documents, or `null` in some, become optional.
+
+use serde_json::Value;
+
+/// Fields that commonly tell document variants apart: `_t` (C# and Java
+/// drivers), `__t` (Mongoose), `_cls` (MongoEngine) and plain type tags
+pub const DISCRIMINATORS: [&str; 6] = ["_t", "__t", "_cls", "_type", "type", "kind"];
+
+/// Inferred shape of values found in one position
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    /// Only `null` was seen
+    Null,
+    Bool,
+    /// 32-bit integer
+    Int,
+    /// 64-bit integer
+    Long,
+    Double,
+    Decimal,
+    String,
+    ObjectId,
+    Date,
+    Binary,
+    Timestamp,
+    /// String values listed by a validator's `enum`
+    Enum(Vec<String>),
+    /// Scalars of different kinds
+    Mixed,
+    /// Element shape; `Null` for arrays that were always empty
+    List(Box<Shape>),
+    Object(ObjectShape),
+    /// Values with incompatible structure
+    Any,
+}
+
+/// Merged keys of one or more documents
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ObjectShape {
+    /// Number of documents merged
+    pub samples: usize,
+    /// Keys in first-seen order
+    pub fields: Vec<FieldShape>,
+}
+
+/// One key of an [`ObjectShape`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldShape {
+    pub name: String,
+    pub shape: Shape,
+    /// Number of merged documents that had the key
+    pub present: usize,
+    /// Whether the key was `null` in some document
+    pub nullable: bool,
+}
+
+/// Shape of the documents of a collection
+#[derive(Debug, Clone, PartialEq)]
+pub enum DocumentShape {
+    Single(ObjectShape),
+    /// Documents told apart by the string value of a discriminator field;
+    /// variant shapes do not include the discriminator
+    Polymorphic {
+        field: String,
+        variants: Vec<(String, ObjectShape)>,
+    },
+}
+
+impl FieldShape {
+    /// Whether some document lacked the key or set it to `null`
+    pub fn is_optional(&self, samples: usize) -> bool {
+        self.nullable || self.present < samples
+    }
+}
+
+impl Shape {
+    /// Shape of a single value in Extended JSON, canonical or relaxed
+    pub fn of(value: &Value) -> Self {
+        match value {
+            Value::Null => Shape::Null,
+            Value::Bool(_) => Shape::Bool,
+            Value::Number(n) if n.is_f64() => Shape::Double,
+            Value::Number(n) => match n.as_i64() {
+                Some(n) if i32::try_from(n).is_ok() => Shape::Int,
+                _ => Shape::Long,
+            },
+            Value::String(_) => Shape::String,
+            Value::Array(items) => Shape::List(Box::new(Shape::merge_all(items.iter().map(Shape::of)))),
+            Value::Object(object) => {
+                if let Some(shape) = extended_json(object) {
+                    return shape;
+                }
+                Shape::Object(ObjectShape::of(object))
+            }
+        }
+    }
+
+    /// Shape described by a `$jsonSchema` validator
+    ///
+    /// Both `bsonType` and JSON Schema `type` are understood. Properties
+    /// missing from `required` are optional.
+    pub fn of_schema(schema: &Value) -> Self {
+        let types: Vec<&str> = match schema.get("bsonType").or_else(|| schema.get("type")) {
+            Some(Value::String(t)) => vec![t.as_str()],
+            Some(Value::Array(ts)) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        let non_null: Vec<&str> = types.iter().copied().filter(|t| *t != "null").collect();
+
+        if let Some(values) = string_enum(schema) {
+            return Shape::Enum(values);
+        }
+
+        for keyword in ["oneOf", "anyOf"] {
+            if let Some(alternatives) = schema.get(keyword).and_then(Value::as_array) {
+                return Shape::merge_all(alternatives.iter().map(Shape::of_schema));
+            }
+        }
+
+        match non_null.as_slice() {
+            [] if schema.get("properties").is_some() => Shape::Object(ObjectShape::of_schema(schema)),
+            [] if schema.get("items").is_some() => Shape::List(Box::new(items_shape(schema))),
+            [] if types.is_empty() => Shape::Any,
+            [] => Shape::Null,
+            types => Shape::merge_all(types.iter().map(|t| match *t {
+                "object" => Shape::Object(ObjectShape::of_schema(schema)),
+                "array" => Shape::List(Box::new(items_shape(schema))),
+                bson_type => Shape::of_bson_type(bson_type),
+            })),
+        }
+    }
+
+    /// Shape of a scalar BSON type alias
+    fn of_bson_type(bson_type: &str) -> Self {
+        match bson_type {
+            "bool" | "boolean" => Shape::Bool,
+            "int" => Shape::Int,
+            "long" | "integer" => Shape::Long,
+            "double" | "number" => Shape::Double,
+            "decimal" => Shape::Decimal,
+            "string" | "regex" | "javascript" | "symbol" => Shape::String,
+            "objectId" => Shape::ObjectId,
+            "date" => Shape::Date,
+            "binData" => Shape::Binary,
+            "timestamp" => Shape::Timestamp,
+            _ => Shape::Any,
+        }
+    }
+
+    /// Merge the shapes of values found in the same position
+    pub fn merge(self, other: Shape) -> Shape {
+        match (self, other) {
+            (Shape::Null, shape) | (shape, Shape::Null) => shape,
+            (a, b) if a == b && !matches!(a, Shape::Object(_)) => a,
+            (Shape::Int, Shape::Long) | (Shape::Long, Shape::Int) => Shape::Long,
+            (Shape::Int | Shape::Long, Shape::Double) | (Shape::Double, Shape::Int | Shape::Long) => Shape::Double,
+            (Shape::Decimal, b) | (b, Shape::Decimal) if b.is_numeric() => Shape::Decimal,
+            (Shape::Enum(mut a), Shape::Enum(b)) => {
+                for value in b {
+                    if !a.contains(&value) {
+                        a.push(value);
+                    }
+                }
+                Shape::Enum(a)
+            }
+            (Shape::Enum(_), Shape::String) | (Shape::String, Shape::Enum(_)) => Shape::String,
+            (a, b) if a.is_scalar() && b.is_scalar() => Shape::Mixed,
+            (Shape::List(a), Shape::List(b)) => Shape::List(Box::new(a.merge(*b))),
+            (Shape::Object(a), Shape::Object(b)) => Shape::Object(a.merge(b)),
+            _ => Shape::Any,
+        }
+    }
+
+    /// Merge any number of shapes; `Null` when there are none
+    pub fn merge_all(shapes: impl IntoIterator<Item = Shape>) -> Shape {
+        shapes.into_iter().fold(Shape::Null, Shape::merge)
+    }
+
+    fn is_numeric(&self) -> bool {
+        matches!(self, Shape::Int | Shape::Long | Shape::Double | Shape::Decimal)
+    }
+
+    fn is_scalar(&self) -> bool {
+        !matches!(self, Shape::Null | Shape::List(_) | Shape::Object(_) | Shape::Any)
+    }
+}
+
+impl ObjectShape {
+    fn of(object: &serde_json::Map<String, Value>) -> Self {
+        ObjectShape {
+            samples: 1,
+            fields: object
+                .iter()
+                .map(|(name, value)| FieldShape {
+                    name: name.clone(),
+                    shape: Shape::of(value),
+                    present: 1,
+                    nullable: value.is_null(),
+                })
+                .collect(),
+        }
+    }
+
+    fn of_schema(schema: &Value) -> Self {
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|r| r.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+
+        let properties = schema.get("properties").and_then(Value::as_object);
+        ObjectShape {
+            samples: 1,
+            fields: properties
+                .into_iter()
+                .flatten()
+                .map(|(name, property)| FieldShape {
+                    name: name.clone(),
+                    shape: Shape::of_schema(property),
+                    present: usize::from(required.contains(&name.as_str())),
+                    nullable: allows_null(property),
+                })
+                .collect(),
+        }
+    }
+
+    fn merge(mut self, other: ObjectShape) -> ObjectShape {
+        self.samples += other.samples;
+        for field in other.fields {
+            match self.fields.iter_mut().find(|f| f.name == field.name) {
+                Some(existing) => {
+                    existing.present += field.present;
+                    existing.nullable |= field.nullable;
+                    let shape = std::mem::replace(&mut existing.shape, Shape::Null);
+                    existing.shape = shape.merge(field.shape);
+                }
+                None => self.fields.push(field),
+            }
+        }
+        self
+    }
+
+    /// The shape without one field
+    fn without(mut self, name: &str) -> ObjectShape {
+        self.fields.retain(|f| f.name != name);
+        self
+    }
+
+    fn has_same_keys(&self, other: &ObjectShape) -> bool {
+        self.fields.len() == other.fields.len()
+            && self.fields.iter().all(|f| other.fields.iter().any(|o| o.name == f.name))
+    }
+}
+
+impl DocumentShape {
+    /// Infer the shape of sampled documents
+    ///
+    /// Documents split into variants on `discriminator` when given, or on
+    /// the first of [`DISCRIMINATORS`] that every document sets to a string
+    /// and whose variants differ in their keys.
+    pub fn infer(documents: &[Value], discriminator: Option<&str>) -> Self {
+        let candidates: Vec<&str> = match discriminator {
+            Some(field) => vec![field],
+            None => DISCRIMINATORS.to_vec(),
+        };
+
+        for field in candidates {
+            let Some(variants) = split_variants(documents, field) else {
+                continue;
+            };
+            let distinct = variants
+                .iter()
+                .any(|(_, shape)| variants.iter().any(|(_, other)| !shape.has_same_keys(other)));
+            if variants.len() > 1 && (discriminator.is_some() || distinct) {
+                return DocumentShape::Polymorphic { field: field.to_string(), variants };
+            }
+        }
+
+        let merged = Shape::merge_all(documents.iter().map(Shape::of));
+        match merged {
+            Shape::Object(object) => DocumentShape::Single(object),
+            _ => DocumentShape::Single(ObjectShape::default()),
+        }
+    }
+
+    /// Shape described by a `$jsonSchema` validator; a top-level `oneOf`
+    /// of objects tagged by a single-valued `enum` or `const` property is
+    /// polymorphic
+    pub fn of_schema(schema: &Value) -> Self {
+        let alternatives = schema
+            .get("oneOf")
+            .or_else(|| schema.get("anyOf"))
+            .and_then(Value::as_array)
+            .filter(|alts| alts.len() > 1);
+
+        if let Some(alternatives) = alternatives {
+            let field = alternatives[0]
+                .get("properties")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+                .map(|(name, _)| name.as_str())
+                .find(|name| alternatives.iter().all(|alt| tag_value(alt, name).is_some()));
+
+            if let Some(field) = field {
+                let base = ObjectShape::of_schema(schema);
+                let variants = alternatives
+                    .iter()
+                    .filter_map(|alt| {
+                        let tag = tag_value(alt, field)?;
+                        let shape = base.clone().merge_schema(ObjectShape::of_schema(alt));
+                        Some((tag, shape.without(field)))
+                    })
+                    .collect();
+                return DocumentShape::Polymorphic { field: field.to_string(), variants };
+            }
+        }
+
+        match Shape::of_schema(schema) {
+            Shape::Object(object) => DocumentShape::Single(object),
+            _ => DocumentShape::Single(ObjectShape::default()),
+        }
+    }
+}
+
+impl ObjectShape {
+    /// Combine the properties common to all variants with those of one
+    /// variant, keeping requiredness from the schema
+    fn merge_schema(mut self, other: ObjectShape) -> ObjectShape {
+        for field in other.fields {
+            match self.fields.iter_mut().find(|f| f.name == field.name) {
+                Some(existing) => {
+                    existing.present = existing.present.max(field.present);
+                    existing.shape = field.shape;
+                }
+                None => self.fields.push(field),
+            }
+        }
+        self
+    }
+}
+
+/// Group documents by the string value of `field`, merging each group;
+/// `None` unless every document sets it to a string
+fn split_variants(documents: &[Value], field: &str) -> Option<Vec<(String, ObjectShape)>> {
+    let mut variants: Vec<(String, ObjectShape)> = Vec::new();
+    for document in documents {
+        let tag = document.get(field)?.as_str()?;
+        let Shape::Object(shape) = Shape::of(document) else {
+            return None;
+        };
+        let shape = shape.without(field);
+        match variants.iter_mut().find(|(name, _)| name == tag) {
+            Some((_, existing)) => *existing = std::mem::take(existing).merge(shape),
+            None => variants.push((tag.to_string(), shape)),
+        }
+    }
+    (!variants.is_empty()).then_some(variants)
+}
+
+/// Shape of an Extended JSON type wrapper such as `{"$oid": "..."}`
+fn extended_json(object: &serde_json::Map<String, Value>) -> Option<Shape> {
+    let (key, _) = object.iter().next()?;
+    let shape = match key.as_str() {
+        "$oid" => Shape::ObjectId,
+        "$date" => Shape::Date,
+        "$numberInt" => Shape::Int,
+        "$numberLong" => Shape::Long,
+        "$numberDouble" => Shape::Double,
+        "$numberDecimal" => Shape::Decimal,
+        "$binary" | "$type" => Shape::Binary,
+        "$uuid" | "$symbol" | "$code" | "$regularExpression" | "$regex" | "$options" => Shape::String,
+        "$timestamp" => Shape::Timestamp,
+        "$undefined" => Shape::Null,
+        "$minKey" | "$maxKey" | "$dbPointer" | "$scope" => Shape::Any,
+        _ => return None,
+    };
+    // Legacy forms pair `$binary` with `$type` and `$regex` with `$options`
+    let wrapper_keys = if matches!(shape, Shape::Binary | Shape::String) { 2 } else { 1 };
+    (object.len() <= wrapper_keys && object.keys().all(|k| k.starts_with('$'))).then_some(shape)
+}
+
+fn items_shape(schema: &Value) -> Shape {
+    schema.get("items").map_or(Shape::Null, Shape::of_schema)
+}
+
+fn allows_null(schema: &Value) -> bool {
+    match schema.get("bsonType").or_else(|| schema.get("type")) {
+        Some(Value::String(t)) => t == "null",
+        Some(Value::Array(ts)) => ts.iter().any(|t| t == "null"),
+        _ => false,
+    }
+}
+
+/// String values of an `enum` made only of strings
+fn string_enum(schema: &Value) -> Option<Vec<String>> {
+    let values = schema.get("enum")?.as_array()?;
+    values.iter().map(|v| v.as_str().map(String::from)).collect()
+}
+
+/// The single value a variant's property is fixed to
+fn tag_value(schema: &Value, field: &str) -> Option<String> {
+    let property = schema.get("properties")?.get(field)?;
+    if let Some(value) = property.get("const").and_then(Value::as_str) {
+        return Some(value.to_string());
+    }
+    match string_enum(property)?.as_slice() {
+        [single] => Some(single.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_merge_documents() {
+        let documents = [
+            json!({ "_id": { "$oid": "65a1" }, "name": "Ada", "age": 36, "tags": [] }),
+            json!({ "_id": { "$oid": "65a2" }, "name": "Bob", "age": { "$numberLong": "5000000000" }, "email": null }),
+        ];
+
+        let DocumentShape::Single(object) = DocumentShape::infer(&documents, None) else {
+            panic!("Expected a single document shape");
+        };
+        assert_eq!(object.samples, 2);
+        let field = |name: &str| object.fields.iter().find(|f| f.name == name).unwrap();
+        assert_eq!(field("_id").shape, Shape::ObjectId);
+        assert!(!field("name").is_optional(2));
+        assert_eq!(field("age").shape, Shape::Long);
+        assert!(field("email").is_optional(2));
+        assert!(field("tags").is_optional(2));
+        assert_eq!(field("tags").shape, Shape::List(Box::new(Shape::Null)));
+    }
+
+    #[test]
+    fn test_infer_polymorphic_documents() {
+        let documents = [
+            json!({ "_t": "Card", "amount": 10.5, "last4": "4242" }),
+            json!({ "_t": "Transfer", "amount": 20, "iban": "DE89" }),
+            json!({ "_t": "Card", "amount": 3, "last4": "1881" }),
+        ];
+
+        let DocumentShape::Polymorphic { field, variants } = DocumentShape::infer(&documents, None) else {
+            panic!("Expected a polymorphic document shape");
+        };
+        assert_eq!(field, "_t");
+        let names: Vec<&str> = variants.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["Card", "Transfer"]);
+        assert_eq!(variants[0].1.samples, 2);
+        assert_eq!(variants[0].1.fields[0].shape, Shape::Double);
+
+        // The same tag on structurally identical documents is just a field
+        let status = [json!({ "type": "a", "n": 1 }), json!({ "type": "b", "n": 2 })];
+        assert!(matches!(DocumentShape::infer(&status, None), DocumentShape::Single(_)));
+    }
+
+    #[test]
+    fn test_schema_shape() {
+        let schema = json!({
+            "bsonType": "object",
+            "required": ["name"],
+            "properties": {
+                "name": { "bsonType": "string" },
+                "status": { "enum": ["active", "closed"] },
+                "balance": { "bsonType": ["decimal", "null"] },
+                "opened": { "bsonType": "date" }
+            }
+        });
+
+        let Shape::Object(object) = Shape::of_schema(&schema) else {
+            panic!("Expected object shape");
+        };
+        let field = |name: &str| object.fields.iter().find(|f| f.name == name).unwrap();
+        assert!(!field("name").is_optional(1));
+        assert_eq!(field("status").shape, Shape::Enum(vec!["active".to_string(), "closed".to_string()]));
+        assert!(field("balance").is_optional(1));
+        assert_eq!(field("balance").shape, Shape::Decimal);
+        assert_eq!(field("opened").shape, Shape::Date);
+    }
+}
@@ -9,6 +9,15 @@
 //! - Support for custom eBPF event structures
 //! - Type-safe mapping from kernel/eBPF types to Fusabi types
 //! - Comprehensive event categories: syscall, network, file, process, security
+//! - Struct, field and enum descriptions carried through as documentation
+//!
+//! # Cargo features
+//!
+//! Each embedded category (`syscall`, `network`, `file`, `process`) is
+//! behind the feature of the same name, all enabled by default. Hosts that
+//! only need some of them build with `default-features = false`;
+//! [`embedded_packs`] reports which are compiled in, and `embedded:all` and
+//! `embedded:security` cover just those.
 //!
 //! # Example
 //!
@@ -35,13 +44,22 @@ pub use types::{
     ObiType, ObiPrimitiveType, EventCategory,
 };
 
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, DocumentedProvider, InputLimits,
+    OutputFeature, SourceValidator, TypeDocs, TypeShape,
+};
 use fusabi_type_providers::{
     TypeProvider, ProviderParams, Schema,
     GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
-    RecordDef, DuDef, VariantDef, TypeExpr, TypeDefinition,
+    RecordDef, DuDef, VariantDef, TypeDefinition,
     ProviderError, ProviderResult,
 };
 
+/// Embedded event categories compiled into this build
+pub fn embedded_packs() -> Vec<&'static str> {
+    types::embedded::PACKS.iter().map(|(name, _)| *name).collect()
+}
+
 /// OBI type provider for eBPF event structures
 pub struct ObiProvider {
     generator: TypeGenerator,
@@ -55,34 +73,38 @@ impl ObiProvider {
         }
     }
 
-    /// Generate types from an OBI schema
+    /// Generate types and their documentation from an OBI schema
     fn generate_from_schema(
         &self,
         schema: &ObiSchema,
         namespace: &str,
-    ) -> ProviderResult<GeneratedTypes> {
+    ) -> ProviderResult<(GeneratedTypes, TypeDocs)> {
         // Validate schema first
         parser::validate_schema(schema)?;
 
         let mut result = GeneratedTypes::new();
+        let mut docs = TypeDocs::new();
 
         // Create a module for the namespace if we have definitions
         if !schema.structs.is_empty() || !schema.enums.is_empty() {
             let mut module = GeneratedModule::new(vec![namespace.to_string()]);
 
             // Generate enum definitions first (they may be referenced by structs)
-            for (_enum_name, obi_enum) in &schema.enums {
+            for obi_enum in schema.enums.values() {
                 let type_def = self.enum_to_typedef(obi_enum)?;
+                self.document_enum(&mut docs, &module.path, obi_enum);
                 module.types.push(type_def);
             }
 
             // Generate struct definitions
-            for (_struct_name, obi_struct) in &schema.structs {
+            for obi_struct in schema.structs.values() {
                 let type_def = self.struct_to_typedef(obi_struct)?;
+                self.document_struct(&mut docs, &module.path, obi_struct);
 
                 // For embedded mode, add structs as root types
                 if schema.is_embedded() {
                     result.root_types.push(type_def.clone());
+                    self.document_struct(&mut docs, &[], obi_struct);
                 }
 
                 module.types.push(type_def);
@@ -93,7 +115,34 @@ impl ObiProvider {
             }
         }
 
-        Ok(result)
+        Ok((result, docs))
+    }
+
+    /// Record the descriptions of a struct and its fields
+    fn document_struct(&self, docs: &mut TypeDocs, path: &[String], obi_struct: &ObiStruct) {
+        let name = self.generator.naming.apply(&obi_struct.name);
+        if let Some(description) = &obi_struct.description {
+            docs.set_type(path, &name, description);
+        }
+        for field in &obi_struct.fields {
+            if let Some(description) = &field.description {
+                docs.set_member(path, &name, &field.name, description);
+            }
+        }
+    }
+
+    /// Record the descriptions of an enum and its variants
+    fn document_enum(&self, docs: &mut TypeDocs, path: &[String], obi_enum: &ObiEnum) {
+        let name = self.generator.naming.apply(&obi_enum.name);
+        if let Some(description) = &obi_enum.description {
+            docs.set_type(path, &name, description);
+        }
+        for variant in &obi_enum.variants {
+            if let Some(description) = &variant.description {
+                let variant_name = self.generator.naming.apply(&variant.name);
+                docs.set_member(path, &name, &variant_name, description);
+            }
+        }
     }
 
     /// Convert an OBI struct to a Fusabi RecordDef
@@ -101,8 +150,8 @@ impl ObiProvider {
         let mut fields = Vec::new();
 
         for field in &obi_struct.fields {
-            let type_expr = self.obi_type_to_type_expr(&field.field_type)?;
-            fields.push((field.name.clone(), type_expr));
+            let shape = self.obi_type_shape(&field.field_type)?;
+            fields.push((field.name.clone(), shape.into()));
         }
 
         Ok(TypeDefinition::Record(RecordDef {
@@ -125,31 +174,31 @@ impl ObiProvider {
         }))
     }
 
-    /// Convert an OBI type to a Fusabi TypeExpr
-    fn obi_type_to_type_expr(&self, obi_type: &ObiType) -> ProviderResult<TypeExpr> {
+    /// Convert an OBI type to a Fusabi TypeShape
+    fn obi_type_shape(&self, obi_type: &ObiType) -> ProviderResult<TypeShape> {
         match obi_type {
             ObiType::Primitive { prim_type } => {
-                Ok(TypeExpr::Named(self.primitive_to_fusabi_type(prim_type)))
+                Ok(TypeShape::named(self.primitive_to_fusabi_type(prim_type)))
             }
             ObiType::Array { element_type, size: _ } => {
-                let elem_expr = self.obi_type_to_type_expr(element_type)?;
+                let elem_shape = self.obi_type_shape(element_type)?;
                 // For fixed arrays, we use list for now
                 // TODO: Consider adding array type to Fusabi
-                Ok(TypeExpr::Named(format!("{} list", elem_expr)))
+                Ok(elem_shape.list())
             }
             ObiType::List { element_type } => {
-                let elem_expr = self.obi_type_to_type_expr(element_type)?;
-                Ok(TypeExpr::Named(format!("{} list", elem_expr)))
+                let elem_shape = self.obi_type_shape(element_type)?;
+                Ok(elem_shape.list())
             }
             ObiType::Struct { name } => {
-                Ok(TypeExpr::Named(self.generator.naming.apply(name)))
+                Ok(TypeShape::named(self.generator.naming.apply(name)))
             }
             ObiType::Enum { name } => {
-                Ok(TypeExpr::Named(self.generator.naming.apply(name)))
+                Ok(TypeShape::named(self.generator.naming.apply(name)))
             }
             ObiType::Option { inner_type } => {
-                let inner_expr = self.obi_type_to_type_expr(inner_type)?;
-                Ok(TypeExpr::Named(format!("{} option", inner_expr)))
+                let inner_shape = self.obi_type_shape(inner_type)?;
+                Ok(inner_shape.option())
             }
         }
     }
@@ -187,8 +236,17 @@ impl TypeProvider for ObiProvider {
         "ObiProvider"
     }
 
-    fn resolve_schema(&self, source: &str, _params: &ProviderParams) -> ProviderResult<Schema> {
-        let obi_schema = parser::parse_from_source(source)?;
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let obi_schema = if source.starts_with("embedded:") {
+            parser::parse_from_source(source)?
+        } else {
+            let json = if source.trim().starts_with('{') {
+                source.to_string()
+            } else {
+                read_source(source, params)?
+            };
+            parser::parse_obi_schema_with_limits(&json, &InputLimits::from_params(params)?)?
+        };
 
         // Validate the schema
         parser::validate_schema(&obi_schema)?;
@@ -201,6 +259,16 @@ impl TypeProvider for ObiProvider {
     }
 
     fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        self.generate_documented(schema, namespace).map(|(types, _)| types)
+    }
+}
+
+impl DocumentedProvider for ObiProvider {
+    fn generate_documented(
+        &self,
+        schema: &Schema,
+        namespace: &str,
+    ) -> ProviderResult<(GeneratedTypes, TypeDocs)> {
         match schema {
             Schema::JsonSchema(value) => {
                 // Deserialize back to ObiSchema
@@ -214,9 +282,70 @@ impl TypeProvider for ObiProvider {
     }
 }
 
+impl SourceValidator for ObiProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::JsonSchema(value) = schema else {
+            return Err(ProviderError::ParseError("Expected OBI schema (JSON format)".to_string()));
+        };
+
+        let obi_schema: ObiSchema = serde_json::from_value(value.clone())
+            .map_err(|e| ProviderError::ParseError(format!("Invalid OBI schema: {}", e)))?;
+
+        let mut diagnostics = Vec::new();
+        for obi_struct in obi_schema.structs.values() {
+            for field in &obi_struct.fields {
+                let location = format!("{}.{}", obi_struct.name, field.name);
+                check_obi_type(&field.field_type, &location, &mut diagnostics);
+            }
+        }
+
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for ObiProvider {
+    fn capabilities(&self) -> Capabilities {
+        let capabilities = types::embedded::PACKS
+            .iter()
+            .fold(Capabilities::new(self.name()).documents(), |capabilities, &(_, source)| {
+                capabilities.embedded(source)
+            });
+        capabilities
+            .embedded("embedded:security")
+            .embedded("embedded:all")
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Docs)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+/// Flag OBI types that lose information when mapped to Fusabi types
+fn check_obi_type(obi_type: &ObiType, location: &str, diagnostics: &mut Vec<Diagnostic>) {
+    match obi_type {
+        ObiType::Primitive { prim_type: ObiPrimitiveType::U64 } => diagnostics.push(
+            Diagnostic::warning("lossy-conversion", "u64 values above i64::MAX do not fit in `int`")
+                .at(location),
+        ),
+        ObiType::Array { element_type, size } => {
+            diagnostics.push(
+                Diagnostic::info(
+                    "lossy-conversion",
+                    format!("Fixed-size array of {} is typed as a list", size),
+                )
+                .at(location),
+            );
+            check_obi_type(element_type, location, diagnostics);
+        }
+        ObiType::List { element_type } => check_obi_type(element_type, location, diagnostics),
+        ObiType::Option { inner_type } => check_obi_type(inner_type, location, diagnostics),
+        _ => {}
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fusabi_provider_common::emit::golden;
 
     #[test]
     fn test_generate_syscall_event() {
@@ -231,6 +360,32 @@ mod tests {
         } else {
             panic!("Expected Record type definition");
         }
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/obi.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_generate_documented() {
+        let provider = ObiProvider::new();
+        let schema = provider.resolve_schema("embedded:syscall", &ProviderParams::default()).unwrap();
+        let (types, docs) = provider.generate_documented(&schema, "Syscall").unwrap();
+
+        let module = vec!["Syscall".to_string()];
+        assert_eq!(
+            docs.type_doc(&module, "SyscallEvent"),
+            Some("System call event from eBPF tracepoint")
+        );
+        assert_eq!(docs.member_doc(&module, "SyscallEvent", "pid"), Some("Process ID"));
+        assert_eq!(docs.member_doc(&[], "SyscallEvent", "pid"), Some("Process ID"));
+
+        golden::assert_fusabi_documented(
+            &types,
+            &docs,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/obi-documented.fsx"),
+        );
     }
 
     #[test]
@@ -438,4 +593,34 @@ mod tests {
             panic!("Expected Record type definition");
         }
     }
+
+    #[test]
+    fn test_validate_embedded_schema() {
+        let provider = ObiProvider::new();
+        let report = provider.validate_source("embedded:syscall", &ProviderParams::default());
+
+        assert!(report.is_valid());
+        assert!(report.type_count > 0);
+        assert!(report
+            .warnings()
+            .any(|d| d.code == "lossy-conversion" && d.location.as_deref() == Some("SyscallEvent.syscall_nr")));
+    }
+
+    #[test]
+    fn test_validate_unknown_source() {
+        let provider = ObiProvider::new();
+        let report = provider.validate_source("embedded:nope", &ProviderParams::default());
+        assert!(!report.is_valid());
+        assert_eq!(report.errors().count(), 1);
+    }
+
+    #[test]
+    #[cfg(all(feature = "syscall", feature = "network", feature = "file", feature = "process"))]
+    fn test_embedded_packs() {
+        assert_eq!(embedded_packs(), ["syscall", "network", "file", "process"]);
+
+        let capabilities = ObiProvider::new().capabilities();
+        assert_eq!(capabilities.embedded_sources().len(), 6);
+        assert!(capabilities.embedded_sources().contains(&"embedded:file"));
+    }
 }
@@ -2,14 +2,30 @@
 //!
 //! Parses OBI schema definitions from JSON format or generates
 //! embedded schemas for built-in Hibana event types.
+//!
+//! JSON schemas are checked against the input limits: nesting at most
+//! `max_depth` deep and at most `max_types` structs and enums.
 
 use crate::types::{ObiSchema, EventCategory};
-use fusabi_type_providers::{ProviderError, ProviderResult};
+use fusabi_provider_common::limits::InputLimits;
+use fusabi_provider_common::read_source;
+use fusabi_type_providers::{ProviderError, ProviderParams, ProviderResult};
 
 /// Parse an OBI schema from JSON string
 pub fn parse_obi_schema(json: &str) -> ProviderResult<ObiSchema> {
-    serde_json::from_str(json)
-        .map_err(|e| ProviderError::ParseError(format!("Invalid OBI JSON: {}", e)))
+    parse_obi_schema_with_limits(json, &InputLimits::default())
+}
+
+/// Parse an OBI schema from JSON string, rejecting schemas that exceed `limits`
+pub fn parse_obi_schema_with_limits(json: &str, limits: &InputLimits) -> ProviderResult<ObiSchema> {
+    let invalid = |e: serde_json::Error| ProviderError::ParseError(format!("Invalid OBI JSON: {}", e));
+
+    limits.check_size(json)?;
+    let value: serde_json::Value = serde_json::from_str(json).map_err(invalid)?;
+    limits.check_json_depth(&value)?;
+    let schema: ObiSchema = serde_json::from_value(value).map_err(invalid)?;
+    limits.check_type_count(schema.structs.len() + schema.enums.len())?;
+    Ok(schema)
 }
 
 /// Parse an OBI schema from a source specifier
@@ -19,9 +35,11 @@ pub fn parse_obi_schema(json: &str) -> ProviderResult<ObiSchema> {
 /// - "embedded:network" - Built-in network events
 /// - "embedded:file" - Built-in file events
 /// - "embedded:process" - Built-in process events
+/// - "embedded:security" - Built-in security events
 /// - "embedded:all" - All built-in events
 /// - JSON string starting with '{'
-/// - File path (with or without "file://" prefix)
+/// - File path (with or without "file://" prefix), no larger than the
+///   default `max_source_bytes`
 pub fn parse_from_source(source: &str) -> ProviderResult<ObiSchema> {
     // Handle embedded schemas
     if let Some(category_str) = source.strip_prefix("embedded:") {
@@ -40,7 +58,13 @@ pub fn parse_from_source(source: &str) -> ProviderResult<ObiSchema> {
             }
         };
 
-        return Ok(crate::types::embedded::get_schema(category));
+        return crate::types::embedded::get_schema(category).ok_or_else(|| {
+            ProviderError::InvalidSource(format!(
+                "Embedded category {} is not enabled; build fusabi-provider-obi with the `{}` feature",
+                category_str,
+                category_str.to_lowercase()
+            ))
+        });
     }
 
     // Handle inline JSON
@@ -49,9 +73,7 @@ pub fn parse_from_source(source: &str) -> ProviderResult<ObiSchema> {
     }
 
     // Handle file paths
-    let path = source.strip_prefix("file://").unwrap_or(source);
-    let json_str = std::fs::read_to_string(path)
-        .map_err(|e| ProviderError::IoError(format!("Failed to read {}: {}", path, e)))?;
+    let json_str = read_source(source, &ProviderParams::default())?;
 
     parse_obi_schema(&json_str)
 }
@@ -219,4 +241,28 @@ mod tests {
         let result = validate_schema(&schema);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_parse_with_limits() {
+        let json = r#"{
+            "structs": {
+                "A": { "name": "A", "fields": [{ "name": "id", "type": { "kind": "primitive", "type": "u64" } }] },
+                "B": { "name": "B", "fields": [] }
+            }
+        }"#;
+        let limits = InputLimits {
+            max_depth: 6,
+            max_types: 2,
+            ..InputLimits::default()
+        };
+        assert!(parse_obi_schema_with_limits(json, &limits).is_ok());
+
+        let shallow = InputLimits { max_depth: 5, ..limits };
+        let error = parse_obi_schema_with_limits(json, &shallow).unwrap_err();
+        assert!(error.to_string().contains("max_depth (5)"));
+
+        let few = InputLimits { max_types: 1, ..limits };
+        let error = parse_obi_schema_with_limits(json, &few).unwrap_err();
+        assert!(error.to_string().contains("Source declares 2 types, more than max_types (1)"));
+    }
 }
@@ -211,6 +211,7 @@ impl Default for ObiSchema {
 pub mod embedded {
     use super::*;
 
+    #[cfg(feature = "syscall")]
     /// Create SyscallEvent struct definition
     pub fn syscall_event() -> ObiStruct {
         ObiStruct {
@@ -262,6 +263,7 @@ pub mod embedded {
         }
     }
 
+    #[cfg(feature = "network")]
     /// Create NetworkEvent struct definition
     pub fn network_event() -> ObiStruct {
         ObiStruct {
@@ -321,6 +323,7 @@ pub mod embedded {
         }
     }
 
+    #[cfg(feature = "file")]
     /// Create FileEvent struct definition
     pub fn file_event() -> ObiStruct {
         ObiStruct {
@@ -367,6 +370,7 @@ pub mod embedded {
         }
     }
 
+    #[cfg(feature = "process")]
     /// Create ProcessEvent struct definition
     pub fn process_event() -> ObiStruct {
         ObiStruct {
@@ -436,6 +440,7 @@ pub mod embedded {
         }
     }
 
+    #[cfg(feature = "process")]
     /// Create ProcessEventType enum
     pub fn process_event_type_enum() -> ObiEnum {
         ObiEnum {
@@ -462,56 +467,73 @@ pub mod embedded {
         }
     }
 
+    /// Enabled event categories, as `(name, source)`
+    ///
+    /// Each category is behind the cargo feature of the same name; all of
+    /// them are enabled by default.
+    pub const PACKS: &[(&str, &str)] = &[
+        #[cfg(feature = "syscall")]
+        ("syscall", "embedded:syscall"),
+        #[cfg(feature = "network")]
+        ("network", "embedded:network"),
+        #[cfg(feature = "file")]
+        ("file", "embedded:file"),
+        #[cfg(feature = "process")]
+        ("process", "embedded:process"),
+    ];
+
     /// Get embedded schema for a specific category
-    pub fn get_schema(category: EventCategory) -> ObiSchema {
+    ///
+    /// Security, custom and other categories include every enabled
+    /// category. Returns `None` for a category disabled in this build.
+    pub fn get_schema(category: EventCategory) -> Option<ObiSchema> {
         let mut schema = ObiSchema::new();
         schema.mode = "embedded".to_string();
         schema.category = Some(category.clone());
 
-        match category {
-            EventCategory::Syscall => {
-                schema.structs.insert(
-                    "SyscallEvent".to_string(),
-                    syscall_event(),
-                );
-                schema.description = Some("Embedded syscall event types for Hibana".to_string());
-            }
-            EventCategory::Network => {
-                schema.structs.insert(
-                    "NetworkEvent".to_string(),
-                    network_event(),
-                );
-                schema.description = Some("Embedded network event types for Hibana".to_string());
-            }
-            EventCategory::File => {
-                schema.structs.insert(
-                    "FileEvent".to_string(),
-                    file_event(),
-                );
-                schema.description = Some("Embedded file event types for Hibana".to_string());
+        let (pack, description) = match category {
+            EventCategory::Syscall => (Some("syscall"), "Embedded syscall event types for Hibana"),
+            EventCategory::Network => (Some("network"), "Embedded network event types for Hibana"),
+            EventCategory::File => (Some("file"), "Embedded file event types for Hibana"),
+            EventCategory::Process => (Some("process"), "Embedded process event types for Hibana"),
+            _ => (None, "All embedded event types for Hibana"),
+        };
+        if let Some(pack) = pack {
+            if !PACKS.iter().any(|(name, _)| *name == pack) {
+                return None;
             }
-            EventCategory::Process => {
-                schema.structs.insert(
-                    "ProcessEvent".to_string(),
-                    process_event(),
-                );
-                schema.enums.insert(
-                    "ProcessEventType".to_string(),
-                    process_event_type_enum(),
-                );
-                schema.description = Some("Embedded process event types for Hibana".to_string());
+        }
+
+        for (name, _) in PACKS {
+            if pack.is_none() || pack == Some(*name) {
+                add_pack(name, &mut schema);
             }
-            _ => {
-                // For custom or other categories, include all event types
+        }
+        schema.description = Some(description.to_string());
+        Some(schema)
+    }
+
+    /// Add the types of an enabled category
+    fn add_pack(name: &str, schema: &mut ObiSchema) {
+        match name {
+            #[cfg(feature = "syscall")]
+            "syscall" => {
                 schema.structs.insert("SyscallEvent".to_string(), syscall_event());
+            }
+            #[cfg(feature = "network")]
+            "network" => {
                 schema.structs.insert("NetworkEvent".to_string(), network_event());
+            }
+            #[cfg(feature = "file")]
+            "file" => {
                 schema.structs.insert("FileEvent".to_string(), file_event());
+            }
+            #[cfg(feature = "process")]
+            "process" => {
                 schema.structs.insert("ProcessEvent".to_string(), process_event());
                 schema.enums.insert("ProcessEventType".to_string(), process_event_type_enum());
-                schema.description = Some("All embedded event types for Hibana".to_string());
             }
+            _ => {}
         }
-
-        schema
     }
 }
@@ -0,0 +1,142 @@
+//! Compiled OCSF schemas
+//!
+//! The schema server's `/export/schema` endpoint returns the whole schema
+//! for one version, with inheritance already applied:
+//!
+//! ```json
+//! {
+//!   "version": "1.1.0",
+//!   "classes": {"file_activity": {"uid": 1001, "category": "system", "attributes": {...}}},
+//!   "objects": {"file": {"attributes": {...}}},
+//!   "types": {"timestamp_t": {"type": "long_t"}}
+//! }
+//! ```
+
+use crate::types::{Attribute, Definition, OcsfSchema, Requirement};
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde_json::{Map, Value};
+
+/// Whether a JSON document is a compiled schema
+pub fn is_export(value: &Value) -> bool {
+    value.get("classes").is_some_and(Value::is_object) && value.get("objects").is_some_and(Value::is_object)
+}
+
+/// Read a compiled schema
+pub fn parse_export(value: &Value) -> ProviderResult<OcsfSchema> {
+    if !is_export(value) {
+        return Err(ProviderError::ParseError(
+            "Expected an OCSF schema export with `classes` and `objects`".to_string(),
+        ));
+    }
+
+    let mut schema = OcsfSchema {
+        version: value.get("version").and_then(Value::as_str).map(String::from),
+        ..Default::default()
+    };
+    for (name, data_type) in value.get("types").and_then(Value::as_object).into_iter().flatten() {
+        if let Some(base) = data_type.get("type").and_then(Value::as_str) {
+            schema.types.insert(name.clone(), base.to_string());
+        }
+    }
+
+    for (name, class) in members(value, "classes") {
+        schema.classes.push(definition(name, class)?);
+    }
+    // Older exports keep the base event beside the classes
+    if let Some(base_event) = value.get("base_event") {
+        if !schema.classes.iter().any(|c| c.name == "base_event") {
+            schema.classes.insert(0, definition("base_event", base_event)?);
+        }
+    }
+    for (name, object) in members(value, "objects") {
+        schema.objects.push(definition(name, object)?);
+    }
+    Ok(schema)
+}
+
+/// Build a definition from its JSON form, whose attributes are either a
+/// map or a list of single-entry maps
+fn definition(name: &str, value: &Value) -> ProviderResult<Definition> {
+    let name = value.get("name").and_then(Value::as_str).unwrap_or(name);
+    let mut attributes: Vec<Attribute> = match value.get("attributes") {
+        Some(Value::Object(map)) => map.iter().map(|(n, a)| attribute(name, n, a)).collect::<ProviderResult<_>>()?,
+        Some(Value::Array(list)) => list
+            .iter()
+            .filter_map(Value::as_object)
+            .flat_map(Map::iter)
+            .map(|(n, a)| attribute(name, n, a))
+            .collect::<ProviderResult<_>>()?,
+        _ => Vec::new(),
+    };
+    attributes.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(Definition {
+        name: name.to_string(),
+        caption: value.get("caption").and_then(Value::as_str).map(String::from),
+        category: value.get("category").and_then(Value::as_str).map(String::from),
+        uid: value.get("uid").and_then(Value::as_i64),
+        attributes,
+    })
+}
+
+pub(crate) fn attribute(owner: &str, name: &str, value: &Value) -> ProviderResult<Attribute> {
+    let type_name = value
+        .get("type")
+        .and_then(Value::as_str)
+        .ok_or_else(|| ProviderError::ParseError(format!("Attribute `{}.{}` has no type", owner, name)))?;
+
+    Ok(Attribute {
+        name: name.to_string(),
+        type_name: type_name.to_string(),
+        object_type: value.get("object_type").and_then(Value::as_str).map(String::from),
+        is_array: value.get("is_array").and_then(Value::as_bool).unwrap_or(false),
+        requirement: Requirement::parse(value.get("requirement").and_then(Value::as_str).unwrap_or_default()),
+    })
+}
+
+fn members<'a>(value: &'a Value, key: &str) -> impl Iterator<Item = (&'a str, &'a Value)> {
+    value
+        .get(key)
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .map(|(name, v)| (name.as_str(), v))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_export() {
+        let value: Value = serde_json::from_str(
+            r#"{
+                "version": "1.1.0",
+                "base_event": {"name": "base_event", "uid": 0, "attributes": {"time": {"type": "timestamp_t", "requirement": "required"}}},
+                "classes": {
+                    "file_activity": {
+                        "name": "file_activity", "uid": 1001, "category": "system",
+                        "attributes": [
+                            {"activity_id": {"type": "integer_t", "requirement": "required"}},
+                            {"actor": {"type": "object_t", "object_type": "actor", "requirement": "required"}}
+                        ]
+                    }
+                },
+                "objects": {"actor": {"name": "actor", "attributes": {"user": {"type": "object_t", "object_type": "user"}}}},
+                "types": {"timestamp_t": {"caption": "Timestamp", "type": "long_t"}, "string_t": {"caption": "String"}}
+            }"#,
+        )
+        .unwrap();
+
+        let schema = parse_export(&value).unwrap();
+        assert_eq!(schema.version.as_deref(), Some("1.1.0"));
+        assert_eq!(schema.base_type("timestamp_t"), Some("long_t"));
+
+        let names: Vec<&str> = schema.classes.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["base_event", "file_activity"]);
+        let actor = &schema.classes[1].attributes[1];
+        assert_eq!(actor.object_type.as_deref(), Some("actor"));
+        assert_eq!(actor.requirement, Requirement::Required);
+        assert_eq!(schema.objects[0].attributes[0].requirement, Requirement::Optional);
+    }
+}
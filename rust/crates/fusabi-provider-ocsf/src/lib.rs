@@ -0,0 +1,532 @@
+//! OCSF Type Provider
+//!
+//! Generates Fusabi types for the event classes and objects of the Open
+//! Cybersecurity Schema Framework, so security analytics over normalized
+//! events (such as Hibana's OCSF output) are checked against the standard.
+//!
+//! # Sources
+//!
+//! - An `ocsf://<version>` URI, e.g. `ocsf://1.1.0`: the compiled schema is
+//!   fetched from the schema server's `/export/schema`. `ocsf://latest`
+//!   fetches the server's current version
+//! - A compiled schema file (the same export, saved)
+//! - A checkout of the `ocsf-schema` repository, compiled here; see
+//!   [`repository`]
+//!
+//! The `version` param pins the schema: a source of any other version is
+//! rejected, so a moved `latest` or a checkout on the wrong tag fails loudly
+//! instead of quietly changing the generated types.
+//!
+//! # Generated Types
+//!
+//! Objects become records in `<namespace>.Objects`, and event classes
+//! records in a module per category (`<namespace>.System`,
+//! `<namespace>.Network`, ...); `base_event` stays in `<namespace>`.
+//! Attribute names are kept as they are (`activity_id`, `src_endpoint`),
+//! in name order as the schema server lists them.
+//! Required attributes are plain, recommended and optional ones are
+//! `T option`, and arrays are `T list`. Data types follow the dictionary
+//! down to their base type:
+//!
+//! | OCSF base type | Fusabi                      |
+//! |----------------|-----------------------------|
+//! | `boolean_t`    | `bool`                      |
+//! | `integer_t`    | `int`                       |
+//! | `long_t`       | `int64` (e.g. `timestamp_t`) |
+//! | `float_t`      | `float`                     |
+//! | `string_t`     | `string` (e.g. `ip_t`, `datetime_t`) |
+//! | `json_t`       | `any`                       |
+//! | `object_t`     | the object's record         |
+//!
+//! # Params
+//!
+//! | Param      | Meaning                                                 |
+//! |------------|---------------------------------------------------------|
+//! | `version`  | Required schema version, e.g. `1.1.0`                   |
+//! | `class`    | Comma-separated classes to keep, by name or uid         |
+//! | `category` | Comma-separated categories to keep                      |
+//! | `endpoint` | Schema server URL; defaults to `https://schema.ocsf.io` |
+//!
+//! When classes are filtered, only the objects they reach are generated.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_ocsf::OcsfProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = OcsfProvider::new();
+//! let params = ProviderParams::default().with("category", "network");
+//! let schema = provider.resolve_schema("ocsf://1.1.0", &params)?;
+//! let types = provider.generate_types(&schema, "Ocsf")?;
+//! ```
+
+pub mod export;
+pub mod repository;
+mod types;
+
+pub use export::{is_export, parse_export};
+pub use repository::{is_repository, read_repository};
+pub use types::{Attribute, Definition, OcsfSchema, Requirement, BASE_TYPES};
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceKind, SourceValidator, TypeShape, TypedParams,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// Default schema server
+pub const DEFAULT_ENDPOINT: &str = "https://schema.ocsf.io";
+
+/// OCSF type provider
+pub struct OcsfProvider {
+    generator: TypeGenerator,
+}
+
+impl OcsfProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<OcsfSchema> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected OCSF schema: {}", e)))
+    }
+
+    fn parse_content(&self, content: &str) -> ProviderResult<OcsfSchema> {
+        let value: Value = serde_json::from_str(content)
+            .map_err(|e| ProviderError::ParseError(format!("Invalid OCSF schema: {}", e)))?;
+        parse_export(&value)
+    }
+
+    /// Fetch the compiled schema of a version from the schema server
+    fn fetch_export(&self, uri: &str, params: &ProviderParams, typed: &TypedParams) -> ProviderResult<OcsfSchema> {
+        let version = match uri.trim_start_matches("ocsf://").trim_end_matches('/') {
+            "" => typed.str("version").unwrap_or("latest"),
+            version => version,
+        };
+        let endpoint = typed.str("endpoint").map_or(DEFAULT_ENDPOINT, |e| e.trim_end_matches('/'));
+        let url = if version == "latest" {
+            format!("{}/export/schema", endpoint)
+        } else {
+            format!("{}/export/schema?version={}", endpoint, version)
+        };
+        self.parse_content(&read_source(&url, params)?)
+    }
+
+    /// Generate the object and class modules
+    fn generate(&self, schema: &OcsfSchema, namespace: &str) -> (Vec<GeneratedModule>, Vec<Diagnostic>) {
+        let mut diagnostics = Vec::new();
+        let mut modules: Vec<GeneratedModule> = Vec::new();
+        let mut module_for = |path: Vec<String>| -> usize {
+            match modules.iter().position(|m| m.path == path) {
+                Some(index) => index,
+                None => {
+                    modules.push(GeneratedModule::new(path));
+                    modules.len() - 1
+                }
+            }
+        };
+
+        let objects = module_for(vec![namespace.to_string(), "Objects".to_string()]);
+        let mut placed = Vec::new();
+        for object in &schema.objects {
+            placed.push((objects, self.record(schema, object, &mut diagnostics)));
+        }
+        for class in &schema.classes {
+            let mut path = vec![namespace.to_string()];
+            path.extend(class.category.as_deref().map(|c| self.generator.naming.apply(c)));
+            placed.push((module_for(path), self.record(schema, class, &mut diagnostics)));
+        }
+
+        for (index, record) in placed {
+            modules[index].types.push(TypeDefinition::Record(record));
+        }
+        modules.retain(|m| !m.types.is_empty());
+        (modules, diagnostics)
+    }
+
+    fn record(&self, schema: &OcsfSchema, definition: &Definition, diagnostics: &mut Vec<Diagnostic>) -> RecordDef {
+        let fields = definition
+            .attributes
+            .iter()
+            .map(|attribute| {
+                let location = format!("{}.{}", definition.name, attribute.name);
+                let mut shape = self.attribute_shape(schema, attribute, &location, diagnostics);
+                if attribute.is_array {
+                    shape = TypeShape::List(Box::new(shape));
+                }
+                if attribute.requirement != Requirement::Required {
+                    shape = TypeShape::Option(Box::new(shape));
+                }
+                (attribute.name.clone(), shape.into())
+            })
+            .collect();
+
+        RecordDef {
+            name: self.generator.naming.apply(&definition.name),
+            fields,
+        }
+    }
+
+    fn attribute_shape(
+        &self,
+        schema: &OcsfSchema,
+        attribute: &Attribute,
+        location: &str,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> TypeShape {
+        let named = |name: &str| TypeShape::Named(name.to_string());
+        match schema.base_type(&attribute.type_name) {
+            Some("boolean_t") => named("bool"),
+            Some("integer_t") => named("int"),
+            Some("long_t") => named("int64"),
+            Some("float_t") => named("float"),
+            Some("string_t") => named("string"),
+            Some("json_t") => named("any"),
+            Some("object_t") => match attribute.object_type.as_deref() {
+                Some(object) if schema.object(object).is_some() => named(&self.generator.naming.apply(object)),
+                object => {
+                    diagnostics.push(
+                        Diagnostic::warning(
+                            "object-unresolved",
+                            format!("Object `{}` is not in the schema", object.unwrap_or_default()),
+                        )
+                        .at(location.to_string()),
+                    );
+                    named("any")
+                }
+            },
+            _ => {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        "unmapped-type",
+                        format!("Data type `{}` does not derive from an OCSF base type", attribute.type_name),
+                    )
+                    .at(location.to_string()),
+                );
+                named("any")
+            }
+        }
+    }
+}
+
+impl Default for OcsfProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for OcsfProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("OcsfProvider")
+            .param(
+                ParamSpec::new("version", ParamKind::String)
+                    .describe("Required schema version, e.g. `1.1.0`"),
+            )
+            .param(
+                ParamSpec::new("class", ParamKind::List).describe("Classes to keep, by name or uid"),
+            )
+            .param(ParamSpec::new("category", ParamKind::List).describe("Categories to keep"))
+            .param(
+                ParamSpec::new("endpoint", ParamKind::String)
+                    .describe("Schema server URL for `ocsf://` sources"),
+            )
+    }
+}
+
+impl TypeProvider for OcsfProvider {
+    fn name(&self) -> &str {
+        "OcsfProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let trimmed = source.trim();
+        let mut schema = if trimmed.starts_with("ocsf://") {
+            self.fetch_export(trimmed, params, &typed)?
+        } else if trimmed.starts_with('{') {
+            self.parse_content(source)?
+        } else if Path::new(trimmed).is_dir() {
+            if !is_repository(Path::new(trimmed)) {
+                return Err(ProviderError::InvalidSource(format!(
+                    "{} is not an OCSF schema repository (no dictionary.json and events)",
+                    trimmed
+                )));
+            }
+            read_repository(Path::new(trimmed), params)?
+        } else {
+            self.parse_content(&read_source(source, params)?)?
+        };
+
+        if let Some(pinned) = typed.str("version") {
+            if schema.version.as_deref() != Some(pinned) {
+                return Err(ProviderError::InvalidSource(format!(
+                    "Expected OCSF {}, but the source is {}",
+                    pinned,
+                    schema.version.as_deref().map_or("unversioned".to_string(), |v| format!("OCSF {}", v))
+                )));
+            }
+        }
+
+        let classes = typed.list("class").unwrap_or_default();
+        let categories = typed.list("category").unwrap_or_default();
+        if !classes.is_empty() || !categories.is_empty() {
+            schema.classes.retain(|class| {
+                let uid = class.uid.map(|uid| uid.to_string()).unwrap_or_default();
+                (classes.is_empty() || classes.contains(&class.name.as_str()) || classes.contains(&uid.as_str()))
+                    && (categories.is_empty()
+                        || class.category.as_deref().is_some_and(|c| categories.contains(&c)))
+            });
+            let reachable = reachable_objects(&schema);
+            schema.objects.retain(|object| reachable.contains(&object.name));
+        }
+
+        let json = serde_json::to_string(&schema)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected OCSF schema".to_string()));
+        };
+
+        let mut result = GeneratedTypes::new();
+        result.modules = self.generate(&self.parse_schema(json)?, namespace).0;
+        Ok(result)
+    }
+}
+
+impl SourceValidator for OcsfProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected OCSF schema".to_string()));
+        };
+
+        let parsed = self.parse_schema(json)?;
+        let mut diagnostics = Vec::new();
+        if parsed.classes.is_empty() {
+            diagnostics.push(Diagnostic::warning("no-classes", "No event classes found"));
+        }
+        diagnostics.extend(self.generate(&parsed, "Ocsf").1);
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for OcsfProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .source(SourceKind::Directory)
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+/// Names of the objects the classes use, directly or through other objects
+fn reachable_objects(schema: &OcsfSchema) -> BTreeSet<String> {
+    let mut reachable = BTreeSet::new();
+    let mut pending: Vec<&str> = schema
+        .classes
+        .iter()
+        .flat_map(|c| &c.attributes)
+        .filter_map(|a| a.object_type.as_deref())
+        .collect();
+    while let Some(name) = pending.pop() {
+        if !reachable.insert(name.to_string()) {
+            continue;
+        }
+        if let Some(object) = schema.object(name) {
+            pending.extend(object.attributes.iter().filter_map(|a| a.object_type.as_deref()));
+        }
+    }
+    reachable
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    const EXPORT: &str = r#"{
+        "version": "1.1.0",
+        "types": {
+            "timestamp_t": {"type": "long_t"},
+            "ip_t": {"type": "string_t"},
+            "port_t": {"type": "integer_t"}
+        },
+        "classes": {
+            "base_event": {"name": "base_event", "uid": 0, "attributes": {
+                "time": {"type": "timestamp_t", "requirement": "required"},
+                "unmapped": {"type": "object_t", "object_type": "object"}
+            }},
+            "network_activity": {"name": "network_activity", "uid": 4001, "category": "network", "attributes": {
+                "time": {"type": "timestamp_t", "requirement": "required"},
+                "src_endpoint": {"type": "object_t", "object_type": "network_endpoint", "requirement": "required"},
+                "observables": {"type": "object_t", "object_type": "observable", "is_array": true, "requirement": "recommended"}
+            }},
+            "file_activity": {"name": "file_activity", "uid": 1001, "category": "system", "attributes": {
+                "file": {"type": "object_t", "object_type": "file", "requirement": "required"}
+            }}
+        },
+        "objects": {
+            "network_endpoint": {"name": "network_endpoint", "attributes": {
+                "ip": {"type": "ip_t", "requirement": "recommended"},
+                "port": {"type": "port_t", "requirement": "recommended"},
+                "location": {"type": "object_t", "object_type": "location"}
+            }},
+            "location": {"name": "location", "attributes": {"lat": {"type": "float_t"}}},
+            "observable": {"name": "observable", "attributes": {"value": {"type": "string_t"}, "reputation": {"type": "score_t"}}},
+            "file": {"name": "file", "attributes": {"name": {"type": "string_t", "requirement": "required"}}},
+            "object": {"name": "object", "attributes": {}}
+        }
+    }"#;
+
+    fn record<'a>(types: &'a GeneratedTypes, module: &str, name: &str) -> &'a RecordDef {
+        types
+            .modules
+            .iter()
+            .find(|m| m.path.join(".") == module)
+            .unwrap_or_else(|| panic!("Expected module {}", module))
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+    }
+
+    fn fields(record: &RecordDef) -> Vec<(String, String)> {
+        record.fields.iter().map(|(name, ty)| (name.clone(), ty.to_string())).collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect()
+    }
+
+    #[test]
+    fn test_ocsf_types() {
+        let provider = OcsfProvider::new();
+        let schema = provider.resolve_schema(EXPORT, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Ocsf").unwrap();
+
+        assert_eq!(
+            fields(record(&types, "Ocsf.Network", "NetworkActivity")),
+            pairs(&[
+                ("observables", "Observable list option"),
+                ("src_endpoint", "NetworkEndpoint"),
+                ("time", "int64"),
+            ])
+        );
+        assert_eq!(
+            fields(record(&types, "Ocsf.Objects", "NetworkEndpoint")),
+            pairs(&[("ip", "string option"), ("location", "Location option"), ("port", "int option")])
+        );
+        assert_eq!(fields(record(&types, "Ocsf", "BaseEvent")), pairs(&[("time", "int64"), ("unmapped", "Object option")]));
+        assert_eq!(fields(record(&types, "Ocsf.System", "FileActivity")), pairs(&[("file", "File")]));
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/ocsf.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_filter_classes() {
+        let provider = OcsfProvider::new();
+        let params = ProviderParams::default().with("class", "4001");
+        let schema = provider.resolve_schema(EXPORT, &params).unwrap();
+        let types = provider.generate_types(&schema, "Ocsf").unwrap();
+
+        let names: Vec<String> = types
+            .modules
+            .iter()
+            .flat_map(|m| m.types.iter().map(move |t| match t {
+                TypeDefinition::Record(r) => format!("{}.{}", m.path.join("."), r.name),
+                TypeDefinition::Du(du) => du.name.clone(),
+            }))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "Ocsf.Objects.Location",
+                "Ocsf.Objects.NetworkEndpoint",
+                "Ocsf.Objects.Observable",
+                "Ocsf.Network.NetworkActivity",
+            ]
+        );
+
+        let params = ProviderParams::default().with("category", "system");
+        let schema = provider.resolve_schema(EXPORT, &params).unwrap();
+        let types = provider.generate_types(&schema, "Ocsf").unwrap();
+        assert_eq!(types.modules.len(), 2);
+        assert_eq!(fields(record(&types, "Ocsf.Objects", "File")), pairs(&[("name", "string")]));
+    }
+
+    #[test]
+    fn test_version_pinning() {
+        let provider = OcsfProvider::new();
+        let pinned = ProviderParams::default().with("version", "1.1.0");
+        assert!(provider.resolve_schema(EXPORT, &pinned).is_ok());
+
+        let other = ProviderParams::default().with("version", "1.3.0");
+        let err = provider.resolve_schema(EXPORT, &other).unwrap_err();
+        assert!(err.to_string().contains("Expected OCSF 1.3.0, but the source is OCSF 1.1.0"));
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = OcsfProvider::new();
+        let report = provider.validate_source(EXPORT, &ProviderParams::default());
+        assert!(report.is_valid());
+
+        let codes: Vec<(&str, Option<&str>)> =
+            report.diagnostics.iter().map(|d| (d.code.as_str(), d.location.as_deref())).collect();
+        assert!(codes.contains(&("unmapped-type", Some("observable.reputation"))));
+        assert!(!codes.iter().any(|(code, _)| *code == "unresolved-reference"));
+    }
+
+    #[test]
+    fn test_fetch_version() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 4096];
+            let n = stream.read(&mut buf).unwrap();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                EXPORT.len(),
+                EXPORT
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+
+        let provider = OcsfProvider::new();
+        let params = ProviderParams::default()
+            .with("endpoint", &format!("http://{}/", addr))
+            .with("version", "1.1.0");
+        let schema = provider.resolve_schema("ocsf://1.1.0", &params).unwrap();
+        let types = provider.generate_types(&schema, "Ocsf").unwrap();
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /export/schema?version=1.1.0 "));
+        assert_eq!(fields(record(&types, "Ocsf.System", "FileActivity")), pairs(&[("file", "File")]));
+    }
+}
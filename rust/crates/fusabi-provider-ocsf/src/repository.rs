@@ -0,0 +1,313 @@
+//! OCSF schema repository checkouts
+//!
+//! A checkout of `ocsf-schema` keeps each class and object in its own file
+//! and leaves the compiling to the schema server:
+//!
+//! - `dictionary.json` defines every attribute's type, and the data types
+//!   under `types`
+//! - `objects/*.json` and `events/<category>/*.json` list the attributes a
+//!   definition uses, overriding dictionary entries (usually just the
+//!   `requirement`)
+//! - `extends` names the parent definition, whose attributes come first
+//! - an `$include` entry among the attributes pulls in the attributes of
+//!   profile or include files, by path from the repository root
+//!
+//! Definitions whose name starts with `_` are abstract: they are used as
+//! parents but not returned.
+
+use crate::export::attribute;
+use crate::types::{Attribute, Definition, OcsfSchema};
+use fusabi_provider_common::read_source;
+use fusabi_type_providers::{ProviderError, ProviderParams, ProviderResult};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Whether a directory looks like a schema repository checkout
+pub fn is_repository(dir: &Path) -> bool {
+    dir.join("dictionary.json").is_file() && dir.join("events").is_dir()
+}
+
+/// Compile the classes and objects of a repository checkout
+pub fn read_repository(dir: &Path, params: &ProviderParams) -> ProviderResult<OcsfSchema> {
+    let read_json = |path: &Path| -> ProviderResult<Value> {
+        let content = read_source(&path.to_string_lossy(), params)?;
+        serde_json::from_str(&content)
+            .map_err(|e| ProviderError::ParseError(format!("Invalid JSON in {}: {}", path.display(), e)))
+    };
+
+    let dictionary = read_json(&dir.join("dictionary.json"))?;
+    let mut schema = OcsfSchema::default();
+    let version_file = dir.join("version.json");
+    if version_file.is_file() {
+        schema.version = read_json(&version_file)?.get("version").and_then(Value::as_str).map(String::from);
+    }
+    for (name, data_type) in section(&dictionary, &["types", "attributes"]) {
+        if let Some(base) = data_type.get("type").and_then(Value::as_str) {
+            schema.types.insert(name.clone(), base.to_string());
+        }
+    }
+    let category_uids: BTreeMap<String, i64> = if dir.join("categories.json").is_file() {
+        section(&read_json(&dir.join("categories.json"))?, &["attributes"])
+            .filter_map(|(name, c)| Some((name.clone(), c.get("uid")?.as_i64()?)))
+            .collect()
+    } else {
+        BTreeMap::new()
+    };
+
+    let mut files = Vec::new();
+    collect_json(&dir.join("events"), &mut files)?;
+    let events = load(&files, &read_json)?;
+    files.clear();
+    if dir.join("objects").is_dir() {
+        collect_json(&dir.join("objects"), &mut files)?;
+    }
+    let objects = load(&files, &read_json)?;
+
+    let compiler = Compiler {
+        root: dir,
+        dictionary: section(&dictionary, &["attributes"]).collect(),
+        read_json: &read_json,
+    };
+    for name in events.keys().filter(|n| !n.starts_with('_')) {
+        let mut class = compiler.compile(&events, name, &mut Vec::new())?;
+        if let (Some(uid), Some(category)) = (class.uid, &class.category) {
+            if let Some(category_uid) = category_uids.get(category) {
+                class.uid = Some(category_uid * 1000 + uid);
+            }
+        }
+        schema.classes.push(class);
+    }
+    for name in objects.keys().filter(|n| !n.starts_with('_')) {
+        schema.objects.push(compiler.compile(&objects, name, &mut Vec::new())?);
+    }
+    Ok(schema)
+}
+
+type ReadJson<'a> = dyn Fn(&Path) -> ProviderResult<Value> + 'a;
+
+struct Compiler<'a> {
+    root: &'a Path,
+    dictionary: BTreeMap<&'a String, &'a Value>,
+    read_json: &'a ReadJson<'a>,
+}
+
+impl Compiler<'_> {
+    /// Compile a definition, `stack` holding the definitions extending it
+    fn compile(
+        &self,
+        definitions: &BTreeMap<String, Value>,
+        name: &str,
+        stack: &mut Vec<String>,
+    ) -> ProviderResult<Definition> {
+        if stack.iter().any(|n| n == name) {
+            return Err(ProviderError::ParseError(format!(
+                "OCSF definitions extend each other in a cycle: {} -> {}",
+                stack.join(" -> "),
+                name
+            )));
+        }
+        let raw = &definitions[name];
+
+        let (mut attributes, mut category) = match raw.get("extends").and_then(Value::as_str) {
+            Some(parent) => {
+                if !definitions.contains_key(parent) {
+                    return Err(ProviderError::ParseError(format!(
+                        "`{}` extends unknown definition `{}`",
+                        name, parent
+                    )));
+                }
+                stack.push(name.to_string());
+                let parent = self.compile(definitions, parent, stack)?;
+                stack.pop();
+                let attributes = parent
+                    .attributes
+                    .into_iter()
+                    .map(|a| {
+                        let value = serde_json::to_value(&a).unwrap_or_default();
+                        (a.name, value)
+                    })
+                    .collect();
+                (attributes, parent.category)
+            }
+            None => (Vec::new(), None),
+        };
+        if let Some(own) = raw.get("category").and_then(Value::as_str) {
+            category = Some(own.to_string());
+        }
+        self.merge(raw.get("attributes"), &mut attributes, 0)?;
+
+        let mut attributes: Vec<Attribute> = attributes
+            .iter()
+            .map(|(attr, local)| {
+                let mut merged = self.dictionary.get(attr).map_or_else(Map::new, |d| {
+                    d.as_object().cloned().unwrap_or_default()
+                });
+                if let Some(local) = local.as_object() {
+                    merged.extend(local.iter().map(|(k, v)| (k.clone(), v.clone())));
+                }
+                attribute(name, attr, &Value::Object(merged))
+            })
+            .collect::<ProviderResult<_>>()?;
+        attributes.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(Definition {
+            name: name.to_string(),
+            caption: raw.get("caption").and_then(Value::as_str).map(String::from),
+            category,
+            uid: raw.get("uid").and_then(Value::as_i64),
+            attributes,
+        })
+    }
+
+    /// Overlay an `attributes` map, expanding its `$include` entries first
+    fn merge(&self, own: Option<&Value>, attributes: &mut Vec<(String, Value)>, depth: usize) -> ProviderResult<()> {
+        let Some(own) = own.and_then(Value::as_object) else {
+            return Ok(());
+        };
+        if depth > 8 {
+            return Err(ProviderError::ParseError("OCSF includes are nested too deeply".to_string()));
+        }
+
+        let includes = match own.get("$include") {
+            Some(Value::String(path)) => vec![path.as_str()],
+            Some(Value::Array(paths)) => paths.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        for path in includes {
+            let included = (self.read_json)(&self.root.join(path))?;
+            self.merge(included.get("attributes"), attributes, depth + 1)?;
+        }
+
+        for (name, value) in own.iter().filter(|(name, _)| *name != "$include") {
+            match attributes.iter_mut().find(|(existing, _)| existing == name) {
+                Some((_, existing)) => {
+                    if let (Some(existing), Some(value)) = (existing.as_object_mut(), value.as_object()) {
+                        existing.extend(value.iter().map(|(k, v)| (k.clone(), v.clone())));
+                    }
+                }
+                None => attributes.push((name.clone(), value.clone())),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Definitions by name, from their files
+fn load(files: &[PathBuf], read_json: &ReadJson<'_>) -> ProviderResult<BTreeMap<String, Value>> {
+    let mut definitions = BTreeMap::new();
+    for path in files {
+        let value = read_json(path)?;
+        let name = value
+            .get("name")
+            .and_then(Value::as_str)
+            .map(String::from)
+            .or_else(|| path.file_stem().map(|s| s.to_string_lossy().into_owned()))
+            .unwrap_or_default();
+        definitions.insert(name, value);
+    }
+    Ok(definitions)
+}
+
+/// JSON files under a directory, recursively, in path order
+fn collect_json(dir: &Path, files: &mut Vec<PathBuf>) -> ProviderResult<()> {
+    let entries = std::fs::read_dir(dir).map_err(|e| ProviderError::IoError(format!("{}: {}", dir.display(), e)))?;
+    let mut paths: Vec<PathBuf> = entries.filter_map(Result::ok).map(|entry| entry.path()).collect();
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            collect_json(&path, files)?;
+        } else if path.extension().is_some_and(|e| e == "json") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// The members of a nested JSON object
+fn section<'a>(value: &'a Value, keys: &[&str]) -> impl Iterator<Item = (&'a String, &'a Value)> {
+    keys.iter()
+        .try_fold(value, |v, key| v.get(key))
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Requirement;
+
+    #[test]
+    fn test_read_repository() {
+        let dir = std::env::temp_dir().join(format!("fusabi-ocsf-repo-{}", std::process::id()));
+        let write = |path: &str, content: &str| {
+            let path = dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        write("version.json", r#"{"version": "1.2.0-dev"}"#);
+        write("categories.json", r#"{"attributes": {"system": {"uid": 1}}}"#);
+        write(
+            "dictionary.json",
+            r#"{
+                "attributes": {
+                    "time": {"type": "timestamp_t"},
+                    "message": {"type": "string_t"},
+                    "activity_id": {"type": "integer_t"},
+                    "name": {"type": "string_t"},
+                    "uid": {"type": "string_t"},
+                    "file": {"type": "object_t", "object_type": "file"},
+                    "cloud": {"type": "object_t", "object_type": "cloud"}
+                },
+                "types": {"attributes": {"timestamp_t": {"type": "long_t"}, "string_t": {}}}
+            }"#,
+        );
+        write("profiles/cloud.json", r#"{"name": "cloud", "attributes": {"cloud": {"requirement": "required"}}}"#);
+        write(
+            "events/base_event.json",
+            r#"{"name": "base_event", "uid": 0, "attributes": {
+                "$include": ["profiles/cloud.json"],
+                "time": {"requirement": "required"},
+                "message": {"requirement": "recommended"}
+            }}"#,
+        );
+        write(
+            "events/system/file_activity.json",
+            r#"{"name": "file_activity", "uid": 1, "category": "system", "extends": "base_event", "attributes": {
+                "activity_id": {"requirement": "required"},
+                "file": {"requirement": "required"},
+                "message": {"requirement": "required"}
+            }}"#,
+        );
+        write("objects/_entity.json", r#"{"name": "_entity", "attributes": {"name": {}, "uid": {}}}"#);
+        write("objects/file.json", r#"{"name": "file", "extends": "_entity", "attributes": {"name": {"requirement": "required"}}}"#);
+        write("objects/cloud.json", r#"{"name": "cloud", "attributes": {"uid": {}}}"#);
+
+        let schema = read_repository(&dir, &ProviderParams::default()).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(schema.version.as_deref(), Some("1.2.0-dev"));
+        let class = schema.classes.iter().find(|c| c.name == "file_activity").unwrap();
+        assert_eq!(class.uid, Some(1001));
+        let attributes: Vec<(&str, Requirement)> =
+            class.attributes.iter().map(|a| (a.name.as_str(), a.requirement)).collect();
+        assert_eq!(
+            attributes,
+            [
+                ("activity_id", Requirement::Required),
+                ("cloud", Requirement::Required),
+                ("file", Requirement::Required),
+                ("message", Requirement::Required),
+                ("time", Requirement::Required),
+            ]
+        );
+        assert_eq!(class.attributes[4].type_name, "timestamp_t");
+
+        let names: Vec<&str> = schema.objects.iter().map(|o| o.name.as_str()).collect();
+        assert_eq!(names, ["cloud", "file"]);
+        let file = schema.object("file").unwrap();
+        assert_eq!(file.attributes[0].requirement, Requirement::Required);
+        assert_eq!(file.attributes[1].name, "uid");
+    }
+}
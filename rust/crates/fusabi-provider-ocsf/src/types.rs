@@ -0,0 +1,122 @@
+//! OCSF schema types
+//!
+//! Both source formats are compiled into this model: inheritance and
+//! includes are already applied, so every class and object lists all of
+//! its attributes.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The OCSF base types every other data type derives from
+pub const BASE_TYPES: &[&str] = &[
+    "boolean_t",
+    "integer_t",
+    "long_t",
+    "float_t",
+    "string_t",
+    "json_t",
+    "object_t",
+];
+
+/// A compiled OCSF schema
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OcsfSchema {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Derived data types by name, mapped to the type they derive from,
+    /// e.g. `timestamp_t` → `long_t`
+    #[serde(default)]
+    pub types: BTreeMap<String, String>,
+    pub classes: Vec<Definition>,
+    pub objects: Vec<Definition>,
+}
+
+/// An event class or object
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Definition {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub caption: Option<String>,
+    /// The category of an event class, e.g. `system`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uid: Option<i64>,
+    pub attributes: Vec<Attribute>,
+}
+
+/// An attribute of a class or object
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attribute {
+    pub name: String,
+    /// Data type name, e.g. `string_t` or `object_t`
+    #[serde(rename = "type")]
+    pub type_name: String,
+    /// The object an `object_t` attribute holds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object_type: Option<String>,
+    #[serde(default)]
+    pub is_array: bool,
+    #[serde(default)]
+    pub requirement: Requirement,
+}
+
+/// How strongly an attribute is expected to be present
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Requirement {
+    Required,
+    Recommended,
+    #[default]
+    Optional,
+}
+
+impl Requirement {
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "required" => Self::Required,
+            "recommended" => Self::Recommended,
+            _ => Self::Optional,
+        }
+    }
+}
+
+impl OcsfSchema {
+    /// The base type a data type derives from, if it is known
+    pub fn base_type<'a>(&'a self, type_name: &'a str) -> Option<&'a str> {
+        let mut current = type_name;
+        // Derivation chains are one or two steps; the bound stops cycles
+        for _ in 0..8 {
+            if BASE_TYPES.contains(&current) {
+                return Some(current);
+            }
+            current = self.types.get(current)?;
+        }
+        None
+    }
+
+    pub fn object(&self, name: &str) -> Option<&Definition> {
+        self.objects.iter().find(|o| o.name == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_type() {
+        let schema = OcsfSchema {
+            types: BTreeMap::from([
+                ("timestamp_t".to_string(), "long_t".to_string()),
+                ("port_t".to_string(), "integer_t".to_string()),
+                ("loop_t".to_string(), "loop_t".to_string()),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(schema.base_type("timestamp_t"), Some("long_t"));
+        assert_eq!(schema.base_type("string_t"), Some("string_t"));
+        assert_eq!(schema.base_type("email_t"), None);
+        assert_eq!(schema.base_type("loop_t"), None);
+    }
+}
@@ -0,0 +1,604 @@
+//! OpenAPI Type Provider
+//!
+//! Generates Fusabi types from OpenAPI 3.x and Swagger 2.0 documents, in
+//! JSON or YAML.
+//!
+//! # Generated types
+//!
+//! - `<Namespace>.Schemas` - one type per component schema
+//!   (`components.schemas` or Swagger `definitions`)
+//! - `<Namespace>.<Tag>` - per-operation types, grouped by the operation's
+//!   first tag (untagged operations go to `<Namespace>.Operations`):
+//!   - `<Op>Request` - a record with one field per parameter, plus `body`
+//!     when the operation takes a request body
+//!   - `<Op>Response` - a union with one case per documented status code,
+//!     such as `Ok of Pet` or `NotFound`
+//!
+//! `<Op>` is the PascalCase `operationId`, or a name derived from the method
+//! and path (`GET /pets/{id}` becomes `GetPetsById`). Inline object bodies
+//! get their own records (`<Op>Body`, `<Op><Status>Body`).
+//!
+//! # Params
+//!
+//! | Param   | Meaning                                                        |
+//! |---------|----------------------------------------------------------------|
+//! | `paths` | Comma-separated paths to keep; `/pets*` matches by prefix       |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_openapi::OpenApiProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = OpenApiProvider::new();
+//! let params = ProviderParams::default().with("paths", "/pets*");
+//! let schema = provider.resolve_schema("petstore.yaml", &params)?;
+//! let types = provider.generate_types(&schema, "Petstore")?;
+//! ```
+
+mod operations;
+
+use fusabi_jsonschema_core::{definitions_of, Converter};
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceValidator, TypeShape,
+};
+use fusabi_type_providers::{
+    DuDef, GeneratedModule, GeneratedTypes, NamingStrategy, ProviderError, ProviderParams,
+    ProviderResult, RecordDef, Schema, TypeDefinition, TypeExpr, TypeGenerator, TypeProvider,
+    VariantDef,
+};
+use operations::{content_schema, filter_paths, operations, resolve, Operation};
+use serde_json::Value;
+
+/// Module for operations without tags
+const UNTAGGED_MODULE: &str = "Operations";
+
+/// Module for component schemas
+const SCHEMAS_MODULE: &str = "Schemas";
+
+/// OpenAPI 3.x / Swagger 2.0 type provider
+pub struct OpenApiProvider {
+    converter: Converter,
+    generator: TypeGenerator,
+}
+
+impl OpenApiProvider {
+    pub fn new() -> Self {
+        Self {
+            converter: Converter::new(),
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    /// Parse a JSON or YAML document and check that it is OpenAPI
+    fn parse_document(&self, content: &str) -> ProviderResult<Value> {
+        // YAML is a superset of JSON, so one parser covers both
+        let document: Value = serde_yaml::from_str(content)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+
+        let is_openapi_3 = document
+            .get("openapi")
+            .and_then(Value::as_str)
+            .is_some_and(|v| v.starts_with("3."));
+        let is_swagger_2 = document.get("swagger").and_then(Value::as_str) == Some("2.0");
+        if !is_openapi_3 && !is_swagger_2 {
+            return Err(ProviderError::InvalidSource(
+                "Expected an OpenAPI 3.x or Swagger 2.0 document".to_string(),
+            ));
+        }
+
+        Ok(document)
+    }
+
+    fn generate_document(&self, document: &Value, namespace: &str) -> GeneratedTypes {
+        let mut result = GeneratedTypes::new();
+
+        if let Some(definitions) = definitions_of(document) {
+            let mut module = GeneratedModule::new(vec![namespace.to_string(), SCHEMAS_MODULE.to_string()]);
+            module.types = self.converter.definitions(definitions);
+            if !module.types.is_empty() {
+                result.modules.push(module);
+            }
+        }
+
+        for operation in operations(document) {
+            let module_name = operation
+                .tag()
+                .map(|tag| self.generator.naming.apply(tag))
+                .unwrap_or_else(|| UNTAGGED_MODULE.to_string());
+            let path = vec![namespace.to_string(), module_name];
+
+            let index = match result.modules.iter().position(|m| m.path == path) {
+                Some(index) => index,
+                None => {
+                    result.modules.push(GeneratedModule::new(path));
+                    result.modules.len() - 1
+                }
+            };
+
+            let types = self.operation_types(document, &operation);
+            result.modules[index].types.extend(types);
+        }
+
+        result
+    }
+
+    /// Request and response types for one operation
+    fn operation_types(&self, document: &Value, operation: &Operation) -> Vec<TypeDefinition> {
+        let name = self.generator.naming.apply(&operation.raw_name());
+        let mut types = Vec::new();
+        let mut fields = Vec::new();
+        let mut body = None;
+
+        for parameter in operation.parameters(document) {
+            let Some(param_name) = parameter.get("name").and_then(Value::as_str) else {
+                continue;
+            };
+            let location = parameter.get("in").and_then(Value::as_str).unwrap_or("query");
+            let required = location == "path"
+                || parameter.get("required").and_then(Value::as_bool) == Some(true);
+
+            if location == "body" {
+                // Swagger 2.0 request body
+                if let Some(schema) = parameter.get("schema") {
+                    body = Some((self.payload_type(schema, &format!("{}Body", name), &mut types), required));
+                }
+                continue;
+            }
+
+            if fields.iter().any(|(existing, _)| existing == param_name) {
+                continue;
+            }
+
+            // OpenAPI 3 nests the schema; Swagger 2.0 puts it on the parameter
+            let schema = parameter.get("schema").unwrap_or(parameter);
+            fields.push((param_name.to_string(), optional(self.converter.type_expr(schema), required)));
+        }
+
+        if let Some(request_body) = operation.operation.get("requestBody") {
+            let request_body = resolve(document, request_body);
+            if let Some(schema) = request_body.get("content").and_then(content_schema) {
+                let required = request_body.get("required").and_then(Value::as_bool) == Some(true);
+                body = Some((self.payload_type(schema, &format!("{}Body", name), &mut types), required));
+            }
+        }
+
+        if let Some((body_type, required)) = body {
+            fields.push(("body".to_string(), optional(body_type, required)));
+        }
+
+        types.push(TypeDefinition::Record(RecordDef {
+            name: format!("{}Request", name),
+            fields,
+        }));
+
+        if let Some(responses) = operation.operation.get("responses").and_then(Value::as_object) {
+            let mut variants = Vec::new();
+            for (status, response) in responses {
+                let response = resolve(document, response);
+                let variant_name = status_variant(status);
+
+                // OpenAPI 3 uses `content`; Swagger 2.0 puts `schema` on the response
+                let schema = response
+                    .get("content")
+                    .and_then(content_schema)
+                    .or_else(|| response.get("schema"));
+
+                variants.push(match schema {
+                    Some(schema) => {
+                        let payload_name = format!("{}{}Body", name, variant_name);
+                        VariantDef::new(variant_name, vec![self.payload_type(schema, &payload_name, &mut types)])
+                    }
+                    None => VariantDef::new_simple(variant_name),
+                });
+            }
+
+            if !variants.is_empty() {
+                types.push(TypeDefinition::Du(DuDef {
+                    name: format!("{}Response", name),
+                    variants,
+                }));
+            }
+        }
+
+        types
+    }
+
+    /// Type of a request or response payload
+    ///
+    /// Inline objects, enums and unions become named types of their own.
+    fn payload_type(&self, schema: &Value, name: &str, types: &mut Vec<TypeDefinition>) -> TypeExpr {
+        match self.converter.definition(name, schema) {
+            Some(definition) => {
+                let type_name = match &definition {
+                    TypeDefinition::Record(record) => record.name.clone(),
+                    TypeDefinition::Du(du) => du.name.clone(),
+                };
+                types.push(definition);
+                TypeShape::named(type_name).into()
+            }
+            None => self.converter.type_expr(schema),
+        }
+    }
+}
+
+impl Default for OpenApiProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for OpenApiProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("OpenApiProvider")
+            .param(
+                ParamSpec::new("paths", ParamKind::List)
+                    .describe("Paths to keep; `/pets*` matches by prefix"),
+            )
+    }
+}
+
+impl TypeProvider for OpenApiProvider {
+    fn name(&self) -> &str {
+        "OpenApiProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let trimmed = source.trim_start();
+        let content = if trimmed.starts_with('{')
+            || trimmed.starts_with("openapi:")
+            || trimmed.starts_with("swagger:")
+        {
+            source.to_string()
+        } else {
+            read_source(source, params)?
+        };
+
+        let mut document = self.parse_document(&content)?;
+        if let Some(filter) = typed.str("paths") {
+            filter_paths(&mut document, filter);
+        }
+
+        Ok(Schema::OpenApi(document))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        match schema {
+            Schema::OpenApi(document) => Ok(self.generate_document(document, namespace)),
+            _ => Err(ProviderError::ParseError("Expected OpenAPI schema".to_string())),
+        }
+    }
+}
+
+impl SourceValidator for OpenApiProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::OpenApi(document) = schema else {
+            return Err(ProviderError::ParseError("Expected OpenAPI schema".to_string()));
+        };
+
+        let mut diagnostics = Vec::new();
+
+        if let Some(webhooks) = document.get("webhooks").and_then(Value::as_object) {
+            for name in webhooks.keys() {
+                diagnostics.push(
+                    Diagnostic::info("webhook-skipped", format!("Webhook `{}` does not generate types", name))
+                        .at(format!("webhooks.{}", name)),
+                );
+            }
+        }
+
+        for operation in operations(document) {
+            let location = format!("{} {}", operation.method.to_uppercase(), operation.path);
+            if let Some(callbacks) = operation.operation.get("callbacks").and_then(Value::as_object) {
+                for name in callbacks.keys() {
+                    diagnostics.push(
+                        Diagnostic::info("callback-skipped", format!("Callback `{}` does not generate types", name))
+                            .at(location.clone()),
+                    );
+                }
+            }
+        }
+
+        let mut external = Vec::new();
+        external_refs(document, &mut external);
+        for reference in external {
+            diagnostics.push(
+                Diagnostic::warning(
+                    "ref-unresolved",
+                    format!("External reference `{}` is not loaded; it is typed by name only", reference),
+                )
+                .at(reference),
+            );
+        }
+
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for OpenApiProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+/// Case name for a response status code
+fn status_variant(status: &str) -> String {
+    let name = match status {
+        "default" => "Default",
+        "200" => "Ok",
+        "201" => "Created",
+        "202" => "Accepted",
+        "204" => "NoContent",
+        "301" => "MovedPermanently",
+        "302" => "Found",
+        "304" => "NotModified",
+        "400" => "BadRequest",
+        "401" => "Unauthorized",
+        "403" => "Forbidden",
+        "404" => "NotFound",
+        "405" => "MethodNotAllowed",
+        "409" => "Conflict",
+        "410" => "Gone",
+        "422" => "UnprocessableEntity",
+        "429" => "TooManyRequests",
+        "500" => "InternalServerError",
+        "502" => "BadGateway",
+        "503" => "ServiceUnavailable",
+        "504" => "GatewayTimeout",
+        _ => return format!("Status{}", status.to_uppercase()),
+    };
+    name.to_string()
+}
+
+/// Mark a type optional unless the value is required
+fn optional(type_expr: TypeExpr, required: bool) -> TypeExpr {
+    let shape = TypeShape::from_type_expr(&type_expr);
+    if required || shape.is_option() {
+        type_expr
+    } else {
+        TypeShape::Option(Box::new(shape)).into()
+    }
+}
+
+/// Collect `$ref`s that point outside the document
+fn external_refs(value: &Value, refs: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(reference) = map.get("$ref").and_then(Value::as_str) {
+                if !reference.starts_with('#') && !refs.iter().any(|r| r == reference) {
+                    refs.push(reference.to_string());
+                }
+            }
+            map.values().for_each(|v| external_refs(v, refs));
+        }
+        Value::Array(items) => items.iter().for_each(|v| external_refs(v, refs)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    const PETSTORE: &str = r##"
+openapi: 3.0.3
+info:
+  title: Petstore
+  version: 1.0.0
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      tags: [pets]
+      parameters:
+        - name: limit
+          in: query
+          schema:
+            type: integer
+      responses:
+        "200":
+          description: A page of pets
+          content:
+            application/json:
+              schema:
+                type: array
+                items:
+                  $ref: "#/components/schemas/Pet"
+        default:
+          $ref: "#/components/responses/Error"
+    post:
+      operationId: createPet
+      tags: [pets]
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema:
+              type: object
+              required: [name]
+              properties:
+                name:
+                  type: string
+                tag:
+                  type: string
+      responses:
+        "201":
+          description: Created
+  /health:
+    get:
+      responses:
+        "204":
+          description: Healthy
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [id, name]
+      properties:
+        id:
+          type: integer
+          format: int64
+        name:
+          type: string
+  responses:
+    Error:
+      description: Unexpected error
+      content:
+        application/json:
+          schema:
+            $ref: "#/components/schemas/Error"
+"##;
+
+    fn find<'a>(types: &'a GeneratedTypes, module: &[&str], name: &str) -> &'a TypeDefinition {
+        types
+            .modules
+            .iter()
+            .find(|m| m.path == module)
+            .and_then(|m| {
+                m.types.iter().find(|t| match t {
+                    TypeDefinition::Record(r) => r.name == name,
+                    TypeDefinition::Du(d) => d.name == name,
+                })
+            })
+            .unwrap_or_else(|| panic!("{} not found in {:?}", name, module))
+    }
+
+    #[test]
+    fn test_components_and_operations() {
+        let provider = OpenApiProvider::new();
+        let schema = provider.resolve_schema(PETSTORE, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Petstore").unwrap();
+
+        let TypeDefinition::Record(pet) = find(&types, &["Petstore", "Schemas"], "Pet") else {
+            panic!("Expected record");
+        };
+        assert_eq!(pet.fields.len(), 2);
+
+        let TypeDefinition::Record(request) = find(&types, &["Petstore", "Pets"], "ListPetsRequest") else {
+            panic!("Expected record");
+        };
+        assert_eq!(request.fields[0].0, "limit");
+        assert_eq!(request.fields[0].1.to_string(), "int option");
+
+        let TypeDefinition::Du(response) = find(&types, &["Petstore", "Pets"], "ListPetsResponse") else {
+            panic!("Expected union");
+        };
+        assert_eq!(response.variants[0].name, "Ok");
+        assert_eq!(response.variants[0].fields[0].to_string(), "Pet list");
+        assert_eq!(response.variants[1].name, "Default");
+        assert_eq!(response.variants[1].fields[0].to_string(), "Error");
+
+        let TypeDefinition::Record(create) = find(&types, &["Petstore", "Pets"], "CreatePetRequest") else {
+            panic!("Expected record");
+        };
+        assert_eq!(create.fields.len(), 1);
+        assert_eq!(create.fields[0].0, "body");
+        assert_eq!(create.fields[0].1.to_string(), "CreatePetBody");
+        find(&types, &["Petstore", "Pets"], "CreatePetBody");
+
+        find(&types, &["Petstore", "Operations"], "GetHealthResponse");
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/openapi.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_swagger_2() {
+        let document = r##"{
+            "swagger": "2.0",
+            "paths": {
+                "/users/{id}": {
+                    "put": {
+                        "parameters": [
+                            { "name": "id", "in": "path", "type": "string" },
+                            { "name": "user", "in": "body", "schema": { "$ref": "#/definitions/User" } }
+                        ],
+                        "responses": {
+                            "200": { "description": "ok", "schema": { "$ref": "#/definitions/User" } },
+                            "404": { "description": "missing" }
+                        }
+                    }
+                }
+            },
+            "definitions": {
+                "User": { "type": "object", "properties": { "email": { "type": "string" } } }
+            }
+        }"##;
+
+        let provider = OpenApiProvider::new();
+        let schema = provider.resolve_schema(document, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Api").unwrap();
+
+        find(&types, &["Api", "Schemas"], "User");
+        let TypeDefinition::Record(request) = find(&types, &["Api", "Operations"], "PutUsersByIdRequest") else {
+            panic!("Expected record");
+        };
+        assert_eq!(request.fields[0].0, "id");
+        assert_eq!(request.fields[0].1.to_string(), "string");
+        assert_eq!(request.fields[1].0, "body");
+        assert_eq!(request.fields[1].1.to_string(), "User option");
+
+        let TypeDefinition::Du(response) = find(&types, &["Api", "Operations"], "PutUsersByIdResponse") else {
+            panic!("Expected union");
+        };
+        assert_eq!(response.variants[1].name, "NotFound");
+        assert!(response.variants[1].fields.is_empty());
+    }
+
+    #[test]
+    fn test_paths_filter() {
+        let provider = OpenApiProvider::new();
+        let params = ProviderParams::default().with("paths", "/health");
+        let schema = provider.resolve_schema(PETSTORE, &params).unwrap();
+        let types = provider.generate_types(&schema, "Petstore").unwrap();
+
+        assert!(types.modules.iter().all(|m| m.path != ["Petstore", "Pets"]));
+        assert!(types.modules.iter().any(|m| m.path == ["Petstore", "Operations"]));
+    }
+
+    #[test]
+    fn test_rejects_non_openapi() {
+        let provider = OpenApiProvider::new();
+        let err = provider
+            .resolve_schema(r#"{"type": "object"}"#, &ProviderParams::default())
+            .unwrap_err();
+        assert!(err.to_string().contains("OpenAPI"));
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let document = r#"{
+            "openapi": "3.1.0",
+            "webhooks": { "newPet": {} },
+            "paths": {
+                "/pets": {
+                    "post": {
+                        "callbacks": { "onCreated": {} },
+                        "responses": {
+                            "200": {
+                                "description": "ok",
+                                "content": { "application/json": { "schema": { "$ref": "common.yaml#/Pet" } } }
+                            }
+                        }
+                    }
+                }
+            }
+        }"#;
+
+        let report = OpenApiProvider::new().validate_source(document, &ProviderParams::default());
+        assert!(report.is_valid());
+        let codes: Vec<&str> = report.diagnostics.iter().map(|d| d.code.as_str()).collect();
+        assert!(codes.contains(&"webhook-skipped"));
+        assert!(codes.contains(&"callback-skipped"));
+        assert!(codes.contains(&"ref-unresolved"));
+    }
+}
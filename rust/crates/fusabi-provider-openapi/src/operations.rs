@@ -0,0 +1,222 @@
+//! Operation walking for OpenAPI and Swagger documents
+
+use serde_json::Value;
+
+/// HTTP methods that can carry an operation in a path item
+pub const METHODS: [&str; 8] = ["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+
+/// An operation together with its location in the document
+pub struct Operation<'a> {
+    /// Path template, such as `/pets/{petId}`
+    pub path: &'a str,
+    /// Lowercase HTTP method
+    pub method: &'a str,
+    /// The operation object
+    pub operation: &'a Value,
+    /// Parameters declared on the path item, shared by all its operations
+    pub path_parameters: &'a [Value],
+}
+
+impl<'a> Operation<'a> {
+    /// `operationId`, or a name derived from the method and path
+    pub fn raw_name(&self) -> String {
+        if let Some(id) = self.operation.get("operationId").and_then(Value::as_str) {
+            return id.to_string();
+        }
+
+        let mut name = self.method.to_string();
+        for segment in self.path.split('/').filter(|s| !s.is_empty()) {
+            match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+                Some(param) => {
+                    name.push_str("_by_");
+                    name.push_str(param);
+                }
+                None => {
+                    name.push('_');
+                    name.push_str(segment);
+                }
+            }
+        }
+        name
+    }
+
+    /// First tag, used to group operations into modules
+    pub fn tag(&self) -> Option<&'a str> {
+        self.operation
+            .get("tags")
+            .and_then(Value::as_array)
+            .and_then(|tags| tags.first())
+            .and_then(Value::as_str)
+    }
+
+    /// Path-level and operation-level parameters, resolved
+    ///
+    /// Operation parameters override path parameters with the same name and
+    /// location.
+    pub fn parameters(&self, document: &'a Value) -> Vec<&'a Value> {
+        let own: Vec<&Value> = self
+            .operation
+            .get("parameters")
+            .and_then(Value::as_array)
+            .map(|params| params.iter().map(|p| resolve(document, p)).collect())
+            .unwrap_or_default();
+
+        let mut parameters: Vec<&Value> = self
+            .path_parameters
+            .iter()
+            .map(|p| resolve(document, p))
+            .filter(|p| !own.iter().any(|o| same_parameter(o, p)))
+            .collect();
+        parameters.extend(own);
+        parameters
+    }
+}
+
+/// Every operation in the document
+pub fn operations(document: &Value) -> Vec<Operation<'_>> {
+    let Some(paths) = document.get("paths").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+
+    let mut result = Vec::new();
+    for (path, item) in paths {
+        let item = resolve(document, item);
+        let path_parameters = item
+            .get("parameters")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or(&[]);
+
+        for method in METHODS {
+            if let Some(operation) = item.get(method) {
+                result.push(Operation {
+                    path,
+                    method,
+                    operation,
+                    path_parameters,
+                });
+            }
+        }
+    }
+    result
+}
+
+/// Follow local `$ref`s (`#/components/...`), giving up after a few hops
+pub fn resolve<'a>(document: &'a Value, value: &'a Value) -> &'a Value {
+    let mut current = value;
+    for _ in 0..8 {
+        let Some(target) = current
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|r| r.strip_prefix('#'))
+            .and_then(|pointer| document.pointer(pointer))
+        else {
+            break;
+        };
+        current = target;
+    }
+    current
+}
+
+/// Pick the schema of the preferred media type in a `content` map
+///
+/// JSON media types win; otherwise the first entry is used.
+pub fn content_schema(content: &Value) -> Option<&Value> {
+    let content = content.as_object()?;
+    let is_json = |media: &str| {
+        let media = media.split(';').next().unwrap_or(media).trim();
+        media == "application/json" || media.ends_with("+json") || media == "*/*"
+    };
+
+    content
+        .iter()
+        .find(|(media, _)| is_json(media))
+        .or_else(|| content.iter().next())
+        .and_then(|(_, entry)| entry.get("schema"))
+}
+
+/// Keep only the paths matched by a comma-separated filter
+///
+/// Entries ending in `*` match by prefix; other entries must match exactly.
+pub fn filter_paths(document: &mut Value, filter: &str) {
+    let patterns: Vec<&str> = filter.split(',').map(str::trim).filter(|p| !p.is_empty()).collect();
+    if patterns.is_empty() {
+        return;
+    }
+
+    if let Some(paths) = document.get_mut("paths").and_then(Value::as_object_mut) {
+        paths.retain(|path, _| {
+            patterns.iter().any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => path.starts_with(prefix),
+                None => path == pattern,
+            })
+        });
+    }
+}
+
+fn same_parameter(a: &Value, b: &Value) -> bool {
+    a.get("name") == b.get("name") && a.get("in") == b.get("in")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_operation_names_and_parameters() {
+        let document = json!({
+            "components": {
+                "parameters": {
+                    "Limit": { "name": "limit", "in": "query", "schema": { "type": "integer" } }
+                }
+            },
+            "paths": {
+                "/pets/{petId}": {
+                    "parameters": [
+                        { "name": "petId", "in": "path", "required": true, "schema": { "type": "string" } },
+                        { "$ref": "#/components/parameters/Limit" }
+                    ],
+                    "get": {
+                        "parameters": [
+                            { "name": "petId", "in": "path", "required": true, "schema": { "type": "integer" } }
+                        ]
+                    },
+                    "delete": { "operationId": "removePet" }
+                }
+            }
+        });
+
+        let ops = operations(&document);
+        assert_eq!(ops.len(), 2);
+        assert_eq!(ops[0].raw_name(), "get_pets_by_petId");
+        assert_eq!(ops[1].raw_name(), "removePet");
+
+        let params = ops[0].parameters(&document);
+        assert_eq!(params.len(), 2);
+        assert_eq!(params[0]["name"], "limit");
+        assert_eq!(params[1]["schema"]["type"], "integer");
+    }
+
+    #[test]
+    fn test_filter_paths() {
+        let mut document = json!({
+            "paths": { "/pets": {}, "/pets/{id}": {}, "/stores": {}, "/users": {} }
+        });
+
+        filter_paths(&mut document, "/pets*, /users");
+        let paths: Vec<&String> = document["paths"].as_object().unwrap().keys().collect();
+        assert_eq!(paths, ["/pets", "/pets/{id}", "/users"]);
+    }
+
+    #[test]
+    fn test_content_schema_prefers_json() {
+        let content = json!({
+            "text/plain": { "schema": { "type": "string" } },
+            "application/problem+json": { "schema": { "$ref": "#/components/schemas/Problem" } }
+        });
+
+        let schema = content_schema(&content).unwrap();
+        assert_eq!(schema["$ref"], "#/components/schemas/Problem");
+    }
+}
@@ -0,0 +1,390 @@
+//! OpenFeature Type Provider
+//!
+//! Generates Fusabi types from a feature-flag manifest, so programs
+//! evaluating flags through OpenFeature can only name flags that exist and
+//! only compare against variants the flag defines.
+//!
+//! # Sources
+//!
+//! A flagd flag definition file or an OpenFeature CLI manifest, in YAML or
+//! JSON, inline or from a file or URL; see [`manifest`].
+//!
+//! # Generated Types
+//!
+//! - `Flags`: one field per flag with the type its evaluation returns
+//!   (`bool`, `string`, `int`, `float`, or `Map<string, any>` for object
+//!   flags). Keys become field names with punctuation replaced by `_`.
+//! - `<Flag>Variant`: a union of each flagd flag's variants, e.g.
+//!   `NewWelcomeBannerVariant = On | Off`
+//!
+//! A flag's type is its `flagType`, else the common type of its variants,
+//! else the type of its `defaultValue`.
+//!
+//! # Params
+//!
+//! | Param    | Meaning                                           |
+//! |----------|---------------------------------------------------|
+//! | `record` | Name of the flags record; defaults to `Flags`      |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_openfeature::OpenFeatureProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = OpenFeatureProvider::new();
+//! let schema = provider.resolve_schema("flags.flagd.json", &ProviderParams::default())?;
+//! let types = provider.generate_types(&schema, "Features")?;
+//! ```
+
+pub mod manifest;
+
+pub use manifest::{parse_manifest, FlagDef, FlagManifest, FlagType, Variant};
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceValidator, TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, DuDef, VariantDef, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use serde::{Deserialize, Serialize};
+
+/// Default name of the flags record
+pub const DEFAULT_RECORD: &str = "Flags";
+
+/// The manifest and record name, stored in [`Schema::Custom`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ResolvedManifest {
+    record: String,
+    manifest: FlagManifest,
+}
+
+/// OpenFeature flag manifest type provider
+pub struct OpenFeatureProvider {
+    generator: TypeGenerator,
+}
+
+impl OpenFeatureProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<ResolvedManifest> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected flag manifest: {}", e)))
+    }
+
+    /// Generate the variant unions and the flags record
+    fn generate(&self, resolved: &ResolvedManifest) -> (Vec<TypeDefinition>, Vec<Diagnostic>) {
+        let mut types = Vec::new();
+        let mut diagnostics = Vec::new();
+        let mut fields = Vec::new();
+
+        if resolved.manifest.flags.is_empty() {
+            diagnostics.push(Diagnostic::warning("no-flags", "The manifest defines no flags"));
+        }
+        for flag in &resolved.manifest.flags {
+            let location = format!("flags.{}", flag.key);
+            let variant_type = flag.variant_type();
+            if !flag.variants.is_empty() && variant_type.is_none() {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        "variant-type-mismatch",
+                        "Variants have different value types; the flag is typed `any`",
+                    )
+                    .at(location.clone()),
+                );
+            }
+            if let (Some(declared), Some(default)) = (flag.declared_type, flag.default_type) {
+                if declared != default && (declared, default) != (FlagType::Float, FlagType::Integer) {
+                    diagnostics.push(
+                        Diagnostic::warning(
+                            "default-type-mismatch",
+                            format!("`defaultValue` does not match flagType `{}`", declared.name()),
+                        )
+                        .at(location.clone()),
+                    );
+                }
+            }
+            if let Some(default) = &flag.default_variant {
+                if !flag.variants.iter().any(|v| &v.name == default) {
+                    diagnostics.push(
+                        Diagnostic::warning(
+                            "default-variant-unknown",
+                            format!("Default variant `{}` is not one of the flag's variants", default),
+                        )
+                        .at(location.clone()),
+                    );
+                }
+            }
+            if !flag.enabled {
+                diagnostics.push(
+                    Diagnostic::info("flag-disabled", "Flag is disabled and always evaluates to its default")
+                        .at(location),
+                );
+            }
+
+            if !flag.variants.is_empty() {
+                types.push(TypeDefinition::Du(DuDef {
+                    name: self.variant_union(flag),
+                    variants: flag
+                        .variants
+                        .iter()
+                        .map(|variant| VariantDef::new_simple(self.generator.naming.apply(&variant.name)))
+                        .collect(),
+                }));
+            }
+            let flag_type = flag
+                .declared_type
+                .or(variant_type)
+                .or(if flag.variants.is_empty() { flag.default_type } else { None })
+                .map_or("any", FlagType::fusabi_type);
+            fields.push((field_name(&flag.key), TypeShape::parse(flag_type).into()));
+        }
+
+        types.push(TypeDefinition::Record(RecordDef {
+            name: resolved.record.clone(),
+            fields,
+        }));
+        (types, diagnostics)
+    }
+
+    /// Name of a flag's variant union
+    fn variant_union(&self, flag: &FlagDef) -> String {
+        format!("{}Variant", self.generator.naming.apply(&field_name(&flag.key)))
+    }
+}
+
+/// Field name for a flag key: `kebab-case`, dots and other punctuation
+/// become `_`
+fn field_name(key: &str) -> String {
+    key.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect()
+}
+
+impl Default for OpenFeatureProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for OpenFeatureProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("OpenFeatureProvider")
+            .param(
+                ParamSpec::new("record", ParamKind::String)
+                    .default(DEFAULT_RECORD)
+                    .describe("Name of the flags record"),
+            )
+    }
+}
+
+impl TypeProvider for OpenFeatureProvider {
+    fn name(&self) -> &str {
+        "OpenFeatureProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let trimmed = source.trim();
+        let manifest = if trimmed.starts_with('{') || trimmed.contains('\n') {
+            parse_manifest(source)?
+        } else {
+            parse_manifest(&read_source(source, params)?)?
+        };
+
+        let resolved = ResolvedManifest {
+            record: typed.str("record").unwrap_or(DEFAULT_RECORD).to_string(),
+            manifest,
+        };
+        let json = serde_json::to_string(&resolved)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected flag manifest".to_string()));
+        };
+
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+        module.types = self.generate(&self.parse_schema(json)?).0;
+
+        let mut result = GeneratedTypes::new();
+        result.modules.push(module);
+        Ok(result)
+    }
+}
+
+impl SourceValidator for OpenFeatureProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected flag manifest".to_string()));
+        };
+
+        Ok(self.generate(&self.parse_schema(json)?).1)
+    }
+}
+
+impl DescribedProvider for OpenFeatureProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    const FLAGD: &str = r##"{
+        "$schema": "https://flagd.dev/schema/v0/flags.json",
+        "flags": {
+            "new-welcome-banner": {
+                "state": "ENABLED",
+                "variants": {"on": true, "off": false},
+                "defaultVariant": "off"
+            },
+            "checkout.theme": {
+                "state": "DISABLED",
+                "variants": {"light": {"bg": "#fff"}, "dark": {"bg": "#000"}},
+                "defaultVariant": "dim"
+            },
+            "retries": {
+                "state": "ENABLED",
+                "variants": {"few": 1, "many": "10"},
+                "defaultVariant": "few"
+            }
+        }
+    }"##;
+
+    fn generate(source: &str) -> GeneratedTypes {
+        let provider = OpenFeatureProvider::new();
+        let schema = provider.resolve_schema(source, &ProviderParams::default()).unwrap();
+        provider.generate_types(&schema, "Features").unwrap()
+    }
+
+    fn fields(types: &GeneratedTypes, name: &str) -> Vec<(String, String)> {
+        types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+            .fields
+            .iter()
+            .map(|(field, ty)| (field.clone(), ty.to_string()))
+            .collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(f, t)| (f.to_string(), t.to_string())).collect()
+    }
+
+    #[test]
+    fn test_flagd_definitions() {
+        let types = generate(FLAGD);
+        assert_eq!(
+            fields(&types, "Flags"),
+            pairs(&[
+                ("new_welcome_banner", "bool"),
+                ("checkout_theme", "Map<string, any>"),
+                ("retries", "any"),
+            ])
+        );
+
+        let unions: Vec<(&str, Vec<&str>)> = types.modules[0]
+            .types
+            .iter()
+            .filter_map(|t| match t {
+                TypeDefinition::Du(du) => {
+                    Some((du.name.as_str(), du.variants.iter().map(|v| v.name.as_str()).collect()))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            unions,
+            [
+                ("NewWelcomeBannerVariant", vec!["On", "Off"]),
+                ("CheckoutThemeVariant", vec!["Light", "Dark"]),
+                ("RetriesVariant", vec!["Few", "Many"]),
+            ]
+        );
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/openfeature.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_cli_manifest() {
+        let provider = OpenFeatureProvider::new();
+        let params = ProviderParams::default().with("record", "FeatureFlags");
+        let schema = provider
+            .resolve_schema(
+                "flags:\n  themeColor: {flagType: string, defaultValue: blue}\n  maxItems: {flagType: float, defaultValue: 10}\n  beta: {defaultValue: false}\n",
+                &params,
+            )
+            .unwrap();
+        let types = provider.generate_types(&schema, "Features").unwrap();
+        assert_eq!(types.modules[0].types.len(), 1);
+        assert_eq!(
+            fields(&types, "FeatureFlags"),
+            pairs(&[("themeColor", "string"), ("maxItems", "float"), ("beta", "bool")])
+        );
+        assert!(provider.schema_diagnostics(&schema).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let provider = OpenFeatureProvider::new();
+        let schema = provider.resolve_schema(FLAGD, &ProviderParams::default()).unwrap();
+        let diagnostics = provider.schema_diagnostics(&schema).unwrap();
+        let found: Vec<(&str, &str)> = diagnostics
+            .iter()
+            .map(|d| (d.code.as_str(), d.location.as_deref().unwrap_or("")))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("default-variant-unknown", "flags.checkout.theme"),
+                ("flag-disabled", "flags.checkout.theme"),
+                ("variant-type-mismatch", "flags.retries"),
+            ]
+        );
+
+        let schema = provider
+            .resolve_schema("flags:\n  limit: {flagType: integer, defaultValue: many}\n", &ProviderParams::default())
+            .unwrap();
+        let diagnostics = provider.schema_diagnostics(&schema).unwrap();
+        assert_eq!(diagnostics[0].code, "default-type-mismatch");
+        assert_eq!(diagnostics[0].message, "`defaultValue` does not match flagType `integer`");
+    }
+
+    #[test]
+    fn test_file_source() {
+        let dir = std::env::temp_dir().join(format!("fusabi-openfeature-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("flags.yaml");
+        std::fs::write(&path, "flags:\n  dark-mode:\n    variants: {on: true, off: false}\n    defaultVariant: on\n").unwrap();
+
+        let types = generate(path.to_str().unwrap());
+        assert_eq!(fields(&types, "Flags"), pairs(&[("dark_mode", "bool")]));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,245 @@
+//! Flag manifests
+//!
+//! Two layouts are read, in YAML or JSON. flagd flag definitions list each
+//! flag's variants and default variant:
+//!
+//! ```yaml
+//! flags:
+//!   new-welcome-banner:
+//!     state: ENABLED
+//!     variants: {on: true, off: false}
+//!     defaultVariant: off
+//! ```
+//!
+//! The OpenFeature CLI manifest declares a type and default value instead:
+//!
+//! ```yaml
+//! flags:
+//!   themeColor: {flagType: string, defaultValue: blue, description: Accent colour}
+//! ```
+//!
+//! Flags keep manifest order.
+
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+/// An OpenFeature flag value type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FlagType {
+    Boolean,
+    String,
+    Integer,
+    Float,
+    Object,
+}
+
+impl FlagType {
+    /// Parse a CLI manifest `flagType`
+    pub fn parse(name: &str) -> Option<FlagType> {
+        match name {
+            "boolean" => Some(FlagType::Boolean),
+            "string" => Some(FlagType::String),
+            "integer" => Some(FlagType::Integer),
+            "float" => Some(FlagType::Float),
+            "object" => Some(FlagType::Object),
+            _ => None,
+        }
+    }
+
+    /// The `flagType` spelling
+    pub fn name(self) -> &'static str {
+        match self {
+            FlagType::Boolean => "boolean",
+            FlagType::String => "string",
+            FlagType::Integer => "integer",
+            FlagType::Float => "float",
+            FlagType::Object => "object",
+        }
+    }
+
+    /// The type of a flag value; `None` for null
+    pub fn of(value: &Value) -> Option<FlagType> {
+        match value {
+            Value::Bool(_) => Some(FlagType::Boolean),
+            Value::String(_) => Some(FlagType::String),
+            Value::Number(n) if n.is_f64() => Some(FlagType::Float),
+            Value::Number(_) => Some(FlagType::Integer),
+            Value::Mapping(_) | Value::Sequence(_) => Some(FlagType::Object),
+            Value::Tagged(tagged) => FlagType::of(&tagged.value),
+            Value::Null => None,
+        }
+    }
+
+    /// Fusabi type of the flag's evaluated value
+    pub fn fusabi_type(self) -> &'static str {
+        match self {
+            FlagType::Boolean => "bool",
+            FlagType::String => "string",
+            FlagType::Integer => "int",
+            FlagType::Float => "float",
+            FlagType::Object => "Map<string, any>",
+        }
+    }
+}
+
+/// A parsed flag manifest
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FlagManifest {
+    pub flags: Vec<FlagDef>,
+}
+
+/// A feature flag
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlagDef {
+    pub key: String,
+    /// `flagType` from a CLI manifest
+    pub declared_type: Option<FlagType>,
+    /// flagd variants, in manifest order
+    #[serde(default)]
+    pub variants: Vec<Variant>,
+    pub default_variant: Option<String>,
+    /// Type of a CLI manifest `defaultValue`
+    pub default_type: Option<FlagType>,
+    pub description: Option<String>,
+    /// False for flagd flags with `state: DISABLED`
+    pub enabled: bool,
+}
+
+/// A flagd variant
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Variant {
+    pub name: String,
+    pub value_type: Option<FlagType>,
+}
+
+impl FlagDef {
+    /// The variants' common type, widening integers to floats; `None` when
+    /// there are no variants or they disagree
+    pub fn variant_type(&self) -> Option<FlagType> {
+        let mut types = self.variants.iter().map(|v| v.value_type);
+        let first = types.next()??;
+        types.try_fold(first, |common, value_type| match (common, value_type?) {
+            (a, b) if a == b => Some(a),
+            (FlagType::Integer, FlagType::Float) | (FlagType::Float, FlagType::Integer) => Some(FlagType::Float),
+            _ => None,
+        })
+    }
+}
+
+/// Parse a flagd or OpenFeature CLI manifest
+pub fn parse_manifest(content: &str) -> ProviderResult<FlagManifest> {
+    let value: Value = serde_yaml::from_str(content)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid flag manifest: {}", e)))?;
+    let flags = match value.get("flags") {
+        Some(Value::Mapping(flags)) => flags,
+        Some(Value::Null) => return Ok(FlagManifest::default()),
+        _ => return Err(ProviderError::ParseError("Expected a flag manifest with a `flags` mapping".to_string())),
+    };
+
+    let mut manifest = FlagManifest::default();
+    for (key, definition) in flags {
+        let key = key
+            .as_str()
+            .ok_or_else(|| ProviderError::ParseError(format!("Flag keys must be strings, got {:?}", key)))?;
+        let Value::Mapping(definition) = definition else {
+            return Err(ProviderError::ParseError(format!("Flag `{}` must be a mapping", key)));
+        };
+
+        let declared_type = match definition.get("flagType") {
+            Some(Value::String(name)) => Some(FlagType::parse(name).ok_or_else(|| {
+                ProviderError::ParseError(format!("Flag `{}` has unknown flagType `{}`", key, name))
+            })?),
+            Some(_) => return Err(ProviderError::ParseError(format!("Flag `{}` flagType must be a string", key))),
+            None => None,
+        };
+        let variants = match definition.get("variants") {
+            Some(Value::Mapping(variants)) => variants
+                .iter()
+                .map(|(name, value)| {
+                    let name = match name {
+                        Value::String(name) => name.clone(),
+                        Value::Bool(b) => b.to_string(),
+                        Value::Number(n) => n.to_string(),
+                        _ => {
+                            return Err(ProviderError::ParseError(format!(
+                                "Flag `{}` has a variant name that is not a string",
+                                key
+                            )))
+                        }
+                    };
+                    Ok(Variant {
+                        name,
+                        value_type: FlagType::of(value),
+                    })
+                })
+                .collect::<ProviderResult<Vec<_>>>()?,
+            Some(_) => return Err(ProviderError::ParseError(format!("Flag `{}` variants must be a mapping", key))),
+            None => Vec::new(),
+        };
+        if declared_type.is_none() && variants.is_empty() && definition.get("defaultValue").is_none() {
+            return Err(ProviderError::ParseError(format!(
+                "Flag `{}` has neither `variants`, `flagType` nor `defaultValue`",
+                key
+            )));
+        }
+
+        manifest.flags.push(FlagDef {
+            key: key.to_string(),
+            declared_type,
+            variants,
+            default_variant: definition.get("defaultVariant").and_then(Value::as_str).map(str::to_string),
+            default_type: definition.get("defaultValue").and_then(FlagType::of),
+            description: definition.get("description").and_then(Value::as_str).map(str::to_string),
+            enabled: definition.get("state").and_then(Value::as_str) != Some("DISABLED"),
+        });
+    }
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_flagd() {
+        let manifest = parse_manifest(
+            r#"{"flags": {
+                "banner": {"state": "ENABLED", "variants": {"on": true, "off": false}, "defaultVariant": "off"},
+                "ratio": {"state": "DISABLED", "variants": {"low": 1, "high": 2.5}, "defaultVariant": "low"},
+                "mixed": {"variants": {"a": "x", "b": 1}}
+            }}"#,
+        )
+        .unwrap();
+        let keys: Vec<&str> = manifest.flags.iter().map(|f| f.key.as_str()).collect();
+        assert_eq!(keys, ["banner", "ratio", "mixed"]);
+
+        let banner = &manifest.flags[0];
+        let variants: Vec<&str> = banner.variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(variants, ["on", "off"]);
+        assert_eq!(banner.variant_type(), Some(FlagType::Boolean));
+        assert_eq!(banner.default_variant.as_deref(), Some("off"));
+        assert!(banner.enabled);
+
+        assert_eq!(manifest.flags[1].variant_type(), Some(FlagType::Float));
+        assert!(!manifest.flags[1].enabled);
+        assert_eq!(manifest.flags[2].variant_type(), None);
+    }
+
+    #[test]
+    fn test_parse_cli_manifest() {
+        let manifest = parse_manifest(
+            "flags:\n  themeColor:\n    flagType: string\n    defaultValue: blue\n    description: Accent colour\n  maxItems:\n    defaultValue: 10\n",
+        )
+        .unwrap();
+        assert_eq!(manifest.flags[0].declared_type, Some(FlagType::String));
+        assert_eq!(manifest.flags[0].description.as_deref(), Some("Accent colour"));
+        assert_eq!(manifest.flags[1].declared_type, None);
+        assert_eq!(manifest.flags[1].default_type, Some(FlagType::Integer));
+
+        assert!(parse_manifest("flags:\n  broken: {flagType: date}\n").is_err());
+        assert!(parse_manifest("flags:\n  empty: {}\n").is_err());
+        assert!(parse_manifest("toggles: {}").is_err());
+    }
+}
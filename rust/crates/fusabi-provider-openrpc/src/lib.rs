@@ -0,0 +1,522 @@
+//! OpenRPC Type Provider
+//!
+//! Generates Fusabi types from OpenRPC 1.x documents describing JSON-RPC
+//! services, complementing the MCP provider for non-MCP JSON-RPC servers.
+//!
+//! # Generated Types
+//!
+//! - `components.schemas` go into a `Schemas` module
+//! - each method gets a `<Method>Params` record with one field per param
+//!   (params that are not `required` become `T option`) and, unless it is
+//!   a notification, a `<Method>Result` record with a single field named
+//!   after the result
+//! - `MethodCall` is a union with a case per method carrying its params
+//! - `ErrorCode` is a union of the standard JSON-RPC errors plus every
+//!   error declared by the methods or `components.errors`
+//!
+//! Method types live in a `Methods` module.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_openrpc::OpenRpcProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = OpenRpcProvider::new();
+//! let schema = provider.resolve_schema("openrpc.json", &ProviderParams::default())?;
+//! let types = provider.generate_types(&schema, "Wallet")?;
+//! ```
+
+use fusabi_jsonschema_core::{definitions_of, Converter};
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, SourceValidator,
+    TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, DuDef, VariantDef, TypeExpr, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use serde_json::Value;
+
+/// Module for `components.schemas`
+const SCHEMAS_MODULE: &str = "Schemas";
+
+/// Module for method params, results and the call and error unions
+const METHODS_MODULE: &str = "Methods";
+
+/// Errors defined by the JSON-RPC 2.0 specification
+pub const STANDARD_ERRORS: [(i64, &str); 5] = [
+    (-32700, "ParseError"),
+    (-32600, "InvalidRequest"),
+    (-32601, "MethodNotFound"),
+    (-32602, "InvalidParams"),
+    (-32603, "InternalError"),
+];
+
+/// Codes reserved by JSON-RPC for predefined errors
+const RESERVED_CODES: std::ops::RangeInclusive<i64> = -32768..=-32100;
+
+/// OpenRPC type provider
+pub struct OpenRpcProvider {
+    converter: Converter,
+    generator: TypeGenerator,
+}
+
+impl OpenRpcProvider {
+    pub fn new() -> Self {
+        Self {
+            converter: Converter::new(),
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    /// Parse a JSON or YAML document and check that it is OpenRPC
+    fn parse_document(&self, content: &str) -> ProviderResult<Value> {
+        // YAML is a superset of JSON, so one parser covers both
+        let document: Value = serde_yaml::from_str(content)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+
+        let is_openrpc = document
+            .get("openrpc")
+            .and_then(Value::as_str)
+            .is_some_and(|v| v.starts_with("1."));
+        if !is_openrpc {
+            return Err(ProviderError::InvalidSource(
+                "Expected an OpenRPC 1.x document".to_string(),
+            ));
+        }
+        if !document.get("methods").is_some_and(Value::is_array) {
+            return Err(ProviderError::ParseError(
+                "OpenRPC document has no `methods` array".to_string(),
+            ));
+        }
+
+        Ok(document)
+    }
+
+    fn generate_document(&self, document: &Value, namespace: &str) -> GeneratedTypes {
+        let mut result = GeneratedTypes::new();
+
+        if let Some(definitions) = definitions_of(document) {
+            let mut module = GeneratedModule::new(vec![namespace.to_string(), SCHEMAS_MODULE.to_string()]);
+            module.types = self.converter.definitions(definitions);
+            if !module.types.is_empty() {
+                result.modules.push(module);
+            }
+        }
+
+        let mut module = GeneratedModule::new(vec![namespace.to_string(), METHODS_MODULE.to_string()]);
+        let mut calls = Vec::new();
+
+        for method in methods(document) {
+            let Some(raw_name) = method.get("name").and_then(Value::as_str) else {
+                continue;
+            };
+            let name = self.generator.naming.apply(raw_name);
+            let params_type = format!("{}Params", name);
+
+            let fields = content_descriptors(document, method.get("params"))
+                .filter_map(|param| {
+                    let param_name = param.get("name").and_then(Value::as_str)?;
+                    let required = param.get("required").and_then(Value::as_bool) == Some(true);
+                    Some((param_name.to_string(), self.descriptor_type(param, required)))
+                })
+                .collect();
+            module.types.push(TypeDefinition::Record(RecordDef {
+                name: params_type.clone(),
+                fields,
+            }));
+
+            if let Some(result_descriptor) = method.get("result").map(|r| resolve(document, r)) {
+                let field_name = result_descriptor
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .unwrap_or("result");
+                // A result is always present on success, even if its schema allows null
+                module.types.push(TypeDefinition::Record(RecordDef {
+                    name: format!("{}Result", name),
+                    fields: vec![(field_name.to_string(), self.descriptor_type(result_descriptor, true))],
+                }));
+            }
+
+            calls.push(VariantDef::new(name, vec![TypeShape::named(params_type).into()]));
+        }
+
+        if !calls.is_empty() {
+            module.types.push(TypeDefinition::Du(DuDef {
+                name: "MethodCall".to_string(),
+                variants: calls,
+            }));
+        }
+        module.types.push(self.error_codes(document));
+
+        result.modules.push(module);
+        result
+    }
+
+    /// Type of a content descriptor's schema
+    fn descriptor_type(&self, descriptor: &Value, required: bool) -> TypeExpr {
+        let schema = descriptor.get("schema").unwrap_or(&Value::Null);
+        let shape = self.converter.shape(schema);
+        let required = required || shape.is_option();
+        shape.option_unless(required).into()
+    }
+
+    /// Standard errors followed by every declared error, one case per code
+    fn error_codes(&self, document: &Value) -> TypeDefinition {
+        let mut cases: Vec<(i64, String)> = STANDARD_ERRORS
+            .iter()
+            .map(|(code, name)| (*code, name.to_string()))
+            .collect();
+
+        for (code, message) in declared_errors(document) {
+            if cases.iter().any(|(c, _)| *c == code) {
+                continue;
+            }
+            let mut case = self.generator.naming.apply(&message);
+            if case.is_empty()
+                || !case.starts_with(|c: char| c.is_alphabetic())
+                || cases.iter().any(|(_, n)| *n == case)
+            {
+                case = format!("Code{}", code.unsigned_abs());
+            }
+            cases.push((code, case));
+        }
+
+        TypeDefinition::Du(DuDef {
+            name: "ErrorCode".to_string(),
+            variants: cases
+                .into_iter()
+                .map(|(_, case)| VariantDef::new_simple(case))
+                .collect(),
+        })
+    }
+}
+
+impl Default for OpenRpcProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypeProvider for OpenRpcProvider {
+    fn name(&self) -> &str {
+        "OpenRpcProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let trimmed = source.trim_start();
+        let content = if trimmed.starts_with('{') || trimmed.starts_with("openrpc:") {
+            source.to_string()
+        } else {
+            read_source(source, params)?
+        };
+
+        let document = self.parse_document(&content)?;
+        Ok(Schema::Custom(document.to_string()))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        match schema {
+            Schema::Custom(content) => {
+                let document = self.parse_document(content)?;
+                Ok(self.generate_document(&document, namespace))
+            }
+            _ => Err(ProviderError::ParseError("Expected OpenRPC schema".to_string())),
+        }
+    }
+}
+
+impl SourceValidator for OpenRpcProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(content) = schema else {
+            return Err(ProviderError::ParseError("Expected OpenRPC schema".to_string()));
+        };
+
+        let document = self.parse_document(content)?;
+        let mut diagnostics = Vec::new();
+
+        for method in methods(&document) {
+            let name = method.get("name").and_then(Value::as_str).unwrap_or_default();
+            if method.get("result").is_none() {
+                diagnostics.push(
+                    Diagnostic::info(
+                        "notification",
+                        format!("Method `{}` has no result; only params are generated", name),
+                    )
+                    .at(format!("methods.{}", name)),
+                );
+            }
+        }
+
+        for (code, message) in declared_errors(&document) {
+            let standard = STANDARD_ERRORS.iter().any(|(c, _)| *c == code);
+            if RESERVED_CODES.contains(&code) && !standard {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        "error-code-reserved",
+                        format!("Error {} `{}` uses a code reserved by JSON-RPC", code, message),
+                    )
+                    .at(format!("errors.{}", code)),
+                );
+            }
+        }
+
+        let mut external = Vec::new();
+        external_refs(&document, &mut external);
+        for reference in external {
+            diagnostics.push(
+                Diagnostic::warning(
+                    "ref-unresolved",
+                    format!("External reference `{}` is not loaded; it is typed by name only", reference),
+                )
+                .at(reference),
+            );
+        }
+
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for OpenRpcProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+/// Methods of a document, with `$ref`s resolved
+fn methods(document: &Value) -> impl Iterator<Item = &Value> {
+    document
+        .get("methods")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(move |m| resolve(document, m))
+}
+
+/// Content descriptors of a `params` array, with `$ref`s resolved
+fn content_descriptors<'a>(
+    document: &'a Value,
+    params: Option<&'a Value>,
+) -> impl Iterator<Item = &'a Value> {
+    params
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(move |p| resolve(document, p))
+}
+
+/// Every `(code, message)` declared by methods or `components.errors`, in
+/// document order
+fn declared_errors(document: &Value) -> Vec<(i64, String)> {
+    let method_errors = methods(document)
+        .filter_map(|m| m.get("errors").and_then(Value::as_array))
+        .flatten();
+    let component_errors = document
+        .pointer("/components/errors")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flat_map(|errors| errors.values());
+
+    let mut errors: Vec<(i64, String)> = Vec::new();
+    for error in method_errors.chain(component_errors) {
+        let error = resolve(document, error);
+        let Some(code) = error.get("code").and_then(Value::as_i64) else {
+            continue;
+        };
+        if errors.iter().any(|(c, _)| *c == code) {
+            continue;
+        }
+        let message = error.get("message").and_then(Value::as_str).unwrap_or_default();
+        errors.push((code, message.to_string()));
+    }
+    errors
+}
+
+/// Follow local `$ref`s
+fn resolve<'a>(document: &'a Value, value: &'a Value) -> &'a Value {
+    let mut current = value;
+    for _ in 0..8 {
+        let Some(target) = current
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|r| r.strip_prefix('#'))
+            .and_then(|pointer| document.pointer(pointer))
+        else {
+            break;
+        };
+        current = target;
+    }
+    current
+}
+
+fn external_refs(value: &Value, refs: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(reference) = map.get("$ref").and_then(Value::as_str) {
+                if !reference.starts_with('#') && !refs.iter().any(|r| r == reference) {
+                    refs.push(reference.to_string());
+                }
+            }
+            map.values().for_each(|v| external_refs(v, refs));
+        }
+        Value::Array(items) => items.iter().for_each(|v| external_refs(v, refs)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    const PETSTORE: &str = r##"{
+        "openrpc": "1.2.6",
+        "info": {"title": "Petstore", "version": "1.0.0"},
+        "methods": [
+            {
+                "name": "list_pets",
+                "params": [
+                    {"name": "limit", "schema": {"type": "integer"}},
+                    {"$ref": "#/components/contentDescriptors/Owner"}
+                ],
+                "result": {"name": "pets", "schema": {"type": "array", "items": {"$ref": "#/components/schemas/Pet"}}}
+            },
+            {
+                "name": "pet.get",
+                "params": [{"name": "id", "required": true, "schema": {"type": "string"}}],
+                "result": {"name": "pet", "schema": {"$ref": "#/components/schemas/Pet"}},
+                "errors": [
+                    {"code": 404, "message": "Pet not found"},
+                    {"$ref": "#/components/errors/RateLimited"}
+                ]
+            },
+            {"name": "notify_feed", "params": []}
+        ],
+        "components": {
+            "schemas": {
+                "Pet": {"type": "object", "required": ["id"], "properties": {"id": {"type": "string"}, "tag": {"type": "string"}}}
+            },
+            "contentDescriptors": {
+                "Owner": {"name": "owner", "required": true, "schema": {"type": "string"}}
+            },
+            "errors": {
+                "RateLimited": {"code": -32000, "message": "Rate limited"},
+                "Legacy": {"code": -32200, "message": "Legacy failure"}
+            }
+        }
+    }"##;
+
+    fn generate() -> GeneratedTypes {
+        let provider = OpenRpcProvider::new();
+        let schema = provider.resolve_schema(PETSTORE, &ProviderParams::default()).unwrap();
+        provider.generate_types(&schema, "Petstore").unwrap()
+    }
+
+    fn record(types: &GeneratedTypes, name: &str) -> Vec<(String, String)> {
+        types
+            .modules
+            .iter()
+            .flat_map(|m| &m.types)
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(
+                    r.fields
+                        .iter()
+                        .map(|(n, t)| (n.clone(), t.to_string()))
+                        .collect(),
+                ),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+    }
+
+    fn union_cases(types: &GeneratedTypes, name: &str) -> Vec<String> {
+        types
+            .modules
+            .iter()
+            .flat_map(|m| &m.types)
+            .find_map(|t| match t {
+                TypeDefinition::Du(d) if d.name == name => {
+                    Some(d.variants.iter().map(|v| v.name.clone()).collect())
+                }
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected union {}", name))
+    }
+
+    #[test]
+    fn test_generate_methods() {
+        let types = generate();
+        let paths: Vec<Vec<String>> = types.modules.iter().map(|m| m.path.clone()).collect();
+        assert_eq!(paths, [vec!["Petstore", "Schemas"], vec!["Petstore", "Methods"]]);
+
+        assert_eq!(
+            record(&types, "ListPetsParams"),
+            [
+                ("limit".to_string(), "int option".to_string()),
+                ("owner".to_string(), "string".to_string()),
+            ]
+        );
+        assert_eq!(
+            record(&types, "ListPetsResult"),
+            [("pets".to_string(), "Pet list".to_string())]
+        );
+        assert_eq!(record(&types, "PetGetParams"), [("id".to_string(), "string".to_string())]);
+        assert_eq!(record(&types, "PetGetResult"), [("pet".to_string(), "Pet".to_string())]);
+        assert!(record(&types, "NotifyFeedParams").is_empty());
+        assert_eq!(union_cases(&types, "MethodCall"), ["ListPets", "PetGet", "NotifyFeed"]);
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/openrpc.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_error_codes() {
+        let cases = union_cases(&generate(), "ErrorCode");
+        assert_eq!(
+            cases,
+            [
+                "ParseError",
+                "InvalidRequest",
+                "MethodNotFound",
+                "InvalidParams",
+                "InternalError",
+                "PetNotFound",
+                "RateLimited",
+                "LegacyFailure",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rejects_non_openrpc() {
+        let provider = OpenRpcProvider::new();
+        assert!(provider
+            .resolve_schema(r#"{"openapi": "3.0.0", "paths": {}}"#, &ProviderParams::default())
+            .is_err());
+        assert!(provider
+            .resolve_schema(r#"{"openrpc": "1.2.6"}"#, &ProviderParams::default())
+            .is_err());
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let report = OpenRpcProvider::new().validate_source(PETSTORE, &ProviderParams::default());
+        assert!(report.is_valid());
+
+        let notification = report.diagnostics.iter().find(|d| d.code == "notification").unwrap();
+        assert_eq!(notification.location.as_deref(), Some("methods.notify_feed"));
+
+        let reserved: Vec<_> = report.warnings().collect();
+        assert_eq!(reserved.len(), 1);
+        assert_eq!(reserved[0].code, "error-code-reserved");
+        assert_eq!(reserved[0].location.as_deref(), Some("errors.-32200"));
+    }
+}
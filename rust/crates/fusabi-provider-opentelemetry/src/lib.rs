@@ -2,15 +2,17 @@
 //!
 //! Generates Fusabi types from OpenTelemetry semantic conventions.
 
+use fusabi_provider_common::{Capabilities, DescribedProvider, SourceValidator, TypeShape};
 use fusabi_type_providers::{
     TypeProvider, ProviderParams, Schema,
     GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
-    RecordDef, TypeExpr, TypeDefinition,
+    RecordDef, TypeDefinition,
     ProviderError, ProviderResult,
 };
 
 /// OpenTelemetry type provider
 pub struct OpenTelemetryProvider {
+    #[allow(dead_code)]
     generator: TypeGenerator,
 }
 
@@ -28,13 +30,13 @@ impl OpenTelemetryProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "Client".to_string(),
             fields: vec![
-                ("requestMethod".to_string(), TypeExpr::Named("string".to_string())),
-                ("requestUrl".to_string(), TypeExpr::Named("string option".to_string())),
-                ("responseStatusCode".to_string(), TypeExpr::Named("int option".to_string())),
-                ("networkProtocolName".to_string(), TypeExpr::Named("string option".to_string())),
-                ("networkProtocolVersion".to_string(), TypeExpr::Named("string option".to_string())),
-                ("serverAddress".to_string(), TypeExpr::Named("string option".to_string())),
-                ("serverPort".to_string(), TypeExpr::Named("int option".to_string())),
+                ("requestMethod".to_string(), TypeShape::named("string").into()),
+                ("requestUrl".to_string(), TypeShape::named("string").option().into()),
+                ("responseStatusCode".to_string(), TypeShape::named("int").option().into()),
+                ("networkProtocolName".to_string(), TypeShape::named("string").option().into()),
+                ("networkProtocolVersion".to_string(), TypeShape::named("string").option().into()),
+                ("serverAddress".to_string(), TypeShape::named("string").option().into()),
+                ("serverPort".to_string(), TypeShape::named("int").option().into()),
             ],
         }));
 
@@ -42,11 +44,11 @@ impl OpenTelemetryProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "Server".to_string(),
             fields: vec![
-                ("requestMethod".to_string(), TypeExpr::Named("string".to_string())),
-                ("route".to_string(), TypeExpr::Named("string option".to_string())),
-                ("responseStatusCode".to_string(), TypeExpr::Named("int option".to_string())),
-                ("scheme".to_string(), TypeExpr::Named("string option".to_string())),
-                ("target".to_string(), TypeExpr::Named("string option".to_string())),
+                ("requestMethod".to_string(), TypeShape::named("string").into()),
+                ("route".to_string(), TypeShape::named("string").option().into()),
+                ("responseStatusCode".to_string(), TypeShape::named("int").option().into()),
+                ("scheme".to_string(), TypeShape::named("string").option().into()),
+                ("target".to_string(), TypeShape::named("string").option().into()),
             ],
         }));
 
@@ -59,10 +61,10 @@ impl OpenTelemetryProvider {
         module.types.push(TypeDefinition::Record(RecordDef {
             name: "Client".to_string(),
             fields: vec![
-                ("system".to_string(), TypeExpr::Named("string".to_string())),
-                ("statement".to_string(), TypeExpr::Named("string option".to_string())),
-                ("operation".to_string(), TypeExpr::Named("string option".to_string())),
-                ("name".to_string(), TypeExpr::Named("string option".to_string())),
+                ("system".to_string(), TypeShape::named("string").into()),
+                ("statement".to_string(), TypeShape::named("string").option().into()),
+                ("operation".to_string(), TypeShape::named("string").option().into()),
+                ("name".to_string(), TypeShape::named("string").option().into()),
             ],
         }));
 
@@ -108,3 +110,31 @@ impl TypeProvider for OpenTelemetryProvider {
         }
     }
 }
+
+impl SourceValidator for OpenTelemetryProvider {}
+
+impl DescribedProvider for OpenTelemetryProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .embedded("embedded")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    #[test]
+    fn test_generate_embedded() {
+        let provider = OpenTelemetryProvider::new();
+        let schema = provider.resolve_schema("embedded", &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Otel").unwrap();
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/opentelemetry.fsx"),
+        );
+        assert!(provider.resolve_schema("semconv.yaml", &ProviderParams::default()).is_err());
+    }
+}
@@ -0,0 +1,351 @@
+//! Collector component models
+//!
+//! Configuration surfaces of the core and most-used contrib components,
+//! keyed by config key, with their Fusabi types written the way they are
+//! generated. Settings shared between components (TLS, retries, queues)
+//! are listed once in [`SETTINGS`] and referenced by name. Fields the
+//! component rejects when missing are required; everything with a default
+//! is optional.
+
+use serde::{Deserialize, Serialize};
+
+/// A required field
+pub const REQ: bool = true;
+/// An optional field
+pub const OPT: bool = false;
+
+/// Fields of a record: `(key, type, required)`
+pub type Fields = &'static [(&'static str, &'static str, bool)];
+
+/// Every pipeline signal
+pub const ALL_SIGNALS: &[Signal] = &[Signal::Traces, Signal::Metrics, Signal::Logs];
+const METRICS: &[Signal] = &[Signal::Metrics];
+const LOGS: &[Signal] = &[Signal::Logs];
+
+/// A component kind, as the config's top-level section names it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Kind {
+    Receiver,
+    Processor,
+    Exporter,
+    Extension,
+    Connector,
+}
+
+impl Kind {
+    /// Kinds with typed component models
+    pub const MODELED: [Kind; 4] = [Kind::Receiver, Kind::Processor, Kind::Exporter, Kind::Extension];
+
+    /// The config section: `receivers`
+    pub fn section(self) -> &'static str {
+        match self {
+            Kind::Receiver => "receivers",
+            Kind::Processor => "processors",
+            Kind::Exporter => "exporters",
+            Kind::Extension => "extensions",
+            Kind::Connector => "connectors",
+        }
+    }
+
+    /// Suffix of component record names: `OtlpReceiver`
+    pub fn suffix(self) -> &'static str {
+        match self {
+            Kind::Receiver => "Receiver",
+            Kind::Processor => "Processor",
+            Kind::Exporter => "Exporter",
+            Kind::Extension => "Extension",
+            Kind::Connector => "Connector",
+        }
+    }
+}
+
+/// A pipeline signal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Signal {
+    Traces,
+    Metrics,
+    Logs,
+}
+
+impl Signal {
+    /// The signal of a pipeline ID: `traces/sampled` → `Traces`
+    pub fn of_pipeline(id: &str) -> Option<Signal> {
+        match id.split('/').next().unwrap_or(id) {
+            "traces" => Some(Signal::Traces),
+            "metrics" => Some(Signal::Metrics),
+            "logs" => Some(Signal::Logs),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Signal::Traces => "traces",
+            Signal::Metrics => "metrics",
+            Signal::Logs => "logs",
+        }
+    }
+}
+
+/// A component: kind, type, signals, fields
+pub type ComponentDef = (Kind, &'static str, &'static [Signal], Fields);
+
+pub const COMPONENTS: &[ComponentDef] = &[
+    // Receivers
+    (Kind::Receiver, "otlp", ALL_SIGNALS, &[
+        ("protocols", "OtlpProtocols", REQ),
+    ]),
+    (Kind::Receiver, "prometheus", METRICS, &[
+        ("config", "PrometheusConfig", REQ),
+    ]),
+    (Kind::Receiver, "hostmetrics", METRICS, &[
+        ("collection_interval", "string", OPT),
+        ("root_path", "string", OPT),
+        ("scrapers", "Map<string, any>", REQ),
+    ]),
+    (Kind::Receiver, "filelog", LOGS, &[
+        ("include", "string list", REQ),
+        ("exclude", "string list", OPT),
+        ("start_at", "string", OPT),
+        ("include_file_path", "bool", OPT),
+        ("include_file_name", "bool", OPT),
+        ("operators", "Map<string, any> list", OPT),
+    ]),
+    (Kind::Receiver, "kafka", ALL_SIGNALS, &[
+        ("brokers", "string list", OPT),
+        ("topic", "string", OPT),
+        ("encoding", "string", OPT),
+        ("group_id", "string", OPT),
+        ("auth", "Map<string, any>", OPT),
+    ]),
+    // Processors
+    (Kind::Processor, "batch", ALL_SIGNALS, &[
+        ("timeout", "string", OPT),
+        ("send_batch_size", "int", OPT),
+        ("send_batch_max_size", "int", OPT),
+        ("metadata_keys", "string list", OPT),
+    ]),
+    (Kind::Processor, "memory_limiter", ALL_SIGNALS, &[
+        ("check_interval", "string", REQ),
+        ("limit_mib", "int", OPT),
+        ("spike_limit_mib", "int", OPT),
+        ("limit_percentage", "int", OPT),
+        ("spike_limit_percentage", "int", OPT),
+    ]),
+    (Kind::Processor, "attributes", ALL_SIGNALS, &[
+        ("actions", "AttributeAction list", REQ),
+        ("include", "Map<string, any>", OPT),
+        ("exclude", "Map<string, any>", OPT),
+    ]),
+    (Kind::Processor, "resource", ALL_SIGNALS, &[
+        ("attributes", "AttributeAction list", REQ),
+    ]),
+    (Kind::Processor, "filter", ALL_SIGNALS, &[
+        ("error_mode", "string", OPT),
+        ("traces", "Map<string, string list>", OPT),
+        ("metrics", "Map<string, string list>", OPT),
+        ("logs", "Map<string, string list>", OPT),
+    ]),
+    (Kind::Processor, "k8sattributes", ALL_SIGNALS, &[
+        ("auth_type", "string", OPT),
+        ("passthrough", "bool", OPT),
+        ("extract", "Map<string, any>", OPT),
+        ("pod_association", "Map<string, any> list", OPT),
+    ]),
+    // Exporters
+    (Kind::Exporter, "otlp", ALL_SIGNALS, &[
+        ("endpoint", "string", REQ),
+        ("tls", "TlsClientSettings", OPT),
+        ("headers", "Map<string, string>", OPT),
+        ("compression", "string", OPT),
+        ("timeout", "string", OPT),
+        ("retry_on_failure", "RetrySettings", OPT),
+        ("sending_queue", "QueueSettings", OPT),
+    ]),
+    (Kind::Exporter, "otlphttp", ALL_SIGNALS, &[
+        ("endpoint", "string", OPT),
+        ("traces_endpoint", "string", OPT),
+        ("metrics_endpoint", "string", OPT),
+        ("logs_endpoint", "string", OPT),
+        ("tls", "TlsClientSettings", OPT),
+        ("headers", "Map<string, string>", OPT),
+        ("compression", "string", OPT),
+        ("timeout", "string", OPT),
+        ("retry_on_failure", "RetrySettings", OPT),
+        ("sending_queue", "QueueSettings", OPT),
+    ]),
+    (Kind::Exporter, "debug", ALL_SIGNALS, &[
+        ("verbosity", "string", OPT),
+        ("sampling_initial", "int", OPT),
+        ("sampling_thereafter", "int", OPT),
+    ]),
+    (Kind::Exporter, "prometheus", METRICS, &[
+        ("endpoint", "string", REQ),
+        ("namespace", "string", OPT),
+        ("const_labels", "Map<string, string>", OPT),
+        ("send_timestamps", "bool", OPT),
+        ("metric_expiration", "string", OPT),
+        ("resource_to_telemetry_conversion", "Map<string, bool>", OPT),
+    ]),
+    (Kind::Exporter, "prometheusremotewrite", METRICS, &[
+        ("endpoint", "string", REQ),
+        ("tls", "TlsClientSettings", OPT),
+        ("headers", "Map<string, string>", OPT),
+        ("namespace", "string", OPT),
+        ("external_labels", "Map<string, string>", OPT),
+        ("retry_on_failure", "RetrySettings", OPT),
+        ("remote_write_queue", "Map<string, any>", OPT),
+    ]),
+    (Kind::Exporter, "kafka", ALL_SIGNALS, &[
+        ("brokers", "string list", OPT),
+        ("topic", "string", OPT),
+        ("encoding", "string", OPT),
+        ("auth", "Map<string, any>", OPT),
+        ("retry_on_failure", "RetrySettings", OPT),
+        ("sending_queue", "QueueSettings", OPT),
+    ]),
+    (Kind::Exporter, "file", ALL_SIGNALS, &[
+        ("path", "string", REQ),
+        ("format", "string", OPT),
+        ("rotation", "Map<string, any>", OPT),
+    ]),
+    // Extensions
+    (Kind::Extension, "health_check", &[], &[
+        ("endpoint", "string", OPT),
+        ("path", "string", OPT),
+    ]),
+    (Kind::Extension, "pprof", &[], &[
+        ("endpoint", "string", OPT),
+    ]),
+    (Kind::Extension, "zpages", &[], &[
+        ("endpoint", "string", OPT),
+    ]),
+];
+
+/// Settings records shared between components
+pub const SETTINGS: &[(&str, Fields)] = &[
+    ("OtlpProtocols", &[
+        ("grpc", "GrpcServerSettings", OPT),
+        ("http", "HttpServerSettings", OPT),
+    ]),
+    ("GrpcServerSettings", &[
+        ("endpoint", "string", OPT),
+        ("tls", "TlsServerSettings", OPT),
+        ("max_recv_msg_size_mib", "int", OPT),
+        ("include_metadata", "bool", OPT),
+    ]),
+    ("HttpServerSettings", &[
+        ("endpoint", "string", OPT),
+        ("tls", "TlsServerSettings", OPT),
+        ("cors", "CorsSettings", OPT),
+        ("include_metadata", "bool", OPT),
+    ]),
+    ("CorsSettings", &[
+        ("allowed_origins", "string list", OPT),
+        ("allowed_headers", "string list", OPT),
+        ("max_age", "int", OPT),
+    ]),
+    ("TlsServerSettings", &[
+        ("cert_file", "string", OPT),
+        ("key_file", "string", OPT),
+        ("client_ca_file", "string", OPT),
+        ("min_version", "string", OPT),
+    ]),
+    ("TlsClientSettings", &[
+        ("insecure", "bool", OPT),
+        ("insecure_skip_verify", "bool", OPT),
+        ("ca_file", "string", OPT),
+        ("cert_file", "string", OPT),
+        ("key_file", "string", OPT),
+        ("server_name_override", "string", OPT),
+    ]),
+    ("RetrySettings", &[
+        ("enabled", "bool", OPT),
+        ("initial_interval", "string", OPT),
+        ("max_interval", "string", OPT),
+        ("max_elapsed_time", "string", OPT),
+    ]),
+    ("QueueSettings", &[
+        ("enabled", "bool", OPT),
+        ("num_consumers", "int", OPT),
+        ("queue_size", "int", OPT),
+        ("storage", "string", OPT),
+    ]),
+    ("PrometheusConfig", &[
+        ("global", "Map<string, any>", OPT),
+        ("scrape_configs", "ScrapeConfig list", REQ),
+    ]),
+    ("ScrapeConfig", &[
+        ("job_name", "string", REQ),
+        ("scrape_interval", "string", OPT),
+        ("metrics_path", "string", OPT),
+        ("static_configs", "StaticConfig list", OPT),
+    ]),
+    ("StaticConfig", &[
+        ("targets", "string list", REQ),
+        ("labels", "Map<string, string>", OPT),
+    ]),
+    ("AttributeAction", &[
+        ("key", "string", REQ),
+        ("action", "string", REQ),
+        ("value", "any", OPT),
+        ("from_attribute", "string", OPT),
+        ("pattern", "string", OPT),
+    ]),
+    ("Pipeline", &[
+        ("receivers", "string list", REQ),
+        ("processors", "string list", OPT),
+        ("exporters", "string list", REQ),
+    ]),
+    ("ServiceTelemetry", &[
+        ("logs", "Map<string, any>", OPT),
+        ("metrics", "Map<string, any>", OPT),
+        ("resource", "Map<string, string>", OPT),
+    ]),
+];
+
+/// Look up a component by kind and type
+pub fn component(kind: Kind, component_type: &str) -> Option<&'static ComponentDef> {
+    COMPONENTS.iter().find(|(k, t, _, _)| *k == kind && *t == component_type)
+}
+
+/// Look up a settings record
+pub fn settings(name: &str) -> Option<Fields> {
+    SETTINGS.iter().find(|(n, _)| *n == name).map(|(_, fields)| *fields)
+}
+
+/// The type of a component ID: `otlp/internal` → `otlp`
+pub fn component_type(id: &str) -> &str {
+    id.split('/').next().unwrap_or(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup() {
+        assert_eq!(component(Kind::Exporter, "prometheus").map(|c| c.2), Some(METRICS));
+        assert!(component(Kind::Processor, "otlp").is_none());
+        assert_eq!(component_type("otlp/internal"), "otlp");
+        assert_eq!(Signal::of_pipeline("traces/sampled"), Some(Signal::Traces));
+        assert_eq!(Signal::of_pipeline("profiles"), None);
+    }
+
+    #[test]
+    fn test_settings_references_resolve() {
+        let primitives = ["string", "int", "bool", "any"];
+        let types = COMPONENTS
+            .iter()
+            .flat_map(|c| c.3.iter())
+            .chain(SETTINGS.iter().flat_map(|s| s.1.iter()))
+            .map(|(_, ty, _)| *ty)
+            .filter(|ty| !ty.starts_with("Map<"));
+        for ty in types {
+            let base = ty.trim_end_matches(" list");
+            assert!(primitives.contains(&base) || settings(base).is_some(), "unresolved `{}`", ty);
+        }
+    }
+}
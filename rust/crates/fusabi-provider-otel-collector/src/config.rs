@@ -0,0 +1,141 @@
+//! Collector configuration files
+//!
+//! Only the structure matters here: which components are configured under
+//! each section, and which of them the `service` section wires into
+//! pipelines. Component settings are left to the generated types.
+
+use crate::components::Kind;
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+
+/// The components and pipelines of a collector configuration
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CollectorConfig {
+    /// Configured components, section by section in file order
+    pub components: Vec<ComponentId>,
+    pub pipelines: Vec<PipelineDef>,
+    /// Extensions enabled in `service.extensions`
+    pub extensions: Vec<String>,
+}
+
+/// A configured component: `otlp/internal` under `receivers`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComponentId {
+    pub kind: Kind,
+    pub id: String,
+}
+
+/// A pipeline under `service.pipelines`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PipelineDef {
+    pub id: String,
+    pub receivers: Vec<String>,
+    pub processors: Vec<String>,
+    pub exporters: Vec<String>,
+}
+
+/// Parse a collector configuration
+pub fn parse_config(content: &str) -> ProviderResult<CollectorConfig> {
+    let value: Value = serde_yaml::from_str(content)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid collector configuration: {}", e)))?;
+    let root = match value {
+        Value::Mapping(root) if root.contains_key("service") || root.contains_key("receivers") => root,
+        _ => {
+            return Err(ProviderError::ParseError(
+                "Expected a collector configuration with `receivers` or `service`".to_string(),
+            ))
+        }
+    };
+
+    let mut components = Vec::new();
+    for kind in [Kind::Receiver, Kind::Processor, Kind::Exporter, Kind::Extension, Kind::Connector] {
+        match root.get(kind.section()) {
+            Some(Value::Mapping(section)) => {
+                for id in section.keys() {
+                    components.push(ComponentId {
+                        kind,
+                        id: string(id, kind.section())?,
+                    });
+                }
+            }
+            Some(Value::Null) | None => {}
+            Some(_) => {
+                return Err(ProviderError::ParseError(format!("`{}` must be a mapping", kind.section())));
+            }
+        }
+    }
+
+    let empty = Mapping::new();
+    let service = match root.get("service") {
+        Some(Value::Mapping(service)) => service,
+        _ => &empty,
+    };
+    let pipelines = match service.get("pipelines") {
+        Some(Value::Mapping(pipelines)) => pipelines
+            .iter()
+            .map(|(id, pipeline)| {
+                let id = string(id, "service.pipelines")?;
+                let list = |key: &str| ids(pipeline.get(key), &format!("service.pipelines.{}.{}", id, key));
+                Ok(PipelineDef {
+                    receivers: list("receivers")?,
+                    processors: list("processors")?,
+                    exporters: list("exporters")?,
+                    id,
+                })
+            })
+            .collect::<ProviderResult<_>>()?,
+        _ => Vec::new(),
+    };
+
+    Ok(CollectorConfig {
+        components,
+        pipelines,
+        extensions: ids(service.get("extensions"), "service.extensions")?,
+    })
+}
+
+fn ids(value: Option<&Value>, location: &str) -> ProviderResult<Vec<String>> {
+    match value {
+        Some(Value::Sequence(ids)) => ids.iter().map(|id| string(id, location)).collect(),
+        Some(Value::Null) | None => Ok(Vec::new()),
+        Some(_) => Err(ProviderError::ParseError(format!("`{}` must be a list", location))),
+    }
+}
+
+fn string(value: &Value, location: &str) -> ProviderResult<String> {
+    value
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| ProviderError::ParseError(format!("Component IDs in `{}` must be strings", location)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config = parse_config(
+            "receivers:\n  otlp:\n    protocols: {grpc: {}}\n  prometheus/self: {}\nexporters:\n  debug:\nconnectors:\n  spanmetrics:\nservice:\n  extensions: [health_check]\n  pipelines:\n    traces:\n      receivers: [otlp]\n      exporters: [debug, spanmetrics]\n    metrics/self:\n      receivers: [prometheus/self, spanmetrics]\n      processors: [batch]\n      exporters: [debug]\n",
+        )
+        .unwrap();
+        let ids: Vec<(Kind, &str)> = config.components.iter().map(|c| (c.kind, c.id.as_str())).collect();
+        assert_eq!(
+            ids,
+            [
+                (Kind::Receiver, "otlp"),
+                (Kind::Receiver, "prometheus/self"),
+                (Kind::Exporter, "debug"),
+                (Kind::Connector, "spanmetrics"),
+            ]
+        );
+        assert_eq!(config.extensions, ["health_check"]);
+        assert_eq!(config.pipelines[1].id, "metrics/self");
+        assert_eq!(config.pipelines[1].processors, ["batch"]);
+        assert!(config.pipelines[0].processors.is_empty());
+
+        assert!(parse_config("exporters: {}").is_err());
+        assert!(parse_config("receivers: [otlp]").is_err());
+    }
+}
@@ -0,0 +1,633 @@
+//! OpenTelemetry Collector Type Provider
+//!
+//! Generates Fusabi types for OpenTelemetry Collector configuration:
+//! receivers, processors, exporters, extensions and the `service`
+//! pipelines that wire them, so collector configs can be generated and
+//! checked by the same Fusabi tooling that manages Hibana's own.
+//!
+//! # Sources
+//!
+//! - `otelcol` (or `collector`): the modeled components, narrowed by the
+//!   `receivers`, `processors`, `exporters` and `extensions` params; see
+//!   [`components`] for the components and their settings
+//! - A collector configuration, inline or from a file: the components it
+//!   configures, by ID, and its pipelines
+//!
+//! # Generated Types
+//!
+//! Each component type gets a record named after it and its kind
+//! (`OtlpReceiver`, `MemoryLimiterProcessor`), and settings it shares with
+//! others get their own (`TlsClientSettings`, `RetrySettings`). The
+//! sections are records with a field per component ID (`Receivers`,
+//! `Exporters`), followed by `Pipelines`, `Service` and `Config` for the
+//! whole file. IDs that are not identifiers are sanitized
+//! (`otlp/internal` → `otlp_internal`).
+//!
+//! For a configuration, section fields and pipelines are the ones it
+//! declares; for `otelcol` every field is optional and `Pipelines` has the
+//! `traces`, `metrics` and `logs` pipelines. Components without a model,
+//! and `connectors`, are `Map<string, any>`.
+//!
+//! # Params
+//!
+//! | Param        | Meaning                                                 |
+//! |--------------|---------------------------------------------------------|
+//! | `receivers`  | Comma-separated receiver types for `otelcol`            |
+//! | `processors` | Comma-separated processor types for `otelcol`           |
+//! | `exporters`  | Comma-separated exporter types for `otelcol`            |
+//! | `extensions` | Comma-separated extension types for `otelcol`           |
+//!
+//! Without any of them every modeled component is generated; with any,
+//! only the listed ones.
+//!
+//! # Validation
+//!
+//! Configurations are checked the way the collector checks them on start:
+//! pipelines must name configured components that handle their signal,
+//! and need at least one receiver and exporter.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_otel_collector::OtelCollectorProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = OtelCollectorProvider::new();
+//! let params = ProviderParams::default()
+//!     .with("receivers", "otlp")
+//!     .with("processors", "batch,memory_limiter")
+//!     .with("exporters", "otlphttp");
+//! let schema = provider.resolve_schema("otelcol", &params)?;
+//! let types = provider.generate_types(&schema, "Collector")?;
+//! ```
+
+pub mod components;
+pub mod config;
+
+pub use components::{Kind, Signal};
+pub use config::{parse_config, CollectorConfig, ComponentId, PipelineDef};
+
+use std::collections::{BTreeSet, HashSet};
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceValidator, TypeShape, TypedParams,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, TypeExpr, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use serde::{Deserialize, Serialize};
+
+/// Type of components without a model
+const UNTYPED: &str = "Map<string, any>";
+
+/// Components and pipelines to generate, stored in [`Schema::Custom`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Selection {
+    components: Vec<ComponentId>,
+    pipelines: Vec<String>,
+    /// The configuration the selection was read from, if any
+    config: Option<CollectorConfig>,
+}
+
+/// OpenTelemetry Collector type provider
+pub struct OtelCollectorProvider {
+    generator: TypeGenerator,
+}
+
+impl OtelCollectorProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<Selection> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected a collector component selection: {}", e)))
+    }
+
+    /// The modeled components the params select
+    fn select(&self, typed: &TypedParams) -> ProviderResult<Vec<ComponentId>> {
+        let lists: Vec<(Kind, Vec<&str>)> = Kind::MODELED
+            .iter()
+            .filter_map(|kind| typed.list(kind.section()).map(|list| (*kind, list)))
+            .collect();
+        if lists.is_empty() {
+            return Ok(components::COMPONENTS
+                .iter()
+                .map(|(kind, component_type, _, _)| ComponentId { kind: *kind, id: component_type.to_string() })
+                .collect());
+        }
+
+        let mut selected = Vec::new();
+        for (kind, list) in lists {
+            for component_type in list {
+                if components::component(kind, component_type).is_none() {
+                    let known: Vec<&str> = components::COMPONENTS
+                        .iter()
+                        .filter(|c| c.0 == kind)
+                        .map(|c| c.1)
+                        .collect();
+                    return Err(ProviderError::InvalidSource(format!(
+                        "Unknown {} `{}`; expected one of {}",
+                        kind.section().trim_end_matches('s'),
+                        component_type,
+                        known.join(", ")
+                    )));
+                }
+                selected.push(ComponentId { kind, id: component_type.to_string() });
+            }
+        }
+        Ok(selected)
+    }
+
+    fn generate(&self, selection: &Selection) -> (Vec<TypeDefinition>, Vec<Diagnostic>) {
+        let named = |name: &str| -> TypeExpr { TypeShape::named(name).into() };
+        let declared = selection.config.is_some();
+        let field_type = |ty: &str, required: bool| -> TypeExpr { TypeShape::parse(ty).option_unless(required).into() };
+
+        let mut diagnostics = Vec::new();
+        let mut component_records = Vec::new();
+        let mut emitted = BTreeSet::new();
+        let mut sections = Vec::new();
+        for kind in Kind::MODELED {
+            let mut fields = Vec::new();
+            for component in selection.components.iter().filter(|c| c.kind == kind) {
+                let component_type = components::component_type(&component.id);
+                let ty = match components::component(kind, component_type) {
+                    Some((_, _, _, component_fields)) => {
+                        let record = format!("{}{}", self.generator.naming.apply(component_type), kind.suffix());
+                        if emitted.insert(record.clone()) {
+                            component_records.push(self.record(&record, component_fields));
+                        }
+                        record
+                    }
+                    None => {
+                        diagnostics.push(
+                            Diagnostic::warning(
+                                "unknown-component",
+                                format!(
+                                    "No model for {} `{}`; generated as `{}`",
+                                    kind.section().trim_end_matches('s'),
+                                    component_type,
+                                    UNTYPED
+                                ),
+                            )
+                            .at(format!("{}.{}", kind.section(), component.id)),
+                        );
+                        UNTYPED.to_string()
+                    }
+                };
+                fields.push((field_name(&component.id), field_type(&ty, declared)));
+            }
+            if !fields.is_empty() {
+                sections.push((kind, self.generator.naming.apply(kind.section()), fields));
+            }
+        }
+
+        // Settings the generated components reach, in model order
+        let mut reached: BTreeSet<&str> = ["Pipeline", "ServiceTelemetry"].into();
+        let mut pending: Vec<&str> = components::COMPONENTS
+            .iter()
+            .filter(|(kind, component_type, _, _)| {
+                emitted.contains(&format!("{}{}", self.generator.naming.apply(component_type), kind.suffix()))
+            })
+            .flat_map(|component| component.3.iter().map(|(_, ty, _)| *ty))
+            .collect();
+        while let Some(ty) = pending.pop() {
+            let base = ty.trim_end_matches(" list");
+            if let Some(fields) = components::settings(base) {
+                if reached.insert(base) {
+                    pending.extend(fields.iter().map(|(_, ty, _)| *ty));
+                }
+            }
+        }
+        let mut types: Vec<TypeDefinition> = components::SETTINGS
+            .iter()
+            .filter(|(name, _)| reached.contains(name))
+            .map(|(name, fields)| self.record(name, fields))
+            .collect();
+        types.extend(component_records);
+
+        let mut config_fields = Vec::new();
+        for (kind, name, fields) in sections {
+            let required = matches!(kind, Kind::Receiver | Kind::Exporter);
+            config_fields.push((kind.section().to_string(), field_type(&name, required)));
+            types.push(TypeDefinition::Record(RecordDef { name, fields }));
+        }
+        config_fields.push(("connectors".to_string(), field_type(UNTYPED, false)));
+        config_fields.push(("service".to_string(), named("Service")));
+
+        types.push(TypeDefinition::Record(RecordDef {
+            name: "Pipelines".to_string(),
+            fields: selection
+                .pipelines
+                .iter()
+                .map(|id| (field_name(id), field_type("Pipeline", declared)))
+                .collect(),
+        }));
+        types.push(TypeDefinition::Record(RecordDef {
+            name: "Service".to_string(),
+            fields: vec![
+                ("extensions".to_string(), field_type("string list", false)),
+                ("pipelines".to_string(), named("Pipelines")),
+                ("telemetry".to_string(), field_type("ServiceTelemetry", false)),
+            ],
+        }));
+        types.push(TypeDefinition::Record(RecordDef {
+            name: "Config".to_string(),
+            fields: config_fields,
+        }));
+
+        if let Some(config) = &selection.config {
+            check_config(config, &mut diagnostics);
+        }
+        (types, diagnostics)
+    }
+
+    fn record(&self, name: &str, fields: components::Fields) -> TypeDefinition {
+        TypeDefinition::Record(RecordDef {
+            name: name.to_string(),
+            fields: fields
+                .iter()
+                .map(|(key, ty, required)| {
+                    (key.to_string(), TypeShape::parse(ty).option_unless(*required).into())
+                })
+                .collect(),
+        })
+    }
+}
+
+/// Check pipelines against the configured components
+fn check_config(config: &CollectorConfig, diagnostics: &mut Vec<Diagnostic>) {
+    let configured = |kind: Kind, id: &str| config.components.iter().any(|c| c.kind == kind && c.id == id);
+    let mut used: HashSet<(Kind, &str)> = HashSet::new();
+
+    for pipeline in &config.pipelines {
+        let location = format!("service.pipelines.{}", pipeline.id);
+        let signal = Signal::of_pipeline(&pipeline.id);
+        if signal.is_none() {
+            diagnostics.push(
+                Diagnostic::warning(
+                    "unknown-signal",
+                    format!("Pipeline `{}` is not a traces, metrics or logs pipeline", pipeline.id),
+                )
+                .at(location.clone()),
+            );
+        }
+        for (kind, ids) in [
+            (Kind::Receiver, &pipeline.receivers),
+            (Kind::Processor, &pipeline.processors),
+            (Kind::Exporter, &pipeline.exporters),
+        ] {
+            let location = format!("{}.{}", location, kind.section());
+            if ids.is_empty() && kind != Kind::Processor {
+                diagnostics.push(
+                    Diagnostic::error("empty-pipeline", format!("Pipeline `{}` has no {}", pipeline.id, kind.section()))
+                        .at(location.clone()),
+                );
+            }
+            for id in ids {
+                // Connectors are exporters of one pipeline and receivers of another
+                if kind != Kind::Processor && configured(Kind::Connector, id) {
+                    used.insert((Kind::Connector, id));
+                    continue;
+                }
+                if !configured(kind, id) {
+                    diagnostics.push(
+                        Diagnostic::error(
+                            "undefined-component",
+                            format!("{} `{}` is not configured", kind.suffix(), id),
+                        )
+                        .at(location.clone()),
+                    );
+                    continue;
+                }
+                used.insert((kind, id));
+                let supported = components::component(kind, components::component_type(id)).map(|c| c.2);
+                if let (Some(signal), Some(supported)) = (signal, supported) {
+                    if !supported.contains(&signal) {
+                        diagnostics.push(
+                            Diagnostic::error(
+                                "unsupported-signal",
+                                format!("{} `{}` does not support {}", kind.suffix(), id, signal.name()),
+                            )
+                            .at(location.clone()),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    for id in &config.extensions {
+        if configured(Kind::Extension, id) {
+            used.insert((Kind::Extension, id));
+        } else {
+            diagnostics.push(
+                Diagnostic::error("undefined-component", format!("Extension `{}` is not configured", id))
+                    .at("service.extensions".to_string()),
+            );
+        }
+    }
+
+    for component in &config.components {
+        if !used.contains(&(component.kind, component.id.as_str())) {
+            diagnostics.push(
+                Diagnostic::info(
+                    "unused-component",
+                    format!("{} `{}` is configured but not used by the service", component.kind.suffix(), component.id),
+                )
+                .at(format!("{}.{}", component.kind.section(), component.id)),
+            );
+        }
+    }
+}
+
+/// `otlp/internal` → `otlp_internal`
+fn field_name(id: &str) -> String {
+    id.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect()
+}
+
+impl Default for OtelCollectorProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for OtelCollectorProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("OtelCollectorProvider")
+            .param(
+                ParamSpec::new("receivers", ParamKind::List)
+                    .describe("Receiver types for `otelcol`"),
+            )
+            .param(
+                ParamSpec::new("processors", ParamKind::List)
+                    .describe("Processor types for `otelcol`"),
+            )
+            .param(
+                ParamSpec::new("exporters", ParamKind::List)
+                    .describe("Exporter types for `otelcol`"),
+            )
+            .param(
+                ParamSpec::new("extensions", ParamKind::List)
+                    .describe("Extension types for `otelcol`"),
+            )
+    }
+}
+
+impl TypeProvider for OtelCollectorProvider {
+    fn name(&self) -> &str {
+        "OtelCollectorProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let trimmed = source.trim();
+        let selection = match trimmed.to_ascii_lowercase().as_str() {
+            "otelcol" | "collector" => Selection {
+                components: self.select(&typed)?,
+                pipelines: components::ALL_SIGNALS.iter().map(|s| s.name().to_string()).collect(),
+                config: None,
+            },
+            _ => {
+                let config = if trimmed.starts_with('{') || trimmed.contains('\n') {
+                    parse_config(source)?
+                } else {
+                    parse_config(&read_source(source, params)?)?
+                };
+                Selection {
+                    components: config
+                        .components
+                        .iter()
+                        .filter(|c| c.kind != Kind::Connector)
+                        .cloned()
+                        .collect(),
+                    pipelines: config.pipelines.iter().map(|p| p.id.clone()).collect(),
+                    config: Some(config),
+                }
+            }
+        };
+
+        let json = serde_json::to_string(&selection)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected a collector component selection".to_string()));
+        };
+
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+        module.types = self.generate(&self.parse_schema(json)?).0;
+
+        let mut result = GeneratedTypes::new();
+        result.modules.push(module);
+        Ok(result)
+    }
+}
+
+impl SourceValidator for OtelCollectorProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected a collector component selection".to_string()));
+        };
+
+        Ok(self.generate(&self.parse_schema(json)?).1)
+    }
+}
+
+impl DescribedProvider for OtelCollectorProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .embedded("otelcol")
+            .embedded("collector")
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    const CONFIG: &str = r#"
+receivers:
+  otlp:
+    protocols:
+      grpc: {endpoint: "0.0.0.0:4317"}
+  prometheus/self:
+    config:
+      scrape_configs: [{job_name: collector, static_configs: [{targets: ["localhost:8888"]}]}]
+  journald: {}
+processors:
+  batch:
+exporters:
+  otlphttp/hibana: {endpoint: "https://hibana:4318"}
+  debug:
+connectors:
+  spanmetrics:
+extensions:
+  health_check:
+  pprof:
+service:
+  extensions: [health_check, zpages]
+  pipelines:
+    traces:
+      receivers: [otlp, prometheus/self]
+      processors: [batch]
+      exporters: [otlphttp/hibana, spanmetrics]
+    metrics:
+      receivers: [spanmetrics]
+      exporters: [otlphttp/hibana, missing]
+    profiles:
+      receivers: [otlp]
+      exporters: []
+"#;
+
+    fn fields(types: &GeneratedTypes, name: &str) -> Vec<(String, String)> {
+        types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+            .fields
+            .iter()
+            .map(|(field, ty)| (field.clone(), ty.to_string()))
+            .collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(f, t)| (f.to_string(), t.to_string())).collect()
+    }
+
+    fn names(types: &GeneratedTypes) -> Vec<String> {
+        types.modules[0]
+            .types
+            .iter()
+            .map(|t| match t {
+                TypeDefinition::Record(r) => r.name.clone(),
+                TypeDefinition::Du(du) => du.name.clone(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_selected_components() {
+        let provider = OtelCollectorProvider::new();
+        let params = ProviderParams::default()
+            .with("receivers", "otlp")
+            .with("processors", "batch, memory_limiter")
+            .with("exporters", "otlp");
+        let schema = provider.resolve_schema("otelcol", &params).unwrap();
+        let types = provider.generate_types(&schema, "Collector").unwrap();
+
+        assert_eq!(
+            names(&types),
+            [
+                "OtlpProtocols", "GrpcServerSettings", "HttpServerSettings", "CorsSettings",
+                "TlsServerSettings", "TlsClientSettings", "RetrySettings", "QueueSettings",
+                "Pipeline", "ServiceTelemetry",
+                "OtlpReceiver", "BatchProcessor", "MemoryLimiterProcessor", "OtlpExporter",
+                "Receivers", "Processors", "Exporters", "Pipelines", "Service", "Config",
+            ]
+        );
+        assert_eq!(
+            fields(&types, "MemoryLimiterProcessor")[..2],
+            pairs(&[("check_interval", "string"), ("limit_mib", "int option")])
+        );
+        assert_eq!(
+            fields(&types, "Processors"),
+            pairs(&[("batch", "BatchProcessor option"), ("memory_limiter", "MemoryLimiterProcessor option")])
+        );
+        assert_eq!(
+            fields(&types, "Config"),
+            pairs(&[
+                ("receivers", "Receivers"),
+                ("processors", "Processors option"),
+                ("exporters", "Exporters"),
+                ("connectors", "Map<string, any> option"),
+                ("service", "Service"),
+            ])
+        );
+        assert_eq!(fields(&types, "Pipelines").len(), 3);
+
+        let error = provider
+            .resolve_schema("otelcol", &ProviderParams::default().with("exporters", "zipkin"))
+            .unwrap_err();
+        assert!(error.to_string().contains("Unknown exporter `zipkin`"));
+    }
+
+    #[test]
+    fn test_config_types() {
+        let provider = OtelCollectorProvider::new();
+        let schema = provider.resolve_schema(CONFIG, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Collector").unwrap();
+
+        assert_eq!(
+            fields(&types, "Receivers"),
+            pairs(&[
+                ("otlp", "OtlpReceiver"),
+                ("prometheus_self", "PrometheusReceiver"),
+                ("journald", "Map<string, any>"),
+            ])
+        );
+        assert_eq!(
+            fields(&types, "Exporters"),
+            pairs(&[("otlphttp_hibana", "OtlphttpExporter"), ("debug", "DebugExporter")])
+        );
+        assert_eq!(
+            fields(&types, "Pipelines"),
+            pairs(&[("traces", "Pipeline"), ("metrics", "Pipeline"), ("profiles", "Pipeline")])
+        );
+        assert!(names(&types).contains(&"ScrapeConfig".to_string()));
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/otel-collector.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_config_diagnostics() {
+        let path = std::env::temp_dir().join(format!("fusabi-otelcol-{}.yaml", std::process::id()));
+        std::fs::write(&path, CONFIG).unwrap();
+
+        let provider = OtelCollectorProvider::new();
+        let schema = provider.resolve_schema(path.to_str().unwrap(), &ProviderParams::default()).unwrap();
+        let diagnostics = provider.schema_diagnostics(&schema).unwrap();
+        let found: Vec<(&str, &str)> = diagnostics
+            .iter()
+            .map(|d| (d.code.as_str(), d.location.as_deref().unwrap_or("")))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("unknown-component", "receivers.journald"),
+                ("unsupported-signal", "service.pipelines.traces.receivers"),
+                ("undefined-component", "service.pipelines.metrics.exporters"),
+                ("unknown-signal", "service.pipelines.profiles"),
+                ("empty-pipeline", "service.pipelines.profiles.exporters"),
+                ("undefined-component", "service.extensions"),
+                ("unused-component", "receivers.journald"),
+                ("unused-component", "exporters.debug"),
+                ("unused-component", "extensions.pprof"),
+            ]
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+}
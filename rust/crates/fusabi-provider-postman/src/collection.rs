@@ -0,0 +1,461 @@
+//! Postman collection reading
+//!
+//! A v2.0 or v2.1 collection is flattened into its requests, each with the
+//! folders it sits in. Saved examples contribute their responses, and the
+//! original requests they were saved from contribute query parameters and
+//! body samples.
+//!
+//! Raw bodies are often not quite JSON: `{"id": {{userId}}}` leaves a
+//! variable where a value goes. Variables outside strings are replaced
+//! with the collection variable's value when that is JSON, and `null`
+//! otherwise, before parsing.
+
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// The requests of a collection, in document order
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Collection {
+    pub name: String,
+    pub requests: Vec<Request>,
+}
+
+/// A request, with what its examples add
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Request {
+    /// Enclosing folders, outermost first
+    pub folders: Vec<String>,
+    pub name: String,
+    /// Upper case
+    pub method: String,
+    /// `/users/:id`, without host and query
+    pub path: String,
+    /// Path variables (`:id`) then query parameters
+    pub params: Vec<Param>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Payload>,
+    pub examples: Vec<Example>,
+}
+
+/// A path variable or query parameter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Param {
+    pub key: String,
+    /// Example values, variables substituted where known
+    pub values: Vec<String>,
+    /// Path variables and enabled query parameters are required
+    pub required: bool,
+}
+
+/// A saved example response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Example {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Payload>,
+}
+
+/// A request or response body
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Payload {
+    /// Parsed JSON samples, one per body seen
+    Json { samples: Vec<Value> },
+    /// `urlencoded` or `formdata` fields
+    Form { fields: Vec<FormField> },
+    /// A body that claims to be JSON but isn't
+    Invalid { error: String },
+    /// XML, HTML, plain text or a file
+    Other { language: String },
+}
+
+/// A form field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormField {
+    pub key: String,
+    /// A `formdata` file upload
+    pub file: bool,
+}
+
+/// Read a collection
+pub fn parse_collection(content: &str) -> ProviderResult<Collection> {
+    let document: Value = serde_json::from_str(content)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid Postman collection: {}", e)))?;
+    if document.get("requests").is_some() && document.get("item").is_none() {
+        return Err(ProviderError::InvalidSource(
+            "Postman collection v1 is not supported; export the collection as v2.1".to_string(),
+        ));
+    }
+    let (Some(info), Some(items)) = (document.get("info"), document.get("item").and_then(Value::as_array)) else {
+        return Err(ProviderError::InvalidSource(
+            "Expected a Postman collection with `info` and `item`".to_string(),
+        ));
+    };
+
+    let variables: HashMap<String, String> = document
+        .get("variable")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|v| Some((v.get("key")?.as_str()?.to_string(), text(v.get("value")?))))
+        .collect();
+    let reader = Reader { variables };
+
+    let mut collection = Collection {
+        name: info.get("name").and_then(Value::as_str).unwrap_or_default().to_string(),
+        requests: Vec::new(),
+    };
+    reader.items(items, &mut Vec::new(), &mut collection.requests);
+    Ok(collection)
+}
+
+struct Reader {
+    variables: HashMap<String, String>,
+}
+
+impl Reader {
+    fn items(&self, items: &[Value], folders: &mut Vec<String>, requests: &mut Vec<Request>) {
+        for item in items {
+            let name = item.get("name").and_then(Value::as_str).unwrap_or_default().to_string();
+            if let Some(children) = item.get("item").and_then(Value::as_array) {
+                folders.push(name);
+                self.items(children, folders, requests);
+                folders.pop();
+            } else if let Some(request) = item.get("request") {
+                requests.push(self.request(folders.clone(), name, request, item.get("response")));
+            }
+        }
+    }
+
+    fn request(&self, folders: Vec<String>, name: String, request: &Value, responses: Option<&Value>) -> Request {
+        let method = request.get("method").and_then(Value::as_str).unwrap_or("GET").to_uppercase();
+        let url = request.get("url").unwrap_or(request);
+        let mut result = Request {
+            folders,
+            name,
+            method,
+            path: path(url),
+            params: Vec::new(),
+            body: request.get("body").and_then(|body| self.body(body, request.get("header"))),
+            examples: Vec::new(),
+        };
+
+        let variables = url.get("variable").and_then(Value::as_array);
+        for segment in result.path.split('/').filter_map(|s| s.strip_prefix(':')) {
+            let value = variables
+                .into_iter()
+                .flatten()
+                .find(|v| v.get("key").and_then(Value::as_str) == Some(segment))
+                .and_then(|v| v.get("value"))
+                .map(text);
+            result.params.push(Param {
+                key: segment.to_string(),
+                values: value.map(|v| self.substitute(&v)).into_iter().collect(),
+                required: true,
+            });
+        }
+        self.add_query(&mut result.params, url, true);
+
+        for response in responses.and_then(Value::as_array).into_iter().flatten() {
+            let original = response.get("originalRequest");
+            if let Some(original) = original {
+                self.add_query(&mut result.params, original.get("url").unwrap_or(original), false);
+                if let Some(Payload::Json { samples }) = original.get("body").and_then(|b| self.body(b, original.get("header"))) {
+                    if let Some(Payload::Json { samples: existing }) = &mut result.body {
+                        existing.extend(samples);
+                    }
+                }
+            }
+
+            let language = response
+                .get("_postman_previewlanguage")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .or_else(|| content_type(response.get("header")));
+            result.examples.push(Example {
+                name: response.get("name").and_then(Value::as_str).unwrap_or_default().to_string(),
+                code: response.get("code").and_then(Value::as_u64).and_then(|c| u16::try_from(c).ok()),
+                body: response
+                    .get("body")
+                    .and_then(Value::as_str)
+                    .filter(|body| !body.trim().is_empty())
+                    .map(|body| self.raw(body, language.as_deref())),
+            });
+        }
+        result
+    }
+
+    /// Query parameters of a URL. Those of the request itself are required
+    /// unless disabled; those only examples have are optional.
+    fn add_query(&self, params: &mut Vec<Param>, url: &Value, own: bool) {
+        let query: Vec<(String, String, bool)> = match url.get("query").and_then(Value::as_array) {
+            Some(query) => query
+                .iter()
+                .filter_map(|q| {
+                    let key = q.get("key")?.as_str()?.to_string();
+                    let disabled = q.get("disabled").and_then(Value::as_bool) == Some(true);
+                    Some((key, q.get("value").map(text).unwrap_or_default(), !disabled))
+                })
+                .collect(),
+            None => {
+                let raw = url.as_str().or_else(|| url.get("raw").and_then(Value::as_str)).unwrap_or_default();
+                let query = raw.split_once('?').map(|(_, q)| q).unwrap_or_default();
+                let query = query.split('#').next().unwrap_or_default();
+                query
+                    .split('&')
+                    .filter(|pair| !pair.is_empty())
+                    .map(|pair| {
+                        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                        (key.to_string(), value.to_string(), true)
+                    })
+                    .collect()
+            }
+        };
+
+        for (key, value, enabled) in query {
+            let value = self.substitute(&value);
+            match params.iter_mut().find(|p| p.key == key) {
+                Some(param) => {
+                    if !value.is_empty() {
+                        param.values.push(value);
+                    }
+                }
+                None => params.push(Param {
+                    key,
+                    values: Some(value).filter(|v| !v.is_empty()).into_iter().collect(),
+                    required: own && enabled,
+                }),
+            }
+        }
+    }
+
+    fn body(&self, body: &Value, headers: Option<&Value>) -> Option<Payload> {
+        let disabled = |field: &&Value| field.get("disabled").and_then(Value::as_bool) == Some(true);
+        match body.get("mode").and_then(Value::as_str)? {
+            "raw" => {
+                let raw = body.get("raw").and_then(Value::as_str).filter(|raw| !raw.trim().is_empty())?;
+                let language = body
+                    .pointer("/options/raw/language")
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+                    .or_else(|| content_type(headers));
+                Some(self.raw(raw, language.as_deref()))
+            }
+            mode @ ("urlencoded" | "formdata") => Some(Payload::Form {
+                fields: body
+                    .get(mode)
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter(|field| !disabled(field))
+                    .filter_map(|field| {
+                        Some(FormField {
+                            key: field.get("key")?.as_str()?.to_string(),
+                            file: field.get("type").and_then(Value::as_str) == Some("file"),
+                        })
+                    })
+                    .collect(),
+            }),
+            "graphql" => Some(Payload::Other { language: "graphql".to_string() }),
+            "file" => Some(Payload::Other { language: "file".to_string() }),
+            _ => None,
+        }
+    }
+
+    /// A raw body: JSON when its language says so, or when it looks like
+    /// JSON and its language doesn't say otherwise
+    fn raw(&self, raw: &str, language: Option<&str>) -> Payload {
+        let looks_like_json = raw.trim_start().starts_with(['{', '[']);
+        let language = language.map_or("text".to_string(), str::to_ascii_lowercase);
+        let json = language.contains("json") || (language == "text" && looks_like_json);
+        if !json {
+            return Payload::Other { language };
+        }
+        match serde_json::from_str(&self.substitute_json(raw)) {
+            Ok(sample) => Payload::Json { samples: vec![sample] },
+            Err(e) => Payload::Invalid { error: e.to_string() },
+        }
+    }
+
+    /// Replace `{{variable}}` with its value, where known
+    fn substitute(&self, text: &str) -> String {
+        let mut result = String::new();
+        let mut rest = text;
+        while let Some(start) = rest.find("{{") {
+            let Some(end) = rest[start..].find("}}") else {
+                break;
+            };
+            result.push_str(&rest[..start]);
+            let name = &rest[start + 2..start + end];
+            match self.variables.get(name.trim()) {
+                Some(value) => result.push_str(value),
+                None => result.push_str(&rest[start..start + end + 2]),
+            }
+            rest = &rest[start + end + 2..];
+        }
+        result.push_str(rest);
+        result
+    }
+
+    /// Replace variables outside strings with a JSON value
+    fn substitute_json(&self, raw: &str) -> String {
+        let mut result = String::with_capacity(raw.len());
+        let mut in_string = false;
+        let mut chars = raw.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => in_string = !in_string,
+                '\\' if in_string => {
+                    result.push(c);
+                    if let Some((_, escaped)) = chars.next() {
+                        result.push(escaped);
+                    }
+                    continue;
+                }
+                '{' if !in_string && raw[i..].starts_with("{{") => {
+                    if let Some(end) = raw[i..].find("}}") {
+                        let name = raw[i + 2..i + end].trim();
+                        result.push_str(&self.json_value(name));
+                        while chars.peek().is_some_and(|(j, _)| *j < i + end + 2) {
+                            chars.next();
+                        }
+                        continue;
+                    }
+                }
+                _ => {}
+            }
+            result.push(c);
+        }
+        result
+    }
+
+    /// JSON text for a variable used as a value
+    fn json_value(&self, name: &str) -> String {
+        match name {
+            "$timestamp" | "$randomInt" => return "0".to_string(),
+            "$randomBoolean" => return "true".to_string(),
+            _ => {}
+        }
+        match self.variables.get(name) {
+            Some(value) if serde_json::from_str::<Value>(value).is_ok() => value.clone(),
+            _ => "null".to_string(),
+        }
+    }
+}
+
+/// The path of a URL, from its `path` segments or its raw form
+fn path(url: &Value) -> String {
+    if let Some(segments) = url.get("path") {
+        let segments: Vec<String> = match segments {
+            Value::Array(segments) => segments.iter().map(text).collect(),
+            other => vec![text(other).trim_start_matches('/').to_string()],
+        };
+        return format!("/{}", segments.join("/"));
+    }
+
+    let raw = url.as_str().or_else(|| url.get("raw").and_then(Value::as_str)).unwrap_or_default();
+    let raw = raw.split(['?', '#']).next().unwrap_or_default();
+    let without_scheme = raw.split_once("://").map_or(raw, |(_, rest)| rest);
+    match without_scheme.find('/') {
+        Some(start) => without_scheme[start..].to_string(),
+        None => "/".to_string(),
+    }
+}
+
+/// The media type of a `Content-Type` header, if any
+fn content_type(headers: Option<&Value>) -> Option<String> {
+    headers?
+        .as_array()?
+        .iter()
+        .find(|h| h.get("key").and_then(Value::as_str).is_some_and(|k| k.eq_ignore_ascii_case("content-type")))
+        .and_then(|h| h.get("value")?.as_str())
+        .map(|value| value.split(';').next().unwrap_or_default().trim().to_string())
+}
+
+/// A variable, parameter or path segment value; Postman writes most as
+/// strings, some as numbers or path segment objects
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        Value::Object(segment) => segment.get("value").map(text).unwrap_or_default(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_collection() {
+        let collection = json!({
+            "info": {"name": "Shop", "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"},
+            "variable": [{"key": "userId", "value": "42"}, {"key": "baseUrl", "value": "https://api.shop.test"}],
+            "item": [
+                {"name": "Users", "item": [
+                    {"name": "Get user", "request": {
+                        "method": "get",
+                        "url": {"raw": "{{baseUrl}}/users/:id?verbose=true", "host": ["{{baseUrl}}"], "path": ["users", ":id"],
+                            "query": [{"key": "verbose", "value": "true"}, {"key": "fields", "value": "", "disabled": true}],
+                            "variable": [{"key": "id", "value": "{{userId}}"}]}
+                    }, "response": [
+                        {"name": "Found", "code": 200, "_postman_previewlanguage": "json", "body": "{\"id\": 42}",
+                         "originalRequest": {"method": "GET", "url": "{{baseUrl}}/users/42?expand=orders"}},
+                        {"name": "Missing", "code": 404, "header": [{"key": "Content-Type", "value": "text/html; charset=utf-8"}], "body": "<h1>Not found</h1>"}
+                    ]}
+                ]},
+                {"name": "Create order", "request": {
+                    "method": "POST",
+                    "url": "https://api.shop.test/orders",
+                    "body": {"mode": "raw", "raw": "{\"user\": {{userId}}, \"note\": \"{{note}}\", \"qty\": {{qty}}}", "options": {"raw": {"language": "json"}}}
+                }},
+                {"name": "Upload", "request": {
+                    "method": "PUT",
+                    "url": "{{baseUrl}}/files",
+                    "body": {"mode": "formdata", "formdata": [{"key": "name", "value": "a"}, {"key": "file", "type": "file", "src": "a.png"}, {"key": "old", "disabled": true}]}
+                }}
+            ]
+        });
+
+        let collection = parse_collection(&collection.to_string()).unwrap();
+        assert_eq!(collection.name, "Shop");
+        assert_eq!(collection.requests.len(), 3);
+
+        let get_user = &collection.requests[0];
+        assert_eq!(get_user.folders, ["Users"]);
+        assert_eq!((get_user.method.as_str(), get_user.path.as_str()), ("GET", "/users/:id"));
+        let params: Vec<(&str, &[String], bool)> =
+            get_user.params.iter().map(|p| (p.key.as_str(), p.values.as_slice(), p.required)).collect();
+        assert_eq!(
+            params,
+            [
+                ("id", &["42".to_string()][..], true),
+                ("verbose", &["true".to_string()][..], true),
+                ("fields", &[][..], false),
+                ("expand", &["orders".to_string()][..], false),
+            ]
+        );
+        assert_eq!(get_user.examples[0].body, Some(Payload::Json { samples: vec![json!({"id": 42})] }));
+        assert_eq!(get_user.examples[1].body, Some(Payload::Other { language: "text/html".to_string() }));
+
+        let create = &collection.requests[1];
+        assert_eq!(create.path, "/orders");
+        assert_eq!(
+            create.body,
+            Some(Payload::Json { samples: vec![json!({"user": 42, "note": "{{note}}", "qty": null})] })
+        );
+
+        let Some(Payload::Form { fields }) = &collection.requests[2].body else {
+            panic!("Expected a form body");
+        };
+        assert_eq!(fields, &[FormField { key: "name".to_string(), file: false }, FormField { key: "file".to_string(), file: true }]);
+
+        assert!(parse_collection(r#"{"requests": []}"#).unwrap_err().to_string().contains("v1"));
+    }
+}
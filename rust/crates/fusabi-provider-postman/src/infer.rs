@@ -0,0 +1,169 @@
+//! Shape inference for sample bodies and parameter values
+//!
+//! Samples of the same body merge key by key: a key some samples lack, or
+//! set to `null`, is optional. Integers and floats merge to floats; other
+//! disagreements give up on the position and type it `any`.
+
+use serde_json::Value;
+
+/// Inferred shape of the values found in one position
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    /// Only `null` was seen
+    Null,
+    Bool,
+    /// Fits in 32 bits
+    Int,
+    Int64,
+    Float,
+    String,
+    /// Element shape; `Null` for lists that were always empty
+    List(Box<Shape>),
+    Object(ObjectShape),
+    /// Values that don't agree
+    Any,
+}
+
+/// Merged keys of one or more objects
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ObjectShape {
+    /// Number of objects merged
+    pub samples: usize,
+    /// Keys in first-seen order
+    pub fields: Vec<FieldShape>,
+}
+
+/// One key of an [`ObjectShape`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldShape {
+    pub name: String,
+    pub shape: Shape,
+    /// Number of merged objects that had the key
+    pub present: usize,
+    /// Whether the key was `null` somewhere
+    pub nullable: bool,
+}
+
+impl FieldShape {
+    /// Whether some object lacked the key or set it to `null`
+    pub fn is_optional(&self, samples: usize) -> bool {
+        self.nullable || self.present < samples
+    }
+}
+
+impl Shape {
+    /// Shape of a JSON value
+    pub fn of(value: &Value) -> Self {
+        match value {
+            Value::Null => Shape::Null,
+            Value::Bool(_) => Shape::Bool,
+            Value::Number(n) => match n.as_i64() {
+                Some(n) if i32::try_from(n).is_ok() => Shape::Int,
+                Some(_) => Shape::Int64,
+                None => Shape::Float,
+            },
+            Value::String(_) => Shape::String,
+            Value::Array(items) => Shape::List(Box::new(Shape::merge_all(items.iter().map(Shape::of)))),
+            Value::Object(object) => Shape::Object(ObjectShape {
+                samples: 1,
+                fields: object
+                    .iter()
+                    .map(|(name, value)| FieldShape {
+                        name: name.clone(),
+                        shape: Shape::of(value),
+                        present: 1,
+                        nullable: value.is_null(),
+                    })
+                    .collect(),
+            }),
+        }
+    }
+
+    /// Shape of a parameter value, which is always text: `42` is an
+    /// integer and `true` a boolean. Unsubstituted `{{variables}}` say
+    /// nothing.
+    pub fn of_text(text: &str) -> Self {
+        if text.contains("{{") {
+            Shape::Null
+        } else if text == "true" || text == "false" {
+            Shape::Bool
+        } else if let Ok(n) = text.parse::<i64>() {
+            Shape::of(&Value::from(n))
+        } else if text.parse::<f64>().is_ok_and(f64::is_finite) {
+            Shape::Float
+        } else {
+            Shape::String
+        }
+    }
+
+    /// Merge the shapes of values found in the same position
+    pub fn merge(self, other: Shape) -> Shape {
+        match (self, other) {
+            (Shape::Null, shape) | (shape, Shape::Null) => shape,
+            (Shape::Object(a), Shape::Object(b)) => Shape::Object(a.merge(b)),
+            (a, b) if a == b => a,
+            (Shape::Int, Shape::Int64) | (Shape::Int64, Shape::Int) => Shape::Int64,
+            (Shape::Int | Shape::Int64, Shape::Float) | (Shape::Float, Shape::Int | Shape::Int64) => Shape::Float,
+            (Shape::List(a), Shape::List(b)) => Shape::List(Box::new(a.merge(*b))),
+            _ => Shape::Any,
+        }
+    }
+
+    /// Merge any number of shapes; `Null` when there are none
+    pub fn merge_all(shapes: impl IntoIterator<Item = Shape>) -> Shape {
+        shapes.into_iter().fold(Shape::Null, Shape::merge)
+    }
+}
+
+impl ObjectShape {
+    fn merge(mut self, other: ObjectShape) -> ObjectShape {
+        self.samples += other.samples;
+        for field in other.fields {
+            match self.fields.iter_mut().find(|f| f.name == field.name) {
+                Some(existing) => {
+                    existing.present += field.present;
+                    existing.nullable |= field.nullable;
+                    let shape = std::mem::replace(&mut existing.shape, Shape::Null);
+                    existing.shape = shape.merge(field.shape);
+                }
+                None => self.fields.push(field),
+            }
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_merge_samples() {
+        let samples = [
+            json!({"id": 1, "price": 10, "tags": [], "owner": {"name": "Ada"}}),
+            json!({"id": 5000000000i64, "price": 9.5, "tags": ["a"], "owner": null, "note": "x"}),
+            json!({"id": 3, "price": 1, "tags": [], "owner": {"name": 7}}),
+        ];
+        let Shape::Object(object) = Shape::merge_all(samples.iter().map(Shape::of)) else {
+            panic!("Expected an object shape");
+        };
+        let field = |name: &str| object.fields.iter().find(|f| f.name == name).unwrap();
+        assert_eq!(object.samples, 3);
+        assert_eq!(field("id").shape, Shape::Int64);
+        assert_eq!(field("price").shape, Shape::Float);
+        assert_eq!(field("tags").shape, Shape::List(Box::new(Shape::String)));
+        assert!(field("owner").is_optional(3));
+        let Shape::Object(owner) = &field("owner").shape else {
+            panic!("Expected an object shape");
+        };
+        assert_eq!(owner.fields[0].shape, Shape::Any);
+        assert!(field("note").is_optional(3));
+        assert!(!field("id").is_optional(3));
+
+        assert_eq!(Shape::of_text("42"), Shape::Int);
+        assert_eq!(Shape::of_text("2.5"), Shape::Float);
+        assert_eq!(Shape::of_text("{{page}}"), Shape::Null);
+        assert_eq!(Shape::of_text("nan"), Shape::String);
+    }
+}
@@ -0,0 +1,551 @@
+//! Postman Type Provider
+//!
+//! Generates Fusabi types from a Postman collection (v2.1, or v2.0), for
+//! APIs that have a collection but no OpenAPI document. Types are inferred
+//! from what the collection shows: example parameter values, request
+//! bodies, and the responses saved as examples.
+//!
+//! # Generated types
+//!
+//! Requests are grouped into modules by folder: a request in `Users/Admin`
+//! goes to `<Namespace>.Users.Admin`, one outside any folder to
+//! `<Namespace>`. For each request:
+//!
+//! - `<Req>Request` - a record with one field per path variable (`:id`)
+//!   and query parameter, plus `body` when the request has one. Parameter
+//!   types come from their example values; disabled query parameters, and
+//!   those only examples use, are optional
+//! - `<Req>Response` - a union with one case per status code of its saved
+//!   examples, such as `Ok of GetUserOkBody` or `NotFound`
+//!
+//! `<Req>` is the PascalCase request name. JSON bodies get records of
+//! their own (`<Req>Body`, `<Req><Status>Body`), merged across every
+//! sample of the same body: keys missing from some samples, or `null` in
+//! some, are optional. Form bodies become records of `string` fields, and
+//! `bytes` for file uploads.
+//!
+//! # Params
+//!
+//! | Param    | Meaning                                                     |
+//! |----------|-------------------------------------------------------------|
+//! | `folder` | Comma-separated folders to keep, such as `Users/Admin`      |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_postman::PostmanProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = PostmanProvider::new();
+//! let schema = provider.resolve_schema("shop.postman_collection.json", &ProviderParams::default())?;
+//! let types = provider.generate_types(&schema, "Shop")?;
+//! ```
+
+mod collection;
+mod infer;
+
+pub use collection::{parse_collection, Collection, Example, FormField, Param, Payload, Request};
+pub use infer::{FieldShape, ObjectShape, Shape};
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceValidator, TypeShape,
+};
+use fusabi_type_providers::{
+    DuDef, GeneratedModule, GeneratedTypes, NamingStrategy, ProviderError, ProviderParams,
+    ProviderResult, RecordDef, Schema, TypeDefinition, TypeExpr, TypeGenerator, TypeProvider,
+    VariantDef,
+};
+
+/// Postman collection type provider
+pub struct PostmanProvider {
+    generator: TypeGenerator,
+}
+
+impl PostmanProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<Collection> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected Postman collection: {}", e)))
+    }
+
+    /// The modules of a collection, with the types of each and what was
+    /// lost on the way
+    fn generate(&self, collection: &Collection, namespace: &str) -> (Vec<GeneratedModule>, Vec<Diagnostic>) {
+        let mut generation = Generation {
+            generator: &self.generator,
+            types: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+        let mut modules: Vec<GeneratedModule> = Vec::new();
+        let mut used: Vec<(Vec<String>, String)> = Vec::new();
+
+        for request in &collection.requests {
+            let mut path = vec![namespace.to_string()];
+            path.extend(request.folders.iter().map(|folder| generation.type_name(folder, "Folder")));
+            let location = request.folders.iter().chain([&request.name]).cloned().collect::<Vec<_>>().join("/");
+
+            // An unnamed request is named by method and path, as in `GetUsersId`
+            let mut name = if request.name.chars().any(char::is_alphanumeric) {
+                generation.type_name(&request.name, &generation.type_name(&request.method, "Request"))
+            } else {
+                generation.type_name(&format!("{} {}", request.method, request.path), "Request")
+            };
+            if used.iter().any(|(p, n)| p == &path && n == &name) {
+                let unique = (2..).map(|i| format!("{}{}", name, i)).find(|n| !used.iter().any(|(p, u)| p == &path && u == n));
+                let unique = unique.unwrap_or_default();
+                generation.diagnostics.push(
+                    Diagnostic::warning(
+                        "duplicate-request",
+                        format!("Another request in the folder is also named `{}`; this one is `{}`", name, unique),
+                    )
+                    .at(location.clone()),
+                );
+                name = unique;
+            }
+            used.push((path.clone(), name.clone()));
+
+            generation.request(request, &name, &location);
+            let types = std::mem::take(&mut generation.types);
+            match modules.iter_mut().find(|m| m.path == path) {
+                Some(module) => module.types.extend(types),
+                None => {
+                    let mut module = GeneratedModule::new(path);
+                    module.types = types;
+                    modules.push(module);
+                }
+            }
+        }
+        (modules, generation.diagnostics)
+    }
+}
+
+impl Default for PostmanProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for PostmanProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("PostmanProvider")
+            .param(
+                ParamSpec::new("folder", ParamKind::List)
+                    .describe("Folders to keep, such as `Users/Admin`"),
+            )
+    }
+}
+
+impl TypeProvider for PostmanProvider {
+    fn name(&self) -> &str {
+        "PostmanProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let mut collection = if source.trim_start().starts_with('{') {
+            parse_collection(source)?
+        } else {
+            parse_collection(&read_source(source, params)?)?
+        };
+
+        if let Some(filter) = typed.list("folder") {
+            let filter: Vec<Vec<&str>> = filter
+                .iter()
+                .map(|f| f.split('/').filter(|s| !s.is_empty()).collect())
+                .collect();
+            collection.requests.retain(|r| {
+                filter.iter().any(|folder| {
+                    folder.len() <= r.folders.len() && folder.iter().zip(&r.folders).all(|(f, r)| f.eq_ignore_ascii_case(r))
+                })
+            });
+        }
+
+        let json = serde_json::to_string(&collection)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected Postman collection".to_string()));
+        };
+
+        let mut result = GeneratedTypes::new();
+        result.modules = self.generate(&self.parse_schema(json)?, namespace).0;
+        Ok(result)
+    }
+}
+
+impl SourceValidator for PostmanProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected Postman collection".to_string()));
+        };
+
+        let collection = self.parse_schema(json)?;
+        let mut diagnostics = Vec::new();
+        if collection.requests.is_empty() {
+            diagnostics.push(Diagnostic::warning("no-requests", "No requests found"));
+        }
+        diagnostics.extend(self.generate(&collection, "Postman").1);
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for PostmanProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+/// Types generated for one request, with what was lost on the way
+struct Generation<'a> {
+    generator: &'a TypeGenerator,
+    types: Vec<TypeDefinition>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Generation<'_> {
+    fn request(&mut self, request: &Request, name: &str, location: &str) {
+        let mut fields = Vec::new();
+        for param in &request.params {
+            let ty = match Shape::merge_all(param.values.iter().map(|v| Shape::of_text(v))) {
+                Shape::Bool => "bool",
+                Shape::Int => "int",
+                Shape::Int64 => "int64",
+                Shape::Float => "float",
+                _ => "string",
+            };
+            fields.push((param.key.clone(), optional(TypeShape::named(ty).into(), param.required)));
+        }
+        if let Some(body) = &request.body {
+            let body = self.payload(body, &format!("{}Body", name), &format!("{}.body", location));
+            fields.push(("body".to_string(), body.into()));
+        }
+        self.types.push(TypeDefinition::Record(RecordDef {
+            name: format!("{}Request", name),
+            fields,
+        }));
+
+        if request.examples.is_empty() {
+            self.diagnostics.push(
+                Diagnostic::info("no-examples", "Request has no saved examples, so its response has no type")
+                    .at(location.to_string()),
+            );
+            return;
+        }
+
+        let mut codes: Vec<Option<u16>> = request.examples.iter().map(|e| e.code).collect();
+        codes.sort();
+        codes.dedup();
+        let mut variants = Vec::new();
+        for code in codes {
+            let variant = code.map_or("Default".to_string(), status_variant);
+            let bodies: Vec<&Payload> =
+                request.examples.iter().filter(|e| e.code == code).filter_map(|e| e.body.as_ref()).collect();
+            let samples: Vec<serde_json::Value> = bodies
+                .iter()
+                .flat_map(|body| match body {
+                    Payload::Json { samples } => samples.clone(),
+                    _ => Vec::new(),
+                })
+                .collect();
+            let location = format!("{}.{}", location, variant);
+
+            let body = match (samples.is_empty(), bodies.first()) {
+                (false, _) => Payload::Json { samples },
+                (true, Some(&body)) => body.clone(),
+                (true, None) => {
+                    variants.push(VariantDef::new_simple(variant));
+                    continue;
+                }
+            };
+            let ty = self.payload(&body, &format!("{}{}Body", name, variant), &location);
+            variants.push(VariantDef::new(variant, vec![ty.into()]));
+        }
+        self.types.push(TypeDefinition::Du(DuDef {
+            name: format!("{}Response", name),
+            variants,
+        }));
+    }
+
+    /// Type of a request or response body; `name` names its record
+    fn payload(&mut self, payload: &Payload, name: &str, location: &str) -> TypeShape {
+        let named = |name: &str| TypeShape::Named(name.to_string());
+        match payload {
+            Payload::Json { samples } => {
+                let shape = Shape::merge_all(samples.iter().map(Shape::of));
+                self.shape(&shape, name, location)
+            }
+            Payload::Form { fields } => {
+                let fields = fields
+                    .iter()
+                    .map(|f| (f.key.clone(), TypeShape::named(if f.file { "bytes" } else { "string" }).into()))
+                    .collect();
+                self.types.push(TypeDefinition::Record(RecordDef { name: name.to_string(), fields }));
+                named(name)
+            }
+            Payload::Invalid { error } => {
+                self.diagnostics.push(
+                    Diagnostic::warning("invalid-json", format!("Body is not valid JSON: {}", error))
+                        .at(location.to_string()),
+                );
+                named("any")
+            }
+            Payload::Other { language } => {
+                let ty = if language == "file" { "bytes" } else { "string" };
+                self.diagnostics.push(
+                    Diagnostic::info("non-json-body", format!("`{}` body is typed as `{}`", language, ty))
+                        .at(location.to_string()),
+                );
+                named(ty)
+            }
+        }
+    }
+
+    fn shape(&mut self, shape: &Shape, name: &str, location: &str) -> TypeShape {
+        let named = |name: &str| TypeShape::Named(name.to_string());
+        match shape {
+            Shape::Bool => named("bool"),
+            Shape::Int => named("int"),
+            Shape::Int64 => named("int64"),
+            Shape::Float => named("float"),
+            Shape::String => named("string"),
+            Shape::List(element) => TypeShape::List(Box::new(self.shape(element, name, location))),
+            Shape::Object(object) => {
+                let mut fields = Vec::new();
+                for field in &object.fields {
+                    let nested = format!("{}{}", name, self.type_name(&field.name, "Field"));
+                    let shape = self.shape(&field.shape, &nested, &format!("{}.{}", location, field.name));
+                    fields.push((field.name.clone(), optional(shape.into(), !field.is_optional(object.samples))));
+                }
+                self.types.push(TypeDefinition::Record(RecordDef {
+                    name: name.to_string(),
+                    fields,
+                }));
+                named(name)
+            }
+            Shape::Null => self.coerced(location, "Only `null` or empty lists were seen"),
+            Shape::Any => self.coerced(location, "Samples disagree on the type"),
+        }
+    }
+
+    fn coerced(&mut self, location: &str, reason: &str) -> TypeShape {
+        self.diagnostics.push(
+            Diagnostic::info("coerced-any", format!("{}; typed as `any`", reason)).at(location.to_string()),
+        );
+        TypeShape::Named("any".to_string())
+    }
+
+    /// PascalCase of a name that may hold any characters; `prefix` keeps
+    /// one that starts with a digit a valid identifier
+    fn type_name(&self, raw: &str, prefix: &str) -> String {
+        let words: String = raw.chars().map(|c| if c.is_alphanumeric() { c } else { ' ' }).collect();
+        let name = self.generator.naming.apply(&words);
+        if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+            format!("{}{}", prefix, name)
+        } else {
+            name
+        }
+    }
+}
+
+/// Case name for a response status code
+fn status_variant(code: u16) -> String {
+    let name = match code {
+        200 => "Ok",
+        201 => "Created",
+        202 => "Accepted",
+        204 => "NoContent",
+        301 => "MovedPermanently",
+        302 => "Found",
+        304 => "NotModified",
+        400 => "BadRequest",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "NotFound",
+        405 => "MethodNotAllowed",
+        409 => "Conflict",
+        410 => "Gone",
+        422 => "UnprocessableEntity",
+        429 => "TooManyRequests",
+        500 => "InternalServerError",
+        502 => "BadGateway",
+        503 => "ServiceUnavailable",
+        504 => "GatewayTimeout",
+        _ => return format!("Status{}", code),
+    };
+    name.to_string()
+}
+
+/// Mark a type optional unless the value is required
+fn optional(type_expr: TypeExpr, required: bool) -> TypeExpr {
+    let shape = TypeShape::from_type_expr(&type_expr);
+    if required || shape.is_option() {
+        type_expr
+    } else {
+        TypeShape::Option(Box::new(shape)).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+    use serde_json::json;
+
+    fn collection() -> String {
+        json!({
+            "info": {"name": "Shop", "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"},
+            "variable": [{"key": "baseUrl", "value": "https://api.shop.test"}],
+            "item": [
+                {"name": "Users", "item": [
+                    {"name": "Get user", "request": {
+                        "method": "GET",
+                        "url": {"raw": "{{baseUrl}}/users/:id?verbose=true", "path": ["users", ":id"],
+                            "query": [{"key": "verbose", "value": "true"}, {"key": "page", "value": "2", "disabled": true}],
+                            "variable": [{"key": "id", "value": "42"}]}
+                    }, "response": [
+                        {"name": "Found", "code": 200, "_postman_previewlanguage": "json",
+                         "body": "{\"id\": 42, \"name\": \"Ada\", \"address\": {\"city\": \"Oslo\"}, \"roles\": [\"admin\"]}"},
+                        {"name": "Found, no address", "code": 200, "_postman_previewlanguage": "json",
+                         "body": "{\"id\": 7, \"name\": \"Bob\", \"nickname\": null, \"roles\": []}"},
+                        {"name": "Missing", "code": 404, "body": ""}
+                    ]},
+                    {"name": "Admin", "item": [
+                        {"name": "Ban user", "request": {
+                            "method": "POST",
+                            "url": "{{baseUrl}}/admin/users/:id/ban",
+                            "body": {"mode": "urlencoded", "urlencoded": [{"key": "reason", "value": "spam"}]}
+                        }}
+                    ]}
+                ]},
+                {"name": "Create order", "request": {
+                    "method": "POST",
+                    "url": "{{baseUrl}}/orders",
+                    "body": {"mode": "raw", "raw": "{\"items\": [{\"sku\": \"A1\", \"qty\": 2}], \"coupon\": {{coupon}}}"}
+                }, "response": [
+                    {"name": "Created", "code": 201, "body": "<order/>", "_postman_previewlanguage": "xml"},
+                    {"name": "Broken", "code": 400, "_postman_previewlanguage": "json", "body": "{\"error\": "}
+                ]}
+            ]
+        })
+        .to_string()
+    }
+
+    fn find<'a>(types: &'a GeneratedTypes, module: &[&str], name: &str) -> &'a TypeDefinition {
+        types
+            .modules
+            .iter()
+            .find(|m| m.path == module)
+            .and_then(|m| {
+                m.types.iter().find(|t| match t {
+                    TypeDefinition::Record(r) => r.name == name,
+                    TypeDefinition::Du(d) => d.name == name,
+                })
+            })
+            .unwrap_or_else(|| panic!("{} not found in {:?}", name, module))
+    }
+
+    fn fields(definition: &TypeDefinition) -> Vec<(String, String)> {
+        let TypeDefinition::Record(record) = definition else {
+            panic!("Expected record");
+        };
+        record.fields.iter().map(|(name, ty)| (name.clone(), ty.to_string())).collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect()
+    }
+
+    #[test]
+    fn test_collection_types() {
+        let provider = PostmanProvider::new();
+        let schema = provider.resolve_schema(&collection(), &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Shop").unwrap();
+
+        let paths: Vec<&[String]> = types.modules.iter().map(|m| m.path.as_slice()).collect();
+        assert_eq!(paths, [&["Shop", "Users"][..], &["Shop", "Users", "Admin"], &["Shop"]]);
+
+        let users = ["Shop", "Users"];
+        assert_eq!(
+            fields(find(&types, &users, "GetUserRequest")),
+            pairs(&[("id", "int"), ("verbose", "bool"), ("page", "int option")])
+        );
+        let TypeDefinition::Du(response) = find(&types, &users, "GetUserResponse") else {
+            panic!("Expected union");
+        };
+        let cases: Vec<(&str, Vec<String>)> = response
+            .variants
+            .iter()
+            .map(|v| (v.name.as_str(), v.fields.iter().map(ToString::to_string).collect()))
+            .collect();
+        assert_eq!(cases, [("Ok", vec!["GetUserOkBody".to_string()]), ("NotFound", vec![])]);
+        assert_eq!(
+            fields(find(&types, &users, "GetUserOkBody")),
+            pairs(&[
+                ("address", "GetUserOkBodyAddress option"),
+                ("id", "int"),
+                ("name", "string"),
+                ("roles", "string list"),
+                ("nickname", "any option"),
+            ])
+        );
+
+        let admin = ["Shop", "Users", "Admin"];
+        assert_eq!(
+            fields(find(&types, &admin, "BanUserRequest")),
+            pairs(&[("id", "string"), ("body", "BanUserBody")])
+        );
+        assert_eq!(fields(find(&types, &admin, "BanUserBody")), pairs(&[("reason", "string")]));
+
+        assert_eq!(
+            fields(find(&types, &["Shop"], "CreateOrderBody")),
+            pairs(&[("coupon", "any option"), ("items", "CreateOrderBodyItems list")])
+        );
+        let TypeDefinition::Du(response) = find(&types, &["Shop"], "CreateOrderResponse") else {
+            panic!("Expected union");
+        };
+        assert_eq!(response.variants[0].fields[0].to_string(), "string");
+
+        let params = ProviderParams::default().with("folder", "users/admin");
+        let schema = provider.resolve_schema(&collection(), &params).unwrap();
+        let types = provider.generate_types(&schema, "Shop").unwrap();
+        assert_eq!(types.modules.len(), 1);
+        assert_eq!(types.modules[0].path, ["Shop", "Users", "Admin"]);
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/postman.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = PostmanProvider::new();
+        let report = provider.validate_source(&collection(), &ProviderParams::default());
+        assert!(report.is_valid());
+
+        let codes: Vec<(&str, Option<&str>)> =
+            report.diagnostics.iter().map(|d| (d.code.as_str(), d.location.as_deref())).collect();
+        assert!(codes.contains(&("coerced-any", Some("Users/Get user.Ok.nickname"))));
+        assert!(codes.contains(&("no-examples", Some("Users/Admin/Ban user"))));
+        assert!(codes.contains(&("coerced-any", Some("Create order.body.coupon"))));
+        assert!(codes.contains(&("non-json-body", Some("Create order.Created"))));
+        assert!(codes.contains(&("invalid-json", Some("Create order.BadRequest"))));
+
+        let empty = r#"{"info": {"name": "Empty"}, "item": []}"#;
+        let report = provider.validate_source(empty, &ProviderParams::default());
+        assert!(report.diagnostics.iter().any(|d| d.code == "no-requests"));
+    }
+}
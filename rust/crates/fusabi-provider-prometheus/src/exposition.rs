@@ -0,0 +1,353 @@
+//! Prometheus text exposition format parser
+//!
+//! Handles the classic text format and OpenMetrics: `# HELP`, `# TYPE` and
+//! `# UNIT` metadata, samples with labels, and the suffixed series that make
+//! up counters (`_total`, `_created`), histograms (`_bucket`, `_sum`,
+//! `_count`) and summaries. Sample values and timestamps are not kept.
+
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde::{Deserialize, Serialize};
+
+/// Metric type from `# TYPE`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricType {
+    Counter,
+    Gauge,
+    Histogram,
+    GaugeHistogram,
+    Summary,
+    Info,
+    StateSet,
+    Untyped,
+}
+
+impl MetricType {
+    fn parse(value: &str) -> Option<Self> {
+        Some(match value {
+            "counter" => MetricType::Counter,
+            "gauge" => MetricType::Gauge,
+            "histogram" => MetricType::Histogram,
+            "gaugehistogram" => MetricType::GaugeHistogram,
+            "summary" => MetricType::Summary,
+            "info" => MetricType::Info,
+            "stateset" => MetricType::StateSet,
+            "untyped" | "unknown" => MetricType::Untyped,
+            _ => return None,
+        })
+    }
+
+    /// Series suffixes that belong to a family of this type
+    fn suffixes(self) -> &'static [&'static str] {
+        match self {
+            MetricType::Counter => &["_total", "_created"],
+            MetricType::Histogram => &["_bucket", "_sum", "_count", "_created"],
+            MetricType::GaugeHistogram => &["_bucket", "_gsum", "_gcount"],
+            MetricType::Summary => &["_sum", "_count", "_created"],
+            MetricType::Info => &["_info"],
+            _ => &[],
+        }
+    }
+
+    /// Whether a series counts as one child of the family. Histograms and
+    /// summaries repeat a child's labels on every bucket, quantile, `_sum`
+    /// and `_count`, so only the `_count` series is counted.
+    fn is_counted(self, family: &str, series: &str) -> bool {
+        let suffix = series.strip_prefix(family).unwrap_or("");
+        match self {
+            MetricType::Histogram | MetricType::Summary => suffix == "_count",
+            MetricType::GaugeHistogram => suffix == "_gcount",
+            MetricType::Counter => suffix != "_created",
+            _ => true,
+        }
+    }
+
+    /// Labels that structure the family's series rather than identify them
+    pub fn structural_label(self) -> Option<&'static str> {
+        match self {
+            MetricType::Histogram | MetricType::GaugeHistogram => Some("le"),
+            MetricType::Summary => Some("quantile"),
+            _ => None,
+        }
+    }
+}
+
+/// A label and how many of the family's samples carry it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Label {
+    pub name: String,
+    pub present: usize,
+}
+
+/// A metric family: metadata plus the labels seen on its samples
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricFamily {
+    pub name: String,
+    pub metric_type: MetricType,
+    /// Whether the type came from `# TYPE` rather than being assumed
+    pub declared: bool,
+    pub help: Option<String>,
+    pub unit: Option<String>,
+    /// Number of children (distinct series, counting a histogram or summary
+    /// child once)
+    pub samples: usize,
+    pub labels: Vec<Label>,
+}
+
+impl MetricFamily {
+    fn new(name: &str, metric_type: MetricType, declared: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            metric_type,
+            declared,
+            help: None,
+            unit: None,
+            samples: 0,
+            labels: Vec::new(),
+        }
+    }
+
+    /// Labels carried by every sample
+    pub fn is_label_required(&self, label: &Label) -> bool {
+        label.present == self.samples
+    }
+}
+
+/// Parse an exposition document into metric families, in order of first
+/// appearance
+pub fn parse_exposition(content: &str) -> ProviderResult<Vec<MetricFamily>> {
+    let mut families: Vec<MetricFamily> = Vec::new();
+
+    for (index, raw_line) in content.lines().enumerate() {
+        let line = raw_line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(comment) = line.strip_prefix('#') {
+            let mut parts = comment.trim_start().splitn(3, ' ');
+            let (Some(keyword), Some(name)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            let rest = parts.next().unwrap_or("").trim();
+            match keyword {
+                "TYPE" => {
+                    let metric_type = MetricType::parse(rest).ok_or_else(|| {
+                        line_error(index, &format!("unknown metric type `{}`", rest))
+                    })?;
+                    let family = family_mut(&mut families, name, metric_type);
+                    family.metric_type = metric_type;
+                    family.declared = true;
+                }
+                "HELP" => {
+                    family_mut(&mut families, name, MetricType::Untyped).help = Some(unescape(rest));
+                }
+                "UNIT" if !rest.is_empty() => {
+                    family_mut(&mut families, name, MetricType::Untyped).unit = Some(rest.to_string());
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        let (series, labels) = parse_sample(line).map_err(|message| line_error(index, &message))?;
+        let family_index = match find_family(&families, &series) {
+            Some(i) => i,
+            None => {
+                families.push(MetricFamily::new(&series, MetricType::Untyped, false));
+                families.len() - 1
+            }
+        };
+        let family = &mut families[family_index];
+
+        if !family.metric_type.is_counted(&family.name, &series) {
+            continue;
+        }
+
+        let structural = family.metric_type.structural_label();
+        family.samples += 1;
+        for name in labels.into_iter().filter(|l| Some(l.as_str()) != structural) {
+            match family.labels.iter_mut().find(|l| l.name == name) {
+                Some(label) => label.present += 1,
+                None => family.labels.push(Label { name, present: 1 }),
+            }
+        }
+    }
+
+    Ok(families)
+}
+
+fn family_mut<'a>(families: &'a mut Vec<MetricFamily>, name: &str, metric_type: MetricType) -> &'a mut MetricFamily {
+    match families.iter().position(|f| f.name == name) {
+        Some(i) => &mut families[i],
+        None => {
+            families.push(MetricFamily::new(name, metric_type, false));
+            families.last_mut().expect("family was just pushed")
+        }
+    }
+}
+
+/// The family a series belongs to: an exact match, or a typed family whose
+/// name plus one of its suffixes gives the series
+fn find_family(families: &[MetricFamily], series: &str) -> Option<usize> {
+    families.iter().position(|f| f.name == series).or_else(|| {
+        families.iter().position(|f| {
+            f.metric_type
+                .suffixes()
+                .iter()
+                .any(|suffix| series.strip_suffix(suffix) == Some(f.name.as_str()))
+        })
+    })
+}
+
+/// Parse `name{a="1",b="2"} value [timestamp]` into the series name and
+/// label names
+fn parse_sample(line: &str) -> Result<(String, Vec<String>), String> {
+    let name_end = line
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == ':'))
+        .unwrap_or(line.len());
+    let name = &line[..name_end];
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        return Err(format!("invalid metric name in `{}`", line));
+    }
+
+    let mut labels = Vec::new();
+    let rest = line[name_end..].trim_start();
+    let rest = match rest.strip_prefix('{') {
+        Some(body) => {
+            let mut chars = body.char_indices().peekable();
+            let mut end = None;
+            loop {
+                while chars.next_if(|(_, c)| c.is_whitespace() || *c == ',').is_some() {}
+                match chars.peek() {
+                    Some((i, '}')) => {
+                        end = Some(*i + 1);
+                        break;
+                    }
+                    Some(_) => {}
+                    None => break,
+                }
+
+                let mut label = String::new();
+                while let Some((_, c)) = chars.next_if(|(_, c)| *c != '=' && !c.is_whitespace()) {
+                    label.push(c);
+                }
+                while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+                if chars.next().map(|(_, c)| c) != Some('=') {
+                    return Err(format!("expected '=' after label `{}`", label));
+                }
+                while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+                if chars.next().map(|(_, c)| c) != Some('"') {
+                    return Err(format!("expected quoted value for label `{}`", label));
+                }
+                let mut closed = false;
+                while let Some((_, c)) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '"' => {
+                            closed = true;
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+                if !closed {
+                    return Err(format!("unterminated value for label `{}`", label));
+                }
+                labels.push(label);
+            }
+            let end = end.ok_or_else(|| "unterminated label set".to_string())?;
+            body[end..].trim_start()
+        }
+        None => rest,
+    };
+
+    if rest.split_whitespace().next().is_none() {
+        return Err(format!("sample `{}` has no value", name));
+    }
+
+    Ok((name.to_string(), labels))
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("\\n", "\n").replace("\\\\", "\\")
+}
+
+fn line_error(index: usize, message: &str) -> ProviderError {
+    ProviderError::ParseError(format!("Invalid exposition format at line {}: {}", index + 1, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METRICS: &str = r#"
+# HELP http_requests_total Total HTTP requests.
+# TYPE http_requests_total counter
+http_requests_total{method="GET",code="200"} 1027 1395066363000
+http_requests_total{method="POST",code="400",handler="/api"} 3
+# HELP request_duration_seconds Request latency.
+# TYPE request_duration_seconds histogram
+request_duration_seconds_bucket{route="/",le="0.1"} 5
+request_duration_seconds_bucket{route="/",le="+Inf"} 7
+request_duration_seconds_sum{route="/"} 1.2
+request_duration_seconds_count{route="/"} 7
+# TYPE rpc_duration_seconds summary
+rpc_duration_seconds{quantile="0.5"} 4773
+rpc_duration_seconds{quantile="0.9"} 9001
+rpc_duration_seconds_sum 1.7560473e+07
+rpc_duration_seconds_count 2693
+process_open_fds 12
+"#;
+
+    #[test]
+    fn test_parse_families() {
+        let families = parse_exposition(METRICS).unwrap();
+        let names: Vec<&str> = families.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["http_requests_total", "request_duration_seconds", "rpc_duration_seconds", "process_open_fds"]
+        );
+
+        let requests = &families[0];
+        assert_eq!(requests.metric_type, MetricType::Counter);
+        assert_eq!(requests.help.as_deref(), Some("Total HTTP requests."));
+        assert_eq!(requests.samples, 2);
+        let required: Vec<(&str, bool)> = requests
+            .labels
+            .iter()
+            .map(|l| (l.name.as_str(), requests.is_label_required(l)))
+            .collect();
+        assert_eq!(required, [("method", true), ("code", true), ("handler", false)]);
+
+        let duration = &families[1];
+        assert_eq!(duration.metric_type, MetricType::Histogram);
+        assert_eq!(duration.labels.len(), 1);
+        assert!(duration.is_label_required(&duration.labels[0]));
+
+        assert!(families[2].labels.is_empty());
+        assert_eq!(families[3].metric_type, MetricType::Untyped);
+        assert!(!families[3].declared);
+    }
+
+    #[test]
+    fn test_openmetrics_counter() {
+        let families = parse_exposition(
+            "# TYPE jobs counter\n# UNIT jobs jobs\njobs_total{queue=\"a, \\\"b\\\"\"} 1\njobs_created{queue=\"a\"} 1.6e9\n# EOF\n",
+        )
+        .unwrap();
+        assert_eq!(families.len(), 1);
+        assert_eq!(families[0].unit.as_deref(), Some("jobs"));
+        assert_eq!(families[0].labels[0].name, "queue");
+    }
+
+    #[test]
+    fn test_invalid_exposition() {
+        assert!(parse_exposition("# TYPE x histo\n").is_err());
+        assert!(parse_exposition("x{a=1} 2\n").is_err());
+        assert!(parse_exposition("x{a=\"1\" 2\n").is_err());
+        assert!(parse_exposition("x\n").is_err());
+    }
+}
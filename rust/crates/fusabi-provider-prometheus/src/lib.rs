@@ -0,0 +1,359 @@
+//! Prometheus Type Provider
+//!
+//! Generates Fusabi types from Prometheus metrics in the text exposition
+//! format (or OpenMetrics), scraped from a `/metrics` endpoint or read from a
+//! file. Each metric family becomes a record of its label set, so pipelines
+//! and dashboards can refer to metrics by typed identity.
+//!
+//! # Generated Types
+//!
+//! Each family gets a record named after it (`http_requests_total` →
+//! `HttpRequestsTotal`) with one `string` field per label; labels missing
+//! from some series become `string option`. Value fields follow the type:
+//!
+//! | `# TYPE`                        | Value fields                                   |
+//! |---------------------------------|------------------------------------------------|
+//! | `counter`, `gauge`, `untyped`   | `value: float`                                 |
+//! | `histogram`, `gaugehistogram`   | `buckets: Map<string, float>`, `sum`, `count`  |
+//! | `summary`                       | `quantiles: Map<string, float>`, `sum`, `count`|
+//! | `stateset`                      | `value: float`                                 |
+//! | `info`                          | none                                           |
+//!
+//! Bucket and quantile maps are keyed by the `le` / `quantile` label. A
+//! `Metric` union has one case per family carrying its record.
+//!
+//! # Params
+//!
+//! | Param     | Meaning                                                      |
+//! |-----------|--------------------------------------------------------------|
+//! | `metrics` | Comma-separated families to keep; `http_*` matches by prefix |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_prometheus::PrometheusProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = PrometheusProvider::new();
+//! let params = ProviderParams::default().with("metrics", "http_*,process_*");
+//! let schema = provider.resolve_schema("http://localhost:9090/metrics", &params)?;
+//! let types = provider.generate_types(&schema, "Metrics")?;
+//! ```
+
+mod exposition;
+
+pub use exposition::{parse_exposition, Label, MetricFamily, MetricType};
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceValidator, TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, DuDef, VariantDef, TypeExpr, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+
+/// Prometheus exposition format type provider
+pub struct PrometheusProvider {
+    generator: TypeGenerator,
+}
+
+impl PrometheusProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<Vec<MetricFamily>> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected Prometheus schema: {}", e)))
+    }
+
+    /// Type name for a family; recording-rule names use `:` separators
+    fn family_type_name(&self, family: &MetricFamily) -> String {
+        self.generator.naming.apply(&family.name.replace(':', "_"))
+    }
+
+    /// Generate types for metric families
+    fn generate_from_families(&self, families: &[MetricFamily], namespace: &str) -> GeneratedTypes {
+        let mut result = GeneratedTypes::new();
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+
+        for family in families {
+            let mut fields: Vec<(String, TypeExpr)> = family
+                .labels
+                .iter()
+                .map(|label| {
+                    let required = family.is_label_required(label);
+                    (label.name.clone(), TypeShape::named("string").option_unless(required).into())
+                })
+                .collect();
+            fields.extend(value_fields(family.metric_type));
+
+            module.types.push(TypeDefinition::Record(RecordDef {
+                name: self.family_type_name(family),
+                fields,
+            }));
+        }
+
+        if !families.is_empty() {
+            module.types.push(TypeDefinition::Du(DuDef {
+                name: "Metric".to_string(),
+                variants: families
+                    .iter()
+                    .map(|family| {
+                        let name = self.family_type_name(family);
+                        VariantDef::new(name.clone(), vec![TypeShape::named(name).into()])
+                    })
+                    .collect(),
+            }));
+            result.modules.push(module);
+        }
+
+        result
+    }
+}
+
+/// Fields holding a family's values, by metric type
+fn value_fields(metric_type: MetricType) -> Vec<(String, TypeExpr)> {
+    let field = |name: &str, ty: &str| -> (String, TypeExpr) { (name.to_string(), TypeShape::parse(ty).into()) };
+    match metric_type {
+        MetricType::Counter | MetricType::Gauge | MetricType::Untyped | MetricType::StateSet => {
+            vec![field("value", "float")]
+        }
+        MetricType::Histogram | MetricType::GaugeHistogram => vec![
+            field("buckets", "Map<string, float>"),
+            field("sum", "float"),
+            field("count", "float"),
+        ],
+        MetricType::Summary => vec![
+            field("quantiles", "Map<string, float>"),
+            field("sum", "float"),
+            field("count", "float"),
+        ],
+        MetricType::Info => Vec::new(),
+    }
+}
+
+impl Default for PrometheusProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for PrometheusProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("PrometheusProvider")
+            .param(
+                ParamSpec::new("metrics", ParamKind::List)
+                    .describe("Families to keep; `http_*` matches by prefix"),
+            )
+    }
+}
+
+impl TypeProvider for PrometheusProvider {
+    fn name(&self) -> &str {
+        "PrometheusProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        // URLs and paths never span lines; exposition text does
+        let content = if source.contains('\n') {
+            source.to_string()
+        } else {
+            read_source(source, params)?
+        };
+
+        let mut families = parse_exposition(&content)?;
+
+        if let Some(patterns) = typed.list("metrics") {
+            if !patterns.is_empty() {
+                families.retain(|family| {
+                    patterns.iter().any(|pattern| match pattern.strip_suffix('*') {
+                        Some(prefix) => family.name.starts_with(prefix),
+                        None => family.name == *pattern,
+                    })
+                });
+                if families.is_empty() {
+                    return Err(ProviderError::InvalidSource(format!(
+                        "No metric family matches '{}'",
+                        patterns.join(",")
+                    )));
+                }
+            }
+        }
+
+        let json = serde_json::to_string(&families)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        match schema {
+            Schema::Custom(json) => {
+                let families = self.parse_schema(json)?;
+                Ok(self.generate_from_families(&families, namespace))
+            }
+            _ => Err(ProviderError::ParseError("Expected Prometheus schema".to_string())),
+        }
+    }
+}
+
+impl SourceValidator for PrometheusProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected Prometheus schema".to_string()));
+        };
+
+        let families = self.parse_schema(json)?;
+        let mut diagnostics = Vec::new();
+
+        if families.is_empty() {
+            diagnostics.push(Diagnostic::warning("no-metrics", "The exposition contains no metric families"));
+        }
+
+        for family in &families {
+            if !family.declared {
+                diagnostics.push(
+                    Diagnostic::info(
+                        "metric-untyped",
+                        format!("Metric `{}` has no `# TYPE`; it is typed as a single sample", family.name),
+                    )
+                    .at(family.name.clone()),
+                );
+            }
+            if family.samples == 0 {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        "no-samples",
+                        format!("Metric `{}` has no samples; its labels are unknown", family.name),
+                    )
+                    .at(family.name.clone()),
+                );
+            }
+        }
+
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for PrometheusProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    const METRICS: &str = r#"# HELP http_requests_total Total HTTP requests.
+# TYPE http_requests_total counter
+http_requests_total{method="GET",code="200"} 1027
+http_requests_total{method="POST",code="400",handler="/api"} 3
+# TYPE request_duration_seconds histogram
+request_duration_seconds_bucket{route="/",le="0.1"} 5
+request_duration_seconds_bucket{route="/",le="+Inf"} 7
+request_duration_seconds_sum{route="/"} 1.2
+request_duration_seconds_count{route="/"} 7
+# TYPE build_info gauge
+build_info{version="1.2.3"} 1
+# TYPE queue_depth gauge
+job:queue_depth:max5m{queue="a"} 3
+"#;
+
+    fn fields(module: &GeneratedModule, name: &str) -> Vec<(String, String)> {
+        module
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+            .fields
+            .iter()
+            .map(|(name, ty)| (name.clone(), ty.to_string()))
+            .collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect()
+    }
+
+    #[test]
+    fn test_generate_families() {
+        let provider = PrometheusProvider::new();
+        let schema = provider.resolve_schema(METRICS, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Metrics").unwrap();
+        let module = &types.modules[0];
+
+        assert_eq!(
+            fields(module, "HttpRequestsTotal"),
+            pairs(&[
+                ("method", "string"),
+                ("code", "string"),
+                ("handler", "string option"),
+                ("value", "float"),
+            ])
+        );
+        assert_eq!(
+            fields(module, "RequestDurationSeconds"),
+            pairs(&[
+                ("route", "string"),
+                ("buckets", "Map<string, float>"),
+                ("sum", "float"),
+                ("count", "float"),
+            ])
+        );
+        assert_eq!(fields(module, "JobQueueDepthMax5m").len(), 2);
+
+        let TypeDefinition::Du(metric) = module.types.last().unwrap() else {
+            panic!("Expected Metric union");
+        };
+        assert_eq!(metric.name, "Metric");
+        assert_eq!(metric.variants.len(), 5);
+        assert_eq!(metric.variants[0].fields[0].to_string(), "HttpRequestsTotal");
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/prometheus.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_metrics_filter() {
+        let provider = PrometheusProvider::new();
+        let params = ProviderParams::default().with("metrics", "http_*, build_info");
+        let schema = provider.resolve_schema(METRICS, &params).unwrap();
+        let types = provider.generate_types(&schema, "Metrics").unwrap();
+        assert_eq!(types.modules[0].types.len(), 3);
+
+        let params = ProviderParams::default().with("metrics", "grpc_*");
+        assert!(provider.resolve_schema(METRICS, &params).is_err());
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = PrometheusProvider::new();
+        let report = provider.validate_source(METRICS, &ProviderParams::default());
+        assert!(report.is_valid());
+
+        let codes: Vec<(&str, Option<&str>)> = report
+            .diagnostics
+            .iter()
+            .map(|d| (d.code.as_str(), d.location.as_deref()))
+            .collect();
+        assert!(codes.contains(&("metric-untyped", Some("job:queue_depth:max5m"))));
+        assert!(codes.contains(&("no-samples", Some("queue_depth"))));
+    }
+}
@@ -0,0 +1,35 @@
+//! Resolve and generate benchmarks for large `.proto` files
+//!
+//! Run with `cargo bench -p fusabi-provider-protobuf`. Set
+//! `FUSABI_BENCH_CORPUS` to a directory containing `schema.proto` to
+//! measure a real file instead of the synthetic 500-message one.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use fusabi_provider_common::check_generated_types;
+use fusabi_provider_protobuf::ProtobufProvider;
+use fusabi_provider_testkit::corpus;
+use fusabi_type_providers::{ProviderParams, TypeProvider};
+use std::hint::black_box;
+
+fn bench_generate(c: &mut Criterion) {
+    let provider = ProtobufProvider::new();
+    let proto = corpus::load_or("schema.proto", || corpus::proto_messages(500, 20));
+    let path = corpus::write_temp("schema.proto", &proto);
+    let source = path.to_str().unwrap();
+    let params = ProviderParams::default();
+    let schema = provider.resolve_schema(source, &params).unwrap();
+    let types = provider.generate_types(&schema, "Proto").unwrap();
+
+    let mut group = c.benchmark_group("protobuf");
+    group.bench_function("resolve", |b| {
+        b.iter(|| provider.resolve_schema(black_box(source), &params).unwrap())
+    });
+    group.bench_function("generate", |b| {
+        b.iter(|| provider.generate_types(black_box(&schema), "Proto").unwrap())
+    });
+    group.bench_function("validate", |b| b.iter(|| check_generated_types(black_box(&types))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_generate);
+criterion_main!(benches);
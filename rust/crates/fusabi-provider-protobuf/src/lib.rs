@@ -1,6 +1,25 @@
 //! Protobuf Type Provider
 //!
 //! Generates Fusabi types from Protocol Buffer (.proto) definitions.
+//! Comments on messages, fields, enums and enum values are carried through
+//! as documentation.
+//!
+//! # Attributes
+//!
+//! [`AttributedProvider`] records the wire details serializers need:
+//!
+//! | Key               | On                    | Value                                 |
+//! |-------------------|-----------------------|---------------------------------------|
+//! | `protobuf.name`   | messages, enums       | Fully qualified name, `pkg.Outer.Inner` |
+//! | `protobuf.number` | fields, enum values   | Field number or enum value number     |
+//! | `protobuf.type`   | fields                | Type as written, such as `sint64`     |
+//! | `protobuf.label`  | fields                | `optional`, `required` or `repeated`  |
+//!
+//! # Decoders
+//!
+//! [`DecoderCodegen`] plans decoders for the binary encoding: field numbers
+//! and wire encodings for messages, value numbers for enums. Rendered with
+//! `emit_rust_decoders`, each message gets `decode_protobuf(&[u8])`.
 //!
 //! # Example
 //!
@@ -18,10 +37,15 @@ mod types;
 
 pub use types::{ProtoFile, Message, Enum, Field, FieldType, FieldLabel};
 
+use fusabi_provider_common::{
+    read_source, AttributedProvider, Capabilities, DecoderCodegen, DecoderPlan, DescribedProvider,
+    Diagnostic, DocumentedProvider, FieldCodec, FieldDecoder, InputLimits, OutputFeature,
+    ProtoEncoding, SourceValidator, TypeAttributes, TypeDecoder, TypeDocs, TypeShape, WireFormat,
+};
 use fusabi_type_providers::{
     TypeProvider, ProviderParams, Schema,
     GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
-    RecordDef, DuDef, VariantDef, TypeExpr, TypeDefinition,
+    RecordDef, DuDef, VariantDef, TypeDefinition,
     ProviderError, ProviderResult,
 };
 use std::collections::HashMap;
@@ -39,8 +63,11 @@ impl ProtobufProvider {
     }
 
     /// Parse a .proto file from string content
+    ///
+    /// The content was checked against the caller's limits in
+    /// `resolve_schema`, which may be looser than the defaults.
     fn parse_proto(&self, content: &str) -> ProviderResult<ProtoFile> {
-        parser::parse_proto(content)
+        parser::parse_proto_with_limits(content, &InputLimits::unlimited())
     }
 
     /// Generate types from parsed proto file
@@ -48,8 +75,9 @@ impl ProtobufProvider {
         &self,
         proto: &ProtoFile,
         namespace: &str,
-    ) -> ProviderResult<GeneratedTypes> {
+    ) -> ProviderResult<(GeneratedTypes, TypeDocs)> {
         let mut result = GeneratedTypes::new();
+        let mut docs = TypeDocs::new();
 
         // Create a module for the package if present
         let module_path = if let Some(ref package) = proto.package {
@@ -67,18 +95,140 @@ impl ProtobufProvider {
         // Process top-level enums
         for enum_def in &proto.enums {
             types_module.types.push(self.enum_to_typedef(enum_def)?);
+            self.document_enum(&mut docs, &types_module.path, enum_def);
         }
 
         // Process top-level messages
         for message in &proto.messages {
             self.process_message(message, &mut types_module, &message_map, &enum_map)?;
+            self.document_message(&mut docs, &types_module.path, message);
         }
 
         if !types_module.types.is_empty() {
             result.modules.push(types_module);
         }
 
-        Ok(result)
+        Ok((result, docs))
+    }
+
+    /// Record the comments of a message, its fields and its nested types
+    fn document_message(&self, docs: &mut TypeDocs, path: &[String], message: &Message) {
+        let name = self.generator.naming.apply(&message.name);
+        if let Some(comment) = &message.comment {
+            docs.set_type(path, &name, comment);
+        }
+        for field in &message.fields {
+            if let Some(comment) = &field.comment {
+                docs.set_member(path, &name, &field.name, comment);
+            }
+        }
+
+        for nested_enum in &message.nested_enums {
+            self.document_enum(docs, path, nested_enum);
+        }
+        for nested_message in &message.nested_messages {
+            self.document_message(docs, path, nested_message);
+        }
+    }
+
+    /// Record the comments of an enum and its values
+    fn document_enum(&self, docs: &mut TypeDocs, path: &[String], enum_def: &Enum) {
+        let name = self.generator.naming.apply(&enum_def.name);
+        if let Some(comment) = &enum_def.comment {
+            docs.set_type(path, &name, comment);
+        }
+        for value in &enum_def.values {
+            if let Some(comment) = &value.comment {
+                docs.set_member(path, &name, &self.generator.naming.apply(&value.name), comment);
+            }
+        }
+    }
+
+    /// Record the proto names, numbers and field types of every message and enum
+    fn annotate_proto(&self, proto: &ProtoFile, path: &[String]) -> TypeAttributes {
+        let mut attributes = TypeAttributes::new();
+        let prefix = proto.package.as_deref().unwrap_or_default();
+        for enum_def in &proto.enums {
+            self.annotate_enum(&mut attributes, path, prefix, enum_def);
+        }
+        for message in &proto.messages {
+            self.annotate_message(&mut attributes, path, prefix, message);
+        }
+        attributes
+    }
+
+    /// Record the attributes of a message, its fields and its nested types;
+    /// `scope` is the qualified name of the enclosing package or message
+    fn annotate_message(
+        &self,
+        attributes: &mut TypeAttributes,
+        path: &[String],
+        scope: &str,
+        message: &Message,
+    ) {
+        let name = self.generator.naming.apply(&message.name);
+        let full_name = qualify(scope, &message.name);
+        attributes.set_type(path, &name, "protobuf.name", &full_name);
+        for field in &message.fields {
+            let label = match field.label {
+                FieldLabel::Optional => "optional",
+                FieldLabel::Required => "required",
+                FieldLabel::Repeated => "repeated",
+            };
+            attributes.set_member(path, &name, &field.name, "protobuf.number", field.number);
+            attributes.set_member(path, &name, &field.name, "protobuf.type", &field.field_type);
+            attributes.set_member(path, &name, &field.name, "protobuf.label", label);
+        }
+
+        for nested_enum in &message.nested_enums {
+            self.annotate_enum(attributes, path, &full_name, nested_enum);
+        }
+        for nested_message in &message.nested_messages {
+            self.annotate_message(attributes, path, &full_name, nested_message);
+        }
+    }
+
+    /// Record the qualified name of an enum and the numbers of its values
+    fn annotate_enum(&self, attributes: &mut TypeAttributes, path: &[String], scope: &str, enum_def: &Enum) {
+        let name = self.generator.naming.apply(&enum_def.name);
+        attributes.set_type(path, &name, "protobuf.name", qualify(scope, &enum_def.name));
+        for value in &enum_def.values {
+            let variant = self.generator.naming.apply(&value.name);
+            attributes.set_member(path, &name, &variant, "protobuf.number", value.number);
+        }
+    }
+
+    /// Plan decoders for a message, its fields and its nested types
+    fn plan_message(&self, plan: &mut DecoderPlan, path: &[String], message: &Message) {
+        let fields = message
+            .fields
+            .iter()
+            .map(|field| {
+                let codec = FieldCodec::Protobuf {
+                    number: field.number,
+                    encoding: proto_encoding(&field.field_type),
+                };
+                FieldDecoder::new(&field.name, codec)
+            })
+            .collect();
+        plan.set(path, &self.generator.naming.apply(&message.name), TypeDecoder::Record(fields));
+
+        for nested_enum in &message.nested_enums {
+            self.plan_enum(plan, path, nested_enum);
+        }
+        for nested_message in &message.nested_messages {
+            self.plan_message(plan, path, nested_message);
+        }
+    }
+
+    /// Plan the decoder of an enum from the numbers of its values
+    fn plan_enum(&self, plan: &mut DecoderPlan, path: &[String], enum_def: &Enum) {
+        let values = enum_def
+            .values
+            .iter()
+            .map(|value| (i64::from(value.number), self.generator.naming.apply(&value.name)))
+            .collect();
+        plan.set(path, &self.generator.naming.apply(&enum_def.name), TypeDecoder::Numbered(values));
     }
 
     /// Process a message and its nested types
@@ -115,13 +265,13 @@ impl ProtobufProvider {
         let mut fields = Vec::new();
 
         for field in &message.fields {
-            let type_expr = self.field_type_to_type_expr(
+            let shape = self.field_type_shape(
                 &field.field_type,
                 &field.label,
                 message_map,
                 enum_map,
             )?;
-            fields.push((field.name.clone(), type_expr));
+            fields.push((field.name.clone(), shape.into()));
         }
 
         Ok(TypeDefinition::Record(RecordDef {
@@ -144,73 +294,74 @@ impl ProtobufProvider {
         }))
     }
 
-    /// Convert a protobuf field type to a Fusabi TypeExpr
-    fn field_type_to_type_expr(
+    /// Convert a protobuf field type to a Fusabi TypeShape
+    fn field_type_shape(
         &self,
         field_type: &FieldType,
         label: &FieldLabel,
         message_map: &HashMap<String, &Message>,
         enum_map: &HashMap<String, &Enum>,
-    ) -> ProviderResult<TypeExpr> {
+    ) -> ProviderResult<TypeShape> {
         let base_type = match field_type {
-            FieldType::Double | FieldType::Float => TypeExpr::Named("float".to_string()),
-            FieldType::Int32 | FieldType::SInt32 | FieldType::SFixed32 => {
-                TypeExpr::Named("int".to_string())
-            }
-            FieldType::Int64 | FieldType::SInt64 | FieldType::SFixed64 => {
-                TypeExpr::Named("int64".to_string())
-            }
-            FieldType::UInt32 | FieldType::Fixed32 => TypeExpr::Named("uint".to_string()),
-            FieldType::UInt64 | FieldType::Fixed64 => TypeExpr::Named("uint64".to_string()),
-            FieldType::Bool => TypeExpr::Named("bool".to_string()),
-            FieldType::String => TypeExpr::Named("string".to_string()),
-            FieldType::Bytes => TypeExpr::Named("bytes".to_string()),
+            FieldType::Double | FieldType::Float => TypeShape::named("float"),
+            FieldType::Int32 | FieldType::SInt32 | FieldType::SFixed32 => TypeShape::named("int"),
+            FieldType::Int64 | FieldType::SInt64 | FieldType::SFixed64 => TypeShape::named("int64"),
+            FieldType::UInt32 | FieldType::Fixed32 => TypeShape::named("uint"),
+            FieldType::UInt64 | FieldType::Fixed64 => TypeShape::named("uint64"),
+            FieldType::Bool => TypeShape::named("bool"),
+            FieldType::String => TypeShape::named("string"),
+            FieldType::Bytes => TypeShape::named("bytes"),
             FieldType::Message(type_name) => {
                 // Check if it's a known message type
                 if message_map.contains_key(type_name) {
-                    TypeExpr::Named(self.generator.naming.apply(type_name))
+                    TypeShape::named(self.generator.naming.apply(type_name))
                 } else {
                     // Could be a fully qualified name or external reference
                     // For now, use the type name as-is
-                    TypeExpr::Named(self.generator.naming.apply(type_name))
+                    TypeShape::named(self.generator.naming.apply(type_name))
                 }
             }
             FieldType::Enum(type_name) => {
                 // Check if it's a known enum type
                 if enum_map.contains_key(type_name) {
-                    TypeExpr::Named(self.generator.naming.apply(type_name))
+                    TypeShape::named(self.generator.naming.apply(type_name))
                 } else {
                     // External enum reference
-                    TypeExpr::Named(self.generator.naming.apply(type_name))
+                    TypeShape::named(self.generator.naming.apply(type_name))
                 }
             }
             FieldType::Map(key_type, value_type) => {
-                let key_expr = self.field_type_to_type_expr(
+                let key_shape = self.field_type_shape(
                     key_type,
                     &FieldLabel::Required,
                     message_map,
                     enum_map,
                 )?;
-                let value_expr = self.field_type_to_type_expr(
+                let value_shape = self.field_type_shape(
                     value_type,
                     &FieldLabel::Required,
                     message_map,
                     enum_map,
                 )?;
-                TypeExpr::Named(format!("Map<{}, {}>", key_expr, value_expr))
+                TypeShape::map(key_shape, value_shape)
             }
         };
 
+        // Map fields are repeated entries on the wire, but a single map in the type
+        if matches!(field_type, FieldType::Map(..)) {
+            return Ok(base_type);
+        }
+
         // Apply label modifiers
         match label {
             FieldLabel::Optional => {
                 // Wrap in Option for optional fields
-                Ok(TypeExpr::Named(format!("{} option", base_type)))
+                Ok(base_type.option())
             }
             FieldLabel::Required => Ok(base_type),
             FieldLabel::Repeated => {
                 // Wrap in list for repeated fields
-                Ok(TypeExpr::Named(format!("{} list", base_type)))
+                Ok(base_type.list())
             }
         }
     }
@@ -227,7 +378,7 @@ impl TypeProvider for ProtobufProvider {
         "ProtobufProvider"
     }
 
-    fn resolve_schema(&self, source: &str, _params: &ProviderParams) -> ProviderResult<Schema> {
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
         // Load proto file from path or inline content
         // Check if source looks like inline proto content (contains proto keywords)
         let looks_like_proto = source.contains("syntax") || source.contains("package")
@@ -236,18 +387,13 @@ impl TypeProvider for ProtobufProvider {
         let proto_content = if looks_like_proto {
             // Inline proto content
             source.to_string()
-        } else if source.starts_with("file://") {
-            let path = source.strip_prefix("file://").unwrap();
-            std::fs::read_to_string(path)
-                .map_err(|e| ProviderError::IoError(e.to_string()))?
         } else {
-            // Treat as file path
-            std::fs::read_to_string(source)
-                .map_err(|e| ProviderError::IoError(e.to_string()))?
+            read_source(source, params)?
         };
 
         // Parse the proto file to validate it
-        let _proto_file = self.parse_proto(&proto_content)?;
+        let limits = InputLimits::from_params(params)?;
+        let _proto_file = parser::parse_proto_with_limits(&proto_content, &limits)?;
 
         // Store the actual proto content directly in the Schema
         // This way we don't need to re-read files or handle paths again
@@ -255,6 +401,16 @@ impl TypeProvider for ProtobufProvider {
     }
 
     fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        self.generate_documented(schema, namespace).map(|(types, _)| types)
+    }
+}
+
+impl DocumentedProvider for ProtobufProvider {
+    fn generate_documented(
+        &self,
+        schema: &Schema,
+        namespace: &str,
+    ) -> ProviderResult<(GeneratedTypes, TypeDocs)> {
         match schema {
             Schema::Custom(proto_content) => {
                 // Parse the proto content
@@ -268,9 +424,139 @@ impl TypeProvider for ProtobufProvider {
     }
 }
 
+impl AttributedProvider for ProtobufProvider {
+    fn generate_attributed(
+        &self,
+        schema: &Schema,
+        namespace: &str,
+    ) -> ProviderResult<(GeneratedTypes, TypeAttributes)> {
+        let Schema::Custom(proto_content) = schema else {
+            return Err(ProviderError::ParseError("Expected Protobuf schema".to_string()));
+        };
+
+        let proto = self.parse_proto(proto_content)?;
+        let (types, _) = self.generate_from_proto(&proto, namespace)?;
+        let attributes = match types.modules.first() {
+            Some(module) => self.annotate_proto(&proto, &module.path),
+            None => TypeAttributes::new(),
+        };
+        Ok((types, attributes))
+    }
+}
+
+impl DecoderCodegen for ProtobufProvider {
+    fn wire_format(&self) -> WireFormat {
+        WireFormat::Protobuf
+    }
+
+    fn generate_decoders(
+        &self,
+        schema: &Schema,
+        namespace: &str,
+    ) -> ProviderResult<(GeneratedTypes, DecoderPlan)> {
+        let Schema::Custom(proto_content) = schema else {
+            return Err(ProviderError::ParseError("Expected Protobuf schema".to_string()));
+        };
+
+        let proto = self.parse_proto(proto_content)?;
+        let (types, _) = self.generate_from_proto(&proto, namespace)?;
+        let mut plan = DecoderPlan::new(WireFormat::Protobuf);
+        if let Some(module) = types.modules.first() {
+            for enum_def in &proto.enums {
+                self.plan_enum(&mut plan, &module.path, enum_def);
+            }
+            for message in &proto.messages {
+                self.plan_message(&mut plan, &module.path, message);
+            }
+        }
+        Ok((types, plan))
+    }
+}
+
+/// Wire encoding of values of a field type
+fn proto_encoding(field_type: &FieldType) -> ProtoEncoding {
+    match field_type {
+        FieldType::Int32 | FieldType::Int64 | FieldType::UInt32 | FieldType::UInt64 => ProtoEncoding::Varint,
+        FieldType::SInt32 | FieldType::SInt64 => ProtoEncoding::ZigZag,
+        FieldType::Bool => ProtoEncoding::Bool,
+        FieldType::Fixed32 => ProtoEncoding::Fixed32,
+        FieldType::Fixed64 => ProtoEncoding::Fixed64,
+        FieldType::SFixed32 => ProtoEncoding::SFixed32,
+        FieldType::SFixed64 => ProtoEncoding::SFixed64,
+        FieldType::Float => ProtoEncoding::Float,
+        FieldType::Double => ProtoEncoding::Double,
+        FieldType::String => ProtoEncoding::String,
+        FieldType::Bytes => ProtoEncoding::Bytes,
+        FieldType::Message(_) => ProtoEncoding::Message,
+        FieldType::Enum(_) => ProtoEncoding::Enum,
+        FieldType::Map(key, value) => {
+            ProtoEncoding::Map(Box::new(proto_encoding(key)), Box::new(proto_encoding(value)))
+        }
+    }
+}
+
+/// Name qualified by its enclosing package or message, if any
+fn qualify(scope: &str, name: &str) -> String {
+    if scope.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", scope, name)
+    }
+}
+
+impl SourceValidator for ProtobufProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(proto_content) = schema else {
+            return Err(ProviderError::ParseError("Expected Protobuf schema".to_string()));
+        };
+
+        let proto = self.parse_proto(proto_content)?;
+        let mut diagnostics = Vec::new();
+
+        for import in &proto.imports {
+            diagnostics.push(
+                Diagnostic::warning(
+                    "import-unresolved",
+                    format!("Imported file `{}` is not loaded; its types will be unresolved", import),
+                )
+                .at(import.clone()),
+            );
+        }
+
+        for service in &proto.services {
+            diagnostics.push(
+                Diagnostic::info(
+                    "service-skipped",
+                    format!(
+                        "Service `{}` ({} methods) does not generate types",
+                        service.name,
+                        service.methods.len()
+                    ),
+                )
+                .at(service.name.clone()),
+            );
+        }
+
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for ProtobufProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Docs)
+            .feature(OutputFeature::Attributes)
+            .feature(OutputFeature::Diagnostics)
+            .feature(OutputFeature::Decoders)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fusabi_provider_common::emit::golden;
 
     #[test]
     fn test_generate_simple_message() {
@@ -292,6 +578,86 @@ mod tests {
         assert!(!types.modules.is_empty());
         let module = &types.modules[0];
         assert!(!module.types.is_empty());
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/protobuf.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_generate_documented() {
+        let provider = ProtobufProvider::new();
+        let proto = r#"
+            syntax = "proto3";
+            package example;
+
+            // A person in the address book.
+            message Person {
+                string name = 1; // Full name
+                int32 age = 2;
+
+                // Every address the person
+                // can be reached at.
+                repeated string emails = 3;
+            }
+        "#;
+
+        let schema = provider.resolve_schema(proto, &ProviderParams::default()).unwrap();
+        let (types, docs) = provider.generate_documented(&schema, "Example").unwrap();
+
+        let module = &types.modules[0];
+        assert_eq!(docs.type_doc(&module.path, "Person"), Some("A person in the address book."));
+        assert_eq!(docs.member_doc(&module.path, "Person", "name"), Some("Full name"));
+        assert_eq!(docs.member_doc(&module.path, "Person", "age"), None);
+
+        golden::assert_fusabi_documented(
+            &types,
+            &docs,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/protobuf-documented.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_attributes() {
+        let provider = ProtobufProvider::new();
+        let proto = r#"
+            syntax = "proto3";
+            package shop.v1;
+
+            message Order {
+                enum Status {
+                    PENDING = 0;
+                    SHIPPED = 2;
+                }
+                sint64 id = 1;
+                repeated string items = 4;
+                map<string, int32> counts = 5;
+                Status status = 7;
+            }
+        "#;
+
+        let schema = provider.resolve_schema(proto, &ProviderParams::default()).unwrap();
+        let (types, attributes) = provider.generate_attributed(&schema, "Shop").unwrap();
+        let path = &types.modules[0].path;
+
+        assert_eq!(attributes.type_attribute(path, "Order", "protobuf.name"), Some("shop.v1.Order"));
+        assert_eq!(
+            attributes.type_attribute(path, "Status", "protobuf.name"),
+            Some("shop.v1.Order.Status")
+        );
+        assert_eq!(attributes.member_attribute(path, "Order", "id", "protobuf.number"), Some("1"));
+        assert_eq!(attributes.member_attribute(path, "Order", "id", "protobuf.type"), Some("sint64"));
+        assert_eq!(
+            attributes.member_attribute(path, "Order", "items", "protobuf.label"),
+            Some("repeated")
+        );
+        assert_eq!(
+            attributes.member_attribute(path, "Order", "counts", "protobuf.type"),
+            Some("map<string, int32>")
+        );
+        let shipped = provider.generator.naming.apply("SHIPPED");
+        assert_eq!(attributes.member_attribute(path, "Status", &shipped, "protobuf.number"), Some("2"));
     }
 
     #[test]
@@ -451,4 +817,80 @@ mod tests {
         });
         assert!(has_user, "Should have User record");
     }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = ProtobufProvider::new();
+        let proto = r#"
+            syntax = "proto3";
+            import "google/protobuf/timestamp.proto";
+
+            message Ping {
+                string id = 1;
+            }
+
+            service Health {
+                rpc Check(Ping) returns (Ping);
+            }
+        "#;
+
+        let report = provider.validate_source(proto, &ProviderParams::default());
+        assert!(report.is_valid());
+        assert_eq!(report.warnings().next().unwrap().code, "import-unresolved");
+        assert!(report.diagnostics.iter().any(|d| d.code == "service-skipped"));
+    }
+
+    #[test]
+    fn test_generate_decoders() {
+        let provider = ProtobufProvider::new();
+        let proto = r#"
+            syntax = "proto3";
+            package shop;
+
+            enum Status {
+                UNKNOWN = 0;
+                SHIPPED = 2;
+            }
+
+            message Order {
+                sint64 id = 1;
+                Status status = 3;
+                map<string, int32> counts = 4;
+            }
+        "#;
+
+        let schema = provider.resolve_schema(proto, &ProviderParams::default()).unwrap();
+        let (types, plan) = provider.generate_decoders(&schema, "Shop").unwrap();
+        assert_eq!(plan.format(), WireFormat::Protobuf);
+        assert_eq!(plan.len(), 2);
+
+        let path = vec!["shop".to_string()];
+        let variants = ["UNKNOWN", "SHIPPED"].map(|name| provider.generator.naming.apply(name));
+        assert_eq!(
+            plan.decoder(&path, "Status"),
+            Some(&TypeDecoder::Numbered(vec![(0, variants[0].clone()), (2, variants[1].clone())]))
+        );
+        let Some(TypeDecoder::Record(fields)) = plan.decoder(&path, "Order") else {
+            panic!("Expected a record decoder");
+        };
+        assert_eq!(
+            fields[0],
+            FieldDecoder::new("id", FieldCodec::Protobuf { number: 1, encoding: ProtoEncoding::ZigZag })
+        );
+        assert_eq!(
+            fields[2].codec,
+            FieldCodec::Protobuf {
+                number: 4,
+                encoding: ProtoEncoding::Map(Box::new(ProtoEncoding::String), Box::new(ProtoEncoding::Varint)),
+            }
+        );
+
+        let TypeDefinition::Record(order) = &types.modules[0].types[1] else {
+            panic!("Expected Order record");
+        };
+        assert_eq!(
+            TypeShape::from_type_expr(&order.fields[2].1),
+            TypeShape::map(TypeShape::named("string"), TypeShape::named("int"))
+        );
+    }
 }
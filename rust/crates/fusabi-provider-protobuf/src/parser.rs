@@ -2,22 +2,64 @@
 //!
 //! This is a simplified protobuf parser that handles the most common syntax.
 //! It supports proto2 and proto3 syntax for messages, enums, and services.
+//!
+//! Comments follow `protoc`'s attachment rules: a comment block directly
+//! above a declaration, with no blank line in between, is its leading
+//! comment, and a comment after it on the same line is its trailing
+//! comment. Messages, fields, enums and enum values keep the leading
+//! comment, or the trailing one when there is none.
+//!
+//! Errors report the line and column of the offending token, and the
+//! messages, enums and services being parsed.
+//!
+//! [`parse_proto_with_limits`] also rejects messages nested deeper than
+//! `max_depth` and files declaring more than `max_types` messages, enums
+//! and services.
 
 use crate::types::{
     ProtoFile, Message, Field, FieldType, FieldLabel, Enum, EnumValue, Service, Method,
 };
+use fusabi_provider_common::error::Context;
+use fusabi_provider_common::limits::InputLimits;
+use fusabi_provider_common::span::SourceSpan;
 use fusabi_type_providers::{ProviderError, ProviderResult};
+use std::fmt;
+use std::str::FromStr;
 
 /// Parse a .proto file from string content
 pub fn parse_proto(content: &str) -> ProviderResult<ProtoFile> {
-    let mut parser = Parser::new(content);
+    parse_proto_with_limits(content, &InputLimits::default())
+}
+
+/// Parse a .proto file, rejecting sources that exceed `limits`
+pub fn parse_proto_with_limits(content: &str, limits: &InputLimits) -> ProviderResult<ProtoFile> {
+    limits.check_size(content)?;
+    let mut parser = Parser::new(content, *limits);
     parser.parse_file()
 }
 
 /// Simple protobuf parser
-struct Parser {
+struct Parser<'a> {
+    source: &'a str,
     tokens: Vec<Token>,
+    comments: Vec<TokenComments>,
+    /// Byte offset of each token in the source
+    offsets: Vec<usize>,
     pos: usize,
+    limits: InputLimits,
+    /// Messages open around the current token
+    depth: usize,
+    /// Messages, enums and services declared so far
+    types: usize,
+}
+
+/// Comments attached to a token
+#[derive(Debug, Clone, Default)]
+struct TokenComments {
+    /// Comment block directly above the token
+    leading: Option<String>,
+    /// Comment after the token on the same line
+    trailing: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -57,10 +99,49 @@ enum Token {
     Eof,
 }
 
-impl Parser {
-    fn new(content: &str) -> Self {
-        let tokens = tokenize(content);
-        Self { tokens, pos: 0 }
+impl<'a> Parser<'a> {
+    fn new(content: &'a str, limits: InputLimits) -> Self {
+        let (tokens, comments, offsets) = tokenize(content);
+        Self {
+            source: content,
+            tokens,
+            comments,
+            offsets,
+            pos: 0,
+            limits,
+            depth: 0,
+            types: 0,
+        }
+    }
+
+    /// Position of the current token
+    fn span(&self) -> SourceSpan {
+        let offset = self.offsets.get(self.pos).copied().unwrap_or(self.source.len());
+        SourceSpan::locate(self.source, offset)
+    }
+
+    /// Parse error located at the current token
+    fn error(&self, message: impl fmt::Display) -> ProviderError {
+        self.span().error(message)
+    }
+
+    /// Count a declared type, checking the limits at the current token
+    fn declare(&mut self) -> ProviderResult<()> {
+        self.types += 1;
+        let at = format!("at line {}", self.span());
+        self.limits.check_depth(self.depth).context(&at)?;
+        self.limits.check_type_count(self.types).context(&at)
+    }
+
+    /// Leading comment of the current token
+    fn leading_comment(&self) -> Option<String> {
+        self.comments.get(self.pos).and_then(|c| c.leading.clone())
+    }
+
+    /// Trailing comment of the token just consumed
+    fn trailing_comment(&self) -> Option<String> {
+        let previous = self.pos.checked_sub(1)?;
+        self.comments.get(previous).and_then(|c| c.trailing.clone())
     }
 
     fn current(&self) -> &Token {
@@ -78,11 +159,7 @@ impl Parser {
             self.advance();
             Ok(())
         } else {
-            Err(ProviderError::ParseError(format!(
-                "Expected {:?}, got {:?}",
-                expected,
-                self.current()
-            )))
+            Err(self.error(format_args!("Expected {:?}, got {:?}", expected, self.current())))
         }
     }
 
@@ -93,24 +170,21 @@ impl Parser {
                 self.advance();
                 Ok(result)
             }
-            _ => Err(ProviderError::ParseError(format!(
-                "Expected identifier, got {:?}",
-                self.current()
-            ))),
+            _ => Err(self.error(format_args!("Expected identifier, got {:?}", self.current()))),
         }
     }
 
-    fn expect_number(&mut self) -> ProviderResult<String> {
+    /// Field or enum number, `what` naming it in errors
+    fn expect_number<T: FromStr>(&mut self, what: &str) -> ProviderResult<T> {
         match self.current() {
             Token::Number(s) => {
-                let result = s.clone();
+                let number = s
+                    .parse()
+                    .map_err(|_| self.error(format_args!("Invalid {} number: {}", what, s)))?;
                 self.advance();
-                Ok(result)
+                Ok(number)
             }
-            _ => Err(ProviderError::ParseError(format!(
-                "Expected number, got {:?}",
-                self.current()
-            ))),
+            _ => Err(self.error(format_args!("Expected number, got {:?}", self.current()))),
         }
     }
 
@@ -166,11 +240,22 @@ impl Parser {
     }
 
     fn parse_message(&mut self) -> ProviderResult<Message> {
+        let leading = self.leading_comment();
+        self.depth += 1;
+        self.declare()?;
         self.expect(Token::Message)?;
         let name = self.expect_identifier()?;
-        self.expect(Token::LeftBrace)?;
+        let context = format!("while parsing message {}", name);
 
         let mut message = Message::new(name);
+        self.parse_message_body(&mut message, leading).context(context)?;
+        self.depth -= 1;
+        Ok(message)
+    }
+
+    fn parse_message_body(&mut self, message: &mut Message, leading: Option<String>) -> ProviderResult<()> {
+        self.expect(Token::LeftBrace)?;
+        message.comment = leading.or_else(|| self.trailing_comment());
 
         while self.current() != &Token::RightBrace && self.current() != &Token::Eof {
             match self.current() {
@@ -197,11 +282,12 @@ impl Parser {
             }
         }
 
-        self.expect(Token::RightBrace)?;
-        Ok(message)
+        self.expect(Token::RightBrace)
     }
 
     fn parse_field(&mut self) -> ProviderResult<Field> {
+        let leading = self.leading_comment();
+
         // Parse optional label
         let label = match self.current() {
             Token::Optional => {
@@ -228,10 +314,7 @@ impl Parser {
 
         // Parse field number
         self.expect(Token::Equals)?;
-        let number_str = self.expect_number()?;
-        let number: u32 = number_str.parse().map_err(|_| {
-            ProviderError::ParseError(format!("Invalid field number: {}", number_str))
-        })?;
+        let number: u32 = self.expect_number("field")?;
 
         self.expect(Token::Semicolon)?;
 
@@ -240,10 +323,12 @@ impl Parser {
             field_type,
             number,
             label,
+            comment: leading.or_else(|| self.trailing_comment()),
         })
     }
 
     fn parse_map_field(&mut self) -> ProviderResult<Field> {
+        let leading = self.leading_comment();
         self.expect(Token::Map)?;
         self.expect(Token::LeftAngle)?;
 
@@ -264,10 +349,7 @@ impl Parser {
 
         // Parse field number
         self.expect(Token::Equals)?;
-        let number_str = self.expect_number()?;
-        let number: u32 = number_str.parse().map_err(|_| {
-            ProviderError::ParseError(format!("Invalid field number: {}", number_str))
-        })?;
+        let number: u32 = self.expect_number("field")?;
 
         self.expect(Token::Semicolon)?;
 
@@ -276,46 +358,64 @@ impl Parser {
             field_type: FieldType::Map(Box::new(key_type), Box::new(value_type)),
             number,
             label: FieldLabel::Repeated, // Maps are always repeated
+            comment: leading.or_else(|| self.trailing_comment()),
         })
     }
 
     fn parse_enum(&mut self) -> ProviderResult<Enum> {
+        let leading = self.leading_comment();
+        self.declare()?;
         self.expect(Token::Enum)?;
         let name = self.expect_identifier()?;
-        self.expect(Token::LeftBrace)?;
+        let context = format!("while parsing enum {}", name);
 
         let mut enum_def = Enum::new(name);
+        self.parse_enum_body(&mut enum_def, leading).context(context)?;
+        Ok(enum_def)
+    }
+
+    fn parse_enum_body(&mut self, enum_def: &mut Enum, leading: Option<String>) -> ProviderResult<()> {
+        self.expect(Token::LeftBrace)?;
+        enum_def.comment = leading.or_else(|| self.trailing_comment());
 
         while self.current() != &Token::RightBrace && self.current() != &Token::Eof {
             if let Token::Identifier(value_name) = self.current() {
                 let value_name = value_name.clone();
+                let leading = self.leading_comment();
                 self.advance();
                 self.expect(Token::Equals)?;
-                let number_str = self.expect_number()?;
-                let number: i32 = number_str.parse().map_err(|_| {
-                    ProviderError::ParseError(format!("Invalid enum number: {}", number_str))
-                })?;
+                let number: i32 = self.expect_number("enum")?;
                 self.expect(Token::Semicolon)?;
 
-                enum_def.values.push(EnumValue { name: value_name, number });
+                enum_def.values.push(EnumValue {
+                    name: value_name,
+                    number,
+                    comment: leading.or_else(|| self.trailing_comment()),
+                });
             } else {
                 self.advance();
             }
         }
 
-        self.expect(Token::RightBrace)?;
-        Ok(enum_def)
+        self.expect(Token::RightBrace)
     }
 
     fn parse_service(&mut self) -> ProviderResult<Service> {
+        self.declare()?;
         self.expect(Token::Service)?;
         let name = self.expect_identifier()?;
-        self.expect(Token::LeftBrace)?;
+        let context = format!("while parsing service {}", name);
 
         let mut service = Service {
             name,
             methods: Vec::new(),
         };
+        self.parse_service_body(&mut service).context(context)?;
+        Ok(service)
+    }
+
+    fn parse_service_body(&mut self, service: &mut Service) -> ProviderResult<()> {
+        self.expect(Token::LeftBrace)?;
 
         while self.current() != &Token::RightBrace && self.current() != &Token::Eof {
             if self.current() == &Token::Rpc {
@@ -325,8 +425,7 @@ impl Parser {
             }
         }
 
-        self.expect(Token::RightBrace)?;
-        Ok(service)
+        self.expect(Token::RightBrace)
     }
 
     fn parse_method(&mut self) -> ProviderResult<Method> {
@@ -389,19 +488,71 @@ impl Parser {
     }
 }
 
-/// Tokenize a protobuf file
-fn tokenize(content: &str) -> Vec<Token> {
+/// Character iterator that knows its byte offset
+struct Cursor<'a> {
+    source: &'a str,
+    offset: usize,
+    current: Option<char>,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            offset: 0,
+            current: source.chars().next(),
+        }
+    }
+
+    fn peek(&self) -> Option<&char> {
+        self.current.as_ref()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let ch = self.current?;
+        self.offset += ch.len_utf8();
+        self.current = self.source[self.offset..].chars().next();
+        Some(ch)
+    }
+}
+
+/// Tokenize a protobuf file, attaching comments to the tokens they document
+///
+/// Also returns the byte offset of each token, with the end of the content
+/// for the final `Eof`.
+fn tokenize(content: &str) -> (Vec<Token>, Vec<TokenComments>, Vec<usize>) {
     let mut tokens = Vec::new();
-    let mut chars = content.chars().peekable();
+    let mut comments: Vec<TokenComments> = Vec::new();
+    let mut offsets = Vec::new();
+    let mut chars = Cursor::new(content);
+
+    // Comment lines waiting for the next token
+    let mut pending: Vec<String> = Vec::new();
+    // Whether the current line has a token, or nothing at all yet
+    let mut line_has_token = false;
+    let mut line_is_blank = true;
 
     while let Some(&ch) = chars.peek() {
+        let token_count = tokens.len();
+        let start = chars.offset;
+
         match ch {
-            ' ' | '\t' | '\n' | '\r' => {
+            '\n' => {
+                chars.next();
+                // A blank line detaches the comments above it
+                if line_is_blank {
+                    pending.clear();
+                }
+                line_has_token = false;
+                line_is_blank = true;
+            }
+            ' ' | '\t' | '\r' => {
                 chars.next();
             }
             '/' => {
                 chars.next();
-                if chars.peek() == Some(&'/') {
+                let mut text = String::new();
+                let block = if chars.peek() == Some(&'/') {
                     // Line comment
                     chars.next();
                     while let Some(&c) = chars.peek() {
@@ -409,7 +560,9 @@ fn tokenize(content: &str) -> Vec<Token> {
                         if c == '\n' {
                             break;
                         }
+                        text.push(c);
                     }
+                    false
                 } else if chars.peek() == Some(&'*') {
                     // Block comment
                     chars.next();
@@ -419,7 +572,30 @@ fn tokenize(content: &str) -> Vec<Token> {
                             chars.next();
                             break;
                         }
+                        text.push(c);
                     }
+                    true
+                } else {
+                    continue;
+                };
+
+                let text = clean_comment(&text, block);
+                match comments.last_mut() {
+                    Some(last) if line_has_token => {
+                        last.trailing = Some(match last.trailing.take() {
+                            Some(existing) => format!("{}\n{}", existing, text),
+                            None => text,
+                        });
+                    }
+                    _ => pending.push(text),
+                }
+
+                if block {
+                    line_is_blank = false;
+                } else {
+                    // The line comment consumed the newline
+                    line_has_token = false;
+                    line_is_blank = true;
                 }
             }
             '{' => {
@@ -526,15 +702,57 @@ fn tokenize(content: &str) -> Vec<Token> {
                 chars.next();
             }
         }
+
+        if tokens.len() > token_count {
+            let leading = if pending.is_empty() {
+                None
+            } else {
+                Some(pending.join("\n"))
+            };
+            pending.clear();
+            comments.push(TokenComments {
+                leading,
+                trailing: None,
+            });
+            offsets.push(start);
+            line_has_token = true;
+            line_is_blank = false;
+        }
     }
 
     tokens.push(Token::Eof);
-    tokens
+    comments.push(TokenComments::default());
+    offsets.push(content.len());
+    (tokens, comments, offsets)
+}
+
+/// Strip the conventional single space after comment markers
+///
+/// Block comment lines also lose their leading `*` decoration, and blank
+/// lines at either end are dropped.
+fn clean_comment(text: &str, block: bool) -> String {
+    let lines: Vec<&str> = text
+        .lines()
+        .map(|line| {
+            let line = if block {
+                let line = line.trim_start();
+                line.strip_prefix('*').unwrap_or(line)
+            } else {
+                line
+            };
+            line.strip_prefix(' ').unwrap_or(line).trim_end()
+        })
+        .collect();
+
+    let start = lines.iter().position(|l| !l.is_empty()).unwrap_or(lines.len());
+    let end = lines.iter().rposition(|l| !l.is_empty()).map_or(start, |i| i + 1);
+    lines[start..end].join("\n")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fusabi_provider_common::span::span_of;
 
     #[test]
     fn test_parse_simple_message() {
@@ -586,4 +804,74 @@ mod tests {
         assert_eq!(file.messages.len(), 1);
         assert_eq!(file.messages[0].nested_messages.len(), 1);
     }
+
+    #[test]
+    fn test_parse_comments() {
+        let proto = r#"
+            // Detached comment, separated by a blank line.
+
+            /**
+             * Lifecycle of an order.
+             */
+            enum State {
+                PENDING = 0; // Not yet paid
+                // Paid and shipped
+                DONE = 1;
+            }
+
+            message Order { // An order
+                /* Order id */ string id = 1;
+                map<string, string> labels = 2; // Free-form labels
+                string note = 3;
+            }
+        "#;
+
+        let file = parse_proto(proto).unwrap();
+        let state = &file.enums[0];
+        assert_eq!(state.comment.as_deref(), Some("Lifecycle of an order."));
+        assert_eq!(state.values[0].comment.as_deref(), Some("Not yet paid"));
+        assert_eq!(state.values[1].comment.as_deref(), Some("Paid and shipped"));
+
+        let order = &file.messages[0];
+        assert_eq!(order.comment.as_deref(), Some("An order"));
+        assert_eq!(order.fields[0].comment.as_deref(), Some("Order id"));
+        assert_eq!(order.fields[1].comment.as_deref(), Some("Free-form labels"));
+        assert_eq!(order.fields[2].comment, None);
+    }
+
+    #[test]
+    fn test_error_positions() {
+        let proto = "syntax = \"proto3\";\n\nmessage Person {\n  string = 1;\n}\n";
+        let error = parse_proto(proto).unwrap_err();
+        let span = span_of(&error).unwrap();
+        assert_eq!((span.line, span.column), (4, 10));
+        assert_eq!(span.snippet, "  string = 1;");
+        assert!(error
+            .to_string()
+            .contains("while parsing message Person: Expected identifier, got Equals"));
+
+        let span = span_of(&parse_proto("enum E {\n  A = 99999999999;\n}").unwrap_err()).unwrap();
+        assert_eq!((span.line, span.column), (2, 7));
+
+        let span = span_of(&parse_proto("message Person {\n  string name = 1;\n").unwrap_err()).unwrap();
+        assert_eq!((span.line, span.column), (3, 1));
+    }
+
+    #[test]
+    fn test_limits() {
+        let limits = InputLimits {
+            max_depth: 2,
+            max_types: 3,
+            ..InputLimits::default()
+        };
+        let nested = "message A {\n  message B {\n    message C {}\n  }\n}";
+        assert!(parse_proto(nested).is_ok());
+        let error = parse_proto_with_limits(nested, &limits).unwrap_err();
+        assert!(matches!(error, ProviderError::InvalidSource(_)));
+        assert!(error.to_string().contains("at line 3, column 5: Nesting is deeper than max_depth (2)"));
+
+        let flat = "message A {}\nenum B { X = 0; }\nservice C {}\nmessage D {}";
+        let error = parse_proto_with_limits(flat, &limits).unwrap_err();
+        assert!(error.to_string().contains("at line 4, column 1: Source declares 4 types, more than max_types (3)"));
+    }
 }
@@ -1,6 +1,7 @@
 //! Protobuf AST type definitions
 
 use std::collections::HashMap;
+use std::fmt;
 
 /// Represents a complete .proto file
 #[derive(Debug, Clone, Default)]
@@ -28,6 +29,8 @@ pub struct Message {
     pub nested_messages: Vec<Message>,
     /// Nested enums
     pub nested_enums: Vec<Enum>,
+    /// Leading or trailing comment
+    pub comment: Option<String>,
 }
 
 /// Protobuf field definition
@@ -41,6 +44,8 @@ pub struct Field {
     pub number: u32,
     /// Field label (optional, required, repeated)
     pub label: FieldLabel,
+    /// Leading or trailing comment
+    pub comment: Option<String>,
 }
 
 /// Field label indicating cardinality
@@ -91,6 +96,8 @@ pub struct Enum {
     pub name: String,
     /// Enum values
     pub values: Vec<EnumValue>,
+    /// Leading or trailing comment
+    pub comment: Option<String>,
 }
 
 /// Protobuf enum value
@@ -100,6 +107,8 @@ pub struct EnumValue {
     pub name: String,
     /// Value number
     pub number: i32,
+    /// Leading or trailing comment
+    pub comment: Option<String>,
 }
 
 /// Protobuf service definition
@@ -181,6 +190,7 @@ impl Message {
             fields: Vec::new(),
             nested_messages: Vec::new(),
             nested_enums: Vec::new(),
+            comment: None,
         }
     }
 
@@ -213,12 +223,14 @@ impl Enum {
         Self {
             name,
             values: Vec::new(),
+            comment: None,
         }
     }
 }
 
 impl FieldType {
     /// Parse a field type from a string
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Self {
         match s {
             "double" => FieldType::Double,
@@ -249,3 +261,28 @@ impl FieldType {
         !matches!(self, FieldType::Message(_) | FieldType::Enum(_) | FieldType::Map(_, _))
     }
 }
+
+impl fmt::Display for FieldType {
+    /// Renders the type as written in a .proto file
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldType::Double => f.write_str("double"),
+            FieldType::Float => f.write_str("float"),
+            FieldType::Int32 => f.write_str("int32"),
+            FieldType::Int64 => f.write_str("int64"),
+            FieldType::UInt32 => f.write_str("uint32"),
+            FieldType::UInt64 => f.write_str("uint64"),
+            FieldType::SInt32 => f.write_str("sint32"),
+            FieldType::SInt64 => f.write_str("sint64"),
+            FieldType::Fixed32 => f.write_str("fixed32"),
+            FieldType::Fixed64 => f.write_str("fixed64"),
+            FieldType::SFixed32 => f.write_str("sfixed32"),
+            FieldType::SFixed64 => f.write_str("sfixed64"),
+            FieldType::Bool => f.write_str("bool"),
+            FieldType::String => f.write_str("string"),
+            FieldType::Bytes => f.write_str("bytes"),
+            FieldType::Message(name) | FieldType::Enum(name) => f.write_str(name),
+            FieldType::Map(key, value) => write!(f, "map<{}, {}>", key, value),
+        }
+    }
+}
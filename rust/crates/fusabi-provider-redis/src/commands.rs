@@ -0,0 +1,137 @@
+//! Redis command specs
+//!
+//! Reads command definitions in the format of Redis's `src/commands/*.json`
+//! and the `commands.json` published with the docs: an object from command
+//! name to its spec.
+//!
+//! ```json
+//! {"SET": {"group": "string", "since": "1.0.0", "arguments": [
+//!     {"name": "key", "type": "key"},
+//!     {"name": "condition", "type": "oneof", "optional": true, "arguments": [
+//!         {"name": "nx", "type": "pure-token", "token": "NX"},
+//!         {"name": "xx", "type": "pure-token", "token": "XX"}]}],
+//!   "reply_schema": {"anyOf": [{"const": "OK"}, {"type": "null"}]}}}
+//! ```
+//!
+//! Subcommands are keyed `CONFIG GET`, or `GET` with `"container": "CONFIG"`.
+
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A command
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommandDef {
+    /// Upper case, subcommands after their container: `CONFIG GET`
+    pub name: String,
+    pub group: Option<String>,
+    pub deprecated_since: Option<String>,
+    pub arguments: Vec<ArgumentDef>,
+    /// JSON Schema of the RESP3 reply, from Redis 7.2 specs
+    pub reply_schema: Option<Value>,
+}
+
+/// A command argument
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArgumentDef {
+    pub name: String,
+    /// `key`, `string`, `pattern`, `integer`, `double`, `unix-time`,
+    /// `pure-token`, `oneof` or `block`
+    pub arg_type: String,
+    pub token: Option<String>,
+    pub optional: bool,
+    pub multiple: bool,
+    /// Alternatives of a `oneof`, members of a `block`
+    pub arguments: Vec<ArgumentDef>,
+}
+
+/// Parse command specs: one object of commands, or a list of them
+pub fn parse_commands(content: &str) -> ProviderResult<Vec<CommandDef>> {
+    let document: Value = serde_json::from_str(content)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid Redis command specs: {}", e)))?;
+    let objects = match &document {
+        Value::Array(objects) => objects.as_slice(),
+        Value::Object(_) => std::slice::from_ref(&document),
+        _ => return Err(ProviderError::ParseError("Expected an object of Redis command specs".to_string())),
+    };
+
+    let mut commands = Vec::new();
+    for object in objects {
+        let Value::Object(specs) = object else {
+            return Err(ProviderError::ParseError("Expected an object of Redis command specs".to_string()));
+        };
+        for (name, spec) in specs {
+            if !spec.is_object() {
+                return Err(ProviderError::ParseError(format!("Command `{}` is not an object", name)));
+            }
+            let name = match spec.get("container").and_then(Value::as_str) {
+                Some(container) => format!("{} {}", container, name),
+                None => name.clone(),
+            };
+            commands.push(CommandDef {
+                name: name.to_ascii_uppercase(),
+                group: spec.get("group").and_then(Value::as_str).map(str::to_string),
+                deprecated_since: spec.get("deprecated_since").and_then(Value::as_str).map(str::to_string),
+                arguments: arguments(spec.get("arguments"))?,
+                reply_schema: spec.get("reply_schema").cloned(),
+            });
+        }
+    }
+    Ok(commands)
+}
+
+fn arguments(value: Option<&Value>) -> ProviderResult<Vec<ArgumentDef>> {
+    let Some(list) = value else {
+        return Ok(Vec::new());
+    };
+    list.as_array()
+        .ok_or_else(|| ProviderError::ParseError("Command `arguments` must be a list".to_string()))?
+        .iter()
+        .map(|argument| {
+            let name = argument
+                .get("name")
+                .and_then(Value::as_str)
+                .ok_or_else(|| ProviderError::ParseError(format!("Argument has no `name`: {}", argument)))?;
+            let flag = |key: &str| argument.get(key).and_then(Value::as_bool).unwrap_or(false);
+            Ok(ArgumentDef {
+                name: name.to_string(),
+                arg_type: argument.get("type").and_then(Value::as_str).unwrap_or("string").to_string(),
+                token: argument.get("token").and_then(Value::as_str).map(str::to_string),
+                optional: flag("optional"),
+                multiple: flag("multiple"),
+                arguments: arguments(argument.get("arguments"))?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        let commands = parse_commands(
+            r#"{
+                "MSET": {"group": "string", "arguments": [
+                    {"name": "data", "type": "block", "multiple": true, "arguments": [
+                        {"name": "key", "type": "key", "key_spec_index": 0},
+                        {"name": "value", "type": "string"}]}]},
+                "GET": {"container": "CONFIG", "group": "server", "arguments": [
+                    {"name": "parameter", "type": "string", "multiple": true}]},
+                "GETSET": {"group": "string", "deprecated_since": "6.2.0"}
+            }"#,
+        )
+        .unwrap();
+
+        let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["CONFIG GET", "GETSET", "MSET"]);
+        let data = &commands[2].arguments[0];
+        assert!(data.multiple);
+        assert_eq!(data.arguments[0].arg_type, "key");
+        assert_eq!(commands[1].deprecated_since.as_deref(), Some("6.2.0"));
+
+        assert!(parse_commands(r#"{"GET": {"arguments": [{"type": "key"}]}}"#).is_err());
+        assert!(parse_commands("[1]").is_err());
+    }
+}
@@ -0,0 +1,150 @@
+//! Key pattern declarations
+//!
+//! Redis has no schema for what is stored under a key, so applications
+//! declare it: a mapping from key pattern to the Redis type and the shape
+//! of the values.
+//!
+//! ```yaml
+//! keys:
+//!   "user:{id}":
+//!     type: hash
+//!     fields: {name: string, age: "int?"}
+//!   "session:{token}": {type: string, value: string}
+//!   "cart:{user_id}":
+//!     type: string          # a JSON-encoded value
+//!     fields: {items: "string list", total: float}
+//!   "leaderboard:{game}": {type: zset, member: string}
+//!   "events:{source}":
+//!     type: stream
+//!     fields: {kind: string, payload: string}
+//! ```
+//!
+//! `{name}` segments are placeholders. Field types are Fusabi types; a
+//! trailing `?` makes one optional.
+
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+/// Redis types a key can hold
+pub const REDIS_TYPES: &[&str] = &["string", "hash", "list", "set", "zset", "stream", "json"];
+
+/// A declared key pattern
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyPattern {
+    pub pattern: String,
+    /// Type name to generate under, instead of one from the pattern
+    pub name: Option<String>,
+    pub redis_type: String,
+    /// Field name and type, for structured values
+    pub fields: Vec<(String, String)>,
+    /// Element type of scalar values
+    pub value: Option<String>,
+    /// Member type of sorted sets
+    pub member: Option<String>,
+}
+
+impl KeyPattern {
+    /// `user:{id}:cart` → `["id"]`
+    pub fn placeholders(&self) -> Vec<&str> {
+        self.pattern
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    /// The literal parts of the pattern: `user:{id}:cart` → `["user", "cart"]`
+    pub fn literals(&self) -> Vec<&str> {
+        let mut literals = Vec::new();
+        let mut rest = self.pattern.as_str();
+        loop {
+            let (literal, tail) = rest.split_once('{').unwrap_or((rest, ""));
+            literals.extend(literal.split(|c: char| !c.is_ascii_alphanumeric()).filter(|part| !part.is_empty()));
+            match tail.split_once('}') {
+                Some((_, after)) => rest = after,
+                None => return literals,
+            }
+        }
+    }
+
+    /// The pattern with placeholders as `*`, to compare patterns by
+    pub fn shape(&self) -> String {
+        let mut shape = String::new();
+        let mut rest = self.pattern.as_str();
+        while let Some((literal, tail)) = rest.split_once('{') {
+            shape.push_str(literal);
+            shape.push('*');
+            rest = tail.split_once('}').map_or("", |(_, after)| after);
+        }
+        shape + rest
+    }
+}
+
+/// Parse a key pattern declaration
+pub fn parse_keys(content: &str) -> ProviderResult<Vec<KeyPattern>> {
+    let document: Value = serde_yaml::from_str(content)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid Redis key declaration: {}", e)))?;
+    let Some(Value::Mapping(keys)) = document.get("keys") else {
+        return Err(ProviderError::ParseError("Expected a `keys` mapping of key pattern to value shape".to_string()));
+    };
+
+    keys.iter()
+        .map(|(pattern, spec)| {
+            let pattern = pattern
+                .as_str()
+                .ok_or_else(|| ProviderError::ParseError(format!("Key patterns must be strings, got {:?}", pattern)))?;
+            let text = |key: &str| spec.get(key).and_then(Value::as_str).map(str::to_string);
+            let fields = match spec.get("fields") {
+                Some(Value::Mapping(fields)) => fields
+                    .iter()
+                    .map(|(name, ty)| match (name.as_str(), ty.as_str()) {
+                        (Some(name), Some(ty)) => Ok((name.to_string(), ty.to_string())),
+                        _ => Err(ProviderError::ParseError(format!("Fields of `{}` must map names to types", pattern))),
+                    })
+                    .collect::<ProviderResult<_>>()?,
+                Some(_) => {
+                    return Err(ProviderError::ParseError(format!("`fields` of `{}` must be a mapping", pattern)))
+                }
+                None => Vec::new(),
+            };
+            Ok(KeyPattern {
+                pattern: pattern.to_string(),
+                name: text("name"),
+                redis_type: text("type")
+                    .ok_or_else(|| ProviderError::ParseError(format!("Key pattern `{}` has no `type`", pattern)))?,
+                fields,
+                value: text("value"),
+                member: text("member"),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_keys() {
+        let keys = parse_keys(
+            r#"
+keys:
+  "user:{id}:profile":
+    type: hash
+    fields: {name: string, age: "int?"}
+  "leaderboard:{game}": {type: zset, member: string, name: Scores}
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(keys[0].placeholders(), ["id"]);
+        assert_eq!(keys[0].literals(), ["user", "profile"]);
+        assert_eq!(keys[0].shape(), "user:*:profile");
+        assert_eq!(keys[0].fields[1], ("age".to_string(), "int?".to_string()));
+        assert_eq!(keys[1].name.as_deref(), Some("Scores"));
+
+        assert!(parse_keys("keys:\n  \"a:{id}\": {value: string}\n").is_err());
+        assert!(parse_keys("GET: {}").is_err());
+    }
+}
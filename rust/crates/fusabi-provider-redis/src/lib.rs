@@ -0,0 +1,623 @@
+//! Redis Type Provider
+//!
+//! Generates Fusabi types for typed Redis access layers: argument and
+//! reply types from Redis's command specs, and value types from declared
+//! key patterns.
+//!
+//! # Sources
+//!
+//! Inline or from a file, either:
+//!
+//! - Command specs, as in Redis's `commands.json`; see [`commands`]
+//! - A key pattern declaration with a `keys` mapping; see [`keys`]
+//!
+//! # Generated Types
+//!
+//! For each command, named after it (`CONFIG GET` → `ConfigGet`):
+//!
+//! - `<Command>Args`: a field per argument. Keys, strings and patterns are
+//!   `string`, integers and Unix times `int64`, doubles `float`, and an
+//!   optional pure token (`GET`, `KEEPTTL`) is a `bool`. `multiple`
+//!   arguments are lists and `optional` ones options.
+//! - A union per `oneof` argument and a record per `block`, named after
+//!   the command and argument (`SetCondition`, `MsetData`)
+//! - `<Command>Reply` from the reply schema, when the reply is a union or
+//!   a map; single-value replies need no type
+//!
+//! `Command` is a union of every command's arguments.
+//!
+//! For each key pattern, named after its literal parts (`user:{id}:cart`
+//! → `UserCart`) or its `name`:
+//!
+//! - `<Name>Key`: a `string` field per placeholder, for building keys
+//! - The value type: `<Name>Hash` for hashes, `<Name>Value` for JSON
+//!   strings and documents, `<Name>Item` for structured list and set
+//!   elements, and `<Name>Entry` for stream entries (with `id`) and sorted
+//!   set members (with `score`)
+//!
+//! # Params
+//!
+//! | Param      | Meaning                                                        |
+//! |------------|----------------------------------------------------------------|
+//! | `commands` | Comma-separated commands to generate, in any case; a trailing `*` matches a prefix |
+//! | `groups`   | Comma-separated command groups to generate (`string`, `hash`)  |
+//! | `keys`     | A key pattern declaration to generate along with command specs, inline or a path |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_redis::RedisProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = RedisProvider::new();
+//! let params = ProviderParams::default()
+//!     .with("groups", "string, hash")
+//!     .with("keys", "redis-keys.yaml");
+//! let schema = provider.resolve_schema("redis/commands.json", &params)?;
+//! let types = provider.generate_types(&schema, "Redis")?;
+//! ```
+
+pub mod commands;
+pub mod keys;
+
+pub use commands::{parse_commands, ArgumentDef, CommandDef};
+pub use keys::{parse_keys, KeyPattern, REDIS_TYPES};
+
+use std::collections::{HashMap, HashSet};
+
+use fusabi_jsonschema_core::Converter;
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceValidator, TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, DuDef, VariantDef, TypeExpr, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Commands and key patterns, stored in [`Schema::Custom`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Resolved {
+    commands: Vec<CommandDef>,
+    keys: Vec<KeyPattern>,
+}
+
+/// Redis type provider
+pub struct RedisProvider {
+    generator: TypeGenerator,
+    converter: Converter,
+}
+
+impl RedisProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+            converter: Converter::new(),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<Resolved> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected Redis commands or keys: {}", e)))
+    }
+
+    fn generate(&self, resolved: &Resolved) -> (Vec<TypeDefinition>, Vec<Diagnostic>) {
+        let mut types = Vec::new();
+        let mut diagnostics = Vec::new();
+        let mut names = HashSet::new();
+
+        let mut variants = Vec::new();
+        for command in &resolved.commands {
+            let location = command.name.clone();
+            if let Some(since) = &command.deprecated_since {
+                diagnostics.push(
+                    Diagnostic::info("deprecated", format!("Deprecated since Redis {}", since)).at(location.clone()),
+                );
+            }
+            let base = self.generator.naming.apply(&command.name);
+            if !names.insert(base.clone()) {
+                diagnostics.push(
+                    Diagnostic::warning("duplicate-name", format!("Another command is also named `{}`; skipped", base))
+                        .at(location),
+                );
+                continue;
+            }
+
+            let args = format!("{}Args", base);
+            let fields = self.argument_fields(&base, &command.arguments, &location, &mut types, &mut diagnostics);
+            types.push(TypeDefinition::Record(RecordDef { name: args.clone(), fields }));
+            if let Some(reply) = &command.reply_schema {
+                let mut reply = reply.clone();
+                title_alternatives(&mut reply);
+                types.extend(self.converter.definition(&format!("{}Reply", base), &reply));
+            }
+            variants.push(VariantDef::new(base, vec![TypeShape::named(args).into()]));
+        }
+        if !variants.is_empty() {
+            types.push(TypeDefinition::Du(DuDef { name: "Command".to_string(), variants }));
+        }
+
+        self.key_types(&resolved.keys, &mut names, &mut types, &mut diagnostics);
+        (types, diagnostics)
+    }
+
+    fn argument_fields(
+        &self,
+        prefix: &str,
+        arguments: &[ArgumentDef],
+        location: &str,
+        types: &mut Vec<TypeDefinition>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Vec<(String, TypeExpr)> {
+        arguments
+            .iter()
+            .filter_map(|argument| {
+                // A required token is always sent, so there's nothing to choose
+                if argument.arg_type == "pure-token" {
+                    return argument.optional.then(|| (field_name(&argument.name), TypeShape::named("bool").into()));
+                }
+                let ty = self.argument_type(prefix, argument, location, types, diagnostics)?;
+                let ty = if argument.multiple { ty.list() } else { ty };
+                let ty = ty.option_unless(!argument.optional);
+                Some((field_name(&argument.name), ty.into()))
+            })
+            .collect()
+    }
+
+    /// The type of an argument's value, or `None` for a pure token
+    fn argument_type(
+        &self,
+        prefix: &str,
+        argument: &ArgumentDef,
+        location: &str,
+        types: &mut Vec<TypeDefinition>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Option<TypeShape> {
+        let ty = match argument.arg_type.as_str() {
+            "pure-token" => return None,
+            "key" | "string" | "pattern" => "string",
+            "integer" | "unix-time" => "int64",
+            "double" => "float",
+            "oneof" => {
+                let name = format!("{}{}", prefix, self.generator.naming.apply(&argument.name));
+                let variants = argument
+                    .arguments
+                    .iter()
+                    .map(|alternative| {
+                        let variant = self.generator.naming.apply(&alternative.name);
+                        match self.argument_type(&name, alternative, location, types, diagnostics) {
+                            Some(ty) => {
+                                let ty = if alternative.multiple { ty.list() } else { ty };
+                                VariantDef::new(variant, vec![ty.into()])
+                            }
+                            None => VariantDef::new_simple(variant),
+                        }
+                    })
+                    .collect();
+                types.push(TypeDefinition::Du(DuDef { name: name.clone(), variants }));
+                return Some(TypeShape::named(name));
+            }
+            "block" => {
+                let name = format!("{}{}", prefix, self.generator.naming.apply(&argument.name));
+                let fields = self.argument_fields(&name, &argument.arguments, location, types, diagnostics);
+                types.push(TypeDefinition::Record(RecordDef { name: name.clone(), fields }));
+                return Some(TypeShape::named(name));
+            }
+            other => {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        "unknown-argument-type",
+                        format!("Argument `{}` has unknown type `{}`; generated as `string`", argument.name, other),
+                    )
+                    .at(location.to_string()),
+                );
+                "string"
+            }
+        };
+        Some(TypeShape::named(ty))
+    }
+
+    fn key_types(
+        &self,
+        keys: &[KeyPattern],
+        names: &mut HashSet<String>,
+        types: &mut Vec<TypeDefinition>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        let named = |ty: &str| -> TypeExpr { TypeShape::named(ty).into() };
+        let mut shapes: HashMap<String, &str> = HashMap::new();
+        for key in keys {
+            let location = key.pattern.clone();
+            if !REDIS_TYPES.contains(&key.redis_type.as_str()) {
+                diagnostics.push(
+                    Diagnostic::error("unknown-redis-type", format!("Unknown Redis type `{}`", key.redis_type))
+                        .at(location),
+                );
+                continue;
+            }
+            if let Some(other) = shapes.insert(key.shape(), &key.pattern) {
+                diagnostics.push(
+                    Diagnostic::warning("overlapping-pattern", format!("Matches the same keys as `{}`", other))
+                        .at(location.clone()),
+                );
+            }
+            let base = match &key.name {
+                Some(name) => self.generator.naming.apply(name),
+                None => key.literals().iter().map(|part| self.generator.naming.apply(part)).collect(),
+            };
+            if base.is_empty() {
+                diagnostics.push(
+                    Diagnostic::error("unnamed-pattern", "The pattern has no literal parts to name it by; set `name`")
+                        .at(location),
+                );
+                continue;
+            }
+            if !names.insert(base.clone()) {
+                diagnostics.push(
+                    Diagnostic::warning("duplicate-name", format!("`{}` is already defined; skipped", base)).at(location),
+                );
+                continue;
+            }
+
+            let placeholders = key.placeholders();
+            if !placeholders.is_empty() {
+                types.push(TypeDefinition::Record(RecordDef {
+                    name: format!("{}Key", base),
+                    fields: placeholders.iter().map(|p| (field_name(p), named("string"))).collect(),
+                }));
+            }
+
+            let fields: Vec<(String, TypeExpr)> = key
+                .fields
+                .iter()
+                .map(|(name, ty)| {
+                    let ty = match ty.strip_suffix('?') {
+                        Some(ty) => TypeShape::parse(ty).option(),
+                        None => TypeShape::parse(ty),
+                    };
+                    (field_name(name), ty.into())
+                })
+                .collect();
+            let scalar = key.value.is_some() || key.member.is_some();
+            let (suffix, fields, ignored) = match key.redis_type.as_str() {
+                "hash" => ("Hash", fields, scalar),
+                "string" | "json" | "list" | "set" if fields.is_empty() => ("", Vec::new(), key.member.is_some()),
+                "string" | "json" => ("Value", fields, scalar),
+                "list" | "set" => ("Item", fields, scalar),
+                "zset" => {
+                    let member = key.member.as_deref().or(key.value.as_deref()).unwrap_or("string");
+                    let entry = vec![("member".to_string(), named(member)), ("score".to_string(), named("float"))];
+                    let both = key.member.is_some() && key.value.is_some();
+                    ("Entry", entry, !key.fields.is_empty() || both)
+                }
+                _ => {
+                    let mut entry = vec![("id".to_string(), named("string"))];
+                    entry.extend(fields);
+                    ("Entry", entry, scalar)
+                }
+            };
+            if ignored {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        "ignored-setting",
+                        format!("Settings that don't apply to a `{}` key are ignored", key.redis_type),
+                    )
+                    .at(key.pattern.clone()),
+                );
+            }
+            if !suffix.is_empty() {
+                types.push(TypeDefinition::Record(RecordDef { name: format!("{}{}", base, suffix), fields }));
+            }
+        }
+    }
+}
+
+/// Give untitled reply alternatives names for their variants:
+/// `{"const": "OK"}` → `Ok`, `{"type": "null"}` → `Nil`
+fn title_alternatives(schema: &mut Value) {
+    for key in ["oneOf", "anyOf"] {
+        let Some(alternatives) = schema.get_mut(key).and_then(Value::as_array_mut) else {
+            continue;
+        };
+        let mut titles = HashSet::new();
+        for (i, alternative) in alternatives.iter_mut().enumerate() {
+            if alternative.get("title").is_some() || !alternative.is_object() {
+                continue;
+            }
+            let title = match (alternative.get("const"), alternative.get("type").and_then(Value::as_str)) {
+                (Some(Value::String(value)), _) => value.to_ascii_lowercase(),
+                (Some(value), _) => format!("value {}", value),
+                (None, Some("null")) => "nil".to_string(),
+                (None, Some("number")) => "double".to_string(),
+                (None, Some("object")) => "map".to_string(),
+                (None, Some(ty)) => ty.to_string(),
+                (None, None) => format!("variant {}", i),
+            };
+            let title = if titles.insert(title.clone()) { title } else { format!("{} {}", title, i) };
+            alternative["title"] = json!(title);
+        }
+    }
+}
+
+/// `unix-time-seconds` → `unix_time_seconds`
+fn field_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect()
+}
+
+fn matches_any(patterns: &[&str], name: &str) -> bool {
+    patterns.iter().any(|pattern| match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(&prefix.to_ascii_uppercase()),
+        None => name.eq_ignore_ascii_case(pattern),
+    })
+}
+
+impl Default for RedisProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for RedisProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("RedisProvider")
+            .param(
+                ParamSpec::new("commands", ParamKind::List)
+                    .describe("Commands to generate; a trailing `*` matches a prefix"),
+            )
+            .param(ParamSpec::new("groups", ParamKind::List).describe("Command groups to generate"))
+            .param(
+                ParamSpec::new("keys", ParamKind::String)
+                    .describe("Key pattern declaration to generate along with command specs, inline or a path"),
+            )
+    }
+}
+
+impl TypeProvider for RedisProvider {
+    fn name(&self) -> &str {
+        "RedisProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let read = |source: &str| -> ProviderResult<String> {
+            if source.trim_start().starts_with(['{', '[']) || source.contains('\n') {
+                Ok(source.to_string())
+            } else {
+                read_source(source, params)
+            }
+        };
+        let content = read(source)?;
+        let is_key_declaration = serde_yaml::from_str::<serde_yaml::Value>(&content)
+            .is_ok_and(|document| document.get("keys").is_some_and(serde_yaml::Value::is_mapping));
+
+        let mut resolved = Resolved::default();
+        if is_key_declaration {
+            resolved.keys = parse_keys(&content)?;
+        } else {
+            resolved.commands = parse_commands(&content)?;
+        }
+        if let Some(keys) = typed.str("keys") {
+            resolved.keys.extend(parse_keys(&read(keys)?)?);
+        }
+
+        if let Some(patterns) = typed.list("commands") {
+            resolved.commands.retain(|command| matches_any(&patterns, &command.name));
+        }
+        if let Some(groups) = typed.list("groups") {
+            resolved.commands.retain(|command| command.group.as_deref().is_some_and(|g| groups.contains(&g)));
+        }
+
+        let json = serde_json::to_string(&resolved)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected Redis commands or keys".to_string()));
+        };
+
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+        module.types = self.generate(&self.parse_schema(json)?).0;
+
+        let mut result = GeneratedTypes::new();
+        result.modules.push(module);
+        Ok(result)
+    }
+}
+
+impl SourceValidator for RedisProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected Redis commands or keys".to_string()));
+        };
+
+        Ok(self.generate(&self.parse_schema(json)?).1)
+    }
+}
+
+impl DescribedProvider for RedisProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    const COMMANDS: &str = r#"{
+        "SET": {"group": "string", "arguments": [
+            {"name": "key", "type": "key"},
+            {"name": "value", "type": "string"},
+            {"name": "condition", "type": "oneof", "optional": true, "arguments": [
+                {"name": "nx", "type": "pure-token", "token": "NX"},
+                {"name": "xx", "type": "pure-token", "token": "XX"}]},
+            {"name": "get", "type": "pure-token", "token": "GET", "optional": true},
+            {"name": "expiration", "type": "oneof", "optional": true, "arguments": [
+                {"name": "seconds", "type": "integer", "token": "EX"},
+                {"name": "unix-time-seconds", "type": "unix-time", "token": "EXAT"},
+                {"name": "keepttl", "type": "pure-token", "token": "KEEPTTL"}]}],
+          "reply_schema": {"anyOf": [
+            {"description": "The key was set", "const": "OK"},
+            {"description": "The key was not set", "type": "null"},
+            {"description": "The old value", "type": "string"}]}},
+        "MSET": {"group": "string", "arguments": [
+            {"name": "data", "type": "block", "multiple": true, "arguments": [
+                {"name": "key", "type": "key"}, {"name": "value", "type": "string"}]}],
+          "reply_schema": {"const": "OK"}},
+        "GET": {"container": "CONFIG", "group": "server", "arguments": [
+            {"name": "parameter", "type": "string", "multiple": true}],
+          "reply_schema": {"type": "object", "additionalProperties": {"type": "string"}}},
+        "GETSET": {"group": "string", "deprecated_since": "6.2.0", "arguments": [
+            {"name": "key", "type": "key"}, {"name": "value", "type": "bulk"}]}
+    }"#;
+
+    const KEYS: &str = r#"
+keys:
+  "user:{id}":
+    type: hash
+    fields: {name: string, age: "int?"}
+  "session:{token}": {type: string, value: string}
+  "cart:{user_id}":
+    type: string
+    fields: {items: "string list", total: float}
+  "leaderboard:{game}": {type: zset, member: string, value: int}
+  "events:{source}":
+    type: stream
+    fields: {kind: string}
+  "usr:{name}": {type: hash, name: User}
+  "user:{email}": {type: bitmap}
+"#;
+
+    fn fields(types: &GeneratedTypes, name: &str) -> Vec<(String, String)> {
+        types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+            .fields
+            .iter()
+            .map(|(field, ty)| (field.clone(), ty.to_string()))
+            .collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(f, t)| (f.to_string(), t.to_string())).collect()
+    }
+
+    fn variants(types: &GeneratedTypes, name: &str) -> Vec<String> {
+        types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Du(du) if du.name == name => Some(du),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected union {}", name))
+            .variants
+            .iter()
+            .map(|v| v.name.clone())
+            .collect()
+    }
+
+    fn diagnostics(provider: &RedisProvider, schema: &Schema) -> Vec<(String, String)> {
+        provider
+            .schema_diagnostics(schema)
+            .unwrap()
+            .into_iter()
+            .map(|d| (d.code, d.location.unwrap_or_default()))
+            .collect()
+    }
+
+    #[test]
+    fn test_command_types() {
+        let provider = RedisProvider::new();
+        let schema = provider.resolve_schema(COMMANDS, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Redis").unwrap();
+
+        assert_eq!(
+            fields(&types, "SetArgs"),
+            pairs(&[
+                ("key", "string"),
+                ("value", "string"),
+                ("condition", "SetCondition option"),
+                ("get", "bool"),
+                ("expiration", "SetExpiration option"),
+            ])
+        );
+        assert_eq!(variants(&types, "SetCondition"), ["Nx", "Xx"]);
+        assert_eq!(variants(&types, "SetExpiration"), ["Seconds", "UnixTimeSeconds", "Keepttl"]);
+        assert_eq!(variants(&types, "SetReply"), ["Ok", "Nil", "String"]);
+        assert_eq!(fields(&types, "MsetArgs"), pairs(&[("data", "MsetData list")]));
+        assert_eq!(fields(&types, "MsetData"), pairs(&[("key", "string"), ("value", "string")]));
+        assert_eq!(fields(&types, "ConfigGetArgs"), pairs(&[("parameter", "string list")]));
+        assert_eq!(variants(&types, "Command"), ["ConfigGet", "Getset", "Mset", "Set"]);
+
+        assert_eq!(
+            diagnostics(&provider, &schema),
+            pairs(&[("deprecated", "GETSET"), ("unknown-argument-type", "GETSET")])
+        );
+
+        let params = ProviderParams::default().with("groups", "string").with("commands", "m*, set");
+        let schema = provider.resolve_schema(COMMANDS, &params).unwrap();
+        let types = provider.generate_types(&schema, "Redis").unwrap();
+        assert_eq!(variants(&types, "Command"), ["Mset", "Set"]);
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/redis.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_key_types() {
+        let provider = RedisProvider::new();
+        let schema = provider.resolve_schema(KEYS, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Redis").unwrap();
+
+        assert_eq!(fields(&types, "UserKey"), pairs(&[("id", "string")]));
+        assert_eq!(fields(&types, "UserHash"), pairs(&[("name", "string"), ("age", "int option")]));
+        assert_eq!(fields(&types, "SessionKey"), pairs(&[("token", "string")]));
+        assert_eq!(fields(&types, "CartValue"), pairs(&[("items", "string list"), ("total", "float")]));
+        assert_eq!(fields(&types, "LeaderboardEntry"), pairs(&[("member", "string"), ("score", "float")]));
+        assert_eq!(fields(&types, "EventsEntry"), pairs(&[("id", "string"), ("kind", "string")]));
+
+        assert_eq!(
+            diagnostics(&provider, &schema),
+            pairs(&[
+                ("ignored-setting", "leaderboard:{game}"),
+                ("duplicate-name", "usr:{name}"),
+                ("unknown-redis-type", "user:{email}"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_keys_param_and_file_source() {
+        let path = std::env::temp_dir().join(format!("fusabi-redis-{}.json", std::process::id()));
+        std::fs::write(&path, COMMANDS).unwrap();
+
+        let provider = RedisProvider::new();
+        let params = ProviderParams::default().with("commands", "GET*").with("keys", KEYS);
+        let schema = provider.resolve_schema(path.to_str().unwrap(), &params).unwrap();
+        let types = provider.generate_types(&schema, "Redis").unwrap();
+        assert_eq!(variants(&types, "Command"), ["Getset"]);
+        assert_eq!(fields(&types, "UserHash").len(), 2);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -22,6 +22,9 @@
 //! - Validates regex syntax at compile time
 //! - All captured values are typed as strings
 
+use fusabi_provider_common::{
+    Capabilities, DescribedProvider, SourceKind, SourceValidator, TypeShape,
+};
 use fusabi_type_providers::{
     TypeProvider, ProviderParams, Schema,
     GeneratedTypes, TypeGenerator, NamingStrategy,
@@ -135,10 +138,10 @@ impl RegexProvider {
         let fields: Vec<(String, TypeExpr)> = pattern.named_groups.iter()
             .map(|(name, is_optional)| {
                 // Keep field names as-is from the regex pattern
-                let type_expr = if *is_optional {
-                    TypeExpr::Named("string option".to_string())
+                let type_expr: TypeExpr = if *is_optional {
+                    TypeShape::named("string").option().into()
                 } else {
-                    TypeExpr::Named("string".to_string())
+                    TypeShape::named("string").into()
                 };
                 (name.clone(), type_expr)
             })
@@ -185,9 +188,19 @@ impl TypeProvider for RegexProvider {
     }
 }
 
+impl SourceValidator for RegexProvider {}
+
+impl DescribedProvider for RegexProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .source(SourceKind::Inline)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fusabi_provider_common::emit::golden;
 
     #[test]
     fn test_parse_simple_date_pattern() {
@@ -219,6 +232,11 @@ mod tests {
         } else {
             panic!("Expected Record type definition");
         }
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/regex.fsx"),
+        );
     }
 
     #[test]
@@ -0,0 +1,277 @@
+//! Runtime registry of Fusabi type providers
+//!
+//! Tools that pick a provider from configuration, such as a manifest entry
+//! that says `provider = "sql"`, resolve it by name here instead of linking
+//! and matching on every provider crate themselves.
+//!
+//! Each community provider is behind a feature flag named after its crate
+//! suffix (`sql`, `protobuf`, `json-schema`, ...). The default `all` feature
+//! enables every one of them; build with `default-features = false` and a
+//! list of features to ship a smaller binary.
+//!
+//! [`community_capabilities`] tells tools which sources, params and output
+//! features each of them supports without trying it.
+//!
+//! With the `wasm` feature, third-party providers compiled to WebAssembly
+//! are loaded from the `[[plugins]]` entries of the registry index through
+//! [`ProviderRegistry::load_plugins`]; see [`plugins`].
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_registry::ProviderRegistry;
+//! use fusabi_type_providers::ProviderParams;
+//!
+//! let mut registry = ProviderRegistry::community();
+//! registry.register("in-house", Box::new(InHouseProvider::new()));
+//!
+//! let provider = registry.get("sql").expect("sql provider is enabled");
+//! let schema = provider.resolve_schema("schema.sql", &ProviderParams::default())?;
+//! let types = provider.generate_types(&schema, "Database")?;
+//! ```
+
+#[cfg(feature = "wasm")]
+pub mod plugins;
+
+#[cfg(feature = "wasm")]
+pub use plugins::PluginEntry;
+
+use fusabi_provider_common::{Capabilities, DescribedProvider};
+use fusabi_type_providers::TypeProvider;
+use std::collections::BTreeMap;
+
+/// Declares the community providers, each gated on the feature of the same name
+macro_rules! community_providers {
+    ($($name:literal => $provider:path;)*) => {
+        /// Names of the community providers enabled in this build, sorted
+        pub fn community_provider_names() -> &'static [&'static str] {
+            &[$(#[cfg(feature = $name)] $name,)*]
+        }
+
+        /// Capabilities of the community providers enabled in this build, by
+        /// name
+        pub fn community_capabilities() -> BTreeMap<&'static str, Capabilities> {
+            [$(#[cfg(feature = $name)] ($name, <$provider>::new().capabilities()),)*]
+                .into_iter()
+                .collect()
+        }
+
+        fn register_community_providers(registry: &mut ProviderRegistry) {
+            $(
+                #[cfg(feature = $name)]
+                registry.register($name, Box::new(<$provider>::new()));
+            )*
+        }
+    };
+}
+
+community_providers! {
+    "ansible" => fusabi_provider_ansible::AnsibleProvider;
+    "arrow" => fusabi_provider_arrow::ArrowProvider;
+    "asn1" => fusabi_provider_asn1::Asn1Provider;
+    "avro" => fusabi_provider_avro::AvroProvider;
+    "azure" => fusabi_provider_azure::AzureProvider;
+    "bigquery" => fusabi_provider_bigquery::BigQueryProvider;
+    "cddl" => fusabi_provider_cddl::CddlProvider;
+    "cef-leef" => fusabi_provider_cef_leef::CefLeefProvider;
+    "clickhouse" => fusabi_provider_clickhouse::ClickHouseProvider;
+    "cloudevents" => fusabi_provider_cloudevents::CloudEventsProvider;
+    "cloudformation" => fusabi_provider_cloudformation::CloudFormationProvider;
+    "compose" => fusabi_provider_compose::ComposeProvider;
+    "cql" => fusabi_provider_cql::CqlProvider;
+    "csv" => fusabi_provider_csv::CsvProvider;
+    "dynamodb" => fusabi_provider_dynamodb::DynamoDbProvider;
+    "ecs" => fusabi_provider_ecs::EcsProvider;
+    "env-config" => fusabi_provider_env_config::EnvConfigProvider;
+    "falco" => fusabi_provider_falco::FalcoProvider;
+    "fhir" => fusabi_provider_fhir::FhirProvider;
+    "github-actions" => fusabi_provider_github_actions::GitHubActionsProvider;
+    "graphql" => fusabi_provider_graphql::GraphQLProvider;
+    "har" => fusabi_provider_har::HarProvider;
+    "hibana-pipeline" => fusabi_provider_hibana_pipeline::HibanaPipelineProvider;
+    "hibana-sinks" => fusabi_provider_hibana_sinks::HibanaSinksProvider;
+    "hibana-sources" => fusabi_provider_hibana_sources::HibanaSourcesProvider;
+    "jmx" => fusabi_provider_jmx::JmxProvider;
+    "json-schema" => fusabi_provider_json_schema::JsonSchemaProvider;
+    "kubernetes" => fusabi_provider_kubernetes::KubernetesProvider;
+    "lakehouse" => fusabi_provider_lakehouse::LakehouseProvider;
+    "llm-tools" => fusabi_provider_llm_tools::LlmToolsProvider;
+    "mcp" => fusabi_provider_mcp::McpProvider;
+    "mongodb" => fusabi_provider_mongodb::MongoProvider;
+    "obi" => fusabi_provider_obi::ObiProvider;
+    "ocsf" => fusabi_provider_ocsf::OcsfProvider;
+    "openapi" => fusabi_provider_openapi::OpenApiProvider;
+    "openfeature" => fusabi_provider_openfeature::OpenFeatureProvider;
+    "openrpc" => fusabi_provider_openrpc::OpenRpcProvider;
+    "opentelemetry" => fusabi_provider_opentelemetry::OpenTelemetryProvider;
+    "otel-collector" => fusabi_provider_otel_collector::OtelCollectorProvider;
+    "postman" => fusabi_provider_postman::PostmanProvider;
+    "prometheus" => fusabi_provider_prometheus::PrometheusProvider;
+    "protobuf" => fusabi_provider_protobuf::ProtobufProvider;
+    "redis" => fusabi_provider_redis::RedisProvider;
+    "regex" => fusabi_provider_regex::RegexProvider;
+    "sarif" => fusabi_provider_sarif::SarifProvider;
+    "sbom" => fusabi_provider_sbom::SbomProvider;
+    "schema-registry" => fusabi_provider_schema_registry::SchemaRegistryProvider;
+    "snowflake" => fusabi_provider_snowflake::SnowflakeProvider;
+    "sparkplug" => fusabi_provider_sparkplug::SparkplugProvider;
+    "sql" => fusabi_provider_sql::SqlProvider;
+    "syslog" => fusabi_provider_syslog::SyslogProvider;
+    "terraform" => fusabi_provider_terraform::TerraformProvider;
+    "terraform-schema" => fusabi_provider_terraform_schema::TerraformSchemaProvider;
+    "thrift" => fusabi_provider_thrift::ThriftProvider;
+    "toml" => fusabi_provider_toml::TomlProvider;
+    "webhooks" => fusabi_provider_webhooks::WebhookProvider;
+    "wit" => fusabi_provider_wit::WitProvider;
+    "wsdl" => fusabi_provider_wsdl::WsdlProvider;
+}
+
+/// Type providers by name
+///
+/// Names are case-insensitive, and `_` matches `-`, so `json_schema` finds
+/// the `json-schema` provider.
+#[derive(Default)]
+pub struct ProviderRegistry {
+    providers: BTreeMap<String, Box<dyn TypeProvider>>,
+}
+
+impl ProviderRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry with every community provider enabled in this build
+    pub fn community() -> Self {
+        let mut registry = Self::new();
+        register_community_providers(&mut registry);
+        registry
+    }
+
+    /// Register a provider under `name`
+    ///
+    /// Returns the provider previously registered under that name, if any.
+    pub fn register(
+        &mut self,
+        name: &str,
+        provider: Box<dyn TypeProvider>,
+    ) -> Option<Box<dyn TypeProvider>> {
+        self.providers.insert(normalize(name), provider)
+    }
+
+    /// Remove the provider registered under `name`
+    pub fn unregister(&mut self, name: &str) -> Option<Box<dyn TypeProvider>> {
+        self.providers.remove(&normalize(name))
+    }
+
+    /// Look up a provider by name
+    pub fn get(&self, name: &str) -> Option<&dyn TypeProvider> {
+        self.providers.get(&normalize(name)).map(|p| p.as_ref())
+    }
+
+    /// Whether a provider is registered under `name`
+    pub fn contains(&self, name: &str) -> bool {
+        self.providers.contains_key(&normalize(name))
+    }
+
+    /// Registered names, sorted
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.providers.keys().map(String::as_str)
+    }
+
+    /// Number of registered providers
+    pub fn len(&self) -> usize {
+        self.providers.len()
+    }
+
+    /// Whether no providers are registered
+    pub fn is_empty(&self) -> bool {
+        self.providers.is_empty()
+    }
+}
+
+impl std::fmt::Debug for ProviderRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.names()).finish()
+    }
+}
+
+/// Canonical form of a provider name
+fn normalize(name: &str) -> String {
+    name.trim().to_ascii_lowercase().replace('_', "-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::{OutputFeature, SourceKind};
+    use fusabi_type_providers::{
+        GeneratedTypes, ProviderParams, ProviderResult, Schema,
+    };
+
+    struct EchoProvider;
+
+    impl TypeProvider for EchoProvider {
+        fn name(&self) -> &str {
+            "EchoProvider"
+        }
+
+        fn resolve_schema(&self, source: &str, _params: &ProviderParams) -> ProviderResult<Schema> {
+            Ok(Schema::Custom(source.to_string()))
+        }
+
+        fn generate_types(&self, _schema: &Schema, _namespace: &str) -> ProviderResult<GeneratedTypes> {
+            Ok(GeneratedTypes::new())
+        }
+    }
+
+    #[test]
+    fn test_community_lookup() {
+        let registry = ProviderRegistry::community();
+
+        assert_eq!(registry.len(), community_provider_names().len());
+        assert!(registry.names().eq(community_provider_names().iter().copied()));
+        assert_eq!(registry.get("sql").unwrap().name(), "SqlProvider");
+        assert_eq!(registry.get("Protobuf").unwrap().name(), "ProtobufProvider");
+        assert_eq!(registry.get("json_schema").unwrap().name(), "JsonSchemaProvider");
+        assert!(registry.get("cobol").is_none());
+
+        let provider = registry.get("mcp").unwrap();
+        let schema = provider.resolve_schema(r#"{"tools": []}"#, &ProviderParams::default());
+        assert!(provider.generate_types(&schema.unwrap(), "Mcp").is_ok());
+    }
+
+    #[test]
+    fn test_community_capabilities() {
+        let capabilities = community_capabilities();
+        assert!(capabilities.keys().copied().eq(community_provider_names().iter().copied()));
+        let registry = ProviderRegistry::community();
+        for (name, capabilities) in &capabilities {
+            assert_eq!(capabilities.provider(), registry.get(name).unwrap().name());
+            assert!(capabilities.sources().next().is_some(), "{} accepts no source", name);
+        }
+
+        let mongodb = &capabilities["mongodb"];
+        assert!(mongodb.supports(SourceKind::Live));
+        assert_eq!(mongodb.live_schemes(), ["mongodb://", "mongodb+srv://"]);
+        assert!(capabilities["kubernetes"].embedded_sources().contains(&"embedded"));
+        assert!(!capabilities["kubernetes"].supports(SourceKind::Inline));
+        assert!(capabilities["csv"].spec("delimiter").is_some());
+        assert!(capabilities["sql"].has_feature(OutputFeature::Docs));
+    }
+
+    #[test]
+    fn test_register_and_replace() {
+        let mut registry = ProviderRegistry::new();
+        assert!(registry.is_empty());
+
+        assert!(registry.register("echo", Box::new(EchoProvider)).is_none());
+        assert!(registry.contains("ECHO"));
+        let previous = registry.register("Echo", Box::new(EchoProvider));
+        assert_eq!(previous.unwrap().name(), "EchoProvider");
+        assert_eq!(registry.len(), 1);
+
+        assert!(registry.unregister("echo").is_some());
+        assert!(!registry.contains("echo"));
+    }
+}
@@ -0,0 +1,144 @@
+//! WebAssembly plugins listed in the registry index
+//!
+//! Third-party providers are published as `[[plugins]]` entries of the
+//! registry `index.toml`, next to the `[[packages]]` entries:
+//!
+//! ```toml
+//! [[plugins]]
+//! name = "in-house"
+//! version = "0.3.0"
+//! description = "Types for our in-house IDL"
+//! wasm = "plugins/in-house-0.3.0.wasm"
+//! sha256 = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+//! ```
+//!
+//! `wasm` is relative to the index file. `sha256` is required: plugins
+//! are executable, so a module is rejected unless the entry publishes a
+//! digest and the module matches it.
+//!
+//! Entries are parsed and their modules read and checked against `sha256`
+//! by [`fusabi_registry::RegistryIndex`]; this module registers them.
+
+use crate::ProviderRegistry;
+use fusabi_provider_common::StructuredError;
+use fusabi_provider_wasm::WasmProvider;
+use fusabi_registry::{RegistryError, RegistryIndex};
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use std::path::Path;
+
+pub use fusabi_registry::PluginEntry;
+
+impl ProviderRegistry {
+    /// Register every plugin listed in the registry index at `index_path`
+    ///
+    /// Every plugin is verified and loaded before any is registered, so
+    /// nothing is registered when one entry has no digest or a bad module.
+    /// Plugins replace providers already registered under the same name.
+    /// Returns the names of the registered plugins.
+    pub fn load_plugins(&mut self, index_path: impl AsRef<Path>) -> ProviderResult<Vec<String>> {
+        let index = RegistryIndex::load(index_path).map_err(registry_error)?;
+
+        let mut providers = Vec::new();
+        for entry in &index.plugins {
+            let bytes = index.read_plugin(entry).map_err(registry_error)?;
+            providers.push((entry.name.clone(), WasmProvider::from_bytes(&bytes)?));
+        }
+
+        let mut names = Vec::new();
+        for (name, provider) in providers {
+            self.register(&name, Box::new(provider));
+            names.push(name);
+        }
+        Ok(names)
+    }
+}
+
+fn registry_error(error: RegistryError) -> ProviderError {
+    let structured = match &error {
+        RegistryError::Io(message) => StructuredError::io(message.clone()),
+        RegistryError::Parse(_) | RegistryError::InvalidVersion { .. } => {
+            StructuredError::parse(error.to_string())
+        }
+        _ => StructuredError::validation(error.to_string()),
+    };
+    structured.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLUGIN: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (data (i32.const 0) "in-house")
+          (data (i32.const 16) "{\"types\":{}}")
+          (func (export "fusabi_abi_version") (result i32) (i32.const 1))
+          (func (export "fusabi_alloc") (param i32) (result i32) (i32.const 1024))
+          (func (export "fusabi_provider_name") (result i64) (i64.const 8))
+          (func (export "fusabi_generate") (param i32 i32) (result i64)
+            (i64.or (i64.shl (i64.const 16) (i64.const 32)) (i64.const 12))))
+    "#;
+
+    #[test]
+    fn test_invalid_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = dir.path().join("index.toml");
+
+        std::fs::write(&index, "[[plugins]]\nname = 1").unwrap();
+        let err = ProviderRegistry::new().load_plugins(&index).unwrap_err();
+        assert!(matches!(err, ProviderError::ParseError(_)));
+
+        let invalid_version = "[[plugins]]\nname = \"a\"\nversion = \"1.0\"\nwasm = \"a.wasm\"\n";
+        std::fs::write(&index, invalid_version).unwrap();
+        let err = ProviderRegistry::new().load_plugins(&index).unwrap_err();
+        assert!(matches!(err, ProviderError::ParseError(_)));
+
+        std::fs::write(&index, "[registry]\nversion = \"1.0.0\"\n").unwrap();
+        assert!(ProviderRegistry::new().load_plugins(&index).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_load_plugins_from_index() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("plugins")).unwrap();
+        std::fs::write(dir.path().join("plugins/in-house.wat"), PLUGIN).unwrap();
+        let digest = fusabi_registry::sha256_hex(PLUGIN.as_bytes());
+
+        let index = dir.path().join("index.toml");
+        std::fs::write(
+            &index,
+            format!(
+                "[[plugins]]\nname = \"in-house\"\nversion = \"0.3.0\"\nwasm = \"plugins/in-house.wat\"\nsha256 = \"{}\"\n",
+                digest
+            ),
+        )
+        .unwrap();
+
+        let mut registry = ProviderRegistry::new();
+        assert_eq!(registry.load_plugins(&index).unwrap(), vec!["in-house"]);
+        assert_eq!(registry.get("in_house").unwrap().name(), "in-house");
+
+        std::fs::write(
+            &index,
+            "[[plugins]]\nname = \"in-house\"\nversion = \"0.3.0\"\nwasm = \"plugins/in-house.wat\"\nsha256 = \"00\"\n",
+        )
+        .unwrap();
+        let err = ProviderRegistry::new().load_plugins(&index).unwrap_err();
+        assert!(err.to_string().contains("Checksum mismatch"));
+
+        std::fs::write(
+            &index,
+            format!(
+                "[[plugins]]\nname = \"in-house\"\nversion = \"0.3.0\"\nwasm = \"plugins/in-house.wat\"\nsha256 = \"{}\"\n\n\
+                 [[plugins]]\nname = \"unsigned\"\nversion = \"0.1.0\"\nwasm = \"plugins/in-house.wat\"\n",
+                digest
+            ),
+        )
+        .unwrap();
+        let mut registry = ProviderRegistry::new();
+        let err = registry.load_plugins(&index).unwrap_err();
+        assert!(err.to_string().contains("'unsigned' publishes no sha256 checksum"));
+        assert!(registry.get("in-house").is_none());
+    }
+}
@@ -0,0 +1,356 @@
+//! SARIF Type Provider
+//!
+//! Generates Fusabi types for the SARIF 2.1.0 object model, the format
+//! static analysis tools (CodeQL, Semgrep, ESLint, Trivy, ...) report
+//! results in, so Fusabi tooling can read, merge and aggregate their
+//! output with every property typed.
+//!
+//! # Sources
+//!
+//! - `sarif` or `2.1.0`: the object model
+//! - A SARIF log, inline or from a file, whose `version` must be `2.1.0`
+//!
+//! # Generated Types
+//!
+//! Every SARIF object becomes a record with the specification's property
+//! names (`SarifLog`, `Run`, `Tool`, `ToolComponent`, `ReportingDescriptor`,
+//! `Result`, `Location`, `PhysicalLocation`, `Region`, `CodeFlow`, `Fix`,
+//! ...); see [`model`]. Properties the schema requires are plain and the
+//! rest are `T option`. Each object's property bag is
+//! `properties: Map<string, any> option`. Fingerprints and other string
+//! dictionaries are `Map<string, string>`.
+//!
+//! Enumerated properties become unions: `Level`, `ResultKind`,
+//! `BaselineState`, `SuppressionKind`, `SuppressionStatus`, `Importance`
+//! and `ColumnKind`.
+//!
+//! # Params
+//!
+//! | Param  | Meaning                                                          |
+//! |--------|------------------------------------------------------------------|
+//! | `root` | Comma-separated objects to generate, with the types they use; defaults to `SarifLog` |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_sarif::SarifProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = SarifProvider::new();
+//! let params = ProviderParams::default().with("root", "Result");
+//! let schema = provider.resolve_schema("sarif", &params)?;
+//! let types = provider.generate_types(&schema, "Sarif")?;
+//! ```
+
+pub mod model;
+
+pub use model::{ENUMS, OBJECTS, SARIF_VERSION};
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceValidator, TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, DuDef, VariantDef, TypeExpr, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Default object to generate from
+pub const DEFAULT_ROOT: &str = "SarifLog";
+
+/// The objects to generate, stored in [`Schema::Custom`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Selection {
+    roots: Vec<String>,
+}
+
+/// SARIF type provider
+pub struct SarifProvider {
+    generator: TypeGenerator,
+}
+
+impl SarifProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<Selection> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected SARIF selection: {}", e)))
+    }
+
+    /// Check that a log is SARIF 2.1.0
+    fn check_log(&self, content: &str) -> ProviderResult<()> {
+        let log: Value = serde_json::from_str(content)
+            .map_err(|e| ProviderError::ParseError(format!("Invalid SARIF log: {}", e)))?;
+        match log.get("version").and_then(Value::as_str) {
+            Some(SARIF_VERSION) => Ok(()),
+            Some(version) => Err(ProviderError::InvalidSource(format!(
+                "SARIF {} is not supported; expected {}",
+                version, SARIF_VERSION
+            ))),
+            None => Err(ProviderError::ParseError("SARIF log has no `version`".to_string())),
+        }
+    }
+
+    /// Names of the objects and enumerations reachable from the roots, in
+    /// model order
+    fn reachable(&self, roots: &[String]) -> Vec<&'static str> {
+        let mut reached: Vec<&str> = Vec::new();
+        let mut pending: Vec<&str> = roots.iter().map(String::as_str).collect();
+        while let Some(name) = pending.pop() {
+            if reached.contains(&name) {
+                continue;
+            }
+            reached.push(name);
+            if let Some((_, fields)) = model::object(name) {
+                pending.extend(fields.iter().filter_map(|(_, field_type, _)| model::referenced_type(field_type)));
+            }
+        }
+
+        ENUMS
+            .iter()
+            .map(|(name, _)| *name)
+            .chain(OBJECTS.iter().map(|(name, _)| *name))
+            .filter(|name| reached.contains(name))
+            .collect()
+    }
+
+    fn definition(&self, name: &str) -> TypeDefinition {
+        if let Some((_, values)) = ENUMS.iter().find(|(e, _)| *e == name) {
+            return TypeDefinition::Du(DuDef {
+                name: name.to_string(),
+                variants: values
+                    .iter()
+                    .map(|value| VariantDef::new_simple(self.generator.naming.apply(value)))
+                    .collect(),
+            });
+        }
+
+        let (_, properties) = model::object(name).expect("reachable names are in the model");
+        let mut fields: Vec<(String, TypeExpr)> = properties
+            .iter()
+            .map(|(property, field_type, required)| {
+                (property.to_string(), TypeShape::parse(field_type).option_unless(*required).into())
+            })
+            .collect();
+        fields.push((
+            "properties".to_string(),
+            TypeShape::map(TypeShape::named("string"), TypeShape::named("any")).option().into(),
+        ));
+        TypeDefinition::Record(RecordDef {
+            name: name.to_string(),
+            fields,
+        })
+    }
+}
+
+impl Default for SarifProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for SarifProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("SarifProvider")
+            .param(
+                ParamSpec::new("root", ParamKind::List)
+                    .default("SarifLog")
+                    .describe("Objects to generate, with the types they use"),
+            )
+    }
+}
+
+impl TypeProvider for SarifProvider {
+    fn name(&self) -> &str {
+        "SarifProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let trimmed = source.trim();
+        match trimmed.to_ascii_lowercase().as_str() {
+            "sarif" | "sarif-2.1.0" | SARIF_VERSION => {}
+            _ if trimmed.starts_with('{') => self.check_log(trimmed)?,
+            _ => self.check_log(&read_source(source, params)?)?,
+        }
+
+        let roots: Vec<String> = typed
+            .list("root")
+            .unwrap_or_else(|| vec![DEFAULT_ROOT])
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        for root in &roots {
+            if model::object(root).is_none() && !ENUMS.iter().any(|(e, _)| e == root) {
+                return Err(ProviderError::InvalidSource(format!(
+                    "`{}` is not a SARIF {} object",
+                    root, SARIF_VERSION
+                )));
+            }
+        }
+
+        let json = serde_json::to_string(&Selection { roots })
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected SARIF selection".to_string()));
+        };
+
+        let selection = self.parse_schema(json)?;
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+        module.types = self
+            .reachable(&selection.roots)
+            .into_iter()
+            .map(|name| self.definition(name))
+            .collect();
+
+        let mut result = GeneratedTypes::new();
+        result.modules.push(module);
+        Ok(result)
+    }
+}
+
+impl SourceValidator for SarifProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected SARIF selection".to_string()));
+        };
+
+        self.parse_schema(json)?;
+        Ok(Vec::new())
+    }
+}
+
+impl DescribedProvider for SarifProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .embedded("sarif")
+            .embedded("2.1.0")
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    fn names(types: &GeneratedTypes) -> Vec<&str> {
+        types.modules[0]
+            .types
+            .iter()
+            .map(|t| match t {
+                TypeDefinition::Record(r) => r.name.as_str(),
+                TypeDefinition::Du(du) => du.name.as_str(),
+            })
+            .collect()
+    }
+
+    fn record<'a>(types: &'a GeneratedTypes, name: &str) -> &'a RecordDef {
+        types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+    }
+
+    fn field(record: &RecordDef, name: &str) -> String {
+        record
+            .fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, ty)| ty.to_string())
+            .unwrap_or_else(|| panic!("Expected field {}.{}", record.name, name))
+    }
+
+    #[test]
+    fn test_full_model() {
+        let provider = SarifProvider::new();
+        let schema = provider.resolve_schema("sarif", &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Sarif").unwrap();
+
+        assert_eq!(types.modules[0].types.len(), ENUMS.len() + OBJECTS.len());
+        let result = record(&types, "Result");
+        assert_eq!(field(result, "message"), "Message");
+        assert_eq!(field(result, "level"), "Level option");
+        assert_eq!(field(result, "partialFingerprints"), "Map<string, string> option");
+        assert_eq!(field(result, "properties"), "Map<string, any> option");
+        assert_eq!(field(record(&types, "Run"), "tool"), "Tool");
+
+        let kind = types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Du(du) if du.name == "ResultKind" => Some(du),
+                _ => None,
+            })
+            .unwrap();
+        let cases: Vec<&str> = kind.variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(cases, ["NotApplicable", "Pass", "Fail", "Review", "Open", "Informational"]);
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/sarif.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_root_selection() {
+        let provider = SarifProvider::new();
+        let params = ProviderParams::default().with("root", "Fix");
+        let schema = provider.resolve_schema("2.1.0", &params).unwrap();
+        let types = provider.generate_types(&schema, "Sarif").unwrap();
+        assert_eq!(
+            names(&types),
+            [
+                "MultiformatMessageString",
+                "Message",
+                "ArtifactLocation",
+                "Region",
+                "ArtifactContent",
+                "Fix",
+                "ArtifactChange",
+                "Replacement",
+            ]
+        );
+
+        let params = ProviderParams::default().with("root", "Finding");
+        assert!(provider.resolve_schema("sarif", &params).is_err());
+    }
+
+    #[test]
+    fn test_log_version() {
+        let provider = SarifProvider::new();
+        let log = r#"{"version": "2.1.0", "$schema": "https://json.schemastore.org/sarif-2.1.0.json", "runs": []}"#;
+        assert!(provider.resolve_schema(log, &ProviderParams::default()).is_ok());
+
+        let old = r#"{"version": "1.0.0", "runLogs": []}"#;
+        let err = provider.resolve_schema(old, &ProviderParams::default()).unwrap_err();
+        assert!(err.to_string().contains("SARIF 1.0.0 is not supported"));
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = SarifProvider::new();
+        let report = provider.validate_source("sarif", &ProviderParams::default());
+        assert!(report.is_valid());
+        assert!(!report.diagnostics.iter().any(|d| d.code == "unresolved-reference"));
+    }
+}
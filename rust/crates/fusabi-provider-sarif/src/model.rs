@@ -0,0 +1,540 @@
+//! The SARIF 2.1.0 object model
+//!
+//! Objects are listed as in the OASIS specification (§3), with their
+//! property names and Fusabi types. Field types are written the way they
+//! are generated: an object name, `T list`, or `Map<string, T>`. Every
+//! object also has a `properties` bag, added when generating.
+
+/// SARIF version this model describes
+pub const SARIF_VERSION: &str = "2.1.0";
+
+/// Whether a property is required by the schema
+pub const REQ: bool = true;
+/// An optional property
+pub const OPT: bool = false;
+
+/// A SARIF object: name, then `(property, type, required)`
+pub type ObjectDef = (&'static str, &'static [(&'static str, &'static str, bool)]);
+
+/// SARIF enumerations: name, then their values as they appear in logs
+pub const ENUMS: &[(&str, &[&str])] = &[
+    ("Level", &["none", "note", "warning", "error"]),
+    ("ResultKind", &["notApplicable", "pass", "fail", "review", "open", "informational"]),
+    ("BaselineState", &["new", "unchanged", "updated", "absent"]),
+    ("SuppressionKind", &["inSource", "external"]),
+    ("SuppressionStatus", &["accepted", "underReview", "rejected"]),
+    ("Importance", &["important", "essential", "unimportant"]),
+    ("ColumnKind", &["utf16CodeUnits", "unicodeCodePoints"]),
+];
+
+pub const OBJECTS: &[ObjectDef] = &[
+    ("SarifLog", &[
+        ("version", "string", REQ),
+        ("$schema", "string", OPT),
+        ("runs", "Run list", REQ),
+        ("inlineExternalProperties", "ExternalProperties list", OPT),
+    ]),
+    ("Run", &[
+        ("tool", "Tool", REQ),
+        ("invocations", "Invocation list", OPT),
+        ("conversion", "Conversion", OPT),
+        ("language", "string", OPT),
+        ("versionControlProvenance", "VersionControlDetails list", OPT),
+        ("originalUriBaseIds", "Map<string, ArtifactLocation>", OPT),
+        ("artifacts", "Artifact list", OPT),
+        ("logicalLocations", "LogicalLocation list", OPT),
+        ("graphs", "Graph list", OPT),
+        ("results", "Result list", OPT),
+        ("automationDetails", "RunAutomationDetails", OPT),
+        ("runAggregates", "RunAutomationDetails list", OPT),
+        ("baselineGuid", "string", OPT),
+        ("redactionTokens", "string list", OPT),
+        ("defaultEncoding", "string", OPT),
+        ("defaultSourceLanguage", "string", OPT),
+        ("newlineSequences", "string list", OPT),
+        ("columnKind", "ColumnKind", OPT),
+        ("externalPropertyFileReferences", "ExternalPropertyFileReferences", OPT),
+        ("threadFlowLocations", "ThreadFlowLocation list", OPT),
+        ("taxonomies", "ToolComponent list", OPT),
+        ("addresses", "Address list", OPT),
+        ("translations", "ToolComponent list", OPT),
+        ("policies", "ToolComponent list", OPT),
+        ("webRequests", "WebRequest list", OPT),
+        ("webResponses", "WebResponse list", OPT),
+        ("specialLocations", "SpecialLocations", OPT),
+    ]),
+    ("Tool", &[
+        ("driver", "ToolComponent", REQ),
+        ("extensions", "ToolComponent list", OPT),
+    ]),
+    ("ToolComponent", &[
+        ("guid", "string", OPT),
+        ("name", "string", REQ),
+        ("organization", "string", OPT),
+        ("product", "string", OPT),
+        ("productSuite", "string", OPT),
+        ("shortDescription", "MultiformatMessageString", OPT),
+        ("fullDescription", "MultiformatMessageString", OPT),
+        ("fullName", "string", OPT),
+        ("version", "string", OPT),
+        ("semanticVersion", "string", OPT),
+        ("dottedQuadFileVersion", "string", OPT),
+        ("releaseDateUtc", "string", OPT),
+        ("downloadUri", "string", OPT),
+        ("informationUri", "string", OPT),
+        ("globalMessageStrings", "Map<string, MultiformatMessageString>", OPT),
+        ("notifications", "ReportingDescriptor list", OPT),
+        ("rules", "ReportingDescriptor list", OPT),
+        ("taxa", "ReportingDescriptor list", OPT),
+        ("locations", "ArtifactLocation list", OPT),
+        ("language", "string", OPT),
+        ("contents", "string list", OPT),
+        ("isComprehensive", "bool", OPT),
+        ("localizedDataSemanticVersion", "string", OPT),
+        ("minimumRequiredLocalizedDataSemanticVersion", "string", OPT),
+        ("associatedComponent", "ToolComponentReference", OPT),
+        ("translationMetadata", "TranslationMetadata", OPT),
+        ("supportedTaxonomies", "ToolComponentReference list", OPT),
+    ]),
+    ("ToolComponentReference", &[
+        ("name", "string", OPT),
+        ("index", "int", OPT),
+        ("guid", "string", OPT),
+    ]),
+    ("TranslationMetadata", &[
+        ("name", "string", REQ),
+        ("fullName", "string", OPT),
+        ("shortDescription", "MultiformatMessageString", OPT),
+        ("fullDescription", "MultiformatMessageString", OPT),
+        ("downloadUri", "string", OPT),
+        ("informationUri", "string", OPT),
+    ]),
+    ("ReportingDescriptor", &[
+        ("id", "string", REQ),
+        ("deprecatedIds", "string list", OPT),
+        ("guid", "string", OPT),
+        ("deprecatedGuids", "string list", OPT),
+        ("name", "string", OPT),
+        ("deprecatedNames", "string list", OPT),
+        ("shortDescription", "MultiformatMessageString", OPT),
+        ("fullDescription", "MultiformatMessageString", OPT),
+        ("messageStrings", "Map<string, MultiformatMessageString>", OPT),
+        ("defaultConfiguration", "ReportingConfiguration", OPT),
+        ("helpUri", "string", OPT),
+        ("help", "MultiformatMessageString", OPT),
+        ("relationships", "ReportingDescriptorRelationship list", OPT),
+    ]),
+    ("ReportingConfiguration", &[
+        ("enabled", "bool", OPT),
+        ("level", "Level", OPT),
+        ("rank", "float", OPT),
+        ("parameters", "Map<string, any>", OPT),
+    ]),
+    ("ReportingDescriptorReference", &[
+        ("id", "string", OPT),
+        ("index", "int", OPT),
+        ("guid", "string", OPT),
+        ("toolComponent", "ToolComponentReference", OPT),
+    ]),
+    ("ReportingDescriptorRelationship", &[
+        ("target", "ReportingDescriptorReference", REQ),
+        ("kinds", "string list", OPT),
+        ("description", "Message", OPT),
+    ]),
+    ("MultiformatMessageString", &[
+        ("text", "string", REQ),
+        ("markdown", "string", OPT),
+    ]),
+    ("Message", &[
+        ("text", "string", OPT),
+        ("markdown", "string", OPT),
+        ("id", "string", OPT),
+        ("arguments", "string list", OPT),
+    ]),
+    ("Result", &[
+        ("ruleId", "string", OPT),
+        ("ruleIndex", "int", OPT),
+        ("rule", "ReportingDescriptorReference", OPT),
+        ("kind", "ResultKind", OPT),
+        ("level", "Level", OPT),
+        ("message", "Message", REQ),
+        ("analysisTarget", "ArtifactLocation", OPT),
+        ("locations", "Location list", OPT),
+        ("guid", "string", OPT),
+        ("correlationGuid", "string", OPT),
+        ("occurrenceCount", "int", OPT),
+        ("partialFingerprints", "Map<string, string>", OPT),
+        ("fingerprints", "Map<string, string>", OPT),
+        ("stacks", "Stack list", OPT),
+        ("codeFlows", "CodeFlow list", OPT),
+        ("graphs", "Graph list", OPT),
+        ("graphTraversals", "GraphTraversal list", OPT),
+        ("relatedLocations", "Location list", OPT),
+        ("suppressions", "Suppression list", OPT),
+        ("baselineState", "BaselineState", OPT),
+        ("rank", "float", OPT),
+        ("attachments", "Attachment list", OPT),
+        ("hostedViewerUri", "string", OPT),
+        ("workItemUris", "string list", OPT),
+        ("provenance", "ResultProvenance", OPT),
+        ("fixes", "Fix list", OPT),
+        ("taxa", "ReportingDescriptorReference list", OPT),
+        ("webRequest", "WebRequest", OPT),
+        ("webResponse", "WebResponse", OPT),
+    ]),
+    ("Location", &[
+        ("id", "int", OPT),
+        ("physicalLocation", "PhysicalLocation", OPT),
+        ("logicalLocations", "LogicalLocation list", OPT),
+        ("message", "Message", OPT),
+        ("annotations", "Region list", OPT),
+        ("relationships", "LocationRelationship list", OPT),
+    ]),
+    ("PhysicalLocation", &[
+        ("address", "Address", OPT),
+        ("artifactLocation", "ArtifactLocation", OPT),
+        ("region", "Region", OPT),
+        ("contextRegion", "Region", OPT),
+    ]),
+    ("ArtifactLocation", &[
+        ("uri", "string", OPT),
+        ("uriBaseId", "string", OPT),
+        ("index", "int", OPT),
+        ("description", "Message", OPT),
+    ]),
+    ("Region", &[
+        ("startLine", "int", OPT),
+        ("startColumn", "int", OPT),
+        ("endLine", "int", OPT),
+        ("endColumn", "int", OPT),
+        ("charOffset", "int", OPT),
+        ("charLength", "int", OPT),
+        ("byteOffset", "int", OPT),
+        ("byteLength", "int", OPT),
+        ("snippet", "ArtifactContent", OPT),
+        ("message", "Message", OPT),
+        ("sourceLanguage", "string", OPT),
+    ]),
+    ("ArtifactContent", &[
+        ("text", "string", OPT),
+        ("binary", "string", OPT),
+        ("rendered", "MultiformatMessageString", OPT),
+    ]),
+    ("LogicalLocation", &[
+        ("name", "string", OPT),
+        ("index", "int", OPT),
+        ("fullyQualifiedName", "string", OPT),
+        ("decoratedName", "string", OPT),
+        ("parentIndex", "int", OPT),
+        ("kind", "string", OPT),
+    ]),
+    ("LocationRelationship", &[
+        ("target", "int", REQ),
+        ("kinds", "string list", OPT),
+        ("description", "Message", OPT),
+    ]),
+    ("Address", &[
+        ("absoluteAddress", "int64", OPT),
+        ("relativeAddress", "int64", OPT),
+        ("length", "int64", OPT),
+        ("kind", "string", OPT),
+        ("name", "string", OPT),
+        ("fullyQualifiedName", "string", OPT),
+        ("offsetFromParent", "int64", OPT),
+        ("index", "int", OPT),
+        ("parentIndex", "int", OPT),
+    ]),
+    ("Artifact", &[
+        ("description", "Message", OPT),
+        ("location", "ArtifactLocation", OPT),
+        ("parentIndex", "int", OPT),
+        ("offset", "int", OPT),
+        ("length", "int64", OPT),
+        ("roles", "string list", OPT),
+        ("mimeType", "string", OPT),
+        ("contents", "ArtifactContent", OPT),
+        ("encoding", "string", OPT),
+        ("sourceLanguage", "string", OPT),
+        ("hashes", "Map<string, string>", OPT),
+        ("lastModifiedTimeUtc", "string", OPT),
+    ]),
+    ("CodeFlow", &[
+        ("message", "Message", OPT),
+        ("threadFlows", "ThreadFlow list", REQ),
+    ]),
+    ("ThreadFlow", &[
+        ("id", "string", OPT),
+        ("message", "Message", OPT),
+        ("initialState", "Map<string, MultiformatMessageString>", OPT),
+        ("immutableState", "Map<string, MultiformatMessageString>", OPT),
+        ("locations", "ThreadFlowLocation list", REQ),
+    ]),
+    ("ThreadFlowLocation", &[
+        ("index", "int", OPT),
+        ("location", "Location", OPT),
+        ("stack", "Stack", OPT),
+        ("kinds", "string list", OPT),
+        ("taxa", "ReportingDescriptorReference list", OPT),
+        ("module", "string", OPT),
+        ("state", "Map<string, MultiformatMessageString>", OPT),
+        ("nestingLevel", "int", OPT),
+        ("executionOrder", "int", OPT),
+        ("executionTimeUtc", "string", OPT),
+        ("importance", "Importance", OPT),
+        ("webRequest", "WebRequest", OPT),
+        ("webResponse", "WebResponse", OPT),
+    ]),
+    ("Stack", &[
+        ("message", "Message", OPT),
+        ("frames", "StackFrame list", REQ),
+    ]),
+    ("StackFrame", &[
+        ("location", "Location", OPT),
+        ("module", "string", OPT),
+        ("threadId", "int", OPT),
+        ("parameters", "string list", OPT),
+    ]),
+    ("Graph", &[
+        ("description", "Message", OPT),
+        ("nodes", "Node list", OPT),
+        ("edges", "Edge list", OPT),
+    ]),
+    ("Node", &[
+        ("id", "string", REQ),
+        ("label", "Message", OPT),
+        ("location", "Location", OPT),
+        ("children", "Node list", OPT),
+    ]),
+    ("Edge", &[
+        ("id", "string", REQ),
+        ("label", "Message", OPT),
+        ("sourceNodeId", "string", REQ),
+        ("targetNodeId", "string", REQ),
+    ]),
+    ("GraphTraversal", &[
+        ("runGraphIndex", "int", OPT),
+        ("resultGraphIndex", "int", OPT),
+        ("description", "Message", OPT),
+        ("initialState", "Map<string, MultiformatMessageString>", OPT),
+        ("immutableState", "Map<string, MultiformatMessageString>", OPT),
+        ("edgeTraversals", "EdgeTraversal list", OPT),
+    ]),
+    ("EdgeTraversal", &[
+        ("edgeId", "string", REQ),
+        ("message", "Message", OPT),
+        ("finalState", "Map<string, MultiformatMessageString>", OPT),
+        ("stepOverEdgeCount", "int", OPT),
+    ]),
+    ("Suppression", &[
+        ("guid", "string", OPT),
+        ("kind", "SuppressionKind", REQ),
+        ("status", "SuppressionStatus", OPT),
+        ("justification", "string", OPT),
+        ("location", "Location", OPT),
+    ]),
+    ("Attachment", &[
+        ("description", "Message", OPT),
+        ("artifactLocation", "ArtifactLocation", REQ),
+        ("regions", "Region list", OPT),
+        ("rectangles", "Rectangle list", OPT),
+    ]),
+    ("Rectangle", &[
+        ("top", "float", OPT),
+        ("left", "float", OPT),
+        ("bottom", "float", OPT),
+        ("right", "float", OPT),
+        ("message", "Message", OPT),
+    ]),
+    ("ResultProvenance", &[
+        ("firstDetectionTimeUtc", "string", OPT),
+        ("lastDetectionTimeUtc", "string", OPT),
+        ("firstDetectionRunGuid", "string", OPT),
+        ("lastDetectionRunGuid", "string", OPT),
+        ("invocationIndex", "int", OPT),
+        ("conversionSources", "PhysicalLocation list", OPT),
+    ]),
+    ("Fix", &[
+        ("description", "Message", OPT),
+        ("artifactChanges", "ArtifactChange list", REQ),
+    ]),
+    ("ArtifactChange", &[
+        ("artifactLocation", "ArtifactLocation", REQ),
+        ("replacements", "Replacement list", REQ),
+    ]),
+    ("Replacement", &[
+        ("deletedRegion", "Region", REQ),
+        ("insertedContent", "ArtifactContent", OPT),
+    ]),
+    ("Invocation", &[
+        ("commandLine", "string", OPT),
+        ("arguments", "string list", OPT),
+        ("responseFiles", "ArtifactLocation list", OPT),
+        ("startTimeUtc", "string", OPT),
+        ("endTimeUtc", "string", OPT),
+        ("exitCode", "int", OPT),
+        ("ruleConfigurationOverrides", "ConfigurationOverride list", OPT),
+        ("notificationConfigurationOverrides", "ConfigurationOverride list", OPT),
+        ("toolExecutionNotifications", "Notification list", OPT),
+        ("toolConfigurationNotifications", "Notification list", OPT),
+        ("exitCodeDescription", "string", OPT),
+        ("exitSignalName", "string", OPT),
+        ("exitSignalNumber", "int", OPT),
+        ("processStartFailureMessage", "string", OPT),
+        ("executionSuccessful", "bool", REQ),
+        ("machine", "string", OPT),
+        ("account", "string", OPT),
+        ("processId", "int", OPT),
+        ("executableLocation", "ArtifactLocation", OPT),
+        ("workingDirectory", "ArtifactLocation", OPT),
+        ("environmentVariables", "Map<string, string>", OPT),
+        ("stdin", "ArtifactLocation", OPT),
+        ("stdout", "ArtifactLocation", OPT),
+        ("stderr", "ArtifactLocation", OPT),
+        ("stdoutStderr", "ArtifactLocation", OPT),
+    ]),
+    ("ConfigurationOverride", &[
+        ("configuration", "ReportingConfiguration", REQ),
+        ("descriptor", "ReportingDescriptorReference", REQ),
+    ]),
+    ("Notification", &[
+        ("locations", "Location list", OPT),
+        ("message", "Message", REQ),
+        ("level", "Level", OPT),
+        ("threadId", "int", OPT),
+        ("timeUtc", "string", OPT),
+        ("exception", "Exception", OPT),
+        ("descriptor", "ReportingDescriptorReference", OPT),
+        ("associatedRule", "ReportingDescriptorReference", OPT),
+    ]),
+    ("Exception", &[
+        ("kind", "string", OPT),
+        ("message", "string", OPT),
+        ("stack", "Stack", OPT),
+        ("innerExceptions", "Exception list", OPT),
+    ]),
+    ("Conversion", &[
+        ("tool", "Tool", REQ),
+        ("invocation", "Invocation", OPT),
+        ("analysisToolLogFiles", "ArtifactLocation list", OPT),
+    ]),
+    ("VersionControlDetails", &[
+        ("repositoryUri", "string", REQ),
+        ("revisionId", "string", OPT),
+        ("branch", "string", OPT),
+        ("revisionTag", "string", OPT),
+        ("asOfTimeUtc", "string", OPT),
+        ("mappedTo", "ArtifactLocation", OPT),
+    ]),
+    ("RunAutomationDetails", &[
+        ("description", "Message", OPT),
+        ("id", "string", OPT),
+        ("guid", "string", OPT),
+        ("correlationGuid", "string", OPT),
+    ]),
+    ("SpecialLocations", &[
+        ("displayBase", "ArtifactLocation", OPT),
+    ]),
+    ("WebRequest", &[
+        ("index", "int", OPT),
+        ("protocol", "string", OPT),
+        ("version", "string", OPT),
+        ("target", "string", OPT),
+        ("method", "string", OPT),
+        ("headers", "Map<string, string>", OPT),
+        ("parameters", "Map<string, string>", OPT),
+        ("body", "ArtifactContent", OPT),
+    ]),
+    ("WebResponse", &[
+        ("index", "int", OPT),
+        ("protocol", "string", OPT),
+        ("version", "string", OPT),
+        ("statusCode", "int", OPT),
+        ("reasonPhrase", "string", OPT),
+        ("headers", "Map<string, string>", OPT),
+        ("body", "ArtifactContent", OPT),
+        ("noResponseReceived", "bool", OPT),
+    ]),
+    ("ExternalPropertyFileReferences", &[
+        ("conversion", "ExternalPropertyFileReference", OPT),
+        ("graphs", "ExternalPropertyFileReference list", OPT),
+        ("externalizedProperties", "ExternalPropertyFileReference", OPT),
+        ("artifacts", "ExternalPropertyFileReference list", OPT),
+        ("invocations", "ExternalPropertyFileReference list", OPT),
+        ("logicalLocations", "ExternalPropertyFileReference list", OPT),
+        ("threadFlowLocations", "ExternalPropertyFileReference list", OPT),
+        ("results", "ExternalPropertyFileReference list", OPT),
+        ("taxonomies", "ExternalPropertyFileReference list", OPT),
+        ("addresses", "ExternalPropertyFileReference list", OPT),
+        ("driver", "ExternalPropertyFileReference", OPT),
+        ("extensions", "ExternalPropertyFileReference list", OPT),
+        ("policies", "ExternalPropertyFileReference list", OPT),
+        ("translations", "ExternalPropertyFileReference list", OPT),
+        ("webRequests", "ExternalPropertyFileReference list", OPT),
+        ("webResponses", "ExternalPropertyFileReference list", OPT),
+    ]),
+    ("ExternalPropertyFileReference", &[
+        ("location", "ArtifactLocation", OPT),
+        ("guid", "string", OPT),
+        ("itemCount", "int", OPT),
+    ]),
+    ("ExternalProperties", &[
+        ("$schema", "string", OPT),
+        ("version", "string", OPT),
+        ("guid", "string", OPT),
+        ("runGuid", "string", OPT),
+        ("conversion", "Conversion", OPT),
+        ("graphs", "Graph list", OPT),
+        ("externalizedProperties", "Map<string, any>", OPT),
+        ("artifacts", "Artifact list", OPT),
+        ("invocations", "Invocation list", OPT),
+        ("logicalLocations", "LogicalLocation list", OPT),
+        ("threadFlowLocations", "ThreadFlowLocation list", OPT),
+        ("results", "Result list", OPT),
+        ("taxonomies", "ToolComponent list", OPT),
+        ("driver", "ToolComponent", OPT),
+        ("extensions", "ToolComponent list", OPT),
+        ("policies", "ToolComponent list", OPT),
+        ("translations", "ToolComponent list", OPT),
+        ("addresses", "Address list", OPT),
+        ("webRequests", "WebRequest list", OPT),
+        ("webResponses", "WebResponse list", OPT),
+    ]),
+];
+
+/// The object or enumeration a field type refers to, if any
+pub fn referenced_type(field_type: &str) -> Option<&str> {
+    let inner = field_type.trim_end_matches(" list");
+    let inner = inner
+        .strip_prefix("Map<string, ")
+        .and_then(|rest| rest.strip_suffix('>'))
+        .unwrap_or(inner);
+    inner.starts_with(|c: char| c.is_ascii_uppercase()).then_some(inner)
+}
+
+pub fn object(name: &str) -> Option<&'static ObjectDef> {
+    OBJECTS.iter().find(|(object, _)| *object == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_is_closed() {
+        for (name, fields) in OBJECTS {
+            for (field, field_type, _) in fields.iter() {
+                if let Some(referenced) = referenced_type(field_type) {
+                    assert!(
+                        object(referenced).is_some() || ENUMS.iter().any(|(e, _)| *e == referenced),
+                        "{}.{} refers to unknown type {}",
+                        name,
+                        field,
+                        referenced
+                    );
+                }
+            }
+        }
+        assert_eq!(referenced_type("Map<string, ArtifactLocation>"), Some("ArtifactLocation"));
+        assert_eq!(referenced_type("string list"), None);
+    }
+}
@@ -0,0 +1,275 @@
+//! The CycloneDX 1.5 object model
+//!
+//! Covers the BOM, its components and services, the dependency graph and
+//! vulnerabilities with their VEX `analysis`. Objects are named as in the
+//! specification's JSON schema; the `licenses` choice is `LicenseChoice`
+//! and vulnerability `affects` entries are `Affect`. `Metadata.tools` uses
+//! the 1.5 form with `components` and `services`.
+//!
+//! Machine learning model cards, formulation, data flows and component
+//! evidence are not modelled.
+
+use crate::model::{EnumDef, ObjectDef, OPT, REQ};
+
+/// `specVersion` values documents may declare; later versions only add
+/// properties, which are ignored when reading
+pub const VERSIONS: &[&str] = &["1.4", "1.5", "1.6"];
+
+pub const ENUMS: &[EnumDef] = &[
+    ("ComponentType", &[
+        "application", "framework", "library", "container", "platform", "operating-system",
+        "device", "device-driver", "firmware", "file", "machine-learning-model", "data",
+    ]),
+    ("Scope", &["required", "optional", "excluded"]),
+    ("Severity", &["critical", "high", "medium", "low", "info", "none", "unknown"]),
+    ("ScoreMethod", &["CVSSv2", "CVSSv3", "CVSSv31", "CVSSv4", "OWASP", "SSVC", "other"]),
+    ("ImpactAnalysisState", &[
+        "resolved", "resolved_with_pedigree", "exploitable", "in_triage", "false_positive",
+        "not_affected",
+    ]),
+    ("ImpactAnalysisJustification", &[
+        "code_not_present", "code_not_reachable", "requires_configuration", "requires_dependency",
+        "requires_environment", "protected_by_compiler", "protected_at_runtime",
+        "protected_at_perimeter", "protected_by_mitigating_control",
+    ]),
+    ("AnalysisResponse", &["can_not_fix", "will_not_fix", "update", "rollback", "workaround_available"]),
+    ("AffectedStatus", &["affected", "unaffected", "unknown"]),
+];
+
+pub const OBJECTS: &[ObjectDef] = &[
+    ("Bom", &[
+        ("bomFormat", "string", REQ),
+        ("specVersion", "string", REQ),
+        ("serialNumber", "string", OPT),
+        ("version", "int", OPT),
+        ("metadata", "Metadata", OPT),
+        ("components", "Component list", OPT),
+        ("services", "Service list", OPT),
+        ("externalReferences", "ExternalReference list", OPT),
+        ("dependencies", "Dependency list", OPT),
+        ("compositions", "Composition list", OPT),
+        ("vulnerabilities", "Vulnerability list", OPT),
+        ("properties", "Property list", OPT),
+    ]),
+    ("Metadata", &[
+        ("timestamp", "string", OPT),
+        ("lifecycles", "Lifecycle list", OPT),
+        ("tools", "Tools", OPT),
+        ("authors", "OrganizationalContact list", OPT),
+        ("component", "Component", OPT),
+        ("manufacture", "OrganizationalEntity", OPT),
+        ("supplier", "OrganizationalEntity", OPT),
+        ("licenses", "LicenseChoice list", OPT),
+        ("properties", "Property list", OPT),
+    ]),
+    ("Lifecycle", &[
+        ("phase", "string", OPT),
+        ("name", "string", OPT),
+        ("description", "string", OPT),
+    ]),
+    ("Tools", &[
+        ("components", "Component list", OPT),
+        ("services", "Service list", OPT),
+    ]),
+    ("OrganizationalEntity", &[
+        ("bom-ref", "string", OPT),
+        ("name", "string", OPT),
+        ("url", "string list", OPT),
+        ("contact", "OrganizationalContact list", OPT),
+    ]),
+    ("OrganizationalContact", &[
+        ("bom-ref", "string", OPT),
+        ("name", "string", OPT),
+        ("email", "string", OPT),
+        ("phone", "string", OPT),
+    ]),
+    ("Component", &[
+        ("type", "ComponentType", REQ),
+        ("mime-type", "string", OPT),
+        ("bom-ref", "string", OPT),
+        ("supplier", "OrganizationalEntity", OPT),
+        ("author", "string", OPT),
+        ("publisher", "string", OPT),
+        ("group", "string", OPT),
+        ("name", "string", REQ),
+        ("version", "string", OPT),
+        ("description", "string", OPT),
+        ("scope", "Scope", OPT),
+        ("hashes", "Hash list", OPT),
+        ("licenses", "LicenseChoice list", OPT),
+        ("copyright", "string", OPT),
+        ("cpe", "string", OPT),
+        ("purl", "string", OPT),
+        ("swid", "Swid", OPT),
+        ("pedigree", "Pedigree", OPT),
+        ("externalReferences", "ExternalReference list", OPT),
+        ("properties", "Property list", OPT),
+        ("components", "Component list", OPT),
+    ]),
+    ("Hash", &[
+        ("alg", "string", REQ),
+        ("content", "string", REQ),
+    ]),
+    ("LicenseChoice", &[
+        ("license", "License", OPT),
+        ("expression", "string", OPT),
+    ]),
+    ("License", &[
+        ("bom-ref", "string", OPT),
+        ("id", "string", OPT),
+        ("name", "string", OPT),
+        ("text", "AttachedText", OPT),
+        ("url", "string", OPT),
+    ]),
+    ("AttachedText", &[
+        ("contentType", "string", OPT),
+        ("encoding", "string", OPT),
+        ("content", "string", REQ),
+    ]),
+    ("Swid", &[
+        ("tagId", "string", REQ),
+        ("name", "string", REQ),
+        ("version", "string", OPT),
+        ("tagVersion", "int", OPT),
+        ("patch", "bool", OPT),
+        ("text", "AttachedText", OPT),
+        ("url", "string", OPT),
+    ]),
+    ("Pedigree", &[
+        ("ancestors", "Component list", OPT),
+        ("descendants", "Component list", OPT),
+        ("variants", "Component list", OPT),
+        ("commits", "Commit list", OPT),
+        ("patches", "Patch list", OPT),
+        ("notes", "string", OPT),
+    ]),
+    ("Commit", &[
+        ("uid", "string", OPT),
+        ("url", "string", OPT),
+        ("author", "IdentifiableAction", OPT),
+        ("committer", "IdentifiableAction", OPT),
+        ("message", "string", OPT),
+    ]),
+    ("IdentifiableAction", &[
+        ("timestamp", "string", OPT),
+        ("name", "string", OPT),
+        ("email", "string", OPT),
+    ]),
+    ("Patch", &[
+        ("type", "string", REQ),
+        ("diff", "Diff", OPT),
+        ("resolves", "Issue list", OPT),
+    ]),
+    ("Diff", &[
+        ("text", "AttachedText", OPT),
+        ("url", "string", OPT),
+    ]),
+    ("Issue", &[
+        ("type", "string", REQ),
+        ("id", "string", OPT),
+        ("name", "string", OPT),
+        ("description", "string", OPT),
+        ("source", "VulnerabilitySource", OPT),
+        ("references", "string list", OPT),
+    ]),
+    ("ExternalReference", &[
+        ("url", "string", REQ),
+        ("comment", "string", OPT),
+        ("type", "string", REQ),
+        ("hashes", "Hash list", OPT),
+    ]),
+    ("Property", &[
+        ("name", "string", REQ),
+        ("value", "string", OPT),
+    ]),
+    ("Service", &[
+        ("bom-ref", "string", OPT),
+        ("provider", "OrganizationalEntity", OPT),
+        ("group", "string", OPT),
+        ("name", "string", REQ),
+        ("version", "string", OPT),
+        ("description", "string", OPT),
+        ("endpoints", "string list", OPT),
+        ("authenticated", "bool", OPT),
+        ("x-trust-boundary", "bool", OPT),
+        ("trustZone", "string", OPT),
+        ("licenses", "LicenseChoice list", OPT),
+        ("externalReferences", "ExternalReference list", OPT),
+        ("services", "Service list", OPT),
+        ("properties", "Property list", OPT),
+    ]),
+    ("Dependency", &[
+        ("ref", "string", REQ),
+        ("dependsOn", "string list", OPT),
+    ]),
+    ("Composition", &[
+        ("bom-ref", "string", OPT),
+        ("aggregate", "string", REQ),
+        ("assemblies", "string list", OPT),
+        ("dependencies", "string list", OPT),
+        ("vulnerabilities", "string list", OPT),
+    ]),
+    ("Vulnerability", &[
+        ("bom-ref", "string", OPT),
+        ("id", "string", OPT),
+        ("source", "VulnerabilitySource", OPT),
+        ("references", "VulnerabilityReference list", OPT),
+        ("ratings", "Rating list", OPT),
+        ("cwes", "int list", OPT),
+        ("description", "string", OPT),
+        ("detail", "string", OPT),
+        ("recommendation", "string", OPT),
+        ("workaround", "string", OPT),
+        ("advisories", "Advisory list", OPT),
+        ("created", "string", OPT),
+        ("published", "string", OPT),
+        ("updated", "string", OPT),
+        ("rejected", "string", OPT),
+        ("credits", "Credits", OPT),
+        ("tools", "Tools", OPT),
+        ("analysis", "Analysis", OPT),
+        ("affects", "Affect list", OPT),
+        ("properties", "Property list", OPT),
+    ]),
+    ("VulnerabilitySource", &[
+        ("name", "string", OPT),
+        ("url", "string", OPT),
+    ]),
+    ("VulnerabilityReference", &[
+        ("id", "string", REQ),
+        ("source", "VulnerabilitySource", REQ),
+    ]),
+    ("Rating", &[
+        ("source", "VulnerabilitySource", OPT),
+        ("score", "float", OPT),
+        ("severity", "Severity", OPT),
+        ("method", "ScoreMethod", OPT),
+        ("vector", "string", OPT),
+        ("justification", "string", OPT),
+    ]),
+    ("Advisory", &[
+        ("title", "string", OPT),
+        ("url", "string", REQ),
+    ]),
+    ("Credits", &[
+        ("organizations", "OrganizationalEntity list", OPT),
+        ("individuals", "OrganizationalContact list", OPT),
+    ]),
+    ("Analysis", &[
+        ("state", "ImpactAnalysisState", OPT),
+        ("justification", "ImpactAnalysisJustification", OPT),
+        ("response", "AnalysisResponse list", OPT),
+        ("detail", "string", OPT),
+        ("firstIssued", "string", OPT),
+        ("lastUpdated", "string", OPT),
+    ]),
+    ("Affect", &[
+        ("ref", "string", REQ),
+        ("versions", "AffectedVersion list", OPT),
+    ]),
+    ("AffectedVersion", &[
+        ("version", "string", OPT),
+        ("range", "string", OPT),
+        ("status", "AffectedStatus", OPT),
+    ]),
+];
@@ -0,0 +1,432 @@
+//! SBOM Type Provider
+//!
+//! Generates Fusabi types for software bills of materials in the
+//! CycloneDX and SPDX formats, so supply-chain automation (license
+//! checks, dependency audits, VEX triage) can read SBOMs with every
+//! property typed.
+//!
+//! # Sources
+//!
+//! - `cyclonedx` (or `cdx`): the CycloneDX 1.5 model; see [`cyclonedx`]
+//! - `spdx`: the SPDX 2.3 model; see [`spdx`]
+//! - `sbom`: the formats the `format` param selects, both by default
+//! - An SBOM document, inline or from a file, whose format is read from
+//!   `bomFormat`/`specVersion` or `spdxVersion`
+//!
+//! # Generated Types
+//!
+//! Each format is generated into its own module, `<namespace>.CycloneDx`
+//! and `<namespace>.Spdx`, as records named after the specification's
+//! objects with its JSON property names. Properties the schema requires
+//! are plain and the rest are `T option`. Enumerated properties such as
+//! `ComponentType`, `Severity` and the VEX `ImpactAnalysisState` become
+//! unions.
+//!
+//! # Params
+//!
+//! | Param    | Meaning                                                        |
+//! |----------|----------------------------------------------------------------|
+//! | `format` | `cyclonedx`, `spdx` or `both`, for the `sbom` source           |
+//! | `root`   | Comma-separated objects to generate, with the types they use; defaults to `Bom` and `SpdxDocument` |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_sbom::SbomProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = SbomProvider::new();
+//! let params = ProviderParams::default().with("root", "Vulnerability");
+//! let schema = provider.resolve_schema("cyclonedx", &params)?;
+//! let types = provider.generate_types(&schema, "Sbom")?;
+//! ```
+
+pub mod cyclonedx;
+pub mod model;
+pub mod spdx;
+
+pub use model::SbomFormat;
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceValidator, TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, DuDef, VariantDef, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// CycloneDX version the model describes
+pub const CYCLONEDX_MODEL_VERSION: &str = "1.5";
+
+/// The formats and objects to generate, stored in [`Schema::Custom`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Selection {
+    formats: Vec<FormatSelection>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FormatSelection {
+    format: SbomFormat,
+    roots: Vec<String>,
+    /// Version declared by the source document, if one was read
+    document_version: Option<String>,
+}
+
+/// SBOM type provider
+pub struct SbomProvider {
+    generator: TypeGenerator,
+}
+
+impl SbomProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<Selection> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected SBOM selection: {}", e)))
+    }
+
+    /// Detect a document's format and check its declared version
+    fn detect(&self, content: &str) -> ProviderResult<(SbomFormat, String)> {
+        let document: Value = serde_json::from_str(content)
+            .map_err(|e| ProviderError::ParseError(format!("Invalid SBOM document: {}", e)))?;
+        let (format, version) = if document.get("bomFormat").and_then(Value::as_str) == Some("CycloneDX") {
+            (SbomFormat::CycloneDx, document.get("specVersion"))
+        } else if let Some(version) = document.get("spdxVersion") {
+            (SbomFormat::Spdx, Some(version))
+        } else {
+            return Err(ProviderError::ParseError(
+                "Expected a CycloneDX (`bomFormat`) or SPDX (`spdxVersion`) JSON document".to_string(),
+            ));
+        };
+
+        let version = version
+            .and_then(Value::as_str)
+            .ok_or_else(|| ProviderError::ParseError(format!("{} document has no version", format.spec_name())))?;
+        if !format.versions().contains(&version) {
+            return Err(ProviderError::InvalidSource(format!(
+                "{} {} is not supported; expected one of {}",
+                format.spec_name(),
+                version,
+                format.versions().join(", ")
+            )));
+        }
+        Ok((format, version.to_string()))
+    }
+
+    /// Names of the objects and enumerations reachable from the roots, in
+    /// model order
+    fn reachable(&self, format: SbomFormat, roots: &[String]) -> Vec<&'static str> {
+        let mut reached: Vec<&str> = Vec::new();
+        let mut pending: Vec<&str> = roots.iter().map(String::as_str).collect();
+        while let Some(name) = pending.pop() {
+            if reached.contains(&name) {
+                continue;
+            }
+            reached.push(name);
+            if let Some((_, fields)) = format.object(name) {
+                pending.extend(fields.iter().filter_map(|(_, field_type, _)| model::referenced_type(field_type)));
+            }
+        }
+
+        format
+            .enums()
+            .iter()
+            .map(|(name, _)| *name)
+            .chain(format.objects().iter().map(|(name, _)| *name))
+            .filter(|name| reached.contains(name))
+            .collect()
+    }
+
+    fn definition(&self, format: SbomFormat, name: &str) -> TypeDefinition {
+        if let Some((_, values)) = format.enumeration(name) {
+            return TypeDefinition::Du(DuDef {
+                name: name.to_string(),
+                variants: values
+                    .iter()
+                    .map(|value| VariantDef::new_simple(self.generator.naming.apply(value)))
+                    .collect(),
+            });
+        }
+
+        let (_, properties) = format.object(name).expect("reachable names are in the model");
+        TypeDefinition::Record(RecordDef {
+            name: name.to_string(),
+            fields: properties
+                .iter()
+                .map(|(property, field_type, required)| {
+                    (property.to_string(), TypeShape::parse(field_type).option_unless(*required).into())
+                })
+                .collect(),
+        })
+    }
+}
+
+impl Default for SbomProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for SbomProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("SbomProvider")
+            .param(
+                ParamSpec::new("format", ParamKind::Choice(&["cyclonedx", "spdx", "both"]))
+                    .default("both")
+                    .describe("Formats for the `sbom` source"),
+            )
+            .param(
+                ParamSpec::new("root", ParamKind::List)
+                    .describe("Objects to generate, with the types they use"),
+            )
+    }
+}
+
+impl TypeProvider for SbomProvider {
+    fn name(&self) -> &str {
+        "SbomProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let trimmed = source.trim();
+        let (formats, document_version) = match trimmed.to_ascii_lowercase().as_str() {
+            "cyclonedx" | "cdx" => (vec![SbomFormat::CycloneDx], None),
+            "spdx" => (vec![SbomFormat::Spdx], None),
+            "sbom" => (
+                SbomFormat::parse_list(typed.str("format").unwrap_or("both"))?,
+                None,
+            ),
+            _ => {
+                let (format, version) = if trimmed.starts_with('{') {
+                    self.detect(trimmed)?
+                } else {
+                    self.detect(&read_source(source, params)?)?
+                };
+                (vec![format], Some(version))
+            }
+        };
+
+        let roots: Option<Vec<String>> = typed
+            .list("root")
+            .map(|roots| roots.into_iter().map(str::to_string).collect());
+        if let Some(roots) = &roots {
+            for root in roots {
+                if !formats
+                    .iter()
+                    .any(|format| format.object(root).is_some() || format.enumeration(root).is_some())
+                {
+                    return Err(ProviderError::InvalidSource(format!(
+                        "`{}` is not an object of the selected SBOM formats",
+                        root
+                    )));
+                }
+            }
+        }
+
+        let formats = formats
+            .into_iter()
+            .map(|format| FormatSelection {
+                format,
+                roots: match &roots {
+                    Some(roots) => roots
+                        .iter()
+                        .filter(|root| format.object(root).is_some() || format.enumeration(root).is_some())
+                        .cloned()
+                        .collect(),
+                    None => vec![format.root().to_string()],
+                },
+                document_version: document_version.clone(),
+            })
+            .filter(|selection| !selection.roots.is_empty())
+            .collect();
+
+        let json = serde_json::to_string(&Selection { formats })
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected SBOM selection".to_string()));
+        };
+
+        let selection = self.parse_schema(json)?;
+        let mut result = GeneratedTypes::new();
+        for FormatSelection { format, roots, .. } in &selection.formats {
+            let mut module =
+                GeneratedModule::new(vec![namespace.to_string(), format.module_name().to_string()]);
+            module.types = self
+                .reachable(*format, roots)
+                .into_iter()
+                .map(|name| self.definition(*format, name))
+                .collect();
+            result.modules.push(module);
+        }
+        Ok(result)
+    }
+}
+
+impl SourceValidator for SbomProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected SBOM selection".to_string()));
+        };
+
+        let selection = self.parse_schema(json)?;
+        let mut diagnostics = Vec::new();
+        for selection in &selection.formats {
+            if let (SbomFormat::CycloneDx, Some(version)) = (selection.format, &selection.document_version) {
+                if version.as_str() > CYCLONEDX_MODEL_VERSION {
+                    diagnostics.push(Diagnostic::info(
+                        "newer-spec-version",
+                        format!(
+                            "The document is CycloneDX {}; properties added after {} are not typed",
+                            version, CYCLONEDX_MODEL_VERSION
+                        ),
+                    ));
+                }
+            }
+        }
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for SbomProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .embedded("cyclonedx")
+            .embedded("cdx")
+            .embedded("spdx")
+            .embedded("sbom")
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    fn names(module: &GeneratedModule) -> Vec<&str> {
+        module
+            .types
+            .iter()
+            .map(|t| match t {
+                TypeDefinition::Record(r) => r.name.as_str(),
+                TypeDefinition::Du(du) => du.name.as_str(),
+            })
+            .collect()
+    }
+
+    fn record<'a>(module: &'a GeneratedModule, name: &str) -> &'a RecordDef {
+        module
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+    }
+
+    fn field(record: &RecordDef, name: &str) -> String {
+        record
+            .fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, ty)| ty.to_string())
+            .unwrap_or_else(|| panic!("Expected field {}.{}", record.name, name))
+    }
+
+    #[test]
+    fn test_both_formats() {
+        let provider = SbomProvider::new();
+        let schema = provider.resolve_schema("sbom", &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Sbom").unwrap();
+
+        let paths: Vec<Vec<String>> = types.modules.iter().map(|m| m.path.clone()).collect();
+        assert_eq!(paths, [vec!["Sbom", "CycloneDx"], vec!["Sbom", "Spdx"]]);
+
+        let cyclonedx = &types.modules[0];
+        assert_eq!(cyclonedx.types.len(), cyclonedx::ENUMS.len() + cyclonedx::OBJECTS.len());
+        let component = record(cyclonedx, "Component");
+        assert_eq!(field(component, "type"), "ComponentType");
+        assert_eq!(field(component, "purl"), "string option");
+        assert_eq!(field(component, "components"), "Component list option");
+        assert_eq!(field(record(cyclonedx, "Analysis"), "state"), "ImpactAnalysisState option");
+
+        let spdx = &types.modules[1];
+        assert_eq!(spdx.types.len(), spdx::ENUMS.len() + spdx::OBJECTS.len());
+        assert_eq!(field(record(spdx, "SpdxDocument"), "creationInfo"), "CreationInfo");
+        assert_eq!(field(record(spdx, "Package"), "primaryPackagePurpose"), "PackagePurpose option");
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/sbom.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_format_and_root_params() {
+        let provider = SbomProvider::new();
+        let params = ProviderParams::default().with("format", "spdx");
+        let schema = provider.resolve_schema("sbom", &params).unwrap();
+        let types = provider.generate_types(&schema, "Sbom").unwrap();
+        assert_eq!(types.modules.len(), 1);
+        assert_eq!(types.modules[0].path, ["Sbom", "Spdx"]);
+
+        // Roots only select the formats that define them
+        let params = ProviderParams::default().with("root", "Affect");
+        let schema = provider.resolve_schema("sbom", &params).unwrap();
+        let types = provider.generate_types(&schema, "Sbom").unwrap();
+        assert_eq!(types.modules.len(), 1);
+        assert_eq!(names(&types.modules[0]), ["AffectedStatus", "Affect", "AffectedVersion"]);
+
+        let params = ProviderParams::default().with("root", "Package");
+        assert!(provider.resolve_schema("cyclonedx", &params).is_err());
+        let params = ProviderParams::default().with("format", "swid");
+        assert!(provider.resolve_schema("sbom", &params).is_err());
+    }
+
+    #[test]
+    fn test_documents() {
+        let provider = SbomProvider::new();
+        let bom = r#"{"bomFormat": "CycloneDX", "specVersion": "1.6", "version": 1, "components": []}"#;
+        let schema = provider.resolve_schema(bom, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Sbom").unwrap();
+        assert_eq!(types.modules[0].path, ["Sbom", "CycloneDx"]);
+        let diagnostics = provider.schema_diagnostics(&schema).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "newer-spec-version");
+
+        let document = r#"{"spdxVersion": "SPDX-2.3", "SPDXID": "SPDXRef-DOCUMENT", "packages": []}"#;
+        let schema = provider.resolve_schema(document, &ProviderParams::default()).unwrap();
+        assert!(provider.schema_diagnostics(&schema).unwrap().is_empty());
+
+        let old = r#"{"bomFormat": "CycloneDX", "specVersion": "1.2"}"#;
+        let err = provider.resolve_schema(old, &ProviderParams::default()).unwrap_err();
+        assert!(err.to_string().contains("CycloneDX 1.2 is not supported"));
+        assert!(provider.resolve_schema(r#"{"runs": []}"#, &ProviderParams::default()).is_err());
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = SbomProvider::new();
+        let report = provider.validate_source("sbom", &ProviderParams::default());
+        assert!(report.is_valid());
+        assert!(!report.diagnostics.iter().any(|d| d.code == "unresolved-reference"));
+    }
+}
@@ -0,0 +1,139 @@
+//! Static SBOM object models
+//!
+//! Each format's model is a table of objects with their JSON property
+//! names and Fusabi types, written the way they are generated: an object
+//! name, `T list`, or a primitive. Enumerations are listed with their
+//! values as they appear in documents.
+
+use crate::{cyclonedx, spdx};
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde::{Deserialize, Serialize};
+
+/// Whether a property is required by the schema
+pub const REQ: bool = true;
+/// An optional property
+pub const OPT: bool = false;
+
+/// An object: name, then `(property, type, required)`
+pub type ObjectDef = (&'static str, &'static [(&'static str, &'static str, bool)]);
+
+/// An enumeration: name, then its values
+pub type EnumDef = (&'static str, &'static [&'static str]);
+
+/// An SBOM format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SbomFormat {
+    CycloneDx,
+    Spdx,
+}
+
+impl SbomFormat {
+    /// The formats a `format` value selects
+    pub fn parse_list(value: &str) -> ProviderResult<Vec<SbomFormat>> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "cyclonedx" | "cdx" => Ok(vec![SbomFormat::CycloneDx]),
+            "spdx" => Ok(vec![SbomFormat::Spdx]),
+            "both" | "" => Ok(vec![SbomFormat::CycloneDx, SbomFormat::Spdx]),
+            other => Err(ProviderError::InvalidSource(format!(
+                "Unknown SBOM format `{}`; expected `cyclonedx`, `spdx` or `both`",
+                other
+            ))),
+        }
+    }
+
+    /// The format's name as its specification spells it
+    pub fn spec_name(self) -> &'static str {
+        match self {
+            SbomFormat::CycloneDx => "CycloneDX",
+            SbomFormat::Spdx => "SPDX",
+        }
+    }
+
+    /// Module the format's types are generated into
+    pub fn module_name(self) -> &'static str {
+        match self {
+            SbomFormat::CycloneDx => "CycloneDx",
+            SbomFormat::Spdx => "Spdx",
+        }
+    }
+
+    /// The document object generation starts from by default
+    pub fn root(self) -> &'static str {
+        match self {
+            SbomFormat::CycloneDx => "Bom",
+            SbomFormat::Spdx => "SpdxDocument",
+        }
+    }
+
+    /// Specification versions documents may declare
+    pub fn versions(self) -> &'static [&'static str] {
+        match self {
+            SbomFormat::CycloneDx => cyclonedx::VERSIONS,
+            SbomFormat::Spdx => spdx::VERSIONS,
+        }
+    }
+
+    pub fn enums(self) -> &'static [EnumDef] {
+        match self {
+            SbomFormat::CycloneDx => cyclonedx::ENUMS,
+            SbomFormat::Spdx => spdx::ENUMS,
+        }
+    }
+
+    pub fn objects(self) -> &'static [ObjectDef] {
+        match self {
+            SbomFormat::CycloneDx => cyclonedx::OBJECTS,
+            SbomFormat::Spdx => spdx::OBJECTS,
+        }
+    }
+
+    pub fn object(self, name: &str) -> Option<&'static ObjectDef> {
+        self.objects().iter().find(|(object, _)| *object == name)
+    }
+
+    pub fn enumeration(self, name: &str) -> Option<&'static EnumDef> {
+        self.enums().iter().find(|(e, _)| *e == name)
+    }
+}
+
+/// The object or enumeration a field type refers to, if any
+pub fn referenced_type(field_type: &str) -> Option<&str> {
+    let inner = field_type.trim_end_matches(" list");
+    inner.starts_with(|c: char| c.is_ascii_uppercase()).then_some(inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_models_are_closed() {
+        for format in [SbomFormat::CycloneDx, SbomFormat::Spdx] {
+            assert!(format.object(format.root()).is_some());
+            for (name, fields) in format.objects() {
+                for (field, field_type, _) in fields.iter() {
+                    if let Some(referenced) = referenced_type(field_type) {
+                        assert!(
+                            format.object(referenced).is_some() || format.enumeration(referenced).is_some(),
+                            "{:?} {}.{} refers to unknown type {}",
+                            format,
+                            name,
+                            field,
+                            referenced
+                        );
+                    }
+                }
+            }
+        }
+        assert_eq!(referenced_type("Component list"), Some("Component"));
+        assert_eq!(referenced_type("string list"), None);
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(SbomFormat::parse_list("CycloneDX").unwrap(), [SbomFormat::CycloneDx]);
+        assert_eq!(SbomFormat::parse_list("both").unwrap().len(), 2);
+        assert!(SbomFormat::parse_list("swid").is_err());
+    }
+}
@@ -0,0 +1,150 @@
+//! The SPDX 2.3 JSON object model
+//!
+//! Objects follow the SPDX 2.3 JSON schema, named after the section of the
+//! specification that defines them. Checksum algorithms, external
+//! reference categories and relationship types stay strings: their
+//! spelling differs between 2.2 and 2.3 documents.
+
+use crate::model::{EnumDef, ObjectDef, OPT, REQ};
+
+/// `spdxVersion` values documents may declare
+pub const VERSIONS: &[&str] = &["SPDX-2.2", "SPDX-2.3"];
+
+pub const ENUMS: &[EnumDef] = &[
+    ("PackagePurpose", &[
+        "APPLICATION", "FRAMEWORK", "LIBRARY", "CONTAINER", "OPERATING-SYSTEM", "DEVICE",
+        "FIRMWARE", "SOURCE", "ARCHIVE", "FILE", "INSTALL", "OTHER",
+    ]),
+    ("AnnotationType", &["REVIEW", "OTHER"]),
+];
+
+pub const OBJECTS: &[ObjectDef] = &[
+    ("SpdxDocument", &[
+        ("spdxVersion", "string", REQ),
+        ("dataLicense", "string", REQ),
+        ("SPDXID", "string", REQ),
+        ("name", "string", REQ),
+        ("documentNamespace", "string", REQ),
+        ("creationInfo", "CreationInfo", REQ),
+        ("comment", "string", OPT),
+        ("externalDocumentRefs", "ExternalDocumentRef list", OPT),
+        ("documentDescribes", "string list", OPT),
+        ("packages", "Package list", OPT),
+        ("files", "File list", OPT),
+        ("snippets", "Snippet list", OPT),
+        ("relationships", "Relationship list", OPT),
+        ("hasExtractedLicensingInfos", "ExtractedLicensingInfo list", OPT),
+        ("annotations", "Annotation list", OPT),
+    ]),
+    ("CreationInfo", &[
+        ("created", "string", REQ),
+        ("creators", "string list", REQ),
+        ("licenseListVersion", "string", OPT),
+        ("comment", "string", OPT),
+    ]),
+    ("ExternalDocumentRef", &[
+        ("externalDocumentId", "string", REQ),
+        ("spdxDocument", "string", REQ),
+        ("checksum", "Checksum", REQ),
+    ]),
+    ("Checksum", &[
+        ("algorithm", "string", REQ),
+        ("checksumValue", "string", REQ),
+    ]),
+    ("Package", &[
+        ("SPDXID", "string", REQ),
+        ("name", "string", REQ),
+        ("versionInfo", "string", OPT),
+        ("packageFileName", "string", OPT),
+        ("supplier", "string", OPT),
+        ("originator", "string", OPT),
+        ("downloadLocation", "string", REQ),
+        ("filesAnalyzed", "bool", OPT),
+        ("packageVerificationCode", "PackageVerificationCode", OPT),
+        ("checksums", "Checksum list", OPT),
+        ("homepage", "string", OPT),
+        ("sourceInfo", "string", OPT),
+        ("licenseConcluded", "string", OPT),
+        ("licenseInfoFromFiles", "string list", OPT),
+        ("licenseDeclared", "string", OPT),
+        ("licenseComments", "string", OPT),
+        ("copyrightText", "string", OPT),
+        ("summary", "string", OPT),
+        ("description", "string", OPT),
+        ("comment", "string", OPT),
+        ("externalRefs", "ExternalRef list", OPT),
+        ("attributionTexts", "string list", OPT),
+        ("primaryPackagePurpose", "PackagePurpose", OPT),
+        ("releaseDate", "string", OPT),
+        ("builtDate", "string", OPT),
+        ("validUntilDate", "string", OPT),
+        ("hasFiles", "string list", OPT),
+        ("annotations", "Annotation list", OPT),
+    ]),
+    ("PackageVerificationCode", &[
+        ("packageVerificationCodeValue", "string", REQ),
+        ("packageVerificationCodeExcludedFiles", "string list", OPT),
+    ]),
+    ("ExternalRef", &[
+        ("referenceCategory", "string", REQ),
+        ("referenceType", "string", REQ),
+        ("referenceLocator", "string", REQ),
+        ("comment", "string", OPT),
+    ]),
+    ("File", &[
+        ("SPDXID", "string", REQ),
+        ("fileName", "string", REQ),
+        ("fileTypes", "string list", OPT),
+        ("checksums", "Checksum list", REQ),
+        ("licenseConcluded", "string", OPT),
+        ("licenseInfoInFiles", "string list", OPT),
+        ("licenseComments", "string", OPT),
+        ("copyrightText", "string", OPT),
+        ("comment", "string", OPT),
+        ("noticeText", "string", OPT),
+        ("fileContributors", "string list", OPT),
+        ("attributionTexts", "string list", OPT),
+        ("annotations", "Annotation list", OPT),
+    ]),
+    ("Snippet", &[
+        ("SPDXID", "string", REQ),
+        ("snippetFromFile", "string", REQ),
+        ("ranges", "SnippetRange list", REQ),
+        ("name", "string", OPT),
+        ("licenseConcluded", "string", OPT),
+        ("licenseInfoInSnippets", "string list", OPT),
+        ("licenseComments", "string", OPT),
+        ("copyrightText", "string", OPT),
+        ("comment", "string", OPT),
+        ("attributionTexts", "string list", OPT),
+        ("annotations", "Annotation list", OPT),
+    ]),
+    ("SnippetRange", &[
+        ("startPointer", "RangePointer", REQ),
+        ("endPointer", "RangePointer", REQ),
+    ]),
+    ("RangePointer", &[
+        ("reference", "string", REQ),
+        ("offset", "int", OPT),
+        ("lineNumber", "int", OPT),
+    ]),
+    ("Relationship", &[
+        ("spdxElementId", "string", REQ),
+        ("relationshipType", "string", REQ),
+        ("relatedSpdxElement", "string", REQ),
+        ("comment", "string", OPT),
+    ]),
+    ("ExtractedLicensingInfo", &[
+        ("licenseId", "string", REQ),
+        ("extractedText", "string", REQ),
+        ("name", "string", OPT),
+        ("seeAlsos", "string list", OPT),
+        ("comment", "string", OPT),
+    ]),
+    ("Annotation", &[
+        ("annotator", "string", REQ),
+        ("annotationDate", "string", REQ),
+        ("annotationType", "AnnotationType", REQ),
+        ("comment", "string", REQ),
+    ]),
+];
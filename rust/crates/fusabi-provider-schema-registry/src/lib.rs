@@ -0,0 +1,663 @@
+//! Schema Registry Type Provider
+//!
+//! Generates Fusabi types for Kafka topics from a Confluent-compatible schema
+//! registry (Confluent Schema Registry, Karapace, Redpanda). Subjects and
+//! their schemas are fetched over the registry's REST API, and each payload
+//! is converted by the provider for its format.
+//!
+//! # Sources
+//!
+//! - A registry base URL such as `http://localhost:8081`: the provider lists
+//!   `/subjects` and fetches `/subjects/<subject>/versions/<version>` for each
+//! - A JSON export, inline or from a file: an array of version objects as the
+//!   registry returns them (`subject`, `version`, `id`, `schemaType`, `schema`)
+//!
+//! # Generated Types
+//!
+//! Subjects are grouped by the topic name strategy: `orders-key` and
+//! `orders-value` both belong to topic `orders`, generated in module
+//! `<namespace>.Orders`. Other subjects (record name strategies) become a
+//! topic of their own carrying only a value.
+//!
+//! | `schemaType`    | Converted by             |
+//! |-----------------|--------------------------|
+//! | `AVRO` (or none)| Avro provider            |
+//! | `PROTOBUF`      | Protobuf provider        |
+//! | `JSON`          | JSON Schema converter    |
+//!
+//! Each topic module holds the payload types and a `Message` record with
+//! `key` and `value` fields typed by the payload roots. Topics without a key
+//! subject have a `bytes` key; primitive schemas map to the primitive type.
+//!
+//! # Params
+//!
+//! | Param      | Meaning                                                       |
+//! |------------|---------------------------------------------------------------|
+//! | `subjects` | Comma-separated subjects to keep; `orders-*` matches by prefix |
+//! | `version`  | Version to fetch from a registry; defaults to `latest`        |
+//!
+//! The `auth`, `refresh` and other fetch params apply to every registry
+//! request.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_schema_registry::SchemaRegistryProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = SchemaRegistryProvider::new();
+//! let params = ProviderParams::default().with("subjects", "orders-*");
+//! let schema = provider.resolve_schema("http://localhost:8081", &params)?;
+//! let types = provider.generate_types(&schema, "Topics")?;
+//! ```
+
+use fusabi_jsonschema_core::{definitions_of, Converter};
+use fusabi_provider_avro::AvroProvider;
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceKind, SourceValidator, TypeShape,
+    TypedParams,
+};
+use fusabi_provider_protobuf::ProtobufProvider;
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, TypeExpr, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A schema reference to another subject
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SchemaReference {
+    name: String,
+    subject: String,
+    version: i64,
+}
+
+/// One subject version, as returned by the registry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SubjectVersion {
+    subject: String,
+    #[serde(default)]
+    version: i64,
+    #[serde(default)]
+    id: i64,
+    #[serde(default = "default_schema_type")]
+    schema_type: String,
+    schema: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    references: Vec<SchemaReference>,
+}
+
+fn default_schema_type() -> String {
+    "AVRO".to_string()
+}
+
+/// Key or value half of a topic
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Role {
+    Key,
+    Value,
+}
+
+impl Role {
+    fn name(self) -> &'static str {
+        match self {
+            Role::Key => "Key",
+            Role::Value => "Value",
+        }
+    }
+}
+
+/// Subjects grouped under one topic
+struct Topic<'a> {
+    name: &'a str,
+    key: Option<&'a SubjectVersion>,
+    value: Option<&'a SubjectVersion>,
+}
+
+/// Split a subject into its topic and role under the topic name strategy
+fn topic_of(subject: &str) -> (&str, Role, bool) {
+    if let Some(topic) = subject.strip_suffix("-key") {
+        (topic, Role::Key, true)
+    } else if let Some(topic) = subject.strip_suffix("-value") {
+        (topic, Role::Value, true)
+    } else {
+        (subject, Role::Value, false)
+    }
+}
+
+/// Schema Registry type provider
+pub struct SchemaRegistryProvider {
+    generator: TypeGenerator,
+    converter: Converter,
+    avro: AvroProvider,
+    protobuf: ProtobufProvider,
+}
+
+impl SchemaRegistryProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+            converter: Converter::new(),
+            avro: AvroProvider::new(),
+            protobuf: ProtobufProvider::new(),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<Vec<SubjectVersion>> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected Schema Registry schema: {}", e)))
+    }
+
+    /// Fetch the selected subjects from a registry
+    fn fetch_subjects(
+        &self,
+        base: &str,
+        filter: &[&str],
+        params: &ProviderParams,
+        typed: &TypedParams,
+    ) -> ProviderResult<Vec<SubjectVersion>> {
+        let base = base.trim_end_matches('/');
+        let version = typed.str("version").unwrap_or("latest");
+
+        let listing = read_source(&format!("{}/subjects", base), params)?;
+        let subjects: Vec<String> = serde_json::from_str(&listing)
+            .map_err(|e| ProviderError::ParseError(format!("Invalid subject list from {}: {}", base, e)))?;
+
+        let mut versions = Vec::new();
+        for subject in subjects.iter().filter(|s| matches_filter(filter, s)) {
+            let url = format!("{}/subjects/{}/versions/{}", base, encode_segment(subject), version);
+            let body = read_source(&url, params)?;
+            let entry: SubjectVersion = serde_json::from_str(&body).map_err(|e| {
+                ProviderError::ParseError(format!("Invalid schema for subject `{}`: {}", subject, e))
+            })?;
+            versions.push(entry);
+        }
+        Ok(versions)
+    }
+
+    /// Group subjects into topics, in order of first appearance
+    fn topics<'a>(&self, versions: &'a [SubjectVersion]) -> Vec<Topic<'a>> {
+        let mut topics: Vec<Topic<'a>> = Vec::new();
+        for entry in versions {
+            let (name, role, _) = topic_of(&entry.subject);
+            let index = match topics.iter().position(|t| t.name == name) {
+                Some(i) => i,
+                None => {
+                    topics.push(Topic { name, key: None, value: None });
+                    topics.len() - 1
+                }
+            };
+            match role {
+                Role::Key => topics[index].key = Some(entry),
+                Role::Value => topics[index].value = Some(entry),
+            }
+        }
+        topics
+    }
+
+    /// Payload definitions and the root type of one subject
+    fn payload_types(&self, entry: &SubjectVersion, role: Role) -> ProviderResult<(Vec<TypeDefinition>, TypeExpr)> {
+        match entry.schema_type.as_str() {
+            "AVRO" => {
+                let value: Value = serde_json::from_str(&entry.schema).map_err(|e| subject_error(entry, e))?;
+                // Primitive schemas are a bare type name or `{"type": "string"}`
+                let primitive = match &value {
+                    Value::String(name) => Some(name.as_str()),
+                    Value::Object(o) if o.len() == 1 => o.get("type").and_then(Value::as_str),
+                    _ => None,
+                };
+                if let Some(type_name) = primitive.and_then(avro_primitive) {
+                    return Ok((Vec::new(), TypeShape::named(type_name).into()));
+                }
+
+                let generated = self
+                    .avro
+                    .generate_types(&Schema::Custom(entry.schema.clone()), role.name())?;
+                let types: Vec<TypeDefinition> = generated.modules.into_iter().flat_map(|m| m.types).collect();
+                // The top-level named type is generated last
+                let root = types.last().map(type_name).unwrap_or("any").to_string();
+                Ok((types, TypeShape::named(root).into()))
+            }
+            "PROTOBUF" => {
+                let generated = self
+                    .protobuf
+                    .generate_types(&Schema::Custom(entry.schema.clone()), role.name())?;
+                let types: Vec<TypeDefinition> = generated.modules.into_iter().flat_map(|m| m.types).collect();
+                // The registry serializes with the first message of the file
+                let root = first_message(&entry.schema)
+                    .map(|name| self.generator.naming.apply(name))
+                    .filter(|name| types.iter().any(|t| type_name(t) == name))
+                    .unwrap_or_else(|| "any".to_string());
+                Ok((types, TypeShape::named(root).into()))
+            }
+            "JSON" => {
+                let schema: Value = serde_json::from_str(&entry.schema).map_err(|e| subject_error(entry, e))?;
+                let mut types = definitions_of(&schema)
+                    .map(|defs| self.converter.definitions(defs))
+                    .unwrap_or_default();
+                match self.converter.definition(role.name(), &schema) {
+                    Some(root) => {
+                        types.push(root);
+                        Ok((types, TypeShape::named(role.name()).into()))
+                    }
+                    // Primitive or `$ref` payloads need no definition of their own
+                    None => Ok((types, self.converter.type_expr(&schema))),
+                }
+            }
+            // Reported by the validator; the payload stays raw
+            _ => Ok((Vec::new(), TypeShape::named("bytes").into())),
+        }
+    }
+
+    /// Generate one module per topic
+    fn generate_from_versions(&self, versions: &[SubjectVersion], namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let mut result = GeneratedTypes::new();
+
+        for topic in self.topics(versions) {
+            let mut module = GeneratedModule::new(vec![
+                namespace.to_string(),
+                self.generator.naming.apply(topic.name),
+            ]);
+            let mut fields = Vec::new();
+
+            for (role, entry) in [(Role::Key, topic.key), (Role::Value, topic.value)] {
+                let type_expr = match entry {
+                    Some(entry) => {
+                        let (types, root) = self.payload_types(entry, role)?;
+                        for definition in types {
+                            // Keys and values often share definitions; keep the first
+                            if !module.types.iter().any(|t| type_name(t) == type_name(&definition)) {
+                                module.types.push(definition);
+                            }
+                        }
+                        root
+                    }
+                    None => TypeShape::named("bytes").into(),
+                };
+                fields.push((role.name().to_lowercase(), type_expr));
+            }
+
+            module.types.push(TypeDefinition::Record(RecordDef {
+                name: "Message".to_string(),
+                fields,
+            }));
+            result.modules.push(module);
+        }
+
+        Ok(result)
+    }
+}
+
+impl Default for SchemaRegistryProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for SchemaRegistryProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("SchemaRegistryProvider")
+            .param(
+                ParamSpec::new("subjects", ParamKind::List)
+                    .describe("Subjects to keep; `orders-*` matches by prefix"),
+            )
+            .param(
+                ParamSpec::new("version", ParamKind::String)
+                    .default("latest")
+                    .describe("Version to fetch from a registry"),
+            )
+    }
+}
+
+impl TypeProvider for SchemaRegistryProvider {
+    fn name(&self) -> &str {
+        "SchemaRegistryProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let filter = typed.list("subjects").unwrap_or_default();
+
+        let trimmed = source.trim_start();
+        let mut versions = if trimmed.starts_with('[') {
+            self.parse_schema(source)?
+        } else if source.starts_with("http://") || source.starts_with("https://") {
+            self.fetch_subjects(source, &filter, params, &typed)?
+        } else {
+            self.parse_schema(&read_source(source, params)?)?
+        };
+
+        versions.retain(|entry| matches_filter(&filter, &entry.subject));
+        if versions.is_empty() && !filter.is_empty() {
+            return Err(ProviderError::InvalidSource(format!(
+                "No subject matches '{}'",
+                filter.join(",")
+            )));
+        }
+
+        // Validate payloads up front so errors name the subject
+        for entry in &versions {
+            let result = match entry.schema_type.as_str() {
+                "AVRO" => self.avro.resolve_schema(&entry.schema, params).map(drop),
+                "PROTOBUF" => self.protobuf.resolve_schema(&entry.schema, params).map(drop),
+                "JSON" => serde_json::from_str::<Value>(&entry.schema)
+                    .map(drop)
+                    .map_err(|e| ProviderError::ParseError(e.to_string())),
+                _ => Ok(()),
+            };
+            result.map_err(|e| {
+                ProviderError::ParseError(format!("Invalid schema for subject `{}`: {}", entry.subject, e))
+            })?;
+        }
+
+        let json = serde_json::to_string(&versions)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        match schema {
+            Schema::Custom(json) => {
+                let versions = self.parse_schema(json)?;
+                self.generate_from_versions(&versions, namespace)
+            }
+            _ => Err(ProviderError::ParseError("Expected Schema Registry schema".to_string())),
+        }
+    }
+}
+
+impl SourceValidator for SchemaRegistryProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected Schema Registry schema".to_string()));
+        };
+
+        let versions = self.parse_schema(json)?;
+        let mut diagnostics = Vec::new();
+
+        if versions.is_empty() {
+            diagnostics.push(Diagnostic::warning("no-types", "The registry has no subjects"));
+        }
+
+        for entry in &versions {
+            let (_, _, topic_strategy) = topic_of(&entry.subject);
+            if !topic_strategy {
+                diagnostics.push(
+                    Diagnostic::info(
+                        "subject-strategy",
+                        format!(
+                            "Subject `{}` does not follow the topic name strategy; it is generated as a value",
+                            entry.subject
+                        ),
+                    )
+                    .at(entry.subject.clone()),
+                );
+            }
+
+            for reference in &entry.references {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        "reference-unresolved",
+                        format!(
+                            "Reference `{}` to subject `{}` version {} is not loaded; its types will be unresolved",
+                            reference.name, reference.subject, reference.version
+                        ),
+                    )
+                    .at(entry.subject.clone()),
+                );
+            }
+
+            let delegated = match entry.schema_type.as_str() {
+                "AVRO" => self.avro.schema_diagnostics(&Schema::Custom(entry.schema.clone()))?,
+                "PROTOBUF" => self.protobuf.schema_diagnostics(&Schema::Custom(entry.schema.clone()))?,
+                "JSON" => Vec::new(),
+                other => {
+                    diagnostics.push(
+                        Diagnostic::warning(
+                            "schema-type-unsupported",
+                            format!("Schema type `{}` is not supported; the payload is typed as `bytes`", other),
+                        )
+                        .at(entry.subject.clone()),
+                    );
+                    Vec::new()
+                }
+            };
+            for mut diagnostic in delegated {
+                diagnostic.location = Some(match diagnostic.location {
+                    Some(location) => format!("{}:{}", entry.subject, location),
+                    None => entry.subject.clone(),
+                });
+                diagnostics.push(diagnostic);
+            }
+        }
+
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for SchemaRegistryProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .source(SourceKind::Inline)
+            .source(SourceKind::File)
+            .live("http://")
+            .live("https://")
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+/// Fusabi type of an Avro primitive
+fn avro_primitive(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "null" => "unit",
+        "boolean" => "bool",
+        "int" | "long" => "int",
+        "float" | "double" => "float",
+        "bytes" => "bytes",
+        "string" => "string",
+        _ => return None,
+    })
+}
+
+/// Name of the first top-level message in a proto file
+fn first_message(proto: &str) -> Option<&str> {
+    let mut depth = 0usize;
+    let mut tokens = proto
+        .lines()
+        .map(|line| line.split("//").next().unwrap_or(""))
+        .flat_map(|line| line.split_inclusive(['{', '}']))
+        .flat_map(str::split_whitespace);
+
+    while let Some(token) = tokens.next() {
+        if token == "message" && depth == 0 {
+            return tokens.next().map(|name| name.trim_end_matches('{'));
+        }
+        depth += token.matches('{').count();
+        depth = depth.saturating_sub(token.matches('}').count());
+    }
+    None
+}
+
+fn matches_filter(patterns: &[&str], subject: &str) -> bool {
+    patterns.is_empty()
+        || patterns.iter().any(|pattern| match pattern.strip_suffix('*') {
+            Some(prefix) => subject.starts_with(prefix),
+            None => subject == *pattern,
+        })
+}
+
+/// Percent-encode a subject for use as a URL path segment
+fn encode_segment(segment: &str) -> String {
+    let mut encoded = String::new();
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+fn subject_error(entry: &SubjectVersion, error: impl std::fmt::Display) -> ProviderError {
+    ProviderError::ParseError(format!("Invalid schema for subject `{}`: {}", entry.subject, error))
+}
+
+fn type_name(definition: &TypeDefinition) -> &str {
+    match definition {
+        TypeDefinition::Record(r) => &r.name,
+        TypeDefinition::Du(d) => &d.name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    fn export() -> String {
+        let order = r#"{"type":"record","name":"Order","namespace":"shop","fields":[{"name":"id","type":"string"},{"name":"status","type":{"type":"enum","name":"Status","symbols":["NEW","PAID"]}}]}"#;
+        let payment = "syntax = \"proto3\";\npackage shop;\n\nmessage Payment {\n  string id = 1;\n  Amount amount = 2;\n}\n\nmessage Amount {\n  int64 cents = 1;\n}\n";
+        let customer = r#"{"title":"Customer","type":"object","properties":{"name":{"type":"string"}},"required":["name"]}"#;
+        serde_json::json!([
+            {"subject": "orders-key", "version": 1, "id": 1, "schema": "\"string\""},
+            {"subject": "orders-value", "version": 3, "id": 7, "schema": order},
+            {"subject": "payments-value", "version": 1, "id": 9, "schemaType": "PROTOBUF", "schema": payment},
+            {"subject": "shop.Customer", "version": 2, "id": 11, "schemaType": "JSON", "schema": customer,
+             "references": [{"name": "address.json", "subject": "address", "version": 1}]}
+        ])
+        .to_string()
+    }
+
+    fn module<'a>(types: &'a GeneratedTypes, topic: &str) -> &'a GeneratedModule {
+        types
+            .modules
+            .iter()
+            .find(|m| m.path.last().map(String::as_str) == Some(topic))
+            .unwrap_or_else(|| panic!("Expected module {}", topic))
+    }
+
+    fn message_fields(module: &GeneratedModule) -> Vec<(String, String)> {
+        let Some(TypeDefinition::Record(message)) = module.types.last() else {
+            panic!("Expected Message record");
+        };
+        assert_eq!(message.name, "Message");
+        message.fields.iter().map(|(name, ty)| (name.clone(), ty.to_string())).collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect()
+    }
+
+    #[test]
+    fn test_generate_topics() {
+        let provider = SchemaRegistryProvider::new();
+        let schema = provider.resolve_schema(&export(), &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Topics").unwrap();
+        assert_eq!(types.modules.len(), 3);
+
+        let orders = module(&types, "Orders");
+        assert_eq!(orders.path, vec!["Topics", "Orders"]);
+        let names: Vec<&str> = orders.types.iter().map(type_name).collect();
+        assert_eq!(names, ["Status", "Order", "Message"]);
+        assert_eq!(message_fields(orders), pairs(&[("key", "string"), ("value", "Order")]));
+
+        let payments = module(&types, "Payments");
+        assert_eq!(message_fields(payments), pairs(&[("key", "bytes"), ("value", "Payment")]));
+
+        let customers = module(&types, "ShopCustomer");
+        assert!(customers.types.iter().any(|t| type_name(t) == "Value"));
+        assert_eq!(message_fields(customers), pairs(&[("key", "bytes"), ("value", "Value")]));
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/schema-registry.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_subjects_filter() {
+        let provider = SchemaRegistryProvider::new();
+        let params = ProviderParams::default().with("subjects", "orders-*");
+        let schema = provider.resolve_schema(&export(), &params).unwrap();
+        let types = provider.generate_types(&schema, "Topics").unwrap();
+        assert_eq!(types.modules.len(), 1);
+
+        let params = ProviderParams::default().with("subjects", "invoices-*");
+        assert!(provider.resolve_schema(&export(), &params).is_err());
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = SchemaRegistryProvider::new();
+        let report = provider.validate_source(&export(), &ProviderParams::default());
+        assert!(report.is_valid());
+
+        let codes: Vec<(&str, Option<&str>)> = report
+            .diagnostics
+            .iter()
+            .map(|d| (d.code.as_str(), d.location.as_deref()))
+            .collect();
+        assert!(codes.contains(&("subject-strategy", Some("shop.Customer"))));
+        assert!(codes.contains(&("reference-unresolved", Some("shop.Customer"))));
+
+        let invalid = r#"[{"subject": "bad-value", "schema": "{\"type\": \"record\"}"}]"#;
+        assert!(provider.resolve_schema(invalid, &ProviderParams::default()).is_err());
+    }
+
+    /// Serve canned JSON bodies by request path until the listener is dropped
+    fn serve(routes: Vec<(&'static str, String)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                let mut buf = [0u8; 4096];
+                let n = stream.read(&mut buf).unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let path = request.split_whitespace().nth(1).unwrap_or("");
+                let response = match routes.iter().find(|(route, _)| *route == path) {
+                    Some((_, body)) => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    ),
+                    None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+                };
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    #[test]
+    fn test_fetch_from_registry() {
+        let entries: Vec<Value> = serde_json::from_str(&export()).unwrap();
+        let base = serve(vec![
+            ("/subjects", r#"["orders-key","orders-value","shop.Customer"]"#.to_string()),
+            ("/subjects/orders-key/versions/latest", entries[0].to_string()),
+            ("/subjects/orders-value/versions/latest", entries[1].to_string()),
+        ]);
+
+        let params = ProviderParams::default()
+            .with("refresh", "always")
+            .with("cache_dir", std::env::temp_dir().join("fusabi-schema-registry-tests").to_str().unwrap())
+            .with("subjects", "orders-*");
+        let provider = SchemaRegistryProvider::new();
+        let schema = provider.resolve_schema(&base, &params).unwrap();
+        let types = provider.generate_types(&schema, "Topics").unwrap();
+        assert_eq!(types.modules.len(), 1);
+        assert_eq!(message_fields(&types.modules[0]), pairs(&[("key", "string"), ("value", "Order")]));
+
+        assert_eq!(encode_segment("shop.Customer/v1"), "shop.Customer%2Fv1");
+    }
+}
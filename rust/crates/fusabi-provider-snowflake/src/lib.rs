@@ -0,0 +1,864 @@
+//! Snowflake Type Provider
+//!
+//! Generates Fusabi types from Snowflake tables, views and stages. The
+//! generic SQL provider reads `NUMBER(38,0)` as a float and has no answer
+//! for `VARIANT`, `OBJECT` and `ARRAY`, or for views, whose `GET_DDL`
+//! output lists columns without types.
+//!
+//! # Sources
+//!
+//! - A `snowflake://<account>/<database>[/<schema>[/<tables>]]` URI:
+//!   columns are read from `INFORMATION_SCHEMA` and stages from `SHOW
+//!   STAGES` through the SQL API. Tables may be named in the path,
+//!   `snowflake://myorg-acme/SHOP/SALES/ORDERS,CUSTOMERS`. The `auth`
+//!   param references an OAuth token, or a key-pair JWT with
+//!   `token_type=KEYPAIR_JWT`
+//! - `GET_DDL` output, inline or from a file: `CREATE TABLE`, `VIEW` and
+//!   `STAGE` statements. View column types come from the view's query,
+//!   from casts and from the columns of tables defined earlier in the dump
+//!
+//! # Generated Types
+//!
+//! Each table and view becomes a record, in the `<namespace>` module or,
+//! when there are several schemas, in `<namespace>.<Schema>`. A nullable
+//! column is `T option`. Upper-case column names are lowercased:
+//! `ORDER_ID` is `order_id`, `"orderId"` stays `orderId`.
+//!
+//! | Snowflake                                 | Fusabi                  |
+//! |-------------------------------------------|-------------------------|
+//! | `NUMBER(p,0)`, p ≤ 9                      | `int`                   |
+//! | `NUMBER(p,0)`, `INT`, `BIGINT`            | `int64`                 |
+//! | `NUMBER(p,s)`, s > 0, `DECFLOAT`          | `float`                 |
+//! | `FLOAT`, `DOUBLE`, `REAL`                 | `float`                 |
+//! | `VARCHAR`, `STRING`, `TEXT`, `CHAR`       | `string`                |
+//! | `BINARY`, `VARBINARY`                     | `bytes`                 |
+//! | `BOOLEAN`                                 | `bool`                  |
+//! | `DATE`, `TIME`, `TIMESTAMP_*`             | `string`                |
+//! | `GEOGRAPHY`, `GEOMETRY`                   | `string`                |
+//! | `VARIANT`                                 | `any`                   |
+//! | `OBJECT`                                  | `Map<string, any>`      |
+//! | `OBJECT(a A, b B)`                        | record `<Table><Column>` |
+//! | `ARRAY`                                   | `any list`              |
+//! | `ARRAY(T)`                                | `T list`                |
+//! | `MAP(K, V)`                               | `Map<K, V>`             |
+//! | `VECTOR(INT, n)`, `VECTOR(FLOAT, n)`      | `int list`, `float list` |
+//!
+//! Each module with stages gets a `Stage` union of them. A stage with a
+//! directory table also gets a `<Stage>File` record of its rows.
+//!
+//! # Params
+//!
+//! | Param        | Meaning                                             |
+//! |--------------|-----------------------------------------------------|
+//! | `table`      | Comma-separated tables and views to keep            |
+//! | `warehouse`  | Warehouse to run the introspection queries in       |
+//! | `role`       | Role to run them as                                 |
+//! | `token_type` | `OAUTH` (default), `KEYPAIR_JWT` or `PROGRAMMATIC_ACCESS_TOKEN` |
+//! | `endpoint`   | SQL API base URL, for private connectivity           |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_snowflake::SnowflakeProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = SnowflakeProvider::new();
+//! let params = ProviderParams::default()
+//!     .with("auth", "env:SNOWFLAKE_TOKEN")
+//!     .with("warehouse", "REPORTING");
+//! let schema = provider.resolve_schema("snowflake://myorg-acme/SHOP/SALES", &params)?;
+//! let types = provider.generate_types(&schema, "Shop")?;
+//! ```
+
+mod parser;
+mod types;
+
+pub use parser::{parse_ddl, parse_type};
+pub use types::{Column, SfArg, SfType, SnowflakeSchema, Stage, Table};
+
+use fusabi_provider_common::{
+    read_source, Capabilities, CredentialResolver, DescribedProvider, Diagnostic, OutputFeature,
+    ParamKind, ParamSchema, ParamSpec, ParameterizedProvider, SourceOptions, SourceValidator,
+    StructuredError, TypeShape, TypedParams,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, DuDef, VariantDef, TypeDefinition, TypeExpr,
+    ProviderError, ProviderResult,
+};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::Read;
+use std::time::{Duration, Instant};
+
+/// Columns of every table and view in the database, in table order
+const COLUMNS_QUERY: &str = "SELECT c.TABLE_SCHEMA, c.TABLE_NAME, t.TABLE_TYPE, c.COLUMN_NAME, c.DATA_TYPE, \
+     c.IS_NULLABLE, c.NUMERIC_PRECISION, c.NUMERIC_SCALE \
+     FROM INFORMATION_SCHEMA.COLUMNS c JOIN INFORMATION_SCHEMA.TABLES t \
+     ON t.TABLE_SCHEMA = c.TABLE_SCHEMA AND t.TABLE_NAME = c.TABLE_NAME \
+     WHERE c.TABLE_SCHEMA <> 'INFORMATION_SCHEMA'";
+
+/// A row of a statement's result, by lower-case column name
+type Row = HashMap<String, Option<String>>;
+
+/// Snowflake type provider
+pub struct SnowflakeProvider {
+    generator: TypeGenerator,
+}
+
+impl SnowflakeProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<SnowflakeSchema> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected Snowflake schema: {}", e)))
+    }
+
+    /// Read the tables, views and stages of a live database
+    fn fetch_schema(&self, uri: &str, params: &ProviderParams, typed: &TypedParams) -> ProviderResult<SnowflakeSchema> {
+        let mut parts = uri.trim_start_matches("snowflake://").splitn(4, '/');
+        let account = parts.next().unwrap_or_default();
+        let database = parts.next().unwrap_or_default();
+        if account.is_empty() || database.is_empty() {
+            return Err(ProviderError::InvalidSource(format!(
+                "Expected snowflake://<account>/<database>[/<schema>], got {}",
+                uri
+            )));
+        }
+        let schema_name = parts.next().filter(|s| !s.is_empty());
+        let tables_in_path: Vec<&str> = parts.next().unwrap_or_default().split(',').filter(|t| !t.is_empty()).collect();
+
+        let token = CredentialResolver::new().from_params(params)?.ok_or_else(|| {
+            ProviderError::InvalidSource("Snowflake needs a token: set `auth`".to_string())
+        })?;
+        let api = SqlApi {
+            base: typed
+                .str("endpoint")
+                .map(|e| e.trim_end_matches('/').to_string())
+                .unwrap_or_else(|| format!("https://{}.snowflakecomputing.com", account)),
+            token: token.expose().to_string(),
+            token_type: typed.str("token_type").unwrap_or("OAUTH").to_string(),
+            database: database.to_string(),
+            schema: schema_name.map(str::to_string),
+            warehouse: typed.str("warehouse").map(str::to_string),
+            role: typed.str("role").map(str::to_string),
+            options: SourceOptions::from_params(params)?,
+        };
+
+        let mut columns_query = COLUMNS_QUERY.to_string();
+        let mut bindings = Vec::new();
+        if let Some(schema_name) = schema_name {
+            columns_query.push_str(" AND c.TABLE_SCHEMA = ?");
+            bindings.push(schema_name);
+        }
+        columns_query.push_str(" ORDER BY c.TABLE_SCHEMA, c.TABLE_NAME, c.ORDINAL_POSITION");
+
+        let mut schema = SnowflakeSchema::default();
+        for row in api.rows(&columns_query, &bindings)? {
+            let text = |key: &str| row.get(key).cloned().flatten().unwrap_or_default();
+            let (table_schema, table) = (text("table_schema"), text("table_name"));
+            if !tables_in_path.is_empty() && !tables_in_path.iter().any(|t| t.eq_ignore_ascii_case(&table)) {
+                continue;
+            }
+            let data_type = match (text("data_type").as_str(), text("numeric_precision"), text("numeric_scale")) {
+                ("NUMBER", precision, scale) if !precision.is_empty() => {
+                    format!("NUMBER({},{})", precision, if scale.is_empty() { "0".to_string() } else { scale })
+                }
+                (data_type, _, _) => data_type.to_string(),
+            };
+            let column = Column { name: text("column_name"), data_type, nullable: text("is_nullable") != "NO" };
+            match schema.tables.last_mut() {
+                Some(last) if last.name == table && last.schema.as_deref() == Some(table_schema.as_str()) => {
+                    last.columns.push(column)
+                }
+                _ => schema.tables.push(Table {
+                    schema: Some(table_schema),
+                    view: text("table_type").contains("VIEW"),
+                    name: table,
+                    columns: vec![column],
+                }),
+            }
+        }
+
+        let stages = match schema_name {
+            Some(schema_name) => format!("SHOW STAGES IN SCHEMA {}.{}", quote(database), quote(schema_name)),
+            None => format!("SHOW STAGES IN DATABASE {}", quote(database)),
+        };
+        for row in api.rows(&stages, &[])? {
+            let text = |key: &str| row.get(key).cloned().flatten().filter(|v| !v.is_empty());
+            schema.stages.push(Stage {
+                schema: text("schema_name"),
+                name: text("name").unwrap_or_default(),
+                url: text("url"),
+                directory: text("directory_enabled").as_deref() == Some("Y"),
+            });
+        }
+        Ok(schema)
+    }
+
+    /// The modules of a schema, with the types of each and what was lost
+    /// on the way
+    fn generate(&self, schema: &SnowflakeSchema, namespace: &str) -> (Vec<GeneratedModule>, Vec<Diagnostic>) {
+        let mut schemas: Vec<Option<&str>> = schema
+            .tables
+            .iter()
+            .map(|t| t.schema.as_deref())
+            .chain(schema.stages.iter().map(|s| s.schema.as_deref()))
+            .collect();
+        schemas.sort();
+        schemas.dedup();
+        let path = |name: Option<&str>| match name {
+            Some(name) if schemas.len() > 1 => vec![namespace.to_string(), self.generator.naming.apply(name)],
+            _ => vec![namespace.to_string()],
+        };
+
+        let mut generation = Generation {
+            generator: &self.generator,
+            types: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+        let mut modules: Vec<GeneratedModule> = Vec::new();
+        for &name in &schemas {
+            for table in schema.tables.iter().filter(|t| t.schema.as_deref() == name) {
+                generation.table(table);
+            }
+            let stages: Vec<&Stage> = schema.stages.iter().filter(|s| s.schema.as_deref() == name).collect();
+            if !stages.is_empty() {
+                generation.stages(&stages);
+            }
+
+            let path = path(name);
+            let types = std::mem::take(&mut generation.types);
+            match modules.iter_mut().find(|m| m.path == path) {
+                Some(module) => module.types.extend(types),
+                None => {
+                    let mut module = GeneratedModule::new(path);
+                    module.types = types;
+                    modules.push(module);
+                }
+            }
+        }
+        (modules, generation.diagnostics)
+    }
+}
+
+impl Default for SnowflakeProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for SnowflakeProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("SnowflakeProvider")
+            .param(ParamSpec::new("table", ParamKind::List).describe("Tables and views to keep"))
+            .param(
+                ParamSpec::new("warehouse", ParamKind::String)
+                    .describe("Warehouse to run the introspection queries in"),
+            )
+            .param(
+                ParamSpec::new("role", ParamKind::String).describe("Role to run the introspection queries as"),
+            )
+            .param(
+                ParamSpec::new("token_type", ParamKind::Choice(&["OAUTH", "KEYPAIR_JWT", "PROGRAMMATIC_ACCESS_TOKEN"]))
+                    .default("OAUTH")
+                    .describe("Kind of token in the credential"),
+            )
+            .param(
+                ParamSpec::new("endpoint", ParamKind::String)
+                    .describe("SQL API base URL, for private connectivity"),
+            )
+    }
+}
+
+impl TypeProvider for SnowflakeProvider {
+    fn name(&self) -> &str {
+        "SnowflakeProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let trimmed = source.trim();
+        let mut schema = if trimmed.starts_with("snowflake://") {
+            self.fetch_schema(trimmed, params, &typed)?
+        } else if trimmed.to_uppercase().contains("CREATE ") {
+            parse_ddl(source)?
+        } else {
+            parse_ddl(&read_source(source, params)?)?
+        };
+
+        if let Some(filter) = typed.list("table") {
+            schema.tables.retain(|t| filter.iter().any(|f| f.eq_ignore_ascii_case(&t.name)));
+        }
+
+        let json = serde_json::to_string(&schema)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected Snowflake schema".to_string()));
+        };
+
+        let mut result = GeneratedTypes::new();
+        result.modules = self.generate(&self.parse_schema(json)?, namespace).0;
+        Ok(result)
+    }
+}
+
+impl SourceValidator for SnowflakeProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected Snowflake schema".to_string()));
+        };
+
+        let parsed = self.parse_schema(json)?;
+        let mut diagnostics = Vec::new();
+        if parsed.tables.is_empty() {
+            diagnostics.push(Diagnostic::warning("no-tables", "No tables or views found"));
+        }
+        diagnostics.extend(self.generate(&parsed, "Snowflake").1);
+        for statement in &parsed.skipped {
+            diagnostics.push(Diagnostic::info(
+                "statement-skipped",
+                format!("{} statement does not define a table, view or stage", statement),
+            ));
+        }
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for SnowflakeProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .live("snowflake://")
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+/// Types generated for a module, with what was lost on the way
+struct Generation<'a> {
+    generator: &'a TypeGenerator,
+    types: Vec<TypeDefinition>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Generation<'_> {
+    fn table(&mut self, table: &Table) {
+        let record = self.generator.naming.apply(&table.name);
+        let mut fields = Vec::new();
+        for column in &table.columns {
+            let location = format!("{}.{}", table.name, column.name);
+            let shape = if column.data_type.is_empty() {
+                self.diagnostics.push(
+                    Diagnostic::warning(
+                        "untyped-column",
+                        "Column type can't be worked out from the view's query; cast it there to type it",
+                    )
+                    .at(location.clone()),
+                );
+                TypeShape::Named("any".to_string())
+            } else {
+                match parse_type(&column.data_type) {
+                    Ok(ty) => {
+                        let name = format!("{}{}", record, self.generator.naming.apply(&column.name));
+                        self.shape(&ty, &name, &location)
+                    }
+                    Err(e) => {
+                        self.diagnostics.push(
+                            Diagnostic::warning("unmapped-type", format!("Unreadable type `{}`: {}", column.data_type, e))
+                                .at(location.clone()),
+                        );
+                        TypeShape::Named("any".to_string())
+                    }
+                }
+            };
+            let shape = if column.nullable { TypeShape::Option(Box::new(shape)) } else { shape };
+            fields.push((field_name(&column.name), shape.into()));
+        }
+        self.types.push(TypeDefinition::Record(RecordDef { name: record, fields }));
+    }
+
+    /// The `Stage` union, and the directory table rows of those that have one
+    fn stages(&mut self, stages: &[&Stage]) {
+        let variants = stages.iter().map(|s| VariantDef::new_simple(self.generator.naming.apply(&s.name))).collect();
+        self.types.push(TypeDefinition::Du(DuDef { name: "Stage".to_string(), variants }));
+
+        for stage in stages.iter().filter(|s| s.directory) {
+            let fields = [
+                ("relative_path", "string"),
+                ("size", "int64"),
+                ("last_modified", "string"),
+                ("md5", "string option"),
+                ("etag", "string option"),
+                ("file_url", "string"),
+            ];
+            self.types.push(TypeDefinition::Record(RecordDef {
+                name: format!("{}File", self.generator.naming.apply(&stage.name)),
+                fields: fields.iter().map(|(f, ty)| (f.to_string(), TypeShape::parse(ty).into())).collect(),
+            }));
+        }
+    }
+
+    /// Map a type; `name` names the record a structured `OBJECT` needs
+    fn shape(&mut self, ty: &SfType, name: &str, location: &str) -> TypeShape {
+        let named = |name: &str| TypeShape::Named(name.to_string());
+
+        match ty.name.as_str() {
+            "NUMBER" | "DECIMAL" | "NUMERIC" => {
+                let mut literals = ty.literals();
+                let precision = literals.next().unwrap_or(38);
+                let scale = literals.next().unwrap_or(0);
+                if scale > 0 {
+                    self.lossy(location, format!("Fixed-point `{}` is typed as `float`", ty));
+                    named("float")
+                } else if precision <= 9 {
+                    named("int")
+                } else {
+                    named("int64")
+                }
+            }
+            "INT" | "INTEGER" | "BIGINT" | "SMALLINT" | "TINYINT" | "BYTEINT" => named("int64"),
+            "FLOAT" | "FLOAT4" | "FLOAT8" | "DOUBLE" | "DOUBLE PRECISION" | "REAL" => named("float"),
+            "DECFLOAT" => {
+                self.lossy(location, "`DECFLOAT` is typed as `float`".to_string());
+                named("float")
+            }
+            "VARCHAR" | "STRING" | "TEXT" | "CHAR" | "CHARACTER" | "NCHAR" | "NVARCHAR" | "NVARCHAR2"
+            | "CHAR VARYING" | "NCHAR VARYING" => named("string"),
+            "BINARY" | "VARBINARY" => named("bytes"),
+            "BOOLEAN" => named("bool"),
+            "DATE" | "DATETIME" | "TIME" | "TIMESTAMP" | "TIMESTAMP_LTZ" | "TIMESTAMP_NTZ" | "TIMESTAMP_TZ"
+            | "GEOGRAPHY" | "GEOMETRY" => named("string"),
+            "VARIANT" => self.coerced(location, "Semi-structured `VARIANT` is typed as `any`", named("any")),
+            "OBJECT" if ty.args.is_empty() => self.coerced(
+                location,
+                "Semi-structured `OBJECT` is typed as `Map<string, any>`",
+                TypeShape::Map(Box::new(named("string")), Box::new(named("any"))),
+            ),
+            "OBJECT" => self.record(ty, name, location),
+            "ARRAY" => match ty.type_args().next() {
+                Some(element) => TypeShape::List(Box::new(self.shape(element, name, location))),
+                None => self.coerced(
+                    location,
+                    "Semi-structured `ARRAY` is typed as `any list`",
+                    TypeShape::List(Box::new(named("any"))),
+                ),
+            },
+            "MAP" => {
+                let mut args = ty.type_args();
+                match (args.next(), args.next()) {
+                    (Some(key), Some(value)) => TypeShape::Map(
+                        Box::new(self.shape(key, &format!("{}Key", name), location)),
+                        Box::new(self.shape(value, &format!("{}Value", name), location)),
+                    ),
+                    _ => self.unmapped(ty, location),
+                }
+            }
+            "VECTOR" => match ty.type_args().next().map(|t| t.name.as_str()) {
+                Some("INT") => TypeShape::List(Box::new(named("int"))),
+                Some("FLOAT") => TypeShape::List(Box::new(named("float"))),
+                _ => self.unmapped(ty, location),
+            },
+            _ => self.unmapped(ty, location),
+        }
+    }
+
+    /// A record for a structured `OBJECT`
+    fn record(&mut self, ty: &SfType, name: &str, location: &str) -> TypeShape {
+        let mut fields = Vec::new();
+        for arg in &ty.args {
+            let SfArg::Field { name: field, ty: field_type, nullable } = arg else {
+                continue;
+            };
+            let nested = format!("{}{}", name, self.generator.naming.apply(field));
+            let shape = self.shape(field_type, &nested, &format!("{}.{}", location, field));
+            let shape = if *nullable { TypeShape::Option(Box::new(shape)) } else { shape };
+            fields.push((field_name(field), shape.into()));
+        }
+        self.types.push(TypeDefinition::Record(RecordDef {
+            name: name.to_string(),
+            fields,
+        }));
+        TypeShape::Named(name.to_string())
+    }
+
+    fn coerced(&mut self, location: &str, message: &str, shape: TypeShape) -> TypeShape {
+        self.diagnostics
+            .push(Diagnostic::info("coerced-any", message).at(location.to_string()));
+        shape
+    }
+
+    fn lossy(&mut self, location: &str, message: String) {
+        self.diagnostics
+            .push(Diagnostic::info("lossy-conversion", message).at(location.to_string()));
+    }
+
+    fn unmapped(&mut self, ty: &SfType, location: &str) -> TypeShape {
+        self.diagnostics.push(
+            Diagnostic::warning("unmapped-type", format!("Snowflake type `{}` has no Fusabi mapping", ty))
+                .at(location.to_string()),
+        );
+        TypeShape::Named("any".to_string())
+    }
+}
+
+/// `ORDER_ID` → `order_id`; names with lower-case letters were quoted
+/// and are kept
+fn field_name(name: &str) -> String {
+    if name.chars().any(|c| c.is_lowercase()) {
+        name.to_string()
+    } else {
+        name.to_lowercase()
+    }
+}
+
+/// A double-quoted identifier
+fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+/// A connection to the SQL API
+struct SqlApi {
+    base: String,
+    token: String,
+    token_type: String,
+    database: String,
+    schema: Option<String>,
+    warehouse: Option<String>,
+    role: Option<String>,
+    options: SourceOptions,
+}
+
+impl SqlApi {
+    /// Run a statement and read every partition of its result
+    fn rows(&self, sql: &str, bindings: &[&str]) -> ProviderResult<Vec<Row>> {
+        let agent = ureq::AgentBuilder::new().timeout(self.options.fetch_timeout).redirects(0).build();
+        let bindings: serde_json::Map<String, Value> = bindings
+            .iter()
+            .enumerate()
+            .map(|(i, value)| ((i + 1).to_string(), json!({"type": "TEXT", "value": value})))
+            .collect();
+        let body = json!({
+            "statement": sql,
+            "timeout": self.options.fetch_timeout.as_secs().max(1),
+            "database": self.database,
+            "schema": self.schema,
+            "warehouse": self.warehouse,
+            "role": self.role,
+            "bindings": bindings,
+        });
+
+        let statements = format!("{}/api/v2/statements", self.base);
+        let mut result = self.read(self.request(agent.post(&statements)).send_json(body))?;
+        // 202: still running; poll its handle until it finishes
+        let started = Instant::now();
+        while result.0 == 202 {
+            if started.elapsed() > self.options.fetch_timeout {
+                return Err(StructuredError::network("Snowflake statement exceeded fetch_timeout").into());
+            }
+            std::thread::sleep(Duration::from_millis(250));
+            let handle = handle(&result.1)?;
+            result = self.read(self.request(agent.get(&format!("{}/{}", statements, handle))).call())?;
+        }
+        let first = result.1;
+
+        let meta = first.get("resultSetMetaData");
+        let names: Vec<String> = meta
+            .and_then(|m| m.get("rowType"))
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .map(|column| column.get("name").and_then(Value::as_str).unwrap_or_default().to_ascii_lowercase())
+            .collect();
+        let partitions = meta
+            .and_then(|m| m.get("partitionInfo"))
+            .and_then(Value::as_array)
+            .map_or(1, Vec::len);
+
+        let mut rows = Vec::new();
+        let mut data = first.get("data").cloned();
+        for partition in 0..partitions {
+            if partition > 0 {
+                let url = format!("{}/{}?partition={}", statements, handle(&first)?, partition);
+                data = self.read(self.request(agent.get(&url)).call())?.1.get("data").cloned();
+            }
+            for row in data.iter().filter_map(Value::as_array).flatten().filter_map(Value::as_array) {
+                rows.push(
+                    names
+                        .iter()
+                        .zip(row)
+                        .map(|(name, value)| (name.clone(), value.as_str().map(str::to_string)))
+                        .collect(),
+                );
+            }
+        }
+        Ok(rows)
+    }
+
+    fn request(&self, request: ureq::Request) -> ureq::Request {
+        request
+            .set("Authorization", &format!("Bearer {}", self.token))
+            .set("X-Snowflake-Authorization-Token-Type", &self.token_type)
+            .set("Accept", "application/json")
+            .set("User-Agent", "fusabi-provider-snowflake")
+    }
+
+    /// The status and JSON body of a response
+    fn read(&self, response: Result<ureq::Response, ureq::Error>) -> ProviderResult<(u16, Value)> {
+        let response = response.map_err(|e| match e {
+            ureq::Error::Status(code, response) => {
+                let body: Value = response.into_json().unwrap_or_default();
+                ProviderError::from(StructuredError::network(format!(
+                    "Snowflake statement failed with HTTP {}: {}",
+                    code,
+                    body.get("message").and_then(Value::as_str).unwrap_or_default()
+                )))
+            }
+            ureq::Error::Transport(t) => StructuredError::network("Snowflake statement failed").with_source(t).into(),
+        })?;
+
+        let status = response.status();
+        let mut buf = Vec::new();
+        response
+            .into_reader()
+            .take(self.options.max_source_bytes + 1)
+            .read_to_end(&mut buf)
+            .map_err(|e| StructuredError::network("Failed to read Snowflake response").with_source(e))?;
+        if buf.len() as u64 > self.options.max_source_bytes {
+            return Err(StructuredError::validation(format!(
+                "Snowflake response exceeds max_source_bytes ({})",
+                self.options.max_source_bytes
+            ))
+            .into());
+        }
+        let body = serde_json::from_slice(&buf)
+            .map_err(|e| StructuredError::parse("Invalid Snowflake response").with_source(e))?;
+        Ok((status, body))
+    }
+}
+
+fn handle(response: &Value) -> ProviderResult<&str> {
+    response
+        .get("statementHandle")
+        .and_then(Value::as_str)
+        .ok_or_else(|| ProviderError::ParseError("Snowflake response has no statementHandle".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+    use std::io::Write;
+    use std::net::TcpListener;
+
+    const DDL: &str = r#"
+create or replace schema SHOP.SALES;
+
+create or replace TABLE ORDERS (
+	ID NUMBER(38,0) NOT NULL,
+	"customerId" NUMBER(9,0),
+	AMOUNT NUMBER(12,2),
+	PAYLOAD VARIANT,
+	ATTRIBUTES OBJECT,
+	TAGS ARRAY,
+	ADDRESS OBJECT(city VARCHAR, zip VARCHAR NOT NULL) NOT NULL,
+	LINES ARRAY(OBJECT(sku VARCHAR NOT NULL, qty NUMBER(9,0) NOT NULL)),
+	EMBEDDING VECTOR(FLOAT, 256),
+	PLACED_AT TIMESTAMP_NTZ(9) NOT NULL,
+	SHAPE RING
+);
+
+create or replace view BIG_ORDERS(
+	ID,
+	CITY,
+	NOTE
+) as
+select id, address:city::varchar, upper(payload:note) from orders where amount > 100;
+
+create or replace stage RAW url = 's3://acme/raw/' directory = (enable = true);
+create or replace stage SCRATCH;
+create or replace sequence ORDER_SEQ start 1;
+"#;
+
+    fn record<'a>(types: &'a GeneratedTypes, name: &str) -> &'a RecordDef {
+        types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+    }
+
+    fn fields(record: &RecordDef) -> Vec<(String, String)> {
+        record.fields.iter().map(|(name, ty)| (name.clone(), ty.to_string())).collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect()
+    }
+
+    #[test]
+    fn test_snowflake_types() {
+        let provider = SnowflakeProvider::new();
+        let schema = provider.resolve_schema(DDL, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Shop").unwrap();
+
+        assert_eq!(types.modules.len(), 1);
+        assert_eq!(types.modules[0].path, ["Shop"]);
+        assert_eq!(
+            fields(record(&types, "Orders")),
+            pairs(&[
+                ("id", "int64"),
+                ("customerId", "int option"),
+                ("amount", "float option"),
+                ("payload", "any option"),
+                ("attributes", "Map<string, any> option"),
+                ("tags", "any list option"),
+                ("address", "OrdersAddress"),
+                ("lines", "OrdersLines list option"),
+                ("embedding", "float list option"),
+                ("placed_at", "string"),
+                ("shape", "any option"),
+            ])
+        );
+        assert_eq!(
+            fields(record(&types, "OrdersAddress")),
+            pairs(&[("city", "string option"), ("zip", "string")])
+        );
+        assert_eq!(fields(record(&types, "OrdersLines")), pairs(&[("sku", "string"), ("qty", "int")]));
+        assert_eq!(
+            fields(record(&types, "BigOrders")),
+            pairs(&[("id", "int64"), ("city", "string option"), ("note", "any option")])
+        );
+
+        let stage = types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Du(du) if du.name == "Stage" => Some(du),
+                _ => None,
+            })
+            .unwrap();
+        let cases: Vec<&str> = stage.variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(cases, ["Raw", "Scratch"]);
+        assert_eq!(record(&types, "RawFile").fields[0].0, "relative_path");
+        assert!(!types.modules[0].types.iter().any(|t| matches!(t, TypeDefinition::Record(r) if r.name == "ScratchFile")));
+
+        let params = ProviderParams::default().with("table", "big_orders");
+        let schema = provider.resolve_schema(DDL, &params).unwrap();
+        let types = provider.generate_types(&schema, "Shop").unwrap();
+        assert!(types.modules[0].types.iter().all(|t| !matches!(t, TypeDefinition::Record(r) if r.name == "Orders")));
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/snowflake.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = SnowflakeProvider::new();
+        let report = provider.validate_source(DDL, &ProviderParams::default());
+        assert!(report.is_valid());
+
+        let codes: Vec<(&str, Option<&str>)> =
+            report.diagnostics.iter().map(|d| (d.code.as_str(), d.location.as_deref())).collect();
+        assert!(codes.contains(&("lossy-conversion", Some("ORDERS.AMOUNT"))));
+        assert!(codes.contains(&("coerced-any", Some("ORDERS.PAYLOAD"))));
+        assert!(codes.contains(&("coerced-any", Some("ORDERS.TAGS"))));
+        assert!(codes.contains(&("unmapped-type", Some("ORDERS.SHAPE"))));
+        assert!(codes.contains(&("untyped-column", Some("BIG_ORDERS.NOTE"))));
+        assert!(codes.contains(&("statement-skipped", None)));
+        assert!(!codes.contains(&("coerced-any", Some("ORDERS.ADDRESS"))));
+    }
+
+    #[test]
+    fn test_fetch_information_schema() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let bodies = [
+                r#"{"statementHandle": "01b2", "resultSetMetaData": {
+                    "partitionInfo": [{"rowCount": 2}, {"rowCount": 1}],
+                    "rowType": [{"name": "TABLE_SCHEMA"}, {"name": "TABLE_NAME"}, {"name": "TABLE_TYPE"},
+                        {"name": "COLUMN_NAME"}, {"name": "DATA_TYPE"}, {"name": "IS_NULLABLE"},
+                        {"name": "NUMERIC_PRECISION"}, {"name": "NUMERIC_SCALE"}]},
+                  "data": [
+                    ["SALES", "ORDERS", "BASE TABLE", "ID", "NUMBER", "NO", "38", "0"],
+                    ["SALES", "ORDERS", "BASE TABLE", "AMOUNT", "NUMBER", "YES", "12", "2"]
+                  ]}"#,
+                r#"{"data": [["SALES", "RECENT", "VIEW", "PAYLOAD", "VARIANT", "YES", null, null]]}"#,
+                r#"{"statementHandle": "01b3", "resultSetMetaData": {
+                    "partitionInfo": [{"rowCount": 1}],
+                    "rowType": [{"name": "created_on"}, {"name": "name"}, {"name": "database_name"},
+                        {"name": "schema_name"}, {"name": "url"}, {"name": "directory_enabled"}]},
+                  "data": [["2024-01-01", "RAW", "SHOP", "SALES", "s3://acme/raw/", "Y"]]}"#,
+            ];
+            let mut requests = Vec::new();
+            for body in bodies {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                // Read headers, then as much body as Content-Length says
+                loop {
+                    let n = stream.read(&mut buf).unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        let len = head
+                            .lines()
+                            .find_map(|l| l.to_ascii_lowercase().strip_prefix("content-length: ").map(String::from))
+                            .and_then(|l| l.trim().parse::<usize>().ok())
+                            .unwrap_or(0);
+                        if n == 0 || body.len() >= len {
+                            break;
+                        }
+                    }
+                }
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+                requests.push(String::from_utf8_lossy(&request).to_string());
+            }
+            requests
+        });
+
+        std::env::set_var("FUSABI_SNOWFLAKE_TEST_TOKEN", "t0ken");
+        let provider = SnowflakeProvider::new();
+        let params = ProviderParams::default()
+            .with("auth", "env:FUSABI_SNOWFLAKE_TEST_TOKEN")
+            .with("endpoint", &format!("http://{}", addr))
+            .with("warehouse", "REPORTING");
+        let schema = provider.resolve_schema("snowflake://acme/SHOP/SALES", &params).unwrap();
+        let types = provider.generate_types(&schema, "Shop").unwrap();
+
+        let requests = server.join().unwrap();
+        let first = requests[0].to_ascii_lowercase();
+        assert!(first.starts_with("post /api/v2/statements "));
+        assert!(first.contains("authorization: bearer t0ken"));
+        assert!(first.contains("x-snowflake-authorization-token-type: oauth"));
+        assert!(requests[0].contains(r#""warehouse":"REPORTING""#));
+        assert!(requests[0].contains(r#""bindings":{"1":{"type":"TEXT","value":"SALES"}}"#));
+        assert!(requests[1].starts_with("GET /api/v2/statements/01b2?partition=1 "));
+        assert!(requests[2].contains(r#"SHOW STAGES IN SCHEMA \"SHOP\".\"SALES\""#));
+
+        assert_eq!(fields(record(&types, "Orders")), pairs(&[("id", "int64"), ("amount", "float option")]));
+        assert_eq!(fields(record(&types, "Recent")), pairs(&[("payload", "any option")]));
+        assert_eq!(record(&types, "RawFile").fields.len(), 6);
+    }
+}
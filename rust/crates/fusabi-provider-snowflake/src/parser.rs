@@ -0,0 +1,808 @@
+//! Snowflake DDL and type parser
+//!
+//! Parses `GET_DDL` output for a database, schema, table or view: `CREATE
+//! TABLE`, `VIEW` and `STAGE` statements, and `CREATE SCHEMA`, which sets
+//! the schema of the unqualified names after it. Constraints, clustering
+//! keys and other options are ignored; other statements are recorded as
+//! skipped.
+//!
+//! Views list their columns without types, so the types come from the
+//! view's query: a cast (`payload:id::NUMBER`, `CAST(x AS VARCHAR)`) gives
+//! its type, and a plain column of a table defined earlier gives that
+//! column's. `CREATE TABLE ... AS SELECT` and dynamic tables are read the
+//! same way. Unaliased expressions in a query have no usable name and are
+//! left out, unless the statement lists its columns.
+
+use crate::types::{Column, SfArg, SfType, SnowflakeSchema, Stage, Table};
+use fusabi_type_providers::{ProviderError, ProviderResult};
+
+/// Parse CREATE statements from string content
+pub fn parse_ddl(content: &str) -> ProviderResult<SnowflakeSchema> {
+    let mut parser = Parser::new(content);
+    parser.parse_schema()
+}
+
+/// Parse a type such as `OBJECT(city VARCHAR, zip VARCHAR NOT NULL)`
+pub fn parse_type(text: &str) -> ProviderResult<SfType> {
+    let mut parser = Parser::new(text);
+    let ty = parser.parse_type_expr()?;
+    match parser.current() {
+        Token::Eof => Ok(ty),
+        other => Err(ProviderError::ParseError(format!(
+            "Unexpected {:?} after type in `{}`",
+            other, text
+        ))),
+    }
+}
+
+/// Keywords that end a column's type
+const COLUMN_OPTIONS: &[&str] = &[
+    "NOT", "NULL", "DEFAULT", "AUTOINCREMENT", "IDENTITY", "COLLATE", "COMMENT", "PRIMARY", "UNIQUE",
+    "FOREIGN", "REFERENCES", "CONSTRAINT", "WITH", "MASKING", "TAG", "AS",
+];
+
+/// Words between `CREATE [OR REPLACE]` and the kind of object
+const MODIFIERS: &[&str] = &[
+    "TRANSIENT", "TEMPORARY", "TEMP", "LOCAL", "GLOBAL", "VOLATILE", "SECURE", "RECURSIVE", "MATERIALIZED",
+    "DYNAMIC", "HYBRID", "ICEBERG", "EXTERNAL",
+];
+
+/// Words that continue a type name: `DOUBLE PRECISION`, `CHAR VARYING`
+const TYPE_WORDS: &[&str] = &["PRECISION", "VARYING"];
+
+/// Keywords that end the `FROM` clause of a query
+const CLAUSES: &[&str] = &[
+    "WHERE", "GROUP", "HAVING", "QUALIFY", "ORDER", "LIMIT", "UNION", "EXCEPT", "INTERSECT", "MINUS", "WINDOW",
+];
+
+/// Words that can't be a table alias
+const NOT_ALIASES: &[&str] = &[
+    "ON", "USING", "JOIN", "LEFT", "RIGHT", "INNER", "OUTER", "FULL", "CROSS", "NATURAL", "LATERAL", "ASOF",
+    "MATCH_CONDITION", "AT", "BEFORE", "CHANGES", "SAMPLE", "TABLESAMPLE", "PIVOT", "UNPIVOT",
+];
+
+/// A query column: its name, if it has a usable one, type and nullability
+type QueryColumn = (Option<String>, String, bool);
+
+/// Simple Snowflake parser
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Schema of unqualified names, from the last `CREATE SCHEMA`
+    schema: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Bare identifier or keyword, case preserved
+    Identifier(String),
+    /// `"quoted"` identifier
+    QuotedIdentifier(String),
+    /// `'text'` or `$$text$$`, unescaped
+    StringLiteral(String),
+    Number(String),
+    Symbol(char),
+    Eof,
+}
+
+impl Token {
+    /// An identifier as Snowflake resolves it: unquoted ones upper case
+    fn name(&self) -> Option<String> {
+        match self {
+            Token::Identifier(s) => Some(s.to_ascii_uppercase()),
+            Token::QuotedIdentifier(s) => Some(s.clone()),
+            _ => None,
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Token::Identifier(s) if s.eq_ignore_ascii_case(keyword))
+    }
+
+    fn is_any_keyword(&self, keywords: &[&str]) -> bool {
+        keywords.iter().any(|k| self.is_keyword(k))
+    }
+}
+
+impl Parser {
+    fn new(content: &str) -> Self {
+        Self::from_tokens(tokenize(content))
+    }
+
+    fn from_tokens(tokens: Vec<Token>) -> Self {
+        Self { tokens, pos: 0, schema: None }
+    }
+
+    fn current(&self) -> &Token {
+        self.tokens.get(self.pos).unwrap_or(&Token::Eof)
+    }
+
+    fn peek(&self, offset: usize) -> &Token {
+        self.tokens.get(self.pos + offset).unwrap_or(&Token::Eof)
+    }
+
+    fn advance(&mut self) {
+        if self.pos < self.tokens.len() {
+            self.pos += 1;
+        }
+    }
+
+    /// Keywords are case-insensitive
+    fn is_keyword(&self, keyword: &str) -> bool {
+        self.current().is_keyword(keyword)
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.is_keyword(keyword);
+        if found {
+            self.advance();
+        }
+        found
+    }
+
+    fn is_symbol(&self, symbol: char) -> bool {
+        self.current() == &Token::Symbol(symbol)
+    }
+
+    fn expect_symbol(&mut self, symbol: char) -> ProviderResult<()> {
+        if self.is_symbol(symbol) {
+            self.advance();
+            Ok(())
+        } else {
+            Err(ProviderError::ParseError(format!(
+                "Expected '{}', got {:?}",
+                symbol,
+                self.current()
+            )))
+        }
+    }
+
+    fn expect_name(&mut self) -> ProviderResult<String> {
+        let name = self.current().name().ok_or_else(|| {
+            ProviderError::ParseError(format!("Expected identifier, got {:?}", self.current()))
+        })?;
+        self.advance();
+        Ok(name)
+    }
+
+    /// `[db.][schema.]name`, with the schema defaulting to the current one
+    fn qualified_name(&mut self) -> ProviderResult<(Option<String>, String)> {
+        let mut parts = vec![self.expect_name()?];
+        while self.is_symbol('.') {
+            self.advance();
+            parts.push(self.expect_name()?);
+        }
+        let name = parts.pop().unwrap_or_default();
+        Ok((parts.pop().or_else(|| self.schema.clone()), name))
+    }
+
+    fn eat_if_not_exists(&mut self) {
+        if self.eat_keyword("if") {
+            self.eat_keyword("not");
+            self.eat_keyword("exists");
+        }
+    }
+
+    /// Skip to the end of the current statement
+    fn skip_statement(&mut self) {
+        while !matches!(self.current(), Token::Symbol(';') | Token::Eof) {
+            self.advance();
+        }
+    }
+
+    /// Skip to the `,` or `)` that ends the current table element
+    fn skip_element(&mut self) {
+        let mut depth = 0usize;
+        loop {
+            match self.current() {
+                Token::Eof => return,
+                Token::Symbol('(') => depth += 1,
+                Token::Symbol(')') if depth == 0 => return,
+                Token::Symbol(')') => depth -= 1,
+                Token::Symbol(',') if depth == 0 => return,
+                _ => {}
+            }
+            self.advance();
+        }
+    }
+
+    fn parse_schema(&mut self) -> ProviderResult<SnowflakeSchema> {
+        let mut schema = SnowflakeSchema::default();
+
+        while self.current() != &Token::Eof {
+            if self.is_symbol(';') {
+                self.advance();
+            } else if self.is_keyword("create") {
+                self.parse_create(&mut schema)?;
+            } else {
+                schema.skipped.push(self.leading_keywords());
+                self.skip_statement();
+            }
+        }
+
+        Ok(schema)
+    }
+
+    fn parse_create(&mut self, schema: &mut SnowflakeSchema) -> ProviderResult<()> {
+        self.advance();
+        if self.eat_keyword("or") && !self.eat_keyword("replace") {
+            return Err(ProviderError::ParseError("Expected REPLACE after CREATE OR".to_string()));
+        }
+        while self.current().is_any_keyword(MODIFIERS) {
+            self.advance();
+        }
+
+        if self.eat_keyword("table") {
+            let table = self.parse_relation(false, &schema.tables)?;
+            schema.tables.push(table);
+        } else if self.eat_keyword("view") {
+            let view = self.parse_relation(true, &schema.tables)?;
+            schema.tables.push(view);
+        } else if self.eat_keyword("stage") {
+            schema.stages.push(self.parse_stage()?);
+        } else if self.eat_keyword("schema") {
+            self.eat_if_not_exists();
+            self.schema = Some(self.qualified_name()?.1);
+        } else if !self.is_keyword("database") {
+            schema.skipped.push(format!("CREATE {}", self.leading_keywords()));
+        }
+
+        self.skip_statement();
+        Ok(())
+    }
+
+    /// The kind of statement or object: `ALTER TABLE`, `FILE FORMAT`
+    fn leading_keywords(&mut self) -> String {
+        let mut keywords = Vec::new();
+        while let Token::Identifier(word) = self.current() {
+            let continues = ["TABLE", "SCHEMA", "FORMAT", "POLICY", "ACCESS", "FUNCTION", "INTEGRATION"];
+            if !keywords.is_empty() && !continues.iter().any(|k| word.eq_ignore_ascii_case(k)) {
+                break;
+            }
+            keywords.push(word.to_uppercase());
+            self.advance();
+        }
+        keywords.join(" ")
+    }
+
+    /// The table or view after `CREATE ... TABLE` or `VIEW`, its column
+    /// types filled in from its query, if it has one
+    fn parse_relation(&mut self, view: bool, known: &[Table]) -> ProviderResult<Table> {
+        self.eat_if_not_exists();
+        let (schema, name) = self.qualified_name()?;
+
+        let mut columns = Vec::new();
+        if self.is_symbol('(') {
+            self.advance();
+            while !self.is_symbol(')') && self.current() != &Token::Eof {
+                if !self.is_constraint() {
+                    columns.push(self.parse_column()?);
+                }
+                self.skip_element();
+                if self.is_symbol(',') {
+                    self.advance();
+                }
+            }
+            self.expect_symbol(')')?;
+        }
+
+        if let Some(query) = self.query() {
+            let from_query = query_columns(&query, known);
+            if columns.is_empty() {
+                columns = from_query
+                    .into_iter()
+                    .filter_map(|(name, data_type, nullable)| Some(Column { name: name?, data_type, nullable }))
+                    .collect();
+            } else if from_query.len() == columns.len() {
+                for (column, (_, data_type, nullable)) in columns.iter_mut().zip(from_query) {
+                    if column.data_type.is_empty() {
+                        column.data_type = data_type;
+                        column.nullable = nullable;
+                    }
+                }
+            }
+        }
+
+        Ok(Table { schema, name, view, columns })
+    }
+
+    fn is_constraint(&self) -> bool {
+        match self.current() {
+            t if t.is_keyword("constraint") => true,
+            t if t.is_keyword("primary") || t.is_keyword("foreign") => self.peek(1).is_keyword("key"),
+            t if t.is_keyword("unique") => self.peek(1) == &Token::Symbol('('),
+            _ => false,
+        }
+    }
+
+    /// `name [type] [NOT NULL] [options]`; views list only names
+    fn parse_column(&mut self) -> ProviderResult<Column> {
+        let name = self.expect_name()?;
+        let untyped = self.is_symbol(',') || self.is_symbol(')') || self.current().is_any_keyword(COLUMN_OPTIONS);
+        let data_type = if untyped { String::new() } else { self.parse_type_expr()?.to_string() };
+
+        let mut nullable = true;
+        let mut depth = 0usize;
+        loop {
+            match self.current() {
+                Token::Eof => break,
+                Token::Symbol('(') => depth += 1,
+                Token::Symbol(')') if depth == 0 => break,
+                Token::Symbol(')') => depth -= 1,
+                Token::Symbol(',') if depth == 0 => break,
+                t if depth == 0 && t.is_keyword("not") && self.peek(1).is_keyword("null") => nullable = false,
+                _ => {}
+            }
+            self.advance();
+        }
+
+        Ok(Column { name, data_type, nullable })
+    }
+
+    fn parse_type_expr(&mut self) -> ProviderResult<SfType> {
+        let mut name = match self.current() {
+            Token::Identifier(word) => word.to_ascii_uppercase(),
+            other => return Err(ProviderError::ParseError(format!("Expected a type, got {:?}", other))),
+        };
+        self.advance();
+        while self.current().is_any_keyword(TYPE_WORDS) {
+            if let Token::Identifier(word) = self.current() {
+                name = format!("{} {}", name, word.to_ascii_uppercase());
+            }
+            self.advance();
+        }
+
+        let mut args = Vec::new();
+        if self.is_symbol('(') {
+            self.advance();
+            while !self.is_symbol(')') {
+                args.push(self.parse_type_arg()?);
+                if self.is_symbol(',') {
+                    self.advance();
+                } else {
+                    break;
+                }
+            }
+            self.expect_symbol(')')?;
+        }
+
+        Ok(SfType { name, args })
+    }
+
+    fn parse_type_arg(&mut self) -> ProviderResult<SfArg> {
+        match self.current().clone() {
+            Token::Number(n) => {
+                self.advance();
+                Ok(SfArg::Literal(n))
+            }
+            // A field of a structured `OBJECT`: `city VARCHAR`
+            Token::Identifier(name) | Token::QuotedIdentifier(name)
+                if self.peek(1).name().is_some()
+                    && !self.peek(1).is_keyword("not")
+                    && !self.peek(1).is_keyword("null")
+                    && !self.peek(1).is_any_keyword(TYPE_WORDS) =>
+            {
+                self.advance();
+                let ty = self.parse_type_expr()?;
+                Ok(SfArg::Field { name, ty, nullable: !self.eat_not_null() })
+            }
+            _ => {
+                let ty = self.parse_type_expr()?;
+                self.eat_not_null();
+                Ok(SfArg::Type(ty))
+            }
+        }
+    }
+
+    /// Whether a `NOT NULL` (or a `NULL`) was eaten, and it was `NOT NULL`
+    fn eat_not_null(&mut self) -> bool {
+        if self.is_keyword("not") && self.peek(1).is_keyword("null") {
+            self.advance();
+            self.advance();
+            true
+        } else {
+            self.eat_keyword("null");
+            false
+        }
+    }
+
+    /// The tokens of the query after a top-level `AS`, if the statement
+    /// has one
+    fn query(&mut self) -> Option<Vec<Token>> {
+        let mut depth = 0usize;
+        while !matches!(self.current(), Token::Symbol(';') | Token::Eof) {
+            match self.current() {
+                Token::Symbol('(') => depth += 1,
+                Token::Symbol(')') => depth = depth.saturating_sub(1),
+                t if depth == 0
+                    && t.is_keyword("as")
+                    && (self.peek(1).is_any_keyword(&["SELECT", "WITH"]) || self.peek(1) == &Token::Symbol('(')) =>
+                {
+                    self.advance();
+                    let start = self.pos;
+                    self.skip_statement();
+                    return Some(self.tokens[start..self.pos].to_vec());
+                }
+                _ => {}
+            }
+            self.advance();
+        }
+        None
+    }
+
+    fn parse_stage(&mut self) -> ProviderResult<Stage> {
+        self.eat_if_not_exists();
+        let (schema, name) = self.qualified_name()?;
+        let mut stage = Stage { schema, name, url: None, directory: false };
+
+        let mut in_directory = false;
+        while !matches!(self.current(), Token::Symbol(';') | Token::Eof) {
+            let assigns = self.peek(1) == &Token::Symbol('=');
+            match self.current() {
+                t if t.is_keyword("url") && assigns => {
+                    if let Token::StringLiteral(url) = self.peek(2) {
+                        stage.url = Some(url.clone());
+                    }
+                }
+                t if t.is_keyword("directory") && assigns => in_directory = true,
+                t if in_directory && t.is_keyword("enable") && assigns => {
+                    stage.directory = self.peek(2).is_keyword("true");
+                }
+                Token::Symbol(')') => in_directory = false,
+                _ => {}
+            }
+            self.advance();
+        }
+        Ok(stage)
+    }
+}
+
+/// The columns of a query's select list, typed from casts and from the
+/// columns of `known` tables it selects from
+fn query_columns(tokens: &[Token], known: &[Table]) -> Vec<QueryColumn> {
+    let mut tokens = tokens;
+    while tokens.first() == Some(&Token::Symbol('(')) && tokens.last() == Some(&Token::Symbol(')')) {
+        tokens = &tokens[1..tokens.len() - 1];
+    }
+    let Some(select) = top_level(tokens).find(|&i| tokens[i].is_keyword("select")) else {
+        return Vec::new();
+    };
+    let rest = &tokens[select + 1..];
+    let end = top_level(rest)
+        .find(|&i| rest[i].is_keyword("from") || rest[i].is_any_keyword(CLAUSES) || rest[i] == Token::Symbol(';'))
+        .unwrap_or(rest.len());
+    let mut list = &rest[..end];
+    while list.first().is_some_and(|t| t.is_keyword("distinct") || t.is_keyword("all")) {
+        list = &list[1..];
+    }
+    if list.first().is_some_and(|t| t.is_keyword("top")) {
+        list = list.get(2..).unwrap_or_default();
+    }
+    let sources = match rest.get(end) {
+        Some(t) if t.is_keyword("from") => sources(&rest[end + 1..], known),
+        _ => Vec::new(),
+    };
+
+    let mut columns = Vec::new();
+    for item in split_top_level(list) {
+        match item {
+            [Token::Symbol('*')] => {
+                columns.extend(sources.iter().flat_map(|(table, _)| expand(table)));
+                continue;
+            }
+            [qualifier, Token::Symbol('.'), Token::Symbol('*')] => {
+                let qualifier = qualifier.name();
+                let matching: Vec<_> = sources.iter().filter(|(t, alias)| Some(&t.name) == qualifier.as_ref() || alias == &qualifier).collect();
+                let matching = if matching.is_empty() { sources.iter().collect() } else { matching };
+                columns.extend(matching.into_iter().flat_map(|(table, _)| expand(table)));
+                continue;
+            }
+            _ => {}
+        }
+
+        let (expr, alias) = split_alias(item);
+        let reference = column_reference(expr);
+        let (data_type, nullable) = match (cast_type(expr), &reference) {
+            (Some(ty), _) => (ty, true),
+            (None, Some((qualifier, name))) => sources
+                .iter()
+                .filter(|(t, alias)| qualifier.is_none() || Some(&t.name) == qualifier.as_ref() || alias == qualifier)
+                .find_map(|(t, _)| t.columns.iter().find(|c| &c.name == name))
+                .map_or((String::new(), true), |c| (c.data_type.clone(), c.nullable)),
+            (None, None) => (String::new(), true),
+        };
+        columns.push((alias.or(reference.map(|(_, name)| name)), data_type, nullable));
+    }
+    columns
+}
+
+fn expand(table: &Table) -> Vec<QueryColumn> {
+    table.columns.iter().map(|c| (Some(c.name.clone()), c.data_type.clone(), c.nullable)).collect()
+}
+
+/// The known tables of a `FROM` clause, with their aliases
+fn sources<'a>(from: &[Token], known: &'a [Table]) -> Vec<(&'a Table, Option<String>)> {
+    let end = top_level(from).find(|&i| from[i].is_any_keyword(CLAUSES) || from[i] == Token::Symbol(';')).unwrap_or(from.len());
+    let from = &from[..end];
+
+    let mut sources = Vec::new();
+    for start in top_level(from).filter(|&i| i == 0 || from[i - 1] == Token::Symbol(',') || from[i - 1].is_keyword("join")) {
+        let mut i = start;
+        let mut name = None;
+        while let Some(part) = from.get(i).and_then(Token::name) {
+            name = Some(part);
+            if from.get(i + 1) != Some(&Token::Symbol('.')) {
+                break;
+            }
+            i += 2;
+        }
+        let Some(table) = name.and_then(|name| known.iter().rev().find(|t| t.name == name)) else {
+            continue;
+        };
+        let mut next = i + 1;
+        if from.get(next).is_some_and(|t| t.is_keyword("as")) {
+            next += 1;
+        }
+        let alias = from.get(next).filter(|t| !t.is_any_keyword(NOT_ALIASES) && !t.is_any_keyword(CLAUSES)).and_then(Token::name);
+        sources.push((table, alias));
+    }
+    sources
+}
+
+/// Indexes of the tokens outside parentheses
+fn top_level(tokens: &[Token]) -> impl Iterator<Item = usize> + '_ {
+    let mut depth = 0usize;
+    tokens.iter().enumerate().filter_map(move |(i, token)| {
+        let outside = depth == 0;
+        match token {
+            Token::Symbol('(') => depth += 1,
+            Token::Symbol(')') => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        (outside && token != &Token::Symbol('(')).then_some(i)
+    })
+}
+
+fn split_top_level(tokens: &[Token]) -> Vec<&[Token]> {
+    let mut items = Vec::new();
+    let mut start = 0;
+    for i in top_level(tokens).filter(|&i| tokens[i] == Token::Symbol(',')) {
+        items.push(&tokens[start..i]);
+        start = i + 1;
+    }
+    items.push(&tokens[start..]);
+    items.retain(|item| !item.is_empty());
+    items
+}
+
+/// A select item and its alias: `x AS y` and `x y`
+fn split_alias(item: &[Token]) -> (&[Token], Option<String>) {
+    let [rest @ .., before, last] = item else {
+        return (item, None);
+    };
+    let Some(alias) = last.name().filter(|_| !last.is_any_keyword(&["NULL", "END", "TRUE", "FALSE"])) else {
+        return (item, None);
+    };
+    match before {
+        t if t.is_keyword("as") => (rest, Some(alias)),
+        Token::Identifier(_) | Token::QuotedIdentifier(_) | Token::Number(_) | Token::StringLiteral(_)
+        | Token::Symbol(')') => (&item[..item.len() - 1], Some(alias)),
+        _ => (item, None),
+    }
+}
+
+/// `name` or `qualifier.name`
+fn column_reference(expr: &[Token]) -> Option<(Option<String>, String)> {
+    match expr {
+        [name] => Some((None, name.name()?)),
+        [.., qualifier, Token::Symbol('.'), name] => Some((qualifier.name(), name.name()?)),
+        _ => None,
+    }
+    .filter(|_| expr.iter().step_by(2).all(|t| t.name().is_some()))
+}
+
+/// The type of `x::TYPE` or `[TRY_]CAST(x AS TYPE)`
+fn cast_type(expr: &[Token]) -> Option<String> {
+    let colons = top_level(expr)
+        .filter(|&i| expr[i] == Token::Symbol(':') && expr.get(i + 1) == Some(&Token::Symbol(':')))
+        .last();
+    let type_tokens = match (colons, expr) {
+        (Some(i), _) => &expr[i + 2..],
+        (None, [function, Token::Symbol('('), inner @ .., Token::Symbol(')')])
+            if function.is_keyword("cast") || function.is_keyword("try_cast") =>
+        {
+            let as_index = top_level(inner).filter(|&i| inner[i].is_keyword("as")).last()?;
+            &inner[as_index + 1..]
+        }
+        _ => return None,
+    };
+    let mut parser = Parser::from_tokens(type_tokens.to_vec());
+    let ty = parser.parse_type_expr().ok()?;
+    (parser.current() == &Token::Eof).then(|| ty.to_string())
+}
+
+fn tokenize(content: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = content.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '-' | '/' => {
+                chars.next();
+                if chars.peek() == Some(&c) {
+                    // Line comment: `--` or `//`
+                    for ch in chars.by_ref() {
+                        if ch == '\n' {
+                            break;
+                        }
+                    }
+                } else if c == '/' && chars.peek() == Some(&'*') {
+                    chars.next();
+                    let mut prev = ' ';
+                    for ch in chars.by_ref() {
+                        if prev == '*' && ch == '/' {
+                            break;
+                        }
+                        prev = ch;
+                    }
+                } else {
+                    tokens.push(Token::Symbol(c));
+                }
+            }
+            '$' => {
+                chars.next();
+                if chars.peek() != Some(&'$') {
+                    tokens.push(Token::Symbol('$'));
+                    continue;
+                }
+                // `$$body$$`, as in procedure and function definitions
+                chars.next();
+                let mut s = String::new();
+                while let Some(ch) = chars.next() {
+                    if ch == '$' && chars.peek() == Some(&'$') {
+                        chars.next();
+                        break;
+                    }
+                    s.push(ch);
+                }
+                tokens.push(Token::StringLiteral(s));
+            }
+            '\'' | '"' => {
+                chars.next();
+                let mut s = String::new();
+                while let Some(ch) = chars.next() {
+                    match ch {
+                        '\\' if c == '\'' => {
+                            if let Some(escaped) = chars.next() {
+                                s.push(escaped);
+                            }
+                        }
+                        ch if ch == c => {
+                            // A doubled quote escapes itself
+                            if chars.peek() == Some(&c) {
+                                chars.next();
+                                s.push(c);
+                            } else {
+                                break;
+                            }
+                        }
+                        ch => s.push(ch),
+                    }
+                }
+                tokens.push(if c == '\'' { Token::StringLiteral(s) } else { Token::QuotedIdentifier(s) });
+            }
+            c if c.is_ascii_digit() => {
+                let mut num = String::new();
+                while let Some(&d) = chars.peek() {
+                    if d.is_ascii_alphanumeric() || d == '.' {
+                        num.push(d);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Number(num));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut ident = String::new();
+                while let Some(&d) = chars.peek() {
+                    if d.is_alphanumeric() || d == '_' || d == '$' {
+                        ident.push(d);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Identifier(ident));
+            }
+            _ => {
+                tokens.push(Token::Symbol(c));
+                chars.next();
+            }
+        }
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DDL: &str = r#"
+create or replace database SHOP;
+
+create or replace schema SHOP.SALES;
+
+create or replace TABLE ORDERS (
+	ID NUMBER(38,0) NOT NULL autoincrement start 1 increment 1 noorder,
+	CUSTOMER VARCHAR(16777216) COMMENT 'Who, when',
+	PAYLOAD VARIANT,
+	"lineItems" ARRAY(OBJECT(sku VARCHAR NOT NULL, qty NUMBER(9,0))),
+	AMOUNT NUMBER(12,2) DEFAULT 0,
+	primary key (ID)
+) cluster by (CUSTOMER);
+
+create or replace view ORDER_SUMMARY(
+	ID,
+	CUSTOMER COMMENT 'Name',
+	CITY,
+	TOTAL
+) as
+select o.id, o.customer, payload:address.city::string, try_cast(amount as float)
+from orders o;
+
+create or replace transient table SHOP.STAGING.EVENTS as select id, payload:kind::varchar as kind, current_timestamp() from sales.orders;
+
+create or replace file format CSV_FORMAT type = csv;
+create or replace procedure NOOP() returns varchar language sql as $$ begin return 'a;b'; end $$;
+create or replace stage EXPORTS url = 's3://bucket/exports/' directory = ( enable = true ) file_format = CSV_FORMAT;
+"#;
+
+    #[test]
+    fn test_parse_types() {
+        let ty = parse_type("OBJECT(city VARCHAR, zip VARCHAR NOT NULL)").unwrap();
+        assert_eq!(ty.to_string(), "OBJECT(city VARCHAR, zip VARCHAR NOT NULL)");
+        assert_eq!(parse_type("number(12, 2)").unwrap().to_string(), "NUMBER(12,2)");
+        assert_eq!(parse_type("ARRAY(DOUBLE PRECISION NOT NULL)").unwrap().to_string(), "ARRAY(DOUBLE PRECISION)");
+        assert_eq!(parse_type("MAP(VARCHAR, NUMBER(38,0))").unwrap().type_args().count(), 2);
+        assert!(parse_type("VARCHAR)").is_err());
+    }
+
+    #[test]
+    fn test_parse_get_ddl() {
+        let schema = parse_ddl(DDL).unwrap();
+        let names: Vec<(Option<&str>, &str, bool)> =
+            schema.tables.iter().map(|t| (t.schema.as_deref(), t.name.as_str(), t.view)).collect();
+        assert_eq!(
+            names,
+            [(Some("SALES"), "ORDERS", false), (Some("SALES"), "ORDER_SUMMARY", true), (Some("STAGING"), "EVENTS", false)]
+        );
+
+        let orders = &schema.tables[0].columns;
+        assert_eq!(orders.len(), 5);
+        assert_eq!((orders[0].data_type.as_str(), orders[0].nullable), ("NUMBER(38,0)", false));
+        assert_eq!(orders[3].name, "lineItems");
+        assert_eq!(orders[3].data_type, "ARRAY(OBJECT(sku VARCHAR NOT NULL, qty NUMBER(9,0)))");
+
+        let summary: Vec<(&str, &str)> =
+            schema.tables[1].columns.iter().map(|c| (c.name.as_str(), c.data_type.as_str())).collect();
+        assert_eq!(
+            summary,
+            [("ID", "NUMBER(38,0)"), ("CUSTOMER", "VARCHAR(16777216)"), ("CITY", "STRING"), ("TOTAL", "FLOAT")]
+        );
+        assert!(!schema.tables[1].columns[0].nullable);
+
+        let events: Vec<(&str, &str)> =
+            schema.tables[2].columns.iter().map(|c| (c.name.as_str(), c.data_type.as_str())).collect();
+        assert_eq!(events, [("ID", "NUMBER(38,0)"), ("KIND", "VARCHAR")]);
+
+        assert_eq!(schema.stages[0].url.as_deref(), Some("s3://bucket/exports/"));
+        assert!(schema.stages[0].directory);
+        assert_eq!(schema.stages[0].schema.as_deref(), Some("SALES"));
+        assert_eq!(schema.skipped, ["CREATE FILE FORMAT", "CREATE PROCEDURE"]);
+    }
+}
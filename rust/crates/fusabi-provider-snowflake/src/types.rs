@@ -0,0 +1,116 @@
+//! Snowflake schema types
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Tables, views and stages read from `GET_DDL` output or
+/// `INFORMATION_SCHEMA`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SnowflakeSchema {
+    pub tables: Vec<Table>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stages: Vec<Stage>,
+    /// Leading keywords of statements that define nothing typed, in order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<String>,
+}
+
+/// A table or view. Names are as Snowflake stores them: unquoted
+/// identifiers upper case, quoted ones as written.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Table {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub view: bool,
+    pub columns: Vec<Column>,
+}
+
+/// A column
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Column {
+    pub name: String,
+    /// The type as Snowflake prints it, e.g. `NUMBER(38,0)` or
+    /// `ARRAY(VARCHAR)`; empty for a view column whose type could not be
+    /// worked out from its query
+    #[serde(rename = "type")]
+    pub data_type: String,
+    pub nullable: bool,
+}
+
+/// A named stage
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Stage {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    pub name: String,
+    /// Location of an external stage
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Whether the stage has a directory table
+    #[serde(default)]
+    pub directory: bool,
+}
+
+/// A parsed type: a name with optional arguments, such as `NUMBER(12,2)`
+/// or `OBJECT(city VARCHAR, zip VARCHAR NOT NULL)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SfType {
+    /// Upper case: `TIMESTAMP_NTZ`, `DOUBLE PRECISION`
+    pub name: String,
+    pub args: Vec<SfArg>,
+}
+
+/// An argument of a parameterized type
+#[derive(Debug, Clone, PartialEq)]
+pub enum SfArg {
+    Type(SfType),
+    /// A field of a structured `OBJECT`
+    Field { name: String, ty: SfType, nullable: bool },
+    /// A number, such as the precision of `NUMBER(12,2)` or the dimension
+    /// of `VECTOR(FLOAT, 256)`
+    Literal(String),
+}
+
+impl SfType {
+    pub fn type_args(&self) -> impl Iterator<Item = &SfType> {
+        self.args.iter().filter_map(|arg| match arg {
+            SfArg::Type(ty) => Some(ty),
+            _ => None,
+        })
+    }
+
+    /// The numeric arguments, in order
+    pub fn literals(&self) -> impl Iterator<Item = u32> + '_ {
+        self.args.iter().filter_map(|arg| match arg {
+            SfArg::Literal(literal) => literal.parse().ok(),
+            _ => None,
+        })
+    }
+}
+
+impl fmt::Display for SfType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if self.args.is_empty() {
+            return Ok(());
+        }
+        write!(f, "(")?;
+        for (i, arg) in self.args.iter().enumerate() {
+            match (i, arg) {
+                (0, _) => {}
+                (_, SfArg::Literal(_)) => write!(f, ",")?,
+                _ => write!(f, ", ")?,
+            }
+            match arg {
+                SfArg::Type(ty) => write!(f, "{}", ty)?,
+                SfArg::Field { name, ty, nullable } => {
+                    write!(f, "{} {}{}", name, ty, if *nullable { "" } else { " NOT NULL" })?
+                }
+                SfArg::Literal(literal) => write!(f, "{}", literal)?,
+            }
+        }
+        write!(f, ")")
+    }
+}
@@ -0,0 +1,378 @@
+//! MQTT Sparkplug B Type Provider
+//!
+//! Generates Fusabi types for Sparkplug B, the payload encoding industrial
+//! IoT edge nodes publish over MQTT, so data flowing into Hibana from the
+//! plant floor is typed at the edge instead of read as untyped metrics.
+//!
+//! # Sources
+//!
+//! - `sparkplug` (or `spb`): the payload model only; see [`payload`]
+//! - A metric declaration, inline or from a file, naming the metrics the
+//!   edge node and each device publish; see [`metrics`]
+//!
+//! # Generated Types
+//!
+//! The payload model is always generated: `Payload`, `Metric`, `DataSet`,
+//! `Template` and the rest of `sparkplug_b.proto`, each `value` oneof as a
+//! union (`MetricValue`), and the `DataType` and `MessageType` enums.
+//!
+//! A declaration adds a record per device, `<Device>Metrics`, and
+//! `NodeMetrics` for the edge node's own metrics, with a field per metric.
+//! Metric names that are not identifiers are sanitized
+//! (`Motor/Speed` → `Motor_Speed`). Every field is `T option`: `NDATA` and
+//! `DDATA` messages report by exception and carry only the metrics that
+//! changed.
+//!
+//! | Data type                        | Fusabi                 |
+//! |----------------------------------|------------------------|
+//! | `Int8`, `Int16`, `Int32`         | `int`                  |
+//! | `UInt8`, `UInt16`, `UInt32`      | `uint`                 |
+//! | `Int64` / `UInt64`, `DateTime`   | `int64` / `uint64`     |
+//! | `Float`, `Double`                | `float`                |
+//! | `String`, `Text`, `UUID`         | `string`               |
+//! | `Bytes`, `File`                  | `bytes`                |
+//! | `DataSet`, `Template`, `PropertySet` | the payload record |
+//! | `<T>Array`                       | `T list`               |
+//!
+//! # Params
+//!
+//! | Param     | Meaning                                                     |
+//! |-----------|-------------------------------------------------------------|
+//! | `devices` | Comma-separated device names to generate; a trailing `*` matches a prefix |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_sparkplug::SparkplugProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = SparkplugProvider::new();
+//! let params = ProviderParams::default().with("devices", "Press*");
+//! let schema = provider.resolve_schema("plant1/metrics.yaml", &params)?;
+//! let types = provider.generate_types(&schema, "Plant1")?;
+//! ```
+
+pub mod metrics;
+pub mod payload;
+
+pub use metrics::{parse_declaration, DeviceDef, MetricDeclaration, MetricDef};
+
+use std::collections::HashMap;
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceValidator, TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, DuDef, VariantDef, TypeExpr, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+
+/// Record name for the edge node's own metrics
+pub const NODE_RECORD: &str = "NodeMetrics";
+
+/// Sparkplug B type provider
+pub struct SparkplugProvider {
+    generator: TypeGenerator,
+}
+
+impl SparkplugProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<MetricDeclaration> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected a Sparkplug metric declaration: {}", e)))
+    }
+
+    fn payload_types(&self) -> Vec<TypeDefinition> {
+        let named = |ty: &str| -> TypeExpr { TypeShape::parse(ty).into() };
+        let enums = [
+            ("DataType", payload::DATA_TYPES.iter().map(|(name, _, _)| *name).collect::<Vec<_>>()),
+            ("MessageType", payload::MESSAGE_TYPES.to_vec()),
+        ];
+        let mut types: Vec<TypeDefinition> = enums
+            .into_iter()
+            .map(|(name, values)| {
+                TypeDefinition::Du(DuDef {
+                    name: name.to_string(),
+                    variants: values.into_iter().map(|value| VariantDef::new_simple(value.to_string())).collect(),
+                })
+            })
+            .collect();
+        types.extend(payload::ONEOFS.iter().map(|(name, variants)| {
+            TypeDefinition::Du(DuDef {
+                name: name.to_string(),
+                variants: variants
+                    .iter()
+                    .map(|(variant, ty)| VariantDef::new(variant.to_string(), vec![named(ty)]))
+                    .collect(),
+            })
+        }));
+        types.extend(payload::MESSAGES.iter().map(|(name, fields)| {
+            TypeDefinition::Record(RecordDef {
+                name: name.to_string(),
+                fields: fields.iter().map(|(field, ty)| (field.to_string(), named(ty))).collect(),
+            })
+        }));
+        types
+    }
+
+    fn generate(&self, declaration: &MetricDeclaration) -> (Vec<TypeDefinition>, Vec<Diagnostic>) {
+        let mut types = self.payload_types();
+        let mut diagnostics = Vec::new();
+
+        let devices = declaration.devices.iter().map(|device| {
+            (format!("{}Metrics", self.generator.naming.apply(&device.name)), device.name.as_str(), &device.metrics)
+        });
+        let node = Some((NODE_RECORD.to_string(), "node", &declaration.node))
+            .filter(|(_, _, metrics)| !metrics.is_empty());
+        for (record, owner, metrics) in node.into_iter().chain(devices) {
+            let mut seen: HashMap<String, &str> = HashMap::new();
+            let mut fields = Vec::new();
+            for metric in metrics {
+                let location = format!("{}.{}", owner, metric.name);
+                let field = field_name(&metric.name);
+                if let Some(first) = seen.get(&field) {
+                    diagnostics.push(
+                        Diagnostic::warning(
+                            "duplicate-field",
+                            format!("Metrics `{}` and `{}` both map to field `{}`; keeping the first", first, metric.name, field),
+                        )
+                        .at(location),
+                    );
+                    continue;
+                }
+                seen.insert(field.clone(), &metric.name);
+
+                let ty = match payload::data_type(&metric.datatype) {
+                    Some((_, _, ty)) => ty,
+                    None => {
+                        diagnostics.push(
+                            Diagnostic::warning(
+                                "unmapped-type",
+                                format!("Unknown data type `{}`; generated as `any`", metric.datatype),
+                            )
+                            .at(location),
+                        );
+                        "any"
+                    }
+                };
+                fields.push((field, TypeShape::parse(ty).option().into()));
+            }
+            types.push(TypeDefinition::Record(RecordDef { name: record, fields }));
+        }
+        (types, diagnostics)
+    }
+}
+
+/// `Motor/Speed` → `Motor_Speed`
+fn field_name(metric: &str) -> String {
+    metric
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect()
+}
+
+fn matches_any(patterns: &[&str], name: &str) -> bool {
+    patterns.iter().any(|pattern| match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => name == *pattern,
+    })
+}
+
+impl Default for SparkplugProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for SparkplugProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("SparkplugProvider")
+            .param(
+                ParamSpec::new("devices", ParamKind::List)
+                    .describe("Device names to generate; a trailing `*` matches a prefix"),
+            )
+    }
+}
+
+impl TypeProvider for SparkplugProvider {
+    fn name(&self) -> &str {
+        "SparkplugProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let trimmed = source.trim();
+        let mut declaration = match trimmed.to_ascii_lowercase().as_str() {
+            "sparkplug" | "spb" => MetricDeclaration::default(),
+            _ if trimmed.starts_with('{') || trimmed.contains('\n') => parse_declaration(source)?,
+            _ => parse_declaration(&read_source(source, params)?)?,
+        };
+
+        if let Some(patterns) = typed.list("devices") {
+            declaration.devices.retain(|device| matches_any(&patterns, &device.name));
+        }
+
+        let json = serde_json::to_string(&declaration)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected a Sparkplug metric declaration".to_string()));
+        };
+
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+        module.types = self.generate(&self.parse_schema(json)?).0;
+
+        let mut result = GeneratedTypes::new();
+        result.modules.push(module);
+        Ok(result)
+    }
+}
+
+impl SourceValidator for SparkplugProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected a Sparkplug metric declaration".to_string()));
+        };
+
+        Ok(self.generate(&self.parse_schema(json)?).1)
+    }
+}
+
+impl DescribedProvider for SparkplugProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .embedded("sparkplug")
+            .embedded("spb")
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    const DECLARATION: &str = "node:\n  bdSeq: Int64\n  Node Control/Rebirth: Boolean\ndevices:\n  Press01:\n    Temperature: Float\n    Motor/Speed: UInt32\n    Motor Speed: Double\n    Recipe: Template\n  Press02:\n    - {name: Cycles, datatype: 8}\n    - {name: Mode, datatype: Enum}\n  oven-1:\n    Zones: FloatArray\n";
+
+    fn fields(types: &GeneratedTypes, name: &str) -> Vec<(String, String)> {
+        types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+            .fields
+            .iter()
+            .map(|(field, ty)| (field.clone(), ty.to_string()))
+            .collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(f, t)| (f.to_string(), t.to_string())).collect()
+    }
+
+    #[test]
+    fn test_payload_model() {
+        let provider = SparkplugProvider::new();
+        let schema = provider.resolve_schema("sparkplug", &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "SparkplugB").unwrap();
+
+        assert_eq!(
+            fields(&types, "Payload"),
+            pairs(&[
+                ("timestamp", "uint64 option"),
+                ("metrics", "Metric list"),
+                ("seq", "uint64 option"),
+                ("uuid", "string option"),
+                ("body", "bytes option"),
+            ])
+        );
+        let metric_value = types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Du(du) if du.name == "MetricValue" => Some(du),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(metric_value.variants.len(), 9);
+        assert!(provider.schema_diagnostics(&schema).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_device_metrics() {
+        let provider = SparkplugProvider::new();
+        let schema = provider.resolve_schema(DECLARATION, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Plant1").unwrap();
+
+        assert_eq!(
+            fields(&types, "NodeMetrics"),
+            pairs(&[("bdSeq", "int64 option"), ("Node_Control_Rebirth", "bool option")])
+        );
+        assert_eq!(
+            fields(&types, "Press01Metrics"),
+            pairs(&[
+                ("Temperature", "float option"),
+                ("Motor_Speed", "uint option"),
+                ("Recipe", "Template option"),
+            ])
+        );
+        assert_eq!(
+            fields(&types, "Press02Metrics"),
+            pairs(&[("Cycles", "uint64 option"), ("Mode", "any option")])
+        );
+        assert_eq!(fields(&types, "Oven1Metrics"), pairs(&[("Zones", "float list option")]));
+
+        let diagnostics = provider.schema_diagnostics(&schema).unwrap();
+        let found: Vec<(&str, &str)> = diagnostics
+            .iter()
+            .map(|d| (d.code.as_str(), d.location.as_deref().unwrap_or("")))
+            .collect();
+        assert_eq!(
+            found,
+            [("duplicate-field", "Press01.Motor Speed"), ("unmapped-type", "Press02.Mode")]
+        );
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/sparkplug.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_devices_filter_and_file_source() {
+        let path = std::env::temp_dir().join(format!("fusabi-sparkplug-{}.yaml", std::process::id()));
+        std::fs::write(&path, DECLARATION).unwrap();
+
+        let provider = SparkplugProvider::new();
+        let params = ProviderParams::default().with("devices", "Press*");
+        let schema = provider.resolve_schema(path.to_str().unwrap(), &params).unwrap();
+        let types = provider.generate_types(&schema, "Plant1").unwrap();
+        let records: Vec<&str> = types.modules[0]
+            .types
+            .iter()
+            .filter_map(|t| match t {
+                TypeDefinition::Record(r) if r.name.ends_with("Metrics") => Some(r.name.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(records, ["NodeMetrics", "Press01Metrics", "Press02Metrics"]);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,152 @@
+//! Metric declarations
+//!
+//! Sparkplug payloads carry metrics by name, with a `datatype` announced in
+//! the birth certificate. A declaration lists those names and types ahead
+//! of time, for the edge node and for each device, in YAML or JSON:
+//!
+//! ```yaml
+//! node:
+//!   bdSeq: Int64
+//!   Node Control/Rebirth: Boolean
+//! devices:
+//!   Press01:
+//!     Temperature: Float
+//!     Motor/Speed: UInt32
+//!   Press02:
+//!     - {name: Temperature, datatype: Float}
+//!     - {name: Cycles, datatype: 8}
+//! ```
+//!
+//! Metrics are a mapping of name to data type, or a list of
+//! `{name, datatype}` entries as a birth certificate spells them, with the
+//! type given by name or by its `DataType` number.
+
+use crate::payload::DATA_TYPES;
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+/// Metrics declared for an edge node and its devices
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetricDeclaration {
+    pub node: Vec<MetricDef>,
+    pub devices: Vec<DeviceDef>,
+}
+
+/// A device and its metrics, in declaration order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceDef {
+    pub name: String,
+    pub metrics: Vec<MetricDef>,
+}
+
+/// A metric name and its data type, as written
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricDef {
+    pub name: String,
+    pub datatype: String,
+}
+
+/// Parse a metric declaration
+pub fn parse_declaration(content: &str) -> ProviderResult<MetricDeclaration> {
+    let value: Value = serde_yaml::from_str(content)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid Sparkplug metric declaration: {}", e)))?;
+    let root = match value {
+        Value::Mapping(root) if root.contains_key("node") || root.contains_key("devices") => root,
+        _ => {
+            return Err(ProviderError::ParseError(
+                "Expected a Sparkplug metric declaration with `node` or `devices`".to_string(),
+            ))
+        }
+    };
+
+    let node = match root.get("node") {
+        Some(metrics) => parse_metrics(metrics, "node")?,
+        None => Vec::new(),
+    };
+    let devices = match root.get("devices") {
+        Some(Value::Mapping(devices)) => devices
+            .iter()
+            .map(|(name, metrics)| {
+                let name = scalar(name)
+                    .ok_or_else(|| ProviderError::ParseError(format!("Device names must be strings, got {:?}", name)))?;
+                Ok(DeviceDef {
+                    metrics: parse_metrics(metrics, &name)?,
+                    name,
+                })
+            })
+            .collect::<ProviderResult<_>>()?,
+        Some(Value::Null) | None => Vec::new(),
+        Some(_) => return Err(ProviderError::ParseError("`devices` must be a mapping".to_string())),
+    };
+    Ok(MetricDeclaration { node, devices })
+}
+
+fn parse_metrics(value: &Value, owner: &str) -> ProviderResult<Vec<MetricDef>> {
+    let invalid = || ProviderError::ParseError(format!("Invalid metric in `{}`", owner));
+    match value {
+        Value::Mapping(metrics) => metrics
+            .iter()
+            .map(|(name, datatype)| {
+                Ok(MetricDef {
+                    name: scalar(name).ok_or_else(invalid)?,
+                    datatype: datatype_name(datatype).ok_or_else(invalid)?,
+                })
+            })
+            .collect(),
+        Value::Sequence(metrics) => metrics
+            .iter()
+            .map(|metric| {
+                Ok(MetricDef {
+                    name: metric.get("name").and_then(scalar).ok_or_else(invalid)?,
+                    datatype: metric.get("datatype").and_then(datatype_name).ok_or_else(invalid)?,
+                })
+            })
+            .collect(),
+        Value::Null => Ok(Vec::new()),
+        _ => Err(ProviderError::ParseError(format!("Metrics of `{}` must be a mapping or a list", owner))),
+    }
+}
+
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// A data type name, with `DataType` numbers replaced by their names
+fn datatype_name(value: &Value) -> Option<String> {
+    if let Some(code) = value.as_u64() {
+        let name = DATA_TYPES.iter().find(|(_, c, _)| u64::from(*c) == code);
+        return Some(name.map_or_else(|| code.to_string(), |(name, _, _)| name.to_string()));
+    }
+    value.as_str().map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_declaration() {
+        let declaration = parse_declaration(
+            "node:\n  bdSeq: Int64\ndevices:\n  Press01:\n    Temperature: Float\n    Motor/Speed: UInt32\n  Press02:\n    - {name: Cycles, datatype: 8}\n    - {name: Mode, datatype: 99}\n  Idle:\n",
+        )
+        .unwrap();
+        assert_eq!(
+            declaration.node,
+            [MetricDef { name: "bdSeq".to_string(), datatype: "Int64".to_string() }]
+        );
+        let names: Vec<&str> = declaration.devices.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["Press01", "Press02", "Idle"]);
+        assert_eq!(declaration.devices[0].metrics[1].name, "Motor/Speed");
+        let types: Vec<&str> = declaration.devices[1].metrics.iter().map(|m| m.datatype.as_str()).collect();
+        assert_eq!(types, ["UInt64", "99"]);
+        assert!(declaration.devices[2].metrics.is_empty());
+
+        assert!(parse_declaration("metrics: []").is_err());
+        assert!(parse_declaration("devices:\n  Press01: [{name: Temperature}]").is_err());
+    }
+}
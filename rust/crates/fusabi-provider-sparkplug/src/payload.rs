@@ -0,0 +1,189 @@
+//! The Sparkplug B payload model
+//!
+//! Transcribed from Eclipse Tahu's `sparkplug_b.proto` (Sparkplug 3.0).
+//! Nested messages are flattened with their parent's name
+//! (`Payload.Template.Parameter` → `TemplateParameter`), and each `value`
+//! oneof becomes a union named `<Message>Value`. The proto is `proto2`
+//! with every scalar `optional`, so only repeated fields are not options.
+
+/// A message: name, then `(field, type)`
+pub type MessageDef = (&'static str, &'static [(&'static str, &'static str)]);
+
+/// A `value` oneof: union name, then `(variant, type)`
+pub type OneofDef = (&'static str, &'static [(&'static str, &'static str)]);
+
+pub const MESSAGES: &[MessageDef] = &[
+    ("Payload", &[
+        ("timestamp", "uint64 option"),
+        ("metrics", "Metric list"),
+        ("seq", "uint64 option"),
+        ("uuid", "string option"),
+        ("body", "bytes option"),
+    ]),
+    ("Metric", &[
+        ("name", "string option"),
+        ("alias", "uint64 option"),
+        ("timestamp", "uint64 option"),
+        ("datatype", "uint option"),
+        ("is_historical", "bool option"),
+        ("is_transient", "bool option"),
+        ("is_null", "bool option"),
+        ("metadata", "MetaData option"),
+        ("properties", "PropertySet option"),
+        ("value", "MetricValue option"),
+    ]),
+    ("MetaData", &[
+        ("is_multi_part", "bool option"),
+        ("content_type", "string option"),
+        ("size", "uint64 option"),
+        ("seq", "uint64 option"),
+        ("file_name", "string option"),
+        ("file_type", "string option"),
+        ("md5", "string option"),
+        ("description", "string option"),
+    ]),
+    ("PropertyValue", &[
+        ("type", "uint option"),
+        ("is_null", "bool option"),
+        ("value", "PropertyValueValue option"),
+    ]),
+    ("PropertySet", &[
+        ("keys", "string list"),
+        ("values", "PropertyValue list"),
+    ]),
+    ("PropertySetList", &[
+        ("propertyset", "PropertySet list"),
+    ]),
+    ("DataSet", &[
+        ("num_of_columns", "uint64 option"),
+        ("columns", "string list"),
+        ("types", "uint list"),
+        ("rows", "DataSetRow list"),
+    ]),
+    ("DataSetRow", &[
+        ("elements", "DataSetValue list"),
+    ]),
+    ("DataSetValue", &[
+        ("value", "DataSetValueValue option"),
+    ]),
+    ("Template", &[
+        ("version", "string option"),
+        ("metrics", "Metric list"),
+        ("parameters", "TemplateParameter list"),
+        ("template_ref", "string option"),
+        ("is_definition", "bool option"),
+    ]),
+    ("TemplateParameter", &[
+        ("name", "string option"),
+        ("type", "uint option"),
+        ("value", "TemplateParameterValue option"),
+    ]),
+];
+
+/// Scalar alternatives shared by every `value` oneof
+const SCALAR_VALUES: [(&str, &str); 6] = [
+    ("IntValue", "uint"),
+    ("LongValue", "uint64"),
+    ("FloatValue", "float"),
+    ("DoubleValue", "float"),
+    ("BooleanValue", "bool"),
+    ("StringValue", "string"),
+];
+
+pub const ONEOFS: &[OneofDef] = &[
+    ("MetricValue", &[
+        SCALAR_VALUES[0], SCALAR_VALUES[1], SCALAR_VALUES[2],
+        SCALAR_VALUES[3], SCALAR_VALUES[4], SCALAR_VALUES[5],
+        ("BytesValue", "bytes"),
+        ("DatasetValue", "DataSet"),
+        ("TemplateValue", "Template"),
+    ]),
+    ("PropertyValueValue", &[
+        SCALAR_VALUES[0], SCALAR_VALUES[1], SCALAR_VALUES[2],
+        SCALAR_VALUES[3], SCALAR_VALUES[4], SCALAR_VALUES[5],
+        ("PropertysetValue", "PropertySet"),
+        ("PropertysetsValue", "PropertySetList"),
+    ]),
+    ("DataSetValueValue", &SCALAR_VALUES),
+    ("TemplateParameterValue", &SCALAR_VALUES),
+];
+
+/// The `DataType` enum with its wire values, and the Fusabi type a metric
+/// of that type carries. `DateTime` is milliseconds since the epoch.
+pub const DATA_TYPES: &[(&str, u32, &str)] = &[
+    ("Unknown", 0, "any"),
+    ("Int8", 1, "int"),
+    ("Int16", 2, "int"),
+    ("Int32", 3, "int"),
+    ("Int64", 4, "int64"),
+    ("UInt8", 5, "uint"),
+    ("UInt16", 6, "uint"),
+    ("UInt32", 7, "uint"),
+    ("UInt64", 8, "uint64"),
+    ("Float", 9, "float"),
+    ("Double", 10, "float"),
+    ("Boolean", 11, "bool"),
+    ("String", 12, "string"),
+    ("DateTime", 13, "uint64"),
+    ("Text", 14, "string"),
+    ("UUID", 15, "string"),
+    ("DataSet", 16, "DataSet"),
+    ("Bytes", 17, "bytes"),
+    ("File", 18, "bytes"),
+    ("Template", 19, "Template"),
+    ("PropertySet", 20, "PropertySet"),
+    ("PropertySetList", 21, "PropertySetList"),
+    ("Int8Array", 22, "int list"),
+    ("Int16Array", 23, "int list"),
+    ("Int32Array", 24, "int list"),
+    ("Int64Array", 25, "int64 list"),
+    ("UInt8Array", 26, "uint list"),
+    ("UInt16Array", 27, "uint list"),
+    ("UInt32Array", 28, "uint list"),
+    ("UInt64Array", 29, "uint64 list"),
+    ("FloatArray", 30, "float list"),
+    ("DoubleArray", 31, "float list"),
+    ("BooleanArray", 32, "bool list"),
+    ("StringArray", 33, "string list"),
+    ("DateTimeArray", 34, "uint64 list"),
+];
+
+/// Sparkplug topic message types, in the order the specification lists them
+pub const MESSAGE_TYPES: &[&str] = &[
+    "NBIRTH", "NDEATH", "DBIRTH", "DDEATH", "NDATA", "DDATA", "NCMD", "DCMD", "STATE",
+];
+
+/// Look up a data type by name, ignoring case (`uint32`, `UInt32`)
+pub fn data_type(name: &str) -> Option<(&'static str, u32, &'static str)> {
+    DATA_TYPES
+        .iter()
+        .copied()
+        .find(|(data_type, _, _)| data_type.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_type() {
+        assert_eq!(data_type("uint32"), Some(("UInt32", 7, "uint")));
+        assert_eq!(data_type("DateTimeArray").map(|t| t.2), Some("uint64 list"));
+        assert_eq!(data_type("Decimal"), None);
+    }
+
+    #[test]
+    fn test_model_references_resolve() {
+        let defined: Vec<&str> = MESSAGES.iter().map(|m| m.0).chain(ONEOFS.iter().map(|o| o.0)).collect();
+        let primitives = ["any", "int", "int64", "uint", "uint64", "float", "bool", "string", "bytes"];
+        let types = MESSAGES
+            .iter()
+            .flat_map(|m| m.1.iter().map(|f| f.1))
+            .chain(ONEOFS.iter().flat_map(|o| o.1.iter().map(|v| v.1)))
+            .chain(DATA_TYPES.iter().map(|t| t.2));
+        for ty in types {
+            let base = ty.trim_end_matches(" option").trim_end_matches(" list");
+            assert!(defined.contains(&base) || primitives.contains(&base), "unresolved `{}`", ty);
+        }
+    }
+}
@@ -0,0 +1,35 @@
+//! Resolve and generate benchmarks for large DDL files
+//!
+//! Run with `cargo bench -p fusabi-provider-sql`. Set `FUSABI_BENCH_CORPUS`
+//! to a directory containing `schema.sql` to measure a real schema instead
+//! of the synthetic 300-table one.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use fusabi_provider_common::check_generated_types;
+use fusabi_provider_sql::SqlProvider;
+use fusabi_provider_testkit::corpus;
+use fusabi_type_providers::{ProviderParams, TypeProvider};
+use std::hint::black_box;
+
+fn bench_generate(c: &mut Criterion) {
+    let provider = SqlProvider::new();
+    let ddl = corpus::load_or("schema.sql", || corpus::sql_tables(300, 20));
+    let path = corpus::write_temp("schema.sql", &ddl);
+    let source = path.to_str().unwrap();
+    let params = ProviderParams::default();
+    let schema = provider.resolve_schema(source, &params).unwrap();
+    let types = provider.generate_types(&schema, "Db").unwrap();
+
+    let mut group = c.benchmark_group("sql");
+    group.bench_function("resolve", |b| {
+        b.iter(|| provider.resolve_schema(black_box(source), &params).unwrap())
+    });
+    group.bench_function("generate", |b| {
+        b.iter(|| provider.generate_types(black_box(&schema), "Db").unwrap())
+    });
+    group.bench_function("validate", |b| b.iter(|| check_generated_types(black_box(&types))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_generate);
+criterion_main!(benches);
@@ -0,0 +1,182 @@
+//! SQL DDL from generated types
+//!
+//! The reverse of the provider: every record becomes a `CREATE TABLE`
+//! named after it in snake_case, with one column per field in field order.
+//!
+//! | Field type                     | Column                                  |
+//! |--------------------------------|-----------------------------------------|
+//! | `string`                       | `TEXT`                                  |
+//! | `int`, `int64`, `uint`, ...    | `BIGINT`                                |
+//! | `float`                        | `DOUBLE PRECISION`                      |
+//! | `bool`                         | `BOOLEAN`                               |
+//! | `bytes`                        | `BYTEA` on PostgreSQL, `BLOB` elsewhere |
+//! | union of unit variants         | `TEXT` with a `CHECK` on the names      |
+//! | list of the above (PostgreSQL) | array of the element type               |
+//! | anything else                  | `JSONB` on PostgreSQL, `JSON` elsewhere |
+//!
+//! Fields of an option type are nullable; every other column is
+//! `NOT NULL`. Module paths are not part of table names, so records of the
+//! same name in two modules are rejected.
+
+use crate::types::{SqlDialect, SqlType};
+use fusabi_provider_common::{base_name, Naming, TypeShape};
+use fusabi_type_providers::{
+    DuDef, GeneratedTypes, ProviderError, ProviderParams, ProviderResult, RecordDef, TypeDefinition,
+};
+use std::collections::HashMap;
+
+/// Params key selecting the dialect of exported DDL
+pub const DIALECT_PARAM: &str = "dialect";
+
+/// Words quoted when used as table or column names
+const RESERVED: &[&str] = &[
+    "all", "and", "as", "by", "check", "column", "create", "default", "desc", "from", "group",
+    "index", "key", "limit", "not", "null", "or", "order", "primary", "references", "select",
+    "table", "to", "unique", "user", "where",
+];
+
+/// Read the `dialect` param, defaulting to generic SQL
+pub fn dialect_from_params(params: &ProviderParams) -> ProviderResult<SqlDialect> {
+    match params.custom.get(DIALECT_PARAM) {
+        Some(value) => SqlDialect::parse(value).ok_or_else(|| {
+            ProviderError::InvalidSource(format!(
+                "Invalid dialect '{}': expected postgres, mysql, sqlite or generic",
+                value
+            ))
+        }),
+        None => Ok(SqlDialect::Generic),
+    }
+}
+
+/// Render the records of `types` as `CREATE TABLE` statements
+pub fn to_sql_ddl(types: &GeneratedTypes, dialect: SqlDialect) -> ProviderResult<String> {
+    let definitions: Vec<(&[String], &TypeDefinition)> = types
+        .root_types
+        .iter()
+        .map(|definition| (&[] as &[String], definition))
+        .chain(
+            types
+                .modules
+                .iter()
+                .flat_map(|m| m.types.iter().map(move |definition| (m.path.as_slice(), definition))),
+        )
+        .collect();
+
+    let enums = definitions
+        .iter()
+        .filter_map(|&(_, definition)| match definition {
+            TypeDefinition::Du(du) if du.variants.iter().all(|v| v.fields.is_empty()) => {
+                Some((base_name(&du.name), du))
+            }
+            _ => None,
+        })
+        .collect();
+    let exporter = Exporter { dialect, enums };
+
+    let mut tables: HashMap<String, String> = HashMap::new();
+    let mut statements = Vec::new();
+    for (path, definition) in definitions {
+        let TypeDefinition::Record(record) = definition else {
+            continue;
+        };
+        let qualified = path.iter().chain([&record.name]).cloned().collect::<Vec<_>>().join(".");
+        let table = Naming::SnakeCase.apply(base_name(&record.name));
+        if let Some(previous) = tables.insert(table.clone(), qualified.clone()) {
+            return Err(ProviderError::InvalidSource(format!(
+                "Records {} and {} both map to table {}",
+                previous, qualified, table
+            )));
+        }
+        statements.push(exporter.create_table(&table, record));
+    }
+
+    Ok(statements.join("\n"))
+}
+
+/// Column type and, for unions of unit variants, the allowed names
+type ColumnType<'a> = (SqlType, Option<&'a DuDef>);
+
+/// Converts records to DDL in one dialect
+struct Exporter<'a> {
+    dialect: SqlDialect,
+    enums: HashMap<&'a str, &'a DuDef>,
+}
+
+impl<'a> Exporter<'a> {
+    fn create_table(&self, table: &str, record: &RecordDef) -> String {
+        let mut lines = Vec::new();
+        let mut checks = Vec::new();
+
+        for (field_name, type_expr) in &record.fields {
+            let shape = TypeShape::from_type_expr(type_expr);
+            let (sql_type, values) = self.column_type(shape.unwrap_option());
+            let column = self.quote(field_name);
+            let null = if shape.is_option() { "" } else { " NOT NULL" };
+            lines.push(format!("{} {}{}", column, sql_type, null));
+
+            if let Some(du) = values {
+                let names: Vec<String> = du
+                    .variants
+                    .iter()
+                    .map(|v| format!("'{}'", v.name.replace('\'', "''")))
+                    .collect();
+                checks.push(format!("CHECK ({} IN ({}))", column, names.join(", ")));
+            }
+        }
+        lines.extend(checks);
+
+        let body: Vec<String> = lines.iter().map(|line| format!("    {}", line)).collect();
+        format!("CREATE TABLE {} (\n{}\n);\n", self.quote(table), body.join(",\n"))
+    }
+
+    fn column_type(&self, shape: &TypeShape) -> ColumnType<'a> {
+        match shape {
+            TypeShape::Named(name) => match name.as_str() {
+                "string" => (SqlType::Text, None),
+                "int" | "int64" | "uint" | "uint64" => (SqlType::BigInt, None),
+                "float" => (SqlType::Double, None),
+                "bool" => (SqlType::Boolean, None),
+                "bytes" if self.dialect == SqlDialect::PostgreSQL => (SqlType::Bytea, None),
+                "bytes" => (SqlType::Blob, None),
+                _ => {
+                    let bare = base_name(name.rsplit('.').next().unwrap_or(name));
+                    match self.enums.get(bare).copied() {
+                        Some(du) => (SqlType::Text, Some(du)),
+                        None => (self.json(), None),
+                    }
+                }
+            },
+            TypeShape::List(inner) if self.dialect == SqlDialect::PostgreSQL => {
+                match self.column_type(inner) {
+                    (SqlType::JsonB, _) => (SqlType::JsonB, None),
+                    (element, _) => (SqlType::Array { element_type: Box::new(element) }, None),
+                }
+            }
+            _ => (self.json(), None),
+        }
+    }
+
+    fn json(&self) -> SqlType {
+        match self.dialect {
+            SqlDialect::PostgreSQL => SqlType::JsonB,
+            _ => SqlType::Json,
+        }
+    }
+
+    /// Quote names that are not plain lowercase identifiers or are reserved
+    fn quote(&self, name: &str) -> String {
+        let plain = name.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+            && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+            && !RESERVED.contains(&name);
+        if plain {
+            return name.to_string();
+        }
+
+        let quote = match self.dialect {
+            SqlDialect::MySQL => '`',
+            _ => '"',
+        };
+        let escaped = name.replace(quote, &format!("{}{}", quote, quote));
+        format!("{}{}{}", quote, escaped, quote)
+    }
+}
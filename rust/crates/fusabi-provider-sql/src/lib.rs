@@ -1,6 +1,24 @@
 //! SQL DDL Type Provider
 //!
 //! Generates Fusabi types from SQL DDL (Data Definition Language) statements.
+//! Table and column comments, from MySQL `COMMENT` clauses or PostgreSQL
+//! `COMMENT ON` statements, become documentation on the generated records,
+//! and column `DEFAULT` clauses become field defaults.
+//!
+//! [`AttributedProvider`] records `sql.table` on every record, and on its
+//! fields `sql.type` (the column type, such as `VARCHAR(255)`),
+//! `sql.primary_key` and `sql.references` (`table.column`) where they apply.
+//!
+//! [`DecoderCodegen`] plans row decoders that read each field from its
+//! column, in declaration order, as in CSV exports and query results.
+//!
+//! [`SchemaExporter`] goes the other way, writing `CREATE TABLE` statements
+//! for hand-maintained Fusabi records in the dialect named by the `dialect`
+//! param; see [`to_sql_ddl`].
+//!
+//! All tables share the namespace module. Through [`GroupedProvider`],
+//! `module_layout=nested` gives every table a module of its own instead,
+//! such as `Database.Users` holding the `Users` record.
 //!
 //! # Supported Databases
 //!
@@ -19,14 +37,23 @@
 //! let types = provider.generate_types(&schema, "Database")?;
 //! ```
 
+mod ddl;
 mod parser;
 mod types;
 
+pub use ddl::{dialect_from_params, to_sql_ddl, DIALECT_PARAM};
 pub use types::{SqlDialect, SqlSchema, SqlType};
 
+use fusabi_provider_common::{
+    read_source, AttributedProvider, Capabilities, DecoderCodegen, DecoderPlan, DefaultValue,
+    DefaultedProvider, DescribedProvider, Diagnostic, DocumentedProvider, FieldCodec, FieldDecoder,
+    FieldDefaults, GroupedProvider, InputLimits, ModuleBuilder, ModuleGrouping, OutputFeature,
+    SchemaExporter, SourceValidator, TypeAttributes,
+    TypeDecoder, TypeDocs, TypeShape, WireFormat,
+};
 use fusabi_type_providers::{
-    GeneratedModule, GeneratedTypes, NamingStrategy, ProviderError, ProviderParams,
-    ProviderResult, RecordDef, Schema, TypeDefinition, TypeExpr, TypeGenerator, TypeProvider,
+    GeneratedTypes, NamingStrategy, ProviderError, ProviderParams,
+    ProviderResult, RecordDef, Schema, TypeDefinition, TypeGenerator, TypeProvider,
 };
 
 /// SQL DDL type provider
@@ -47,25 +74,106 @@ impl SqlProvider {
     }
 
     /// Generate types from parsed SQL schema
+    ///
+    /// Tables share the namespace module, or with a nested grouping each
+    /// get a module of their own named after the record.
     fn generate_from_schema(
         &self,
         schema: &types::SqlSchema,
         namespace: &str,
-    ) -> ProviderResult<GeneratedTypes> {
+        grouping: ModuleGrouping,
+    ) -> ProviderResult<(GeneratedTypes, TypeDocs, FieldDefaults)> {
         let mut result = GeneratedTypes::new();
-        let mut tables_module = GeneratedModule::new(vec![namespace.to_string()]);
+        let mut docs = TypeDocs::new();
+        let mut defaults = FieldDefaults::new();
+        let mut modules = ModuleBuilder::new(namespace, grouping);
 
         // Generate a RecordDef for each table
-        for (_table_name, table) in &schema.tables {
+        for table in schema.tables.values() {
             let type_def = self.table_to_typedef(table)?;
-            tables_module.types.push(type_def);
+            let type_name = self.generator.naming.apply(&table.name);
+            let path = modules.path(&[&type_name]);
+            modules.push(&[&type_name], type_def);
+
+            if let Some(comment) = &table.comment {
+                docs.set_type(&path, &type_name, comment);
+            }
+            for column in &table.columns {
+                if let Some(comment) = &column.comment {
+                    docs.set_member(&path, &type_name, &column.name, comment);
+                }
+                if let Some(expr) = column.default_expression() {
+                    let value = sql_default(expr, &column.sql_type);
+                    defaults.set(&path, &type_name, &column.name, value);
+                }
+            }
         }
 
-        if !tables_module.types.is_empty() {
-            result.modules.push(tables_module);
+        result.modules = modules.finish()?;
+        Ok((result, docs, defaults))
+    }
+
+    /// Record table names, column types and keys
+    fn annotate_schema(&self, schema: &types::SqlSchema, path: &[String]) -> TypeAttributes {
+        let mut attributes = TypeAttributes::new();
+
+        for table in schema.tables.values() {
+            let type_name = self.generator.naming.apply(&table.name);
+            attributes.set_type(path, &type_name, "sql.table", &table.name);
+
+            for column in &table.columns {
+                attributes.set_member(path, &type_name, &column.name, "sql.type", &column.sql_type);
+                if column.is_primary_key() {
+                    attributes.set_member(path, &type_name, &column.name, "sql.primary_key", true);
+                }
+                for constraint in &column.constraints {
+                    if let types::Constraint::ForeignKey { table: target, column: target_column } = constraint {
+                        let reference = format!("{}.{}", target, target_column);
+                        attributes.set_member(path, &type_name, &column.name, "sql.references", reference);
+                    }
+                }
+            }
+
+            for constraint in &table.table_constraints {
+                match constraint {
+                    types::TableConstraint::PrimaryKey(columns) => {
+                        for column in columns {
+                            attributes.set_member(path, &type_name, column, "sql.primary_key", true);
+                        }
+                    }
+                    types::TableConstraint::ForeignKey {
+                        columns,
+                        referenced_table,
+                        referenced_columns,
+                    } => {
+                        for (column, target) in columns.iter().zip(referenced_columns) {
+                            let reference = format!("{}.{}", referenced_table, target);
+                            attributes.set_member(path, &type_name, column, "sql.references", reference);
+                        }
+                    }
+                    _ => {}
+                }
+            }
         }
 
-        Ok(result)
+        attributes
+    }
+
+    /// Parse the SQL schema and generate types, docs and defaults
+    fn generate_all(
+        &self,
+        schema: &Schema,
+        namespace: &str,
+    ) -> ProviderResult<(GeneratedTypes, TypeDocs, FieldDefaults)> {
+        match schema {
+            Schema::Custom(sql_str) => {
+                let parsed = self.parse_sql(sql_str)?;
+                self.generate_from_schema(&parsed, namespace, ModuleGrouping::Flat)
+            }
+            _ => Err(ProviderError::ParseError(
+                "Expected SQL schema".to_string(),
+            )),
+        }
     }
 
     /// Convert a SQL table to a Fusabi RecordDef
@@ -73,16 +181,12 @@ impl SqlProvider {
         let mut fields = Vec::new();
 
         for column in &table.columns {
-            let type_expr = self.sql_type_to_type_expr(&column.sql_type)?;
+            let shape = self.sql_type_to_shape(&column.sql_type)?;
 
             // Wrap in option if nullable and not primary key
-            let final_type = if column.is_nullable() && !column.is_primary_key() {
-                TypeExpr::Named(format!("{} option", type_expr))
-            } else {
-                type_expr
-            };
+            let final_type = shape.option_unless(!column.is_nullable() || column.is_primary_key());
 
-            fields.push((column.name.clone(), final_type));
+            fields.push((column.name.clone(), final_type.into()));
         }
 
         Ok(TypeDefinition::Record(RecordDef {
@@ -92,7 +196,7 @@ impl SqlProvider {
     }
 
     /// Map SQL types to Fusabi types
-    fn sql_type_to_type_expr(&self, sql_type: &types::SqlType) -> ProviderResult<TypeExpr> {
+    fn sql_type_to_shape(&self, sql_type: &types::SqlType) -> ProviderResult<TypeShape> {
         let type_name = match sql_type {
             // Integer types -> int
             SqlType::TinyInt
@@ -134,15 +238,14 @@ impl SqlProvider {
 
             // Array types -> list
             SqlType::Array { element_type } => {
-                let element = self.sql_type_to_type_expr(element_type)?;
-                format!("{} list", element)
+                return Ok(self.sql_type_to_shape(element_type)?.list());
             }
 
             // Custom types -> use type name as-is
             SqlType::Custom(name) => name.clone(),
         };
 
-        Ok(TypeExpr::Named(type_name))
+        Ok(TypeShape::named(type_name))
     }
 }
 
@@ -157,42 +260,263 @@ impl TypeProvider for SqlProvider {
         "SqlProvider"
     }
 
-    fn resolve_schema(&self, source: &str, _params: &ProviderParams) -> ProviderResult<Schema> {
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
         // Support inline SQL or file paths
         let sql_str = if source.to_uppercase().trim().starts_with("CREATE") {
             // Inline SQL
             source.to_string()
-        } else if source.starts_with("file://") {
-            // File URL
-            let path = source.strip_prefix("file://").unwrap();
-            std::fs::read_to_string(path)
-                .map_err(|e| ProviderError::IoError(e.to_string()))?
         } else {
-            // Treat as file path
-            std::fs::read_to_string(source)
-                .map_err(|e| ProviderError::IoError(e.to_string()))?
+            read_source(source, params)?
         };
+        parser::check_limits(&sql_str, &InputLimits::from_params(params)?)?;
 
         // Store SQL as custom schema
         Ok(Schema::Custom(sql_str))
     }
 
     fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
-        match schema {
-            Schema::Custom(sql_str) => {
-                let parsed = self.parse_sql(sql_str)?;
-                self.generate_from_schema(&parsed, namespace)
+        self.generate_documented(schema, namespace).map(|(types, _)| types)
+    }
+}
+
+impl DocumentedProvider for SqlProvider {
+    fn generate_documented(
+        &self,
+        schema: &Schema,
+        namespace: &str,
+    ) -> ProviderResult<(GeneratedTypes, TypeDocs)> {
+        self.generate_all(schema, namespace)
+            .map(|(types, docs, _)| (types, docs))
+    }
+}
+
+impl DefaultedProvider for SqlProvider {
+    fn generate_with_defaults(
+        &self,
+        schema: &Schema,
+        namespace: &str,
+    ) -> ProviderResult<(GeneratedTypes, FieldDefaults)> {
+        self.generate_all(schema, namespace)
+            .map(|(types, _, defaults)| (types, defaults))
+    }
+}
+
+impl AttributedProvider for SqlProvider {
+    fn generate_attributed(
+        &self,
+        schema: &Schema,
+        namespace: &str,
+    ) -> ProviderResult<(GeneratedTypes, TypeAttributes)> {
+        let Schema::Custom(sql_str) = schema else {
+            return Err(ProviderError::ParseError("Expected SQL schema".to_string()));
+        };
+
+        let parsed = self.parse_sql(sql_str)?;
+        let (types, _, _) = self.generate_from_schema(&parsed, namespace, ModuleGrouping::Flat)?;
+        let attributes = self.annotate_schema(&parsed, &[namespace.to_string()]);
+        Ok((types, attributes))
+    }
+}
+
+impl DecoderCodegen for SqlProvider {
+    fn wire_format(&self) -> WireFormat {
+        WireFormat::Row
+    }
+
+    fn generate_decoders(
+        &self,
+        schema: &Schema,
+        namespace: &str,
+    ) -> ProviderResult<(GeneratedTypes, DecoderPlan)> {
+        let Schema::Custom(sql_str) = schema else {
+            return Err(ProviderError::ParseError("Expected SQL schema".to_string()));
+        };
+
+        let parsed = self.parse_sql(sql_str)?;
+        let (types, _, _) = self.generate_from_schema(&parsed, namespace, ModuleGrouping::Flat)?;
+        let mut plan = DecoderPlan::new(WireFormat::Row);
+        for table in parsed.tables.values() {
+            let fields = table
+                .columns
+                .iter()
+                .enumerate()
+                .map(|(index, column)| FieldDecoder::new(&column.name, FieldCodec::Column { index }))
+                .collect();
+            let type_name = self.generator.naming.apply(&table.name);
+            plan.set(&[namespace.to_string()], &type_name, TypeDecoder::Record(fields));
+        }
+        Ok((types, plan))
+    }
+}
+
+impl GroupedProvider for SqlProvider {
+    fn default_grouping(&self) -> ModuleGrouping {
+        ModuleGrouping::Flat
+    }
+
+    fn generate_grouped(
+        &self,
+        schema: &Schema,
+        namespace: &str,
+        grouping: ModuleGrouping,
+    ) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(sql_str) = schema else {
+            return Err(ProviderError::ParseError("Expected SQL schema".to_string()));
+        };
+
+        let parsed = self.parse_sql(sql_str)?;
+        self.generate_from_schema(&parsed, namespace, grouping)
+            .map(|(types, _, _)| types)
+    }
+}
+
+impl SchemaExporter for SqlProvider {
+    fn schema_extension(&self) -> &str {
+        "sql"
+    }
+
+    fn export_schema(&self, types: &GeneratedTypes, params: &ProviderParams) -> ProviderResult<String> {
+        to_sql_ddl(types, dialect_from_params(params)?)
+    }
+}
+
+/// Interpret a `DEFAULT` expression for a column of type `sql_type`
+///
+/// Literals become values; anything else, such as `CURRENT_TIMESTAMP` or
+/// `nextval('seq')`, is kept as an expression.
+fn sql_default(expr: &str, sql_type: &SqlType) -> DefaultValue {
+    // PostgreSQL dumps cast literals, as in `'active'::text`
+    let literal = match expr.rfind("::") {
+        Some(idx) if expr[..idx].ends_with('\'') => &expr[..idx],
+        _ => expr,
+    };
+    let literal = literal.trim_start_matches('(').trim_end_matches(')');
+
+    if literal.eq_ignore_ascii_case("NULL") {
+        return DefaultValue::None;
+    }
+    let (text, quoted) = match literal.strip_prefix('\'').and_then(|l| l.strip_suffix('\'')) {
+        Some(inner) => (inner.replace("''", "'"), true),
+        None => (literal.to_string(), false),
+    };
+
+    let value = match sql_type {
+        SqlType::Boolean => match text.to_ascii_lowercase().as_str() {
+            "true" | "t" | "1" => Some(DefaultValue::Bool(true)),
+            "false" | "f" | "0" => Some(DefaultValue::Bool(false)),
+            _ => None,
+        },
+        SqlType::TinyInt
+        | SqlType::SmallInt
+        | SqlType::Int
+        | SqlType::BigInt
+        | SqlType::Serial
+        | SqlType::BigSerial => text.parse().ok().map(DefaultValue::Int),
+        SqlType::Real
+        | SqlType::Float
+        | SqlType::Double
+        | SqlType::Decimal { .. }
+        | SqlType::Numeric { .. } => text.parse().ok().map(DefaultValue::Float),
+        _ if quoted => Some(DefaultValue::String(text)),
+        _ => None,
+    };
+    value.unwrap_or_else(|| DefaultValue::Expr(expr.to_string()))
+}
+
+impl SourceValidator for SqlProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(sql_str) = schema else {
+            return Err(ProviderError::ParseError("Expected SQL schema".to_string()));
+        };
+
+        let parsed = self.parse_sql(sql_str)?;
+        let mut diagnostics = Vec::new();
+
+        if parsed.tables.is_empty() {
+            diagnostics.push(Diagnostic::warning(
+                "no-tables",
+                "No CREATE TABLE statements found",
+            ));
+        }
+
+        for table in parsed.tables.values() {
+            for column in &table.columns {
+                let location = format!("{}.{}", table.name, column.name);
+                let mut sql_type = &column.sql_type;
+                while let SqlType::Array { element_type } = sql_type {
+                    sql_type = element_type;
+                }
+
+                match sql_type {
+                    SqlType::Custom(name) => diagnostics.push(
+                        Diagnostic::warning(
+                            "unmapped-type",
+                            format!("SQL type `{}` has no Fusabi mapping", name),
+                        )
+                        .at(location.clone()),
+                    ),
+                    SqlType::Json | SqlType::JsonB => diagnostics.push(
+                        Diagnostic::warning("lossy-conversion", "JSON column is typed as `string`")
+                            .at(location.clone()),
+                    ),
+                    SqlType::Decimal { .. } | SqlType::Numeric { .. } => diagnostics.push(
+                        Diagnostic::info("lossy-conversion", "Fixed-point column is typed as `float`")
+                            .at(location.clone()),
+                    ),
+                    _ => {}
+                }
+
+                for constraint in &column.constraints {
+                    if let types::Constraint::ForeignKey { table: target, .. } = constraint {
+                        diagnostics.push(
+                            Diagnostic::info(
+                                "foreign-key-ignored",
+                                format!("Reference to `{}` is not reflected in generated types", target),
+                            )
+                            .at(location.clone()),
+                        );
+                    }
+                }
+            }
+
+            for constraint in &table.table_constraints {
+                if let types::TableConstraint::ForeignKey { referenced_table, .. } = constraint {
+                    diagnostics.push(
+                        Diagnostic::info(
+                            "foreign-key-ignored",
+                            format!(
+                                "Reference to `{}` is not reflected in generated types",
+                                referenced_table
+                            ),
+                        )
+                        .at(table.name.clone()),
+                    );
+                }
             }
-            _ => Err(ProviderError::ParseError(
-                "Expected SQL schema".to_string(),
-            )),
         }
+
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for SqlProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .feature(OutputFeature::Docs)
+            .feature(OutputFeature::Attributes)
+            .feature(OutputFeature::Defaults)
+            .feature(OutputFeature::Diagnostics)
+            .feature(OutputFeature::Decoders)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fusabi_provider_common::emit::golden;
+    use fusabi_provider_common::generate_with_layout;
+    use fusabi_type_providers::GeneratedModule;
 
     #[test]
     fn test_generate_simple_table() {
@@ -226,6 +550,20 @@ mod tests {
         } else {
             panic!("Expected Record type definition");
         }
+
+        fusabi_provider_testkit::assert_valid(&types);
+        fusabi_provider_testkit::assert_snapshot!(types, "users");
+    }
+
+    #[test]
+    fn test_conformance() {
+        let provider = SqlProvider::new();
+        fusabi_provider_testkit::Conformance::new(&provider, "CREATE TABLE users (id INT PRIMARY KEY);")
+            .with_extension("sql")
+            .with_invalid("CREATE TABLE users id INT;")
+            .with_invalid("CREATE TABLE users (id INT")
+            .with_empty("")
+            .assert_conforms();
     }
 
     #[test]
@@ -251,6 +589,128 @@ mod tests {
         assert_eq!(types.modules.len(), 1);
         let module = &types.modules[0];
         assert_eq!(module.types.len(), 2);
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/sql.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_generate_documented() {
+        let provider = SqlProvider::new();
+        let sql = r#"
+            CREATE TABLE users (
+                id INT PRIMARY KEY,
+                name VARCHAR(255) NOT NULL
+            );
+
+            CREATE TABLE posts (
+                id INT PRIMARY KEY,
+                user_id INT NOT NULL,
+                title TEXT NOT NULL,
+                content TEXT COMMENT 'Markdown body'
+            );
+
+            COMMENT ON TABLE posts IS 'Published blog posts';
+        "#;
+
+        let schema = provider.resolve_schema(sql, &ProviderParams::default()).unwrap();
+        let (types, docs) = provider.generate_documented(&schema, "Database").unwrap();
+
+        let module = &types.modules[0];
+        assert_eq!(docs.type_doc(&module.path, "Posts"), Some("Published blog posts"));
+        assert_eq!(docs.member_doc(&module.path, "Posts", "content"), Some("Markdown body"));
+
+        golden::assert_fusabi_documented(
+            &types,
+            &docs,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/sql-documented.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_module_layout() {
+        let provider = SqlProvider::new();
+        let sql = "CREATE TABLE users (id INT PRIMARY KEY); CREATE TABLE posts (id INT PRIMARY KEY);";
+
+        let params = ProviderParams::default().with("module_layout", "nested");
+        let types = generate_with_layout(&provider, sql, &params, "Db").unwrap();
+        let mut paths: Vec<String> = types.modules.iter().map(|m| m.path.join(".")).collect();
+        paths.sort();
+        assert_eq!(paths, ["Db.Posts", "Db.Users"]);
+
+        let types = generate_with_layout(&provider, sql, &ProviderParams::default(), "Db").unwrap();
+        assert_eq!(types.modules.len(), 1);
+        assert_eq!(types.modules[0].types.len(), 2);
+    }
+
+    #[test]
+    fn test_column_defaults() {
+        let provider = SqlProvider::new();
+        let sql = r#"
+            CREATE TABLE settings (
+                id INT PRIMARY KEY,
+                theme VARCHAR(50) NOT NULL DEFAULT 'light',
+                motto TEXT DEFAULT 'it''s fine',
+                notifications BOOLEAN DEFAULT true,
+                retries INT DEFAULT 3,
+                ratio REAL DEFAULT 1,
+                status TEXT DEFAULT 'active'::text,
+                parent_id INT DEFAULT NULL,
+                created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+            );
+        "#;
+
+        let schema = provider.resolve_schema(sql, &ProviderParams::default()).unwrap();
+        let (types, defaults) = provider.generate_with_defaults(&schema, "Database").unwrap();
+        let path = &types.modules[0].path;
+        let get = |field: &str| defaults.get(path, "Settings", field).cloned();
+
+        assert_eq!(get("id"), None);
+        assert_eq!(get("theme"), Some(DefaultValue::String("light".to_string())));
+        assert_eq!(get("motto"), Some(DefaultValue::String("it's fine".to_string())));
+        assert_eq!(get("notifications"), Some(DefaultValue::Bool(true)));
+        assert_eq!(get("retries"), Some(DefaultValue::Int(3)));
+        assert_eq!(get("ratio"), Some(DefaultValue::Float(1.0)));
+        assert_eq!(get("status"), Some(DefaultValue::String("active".to_string())));
+        assert_eq!(get("parent_id"), Some(DefaultValue::None));
+        assert_eq!(
+            get("created_at"),
+            Some(DefaultValue::Expr("CURRENT_TIMESTAMP".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_attributes() {
+        let provider = SqlProvider::new();
+        let sql = r#"
+            CREATE TABLE orders (
+                id BIGINT,
+                user_id INT REFERENCES users(id),
+                total DECIMAL(10, 2),
+                tags TEXT[],
+                PRIMARY KEY (id)
+            );
+        "#;
+
+        let schema = provider.resolve_schema(sql, &ProviderParams::default()).unwrap();
+        let (types, attributes) = provider.generate_attributed(&schema, "Shop").unwrap();
+        let path = &types.modules[0].path;
+
+        assert_eq!(attributes.type_attribute(path, "Orders", "sql.table"), Some("orders"));
+        assert_eq!(attributes.member_attribute(path, "Orders", "id", "sql.type"), Some("BIGINT"));
+        assert_eq!(attributes.member_attribute(path, "Orders", "id", "sql.primary_key"), Some("true"));
+        assert_eq!(
+            attributes.member_attribute(path, "Orders", "user_id", "sql.references"),
+            Some("users.id")
+        );
+        assert_eq!(
+            attributes.member_attribute(path, "Orders", "total", "sql.type"),
+            Some("DECIMAL(10, 2)")
+        );
+        assert_eq!(attributes.member_attribute(path, "Orders", "tags", "sql.type"), Some("TEXT[]"));
+        assert_eq!(attributes.member_attribute(path, "Orders", "total", "sql.primary_key"), None);
     }
 
     #[test]
@@ -340,4 +800,140 @@ mod tests {
             assert!(record.fields[2].1.to_string().contains("list"));
         }
     }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = SqlProvider::new();
+        let sql = r#"
+            CREATE TABLE orders (
+                id INT PRIMARY KEY,
+                total MONEY,
+                payload JSONB
+            );
+        "#;
+
+        let report = provider.validate_source(sql, &ProviderParams::default());
+        assert!(report.is_valid());
+        assert_eq!(report.type_count, 1);
+
+        let codes: Vec<&str> = report.warnings().map(|d| d.code.as_str()).collect();
+        assert!(codes.contains(&"unmapped-type"));
+        assert!(codes.contains(&"lossy-conversion"));
+
+        let unmapped = report.warnings().find(|d| d.code == "unmapped-type").unwrap();
+        assert_eq!(unmapped.location.as_deref(), Some("orders.total"));
+    }
+
+    #[test]
+    fn test_generation_report() {
+        let provider = SqlProvider::new();
+        let sql = r#"
+            CREATE TABLE users (id INT PRIMARY KEY);
+            CREATE TABLE orders (
+                id INT PRIMARY KEY,
+                user_id INT REFERENCES users(id),
+                total DECIMAL(10, 2)
+            );
+        "#;
+
+        let (types, report) = provider
+            .generate_with_report(sql, &ProviderParams::default(), "Shop")
+            .unwrap();
+        assert_eq!(report.type_count(), types.modules.iter().map(|m| m.types.len()).sum::<usize>());
+        assert_eq!(report.skipped().next().unwrap().location.as_deref(), Some("orders.user_id"));
+        assert_eq!(report.lossy().next().unwrap().location.as_deref(), Some("orders.total"));
+        assert_eq!(report.coerced().count(), 0);
+    }
+
+    #[test]
+    fn test_source_size_limit() {
+        let provider = SqlProvider::new();
+        let path = std::env::temp_dir().join("fusabi-sql-size-limit.sql");
+        std::fs::write(&path, "CREATE TABLE t (id INT PRIMARY KEY);").unwrap();
+        let source = path.to_str().unwrap();
+
+        assert!(provider.resolve_schema(source, &ProviderParams::default()).is_ok());
+
+        let params = ProviderParams::default().with("max_source_bytes", "8");
+        assert!(provider.resolve_schema(source, &params).is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_validate_missing_file() {
+        let provider = SqlProvider::new();
+        let report = provider.validate_source("does/not/exist.sql", &ProviderParams::default());
+        assert!(!report.is_valid());
+    }
+
+    #[test]
+    fn test_generate_decoders() {
+        let provider = SqlProvider::new();
+        let sql = "CREATE TABLE users (id INT PRIMARY KEY, email VARCHAR(255), avatar BYTEA);";
+        let schema = provider.resolve_schema(sql, &ProviderParams::default()).unwrap();
+        let (types, plan) = provider.generate_decoders(&schema, "Db").unwrap();
+
+        assert_eq!(plan.format(), WireFormat::Row);
+        let type_name = provider.generator.naming.apply("users");
+        let Some(TypeDecoder::Record(fields)) = plan.decoder(&["Db".to_string()], &type_name) else {
+            panic!("Expected a record decoder");
+        };
+        assert_eq!(fields[1], FieldDecoder::new("email", FieldCodec::Column { index: 1 }));
+
+        let source = fusabi_provider_common::emit_rust_decoders(&types, &plan, &Default::default());
+        assert!(source.contains("email: super::decode_runtime::optional_column(row, 1, \"email\")?,"));
+        assert!(source.contains("avatar: super::decode_runtime::optional_bytes_column(row, 2, \"avatar\")?,"));
+    }
+
+    #[test]
+    fn test_export_schema() {
+        use fusabi_type_providers::{DuDef, TypeExpr, VariantDef};
+
+        let named = |s: &str| TypeExpr::Named(s.to_string());
+        let mut module = GeneratedModule::new(vec!["Db".to_string()]);
+        module.types.push(TypeDefinition::Record(RecordDef {
+            name: "User".to_string(),
+            fields: vec![
+                ("id".to_string(), named("int")),
+                ("email".to_string(), named("string option")),
+                ("tags".to_string(), named("string list")),
+                ("status".to_string(), named("Status")),
+            ],
+        }));
+        module.types.push(TypeDefinition::Du(DuDef {
+            name: "Status".to_string(),
+            variants: vec![
+                VariantDef::new_simple("Active".to_string()),
+                VariantDef::new_simple("Inactive".to_string()),
+            ],
+        }));
+        let mut types = GeneratedTypes::new();
+        types.modules.push(module);
+
+        let provider = SqlProvider::new();
+        let params = ProviderParams::default().with(DIALECT_PARAM, "postgres");
+        let ddl = provider.export_schema(&types, &params).unwrap();
+        assert_eq!(
+            ddl,
+            "CREATE TABLE \"user\" (\n    id BIGINT NOT NULL,\n    email TEXT,\n    tags TEXT[] NOT NULL,\n    \
+             status TEXT NOT NULL,\n    CHECK (status IN ('Active', 'Inactive'))\n);\n"
+        );
+
+        let schema = provider.resolve_schema(&ddl, &ProviderParams::default()).unwrap();
+        let reimported = provider.generate_types(&schema, "Db").unwrap();
+        let TypeDefinition::Record(record) = &reimported.modules[0].types[0] else {
+            panic!("Expected Record type definition");
+        };
+        let fields: Vec<&str> = record.fields.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(fields, ["id", "email", "tags", "status"]);
+
+        let mysql = ProviderParams::default().with(DIALECT_PARAM, "mysql");
+        let ddl = provider.export_schema(&types, &mysql).unwrap();
+        assert!(ddl.starts_with("CREATE TABLE `user` ("));
+        assert!(ddl.contains("tags JSON NOT NULL"));
+
+        let oracle = ProviderParams::default().with(DIALECT_PARAM, "oracle");
+        assert!(provider.export_schema(&types, &oracle).is_err());
+    }
 }
@@ -1,14 +1,45 @@
 //! SQL DDL parser
+//!
+//! The parser works on slices of the DDL text, so errors report the line
+//! and column of the statement part they are about, and the table being
+//! parsed.
+//!
+//! [`check_limits`] rejects DDL that exceeds the caller's input limits
+//! before it is parsed.
 
 use crate::types::{Column, Constraint, SqlSchema, SqlType, Table, TableConstraint};
+use fusabi_provider_common::error::Context;
+use fusabi_provider_common::limits::InputLimits;
+use fusabi_provider_common::span::{parse_error_at, slice_offset};
 use fusabi_type_providers::{ProviderError, ProviderResult};
 
+/// Syntax error at a slice of the DDL text
+struct SyntaxError<'a> {
+    at: &'a str,
+    message: String,
+}
+
+type SyntaxResult<'a, T> = Result<T, SyntaxError<'a>>;
+
+fn syntax_error(at: &str, message: impl Into<String>) -> SyntaxError<'_> {
+    SyntaxError {
+        at,
+        message: message.into(),
+    }
+}
+
+/// Parse error at the position of a syntax error in `sql`
+fn located(sql: &str, error: SyntaxError<'_>) -> ProviderError {
+    parse_error_at(sql, slice_offset(sql, error.at).unwrap_or_default(), error.message)
+}
+
 /// Parse SQL DDL statements into a SqlSchema
 pub fn parse_sql_ddl(sql: &str) -> ProviderResult<SqlSchema> {
     let mut schema = SqlSchema::new();
 
     // Split into individual statements
     let statements = split_statements(sql);
+    let mut comments = Vec::new();
 
     for stmt in statements {
         let stmt = stmt.trim();
@@ -18,24 +49,191 @@ pub fn parse_sql_ddl(sql: &str) -> ProviderResult<SqlSchema> {
 
         // Parse CREATE TABLE statements
         if stmt.to_uppercase().starts_with("CREATE TABLE") {
-            let table = parse_create_table(stmt)?;
+            let (table_name, rest) = parse_create_table_header(stmt).map_err(|e| located(sql, e))?;
+            let table = parse_create_table(table_name.clone(), rest)
+                .map_err(|e| located(sql, e))
+                .with_context(|| format!("while parsing table {}", table_name))?;
             schema.add_table(table);
+        } else if stmt.to_ascii_uppercase().starts_with("COMMENT ON") {
+            comments.extend(parse_comment_on(stmt));
         }
         // Ignore other statements for now (CREATE INDEX, ALTER TABLE, etc.)
     }
 
+    // COMMENT ON statements are applied once every table is known
+    for (target, comment) in comments {
+        apply_comment(&mut schema, target, comment);
+    }
+
     Ok(schema)
 }
 
+/// Check SQL DDL against input limits
+///
+/// Parentheses may nest at most `max_depth` deep, and at most `max_types`
+/// tables may be created.
+pub fn check_limits(sql: &str, limits: &InputLimits) -> ProviderResult<()> {
+    limits.check_size(sql)?;
+    limits.check_nesting(sql, '(', ')')?;
+    let tables = split_statements(sql)
+        .into_iter()
+        .filter(|stmt| stmt.to_uppercase().starts_with("CREATE TABLE"))
+        .count();
+    limits.check_type_count(tables)
+}
+
+/// Target of a `COMMENT ON` statement
+#[derive(Debug, PartialEq)]
+enum CommentTarget {
+    Table(String),
+    Column(String, String),
+}
+
+/// Parse a PostgreSQL `COMMENT ON TABLE|COLUMN name IS 'text'` statement
+///
+/// `IS NULL` yields `None`, which removes an earlier comment.
+/// Comments on other kinds of objects are ignored.
+fn parse_comment_on(stmt: &str) -> Option<(CommentTarget, Option<String>)> {
+    let rest = stmt["COMMENT ON".len()..].trim_start();
+    let upper = rest.to_ascii_uppercase();
+
+    let (is_column, rest) = if upper.starts_with("TABLE ") {
+        (false, rest["TABLE".len()..].trim_start())
+    } else if upper.starts_with("COLUMN ") {
+        (true, rest["COLUMN".len()..].trim_start())
+    } else {
+        return None;
+    };
+
+    let is_idx = find_keyword(rest, "IS")?;
+    let segments: Vec<String> = rest[..is_idx]
+        .trim()
+        .split('.')
+        .map(|s| s.trim().trim_matches('"').trim_matches('`').to_string())
+        .collect();
+    let value = rest[is_idx + "IS".len()..].trim();
+    let comment = if value.eq_ignore_ascii_case("NULL") {
+        None
+    } else {
+        Some(parse_string_literal(value)?.0)
+    };
+
+    let target = if is_column {
+        let (column, table) = segments.split_last()?;
+        if table.is_empty() {
+            return None;
+        }
+        CommentTarget::Column(table.join("."), column.clone())
+    } else {
+        CommentTarget::Table(segments.join("."))
+    };
+
+    Some((target, comment))
+}
+
+/// Attach a `COMMENT ON` text to its table or column
+///
+/// Schema-qualified names fall back to the bare table name, since tables
+/// are usually declared without their schema.
+fn apply_comment(schema: &mut SqlSchema, target: CommentTarget, comment: Option<String>) {
+    let (table_name, column_name) = match target {
+        CommentTarget::Table(table) => (table, None),
+        CommentTarget::Column(table, column) => (table, Some(column)),
+    };
+
+    let key = if schema.tables.contains_key(&table_name) {
+        table_name
+    } else {
+        table_name.rsplit('.').next().unwrap_or_default().to_string()
+    };
+    let Some(table) = schema.tables.get_mut(&key) else {
+        return;
+    };
+
+    match column_name {
+        None => table.comment = comment,
+        Some(column_name) => {
+            if let Some(column) = table.columns.iter_mut().find(|c| c.name == column_name) {
+                column.comment = comment;
+            }
+        }
+    }
+}
+
+/// Split a MySQL `COMMENT 'text'` or `COMMENT = 'text'` clause off a
+/// definition, returning the remaining definition and the comment
+fn take_comment_clause(s: &str) -> (String, Option<String>) {
+    let Some(idx) = find_keyword(s, "COMMENT") else {
+        return (s.to_string(), None);
+    };
+
+    let value = s[idx + "COMMENT".len()..].trim_start();
+    let value = value.strip_prefix('=').unwrap_or(value).trim_start();
+    match parse_string_literal(value) {
+        Some((comment, rest)) => {
+            let remaining = format!("{} {}", s[..idx].trim_end(), rest.trim_start());
+            (remaining.trim().to_string(), Some(comment))
+        }
+        None => (s.to_string(), None),
+    }
+}
+
+/// Find a keyword outside of string literals, at word boundaries
+fn find_keyword(s: &str, keyword: &str) -> Option<usize> {
+    let upper = s.to_ascii_uppercase();
+    let bytes = upper.as_bytes();
+    let is_word = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    let mut in_string = false;
+
+    for (idx, &b) in bytes.iter().enumerate() {
+        if b == b'\'' {
+            in_string = !in_string;
+        } else if !in_string
+            && bytes[idx..].starts_with(keyword.as_bytes())
+            && (idx == 0 || !is_word(bytes[idx - 1]))
+            && bytes.get(idx + keyword.len()).is_none_or(|&next| !is_word(next))
+        {
+            return Some(idx);
+        }
+    }
+
+    None
+}
+
+/// Parse a single-quoted SQL string literal at the start of `s`
+///
+/// Doubled quotes (`''`) are unescaped. Returns the text and whatever
+/// follows the closing quote.
+fn parse_string_literal(s: &str) -> Option<(String, &str)> {
+    let body = s.strip_prefix('\'')?;
+    let mut text = String::new();
+    let mut chars = body.char_indices().peekable();
+
+    while let Some((idx, ch)) = chars.next() {
+        if ch == '\'' {
+            if let Some((_, '\'')) = chars.peek() {
+                chars.next();
+                text.push('\'');
+            } else {
+                return Some((text, &body[idx + 1..]));
+            }
+        } else {
+            text.push(ch);
+        }
+    }
+
+    None
+}
+
 /// Split SQL into individual statements
-fn split_statements(sql: &str) -> Vec<String> {
+fn split_statements(sql: &str) -> Vec<&str> {
     let mut statements = Vec::new();
-    let mut current = String::new();
+    let mut start = 0;
     let mut in_string = false;
     let mut string_char = ' ';
     let mut paren_depth = 0;
 
-    for ch in sql.chars() {
+    for (idx, ch) in sql.char_indices() {
         match ch {
             '\'' | '"' => {
                 if in_string && ch == string_char {
@@ -44,44 +242,37 @@ fn split_statements(sql: &str) -> Vec<String> {
                     in_string = true;
                     string_char = ch;
                 }
-                current.push(ch);
-            }
-            '(' if !in_string => {
-                paren_depth += 1;
-                current.push(ch);
-            }
-            ')' if !in_string => {
-                paren_depth -= 1;
-                current.push(ch);
             }
+            '(' if !in_string => paren_depth += 1,
+            ')' if !in_string => paren_depth -= 1,
             ';' if !in_string && paren_depth == 0 => {
-                if !current.trim().is_empty() {
-                    statements.push(current.trim().to_string());
+                let statement = sql[start..idx].trim();
+                if !statement.is_empty() {
+                    statements.push(statement);
                 }
-                current.clear();
-            }
-            _ => {
-                current.push(ch);
+                start = idx + 1;
             }
+            _ => {}
         }
     }
 
     // Add last statement if it doesn't end with semicolon
-    if !current.trim().is_empty() {
-        statements.push(current.trim().to_string());
+    let statement = sql[start..].trim();
+    if !statement.is_empty() {
+        statements.push(statement);
     }
 
     statements
 }
 
-/// Parse a CREATE TABLE statement
-fn parse_create_table(stmt: &str) -> ProviderResult<Table> {
+/// Split a CREATE TABLE statement into the table name and what follows it
+fn parse_create_table_header(stmt: &str) -> SyntaxResult<'_, (String, &str)> {
     // Remove CREATE TABLE prefix
     let stmt_upper = stmt.to_uppercase();
     let start_idx = if let Some(idx) = stmt_upper.find("CREATE TABLE") {
         idx + "CREATE TABLE".len()
     } else {
-        return Err(ProviderError::ParseError("Invalid CREATE TABLE statement".to_string()));
+        return Err(syntax_error(stmt, "Invalid CREATE TABLE statement"));
     };
 
     let rest = stmt[start_idx..].trim();
@@ -94,30 +285,34 @@ fn parse_create_table(stmt: &str) -> ProviderResult<Table> {
     };
 
     // Extract table name
-    let (table_name, rest) = extract_table_name(rest)?;
+    extract_table_name(rest)
+}
 
+/// Parse the rest of a CREATE TABLE statement after the table name
+fn parse_create_table(table_name: String, rest: &str) -> SyntaxResult<'_, Table> {
     // Find column definitions (between parentheses)
-    let (columns_str, _rest) = extract_parentheses_content(rest)?;
+    let (columns_str, options) = extract_parentheses_content(rest)?;
 
     // Parse column and table constraint definitions
-    let (columns, table_constraints) = parse_table_definitions(&columns_str)?;
+    let (columns, table_constraints) = parse_table_definitions(columns_str)?;
 
     let mut table = Table::new(table_name);
     table.columns = columns;
     table.table_constraints = table_constraints;
+    table.comment = take_comment_clause(options).1;
 
     Ok(table)
 }
 
 /// Extract table name from statement
-fn extract_table_name(s: &str) -> ProviderResult<(String, &str)> {
+fn extract_table_name(s: &str) -> SyntaxResult<'_, (String, &str)> {
     let s = s.trim();
 
     // Handle quoted table names
     if s.starts_with('"') || s.starts_with('`') {
         let quote_char = s.chars().next().unwrap();
         let end_idx = s[1..].find(quote_char)
-            .ok_or_else(|| ProviderError::ParseError("Unclosed quoted table name".to_string()))?;
+            .ok_or_else(|| syntax_error(s, "Unclosed quoted table name"))?;
         let table_name = s[1..end_idx+1].to_string();
         let rest = &s[end_idx+2..];
         return Ok((table_name, rest));
@@ -133,16 +328,16 @@ fn extract_table_name(s: &str) -> ProviderResult<(String, &str)> {
 }
 
 /// Extract content between parentheses
-fn extract_parentheses_content(s: &str) -> ProviderResult<(String, &str)> {
+fn extract_parentheses_content(s: &str) -> SyntaxResult<'_, (&str, &str)> {
     let s = s.trim();
     if !s.starts_with('(') {
-        return Err(ProviderError::ParseError("Expected opening parenthesis".to_string()));
+        return Err(syntax_error(s, "Expected opening parenthesis"));
     }
 
     let mut depth = 0;
     let mut end_idx = 0;
 
-    for (i, ch) in s.chars().enumerate() {
+    for (i, ch) in s.char_indices() {
         match ch {
             '(' => depth += 1,
             ')' => {
@@ -157,17 +352,17 @@ fn extract_parentheses_content(s: &str) -> ProviderResult<(String, &str)> {
     }
 
     if depth != 0 {
-        return Err(ProviderError::ParseError("Unmatched parentheses".to_string()));
+        return Err(syntax_error(s, "Unmatched parentheses"));
     }
 
-    let content = s[1..end_idx].to_string();
+    let content = &s[1..end_idx];
     let rest = &s[end_idx+1..];
 
     Ok((content, rest))
 }
 
 /// Parse table definitions (columns and constraints)
-fn parse_table_definitions(s: &str) -> ProviderResult<(Vec<Column>, Vec<TableConstraint>)> {
+fn parse_table_definitions(s: &str) -> SyntaxResult<'_, (Vec<Column>, Vec<TableConstraint>)> {
     let mut columns = Vec::new();
     let mut table_constraints = Vec::new();
 
@@ -191,8 +386,15 @@ fn parse_table_definitions(s: &str) -> ProviderResult<(Vec<Column>, Vec<TableCon
             let cols = extract_constraint_columns(rest)?;
             table_constraints.push(TableConstraint::Unique(cols));
         } else if def_upper.starts_with("FOREIGN KEY") {
-            // Skip for now - complex to parse
-            continue;
+            let rest = &def["FOREIGN KEY".len()..];
+            let cols = extract_constraint_columns(rest)?;
+            if let Some((referenced_table, referenced_columns)) = parse_references(rest) {
+                table_constraints.push(TableConstraint::ForeignKey {
+                    columns: cols,
+                    referenced_table,
+                    referenced_columns,
+                });
+            }
         } else if def_upper.starts_with("CONSTRAINT") {
             // Skip named constraints for now
             continue;
@@ -209,15 +411,32 @@ fn parse_table_definitions(s: &str) -> ProviderResult<(Vec<Column>, Vec<TableCon
     Ok((columns, table_constraints))
 }
 
+/// Parse the target of a `REFERENCES table(columns)` clause
+fn parse_references(s: &str) -> Option<(String, Vec<String>)> {
+    let idx = s.to_uppercase().find("REFERENCES")?;
+    let target = s[idx + "REFERENCES".len()..].trim();
+
+    let table_end = target
+        .find(|c: char| c == '(' || c.is_whitespace())
+        .unwrap_or(target.len());
+    let table = target[..table_end].trim_matches('"').trim_matches('`').to_string();
+    if table.is_empty() {
+        return None;
+    }
+
+    let columns = extract_constraint_columns(target[table_end..].trim()).unwrap_or_default();
+    Some((table, columns))
+}
+
 /// Extract column names from constraint definition
-fn extract_constraint_columns(s: &str) -> ProviderResult<Vec<String>> {
+fn extract_constraint_columns(s: &str) -> SyntaxResult<'_, Vec<String>> {
     let s = s.trim();
     if !s.starts_with('(') {
-        return Err(ProviderError::ParseError("Expected column list in parentheses".to_string()));
+        return Err(syntax_error(s, "Expected column list in parentheses"));
     }
 
     let (content, _) = extract_parentheses_content(s)?;
-    let columns = split_by_comma(&content)
+    let columns = split_by_comma(content)
         .into_iter()
         .map(|c| c.trim().trim_matches('"').trim_matches('`').to_string())
         .collect();
@@ -226,14 +445,14 @@ fn extract_constraint_columns(s: &str) -> ProviderResult<Vec<String>> {
 }
 
 /// Split string by commas, respecting nested parentheses
-fn split_by_comma(s: &str) -> Vec<String> {
+fn split_by_comma(s: &str) -> Vec<&str> {
     let mut parts = Vec::new();
-    let mut current = String::new();
+    let mut start = 0;
     let mut depth = 0;
     let mut in_string = false;
     let mut string_char = ' ';
 
-    for ch in s.chars() {
+    for (idx, ch) in s.char_indices() {
         match ch {
             '\'' | '"' => {
                 if in_string && ch == string_char {
@@ -242,40 +461,31 @@ fn split_by_comma(s: &str) -> Vec<String> {
                     in_string = true;
                     string_char = ch;
                 }
-                current.push(ch);
-            }
-            '(' if !in_string => {
-                depth += 1;
-                current.push(ch);
-            }
-            ')' if !in_string => {
-                depth -= 1;
-                current.push(ch);
             }
+            '(' if !in_string => depth += 1,
+            ')' if !in_string => depth -= 1,
             ',' if !in_string && depth == 0 => {
-                parts.push(current.clone());
-                current.clear();
-            }
-            _ => {
-                current.push(ch);
+                parts.push(&s[start..idx]);
+                start = idx + 1;
             }
+            _ => {}
         }
     }
 
-    if !current.trim().is_empty() {
-        parts.push(current);
+    if !s[start..].trim().is_empty() {
+        parts.push(&s[start..]);
     }
 
     parts
 }
 
 /// Parse a column definition
-fn parse_column_definition(def: &str) -> ProviderResult<Column> {
+fn parse_column_definition(def: &str) -> SyntaxResult<'_, Column> {
     let def = def.trim();
     let parts: Vec<&str> = def.split_whitespace().collect();
 
     if parts.is_empty() {
-        return Err(ProviderError::ParseError("Empty column definition".to_string()));
+        return Err(syntax_error(def, "Empty column definition"));
     }
 
     // First part is column name
@@ -283,7 +493,7 @@ fn parse_column_definition(def: &str) -> ProviderResult<Column> {
 
     // Second part is data type
     if parts.len() < 2 {
-        return Err(ProviderError::ParseError(format!("Missing type for column {}", column_name)));
+        return Err(syntax_error(def, format!("Missing type for column {}", column_name)));
     }
 
     // Handle types with parameters (e.g., VARCHAR(255))
@@ -303,9 +513,16 @@ fn parse_column_definition(def: &str) -> ProviderResult<Column> {
     let sql_type = SqlType::from_str(&type_str);
     let mut column = Column::new(column_name, sql_type);
 
-    // Parse constraints
-    let remaining = &parts[2..].join(" ").to_uppercase();
-    parse_column_constraints(remaining, &mut column);
+    // Parse constraints, with any comment text taken out first
+    let (remaining, comment) = take_comment_clause(&parts[2..].join(" "));
+    column.comment = comment;
+    parse_column_constraints(&remaining, &mut column);
+    if let Some((table, columns)) = parse_references(&remaining) {
+        column.constraints.push(Constraint::ForeignKey {
+            table,
+            column: columns.into_iter().next().unwrap_or_default(),
+        });
+    }
 
     Ok(column)
 }
@@ -334,24 +551,54 @@ fn parse_column_constraints(s: &str, column: &mut Column) {
 
     // Parse DEFAULT
     if let Some(idx) = s_upper.find("DEFAULT") {
-        let default_part = &s[idx + "DEFAULT".len()..].trim();
-        // Extract default value (simplified - doesn't handle complex expressions)
-        let default_value = default_part
-            .split_whitespace()
-            .next()
-            .unwrap_or("")
-            .trim_matches('\'')
-            .trim_matches('"')
-            .to_string();
+        let default_value = default_expression(&s[idx + "DEFAULT".len()..]);
         if !default_value.is_empty() {
             column.constraints.push(Constraint::Default(default_value));
         }
     }
 }
 
+/// Raw SQL text of a `DEFAULT` expression: a quoted literal, a
+/// parenthesized expression, or a single token such as `now()`
+fn default_expression(s: &str) -> String {
+    let s = s.trim_start();
+    let mut end = 0;
+    let mut depth = 0;
+    let mut quote = None;
+    let mut chars = s.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        end = i + c.len_utf8();
+        match (quote, c) {
+            (Some(q), c) if c == q => {
+                // A doubled quote is an escaped quote
+                if chars.peek().map(|&(_, next)| next) == Some(q) {
+                    chars.next();
+                } else {
+                    quote = None;
+                }
+            }
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') if depth > 0 => depth -= 1,
+            (None, ')') => {
+                end = i;
+                break;
+            }
+            (None, c) if depth == 0 && (c.is_whitespace() || c == ',') => {
+                end = i;
+                break;
+            }
+            _ => {}
+        }
+    }
+    s[..end].to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fusabi_provider_common::span::span_of;
 
     #[test]
     fn test_parse_simple_table() {
@@ -414,6 +661,90 @@ mod tests {
         assert!(table.columns[1].has_default());
     }
 
+    #[test]
+    fn test_default_expression() {
+        assert_eq!(default_expression(" 'light' NOT NULL"), "'light'");
+        assert_eq!(default_expression("'it''s on' NOT NULL"), "'it''s on'");
+        assert_eq!(default_expression("now() NOT NULL"), "now()");
+        assert_eq!(default_expression("(1 + 2),"), "(1 + 2)");
+        assert_eq!(default_expression("0)"), "0");
+        assert_eq!(default_expression("'active'::text"), "'active'::text");
+    }
+
+    #[test]
+    fn test_parse_foreign_keys() {
+        let sql = r#"
+            CREATE TABLE orders (
+                id INT PRIMARY KEY,
+                user_id INT NOT NULL REFERENCES Users(id),
+                product_id INT,
+                FOREIGN KEY (product_id) REFERENCES products (id)
+            );
+        "#;
+
+        let schema = parse_sql_ddl(sql).unwrap();
+        let table = schema.tables.get("orders").unwrap();
+
+        assert_eq!(table.columns.len(), 3);
+        assert!(table.columns[1].constraints.iter().any(|c| matches!(
+            c,
+            Constraint::ForeignKey { table, column } if table == "Users" && column == "id"
+        )));
+        assert!(matches!(
+            &table.table_constraints[0],
+            TableConstraint::ForeignKey { columns, referenced_table, .. }
+                if columns == &["product_id".to_string()] && referenced_table == "products"
+        ));
+    }
+
+    #[test]
+    fn test_parse_comments() {
+        let sql = r#"
+            CREATE TABLE users (
+                id INT PRIMARY KEY COMMENT 'Surrogate key',
+                email TEXT COMMENT = 'Contact address, never null',
+                name TEXT NOT NULL
+            ) ENGINE=InnoDB COMMENT='Registered users';
+
+            CREATE TABLE public.accounts (id INT PRIMARY KEY, owner TEXT);
+            COMMENT ON TABLE public.accounts IS 'Billing accounts';
+            COMMENT ON COLUMN "public"."accounts"."owner" IS 'Owner''s login';
+            COMMENT ON COLUMN users.name IS 'Display name';
+            COMMENT ON COLUMN users.id IS NULL;
+            COMMENT ON INDEX users_email_idx IS 'ignored';
+        "#;
+
+        let schema = parse_sql_ddl(sql).unwrap();
+
+        let users = schema.tables.get("users").unwrap();
+        assert_eq!(users.comment.as_deref(), Some("Registered users"));
+        assert_eq!(users.columns[0].comment, None);
+        assert!(users.columns[0].is_primary_key());
+        assert_eq!(users.columns[1].comment.as_deref(), Some("Contact address, never null"));
+        assert!(users.columns[1].is_nullable());
+        assert_eq!(users.columns[2].comment.as_deref(), Some("Display name"));
+
+        let accounts = schema.tables.get("public.accounts").unwrap();
+        assert_eq!(accounts.comment.as_deref(), Some("Billing accounts"));
+        assert_eq!(accounts.columns[1].comment.as_deref(), Some("Owner's login"));
+    }
+
+    #[test]
+    fn test_error_positions() {
+        let sql = "CREATE TABLE users (id INT);\n\nCREATE TABLE posts (\n    id INT,\n    title\n);";
+        let error = parse_sql_ddl(sql).unwrap_err();
+        let span = span_of(&error).unwrap();
+        assert_eq!((span.line, span.column), (5, 5));
+        assert_eq!(span.snippet, "    title");
+        assert!(error.to_string().contains("while parsing table posts: Missing type for column title"));
+
+        let span = span_of(&parse_sql_ddl("CREATE TABLE users\n  id INT;").unwrap_err()).unwrap();
+        assert_eq!((span.line, span.column), (2, 3));
+
+        let span = span_of(&parse_sql_ddl("CREATE TABLE \"émoji (id INT);").unwrap_err()).unwrap();
+        assert_eq!((span.line, span.column), (1, 14));
+    }
+
     #[test]
     fn test_split_statements() {
         let sql = "CREATE TABLE a (id INT); CREATE TABLE b (id INT);";
@@ -430,4 +761,20 @@ mod tests {
         assert_eq!(parts[1].trim(), "name VARCHAR(255)");
         assert_eq!(parts[2].trim(), "data JSON");
     }
+
+    #[test]
+    fn test_check_limits() {
+        let limits = InputLimits {
+            max_depth: 2,
+            max_types: 1,
+            ..InputLimits::default()
+        };
+        assert!(check_limits("CREATE TABLE a (price DECIMAL(10, 2));", &limits).is_ok());
+
+        let error = check_limits("CREATE TABLE a (\n  id INT CHECK ((id > 0))\n);", &limits).unwrap_err();
+        assert!(error.to_string().contains("at line 2, column 17: Nesting is deeper than max_depth (2)"));
+
+        let error = check_limits("CREATE TABLE a (id INT); CREATE TABLE b (id INT);", &limits).unwrap_err();
+        assert!(error.to_string().contains("Source declares 2 types, more than max_types (1)"));
+    }
 }
@@ -1,6 +1,7 @@
 //! SQL DDL type definitions
 
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::fmt;
 
 /// SQL data type
 #[derive(Debug, Clone, PartialEq)]
@@ -54,6 +55,7 @@ pub enum SqlType {
 
 impl SqlType {
     /// Parse SQL type from string
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Self {
         let s_upper = s.to_uppercase();
         let s_trimmed = s_upper.trim();
@@ -73,14 +75,14 @@ impl SqlType {
                 "DECIMAL" | "DEC" => {
                     let parts: Vec<&str> = params.split(',').collect();
                     SqlType::Decimal {
-                        precision: parts.get(0).and_then(|p| p.trim().parse().ok()),
+                        precision: parts.first().and_then(|p| p.trim().parse().ok()),
                         scale: parts.get(1).and_then(|s| s.trim().parse().ok()),
                     }
                 }
                 "NUMERIC" => {
                     let parts: Vec<&str> = params.split(',').collect();
                     SqlType::Numeric {
-                        precision: parts.get(0).and_then(|p| p.trim().parse().ok()),
+                        precision: parts.first().and_then(|p| p.trim().parse().ok()),
                         scale: parts.get(1).and_then(|s| s.trim().parse().ok()),
                     }
                 }
@@ -90,8 +92,7 @@ impl SqlType {
         }
 
         // Handle array types (PostgreSQL syntax)
-        if s_trimmed.ends_with("[]") {
-            let element_type_str = &s_trimmed[..s_trimmed.len()-2];
+        if let Some(element_type_str) = s_trimmed.strip_suffix("[]") {
             return SqlType::Array {
                 element_type: Box::new(SqlType::from_str(element_type_str)),
             };
@@ -145,6 +146,49 @@ impl SqlType {
     }
 }
 
+impl fmt::Display for SqlType {
+    /// Renders the canonical spelling of the type
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SqlType::TinyInt => f.write_str("TINYINT"),
+            SqlType::SmallInt => f.write_str("SMALLINT"),
+            SqlType::Int => f.write_str("INT"),
+            SqlType::BigInt => f.write_str("BIGINT"),
+            SqlType::Serial => f.write_str("SERIAL"),
+            SqlType::BigSerial => f.write_str("BIGSERIAL"),
+            SqlType::Real => f.write_str("REAL"),
+            SqlType::Double => f.write_str("DOUBLE PRECISION"),
+            SqlType::Float => f.write_str("FLOAT"),
+            SqlType::Decimal { precision: p, scale: s } => precision(f, "DECIMAL", p, s),
+            SqlType::Numeric { precision: p, scale: s } => precision(f, "NUMERIC", p, s),
+            SqlType::Char { length } => precision(f, "CHAR", length, &None),
+            SqlType::VarChar { length } => precision(f, "VARCHAR", length, &None),
+            SqlType::Text => f.write_str("TEXT"),
+            SqlType::Boolean => f.write_str("BOOLEAN"),
+            SqlType::Date => f.write_str("DATE"),
+            SqlType::Time => f.write_str("TIME"),
+            SqlType::Timestamp => f.write_str("TIMESTAMP"),
+            SqlType::TimestampTz => f.write_str("TIMESTAMPTZ"),
+            SqlType::Blob => f.write_str("BLOB"),
+            SqlType::Bytea => f.write_str("BYTEA"),
+            SqlType::Json => f.write_str("JSON"),
+            SqlType::JsonB => f.write_str("JSONB"),
+            SqlType::Uuid => f.write_str("UUID"),
+            SqlType::Array { element_type } => write!(f, "{}[]", element_type),
+            SqlType::Custom(name) => f.write_str(name),
+        }
+    }
+}
+
+/// Write `NAME`, `NAME(p)` or `NAME(p, s)`
+fn precision(f: &mut fmt::Formatter<'_>, name: &str, precision: &Option<u32>, scale: &Option<u32>) -> fmt::Result {
+    match (precision, scale) {
+        (Some(p), Some(s)) => write!(f, "{}({}, {})", name, p, s),
+        (Some(p), None) => write!(f, "{}({})", name, p),
+        _ => f.write_str(name),
+    }
+}
+
 /// Column constraint
 #[derive(Debug, Clone, PartialEq)]
 pub enum Constraint {
@@ -164,6 +208,8 @@ pub struct Column {
     pub name: String,
     pub sql_type: SqlType,
     pub constraints: Vec<Constraint>,
+    /// `COMMENT` clause or `COMMENT ON COLUMN` text
+    pub comment: Option<String>,
 }
 
 impl Column {
@@ -172,6 +218,7 @@ impl Column {
             name,
             sql_type,
             constraints: Vec::new(),
+            comment: None,
         }
     }
 
@@ -187,6 +234,14 @@ impl Column {
     pub fn has_default(&self) -> bool {
         self.constraints.iter().any(|c| matches!(c, Constraint::Default(_)))
     }
+
+    /// Raw SQL text of the `DEFAULT` expression
+    pub fn default_expression(&self) -> Option<&str> {
+        self.constraints.iter().find_map(|c| match c {
+            Constraint::Default(expr) => Some(expr.as_str()),
+            _ => None,
+        })
+    }
 }
 
 /// SQL table definition
@@ -195,6 +250,8 @@ pub struct Table {
     pub name: String,
     pub columns: Vec<Column>,
     pub table_constraints: Vec<TableConstraint>,
+    /// `COMMENT` table option or `COMMENT ON TABLE` text
+    pub comment: Option<String>,
 }
 
 impl Table {
@@ -203,6 +260,7 @@ impl Table {
             name,
             columns: Vec::new(),
             table_constraints: Vec::new(),
+            comment: None,
         }
     }
 }
@@ -229,10 +287,23 @@ pub enum SqlDialect {
     Generic,
 }
 
+impl SqlDialect {
+    /// Parse a `dialect` param value
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "postgres" | "postgresql" => Some(SqlDialect::PostgreSQL),
+            "mysql" => Some(SqlDialect::MySQL),
+            "sqlite" => Some(SqlDialect::SQLite),
+            "generic" | "sql" => Some(SqlDialect::Generic),
+            _ => None,
+        }
+    }
+}
+
 /// Parsed SQL schema
 #[derive(Debug, Clone, Default)]
 pub struct SqlSchema {
-    pub tables: HashMap<String, Table>,
+    pub tables: BTreeMap<String, Table>,
     pub dialect: Option<SqlDialect>,
 }
 
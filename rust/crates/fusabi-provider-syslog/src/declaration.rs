@@ -0,0 +1,228 @@
+//! SD-ELEMENT declarations
+//!
+//! A declaration lists the structured data elements a pipeline expects,
+//! by SD-ID, with the type of each SD-PARAM:
+//!
+//! ```yaml
+//! iana: true                  # include the IANA-registered SD-IDs (the default)
+//! elements:
+//!   exampleSDID@32473:
+//!     iut: int
+//!     eventSource: {type: string, required: true}
+//!     relay: {type: ip, repeated: true}
+//! ```
+//!
+//! Param types are `string`, `int`, `long`, `float`, `bool`, `flag` (`0`
+//! or `1`), `timestamp`, `ip` and `hostname`. A `repeated` param may occur
+//! more than once in an element, as `origin`'s `ip` does.
+//!
+//! SD-IDs without an `@` are reserved for IANA; private ones are
+//! `name@<private enterprise number>`.
+
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+/// Whether a param may occur more than once
+pub const REPEATED: bool = true;
+/// A param occurring at most once
+pub const SINGLE: bool = false;
+
+/// An SD-ELEMENT: SD-ID, then `(param, type, repeated)`
+pub type ElementDef = (&'static str, &'static [(&'static str, &'static str, bool)]);
+
+/// SD-IDs in the IANA "syslog Structured Data ID Values" registry, from
+/// RFC 5424 (`timeQuality`, `origin`, `meta`), RFC 5848 (`ssign`,
+/// `ssign-cert`) and RFC 5674 (`alarm`)
+pub const IANA_SD_IDS: &[ElementDef] = &[
+    ("timeQuality", &[
+        ("tzKnown", "flag", SINGLE),
+        ("isSynced", "flag", SINGLE),
+        // Microseconds
+        ("syncAccuracy", "long", SINGLE),
+    ]),
+    ("origin", &[
+        ("ip", "ip", REPEATED),
+        ("enterpriseId", "string", SINGLE),
+        ("software", "string", SINGLE),
+        ("swVersion", "string", SINGLE),
+    ]),
+    ("meta", &[
+        ("sequenceId", "long", SINGLE),
+        // Hundredths of a second, as SNMP's sysUpTime
+        ("sysUpTime", "long", SINGLE),
+        ("language", "string", SINGLE),
+    ]),
+    ("ssign", &[
+        ("VER", "string", SINGLE),
+        ("RSID", "long", SINGLE),
+        ("SG", "int", SINGLE),
+        ("SPRI", "int", SINGLE),
+        ("GBC", "long", SINGLE),
+        ("FMN", "long", SINGLE),
+        ("CNT", "int", SINGLE),
+        // Space-separated base64 hashes
+        ("HB", "string", SINGLE),
+        ("SIGN", "string", SINGLE),
+    ]),
+    ("ssign-cert", &[
+        ("VER", "string", SINGLE),
+        ("RSID", "long", SINGLE),
+        ("SG", "int", SINGLE),
+        ("SPRI", "int", SINGLE),
+        ("TBPL", "long", SINGLE),
+        ("INDEX", "long", SINGLE),
+        ("FLEN", "int", SINGLE),
+        ("FRAG", "string", SINGLE),
+        ("SIGN", "string", SINGLE),
+    ]),
+    ("alarm", &[
+        ("resource", "string", SINGLE),
+        ("probableCause", "string", SINGLE),
+        ("perceivedSeverity", "string", SINGLE),
+        ("eventType", "string", SINGLE),
+        ("trendIndication", "string", SINGLE),
+        ("resourceURI", "string", SINGLE),
+    ]),
+];
+
+/// A parsed declaration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Declaration {
+    /// Include the IANA-registered SD-IDs
+    pub iana: bool,
+    /// Declared elements, in declaration order
+    pub elements: Vec<Element>,
+}
+
+impl Default for Declaration {
+    fn default() -> Self {
+        Self {
+            iana: true,
+            elements: Vec::new(),
+        }
+    }
+}
+
+/// A declared SD-ELEMENT
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Element {
+    pub sd_id: String,
+    pub params: Vec<Param>,
+}
+
+/// A declared SD-PARAM
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Param {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub param_type: String,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub repeated: bool,
+}
+
+/// Parse a declaration from YAML or JSON
+pub fn parse_declaration(content: &str) -> ProviderResult<Declaration> {
+    let value: Value = serde_yaml::from_str(content)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid SD-ELEMENT declaration: {}", e)))?;
+    let Value::Mapping(map) = value else {
+        return Err(parse_error("Expected an SD-ELEMENT declaration mapping"));
+    };
+
+    let mut declaration = Declaration::default();
+    if let Some(iana) = map.get("iana") {
+        declaration.iana = iana.as_bool().ok_or_else(|| parse_error("`iana` must be true or false"))?;
+    }
+
+    let elements = match map.get("elements") {
+        Some(Value::Mapping(elements)) => elements,
+        Some(Value::Null) | None => return Ok(declaration),
+        Some(_) => return Err(parse_error("`elements` must map SD-IDs to their params")),
+    };
+    for (sd_id, params) in elements {
+        let sd_id = sd_id
+            .as_str()
+            .ok_or_else(|| parse_error(&format!("SD-IDs must be strings, got {:?}", sd_id)))?;
+        let params = match params {
+            Value::Mapping(params) => params,
+            Value::Null => {
+                declaration.elements.push(Element { sd_id: sd_id.to_string(), params: Vec::new() });
+                continue;
+            }
+            _ => return Err(parse_error(&format!("Element `{}` must map params to types", sd_id))),
+        };
+
+        let mut element = Element { sd_id: sd_id.to_string(), params: Vec::new() };
+        for (name, definition) in params {
+            let name = name
+                .as_str()
+                .ok_or_else(|| parse_error(&format!("Param names of `{}` must be strings", sd_id)))?;
+            let param = match definition {
+                Value::String(param_type) => Param {
+                    name: name.to_string(),
+                    param_type: param_type.clone(),
+                    required: false,
+                    repeated: false,
+                },
+                Value::Mapping(definition) => Param {
+                    name: name.to_string(),
+                    param_type: definition.get("type").and_then(Value::as_str).unwrap_or("string").to_string(),
+                    required: definition.get("required").and_then(Value::as_bool).unwrap_or(false),
+                    repeated: definition.get("repeated").and_then(Value::as_bool).unwrap_or(false),
+                },
+                _ => {
+                    return Err(parse_error(&format!(
+                        "Param `{}.{}` must have a type name or a mapping with `type`",
+                        sd_id, name
+                    )))
+                }
+            };
+            element.params.push(param);
+        }
+        declaration.elements.push(element);
+    }
+    Ok(declaration)
+}
+
+/// Whether a name is a valid SD-NAME: 1 to 32 printable US-ASCII
+/// characters other than `=`, space, `]` and `"`
+pub fn is_sd_name(name: &str) -> bool {
+    (1..=32).contains(&name.len()) && name.chars().all(|c| c.is_ascii_graphic() && !matches!(c, '=' | ']' | '"'))
+}
+
+fn parse_error(message: &str) -> ProviderError {
+    ProviderError::ParseError(message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_declaration() {
+        let declaration = parse_declaration(
+            "iana: false\nelements:\n  exampleSDID@32473:\n    iut: int\n    eventSource: {type: string, required: true}\n    relay: {type: ip, repeated: true}\n  heartbeat@32473:\n",
+        )
+        .unwrap();
+        assert!(!declaration.iana);
+        assert_eq!(declaration.elements.len(), 2);
+        let params: Vec<(&str, &str, bool, bool)> = declaration.elements[0]
+            .params
+            .iter()
+            .map(|p| (p.name.as_str(), p.param_type.as_str(), p.required, p.repeated))
+            .collect();
+        assert_eq!(
+            params,
+            [("iut", "int", false, false), ("eventSource", "string", true, false), ("relay", "ip", false, true)]
+        );
+        assert!(declaration.elements[1].params.is_empty());
+
+        assert!(parse_declaration("iana: maybe").is_err());
+        assert!(parse_declaration("elements: [a, b]").is_err());
+        assert!(is_sd_name("exampleSDID@32473"));
+        assert!(!is_sd_name("bad id"));
+        assert!(!is_sd_name(&"x".repeat(33)));
+    }
+}
@@ -0,0 +1,510 @@
+//! Syslog Type Provider
+//!
+//! Generates Fusabi types for RFC 5424 syslog messages: the header envelope
+//! and one record per SD-ELEMENT of the structured data, so that the
+//! payloads the Hibana `Syslog` source receives can be read as typed values
+//! rather than nested string maps.
+//!
+//! # Sources
+//!
+//! - `rfc5424` or `iana`: the envelope with the IANA-registered SD-IDs only
+//! - An SD-ELEMENT declaration, inline or from a file; see [`declaration`]
+//!
+//! # Generated Types
+//!
+//! For `<165>1 2003-10-11T22:14:15.003Z host app - ID47 [exampleSDID@32473 iut="3"] msg`:
+//!
+//! | Type                 | Shape                                                     |
+//! |----------------------|-----------------------------------------------------------|
+//! | `Facility`           | `Kern` ... `Local7`, in code order                        |
+//! | `Severity`           | `Emergency` ... `Debug`, in code order                    |
+//! | `ExampleSDIDElement` | one field per SD-PARAM                                    |
+//! | `StructuredData`     | one `<Element> option` field per SD-ID                    |
+//! | `SyslogMessage`      | `facility`, `severity`, `version: int`, `timestamp`, `hostname`, `appName`, `procId`, `msgId`, `structuredData`, `msg` |
+//!
+//! Header fields that may be the NILVALUE `-` are `string option`, as is
+//! `msg`. SD-PARAMs are `T option` unless declared `required`, and
+//! `T list` when `repeated`. An SD-ELEMENT without params is a `bool`
+//! field telling whether it was present. SD-ELEMENTs outside the
+//! declaration are collected in `extra: Map<string, Map<string, string>>`.
+//!
+//! The field and record names of an SD-ID are its name without the
+//! enterprise number; when two declared SD-IDs share a name, the number is
+//! kept: `example@32473` and `example@18060` become `example32473` and
+//! `example18060`.
+//!
+//! # Params
+//!
+//! | Param  | Meaning                                                      |
+//! |--------|--------------------------------------------------------------|
+//! | `iana` | `true` or `false`, overriding the declaration's `iana`       |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_syslog::SyslogProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = SyslogProvider::new();
+//! let schema = provider.resolve_schema("sd-elements.yaml", &ProviderParams::default())?;
+//! let types = provider.generate_types(&schema, "Syslog")?;
+//! ```
+
+pub mod declaration;
+
+pub use declaration::{is_sd_name, parse_declaration, Declaration, Element, Param, IANA_SD_IDS};
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceValidator, TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule,
+    RecordDef, DuDef, VariantDef, TypeExpr, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+
+/// Facilities, indexed by code
+pub const FACILITIES: [&str; 24] = [
+    "Kern", "User", "Mail", "Daemon", "Auth", "Syslog", "Lpr", "News",
+    "Uucp", "Cron", "AuthPriv", "Ftp", "Ntp", "Audit", "Alert", "Clock",
+    "Local0", "Local1", "Local2", "Local3", "Local4", "Local5", "Local6", "Local7",
+];
+
+/// Severities, indexed by code
+pub const SEVERITIES: [&str; 8] = [
+    "Emergency", "Alert", "Critical", "Error", "Warning", "Notice", "Informational", "Debug",
+];
+
+/// Field collecting the SD-ELEMENTs outside the declaration
+pub const EXTRA_FIELD: &str = "extra";
+
+/// Syslog type provider
+pub struct SyslogProvider;
+
+impl SyslogProvider {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<Declaration> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected SD-ELEMENT declaration: {}", e)))
+    }
+
+    /// Generate the severity and facility unions, the element records and
+    /// the envelope
+    fn generate(&self, declaration: &Declaration) -> (Vec<TypeDefinition>, Vec<Diagnostic>) {
+        let mut diagnostics = Vec::new();
+
+        for element in &declaration.elements {
+            let location = format!("elements.{}", element.sd_id);
+            match element.sd_id.split_once('@') {
+                _ if !is_sd_name(&element.sd_id) => diagnostics.push(
+                    Diagnostic::warning(
+                        "sd-id-invalid",
+                        "SD-IDs are 1 to 32 printable ASCII characters other than `=`, space, `]` and `\"`",
+                    )
+                    .at(location.clone()),
+                ),
+                Some((name, number)) => {
+                    if name.is_empty() || number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+                        diagnostics.push(
+                            Diagnostic::warning("sd-id-invalid", "Private SD-IDs are `name@<enterprise number>`")
+                                .at(location.clone()),
+                        );
+                    }
+                }
+                None if IANA_SD_IDS.iter().any(|(id, _)| *id == element.sd_id) => {
+                    if declaration.iana {
+                        diagnostics.push(
+                            Diagnostic::info("iana-overridden", "Declaration replaces the registered SD-ELEMENT")
+                                .at(location.clone()),
+                        );
+                    }
+                }
+                None => diagnostics.push(
+                    Diagnostic::warning(
+                        "sd-id-reserved",
+                        "SD-IDs without `@` are reserved for IANA; private ones need `@<enterprise number>`",
+                    )
+                    .at(location.clone()),
+                ),
+            }
+
+            for param in &element.params {
+                let location = format!("{}.{}", location, param.name);
+                if !is_sd_name(&param.name) {
+                    diagnostics.push(
+                        Diagnostic::warning("param-invalid", "SD-PARAM names follow the SD-ID rules")
+                            .at(location.clone()),
+                    );
+                }
+                if param_shape(&param.param_type).is_none() {
+                    diagnostics.push(
+                        Diagnostic::warning("unmapped-type", format!("Unknown param type `{}`", param.param_type))
+                            .at(location),
+                    );
+                }
+            }
+        }
+
+        let elements = elements(declaration);
+        if elements.iter().any(|(field, _, _)| field == EXTRA_FIELD) {
+            diagnostics.push(
+                Diagnostic::info("extra-shadowed", "SD-ID `extra` replaces the field collecting undeclared elements")
+                    .at(format!("elements.{}", EXTRA_FIELD)),
+            );
+        }
+
+        let mut types = vec![
+            simple_du("Facility", &FACILITIES),
+            simple_du("Severity", &SEVERITIES),
+        ];
+        let mut structured_data = Vec::new();
+        for (field, record, params) in &elements {
+            if params.is_empty() {
+                structured_data.push((field.clone(), TypeShape::Named("bool".to_string()).into()));
+                continue;
+            }
+            types.push(TypeDefinition::Record(RecordDef {
+                name: record.clone(),
+                fields: params.iter().map(param_field).collect(),
+            }));
+            let shape = TypeShape::Option(Box::new(TypeShape::Named(record.clone())));
+            structured_data.push((field.clone(), shape.into()));
+        }
+        if !structured_data.iter().any(|(name, _)| name == EXTRA_FIELD) {
+            let string = || Box::new(TypeShape::Named("string".to_string()));
+            let extra = TypeShape::Map(string(), Box::new(TypeShape::Map(string(), string())));
+            structured_data.push((EXTRA_FIELD.to_string(), extra.into()));
+        }
+        types.push(TypeDefinition::Record(RecordDef {
+            name: "StructuredData".to_string(),
+            fields: structured_data,
+        }));
+        types.push(TypeDefinition::Record(RecordDef {
+            name: "SyslogMessage".to_string(),
+            fields: envelope_fields(),
+        }));
+        (types, diagnostics)
+    }
+}
+
+impl Default for SyslogProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for SyslogProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("SyslogProvider")
+            .param(
+                ParamSpec::new("iana", ParamKind::Bool)
+                    .describe("Override the declaration's `iana`"),
+            )
+    }
+}
+
+impl TypeProvider for SyslogProvider {
+    fn name(&self) -> &str {
+        "SyslogProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let trimmed = source.trim();
+        let mut declaration = match trimmed.to_ascii_lowercase().as_str() {
+            "rfc5424" | "iana" => Declaration::default(),
+            _ if trimmed.starts_with('{') || trimmed.contains('\n') => parse_declaration(source)?,
+            _ => parse_declaration(&read_source(source, params)?)?,
+        };
+        if let Some(iana) = typed.bool("iana") {
+            declaration.iana = iana;
+        }
+
+        let json = serde_json::to_string(&declaration)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected SD-ELEMENT declaration".to_string()));
+        };
+
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+        module.types = self.generate(&self.parse_schema(json)?).0;
+
+        let mut result = GeneratedTypes::new();
+        result.modules.push(module);
+        Ok(result)
+    }
+}
+
+impl SourceValidator for SyslogProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected SD-ELEMENT declaration".to_string()));
+        };
+
+        Ok(self.generate(&self.parse_schema(json)?).1)
+    }
+}
+
+impl DescribedProvider for SyslogProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .embedded("rfc5424")
+            .embedded("iana")
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+/// Registered then declared elements, which replace registered ones in
+/// place, as `(field, record, params)`
+fn elements(declaration: &Declaration) -> Vec<(String, String, Vec<Param>)> {
+    let mut elements: Vec<(&str, Vec<Param>)> = Vec::new();
+    if declaration.iana {
+        for (sd_id, params) in IANA_SD_IDS {
+            let params = params
+                .iter()
+                .map(|(name, param_type, repeated)| Param {
+                    name: name.to_string(),
+                    param_type: param_type.to_string(),
+                    required: false,
+                    repeated: *repeated,
+                })
+                .collect();
+            elements.push((sd_id, params));
+        }
+    }
+    for element in &declaration.elements {
+        match elements.iter_mut().find(|(sd_id, _)| *sd_id == element.sd_id) {
+            Some(existing) => existing.1 = element.params.clone(),
+            None => elements.push((&element.sd_id, element.params.clone())),
+        }
+    }
+
+    let base = |sd_id: &str| sd_id.split_once('@').map_or(sd_id, |(name, _)| name).to_string();
+    elements
+        .iter()
+        .map(|(sd_id, params)| {
+            let shared = elements.iter().filter(|(other, _)| base(other) == base(sd_id)).count() > 1;
+            let name = if shared { sd_id.replace('@', "") } else { base(sd_id) };
+            let field = identifier(&name);
+            let record = format!("{}Element", pascal_case(&field));
+            (field, record, params.clone())
+        })
+        .collect()
+}
+
+/// A name with characters other than letters, digits and `_` replaced by
+/// `_`, so `ssign-cert` is `ssign_cert`
+fn identifier(name: &str) -> String {
+    let identifier: String = name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' }).collect();
+    if identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", identifier)
+    } else {
+        identifier
+    }
+}
+
+/// `timeQuality` is `TimeQuality`, `ssign_cert` is `SsignCert`; the case of
+/// the rest of each word is kept, so `exampleSDID` is `ExampleSDID`
+fn pascal_case(identifier: &str) -> String {
+    identifier
+        .split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map(|c| c.to_ascii_uppercase().to_string() + chars.as_str()).unwrap_or_default()
+        })
+        .collect()
+}
+
+fn param_field(param: &Param) -> (String, TypeExpr) {
+    let shape = param_shape(&param.param_type).unwrap_or_else(|| TypeShape::Named("any".to_string()));
+    let shape = if param.repeated {
+        TypeShape::List(Box::new(shape))
+    } else if param.required {
+        shape
+    } else {
+        TypeShape::Option(Box::new(shape))
+    };
+    (identifier(&param.name), shape.into())
+}
+
+/// The Fusabi type of a declared param type
+fn param_shape(param_type: &str) -> Option<TypeShape> {
+    let name = match param_type.to_ascii_lowercase().as_str() {
+        "string" | "ip" | "ipv4" | "ipv6" | "hostname" | "timestamp" => "string",
+        "int" | "integer" => "int",
+        "long" => "int64",
+        "float" | "double" => "float",
+        "bool" | "boolean" | "flag" => "bool",
+        _ => return None,
+    };
+    Some(TypeShape::Named(name.to_string()))
+}
+
+fn simple_du(name: &str, cases: &[&str]) -> TypeDefinition {
+    TypeDefinition::Du(DuDef {
+        name: name.to_string(),
+        variants: cases.iter().map(|case| VariantDef::new_simple(case.to_string())).collect(),
+    })
+}
+
+/// RFC 5424 header fields, the structured data, then the message
+fn envelope_fields() -> Vec<(String, TypeExpr)> {
+    let field = |name: &str, shape: TypeShape| (name.to_string(), shape.into());
+    let nil_or_string = || TypeShape::Option(Box::new(TypeShape::Named("string".to_string())));
+    vec![
+        field("facility", TypeShape::Named("Facility".to_string())),
+        field("severity", TypeShape::Named("Severity".to_string())),
+        field("version", TypeShape::Named("int".to_string())),
+        // RFC 3339, with up to microsecond precision
+        field("timestamp", nil_or_string()),
+        field("hostname", nil_or_string()),
+        field("appName", nil_or_string()),
+        field("procId", nil_or_string()),
+        field("msgId", nil_or_string()),
+        field("structuredData", TypeShape::Named("StructuredData".to_string())),
+        field("msg", nil_or_string()),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    fn names(types: &GeneratedTypes) -> Vec<&str> {
+        types.modules[0]
+            .types
+            .iter()
+            .map(|t| match t {
+                TypeDefinition::Record(r) => r.name.as_str(),
+                TypeDefinition::Du(du) => du.name.as_str(),
+            })
+            .collect()
+    }
+
+    fn record<'a>(types: &'a GeneratedTypes, name: &str) -> &'a RecordDef {
+        types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+    }
+
+    fn field(record: &RecordDef, name: &str) -> String {
+        record
+            .fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, ty)| ty.to_string())
+            .unwrap_or_else(|| panic!("Expected field {}.{}", record.name, name))
+    }
+
+    #[test]
+    fn test_envelope_and_registered_elements() {
+        let provider = SyslogProvider::new();
+        let schema = provider.resolve_schema("rfc5424", &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Syslog").unwrap();
+
+        assert_eq!(
+            names(&types),
+            [
+                "Facility", "Severity", "TimeQualityElement", "OriginElement", "MetaElement",
+                "SsignElement", "SsignCertElement", "AlarmElement", "StructuredData", "SyslogMessage",
+            ]
+        );
+        let TypeDefinition::Du(facility) = &types.modules[0].types[0] else {
+            panic!("Expected Facility union");
+        };
+        assert_eq!(facility.variants[16].name, "Local0");
+
+        let message = record(&types, "SyslogMessage");
+        assert_eq!(field(message, "severity"), "Severity");
+        assert_eq!(field(message, "hostname"), "string option");
+        assert_eq!(field(message, "structuredData"), "StructuredData");
+
+        let data = record(&types, "StructuredData");
+        assert_eq!(field(data, "timeQuality"), "TimeQualityElement option");
+        assert_eq!(field(data, "ssign_cert"), "SsignCertElement option");
+        assert_eq!(field(data, "extra"), "Map<string, Map<string, string>>");
+        assert_eq!(field(record(&types, "OriginElement"), "ip"), "string list");
+        assert_eq!(field(record(&types, "TimeQualityElement"), "isSynced"), "bool option");
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/syslog.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_declared_elements() {
+        let provider = SyslogProvider::new();
+        let declaration = "elements:\n  exampleSDID@32473:\n    iut: int\n    eventSource: {type: string, required: true}\n  \
+                           heartbeat@32473:\n  meta:\n    sequenceId: long\n    tenant: string\n";
+        let params = ProviderParams::default().with("iana", "false");
+        let schema = provider.resolve_schema(declaration, &params).unwrap();
+        let types = provider.generate_types(&schema, "Syslog").unwrap();
+
+        assert_eq!(
+            names(&types),
+            ["Facility", "Severity", "ExampleSDIDElement", "MetaElement", "StructuredData", "SyslogMessage"]
+        );
+        let example = record(&types, "ExampleSDIDElement");
+        assert_eq!(field(example, "iut"), "int option");
+        assert_eq!(field(example, "eventSource"), "string");
+
+        let data = record(&types, "StructuredData");
+        assert_eq!(field(data, "exampleSDID"), "ExampleSDIDElement option");
+        assert_eq!(field(data, "heartbeat"), "bool");
+        assert_eq!(field(record(&types, "MetaElement"), "tenant"), "string option");
+
+        // Registered elements are replaced in place
+        let schema = provider.resolve_schema(declaration, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Syslog").unwrap();
+        let meta = record(&types, "MetaElement");
+        assert_eq!(meta.fields.len(), 2);
+        assert_eq!(names(&types)[4], "MetaElement");
+
+        let shared = "iana: false\nelements:\n  example@32473: {a: int}\n  example@18060: {b: int}\n";
+        let schema = provider.resolve_schema(shared, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Syslog").unwrap();
+        let fields: Vec<&str> = record(&types, "StructuredData").fields.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(fields, ["example32473", "example18060", "extra"]);
+        assert!(provider.resolve_schema("rfc5424", &ProviderParams::default().with("iana", "maybe")).is_err());
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = SyslogProvider::new();
+        let declaration = "elements:\n  bad id@1: {a: int}\n  private@acme: {a: int}\n  custom: {a: int}\n  \
+                           origin: {ip: ip}\n  ok@32473:\n    score: decimal\n    'x=y': string\n";
+        let report = provider.validate_source(declaration, &ProviderParams::default());
+        assert!(report.is_valid());
+
+        let codes: Vec<(&str, Option<&str>)> =
+            report.diagnostics.iter().map(|d| (d.code.as_str(), d.location.as_deref())).collect();
+        assert!(codes.contains(&("sd-id-invalid", Some("elements.bad id@1"))));
+        assert!(codes.contains(&("sd-id-invalid", Some("elements.private@acme"))));
+        assert!(codes.contains(&("sd-id-reserved", Some("elements.custom"))));
+        assert!(codes.contains(&("iana-overridden", Some("elements.origin"))));
+        assert!(codes.contains(&("unmapped-type", Some("elements.ok@32473.score"))));
+        assert!(codes.contains(&("param-invalid", Some("elements.ok@32473.x=y"))));
+        assert!(codes.contains(&("coerced-any", Some("OkElement.score"))));
+        assert!(!codes.iter().any(|(code, _)| *code == "unresolved-reference"));
+    }
+}
@@ -0,0 +1,671 @@
+//! Terraform Provider Schema Type Provider
+//!
+//! Generates Fusabi types from the output of `terraform providers schema
+//! -json`: one record per resource and data source, plus the provider's own
+//! configuration block.
+//!
+//! # Generated Types
+//!
+//! For each provider, with `<P>` the PascalCase provider name (`aws` → `Aws`):
+//!
+//! - `<Namespace>.<P>` - a `Provider` record for the provider configuration
+//! - `<Namespace>.<P>.Resources` - one record per resource type
+//! - `<Namespace>.<P>.DataSources` - one record per data source
+//!
+//! Resource and data source records drop the provider prefix
+//! (`aws_s3_bucket` → `S3Bucket`). Nested blocks and object attributes get
+//! their own records named `<Parent><Name>`.
+//!
+//! # Type Mapping
+//!
+//! | Terraform                     | Fusabi                          |
+//! |-------------------------------|---------------------------------|
+//! | `string`                      | `string`                        |
+//! | `number`                      | `float`                         |
+//! | `bool`                        | `bool`                          |
+//! | `dynamic`                     | `any`                           |
+//! | `list(T)`, `set(T)`           | `T list`                        |
+//! | `map(T)`                      | `Map<string, T>`                |
+//! | `object({...})`               | record                          |
+//! | `tuple([...])`                | `T list` (`any list` if mixed)  |
+//! | optional or computed attribute| `T option`                      |
+//! | `single` block                | record (`option` unless required)|
+//! | `list` / `set` block          | `Block list`                    |
+//! | `map` block                   | `Map<string, Block>`            |
+//!
+//! # Params
+//!
+//! Provider schemas are large, so the output can be narrowed with
+//! comma-separated filters. `aws_s3_*` matches by prefix.
+//!
+//! | Param          | Meaning                                                 |
+//! |----------------|---------------------------------------------------------|
+//! | `providers`    | Providers to keep, by name (`aws`) or address            |
+//! | `resources`    | Resource types to keep                                  |
+//! | `data_sources` | Data source types to keep                               |
+//!
+//! Complete schemas for large providers exceed the default
+//! `max_source_bytes`; raise it when reading them unfiltered.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_terraform_schema::TerraformSchemaProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = TerraformSchemaProvider::new();
+//! let params = ProviderParams::default()
+//!     .with("providers", "aws")
+//!     .with("resources", "aws_s3_*,aws_iam_role");
+//! let schema = provider.resolve_schema("schema.json", &params)?;
+//! let types = provider.generate_types(&schema, "Tf")?;
+//! ```
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceValidator, TypeShape, TypedParams,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use serde_json::{Map, Value};
+
+/// Module for resource records
+const RESOURCES_MODULE: &str = "Resources";
+
+/// Module for data source records
+const DATA_SOURCES_MODULE: &str = "DataSources";
+
+/// Terraform provider schema type provider
+pub struct TerraformSchemaProvider {
+    generator: TypeGenerator,
+}
+
+impl TerraformSchemaProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    /// Parse the JSON document and check that it is a provider schema dump
+    fn parse_document(&self, content: &str) -> ProviderResult<Value> {
+        let document: Value = serde_json::from_str(content)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+
+        if !document.get("provider_schemas").is_some_and(Value::is_object) {
+            return Err(ProviderError::ParseError(
+                "Expected `terraform providers schema -json` output with a `provider_schemas` object"
+                    .to_string(),
+            ));
+        }
+
+        Ok(document)
+    }
+
+    /// Apply the `providers`, `resources` and `data_sources` filters
+    fn filter_document(&self, document: &mut Value, typed: &TypedParams) -> ProviderResult<()> {
+        let Some(providers) = document
+            .get_mut("provider_schemas")
+            .and_then(Value::as_object_mut)
+        else {
+            return Ok(());
+        };
+
+        if let Some(patterns) = typed.list("providers") {
+            if !patterns.is_empty() {
+                let available: Vec<String> = providers.keys().map(|a| short_name(a).to_string()).collect();
+                providers.retain(|address, _| {
+                    matches_any(&patterns, address) || matches_any(&patterns, short_name(address))
+                });
+                if providers.is_empty() {
+                    return Err(ProviderError::InvalidSource(format!(
+                        "No provider matches '{}'; the schema contains: {}",
+                        patterns.join(","),
+                        available.join(", ")
+                    )));
+                }
+            }
+        }
+
+        for (param, key) in [("resources", "resource_schemas"), ("data_sources", "data_source_schemas")] {
+            let Some(patterns) = typed.list(param) else {
+                continue;
+            };
+            if patterns.is_empty() {
+                continue;
+            }
+            for provider in providers.values_mut() {
+                if let Some(schemas) = provider.get_mut(key).and_then(Value::as_object_mut) {
+                    schemas.retain(|name, _| matches_any(&patterns, name));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Generate types for every provider in the document
+    fn generate_document(&self, document: &Value, namespace: &str) -> GeneratedTypes {
+        let mut result = GeneratedTypes::new();
+        let Some(providers) = document.get("provider_schemas").and_then(Value::as_object) else {
+            return result;
+        };
+
+        for (address, provider) in providers {
+            let short = short_name(address);
+            let provider_path = vec![namespace.to_string(), self.generator.naming.apply(short)];
+
+            let mut config_module = GeneratedModule::new(provider_path.clone());
+            if let Some(block) = provider.get("provider").and_then(|p| p.get("block")) {
+                if has_content(block) {
+                    self.block_record("Provider", block, &mut config_module.types);
+                }
+            }
+
+            let mut modules = vec![config_module];
+            for (key, module_name) in [
+                ("resource_schemas", RESOURCES_MODULE),
+                ("data_source_schemas", DATA_SOURCES_MODULE),
+            ] {
+                let mut path = provider_path.clone();
+                path.push(module_name.to_string());
+                let mut module = GeneratedModule::new(path);
+
+                for (name, schema) in provider.get(key).and_then(Value::as_object).into_iter().flatten() {
+                    if let Some(block) = schema.get("block") {
+                        let record_name = self.generator.naming.apply(strip_provider_prefix(name, short));
+                        self.block_record(&record_name, block, &mut module.types);
+                    }
+                }
+                modules.push(module);
+            }
+
+            result
+                .modules
+                .extend(modules.into_iter().filter(|m| !m.types.is_empty()));
+        }
+
+        result
+    }
+
+    /// Generate a record for a schema block; nested records are pushed
+    /// before the record that uses them
+    fn block_record(&self, name: &str, block: &Value, records: &mut Vec<TypeDefinition>) -> TypeShape {
+        let mut fields = Vec::new();
+
+        for (attribute_name, attribute) in entries(block, "attributes") {
+            let context = self.nested_name(name, attribute_name);
+            let base = match attribute.get("nested_type") {
+                Some(nested) => self.nested_type_shape(&context, nested, records),
+                None => self.cty_shape(attribute.get("type").unwrap_or(&Value::Null), &context, records),
+            };
+            let required = attribute.get("required").and_then(Value::as_bool) == Some(true);
+            fields.push((attribute_name.clone(), base.option_unless(required).into()));
+        }
+
+        for (block_name, block_type) in entries(block, "block_types") {
+            let context = self.nested_name(name, block_name);
+            let inner = block_type.get("block").unwrap_or(&Value::Null);
+            let record = self.block_record(&context, inner, records);
+            let min_items = block_type.get("min_items").and_then(Value::as_u64).unwrap_or(0);
+            let shape = match block_type.get("nesting_mode").and_then(Value::as_str) {
+                Some("list") | Some("set") => record.list(),
+                Some("map") => TypeShape::map(TypeShape::named("string"), record),
+                Some("group") => record,
+                _ => record.option_unless(min_items > 0),
+            };
+            fields.push((block_name.clone(), shape.into()));
+        }
+
+        records.push(TypeDefinition::Record(RecordDef {
+            name: name.to_string(),
+            fields,
+        }));
+        TypeShape::named(name)
+    }
+
+    /// Protocol 6 `nested_type` attributes: attributes with a nesting mode
+    fn nested_type_shape(&self, name: &str, nested: &Value, records: &mut Vec<TypeDefinition>) -> TypeShape {
+        let record = self.block_record(name, nested, records);
+        match nested.get("nesting_mode").and_then(Value::as_str) {
+            Some("list") | Some("set") => record.list(),
+            Some("map") => TypeShape::map(TypeShape::named("string"), record),
+            _ => record,
+        }
+    }
+
+    /// Convert a cty type in its JSON form (`"string"`, `["list", "number"]`)
+    fn cty_shape(&self, cty: &Value, context: &str, records: &mut Vec<TypeDefinition>) -> TypeShape {
+        match cty {
+            Value::String(primitive) => TypeShape::named(match primitive.as_str() {
+                "string" => "string",
+                "number" => "float",
+                "bool" => "bool",
+                _ => "any",
+            }),
+            Value::Array(parts) => match (parts.first().and_then(Value::as_str), parts.get(1)) {
+                (Some("list") | Some("set"), Some(element)) => self.cty_shape(element, context, records).list(),
+                (Some("map"), Some(element)) => {
+                    TypeShape::map(TypeShape::named("string"), self.cty_shape(element, context, records))
+                }
+                (Some("object"), Some(Value::Object(attributes))) => {
+                    let optional: Vec<&str> = parts
+                        .get(2)
+                        .and_then(Value::as_array)
+                        .map(|names| names.iter().filter_map(Value::as_str).collect())
+                        .unwrap_or_default();
+                    let fields = attributes
+                        .iter()
+                        .map(|(attribute_name, attribute_type)| {
+                            let nested = self.nested_name(context, attribute_name);
+                            let base = self.cty_shape(attribute_type, &nested, records);
+                            let required = !optional.contains(&attribute_name.as_str());
+                            (attribute_name.clone(), base.option_unless(required).into())
+                        })
+                        .collect();
+                    records.push(TypeDefinition::Record(RecordDef {
+                        name: context.to_string(),
+                        fields,
+                    }));
+                    TypeShape::named(context)
+                }
+                (Some("tuple"), Some(Value::Array(elements))) => match homogeneous(elements) {
+                    Some(element) => self.cty_shape(element, context, records).list(),
+                    None => TypeShape::named("any").list(),
+                },
+                _ => TypeShape::named("any"),
+            },
+            _ => TypeShape::named("any"),
+        }
+    }
+
+    fn nested_name(&self, parent: &str, name: &str) -> String {
+        format!("{}{}", parent, self.generator.naming.apply(name))
+    }
+}
+
+impl Default for TerraformSchemaProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for TerraformSchemaProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("TerraformSchemaProvider")
+            .param(
+                ParamSpec::new("providers", ParamKind::List)
+                    .describe("Providers to keep, by name (`aws`) or address"),
+            )
+            .param(ParamSpec::new("resources", ParamKind::List).describe("Resource types to keep"))
+            .param(
+                ParamSpec::new("data_sources", ParamKind::List)
+                    .describe("Data source types to keep"),
+            )
+    }
+}
+
+impl TypeProvider for TerraformSchemaProvider {
+    fn name(&self) -> &str {
+        "TerraformSchemaProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let content = if source.trim_start().starts_with('{') {
+            source.to_string()
+        } else {
+            read_source(source, params)?
+        };
+
+        let mut document = self.parse_document(&content)?;
+        self.filter_document(&mut document, &typed)?;
+
+        Ok(Schema::Custom(document.to_string()))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        match schema {
+            Schema::Custom(content) => {
+                let document = self.parse_document(content)?;
+                Ok(self.generate_document(&document, namespace))
+            }
+            _ => Err(ProviderError::ParseError(
+                "Expected Terraform provider schema".to_string(),
+            )),
+        }
+    }
+}
+
+impl SourceValidator for TerraformSchemaProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(content) = schema else {
+            return Err(ProviderError::ParseError("Expected Terraform provider schema".to_string()));
+        };
+
+        let document = self.parse_document(content)?;
+        let mut diagnostics = Vec::new();
+
+        for (address, provider) in entries(&document, "provider_schemas") {
+            let mut type_count = 0;
+            for key in ["resource_schemas", "data_source_schemas"] {
+                for (name, schema) in entries(provider, key) {
+                    type_count += 1;
+                    if let Some(block) = schema.get("block") {
+                        block_diagnostics(block, name, &mut diagnostics);
+                    }
+                }
+            }
+
+            if type_count == 0 {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        "no-types",
+                        format!("Provider `{}` has no resources or data sources after filtering", address),
+                    )
+                    .at(address.clone()),
+                );
+            }
+        }
+
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for TerraformSchemaProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .params(&self.param_schema())
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+/// Report deprecated and dynamically typed attributes of a block
+fn block_diagnostics(block: &Value, location: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if block.get("deprecated").and_then(Value::as_bool) == Some(true) {
+        diagnostics.push(
+            Diagnostic::info("deprecated", format!("`{}` is deprecated", location)).at(location.to_string()),
+        );
+    }
+
+    for (name, attribute) in entries(block, "attributes") {
+        let attribute_location = format!("{}.{}", location, name);
+        if attribute.get("deprecated").and_then(Value::as_bool) == Some(true) {
+            diagnostics.push(
+                Diagnostic::info("deprecated", format!("Attribute `{}` is deprecated", attribute_location))
+                    .at(attribute_location.clone()),
+            );
+        }
+        if attribute.get("type").is_some_and(contains_dynamic) {
+            diagnostics.push(
+                Diagnostic::info(
+                    "coerced-any",
+                    format!("Attribute `{}` has a dynamic type; generated as `any`", attribute_location),
+                )
+                .at(attribute_location.clone()),
+            );
+        }
+        if let Some(nested) = attribute.get("nested_type") {
+            block_diagnostics(nested, &attribute_location, diagnostics);
+        }
+    }
+
+    for (name, block_type) in entries(block, "block_types") {
+        if let Some(inner) = block_type.get("block") {
+            block_diagnostics(inner, &format!("{}.{}", location, name), diagnostics);
+        }
+    }
+}
+
+fn contains_dynamic(cty: &Value) -> bool {
+    match cty {
+        Value::String(primitive) => primitive == "dynamic",
+        Value::Array(parts) => parts.iter().skip(1).any(contains_dynamic),
+        Value::Object(attributes) => attributes.values().any(contains_dynamic),
+        _ => false,
+    }
+}
+
+/// The entries of an object-valued key, or nothing
+fn entries<'a>(value: &'a Value, key: &str) -> impl Iterator<Item = (&'a String, &'a Value)> {
+    value
+        .get(key)
+        .and_then(Value::as_object)
+        .into_iter()
+        .flat_map(Map::iter)
+}
+
+fn has_content(block: &Value) -> bool {
+    entries(block, "attributes").next().is_some() || entries(block, "block_types").next().is_some()
+}
+
+fn homogeneous(elements: &[Value]) -> Option<&Value> {
+    let first = elements.first()?;
+    elements.iter().all(|e| e == first).then_some(first)
+}
+
+/// `registry.terraform.io/hashicorp/aws` → `aws`
+fn short_name(address: &str) -> &str {
+    address.rsplit('/').next().unwrap_or(address)
+}
+
+/// `aws_s3_bucket` → `s3_bucket` for provider `aws`
+fn strip_provider_prefix<'a>(name: &'a str, provider: &str) -> &'a str {
+    let prefix = provider.split('-').next().unwrap_or(provider);
+    name.strip_prefix(prefix)
+        .and_then(|rest| rest.strip_prefix('_'))
+        .filter(|rest| !rest.is_empty())
+        .unwrap_or(name)
+}
+
+fn matches_any(patterns: &[&str], name: &str) -> bool {
+    patterns.iter().any(|pattern| match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => name == *pattern,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    const SCHEMA: &str = r#"{
+      "format_version": "1.0",
+      "provider_schemas": {
+        "registry.terraform.io/hashicorp/aws": {
+          "provider": {
+            "version": 0,
+            "block": {
+              "attributes": {
+                "region": { "type": "string", "optional": true }
+              }
+            }
+          },
+          "resource_schemas": {
+            "aws_s3_bucket": {
+              "version": 0,
+              "block": {
+                "attributes": {
+                  "bucket": { "type": "string", "required": true },
+                  "arn": { "type": "string", "computed": true },
+                  "tags": { "type": ["map", "string"], "optional": true },
+                  "policy": { "type": "dynamic", "optional": true, "deprecated": true }
+                },
+                "block_types": {
+                  "versioning": {
+                    "nesting_mode": "list",
+                    "block": {
+                      "attributes": { "enabled": { "type": "bool", "optional": true } }
+                    },
+                    "max_items": 1
+                  },
+                  "website": {
+                    "nesting_mode": "single",
+                    "block": {
+                      "attributes": {
+                        "routing": {
+                          "type": ["object", { "prefix": "string", "port": "number" }, ["port"]],
+                          "optional": true
+                        }
+                      }
+                    }
+                  }
+                }
+              }
+            },
+            "aws_iam_role": {
+              "version": 0,
+              "block": {
+                "attributes": {
+                  "name": { "type": "string", "required": true },
+                  "inline_policy": {
+                    "nested_type": {
+                      "nesting_mode": "set",
+                      "attributes": { "policy": { "type": "string", "required": true } }
+                    },
+                    "optional": true
+                  }
+                }
+              }
+            }
+          },
+          "data_source_schemas": {
+            "aws_s3_bucket": {
+              "version": 0,
+              "block": {
+                "attributes": { "bucket": { "type": "string", "required": true } }
+              }
+            }
+          }
+        },
+        "registry.terraform.io/hashicorp/google": {
+          "provider": { "version": 0, "block": {} },
+          "resource_schemas": {
+            "google_storage_bucket": {
+              "version": 0,
+              "block": { "attributes": { "name": { "type": "string", "required": true } } }
+            }
+          }
+        }
+      }
+    }"#;
+
+    fn generate(params: &ProviderParams) -> GeneratedTypes {
+        let provider = TerraformSchemaProvider::new();
+        let schema = provider.resolve_schema(SCHEMA, params).unwrap();
+        provider.generate_types(&schema, "Tf").unwrap()
+    }
+
+    fn module<'a>(types: &'a GeneratedTypes, path: &[&str]) -> &'a GeneratedModule {
+        types
+            .modules
+            .iter()
+            .find(|m| m.path == path)
+            .unwrap_or_else(|| panic!("Expected module {:?}", path))
+    }
+
+    fn fields(module: &GeneratedModule, name: &str) -> Vec<(String, String)> {
+        module
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+            .fields
+            .iter()
+            .map(|(name, ty)| (name.clone(), ty.to_string()))
+            .collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect()
+    }
+
+    #[test]
+    fn test_generate_resources() {
+        let types = generate(&ProviderParams::default());
+
+        let resources = module(&types, &["Tf", "Aws", "Resources"]);
+        assert_eq!(
+            fields(resources, "S3Bucket"),
+            pairs(&[
+                ("arn", "string option"),
+                ("bucket", "string"),
+                ("policy", "any option"),
+                ("tags", "Map<string, string> option"),
+                ("versioning", "S3BucketVersioning list"),
+                ("website", "S3BucketWebsite option"),
+            ])
+        );
+        assert_eq!(
+            fields(resources, "S3BucketWebsiteRouting"),
+            pairs(&[("port", "float option"), ("prefix", "string")])
+        );
+        assert_eq!(
+            fields(resources, "IamRole"),
+            pairs(&[("inline_policy", "IamRoleInlinePolicy list option"), ("name", "string")])
+        );
+
+        let data_sources = module(&types, &["Tf", "Aws", "DataSources"]);
+        assert_eq!(fields(data_sources, "S3Bucket"), pairs(&[("bucket", "string")]));
+
+        let config = module(&types, &["Tf", "Aws"]);
+        assert_eq!(fields(config, "Provider"), pairs(&[("region", "string option")]));
+
+        // The google provider has an empty configuration block
+        assert!(types.modules.iter().all(|m| m.path != ["Tf", "Google"]));
+        assert!(fields(module(&types, &["Tf", "Google", "Resources"]), "StorageBucket").len() == 1);
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/terraform-schema.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_filters() {
+        let params = ProviderParams::default()
+            .with("providers", "aws")
+            .with("resources", "aws_iam_*")
+            .with("data_sources", "none");
+        let types = generate(&params);
+
+        let paths: Vec<Vec<String>> = types.modules.iter().map(|m| m.path.clone()).collect();
+        assert_eq!(paths, vec![vec!["Tf", "Aws"], vec!["Tf", "Aws", "Resources"]]);
+        let resources = module(&types, &["Tf", "Aws", "Resources"]);
+        assert_eq!(resources.types.len(), 2);
+
+        let provider = TerraformSchemaProvider::new();
+        let error = provider
+            .resolve_schema(SCHEMA, &ProviderParams::default().with("providers", "azurerm"))
+            .unwrap_err();
+        assert!(error.to_string().contains("aws, google"));
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = TerraformSchemaProvider::new();
+        let params = ProviderParams::default().with("resources", "aws_s3_bucket");
+        let report = provider.validate_source(SCHEMA, &params);
+        assert!(report.is_valid());
+
+        let codes: Vec<(&str, Option<&str>)> = report
+            .diagnostics
+            .iter()
+            .map(|d| (d.code.as_str(), d.location.as_deref()))
+            .collect();
+        assert!(codes.contains(&("deprecated", Some("aws_s3_bucket.policy"))));
+        assert!(codes.contains(&("coerced-any", Some("aws_s3_bucket.policy"))));
+        assert!(codes.contains(&("no-types", Some("registry.terraform.io/hashicorp/google"))));
+
+        assert!(!provider.validate_source("{\"format_version\": \"1.0\"}", &params).is_valid());
+    }
+}
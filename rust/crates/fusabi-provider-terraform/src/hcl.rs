@@ -0,0 +1,438 @@
+//! Minimal HCL structure parser
+//!
+//! Terraform configuration is HCL native syntax. Only the structure is
+//! parsed here — blocks, labels and attributes. Attribute expressions are
+//! kept as tokens plus their raw source text; they are interpreted by the
+//! caller where it matters (type constraints) and ignored elsewhere.
+
+use fusabi_type_providers::{ProviderError, ProviderResult};
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Token {
+    Identifier(String),
+    /// Quoted string; template interpolations are kept verbatim
+    StringLiteral(String),
+    /// Heredoc body
+    Heredoc(String),
+    Number(String),
+    Symbol(char),
+    Newline,
+    Eof,
+}
+
+/// A token and the byte range it covers in the source
+#[derive(Debug, Clone)]
+pub(crate) struct Spanned {
+    pub token: Token,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// `name = expression`
+#[derive(Debug, Clone)]
+pub(crate) struct Attribute {
+    pub name: String,
+    /// Expression tokens, newlines excluded
+    pub tokens: Vec<Token>,
+    /// Expression source text
+    pub text: String,
+}
+
+impl Attribute {
+    /// The value if the expression is a single string literal
+    pub fn as_str(&self) -> Option<&str> {
+        match self.tokens.as_slice() {
+            [Token::StringLiteral(s)] | [Token::Heredoc(s)] => Some(s),
+            _ => None,
+        }
+    }
+
+    /// The value if the expression is a boolean literal
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.tokens.as_slice() {
+            [Token::Identifier(s)] if s == "true" => Some(true),
+            [Token::Identifier(s)] if s == "false" => Some(false),
+            _ => None,
+        }
+    }
+}
+
+/// `type "label" ... { body }`
+#[derive(Debug, Clone)]
+pub(crate) struct Block {
+    pub kind: String,
+    pub labels: Vec<String>,
+    pub body: Body,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Body {
+    pub attributes: Vec<Attribute>,
+    pub blocks: Vec<Block>,
+}
+
+impl Body {
+    pub fn attribute(&self, name: &str) -> Option<&Attribute> {
+        self.attributes.iter().find(|a| a.name == name)
+    }
+
+    pub fn blocks<'a>(&'a self, kind: &'a str) -> impl Iterator<Item = &'a Block> + 'a {
+        self.blocks.iter().filter(move |b| b.kind == kind)
+    }
+}
+
+/// Parse an HCL file into its top-level body
+pub(crate) fn parse_hcl(content: &str) -> ProviderResult<Body> {
+    let tokens = tokenize(content)?;
+    let mut parser = Parser {
+        content,
+        tokens,
+        pos: 0,
+    };
+    parser.parse_body(false)
+}
+
+struct Parser<'a> {
+    content: &'a str,
+    tokens: Vec<Spanned>,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn current(&self) -> &Token {
+        self.tokens.get(self.pos).map(|t| &t.token).unwrap_or(&Token::Eof)
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.current().clone();
+        if self.pos < self.tokens.len() {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn skip_newlines(&mut self) {
+        while *self.current() == Token::Newline {
+            self.pos += 1;
+        }
+    }
+
+    fn parse_body(&mut self, nested: bool) -> ProviderResult<Body> {
+        let mut body = Body::default();
+
+        loop {
+            self.skip_newlines();
+            match self.advance() {
+                Token::Eof if nested => return Err(parse_error("Unexpected end of file in block")),
+                Token::Eof => return Ok(body),
+                Token::Symbol('}') if nested => return Ok(body),
+                Token::Identifier(name) => {
+                    if *self.current() == Token::Symbol('=') {
+                        self.pos += 1;
+                        body.attributes.push(self.parse_attribute(name)?);
+                    } else {
+                        body.blocks.push(self.parse_block(name)?);
+                    }
+                }
+                other => {
+                    return Err(parse_error(&format!(
+                        "Expected attribute or block, found {:?}",
+                        other
+                    )))
+                }
+            }
+        }
+    }
+
+    fn parse_block(&mut self, kind: String) -> ProviderResult<Block> {
+        let mut labels = Vec::new();
+        loop {
+            match self.advance() {
+                Token::StringLiteral(label) | Token::Identifier(label) => labels.push(label),
+                Token::Symbol('{') => break,
+                other => {
+                    return Err(parse_error(&format!(
+                        "Expected block label or '{{' after `{}`, found {:?}",
+                        kind, other
+                    )))
+                }
+            }
+        }
+
+        let body = self.parse_body(true)?;
+        Ok(Block { kind, labels, body })
+    }
+
+    /// Collect expression tokens up to the end of the line, or up to the
+    /// closing brace of a single-line block
+    fn parse_attribute(&mut self, name: String) -> ProviderResult<Attribute> {
+        let first = self.pos;
+        let mut depth = 0usize;
+        let mut tokens = Vec::new();
+
+        loop {
+            match self.current() {
+                Token::Eof if depth > 0 => {
+                    return Err(parse_error(&format!("Unterminated expression for `{}`", name)))
+                }
+                Token::Eof => break,
+                Token::Newline if depth == 0 => break,
+                Token::Symbol('}') if depth == 0 => break,
+                Token::Symbol('(' | '[' | '{') => depth += 1,
+                Token::Symbol(')' | ']' | '}') => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            let token = self.advance();
+            if token != Token::Newline {
+                tokens.push(token);
+            }
+        }
+
+        if tokens.is_empty() {
+            return Err(parse_error(&format!("Missing value for `{}`", name)));
+        }
+
+        let start = self.tokens[first].start;
+        let end = self.tokens[self.pos - 1].end;
+        Ok(Attribute {
+            name,
+            tokens,
+            text: self.content[start..end].trim().to_string(),
+        })
+    }
+}
+
+fn tokenize(content: &str) -> ProviderResult<Vec<Spanned>> {
+    let bytes = content.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let start = i;
+        let token = match bytes[i] {
+            b'\n' => {
+                i += 1;
+                Token::Newline
+            }
+            b' ' | b'\t' | b'\r' => {
+                i += 1;
+                continue;
+            }
+            b'#' => {
+                i = line_end(bytes, i);
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i = line_end(bytes, i);
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = content[i + 2..]
+                    .find("*/")
+                    .map(|offset| i + 2 + offset + 2)
+                    .ok_or_else(|| parse_error("Unterminated block comment"))?;
+                continue;
+            }
+            b'"' => {
+                i = string_end(bytes, i + 1)?;
+                Token::StringLiteral(unescape(&content[start + 1..i - 1]))
+            }
+            b'<' if bytes.get(i + 1) == Some(&b'<') => {
+                let (body, end) = heredoc(content, i)?;
+                i = end;
+                Token::Heredoc(body)
+            }
+            b'0'..=b'9' => {
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'.') {
+                    i += 1;
+                }
+                Token::Number(content[start..i].to_string())
+            }
+            b if is_identifier_byte(b) => {
+                while i < bytes.len() && (is_identifier_byte(bytes[i]) || bytes[i] == b'-') {
+                    i += 1;
+                }
+                Token::Identifier(content[start..i].to_string())
+            }
+            _ => {
+                let c = content[i..].chars().next().unwrap_or_default();
+                i += c.len_utf8();
+                Token::Symbol(c)
+            }
+        };
+        tokens.push(Spanned {
+            token,
+            start,
+            end: i,
+        });
+    }
+
+    Ok(tokens)
+}
+
+fn is_identifier_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b >= 0x80
+}
+
+fn line_end(bytes: &[u8], from: usize) -> usize {
+    bytes[from..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map_or(bytes.len(), |offset| from + offset)
+}
+
+/// Index just past the closing quote of a string starting at `i`, skipping
+/// over `${ ... }` interpolations which may themselves contain strings
+fn string_end(bytes: &[u8], mut i: usize) -> ProviderResult<usize> {
+    let mut depth = 0usize;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 1,
+            b'"' if depth == 0 => return Ok(i + 1),
+            b'"' => {
+                i = string_end(bytes, i + 1)?;
+                continue;
+            }
+            b'$' | b'%' if depth == 0 && bytes.get(i + 1) == Some(&b'{') => {
+                depth = 1;
+                i += 1;
+            }
+            b'{' if depth > 0 => depth += 1,
+            b'}' if depth > 0 => depth -= 1,
+            b'\n' if depth == 0 => break,
+            _ => {}
+        }
+        i += 1;
+    }
+
+    Err(parse_error("Unterminated string literal"))
+}
+
+fn unescape(raw: &str) -> String {
+    let mut result = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('r') => result.push('\r'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// Parse `<<EOF` / `<<-EOF` heredocs; returns the body and the index of the
+/// newline after the closing marker
+fn heredoc(content: &str, start: usize) -> ProviderResult<(String, usize)> {
+    let rest = &content[start + 2..];
+    let (indented, rest) = match rest.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, rest),
+    };
+
+    let header_end = rest
+        .find('\n')
+        .ok_or_else(|| parse_error("Unterminated heredoc"))?;
+    let marker = rest[..header_end].trim();
+    if marker.is_empty() {
+        return Err(parse_error("Heredoc is missing its marker"));
+    }
+
+    let body_start = content.len() - rest.len() + header_end + 1;
+    let mut lines = Vec::new();
+    let mut offset = body_start;
+    for line in content[body_start..].split_inclusive('\n') {
+        let next = offset + line.len();
+        if line.trim() == marker {
+            let body_end = next - usize::from(line.ends_with('\n'));
+            let lines = if indented { dedent(&lines) } else { lines };
+            let mut body = lines.concat();
+            body.truncate(body.trim_end_matches('\n').len());
+            return Ok((body, body_end));
+        }
+        lines.push(line.to_string());
+        offset = next;
+    }
+
+    Err(parse_error(&format!("Heredoc `{}` is not terminated", marker)))
+}
+
+fn dedent(lines: &[String]) -> Vec<String> {
+    let indent = lines
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|l| l.get(indent..).unwrap_or(l.trim_start()).to_string())
+        .collect()
+}
+
+fn parse_error(message: &str) -> ProviderError {
+    ProviderError::ParseError(format!("Invalid HCL: {}", message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_blocks_and_attributes() {
+        let body = parse_hcl(
+            r#"
+            # comment
+            variable "region" {
+              type    = string // trailing
+              default = "eu-${var.zone == "a" ? "west" : "east"}-1"
+            }
+
+            /* block
+               comment */
+            locals { tags = { Name = "x" } }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(body.blocks.len(), 2);
+        let variable = &body.blocks[0];
+        assert_eq!(variable.kind, "variable");
+        assert_eq!(variable.labels, vec!["region"]);
+        assert_eq!(variable.body.attribute("type").unwrap().text, "string");
+        assert_eq!(
+            variable.body.attribute("default").unwrap().as_str(),
+            Some(r#"eu-${var.zone == "a" ? "west" : "east"}-1"#)
+        );
+        assert_eq!(body.blocks[1].body.attribute("tags").unwrap().text, r#"{ Name = "x" }"#);
+    }
+
+    #[test]
+    fn test_multiline_expression_and_heredoc() {
+        let body = parse_hcl(
+            "variable \"x\" {\n  type = object({\n    a = string\n  })\n  description = <<-EOT\n    Line one\n    Line two\n  EOT\n}\n",
+        )
+        .unwrap();
+
+        let variable = &body.blocks[0].body;
+        assert_eq!(variable.attribute("type").unwrap().tokens.len(), 8);
+        assert_eq!(
+            variable.attribute("description").unwrap().as_str(),
+            Some("Line one\nLine two")
+        );
+    }
+
+    #[test]
+    fn test_invalid_hcl() {
+        assert!(parse_hcl("variable \"x\" {").is_err());
+        assert!(parse_hcl("x = \"unterminated").is_err());
+        assert!(parse_hcl("x = (1").is_err());
+    }
+}
@@ -0,0 +1,570 @@
+//! Terraform Type Provider
+//!
+//! Generates Fusabi types for the interface of Terraform modules: a
+//! `Variables` record with one field per input variable, and an `Outputs`
+//! record with one field per output.
+//!
+//! # Sources
+//!
+//! - a `.tf` file, or inline HCL
+//! - a module directory; every `.tf` file in it is read, and each
+//!   subdirectory of `modules/` becomes a nested module
+//!
+//! # Type Mapping
+//!
+//! | Terraform              | Fusabi                        |
+//! |------------------------|-------------------------------|
+//! | `string`               | `string`                      |
+//! | `number`               | `float`                       |
+//! | `bool`                 | `bool`                        |
+//! | `any`, no type         | `any`                         |
+//! | `list(T)`, `set(T)`    | `T list`                      |
+//! | `map(T)`               | `Map<string, T>`              |
+//! | `tuple([T, T])`        | `T list` (`any list` if mixed)|
+//! | `object({...})`        | record named after the field  |
+//! | `optional(T)`          | `T option`                    |
+//!
+//! Variables with a `default` become `T option` in `Variables`. Outputs have
+//! no declared type and are generated as `any`. Validation blocks are parsed
+//! and reported but not enforced by the generated types.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_terraform::TerraformProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = TerraformProvider::new();
+//! let schema = provider.resolve_schema("infra/network", &ProviderParams::default())?;
+//! let types = provider.generate_types(&schema, "Network")?;
+//! ```
+
+mod hcl;
+mod module;
+
+pub use module::{ObjectAttribute, Output, TerraformModule, TerraformType, Validation, Variable};
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, SourceKind,
+    SourceValidator, TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, TypeExpr, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// The `.tf` sources of one module
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ModuleSource {
+    /// Directory name under `modules/`; `None` for the root module
+    name: Option<String>,
+    content: String,
+}
+
+/// Terraform type provider
+pub struct TerraformProvider {
+    generator: TypeGenerator,
+}
+
+impl TerraformProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    /// Read a module directory and the nested modules under `modules/`
+    fn read_directory(&self, dir: &Path, params: &ProviderParams) -> ProviderResult<Vec<ModuleSource>> {
+        let mut sources = vec![ModuleSource {
+            name: None,
+            content: read_tf_files(dir, params)?,
+        }];
+
+        let modules_dir = dir.join("modules");
+        if modules_dir.is_dir() {
+            for path in sorted_entries(&modules_dir)? {
+                if !path.is_dir() {
+                    continue;
+                }
+                let content = read_tf_files(&path, params)?;
+                if content.is_empty() {
+                    continue;
+                }
+                sources.push(ModuleSource {
+                    name: path.file_name().map(|n| n.to_string_lossy().into_owned()),
+                    content,
+                });
+            }
+        }
+
+        Ok(sources)
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<Vec<(Option<String>, TerraformModule)>> {
+        let sources: Vec<ModuleSource> = serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected Terraform schema: {}", e)))?;
+        sources
+            .into_iter()
+            .map(|source| Ok((source.name, TerraformModule::parse(&source.content)?)))
+            .collect()
+    }
+
+    /// Generate types from parsed modules
+    fn generate_from_modules(
+        &self,
+        modules: &[(Option<String>, TerraformModule)],
+        namespace: &str,
+    ) -> GeneratedTypes {
+        let mut result = GeneratedTypes::new();
+
+        for (name, module) in modules {
+            let mut path = vec![namespace.to_string()];
+            path.extend(name.as_deref().map(|n| self.generator.naming.apply(n)));
+
+            let mut generated = GeneratedModule::new(path);
+            generated.types = self.module_types(module);
+            if !generated.types.is_empty() {
+                result.modules.push(generated);
+            }
+        }
+
+        result
+    }
+
+    fn module_types(&self, module: &TerraformModule) -> Vec<TypeDefinition> {
+        let mut types = Vec::new();
+
+        if !module.variables.is_empty() {
+            let fields = module
+                .variables
+                .iter()
+                .map(|variable| {
+                    let context = self.generator.naming.apply(&variable.name);
+                    let base = match &variable.variable_type {
+                        Some(variable_type) => self.type_shape(variable_type, &context, &mut types),
+                        None => TypeShape::named("any"),
+                    };
+                    let shape = base.option_unless(variable.default.is_none());
+                    (variable.name.clone(), shape.into())
+                })
+                .collect();
+
+            types.push(TypeDefinition::Record(RecordDef {
+                name: "Variables".to_string(),
+                fields,
+            }));
+        }
+
+        if !module.outputs.is_empty() {
+            types.push(TypeDefinition::Record(RecordDef {
+                name: "Outputs".to_string(),
+                fields: module
+                    .outputs
+                    .iter()
+                    .map(|output| (output.name.clone(), TypeShape::named("any").into()))
+                    .collect(),
+            }));
+        }
+
+        types
+    }
+
+    /// Convert a Terraform type to a Fusabi TypeShape; object types become
+    /// records named after `context` and are appended to `records`
+    fn type_shape(
+        &self,
+        terraform_type: &TerraformType,
+        context: &str,
+        records: &mut Vec<TypeDefinition>,
+    ) -> TypeShape {
+        match terraform_type {
+            TerraformType::String => TypeShape::named("string"),
+            TerraformType::Number => TypeShape::named("float"),
+            TerraformType::Bool => TypeShape::named("bool"),
+            TerraformType::Any => TypeShape::named("any"),
+            TerraformType::List(element) | TerraformType::Set(element) => {
+                self.type_shape(element, context, records).list()
+            }
+            TerraformType::Map(element) => {
+                TypeShape::map(TypeShape::named("string"), self.type_shape(element, context, records))
+            }
+            TerraformType::Tuple(elements) => match homogeneous(elements) {
+                Some(element) => self.type_shape(element, context, records).list(),
+                None => TypeShape::named("any").list(),
+            },
+            TerraformType::Object(attributes) => {
+                let fields = attributes
+                    .iter()
+                    .map(|attribute| {
+                        let nested = format!(
+                            "{}{}",
+                            context,
+                            self.generator.naming.apply(&attribute.name)
+                        );
+                        let base = self.type_shape(&attribute.attribute_type, &nested, records);
+                        let shape = base.option_unless(!attribute.optional);
+                        (attribute.name.clone(), shape.into())
+                    })
+                    .collect();
+
+                records.push(TypeDefinition::Record(RecordDef {
+                    name: context.to_string(),
+                    fields,
+                }));
+                TypeShape::named(context)
+            }
+        }
+    }
+}
+
+impl Default for TerraformProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypeProvider for TerraformProvider {
+    fn name(&self) -> &str {
+        "TerraformProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        // Paths and URLs never contain braces; HCL blocks always do
+        let sources = if source.contains('{') {
+            vec![ModuleSource {
+                name: None,
+                content: source.to_string(),
+            }]
+        } else if Path::new(source).is_dir() {
+            self.read_directory(Path::new(source), params)?
+        } else {
+            vec![ModuleSource {
+                name: None,
+                content: read_source(source, params)?,
+            }]
+        };
+
+        // Parse every module to validate it
+        for module_source in &sources {
+            TerraformModule::parse(&module_source.content)?;
+        }
+
+        let json = serde_json::to_string(&sources)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        match schema {
+            Schema::Custom(json) => {
+                let modules = self.parse_schema(json)?;
+                Ok(self.generate_from_modules(&modules, namespace))
+            }
+            _ => Err(ProviderError::ParseError(
+                "Expected Terraform schema".to_string(),
+            )),
+        }
+    }
+}
+
+impl SourceValidator for TerraformProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected Terraform schema".to_string()));
+        };
+
+        let mut diagnostics = Vec::new();
+
+        for (name, module) in self.parse_schema(json)? {
+            let prefix = name.map(|n| format!("module.{}.", n)).unwrap_or_default();
+
+            for variable in &module.variables {
+                let location = format!("{}variable.{}", prefix, variable.name);
+
+                match &variable.variable_type {
+                    None => diagnostics.push(
+                        Diagnostic::info(
+                            "coerced-any",
+                            format!("Variable `{}` has no type constraint; generated as `any`", variable.name),
+                        )
+                        .at(location.clone()),
+                    ),
+                    Some(variable_type) => {
+                        if contains(variable_type, &|t| *t == TerraformType::Any) {
+                            diagnostics.push(
+                                Diagnostic::info(
+                                    "coerced-any",
+                                    format!("Variable `{}` accepts `any` values", variable.name),
+                                )
+                                .at(location.clone()),
+                            );
+                        }
+                        if contains(variable_type, &|t| matches!(t, TerraformType::Set(_))) {
+                            diagnostics.push(
+                                Diagnostic::info(
+                                    "lossy-conversion",
+                                    format!(
+                                        "Set in variable `{}` is generated as a list; uniqueness is not enforced",
+                                        variable.name
+                                    ),
+                                )
+                                .at(location.clone()),
+                            );
+                        }
+                        let mixed_tuple = |t: &TerraformType| {
+                            matches!(t, TerraformType::Tuple(elements) if homogeneous(elements).is_none())
+                        };
+                        if contains(variable_type, &mixed_tuple) {
+                            diagnostics.push(
+                                Diagnostic::info(
+                                    "lossy-conversion",
+                                    format!(
+                                        "Tuple with mixed element types in variable `{}` is generated as `any list`",
+                                        variable.name
+                                    ),
+                                )
+                                .at(location.clone()),
+                            );
+                        }
+                    }
+                }
+
+                if !variable.validations.is_empty() {
+                    diagnostics.push(
+                        Diagnostic::info(
+                            "validation-skipped",
+                            format!(
+                                "Variable `{}` has {} validation rule(s) that the generated type does not enforce",
+                                variable.name,
+                                variable.validations.len()
+                            ),
+                        )
+                        .at(location),
+                    );
+                }
+            }
+
+            if !module.outputs.is_empty() {
+                diagnostics.push(
+                    Diagnostic::info(
+                        "coerced-any",
+                        "Terraform outputs have no declared types; every output is generated as `any`",
+                    )
+                    .at(format!("{}output", prefix)),
+                );
+            }
+        }
+
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for TerraformProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .source(SourceKind::Directory)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+/// Concatenate the `.tf` files of a directory in name order
+fn read_tf_files(dir: &Path, params: &ProviderParams) -> ProviderResult<String> {
+    let mut content = String::new();
+    for path in sorted_entries(dir)? {
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "tf") {
+            content.push_str(&read_source(&path.to_string_lossy(), params)?);
+            content.push('\n');
+        }
+    }
+    Ok(content)
+}
+
+fn sorted_entries(dir: &Path) -> ProviderResult<Vec<std::path::PathBuf>> {
+    let io_error = |e: std::io::Error| {
+        ProviderError::IoError(format!("Failed to read {}: {}", dir.display(), e))
+    };
+    let mut paths = std::fs::read_dir(dir)
+        .map_err(io_error)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(io_error)?;
+    paths.sort();
+    Ok(paths)
+}
+
+/// The shared element type of a tuple whose elements all have the same type
+fn homogeneous(elements: &[TerraformType]) -> Option<&TerraformType> {
+    let first = elements.first()?;
+    elements.iter().all(|e| e == first).then_some(first)
+}
+
+fn contains(terraform_type: &TerraformType, predicate: &dyn Fn(&TerraformType) -> bool) -> bool {
+    if predicate(terraform_type) {
+        return true;
+    }
+    match terraform_type {
+        TerraformType::List(element) | TerraformType::Set(element) | TerraformType::Map(element) => {
+            contains(element, predicate)
+        }
+        TerraformType::Tuple(elements) => elements.iter().any(|e| contains(e, predicate)),
+        TerraformType::Object(attributes) => attributes
+            .iter()
+            .any(|a| contains(&a.attribute_type, predicate)),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    fn record<'a>(module: &'a GeneratedModule, name: &str) -> &'a RecordDef {
+        module
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+    }
+
+    fn fields(record: &RecordDef) -> Vec<(&str, String)> {
+        record
+            .fields
+            .iter()
+            .map(|(name, ty)| (name.as_str(), ty.to_string()))
+            .collect()
+    }
+
+    const NETWORK: &str = r#"
+        variable "name" {
+          type = string
+        }
+
+        variable "cidr_blocks" {
+          type    = set(string)
+          default = ["10.0.0.0/16"]
+        }
+
+        variable "subnets" {
+          type = map(object({
+            cidr = string
+            public = optional(bool, false)
+            nat = optional(object({ enabled = bool }))
+          }))
+        }
+
+        variable "extra" {}
+
+        output "vpc_id" {
+          value = aws_vpc.this.id
+        }
+    "#;
+
+    #[test]
+    fn test_generate_variables() {
+        let provider = TerraformProvider::new();
+        let schema = provider.resolve_schema(NETWORK, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Network").unwrap();
+
+        assert_eq!(types.modules.len(), 1);
+        let module = &types.modules[0];
+        assert_eq!(module.path, vec!["Network"]);
+
+        assert_eq!(
+            fields(record(module, "Variables")),
+            [
+                ("name", "string".to_string()),
+                ("cidr_blocks", "string list option".to_string()),
+                ("subnets", "Map<string, Subnets>".to_string()),
+                ("extra", "any".to_string()),
+            ]
+        );
+        assert_eq!(
+            fields(record(module, "Subnets")),
+            [
+                ("cidr", "string".to_string()),
+                ("public", "bool option".to_string()),
+                ("nat", "SubnetsNat option".to_string()),
+            ]
+        );
+        assert_eq!(fields(record(module, "SubnetsNat")), [("enabled", "bool".to_string())]);
+        assert_eq!(fields(record(module, "Outputs")), [("vpc_id", "any".to_string())]);
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/terraform.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_tuple_mapping() {
+        let provider = TerraformProvider::new();
+        let schema = provider
+            .resolve_schema(
+                "variable \"pair\" { type = tuple([string, string]) }\nvariable \"mixed\" { type = tuple([string, number]) }",
+                &ProviderParams::default(),
+            )
+            .unwrap();
+        let types = provider.generate_types(&schema, "Test").unwrap();
+        assert_eq!(
+            fields(record(&types.modules[0], "Variables")),
+            [("pair", "string list".to_string()), ("mixed", "any list".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_module_directory() {
+        let dir = std::env::temp_dir().join(format!("fusabi-terraform-{}", std::process::id()));
+        let nested = dir.join("modules").join("dns_zone");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(dir.join("variables.tf"), "variable \"region\" { type = string }\n").unwrap();
+        std::fs::write(dir.join("outputs.tf"), "output \"id\" { value = 1 }\n").unwrap();
+        std::fs::write(dir.join("README.md"), "variable {").unwrap();
+        std::fs::write(nested.join("main.tf"), "variable \"domain\" { type = string }\n").unwrap();
+
+        let provider = TerraformProvider::new();
+        let schema = provider
+            .resolve_schema(&dir.to_string_lossy(), &ProviderParams::default())
+            .unwrap();
+        let types = provider.generate_types(&schema, "Infra").unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(types.modules.len(), 2);
+        assert_eq!(types.modules[0].path, vec!["Infra"]);
+        assert_eq!(record(&types.modules[0], "Outputs").fields.len(), 1);
+        assert_eq!(types.modules[1].path, vec!["Infra", "DnsZone"]);
+        assert_eq!(
+            fields(record(&types.modules[1], "Variables")),
+            [("domain", "string".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = TerraformProvider::new();
+        let source = format!(
+            "{}\nvariable \"port\" {{\n  type = number\n  validation {{\n    condition = var.port > 0\n    error_message = \"Port must be positive.\"\n  }}\n}}\n",
+            NETWORK
+        );
+
+        let report = provider.validate_source(&source, &ProviderParams::default());
+        assert!(report.is_valid());
+        let codes: Vec<(&str, Option<&str>)> = report
+            .diagnostics
+            .iter()
+            .map(|d| (d.code.as_str(), d.location.as_deref()))
+            .collect();
+        assert!(codes.contains(&("lossy-conversion", Some("variable.cidr_blocks"))));
+        assert!(codes.contains(&("coerced-any", Some("variable.extra"))));
+        assert!(codes.contains(&("validation-skipped", Some("variable.port"))));
+        assert!(codes.contains(&("coerced-any", Some("output"))));
+    }
+}
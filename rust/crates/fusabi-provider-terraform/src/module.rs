@@ -0,0 +1,369 @@
+//! Terraform module interface: input variables and outputs
+
+use crate::hcl::{parse_hcl, Body, Token};
+use fusabi_type_providers::{ProviderError, ProviderResult};
+
+/// Terraform type constraint
+#[derive(Debug, Clone, PartialEq)]
+pub enum TerraformType {
+    String,
+    Number,
+    Bool,
+    Any,
+    List(Box<TerraformType>),
+    Set(Box<TerraformType>),
+    Map(Box<TerraformType>),
+    Tuple(Vec<TerraformType>),
+    Object(Vec<ObjectAttribute>),
+}
+
+/// One attribute of an `object({...})` type
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectAttribute {
+    pub name: String,
+    pub attribute_type: TerraformType,
+    /// Declared with `optional(...)`
+    pub optional: bool,
+}
+
+/// A `validation { ... }` block
+#[derive(Debug, Clone, PartialEq)]
+pub struct Validation {
+    /// Condition expression source
+    pub condition: String,
+    /// Error message, or its expression source if it is not a plain string
+    pub error_message: String,
+}
+
+/// A `variable "name" { ... }` block
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variable {
+    pub name: String,
+    /// Declared type; Terraform treats a missing type as `any`
+    pub variable_type: Option<TerraformType>,
+    pub description: Option<String>,
+    /// Default expression source
+    pub default: Option<String>,
+    pub sensitive: bool,
+    pub nullable: bool,
+    pub validations: Vec<Validation>,
+}
+
+/// An `output "name" { ... }` block
+#[derive(Debug, Clone, PartialEq)]
+pub struct Output {
+    pub name: String,
+    pub description: Option<String>,
+    pub sensitive: bool,
+}
+
+/// Variables and outputs declared by a module
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TerraformModule {
+    pub variables: Vec<Variable>,
+    pub outputs: Vec<Output>,
+}
+
+impl TerraformModule {
+    /// Parse the contents of one or more `.tf` files. Blocks other than
+    /// `variable` and `output` are ignored.
+    pub fn parse(content: &str) -> ProviderResult<Self> {
+        let body = parse_hcl(content)?;
+        let mut module = TerraformModule::default();
+
+        for block in body.blocks("variable") {
+            let name = block_name(&block.labels, "variable")?;
+            module.variables.push(parse_variable(name, &block.body)?);
+        }
+
+        for block in body.blocks("output") {
+            let name = block_name(&block.labels, "output")?;
+            module.outputs.push(Output {
+                name: name.to_string(),
+                description: string_attribute(&block.body, "description"),
+                sensitive: bool_attribute(&block.body, "sensitive", false),
+            });
+        }
+
+        Ok(module)
+    }
+}
+
+fn block_name<'a>(labels: &'a [String], kind: &str) -> ProviderResult<&'a str> {
+    match labels {
+        [name] => Ok(name),
+        _ => Err(ProviderError::ParseError(format!(
+            "A `{}` block must have exactly one label",
+            kind
+        ))),
+    }
+}
+
+fn parse_variable(name: &str, body: &Body) -> ProviderResult<Variable> {
+    let variable_type = body
+        .attribute("type")
+        .map(|attribute| parse_type_constraint(&attribute.tokens))
+        .transpose()
+        .map_err(|e| {
+            ProviderError::ParseError(format!("Invalid type for variable `{}`: {}", name, e))
+        })?;
+
+    let validations = body
+        .blocks("validation")
+        .map(|block| Validation {
+            condition: block
+                .body
+                .attribute("condition")
+                .map(|a| a.text.clone())
+                .unwrap_or_default(),
+            error_message: block
+                .body
+                .attribute("error_message")
+                .map(|a| a.as_str().map(String::from).unwrap_or_else(|| a.text.clone()))
+                .unwrap_or_default(),
+        })
+        .collect();
+
+    Ok(Variable {
+        name: name.to_string(),
+        variable_type,
+        description: string_attribute(body, "description"),
+        default: body.attribute("default").map(|a| a.text.clone()),
+        sensitive: bool_attribute(body, "sensitive", false),
+        nullable: bool_attribute(body, "nullable", true),
+        validations,
+    })
+}
+
+fn string_attribute(body: &Body, name: &str) -> Option<String> {
+    body.attribute(name).and_then(|a| a.as_str()).map(String::from)
+}
+
+fn bool_attribute(body: &Body, name: &str, default: bool) -> bool {
+    body.attribute(name).and_then(|a| a.as_bool()).unwrap_or(default)
+}
+
+/// Parse a type constraint expression such as `map(object({ a = string }))`
+pub(crate) fn parse_type_constraint(tokens: &[Token]) -> Result<TerraformType, String> {
+    let mut parser = TypeParser { tokens, pos: 0 };
+    let parsed = parser.parse_type()?;
+    match parser.current() {
+        Token::Eof => Ok(parsed),
+        other => Err(format!("unexpected {:?} after type", other)),
+    }
+}
+
+struct TypeParser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl TypeParser<'_> {
+    fn current(&self) -> &Token {
+        self.tokens.get(self.pos).unwrap_or(&Token::Eof)
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.current().clone();
+        self.pos += 1;
+        token
+    }
+
+    fn expect(&mut self, symbol: char) -> Result<(), String> {
+        match self.advance() {
+            Token::Symbol(c) if c == symbol => Ok(()),
+            other => Err(format!("expected '{}', found {:?}", symbol, other)),
+        }
+    }
+
+    fn eat(&mut self, symbol: char) -> bool {
+        if *self.current() == Token::Symbol(symbol) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_type(&mut self) -> Result<TerraformType, String> {
+        let keyword = match self.advance() {
+            Token::Identifier(keyword) => keyword,
+            // Terraform 0.11 quoted types: "string", "list", "map"
+            Token::StringLiteral(keyword) => keyword,
+            other => return Err(format!("expected a type, found {:?}", other)),
+        };
+
+        match keyword.as_str() {
+            "string" => Ok(TerraformType::String),
+            "number" => Ok(TerraformType::Number),
+            "bool" => Ok(TerraformType::Bool),
+            "any" => Ok(TerraformType::Any),
+            "list" | "set" | "map" => {
+                // A bare collection keyword means a collection of `any`
+                let element = if self.eat('(') {
+                    let element = self.parse_type()?;
+                    self.expect(')')?;
+                    element
+                } else {
+                    TerraformType::Any
+                };
+                Ok(match keyword.as_str() {
+                    "list" => TerraformType::List(Box::new(element)),
+                    "set" => TerraformType::Set(Box::new(element)),
+                    _ => TerraformType::Map(Box::new(element)),
+                })
+            }
+            "tuple" => {
+                self.expect('(')?;
+                self.expect('[')?;
+                let mut elements = Vec::new();
+                while !self.eat(']') {
+                    elements.push(self.parse_type()?);
+                    if !self.eat(',') {
+                        self.expect(']')?;
+                        break;
+                    }
+                }
+                self.expect(')')?;
+                Ok(TerraformType::Tuple(elements))
+            }
+            "object" => {
+                self.expect('(')?;
+                self.expect('{')?;
+                let mut attributes = Vec::new();
+                while !self.eat('}') {
+                    attributes.push(self.parse_object_attribute()?);
+                    self.eat(',');
+                }
+                self.expect(')')?;
+                Ok(TerraformType::Object(attributes))
+            }
+            "optional" => Err("optional() is only allowed on object attributes".to_string()),
+            other => Err(format!("unknown type `{}`", other)),
+        }
+    }
+
+    fn parse_object_attribute(&mut self) -> Result<ObjectAttribute, String> {
+        let name = match self.advance() {
+            Token::Identifier(name) | Token::StringLiteral(name) => name,
+            other => return Err(format!("expected an attribute name, found {:?}", other)),
+        };
+        if !self.eat('=') {
+            self.expect(':')?;
+        }
+
+        let optional = *self.current() == Token::Identifier("optional".to_string());
+        let attribute_type = if optional {
+            self.pos += 1;
+            self.expect('(')?;
+            let inner = self.parse_type()?;
+            if self.eat(',') {
+                self.skip_default()?;
+            }
+            self.expect(')')?;
+            inner
+        } else {
+            self.parse_type()?
+        };
+
+        Ok(ObjectAttribute {
+            name,
+            attribute_type,
+            optional,
+        })
+    }
+
+    /// Skip the default value of `optional(type, default)`, stopping before
+    /// the closing parenthesis
+    fn skip_default(&mut self) -> Result<(), String> {
+        let mut depth = 0usize;
+        loop {
+            match self.current() {
+                Token::Eof => return Err("unterminated optional()".to_string()),
+                Token::Symbol(')') if depth == 0 => return Ok(()),
+                Token::Symbol('(' | '[' | '{') => depth += 1,
+                Token::Symbol(')' | ']' | '}') => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            self.pos += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_module() {
+        let module = TerraformModule::parse(
+            r#"
+            terraform { required_version = ">= 1.3" }
+
+            variable "subnets" {
+              description = "Subnets keyed by name"
+              type = map(object({
+                cidr  = string
+                az    = optional(string, "a")
+                tags  = optional(map(string), {})
+                ports = tuple([number, string])
+              }))
+              default   = {}
+              sensitive = true
+            }
+
+            variable "legacy" {
+              type = "list"
+              validation {
+                condition     = length(var.legacy) > 0
+                error_message = "At least one entry is required."
+              }
+            }
+
+            output "vpc_id" {
+              value = aws_vpc.main.id
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(module.variables.len(), 2);
+        let subnets = &module.variables[0];
+        assert_eq!(subnets.default.as_deref(), Some("{}"));
+        assert!(subnets.sensitive);
+        let Some(TerraformType::Map(element)) = &subnets.variable_type else {
+            panic!("Expected map type");
+        };
+        let TerraformType::Object(attributes) = element.as_ref() else {
+            panic!("Expected object type");
+        };
+        assert_eq!(attributes.len(), 4);
+        assert!(attributes[1].optional);
+        assert_eq!(
+            attributes[2].attribute_type,
+            TerraformType::Map(Box::new(TerraformType::String))
+        );
+        assert_eq!(
+            attributes[3].attribute_type,
+            TerraformType::Tuple(vec![TerraformType::Number, TerraformType::String])
+        );
+
+        let legacy = &module.variables[1];
+        assert_eq!(
+            legacy.variable_type,
+            Some(TerraformType::List(Box::new(TerraformType::Any)))
+        );
+        assert_eq!(legacy.validations[0].condition, "length(var.legacy) > 0");
+        assert_eq!(legacy.validations[0].error_message, "At least one entry is required.");
+
+        assert_eq!(module.outputs[0].name, "vpc_id");
+    }
+
+    #[test]
+    fn test_invalid_type() {
+        let error = TerraformModule::parse("variable \"x\" { type = list(strin) }").unwrap_err();
+        assert!(error.to_string().contains("unknown type `strin`"));
+        assert!(TerraformModule::parse("variable \"x\" { type = optional(string) }").is_err());
+        assert!(TerraformModule::parse("variable { type = string }").is_err());
+    }
+}
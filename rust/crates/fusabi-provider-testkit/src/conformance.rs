@@ -0,0 +1,347 @@
+//! Conformance checks for the `TypeProvider` contract
+//!
+//! Every provider is expected to behave the same way at its edges, whatever
+//! the source format:
+//!
+//! - `generate`: a valid source resolves and generates at least one type
+//! - `deterministic`: generating twice gives the same types
+//! - `namespace`: every generated module lives under the namespace
+//! - `valid-output`: the output has no error diagnostics from
+//!   [`check_generated_types`]
+//! - `file-source`: the valid source read from a file gives the same types
+//!   as the source passed inline
+//! - `missing-file`: a path that does not exist fails with
+//!   [`ProviderError::IoError`]
+//! - `invalid-source`: malformed sources fail with an error
+//! - `empty-schema`: a schema that declares nothing generates without error
+//! - `unknown-param`: an unknown param is either ignored or rejected with
+//!   [`ProviderError::InvalidSource`]
+//!
+//! No check may panic; a panic is reported as a violation of the check it
+//! happened in. Provider crates run the suite from a unit test:
+//!
+//! ```rust,ignore
+//! use fusabi_provider_testkit::Conformance;
+//!
+//! #[test]
+//! fn test_conformance() {
+//!     Conformance::new(&SqlProvider::new(), "CREATE TABLE users (id INT PRIMARY KEY);")
+//!         .with_extension("sql")
+//!         .with_invalid("CREATE TABLE users id INT;")
+//!         .with_empty("")
+//!         .assert_conforms();
+//! }
+//! ```
+
+use crate::snapshot;
+use fusabi_provider_common::{check_generated_types, Severity};
+use fusabi_type_providers::{GeneratedTypes, ProviderError, ProviderParams, ProviderResult, TypeProvider};
+use std::fmt;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Namespace the suite generates into
+pub const CONFORMANCE_NAMESPACE: &str = "Conformance";
+
+/// Param no provider declares, used by the `unknown-param` check
+const UNKNOWN_PARAM: &str = "conformance_unknown_param";
+
+/// A failed conformance check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// Name of the check, such as `deterministic`
+    pub check: &'static str,
+    /// What went wrong
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.check, self.message)
+    }
+}
+
+/// Conformance suite for one provider and its fixtures
+pub struct Conformance<'a> {
+    provider: &'a dyn TypeProvider,
+    valid: String,
+    extension: String,
+    invalid: Vec<String>,
+    empty: Option<String>,
+    params: Vec<(String, String)>,
+}
+
+impl<'a> Conformance<'a> {
+    /// Check `provider` with a valid inline source
+    pub fn new(provider: &'a dyn TypeProvider, valid: &str) -> Self {
+        Self {
+            provider,
+            valid: valid.to_string(),
+            extension: "txt".to_string(),
+            invalid: Vec::new(),
+            empty: None,
+            params: Vec::new(),
+        }
+    }
+
+    /// File extension used when writing sources to disk (default `txt`)
+    pub fn with_extension(mut self, extension: &str) -> Self {
+        self.extension = extension.to_string();
+        self
+    }
+
+    /// Add a malformed source that must be rejected
+    pub fn with_invalid(mut self, source: &str) -> Self {
+        self.invalid.push(source.to_string());
+        self
+    }
+
+    /// Content of a schema that declares nothing, checked from a file
+    pub fn with_empty(mut self, content: &str) -> Self {
+        self.empty = Some(content.to_string());
+        self
+    }
+
+    /// Add a param passed to every resolution
+    pub fn with_param(mut self, key: &str, value: &str) -> Self {
+        self.params.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Run every check and return the violations
+    pub fn run(&self) -> Vec<Violation> {
+        let params = self.params(&[]);
+        let types = match self.generate(&self.valid, &params) {
+            Ok(types) => types,
+            Err(message) => return vec![Violation { check: "generate", message }],
+        };
+
+        let mut violations = Vec::new();
+        let mut fail = |check: &'static str, message: String| violations.push(Violation { check, message });
+        let expected = snapshot(&types);
+        if types.root_types.is_empty() && types.modules.iter().all(|m| m.types.is_empty()) {
+            fail("generate", "the valid source generated no types".to_string());
+        }
+
+        match self.generate(&self.valid, &params) {
+            Ok(again) if snapshot(&again) == expected => {}
+            Ok(again) => fail(
+                "deterministic",
+                format!("second run differs:\n{}\n---\n{}", expected, snapshot(&again)),
+            ),
+            Err(message) => fail("deterministic", format!("second run failed: {}", message)),
+        }
+
+        let outside: Vec<String> = types
+            .modules
+            .iter()
+            .filter(|m| m.path.first().map(String::as_str) != Some(CONFORMANCE_NAMESPACE))
+            .map(|m| m.path.join("."))
+            .collect();
+        if !outside.is_empty() {
+            fail(
+                "namespace",
+                format!("modules outside {}: {}", CONFORMANCE_NAMESPACE, outside.join(", ")),
+            );
+        }
+
+        for diagnostic in check_generated_types(&types) {
+            if diagnostic.severity == Severity::Error {
+                fail("valid-output", format!("{}: {}", diagnostic.code, diagnostic.message));
+            }
+        }
+
+        let file = TempFile::new(&self.extension, &self.valid);
+        match self.generate(file.source(), &params) {
+            Ok(from_file) if snapshot(&from_file) == expected => {}
+            Ok(_) => fail("file-source", "reading from a file gives different types".to_string()),
+            Err(message) => fail("file-source", message),
+        }
+
+        let missing = TempFile::path_for(&self.extension);
+        match self.attempt(missing.to_str().unwrap_or_default(), &params) {
+            Err(Some(ProviderError::IoError(_))) => {}
+            Err(Some(other)) => fail("missing-file", format!("expected an I/O error, got: {}", other)),
+            Err(None) => fail("missing-file", "panicked".to_string()),
+            Ok(_) => fail("missing-file", format!("{} was accepted", missing.display())),
+        }
+
+        for source in &self.invalid {
+            match self.attempt(source, &params) {
+                Err(Some(_)) => {}
+                Err(None) => fail("invalid-source", format!("panicked on {:?}", source)),
+                Ok(_) => fail("invalid-source", format!("accepted {:?}", source)),
+            }
+        }
+
+        if let Some(content) = &self.empty {
+            let file = TempFile::new(&self.extension, content);
+            if let Err(message) = self.generate(file.source(), &params) {
+                fail("empty-schema", message);
+            }
+        }
+
+        match self.attempt(&self.valid, &self.params(&[(UNKNOWN_PARAM, "1")])) {
+            Ok(with_param) if snapshot(&with_param) == expected => {}
+            Ok(_) => fail("unknown-param", format!("{} changed the generated types", UNKNOWN_PARAM)),
+            Err(Some(ProviderError::InvalidSource(_))) => {}
+            Err(Some(other)) => fail(
+                "unknown-param",
+                format!("expected an invalid source error, got: {}", other),
+            ),
+            Err(None) => fail("unknown-param", "panicked".to_string()),
+        }
+
+        violations
+    }
+
+    /// Panic listing every violation, if there are any
+    pub fn assert_conforms(&self) {
+        let violations = self.run();
+        if !violations.is_empty() {
+            let lines: Vec<String> = violations.iter().map(|v| format!("  {}", v)).collect();
+            panic!(
+                "{} violates the provider contract:\n{}",
+                self.provider.name(),
+                lines.join("\n")
+            );
+        }
+    }
+
+    fn params(&self, extra: &[(&str, &str)]) -> ProviderParams {
+        let given = self.params.iter().map(|(key, value)| (key.as_str(), value.as_str()));
+        given
+            .chain(extra.iter().copied())
+            .fold(ProviderParams::default(), |params, (key, value)| params.with(key, value))
+    }
+
+    /// Resolve and generate; `Err(None)` when the provider panicked
+    fn attempt(&self, source: &str, params: &ProviderParams) -> Result<GeneratedTypes, Option<ProviderError>> {
+        let run = || -> ProviderResult<GeneratedTypes> {
+            let schema = self.provider.resolve_schema(source, params)?;
+            self.provider.generate_types(&schema, CONFORMANCE_NAMESPACE)
+        };
+        match catch_unwind(AssertUnwindSafe(run)) {
+            Ok(result) => result.map_err(Some),
+            Err(_) => Err(None),
+        }
+    }
+
+    fn generate(&self, source: &str, params: &ProviderParams) -> Result<GeneratedTypes, String> {
+        self.attempt(source, params).map_err(|e| match e {
+            Some(e) => e.to_string(),
+            None => "panicked".to_string(),
+        })
+    }
+}
+
+/// Source file in the temp directory, removed on drop
+struct TempFile {
+    path: PathBuf,
+    source: String,
+}
+
+impl TempFile {
+    fn new(extension: &str, content: &str) -> Self {
+        let path = Self::path_for(extension);
+        std::fs::write(&path, content)
+            .unwrap_or_else(|e| panic!("cannot write {}: {}", path.display(), e));
+        Self {
+            source: path.to_string_lossy().into_owned(),
+            path,
+        }
+    }
+
+    /// A fresh path that no other check uses
+    fn path_for(extension: &str) -> PathBuf {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let n = NEXT.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!(
+            "fusabi-conformance-{}-{}.{}",
+            std::process::id(),
+            n,
+            extension
+        ))
+    }
+
+    fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::read_source;
+    use fusabi_type_providers::{GeneratedModule, RecordDef, Schema, TypeDefinition, TypeExpr};
+
+    /// Each line `Type field:type ...`; inline sources start with `type `
+    struct LineProvider {
+        strict: bool,
+    }
+
+    impl TypeProvider for LineProvider {
+        fn name(&self) -> &str {
+            "LineProvider"
+        }
+
+        fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+            if self.strict && params.custom.contains_key(UNKNOWN_PARAM) {
+                return Err(ProviderError::IoError("unexpected param".to_string()));
+            }
+            let text = match source.strip_prefix("type ") {
+                Some(inline) => inline.to_string(),
+                None => read_source(source, params)?.trim_start_matches("type ").to_string(),
+            };
+            Ok(Schema::Custom(text))
+        }
+
+        fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+            let Schema::Custom(text) = schema else {
+                return Err(ProviderError::ParseError("Expected text".to_string()));
+            };
+            let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+            for line in text.lines() {
+                let mut words = line.split_whitespace();
+                let Some(name) = words.next() else { continue };
+                let fields = words
+                    .map(|w| {
+                        w.split_once(':')
+                            .map(|(f, t)| (f.to_string(), TypeExpr::Named(t.to_string())))
+                            .ok_or_else(|| ProviderError::ParseError(format!("Bad field '{}'", w)))
+                    })
+                    .collect::<ProviderResult<_>>()?;
+                module.types.push(TypeDefinition::Record(RecordDef {
+                    name: name.to_string(),
+                    fields,
+                }));
+            }
+            let mut types = GeneratedTypes::new();
+            types.modules.push(module);
+            Ok(types)
+        }
+    }
+
+    #[test]
+    fn test_conformance() {
+        let conforming = LineProvider { strict: false };
+        Conformance::new(&conforming, "type User id:int name:string")
+            .with_invalid("type User id")
+            .with_empty("")
+            .assert_conforms();
+
+        let strict = LineProvider { strict: true };
+        let violations = Conformance::new(&strict, "type User id:int")
+            .with_invalid("type User id:int")
+            .run();
+        let checks: Vec<_> = violations.iter().map(|v| v.check).collect();
+        assert_eq!(checks, ["invalid-source", "unknown-param"]);
+    }
+}
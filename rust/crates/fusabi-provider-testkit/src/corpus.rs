@@ -0,0 +1,165 @@
+//! Large schemas for benchmarks
+//!
+//! Provider benchmarks need sources the size of real ones: a Kubernetes
+//! OpenAPI document is several megabytes, a production DDL file hundreds
+//! of tables. Checking such files in is impractical, so [`load`] reads
+//! them from the directory named by `FUSABI_BENCH_CORPUS` when it is set,
+//! and the generators below build synthetic sources of a chosen size
+//! otherwise. The synthetic sources reference earlier definitions, so that
+//! reference resolution is exercised as well.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_testkit::corpus;
+//!
+//! let ddl = corpus::load_or("schema.sql", || corpus::sql_tables(300, 20));
+//! let spec = corpus::load_or("kubernetes.json", || corpus::openapi_document(2000, 15));
+//! let path = corpus::write_temp("kubernetes.json", &spec);
+//! ```
+
+use serde_json::{json, Map, Value};
+use std::path::PathBuf;
+
+/// Environment variable naming a directory of real-world corpus files
+pub const CORPUS_DIR_ENV: &str = "FUSABI_BENCH_CORPUS";
+
+/// Column types cycled through by [`sql_tables`]
+const SQL_TYPES: [&str; 6] = ["INT", "VARCHAR(255)", "TEXT", "BOOLEAN", "TIMESTAMP", "DECIMAL(10, 2)"];
+
+/// Property types cycled through by [`openapi_document`]
+const JSON_TYPES: [&str; 4] = ["string", "integer", "boolean", "number"];
+
+/// Field types cycled through by [`proto_messages`]
+const PROTO_TYPES: [&str; 5] = ["string", "int32", "bool", "double", "int64"];
+
+/// Content of `name` in the corpus directory, if one is configured and
+/// contains it
+pub fn load(name: &str) -> Option<String> {
+    let dir = std::env::var_os(CORPUS_DIR_ENV)?;
+    std::fs::read_to_string(PathBuf::from(dir).join(name)).ok()
+}
+
+/// Content of `name` in the corpus directory, or the synthetic fallback
+pub fn load_or(name: &str, fallback: impl FnOnce() -> String) -> String {
+    load(name).unwrap_or_else(fallback)
+}
+
+/// `CREATE TABLE` statements for `tables` tables of `columns` columns each
+///
+/// Every table after the first has a foreign key to its predecessor.
+pub fn sql_tables(tables: usize, columns: usize) -> String {
+    let mut ddl = String::new();
+    for t in 0..tables {
+        let mut definitions = vec!["id INT PRIMARY KEY".to_string()];
+        for c in 0..columns {
+            let not_null = if c % 2 == 0 { " NOT NULL" } else { "" };
+            definitions.push(format!("column_{} {}{}", c, SQL_TYPES[c % SQL_TYPES.len()], not_null));
+        }
+        if t > 0 {
+            definitions.push(format!("table_{}_id INT", t - 1));
+            definitions.push(format!("FOREIGN KEY (table_{0}_id) REFERENCES table_{0}(id)", t - 1));
+        }
+        ddl.push_str(&format!("CREATE TABLE table_{} (\n    {}\n);\n\n", t, definitions.join(",\n    ")));
+    }
+    ddl
+}
+
+/// OpenAPI 3 document with `schemas` component schemas of `properties`
+/// properties each, as JSON
+///
+/// Every schema after the first references its predecessor, and every
+/// tenth one is a string enum.
+pub fn openapi_document(schemas: usize, properties: usize) -> String {
+    let mut components = Map::new();
+    for s in 0..schemas {
+        let schema = if s % 10 == 9 {
+            json!({ "type": "string", "enum": ["Pending", "Running", "Succeeded", "Failed"] })
+        } else {
+            let mut props = Map::new();
+            for p in 0..properties {
+                props.insert(format!("field{}", p), json!({ "type": JSON_TYPES[p % JSON_TYPES.len()] }));
+            }
+            if s > 0 {
+                props.insert("previous".to_string(), json!({ "$ref": format!("#/components/schemas/Schema{}", s - 1) }));
+                props.insert(
+                    "items".to_string(),
+                    json!({ "type": "array", "items": { "$ref": format!("#/components/schemas/Schema{}", s - 1) } }),
+                );
+            }
+            let required: Vec<String> = (0..properties).step_by(2).map(|p| format!("field{}", p)).collect();
+            json!({ "type": "object", "properties": props, "required": required })
+        };
+        components.insert(format!("Schema{}", s), schema);
+    }
+
+    let document = json!({
+        "openapi": "3.0.0",
+        "info": { "title": "Corpus", "version": "1.0.0" },
+        "paths": {},
+        "components": { "schemas": Value::Object(components) },
+    });
+    document.to_string()
+}
+
+/// proto3 file with `messages` messages of `fields` scalar fields each
+///
+/// Every message after the first has a field and a repeated field of its
+/// predecessor's type, and every tenth definition is an enum.
+pub fn proto_messages(messages: usize, fields: usize) -> String {
+    let mut proto = String::from("syntax = \"proto3\";\npackage corpus;\n\n");
+    for m in 0..messages {
+        if m % 10 == 9 {
+            proto.push_str(&format!(
+                "enum Message{} {{\n    PENDING = 0;\n    RUNNING = 1;\n    SUCCEEDED = 2;\n    FAILED = 3;\n}}\n\n",
+                m
+            ));
+            continue;
+        }
+
+        let mut definitions: Vec<String> = (0..fields)
+            .map(|f| format!("{} field_{} = {};", PROTO_TYPES[f % PROTO_TYPES.len()], f, f + 1))
+            .collect();
+        if m > 0 {
+            definitions.push(format!("Message{} previous = {};", m - 1, fields + 1));
+            definitions.push(format!("repeated Message{} items = {};", m - 1, fields + 2));
+        }
+        proto.push_str(&format!("message Message{} {{\n    {}\n}}\n\n", m, definitions.join("\n    ")));
+    }
+    proto
+}
+
+/// Write `content` to a file named `name` in a fresh temp directory
+///
+/// Benchmarks of `resolve_schema` read their sources from disk like real
+/// callers do. The directory is left behind for the OS to clean up.
+pub fn write_temp(name: &str, content: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("fusabi-corpus-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap_or_else(|e| panic!("cannot create {}: {}", dir.display(), e));
+    let path = dir.join(name);
+    std::fs::write(&path, content).unwrap_or_else(|e| panic!("cannot write {}: {}", path.display(), e));
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_synthetic_corpora() {
+        let ddl = sql_tables(3, 4);
+        assert_eq!(ddl.matches("CREATE TABLE").count(), 3);
+        assert!(ddl.contains("REFERENCES table_1(id)"));
+
+        let document: Value = serde_json::from_str(&openapi_document(20, 5)).unwrap();
+        let schemas = document.pointer("/components/schemas").and_then(Value::as_object).unwrap();
+        assert_eq!(schemas.len(), 20);
+        assert!(schemas["Schema9"].get("enum").is_some());
+        assert_eq!(schemas["Schema3"]["properties"]["previous"]["$ref"], "#/components/schemas/Schema2");
+
+        let proto = proto_messages(20, 5);
+        assert_eq!(proto.matches("message ").count(), 18);
+        assert_eq!(proto.matches("enum ").count(), 2);
+        assert!(proto.contains("repeated Message2 items = 7;"));
+    }
+}
@@ -0,0 +1,201 @@
+//! Snapshot testing for Fusabi type providers
+//!
+//! Asserting on module and field counts passes as long as something of the
+//! right size comes out. A snapshot pins the whole output instead:
+//! [`snapshot`] renders [`GeneratedTypes`] as canonical text, one line per
+//! module, type, field and case, with every type expression normalized
+//! through [`TypeShape`], and [`assert_snapshot!`] compares it against a
+//! checked-in file under the crate's `tests/golden/` directory.
+//!
+//! Set `FUSABI_BLESS=1` to write the current output instead of comparing,
+//! then review and commit the result, as with the emitter golden files of
+//! [`fusabi_provider_common::emit::golden`]. Snapshots do not depend on
+//! emitter options, so changes to the Fusabi emitter leave them alone.
+//!
+//! [`Conformance`] checks the edges of the `TypeProvider` contract that
+//! every provider shares: invalid and missing sources, empty schemas,
+//! unknown params and determinism; see [`conformance`]. Benchmarks load
+//! large sources through [`corpus`].
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_testkit::{assert_snapshot, assert_valid, generate};
+//!
+//! #[test]
+//! fn test_users_table() {
+//!     let types = generate(&SqlProvider::new(), "CREATE TABLE users (id INT PRIMARY KEY);", "Db");
+//!     assert_valid(&types);
+//!     assert_snapshot!(types, "users");
+//! }
+//! ```
+//!
+//! writes or checks `tests/golden/users.snap`:
+//!
+//! ```text
+//! module Db
+//!   record Users
+//!     id: int
+//! ```
+
+pub mod conformance;
+pub mod corpus;
+
+pub use conformance::{Conformance, Violation};
+
+use fusabi_provider_common::emit::golden;
+use fusabi_provider_common::{check_generated_types, Severity, TypeShape};
+use fusabi_type_providers::{GeneratedTypes, ProviderParams, TypeDefinition, TypeProvider};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Compare the snapshot of generated types against
+/// `tests/golden/<name>.snap` of the calling crate
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($types:expr, $name:expr) => {
+        $crate::assert_snapshot_at(
+            &$types,
+            ::std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/golden")
+                .join(format!("{}.snap", $name)),
+        )
+    };
+}
+
+/// Canonical text of generated types
+///
+/// Root types come first, then each module under a `module A.B` line, in
+/// declaration order. Records list `name: type` fields and unions list
+/// `Case of a * b` cases.
+pub fn snapshot(types: &GeneratedTypes) -> String {
+    let mut out = String::new();
+    for type_def in &types.root_types {
+        write_type(&mut out, type_def, "");
+    }
+    for module in &types.modules {
+        let _ = writeln!(out, "module {}", module.path.join("."));
+        for type_def in &module.types {
+            write_type(&mut out, type_def, "  ");
+        }
+    }
+    out
+}
+
+fn write_type(out: &mut String, type_def: &TypeDefinition, indent: &str) {
+    match type_def {
+        TypeDefinition::Record(record) => {
+            let _ = writeln!(out, "{}record {}", indent, record.name);
+            for (name, ty) in &record.fields {
+                let _ = writeln!(out, "{}  {}: {}", indent, name, TypeShape::from_type_expr(ty));
+            }
+        }
+        TypeDefinition::Du(du) => {
+            let _ = writeln!(out, "{}union {}", indent, du.name);
+            for variant in &du.variants {
+                let fields: Vec<String> = variant
+                    .fields
+                    .iter()
+                    .map(|ty| TypeShape::from_type_expr(ty).to_string())
+                    .collect();
+                if fields.is_empty() {
+                    let _ = writeln!(out, "{}  {}", indent, variant.name);
+                } else {
+                    let _ = writeln!(out, "{}  {} of {}", indent, variant.name, fields.join(" * "));
+                }
+            }
+        }
+    }
+}
+
+/// Compare the snapshot of `types` against the file at `path`
+///
+/// Panics with both texts when they differ; see [`assert_snapshot!`] for
+/// the usual file layout.
+pub fn assert_snapshot_at(types: &GeneratedTypes, path: impl AsRef<Path>) {
+    golden::assert_golden(path, &snapshot(types));
+}
+
+/// Resolve an inline or file source with default params and generate types
+///
+/// Panics with the provider error when either step fails.
+pub fn generate(provider: &dyn TypeProvider, source: &str, namespace: &str) -> GeneratedTypes {
+    generate_with(provider, source, &ProviderParams::default(), namespace)
+}
+
+/// Like [`generate`], with explicit params
+pub fn generate_with(
+    provider: &dyn TypeProvider,
+    source: &str,
+    params: &ProviderParams,
+    namespace: &str,
+) -> GeneratedTypes {
+    let schema = provider
+        .resolve_schema(source, params)
+        .unwrap_or_else(|e| panic!("{} failed to resolve the source: {}", provider.name(), e));
+    provider
+        .generate_types(&schema, namespace)
+        .unwrap_or_else(|e| panic!("{} failed to generate types: {}", provider.name(), e))
+}
+
+/// Panic when the provider-independent checks report an error, such as a
+/// duplicate type or a case name that is not a valid identifier
+pub fn assert_valid(types: &GeneratedTypes) {
+    let errors: Vec<String> = check_generated_types(types)
+        .into_iter()
+        .filter(|d| d.severity == Severity::Error)
+        .map(|d| format!("  {}: {}", d.code, d.message))
+        .collect();
+    if !errors.is_empty() {
+        panic!("generated types have errors:\n{}", errors.join("\n"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_type_providers::{DuDef, GeneratedModule, RecordDef, VariantDef};
+
+    fn sample() -> GeneratedTypes {
+        let mut types = GeneratedTypes::new();
+        types.root_types.push(TypeDefinition::Du(DuDef {
+            name: "Status".to_string(),
+            variants: vec![
+                VariantDef::new_simple("Active".to_string()),
+                VariantDef::new(
+                    "Banned".to_string(),
+                    vec![TypeShape::named("string").into(), TypeShape::named("int").into()],
+                ),
+            ],
+        }));
+        let mut module = GeneratedModule::new(vec!["Api".to_string(), "V1".to_string()]);
+        module.types.push(TypeDefinition::Record(RecordDef {
+            name: "User".to_string(),
+            fields: vec![
+                ("id".to_string(), TypeShape::named("int").into()),
+                ("tags".to_string(), TypeShape::named("string").list().option().into()),
+                ("status".to_string(), TypeShape::named("Status").into()),
+            ],
+        }));
+        types.modules.push(module);
+        types
+    }
+
+    #[test]
+    fn test_snapshot() {
+        assert_eq!(
+            snapshot(&sample()),
+            "union Status\n  Active\n  Banned of string * int\n\
+             module Api.V1\n  record User\n    id: int\n    tags: string list option\n    status: Status\n"
+        );
+        assert_valid(&sample());
+    }
+
+    #[test]
+    fn test_assert_valid_rejects_duplicates() {
+        let mut types = sample();
+        let duplicate = sample().root_types.remove(0);
+        types.root_types.push(duplicate);
+        assert!(std::panic::catch_unwind(|| assert_valid(&types)).is_err());
+    }
+}
@@ -0,0 +1,49 @@
+/**
+ * Calculator service, adapted from the Apache Thrift tutorial.
+ */
+
+include "shared.thrift"
+
+namespace fusabi tutorial
+namespace rs tutorial
+namespace java tutorial
+
+typedef i32 MyInteger
+
+const i32 INT32CONSTANT = 9853
+const map<string, string> MAPCONSTANT = {'hello': 'world', 'goodnight': 'moon'}
+
+enum Operation {
+  ADD = 1,
+  SUBTRACT = 2,
+  MULTIPLY = 3,
+  DIVIDE = 4
+}
+
+struct Work {
+  1: i32 num1 = 0,
+  2: i32 num2,
+  3: Operation op,
+  4: optional string comment,
+  5: set<string> labels,
+}
+
+union Result {
+  1: i64 value
+  2: string error
+}
+
+exception InvalidOperation {
+  1: i32 whatOp,
+  2: string why
+}
+
+service Calculator extends shared.SharedService {
+  void ping(),
+
+  i32 add(1: MyInteger num1, 2: MyInteger num2),
+
+  Result calculate(1: i32 logid, 2: Work w) throws (1: InvalidOperation ouch),
+
+  oneway void zip()
+}
@@ -0,0 +1,449 @@
+//! Thrift Type Provider
+//!
+//! Generates Fusabi types from Apache Thrift IDL (.thrift) definitions.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_thrift::ThriftProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = ThriftProvider::new();
+//! let schema = provider.resolve_schema("service.thrift", &ProviderParams::default())?;
+//! let types = provider.generate_types(&schema, "MyThrift")?;
+//! ```
+
+mod parser;
+mod types;
+
+pub use types::{
+    Enum, EnumValue, Field, FieldType, Function, Requiredness, Service, Struct, ThriftFile,
+    Typedef,
+};
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, SourceValidator,
+    TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, DuDef, VariantDef, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use std::collections::HashMap;
+
+/// Thrift type provider
+pub struct ThriftProvider {
+    generator: TypeGenerator,
+}
+
+impl ThriftProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    /// Parse a .thrift file from string content
+    fn parse_thrift(&self, content: &str) -> ProviderResult<ThriftFile> {
+        parser::parse_thrift(content)
+    }
+
+    /// Generate types from a parsed thrift file
+    fn generate_from_thrift(
+        &self,
+        thrift: &ThriftFile,
+        namespace: &str,
+    ) -> ProviderResult<GeneratedTypes> {
+        let mut result = GeneratedTypes::new();
+
+        // Create a module for the namespace if present
+        let module_path = if let Some(ns) = thrift.namespace() {
+            ns.split('.').map(String::from).collect()
+        } else {
+            vec![namespace.to_string()]
+        };
+
+        let mut types_module = GeneratedModule::new(module_path);
+        let typedefs = thrift.build_typedef_map();
+
+        for enum_def in &thrift.enums {
+            types_module.types.push(self.enum_to_typedef(enum_def));
+        }
+
+        for struct_def in thrift.structs.iter().chain(&thrift.exceptions) {
+            types_module.types.push(self.struct_to_typedef(struct_def, &typedefs));
+        }
+
+        for union_def in &thrift.unions {
+            types_module.types.push(self.union_to_typedef(union_def, &typedefs));
+        }
+
+        // Each function with arguments gets an argument record
+        for service in &thrift.services {
+            let service_name = self.generator.naming.apply(&service.name);
+            for function in service.functions.iter().filter(|f| !f.params.is_empty()) {
+                let args = Struct {
+                    name: format!(
+                        "{}{}Args",
+                        service_name,
+                        self.generator.naming.apply(&function.name)
+                    ),
+                    fields: function.params.clone(),
+                };
+                types_module.types.push(self.struct_to_typedef(&args, &typedefs));
+            }
+        }
+
+        if !types_module.types.is_empty() {
+            result.modules.push(types_module);
+        }
+
+        Ok(result)
+    }
+
+    /// Convert a thrift struct or exception to a RecordDef
+    fn struct_to_typedef(
+        &self,
+        struct_def: &Struct,
+        typedefs: &HashMap<String, &FieldType>,
+    ) -> TypeDefinition {
+        let fields = struct_def
+            .fields
+            .iter()
+            .map(|field| {
+                let base_type = self.field_type_shape(&field.field_type, typedefs);
+                let shape = match field.requiredness {
+                    Requiredness::Optional => base_type.option(),
+                    Requiredness::Required | Requiredness::Default => base_type,
+                };
+                (field.name.clone(), shape.into())
+            })
+            .collect();
+
+        TypeDefinition::Record(RecordDef {
+            name: self.generator.naming.apply(&struct_def.name),
+            fields,
+        })
+    }
+
+    /// Convert a thrift union to a DuDef with one case per field
+    fn union_to_typedef(
+        &self,
+        union_def: &Struct,
+        typedefs: &HashMap<String, &FieldType>,
+    ) -> TypeDefinition {
+        let variants = union_def
+            .fields
+            .iter()
+            .map(|field| {
+                VariantDef::new(
+                    self.generator.naming.apply(&field.name),
+                    vec![self.field_type_shape(&field.field_type, typedefs).into()],
+                )
+            })
+            .collect();
+
+        TypeDefinition::Du(DuDef {
+            name: self.generator.naming.apply(&union_def.name),
+            variants,
+        })
+    }
+
+    /// Convert a thrift enum to a DuDef
+    fn enum_to_typedef(&self, enum_def: &Enum) -> TypeDefinition {
+        let variants = enum_def
+            .values
+            .iter()
+            .map(|v| VariantDef::new_simple(self.generator.naming.apply(&v.name)))
+            .collect();
+
+        TypeDefinition::Du(DuDef {
+            name: self.generator.naming.apply(&enum_def.name),
+            variants,
+        })
+    }
+
+    /// Convert a thrift field type to a Fusabi TypeExpr
+    fn field_type_shape(
+        &self,
+        field_type: &FieldType,
+        typedefs: &HashMap<String, &FieldType>,
+    ) -> TypeShape {
+        match field_type {
+            FieldType::Bool => TypeShape::named("bool"),
+            FieldType::Byte | FieldType::I8 | FieldType::I16 | FieldType::I32 => TypeShape::named("int"),
+            FieldType::I64 => TypeShape::named("int64"),
+            FieldType::Double => TypeShape::named("float"),
+            FieldType::String | FieldType::Uuid => TypeShape::named("string"),
+            FieldType::Binary => TypeShape::named("bytes"),
+            FieldType::List(element) | FieldType::Set(element) => self.field_type_shape(element, typedefs).list(),
+            FieldType::Map(key, value) => TypeShape::map(
+                self.field_type_shape(key, typedefs),
+                self.field_type_shape(value, typedefs),
+            ),
+            FieldType::Named(name) => match typedefs.get(name) {
+                // Typedefs are inlined; aliases never refer to themselves
+                Some(target) => self.field_type_shape(target, typedefs),
+                // Types from includes are referenced by their short name
+                None => {
+                    let short = name.rsplit('.').next().unwrap_or(name);
+                    TypeShape::named(self.generator.naming.apply(short))
+                }
+            },
+        }
+    }
+}
+
+impl Default for ThriftProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypeProvider for ThriftProvider {
+    fn name(&self) -> &str {
+        "ThriftProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        // Check if source looks like inline thrift content
+        let looks_like_thrift = ["struct ", "enum ", "union ", "exception ", "service ", "namespace ", "include ", "typedef "]
+            .iter()
+            .any(|keyword| source.contains(keyword));
+
+        let thrift_content = if looks_like_thrift {
+            source.to_string()
+        } else {
+            read_source(source, params)?
+        };
+
+        // Parse the thrift file to validate it
+        let _thrift_file = self.parse_thrift(&thrift_content)?;
+
+        Ok(Schema::Custom(thrift_content))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        match schema {
+            Schema::Custom(thrift_content) => {
+                let thrift = self.parse_thrift(thrift_content)?;
+                self.generate_from_thrift(&thrift, namespace)
+            }
+            _ => Err(ProviderError::ParseError(
+                "Expected Thrift schema".to_string(),
+            )),
+        }
+    }
+}
+
+impl SourceValidator for ThriftProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(thrift_content) = schema else {
+            return Err(ProviderError::ParseError("Expected Thrift schema".to_string()));
+        };
+
+        let thrift = self.parse_thrift(thrift_content)?;
+        let mut diagnostics = Vec::new();
+
+        for include in &thrift.includes {
+            diagnostics.push(
+                Diagnostic::warning(
+                    "include-unresolved",
+                    format!("Included file `{}` is not loaded; its types will be unresolved", include),
+                )
+                .at(include.clone()),
+            );
+        }
+
+        for constant in &thrift.constants {
+            diagnostics.push(
+                Diagnostic::info(
+                    "const-skipped",
+                    format!("Constant `{}` does not generate types", constant),
+                )
+                .at(constant.clone()),
+            );
+        }
+
+        let all_structs = thrift
+            .structs
+            .iter()
+            .chain(&thrift.unions)
+            .chain(&thrift.exceptions);
+        for struct_def in all_structs {
+            for field in &struct_def.fields {
+                if contains_set(&field.field_type) {
+                    diagnostics.push(
+                        Diagnostic::info(
+                            "lossy-conversion",
+                            format!(
+                                "Set field `{}.{}` is generated as a list; uniqueness is not enforced",
+                                struct_def.name, field.name
+                            ),
+                        )
+                        .at(format!("{}.{}", struct_def.name, field.name)),
+                    );
+                }
+            }
+        }
+
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for ThriftProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+fn contains_set(field_type: &FieldType) -> bool {
+    match field_type {
+        FieldType::Set(_) => true,
+        FieldType::List(element) => contains_set(element),
+        FieldType::Map(key, value) => contains_set(key) || contains_set(value),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    fn generate(thrift: &str) -> GeneratedModule {
+        let provider = ThriftProvider::new();
+        let schema = provider.resolve_schema(thrift, &ProviderParams::default()).unwrap();
+        let mut types = provider.generate_types(&schema, "Test").unwrap();
+        assert_eq!(types.modules.len(), 1);
+        types.modules.remove(0)
+    }
+
+    fn record<'a>(module: &'a GeneratedModule, name: &str) -> &'a RecordDef {
+        module
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+    }
+
+    #[test]
+    fn test_generate_struct() {
+        let module = generate(
+            r#"
+            namespace fusabi example.v1
+            namespace java com.example
+
+            typedef i64 Timestamp
+
+            struct User {
+              1: required string id,
+              2: optional string email,
+              3: Timestamp created_at,
+              4: list<string> tags,
+              5: map<string, i32> scores,
+              6: shared.Address address,
+            }
+            "#,
+        );
+
+        assert_eq!(module.path, vec!["example", "v1"]);
+        let user = record(&module, "User");
+        let fields: Vec<(&str, String)> = user
+            .fields
+            .iter()
+            .map(|(name, ty)| (name.as_str(), ty.to_string()))
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                ("id", "string".to_string()),
+                ("email", "string option".to_string()),
+                ("created_at", "int64".to_string()),
+                ("tags", "string list".to_string()),
+                ("scores", "Map<string, int>".to_string()),
+                ("address", "Address".to_string()),
+            ]
+        );
+
+        let mut types = GeneratedTypes::new();
+        types.modules.push(module);
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/thrift.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_generate_enum_and_union() {
+        let module = generate(
+            r#"
+            enum Operation { ADD = 1, SUBTRACT = 2 }
+
+            union Value {
+              1: i64 int_value
+              2: string string_value
+            }
+            "#,
+        );
+
+        assert_eq!(module.path, vec!["Test"]);
+        let TypeDefinition::Du(op) = &module.types[0] else {
+            panic!("Expected DU type");
+        };
+        assert_eq!(op.name, "Operation");
+        assert_eq!(op.variants.len(), 2);
+
+        let TypeDefinition::Du(value) = &module.types[1] else {
+            panic!("Expected DU type");
+        };
+        assert_eq!(value.name, "Value");
+        assert_eq!(value.variants[0].name, "IntValue");
+        assert_eq!(value.variants[0].fields[0].to_string(), "int64");
+    }
+
+    #[test]
+    fn test_generate_service_args() {
+        let module = generate(
+            r#"
+            exception InvalidOperation { 1: i32 what_op, 2: string why }
+
+            service Calculator {
+              void ping(),
+              i32 add(1: i32 num1, 2: i32 num2) throws (1: InvalidOperation ouch)
+            }
+            "#,
+        );
+
+        assert_eq!(record(&module, "InvalidOperation").fields.len(), 2);
+        assert_eq!(record(&module, "CalculatorAddArgs").fields.len(), 2);
+        assert_eq!(module.types.len(), 2);
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = ThriftProvider::new();
+        let thrift = r#"
+            include "shared.thrift"
+            const i32 LIMIT = 10
+
+            struct Bag {
+              1: set<string> names
+            }
+        "#;
+
+        let report = provider.validate_source(thrift, &ProviderParams::default());
+        assert!(report.is_valid());
+        assert_eq!(report.warnings().next().unwrap().code, "include-unresolved");
+        assert!(report.diagnostics.iter().any(|d| d.code == "const-skipped"));
+        assert!(report.diagnostics.iter().any(|d| d.code == "lossy-conversion"));
+    }
+}
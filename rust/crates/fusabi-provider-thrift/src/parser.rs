@@ -0,0 +1,622 @@
+//! Thrift IDL parser
+//!
+//! This is a simplified Thrift parser that handles the common IDL syntax:
+//! namespaces, includes, typedefs, constants, enums, structs, unions,
+//! exceptions and services. Annotations and constant values are skipped.
+
+use crate::types::{
+    Enum, EnumValue, Field, FieldType, Function, Requiredness, Service, Struct, ThriftFile,
+    Typedef,
+};
+use fusabi_type_providers::{ProviderError, ProviderResult};
+
+/// Parse a .thrift file from string content
+pub fn parse_thrift(content: &str) -> ProviderResult<ThriftFile> {
+    let mut parser = Parser::new(content);
+    parser.parse_file()
+}
+
+/// Simple Thrift parser
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    // Symbols
+    LeftBrace,
+    RightBrace,
+    LeftParen,
+    RightParen,
+    LeftAngle,
+    RightAngle,
+    LeftBracket,
+    RightBracket,
+    Semicolon,
+    Colon,
+    Equals,
+    Comma,
+
+    // Literals
+    Identifier(String),
+    Number(String),
+    StringLiteral(String),
+
+    // End of file
+    Eof,
+}
+
+impl Parser {
+    fn new(content: &str) -> Self {
+        let tokens = tokenize(content);
+        Self { tokens, pos: 0 }
+    }
+
+    fn current(&self) -> &Token {
+        self.tokens.get(self.pos).unwrap_or(&Token::Eof)
+    }
+
+    fn peek(&self, offset: usize) -> &Token {
+        self.tokens.get(self.pos + offset).unwrap_or(&Token::Eof)
+    }
+
+    fn advance(&mut self) {
+        if self.pos < self.tokens.len() {
+            self.pos += 1;
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.current(), Token::Identifier(s) if s == keyword)
+    }
+
+    fn expect(&mut self, expected: Token) -> ProviderResult<()> {
+        if self.current() == &expected {
+            self.advance();
+            Ok(())
+        } else {
+            Err(ProviderError::ParseError(format!(
+                "Expected {:?}, got {:?}",
+                expected,
+                self.current()
+            )))
+        }
+    }
+
+    fn expect_identifier(&mut self) -> ProviderResult<String> {
+        match self.current() {
+            Token::Identifier(s) => {
+                let result = s.clone();
+                self.advance();
+                Ok(result)
+            }
+            _ => Err(ProviderError::ParseError(format!(
+                "Expected identifier, got {:?}",
+                self.current()
+            ))),
+        }
+    }
+
+    fn expect_string(&mut self) -> ProviderResult<String> {
+        match self.current() {
+            Token::StringLiteral(s) => {
+                let result = s.clone();
+                self.advance();
+                Ok(result)
+            }
+            _ => Err(ProviderError::ParseError(format!(
+                "Expected string literal, got {:?}",
+                self.current()
+            ))),
+        }
+    }
+
+    /// Skip an optional `,` or `;` list separator
+    fn skip_separator(&mut self) {
+        if matches!(self.current(), Token::Comma | Token::Semicolon) {
+            self.advance();
+        }
+    }
+
+    /// Skip a balanced group starting at the current open token
+    fn skip_group(&mut self) {
+        let mut depth = 0;
+        loop {
+            match self.current() {
+                Token::LeftParen | Token::LeftBrace | Token::LeftBracket => depth += 1,
+                Token::RightParen | Token::RightBrace | Token::RightBracket => depth -= 1,
+                Token::Eof => return,
+                _ => {}
+            }
+            self.advance();
+            if depth <= 0 {
+                return;
+            }
+        }
+    }
+
+    /// Skip `(key = "value", ...)` annotations if present
+    fn skip_annotations(&mut self) {
+        if self.current() == &Token::LeftParen {
+            self.skip_group();
+        }
+    }
+
+    /// Skip a constant value: a literal, identifier, list or map
+    fn skip_const_value(&mut self) {
+        match self.current() {
+            Token::LeftBracket | Token::LeftBrace => self.skip_group(),
+            Token::Eof => {}
+            _ => self.advance(),
+        }
+    }
+
+    fn parse_file(&mut self) -> ProviderResult<ThriftFile> {
+        let mut file = ThriftFile::new();
+
+        while self.current() != &Token::Eof {
+            let Token::Identifier(keyword) = self.current().clone() else {
+                // Skip unknown tokens
+                self.advance();
+                continue;
+            };
+
+            match keyword.as_str() {
+                "namespace" => {
+                    self.advance();
+                    let scope = self.expect_identifier()?;
+                    let name = self.expect_identifier()?;
+                    file.namespaces.push((scope, name));
+                    self.skip_annotations();
+                }
+                "include" => {
+                    self.advance();
+                    file.includes.push(self.expect_string()?);
+                }
+                "cpp_include" => {
+                    self.advance();
+                    self.expect_string()?;
+                }
+                "typedef" => {
+                    self.advance();
+                    let target = self.parse_type()?;
+                    let name = self.expect_identifier()?;
+                    self.skip_annotations();
+                    file.typedefs.push(Typedef { name, target });
+                }
+                "const" => {
+                    self.advance();
+                    self.parse_type()?;
+                    file.constants.push(self.expect_identifier()?);
+                    self.expect(Token::Equals)?;
+                    self.skip_const_value();
+                }
+                "enum" => file.enums.push(self.parse_enum()?),
+                "struct" => file.structs.push(self.parse_struct()?),
+                "union" => file.unions.push(self.parse_struct()?),
+                "exception" => file.exceptions.push(self.parse_struct()?),
+                "service" => file.services.push(self.parse_service()?),
+                _ => {
+                    return Err(ProviderError::ParseError(format!(
+                        "Unexpected `{}` at top level",
+                        keyword
+                    )));
+                }
+            }
+
+            self.skip_separator();
+        }
+
+        Ok(file)
+    }
+
+    fn parse_enum(&mut self) -> ProviderResult<Enum> {
+        self.advance();
+        let name = self.expect_identifier()?;
+        self.expect(Token::LeftBrace)?;
+
+        let mut values = Vec::new();
+        while self.current() != &Token::RightBrace && self.current() != &Token::Eof {
+            let value_name = self.expect_identifier()?;
+            let value = if self.current() == &Token::Equals {
+                self.advance();
+                let Token::Number(number) = self.current().clone() else {
+                    return Err(ProviderError::ParseError(format!(
+                        "Expected enum value for {}, got {:?}",
+                        value_name,
+                        self.current()
+                    )));
+                };
+                self.advance();
+                Some(parse_int(&number)?)
+            } else {
+                None
+            };
+            self.skip_annotations();
+            self.skip_separator();
+
+            values.push(EnumValue {
+                name: value_name,
+                value,
+            });
+        }
+
+        self.expect(Token::RightBrace)?;
+        self.skip_annotations();
+        Ok(Enum { name, values })
+    }
+
+    fn parse_struct(&mut self) -> ProviderResult<Struct> {
+        self.advance();
+        let name = self.expect_identifier()?;
+        // `xsd_all` is a legacy modifier
+        if self.is_keyword("xsd_all") {
+            self.advance();
+        }
+        self.expect(Token::LeftBrace)?;
+
+        let mut fields = Vec::new();
+        while self.current() != &Token::RightBrace && self.current() != &Token::Eof {
+            fields.push(self.parse_field()?);
+        }
+
+        self.expect(Token::RightBrace)?;
+        self.skip_annotations();
+        Ok(Struct { name, fields })
+    }
+
+    /// Parse `[id:] [required|optional] type name [= default] [annotations] [,;]`
+    fn parse_field(&mut self) -> ProviderResult<Field> {
+        let id = match (self.current().clone(), self.peek(1)) {
+            (Token::Number(number), Token::Colon) => {
+                self.advance();
+                self.advance();
+                Some(parse_int(&number)? as i32)
+            }
+            _ => None,
+        };
+
+        let requiredness = if self.is_keyword("required") {
+            self.advance();
+            Requiredness::Required
+        } else if self.is_keyword("optional") {
+            self.advance();
+            Requiredness::Optional
+        } else {
+            Requiredness::Default
+        };
+
+        let field_type = self.parse_type()?;
+        let name = self.expect_identifier()?;
+
+        if self.current() == &Token::Equals {
+            self.advance();
+            self.skip_const_value();
+        }
+        self.skip_annotations();
+        self.skip_separator();
+
+        Ok(Field {
+            id,
+            name,
+            field_type,
+            requiredness,
+        })
+    }
+
+    fn parse_type(&mut self) -> ProviderResult<FieldType> {
+        let name = self.expect_identifier()?;
+
+        let field_type = match name.as_str() {
+            "list" | "set" => {
+                self.expect(Token::LeftAngle)?;
+                let element = self.parse_type()?;
+                self.expect(Token::RightAngle)?;
+                if name == "list" {
+                    FieldType::List(Box::new(element))
+                } else {
+                    FieldType::Set(Box::new(element))
+                }
+            }
+            "map" => {
+                self.expect(Token::LeftAngle)?;
+                let key = self.parse_type()?;
+                self.expect(Token::Comma)?;
+                let value = self.parse_type()?;
+                self.expect(Token::RightAngle)?;
+                FieldType::Map(Box::new(key), Box::new(value))
+            }
+            _ => FieldType::from_str(&name),
+        };
+
+        self.skip_annotations();
+        Ok(field_type)
+    }
+
+    fn parse_service(&mut self) -> ProviderResult<Service> {
+        self.advance();
+        let name = self.expect_identifier()?;
+
+        let extends = if self.is_keyword("extends") {
+            self.advance();
+            Some(self.expect_identifier()?)
+        } else {
+            None
+        };
+
+        self.expect(Token::LeftBrace)?;
+
+        let mut functions = Vec::new();
+        while self.current() != &Token::RightBrace && self.current() != &Token::Eof {
+            functions.push(self.parse_function()?);
+        }
+
+        self.expect(Token::RightBrace)?;
+        self.skip_annotations();
+        Ok(Service {
+            name,
+            extends,
+            functions,
+        })
+    }
+
+    fn parse_function(&mut self) -> ProviderResult<Function> {
+        let oneway = self.is_keyword("oneway");
+        if oneway {
+            self.advance();
+        }
+
+        let return_type = if self.is_keyword("void") {
+            self.advance();
+            None
+        } else {
+            Some(self.parse_type()?)
+        };
+
+        let name = self.expect_identifier()?;
+        let params = self.parse_field_list()?;
+
+        let throws = if self.is_keyword("throws") {
+            self.advance();
+            self.parse_field_list()?
+        } else {
+            Vec::new()
+        };
+
+        self.skip_annotations();
+        self.skip_separator();
+
+        Ok(Function {
+            name,
+            return_type,
+            params,
+            throws,
+            oneway,
+        })
+    }
+
+    fn parse_field_list(&mut self) -> ProviderResult<Vec<Field>> {
+        self.expect(Token::LeftParen)?;
+        let mut fields = Vec::new();
+        while self.current() != &Token::RightParen && self.current() != &Token::Eof {
+            fields.push(self.parse_field()?);
+        }
+        self.expect(Token::RightParen)?;
+        Ok(fields)
+    }
+}
+
+fn parse_int(number: &str) -> ProviderResult<i64> {
+    let parsed = match number.strip_prefix("0x").or_else(|| number.strip_prefix("0X")) {
+        Some(hex) => i64::from_str_radix(hex, 16),
+        None => number.trim_start_matches('+').parse(),
+    };
+    parsed.map_err(|_| ProviderError::ParseError(format!("Invalid integer: {}", number)))
+}
+
+/// Tokenize a Thrift file
+fn tokenize(content: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = content.chars().peekable();
+
+    while let Some(&ch) = chars.peek() {
+        match ch {
+            ' ' | '\t' | '\n' | '\r' => {
+                chars.next();
+            }
+            '#' => {
+                // Shell-style line comment
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    // Line comment
+                    for c in chars.by_ref() {
+                        if c == '\n' {
+                            break;
+                        }
+                    }
+                } else if chars.peek() == Some(&'*') {
+                    // Block comment
+                    chars.next();
+                    while let Some(c) = chars.next() {
+                        if c == '*' && chars.peek() == Some(&'/') {
+                            chars.next();
+                            break;
+                        }
+                    }
+                }
+            }
+            '{' | '}' | '(' | ')' | '<' | '>' | '[' | ']' | ';' | ':' | '=' | ',' => {
+                tokens.push(match ch {
+                    '{' => Token::LeftBrace,
+                    '}' => Token::RightBrace,
+                    '(' => Token::LeftParen,
+                    ')' => Token::RightParen,
+                    '<' => Token::LeftAngle,
+                    '>' => Token::RightAngle,
+                    '[' => Token::LeftBracket,
+                    ']' => Token::RightBracket,
+                    ';' => Token::Semicolon,
+                    ':' => Token::Colon,
+                    '=' => Token::Equals,
+                    _ => Token::Comma,
+                });
+                chars.next();
+            }
+            '"' | '\'' => {
+                let quote = ch;
+                chars.next();
+                let mut string = String::new();
+                while let Some(c) = chars.next() {
+                    if c == quote {
+                        break;
+                    }
+                    if c == '\\' {
+                        if let Some(next) = chars.next() {
+                            string.push(next);
+                        }
+                    } else {
+                        string.push(c);
+                    }
+                }
+                tokens.push(Token::StringLiteral(string));
+            }
+            '0'..='9' | '-' | '+' => {
+                let mut number = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '.') {
+                        number.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Number(number));
+            }
+            'a'..='z' | 'A'..='Z' | '_' => {
+                // Identifiers may be qualified with an include name
+                let mut ident = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_alphanumeric() || c == '_' || c == '.' {
+                        ident.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Identifier(ident));
+            }
+            _ => {
+                chars.next();
+            }
+        }
+    }
+
+    tokens.push(Token::Eof);
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_struct() {
+        let thrift = r#"
+            namespace rs tutorial
+            include "shared.thrift"
+
+            struct Work {
+              1: i32 num1 = 0,
+              2: required i32 num2,
+              3: Operation op,
+              4: optional string comment (go.tag = "json:\"comment\""),
+            }
+        "#;
+
+        let file = parse_thrift(thrift).unwrap();
+        assert_eq!(file.namespaces, vec![("rs".to_string(), "tutorial".to_string())]);
+        assert_eq!(file.includes, vec!["shared.thrift"]);
+
+        let work = &file.structs[0];
+        assert_eq!(work.name, "Work");
+        assert_eq!(work.fields.len(), 4);
+        assert_eq!(work.fields[1].id, Some(2));
+        assert_eq!(work.fields[1].requiredness, Requiredness::Required);
+        assert_eq!(work.fields[2].field_type, FieldType::Named("Operation".to_string()));
+        assert_eq!(work.fields[3].requiredness, Requiredness::Optional);
+    }
+
+    #[test]
+    fn test_parse_enum_typedef_and_const() {
+        let thrift = r#"
+            typedef i32 MyInteger
+            const map<string, string> MAPCONSTANT = {'hello': 'world', 'goodnight': 'moon'}
+            const i32 INT32CONSTANT = 9853
+
+            enum Operation {
+              ADD = 1,
+              SUBTRACT = 0x2;
+              MULTIPLY
+            }
+        "#;
+
+        let file = parse_thrift(thrift).unwrap();
+        assert_eq!(file.typedefs[0].name, "MyInteger");
+        assert_eq!(file.typedefs[0].target, FieldType::I32);
+        assert_eq!(file.constants, vec!["MAPCONSTANT", "INT32CONSTANT"]);
+
+        let values: Vec<(&str, Option<i64>)> = file.enums[0]
+            .values
+            .iter()
+            .map(|v| (v.name.as_str(), v.value))
+            .collect();
+        assert_eq!(values, [("ADD", Some(1)), ("SUBTRACT", Some(2)), ("MULTIPLY", None)]);
+    }
+
+    #[test]
+    fn test_parse_service() {
+        let thrift = r#"
+            service Calculator extends shared.SharedService {
+               void ping(),
+               i32 add(1:i32 num1, 2:i32 num2),
+               i32 calculate(1:i32 logid, 2:Work w) throws (1:InvalidOperation ouch),
+               oneway void zip()
+            }
+        "#;
+
+        let file = parse_thrift(thrift).unwrap();
+        let service = &file.services[0];
+        assert_eq!(service.extends.as_deref(), Some("shared.SharedService"));
+        assert_eq!(service.functions.len(), 4);
+        assert!(service.functions[0].return_type.is_none());
+        assert_eq!(service.functions[1].params.len(), 2);
+        assert_eq!(service.functions[2].throws[0].name, "ouch");
+        assert!(service.functions[3].oneway);
+    }
+
+    #[test]
+    fn test_parse_containers() {
+        let thrift = "struct Bag { 1: map<string, list<set<i64>>> nested }";
+        let file = parse_thrift(thrift).unwrap();
+        assert_eq!(
+            file.structs[0].fields[0].field_type,
+            FieldType::Map(
+                Box::new(FieldType::String),
+                Box::new(FieldType::List(Box::new(FieldType::Set(Box::new(FieldType::I64)))))
+            )
+        );
+    }
+
+    #[test]
+    fn test_unexpected_top_level() {
+        assert!(parse_thrift("message Foo {}").is_err());
+    }
+}
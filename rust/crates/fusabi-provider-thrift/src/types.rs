@@ -0,0 +1,184 @@
+//! Thrift IDL AST type definitions
+
+use std::collections::HashMap;
+
+/// Represents a complete .thrift file
+#[derive(Debug, Clone, Default)]
+pub struct ThriftFile {
+    /// Namespace declarations as (scope, name), e.g. ("rs", "tutorial")
+    pub namespaces: Vec<(String, String)>,
+    /// Included files
+    pub includes: Vec<String>,
+    /// Type aliases
+    pub typedefs: Vec<Typedef>,
+    /// Constant names (values are not modelled)
+    pub constants: Vec<String>,
+    /// Enum definitions
+    pub enums: Vec<Enum>,
+    /// Struct definitions
+    pub structs: Vec<Struct>,
+    /// Union definitions
+    pub unions: Vec<Struct>,
+    /// Exception definitions
+    pub exceptions: Vec<Struct>,
+    /// Service definitions
+    pub services: Vec<Service>,
+}
+
+/// Thrift typedef
+#[derive(Debug, Clone)]
+pub struct Typedef {
+    /// Alias name
+    pub name: String,
+    /// Aliased type
+    pub target: FieldType,
+}
+
+/// Thrift struct, union or exception
+#[derive(Debug, Clone)]
+pub struct Struct {
+    /// Struct name
+    pub name: String,
+    /// Struct fields
+    pub fields: Vec<Field>,
+}
+
+/// Thrift field definition
+#[derive(Debug, Clone)]
+pub struct Field {
+    /// Field id, if declared
+    pub id: Option<i32>,
+    /// Field name
+    pub name: String,
+    /// Field type
+    pub field_type: FieldType,
+    /// Field requiredness
+    pub requiredness: Requiredness,
+}
+
+/// Field requiredness
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Requiredness {
+    /// Explicitly `required`
+    Required,
+    /// Explicitly `optional`
+    Optional,
+    /// No qualifier ("opt-in, req-out")
+    Default,
+}
+
+/// Thrift field type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldType {
+    // Base types
+    Bool,
+    Byte,
+    I8,
+    I16,
+    I32,
+    I64,
+    Double,
+    String,
+    Binary,
+    Uuid,
+
+    // Container types
+    List(Box<FieldType>),
+    Set(Box<FieldType>),
+    Map(Box<FieldType>, Box<FieldType>),
+
+    // Reference to a struct, union, exception, enum or typedef,
+    // possibly qualified with an include name (`shared.SharedStruct`)
+    Named(String),
+}
+
+/// Thrift enum definition
+#[derive(Debug, Clone)]
+pub struct Enum {
+    /// Enum name
+    pub name: String,
+    /// Enum values
+    pub values: Vec<EnumValue>,
+}
+
+/// Thrift enum value
+#[derive(Debug, Clone)]
+pub struct EnumValue {
+    /// Value name
+    pub name: String,
+    /// Explicit value, if declared
+    pub value: Option<i64>,
+}
+
+/// Thrift service definition
+#[derive(Debug, Clone)]
+pub struct Service {
+    /// Service name
+    pub name: String,
+    /// Parent service, if any
+    pub extends: Option<String>,
+    /// Service functions
+    pub functions: Vec<Function>,
+}
+
+/// Thrift service function
+#[derive(Debug, Clone)]
+pub struct Function {
+    /// Function name
+    pub name: String,
+    /// Return type (`None` for `void`)
+    pub return_type: Option<FieldType>,
+    /// Arguments
+    pub params: Vec<Field>,
+    /// Declared exceptions
+    pub throws: Vec<Field>,
+    /// Whether the function is `oneway`
+    pub oneway: bool,
+}
+
+impl ThriftFile {
+    /// Create a new empty ThriftFile
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Namespace to use for generated modules
+    ///
+    /// Prefers the `fusabi` scope, then the `*` scope.
+    pub fn namespace(&self) -> Option<&str> {
+        ["fusabi", "*"].iter().find_map(|scope| {
+            self.namespaces
+                .iter()
+                .find(|(s, _)| s == scope)
+                .map(|(_, name)| name.as_str())
+        })
+    }
+
+    /// Build a map of typedef name to aliased type
+    pub fn build_typedef_map(&self) -> HashMap<String, &FieldType> {
+        self.typedefs
+            .iter()
+            .map(|t| (t.name.clone(), &t.target))
+            .collect()
+    }
+}
+
+impl FieldType {
+    /// Parse a base type name, or treat the name as a reference
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "bool" => FieldType::Bool,
+            "byte" => FieldType::Byte,
+            "i8" => FieldType::I8,
+            "i16" => FieldType::I16,
+            "i32" => FieldType::I32,
+            "i64" => FieldType::I64,
+            "double" => FieldType::Double,
+            "string" => FieldType::String,
+            "binary" => FieldType::Binary,
+            "uuid" => FieldType::Uuid,
+            _ => FieldType::Named(s.to_string()),
+        }
+    }
+}
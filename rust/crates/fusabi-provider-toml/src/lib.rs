@@ -1,6 +1,7 @@
 //! TOML Configuration Type Provider
 //!
 //! Generates Fusabi types from TOML configuration files by inferring types from values.
+//! The values themselves become field defaults through [`DefaultedProvider`].
 //!
 //! # Example
 //!
@@ -18,6 +19,10 @@ mod types;
 
 pub use types::{TomlType, TomlValue};
 
+use fusabi_provider_common::{
+    inline_name, item_name, read_source, Capabilities, DefaultValue, DefaultedProvider,
+    DescribedProvider, FieldDefaults, InlineRecords, OutputFeature, SourceValidator, TypeShape,
+};
 use fusabi_type_providers::{
     TypeProvider, ProviderParams, Schema,
     GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
@@ -48,19 +53,29 @@ impl TomlProvider {
         &self,
         schema: &types::TomlSchema,
         namespace: &str,
-    ) -> ProviderResult<GeneratedTypes> {
+    ) -> ProviderResult<(GeneratedTypes, FieldDefaults)> {
         let mut result = GeneratedTypes::new();
+        let mut defaults = FieldDefaults::new();
 
         // Generate the root type from the TOML root table
         if schema.root.is_table() {
-            // Collect all nested table types first
-            let mut nested_types = Vec::new();
-            self.collect_nested_types(&schema.root, namespace, &mut nested_types)?;
-
-            // Generate the root record
-            let fields = self.table_to_fields(&schema.root, namespace)?;
+            let module_path = vec![namespace.to_string()];
+            let root_name = self.generator.naming.apply(namespace);
+
+            // Nested tables, and tables in arrays, are hoisted to records
+            // named after their position
+            let mut nested = Nested {
+                inline: InlineRecords::new(),
+                defaults: &mut defaults,
+                path: &module_path,
+            };
+            nested.inline.reserve(root_name.clone());
+            let fields = self.table_to_fields(&schema.root, &root_name, &mut nested, true);
+            let nested_types = nested.inline.into_definitions();
+
+            record_defaults(&mut defaults, &[], &root_name, &schema.root);
             let root_record = TypeDefinition::Record(RecordDef {
-                name: self.generator.naming.apply(namespace),
+                name: root_name,
                 fields,
             });
 
@@ -68,131 +83,85 @@ impl TomlProvider {
 
             // Add nested types to a module if any were found
             if !nested_types.is_empty() {
-                let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+                let mut module = GeneratedModule::new(module_path);
                 module.types.extend(nested_types);
                 result.modules.push(module);
             }
         }
 
-        Ok(result)
+        Ok((result, defaults))
     }
 
-    /// Collect nested table types that should become separate type definitions
-    fn collect_nested_types(
-        &self,
-        value: &types::TomlValue,
-        parent_name: &str,
-        types: &mut Vec<TypeDefinition>,
-    ) -> ProviderResult<()> {
-        if value.is_table() {
-            for (field_name, field_value) in &value.fields {
-                if field_value.is_table() {
-                    // Create a type for this nested table
-                    let type_name = format!("{}{}", parent_name, self.generator.naming.apply(field_name));
-                    let fields = self.table_to_fields(field_value, &type_name)?;
-
-                    types.push(TypeDefinition::Record(RecordDef {
-                        name: type_name.clone(),
-                        fields,
-                    }));
-
-                    // Recursively collect deeper nested types
-                    self.collect_nested_types(field_value, &type_name, types)?;
-                } else if let types::TomlType::Array(elem_type) = &field_value.value_type {
-                    // Check if array contains tables
-                    if let types::TomlType::Table = **elem_type {
-                        // Get the first array element to infer structure
-                        if let toml::Value::Array(arr) = &field_value.original {
-                            if let Some(toml::Value::Table(_)) = arr.first() {
-                                // Create a type for the array element
-                                let type_name = format!("{}{}Item", parent_name, self.generator.naming.apply(field_name));
-
-                                // Use the first element as template
-                                let template_value = types::TomlValue::from_value(arr[0].clone());
-                                let fields = self.table_to_fields(&template_value, &type_name)?;
-
-                                types.push(TypeDefinition::Record(RecordDef {
-                                    name: type_name,
-                                    fields,
-                                }));
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        Ok(())
-    }
-
-    /// Convert a TOML table to record fields
+    /// Convert a TOML table to the fields of record `record`
+    ///
+    /// With `with_defaults`, the values of nested tables are recorded as
+    /// defaults; tables in arrays have none.
     fn table_to_fields(
         &self,
-        value: &types::TomlValue,
-        parent_name: &str,
-    ) -> ProviderResult<Vec<(String, TypeExpr)>> {
-        let mut fields = Vec::new();
-
-        for (field_name, field_value) in &value.fields {
-            let type_expr = self.value_to_type_expr(field_value, field_name, parent_name)?;
-            fields.push((field_name.clone(), type_expr));
-        }
-
-        Ok(fields)
+        table: &types::TomlValue,
+        record: &str,
+        nested: &mut Nested,
+        with_defaults: bool,
+    ) -> Vec<(String, TypeExpr)> {
+        table
+            .fields
+            .iter()
+            .map(|(field_name, field_value)| {
+                let name = inline_name(record, field_name);
+                let shape = self.value_shape(field_value, &name, nested, with_defaults);
+                (field_name.clone(), shape.into())
+            })
+            .collect()
     }
 
-    /// Convert a TOML value to a TypeExpr
-    fn value_to_type_expr(
+    /// Convert a TOML value to a TypeShape; a table becomes a record named `name`
+    fn value_shape(
         &self,
         value: &types::TomlValue,
-        field_name: &str,
-        parent_name: &str,
-    ) -> ProviderResult<TypeExpr> {
+        name: &str,
+        nested: &mut Nested,
+        with_defaults: bool,
+    ) -> TypeShape {
         match &value.value_type {
-            types::TomlType::String => Ok(TypeExpr::Named("string".to_string())),
-            types::TomlType::Integer => Ok(TypeExpr::Named("int".to_string())),
-            types::TomlType::Float => Ok(TypeExpr::Named("float".to_string())),
-            types::TomlType::Boolean => Ok(TypeExpr::Named("bool".to_string())),
-            types::TomlType::Datetime => Ok(TypeExpr::Named("string".to_string())), // TOML datetime as string
-            types::TomlType::Array(elem_type) => {
-                let elem_type_expr = self.array_elem_to_type_expr(elem_type, field_name, parent_name)?;
-                Ok(TypeExpr::Named(format!("{} list", elem_type_expr)))
-            }
+            types::TomlType::String => TypeShape::named("string"),
+            types::TomlType::Integer => TypeShape::named("int"),
+            types::TomlType::Float => TypeShape::named("float"),
+            types::TomlType::Boolean => TypeShape::named("bool"),
+            types::TomlType::Datetime => TypeShape::named("string"), // TOML datetime as string
             types::TomlType::Table => {
-                // Reference to a nested type
-                let type_name = format!("{}{}", parent_name, self.generator.naming.apply(field_name));
-                Ok(TypeExpr::Named(type_name))
-            }
-        }
-    }
-
-    /// Convert array element type to TypeExpr
-    fn array_elem_to_type_expr(
-        &self,
-        elem_type: &types::TomlType,
-        field_name: &str,
-        parent_name: &str,
-    ) -> ProviderResult<TypeExpr> {
-        match elem_type {
-            types::TomlType::String => Ok(TypeExpr::Named("string".to_string())),
-            types::TomlType::Integer => Ok(TypeExpr::Named("int".to_string())),
-            types::TomlType::Float => Ok(TypeExpr::Named("float".to_string())),
-            types::TomlType::Boolean => Ok(TypeExpr::Named("bool".to_string())),
-            types::TomlType::Datetime => Ok(TypeExpr::Named("string".to_string())),
-            types::TomlType::Table => {
-                // Array of tables - reference the item type
-                let type_name = format!("{}{}Item", parent_name, self.generator.naming.apply(field_name));
-                Ok(TypeExpr::Named(type_name))
+                let fields = self.table_to_fields(value, name, nested, with_defaults);
+                let name = nested.inline.hoist(name, fields);
+                if with_defaults {
+                    record_defaults(nested.defaults, nested.path, &name, value);
+                }
+                TypeShape::named(name)
             }
-            types::TomlType::Array(inner) => {
-                // Nested array
-                let inner_expr = self.array_elem_to_type_expr(inner, field_name, parent_name)?;
-                Ok(TypeExpr::Named(format!("{} list", inner_expr)))
+            types::TomlType::Array(_) => {
+                // The first element stands for all of them
+                let element = match &value.original {
+                    toml::Value::Array(items) => items.first(),
+                    _ => None,
+                };
+                let element_shape = match element {
+                    Some(element) => {
+                        let element = types::TomlValue::from_value(element.clone());
+                        self.value_shape(&element, &item_name(name), nested, false)
+                    }
+                    None => TypeShape::named("string"),
+                };
+                element_shape.list()
             }
         }
     }
 }
 
+/// Records hoisted from nested tables, and where their defaults go
+struct Nested<'a> {
+    inline: InlineRecords,
+    defaults: &'a mut FieldDefaults,
+    path: &'a [String],
+}
+
 impl Default for TomlProvider {
     fn default() -> Self {
         Self::new()
@@ -204,19 +173,13 @@ impl TypeProvider for TomlProvider {
         "TomlProvider"
     }
 
-    fn resolve_schema(&self, source: &str, _params: &ProviderParams) -> ProviderResult<Schema> {
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
         // Source can be inline TOML or file path
         let toml_str = if source.contains('=') || source.contains('[') {
             // Looks like inline TOML
             source.to_string()
-        } else if source.starts_with("file://") {
-            let path = source.strip_prefix("file://").unwrap();
-            std::fs::read_to_string(path)
-                .map_err(|e| ProviderError::IoError(e.to_string()))?
         } else {
-            // Treat as file path without prefix
-            std::fs::read_to_string(source)
-                .map_err(|e| ProviderError::IoError(e.to_string()))?
+            read_source(source, params)?
         };
 
         // Validate that it parses as TOML
@@ -228,6 +191,16 @@ impl TypeProvider for TomlProvider {
     }
 
     fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        self.generate_with_defaults(schema, namespace).map(|(types, _)| types)
+    }
+}
+
+impl DefaultedProvider for TomlProvider {
+    fn generate_with_defaults(
+        &self,
+        schema: &Schema,
+        namespace: &str,
+    ) -> ProviderResult<(GeneratedTypes, FieldDefaults)> {
         let toml_str = match schema {
             Schema::Custom(s) => s,
             _ => return Err(ProviderError::ParseError("Expected TOML Schema".to_string())),
@@ -238,9 +211,49 @@ impl TypeProvider for TomlProvider {
     }
 }
 
+impl SourceValidator for TomlProvider {}
+
+impl DescribedProvider for TomlProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .feature(OutputFeature::Defaults)
+    }
+}
+
+/// Record the values of a table as the defaults of its record's fields
+fn record_defaults(
+    defaults: &mut FieldDefaults,
+    path: &[String],
+    type_name: &str,
+    table: &types::TomlValue,
+) {
+    for (field_name, field_value) in &table.fields {
+        if let Some(value) = toml_default(&field_value.original) {
+            defaults.set(path, type_name, field_name, value);
+        }
+    }
+}
+
+/// Default value for a TOML value; tables have none
+fn toml_default(value: &toml::Value) -> Option<DefaultValue> {
+    Some(match value {
+        toml::Value::String(s) => DefaultValue::String(s.clone()),
+        toml::Value::Integer(i) => DefaultValue::Int(*i),
+        toml::Value::Float(f) => DefaultValue::Float(*f),
+        toml::Value::Boolean(b) => DefaultValue::Bool(*b),
+        toml::Value::Datetime(d) => DefaultValue::String(d.to_string()),
+        toml::Value::Array(items) => {
+            DefaultValue::List(items.iter().map(toml_default).collect::<Option<_>>()?)
+        }
+        toml::Value::Table(_) => return None,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fusabi_provider_common::emit::golden;
 
     #[test]
     fn test_generate_simple_config() {
@@ -283,6 +296,47 @@ mod tests {
         assert!(!types.root_types.is_empty());
         // Should have nested types for database and server
         assert!(!types.modules.is_empty());
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/toml.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_values_become_defaults() {
+        let provider = TomlProvider::new();
+        let toml = r#"
+            name = "myapp"
+            tags = ["web", "api"]
+
+            [[servers]]
+            host = "localhost"
+
+            [database]
+            port = 5432
+
+            [database.pool]
+            size = 10
+        "#;
+
+        let schema = provider.resolve_schema(toml, &ProviderParams::default()).unwrap();
+        let (types, defaults) = provider.generate_with_defaults(&schema, "Config").unwrap();
+        let module = vec!["Config".to_string()];
+
+        assert_eq!(
+            defaults.get(&[], "Config", "name"),
+            Some(&DefaultValue::String("myapp".to_string()))
+        );
+        assert_eq!(
+            defaults.get(&[], "Config", "tags").and_then(DefaultValue::to_fusabi).as_deref(),
+            Some(r#"["web"; "api"]"#)
+        );
+        assert_eq!(defaults.get(&[], "Config", "database"), None);
+        assert_eq!(defaults.get(&module, "ConfigDatabase", "port"), Some(&DefaultValue::Int(5432)));
+        assert_eq!(defaults.get(&module, "ConfigDatabasePool", "size"), Some(&DefaultValue::Int(10)));
+        assert_eq!(defaults.get(&module, "ConfigServersItem", "host"), None);
+        assert_eq!(types.modules[0].path, module);
     }
 
     #[test]
@@ -323,6 +377,46 @@ mod tests {
         assert!(!types.modules.is_empty());
     }
 
+    #[test]
+    fn test_tables_in_arrays() {
+        let provider = TomlProvider::new();
+        let toml = r#"
+            [[servers]]
+            host = "localhost"
+
+            [servers.tls]
+            cert = "server.pem"
+
+            [tls]
+            enabled = true
+        "#;
+
+        let schema = provider.resolve_schema(toml, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "Config").unwrap();
+
+        let records: Vec<(&str, Vec<String>)> = types.modules[0]
+            .types
+            .iter()
+            .map(|t| match t {
+                TypeDefinition::Record(r) => {
+                    (r.name.as_str(), r.fields.iter().map(|(n, t)| format!("{}: {}", n, t)).collect())
+                }
+                _ => panic!("Expected Record type"),
+            })
+            .collect();
+        assert_eq!(
+            records,
+            vec![
+                ("ConfigServersItemTls", vec!["cert: string".to_string()]),
+                (
+                    "ConfigServersItem",
+                    vec!["host: string".to_string(), "tls: ConfigServersItemTls".to_string()]
+                ),
+                ("ConfigTls", vec!["enabled: bool".to_string()]),
+            ]
+        );
+    }
+
     #[test]
     fn test_datetime_type() {
         let provider = TomlProvider::new();
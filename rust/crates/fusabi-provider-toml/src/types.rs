@@ -1,6 +1,6 @@
 //! TOML type definitions
 
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use toml::Value;
 
 /// Inferred TOML type
@@ -28,7 +28,7 @@ pub struct TomlValue {
     /// The inferred type
     pub value_type: TomlType,
     /// For tables: field name -> field value
-    pub fields: HashMap<String, TomlValue>,
+    pub fields: BTreeMap<String, TomlValue>,
     /// For arrays: the element type and whether all elements have same type
     pub array_element_type: Option<Box<TomlType>>,
     /// Original TOML value for reference
@@ -39,7 +39,7 @@ impl TomlValue {
     /// Create a new TomlValue from a TOML Value
     pub fn from_value(value: Value) -> Self {
         let value_type = Self::infer_type(&value);
-        let mut fields = HashMap::new();
+        let mut fields = BTreeMap::new();
         let mut array_element_type = None;
 
         match &value {
@@ -48,12 +48,10 @@ impl TomlValue {
                     fields.insert(key.clone(), TomlValue::from_value(val.clone()));
                 }
             }
-            Value::Array(arr) => {
-                if !arr.is_empty() {
-                    // Infer the common type from array elements
-                    let elem_type = Self::infer_array_type(arr);
-                    array_element_type = Some(Box::new(elem_type));
-                }
+            Value::Array(arr) if !arr.is_empty() => {
+                // Infer the common type from array elements
+                let elem_type = Self::infer_array_type(arr);
+                array_element_type = Some(Box::new(elem_type));
             }
             _ => {}
         }
@@ -0,0 +1,256 @@
+//! JSON messages exchanged with a plugin
+//!
+//! Type expressions travel in their rendered form (`"string option"`,
+//! `"Map<string, int>"`) and are normalized through
+//! [`TypeShape`] on the way in, so plugins need no knowledge of the
+//! host's type representation.
+
+use fusabi_provider_common::{LockedSchema, TypeShape};
+use fusabi_type_providers::{
+    DuDef, GeneratedModule, GeneratedTypes, ProviderError, ProviderResult, RecordDef, Schema,
+    TypeDefinition, TypeExpr, VariantDef,
+};
+use serde::{Deserialize, Serialize};
+
+/// Version of the plugin ABI implemented by this host
+pub const ABI_VERSION: i32 = 1;
+
+/// Input of `fusabi_generate`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenerateRequest {
+    /// ABI version of the host
+    pub abi_version: i32,
+    /// Schema resolved by the host, tagged by `kind`
+    pub schema: LockedSchema,
+    /// Namespace to generate into
+    pub namespace: String,
+}
+
+/// Output of `fusabi_generate`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GenerateResponse {
+    /// Generated types
+    Types(WireTypes),
+    /// Message describing why generation failed
+    Error(String),
+}
+
+/// Generated types as sent by a plugin
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WireTypes {
+    /// Types outside any module
+    #[serde(default)]
+    pub root_types: Vec<WireType>,
+    /// Modules and their types
+    #[serde(default)]
+    pub modules: Vec<WireModule>,
+}
+
+/// Module of generated types
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WireModule {
+    /// Module path, outermost first
+    pub path: Vec<String>,
+    /// Types declared in the module
+    #[serde(default)]
+    pub types: Vec<WireType>,
+}
+
+/// Record or union definition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WireType {
+    /// Record with `[name, type]` fields
+    Record {
+        /// Type name
+        name: String,
+        /// Field names and rendered types
+        #[serde(default)]
+        fields: Vec<(String, String)>,
+    },
+    /// Discriminated union
+    Du {
+        /// Type name
+        name: String,
+        /// Union cases
+        variants: Vec<WireVariant>,
+    },
+}
+
+/// Union case with its rendered payload types
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WireVariant {
+    /// Case name
+    pub name: String,
+    /// Payload types; empty for a case without data
+    #[serde(default)]
+    pub fields: Vec<String>,
+}
+
+impl GenerateRequest {
+    /// Request generation of `schema` into `namespace`
+    pub fn new(schema: &Schema, namespace: &str) -> Self {
+        Self {
+            abi_version: ABI_VERSION,
+            schema: LockedSchema::normalize(schema),
+            namespace: namespace.to_string(),
+        }
+    }
+}
+
+impl GenerateResponse {
+    /// Parse a plugin's response
+    pub fn from_json(json: &[u8]) -> ProviderResult<Self> {
+        serde_json::from_slice(json)
+            .map_err(|e| ProviderError::ParseError(format!("Invalid plugin response: {}", e)))
+    }
+
+    /// Convert into generated types, surfacing a plugin error as a parse error
+    pub fn into_types(self) -> ProviderResult<GeneratedTypes> {
+        match self {
+            GenerateResponse::Types(types) => Ok(types.into()),
+            GenerateResponse::Error(message) => Err(ProviderError::ParseError(message)),
+        }
+    }
+}
+
+impl From<&GeneratedTypes> for WireTypes {
+    fn from(types: &GeneratedTypes) -> Self {
+        Self {
+            root_types: types.root_types.iter().map(WireType::from).collect(),
+            modules: types
+                .modules
+                .iter()
+                .map(|module| WireModule {
+                    path: module.path.clone(),
+                    types: module.types.iter().map(WireType::from).collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<WireTypes> for GeneratedTypes {
+    fn from(wire: WireTypes) -> Self {
+        let mut types = GeneratedTypes::new();
+        types.root_types = wire.root_types.into_iter().map(TypeDefinition::from).collect();
+        for wire_module in wire.modules {
+            let mut module = GeneratedModule::new(wire_module.path);
+            module.types = wire_module.types.into_iter().map(TypeDefinition::from).collect();
+            types.modules.push(module);
+        }
+        types
+    }
+}
+
+impl From<&TypeDefinition> for WireType {
+    fn from(type_def: &TypeDefinition) -> Self {
+        match type_def {
+            TypeDefinition::Record(record) => WireType::Record {
+                name: record.name.clone(),
+                fields: record
+                    .fields
+                    .iter()
+                    .map(|(name, ty)| (name.clone(), ty.to_string()))
+                    .collect(),
+            },
+            TypeDefinition::Du(du) => WireType::Du {
+                name: du.name.clone(),
+                variants: du
+                    .variants
+                    .iter()
+                    .map(|variant| WireVariant {
+                        name: variant.name.clone(),
+                        fields: variant.fields.iter().map(|ty| ty.to_string()).collect(),
+                    })
+                    .collect(),
+            },
+        }
+    }
+}
+
+impl From<WireType> for TypeDefinition {
+    fn from(wire: WireType) -> Self {
+        match wire {
+            WireType::Record { name, fields } => TypeDefinition::Record(RecordDef {
+                name,
+                fields: fields.into_iter().map(|(field, ty)| (field, type_expr(&ty))).collect(),
+            }),
+            WireType::Du { name, variants } => TypeDefinition::Du(DuDef {
+                name,
+                variants: variants
+                    .into_iter()
+                    .map(|variant| {
+                        if variant.fields.is_empty() {
+                            VariantDef::new_simple(variant.name)
+                        } else {
+                            VariantDef::new(
+                                variant.name,
+                                variant.fields.iter().map(String::as_str).map(type_expr).collect(),
+                            )
+                        }
+                    })
+                    .collect(),
+            }),
+        }
+    }
+}
+
+fn type_expr(rendered: &str) -> TypeExpr {
+    TypeShape::parse(rendered).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_response_round_trip() {
+        let json = br#"{
+            "types": {
+                "modules": [{
+                    "path": ["Api"],
+                    "types": [
+                        {"kind": "record", "name": "User", "fields": [["id", "int"], ["tags", "list<string>"]]},
+                        {"kind": "du", "name": "Shape", "variants": [
+                            {"name": "Empty"},
+                            {"name": "Circle", "fields": ["float"]}
+                        ]}
+                    ]
+                }]
+            }
+        }"#;
+
+        let types = GenerateResponse::from_json(json).unwrap().into_types().unwrap();
+        assert!(types.root_types.is_empty());
+        assert_eq!(types.modules[0].path, vec!["Api"]);
+
+        let wire = WireTypes::from(&types);
+        let WireType::Record { fields, .. } = &wire.modules[0].types[0] else { panic!() };
+        assert_eq!(fields[1], ("tags".to_string(), "string list".to_string()));
+        let WireType::Du { variants, .. } = &wire.modules[0].types[1] else { panic!() };
+        assert!(variants[0].fields.is_empty());
+        assert_eq!(variants[1].fields, vec!["float"]);
+    }
+
+    #[test]
+    fn test_error_response() {
+        let response = GenerateResponse::from_json(br#"{"error": "unexpected token"}"#).unwrap();
+        assert!(matches!(
+            response.into_types(),
+            Err(ProviderError::ParseError(message)) if message == "unexpected token"
+        ));
+        assert!(GenerateResponse::from_json(b"not json").is_err());
+    }
+
+    #[test]
+    fn test_request_json() {
+        let request = GenerateRequest::new(&Schema::Custom("a\r\nb".to_string()), "Api");
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["abi_version"], 1);
+        assert_eq!(json["schema"]["kind"], "custom");
+        assert_eq!(json["schema"]["content"], "a\nb");
+        assert_eq!(json["namespace"], "Api");
+    }
+}
@@ -0,0 +1,355 @@
+//! WebAssembly plugin host for third-party type providers
+//!
+//! A provider compiled to WebAssembly can be loaded at runtime through
+//! [`WasmProvider`], which implements `TypeProvider` like any built-in
+//! provider. The host reads the source, with the usual timeouts, caching
+//! and credentials, and hands the plugin the schema; the plugin returns the
+//! generated types as JSON. Plugins run sandboxed, without filesystem or
+//! network access; WASI is linked only so that modules built for
+//! `wasm32-wasip1` can write diagnostics to stderr. Each call runs on a
+//! budget of [`PLUGIN_FUEL`] instructions, with linear memory capped at
+//! [`PLUGIN_MAX_MEMORY_BYTES`] and tables at [`PLUGIN_MAX_TABLE_ELEMENTS`]
+//! entries, so a plugin that loops or grows without bound fails the call
+//! instead of hanging the host.
+//!
+//! # Plugin ABI (version 1)
+//!
+//! A plugin module exports:
+//!
+//! | Export                                   | Meaning                                        |
+//! |------------------------------------------|------------------------------------------------|
+//! | `memory`                                 | Linear memory used to exchange messages        |
+//! | `fusabi_abi_version() -> i32`            | ABI version the plugin implements, `1`         |
+//! | `fusabi_alloc(len: i32) -> i32`          | Allocate `len` bytes for the host to write to  |
+//! | `fusabi_provider_name() -> i64`          | UTF-8 provider name                            |
+//! | `fusabi_generate(ptr: i32, len: i32) -> i64` | Generate types from a [`GenerateRequest`]  |
+//!
+//! Functions returning `i64` return a message packed as `ptr << 32 | len`.
+//! `fusabi_generate` receives a JSON request written to memory from
+//! `fusabi_alloc`:
+//!
+//! ```json
+//! {"abi_version": 1, "schema": {"kind": "custom", "content": "..."}, "namespace": "Api"}
+//! ```
+//!
+//! and answers with either the types or an error:
+//!
+//! ```json
+//! {"types": {"modules": [{"path": ["Api"], "types": [
+//!     {"kind": "record", "name": "User", "fields": [["id", "int"], ["email", "string option"]]},
+//!     {"kind": "du", "name": "Status", "variants": [{"name": "Active"}, {"name": "Banned", "fields": ["string"]}]}
+//! ]}]}}
+//! {"error": "line 3: unexpected token"}
+//! ```
+//!
+//! Every call runs in a fresh instance, so plugins keep no state between
+//! calls and never need to free memory. A module exporting `_initialize`
+//! (a WASI reactor) has it called after instantiation.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_wasm::WasmProvider;
+//! use fusabi_type_providers::{ProviderParams, TypeProvider};
+//!
+//! let provider = WasmProvider::from_file("plugins/in_house.wasm")?;
+//! let schema = provider.resolve_schema("schema.idl", &ProviderParams::default())?;
+//! let types = provider.generate_types(&schema, "InHouse")?;
+//! ```
+
+pub mod abi;
+
+pub use abi::{GenerateRequest, GenerateResponse, WireModule, WireType, WireTypes, WireVariant, ABI_VERSION};
+
+use fusabi_provider_common::source::DEFAULT_MAX_SOURCE_BYTES;
+use fusabi_provider_common::{read_source, Capabilities, DescribedProvider, SourceKind};
+use fusabi_type_providers::{
+    GeneratedTypes, ProviderError, ProviderParams, ProviderResult, Schema, TypeProvider,
+};
+use std::path::Path;
+use wasmtime::{
+    Config, Engine, Instance, Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder, Trap,
+};
+use wasmtime_wasi::preview1::{self, WasiP1Ctx};
+use wasmtime_wasi::WasiCtxBuilder;
+
+/// Instructions a plugin may execute per call, instantiation included
+pub const PLUGIN_FUEL: u64 = 1_000_000_000;
+
+/// Largest linear memory a plugin may grow to
+pub const PLUGIN_MAX_MEMORY_BYTES: usize = 256 * 1024 * 1024;
+
+/// Largest table a plugin may grow to
+pub const PLUGIN_MAX_TABLE_ELEMENTS: usize = 10_000;
+
+/// Type provider implemented by a WebAssembly plugin
+pub struct WasmProvider {
+    name: String,
+    engine: Engine,
+    module: Module,
+}
+
+impl WasmProvider {
+    /// Load a plugin from a `.wasm` file
+    pub fn from_file(path: impl AsRef<Path>) -> ProviderResult<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).map_err(|e| {
+            ProviderError::IoError(format!("Failed to read plugin {}: {}", path.display(), e))
+        })?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Load a plugin from module bytes
+    ///
+    /// Fails when the module does not implement ABI version [`ABI_VERSION`].
+    pub fn from_bytes(bytes: &[u8]) -> ProviderResult<Self> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config).map_err(plugin_error)?;
+        let module = Module::new(&engine, bytes).map_err(plugin_error)?;
+        let mut provider = Self {
+            name: String::new(),
+            engine,
+            module,
+        };
+
+        let mut plugin = provider.instantiate()?;
+        let version = plugin.call_version()?;
+        if version != ABI_VERSION {
+            return Err(ProviderError::InvalidSource(format!(
+                "Plugin implements ABI version {}, host supports {}",
+                version, ABI_VERSION
+            )));
+        }
+        let name = plugin.call_packed("fusabi_provider_name", None)?;
+        provider.name = String::from_utf8(name).map_err(|_| {
+            ProviderError::ParseError("Plugin name is not valid UTF-8".to_string())
+        })?;
+
+        Ok(provider)
+    }
+
+    fn instantiate(&self) -> ProviderResult<Plugin> {
+        let mut linker: Linker<HostState> = Linker::new(&self.engine);
+        preview1::add_to_linker_sync(&mut linker, |state: &mut HostState| &mut state.wasi)
+            .map_err(plugin_error)?;
+
+        let state = HostState {
+            wasi: WasiCtxBuilder::new().inherit_stderr().build_p1(),
+            limits: StoreLimitsBuilder::new()
+                .memory_size(PLUGIN_MAX_MEMORY_BYTES)
+                .table_elements(PLUGIN_MAX_TABLE_ELEMENTS)
+                .build(),
+        };
+        let mut store = Store::new(&self.engine, state);
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(PLUGIN_FUEL).map_err(plugin_error)?;
+
+        let instance = linker.instantiate(&mut store, &self.module).map_err(call_error)?;
+        if let Ok(initialize) = instance.get_typed_func::<(), ()>(&mut store, "_initialize") {
+            initialize.call(&mut store, ()).map_err(call_error)?;
+        }
+        let memory = instance.get_memory(&mut store, "memory").ok_or_else(|| {
+            ProviderError::InvalidSource("Plugin does not export its memory".to_string())
+        })?;
+
+        Ok(Plugin {
+            store,
+            instance,
+            memory,
+        })
+    }
+}
+
+impl std::fmt::Debug for WasmProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WasmProvider").field("name", &self.name).finish_non_exhaustive()
+    }
+}
+
+impl TypeProvider for WasmProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        read_source(source, params).map(Schema::Custom)
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let request = serde_json::to_vec(&GenerateRequest::new(schema, namespace))
+            .map_err(|e| ProviderError::ParseError(format!("Failed to encode request: {}", e)))?;
+        let response = self.instantiate()?.call_packed("fusabi_generate", Some(&request))?;
+        GenerateResponse::from_json(&response)?.into_types()
+    }
+}
+
+impl DescribedProvider for WasmProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .source(SourceKind::File)
+            .source(SourceKind::Remote)
+    }
+}
+
+/// Per-call host state of a plugin
+struct HostState {
+    wasi: WasiP1Ctx,
+    limits: StoreLimits,
+}
+
+/// Instantiated plugin module
+struct Plugin {
+    store: Store<HostState>,
+    instance: Instance,
+    memory: Memory,
+}
+
+impl Plugin {
+    fn call_version(&mut self) -> ProviderResult<i32> {
+        self.instance
+            .get_typed_func::<(), i32>(&mut self.store, "fusabi_abi_version")
+            .and_then(|version| version.call(&mut self.store, ()))
+            .map_err(call_error)
+    }
+
+    /// Call an export returning a packed message, passing `input` if given
+    fn call_packed(&mut self, export: &str, input: Option<&[u8]>) -> ProviderResult<Vec<u8>> {
+        let packed = match input {
+            None => self
+                .instance
+                .get_typed_func::<(), i64>(&mut self.store, export)
+                .and_then(|func| func.call(&mut self.store, ())),
+            Some(input) => {
+                let (ptr, len) = self.write(input)?;
+                self.instance
+                    .get_typed_func::<(i32, i32), i64>(&mut self.store, export)
+                    .and_then(|func| func.call(&mut self.store, (ptr, len)))
+            }
+        }
+        .map_err(call_error)?;
+
+        self.read(packed)
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> ProviderResult<(i32, i32)> {
+        let len = i32::try_from(bytes.len()).map_err(|_| {
+            ProviderError::InvalidSource("Schema is too large for a plugin".to_string())
+        })?;
+        let ptr = self
+            .instance
+            .get_typed_func::<i32, i32>(&mut self.store, "fusabi_alloc")
+            .and_then(|alloc| alloc.call(&mut self.store, len))
+            .map_err(call_error)?;
+        self.memory.write(&mut self.store, ptr as u32 as usize, bytes).map_err(plugin_error)?;
+        Ok((ptr, len))
+    }
+
+    /// Copy out a packed message, checked against the plugin's memory
+    /// before anything is allocated for it
+    fn read(&self, packed: i64) -> ProviderResult<Vec<u8>> {
+        let ptr = (packed as u64 >> 32) as usize;
+        let len = (packed as u64 & 0xffff_ffff) as usize;
+        if len as u64 > DEFAULT_MAX_SOURCE_BYTES {
+            return Err(ProviderError::InvalidSource(format!(
+                "Plugin answered with {} bytes, more than the limit of {}",
+                len, DEFAULT_MAX_SOURCE_BYTES
+            )));
+        }
+        let in_bounds = ptr
+            .checked_add(len)
+            .is_some_and(|end| end <= self.memory.data_size(&self.store));
+        if !in_bounds {
+            return Err(ProviderError::InvalidSource(format!(
+                "Plugin answered with {} bytes at {}, outside its memory",
+                len, ptr
+            )));
+        }
+
+        let mut bytes = vec![0; len];
+        self.memory.read(&self.store, ptr, &mut bytes).map_err(plugin_error)?;
+        Ok(bytes)
+    }
+}
+
+fn plugin_error(e: impl std::fmt::Display) -> ProviderError {
+    ProviderError::InvalidSource(format!("Plugin failed: {:#}", e))
+}
+
+/// Error of a call into the plugin, naming an exhausted fuel budget
+fn call_error(e: wasmtime::Error) -> ProviderError {
+    if e.downcast_ref::<Trap>() == Some(&Trap::OutOfFuel) {
+        ProviderError::InvalidSource(format!(
+            "Plugin failed: exceeded its budget of {} instructions",
+            PLUGIN_FUEL
+        ))
+    } else {
+        plugin_error(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Plugin answering every request with one record, written in WAT
+    const ECHO_PLUGIN: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (global $next (mut i32) (i32.const 1024))
+          (data (i32.const 0) "echo")
+          (data (i32.const 16) "{\"types\":{\"modules\":[{\"path\":[\"Api\"],\"types\":[{\"kind\":\"record\",\"name\":\"User\",\"fields\":[[\"id\",\"int\"]]}]}]}}")
+          (func (export "fusabi_abi_version") (result i32) (i32.const 1))
+          (func (export "fusabi_alloc") (param $len i32) (result i32)
+            (local $ptr i32)
+            (local.set $ptr (global.get $next))
+            (global.set $next (i32.add (global.get $next) (local.get $len)))
+            (local.get $ptr))
+          (func (export "fusabi_provider_name") (result i64) (i64.const 4))
+          (func (export "fusabi_generate") (param $ptr i32) (param $len i32) (result i64)
+            (i64.or (i64.shl (i64.const 16) (i64.const 32)) (i64.const 106))))
+    "#;
+
+    #[test]
+    fn test_generate_through_plugin() {
+        let provider = WasmProvider::from_bytes(ECHO_PLUGIN.as_bytes()).unwrap();
+        assert_eq!(provider.name(), "echo");
+
+        let types = provider
+            .generate_types(&Schema::Custom("user: id".to_string()), "Api")
+            .unwrap();
+        assert_eq!(types.modules[0].path, vec!["Api"]);
+        assert_eq!(types.modules[0].types.len(), 1);
+    }
+
+    #[test]
+    fn test_rejects_other_abi_versions() {
+        let plugin = ECHO_PLUGIN.replace("(result i32) (i32.const 1)", "(result i32) (i32.const 2)");
+        let err = WasmProvider::from_bytes(plugin.as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("ABI version 2"));
+        assert!(WasmProvider::from_bytes(b"(module)").is_err());
+    }
+
+    #[test]
+    fn test_runaway_plugins_fail() {
+        let looping = ECHO_PLUGIN.replace(
+            "(i64.or (i64.shl (i64.const 16) (i64.const 32)) (i64.const 106))))",
+            "(loop $spin (br $spin)) (i64.const 0)))",
+        );
+        let provider = WasmProvider::from_bytes(looping.as_bytes()).unwrap();
+        let err = provider
+            .generate_types(&Schema::Custom("user: id".to_string()), "Api")
+            .unwrap_err();
+        assert!(err.to_string().contains("exceeded its budget"));
+
+        let huge = ECHO_PLUGIN.replace("(memory (export \"memory\") 1)", "(memory (export \"memory\") 8192)");
+        assert!(WasmProvider::from_bytes(huge.as_bytes()).is_err());
+
+        let out_of_bounds = ECHO_PLUGIN.replace("(i64.const 106)", "(i64.const 1048576)");
+        let provider = WasmProvider::from_bytes(out_of_bounds.as_bytes()).unwrap();
+        let err = provider
+            .generate_types(&Schema::Custom("user: id".to_string()), "Api")
+            .unwrap_err();
+        assert!(err.to_string().contains("outside its memory"));
+    }
+}
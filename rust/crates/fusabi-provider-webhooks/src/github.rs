@@ -0,0 +1,267 @@
+//! GitHub repository webhooks
+//!
+//! Deliveries name their event in the `X-GitHub-Event` header; events with
+//! several activities say which in `action`. Payloads follow the REST API
+//! objects, trimmed to the properties every delivery carries plus the ones
+//! automation usually reads. Organization, marketplace and GitHub App
+//! management events are not included.
+
+use crate::pack::{EnumDef, ObjectDef, Pack, PackKey, OPT, REQ};
+
+pub const PACK: Pack = Pack {
+    name: "github",
+    vendor: "GitHub",
+    key: PackKey::Header("X-GitHub-Event"),
+    events: &[
+        ("ping", "PingEvent"),
+        ("push", "PushEvent"),
+        ("pull_request", "PullRequestEvent"),
+        ("pull_request_review", "PullRequestReviewEvent"),
+        ("issues", "IssuesEvent"),
+        ("issue_comment", "IssueCommentEvent"),
+        ("release", "ReleaseEvent"),
+        ("workflow_run", "WorkflowRunEvent"),
+        ("star", "StarEvent"),
+    ],
+    envelopes: &[],
+    objects: OBJECTS,
+    enums: ENUMS,
+};
+
+const ENUMS: &[EnumDef] = &[
+    ("PullRequestAction", &[
+        "assigned", "unassigned", "labeled", "unlabeled", "opened", "edited", "closed", "reopened",
+        "synchronize", "converted_to_draft", "ready_for_review", "review_requested",
+        "review_request_removed", "auto_merge_enabled", "auto_merge_disabled",
+    ]),
+    ("PullRequestReviewAction", &["submitted", "edited", "dismissed"]),
+    ("IssuesAction", &[
+        "opened", "edited", "deleted", "pinned", "unpinned", "closed", "reopened", "assigned",
+        "unassigned", "labeled", "unlabeled", "locked", "unlocked", "transferred", "milestoned",
+        "demilestoned",
+    ]),
+    ("CommentAction", &["created", "edited", "deleted"]),
+    ("ReleaseAction", &[
+        "published", "unpublished", "created", "edited", "deleted", "prereleased", "released",
+    ]),
+    ("WorkflowRunAction", &["requested", "in_progress", "completed"]),
+    ("StarAction", &["created", "deleted"]),
+];
+
+const OBJECTS: &[ObjectDef] = &[
+    ("PingEvent", &[
+        ("zen", "string", REQ),
+        ("hook_id", "int64", REQ),
+        ("hook", "Hook", REQ),
+        ("repository", "Repository", OPT),
+        ("sender", "User", OPT),
+    ]),
+    ("PushEvent", &[
+        ("ref", "string", REQ),
+        ("before", "string", REQ),
+        ("after", "string", REQ),
+        ("created", "bool", REQ),
+        ("deleted", "bool", REQ),
+        ("forced", "bool", REQ),
+        ("base_ref", "string", OPT),
+        ("compare", "string", REQ),
+        ("commits", "Commit list", REQ),
+        ("head_commit", "Commit", OPT),
+        ("pusher", "Committer", REQ),
+        ("repository", "Repository", REQ),
+        ("sender", "User", REQ),
+        ("installation", "Installation", OPT),
+    ]),
+    ("PullRequestEvent", &[
+        ("action", "PullRequestAction", REQ),
+        ("number", "int", REQ),
+        ("pull_request", "PullRequest", REQ),
+        ("repository", "Repository", REQ),
+        ("sender", "User", REQ),
+        ("installation", "Installation", OPT),
+    ]),
+    ("PullRequestReviewEvent", &[
+        ("action", "PullRequestReviewAction", REQ),
+        ("review", "Review", REQ),
+        ("pull_request", "PullRequest", REQ),
+        ("repository", "Repository", REQ),
+        ("sender", "User", REQ),
+        ("installation", "Installation", OPT),
+    ]),
+    ("IssuesEvent", &[
+        ("action", "IssuesAction", REQ),
+        ("issue", "Issue", REQ),
+        ("repository", "Repository", REQ),
+        ("sender", "User", REQ),
+        ("installation", "Installation", OPT),
+    ]),
+    ("IssueCommentEvent", &[
+        ("action", "CommentAction", REQ),
+        ("issue", "Issue", REQ),
+        ("comment", "Comment", REQ),
+        ("repository", "Repository", REQ),
+        ("sender", "User", REQ),
+        ("installation", "Installation", OPT),
+    ]),
+    ("ReleaseEvent", &[
+        ("action", "ReleaseAction", REQ),
+        ("release", "Release", REQ),
+        ("repository", "Repository", REQ),
+        ("sender", "User", REQ),
+        ("installation", "Installation", OPT),
+    ]),
+    ("WorkflowRunEvent", &[
+        ("action", "WorkflowRunAction", REQ),
+        ("workflow_run", "WorkflowRun", REQ),
+        ("repository", "Repository", REQ),
+        ("sender", "User", REQ),
+        ("installation", "Installation", OPT),
+    ]),
+    ("StarEvent", &[
+        ("action", "StarAction", REQ),
+        ("starred_at", "string", OPT),
+        ("repository", "Repository", REQ),
+        ("sender", "User", REQ),
+    ]),
+    ("Hook", &[
+        ("id", "int64", REQ),
+        ("type", "string", REQ),
+        ("name", "string", REQ),
+        ("active", "bool", REQ),
+        ("events", "string list", REQ),
+    ]),
+    ("User", &[
+        ("login", "string", REQ),
+        ("id", "int64", REQ),
+        ("node_id", "string", REQ),
+        ("avatar_url", "string", REQ),
+        ("html_url", "string", REQ),
+        ("type", "string", REQ),
+        ("site_admin", "bool", REQ),
+    ]),
+    ("Repository", &[
+        ("id", "int64", REQ),
+        ("node_id", "string", REQ),
+        ("name", "string", REQ),
+        ("full_name", "string", REQ),
+        ("private", "bool", REQ),
+        ("owner", "User", REQ),
+        ("html_url", "string", REQ),
+        ("description", "string", OPT),
+        ("fork", "bool", REQ),
+        ("default_branch", "string", REQ),
+        ("visibility", "string", OPT),
+        ("archived", "bool", REQ),
+    ]),
+    ("Installation", &[
+        ("id", "int64", REQ),
+        ("node_id", "string", OPT),
+    ]),
+    ("Commit", &[
+        ("id", "string", REQ),
+        ("tree_id", "string", REQ),
+        ("distinct", "bool", REQ),
+        ("message", "string", REQ),
+        ("timestamp", "string", REQ),
+        ("url", "string", REQ),
+        ("author", "Committer", REQ),
+        ("committer", "Committer", REQ),
+        ("added", "string list", REQ),
+        ("removed", "string list", REQ),
+        ("modified", "string list", REQ),
+    ]),
+    ("Committer", &[
+        ("name", "string", REQ),
+        ("email", "string", OPT),
+        ("username", "string", OPT),
+    ]),
+    ("PullRequest", &[
+        ("id", "int64", REQ),
+        ("number", "int", REQ),
+        ("state", "string", REQ),
+        ("title", "string", REQ),
+        ("body", "string", OPT),
+        ("user", "User", REQ),
+        ("draft", "bool", REQ),
+        ("merged", "bool", OPT),
+        ("head", "Branch", REQ),
+        ("base", "Branch", REQ),
+        ("labels", "Label list", REQ),
+        ("html_url", "string", REQ),
+        ("created_at", "string", REQ),
+        ("updated_at", "string", REQ),
+        ("closed_at", "string", OPT),
+        ("merged_at", "string", OPT),
+    ]),
+    ("Review", &[
+        ("id", "int64", REQ),
+        ("user", "User", REQ),
+        ("body", "string", OPT),
+        ("state", "string", REQ),
+        ("commit_id", "string", REQ),
+        ("html_url", "string", REQ),
+        ("submitted_at", "string", OPT),
+    ]),
+    ("Branch", &[
+        ("label", "string", REQ),
+        ("ref", "string", REQ),
+        ("sha", "string", REQ),
+        ("user", "User", REQ),
+        ("repo", "Repository", OPT),
+    ]),
+    ("Label", &[
+        ("id", "int64", REQ),
+        ("name", "string", REQ),
+        ("color", "string", REQ),
+        ("description", "string", OPT),
+    ]),
+    ("Issue", &[
+        ("id", "int64", REQ),
+        ("number", "int", REQ),
+        ("title", "string", REQ),
+        ("body", "string", OPT),
+        ("state", "string", REQ),
+        ("user", "User", REQ),
+        ("labels", "Label list", REQ),
+        ("assignees", "User list", REQ),
+        ("html_url", "string", REQ),
+        ("created_at", "string", REQ),
+        ("updated_at", "string", REQ),
+        ("closed_at", "string", OPT),
+    ]),
+    ("Comment", &[
+        ("id", "int64", REQ),
+        ("body", "string", REQ),
+        ("user", "User", REQ),
+        ("html_url", "string", REQ),
+        ("created_at", "string", REQ),
+        ("updated_at", "string", REQ),
+    ]),
+    ("Release", &[
+        ("id", "int64", REQ),
+        ("tag_name", "string", REQ),
+        ("target_commitish", "string", REQ),
+        ("name", "string", OPT),
+        ("body", "string", OPT),
+        ("draft", "bool", REQ),
+        ("prerelease", "bool", REQ),
+        ("author", "User", REQ),
+        ("html_url", "string", REQ),
+        ("created_at", "string", REQ),
+        ("published_at", "string", OPT),
+    ]),
+    ("WorkflowRun", &[
+        ("id", "int64", REQ),
+        ("name", "string", OPT),
+        ("head_branch", "string", OPT),
+        ("head_sha", "string", REQ),
+        ("run_number", "int", REQ),
+        ("event", "string", REQ),
+        ("status", "string", OPT),
+        ("conclusion", "string", OPT),
+        ("workflow_id", "int64", REQ),
+        ("html_url", "string", REQ),
+        ("created_at", "string", REQ),
+        ("updated_at", "string", REQ),
+    ]),
+];
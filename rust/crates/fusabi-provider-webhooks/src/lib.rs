@@ -0,0 +1,615 @@
+//! Webhook Events Type Provider
+//!
+//! Generates Fusabi types for the webhook deliveries of a vendor, so a
+//! handler can match on the event it received and read its payload typed.
+//!
+//! # Sources
+//!
+//! - `github`, `stripe`, `slack`: embedded catalogs; see [`github`],
+//!   [`stripe`] and [`slack`]
+//! - A vendor's published JSON Schema: a bundle, inline or from a file or
+//!   URL, or a directory of per-event schema files; see [`schema`]
+//!
+//! # Generated Types
+//!
+//! One record per event payload, the objects and enumerations they use,
+//! and `<Vendor>Event`, a union with one case per event type carrying its
+//! payload. Cases are named after the event type as the delivery spells
+//! it: `pull_request` is `PullRequest` and `invoice.payment_failed` is
+//! `InvoicePaymentFailed`. Which header or field the type is read from
+//! depends on the vendor:
+//!
+//! | Source   | Event type in              | Union          |
+//! |----------|----------------------------|----------------|
+//! | `github` | `X-GitHub-Event` header    | `GitHubEvent`  |
+//! | `stripe` | `type`                     | `StripeEvent`  |
+//! | `slack`  | `event.type`               | `SlackEvent`   |
+//!
+//! For schemas, a property every event fixes to a constant (`type`,
+//! `event`, `event_type`, `topic` or `kind`) is found on its own; the
+//! `header` and `field` params say where it is otherwise.
+//!
+//! # Params
+//!
+//! | Param    | Meaning                                                         |
+//! |----------|-----------------------------------------------------------------|
+//! | `events` | Comma-separated event types to generate; `charge.*` selects a family |
+//! | `vendor` | Vendor name for schemas, prefixing the union; `Webhook` by default |
+//! | `header` | Header carrying the event type, for schemas                     |
+//! | `field`  | Dotted path of the payload field carrying the event type, for schemas |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_webhooks::WebhookProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = WebhookProvider::new();
+//! let params = ProviderParams::default().with("events", "invoice.*,customer.subscription.*");
+//! let schema = provider.resolve_schema("stripe", &params)?;
+//! let types = provider.generate_types(&schema, "Stripe")?;
+//! ```
+
+pub mod github;
+pub mod pack;
+pub mod schema;
+pub mod slack;
+pub mod stripe;
+
+pub use pack::{Pack, PackKey, PACKS};
+pub use schema::{EventSchemas, LoadedEvent};
+
+use fusabi_jsonschema_core::Converter;
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceKind, SourceValidator, StructuredError, TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, DuDef, VariantDef, TypeExpr, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Where a delivery names its event type
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventKey {
+    Header(String),
+    /// Dotted path to a payload field
+    Field(String),
+    /// Schemas that don't say
+    Unknown,
+}
+
+impl From<PackKey> for EventKey {
+    fn from(key: PackKey) -> Self {
+        match key {
+            PackKey::Header(header) => EventKey::Header(header.to_string()),
+            PackKey::Field(field) => EventKey::Field(field.to_string()),
+        }
+    }
+}
+
+/// The selected events of a catalog, stored in [`Schema::Custom`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Catalog {
+    vendor: String,
+    key: EventKey,
+    source: CatalogSource,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum CatalogSource {
+    /// An embedded pack and the event types selected from it
+    Pack { name: String, events: Vec<String> },
+    Schemas(EventSchemas),
+}
+
+/// Webhook events type provider
+pub struct WebhookProvider {
+    generator: TypeGenerator,
+    converter: Converter,
+}
+
+impl WebhookProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+            converter: Converter::new(),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<Catalog> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected webhook catalog: {}", e)))
+    }
+
+    fn read_dir(&self, dir: &Path, params: &ProviderParams, schemas: &mut EventSchemas) -> ProviderResult<()> {
+        let mut paths: Vec<_> = std::fs::read_dir(dir)
+            .map_err(|e| StructuredError::io(format!("Failed to read {}", dir.display())).with_source(e))?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            let content = read_source(&path.to_string_lossy(), params)?;
+            let stem = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+            schemas.read(&content, &stem)?;
+        }
+        Ok(())
+    }
+
+    /// PascalCase of a name that may hold any characters
+    fn type_name(&self, raw: &str) -> String {
+        let words: String = raw.chars().map(|c| if c.is_alphanumeric() { c } else { ' ' }).collect();
+        let name = self.generator.naming.apply(&words);
+        if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+            format!("Event{}", name)
+        } else {
+            name
+        }
+    }
+
+    /// The catalog's types, with the event union last, and what was lost
+    /// on the way
+    fn generate(&self, catalog: &Catalog) -> ProviderResult<(Vec<TypeDefinition>, Vec<Diagnostic>)> {
+        let mut diagnostics = Vec::new();
+        let (mut types, events) = match &catalog.source {
+            CatalogSource::Pack { name, events } => {
+                let pack = pack::pack(name)
+                    .ok_or_else(|| ProviderError::InvalidSource(format!("No embedded webhook catalog `{}`", name)))?;
+                self.pack_types(pack, events)
+            }
+            CatalogSource::Schemas(schemas) => self.schema_types(schemas),
+        };
+
+        let mut variants: Vec<VariantDef> = Vec::new();
+        for (event_type, payload) in events {
+            let case = self.type_name(&event_type);
+            if variants.iter().any(|v| v.name == case) {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        "duplicate-event",
+                        format!("Another event type is also named `{}`; only the first is generated", case),
+                    )
+                    .at(event_type),
+                );
+                continue;
+            }
+            variants.push(VariantDef::new(case, vec![payload]));
+        }
+        if !variants.is_empty() {
+            types.push(TypeDefinition::Du(DuDef {
+                name: format!("{}Event", catalog.vendor),
+                variants,
+            }));
+        }
+        Ok((types, diagnostics))
+    }
+
+    /// Enumerations and objects reachable from the selected events and the
+    /// envelopes, in table order, and the payload of each event
+    fn pack_types(&self, pack: &Pack, selected: &[String]) -> (Vec<TypeDefinition>, Vec<(String, TypeExpr)>) {
+        let events: Vec<&(&str, &str)> = pack.events.iter().filter(|(t, _)| selected.iter().any(|s| s == t)).collect();
+
+        let mut reached: Vec<&str> = Vec::new();
+        let mut pending: Vec<&str> = events.iter().map(|(_, object)| *object).chain(pack.envelopes.iter().copied()).collect();
+        while let Some(name) = pending.pop() {
+            if reached.contains(&name) {
+                continue;
+            }
+            reached.push(name);
+            if let Some((_, fields)) = pack.object(name) {
+                pending.extend(fields.iter().filter_map(|(_, field_type, _)| pack::referenced_type(field_type)));
+            }
+        }
+
+        let enums = pack.enums.iter().filter(|(name, _)| reached.contains(name)).map(|(name, values)| {
+            TypeDefinition::Du(DuDef {
+                name: name.to_string(),
+                variants: values.iter().map(|v| VariantDef::new_simple(self.type_name(v))).collect(),
+            })
+        });
+        let objects = pack.objects.iter().filter(|(name, _)| reached.contains(name)).map(|(name, properties)| {
+            TypeDefinition::Record(RecordDef {
+                name: name.to_string(),
+                fields: properties
+                    .iter()
+                    .map(|(property, field_type, required)| {
+                        (property.to_string(), TypeShape::parse(field_type).option_unless(*required).into())
+                    })
+                    .collect(),
+            })
+        });
+
+        let payloads = events
+            .iter()
+            .map(|(event_type, object)| (event_type.to_string(), TypeShape::named(*object).into()))
+            .collect();
+        (enums.chain(objects).collect(), payloads)
+    }
+
+    /// Shared definitions, then payload definitions of their own, and the
+    /// payload of each event
+    fn schema_types(&self, schemas: &EventSchemas) -> (Vec<TypeDefinition>, Vec<(String, TypeExpr)>) {
+        let mut types = self.converter.definitions(&schemas.definitions);
+        let mut payloads = Vec::new();
+        for event in &schemas.events {
+            let payload = match self.converter.definition(&event.name, &event.schema) {
+                Some(definition) => {
+                    let name = type_name(&definition).to_string();
+                    if !types.iter().any(|t| type_name(t) == name) {
+                        types.push(definition);
+                    }
+                    TypeShape::named(name).into()
+                }
+                // `$ref`s into a bundle's definitions, and untyped payloads
+                None => self.converter.type_expr(&event.schema),
+            };
+            payloads.push((event.event_type.clone(), payload));
+        }
+        (types, payloads)
+    }
+}
+
+impl Default for WebhookProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for WebhookProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("WebhookProvider")
+            .param(
+                ParamSpec::new("events", ParamKind::List)
+                    .describe("Event types to generate; `charge.*` selects a family"),
+            )
+            .param(
+                ParamSpec::new("vendor", ParamKind::String)
+                    .default("Webhook")
+                    .describe("Vendor name for schemas, prefixing the union"),
+            )
+            .param(
+                ParamSpec::new("header", ParamKind::String)
+                    .describe("Header carrying the event type, for schemas"),
+            )
+            .param(
+                ParamSpec::new("field", ParamKind::String)
+                    .describe("Dotted path of the payload field carrying the event type, for schemas"),
+            )
+    }
+}
+
+impl TypeProvider for WebhookProvider {
+    fn name(&self) -> &str {
+        "WebhookProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let trimmed = source.trim();
+        let mut catalog = match pack::pack(trimmed) {
+            Some(pack) => Catalog {
+                vendor: pack.vendor.to_string(),
+                key: pack.key.into(),
+                source: CatalogSource::Pack {
+                    name: pack.name.to_string(),
+                    events: pack.events.iter().map(|(event_type, _)| event_type.to_string()).collect(),
+                },
+            },
+            None => {
+                let mut schemas = EventSchemas::default();
+                if trimmed.starts_with('{') {
+                    schemas.read(trimmed, "event")?;
+                } else if Path::new(source).is_dir() {
+                    self.read_dir(Path::new(source), params, &mut schemas)?;
+                } else {
+                    let stem = source.rsplit('/').next().unwrap_or(source);
+                    let stem = stem.rsplit_once('.').map_or(stem, |(stem, _)| stem);
+                    schemas.read(&read_source(source, params)?, stem)?;
+                }
+                let key = schemas.key(typed.str("header"), typed.str("field"));
+                Catalog {
+                    vendor: self.type_name(typed.str("vendor").unwrap_or("webhook")),
+                    key,
+                    source: CatalogSource::Schemas(schemas),
+                }
+            }
+        };
+
+        if let Some(patterns) = typed.list("events") {
+            let matches = |pattern: &str, event_type: &str| match pattern.strip_suffix('*') {
+                Some(prefix) => event_type.starts_with(prefix),
+                None => event_type == pattern,
+            };
+            let mut event_types: Vec<String> = match &catalog.source {
+                CatalogSource::Pack { events, .. } => events.clone(),
+                CatalogSource::Schemas(schemas) => schemas.events.iter().map(|e| e.event_type.clone()).collect(),
+            };
+            for pattern in &patterns {
+                if !event_types.iter().any(|t| matches(pattern, t)) {
+                    return Err(ProviderError::InvalidSource(format!(
+                        "No {} event matches `{}`",
+                        catalog.vendor, pattern
+                    )));
+                }
+            }
+            event_types.retain(|t| patterns.iter().any(|p| matches(p, t)));
+            match &mut catalog.source {
+                CatalogSource::Pack { events, .. } => *events = event_types,
+                CatalogSource::Schemas(schemas) => schemas.events.retain(|e| event_types.contains(&e.event_type)),
+            }
+        }
+
+        let json = serde_json::to_string(&catalog)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected webhook catalog".to_string()));
+        };
+
+        let catalog = self.parse_schema(json)?;
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+        module.types = self.generate(&catalog)?.0;
+        let mut result = GeneratedTypes::new();
+        result.modules.push(module);
+        Ok(result)
+    }
+}
+
+impl SourceValidator for WebhookProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected webhook catalog".to_string()));
+        };
+
+        let catalog = self.parse_schema(json)?;
+        let mut diagnostics = Vec::new();
+        if let CatalogSource::Schemas(schemas) = &catalog.source {
+            if schemas.events.is_empty() {
+                diagnostics.push(Diagnostic::warning("no-events", "The schemas describe no events"));
+            }
+            match &catalog.key {
+                EventKey::Unknown => diagnostics.push(Diagnostic::warning(
+                    "no-event-key",
+                    "No property fixes the event type; set the `header` or `field` param to say where deliveries carry it",
+                )),
+                EventKey::Field(field) => {
+                    for event in schemas.events.iter().filter(|e| e.named) {
+                        diagnostics.push(
+                            Diagnostic::info(
+                                "event-type-from-name",
+                                format!("The schema doesn't fix `{}`; the event type is taken from its name", field),
+                            )
+                            .at(event.event_type.clone()),
+                        );
+                    }
+                }
+                EventKey::Header(_) => {}
+            }
+        }
+        diagnostics.extend(self.generate(&catalog)?.1);
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for WebhookProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .source(SourceKind::Directory)
+            .embedded("github")
+            .embedded("stripe")
+            .embedded("slack")
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+fn type_name(definition: &TypeDefinition) -> &str {
+    match definition {
+        TypeDefinition::Record(r) => &r.name,
+        TypeDefinition::Du(d) => &d.name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+    use serde_json::json;
+
+    fn names(types: &GeneratedTypes) -> Vec<&str> {
+        types.modules[0].types.iter().map(type_name).collect()
+    }
+
+    fn record<'a>(types: &'a GeneratedTypes, name: &str) -> &'a RecordDef {
+        types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+    }
+
+    fn field(record: &RecordDef, name: &str) -> String {
+        record
+            .fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, ty)| ty.to_string())
+            .unwrap_or_else(|| panic!("Expected field {}.{}", record.name, name))
+    }
+
+    fn cases(types: &GeneratedTypes) -> Vec<(String, String)> {
+        let Some(TypeDefinition::Du(union)) = types.modules[0].types.last() else {
+            panic!("Expected the event union last");
+        };
+        union
+            .variants
+            .iter()
+            .map(|v| (v.name.clone(), v.fields.iter().map(|f| f.to_string()).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn test_github_pack() {
+        let provider = WebhookProvider::new();
+        let schema = provider.resolve_schema("github", &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "GitHub").unwrap();
+
+        assert_eq!(types.modules[0].path, ["GitHub"]);
+        assert_eq!(names(&types).last(), Some(&"GitHubEvent"));
+        let cases = cases(&types);
+        assert_eq!(cases.len(), github::PACK.events.len());
+        assert_eq!(cases[2], ("PullRequest".to_string(), "PullRequestEvent".to_string()));
+
+        let pull_request = record(&types, "PullRequestEvent");
+        assert_eq!(field(pull_request, "action"), "PullRequestAction");
+        assert_eq!(field(pull_request, "installation"), "Installation option");
+        assert_eq!(field(record(&types, "PushEvent"), "commits"), "Commit list");
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/webhooks.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_events_param() {
+        let provider = WebhookProvider::new();
+        let params = ProviderParams::default().with("events", "invoice.*, customer.created");
+        let schema = provider.resolve_schema("stripe", &params).unwrap();
+        let types = provider.generate_types(&schema, "Stripe").unwrap();
+
+        assert_eq!(
+            names(&types),
+            [
+                "InvoiceStatus",
+                "CustomerEvent",
+                "CustomerEventData",
+                "InvoiceEvent",
+                "InvoiceEventData",
+                "EventRequest",
+                "Customer",
+                "Invoice",
+                "StripeEvent",
+            ]
+        );
+        let cases: Vec<String> = cases(&types).into_iter().map(|(case, _)| case).collect();
+        assert_eq!(cases, ["CustomerCreated", "InvoiceCreated", "InvoiceFinalized", "InvoicePaid", "InvoicePaymentFailed"]);
+        assert_eq!(field(record(&types, "InvoiceEventData"), "previous_attributes"), "Map<string, any> option");
+
+        let params = ProviderParams::default().with("events", "invoice.voided");
+        let err = provider.resolve_schema("stripe", &params).unwrap_err();
+        assert!(err.to_string().contains("No Stripe event matches `invoice.voided`"));
+    }
+
+    #[test]
+    fn test_slack_envelope() {
+        let provider = WebhookProvider::new();
+        let params = ProviderParams::default().with("events", "message");
+        let schema = provider.resolve_schema("Slack", &params).unwrap();
+        let types = provider.generate_types(&schema, "Slack").unwrap();
+
+        assert_eq!(names(&types), ["EventCallback", "UrlVerification", "Authorization", "MessageEvent", "SlackEvent"]);
+        assert_eq!(field(record(&types, "EventCallback"), "event"), "SlackEvent");
+    }
+
+    #[test]
+    fn test_schema_bundle() {
+        let bundle = json!({
+            "oneOf": [{"$ref": "#/definitions/issues_event"}, {"$ref": "#/definitions/ping_event"}],
+            "definitions": {
+                "issues_event": {"oneOf": [{"$ref": "#/definitions/issues$opened"}, {"$ref": "#/definitions/issues$closed"}]},
+                "issues$opened": {"type": "object", "required": ["action"], "properties": {"action": {"const": "opened"}}},
+                "issues$closed": {"type": "object", "required": ["action"], "properties": {"action": {"const": "closed"}}},
+                "ping_event": {"type": "object", "required": ["zen"], "properties": {"zen": {"type": "string"}}}
+            }
+        });
+        let provider = WebhookProvider::new();
+        let params = ProviderParams::default().with("header", "X-GitHub-Event").with("vendor", "octokit");
+        let schema = provider.resolve_schema(&bundle.to_string(), &params).unwrap();
+        let types = provider.generate_types(&schema, "Hooks").unwrap();
+
+        assert_eq!(names(&types), ["IssuesClosed", "IssuesEvent", "IssuesOpened", "PingEvent", "OctokitEvent"]);
+        assert_eq!(
+            cases(&types),
+            [
+                ("Issues".to_string(), "IssuesEvent".to_string()),
+                ("Ping".to_string(), "PingEvent".to_string()),
+            ]
+        );
+        assert!(provider.schema_diagnostics(&schema).unwrap().is_empty());
+
+        let report = provider.validate_source(&bundle.to_string(), &ProviderParams::default());
+        assert!(report.is_valid());
+        assert_eq!(report.warnings().next().unwrap().code, "no-event-key");
+    }
+
+    #[test]
+    fn test_schema_directory() {
+        let dir = std::env::temp_dir().join("fusabi-webhooks-test");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("order_created.json"),
+            r#"{"type": "object", "required": ["topic", "order_id"],
+                "properties": {"topic": {"const": "orders/create"}, "order_id": {"type": "integer"}}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("app_uninstalled.json"),
+            r#"{"type": "object", "properties": {"topic": {"const": "app/uninstalled"}, "shop": {"type": "string"}}}"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("README.md"), "not a schema").unwrap();
+
+        let provider = WebhookProvider::new();
+        let params = ProviderParams::default().with("vendor", "shop");
+        let schema = provider.resolve_schema(dir.to_str().unwrap(), &params).unwrap();
+        let types = provider.generate_types(&schema, "Shop").unwrap();
+
+        assert_eq!(names(&types), ["AppUninstalledEvent", "OrderCreatedEvent", "ShopEvent"]);
+        assert_eq!(
+            cases(&types),
+            [
+                ("AppUninstalled".to_string(), "AppUninstalledEvent".to_string()),
+                ("OrdersCreate".to_string(), "OrderCreatedEvent".to_string()),
+            ]
+        );
+        assert_eq!(field(record(&types, "OrderCreatedEvent"), "order_id"), "int");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = WebhookProvider::new();
+        for pack in PACKS {
+            let report = provider.validate_source(pack.name, &ProviderParams::default());
+            assert!(report.is_valid());
+            // Only Stripe's `previous_attributes`, which holds any changed values
+            assert!(report.diagnostics.iter().all(|d| d.code == "coerced-any"), "{}: {:?}", pack.name, report.diagnostics);
+        }
+
+        let schemas = r##"{"anyOf": [{"$ref": "#/$defs/a"}, {"$ref": "#/$defs/b"}],
+            "$defs": {"a": {"properties": {"type": {"const": "a.done"}}}, "b": {"properties": {"type": {"const": "a_done"}}}}}"##;
+        let report = provider.validate_source(schemas, &ProviderParams::default());
+        assert!(report.is_valid());
+        let codes: Vec<&str> = report.diagnostics.iter().map(|d| d.code.as_str()).collect();
+        assert_eq!(codes, ["duplicate-event"]);
+    }
+}
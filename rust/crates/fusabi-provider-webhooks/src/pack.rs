@@ -0,0 +1,103 @@
+//! Embedded webhook catalogs
+//!
+//! A pack is a vendor's webhook catalog written as static tables: the event
+//! types it sends with the object each delivers, and the objects and
+//! enumerations those use, written the way they are generated: an object
+//! name, `T list`, or a primitive.
+
+use crate::{github, slack, stripe};
+
+/// Whether a property is always sent
+pub const REQ: bool = true;
+/// A property that may be missing or `null`
+pub const OPT: bool = false;
+
+/// An object: name, then `(property, type, required)`
+pub type ObjectDef = (&'static str, &'static [(&'static str, &'static str, bool)]);
+
+/// An enumeration: name, then its values
+pub type EnumDef = (&'static str, &'static [&'static str]);
+
+/// Where a delivery names its event type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackKey {
+    /// An HTTP header of the delivery
+    Header(&'static str),
+    /// A dotted path to a payload field
+    Field(&'static str),
+}
+
+/// A vendor's webhook catalog
+#[derive(Debug)]
+pub struct Pack {
+    /// Source name selecting the pack
+    pub name: &'static str,
+    /// Vendor name, prefixing the event union
+    pub vendor: &'static str,
+    pub key: PackKey,
+    /// Event types and the object each delivers
+    pub events: &'static [(&'static str, &'static str)],
+    /// Objects generated whichever events are selected, such as envelopes
+    pub envelopes: &'static [&'static str],
+    pub objects: &'static [ObjectDef],
+    pub enums: &'static [EnumDef],
+}
+
+/// Every embedded pack
+pub const PACKS: &[&Pack] = &[&github::PACK, &stripe::PACK, &slack::PACK];
+
+/// The pack a source names, if any
+pub fn pack(name: &str) -> Option<&'static Pack> {
+    PACKS.iter().copied().find(|p| p.name.eq_ignore_ascii_case(name))
+}
+
+impl Pack {
+    pub fn object(&self, name: &str) -> Option<&'static ObjectDef> {
+        self.objects.iter().find(|(object, _)| *object == name)
+    }
+
+    pub fn enumeration(&self, name: &str) -> Option<&'static EnumDef> {
+        self.enums.iter().find(|(e, _)| *e == name)
+    }
+}
+
+/// The object or enumeration a field type refers to, if any
+pub fn referenced_type(field_type: &str) -> Option<&str> {
+    let inner = field_type.trim_end_matches(" list");
+    (inner.starts_with(|c: char| c.is_ascii_uppercase()) && !inner.contains('<')).then_some(inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_packs_are_closed() {
+        for pack in PACKS {
+            let union = format!("{}Event", pack.vendor);
+            let delivered = pack.events.iter().map(|(_, object)| object);
+            for object in delivered.chain(pack.envelopes) {
+                assert!(pack.object(object).is_some(), "{} has no object {}", pack.name, object);
+            }
+            for (name, fields) in pack.objects {
+                for (field, field_type, _) in fields.iter() {
+                    if let Some(referenced) = referenced_type(field_type) {
+                        assert!(
+                            pack.object(referenced).is_some()
+                                || pack.enumeration(referenced).is_some()
+                                || referenced == union,
+                            "{} {}.{} refers to unknown type {}",
+                            pack.name,
+                            name,
+                            field,
+                            referenced
+                        );
+                    }
+                }
+            }
+        }
+        assert_eq!(referenced_type("Label list"), Some("Label"));
+        assert_eq!(referenced_type("string list"), None);
+        assert_eq!(pack("GitHub").map(|p| p.vendor), Some("GitHub"));
+    }
+}
@@ -0,0 +1,249 @@
+//! Vendor-published event schemas
+//!
+//! Vendors that publish their webhook payloads do so as JSON Schema, in one
+//! of two layouts:
+//!
+//! - a bundle, whose root `oneOf` (or `anyOf`) lists a `$ref` to each
+//!   event's definition, as in `@octokit/webhooks-schemas`
+//! - one schema file per event, read from a directory
+//!
+//! Every event is typed by its schema; what remains is finding each event's
+//! type. When the events share a property holding a constant (`"type":
+//! {"const": "invoice.paid"}`), that property is the key and its constants
+//! are the event types. Otherwise the type is taken from the definition or
+//! file name, with a trailing `_event` dropped: `issues_event` is `issues`.
+//!
+//! Definition names may hold characters that are not valid in type names,
+//! such as octokit's `issues$opened`; they are rewritten to `issues_opened`
+//! along with every `$ref` to them.
+
+use crate::EventKey;
+use fusabi_jsonschema_core::definitions_of;
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Properties tried, in order, as the key when none is given
+const KEY_CANDIDATES: [&str; 5] = ["type", "event", "event_type", "topic", "kind"];
+
+/// How deep `$ref` and union chains are followed looking for a key
+const MAX_DEPTH: usize = 16;
+
+/// Events read from schema documents, with the definitions they share
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EventSchemas {
+    pub events: Vec<LoadedEvent>,
+    pub definitions: Map<String, Value>,
+}
+
+/// One event and its payload schema
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoadedEvent {
+    pub event_type: String,
+    /// Name of the payload type
+    pub name: String,
+    pub schema: Value,
+    /// Whether the event type was taken from the name for want of a key value
+    #[serde(default)]
+    pub named: bool,
+}
+
+impl EventSchemas {
+    /// Add the events of one schema document; `stem` names the event of a
+    /// document that describes a single event
+    pub fn read(&mut self, content: &str, stem: &str) -> ProviderResult<()> {
+        let mut document: Value = serde_json::from_str(content)
+            .map_err(|e| ProviderError::ParseError(format!("Invalid event schema `{}`: {}", stem, e)))?;
+        rewrite_refs(&mut document);
+        if let Some(definitions) = definitions_of(&document) {
+            for (name, schema) in definitions {
+                self.definitions.entry(identifier(name)).or_insert_with(|| schema.clone());
+            }
+        }
+
+        let alternatives = document
+            .get("oneOf")
+            .or_else(|| document.get("anyOf"))
+            .and_then(Value::as_array)
+            .filter(|alts| alts.iter().all(|alt| alt.get("$ref").is_some()));
+        match alternatives {
+            Some(alternatives) => {
+                for alternative in alternatives {
+                    let reference = alternative["$ref"].as_str().unwrap_or_default();
+                    let name = reference.rsplit('/').next().unwrap_or(reference).to_string();
+                    self.events.push(LoadedEvent {
+                        event_type: event_type_of(&name),
+                        name,
+                        schema: alternative.clone(),
+                        named: true,
+                    });
+                }
+            }
+            None => self.events.push(LoadedEvent {
+                event_type: stem.to_string(),
+                name: format!("{}_event", identifier(stem)),
+                schema: document,
+                named: true,
+            }),
+        }
+        Ok(())
+    }
+
+    /// Settle which header or field carries the event type, and take each
+    /// event's type from it where its schema fixes one
+    pub fn key(&mut self, header: Option<&str>, field: Option<&str>) -> EventKey {
+        if let Some(header) = header {
+            return EventKey::Header(header.to_string());
+        }
+
+        let field = match field {
+            Some(field) => field.to_string(),
+            None => {
+                let found = KEY_CANDIDATES.iter().find(|candidate| {
+                    !self.events.is_empty() && self.events.iter().all(|e| self.constant(&e.schema, candidate).is_some())
+                });
+                match found {
+                    Some(candidate) => candidate.to_string(),
+                    None => return EventKey::Unknown,
+                }
+            }
+        };
+
+        for i in 0..self.events.len() {
+            if let Some(value) = self.constant(&self.events[i].schema, &field) {
+                self.events[i].event_type = value;
+                self.events[i].named = false;
+            }
+        }
+        EventKey::Field(field)
+    }
+
+    /// The constant a schema fixes at a dotted property path
+    fn constant(&self, schema: &Value, path: &str) -> Option<String> {
+        let path: Vec<&str> = path.split('.').collect();
+        self.constant_at(schema, &path, 0)
+    }
+
+    fn constant_at(&self, schema: &Value, path: &[&str], depth: usize) -> Option<String> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let target = self.definitions.get(reference.rsplit('/').next()?)?;
+            return self.constant_at(target, path, depth + 1);
+        }
+        if let Some(alternatives) = schema.get("oneOf").or_else(|| schema.get("anyOf")).and_then(Value::as_array) {
+            let values: Vec<Option<String>> =
+                alternatives.iter().map(|alt| self.constant_at(alt, path, depth + 1)).collect();
+            let first = values.first()?.clone()?;
+            return values.iter().all(|v| v.as_deref() == Some(first.as_str())).then_some(first);
+        }
+        if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+            return parts.iter().find_map(|part| self.constant_at(part, path, depth + 1));
+        }
+
+        match path.split_first() {
+            Some((property, rest)) => {
+                let property = schema.get("properties")?.get(*property)?;
+                self.constant_at(property, rest, depth + 1)
+            }
+            None => match schema.get("const") {
+                Some(Value::String(value)) => Some(value.clone()),
+                _ => match schema.get("enum").and_then(Value::as_array).map(Vec::as_slice) {
+                    Some([Value::String(value)]) => Some(value.clone()),
+                    _ => None,
+                },
+            },
+        }
+    }
+}
+
+/// Event type of a definition named after it
+fn event_type_of(name: &str) -> String {
+    name.strip_suffix("_event").filter(|s| !s.is_empty()).unwrap_or(name).to_string()
+}
+
+/// A definition name with characters other than letters, digits and `_`
+/// replaced by `_`
+pub fn identifier(name: &str) -> String {
+    name.chars().map(|c| if c.is_alphanumeric() || c == '_' { c } else { '_' }).collect()
+}
+
+/// Rename definitions to identifiers, along with the `$ref`s to them
+fn rewrite_refs(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            for (key, child) in object.iter_mut() {
+                match child {
+                    Value::String(reference) if key == "$ref" => {
+                        if let Some((base, name)) = reference.rsplit_once('/') {
+                            *reference = format!("{}/{}", base, identifier(name));
+                        }
+                    }
+                    Value::Object(definitions) if key == "definitions" || key == "$defs" => {
+                        let renamed: Map<String, Value> = std::mem::take(definitions)
+                            .into_iter()
+                            .map(|(name, mut schema)| {
+                                rewrite_refs(&mut schema);
+                                (identifier(&name), schema)
+                            })
+                            .collect();
+                        *definitions = renamed;
+                    }
+                    _ => rewrite_refs(child),
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(rewrite_refs),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_read_bundle() {
+        let bundle = json!({
+            "oneOf": [{"$ref": "#/definitions/issues_event"}, {"$ref": "#/definitions/ping_event"}],
+            "definitions": {
+                "issues_event": {"oneOf": [{"$ref": "#/definitions/issues$opened"}, {"$ref": "#/definitions/issues$closed"}]},
+                "issues$opened": {"type": "object", "properties": {"action": {"type": "string", "enum": ["opened"]}}},
+                "issues$closed": {"type": "object", "properties": {"action": {"const": "closed"}}},
+                "ping_event": {"type": "object", "properties": {"zen": {"type": "string"}}}
+            }
+        });
+        let mut schemas = EventSchemas::default();
+        schemas.read(&bundle.to_string(), "schema").unwrap();
+
+        let names: Vec<&str> = schemas.definitions.keys().map(String::as_str).collect();
+        assert_eq!(names, ["issues_closed", "issues_event", "issues_opened", "ping_event"]);
+        assert_eq!(schemas.definitions["issues_event"]["oneOf"][0]["$ref"], "#/definitions/issues_opened");
+
+        assert_eq!(schemas.key(None, None), EventKey::Unknown);
+        let types: Vec<&str> = schemas.events.iter().map(|e| e.event_type.as_str()).collect();
+        assert_eq!(types, ["issues", "ping"]);
+
+        // `action` differs between the alternatives, so it fixes nothing
+        assert_eq!(schemas.key(None, Some("action")), EventKey::Field("action".to_string()));
+        assert!(schemas.events.iter().all(|e| e.named));
+    }
+
+    #[test]
+    fn test_key_from_constants() {
+        let mut schemas = EventSchemas::default();
+        let paid = json!({"type": "object", "properties": {"type": {"const": "invoice.paid"}, "data": {"type": "object"}}});
+        let voided = json!({"allOf": [{"properties": {"type": {"enum": ["invoice.voided"]}}}]});
+        schemas.read(&paid.to_string(), "paid").unwrap();
+        schemas.read(&voided.to_string(), "voided").unwrap();
+
+        assert_eq!(schemas.key(None, None), EventKey::Field("type".to_string()));
+        let events: Vec<(&str, &str, bool)> =
+            schemas.events.iter().map(|e| (e.event_type.as_str(), e.name.as_str(), e.named)).collect();
+        assert_eq!(events, [("invoice.paid", "paid_event", false), ("invoice.voided", "voided_event", false)]);
+        assert_eq!(schemas.key(Some("X-Event"), None), EventKey::Header("X-Event".to_string()));
+        assert!(schemas.read("not json", "bad").is_err());
+    }
+}
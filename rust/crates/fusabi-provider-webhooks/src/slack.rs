@@ -0,0 +1,129 @@
+//! Slack Events API
+//!
+//! Events arrive wrapped in an `event_callback` envelope, `EventCallback`,
+//! whose `event` is typed as the event union; the inner `event.type` names
+//! the event. The `url_verification` handshake Slack sends when a request
+//! URL is configured is `UrlVerification`. Message subtypes share
+//! `MessageEvent`, with the subtype in `subtype`.
+
+use crate::pack::{ObjectDef, Pack, PackKey, OPT, REQ};
+
+pub const PACK: Pack = Pack {
+    name: "slack",
+    vendor: "Slack",
+    key: PackKey::Field("event.type"),
+    events: &[
+        ("app_home_opened", "AppHomeOpenedEvent"),
+        ("app_mention", "AppMentionEvent"),
+        ("channel_created", "ChannelCreatedEvent"),
+        ("member_joined_channel", "MemberJoinedChannelEvent"),
+        ("message", "MessageEvent"),
+        ("reaction_added", "ReactionEvent"),
+        ("reaction_removed", "ReactionEvent"),
+        ("team_join", "TeamJoinEvent"),
+    ],
+    envelopes: &["EventCallback", "UrlVerification"],
+    objects: OBJECTS,
+    enums: &[],
+};
+
+const OBJECTS: &[ObjectDef] = &[
+    ("EventCallback", &[
+        ("token", "string", REQ),
+        ("team_id", "string", REQ),
+        ("api_app_id", "string", REQ),
+        ("event", "SlackEvent", REQ),
+        ("type", "string", REQ),
+        ("event_id", "string", REQ),
+        ("event_time", "int64", REQ),
+        ("event_context", "string", OPT),
+        ("authorizations", "Authorization list", OPT),
+        ("is_ext_shared_channel", "bool", OPT),
+    ]),
+    ("UrlVerification", &[
+        ("token", "string", REQ),
+        ("challenge", "string", REQ),
+        ("type", "string", REQ),
+    ]),
+    ("Authorization", &[
+        ("enterprise_id", "string", OPT),
+        ("team_id", "string", OPT),
+        ("user_id", "string", REQ),
+        ("is_bot", "bool", REQ),
+        ("is_enterprise_install", "bool", OPT),
+    ]),
+    ("AppHomeOpenedEvent", &[
+        ("type", "string", REQ),
+        ("user", "string", REQ),
+        ("channel", "string", REQ),
+        ("tab", "string", REQ),
+        ("event_ts", "string", REQ),
+    ]),
+    ("AppMentionEvent", &[
+        ("type", "string", REQ),
+        ("user", "string", REQ),
+        ("text", "string", REQ),
+        ("ts", "string", REQ),
+        ("channel", "string", REQ),
+        ("thread_ts", "string", OPT),
+        ("event_ts", "string", REQ),
+    ]),
+    ("ChannelCreatedEvent", &[
+        ("type", "string", REQ),
+        ("channel", "Channel", REQ),
+    ]),
+    ("Channel", &[
+        ("id", "string", REQ),
+        ("name", "string", REQ),
+        ("created", "int64", REQ),
+        ("creator", "string", REQ),
+    ]),
+    ("MemberJoinedChannelEvent", &[
+        ("type", "string", REQ),
+        ("user", "string", REQ),
+        ("channel", "string", REQ),
+        ("channel_type", "string", OPT),
+        ("team", "string", OPT),
+        ("inviter", "string", OPT),
+        ("event_ts", "string", OPT),
+    ]),
+    ("MessageEvent", &[
+        ("type", "string", REQ),
+        ("subtype", "string", OPT),
+        ("channel", "string", REQ),
+        ("channel_type", "string", OPT),
+        ("user", "string", OPT),
+        ("bot_id", "string", OPT),
+        ("text", "string", OPT),
+        ("ts", "string", REQ),
+        ("thread_ts", "string", OPT),
+        ("event_ts", "string", OPT),
+    ]),
+    ("ReactionEvent", &[
+        ("type", "string", REQ),
+        ("user", "string", REQ),
+        ("reaction", "string", REQ),
+        ("item", "ReactionItem", REQ),
+        ("item_user", "string", OPT),
+        ("event_ts", "string", REQ),
+    ]),
+    ("ReactionItem", &[
+        ("type", "string", REQ),
+        ("channel", "string", OPT),
+        ("ts", "string", OPT),
+        ("file", "string", OPT),
+    ]),
+    ("TeamJoinEvent", &[
+        ("type", "string", REQ),
+        ("user", "SlackUser", REQ),
+    ]),
+    ("SlackUser", &[
+        ("id", "string", REQ),
+        ("team_id", "string", REQ),
+        ("name", "string", REQ),
+        ("real_name", "string", OPT),
+        ("deleted", "bool", OPT),
+        ("is_bot", "bool", REQ),
+        ("tz", "string", OPT),
+    ]),
+];
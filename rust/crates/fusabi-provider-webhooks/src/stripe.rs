@@ -0,0 +1,264 @@
+//! Stripe webhook events
+//!
+//! Every delivery is an `Event` object naming its type in `type`, with the
+//! affected API object in `data.object`. Events on the same kind of object
+//! share a record, such as `ChargeEvent` for every `charge.*` event, whose
+//! `data.previous_attributes` holds the changed values of `*.updated`
+//! events. Objects are trimmed to their commonly read properties; expandable
+//! references stay ID strings.
+
+use crate::pack::{EnumDef, ObjectDef, Pack, PackKey, OPT, REQ};
+
+pub const PACK: Pack = Pack {
+    name: "stripe",
+    vendor: "Stripe",
+    key: PackKey::Field("type"),
+    events: &[
+        ("charge.captured", "ChargeEvent"),
+        ("charge.failed", "ChargeEvent"),
+        ("charge.refunded", "ChargeEvent"),
+        ("charge.succeeded", "ChargeEvent"),
+        ("checkout.session.completed", "CheckoutSessionEvent"),
+        ("checkout.session.expired", "CheckoutSessionEvent"),
+        ("customer.created", "CustomerEvent"),
+        ("customer.deleted", "CustomerEvent"),
+        ("customer.updated", "CustomerEvent"),
+        ("customer.subscription.created", "SubscriptionEvent"),
+        ("customer.subscription.deleted", "SubscriptionEvent"),
+        ("customer.subscription.updated", "SubscriptionEvent"),
+        ("invoice.created", "InvoiceEvent"),
+        ("invoice.finalized", "InvoiceEvent"),
+        ("invoice.paid", "InvoiceEvent"),
+        ("invoice.payment_failed", "InvoiceEvent"),
+        ("payment_intent.canceled", "PaymentIntentEvent"),
+        ("payment_intent.created", "PaymentIntentEvent"),
+        ("payment_intent.payment_failed", "PaymentIntentEvent"),
+        ("payment_intent.succeeded", "PaymentIntentEvent"),
+    ],
+    envelopes: &[],
+    objects: OBJECTS,
+    enums: ENUMS,
+};
+
+const ENUMS: &[EnumDef] = &[
+    ("ChargeStatus", &["succeeded", "pending", "failed"]),
+    ("CheckoutSessionStatus", &["open", "complete", "expired"]),
+    ("InvoiceStatus", &["draft", "open", "paid", "uncollectible", "void"]),
+    ("PaymentIntentStatus", &[
+        "requires_payment_method", "requires_confirmation", "requires_action", "processing",
+        "requires_capture", "canceled", "succeeded",
+    ]),
+    ("SubscriptionStatus", &[
+        "incomplete", "incomplete_expired", "trialing", "active", "past_due", "canceled", "unpaid",
+        "paused",
+    ]),
+];
+
+const OBJECTS: &[ObjectDef] = &[
+    ("ChargeEvent", &[
+        ("id", "string", REQ),
+        ("object", "string", REQ),
+        ("api_version", "string", OPT),
+        ("created", "int64", REQ),
+        ("livemode", "bool", REQ),
+        ("pending_webhooks", "int", REQ),
+        ("type", "string", REQ),
+        ("data", "ChargeEventData", REQ),
+        ("request", "EventRequest", OPT),
+    ]),
+    ("ChargeEventData", &[
+        ("object", "Charge", REQ),
+        ("previous_attributes", "Map<string, any>", OPT),
+    ]),
+    ("CheckoutSessionEvent", &[
+        ("id", "string", REQ),
+        ("object", "string", REQ),
+        ("api_version", "string", OPT),
+        ("created", "int64", REQ),
+        ("livemode", "bool", REQ),
+        ("pending_webhooks", "int", REQ),
+        ("type", "string", REQ),
+        ("data", "CheckoutSessionEventData", REQ),
+        ("request", "EventRequest", OPT),
+    ]),
+    ("CheckoutSessionEventData", &[
+        ("object", "CheckoutSession", REQ),
+        ("previous_attributes", "Map<string, any>", OPT),
+    ]),
+    ("CustomerEvent", &[
+        ("id", "string", REQ),
+        ("object", "string", REQ),
+        ("api_version", "string", OPT),
+        ("created", "int64", REQ),
+        ("livemode", "bool", REQ),
+        ("pending_webhooks", "int", REQ),
+        ("type", "string", REQ),
+        ("data", "CustomerEventData", REQ),
+        ("request", "EventRequest", OPT),
+    ]),
+    ("CustomerEventData", &[
+        ("object", "Customer", REQ),
+        ("previous_attributes", "Map<string, any>", OPT),
+    ]),
+    ("SubscriptionEvent", &[
+        ("id", "string", REQ),
+        ("object", "string", REQ),
+        ("api_version", "string", OPT),
+        ("created", "int64", REQ),
+        ("livemode", "bool", REQ),
+        ("pending_webhooks", "int", REQ),
+        ("type", "string", REQ),
+        ("data", "SubscriptionEventData", REQ),
+        ("request", "EventRequest", OPT),
+    ]),
+    ("SubscriptionEventData", &[
+        ("object", "Subscription", REQ),
+        ("previous_attributes", "Map<string, any>", OPT),
+    ]),
+    ("InvoiceEvent", &[
+        ("id", "string", REQ),
+        ("object", "string", REQ),
+        ("api_version", "string", OPT),
+        ("created", "int64", REQ),
+        ("livemode", "bool", REQ),
+        ("pending_webhooks", "int", REQ),
+        ("type", "string", REQ),
+        ("data", "InvoiceEventData", REQ),
+        ("request", "EventRequest", OPT),
+    ]),
+    ("InvoiceEventData", &[
+        ("object", "Invoice", REQ),
+        ("previous_attributes", "Map<string, any>", OPT),
+    ]),
+    ("PaymentIntentEvent", &[
+        ("id", "string", REQ),
+        ("object", "string", REQ),
+        ("api_version", "string", OPT),
+        ("created", "int64", REQ),
+        ("livemode", "bool", REQ),
+        ("pending_webhooks", "int", REQ),
+        ("type", "string", REQ),
+        ("data", "PaymentIntentEventData", REQ),
+        ("request", "EventRequest", OPT),
+    ]),
+    ("PaymentIntentEventData", &[
+        ("object", "PaymentIntent", REQ),
+        ("previous_attributes", "Map<string, any>", OPT),
+    ]),
+    ("EventRequest", &[
+        ("id", "string", OPT),
+        ("idempotency_key", "string", OPT),
+    ]),
+    ("Charge", &[
+        ("id", "string", REQ),
+        ("object", "string", REQ),
+        ("amount", "int64", REQ),
+        ("amount_captured", "int64", REQ),
+        ("amount_refunded", "int64", REQ),
+        ("currency", "string", REQ),
+        ("captured", "bool", REQ),
+        ("paid", "bool", REQ),
+        ("refunded", "bool", REQ),
+        ("status", "ChargeStatus", REQ),
+        ("customer", "string", OPT),
+        ("payment_intent", "string", OPT),
+        ("description", "string", OPT),
+        ("failure_code", "string", OPT),
+        ("failure_message", "string", OPT),
+        ("receipt_url", "string", OPT),
+        ("created", "int64", REQ),
+        ("livemode", "bool", REQ),
+        ("metadata", "Map<string, string>", REQ),
+    ]),
+    ("CheckoutSession", &[
+        ("id", "string", REQ),
+        ("object", "string", REQ),
+        ("mode", "string", REQ),
+        ("status", "CheckoutSessionStatus", OPT),
+        ("payment_status", "string", REQ),
+        ("amount_total", "int64", OPT),
+        ("currency", "string", OPT),
+        ("customer", "string", OPT),
+        ("customer_email", "string", OPT),
+        ("payment_intent", "string", OPT),
+        ("subscription", "string", OPT),
+        ("client_reference_id", "string", OPT),
+        ("url", "string", OPT),
+        ("created", "int64", REQ),
+        ("expires_at", "int64", REQ),
+        ("livemode", "bool", REQ),
+        ("metadata", "Map<string, string>", OPT),
+    ]),
+    ("Customer", &[
+        ("id", "string", REQ),
+        ("object", "string", REQ),
+        ("email", "string", OPT),
+        ("name", "string", OPT),
+        ("phone", "string", OPT),
+        ("description", "string", OPT),
+        ("balance", "int64", OPT),
+        ("currency", "string", OPT),
+        ("delinquent", "bool", OPT),
+        ("deleted", "bool", OPT),
+        ("created", "int64", OPT),
+        ("livemode", "bool", OPT),
+        ("metadata", "Map<string, string>", OPT),
+    ]),
+    ("Subscription", &[
+        ("id", "string", REQ),
+        ("object", "string", REQ),
+        ("customer", "string", REQ),
+        ("status", "SubscriptionStatus", REQ),
+        ("cancel_at_period_end", "bool", REQ),
+        ("canceled_at", "int64", OPT),
+        ("current_period_start", "int64", REQ),
+        ("current_period_end", "int64", REQ),
+        ("trial_end", "int64", OPT),
+        ("latest_invoice", "string", OPT),
+        ("created", "int64", REQ),
+        ("livemode", "bool", REQ),
+        ("metadata", "Map<string, string>", REQ),
+    ]),
+    ("Invoice", &[
+        ("id", "string", REQ),
+        ("object", "string", REQ),
+        ("number", "string", OPT),
+        ("customer", "string", OPT),
+        ("subscription", "string", OPT),
+        ("status", "InvoiceStatus", OPT),
+        ("amount_due", "int64", REQ),
+        ("amount_paid", "int64", REQ),
+        ("amount_remaining", "int64", REQ),
+        ("currency", "string", REQ),
+        ("attempt_count", "int", REQ),
+        ("billing_reason", "string", OPT),
+        ("hosted_invoice_url", "string", OPT),
+        ("invoice_pdf", "string", OPT),
+        ("created", "int64", REQ),
+        ("livemode", "bool", REQ),
+        ("metadata", "Map<string, string>", OPT),
+    ]),
+    ("PaymentIntent", &[
+        ("id", "string", REQ),
+        ("object", "string", REQ),
+        ("amount", "int64", REQ),
+        ("amount_received", "int64", REQ),
+        ("currency", "string", REQ),
+        ("status", "PaymentIntentStatus", REQ),
+        ("customer", "string", OPT),
+        ("description", "string", OPT),
+        ("latest_charge", "string", OPT),
+        ("last_payment_error", "PaymentError", OPT),
+        ("cancellation_reason", "string", OPT),
+        ("created", "int64", REQ),
+        ("livemode", "bool", REQ),
+        ("metadata", "Map<string, string>", REQ),
+    ]),
+    ("PaymentError", &[
+        ("type", "string", REQ),
+        ("code", "string", OPT),
+        ("decline_code", "string", OPT),
+        ("message", "string", OPT),
+        ("param", "string", OPT),
+    ]),
+];
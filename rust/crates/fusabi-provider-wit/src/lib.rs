@@ -0,0 +1,644 @@
+//! WIT Type Provider
+//!
+//! Generates Fusabi types from WebAssembly component interfaces written in
+//! WIT, so hosts that embed WASM components get types for the boundaries
+//! they call.
+//!
+//! # Sources
+//!
+//! Inline WIT, a `.wit` file, or a directory whose `.wit` files form one
+//! package (dependencies under `deps/` are not loaded).
+//!
+//! # Generated Types
+//!
+//! Each interface becomes a module named after the package and interface
+//! (`wasi:http` interface `types` → `Wasi.Http.Types`); without a package
+//! declaration the namespace is used instead. Worlds get a module of their
+//! own, and inline interfaces of a world are nested under it.
+//!
+//! | WIT                                 | Fusabi                               |
+//! |-------------------------------------|--------------------------------------|
+//! | `record`                            | record                               |
+//! | `variant`, `enum`                   | union                                |
+//! | `flags`                             | record of `bool` fields              |
+//! | `resource`                          | record holding an opaque `handle`    |
+//! | `type x = T`                        | inlined                              |
+//! | `s8`–`s32`, `u8`–`u16`              | `int`                                |
+//! | `u32`, `s64`, `u64`                 | `int64`                              |
+//! | `f32`, `f64`                        | `float`                              |
+//! | `char`, `string`                    | `string`                             |
+//! | `list<u8>`                          | `bytes`                              |
+//! | `list<T>`, `option<T>`, `tuple<..>` | `T list`, `T option`, `A * B`        |
+//! | `result<T, E>`                      | `Result<T, E>` (`unit` when omitted) |
+//! | `own<R>`, `borrow<R>`               | `R`                                  |
+//! | `future`, `stream`, `error-context` | `any`                                |
+//!
+//! `kebab-case` field names become `snake_case`.
+//!
+//! Functions with parameters get an argument record (`handle` →
+//! `HandleArgs`; resource methods are prefixed with the resource name). A
+//! world module also has `Import` and `Export` unions naming its items.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_wit::WitProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = WitProvider::new();
+//! let schema = provider.resolve_schema("wit/", &ProviderParams::default())?;
+//! let types = provider.generate_types(&schema, "Plugin")?;
+//! ```
+
+mod parser;
+mod types;
+
+pub use types::{
+    Case, Field, Function, FunctionKind, Interface, Items, PackageName, TypeDef, TypeDefKind,
+    Use, WitFile, WitType, World, WorldItem, WorldItemKind,
+};
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, SourceKind,
+    SourceValidator, StructuredError, TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, DuDef, VariantDef, TypeExpr, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Aliases deeper than this are assumed to be cyclic
+const MAX_ALIAS_DEPTH: usize = 32;
+
+/// Names visible across the package: type aliases and `use` renames
+struct Scope<'a> {
+    aliases: HashMap<String, &'a WitType>,
+    renames: HashMap<&'a str, &'a str>,
+}
+
+/// WIT type provider
+pub struct WitProvider {
+    generator: TypeGenerator,
+}
+
+impl WitProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    /// Parse WIT from string content
+    fn parse_wit(&self, content: &str) -> ProviderResult<WitFile> {
+        parser::parse_wit(content)
+    }
+
+    /// Concatenate the `.wit` files of a package directory
+    fn read_package_dir(&self, dir: &Path, params: &ProviderParams) -> ProviderResult<String> {
+        let entries = std::fs::read_dir(dir)
+            .map_err(|e| StructuredError::io(format!("Failed to read {}", dir.display())).with_source(e))?;
+        let mut paths: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "wit"))
+            .collect();
+        paths.sort();
+
+        if paths.is_empty() {
+            return Err(ProviderError::InvalidSource(format!(
+                "No .wit files in {}",
+                dir.display()
+            )));
+        }
+
+        let mut content = String::new();
+        for path in paths {
+            content.push_str(&read_source(&path.to_string_lossy(), params)?);
+            content.push('\n');
+        }
+        Ok(content)
+    }
+
+    /// Generate types from a parsed WIT package
+    fn generate_from_wit(&self, wit: &WitFile, namespace: &str) -> GeneratedTypes {
+        let mut result = GeneratedTypes::new();
+
+        // Use the package name for modules if present
+        let base: Vec<String> = match &wit.package {
+            Some(package) => std::iter::once(package.namespace.as_str())
+                .chain(package.name.split(':'))
+                .map(|segment| self.generator.naming.apply(segment))
+                .collect(),
+            None => vec![namespace.to_string()],
+        };
+
+        let scope = Scope {
+            aliases: wit.build_alias_map(),
+            renames: wit
+                .scopes()
+                .into_iter()
+                .flat_map(|(_, items)| &items.uses)
+                .flat_map(|u| &u.names)
+                .filter_map(|(name, alias)| alias.as_deref().map(|alias| (alias, name.as_str())))
+                .collect(),
+        };
+
+        let module_path = |names: &[&str]| -> Vec<String> {
+            base.iter()
+                .cloned()
+                .chain(names.iter().map(|name| self.generator.naming.apply(name)))
+                .collect()
+        };
+
+        for interface in &wit.interfaces {
+            let mut module = GeneratedModule::new(module_path(&[&interface.name]));
+            module.types = self.items_types(&interface.items, &scope);
+            if !module.types.is_empty() {
+                result.modules.push(module);
+            }
+        }
+
+        for world in &wit.worlds {
+            let mut module = GeneratedModule::new(module_path(&[&world.name]));
+            module.types = self.items_types(&world.items, &scope);
+            let mut inline_modules = Vec::new();
+
+            for item in world.imports.iter().chain(&world.exports) {
+                match &item.kind {
+                    WorldItemKind::Function(function) => {
+                        module.types.extend(self.args_record("", function, &scope));
+                    }
+                    WorldItemKind::Interface(items) => {
+                        let mut inline = GeneratedModule::new(module_path(&[&world.name, &item.name]));
+                        inline.types = self.items_types(items, &scope);
+                        if !inline.types.is_empty() {
+                            inline_modules.push(inline);
+                        }
+                    }
+                    WorldItemKind::Path(_) => {}
+                }
+            }
+
+            for (name, items) in [("Import", &world.imports), ("Export", &world.exports)] {
+                if items.is_empty() {
+                    continue;
+                }
+                module.types.push(TypeDefinition::Du(DuDef {
+                    name: name.to_string(),
+                    variants: items
+                        .iter()
+                        .map(|item| VariantDef::new_simple(self.generator.naming.apply(&item.name)))
+                        .collect(),
+                }));
+            }
+
+            if !module.types.is_empty() {
+                result.modules.push(module);
+            }
+            result.modules.extend(inline_modules);
+        }
+
+        result
+    }
+
+    /// Types for the definitions and functions of an interface or world
+    fn items_types(&self, items: &Items, scope: &Scope) -> Vec<TypeDefinition> {
+        let mut types = Vec::new();
+
+        for type_def in &items.types {
+            let name = self.generator.naming.apply(&type_def.name);
+            match &type_def.kind {
+                // Aliases are inlined where they are used
+                TypeDefKind::Alias(_) => {}
+                TypeDefKind::Record(fields) => types.push(TypeDefinition::Record(RecordDef {
+                    name,
+                    fields: self.fields(fields, scope),
+                })),
+                TypeDefKind::Variant(cases) => types.push(TypeDefinition::Du(DuDef {
+                    name,
+                    variants: cases
+                        .iter()
+                        .map(|case| {
+                            let case_name = self.generator.naming.apply(&case.name);
+                            match &case.payload {
+                                Some(payload) => VariantDef::new(case_name, vec![self.type_expr(payload, scope)]),
+                                None => VariantDef::new_simple(case_name),
+                            }
+                        })
+                        .collect(),
+                })),
+                TypeDefKind::Enum(cases) => types.push(TypeDefinition::Du(DuDef {
+                    name,
+                    variants: cases
+                        .iter()
+                        .map(|case| VariantDef::new_simple(self.generator.naming.apply(case)))
+                        .collect(),
+                })),
+                TypeDefKind::Flags(flags) => types.push(TypeDefinition::Record(RecordDef {
+                    name,
+                    fields: flags
+                        .iter()
+                        .map(|flag| (field_name(flag), TypeShape::named("bool").into()))
+                        .collect(),
+                })),
+                TypeDefKind::Resource(methods) => {
+                    types.push(TypeDefinition::Record(RecordDef {
+                        name: name.clone(),
+                        fields: vec![("handle".to_string(), TypeShape::named("int").into())],
+                    }));
+                    for method in methods {
+                        types.extend(self.args_record(&name, method, scope));
+                    }
+                }
+            }
+        }
+
+        for function in &items.functions {
+            types.extend(self.args_record("", function, scope));
+        }
+
+        types
+    }
+
+    /// Argument record of a function with parameters
+    fn args_record(&self, prefix: &str, function: &Function, scope: &Scope) -> Option<TypeDefinition> {
+        if function.params.is_empty() {
+            return None;
+        }
+        Some(TypeDefinition::Record(RecordDef {
+            name: format!("{}{}Args", prefix, self.generator.naming.apply(&function.name)),
+            fields: self.fields(&function.params, scope),
+        }))
+    }
+
+    fn fields(&self, fields: &[Field], scope: &Scope) -> Vec<(String, TypeExpr)> {
+        fields
+            .iter()
+            .map(|field| (field_name(&field.name), self.type_expr(&field.field_type, scope)))
+            .collect()
+    }
+
+    /// Convert a WIT type to a Fusabi TypeExpr
+    fn type_expr(&self, wit_type: &WitType, scope: &Scope) -> TypeExpr {
+        self.shape(wit_type, scope, 0).into()
+    }
+
+    fn shape(&self, wit_type: &WitType, scope: &Scope, depth: usize) -> TypeShape {
+        let named = |name: &str| TypeShape::Named(name.to_string());
+        match wit_type {
+            WitType::Bool => named("bool"),
+            WitType::S8 | WitType::S16 | WitType::S32 | WitType::U8 | WitType::U16 => named("int"),
+            WitType::U32 | WitType::S64 | WitType::U64 => named("int64"),
+            WitType::F32 | WitType::F64 => named("float"),
+            WitType::Char | WitType::String => named("string"),
+            WitType::List(element) if **element == WitType::U8 => named("bytes"),
+            WitType::List(element) => TypeShape::List(Box::new(self.shape(element, scope, depth))),
+            WitType::Option(inner) => TypeShape::Option(Box::new(self.shape(inner, scope, depth))),
+            WitType::Result(ok, err) => {
+                let part = |t: &Option<Box<WitType>>| match t {
+                    Some(t) => self.shape(t, scope, depth).to_string(),
+                    None => "unit".to_string(),
+                };
+                TypeShape::Named(format!("Result<{}, {}>", part(ok), part(err)))
+            }
+            WitType::Tuple(items) if items.is_empty() => named("unit"),
+            WitType::Tuple(items) => {
+                TypeShape::Tuple(items.iter().map(|t| self.shape(t, scope, depth)).collect())
+            }
+            WitType::Handle(resource) => TypeShape::Named(self.generator.naming.apply(resource)),
+            WitType::Future(_) | WitType::Stream(_) | WitType::ErrorContext => named("any"),
+            WitType::Named(name) => match scope.aliases.get(name) {
+                Some(target) if depth < MAX_ALIAS_DEPTH => self.shape(target, scope, depth + 1),
+                _ => {
+                    // Names brought in by `use x.{a as b}` refer to `a`
+                    let name = scope.renames.get(name.as_str()).copied().unwrap_or(name);
+                    TypeShape::Named(self.generator.naming.apply(name))
+                }
+            },
+        }
+    }
+}
+
+/// Field name for a `kebab-case` WIT name
+fn field_name(name: &str) -> String {
+    name.replace('-', "_")
+}
+
+impl Default for WitProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypeProvider for WitProvider {
+    fn name(&self) -> &str {
+        "WitProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        // Paths and URLs never contain braces or semicolons; WIT always does
+        let wit_content = if source.contains('{') || source.contains(';') {
+            source.to_string()
+        } else if Path::new(source).is_dir() {
+            self.read_package_dir(Path::new(source), params)?
+        } else {
+            read_source(source, params)?
+        };
+
+        // Parse the package to validate it
+        let _wit_file = self.parse_wit(&wit_content)?;
+
+        Ok(Schema::Custom(wit_content))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        match schema {
+            Schema::Custom(wit_content) => {
+                let wit = self.parse_wit(wit_content)?;
+                Ok(self.generate_from_wit(&wit, namespace))
+            }
+            _ => Err(ProviderError::ParseError("Expected WIT schema".to_string())),
+        }
+    }
+}
+
+impl SourceValidator for WitProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(wit_content) = schema else {
+            return Err(ProviderError::ParseError("Expected WIT schema".to_string()));
+        };
+
+        let wit = self.parse_wit(wit_content)?;
+        let mut diagnostics = Vec::new();
+
+        for (label, items) in wit.scopes() {
+            for use_item in items.uses.iter().filter(|u| u.path.contains(':')) {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        "use-unresolved",
+                        format!("Interface `{}` is not in this package; its types will be unresolved", use_item.path),
+                    )
+                    .at(label.clone()),
+                );
+            }
+
+            for type_def in &items.types {
+                let location = format!("{}.{}", label, type_def.name);
+                if let TypeDefKind::Resource(_) = type_def.kind {
+                    diagnostics.push(
+                        Diagnostic::info(
+                            "resource-handle",
+                            format!("Resource `{}` is generated as an opaque handle", type_def.name),
+                        )
+                        .at(location.clone()),
+                    );
+                }
+                if let Some(version) = &type_def.deprecated {
+                    diagnostics.push(
+                        Diagnostic::info("deprecated", format!("`{}` is deprecated since {}", type_def.name, version))
+                            .at(location),
+                    );
+                }
+            }
+
+            for function in &items.functions {
+                if let Some(version) = &function.deprecated {
+                    diagnostics.push(
+                        Diagnostic::info("deprecated", format!("`{}` is deprecated since {}", function.name, version))
+                            .at(format!("{}.{}", label, function.name)),
+                    );
+                }
+            }
+
+            for (location, wit_type) in items.type_uses(&label) {
+                wit_type.walk(&mut |t| {
+                    let diagnostic = match t {
+                        WitType::U64 => Diagnostic::info(
+                            "lossy-conversion",
+                            "`u64` is generated as `int64`; values above its range wrap",
+                        ),
+                        WitType::Future(_) | WitType::Stream(_) | WitType::ErrorContext => Diagnostic::info(
+                            "coerced-any",
+                            "Async types (`future`, `stream`, `error-context`) are generated as `any`",
+                        ),
+                        _ => return,
+                    };
+                    diagnostics.push(diagnostic.at(location.clone()));
+                });
+            }
+        }
+
+        for world in &wit.worlds {
+            for include in &world.includes {
+                if !wit.worlds.iter().any(|w| &w.name == include) {
+                    diagnostics.push(
+                        Diagnostic::warning(
+                            "include-unresolved",
+                            format!("Included world `{}` is not in this package; its items are not generated", include),
+                        )
+                        .at(world.name.clone()),
+                    );
+                }
+            }
+        }
+
+        Ok(diagnostics)
+    }
+}
+
+impl DescribedProvider for WitProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .source(SourceKind::Directory)
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    const WIT: &str = r#"
+        package example:shop@1.0.0;
+
+        interface orders {
+          type order-id = string;
+
+          record order {
+            id: order-id,
+            line-items: list<line-item>,
+            placed-at: u64,
+            note: option<string>,
+            receipt: list<u8>,
+          }
+
+          record line-item { sku: string, quantity: u32 }
+
+          variant status { pending, shipped(tuple<string, u32>), cancelled(option<string>) }
+
+          enum priority { low, high }
+
+          flags tags { gift, express-delivery }
+
+          resource cart {
+            constructor(owner: string);
+            add: func(item: line-item) -> result<_, string>;
+            total: func() -> f64;
+          }
+
+          place: func(id: order-id, cart: borrow<cart>) -> result<order, status>;
+          list-all: func() -> list<order>;
+        }
+
+        world storefront {
+          use orders.{order as shop-order};
+          import orders;
+          import notify: func(message: string);
+          export checkout: func(order: shop-order) -> bool;
+          export admin: interface {
+            reset: func(confirm: bool);
+          }
+        }
+    "#;
+
+    fn generate(wit: &str) -> GeneratedTypes {
+        let provider = WitProvider::new();
+        let schema = provider.resolve_schema(wit, &ProviderParams::default()).unwrap();
+        provider.generate_types(&schema, "Test").unwrap()
+    }
+
+    fn fields(module: &GeneratedModule, name: &str) -> Vec<(String, String)> {
+        module
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+            .fields
+            .iter()
+            .map(|(name, ty)| (name.clone(), ty.to_string()))
+            .collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect()
+    }
+
+    #[test]
+    fn test_generate_interface() {
+        let types = generate(WIT);
+        let module = &types.modules[0];
+        assert_eq!(module.path, vec!["Example", "Shop", "Orders"]);
+
+        assert_eq!(
+            fields(module, "Order"),
+            pairs(&[
+                ("id", "string"),
+                ("line_items", "LineItem list"),
+                ("placed_at", "int64"),
+                ("note", "string option"),
+                ("receipt", "bytes"),
+            ])
+        );
+        assert_eq!(fields(module, "Tags"), pairs(&[("gift", "bool"), ("express_delivery", "bool")]));
+        assert_eq!(fields(module, "Cart"), pairs(&[("handle", "int")]));
+        assert_eq!(fields(module, "CartConstructorArgs"), pairs(&[("owner", "string")]));
+        assert_eq!(fields(module, "CartAddArgs"), pairs(&[("item", "LineItem")]));
+        assert_eq!(fields(module, "PlaceArgs"), pairs(&[("id", "string"), ("cart", "Cart")]));
+
+        let TypeDefinition::Du(status) = &module.types[2] else {
+            panic!("Expected DU type");
+        };
+        assert_eq!(status.name, "Status");
+        assert_eq!(status.variants[1].name, "Shipped");
+        assert_eq!(status.variants[1].fields[0].to_string(), "string * int64");
+
+        let names: Vec<&str> = module
+            .types
+            .iter()
+            .map(|t| match t {
+                TypeDefinition::Record(r) => r.name.as_str(),
+                TypeDefinition::Du(d) => d.name.as_str(),
+            })
+            .collect();
+        assert!(!names.contains(&"OrderId"));
+        assert!(!names.contains(&"ListAllArgs"));
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/wit.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_generate_world() {
+        let types = generate(WIT);
+        let paths: Vec<Vec<String>> = types.modules.iter().map(|m| m.path.clone()).collect();
+        assert_eq!(
+            paths[1..],
+            [
+                vec!["Example", "Shop", "Storefront"],
+                vec!["Example", "Shop", "Storefront", "Admin"],
+            ]
+        );
+
+        let world = &types.modules[1];
+        assert_eq!(fields(world, "NotifyArgs"), pairs(&[("message", "string")]));
+        assert_eq!(fields(world, "CheckoutArgs"), pairs(&[("order", "Order")]));
+
+        let TypeDefinition::Du(exports) = world.types.last().unwrap() else {
+            panic!("Expected Export union");
+        };
+        assert_eq!(exports.name, "Export");
+        let cases: Vec<&str> = exports.variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(cases, ["Checkout", "Admin"]);
+
+        let admin = &types.modules[2];
+        assert_eq!(fields(admin, "ResetArgs"), pairs(&[("confirm", "bool")]));
+    }
+
+    #[test]
+    fn test_namespace_without_package() {
+        let types = generate("interface greeter { greet: func(name: string) -> string; }");
+        assert_eq!(types.modules[0].path, vec!["Test", "Greeter"]);
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let provider = WitProvider::new();
+        let wit = r#"
+            interface io {
+              use wasi:io/streams@0.2.0.{input-stream};
+
+              @deprecated(version = 0.3.0)
+              read: func(stream: borrow<input-stream>, len: u64) -> stream<u8>;
+            }
+
+            world app {
+              include wasi:cli/command@0.2.0;
+            }
+        "#;
+
+        let report = provider.validate_source(wit, &ProviderParams::default());
+        assert!(report.is_valid());
+
+        let codes: Vec<(&str, Option<&str>)> = report
+            .diagnostics
+            .iter()
+            .map(|d| (d.code.as_str(), d.location.as_deref()))
+            .collect();
+        assert!(codes.contains(&("use-unresolved", Some("io"))));
+        assert!(codes.contains(&("deprecated", Some("io.read"))));
+        assert!(codes.contains(&("lossy-conversion", Some("io.read.len"))));
+        assert!(codes.contains(&("coerced-any", Some("io.read"))));
+        assert!(codes.contains(&("include-unresolved", Some("app"))));
+    }
+}
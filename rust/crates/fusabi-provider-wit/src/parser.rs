@@ -0,0 +1,902 @@
+//! WIT parser
+//!
+//! Handles the WIT syntax of the component model: package declarations,
+//! interfaces, worlds, `use` statements, type definitions (aliases, records,
+//! variants, enums, flags and resources) and functions. Feature gates such as
+//! `@since` and `@unstable` are skipped; `@deprecated` is kept.
+
+use crate::types::{
+    Case, Field, Function, FunctionKind, Interface, Items, PackageName, TypeDef, TypeDefKind,
+    Use, WitFile, WitType, World, WorldItem, WorldItemKind,
+};
+use fusabi_type_providers::{ProviderError, ProviderResult};
+
+/// Parse WIT from string content
+pub fn parse_wit(content: &str) -> ProviderResult<WitFile> {
+    let mut parser = Parser::new(content);
+    parser.parse_file()
+}
+
+/// Simple WIT parser
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    // Symbols
+    LeftBrace,
+    RightBrace,
+    LeftParen,
+    RightParen,
+    LeftAngle,
+    RightAngle,
+    Semicolon,
+    Colon,
+    Equals,
+    Comma,
+    Slash,
+    Dot,
+    Star,
+    Arrow,
+    At,
+
+    // Literals
+    Identifier(String),
+    Number(String),
+    /// `@1.2.3` after a package or interface path
+    Version(String),
+
+    // End of file
+    Eof,
+}
+
+impl Parser {
+    fn new(content: &str) -> Self {
+        let tokens = tokenize(content);
+        Self { tokens, pos: 0 }
+    }
+
+    fn current(&self) -> &Token {
+        self.tokens.get(self.pos).unwrap_or(&Token::Eof)
+    }
+
+    fn peek(&self, offset: usize) -> &Token {
+        self.tokens.get(self.pos + offset).unwrap_or(&Token::Eof)
+    }
+
+    fn advance(&mut self) {
+        if self.pos < self.tokens.len() {
+            self.pos += 1;
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.current(), Token::Identifier(s) if s == keyword)
+    }
+
+    fn expect(&mut self, expected: Token) -> ProviderResult<()> {
+        if self.current() == &expected {
+            self.advance();
+            Ok(())
+        } else {
+            Err(ProviderError::ParseError(format!(
+                "Expected {:?}, got {:?}",
+                expected,
+                self.current()
+            )))
+        }
+    }
+
+    fn expect_identifier(&mut self) -> ProviderResult<String> {
+        match self.current() {
+            Token::Identifier(s) => {
+                let result = s.clone();
+                self.advance();
+                Ok(result)
+            }
+            _ => Err(ProviderError::ParseError(format!(
+                "Expected identifier, got {:?}",
+                self.current()
+            ))),
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> ProviderResult<()> {
+        if self.is_keyword(keyword) {
+            self.advance();
+            Ok(())
+        } else {
+            Err(ProviderError::ParseError(format!(
+                "Expected `{}`, got {:?}",
+                keyword,
+                self.current()
+            )))
+        }
+    }
+
+    /// Skip an optional `,` list separator
+    fn skip_comma(&mut self) {
+        if self.current() == &Token::Comma {
+            self.advance();
+        }
+    }
+
+    /// Skip a balanced group starting at the current open token
+    fn skip_group(&mut self) {
+        let mut depth = 0;
+        loop {
+            match self.current() {
+                Token::LeftParen | Token::LeftBrace => depth += 1,
+                Token::RightParen | Token::RightBrace => depth -= 1,
+                Token::Eof => return,
+                _ => {}
+            }
+            self.advance();
+            if depth <= 0 {
+                return;
+            }
+        }
+    }
+
+    /// Skip feature gates, returning the version of any `@deprecated`
+    fn parse_gates(&mut self) -> Option<String> {
+        let mut deprecated = None;
+        while self.current() == &Token::At {
+            self.advance();
+            let is_deprecated = self.is_keyword("deprecated");
+            self.advance();
+            if self.current() != &Token::LeftParen {
+                continue;
+            }
+            let start = self.pos;
+            self.skip_group();
+            if is_deprecated {
+                // `@deprecated(version = 0.2.0)`
+                let version = self.tokens[start..self.pos].iter().rev().find_map(|t| match t {
+                    Token::Number(v) | Token::Identifier(v) => Some(v.clone()),
+                    _ => None,
+                });
+                deprecated = Some(version.unwrap_or_default());
+            }
+        }
+        deprecated
+    }
+
+    fn parse_file(&mut self) -> ProviderResult<WitFile> {
+        let mut file = WitFile::new();
+        // Depth of `package name { ... }` blocks
+        let mut nested = 0usize;
+
+        while self.current() != &Token::Eof {
+            self.parse_gates();
+
+            if self.current() == &Token::RightBrace && nested > 0 {
+                self.advance();
+                nested -= 1;
+                continue;
+            }
+
+            let keyword = self.expect_identifier()?;
+            match keyword.as_str() {
+                "package" => {
+                    let package = self.parse_package_name()?;
+                    if file.package.is_none() {
+                        file.package = Some(package);
+                    }
+                    if self.current() == &Token::LeftBrace {
+                        self.advance();
+                        nested += 1;
+                    } else {
+                        self.expect(Token::Semicolon)?;
+                    }
+                }
+                "interface" => {
+                    let name = self.expect_identifier()?;
+                    let items = self.parse_interface_body()?;
+                    file.interfaces.push(Interface { name, items });
+                }
+                "world" => file.worlds.push(self.parse_world()?),
+                // Top-level `use wasi:io/streams as streams;` only names an
+                // interface for other files
+                "use" => {
+                    while !matches!(self.current(), Token::Semicolon | Token::Eof) {
+                        self.advance();
+                    }
+                    self.expect(Token::Semicolon)?;
+                }
+                _ => {
+                    return Err(ProviderError::ParseError(format!(
+                        "Unexpected `{}` at top level",
+                        keyword
+                    )));
+                }
+            }
+        }
+
+        Ok(file)
+    }
+
+    /// Parse `namespace:name[@version]`
+    fn parse_package_name(&mut self) -> ProviderResult<PackageName> {
+        let namespace = self.expect_identifier()?;
+        self.expect(Token::Colon)?;
+        let mut name = self.expect_identifier()?;
+        // Nested namespaces: `wasi:http:types`
+        while self.current() == &Token::Colon {
+            self.advance();
+            name = format!("{}:{}", name, self.expect_identifier()?);
+        }
+        let version = match self.current().clone() {
+            Token::Version(version) => {
+                self.advance();
+                Some(version)
+            }
+            _ => None,
+        };
+        Ok(PackageName { namespace, name, version })
+    }
+
+    /// Parse an interface path: `types`, `wasi:io/streams@0.2.0`
+    fn parse_path(&mut self) -> ProviderResult<String> {
+        let mut path = self.expect_identifier()?;
+        loop {
+            match self.current().clone() {
+                Token::Colon => path.push(':'),
+                Token::Slash => path.push('/'),
+                Token::Version(version) => {
+                    self.advance();
+                    path.push('@');
+                    path.push_str(&version);
+                    return Ok(path);
+                }
+                _ => return Ok(path),
+            }
+            self.advance();
+            path.push_str(&self.expect_identifier()?);
+        }
+    }
+
+    fn parse_interface_body(&mut self) -> ProviderResult<Items> {
+        self.expect(Token::LeftBrace)?;
+        let mut items = Items::default();
+
+        while self.current() != &Token::RightBrace && self.current() != &Token::Eof {
+            let deprecated = self.parse_gates();
+            if !self.parse_item(&mut items, deprecated)? {
+                return Err(ProviderError::ParseError(format!(
+                    "Unexpected {:?} in interface",
+                    self.current()
+                )));
+            }
+        }
+
+        self.expect(Token::RightBrace)?;
+        Ok(items)
+    }
+
+    fn parse_world(&mut self) -> ProviderResult<World> {
+        let name = self.expect_identifier()?;
+        self.expect(Token::LeftBrace)?;
+
+        let mut world = World {
+            name,
+            items: Items::default(),
+            imports: Vec::new(),
+            exports: Vec::new(),
+            includes: Vec::new(),
+        };
+
+        while self.current() != &Token::RightBrace && self.current() != &Token::Eof {
+            let deprecated = self.parse_gates();
+            if self.is_keyword("import") || self.is_keyword("export") {
+                let import = self.is_keyword("import");
+                self.advance();
+                let item = self.parse_world_item(deprecated)?;
+                if import {
+                    world.imports.push(item);
+                } else {
+                    world.exports.push(item);
+                }
+            } else if self.is_keyword("include") {
+                self.advance();
+                world.includes.push(self.parse_path()?);
+                // `include a with { x as y }`
+                if self.is_keyword("with") {
+                    self.advance();
+                    self.skip_group();
+                }
+                self.expect(Token::Semicolon)?;
+            } else if !self.parse_item(&mut world.items, deprecated)? {
+                return Err(ProviderError::ParseError(format!(
+                    "Unexpected {:?} in world `{}`",
+                    self.current(),
+                    world.name
+                )));
+            }
+        }
+
+        self.expect(Token::RightBrace)?;
+        Ok(world)
+    }
+
+    /// Parse the target of an `import` or `export`
+    fn parse_world_item(&mut self, deprecated: Option<String>) -> ProviderResult<WorldItem> {
+        let labeled = matches!(self.peek(1), Token::Colon)
+            && matches!(self.peek(2), Token::Identifier(k) if k == "func" || k == "async" || k == "interface");
+
+        if !labeled {
+            let path = self.parse_path()?;
+            self.expect(Token::Semicolon)?;
+            let name = path
+                .split('@')
+                .next()
+                .and_then(|p| p.rsplit(['/', ':']).next())
+                .unwrap_or(&path)
+                .to_string();
+            return Ok(WorldItem { name, kind: WorldItemKind::Path(path) });
+        }
+
+        let name = self.expect_identifier()?;
+        self.expect(Token::Colon)?;
+        if self.is_keyword("interface") {
+            self.advance();
+            let items = self.parse_interface_body()?;
+            Ok(WorldItem { name, kind: WorldItemKind::Interface(items) })
+        } else {
+            let function = self.parse_function(name.clone(), FunctionKind::Free, deprecated)?;
+            self.expect(Token::Semicolon)?;
+            Ok(WorldItem { name, kind: WorldItemKind::Function(function) })
+        }
+    }
+
+    /// Parse an item shared by interfaces and worlds; returns `false` if the
+    /// current token does not start one
+    fn parse_item(&mut self, items: &mut Items, deprecated: Option<String>) -> ProviderResult<bool> {
+        let Token::Identifier(keyword) = self.current().clone() else {
+            return Ok(false);
+        };
+
+        // `name: func(...)`
+        if self.peek(1) == &Token::Colon {
+            self.advance();
+            self.advance();
+            let function = self.parse_function(keyword, FunctionKind::Free, deprecated)?;
+            self.expect(Token::Semicolon)?;
+            items.functions.push(function);
+            return Ok(true);
+        }
+
+        let kind = match keyword.as_str() {
+            "use" => {
+                self.advance();
+                items.uses.push(self.parse_use()?);
+                return Ok(true);
+            }
+            "type" => {
+                self.advance();
+                let name = self.expect_identifier()?;
+                self.expect(Token::Equals)?;
+                let target = self.parse_type()?;
+                self.expect(Token::Semicolon)?;
+                items.types.push(TypeDef { name, kind: TypeDefKind::Alias(target), deprecated });
+                return Ok(true);
+            }
+            "record" | "variant" | "enum" | "flags" | "resource" => keyword,
+            _ => return Ok(false),
+        };
+
+        self.advance();
+        let name = self.expect_identifier()?;
+
+        // `resource name;` declares a resource without methods
+        if kind == "resource" && self.current() == &Token::Semicolon {
+            self.advance();
+            items.types.push(TypeDef { name, kind: TypeDefKind::Resource(Vec::new()), deprecated });
+            return Ok(true);
+        }
+
+        self.expect(Token::LeftBrace)?;
+        let type_kind = match kind.as_str() {
+            "record" => {
+                let mut fields = Vec::new();
+                while self.current() != &Token::RightBrace && self.current() != &Token::Eof {
+                    self.parse_gates();
+                    fields.push(self.parse_field()?);
+                    self.skip_comma();
+                }
+                TypeDefKind::Record(fields)
+            }
+            "variant" => {
+                let mut cases = Vec::new();
+                while self.current() != &Token::RightBrace && self.current() != &Token::Eof {
+                    self.parse_gates();
+                    let case_name = self.expect_identifier()?;
+                    let payload = if self.current() == &Token::LeftParen {
+                        self.advance();
+                        let payload = self.parse_type()?;
+                        self.expect(Token::RightParen)?;
+                        Some(payload)
+                    } else {
+                        None
+                    };
+                    cases.push(Case { name: case_name, payload });
+                    self.skip_comma();
+                }
+                TypeDefKind::Variant(cases)
+            }
+            "enum" | "flags" => {
+                let mut names = Vec::new();
+                while self.current() != &Token::RightBrace && self.current() != &Token::Eof {
+                    self.parse_gates();
+                    names.push(self.expect_identifier()?);
+                    self.skip_comma();
+                }
+                if kind == "enum" {
+                    TypeDefKind::Enum(names)
+                } else {
+                    TypeDefKind::Flags(names)
+                }
+            }
+            _ => TypeDefKind::Resource(self.parse_resource_methods()?),
+        };
+        self.expect(Token::RightBrace)?;
+
+        items.types.push(TypeDef { name, kind: type_kind, deprecated });
+        Ok(true)
+    }
+
+    /// Parse `use path.{name, name as alias};`
+    fn parse_use(&mut self) -> ProviderResult<Use> {
+        let path = self.parse_path()?;
+        self.expect(Token::Dot)?;
+        self.expect(Token::LeftBrace)?;
+
+        let mut names = Vec::new();
+        while self.current() != &Token::RightBrace && self.current() != &Token::Eof {
+            let name = self.expect_identifier()?;
+            let alias = if self.is_keyword("as") {
+                self.advance();
+                Some(self.expect_identifier()?)
+            } else {
+                None
+            };
+            names.push((name, alias));
+            self.skip_comma();
+        }
+
+        self.expect(Token::RightBrace)?;
+        self.expect(Token::Semicolon)?;
+        Ok(Use { path, names })
+    }
+
+    fn parse_resource_methods(&mut self) -> ProviderResult<Vec<Function>> {
+        let mut methods = Vec::new();
+        while self.current() != &Token::RightBrace && self.current() != &Token::Eof {
+            let deprecated = self.parse_gates();
+            let function = if self.is_keyword("constructor") {
+                self.advance();
+                let params = self.parse_params()?;
+                let result = self.parse_result()?;
+                Function {
+                    name: "constructor".to_string(),
+                    kind: FunctionKind::Constructor,
+                    params,
+                    result,
+                    deprecated,
+                }
+            } else {
+                let name = self.expect_identifier()?;
+                self.expect(Token::Colon)?;
+                let kind = if self.is_keyword("static") {
+                    self.advance();
+                    FunctionKind::Static
+                } else {
+                    FunctionKind::Method
+                };
+                self.parse_function(name, kind, deprecated)?
+            };
+            self.expect(Token::Semicolon)?;
+            methods.push(function);
+        }
+        Ok(methods)
+    }
+
+    /// Parse `[async] func(params) [-> result]`
+    fn parse_function(
+        &mut self,
+        name: String,
+        kind: FunctionKind,
+        deprecated: Option<String>,
+    ) -> ProviderResult<Function> {
+        if self.is_keyword("async") {
+            self.advance();
+        }
+        self.expect_keyword("func")?;
+        let params = self.parse_params()?;
+        let result = self.parse_result()?;
+        Ok(Function { name, kind, params, result, deprecated })
+    }
+
+    fn parse_params(&mut self) -> ProviderResult<Vec<Field>> {
+        self.expect(Token::LeftParen)?;
+        let mut params = Vec::new();
+        while self.current() != &Token::RightParen && self.current() != &Token::Eof {
+            params.push(self.parse_field()?);
+            self.skip_comma();
+        }
+        self.expect(Token::RightParen)?;
+        Ok(params)
+    }
+
+    /// Parse an optional `-> T`; named results `-> (a: T, b: U)` become a
+    /// tuple
+    fn parse_result(&mut self) -> ProviderResult<Option<WitType>> {
+        if self.current() != &Token::Arrow {
+            return Ok(None);
+        }
+        self.advance();
+
+        if self.current() != &Token::LeftParen {
+            return Ok(Some(self.parse_type()?));
+        }
+        let mut types: Vec<WitType> = self.parse_params()?.into_iter().map(|f| f.field_type).collect();
+        Ok(match types.len() {
+            0 => None,
+            1 => types.pop(),
+            _ => Some(WitType::Tuple(types)),
+        })
+    }
+
+    /// Parse `name: type`
+    fn parse_field(&mut self) -> ProviderResult<Field> {
+        let name = self.expect_identifier()?;
+        self.expect(Token::Colon)?;
+        let field_type = self.parse_type()?;
+        Ok(Field { name, field_type })
+    }
+
+    fn parse_type(&mut self) -> ProviderResult<WitType> {
+        let name = self.expect_identifier()?;
+
+        let wit_type = match name.as_str() {
+            "list" => {
+                self.expect(Token::LeftAngle)?;
+                let element = self.parse_type()?;
+                // Fixed-size lists: `list<u8, 4>`
+                if self.current() == &Token::Comma {
+                    self.advance();
+                    self.advance();
+                }
+                self.expect(Token::RightAngle)?;
+                WitType::List(Box::new(element))
+            }
+            "option" => {
+                self.expect(Token::LeftAngle)?;
+                let inner = self.parse_type()?;
+                self.expect(Token::RightAngle)?;
+                WitType::Option(Box::new(inner))
+            }
+            "result" => {
+                if self.current() != &Token::LeftAngle {
+                    return Ok(WitType::Result(None, None));
+                }
+                self.advance();
+                let ok = self.parse_optional_type()?;
+                let err = if self.current() == &Token::Comma {
+                    self.advance();
+                    self.parse_optional_type()?
+                } else {
+                    None
+                };
+                self.expect(Token::RightAngle)?;
+                WitType::Result(ok, err)
+            }
+            "tuple" => {
+                self.expect(Token::LeftAngle)?;
+                let mut items = Vec::new();
+                while self.current() != &Token::RightAngle && self.current() != &Token::Eof {
+                    items.push(self.parse_type()?);
+                    self.skip_comma();
+                }
+                self.expect(Token::RightAngle)?;
+                WitType::Tuple(items)
+            }
+            "own" | "borrow" => {
+                self.expect(Token::LeftAngle)?;
+                let resource = self.expect_identifier()?;
+                self.expect(Token::RightAngle)?;
+                WitType::Handle(resource)
+            }
+            "future" | "stream" => {
+                let inner = if self.current() == &Token::LeftAngle {
+                    self.advance();
+                    let inner = self.parse_type()?;
+                    self.expect(Token::RightAngle)?;
+                    Some(Box::new(inner))
+                } else {
+                    None
+                };
+                if name == "future" {
+                    WitType::Future(inner)
+                } else {
+                    WitType::Stream(inner)
+                }
+            }
+            _ => WitType::from_str(&name),
+        };
+
+        Ok(wit_type)
+    }
+
+    /// A result payload type, where `_` means none
+    fn parse_optional_type(&mut self) -> ProviderResult<Option<Box<WitType>>> {
+        if self.is_keyword("_") {
+            self.advance();
+            return Ok(None);
+        }
+        Ok(Some(Box::new(self.parse_type()?)))
+    }
+}
+
+/// Tokenize WIT content
+fn tokenize(content: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = content.chars().peekable();
+
+    while let Some(&ch) = chars.peek() {
+        match ch {
+            ' ' | '\t' | '\n' | '\r' => {
+                chars.next();
+            }
+            '/' => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    // Line and doc comments
+                    for c in chars.by_ref() {
+                        if c == '\n' {
+                            break;
+                        }
+                    }
+                } else if chars.peek() == Some(&'*') {
+                    // Block comments nest
+                    chars.next();
+                    let mut depth = 1;
+                    while let Some(c) = chars.next() {
+                        match (c, chars.peek()) {
+                            ('*', Some('/')) => {
+                                chars.next();
+                                depth -= 1;
+                                if depth == 0 {
+                                    break;
+                                }
+                            }
+                            ('/', Some('*')) => {
+                                chars.next();
+                                depth += 1;
+                            }
+                            _ => {}
+                        }
+                    }
+                } else {
+                    tokens.push(Token::Slash);
+                }
+            }
+            '-' => {
+                chars.next();
+                if chars.peek() == Some(&'>') {
+                    chars.next();
+                    tokens.push(Token::Arrow);
+                }
+            }
+            '@' => {
+                chars.next();
+                if chars.peek().is_some_and(|c| c.is_ascii_digit()) {
+                    let mut version = String::new();
+                    while let Some(&c) = chars.peek() {
+                        if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+') {
+                            version.push(c);
+                            chars.next();
+                        } else {
+                            break;
+                        }
+                    }
+                    // `use a:b/c@1.0.0.{x}`: the dot before the brace is not
+                    // part of the version
+                    let trailing_dot = version.ends_with('.');
+                    if trailing_dot {
+                        version.pop();
+                    }
+                    tokens.push(Token::Version(version));
+                    if trailing_dot {
+                        tokens.push(Token::Dot);
+                    }
+                } else {
+                    tokens.push(Token::At);
+                }
+            }
+            '{' | '}' | '(' | ')' | '<' | '>' | ';' | ':' | '=' | ',' | '.' | '*' => {
+                tokens.push(match ch {
+                    '{' => Token::LeftBrace,
+                    '}' => Token::RightBrace,
+                    '(' => Token::LeftParen,
+                    ')' => Token::RightParen,
+                    '<' => Token::LeftAngle,
+                    '>' => Token::RightAngle,
+                    ';' => Token::Semicolon,
+                    ':' => Token::Colon,
+                    '=' => Token::Equals,
+                    ',' => Token::Comma,
+                    '.' => Token::Dot,
+                    _ => Token::Star,
+                });
+                chars.next();
+            }
+            '0'..='9' => {
+                let mut number = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+') {
+                        number.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Number(number));
+            }
+            'a'..='z' | 'A'..='Z' | '_' | '%' => {
+                // `%` escapes identifiers that are keywords
+                if ch == '%' {
+                    chars.next();
+                }
+                let mut ident = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                        ident.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Identifier(ident));
+            }
+            _ => {
+                chars.next();
+            }
+        }
+    }
+
+    tokens.push(Token::Eof);
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_interface() {
+        let wit = r#"
+            package wasi:http@0.2.0;
+
+            /// HTTP types
+            interface types {
+              use wasi:io/streams@0.2.0.{input-stream, output-stream as out};
+
+              type field-key = string;
+
+              record request-options {
+                connect-timeout: option<u64>,
+                headers: list<tuple<field-key, list<u8>>>,
+              }
+
+              variant error-code {
+                DNS-timeout,
+                internal-error(option<string>),
+              }
+
+              enum method { get, post, %type }
+
+              flags permissions { read, write }
+
+              @since(version = 0.2.0)
+              resource fields {
+                constructor();
+                get: func(name: field-key) -> list<list<u8>>;
+                from-list: static func(entries: list<tuple<string, string>>) -> result<fields, error-code>;
+              }
+
+              handle: func(request: borrow<fields>) -> result<_, error-code>;
+            }
+        "#;
+
+        let file = parse_wit(wit).unwrap();
+        let package = file.package.unwrap();
+        assert_eq!((package.namespace.as_str(), package.name.as_str()), ("wasi", "http"));
+        assert_eq!(package.version.as_deref(), Some("0.2.0"));
+
+        let items = &file.interfaces[0].items;
+        assert_eq!(items.uses[0].path, "wasi:io/streams@0.2.0");
+        assert_eq!(items.uses[0].names[1], ("output-stream".to_string(), Some("out".to_string())));
+        assert_eq!(items.types.len(), 6);
+
+        let TypeDefKind::Record(fields) = &items.types[1].kind else {
+            panic!("Expected record");
+        };
+        assert_eq!(fields[0].field_type, WitType::Option(Box::new(WitType::U64)));
+
+        let TypeDefKind::Variant(cases) = &items.types[2].kind else {
+            panic!("Expected variant");
+        };
+        assert!(cases[0].payload.is_none());
+        assert_eq!(cases[1].payload, Some(WitType::Option(Box::new(WitType::String))));
+
+        let TypeDefKind::Enum(names) = &items.types[3].kind else {
+            panic!("Expected enum");
+        };
+        assert_eq!(names, &["get", "post", "type"]);
+
+        let TypeDefKind::Resource(methods) = &items.types[5].kind else {
+            panic!("Expected resource");
+        };
+        let kinds: Vec<FunctionKind> = methods.iter().map(|m| m.kind).collect();
+        assert_eq!(kinds, [FunctionKind::Constructor, FunctionKind::Method, FunctionKind::Static]);
+
+        let handle = &items.functions[0];
+        assert_eq!(handle.params[0].field_type, WitType::Handle("fields".to_string()));
+        assert_eq!(
+            handle.result,
+            Some(WitType::Result(None, Some(Box::new(WitType::Named("error-code".to_string())))))
+        );
+    }
+
+    #[test]
+    fn test_parse_world() {
+        let wit = r#"
+            package example:plugin;
+
+            world host {
+              include wasi:cli/imports@0.2.0;
+              import wasi:logging/logging;
+              import log: func(message: string);
+              export run: func(args: list<string>) -> (code: u32, output: string);
+              export config: interface {
+                get: func(key: string) -> option<string>;
+              }
+              export handler;
+            }
+        "#;
+
+        let file = parse_wit(wit).unwrap();
+        let world = &file.worlds[0];
+        assert_eq!(world.includes, ["wasi:cli/imports@0.2.0"]);
+
+        let imports: Vec<&str> = world.imports.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(imports, ["logging", "log"]);
+        let exports: Vec<&str> = world.exports.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(exports, ["run", "config", "handler"]);
+
+        let WorldItemKind::Function(run) = &world.exports[0].kind else {
+            panic!("Expected function");
+        };
+        assert_eq!(run.result, Some(WitType::Tuple(vec![WitType::U32, WitType::String])));
+        assert!(matches!(world.exports[1].kind, WorldItemKind::Interface(_)));
+    }
+
+    #[test]
+    fn test_deprecated_and_errors() {
+        let wit = r#"
+            interface old {
+              @deprecated(version = 0.2.1)
+              legacy: func();
+              /* nested /* block */ comment */
+            }
+        "#;
+        let file = parse_wit(wit).unwrap();
+        assert_eq!(file.interfaces[0].items.functions[0].deprecated.as_deref(), Some("0.2.1"));
+
+        assert!(parse_wit("message foo {}").is_err());
+        assert!(parse_wit("interface x { record y { a } }").is_err());
+    }
+}
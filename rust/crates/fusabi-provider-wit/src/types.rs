@@ -0,0 +1,328 @@
+//! WIT AST type definitions
+
+use std::collections::HashMap;
+
+/// Represents a WIT package: one or more `.wit` files
+#[derive(Debug, Clone, Default)]
+pub struct WitFile {
+    /// Package declaration, if any
+    pub package: Option<PackageName>,
+    /// Interface definitions
+    pub interfaces: Vec<Interface>,
+    /// World definitions
+    pub worlds: Vec<World>,
+}
+
+/// Package name, e.g. `wasi:http@0.2.0`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackageName {
+    /// Package namespace (`wasi`)
+    pub namespace: String,
+    /// Package name (`http`)
+    pub name: String,
+    /// Package version, if declared
+    pub version: Option<String>,
+}
+
+/// WIT interface
+#[derive(Debug, Clone)]
+pub struct Interface {
+    /// Interface name
+    pub name: String,
+    /// Types, functions and `use` statements
+    pub items: Items,
+}
+
+/// Items that can appear in an interface or world body
+#[derive(Debug, Clone, Default)]
+pub struct Items {
+    /// `use` statements
+    pub uses: Vec<Use>,
+    /// Type definitions
+    pub types: Vec<TypeDef>,
+    /// Freestanding functions
+    pub functions: Vec<Function>,
+}
+
+/// `use path.{name, name as alias};`
+#[derive(Debug, Clone)]
+pub struct Use {
+    /// Interface path, e.g. `types` or `wasi:io/streams@0.2.0`
+    pub path: String,
+    /// Imported names and their local aliases
+    pub names: Vec<(String, Option<String>)>,
+}
+
+/// Named type definition
+#[derive(Debug, Clone)]
+pub struct TypeDef {
+    /// Type name
+    pub name: String,
+    /// Definition
+    pub kind: TypeDefKind,
+    /// `@deprecated` version, if any
+    pub deprecated: Option<String>,
+}
+
+/// Kind of a named type definition
+#[derive(Debug, Clone)]
+pub enum TypeDefKind {
+    /// `type name = T;`
+    Alias(WitType),
+    /// `record name { field: T, ... }`
+    Record(Vec<Field>),
+    /// `variant name { case, case(T), ... }`
+    Variant(Vec<Case>),
+    /// `enum name { case, ... }`
+    Enum(Vec<String>),
+    /// `flags name { flag, ... }`
+    Flags(Vec<String>),
+    /// `resource name { ... }` with its methods
+    Resource(Vec<Function>),
+}
+
+/// Record field or function parameter
+#[derive(Debug, Clone)]
+pub struct Field {
+    /// Field name
+    pub name: String,
+    /// Field type
+    pub field_type: WitType,
+}
+
+/// Variant case
+#[derive(Debug, Clone)]
+pub struct Case {
+    /// Case name
+    pub name: String,
+    /// Payload type, if any
+    pub payload: Option<WitType>,
+}
+
+/// Function kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FunctionKind {
+    /// Freestanding function
+    Free,
+    /// Resource method with an implicit `self`
+    Method,
+    /// `static` resource function
+    Static,
+    /// Resource `constructor`
+    Constructor,
+}
+
+/// WIT function
+#[derive(Debug, Clone)]
+pub struct Function {
+    /// Function name (`constructor` for constructors)
+    pub name: String,
+    /// Function kind
+    pub kind: FunctionKind,
+    /// Parameters
+    pub params: Vec<Field>,
+    /// Result type, if any
+    pub result: Option<WitType>,
+    /// `@deprecated` version, if any
+    pub deprecated: Option<String>,
+}
+
+/// WIT world
+#[derive(Debug, Clone)]
+pub struct World {
+    /// World name
+    pub name: String,
+    /// Types and `use` statements declared in the world
+    pub items: Items,
+    /// Imported interfaces and functions
+    pub imports: Vec<WorldItem>,
+    /// Exported interfaces and functions
+    pub exports: Vec<WorldItem>,
+    /// Included worlds
+    pub includes: Vec<String>,
+}
+
+/// An `import` or `export` of a world
+#[derive(Debug, Clone)]
+pub struct WorldItem {
+    /// Item name: the label, or the last segment of an interface path
+    pub name: String,
+    /// What is imported or exported
+    pub kind: WorldItemKind,
+}
+
+/// Target of a world `import` or `export`
+#[derive(Debug, Clone)]
+pub enum WorldItemKind {
+    /// Interface by path: `wasi:cli/stdout` or a local interface name
+    Path(String),
+    /// `name: func(...)`
+    Function(Function),
+    /// `name: interface { ... }`
+    Interface(Items),
+}
+
+/// WIT type
+#[derive(Debug, Clone, PartialEq)]
+pub enum WitType {
+    // Primitive types
+    Bool,
+    S8,
+    S16,
+    S32,
+    S64,
+    U8,
+    U16,
+    U32,
+    U64,
+    F32,
+    F64,
+    Char,
+    String,
+
+    // Compound types
+    List(Box<WitType>),
+    Option(Box<WitType>),
+    Result(Option<Box<WitType>>, Option<Box<WitType>>),
+    Tuple(Vec<WitType>),
+    /// `own<R>` or `borrow<R>`
+    Handle(String),
+
+    // Async types
+    Future(Option<Box<WitType>>),
+    Stream(Option<Box<WitType>>),
+    ErrorContext,
+
+    /// Reference to a named type
+    Named(String),
+}
+
+impl WitFile {
+    /// Create a new empty WitFile
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every item body in the package with a label for locations: interfaces,
+    /// worlds and inline interfaces of worlds
+    pub fn scopes(&self) -> Vec<(String, &Items)> {
+        let mut scopes: Vec<(String, &Items)> =
+            self.interfaces.iter().map(|i| (i.name.clone(), &i.items)).collect();
+        for world in &self.worlds {
+            scopes.push((world.name.clone(), &world.items));
+            for item in world.imports.iter().chain(&world.exports) {
+                if let WorldItemKind::Interface(items) = &item.kind {
+                    scopes.push((format!("{}.{}", world.name, item.name), items));
+                }
+            }
+        }
+        scopes
+    }
+
+    /// Build a map of alias name to aliased type across the package
+    pub fn build_alias_map(&self) -> HashMap<String, &WitType> {
+        self.scopes()
+            .into_iter()
+            .flat_map(|(_, items)| &items.types)
+            .filter_map(|t| match &t.kind {
+                TypeDefKind::Alias(target) => Some((t.name.clone(), target)),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl Items {
+    /// Every type written in these items, with its location
+    pub fn type_uses(&self, scope: &str) -> Vec<(String, &WitType)> {
+        let mut uses = Vec::new();
+        for type_def in &self.types {
+            let location = format!("{}.{}", scope, type_def.name);
+            match &type_def.kind {
+                TypeDefKind::Alias(target) => uses.push((location, target)),
+                TypeDefKind::Record(fields) => {
+                    for field in fields {
+                        uses.push((format!("{}.{}", location, field.name), &field.field_type));
+                    }
+                }
+                TypeDefKind::Variant(cases) => {
+                    for case in cases {
+                        if let Some(payload) = &case.payload {
+                            uses.push((format!("{}.{}", location, case.name), payload));
+                        }
+                    }
+                }
+                TypeDefKind::Resource(methods) => {
+                    for method in methods {
+                        method.type_uses(&format!("{}.{}", location, method.name), &mut uses);
+                    }
+                }
+                TypeDefKind::Enum(_) | TypeDefKind::Flags(_) => {}
+            }
+        }
+        for function in &self.functions {
+            function.type_uses(&format!("{}.{}", scope, function.name), &mut uses);
+        }
+        uses
+    }
+}
+
+impl Function {
+    fn type_uses<'a>(&'a self, location: &str, uses: &mut Vec<(String, &'a WitType)>) {
+        for param in &self.params {
+            uses.push((format!("{}.{}", location, param.name), &param.field_type));
+        }
+        if let Some(result) = &self.result {
+            uses.push((location.to_string(), result));
+        }
+    }
+}
+
+impl WitType {
+    /// Parse a primitive type name, or treat the name as a reference
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "bool" => WitType::Bool,
+            "s8" => WitType::S8,
+            "s16" => WitType::S16,
+            "s32" => WitType::S32,
+            "s64" => WitType::S64,
+            "u8" => WitType::U8,
+            "u16" => WitType::U16,
+            "u32" => WitType::U32,
+            "u64" => WitType::U64,
+            // `float32`/`float64` are the pre-0.2 spellings
+            "f32" | "float32" => WitType::F32,
+            "f64" | "float64" => WitType::F64,
+            "char" => WitType::Char,
+            "string" => WitType::String,
+            "error-context" => WitType::ErrorContext,
+            _ => WitType::Named(s.to_string()),
+        }
+    }
+
+    /// Visit this type and every type nested in it
+    pub fn walk<'a>(&'a self, visit: &mut impl FnMut(&'a WitType)) {
+        visit(self);
+        match self {
+            WitType::List(inner) | WitType::Option(inner) => inner.walk(visit),
+            WitType::Result(ok, err) => {
+                for inner in ok.iter().chain(err) {
+                    inner.walk(visit);
+                }
+            }
+            WitType::Tuple(items) => {
+                for item in items {
+                    item.walk(visit);
+                }
+            }
+            WitType::Future(inner) | WitType::Stream(inner) => {
+                if let Some(inner) = inner {
+                    inner.walk(visit);
+                }
+            }
+            _ => {}
+        }
+    }
+}
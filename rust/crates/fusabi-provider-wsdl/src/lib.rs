@@ -0,0 +1,615 @@
+//! WSDL Type Provider
+//!
+//! Generates Fusabi types from WSDL 1.1 service descriptions, for calling
+//! and implementing SOAP services with typed requests and responses.
+//!
+//! # Sources
+//!
+//! A WSDL document, inline or from a file or URL. Only the types embedded
+//! in it are read; see [`wsdl`] and [`xsd`].
+//!
+//! # Generated Types
+//!
+//! - A record per XSD complex type and per global element with an
+//!   anonymous type. Elements are `T list` when they repeat and `T option`
+//!   when optional or nillable; an extension includes its base's fields
+//!   first, and `simpleContent` is read into a `value` field.
+//! - A union per enumeration simple type; other simple types are their
+//!   base type
+//! - Per operation, `<Operation>Request` and `<Operation>Response` with a
+//!   field per message part, and `<Operation>Fault` with a variant per
+//!   fault. In the document/literal wrapped style, where a message's only
+//!   part is an element wrapping the parameters, the record takes the
+//!   wrapper's fields instead and no type is generated for the wrapper.
+//!
+//! # Params
+//!
+//! | Param        | Meaning                                                     |
+//! |--------------|-------------------------------------------------------------|
+//! | `port_type`  | Only generate operations of this port type                  |
+//! | `operations` | Comma-separated operation names; a trailing `*` matches a prefix |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_provider_wsdl::WsdlProvider;
+//! use fusabi_type_providers::{TypeProvider, ProviderParams};
+//!
+//! let provider = WsdlProvider::new();
+//! let schema = provider.resolve_schema("https://example.com/StockQuote?wsdl", &ProviderParams::default())?;
+//! let types = provider.generate_types(&schema, "StockQuote")?;
+//! ```
+
+pub mod wsdl;
+pub mod xsd;
+
+pub use wsdl::{parse_wsdl, Message, Operation, Part, PortType, Wsdl};
+pub use xsd::{ComplexType, ElementDecl, Field, Schemas, SimpleType};
+
+use std::collections::HashSet;
+
+use fusabi_provider_common::{
+    read_source, Capabilities, DescribedProvider, Diagnostic, OutputFeature, ParamKind, ParamSchema,
+    ParamSpec, ParameterizedProvider, SourceValidator, TypeShape,
+};
+use fusabi_type_providers::{
+    TypeProvider, ProviderParams, Schema,
+    GeneratedTypes, GeneratedModule, TypeGenerator, NamingStrategy,
+    RecordDef, DuDef, VariantDef, TypeExpr, TypeDefinition,
+    ProviderError, ProviderResult,
+};
+use xsd::type_name;
+
+/// WSDL type provider
+pub struct WsdlProvider {
+    generator: TypeGenerator,
+}
+
+impl WsdlProvider {
+    pub fn new() -> Self {
+        Self {
+            generator: TypeGenerator::new(NamingStrategy::PascalCase),
+        }
+    }
+
+    fn parse_schema(&self, json: &str) -> ProviderResult<Wsdl> {
+        serde_json::from_str(json)
+            .map_err(|e| ProviderError::ParseError(format!("Expected a WSDL document: {}", e)))
+    }
+
+    fn generate(&self, wsdl: &Wsdl) -> (Vec<TypeDefinition>, Vec<Diagnostic>) {
+        let schemas = &wsdl.schemas;
+        let mut diagnostics: Vec<Diagnostic> = schemas
+            .imports
+            .iter()
+            .map(|import| {
+                Diagnostic::info(
+                    "external-schema",
+                    format!("`{}` is not read; types from it are generated as `string`", import),
+                )
+            })
+            .chain(schemas.unsupported.iter().map(|construct| {
+                Diagnostic::info("unsupported-construct", format!("`{}` content is not generated", construct))
+            }))
+            .collect();
+
+        let operations: Vec<&Operation> = wsdl.port_types.iter().flat_map(|p| &p.operations).collect();
+        let wrappers: HashSet<&str> = operations
+            .iter()
+            .flat_map(|op| [&op.input, &op.output])
+            .filter_map(|message| wrapper(wsdl, message.as_deref()?))
+            .map(|wrapper| wrapper.name.as_str())
+            .collect();
+        let referenced = referenced_types(wsdl);
+
+        let mut types = Vec::new();
+        let mut names = HashSet::new();
+        for simple in schemas.simple_types.iter().filter(|s| !s.values.is_empty()) {
+            match self.enum_variants(&simple.values) {
+                Some(variants) => {
+                    names.insert(type_name(&simple.name));
+                    types.push(TypeDefinition::Du(DuDef { name: type_name(&simple.name), variants }));
+                }
+                None => diagnostics.push(
+                    Diagnostic::warning(
+                        "invalid-value",
+                        format!("Values of `{}` aren't distinct variant names; generated as its base type", simple.name),
+                    )
+                    .at(simple.name.clone()),
+                ),
+            }
+        }
+        for complex in &schemas.complex_types {
+            if wrappers.contains(complex.name.as_str()) && !referenced.contains(complex.name.as_str()) {
+                continue;
+            }
+            let fields = self.record_fields(schemas, complex, &complex.name, &mut diagnostics);
+            names.insert(type_name(&complex.name));
+            types.push(TypeDefinition::Record(RecordDef { name: type_name(&complex.name), fields }));
+        }
+
+        for operation in operations {
+            let location = operation.name.clone();
+            let base = type_name(&operation.name);
+            let mut messages = vec![("Request", &operation.input)];
+            match &operation.output {
+                Some(_) => messages.push(("Response", &operation.output)),
+                None => diagnostics.push(
+                    Diagnostic::info("one-way", "One-way operation; no response type is generated").at(location.clone()),
+                ),
+            }
+            for (suffix, message) in messages {
+                let Some(message_name) = message else {
+                    continue;
+                };
+                let Some(message) = wsdl.message(message_name) else {
+                    diagnostics.push(
+                        Diagnostic::error("unknown-message", format!("Message `{}` is not defined", message_name))
+                            .at(location.clone()),
+                    );
+                    continue;
+                };
+                let name = format!("{}{}", base, suffix);
+                if !names.insert(name.clone()) {
+                    diagnostics.push(
+                        Diagnostic::warning("duplicate-name", format!("`{}` is already defined; skipped", name))
+                            .at(location.clone()),
+                    );
+                    continue;
+                }
+                let fields = match wrapper(wsdl, message_name) {
+                    Some(wrapper) => self.record_fields(schemas, wrapper, &location, &mut diagnostics),
+                    None => message
+                        .parts
+                        .iter()
+                        .map(|part| {
+                            let ty = self.part_type(schemas, part, &location, &mut diagnostics);
+                            (part.name.clone(), TypeShape::named(ty).into())
+                        })
+                        .collect(),
+                };
+                types.push(TypeDefinition::Record(RecordDef { name, fields }));
+            }
+
+            if !operation.faults.is_empty() {
+                let variants = operation
+                    .faults
+                    .iter()
+                    .map(|(fault, message_name)| {
+                        let part = wsdl.message(message_name).and_then(|m| m.parts.first());
+                        match part {
+                            Some(part) => {
+                                let ty = self.part_type(schemas, part, &location, &mut diagnostics);
+                                VariantDef::new(type_name(fault), vec![TypeShape::named(ty).into()])
+                            }
+                            None => {
+                                diagnostics.push(
+                                    Diagnostic::error(
+                                        "unknown-message",
+                                        format!("Fault message `{}` is not defined or has no parts", message_name),
+                                    )
+                                    .at(location.clone()),
+                                );
+                                VariantDef::new_simple(type_name(fault))
+                            }
+                        }
+                    })
+                    .collect();
+                types.push(TypeDefinition::Du(DuDef { name: format!("{}Fault", base), variants }));
+            }
+        }
+        (types, diagnostics)
+    }
+
+    /// Variant names for enumeration values, or `None` if some aren't
+    /// usable or two collide
+    fn enum_variants(&self, values: &[String]) -> Option<Vec<VariantDef>> {
+        let names: Vec<String> = values.iter().map(|v| type_name(&self.generator.naming.apply(v))).collect();
+        let unique: HashSet<&String> = names.iter().collect();
+        let valid = names.iter().all(|n| n.starts_with(|c: char| c.is_ascii_alphabetic()));
+        (valid && unique.len() == names.len()).then(|| names.into_iter().map(VariantDef::new_simple).collect())
+    }
+
+    /// Fields of a complex type, its base's first
+    fn record_fields(
+        &self,
+        schemas: &Schemas,
+        complex: &ComplexType,
+        location: &str,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Vec<(String, TypeExpr)> {
+        let mut chain = vec![complex];
+        while let Some(base) = chain.last().and_then(|t| t.base.as_deref()) {
+            match schemas.complex_type(base) {
+                Some(base) if !chain.iter().any(|t| t.name == base.name) => chain.push(base),
+                Some(_) => break,
+                None => {
+                    diagnostics.push(
+                        Diagnostic::warning("unknown-type", format!("Base type `{}` is not defined; its fields are left out", base))
+                            .at(location.to_string()),
+                    );
+                    break;
+                }
+            }
+        }
+
+        chain
+            .iter()
+            .rev()
+            .flat_map(|t| &t.fields)
+            .map(|field| {
+                let ty = if field.reference {
+                    match schemas.element(&field.type_ref) {
+                        Some(element) => self.type_of(schemas, &element.type_ref, location, diagnostics),
+                        None => {
+                            diagnostics.push(
+                                Diagnostic::warning(
+                                    "unknown-element",
+                                    format!("Element `{}` is not defined; generated as `string`", field.type_ref),
+                                )
+                                .at(location.to_string()),
+                            );
+                            "string".to_string()
+                        }
+                    }
+                } else {
+                    self.type_of(schemas, &field.type_ref, location, diagnostics)
+                };
+                let ty = TypeShape::named(ty);
+                let ty = if field.many { ty.list() } else { ty };
+                let ty = ty.option_unless(field.min > 0 && !field.nillable);
+                (field_name(&field.name), ty.into())
+            })
+            .collect()
+    }
+
+    fn part_type(&self, schemas: &Schemas, part: &Part, location: &str, diagnostics: &mut Vec<Diagnostic>) -> String {
+        if let Some(element) = &part.element {
+            let Some(element) = schemas.element(element) else {
+                diagnostics.push(
+                    Diagnostic::warning("unknown-element", format!("Element `{}` is not defined; generated as `string`", element))
+                        .at(location.to_string()),
+                );
+                return "string".to_string();
+            };
+            return self.type_of(schemas, &element.type_ref, location, diagnostics);
+        }
+        let type_ref = part.type_ref.as_deref().unwrap_or("xs:anyType");
+        self.type_of(schemas, type_ref, location, diagnostics)
+    }
+
+    fn type_of(&self, schemas: &Schemas, type_ref: &str, location: &str, diagnostics: &mut Vec<Diagnostic>) -> String {
+        if let Some(builtin) = type_ref.strip_prefix("xs:") {
+            return builtin_type(builtin)
+                .unwrap_or_else(|| {
+                    diagnostics.push(
+                        Diagnostic::warning("unmapped-type", format!("Unknown XSD type `{}`; generated as `string`", type_ref))
+                            .at(location.to_string()),
+                    );
+                    "string"
+                })
+                .to_string();
+        }
+        if let Some(simple) = schemas.simple_type(type_ref) {
+            return if !simple.values.is_empty() && self.enum_variants(&simple.values).is_some() {
+                type_name(&simple.name)
+            } else if simple.base == simple.name {
+                "string".to_string()
+            } else {
+                self.type_of(schemas, &simple.base, location, diagnostics)
+            };
+        }
+        if schemas.complex_type(type_ref).is_some() {
+            return type_name(type_ref);
+        }
+        diagnostics.push(
+            Diagnostic::warning("unknown-type", format!("Type `{}` is not defined; generated as `string`", type_ref))
+                .at(location.to_string()),
+        );
+        "string".to_string()
+    }
+}
+
+/// The wrapper type of a document/literal wrapped message: its only part
+/// is an element of a complex type
+fn wrapper<'a>(wsdl: &'a Wsdl, message: &str) -> Option<&'a ComplexType> {
+    let [part] = wsdl.message(message)?.parts.as_slice() else {
+        return None;
+    };
+    let element = wsdl.schemas.element(part.element.as_deref()?)?;
+    wsdl.schemas.complex_type(&element.type_ref)
+}
+
+/// Complex types used other than as a wrapper
+fn referenced_types(wsdl: &Wsdl) -> HashSet<&str> {
+    let schemas = &wsdl.schemas;
+    let mut referenced: HashSet<&str> = HashSet::new();
+    for complex in &schemas.complex_types {
+        referenced.extend(complex.base.as_deref());
+        for field in &complex.fields {
+            if field.reference {
+                referenced.extend(schemas.element(&field.type_ref).map(|e| e.type_ref.as_str()));
+            } else {
+                referenced.insert(field.type_ref.as_str());
+            }
+        }
+    }
+    for part in wsdl.messages.iter().filter(|m| wrapper(wsdl, &m.name).is_none()).flat_map(|m| &m.parts) {
+        referenced.extend(part.type_ref.as_deref());
+        referenced.extend(part.element.as_deref().and_then(|e| schemas.element(e)).map(|e| e.type_ref.as_str()));
+    }
+    referenced
+}
+
+/// The Fusabi type of a built-in XSD type, or `None` if it is not known
+fn builtin_type(name: &str) -> Option<&'static str> {
+    match name {
+        "string" | "normalizedString" | "token" | "language" | "Name" | "NCName" | "NMTOKEN" | "NMTOKENS" | "ID"
+        | "IDREF" | "IDREFS" | "ENTITY" | "ENTITIES" | "QName" | "NOTATION" | "anyURI" | "date" | "dateTime"
+        | "dateTimeStamp" | "time" | "duration" | "dayTimeDuration" | "yearMonthDuration" | "gYear" | "gYearMonth"
+        | "gMonth" | "gMonthDay" | "gDay" | "base64Binary" | "hexBinary" | "anySimpleType" => Some("string"),
+        "boolean" => Some("bool"),
+        "byte" | "short" | "int" | "unsignedByte" | "unsignedShort" => Some("int"),
+        "long" | "integer" | "nonNegativeInteger" | "positiveInteger" | "nonPositiveInteger" | "negativeInteger" => {
+            Some("int64")
+        }
+        "unsignedInt" => Some("uint"),
+        "unsignedLong" => Some("uint64"),
+        "float" | "double" | "decimal" => Some("float"),
+        "anyType" => Some("any"),
+        _ => None,
+    }
+}
+
+/// `order-id` → `order_id`
+fn field_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect()
+}
+
+fn matches_any(patterns: &[&str], name: &str) -> bool {
+    patterns.iter().any(|pattern| match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => name == *pattern,
+    })
+}
+
+impl Default for WsdlProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterizedProvider for WsdlProvider {
+    fn param_schema(&self) -> ParamSchema {
+        ParamSchema::new("WsdlProvider")
+            .param(
+                ParamSpec::new("port_type", ParamKind::String)
+                    .describe("Only generate operations of this port type"),
+            )
+            .param(
+                ParamSpec::new("operations", ParamKind::List)
+                    .describe("Operation names; a trailing `*` matches a prefix"),
+            )
+    }
+}
+
+impl TypeProvider for WsdlProvider {
+    fn name(&self) -> &str {
+        "WsdlProvider"
+    }
+
+    fn resolve_schema(&self, source: &str, params: &ProviderParams) -> ProviderResult<Schema> {
+        let typed = self.param_schema().validate(params)?;
+        let mut wsdl = if source.trim_start().starts_with('<') {
+            parse_wsdl(source)?
+        } else {
+            parse_wsdl(&read_source(source, params)?)?
+        };
+
+        if let Some(port_type) = typed.str("port_type") {
+            if !wsdl.port_types.iter().any(|p| p.name == port_type) {
+                return Err(ProviderError::InvalidSource(format!("Port type `{}` is not defined", port_type)));
+            }
+            wsdl.port_types.retain(|p| p.name == port_type);
+        }
+        if let Some(patterns) = typed.list("operations") {
+            for port_type in &mut wsdl.port_types {
+                port_type.operations.retain(|op| matches_any(&patterns, &op.name));
+            }
+        }
+
+        let json = serde_json::to_string(&wsdl)
+            .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(Schema::Custom(json))
+    }
+
+    fn generate_types(&self, schema: &Schema, namespace: &str) -> ProviderResult<GeneratedTypes> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected a WSDL document".to_string()));
+        };
+
+        let mut module = GeneratedModule::new(vec![namespace.to_string()]);
+        module.types = self.generate(&self.parse_schema(json)?).0;
+
+        let mut result = GeneratedTypes::new();
+        result.modules.push(module);
+        Ok(result)
+    }
+}
+
+impl SourceValidator for WsdlProvider {
+    fn schema_diagnostics(&self, schema: &Schema) -> ProviderResult<Vec<Diagnostic>> {
+        let Schema::Custom(json) = schema else {
+            return Err(ProviderError::ParseError("Expected a WSDL document".to_string()));
+        };
+
+        Ok(self.generate(&self.parse_schema(json)?).1)
+    }
+}
+
+impl DescribedProvider for WsdlProvider {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.name())
+            .documents()
+            .params(&self.param_schema())
+            .feature(OutputFeature::Unions)
+            .feature(OutputFeature::Diagnostics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fusabi_provider_common::emit::golden;
+
+    const STOCK_WSDL: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<wsdl:definitions name="StockQuote" targetNamespace="urn:stock"
+    xmlns:wsdl="http://schemas.xmlsoap.org/wsdl/" xmlns:soap="http://schemas.xmlsoap.org/wsdl/soap/"
+    xmlns:tns="urn:stock" xmlns:s="http://www.w3.org/2001/XMLSchema">
+  <wsdl:types>
+    <s:schema targetNamespace="urn:stock" elementFormDefault="qualified">
+      <s:element name="GetQuote">
+        <s:complexType><s:sequence>
+          <s:element name="symbol" type="s:string"/>
+          <s:element name="exchange" type="tns:Exchange" minOccurs="0"/>
+        </s:sequence></s:complexType>
+      </s:element>
+      <s:element name="GetQuoteResult">
+        <s:complexType><s:sequence>
+          <s:element name="quote" type="tns:Quote" nillable="true"/>
+        </s:sequence></s:complexType>
+      </s:element>
+      <s:complexType name="Price">
+        <s:sequence><s:element name="amount" type="s:decimal"/></s:sequence>
+        <s:attribute name="currency" type="s:string" use="required"/>
+      </s:complexType>
+      <s:complexType name="Quote">
+        <s:complexContent><s:extension base="tns:Price"><s:sequence>
+          <s:element name="symbol" type="s:string"/>
+          <s:element name="history" type="tns:Price" minOccurs="0" maxOccurs="unbounded"/>
+          <s:element name="volume" type="s:unsignedLong"/>
+          <s:element name="venue" type="tns:Venue"/>
+        </s:sequence></s:extension></s:complexContent>
+      </s:complexType>
+      <s:simpleType name="Exchange">
+        <s:restriction base="s:string"><s:enumeration value="NYSE"/><s:enumeration value="NASDAQ"/></s:restriction>
+      </s:simpleType>
+      <s:simpleType name="Ticker"><s:restriction base="s:token"><s:maxLength value="5"/></s:restriction></s:simpleType>
+      <s:element name="QuoteFault"><s:complexType><s:sequence>
+        <s:element name="reason" type="s:string"/>
+      </s:sequence></s:complexType></s:element>
+    </s:schema>
+  </wsdl:types>
+  <wsdl:message name="GetQuoteIn"><wsdl:part name="parameters" element="tns:GetQuote"/></wsdl:message>
+  <wsdl:message name="GetQuoteOut"><wsdl:part name="parameters" element="tns:GetQuoteResult"/></wsdl:message>
+  <wsdl:message name="QuoteFaultMsg"><wsdl:part name="detail" element="tns:QuoteFault"/></wsdl:message>
+  <wsdl:message name="SubscribeIn">
+    <wsdl:part name="symbol" type="tns:Ticker"/>
+    <wsdl:part name="interval" type="s:int"/>
+  </wsdl:message>
+  <wsdl:portType name="StockQuoteSoap">
+    <wsdl:operation name="GetQuote">
+      <wsdl:input message="tns:GetQuoteIn"/>
+      <wsdl:output message="tns:GetQuoteOut"/>
+      <wsdl:fault name="quoteFault" message="tns:QuoteFaultMsg"/>
+    </wsdl:operation>
+    <wsdl:operation name="Subscribe"><wsdl:input message="tns:SubscribeIn"/></wsdl:operation>
+    <wsdl:operation name="Cancel"><wsdl:input message="tns:CancelIn"/></wsdl:operation>
+  </wsdl:portType>
+  <wsdl:binding name="StockQuoteSoap" type="tns:StockQuoteSoap">
+    <soap:binding style="document" transport="http://schemas.xmlsoap.org/soap/http"/>
+  </wsdl:binding>
+</wsdl:definitions>"#;
+
+    fn fields(types: &GeneratedTypes, name: &str) -> Vec<(String, String)> {
+        types.modules[0]
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Record(r) if r.name == name => Some(r),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Expected record {}", name))
+            .fields
+            .iter()
+            .map(|(field, ty)| (field.clone(), ty.to_string()))
+            .collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(f, t)| (f.to_string(), t.to_string())).collect()
+    }
+
+    fn names(types: &GeneratedTypes) -> Vec<&str> {
+        types.modules[0]
+            .types
+            .iter()
+            .map(|t| match t {
+                TypeDefinition::Record(r) => r.name.as_str(),
+                TypeDefinition::Du(du) => du.name.as_str(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_operation_types() {
+        let provider = WsdlProvider::new();
+        let schema = provider.resolve_schema(STOCK_WSDL, &ProviderParams::default()).unwrap();
+        let types = provider.generate_types(&schema, "StockQuote").unwrap();
+
+        assert_eq!(
+            names(&types),
+            [
+                "Exchange", "Price", "Quote", "QuoteFault",
+                "GetQuoteRequest", "GetQuoteResponse", "GetQuoteFault", "SubscribeRequest",
+            ]
+        );
+        assert_eq!(
+            fields(&types, "GetQuoteRequest"),
+            pairs(&[("symbol", "string"), ("exchange", "Exchange option")])
+        );
+        assert_eq!(fields(&types, "GetQuoteResponse"), pairs(&[("quote", "Quote option")]));
+        assert_eq!(
+            fields(&types, "Quote"),
+            pairs(&[
+                ("amount", "float"),
+                ("currency", "string"),
+                ("symbol", "string"),
+                ("history", "Price list option"),
+                ("volume", "uint64"),
+                ("venue", "string"),
+            ])
+        );
+        assert_eq!(fields(&types, "SubscribeRequest"), pairs(&[("symbol", "string"), ("interval", "int")]));
+
+        let diagnostics = provider.schema_diagnostics(&schema).unwrap();
+        let found: Vec<(&str, &str)> = diagnostics
+            .iter()
+            .map(|d| (d.code.as_str(), d.location.as_deref().unwrap_or("")))
+            .collect();
+        assert_eq!(
+            found,
+            [("unknown-type", "Quote"), ("one-way", "Subscribe"), ("one-way", "Cancel"), ("unknown-message", "Cancel")]
+        );
+
+        golden::assert_fusabi(
+            &types,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/wsdl.fsx"),
+        );
+    }
+
+    #[test]
+    fn test_operation_filter() {
+        let provider = WsdlProvider::new();
+        let params = ProviderParams::default().with("port_type", "StockQuoteSoap").with("operations", "Sub*");
+        let schema = provider.resolve_schema(STOCK_WSDL, &params).unwrap();
+        let types = provider.generate_types(&schema, "StockQuote").unwrap();
+        assert_eq!(names(&types).last(), Some(&"SubscribeRequest"));
+        assert!(!names(&types).contains(&"GetQuoteRequest"));
+
+        let params = ProviderParams::default().with("port_type", "Missing");
+        assert!(provider.resolve_schema(STOCK_WSDL, &params).is_err());
+    }
+}
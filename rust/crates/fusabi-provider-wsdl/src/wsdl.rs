@@ -0,0 +1,186 @@
+//! WSDL 1.1 documents
+//!
+//! Reads the abstract part of a service description: the XML Schema in
+//! `types`, the `message`s and their parts, and the operations of each
+//! `portType`. Bindings and services only say how messages travel, so
+//! they are not read. WSDL 2.0 (`description`) is rejected.
+
+use fusabi_type_providers::{ProviderError, ProviderResult};
+use roxmltree::{Document, Node};
+use serde::{Deserialize, Serialize};
+
+use crate::xsd::{qname, Schemas, XSD_NS};
+
+pub const WSDL_NS: &str = "http://schemas.xmlsoap.org/wsdl/";
+const WSDL2_NS: &str = "http://www.w3.org/ns/wsdl";
+
+/// A WSDL 1.1 document
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Wsdl {
+    pub name: Option<String>,
+    pub target_namespace: Option<String>,
+    pub schemas: Schemas,
+    pub messages: Vec<Message>,
+    pub port_types: Vec<PortType>,
+}
+
+/// A message and its parts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Message {
+    pub name: String,
+    pub parts: Vec<Part>,
+}
+
+/// A message part: a global element (document style) or a type (RPC style)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Part {
+    pub name: String,
+    pub element: Option<String>,
+    pub type_ref: Option<String>,
+}
+
+/// A port type and its operations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortType {
+    pub name: String,
+    pub operations: Vec<Operation>,
+}
+
+/// An operation and the messages it exchanges
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Operation {
+    pub name: String,
+    pub input: Option<String>,
+    /// `None` for one-way operations
+    pub output: Option<String>,
+    /// Fault name and message
+    pub faults: Vec<(String, String)>,
+}
+
+impl Wsdl {
+    pub fn message(&self, name: &str) -> Option<&Message> {
+        self.messages.iter().find(|m| m.name == name)
+    }
+}
+
+/// Parse a WSDL 1.1 document
+pub fn parse_wsdl(content: &str) -> ProviderResult<Wsdl> {
+    let document = Document::parse(content)
+        .map_err(|e| ProviderError::ParseError(format!("Invalid WSDL XML: {}", e)))?;
+    let root = document.root_element();
+    match root.tag_name().namespace() {
+        Some(WSDL_NS) if root.tag_name().name() == "definitions" => {}
+        Some(WSDL2_NS) => return Err(ProviderError::ParseError("WSDL 2.0 is not supported; expected WSDL 1.1".to_string())),
+        _ => return Err(ProviderError::ParseError("Expected a WSDL 1.1 `definitions` element".to_string())),
+    }
+
+    let mut wsdl = Wsdl {
+        name: root.attribute("name").map(str::to_string),
+        target_namespace: root.attribute("targetNamespace").map(str::to_string),
+        ..Wsdl::default()
+    };
+    for child in wsdl_children(root) {
+        match child.tag_name().name() {
+            "types" => {
+                let schemas = child
+                    .children()
+                    .filter(|c| c.is_element() && c.tag_name().namespace() == Some(XSD_NS) && c.tag_name().name() == "schema");
+                for schema in schemas {
+                    wsdl.schemas.add_schema(schema);
+                }
+            }
+            "import" => wsdl.schemas.imports.extend(child.attribute("location").map(str::to_string)),
+            "message" => wsdl.messages.push(Message {
+                name: name(child)?,
+                parts: wsdl_children(child)
+                    .filter(|c| c.tag_name().name() == "part")
+                    .map(|part| {
+                        Ok(Part {
+                            name: name(part)?,
+                            element: part.attribute("element").map(|e| qname(part, e)),
+                            type_ref: part.attribute("type").map(|t| qname(part, t)),
+                        })
+                    })
+                    .collect::<ProviderResult<_>>()?,
+            }),
+            "portType" => wsdl.port_types.push(PortType {
+                name: name(child)?,
+                operations: wsdl_children(child)
+                    .filter(|c| c.tag_name().name() == "operation")
+                    .map(operation)
+                    .collect::<ProviderResult<_>>()?,
+            }),
+            _ => {}
+        }
+    }
+    Ok(wsdl)
+}
+
+fn operation(node: Node) -> ProviderResult<Operation> {
+    let message = |tag: &str| {
+        wsdl_children(node)
+            .find(|c| c.tag_name().name() == tag)
+            .and_then(|c| c.attribute("message").map(|m| qname(c, m)))
+    };
+    let faults = wsdl_children(node)
+        .filter(|c| c.tag_name().name() == "fault")
+        .filter_map(|c| Some((c.attribute("name")?.to_string(), qname(c, c.attribute("message")?))))
+        .collect();
+    Ok(Operation {
+        name: name(node)?,
+        input: message("input"),
+        output: message("output"),
+        faults,
+    })
+}
+
+fn name(node: Node) -> ProviderResult<String> {
+    node.attribute("name")
+        .map(str::to_string)
+        .ok_or_else(|| ProviderError::ParseError(format!("WSDL `{}` has no `name`", node.tag_name().name())))
+}
+
+fn wsdl_children<'a, 'input>(node: Node<'a, 'input>) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children().filter(|c| c.is_element() && c.tag_name().namespace() == Some(WSDL_NS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_wsdl() {
+        let wsdl = parse_wsdl(
+            r#"<?xml version="1.0"?>
+            <definitions name="Stock" targetNamespace="urn:stock" xmlns="http://schemas.xmlsoap.org/wsdl/"
+                xmlns:tns="urn:stock" xmlns:xsd="http://www.w3.org/2001/XMLSchema">
+              <types><xsd:schema targetNamespace="urn:stock"><xsd:element name="price" type="xsd:float"/></xsd:schema></types>
+              <message name="PriceRequest"><part name="symbol" type="xsd:string"/></message>
+              <message name="PriceResponse"><part name="body" element="tns:price"/></message>
+              <portType name="StockPortType">
+                <operation name="GetPrice">
+                  <input message="tns:PriceRequest"/>
+                  <output message="tns:PriceResponse"/>
+                  <fault name="NotFound" message="tns:PriceRequest"/>
+                </operation>
+                <operation name="Notify"><input message="tns:PriceRequest"/></operation>
+              </portType>
+            </definitions>"#,
+        )
+        .unwrap();
+
+        assert_eq!(wsdl.target_namespace.as_deref(), Some("urn:stock"));
+        assert_eq!(wsdl.schemas.element("price").unwrap().type_ref, "xs:float");
+        assert_eq!(wsdl.message("PriceRequest").unwrap().parts[0].type_ref.as_deref(), Some("xs:string"));
+        assert_eq!(wsdl.message("PriceResponse").unwrap().parts[0].element.as_deref(), Some("price"));
+
+        let operations = &wsdl.port_types[0].operations;
+        assert_eq!(operations[0].input.as_deref(), Some("PriceRequest"));
+        assert_eq!(operations[0].faults, [("NotFound".to_string(), "PriceRequest".to_string())]);
+        assert_eq!(operations[1].output, None);
+
+        let wsdl2 = parse_wsdl(r#"<description xmlns="http://www.w3.org/ns/wsdl"/>"#).unwrap_err();
+        assert!(wsdl2.to_string().contains("WSDL 2.0"));
+        assert!(parse_wsdl("<definitions/>").is_err());
+    }
+}
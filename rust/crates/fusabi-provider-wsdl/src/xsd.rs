@@ -0,0 +1,319 @@
+//! XML Schema embedded in WSDL
+//!
+//! Reads the parts of XSD that SOAP services use to describe messages:
+//! named and anonymous complex types (`sequence`, `all`, `choice`,
+//! attributes, `complexContent` extension, `simpleContent`), simple types
+//! (enumerations and restrictions) and global elements.
+//!
+//! Anonymous types are given names: a global element's type takes the
+//! element's name, and a nested one its parent's name followed by its own
+//! (`GetQuote` → `GetQuoteItems`). Type references are kept as local names;
+//! built-in XSD types are written `xs:<name>` whatever prefix the document
+//! uses.
+
+use roxmltree::Node;
+use serde::{Deserialize, Serialize};
+
+pub const XSD_NS: &str = "http://www.w3.org/2001/XMLSchema";
+
+/// Types and elements of every schema in a document
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Schemas {
+    pub complex_types: Vec<ComplexType>,
+    pub simple_types: Vec<SimpleType>,
+    pub elements: Vec<ElementDecl>,
+    /// `schemaLocation` or namespace of each `import` and `include`
+    pub imports: Vec<String>,
+    /// Constructs skipped while reading (`group`, `any`), once each
+    pub unsupported: Vec<String>,
+}
+
+/// A complex type, with the fields of its base type not included
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComplexType {
+    pub name: String,
+    /// `complexContent` extension base
+    pub base: Option<String>,
+    pub fields: Vec<Field>,
+}
+
+/// An element or attribute of a complex type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Field {
+    pub name: String,
+    /// The type, or the global element for `ref` elements
+    pub type_ref: String,
+    pub reference: bool,
+    pub min: u32,
+    pub many: bool,
+    pub nillable: bool,
+}
+
+/// A simple type: an enumeration, or an alias of its base
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimpleType {
+    pub name: String,
+    pub base: String,
+    pub values: Vec<String>,
+}
+
+/// A global element
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ElementDecl {
+    pub name: String,
+    pub type_ref: String,
+}
+
+impl Schemas {
+    pub fn complex_type(&self, name: &str) -> Option<&ComplexType> {
+        self.complex_types.iter().find(|t| t.name == name)
+    }
+
+    pub fn simple_type(&self, name: &str) -> Option<&SimpleType> {
+        self.simple_types.iter().find(|t| t.name == name)
+    }
+
+    pub fn element(&self, name: &str) -> Option<&ElementDecl> {
+        self.elements.iter().find(|e| e.name == name)
+    }
+
+    /// Read an `xs:schema` element
+    pub fn add_schema(&mut self, schema: Node) {
+        for child in xsd_children(schema) {
+            let name = child.attribute("name").unwrap_or_default();
+            match child.tag_name().name() {
+                "complexType" => self.add_complex_type(child, name.to_string()),
+                "simpleType" => {
+                    self.add_simple_type(child, name.to_string());
+                }
+                "element" => {
+                    let type_ref = self.element_type(child, String::new());
+                    self.elements.push(ElementDecl { name: name.to_string(), type_ref });
+                }
+                "import" | "include" | "redefine" => {
+                    let location = child.attribute("schemaLocation").or_else(|| child.attribute("namespace"));
+                    self.imports.extend(location.map(str::to_string));
+                }
+                "group" | "attributeGroup" => self.unsupported(child),
+                _ => {}
+            }
+        }
+    }
+
+    fn add_complex_type(&mut self, node: Node, name: String) {
+        let mut complex = ComplexType { name, base: None, fields: Vec::new() };
+        self.content(node, &mut complex, false);
+        self.complex_types.push(complex);
+    }
+
+    /// Read a content model into `complex`; fields in a `choice` are optional
+    fn content(&mut self, node: Node, complex: &mut ComplexType, in_choice: bool) {
+        for child in xsd_children(node) {
+            match child.tag_name().name() {
+                "sequence" | "all" => self.content(child, complex, in_choice),
+                "choice" => self.content(child, complex, true),
+                "complexContent" => self.content(child, complex, in_choice),
+                "simpleContent" => {
+                    if let Some(derivation) = xsd_children(child).next() {
+                        let base = derivation.attribute("base").map(|b| qname(derivation, b));
+                        complex.fields.push(Field {
+                            name: "value".to_string(),
+                            type_ref: base.unwrap_or_else(|| "xs:string".to_string()),
+                            reference: false,
+                            min: 1,
+                            many: false,
+                            nillable: false,
+                        });
+                        self.content(derivation, complex, in_choice);
+                    }
+                }
+                "extension" => {
+                    complex.base = child.attribute("base").map(|b| qname(child, b));
+                    self.content(child, complex, in_choice);
+                }
+                "restriction" => self.content(child, complex, in_choice),
+                "element" => {
+                    let reference = child.attribute("ref");
+                    let name = child
+                        .attribute("name")
+                        .or(reference.map(|r| r.rsplit(':').next().unwrap_or(r)))
+                        .unwrap_or_default()
+                        .to_string();
+                    let type_ref = match reference {
+                        Some(reference) => qname(child, reference),
+                        None => self.element_type(child, format!("{}{}", type_name(&complex.name), type_name(&name))),
+                    };
+                    let max = child.attribute("maxOccurs").unwrap_or("1");
+                    complex.fields.push(Field {
+                        name,
+                        type_ref,
+                        reference: reference.is_some(),
+                        min: if in_choice { 0 } else { occurs(child.attribute("minOccurs")) },
+                        many: max == "unbounded" || max.parse::<u32>().is_ok_and(|max| max > 1),
+                        nillable: child.attribute("nillable") == Some("true"),
+                    });
+                }
+                "attribute" => {
+                    let Some(name) = child.attribute("name") else {
+                        continue;
+                    };
+                    let type_ref = match child.attribute("type") {
+                        Some(type_ref) => qname(child, type_ref),
+                        None => match xsd_children(child).find(|c| c.tag_name().name() == "simpleType") {
+                            Some(simple) => self.add_simple_type(simple, format!("{}{}", type_name(&complex.name), type_name(name))),
+                            None => "xs:string".to_string(),
+                        },
+                    };
+                    complex.fields.push(Field {
+                        name: name.to_string(),
+                        type_ref,
+                        reference: false,
+                        min: u32::from(child.attribute("use") == Some("required")),
+                        many: false,
+                        nillable: false,
+                    });
+                }
+                "group" | "attributeGroup" | "any" | "anyAttribute" => self.unsupported(child),
+                _ => {}
+            }
+        }
+    }
+
+    /// The type of an element declaration, naming an anonymous type
+    /// `synthetic` (or the element's own name when empty)
+    fn element_type(&mut self, element: Node, synthetic: String) -> String {
+        if let Some(type_ref) = element.attribute("type") {
+            return qname(element, type_ref);
+        }
+        let name = if synthetic.is_empty() { element.attribute("name").unwrap_or_default().to_string() } else { synthetic };
+        for child in xsd_children(element) {
+            match child.tag_name().name() {
+                "complexType" => {
+                    self.add_complex_type(child, name.clone());
+                    return name;
+                }
+                "simpleType" => return self.add_simple_type(child, name),
+                _ => {}
+            }
+        }
+        "xs:anyType".to_string()
+    }
+
+    /// Read a simple type; returns the type to reference it by
+    fn add_simple_type(&mut self, node: Node, name: String) -> String {
+        let Some(restriction) = xsd_children(node).find(|c| c.tag_name().name() == "restriction") else {
+            // `list` and `union` values are written as strings
+            return "xs:string".to_string();
+        };
+        let base = restriction.attribute("base").map(|b| qname(restriction, b)).unwrap_or_else(|| "xs:string".to_string());
+        let values: Vec<String> = xsd_children(restriction)
+            .filter(|c| c.tag_name().name() == "enumeration")
+            .filter_map(|c| c.attribute("value").map(str::to_string))
+            .collect();
+        self.simple_types.push(SimpleType { name: name.clone(), base, values });
+        name
+    }
+
+    fn unsupported(&mut self, node: Node) {
+        let construct = format!("xs:{}", node.tag_name().name());
+        if !self.unsupported.contains(&construct) {
+            self.unsupported.push(construct);
+        }
+    }
+}
+
+fn xsd_children<'a, 'input>(node: Node<'a, 'input>) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children().filter(|c| c.is_element() && c.tag_name().namespace() == Some(XSD_NS))
+}
+
+fn occurs(value: Option<&str>) -> u32 {
+    value.and_then(|v| v.parse().ok()).unwrap_or(1)
+}
+
+/// Resolve a `prefix:name` reference: `xs:<name>` for XSD types, the
+/// local name otherwise
+pub fn qname(node: Node, value: &str) -> String {
+    let (prefix, local) = match value.split_once(':') {
+        Some((prefix, local)) => (Some(prefix), local),
+        None => (None, value),
+    };
+    if node.lookup_namespace_uri(prefix) == Some(XSD_NS) {
+        format!("xs:{}", local)
+    } else {
+        local.to_string()
+    }
+}
+
+/// XML names keep their casing (`USState`, `getQuote` → `GetQuote`);
+/// other characters are dropped
+pub fn type_name(name: &str) -> String {
+    let mut name = name.split(|c: char| !c.is_ascii_alphanumeric()).map(|part| {
+        let mut chars = part.chars();
+        chars.next().map(|first| first.to_ascii_uppercase().to_string() + chars.as_str()).unwrap_or_default()
+    });
+    let first = name.next().unwrap_or_default();
+    first + &name.collect::<String>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_schema() {
+        let xml = r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:tns="urn:quotes" targetNamespace="urn:quotes">
+          <xs:import namespace="urn:common" schemaLocation="common.xsd"/>
+          <xs:element name="getQuote">
+            <xs:complexType>
+              <xs:sequence>
+                <xs:element name="symbol" type="xs:string"/>
+                <xs:element name="legs" minOccurs="0" maxOccurs="unbounded">
+                  <xs:complexType><xs:attribute name="side" type="tns:Side" use="required"/></xs:complexType>
+                </xs:element>
+                <xs:choice><xs:element ref="tns:note"/><xs:any/></xs:choice>
+              </xs:sequence>
+            </xs:complexType>
+          </xs:element>
+          <xs:element name="note" type="xs:string"/>
+          <xs:simpleType name="Side">
+            <xs:restriction base="xs:string"><xs:enumeration value="buy"/><xs:enumeration value="sell"/></xs:restriction>
+          </xs:simpleType>
+          <xs:complexType name="Price">
+            <xs:simpleContent><xs:extension base="xs:decimal"><xs:attribute name="currency" type="xs:string"/></xs:extension></xs:simpleContent>
+          </xs:complexType>
+          <xs:complexType name="LimitPrice">
+            <xs:complexContent><xs:extension base="tns:Price"><xs:sequence><xs:element name="limit" type="xs:decimal" nillable="true"/></xs:sequence></xs:extension></xs:complexContent>
+          </xs:complexType>
+        </xs:schema>"#;
+        let document = roxmltree::Document::parse(xml).unwrap();
+        let mut schemas = Schemas::default();
+        schemas.add_schema(document.root_element());
+
+        let names: Vec<&str> = schemas.complex_types.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["GetQuoteLegs", "getQuote", "Price", "LimitPrice"]);
+        assert_eq!(schemas.element("getQuote").unwrap().type_ref, "getQuote");
+
+        let quote = schemas.complex_type("getQuote").unwrap();
+        let fields: Vec<(&str, &str, u32, bool)> =
+            quote.fields.iter().map(|f| (f.name.as_str(), f.type_ref.as_str(), f.min, f.many)).collect();
+        assert_eq!(fields, [("symbol", "xs:string", 1, false), ("legs", "GetQuoteLegs", 0, true), ("note", "note", 0, false)]);
+        assert!(quote.fields[2].reference);
+        assert_eq!(schemas.complex_type("GetQuoteLegs").unwrap().fields[0].type_ref, "Side");
+        assert_eq!(schemas.simple_type("Side").unwrap().values, ["buy", "sell"]);
+
+        let price = schemas.complex_type("Price").unwrap();
+        assert_eq!(price.fields[0].type_ref, "xs:decimal");
+        assert_eq!(price.fields[1].min, 0);
+        assert_eq!(schemas.complex_type("LimitPrice").unwrap().base.as_deref(), Some("Price"));
+
+        assert_eq!(schemas.imports, ["common.xsd"]);
+        assert_eq!(schemas.unsupported, ["xs:any"]);
+    }
+
+    #[test]
+    fn test_type_name() {
+        assert_eq!(type_name("getQuote"), "GetQuote");
+        assert_eq!(type_name("USState"), "USState");
+        assert_eq!(type_name("order-line.item"), "OrderLineItem");
+    }
+}
@@ -0,0 +1,564 @@
+//! `fusabi-providergen` - generate Fusabi types from the command line
+//!
+//! Looks a provider up by name in the [`ProviderRegistry`], resolves the
+//! source, and writes the emitted Fusabi source to a file or stdout:
+//!
+//! ```text
+//! fusabi-providergen --provider sql --source schema.sql --namespace Db --out types.fsb
+//! fusabi-providergen --provider openapi --source https://example.com/api.json \
+//!     --namespace Api --param auth=env:API_TOKEN
+//! fusabi-providergen --provider sql --source schema.sql --namespace Db --out types.fsb --watch
+//! fusabi-providergen --provider protobuf --source api.proto --namespace Api --out types/ --incremental
+//! fusabi-providergen --provider sql --source schema.sql --namespace Db --format json
+//! fusabi-providergen --provider sql --source schema.sql --namespace Db \
+//!     --strip-fields 'legacy*' --base-fields tenant:string --type-prefix Db
+//! fusabi-providergen --list
+//! ```
+//!
+//! `--format json` writes the generated types in the interchange layout of
+//! [`fusabi_provider_common::interchange`] instead of Fusabi source, for
+//! build tooling that post-processes them.
+
+use fusabi_provider_common::watch::{SourceWatcher, WatchEvent, WatchOptions};
+use fusabi_provider_common::{
+    diff_generated, emit_fusabi, rename_with_params, types_to_json, DriftReport, FusabiOptions,
+    ModuleLayout, SchemaLock,
+};
+use fusabi_provider_registry::ProviderRegistry;
+use fusabi_type_providers::{GeneratedTypes, ProviderParams, Schema, TypeProvider};
+use std::collections::BTreeMap;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// Lock file kept next to the modules of an incremental run
+const INCREMENTAL_LOCK: &str = "providergen.lock.json";
+
+const USAGE: &str = "\
+Usage: fusabi-providergen --provider <NAME> --source <SOURCE> --namespace <NAMESPACE> [OPTIONS]
+       fusabi-providergen --list
+
+Options:
+  -p, --provider <NAME>        Provider to generate with (see --list)
+  -s, --source <SOURCE>        Path, file:// or http(s):// URL, or inline schema
+  -n, --namespace <NAMESPACE>  Namespace of the generated types
+  -o, --out <PATH>             Output file (default: stdout)
+  -f, --format <FORMAT>        Output format: fusabi (default) or json
+  -P, --param <KEY=VALUE>      Provider param; may be repeated
+                               (naming=snake_case|camelCase|PascalCase|preserve re-cases names,
+                               rename=from:to,... and exclude=field,... override fields,
+                               overrides=<FILE> reads both from TOML,
+                               keyword_escape=backtick|suffix escapes keyword names,
+                               fix_identifiers=true renames invalid and duplicate names)
+      --offline                Serve remote sources from the cache only
+      --strip-fields <PATTERNS>
+                               Drop fields matching field, Type.field or `*` patterns
+      --base-fields <FIELDS>   Add name:type,... fields to every record
+      --type-prefix <PREFIX>   Prefix every generated type name
+  -w, --watch                  Regenerate whenever the source file changes (needs --out)
+  -i, --incremental            Write one file per module into the --out directory and
+                               rewrite only the modules that changed since the last run
+  -l, --list                   List available providers
+  -h, --help                   Show this help
+";
+
+/// Parsed command line
+#[derive(Debug, PartialEq)]
+enum Command {
+    Generate(GenerateArgs),
+    List,
+    Help,
+}
+
+/// Output format of a generation run
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+enum OutputFormat {
+    /// Fusabi source
+    #[default]
+    Fusabi,
+    /// Interchange JSON
+    Json,
+}
+
+impl OutputFormat {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "fusabi" => Ok(OutputFormat::Fusabi),
+            "json" => Ok(OutputFormat::Json),
+            other => Err(format!("--format expects fusabi or json, got '{}'", other)),
+        }
+    }
+
+    /// Render generated types in this format
+    fn render(self, types: &GeneratedTypes) -> Result<String, String> {
+        match self {
+            OutputFormat::Fusabi => Ok(emit_fusabi(types, &FusabiOptions::default())),
+            OutputFormat::Json => types_to_json(types).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+/// Arguments of a generation run
+#[derive(Debug, Default, Clone, PartialEq)]
+struct GenerateArgs {
+    provider: String,
+    source: String,
+    namespace: String,
+    out: Option<String>,
+    format: OutputFormat,
+    params: Vec<(String, String)>,
+    watch: bool,
+    incremental: bool,
+}
+
+impl GenerateArgs {
+    fn provider_params(&self) -> ProviderParams {
+        self.params
+            .iter()
+            .fold(ProviderParams::default(), |params, (key, value)| params.with(key, value))
+    }
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut args = args.into_iter();
+    let mut provider = None;
+    let mut source = None;
+    let mut namespace = None;
+    let mut generate = GenerateArgs::default();
+    let mut list = false;
+
+    while let Some(arg) = args.next() {
+        // Accept both `--flag value` and `--flag=value`
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+            _ => (arg, None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("{} requires a value", flag))
+        };
+
+        match flag.as_str() {
+            "-p" | "--provider" => provider = Some(value()?),
+            "-s" | "--source" => source = Some(value()?),
+            "-n" | "--namespace" => namespace = Some(value()?),
+            "-o" | "--out" => generate.out = Some(value()?),
+            "-f" | "--format" => generate.format = OutputFormat::parse(&value()?)?,
+            "-P" | "--param" => {
+                let param = value()?;
+                let (key, value) = param
+                    .split_once('=')
+                    .ok_or_else(|| format!("--param expects KEY=VALUE, got '{}'", param))?;
+                generate.params.push((key.to_string(), value.to_string()));
+            }
+            "--offline" => generate.params.push(("offline".to_string(), "true".to_string())),
+            // Transforms are params, so that validation and the watcher see them too
+            "--strip-fields" => generate.params.push(("strip_fields".to_string(), value()?)),
+            "--base-fields" => generate.params.push(("base_fields".to_string(), value()?)),
+            "--type-prefix" => generate.params.push(("type_prefix".to_string(), value()?)),
+            "-w" | "--watch" => generate.watch = true,
+            "-i" | "--incremental" => generate.incremental = true,
+            "-l" | "--list" => list = true,
+            "-h" | "--help" => return Ok(Command::Help),
+            other => return Err(format!("unexpected argument '{}'", other)),
+        }
+    }
+
+    if list {
+        return Ok(Command::List);
+    }
+
+    generate.provider = provider.ok_or("--provider is required")?;
+    generate.source = source.ok_or("--source is required")?;
+    generate.namespace = namespace.ok_or("--namespace is required")?;
+    if generate.watch && generate.out.is_none() {
+        return Err("--watch requires --out".to_string());
+    }
+    if generate.incremental && generate.out.is_none() {
+        return Err("--incremental requires --out".to_string());
+    }
+    if generate.incremental && generate.watch {
+        return Err("--incremental cannot be combined with --watch".to_string());
+    }
+    if generate.incremental && generate.format != OutputFormat::Fusabi {
+        return Err("--incremental only writes Fusabi source".to_string());
+    }
+    Ok(Command::Generate(generate))
+}
+
+fn lookup<'a>(registry: &'a ProviderRegistry, name: &str) -> Result<&'a dyn TypeProvider, String> {
+    registry.get(name).ok_or_else(|| {
+        format!(
+            "unknown provider '{}'; run with --list to see available providers",
+            name
+        )
+    })
+}
+
+/// Resolve, generate and emit; returns the output in the requested format
+fn generate(registry: &ProviderRegistry, args: &GenerateArgs) -> Result<String, String> {
+    let provider = lookup(registry, &args.provider)?;
+    let params = args.provider_params();
+    let schema = resolve(provider, args, &params)?;
+    let types = generate_from(provider, &schema, args, &params)?;
+
+    args.format.render(&types)
+}
+
+fn resolve(provider: &dyn TypeProvider, args: &GenerateArgs, params: &ProviderParams) -> Result<Schema, String> {
+    provider
+        .resolve_schema(&args.source, params)
+        .map_err(|e| format!("failed to resolve {}: {}", args.source, e))
+}
+
+fn generate_from(
+    provider: &dyn TypeProvider,
+    schema: &Schema,
+    args: &GenerateArgs,
+    params: &ProviderParams,
+) -> Result<GeneratedTypes, String> {
+    let types = provider
+        .generate_types(schema, &args.namespace)
+        .map_err(|e| format!("failed to generate types: {}", e))?;
+    let (types, _) = rename_with_params(types, params).map_err(|e| e.to_string())?;
+    Ok(types)
+}
+
+/// Files touched by an incremental run
+#[derive(Debug, Default)]
+struct IncrementalOutcome {
+    changes: DriftReport,
+    written: Vec<PathBuf>,
+    removed: Vec<PathBuf>,
+}
+
+/// Generate one file per module into `dir`, rewriting only the modules
+/// that changed since the run recorded in the directory's lock
+///
+/// The previous types are regenerated from the locked schema with the
+/// current params, so a change of params alone needs the lock removed.
+fn generate_incremental(
+    registry: &ProviderRegistry,
+    args: &GenerateArgs,
+    dir: &str,
+) -> Result<IncrementalOutcome, String> {
+    let provider = lookup(registry, &args.provider)?;
+    let params = args.provider_params();
+    let schema = resolve(provider, args, &params)?;
+    let types = generate_from(provider, &schema, args, &params)?;
+
+    let dir = Path::new(dir);
+    let lock_path = dir.join(INCREMENTAL_LOCK);
+    let previous = match SchemaLock::read_from(&lock_path) {
+        Ok(lock)
+            if lock.provider == provider.name()
+                && lock.source == args.source
+                && lock.namespace == args.namespace =>
+        {
+            generate_from(provider, &lock.schema.to_schema(), args, &params)?
+        }
+        _ => GeneratedTypes::new(),
+    };
+    let diff = diff_generated(&previous, &types);
+    let changed = diff.changed_modules();
+
+    std::fs::create_dir_all(dir).map_err(|e| format!("failed to create {}: {}", dir.display(), e))?;
+    let mut outcome = IncrementalOutcome {
+        changes: diff.to_drift_report(),
+        ..Default::default()
+    };
+    let options = FusabiOptions {
+        modules: ModuleLayout::Qualified,
+        ..Default::default()
+    };
+    let modules = split_modules(types);
+    for (path, module) in &modules {
+        let file = dir.join(module_file(path));
+        if changed.contains(path) || !file.exists() {
+            std::fs::write(&file, emit_fusabi(module, &options))
+                .map_err(|e| format!("failed to write {}: {}", file.display(), e))?;
+            outcome.written.push(file);
+        }
+    }
+    for path in changed.iter().filter(|path| !modules.contains_key(*path)) {
+        let file = dir.join(module_file(path));
+        if file.exists() {
+            std::fs::remove_file(&file).map_err(|e| format!("failed to remove {}: {}", file.display(), e))?;
+            outcome.removed.push(file);
+        }
+    }
+
+    SchemaLock::from_schema(provider.name(), &args.source, &args.namespace, &schema)
+        .write_to(&lock_path)
+        .map_err(|e| format!("failed to write {}: {}", lock_path.display(), e))?;
+    Ok(outcome)
+}
+
+/// Split generated types into one tree per module that declares types
+fn split_modules(types: GeneratedTypes) -> BTreeMap<Vec<String>, GeneratedTypes> {
+    let mut split: BTreeMap<Vec<String>, GeneratedTypes> = BTreeMap::new();
+    if !types.root_types.is_empty() {
+        split.entry(Vec::new()).or_insert_with(GeneratedTypes::new).root_types = types.root_types;
+    }
+    for module in types.modules.into_iter().filter(|m| !m.types.is_empty()) {
+        split
+            .entry(module.path.clone())
+            .or_insert_with(GeneratedTypes::new)
+            .modules
+            .push(module);
+    }
+    split
+}
+
+/// File an incremental run writes a module to
+fn module_file(path: &[String]) -> String {
+    if path.is_empty() {
+        "_root.fsb".to_string()
+    } else {
+        format!("{}.fsb", path.join("."))
+    }
+}
+
+/// Regenerate `out` on every change of the source; runs until interrupted
+fn watch(registry: &ProviderRegistry, args: &GenerateArgs, out: &str) -> Result<(), String> {
+    let provider = lookup(registry, &args.provider)?;
+    let watcher = SourceWatcher::new(
+        provider,
+        &args.source,
+        args.provider_params(),
+        &args.namespace,
+        WatchOptions::default(),
+    )
+    .map_err(|e| e.to_string())?;
+
+    eprintln!("watching {}", watcher.path().display());
+    watcher.run(|event| {
+        match event {
+            WatchEvent::Generated { types, changes } => {
+                let written = args.format.render(types).and_then(|output| {
+                    std::fs::write(out, output).map_err(|e| format!("failed to write {}: {}", out, e))
+                });
+                match written {
+                    Ok(()) => eprintln!("wrote {}: {}", out, summarize(&changes)),
+                    Err(e) => eprintln!("error: {}", e),
+                }
+            }
+            WatchEvent::Failed(e) => eprintln!("error: {}", e),
+        }
+        ControlFlow::Continue(())
+    });
+    Ok(())
+}
+
+/// One-line summary of a regeneration diff
+fn summarize(changes: &DriftReport) -> String {
+    if !changes.types_changed() {
+        return "no type changes".to_string();
+    }
+
+    let mut parts = Vec::new();
+    for (label, names) in [("added", &changes.added), ("removed", &changes.removed)] {
+        if !names.is_empty() {
+            parts.push(format!("{} {}", label, names.join(", ")));
+        }
+    }
+    if !changes.changed.is_empty() {
+        let names: Vec<_> = changes.changed.iter().map(|c| c.name.as_str()).collect();
+        parts.push(format!("changed {}", names.join(", ")));
+    }
+    parts.join("; ")
+}
+
+fn run(command: Command) -> Result<(), String> {
+    let registry = ProviderRegistry::community();
+
+    match command {
+        Command::Help => print!("{}", USAGE),
+        Command::List => {
+            for name in registry.names() {
+                println!("{}", name);
+            }
+        }
+        Command::Generate(args) if args.watch => {
+            watch(&registry, &args, args.out.as_deref().unwrap_or_default())?
+        }
+        Command::Generate(args) if args.incremental => {
+            let outcome = generate_incremental(&registry, &args, args.out.as_deref().unwrap_or_default())?;
+            eprintln!(
+                "wrote {} module(s), removed {}: {}",
+                outcome.written.len(),
+                outcome.removed.len(),
+                summarize(&outcome.changes)
+            );
+        }
+        Command::Generate(args) => {
+            let source = generate(&registry, &args)?;
+            match &args.out {
+                Some(path) => std::fs::write(path, source)
+                    .map_err(|e| format!("failed to write {}: {}", path, e))?,
+                None => print!("{}", source),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let command = match parse_args(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        parse_args(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let command = parse(&[
+            "--provider", "sql", "-s", "schema.sql", "--namespace=Db", "--out", "types.fsb",
+            "--param", "dialect=postgres", "-P", "auth=env:TOKEN", "--offline",
+        ])
+        .unwrap();
+
+        let Command::Generate(args) = command else { panic!("expected generate") };
+        assert_eq!(args.provider, "sql");
+        assert_eq!(args.source, "schema.sql");
+        assert_eq!(args.namespace, "Db");
+        assert_eq!(args.out.as_deref(), Some("types.fsb"));
+
+        let params = args.provider_params();
+        assert_eq!(params.custom["dialect"], "postgres");
+        assert_eq!(params.custom["auth"], "env:TOKEN");
+        assert_eq!(params.custom["offline"], "true");
+
+        let Command::Generate(args) = parse(&[
+            "-p", "sql", "-s", "a.sql", "-n", "Db", "--strip-fields", "legacy*",
+            "--base-fields=tenant:string", "--type-prefix", "Db",
+        ])
+        .unwrap() else {
+            panic!("expected generate")
+        };
+        let params = args.provider_params();
+        assert_eq!(params.custom["strip_fields"], "legacy*");
+        assert_eq!(params.custom["base_fields"], "tenant:string");
+        assert_eq!(params.custom["type_prefix"], "Db");
+
+        assert_eq!(parse(&["--list"]).unwrap(), Command::List);
+        assert_eq!(parse(&["-p", "sql", "-h"]).unwrap(), Command::Help);
+        assert!(parse(&["--provider", "sql"]).unwrap_err().contains("--source"));
+        assert!(parse(&["--param", "novalue"]).is_err());
+        assert!(parse(&["--provider"]).is_err());
+        assert!(parse(&["--bogus"]).is_err());
+        assert!(parse(&["-p", "sql", "-s", "a.sql", "-n", "Db", "--watch"])
+            .unwrap_err()
+            .contains("--out"));
+        assert!(parse(&["-p", "sql", "-s", "a.sql", "-n", "Db", "-i"])
+            .unwrap_err()
+            .contains("--out"));
+        assert!(parse(&["-p", "sql", "-s", "a.sql", "-n", "Db", "-o", "out", "-i", "-w"]).is_err());
+
+        let Command::Generate(args) = parse(&["-p", "sql", "-s", "a.sql", "-n", "Db", "--format=json"]).unwrap() else {
+            panic!("expected generate")
+        };
+        assert_eq!(args.format, OutputFormat::Json);
+        assert!(parse(&["-p", "sql", "-s", "a.sql", "-n", "Db", "-f", "yaml"]).unwrap_err().contains("yaml"));
+        assert!(parse(&["-p", "sql", "-s", "a.sql", "-n", "Db", "-o", "out", "-i", "-f", "json"]).is_err());
+    }
+
+    #[test]
+    fn test_summarize() {
+        let changes = DriftReport {
+            added: vec!["Db.Posts".to_string()],
+            removed: vec!["Db.Tags".to_string(), "Db.Votes".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(summarize(&changes), "added Db.Posts; removed Db.Tags, Db.Votes");
+        assert_eq!(summarize(&DriftReport::default()), "no type changes");
+    }
+
+    #[test]
+    fn test_generate() {
+        let registry = ProviderRegistry::community();
+        let args = GenerateArgs {
+            provider: "sql".to_string(),
+            source: "CREATE TABLE users (id INT PRIMARY KEY, email TEXT NOT NULL);".to_string(),
+            namespace: "Db".to_string(),
+            ..Default::default()
+        };
+
+        let source = generate(&registry, &args).unwrap();
+        assert!(source.contains("module Db"));
+        assert!(source.contains("email: string"));
+
+        let transformed = GenerateArgs {
+            params: vec![
+                ("strip_fields".to_string(), "email".to_string()),
+                ("type_prefix".to_string(), "Db".to_string()),
+            ],
+            ..args.clone()
+        };
+        let source = generate(&registry, &transformed).unwrap();
+        assert!(source.contains("DbUsers"));
+        assert!(!source.contains("email"));
+
+        let json = generate(&registry, &GenerateArgs { format: OutputFormat::Json, ..args.clone() }).unwrap();
+        let types = fusabi_provider_common::types_from_json(&json).unwrap();
+        assert_eq!(types.modules[0].path, vec!["Db".to_string()]);
+
+        let unknown = GenerateArgs {
+            provider: "cobol".to_string(),
+            ..args
+        };
+        assert!(generate(&registry, &unknown).unwrap_err().contains("--list"));
+    }
+
+    #[test]
+    fn test_generate_incremental() {
+        let registry = ProviderRegistry::community();
+        let dir = tempfile::tempdir().unwrap();
+        let schema = dir.path().join("schema.sql");
+        let out = dir.path().join("types");
+        std::fs::write(&schema, "CREATE TABLE users (id INT PRIMARY KEY);").unwrap();
+        let args = GenerateArgs {
+            provider: "sql".to_string(),
+            source: schema.to_str().unwrap().to_string(),
+            namespace: "Db".to_string(),
+            ..Default::default()
+        };
+        let out = out.to_str().unwrap();
+
+        let outcome = generate_incremental(&registry, &args, out).unwrap();
+        assert_eq!(outcome.written, [Path::new(out).join("Db.fsb")]);
+        assert!(std::fs::read_to_string(&outcome.written[0]).unwrap().contains("module Db ="));
+        assert!(Path::new(out).join(INCREMENTAL_LOCK).is_file());
+
+        let outcome = generate_incremental(&registry, &args, out).unwrap();
+        assert!(outcome.written.is_empty());
+        assert!(!outcome.changes.types_changed());
+
+        std::fs::write(&schema, "CREATE TABLE users (id INT PRIMARY KEY);\nCREATE TABLE posts (id INT);").unwrap();
+        let outcome = generate_incremental(&registry, &args, out).unwrap();
+        assert_eq!(outcome.written.len(), 1);
+        assert_eq!(outcome.changes.added, ["Db.Posts"]);
+    }
+}
@@ -0,0 +1,35 @@
+//! SHA-256 digests of published files
+
+use crate::error::{RegistryError, RegistryResult};
+use sha2::{Digest, Sha256};
+
+/// Outcome of checking content against an entry's published digest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verification {
+    /// The content has the published digest
+    Verified,
+    /// The entry publishes no digest, so the content was not checked
+    Unverified,
+}
+
+/// Hex-encoded SHA-256 of `bytes`
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Check `bytes` against the hex-encoded digest published for `name`
+///
+/// Case is ignored, so digests copied from either `sha256sum` or tools
+/// printing upper-case hex are accepted.
+pub fn verify_sha256(name: &str, expected: &str, bytes: &[u8]) -> RegistryResult<()> {
+    let actual = sha256_hex(bytes);
+    if actual.eq_ignore_ascii_case(expected.trim()) {
+        Ok(())
+    } else {
+        Err(RegistryError::ChecksumMismatch {
+            name: name.to_string(),
+            expected: expected.trim().to_string(),
+            actual,
+        })
+    }
+}
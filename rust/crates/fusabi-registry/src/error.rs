@@ -0,0 +1,60 @@
+//! Errors reading and resolving the registry index
+
+use std::fmt;
+
+/// Result of registry operations
+pub type RegistryResult<T> = Result<T, RegistryError>;
+
+/// Error reading the index, resolving a version or verifying a download
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryError {
+    /// The index or a package file could not be read
+    Io(String),
+    /// The index is not valid TOML or does not match the index format
+    Parse(String),
+    /// A version or version requirement is not valid semver
+    InvalidVersion { name: String, version: String, reason: String },
+    /// No package or provider has this name
+    NotFound(String),
+    /// The package exists, but no published version matches the requirement
+    NoMatchingVersion { name: String, requirement: String, available: Vec<String> },
+    /// Downloaded or on-disk content does not have the published digest
+    ChecksumMismatch { name: String, expected: String, actual: String },
+    /// An entry that must publish a digest, such as a plugin, has none
+    MissingChecksum(String),
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryError::Io(message) => write!(f, "I/O error: {}", message),
+            RegistryError::Parse(message) => write!(f, "Invalid registry index: {}", message),
+            RegistryError::InvalidVersion { name, version, reason } => {
+                write!(f, "Invalid version '{}' for '{}': {}", version, name, reason)
+            }
+            RegistryError::NotFound(name) => write!(f, "No package named '{}'", name),
+            RegistryError::NoMatchingVersion {
+                name,
+                requirement,
+                available,
+            } => {
+                write!(f, "No version of '{}' matches {}", name, requirement)?;
+                if available.is_empty() {
+                    f.write_str(" (no versions published)")
+                } else {
+                    write!(f, " (available: {})", available.join(", "))
+                }
+            }
+            RegistryError::ChecksumMismatch { name, expected, actual } => write!(
+                f,
+                "Checksum mismatch for '{}': expected {}, got {}",
+                name, expected, actual
+            ),
+            RegistryError::MissingChecksum(name) => {
+                write!(f, "'{}' publishes no sha256 checksum", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
@@ -0,0 +1,434 @@
+//! The registry index and version resolution
+
+use crate::checksum::{verify_sha256, Verification};
+use crate::error::{RegistryError, RegistryResult};
+use semver::{Version, VersionReq};
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// Main source file of a package whose entry does not name one
+pub const DEFAULT_ENTRY: &str = "src/lib.fsx";
+
+/// `[registry]` table of the index
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct RegistryInfo {
+    /// Version of the registry contents
+    #[serde(default)]
+    pub version: String,
+    /// Version of the index format
+    #[serde(default)]
+    pub schema_version: Option<String>,
+}
+
+/// `[[packages]]` entry: one published version of a Fusabi package
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PackageEntry {
+    /// Package name
+    pub name: String,
+    /// Semantic version
+    pub version: String,
+    /// One-line description
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Source repository
+    #[serde(default)]
+    pub repository: Option<String>,
+    /// SPDX license expression
+    #[serde(default)]
+    pub license: Option<String>,
+    /// Package directory, relative to the index; `../packages/<name>` by default
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Main source file, relative to the package directory; [`DEFAULT_ENTRY`] by default
+    #[serde(default)]
+    pub entry: Option<String>,
+    /// Expected hex-encoded SHA-256 of the main source file
+    #[serde(default)]
+    pub sha256: Option<String>,
+    /// Withdrawn versions are listed but never resolved
+    #[serde(default)]
+    pub yanked: bool,
+}
+
+impl PackageEntry {
+    /// Parsed version; entries of a parsed index always have one
+    pub fn semver(&self) -> Option<Version> {
+        Version::parse(&self.version).ok()
+    }
+
+    /// Main source file, relative to the package directory
+    pub fn entry_file(&self) -> &str {
+        self.entry.as_deref().unwrap_or(DEFAULT_ENTRY)
+    }
+
+    /// Check the main source file against the published digest
+    ///
+    /// Entries without a digest give [`Verification::Unverified`]; callers
+    /// decide whether to use such sources.
+    pub fn verify(&self, bytes: &[u8]) -> RegistryResult<Verification> {
+        match &self.sha256 {
+            Some(expected) => verify_sha256(&self.name, expected, bytes).map(|()| Verification::Verified),
+            None => Ok(Verification::Unverified),
+        }
+    }
+}
+
+/// Main source file of a package, as read by [`RegistryIndex::read_package_source`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageSource {
+    /// File content
+    pub content: String,
+    /// Whether the content was checked against a published digest
+    pub verification: Verification,
+}
+
+/// `[[plugins]]` entry: a type provider compiled to WebAssembly
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PluginEntry {
+    /// Name the provider is registered under
+    pub name: String,
+    /// Plugin version
+    pub version: String,
+    /// One-line description
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Path of the `.wasm` module, relative to the index
+    pub wasm: String,
+    /// Expected hex-encoded SHA-256 of the module
+    #[serde(default)]
+    pub sha256: Option<String>,
+}
+
+impl PluginEntry {
+    /// Check the module against the published digest
+    ///
+    /// Plugins are executable, so an entry without a digest is an error.
+    pub fn verify(&self, bytes: &[u8]) -> RegistryResult<()> {
+        match &self.sha256 {
+            Some(expected) => verify_sha256(&self.name, expected, bytes),
+            None => Err(RegistryError::MissingChecksum(self.name.clone())),
+        }
+    }
+}
+
+/// `[[providers]]` entry: a community type provider crate
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ProviderEntry {
+    /// Name the provider is registered under in `fusabi-provider-registry`,
+    /// which is also the feature enabling it there
+    pub name: String,
+    /// Crate name
+    #[serde(rename = "crate")]
+    pub crate_name: String,
+    /// Crate version
+    pub version: String,
+    /// One-line description
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Parsed registry `index.toml`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RegistryIndex {
+    /// `[registry]` table
+    #[serde(default)]
+    pub registry: RegistryInfo,
+    /// Published package versions, in index order
+    #[serde(default)]
+    pub packages: Vec<PackageEntry>,
+    /// WebAssembly provider plugins
+    #[serde(default)]
+    pub plugins: Vec<PluginEntry>,
+    /// Community provider crates
+    #[serde(default)]
+    pub providers: Vec<ProviderEntry>,
+    /// Directory paths in entries are relative to
+    #[serde(skip)]
+    base: PathBuf,
+}
+
+impl RegistryIndex {
+    /// Parse an index, with paths relative to the current directory
+    ///
+    /// Every version must be valid semver, and no package version may be
+    /// listed twice.
+    pub fn parse(content: &str) -> RegistryResult<Self> {
+        let mut index: Self = toml::from_str(content).map_err(|e| RegistryError::Parse(e.to_string()))?;
+
+        let mut seen = BTreeSet::new();
+        for package in &index.packages {
+            let version = parse_version(&package.name, &package.version)?;
+            if !seen.insert((package.name.as_str(), version)) {
+                return Err(RegistryError::Parse(format!(
+                    "Package '{}' version {} is listed twice",
+                    package.name, package.version
+                )));
+            }
+        }
+        for plugin in &index.plugins {
+            parse_version(&plugin.name, &plugin.version)?;
+        }
+        for provider in &index.providers {
+            parse_version(&provider.name, &provider.version)?;
+        }
+
+        index.base = PathBuf::from(".");
+        Ok(index)
+    }
+
+    /// Read and parse the index at `path`; entry paths are relative to its directory
+    pub fn load(path: impl AsRef<Path>) -> RegistryResult<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| RegistryError::Io(format!("Failed to read {}: {}", path.display(), e)))?;
+        let mut index = Self::parse(&content)?;
+        index.base = path.parent().unwrap_or(Path::new(".")).to_path_buf();
+        Ok(index)
+    }
+
+    /// Directory entry paths are relative to
+    pub fn base(&self) -> &Path {
+        &self.base
+    }
+
+    /// Names of the listed packages, sorted and without duplicates
+    pub fn package_names(&self) -> BTreeSet<&str> {
+        self.packages.iter().map(|p| p.name.as_str()).collect()
+    }
+
+    /// Every listed version of a package, yanked ones included, oldest first
+    pub fn versions(&self, name: &str) -> Vec<&PackageEntry> {
+        let mut versions: Vec<&PackageEntry> = self.packages.iter().filter(|p| p.name == name).collect();
+        versions.sort_by_key(|p| p.semver());
+        versions
+    }
+
+    /// Newest version of a package matching a semver requirement
+    ///
+    /// `requirement` uses Cargo syntax (`^0.2`, `>=1.0, <2`, `=0.1.3`); an
+    /// empty requirement or `*` matches any release. Yanked versions are
+    /// skipped, and pre-releases only match requirements that name one.
+    pub fn resolve(&self, name: &str, requirement: &str) -> RegistryResult<&PackageEntry> {
+        let requirement = requirement.trim();
+        let req = if requirement.is_empty() {
+            VersionReq::STAR
+        } else {
+            VersionReq::parse(requirement).map_err(|e| RegistryError::InvalidVersion {
+                name: name.to_string(),
+                version: requirement.to_string(),
+                reason: e.to_string(),
+            })?
+        };
+
+        let versions = self.versions(name);
+        if versions.is_empty() {
+            return Err(RegistryError::NotFound(name.to_string()));
+        }
+
+        let available: Vec<&PackageEntry> = versions.into_iter().filter(|p| !p.yanked).collect();
+        available
+            .iter()
+            .rev()
+            .find(|p| p.semver().is_some_and(|v| req.matches(&v)))
+            .copied()
+            .ok_or_else(|| RegistryError::NoMatchingVersion {
+                name: name.to_string(),
+                requirement: requirement.to_string(),
+                available: available.iter().map(|p| p.version.clone()).collect(),
+            })
+    }
+
+    /// Newest release of a package
+    pub fn latest(&self, name: &str) -> RegistryResult<&PackageEntry> {
+        self.resolve(name, "*")
+    }
+
+    /// Directory of a package
+    pub fn package_dir(&self, package: &PackageEntry) -> PathBuf {
+        match &package.path {
+            Some(path) => self.base.join(path),
+            None => self.base.join("../packages").join(&package.name),
+        }
+    }
+
+    /// Read a package's main source file, checked against its digest if it
+    /// publishes one
+    pub fn read_package_source(&self, package: &PackageEntry) -> RegistryResult<PackageSource> {
+        let path = self.package_dir(package).join(package.entry_file());
+        let bytes = read(&path)?;
+        let verification = package.verify(&bytes)?;
+        let content = String::from_utf8(bytes)
+            .map_err(|_| RegistryError::Io(format!("{} is not valid UTF-8", path.display())))?;
+        Ok(PackageSource { content, verification })
+    }
+
+    /// Look up a plugin by name
+    pub fn plugin(&self, name: &str) -> Option<&PluginEntry> {
+        self.plugins.iter().find(|p| p.name == name)
+    }
+
+    /// Read a plugin module, checked against its digest; plugins without
+    /// one are rejected
+    pub fn read_plugin(&self, plugin: &PluginEntry) -> RegistryResult<Vec<u8>> {
+        let bytes = read(&self.base.join(&plugin.wasm))?;
+        plugin.verify(&bytes)?;
+        Ok(bytes)
+    }
+
+    /// Look up a provider crate by registry name
+    ///
+    /// As in `fusabi-provider-registry`, names are case-insensitive and `_`
+    /// matches `-`.
+    pub fn provider(&self, name: &str) -> Option<&ProviderEntry> {
+        let name = normalize(name);
+        self.providers.iter().find(|p| normalize(&p.name) == name)
+    }
+}
+
+/// Parse the version of entry `name`
+fn parse_version(name: &str, version: &str) -> RegistryResult<Version> {
+    Version::parse(version).map_err(|e| RegistryError::InvalidVersion {
+        name: name.to_string(),
+        version: version.to_string(),
+        reason: e.to_string(),
+    })
+}
+
+fn read(path: &Path) -> RegistryResult<Vec<u8>> {
+    std::fs::read(path).map_err(|e| RegistryError::Io(format!("Failed to read {}: {}", path.display(), e)))
+}
+
+/// Canonical form of a provider name
+fn normalize(name: &str) -> String {
+    name.trim().to_ascii_lowercase().replace('_', "-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checksum::sha256_hex;
+
+    const INDEX: &str = r#"
+        [registry]
+        version = "1.0.0"
+        schema_version = "1.0.0"
+
+        [[packages]]
+        name = "json"
+        version = "0.1.0"
+
+        [[packages]]
+        name = "json"
+        version = "0.2.1"
+
+        [[packages]]
+        name = "json"
+        version = "0.3.0"
+        yanked = true
+
+        [[packages]]
+        name = "json"
+        version = "0.4.0-beta.1"
+
+        [[providers]]
+        name = "json-schema"
+        crate = "fusabi-provider-json-schema"
+        version = "0.1.0"
+    "#;
+
+    #[test]
+    fn test_resolve() {
+        let index = RegistryIndex::parse(INDEX).unwrap();
+        assert_eq!(index.resolve("json", "^0.1").unwrap().version, "0.1.0");
+        assert_eq!(index.resolve("json", ">=0.2").unwrap().version, "0.2.1");
+        assert_eq!(index.latest("json").unwrap().version, "0.2.1");
+        assert_eq!(index.resolve("json", "=0.4.0-beta.1").unwrap().version, "0.4.0-beta.1");
+        assert_eq!(index.versions("json").len(), 4);
+
+        let error = index.resolve("json", "^0.3").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "No version of 'json' matches ^0.3 (available: 0.1.0, 0.2.1, 0.4.0-beta.1)"
+        );
+        assert_eq!(index.resolve("yaml", "*"), Err(RegistryError::NotFound("yaml".to_string())));
+        assert!(matches!(index.resolve("json", "one"), Err(RegistryError::InvalidVersion { .. })));
+
+        assert_eq!(index.provider("JSON_Schema").unwrap().crate_name, "fusabi-provider-json-schema");
+        assert!(index.provider("yaml").is_none());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let duplicate = "[[packages]]\nname = \"a\"\nversion = \"1.0.0\"\n\n[[packages]]\nname = \"a\"\nversion = \"1.0.0\"\n";
+        assert!(matches!(RegistryIndex::parse(duplicate), Err(RegistryError::Parse(_))));
+        let invalid = "[[packages]]\nname = \"a\"\nversion = \"1.0\"\n";
+        assert!(matches!(RegistryIndex::parse(invalid), Err(RegistryError::InvalidVersion { .. })));
+        assert!(RegistryIndex::parse("[[packages]]\nname = 1").is_err());
+    }
+
+    #[test]
+    fn test_read_package_source() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("packages/json/src")).unwrap();
+        std::fs::create_dir(dir.path().join("registry")).unwrap();
+        let source = "let parse = fun s -> s\n";
+        std::fs::write(dir.path().join("packages/json/src/lib.fsx"), source).unwrap();
+
+        let index_path = dir.path().join("registry/index.toml");
+        let index = format!(
+            "[[packages]]\nname = \"json\"\nversion = \"0.1.0\"\nsha256 = \"{}\"\n\n\
+             [[packages]]\nname = \"json\"\nversion = \"0.1.1\"\n\n\
+             [[packages]]\nname = \"json\"\nversion = \"0.2.0\"\nsha256 = \"{}\"\n",
+            sha256_hex(source.as_bytes()),
+            "0".repeat(64)
+        );
+        std::fs::write(&index_path, index).unwrap();
+
+        let index = RegistryIndex::load(&index_path).unwrap();
+        let package = index.resolve("json", "=0.1.0").unwrap();
+        let read = index.read_package_source(package).unwrap();
+        assert_eq!(read.content, source);
+        assert_eq!(read.verification, Verification::Verified);
+
+        let package = index.resolve("json", "=0.1.1").unwrap();
+        let read = index.read_package_source(package).unwrap();
+        assert_eq!(read.content, source);
+        assert_eq!(read.verification, Verification::Unverified);
+
+        let package = index.latest("json").unwrap();
+        assert!(matches!(
+            index.read_package_source(package),
+            Err(RegistryError::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_read_plugin_requires_checksum() {
+        let dir = tempfile::tempdir().unwrap();
+        let module = b"(module)";
+        std::fs::write(dir.path().join("a.wasm"), module).unwrap();
+
+        let index_path = dir.path().join("index.toml");
+        let index = format!(
+            "[[plugins]]\nname = \"a\"\nversion = \"0.1.0\"\nwasm = \"a.wasm\"\nsha256 = \"{}\"\n\n\
+             [[plugins]]\nname = \"b\"\nversion = \"0.1.0\"\nwasm = \"a.wasm\"\n",
+            sha256_hex(module)
+        );
+        std::fs::write(&index_path, index).unwrap();
+
+        let index = RegistryIndex::load(&index_path).unwrap();
+        assert_eq!(index.read_plugin(index.plugin("a").unwrap()).unwrap(), module);
+        assert_eq!(
+            index.read_plugin(index.plugin("b").unwrap()),
+            Err(RegistryError::MissingChecksum("b".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_community_index() {
+        let index = RegistryIndex::parse(include_str!("../../../../registry/index.toml")).unwrap();
+        assert!(index.latest("json").is_ok());
+        assert_eq!(index.provider("sql").unwrap().crate_name, "fusabi-provider-sql");
+    }
+}
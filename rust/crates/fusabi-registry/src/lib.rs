@@ -0,0 +1,50 @@
+//! The Fusabi community registry index as a library
+//!
+//! `registry/index.toml` lists the community packages, the type provider
+//! crates and third-party WebAssembly provider plugins. Hosts read it here
+//! instead of parsing the TOML themselves:
+//!
+//! - [`RegistryIndex`] parses the index and checks every version is semver
+//! - [`RegistryIndex::resolve`] picks the newest package version matching a
+//!   Cargo-style requirement, skipping yanked versions
+//! - [`RegistryIndex::read_package_source`] and [`RegistryIndex::read_plugin`]
+//!   read files relative to the index and verify their published `sha256`;
+//!   packages without one are reported as [`Verification::Unverified`], and
+//!   plugins without one are rejected
+//! - [`RegistryIndex::provider`] gives the crate behind a provider name
+//!
+//! ```toml
+//! [[packages]]
+//! name = "json"
+//! version = "0.2.0"
+//! path = "../packages/json"
+//! sha256 = "<hex-encoded SHA-256 of src/lib.fsx>"
+//!
+//! [[providers]]
+//! name = "sql"
+//! crate = "fusabi-provider-sql"
+//! version = "0.1.0"
+//! ```
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fusabi_registry::{RegistryIndex, Verification};
+//!
+//! let index = RegistryIndex::load("registry/index.toml")?;
+//! let package = index.resolve("json", "^0.1")?;
+//! let source = index.read_package_source(package)?;
+//! if source.verification == Verification::Unverified {
+//!     eprintln!("warning: {} {} publishes no checksum", package.name, package.version);
+//! }
+//! ```
+
+pub mod checksum;
+pub mod error;
+pub mod index;
+
+pub use checksum::{sha256_hex, verify_sha256, Verification};
+pub use error::{RegistryError, RegistryResult};
+pub use index::{
+    PackageEntry, PackageSource, PluginEntry, ProviderEntry, RegistryIndex, RegistryInfo, DEFAULT_ENTRY,
+};